@@ -19,15 +19,18 @@ use std::str::FromStr;
 use std::sync::Arc;
 use sui_bridge::abi::EthBridgeCommittee;
 use sui_bridge::abi::{eth_sui_bridge, EthSuiBridge};
+use sui_bridge::api_types::ActionDigest;
 use sui_bridge::crypto::BridgeAuthorityPublicKeyBytes;
 use sui_bridge::error::BridgeResult;
+use sui_bridge::replay::ReplaySource;
 use sui_bridge::sui_client::SuiBridgeClient;
-use sui_bridge::types::BridgeAction;
+use sui_bridge::transfer_export::TransferExportFormat;
 use sui_bridge::types::{
     AddTokensOnEvmAction, AddTokensOnSuiAction, AssetPriceUpdateAction, BlocklistCommitteeAction,
     BlocklistType, EmergencyAction, EmergencyActionType, EvmContractUpgradeAction,
     LimitUpdateAction,
 };
+use sui_bridge::types::{BridgeAction, BridgeActionDigest};
 use sui_bridge::utils::{get_eth_signer_client, EthSigner};
 use sui_config::Config;
 use sui_json_rpc_types::SuiObjectDataOptions;
@@ -130,6 +133,338 @@ pub enum BridgeCommand {
         #[clap(subcommand)]
         cmd: BridgeClientCommands,
     },
+    /// Replay raw Sui events (from `queryEvents`) or Eth logs (from `getLogs`) through
+    /// the bridge node parsing code and print the resulting actions
+    #[clap(name = "replay")]
+    Replay {
+        #[clap(long = "source", value_enum)]
+        source: ReplaySource,
+        /// Path of the JSON file with the raw events or logs
+        path: PathBuf,
+        /// Path of the output of a previous run to compare against
+        #[clap(long = "diff")]
+        diff: Option<PathBuf>,
+    },
+    /// View the most recent errors recorded by a bridge node, by error counter
+    #[clap(name = "view-node-errors")]
+    ViewNodeErrors {
+        /// Base url of the bridge node server, e.g. http://127.0.0.1:9191
+        #[clap(long = "url")]
+        url: String,
+        /// View the requesters that sent the most signing requests recently, with their
+        /// number of errors, instead
+        #[clap(long = "requesters")]
+        requesters: bool,
+    },
+    /// View the availability scores of the committee members as seen by a bridge node client
+    #[clap(name = "view-committee-scores")]
+    ViewCommitteeScores {
+        /// Base url of the bridge node server, e.g. http://127.0.0.1:9191
+        #[clap(long = "url")]
+        url: String,
+    },
+    /// View the signature aggregations in flight on a bridge node client: the stake
+    /// collected and required, and the committee members that signed, failed or haven't
+    /// answered yet
+    #[clap(name = "view-aggregation-status")]
+    ViewAggregationStatus {
+        /// Base url of the bridge node server, e.g. http://127.0.0.1:9191
+        #[clap(long = "url")]
+        url: String,
+    },
+    /// Summarize the state of a bridge node: readiness, sync of both chains, its clock,
+    /// registered url, executor, gas coin, pending actions and committee, with warnings
+    /// for the parts beyond thresholds
+    #[clap(name = "node-status")]
+    NodeStatus {
+        /// Base url of the bridge node server
+        #[clap(long = "url", default_value = "http://127.0.0.1:9191")]
+        url: String,
+        /// Print the summary as JSON instead of a table
+        #[clap(long = "json")]
+        json: bool,
+    },
+    /// Estimate how long a bridge node client takes to execute its pending actions, from
+    /// its recent throughput and the transfer limits of the routes
+    #[clap(name = "estimate-drain")]
+    EstimateDrain {
+        /// Base url of the bridge node server, e.g. http://127.0.0.1:9191
+        #[clap(long = "url")]
+        url: String,
+        /// Minutes of executions to measure the throughput over. Defaults to 30.
+        #[clap(long = "window-minutes")]
+        window_minutes: Option<u64>,
+    },
+    /// Compare the sets of actions that bridge nodes signed, and find the days they
+    /// signed different actions on
+    #[clap(name = "compare-signed-sets")]
+    CompareSignedSets {
+        /// Base urls of the bridge node servers, at least 2, e.g. http://10.0.0.1:9191
+        #[clap(long = "url")]
+        urls: Vec<String>,
+    },
+    /// Inspect the transfer limits of the bridge on Sui
+    #[clap(name = "limits")]
+    Limits {
+        #[clap(subcommand)]
+        cmd: LimitsCommand,
+    },
+    /// Inspect the journal of Sui transactions submitted by a bridge node client
+    #[clap(name = "journal")]
+    Journal {
+        #[clap(subcommand)]
+        cmd: JournalCommand,
+    },
+    /// Check a bridge node config against the chains and the local machine without
+    /// changing anything, e.g. before starting a new node
+    #[clap(name = "doctor")]
+    Doctor {
+        /// Path of BridgeNodeConfig
+        #[clap(long = "config-path")]
+        config_path: PathBuf,
+    },
+    /// Print the name, type, labels and help of every metric of the bridge node, for
+    /// dashboard authors
+    #[clap(name = "metrics-schema")]
+    MetricsSchema,
+    /// Inspect bridge actions offline
+    #[clap(name = "action")]
+    Action {
+        #[clap(subcommand)]
+        cmd: ActionCommand,
+    },
+    /// Manage the actions that a bridge node client quarantined after they failed
+    /// permanently. The node must be stopped while its db is opened.
+    #[clap(name = "quarantine")]
+    Quarantine {
+        #[clap(subcommand)]
+        cmd: QuarantineCommand,
+    },
+    /// Capture or restore the state of a bridge node client for disaster recovery.
+    /// The node must be stopped while its db is opened.
+    #[clap(name = "snapshot")]
+    Snapshot {
+        #[clap(subcommand)]
+        cmd: SnapshotCommand,
+    },
+    /// Read the executed token transfers that a bridge node client archived before
+    /// pruning them
+    #[clap(name = "archive")]
+    Archive {
+        #[clap(subcommand)]
+        cmd: ArchiveCommand,
+    },
+    /// Check the client storage of a bridge node. The node must be stopped while its db
+    /// is opened.
+    #[clap(name = "storage")]
+    Storage {
+        #[clap(subcommand)]
+        cmd: StorageCommand,
+    },
+    /// Export the token transfers that a bridge node client executed, e.g. for the
+    /// reconciliation of an exchange. The node must be stopped while its db is opened.
+    #[clap(name = "transfers")]
+    Transfers {
+        #[clap(subcommand)]
+        cmd: TransfersCommand,
+    },
+}
+
+#[derive(Parser)]
+#[clap(rename_all = "kebab-case")]
+pub enum TransfersCommand {
+    /// Write the executed transfers in the order of their route and nonce, to stdout or
+    /// `--output`. The cursor of the last written transfer is printed to stderr, an
+    /// export passed it as `--cursor` continues after it.
+    #[clap(name = "export")]
+    Export {
+        /// `db_path` of the bridge node config
+        #[clap(long = "db-path")]
+        db_path: PathBuf,
+        /// `aux_db_path` of the bridge node config, if set
+        #[clap(long = "aux-db-path")]
+        aux_db_path: Option<PathBuf>,
+        /// `storage_encryption_key_path` of the bridge node config, if set
+        #[clap(long = "storage-encryption-key-path")]
+        storage_encryption_key_path: Option<PathBuf>,
+        #[clap(long, value_enum, default_value = "csv")]
+        format: TransferExportFormat,
+        /// Only transfers executed at or after this time, in ms since the epoch
+        #[clap(long)]
+        from: Option<u64>,
+        /// Only transfers executed before this time, in ms since the epoch
+        #[clap(long)]
+        to: Option<u64>,
+        /// Cursor of the last exported transfer, the export resumes after it
+        #[clap(long)]
+        cursor: Option<String>,
+        /// Export at most this many transfers
+        #[clap(long)]
+        limit: Option<u64>,
+        /// File to write, stdout if not set
+        #[clap(long)]
+        output: Option<PathBuf>,
+    },
+}
+
+#[derive(Parser)]
+#[clap(rename_all = "kebab-case")]
+pub enum StorageCommand {
+    /// Check the invariants between the client tables and report the broken ones
+    #[clap(name = "verify")]
+    Verify {
+        /// `db_path` of the bridge node config
+        #[clap(long = "db-path")]
+        db_path: PathBuf,
+        /// `aux_db_path` of the bridge node config, if set
+        #[clap(long = "aux-db-path")]
+        aux_db_path: Option<PathBuf>,
+        /// Fix the repairable violations: misplaced entries are moved under their key,
+        /// stale pending actions and orphaned records are deleted. Every change is printed.
+        #[clap(long)]
+        repair: bool,
+        /// `storage_encryption_key_path` of the bridge node config, if set
+        #[clap(long = "storage-encryption-key-path")]
+        storage_encryption_key_path: Option<PathBuf>,
+    },
+    /// Encrypt the plaintext client storage with the key of `storage_encryption_key_path`,
+    /// before the node is started with it. The node must be stopped. An interrupted run is
+    /// resumed by running it again.
+    #[clap(name = "encrypt-in-place")]
+    EncryptInPlace {
+        /// `db_path` of the bridge node config
+        #[clap(long = "db-path")]
+        db_path: PathBuf,
+        /// `aux_db_path` of the bridge node config, if set
+        #[clap(long = "aux-db-path")]
+        aux_db_path: Option<PathBuf>,
+        /// File with the base64 encoded key, see `storage_encryption_key_path`
+        #[clap(long = "storage-encryption-key-path")]
+        storage_encryption_key_path: PathBuf,
+    },
+}
+
+#[derive(Parser)]
+#[clap(rename_all = "kebab-case")]
+pub enum ArchiveCommand {
+    /// Print an archived token transfer
+    #[clap(name = "fetch")]
+    Fetch {
+        /// Path of the bridge node config, whose `pruning` section has the archive
+        #[clap(long = "config-path")]
+        config_path: PathBuf,
+        /// Hex encoded digest of the action
+        #[clap(long = "digest")]
+        digest: String,
+    },
+}
+
+#[derive(Parser)]
+#[clap(rename_all = "kebab-case")]
+pub enum SnapshotCommand {
+    /// Write the client storage, the config without its secrets, the cursors and the
+    /// committee scores to an archive, with a manifest of what was left out
+    #[clap(name = "create")]
+    Create {
+        /// Path of the archive to write, e.g. snapshot.tar.zst
+        out: PathBuf,
+        /// Path of the bridge node config
+        #[clap(long = "config-path")]
+        config_path: PathBuf,
+    },
+    /// Verify an archive and restore it into an empty data dir, which becomes the
+    /// `db_path` of the restored config
+    #[clap(name = "restore")]
+    Restore {
+        /// Path of the archive written by `snapshot create`
+        archive: PathBuf,
+        #[clap(long = "data-dir")]
+        data_dir: PathBuf,
+    },
+}
+
+#[derive(Parser)]
+#[clap(rename_all = "kebab-case")]
+pub enum LimitsCommand {
+    /// Print the transfers that count against the limit of a route in the last 24 hours,
+    /// and when they stop counting
+    #[clap(name = "show")]
+    Show {
+        #[clap(long = "sui-rpc-url")]
+        sui_rpc_url: String,
+        /// `<source chain id>-<destination chain id>`, e.g. `12-2`
+        #[clap(long = "route")]
+        route: String,
+        /// USD value with 8 decimal places of a transfer, to print when it fits under the
+        /// limit
+        #[clap(long = "amount")]
+        amount: Option<u64>,
+    },
+}
+
+#[derive(Parser)]
+#[clap(rename_all = "kebab-case")]
+pub enum QuarantineCommand {
+    /// Print every quarantined action
+    #[clap(name = "list")]
+    List {
+        /// `db_path` of the bridge node config
+        #[clap(long = "db-path")]
+        db_path: PathBuf,
+    },
+    /// Delete a quarantined action. It's recorded as dropped and never retried.
+    #[clap(name = "delete")]
+    Delete {
+        /// `db_path` of the bridge node config
+        #[clap(long = "db-path")]
+        db_path: PathBuf,
+        /// Hex encoded digest of the action
+        #[clap(long = "digest")]
+        digest: String,
+    },
+    /// Move a quarantined action back to the pending actions, so that it's retried
+    /// when the node starts
+    #[clap(name = "requeue")]
+    Requeue {
+        /// `db_path` of the bridge node config
+        #[clap(long = "db-path")]
+        db_path: PathBuf,
+        /// Hex encoded digest of the action
+        #[clap(long = "digest")]
+        digest: String,
+    },
+}
+
+#[derive(Parser)]
+#[clap(rename_all = "kebab-case")]
+pub enum ActionCommand {
+    /// Print the signing bytes and the digest that authorities sign of an action, e.g.
+    /// `{"EmergencyAction": {"nonce": 1, "chain_id": "SuiTestnet", "action_type": "Pause"}}`
+    #[clap(name = "digest")]
+    Digest {
+        /// Path of the JSON file with the action
+        #[clap(long = "json")]
+        path: PathBuf,
+    },
+}
+
+#[derive(Parser)]
+#[clap(rename_all = "kebab-case")]
+pub enum JournalCommand {
+    /// Print every valid entry of the journal
+    #[clap(name = "list")]
+    List {
+        /// Directory of the journal files
+        #[clap(long = "path")]
+        path: PathBuf,
+    },
+    /// Validate the checksum of every frame and report the corrupted ones
+    #[clap(name = "verify")]
+    Verify {
+        /// Directory of the journal files
+        #[clap(long = "path")]
+        path: PathBuf,
+    },
 }
 
 #[derive(Parser)]
@@ -208,7 +543,59 @@ pub enum GovernanceClientCommands {
         /// Params to be passed to the function, e.g. `420,false,hello`
         #[clap(name = "params", use_value_delimiter = true, long)]
         params: Vec<String>,
+        /// File with the hex encoded call data, for call data too large or complex to be
+        /// built from a function selector and params
+        #[clap(
+            name = "call-data-file",
+            long,
+            conflicts_with_all = &["function-selector", "params"]
+        )]
+        call_data_file: Option<PathBuf>,
     },
+    /// Asks every committee member whether it would sign a JSON encoded governance action,
+    /// without collecting signatures, and prints the stake of each answer
+    #[clap(name = "probe")]
+    Probe {
+        #[clap(name = "action", long)]
+        action: String,
+    },
+}
+
+/// Computes the signing bytes and digest of a JSON encoded `BridgeAction`, the same way
+/// the bridge node does.
+pub fn action_digest_from_json(json: &str) -> anyhow::Result<ActionDigest> {
+    let action: BridgeAction =
+        serde_json::from_str(json).map_err(|e| anyhow!("Invalid action: {:?}", e))?;
+    Ok(ActionDigest {
+        action_type: action.action_type().into(),
+        signing_bytes: Hex::encode(action.to_signing_bytes()),
+        digest: Hex::encode(action.digest().inner()),
+    })
+}
+
+/// Parses a JSON encoded governance `BridgeAction`, which must be on `chain_id`.
+pub fn governance_action_from_json(
+    json: &str,
+    chain_id: BridgeChainId,
+) -> anyhow::Result<BridgeAction> {
+    let action: BridgeAction =
+        serde_json::from_str(json).map_err(|e| anyhow!("Invalid action: {:?}", e))?;
+    if !action.is_governace_action() {
+        return Err(anyhow!("{:?} is not a governance action", action));
+    }
+    if action.chain_id() != chain_id {
+        return Err(anyhow!(
+            "The action is on chain {:?}, expected {:?}",
+            action.chain_id(),
+            chain_id
+        ));
+    }
+    Ok(action)
+}
+
+/// Parses a hex encoded action digest, as printed by `action digest` and `quarantine list`.
+pub fn parse_action_digest(digest: &str) -> anyhow::Result<BridgeActionDigest> {
+    sui_bridge::api_types::decode_action_digest(digest)
 }
 
 pub fn make_action(chain_id: BridgeChainId, cmd: &GovernanceClientCommands) -> BridgeAction {
@@ -297,10 +684,14 @@ pub fn make_action(chain_id: BridgeChainId, cmd: &GovernanceClientCommands) -> B
             implementation_address,
             function_selector,
             params,
+            call_data_file,
         } => {
-            let call_data = match function_selector {
-                Some(function_selector) => encode_call_data(function_selector, params),
-                None => vec![],
+            let call_data = match (function_selector, call_data_file) {
+                (Some(function_selector), _) => encode_call_data(function_selector, params),
+                (None, Some(path)) => read_call_data_file(path).unwrap_or_else(|e| {
+                    panic!("Invalid call data file {}: {:?}", path.display(), e)
+                }),
+                (None, None) => vec![],
             };
             BridgeAction::EvmContractUpgradeAction(EvmContractUpgradeAction {
                 nonce: *nonce,
@@ -310,9 +701,23 @@ pub fn make_action(chain_id: BridgeChainId, cmd: &GovernanceClientCommands) -> B
                 call_data,
             })
         }
+        // The action of a probe is given as JSON
+        GovernanceClientCommands::Probe { .. } => unreachable!(),
     }
 }
 
+/// Reads hex encoded call data, with or without `0x`, from `path`. Whitespace, e.g. line
+/// breaks in long call data, is ignored.
+pub fn read_call_data_file(path: &std::path::Path) -> anyhow::Result<Vec<u8>> {
+    let content = std::fs::read_to_string(path)?;
+    let hex = content
+        .chars()
+        .filter(|c| !c.is_whitespace())
+        .collect::<String>();
+    Hex::decode(hex.strip_prefix("0x").unwrap_or(&hex))
+        .map_err(|e| anyhow!("Invalid hex call data: {:?}", e))
+}
+
 fn encode_call_data(function_selector: &str, params: &[String]) -> Vec<u8> {
     let left = function_selector
         .find('(')
@@ -370,6 +775,7 @@ pub fn select_contract_address(
         GovernanceClientCommands::UpgradeEVMContract { proxy_address, .. } => *proxy_address,
         GovernanceClientCommands::AddTokensOnSui { .. } => unreachable!(),
         GovernanceClientCommands::AddTokensOnEvm { .. } => config.eth_bridge_config_proxy_address,
+        GovernanceClientCommands::Probe { .. } => unreachable!(),
     }
 }
 
@@ -717,9 +1123,107 @@ async fn claim_on_eth(
 #[cfg(test)]
 mod tests {
     use ethers::abi::FunctionExt;
+    use sui_bridge::api_types::ActionType;
 
     use super::*;
 
+    #[test]
+    fn test_action_digest_from_json() {
+        let digest = action_digest_from_json(
+            r#"{"EmergencyAction": {"nonce": 55, "chain_id": "SuiCustom", "action_type": "Pause"}}"#,
+        )
+        .unwrap();
+        assert_eq!(digest.action_type, ActionType::EmergencyButton);
+        assert_eq!(
+            digest.signing_bytes,
+            "5355495f4252494447455f4d455353414745020100000000000000370200"
+        );
+        assert_eq!(
+            digest.digest,
+            "b60a9b06bcb00169a55e9da47cb9848704e16b2a5edf6e9056b47e7feb8f90d9"
+        );
+
+        let digest = action_digest_from_json(
+            r#"{"LimitUpdateAction": {"nonce": 15, "chain_id": "SuiCustom", "sending_chain_id": "EthCustom", "new_usd_limit": 10000000000}}"#,
+        )
+        .unwrap();
+        assert_eq!(
+            digest.digest,
+            "9fb6a4c7554d038fa48acec21c727e380b27eb2d0105aa245699b8058792af24"
+        );
+
+        action_digest_from_json(r#"{"EmergencyAction": {"nonce": 55}}"#).unwrap_err();
+    }
+
+    #[test]
+    fn test_governance_action_from_json() {
+        let json = r#"{"EmergencyAction": {"nonce": 55, "chain_id": "SuiCustom", "action_type": "Pause"}}"#;
+        let action = governance_action_from_json(json, BridgeChainId::SuiCustom).unwrap();
+        assert_eq!(
+            action,
+            BridgeAction::EmergencyAction(EmergencyAction {
+                nonce: 55,
+                chain_id: BridgeChainId::SuiCustom,
+                action_type: EmergencyActionType::Pause,
+            })
+        );
+        // On another chain
+        governance_action_from_json(json, BridgeChainId::EthCustom).unwrap_err();
+        governance_action_from_json(
+            r#"{"EmergencyAction": {"nonce": 55}}"#,
+            BridgeChainId::SuiCustom,
+        )
+        .unwrap_err();
+    }
+
+    #[test]
+    fn test_parse_action_digest() {
+        let action = action_digest_from_json(r#"{"EmergencyAction": {"nonce": 55, "chain_id": "SuiCustom", "action_type": "Pause"}}"#)
+            .unwrap();
+        let digest = parse_action_digest(&action.digest).unwrap();
+        assert_eq!(Hex::encode(digest.inner()), action.digest);
+        assert_eq!(
+            parse_action_digest(&format!("0x{}", action.digest)).unwrap(),
+            digest
+        );
+        parse_action_digest("0xabcd").unwrap_err();
+        parse_action_digest("not hex").unwrap_err();
+    }
+
+    #[test]
+    fn test_read_call_data_file() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let path = temp_dir.path().join("call_data.hex");
+        // 64KB of call data, spread over lines
+        let call_data = (0..64 * 1024).map(|i| i as u8).collect::<Vec<_>>();
+        let hex = Hex::encode(&call_data)
+            .as_bytes()
+            .chunks(64)
+            .map(|line| std::str::from_utf8(line).unwrap())
+            .collect::<Vec<_>>()
+            .join("\n");
+        std::fs::write(&path, format!("0x{}\n", hex)).unwrap();
+        assert_eq!(read_call_data_file(&path).unwrap(), call_data);
+
+        let cmd = GovernanceClientCommands::UpgradeEVMContract {
+            nonce: 1,
+            proxy_address: EthAddress::repeat_byte(6),
+            implementation_address: EthAddress::repeat_byte(9),
+            function_selector: None,
+            params: vec![],
+            call_data_file: Some(path.clone()),
+        };
+        let BridgeAction::EvmContractUpgradeAction(action) =
+            make_action(BridgeChainId::EthCustom, &cmd)
+        else {
+            panic!("Expected an EVM contract upgrade");
+        };
+        assert_eq!(action.call_data, call_data);
+
+        std::fs::write(&path, "0xnothex").unwrap();
+        read_call_data_file(&path).unwrap_err();
+    }
+
     #[tokio::test]
     async fn test_encode_call_data() {
         let abi_json =
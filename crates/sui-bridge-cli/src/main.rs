@@ -7,16 +7,35 @@ use ethers::types::Address as EthAddress;
 use fastcrypto::encoding::{Encoding, Hex};
 use shared_crypto::intent::Intent;
 use shared_crypto::intent::IntentMessage;
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
+use std::io::IsTerminal;
 use std::str::from_utf8;
 use std::str::FromStr;
 use std::sync::Arc;
 use std::time::Duration;
+use sui_bridge::api_types;
 use sui_bridge::client::bridge_authority_aggregator::BridgeAuthorityAggregator;
+use sui_bridge::client::committee_probe::{probe_governance_action, GOVERNANCE_PROBE_TIMEOUT};
+use sui_bridge::config::BridgeNodeConfig;
 use sui_bridge::crypto::{BridgeAuthorityPublicKey, BridgeAuthorityPublicKeyBytes};
+use sui_bridge::doctor::{format_check_results, run_checks, CheckStatus};
 use sui_bridge::eth_transaction_builder::build_eth_transaction;
+use sui_bridge::limit_window::parse_route;
+use sui_bridge::metrics::BridgeMetrics;
+use sui_bridge::node_status::{fetch_node_status, format_node_status};
+use sui_bridge::replay::{diff_replay_records, replay_file, ReplayRecord};
+use sui_bridge::server::{
+    ADMIN_AGGREGATION_STATUS_PATH, ADMIN_COMMITTEE_SCORES_PATH, ADMIN_DRAIN_ESTIMATE_PATH,
+    ADMIN_ERRORS_PATH, ADMIN_ERRORS_REQUESTERS_PATH, SIGNED_SET_DIGEST_PATH,
+};
+use sui_bridge::snapshot::{create_snapshot, restore_snapshot};
+use sui_bridge::storage::BridgeOrchestratorTables;
+use sui_bridge::storage_encryption::{FileStorageKeyProvider, StorageKeyProvider};
 use sui_bridge::sui_client::SuiClient;
 use sui_bridge::sui_transaction_builder::build_sui_transaction;
+use sui_bridge::timestamps::now_ms;
+use sui_bridge::transfer_export::{export_transfers, TransferFilter};
+use sui_bridge::tx_journal::read_journal;
 use sui_bridge::types::BridgeActionType;
 use sui_bridge::utils::{
     examine_key, generate_bridge_authority_key_and_write_to_file,
@@ -24,13 +43,15 @@ use sui_bridge::utils::{
 };
 use sui_bridge::utils::{get_eth_contracts, EthBridgeContracts};
 use sui_bridge_cli::{
-    make_action, select_contract_address, Args, BridgeCliConfig, BridgeCommand,
-    LoadedBridgeCliConfig, Network, SEPOLIA_BRIDGE_PROXY_ADDR,
+    action_digest_from_json, governance_action_from_json, make_action, parse_action_digest,
+    select_contract_address, ActionCommand, ArchiveCommand, Args, BridgeCliConfig, BridgeCommand,
+    GovernanceClientCommands, JournalCommand, LimitsCommand, LoadedBridgeCliConfig, Network,
+    QuarantineCommand, SnapshotCommand, StorageCommand, TransfersCommand,
+    SEPOLIA_BRIDGE_PROXY_ADDR,
 };
 use sui_config::Config;
 use sui_sdk::SuiClient as SuiSdkClient;
 use sui_sdk::SuiClientBuilder;
-use sui_types::base_types::SuiAddress;
 use sui_types::bridge::BridgeChainId;
 use sui_types::bridge::{MoveTypeCommitteeMember, MoveTypeCommitteeMemberRegistration};
 use sui_types::committee::TOTAL_VOTING_POWER;
@@ -77,11 +98,29 @@ async fn main() -> anyhow::Result<()> {
             dry_run,
         } => {
             let chain_id = BridgeChainId::try_from(chain_id).expect("Invalid chain id");
-            println!("Chain ID: {:?}", chain_id);
             let config = BridgeCliConfig::load(config_path).expect("Couldn't load BridgeCliConfig");
             let config = LoadedBridgeCliConfig::load(config).await?;
             let sui_bridge_client = SuiClient::<SuiSdkClient>::new(&config.sui_rpc_url).await?;
 
+            if let GovernanceClientCommands::Probe { action } = &cmd {
+                let action = governance_action_from_json(action, chain_id)?;
+                let bridge_committee = Arc::new(
+                    sui_bridge_client
+                        .get_bridge_committee()
+                        .await
+                        .map_err(|e| anyhow::anyhow!("Failed to get bridge committee: {:?}", e))?,
+                );
+                let agg = BridgeAuthorityAggregator::new(
+                    bridge_committee,
+                    Arc::new(BridgeMetrics::for_testing()),
+                );
+                let probe = probe_governance_action(&agg, &action, GOVERNANCE_PROBE_TIMEOUT).await;
+                println!("{}", serde_json::to_string_pretty(&probe)?);
+                return Ok(());
+            }
+
+            println!("Chain ID: {:?}", chain_id);
+
             let (sui_key, sui_address, gas_object_ref) = config
                 .get_sui_account_info()
                 .await
@@ -96,7 +135,10 @@ async fn main() -> anyhow::Result<()> {
                     .await
                     .expect("Failed to get bridge committee"),
             );
-            let agg = BridgeAuthorityAggregator::new(bridge_committee);
+            let agg = BridgeAuthorityAggregator::new(
+                bridge_committee,
+                Arc::new(BridgeMetrics::for_testing()),
+            );
 
             // Handle Sui Side
             if chain_id.is_sui_chain() {
@@ -248,14 +290,14 @@ async fn main() -> anyhow::Result<()> {
                 .call()
                 .await?;
 
-            let print = OutputEthBridge {
-                chain_id: chain_id.as_u64(),
-                bridge_proxy: bridge.address(),
-                committee_proxy: committee.address(),
-                limiter_proxy: limiter.address(),
-                config_proxy: config.address(),
-                vault: vault.address(),
-                nonces: Nonces {
+            let print = api_types::EthBridgeView::new(
+                chain_id.as_u64(),
+                bridge.address(),
+                committee.address(),
+                limiter.address(),
+                config.address(),
+                vault.address(),
+                api_types::EthBridgeNonces {
                     token_transfer: token_transfer_next_nonce,
                     blocklist_update: blocklist_update_nonce,
                     emergency_button: emergency_button_nonce,
@@ -267,7 +309,7 @@ async fn main() -> anyhow::Result<()> {
                     contract_upgrade_limiter: limiter_upgrade_next_nonce,
                     contract_upgrade_config: config_upgrade_next_nonce,
                 },
-            };
+            );
             println!("{}", serde_json::to_string_pretty(&print).unwrap());
             return Ok(());
         }
@@ -301,7 +343,8 @@ async fn main() -> anyhow::Result<()> {
                 })
                 .collect::<HashMap<_, _>>();
             let mut authorities = vec![];
-            let mut output_wrapper = Output::<OutputSuiBridgeRegistration>::default();
+            let mut output_wrapper =
+                api_types::CliOutput::<api_types::SuiBridgeRegistrationView>::default();
             for (_, member) in move_type_bridge_committee.member_registration {
                 let MoveTypeCommitteeMemberRegistration {
                     sui_address,
@@ -333,21 +376,19 @@ async fn main() -> anyhow::Result<()> {
                 .iter()
                 .map(|(_, _, _, _, _, stake)| **stake)
                 .sum::<u64>();
-            let mut output = OutputSuiBridgeRegistration {
+            let mut output = api_types::SuiBridgeRegistrationView {
                 total_registered_stake: total_stake as f32 / TOTAL_VOTING_POWER as f32 * 100.0,
                 ..Default::default()
             };
             for (name, sui_address, pubkey, eth_address, url, stake) in authorities {
-                output.committee.push(OutputMember {
-                    name: name.clone(),
+                output.committee.push(api_types::CommitteeMember::new(
+                    name.clone(),
                     sui_address,
                     eth_address,
-                    pubkey: Hex::encode(pubkey.as_bytes()),
+                    Hex::encode(pubkey.as_bytes()),
                     url,
-                    stake: *stake,
-                    blocklisted: None,
-                    status: None,
-                });
+                    *stake,
+                ));
             }
             output_wrapper.inner = output;
             println!("{}", serde_json::to_string_pretty(&output_wrapper).unwrap());
@@ -380,7 +421,7 @@ async fn main() -> anyhow::Result<()> {
                 .timeout(Duration::from_secs(10))
                 .build()
                 .unwrap();
-            let mut output_wrapper = Output::<OutputSuiBridge>::default();
+            let mut output_wrapper = api_types::CliOutput::<api_types::SuiBridgeView>::default();
             for (_, member) in move_type_bridge_committee.members {
                 let MoveTypeCommitteeMember {
                     sui_address,
@@ -425,7 +466,7 @@ async fn main() -> anyhow::Result<()> {
                 .iter()
                 .map(|(_, _, _, _, _, stake, _)| *stake)
                 .sum::<u64>();
-            let mut output = OutputSuiBridge {
+            let mut output = api_types::SuiBridgeView {
                 total_stake: total_stake as f32 / TOTAL_VOTING_POWER as f32 * 100.0,
                 ..Default::default()
             };
@@ -452,39 +493,24 @@ async fn main() -> anyhow::Result<()> {
                 } else {
                     pubkey.to_string()
                 };
-                match ping_resp {
-                    Some(resp) => {
-                        if resp {
-                            total_online_stake += stake;
-                        }
-                        output.committee.push(OutputMember {
-                            name: name.clone(),
-                            sui_address,
-                            eth_address,
-                            pubkey,
-                            url,
-                            stake,
-                            blocklisted: Some(blocklisted),
-                            status: Some(if resp {
-                                "online".to_string()
-                            } else {
-                                "offline".to_string()
-                            }),
-                        });
-                    }
-                    None => {
-                        output.committee.push(OutputMember {
-                            name: name.clone(),
-                            sui_address,
-                            eth_address,
-                            pubkey,
-                            url,
-                            stake,
-                            blocklisted: Some(blocklisted),
-                            status: None,
-                        });
+                let mut member = api_types::CommitteeMember::new(
+                    name.clone(),
+                    sui_address,
+                    eth_address,
+                    pubkey,
+                    url,
+                    stake,
+                );
+                member.blocklisted = Some(blocklisted);
+                member.status = ping_resp.map(|resp| {
+                    if resp {
+                        total_online_stake += stake;
+                        api_types::MemberStatus::Online
+                    } else {
+                        api_types::MemberStatus::Offline
                     }
-                }
+                });
+                output.committee.push(member);
             }
             if ping {
                 output.total_online_stake =
@@ -495,7 +521,7 @@ async fn main() -> anyhow::Result<()> {
             for (type_, nonce) in bridge_summary.sequence_nums {
                 output
                     .nonces
-                    .insert(BridgeActionType::try_from(type_).unwrap(), nonce);
+                    .insert(BridgeActionType::try_from(type_).unwrap().into(), nonce);
             }
 
             output_wrapper.inner = output;
@@ -508,77 +534,440 @@ async fn main() -> anyhow::Result<()> {
             cmd.handle(&config, sui_bridge_client).await?;
             return Ok(());
         }
-    }
-
-    Ok(())
-}
-
-#[derive(serde::Serialize, Default)]
-struct OutputEthBridge {
-    chain_id: u64,
-    bridge_proxy: EthAddress,
-    committee_proxy: EthAddress,
-    limiter_proxy: EthAddress,
-    config_proxy: EthAddress,
-    vault: EthAddress,
-    nonces: Nonces,
-}
-
-#[derive(serde::Serialize, Default)]
-struct Nonces {
-    token_transfer: u64,
-    blocklist_update: u64,
-    emergency_button: u64,
-    limit_update: u64,
-    asset_price_update: u64,
-    add_evm_tokens: u64,
-    contract_upgrade_bridge: u64,
-    contract_upgrade_committee: u64,
-    contract_upgrade_limiter: u64,
-    contract_upgrade_config: u64,
-}
-
-#[derive(serde::Serialize, Default)]
-struct Output<P: Default> {
-    #[serde(skip_serializing_if = "Option::is_none")]
-    errors: Option<Vec<String>>,
-    inner: P,
-}
-
-impl<P: Default> Output<P> {
-    fn add_error(&mut self, error: String) {
-        if self.errors.is_none() {
-            self.errors = Some(vec![]);
+        BridgeCommand::Replay { source, path, diff } => {
+            let records = replay_file(source, &path)?;
+            let Some(diff_path) = diff else {
+                println!("{}", serde_json::to_string_pretty(&records).unwrap());
+                return Ok(());
+            };
+            let expected: Vec<ReplayRecord> =
+                serde_json::from_reader(std::fs::File::open(&diff_path)?)?;
+            let diffs = diff_replay_records(&records, &expected);
+            if diffs.is_empty() {
+                println!("No difference from {}", diff_path.display());
+                return Ok(());
+            }
+            println!("{}", serde_json::to_string_pretty(&diffs).unwrap());
+            return Err(anyhow::anyhow!(
+                "Found {} difference(s) from {}",
+                diffs.len(),
+                diff_path.display()
+            ));
+        }
+        BridgeCommand::ViewNodeErrors { url, requesters } => {
+            if requesters {
+                let url = format!(
+                    "{}{}",
+                    url.trim_end_matches('/'),
+                    ADMIN_ERRORS_REQUESTERS_PATH
+                );
+                let summary: api_types::RequesterSummary = reqwest::Client::builder()
+                    .connect_timeout(Duration::from_secs(10))
+                    .timeout(Duration::from_secs(10))
+                    .build()?
+                    .get(url)
+                    .send()
+                    .await?
+                    .error_for_status()?
+                    .json()
+                    .await?;
+                println!("{}", serde_json::to_string_pretty(&summary).unwrap());
+                return Ok(());
+            }
+            let url = format!("{}{}", url.trim_end_matches('/'), ADMIN_ERRORS_PATH);
+            let errors: BTreeMap<String, Vec<api_types::ErrorEntry>> = reqwest::Client::builder()
+                .connect_timeout(Duration::from_secs(10))
+                .timeout(Duration::from_secs(10))
+                .build()?
+                .get(url)
+                .send()
+                .await?
+                .error_for_status()?
+                .json()
+                .await?;
+            println!("{}", serde_json::to_string_pretty(&errors).unwrap());
+        }
+        BridgeCommand::ViewCommitteeScores { url } => {
+            let url = format!(
+                "{}{}",
+                url.trim_end_matches('/'),
+                ADMIN_COMMITTEE_SCORES_PATH
+            );
+            let scores: BTreeMap<String, api_types::CommitteeMemberScore> =
+                reqwest::Client::builder()
+                    .connect_timeout(Duration::from_secs(10))
+                    .timeout(Duration::from_secs(10))
+                    .build()?
+                    .get(url)
+                    .send()
+                    .await?
+                    .error_for_status()?
+                    .json()
+                    .await?;
+            println!("{}", serde_json::to_string_pretty(&scores).unwrap());
+        }
+        BridgeCommand::ViewAggregationStatus { url } => {
+            let url = format!(
+                "{}{}",
+                url.trim_end_matches('/'),
+                ADMIN_AGGREGATION_STATUS_PATH
+            );
+            let aggregations: Vec<api_types::AggregationStatus> = reqwest::Client::builder()
+                .connect_timeout(Duration::from_secs(10))
+                .timeout(Duration::from_secs(10))
+                .build()?
+                .get(url)
+                .send()
+                .await?
+                .error_for_status()?
+                .json()
+                .await?;
+            println!("{}", serde_json::to_string_pretty(&aggregations).unwrap());
+        }
+        BridgeCommand::NodeStatus { url, json } => {
+            let client = reqwest::Client::builder()
+                .connect_timeout(Duration::from_secs(10))
+                .timeout(Duration::from_secs(10))
+                .build()?;
+            let report = fetch_node_status(&client, &url, now_ms()).await;
+            if json {
+                println!("{}", serde_json::to_string_pretty(&report).unwrap());
+            } else {
+                print!(
+                    "{}",
+                    format_node_status(&report, std::io::stdout().is_terminal())
+                );
+            }
+        }
+        BridgeCommand::EstimateDrain {
+            url,
+            window_minutes,
+        } => {
+            let url = format!("{}{}", url.trim_end_matches('/'), ADMIN_DRAIN_ESTIMATE_PATH);
+            let mut request = reqwest::Client::builder()
+                .connect_timeout(Duration::from_secs(10))
+                .timeout(Duration::from_secs(30))
+                .build()?
+                .get(url);
+            if let Some(window_minutes) = window_minutes {
+                request = request.query(&[("window_minutes", window_minutes)]);
+            }
+            let estimate: api_types::DrainEstimate =
+                request.send().await?.error_for_status()?.json().await?;
+            println!("{}", serde_json::to_string_pretty(&estimate).unwrap());
+        }
+        BridgeCommand::CompareSignedSets { urls } => {
+            if urls.len() < 2 {
+                return Err(anyhow::anyhow!("At least 2 urls are required to compare"));
+            }
+            let client = reqwest::Client::builder()
+                .connect_timeout(Duration::from_secs(10))
+                .timeout(Duration::from_secs(10))
+                .build()?;
+            let digests = futures::future::join_all(urls.iter().map(|url| {
+                let client = client.clone();
+                let digest_url = format!("{}{}", url.trim_end_matches('/'), SIGNED_SET_DIGEST_PATH);
+                async move {
+                    let digest = async {
+                        client
+                            .get(digest_url)
+                            .send()
+                            .await?
+                            .error_for_status()?
+                            .json::<api_types::SignedSetDigest>()
+                            .await
+                    }
+                    .await
+                    .map_err(|e| e.to_string());
+                    (url.clone(), digest)
+                }
+            }))
+            .await;
+            let comparison = api_types::SignedSetComparison::new(digests);
+            println!("{}", serde_json::to_string_pretty(&comparison).unwrap());
+            if !comparison.matching {
+                return Err(anyhow::anyhow!("The nodes didn't sign the same actions"));
+            }
+        }
+        BridgeCommand::Limits { cmd } => match cmd {
+            LimitsCommand::Show {
+                sui_rpc_url,
+                route,
+                amount,
+            } => {
+                let route = parse_route(&route)?;
+                let sui_bridge_client = SuiClient::<SuiSdkClient>::new(&sui_rpc_url).await?;
+                let window = sui_bridge_client
+                    .get_limit_window(route, amount)
+                    .await
+                    .map_err(|e| anyhow::anyhow!("Failed to get the limit window: {:?}", e))?;
+                println!("{}", serde_json::to_string_pretty(&window).unwrap());
+            }
+        },
+        BridgeCommand::Journal { cmd } => {
+            let (path, list) = match cmd {
+                JournalCommand::List { path } => (path, true),
+                JournalCommand::Verify { path } => (path, false),
+            };
+            let report = read_journal(&path)?;
+            if list {
+                let entries = report
+                    .records
+                    .iter()
+                    .map(api_types::JournalEntry::from)
+                    .collect::<Vec<_>>();
+                println!("{}", serde_json::to_string_pretty(&entries).unwrap());
+            }
+            if !report.corruptions.is_empty() {
+                let corruptions = report
+                    .corruptions
+                    .iter()
+                    .map(api_types::JournalFrameCorruption::from)
+                    .collect::<Vec<_>>();
+                println!("{}", serde_json::to_string_pretty(&corruptions).unwrap());
+                return Err(anyhow::anyhow!(
+                    "Found {} corrupted frame(s) in {}",
+                    report.corruptions.len(),
+                    path.display()
+                ));
+            }
+            if !list {
+                println!(
+                    "Verified {} journal entries in {}",
+                    report.records.len(),
+                    path.display()
+                );
+            }
+        }
+        BridgeCommand::Doctor { config_path } => {
+            let config = BridgeNodeConfig::load(&config_path)?;
+            let results = run_checks(&config).await;
+            print!("{}", format_check_results(&results));
+            let failures = results
+                .iter()
+                .filter(|result| result.status == CheckStatus::Fail)
+                .count();
+            if failures > 0 {
+                return Err(anyhow::anyhow!("{} check(s) failed", failures));
+            }
+        }
+        BridgeCommand::MetricsSchema => {
+            println!(
+                "{}",
+                serde_json::to_string_pretty(&BridgeMetrics::schema()).unwrap()
+            );
         }
-        self.errors.as_mut().unwrap().push(error);
+        BridgeCommand::Action { cmd } => match cmd {
+            ActionCommand::Digest { path } => {
+                let digest = action_digest_from_json(&std::fs::read_to_string(&path)?)?;
+                println!("{}", serde_json::to_string_pretty(&digest).unwrap());
+            }
+        },
+        BridgeCommand::Quarantine { cmd } => match cmd {
+            QuarantineCommand::List { db_path } => {
+                let store = BridgeOrchestratorTables::new(&db_path.join("client"));
+                let quarantined = store
+                    .get_all_quarantined_actions()
+                    .into_iter()
+                    .map(|(digest, quarantined)| {
+                        (
+                            api_types::encode_action_digest(&digest),
+                            api_types::QuarantinedAction::from(&quarantined),
+                        )
+                    })
+                    .collect::<BTreeMap<_, _>>();
+                println!("{}", serde_json::to_string_pretty(&quarantined).unwrap());
+            }
+            QuarantineCommand::Delete { db_path, digest } => {
+                let store = BridgeOrchestratorTables::new(&db_path.join("client"));
+                let Some(action) = store
+                    .delete_quarantined_action(&parse_action_digest(&digest)?)
+                    .map_err(|e| anyhow::anyhow!("Failed to delete quarantined action: {:?}", e))?
+                else {
+                    return Err(anyhow::anyhow!("Action {} is not quarantined", digest));
+                };
+                println!("Deleted quarantined action {}", digest);
+                println!(
+                    "{}",
+                    serde_json::to_string_pretty(&api_types::Action::from(&action)).unwrap()
+                );
+            }
+            QuarantineCommand::Requeue { db_path, digest } => {
+                let store = BridgeOrchestratorTables::new(&db_path.join("client"));
+                let Some(action) = store
+                    .requeue_quarantined_action(&parse_action_digest(&digest)?)
+                    .map_err(|e| {
+                        anyhow::anyhow!("Failed to requeue quarantined action: {:?}", e)
+                    })?
+                else {
+                    return Err(anyhow::anyhow!("Action {} is not quarantined", digest));
+                };
+                println!(
+                    "Requeued action {}, it's retried when the bridge node starts",
+                    digest
+                );
+                println!(
+                    "{}",
+                    serde_json::to_string_pretty(&api_types::Action::from(&action)).unwrap()
+                );
+            }
+        },
+        BridgeCommand::Snapshot { cmd } => match cmd {
+            SnapshotCommand::Create { out, config_path } => {
+                let config = BridgeNodeConfig::load(&config_path)?;
+                let manifest = create_snapshot(&config, &out)?;
+                println!("Wrote snapshot to {}", out.display());
+                println!("{}", serde_json::to_string_pretty(&manifest).unwrap());
+            }
+            SnapshotCommand::Restore { archive, data_dir } => {
+                let manifest = restore_snapshot(&archive, &data_dir)?;
+                println!(
+                    "Restored snapshot into {}, with the config in {}",
+                    data_dir.display(),
+                    data_dir.join("config.yaml").display()
+                );
+                println!("Provide the omitted items before starting the node:");
+                for omitted in &manifest.omitted {
+                    println!("  {}: {}", omitted.item, omitted.reason);
+                }
+            }
+        },
+        BridgeCommand::Archive { cmd } => match cmd {
+            ArchiveCommand::Fetch {
+                config_path,
+                digest,
+            } => {
+                let config = BridgeNodeConfig::load(&config_path)?;
+                let archive = config
+                    .pruning
+                    .as_ref()
+                    .and_then(|pruning| pruning.archive.as_ref())
+                    .ok_or_else(|| {
+                        anyhow::anyhow!("No archive is configured in {}", config_path.display())
+                    })?
+                    .open()?;
+                match archive.fetch(&parse_action_digest(&digest)?).await? {
+                    Some(archived) => {
+                        println!("{}", serde_json::to_string_pretty(&archived).unwrap())
+                    }
+                    None => println!("Action {} is not in the archive", digest),
+                }
+            }
+        },
+        BridgeCommand::Storage { cmd } => match cmd {
+            StorageCommand::Verify {
+                db_path,
+                aux_db_path,
+                repair,
+                storage_encryption_key_path,
+            } => {
+                let key_provider = storage_encryption_key_path
+                    .as_deref()
+                    .map(FileStorageKeyProvider::read)
+                    .transpose()
+                    .map_err(|e| anyhow::anyhow!("{:?}", e))?;
+                let store = BridgeOrchestratorTables::open_with_encryption(
+                    &db_path.join("client"),
+                    aux_db_path.as_deref(),
+                    key_provider
+                        .as_ref()
+                        .map(|key_provider| key_provider as &dyn StorageKeyProvider),
+                )
+                .map_err(|e| anyhow::anyhow!("Failed to open storage: {:?}", e))?;
+                let violations = store.verify_integrity();
+                println!("Found {} violations", violations.len());
+                for violation in &violations {
+                    let note = if violation.is_repairable() {
+                        ""
+                    } else {
+                        " (not repairable)"
+                    };
+                    println!("  {}{}", violation, note);
+                }
+                if repair && !violations.is_empty() {
+                    let done = store
+                        .repair_integrity(&violations)
+                        .map_err(|e| anyhow::anyhow!("Failed to repair storage: {:?}", e))?;
+                    println!("Repaired:");
+                    for change in &done {
+                        println!("  {}", change);
+                    }
+                    let remaining = store.verify_integrity();
+                    println!("{} violations remain", remaining.len());
+                    for violation in &remaining {
+                        println!("  {}", violation);
+                    }
+                }
+            }
+            StorageCommand::EncryptInPlace {
+                db_path,
+                aux_db_path,
+                storage_encryption_key_path,
+            } => {
+                let key_provider = FileStorageKeyProvider::read(&storage_encryption_key_path)
+                    .map_err(|e| anyhow::anyhow!("{:?}", e))?;
+                let done = BridgeOrchestratorTables::encrypt_in_place(
+                    &db_path.join("client"),
+                    aux_db_path.as_deref(),
+                    &key_provider,
+                )
+                .map_err(|e| anyhow::anyhow!("Failed to encrypt storage: {:?}", e))?;
+                for change in &done {
+                    println!("{}", change);
+                }
+            }
+        },
+        BridgeCommand::Transfers { cmd } => match cmd {
+            TransfersCommand::Export {
+                db_path,
+                aux_db_path,
+                storage_encryption_key_path,
+                format,
+                from,
+                to,
+                cursor,
+                limit,
+                output,
+            } => {
+                let after = cursor
+                    .as_deref()
+                    .map(api_types::parse_transfer_cursor)
+                    .transpose()
+                    .map_err(|e| anyhow::anyhow!(e))?;
+                let key_provider = storage_encryption_key_path
+                    .as_deref()
+                    .map(FileStorageKeyProvider::read)
+                    .transpose()
+                    .map_err(|e| anyhow::anyhow!("{:?}", e))?;
+                let store = BridgeOrchestratorTables::open_with_encryption(
+                    &db_path.join("client"),
+                    aux_db_path.as_deref(),
+                    key_provider
+                        .as_ref()
+                        .map(|key_provider| key_provider as &dyn StorageKeyProvider),
+                )
+                .map_err(|e| anyhow::anyhow!("Failed to open storage: {:?}", e))?;
+                let filter = TransferFilter {
+                    after,
+                    from_ms: from,
+                    to_ms: to,
+                    limit,
+                };
+                let out: Box<dyn std::io::Write> = match &output {
+                    Some(output) => Box::new(std::fs::File::create(output)?),
+                    None => Box::new(std::io::stdout().lock()),
+                };
+                let summary =
+                    export_transfers(&store, &filter, format, &mut std::io::BufWriter::new(out))
+                        .map_err(|e| anyhow::anyhow!("Failed to export transfers: {:?}", e))?;
+                eprintln!("Exported {} transfers", summary.exported);
+                // Without new transfers, the export resumes after the same cursor
+                if let Some(last_cursor) = summary.last_cursor.or(cursor) {
+                    eprintln!("Resume with --cursor {}", last_cursor);
+                }
+            }
+        },
     }
-}
-
-#[derive(serde::Serialize, Default)]
-struct OutputSuiBridge {
-    total_stake: f32,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    total_online_stake: Option<f32>,
-    committee: Vec<OutputMember>,
-    nonces: HashMap<BridgeActionType, u64>,
-}
 
-#[derive(serde::Serialize)]
-struct OutputMember {
-    name: String,
-    sui_address: SuiAddress,
-    eth_address: EthAddress,
-    pubkey: String,
-    url: String,
-    stake: u64,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    blocklisted: Option<bool>,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    status: Option<String>,
-}
-
-#[derive(serde::Serialize, Default)]
-struct OutputSuiBridgeRegistration {
-    total_registered_stake: f32,
-    committee: Vec<OutputMember>,
+    Ok(())
 }
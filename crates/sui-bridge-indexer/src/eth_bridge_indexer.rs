@@ -12,7 +12,7 @@ use ethers::prelude::Transaction;
 use ethers::providers::{Http, Middleware, Provider, StreamExt, Ws};
 use ethers::types::{Address as EthAddress, Block, Filter, H256};
 use prometheus::{IntCounterVec, IntGaugeVec};
-use sui_bridge::error::BridgeError;
+use sui_bridge::error::{BridgeError, ErrorKind};
 use sui_bridge::eth_client::EthClient;
 use sui_bridge::metered_eth_provider::MeteredEthHttpProvier;
 use sui_bridge::retry_with_max_elapsed_time;
@@ -86,6 +86,7 @@ impl Datasource<RawEthData> for EthSubscriptionDatasource {
                     block_number: log
                         .block_number
                         .ok_or(BridgeError::ProviderError(
+                            ErrorKind::Transient,
                             "Provider returns log without block_number".into(),
                         ))
                         .unwrap()
@@ -93,6 +94,7 @@ impl Datasource<RawEthData> for EthSubscriptionDatasource {
                     tx_hash: log
                         .transaction_hash
                         .ok_or(BridgeError::ProviderError(
+                            ErrorKind::Transient,
                             "Provider returns log without transaction_hash".into(),
                         ))
                         .unwrap(),
@@ -0,0 +1,64 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+// Build script to generate the tonic stubs of the gRPC signing API. The messages are
+// defined in `src/server/grpc.rs`, and described for other languages in
+// `proto/bridge.proto`.
+fn main() {
+    #[cfg(feature = "grpc")]
+    build_tonic_services();
+
+    println!("cargo:rerun-if-changed=build.rs");
+}
+
+#[cfg(feature = "grpc")]
+fn build_tonic_services() {
+    let out_dir = std::path::PathBuf::from(std::env::var("OUT_DIR").unwrap());
+    let codec_path = "tonic::codec::ProstCodec";
+
+    let service = tonic_build::manual::Service::builder()
+        .name("BridgeSigningService")
+        .package("bridge")
+        .comment("Bridge authority signing interface, equivalent to the JSON API")
+        .method(
+            tonic_build::manual::Method::builder()
+                .name("sign_token_transfer")
+                .route_name("SignTokenTransfer")
+                .input_type("crate::server::grpc::SignTokenTransferRequest")
+                .output_type("crate::server::grpc::SignedActionResponse")
+                .codec_path(codec_path)
+                .build(),
+        )
+        .method(
+            tonic_build::manual::Method::builder()
+                .name("sign_governance_action")
+                .route_name("SignGovernanceAction")
+                .input_type("crate::server::grpc::SignGovernanceActionRequest")
+                .output_type("crate::server::grpc::SignedActionResponse")
+                .codec_path(codec_path)
+                .build(),
+        )
+        .method(
+            tonic_build::manual::Method::builder()
+                .name("health")
+                .route_name("Health")
+                .input_type("crate::server::grpc::HealthRequest")
+                .output_type("crate::server::grpc::HealthResponse")
+                .codec_path(codec_path)
+                .build(),
+        )
+        .method(
+            tonic_build::manual::Method::builder()
+                .name("node_info")
+                .route_name("NodeInfo")
+                .input_type("crate::server::grpc::NodeInfoRequest")
+                .output_type("crate::server::grpc::NodeInfoResponse")
+                .codec_path(codec_path)
+                .build(),
+        )
+        .build();
+
+    tonic_build::manual::Builder::new()
+        .out_dir(out_dir)
+        .compile(&[service]);
+}
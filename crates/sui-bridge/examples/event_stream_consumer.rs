@@ -0,0 +1,55 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! A minimal consumer of the bridge node event stream. Prints every event it reads.
+//!
+//! cargo run -p sui-bridge --example event_stream_consumer -- /path/to/events.sock
+//! cargo run -p sui-bridge --example event_stream_consumer -- 127.0.0.1:9300
+
+use std::io::{BufRead, BufReader, Read};
+use std::net::TcpStream;
+use std::os::unix::net::UnixStream;
+use sui_bridge::api_types::StreamEvent;
+
+fn main() -> std::io::Result<()> {
+    let address = std::env::args()
+        .nth(1)
+        .expect("Usage: event_stream_consumer <socket path | 127.0.0.1:port>");
+    // Addresses with a port are on the loopback interface, anything else is a socket path
+    let stream: Box<dyn Read> = if address.parse::<std::net::SocketAddr>().is_ok() {
+        Box::new(TcpStream::connect(&address)?)
+    } else {
+        Box::new(UnixStream::connect(&address)?)
+    };
+    for line in BufReader::new(stream).lines() {
+        let line = line?;
+        match serde_json::from_str::<StreamEvent>(&line) {
+            Ok(StreamEvent::ActionObserved {
+                action_digest,
+                action,
+                ..
+            }) => println!("observed {action_digest}: {action:?}"),
+            Ok(StreamEvent::ActionStatus {
+                action_digest,
+                status,
+                reason,
+                ..
+            }) => println!(
+                "{action_digest} is {status:?}{}",
+                reason.map(|r| format!(": {r}")).unwrap_or_default()
+            ),
+            Ok(StreamEvent::Heartbeat {
+                component,
+                last_active_ms,
+                timestamp_ms,
+            }) => println!(
+                "{component} was last active {}ms ago",
+                timestamp_ms.saturating_sub(last_active_ms)
+            ),
+            // Events added by newer nodes
+            Err(e) => eprintln!("Skipping unknown event {line}: {e}"),
+        }
+    }
+    println!("The node closed the event stream");
+    Ok(())
+}
@@ -0,0 +1,11 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use sui_bridge::fuzzing::{check_eth_log, eth_log_from_bytes};
+
+fuzz_target!(|data: &[u8]| {
+    check_eth_log(&eth_log_from_bytes(data));
+});
@@ -12,17 +12,19 @@ use crate::encoding::{
 use crate::error::{BridgeError, BridgeResult};
 use crate::types::ParsedTokenTransferMessage;
 use crate::types::{
-    AddTokensOnEvmAction, AssetPriceUpdateAction, BlocklistCommitteeAction, BridgeAction,
-    BridgeActionType, EmergencyAction, EthLog, EthToSuiBridgeAction, EvmContractUpgradeAction,
-    LimitUpdateAction, SuiToEthBridgeAction,
+    validate_recipient_address, AddTokensOnEvmAction, AssetPriceUpdateAction,
+    BlocklistCommitteeAction, BridgeAction, BridgeActionType, EmergencyAction, EthLog,
+    EthToSuiBridgeAction, EvmContractUpgradeAction, LimitUpdateAction, SuiToEthBridgeAction,
 };
-use ethers::types::Log;
+use ethers::types::{Log, H256};
 use ethers::{
-    abi::RawLog,
+    abi::{Abi, RawLog},
     contract::{abigen, EthLogDecode},
     types::Address as EthAddress,
 };
+use once_cell::sync::Lazy;
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use sui_types::base_types::SuiAddress;
 use sui_types::bridge::BridgeChainId;
 
@@ -43,24 +45,44 @@ macro_rules! gen_eth_events {
             )*
         }
 
+        /// The decoder of every event in the ABIs of the bridge contracts, by topic. The
+        /// Eth syncer only queries logs with these topics, so that the watched events and
+        /// the decodable ones are the same.
+        static ETH_BRIDGE_EVENT_DECODERS: Lazy<BTreeMap<H256, EthBridgeEventDecoder>> =
+            Lazy::new(|| {
+                let mut decoders = BTreeMap::new();
+                $(
+                    let abi: Abi = serde_json::from_str(include_str!(concat!(
+                        env!("CARGO_MANIFEST_DIR"),
+                        "/",
+                        $abi_path
+                    )))
+                    .expect("ABI json should be valid");
+                    let decoder: EthBridgeEventDecoder = |raw_log| {
+                        $contract_event::decode_log(raw_log)
+                            .ok()
+                            .map(EthBridgeEvent::$contract_event)
+                    };
+                    for event in abi.events() {
+                        // Events of several contracts, e.g. `Initialized`, are decoded
+                        // as the event of the first one
+                        decoders.entry(event.signature()).or_insert(decoder);
+                    }
+                )*
+                decoders
+            });
+
         impl EthBridgeEvent {
             pub fn try_from_eth_log(log: &EthLog) -> Option<EthBridgeEvent> {
                 Self::try_from_log(&log.log)
             }
 
             pub fn try_from_log(log: &Log) -> Option<EthBridgeEvent> {
-                let raw_log = RawLog {
+                let decoder = ETH_BRIDGE_EVENT_DECODERS.get(log.topics.first()?)?;
+                decoder(&RawLog {
                     topics: log.topics.clone(),
                     data: log.data.to_vec(),
-                };
-
-                $(
-                    if let Ok(decoded) = $contract_event::decode_log(&raw_log) {
-                        return Some(EthBridgeEvent::$contract_event(decoded));
-                    }
-                )*
-
-                None
+                })
             }
         }
     };
@@ -77,6 +99,8 @@ macro_rules! gen_eth_events {
     };
 }
 
+type EthBridgeEventDecoder = fn(&RawLog) -> Option<EthBridgeEvent>;
+
 #[rustfmt::skip]
 gen_eth_events!(
     EthSuiBridge, EthSuiBridgeEvents, "abi/sui_bridge.json",
@@ -94,7 +118,73 @@ abigen!(
     event_derives(serde::Deserialize, serde::Serialize)
 );
 
+/// Topics of the events of the bridge contracts, i.e. of the logs that the Eth syncer
+/// queries. Each of them can be decoded into an `EthBridgeEvent`.
+pub fn eth_bridge_event_topics() -> Vec<H256> {
+    ETH_BRIDGE_EVENT_DECODERS.keys().copied().collect()
+}
+
 impl EthBridgeEvent {
+    /// Parses a raw `EthLog` the same way the Eth watcher does. Returns `None` if
+    /// the log is not a recognized bridge event, otherwise the decoded event together
+    /// with the result of converting it into a `BridgeAction`.
+    pub fn parse_eth_log(
+        log: &EthLog,
+    ) -> Option<(EthBridgeEvent, BridgeResult<Option<BridgeAction>>)> {
+        let bridge_event = Self::try_from_eth_log(log)?;
+        let action = bridge_event
+            .clone()
+            .try_into_bridge_action(log.tx_hash, log.log_index_in_tx);
+        Some((bridge_event, action))
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            EthBridgeEvent::EthSuiBridgeEvents(event) => match event {
+                EthSuiBridgeEvents::TokensDepositedFilter(_) => "EthSuiBridge::TokensDeposited",
+                EthSuiBridgeEvents::TokensClaimedFilter(_) => "EthSuiBridge::TokensClaimed",
+                EthSuiBridgeEvents::PausedFilter(_) => "EthSuiBridge::Paused",
+                EthSuiBridgeEvents::UnpausedFilter(_) => "EthSuiBridge::Unpaused",
+                EthSuiBridgeEvents::UpgradedFilter(_) => "EthSuiBridge::Upgraded",
+                EthSuiBridgeEvents::InitializedFilter(_) => "EthSuiBridge::Initialized",
+            },
+            EthBridgeEvent::EthBridgeCommitteeEvents(event) => match event {
+                EthBridgeCommitteeEvents::BlocklistUpdatedFilter(_) => {
+                    "EthBridgeCommittee::BlocklistUpdated"
+                }
+                EthBridgeCommitteeEvents::InitializedFilter(_) => "EthBridgeCommittee::Initialized",
+                EthBridgeCommitteeEvents::UpgradedFilter(_) => "EthBridgeCommittee::Upgraded",
+            },
+            EthBridgeEvent::EthBridgeLimiterEvents(event) => match event {
+                EthBridgeLimiterEvents::LimitUpdatedFilter(_) => "EthBridgeLimiter::LimitUpdated",
+                EthBridgeLimiterEvents::InitializedFilter(_) => "EthBridgeLimiter::Initialized",
+                EthBridgeLimiterEvents::UpgradedFilter(_) => "EthBridgeLimiter::Upgraded",
+                EthBridgeLimiterEvents::HourlyTransferAmountUpdatedFilter(_) => {
+                    "EthBridgeLimiter::HourlyTransferAmountUpdated"
+                }
+                EthBridgeLimiterEvents::OwnershipTransferredFilter(_) => {
+                    "EthBridgeLimiter::OwnershipTransferred"
+                }
+            },
+            EthBridgeEvent::EthBridgeConfigEvents(event) => match event {
+                EthBridgeConfigEvents::InitializedFilter(_) => "EthBridgeConfig::Initialized",
+                EthBridgeConfigEvents::UpgradedFilter(_) => "EthBridgeConfig::Upgraded",
+                EthBridgeConfigEvents::TokenAddedFilter(_) => "EthBridgeConfig::TokenAdded",
+                EthBridgeConfigEvents::TokenPriceUpdatedFilter(_) => {
+                    "EthBridgeConfig::TokenPriceUpdated"
+                }
+            },
+            EthBridgeEvent::EthCommitteeUpgradeableContractEvents(event) => match event {
+                EthCommitteeUpgradeableContractEvents::InitializedFilter(_) => {
+                    "EthCommitteeUpgradeableContract::Initialized"
+                }
+                EthCommitteeUpgradeableContractEvents::UpgradedFilter(_) => {
+                    "EthCommitteeUpgradeableContract::Upgraded"
+                }
+            },
+        }
+    }
+
     pub fn try_into_bridge_action(
         self,
         eth_tx_hash: ethers::types::H256,
@@ -114,6 +204,10 @@ impl EthBridgeEvent {
                                 }
                                 bridge_event
                             }
+                            // The deposit can never be claimed on Sui, signing it would not help
+                            Err(e @ BridgeError::InvalidRecipientAddress(_)) => {
+                                return Err(e);
+                            }
                             // This only happens when solidity code does not align with rust code.
                             // When this happens in production, there is a risk of stuck bridge transfers.
                             // We log error here.
@@ -178,11 +272,26 @@ pub struct EthToSuiTokenBridgeV1 {
 impl TryFrom<&TokensDepositedFilter> for EthToSuiTokenBridgeV1 {
     type Error = BridgeError;
     fn try_from(event: &TokensDepositedFilter) -> BridgeResult<Self> {
+        let chain_id = |id: u8| {
+            BridgeChainId::try_from(id).map_err(|_e| {
+                BridgeError::Generic(format!(
+                    "Failed to convert TokensDepositedFilter to EthToSuiTokenBridgeV1. Failed to convert chain {} to BridgeChainId",
+                    id
+                ))
+            })
+        };
+        let sui_chain_id = chain_id(event.destination_chain_id)?;
+        validate_recipient_address(sui_chain_id, event.recipient_address.as_ref())?;
         Ok(Self {
             nonce: event.nonce,
-            sui_chain_id: BridgeChainId::try_from(event.destination_chain_id)?,
-            eth_chain_id: BridgeChainId::try_from(event.source_chain_id)?,
-            sui_address: SuiAddress::from_bytes(event.recipient_address.as_ref())?,
+            sui_chain_id,
+            eth_chain_id: chain_id(event.source_chain_id)?,
+            sui_address: SuiAddress::from_bytes(event.recipient_address.as_ref()).map_err(|e| {
+                BridgeError::Generic(format!(
+                    "Failed to convert TokensDepositedFilter to EthToSuiTokenBridgeV1. Failed to convert recipient_address to SuiAddress: {:?}",
+                    e
+                ))
+            })?,
             eth_address: event.sender_address,
             token_id: event.token_id,
             sui_adjusted_amount: event.sui_adjusted_amount,
@@ -463,6 +572,7 @@ mod tests {
             block_number: 33,
             tx_hash,
             log_index_in_tx: 1,
+            block_timestamp_ms: None,
             log: Log {
                 address: EthAddress::repeat_byte(1),
                 topics: vec![
@@ -548,4 +658,112 @@ mod tests {
             e => panic!("Unexpected error: {:?}", e),
         }
     }
+
+    #[test]
+    fn test_recipient_address_conversion_for_eth_event() {
+        let deposit = |recipient_address: Vec<u8>| {
+            EthBridgeEvent::EthSuiBridgeEvents(EthSuiBridgeEvents::TokensDepositedFilter(
+                TokensDepositedFilter {
+                    source_chain_id: BridgeChainId::EthSepolia as u8,
+                    nonce: 0,
+                    destination_chain_id: BridgeChainId::SuiTestnet as u8,
+                    token_id: 2,
+                    sui_adjusted_amount: 1,
+                    sender_address: EthAddress::random(),
+                    recipient_address: ethers::types::Bytes::from(recipient_address),
+                },
+            ))
+            .try_into_bridge_action(TxHash::random(), 0)
+        };
+        // The zero address can be claimed on Sui
+        let action = deposit(vec![0; 32]).unwrap().unwrap();
+        match action {
+            BridgeAction::EthToSuiBridgeAction(a) => {
+                assert_eq!(a.eth_bridge_event.sui_address, SuiAddress::ZERO)
+            }
+            a => panic!("Unexpected action: {:?}", a),
+        }
+        for recipient_address in [vec![1; 31], vec![1; 33], vec![1; 20], vec![]] {
+            match deposit(recipient_address).unwrap_err() {
+                BridgeError::InvalidRecipientAddress(_) => {}
+                e => panic!("Unexpected error: {:?}", e),
+            }
+        }
+    }
+
+    // ABIs of contracts whose events the bridge doesn't watch
+    const UNWATCHED_ABIS: [&str; 2] = ["bridge_vault.json", "erc20.json"];
+
+    fn zero_token(kind: &ethers::abi::ParamType) -> ethers::abi::Token {
+        use ethers::abi::{ParamType, Token};
+        match kind {
+            ParamType::Address => Token::Address(EthAddress::zero()),
+            ParamType::Bytes => Token::Bytes(vec![]),
+            ParamType::Int(_) => Token::Int(0.into()),
+            ParamType::Uint(_) => Token::Uint(0.into()),
+            ParamType::Bool => Token::Bool(false),
+            ParamType::String => Token::String(String::new()),
+            ParamType::Array(_) => Token::Array(vec![]),
+            ParamType::FixedBytes(len) => Token::FixedBytes(vec![0; *len]),
+            ParamType::FixedArray(kind, len) => Token::FixedArray(vec![zero_token(kind); *len]),
+            ParamType::Tuple(kinds) => Token::Tuple(kinds.iter().map(zero_token).collect()),
+        }
+    }
+
+    #[test]
+    fn test_every_abi_event_is_watched_and_decoded() {
+        let mut abi_topics = std::collections::BTreeSet::new();
+        let abi_dir = std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("abi");
+        for entry in std::fs::read_dir(abi_dir).unwrap() {
+            let path = entry.unwrap().path();
+            let file_name = path.file_name().unwrap().to_str().unwrap().to_string();
+            if path.is_dir() || UNWATCHED_ABIS.contains(&file_name.as_str()) {
+                continue;
+            }
+            let abi: Abi = serde_json::from_str(&std::fs::read_to_string(&path).unwrap()).unwrap();
+            for event in abi.events() {
+                abi_topics.insert(event.signature());
+                // A log of the event with all arguments zeroed
+                let mut topics = vec![event.signature()];
+                let mut data = vec![];
+                for input in &event.inputs {
+                    if input.indexed {
+                        topics.push(H256::zero());
+                    } else {
+                        data.push(zero_token(&input.kind));
+                    }
+                }
+                let log = Log {
+                    topics,
+                    data: ethers::abi::encode(&data).into(),
+                    ..Default::default()
+                };
+                let decoded = EthBridgeEvent::try_from_log(&log)
+                    .unwrap_or_else(|| panic!("{} event {} has no decoder", file_name, event.name));
+                assert!(
+                    decoded.name().ends_with(&format!("::{}", event.name)),
+                    "{} event {} is decoded as {}",
+                    file_name,
+                    event.name,
+                    decoded.name()
+                );
+            }
+        }
+        // The syncer watches exactly the events of the ABIs
+        assert_eq!(
+            eth_bridge_event_topics(),
+            abi_topics.into_iter().collect::<Vec<_>>()
+        );
+        // Logs of other events are neither watched nor decoded
+        let transfer = H256::from(ethers::utils::keccak256(
+            "Transfer(address,address,uint256)",
+        ));
+        assert!(!eth_bridge_event_topics().contains(&transfer));
+        let log = Log {
+            topics: vec![transfer, H256::zero(), H256::zero()],
+            data: ethers::abi::encode(&[ethers::abi::Token::Uint(0.into())]).into(),
+            ..Default::default()
+        };
+        assert!(EthBridgeEvent::try_from_log(&log).is_none());
+    }
 }
@@ -4,13 +4,17 @@
 //! BridgeActionExecutor receives BridgeActions (from BridgeOrchestrator),
 //! collects bridge authority signatures and submit signatures on chain.
 
-use crate::retry_with_max_elapsed_time;
+use crate::executor_state::{ExecutorStage, SNAPSHOT_QUEUED_ACTIONS};
+use crate::inc_err_counter;
+use crate::notional::{route_label, BridgeRoute};
+use crate::route_pause::RoutePauses;
 use crate::types::IsBridgePaused;
 use arc_swap::ArcSwap;
 use mysten_metrics::spawn_logged_monitored_task;
 use shared_crypto::intent::{Intent, IntentMessage};
 use sui_json_rpc_types::{
-    SuiExecutionStatus, SuiTransactionBlockEffectsAPI, SuiTransactionBlockResponse,
+    SuiExecutionStatus, SuiTransactionBlockEffects, SuiTransactionBlockEffectsAPI,
+    SuiTransactionBlockResponse,
 };
 use sui_types::transaction::ObjectArg;
 use sui_types::TypeTag;
@@ -29,17 +33,29 @@ use crate::events::{
 };
 use crate::metrics::BridgeMetrics;
 use crate::{
+    alerts::{AlertCondition, AlertSink},
+    api_types::StreamActionStatus,
+    certified_actions::CertifiedActions,
     client::bridge_authority_aggregator::BridgeAuthorityAggregator,
     error::BridgeError,
-    storage::BridgeOrchestratorTables,
+    event_stream::EventStream,
+    move_errors::BridgeMoveError,
+    shadow::ShadowComparator,
+    signing_queue::{SigningQueue, DEFAULT_SIGNING_WINDOW},
+    storage::{BridgeOrchestratorTables, DroppedActionReason, QuarantinedAction},
     sui_client::{SuiClient, SuiClientInner},
     sui_transaction_builder::build_sui_transaction,
-    types::{BridgeAction, BridgeActionStatus, VerifiedCertifiedBridgeAction},
+    tx_journal::{TxJournal, TxJournalEntry},
+    tx_submission::SuiTxSubmitter,
+    types::{
+        BridgeAction, BridgeActionDigest, BridgeActionStatus, BridgeActionType,
+        VerifiedCertifiedBridgeAction,
+    },
 };
-use std::collections::HashMap;
-use std::sync::Arc;
-use tokio::sync::Semaphore;
-use tokio::time::Duration;
+use std::collections::{HashMap, HashSet};
+use std::sync::{Arc, Mutex};
+use std::time::{Instant, SystemTime, UNIX_EPOCH};
+use tokio::sync::{OwnedSemaphorePermit, Semaphore};
 use tracing::{error, info, instrument, warn, Instrument};
 
 pub const CHANNEL_SIZE: usize = 1000;
@@ -49,18 +65,104 @@ pub const SIGNING_CONCURRENCY: usize = 10;
 // 0.1s, 0.2s, 0.4s, 0.8s, 1.6s, 3.2s, 6.4s, 12.8s, 25.6s, 51.2s, 102.4s, 204.8s, 409.6s, 819.2s, 1638.4s
 pub const MAX_SIGNING_ATTEMPTS: u64 = 16;
 pub const MAX_EXECUTION_ATTEMPTS: u64 = 16;
+// Actions are quarantined after failing permanently this many times
+pub const DEFAULT_QUARANTINE_THRESHOLD: u64 = 3;
 
 async fn delay(attempt_times: u64) {
     let delay_ms = 100 * (2 ^ attempt_times);
     tokio::time::sleep(tokio::time::Duration::from_millis(delay_ms)).await;
 }
 
+/// An action to sign, with its number of attempts and when it was sent to the signing queue.
 #[derive(Debug)]
-pub struct BridgeActionExecutionWrapper(pub BridgeAction, pub u64);
+pub struct BridgeActionExecutionWrapper(pub BridgeAction, pub u64, pub Instant);
 
 #[derive(Debug)]
 pub struct CertifiedBridgeActionExecutionWrapper(pub VerifiedCertifiedBridgeAction, pub u64);
 
+/// Counts the permanent failures of actions, i.e. failures that happen again however
+/// often the action is retried, and quarantines the actions that fail permanently
+/// `threshold` times. Quarantined actions are not retried until an operator requeues them.
+struct ActionQuarantine {
+    threshold: u64,
+    failures: Mutex<HashMap<BridgeActionDigest, u64>>,
+    store: Arc<BridgeOrchestratorTables>,
+    alerts: Option<Arc<AlertSink>>,
+    event_stream: Option<Arc<EventStream>>,
+    metrics: Arc<BridgeMetrics>,
+}
+
+impl ActionQuarantine {
+    fn new(
+        threshold: u64,
+        store: Arc<BridgeOrchestratorTables>,
+        alerts: Option<Arc<AlertSink>>,
+        event_stream: Option<Arc<EventStream>>,
+        metrics: Arc<BridgeMetrics>,
+    ) -> Self {
+        Self {
+            threshold,
+            failures: Mutex::new(HashMap::new()),
+            store,
+            alerts,
+            event_stream,
+            metrics,
+        }
+    }
+
+    // Records a permanent failure of `action`. Returns true if the action is quarantined
+    // and must not be retried.
+    fn record_permanent_failure(&self, action: &BridgeAction, reason: String) -> bool {
+        let digest = action.digest();
+        let failures = {
+            let mut failures = self.failures.lock().unwrap();
+            let count = failures.entry(digest).or_default();
+            *count += 1;
+            *count
+        };
+        if failures < self.threshold {
+            warn!("Action failed permanently {failures} times: {reason}");
+            return false;
+        }
+        error!(
+            "Manual intervention is required. Quarantining action after {failures} permanent failures: {reason}, action: {:?}",
+            action
+        );
+        self.clear(&digest);
+        self.metrics.action_executor_quarantined_actions.inc();
+        if let Some(alerts) = &self.alerts {
+            alerts.raise(
+                AlertCondition::ExecutionFailures,
+                format!(
+                    "Action {:?} was quarantined after {failures} permanent failures: {reason}",
+                    action.key()
+                ),
+            );
+        }
+        if let Some(event_stream) = &self.event_stream {
+            event_stream.action_status(action, StreamActionStatus::Failed, Some(reason.clone()));
+        }
+        self.store
+            .quarantine_action(&QuarantinedAction {
+                action: action.clone(),
+                reason,
+                failures,
+                quarantined_at_ms: SystemTime::now()
+                    .duration_since(UNIX_EPOCH)
+                    .expect("Time went backwards")
+                    .as_millis() as u64,
+            })
+            .unwrap_or_else(|e| {
+                panic!("Write to DB should not fail: {:?}", e);
+            });
+        true
+    }
+
+    fn clear(&self, digest: &BridgeActionDigest) {
+        self.failures.lock().unwrap().remove(digest);
+    }
+}
+
 pub trait BridgeActionExecutorTrait {
     fn run(
         self,
@@ -80,6 +182,23 @@ pub struct BridgeActionExecutor<C> {
     bridge_object_arg: ObjectArg,
     sui_token_type_tags: Arc<ArcSwap<HashMap<u8, TypeTag>>>,
     bridge_pause_rx: tokio::sync::watch::Receiver<IsBridgePaused>,
+    tx_journal: Option<Arc<TxJournal>>,
+    channel_size: usize,
+    signing_window: usize,
+    execute_action_types: Arc<HashSet<BridgeActionType>>,
+    // Held while the gas coin is used, so that other transactions using it, e.g. gas
+    // top-ups, do not equivocate it
+    gas_lock: Arc<tokio::sync::Mutex<()>>,
+    quarantine_threshold: u64,
+    tx_submitter: Arc<SuiTxSubmitter<C>>,
+    // Set in shadow mode, transactions are compared with the chain instead of submitted
+    shadow: Option<Arc<ShadowComparator<C>>>,
+    alerts: Option<Arc<AlertSink>>,
+    event_stream: Option<Arc<EventStream>>,
+    certified_actions: Option<Arc<CertifiedActions>>,
+    route_pauses: Arc<RoutePauses>,
+    // Skip the signature aggregation of actions on paused routes too
+    skip_signing_paused_routes: bool,
     metrics: Arc<BridgeMetrics>,
 }
 
@@ -111,11 +230,13 @@ where
         gas_object_id: ObjectID,
         sui_token_type_tags: Arc<ArcSwap<HashMap<u8, TypeTag>>>,
         bridge_pause_rx: tokio::sync::watch::Receiver<IsBridgePaused>,
+        tx_journal: Option<Arc<TxJournal>>,
         metrics: Arc<BridgeMetrics>,
     ) -> Self {
         let bridge_object_arg = sui_client
             .get_mutable_bridge_object_arg_must_succeed()
             .await;
+        let tx_submitter = Arc::new(SuiTxSubmitter::single(sui_client.clone(), metrics.clone()));
         Self {
             sui_client,
             bridge_auth_agg,
@@ -126,10 +247,102 @@ where
             bridge_object_arg,
             sui_token_type_tags,
             bridge_pause_rx,
+            tx_journal,
+            channel_size: CHANNEL_SIZE,
+            signing_window: DEFAULT_SIGNING_WINDOW,
+            execute_action_types: Arc::new(HashSet::from(BridgeActionType::ALL)),
+            gas_lock: Arc::new(tokio::sync::Mutex::new(())),
+            quarantine_threshold: DEFAULT_QUARANTINE_THRESHOLD,
+            tx_submitter,
+            shadow: None,
+            alerts: None,
+            event_stream: None,
+            certified_actions: None,
+            route_pauses: Arc::new(RoutePauses::new(metrics.clone())),
+            skip_signing_paused_routes: false,
             metrics,
         }
     }
 
+    /// Overrides the capacity of the signing and execution queues.
+    pub fn with_channel_size(mut self, channel_size: usize) -> Self {
+        self.channel_size = channel_size;
+        self
+    }
+
+    /// Overrides how many actions waiting for signature aggregation are held in memory.
+    /// Beyond that they are read from the pending actions when they are due.
+    pub fn with_signing_window(mut self, signing_window: usize) -> Self {
+        self.signing_window = signing_window;
+        self
+    }
+
+    /// Restricts the action types the executor aggregates signatures for and executes.
+    /// Actions of other types are dropped. All types are allowed by default.
+    pub fn with_execute_action_types(
+        mut self,
+        execute_action_types: HashSet<BridgeActionType>,
+    ) -> Self {
+        self.execute_action_types = Arc::new(execute_action_types);
+        self
+    }
+
+    /// Shares the lock held while the gas coin is used with other users of the gas coin.
+    pub fn with_gas_lock(mut self, gas_lock: Arc<tokio::sync::Mutex<()>>) -> Self {
+        self.gas_lock = gas_lock;
+        self
+    }
+
+    /// Overrides how many permanent failures of an action, e.g. the committee rejecting
+    /// it or a Move abort that does not depend on the bridge state, quarantine it.
+    pub fn with_quarantine_threshold(mut self, quarantine_threshold: u64) -> Self {
+        self.quarantine_threshold = quarantine_threshold.max(1);
+        self
+    }
+
+    /// Overrides how signed transactions are submitted. By default they are submitted
+    /// through `sui_client` only.
+    pub fn with_tx_submitter(mut self, tx_submitter: SuiTxSubmitter<C>) -> Self {
+        self.tx_submitter = Arc::new(tx_submitter);
+        self
+    }
+
+    /// Runs the executor in shadow mode: every action goes through signature aggregation
+    /// and is built into a transaction, even if it was already processed on chain, but
+    /// the transaction is handed to `shadow` instead of being submitted.
+    pub fn with_shadow_comparator(mut self, shadow: ShadowComparator<C>) -> Self {
+        self.shadow = Some(Arc::new(shadow));
+        self
+    }
+
+    /// Raises alerts when actions are quarantined or the gas coin runs low.
+    pub fn with_alert_sink(mut self, alerts: Arc<AlertSink>) -> Self {
+        self.alerts = Some(alerts);
+        self
+    }
+
+    /// Streams the status transitions of actions and heartbeats of the signing and
+    /// execution loops to `event_stream`.
+    pub fn with_event_stream(mut self, event_stream: Arc<EventStream>) -> Self {
+        self.event_stream = Some(event_stream);
+        self
+    }
+
+    /// Publishes the actions to `certified_actions` as soon as they are certified.
+    pub fn with_certified_actions(mut self, certified_actions: Arc<CertifiedActions>) -> Self {
+        self.certified_actions = Some(certified_actions);
+        self
+    }
+
+    /// Defers the execution of token transfers on routes paused on chain until they are
+    /// no longer paused. Their signatures are still aggregated unless `skip_signing` is
+    /// set, in which case they are skipped like actions while the bridge is paused.
+    pub fn with_route_pauses(mut self, route_pauses: Arc<RoutePauses>, skip_signing: bool) -> Self {
+        self.route_pauses = route_pauses;
+        self.skip_signing_paused_routes = skip_signing;
+        self
+    }
+
     fn run_inner(
         self,
     ) -> (
@@ -140,7 +353,7 @@ where
         let key = self.key;
 
         let (sender, receiver) = mysten_metrics::metered_channel::channel(
-            CHANNEL_SIZE,
+            self.channel_size,
             &mysten_metrics::get_metrics()
                 .unwrap()
                 .channel_inflight
@@ -148,12 +361,25 @@ where
         );
 
         let (execution_tx, execution_rx) = mysten_metrics::metered_channel::channel(
-            CHANNEL_SIZE,
+            self.channel_size,
             &mysten_metrics::get_metrics()
                 .unwrap()
                 .channel_inflight
                 .with_label_values(&["executor_execution_queue"]),
         );
+        for channel in ["executor_signing_queue", "executor_execution_queue"] {
+            self.metrics
+                .channel_capacity
+                .with_label_values(&[channel])
+                .set(self.channel_size as i64);
+        }
+        let quarantine = Arc::new(ActionQuarantine::new(
+            self.quarantine_threshold,
+            self.store.clone(),
+            self.alerts.clone(),
+            self.event_stream.clone(),
+            self.metrics.clone(),
+        ));
         let execution_tx_clone = execution_tx.clone();
         let sender_clone = sender.clone();
         let store_clone = self.store.clone();
@@ -168,12 +394,25 @@ where
                 sender_clone,
                 receiver,
                 execution_tx_clone,
+                self.execute_action_types,
+                self.signing_window,
+                self.shadow.is_some(),
+                self.skip_signing_paused_routes
+                    .then(|| self.route_pauses.clone()),
+                quarantine.clone(),
+                self.event_stream.clone(),
+                self.certified_actions,
                 metrics,
             )
         ));
 
+        tasks.push(spawn_logged_monitored_task!(
+            Self::run_bridge_pause_watcher(self.bridge_pause_rx.clone(), self.metrics.clone(),)
+        ));
+
         let metrics = self.metrics.clone();
         let execution_tx_clone = execution_tx.clone();
+        let sender_clone = sender.clone();
         tasks.push(spawn_logged_monitored_task!(
             Self::run_onchain_execution_loop(
                 self.sui_client.clone(),
@@ -181,11 +420,20 @@ where
                 self.sui_address,
                 self.gas_object_id,
                 self.store.clone(),
+                sender_clone,
                 execution_tx_clone,
                 execution_rx,
                 self.bridge_object_arg,
                 self.sui_token_type_tags,
                 self.bridge_pause_rx,
+                self.route_pauses,
+                self.tx_journal,
+                self.gas_lock,
+                self.tx_submitter,
+                self.shadow,
+                self.alerts,
+                self.event_stream,
+                quarantine,
                 metrics,
             )
         ));
@@ -203,38 +451,89 @@ where
         execution_queue_sender: mysten_metrics::metered_channel::Sender<
             CertifiedBridgeActionExecutionWrapper,
         >,
+        execute_action_types: Arc<HashSet<BridgeActionType>>,
+        signing_window: usize,
+        shadow: bool,
+        signing_route_pauses: Option<Arc<RoutePauses>>,
+        quarantine: Arc<ActionQuarantine>,
+        event_stream: Option<Arc<EventStream>>,
+        certified_actions: Option<Arc<CertifiedActions>>,
         metrics: Arc<BridgeMetrics>,
     ) {
         info!("Starting run_signature_aggregation_loop");
         let semaphore = Arc::new(Semaphore::new(SIGNING_CONCURRENCY));
-        while let Some(action) = signing_queue_receiver.recv().await {
-            Self::handle_signing_task(
-                &semaphore,
-                &auth_agg,
-                &signing_queue_sender,
-                &execution_queue_sender,
-                &sui_client,
-                &store,
-                action,
-                &metrics,
-            )
-            .await;
+        // Actions are moved from the channel to `queue` right away, and taken from `queue`
+        // once they can be signed, so that a backlog does not block the orchestrator and
+        // is mostly held on disk
+        let mut queue = SigningQueue::new(signing_window, store.clone(), metrics.clone());
+        loop {
+            tokio::select! {
+                action = signing_queue_receiver.recv() => {
+                    let Some(action) = action else {
+                        break;
+                    };
+                    queue.push(action);
+                    if queue.len() <= SNAPSHOT_QUEUED_ACTIONS {
+                        metrics.executor_state.set_queued(queue.peek(SNAPSHOT_QUEUED_ACTIONS));
+                    }
+                }
+                permit = semaphore.clone().acquire_owned(), if !queue.is_empty() => {
+                    let permit = permit.expect("semaphore should not be closed");
+                    let Some(action) = queue.pop() else {
+                        continue;
+                    };
+                    metrics.executor_state.set_queued(queue.peek(SNAPSHOT_QUEUED_ACTIONS));
+                    Self::handle_signing_task(
+                        permit,
+                        &auth_agg,
+                        &signing_queue_sender,
+                        &execution_queue_sender,
+                        &sui_client,
+                        &store,
+                        &execute_action_types,
+                        shadow,
+                        signing_route_pauses.as_deref(),
+                        action,
+                        &quarantine,
+                        &event_stream,
+                        &certified_actions,
+                        &metrics,
+                    )
+                    .await;
+                }
+            }
+        }
+    }
+
+    // Publishes the pause flag in the executor state, also while an execution is stuck
+    async fn run_bridge_pause_watcher(
+        mut bridge_pause_rx: tokio::sync::watch::Receiver<IsBridgePaused>,
+        metrics: Arc<BridgeMetrics>,
+    ) {
+        loop {
+            metrics
+                .executor_state
+                .set_bridge_paused(*bridge_pause_rx.borrow_and_update());
+            if bridge_pause_rx.changed().await.is_err() {
+                break;
+            }
         }
     }
 
     async fn should_proceed_signing(sui_client: &Arc<SuiClient<C>>) -> bool {
-        let Ok(Ok(is_paused)) =
-            retry_with_max_elapsed_time!(sui_client.is_bridge_paused(), Duration::from_secs(600))
-        else {
-            error!("Failed to get bridge status after retry");
-            return false;
-        };
-        !is_paused
+        // Transient errors are retried by `SuiClient`
+        match sui_client.is_bridge_paused().await {
+            Ok(is_paused) => !is_paused,
+            Err(e) => {
+                error!("Failed to get bridge status: {:?}", e);
+                false
+            }
+        }
     }
 
     #[instrument(level = "error", skip_all, fields(action_key=?action.0.key(), attempt_times=?action.1))]
     async fn handle_signing_task(
-        semaphore: &Arc<Semaphore>,
+        permit: OwnedSemaphorePermit,
         auth_agg: &Arc<ArcSwap<BridgeAuthorityAggregator>>,
         signing_queue_sender: &mysten_metrics::metered_channel::Sender<
             BridgeActionExecutionWrapper,
@@ -244,13 +543,45 @@ where
         >,
         sui_client: &Arc<SuiClient<C>>,
         store: &Arc<BridgeOrchestratorTables>,
+        execute_action_types: &HashSet<BridgeActionType>,
+        shadow: bool,
+        signing_route_pauses: Option<&RoutePauses>,
         action: BridgeActionExecutionWrapper,
+        quarantine: &Arc<ActionQuarantine>,
+        event_stream: &Option<Arc<EventStream>>,
+        certified_actions: &Option<Arc<CertifiedActions>>,
         metrics: &Arc<BridgeMetrics>,
     ) {
         metrics.action_executor_signing_queue_received_actions.inc();
+        if let Some(event_stream) = event_stream {
+            event_stream.beat("executor_signing");
+        }
+        let now = Instant::now();
+        metrics
+            .slo
+            .record_executor_queue_wait(now.saturating_duration_since(action.2), now);
         let action_key = action.0.key();
         info!("Received action for signing: {:?}", action.0);
 
+        let action_type = action.0.action_type();
+        if !execute_action_types.contains(&action_type) {
+            warn!(
+                "Action type {:?} is not allowed, dropping action: {:?}",
+                action_type, action_key
+            );
+            metrics.action_executor_disallowed_actions.inc();
+            store
+                .insert_dropped_actions(
+                    &[action.0.clone()],
+                    DroppedActionReason::ActionTypeNotAllowed,
+                )
+                .and_then(|_| store.remove_pending_actions(&[action.0.digest()]))
+                .unwrap_or_else(|e| {
+                    panic!("Write to DB should not fail: {:?}", e);
+                });
+            return;
+        }
+
         // TODO: this is a temporary fix to avoid signing when the bridge is paused.
         // but the way is implemented is not ideal:
         // 1. it should check the direction
@@ -261,23 +592,37 @@ where
             warn!("skipping signing task: {:?}", action_key);
             return;
         }
+        if let Some(route) =
+            signing_route_pauses.and_then(|route_pauses| route_pauses.paused_route(&action.0))
+        {
+            metrics.action_executor_signing_queue_skipped_actions.inc();
+            warn!(
+                "Route {:?} is paused, skipping signing task: {:?}",
+                route, action_key
+            );
+            return;
+        }
 
         let auth_agg_clone = auth_agg.clone();
         let signing_queue_sender_clone = signing_queue_sender.clone();
         let execution_queue_sender_clone = execution_queue_sender.clone();
         let sui_client_clone = sui_client.clone();
         let store_clone = store.clone();
+        let quarantine_clone = quarantine.clone();
         let metrics_clone = metrics.clone();
-        let semaphore_clone = semaphore.clone();
         spawn_logged_monitored_task!(
             Self::request_signatures(
-                semaphore_clone,
+                permit,
                 sui_client_clone,
                 auth_agg_clone,
                 action,
                 store_clone,
                 signing_queue_sender_clone,
                 execution_queue_sender_clone,
+                shadow,
+                quarantine_clone,
+                event_stream.clone(),
+                certified_actions.clone(),
                 metrics_clone,
             )
             .instrument(tracing::debug_span!("request_signatures", action_key=?action_key)),
@@ -308,7 +653,7 @@ where
                 );
                 metrics.action_executor_already_processed_actions.inc();
                 store
-                    .remove_pending_actions(&[action.digest()])
+                    .mark_actions_executed(&[action.clone()])
                     .unwrap_or_else(|e| {
                         panic!("Write to DB should not fail: {:?}", e);
                     });
@@ -323,7 +668,7 @@ where
     // TODO: introduce a way to properly stagger the handling
     // for various validators.
     async fn request_signatures(
-        semaphore: Arc<Semaphore>,
+        _permit: OwnedSemaphorePermit,
         sui_client: Arc<SuiClient<C>>,
         auth_agg: Arc<ArcSwap<BridgeAuthorityAggregator>>,
         action: BridgeActionExecutionWrapper,
@@ -332,14 +677,14 @@ where
         execution_queue_sender: mysten_metrics::metered_channel::Sender<
             CertifiedBridgeActionExecutionWrapper,
         >,
+        shadow: bool,
+        quarantine: Arc<ActionQuarantine>,
+        event_stream: Option<Arc<EventStream>>,
+        certified_actions: Option<Arc<CertifiedActions>>,
         metrics: Arc<BridgeMetrics>,
     ) {
-        let _permit = semaphore
-            .acquire()
-            .await
-            .expect("semaphore should not be closed");
         info!("requesting signatures");
-        let BridgeActionExecutionWrapper(action, attempt_times) = action;
+        let BridgeActionExecutionWrapper(action, attempt_times, _) = action;
 
         // Only token transfer action should reach here
         match &action {
@@ -347,23 +692,33 @@ where
             _ => unreachable!("Non token transfer action should not reach here"),
         };
 
-        // If the action is already processed, skip it.
-        if Self::handle_already_processed_token_transfer_action_maybe(
-            &sui_client,
-            &action,
-            &store,
-            &metrics,
-        )
-        .await
+        // If the action is already processed, skip it. In shadow mode every action is
+        // processed, to compare it with the chain.
+        if !shadow
+            && Self::handle_already_processed_token_transfer_action_maybe(
+                &sui_client,
+                &action,
+                &store,
+                &metrics,
+            )
+            .await
         {
             return;
         }
-        match auth_agg
+        let in_flight = metrics
+            .executor_state
+            .enter_stage(&action, ExecutorStage::Aggregating);
+        let result = auth_agg
             .load()
             .request_committee_signatures(action.clone())
-            .await
-        {
+            .await;
+        drop(in_flight);
+        match result {
             Ok(certificate) => {
+                quarantine.clear(&action.digest());
+                if let Some(certified_actions) = &certified_actions {
+                    certified_actions.publish(certificate.clone());
+                }
                 info!("Sending certificate to execution");
                 execution_queue_sender
                     .send(CertifiedBridgeActionExecutionWrapper(certificate, 0))
@@ -374,16 +729,39 @@ where
             }
             Err(e) => {
                 warn!("Failed to collect sigs for bridge action: {:?}", e);
-                metrics.err_signature_aggregation.inc();
+                inc_err_counter!(
+                    metrics,
+                    err_signature_aggregation,
+                    e.sanitized(),
+                    vec![format!("{:?}", action.digest())]
+                );
+                // Requesting the signatures again won't help, e.g. if the committee
+                // rejected the action as invalid
+                if !e.is_retryable()
+                    && quarantine.record_permanent_failure(&action, format!("{:?}", e))
+                {
+                    return;
+                }
 
                 // TODO: spawn a task for this
                 if attempt_times >= MAX_SIGNING_ATTEMPTS {
                     error!("Manual intervention is required. Failed to collect sigs for bridge action after {MAX_SIGNING_ATTEMPTS} attempts: {:?}", e);
+                    if let Some(event_stream) = &event_stream {
+                        event_stream.action_status(
+                            &action,
+                            StreamActionStatus::Failed,
+                            Some(format!("{:?}", e)),
+                        );
+                    }
                     return;
                 }
                 delay(attempt_times).await;
                 signing_queue_sender
-                    .send(BridgeActionExecutionWrapper(action, attempt_times + 1))
+                    .send(BridgeActionExecutionWrapper(
+                        action,
+                        attempt_times + 1,
+                        Instant::now(),
+                    ))
                     .await
                     .unwrap_or_else(|e| {
                         panic!("Sending to signing queue should not fail: {:?}", e);
@@ -400,6 +778,7 @@ where
         sui_address: SuiAddress,
         gas_object_id: ObjectID,
         store: Arc<BridgeOrchestratorTables>,
+        signing_queue_sender: mysten_metrics::metered_channel::Sender<BridgeActionExecutionWrapper>,
         execution_queue_sender: mysten_metrics::metered_channel::Sender<
             CertifiedBridgeActionExecutionWrapper,
         >,
@@ -409,10 +788,61 @@ where
         bridge_object_arg: ObjectArg,
         sui_token_type_tags: Arc<ArcSwap<HashMap<u8, TypeTag>>>,
         bridge_pause_rx: tokio::sync::watch::Receiver<IsBridgePaused>,
+        route_pauses: Arc<RoutePauses>,
+        tx_journal: Option<Arc<TxJournal>>,
+        gas_lock: Arc<tokio::sync::Mutex<()>>,
+        tx_submitter: Arc<SuiTxSubmitter<C>>,
+        shadow: Option<Arc<ShadowComparator<C>>>,
+        alerts: Option<Arc<AlertSink>>,
+        event_stream: Option<Arc<EventStream>>,
+        quarantine: Arc<ActionQuarantine>,
         metrics: Arc<BridgeMetrics>,
     ) {
         info!("Starting run_onchain_execution_loop");
-        while let Some(certificate_wrapper) = execution_queue_receiver.recv().await {
+        // Actions that were sent back for signature aggregation after their approval
+        // failed with invalid signatures
+        let mut reaggregated_actions = HashSet::new();
+        // Certified actions of paused routes, sent back to the execution queue once their
+        // route is no longer paused
+        let mut deferred: HashMap<BridgeRoute, Vec<CertifiedBridgeActionExecutionWrapper>> =
+            HashMap::new();
+        let mut route_pauses_rx = route_pauses.subscribe();
+        loop {
+            let certificate_wrapper = tokio::select! {
+                certificate_wrapper = execution_queue_receiver.recv() => match certificate_wrapper {
+                    Some(certificate_wrapper) => certificate_wrapper,
+                    None => break,
+                },
+                Ok(()) = route_pauses_rx.changed() => {
+                    let paused = route_pauses_rx.borrow_and_update().clone();
+                    let resumed = deferred
+                        .keys()
+                        .filter(|route| !paused.contains(route))
+                        .copied()
+                        .collect::<Vec<_>>();
+                    for route in resumed {
+                        let certificates = deferred.remove(&route).unwrap_or_default();
+                        info!(
+                            "Route {:?} is no longer paused, executing {} deferred actions",
+                            route,
+                            certificates.len()
+                        );
+                        metrics
+                            .route_deferred_actions
+                            .with_label_values(&[&route_label(route)])
+                            .set(0);
+                        let sender_clone = execution_queue_sender.clone();
+                        spawn_logged_monitored_task!(async move {
+                            for certificate in certificates {
+                                sender_clone.send(certificate).await.unwrap_or_else(|e| {
+                                    panic!("Sending to execution queue should not fail: {:?}", e);
+                                });
+                            }
+                        });
+                    }
+                    continue;
+                }
+            };
             // When bridge is paused, skip execution.
             // Skipped actions will be picked up upon node restarting
             // if bridge is unpaused.
@@ -423,16 +853,39 @@ where
                     .inc();
                 continue;
             }
+            if let Some(route) = route_pauses.paused_route(certificate_wrapper.0.data()) {
+                warn!(
+                    "Route {:?} is paused, deferring execution: {:?}",
+                    route,
+                    certificate_wrapper.0.data().key()
+                );
+                let certificates = deferred.entry(route).or_default();
+                certificates.push(certificate_wrapper);
+                metrics
+                    .route_deferred_actions
+                    .with_label_values(&[&route_label(route)])
+                    .set(certificates.len() as i64);
+                continue;
+            }
+            let _gas_guard = gas_lock.lock().await;
             Self::handle_execution_task(
                 certificate_wrapper,
                 &sui_client,
+                &tx_submitter,
+                shadow.as_ref(),
+                alerts.as_deref(),
+                event_stream.as_deref(),
                 &sui_key,
                 &sui_address,
                 gas_object_id,
                 &store,
+                &signing_queue_sender,
                 &execution_queue_sender,
                 &bridge_object_arg,
                 &sui_token_type_tags,
+                tx_journal.as_deref(),
+                &mut reaggregated_actions,
+                &quarantine,
                 &metrics,
             )
             .await;
@@ -444,38 +897,61 @@ where
     async fn handle_execution_task(
         certificate_wrapper: CertifiedBridgeActionExecutionWrapper,
         sui_client: &Arc<SuiClient<C>>,
+        tx_submitter: &SuiTxSubmitter<C>,
+        shadow: Option<&Arc<ShadowComparator<C>>>,
+        alerts: Option<&AlertSink>,
+        event_stream: Option<&EventStream>,
         sui_key: &SuiKeyPair,
         sui_address: &SuiAddress,
         gas_object_id: ObjectID,
         store: &Arc<BridgeOrchestratorTables>,
+        signing_queue_sender: &mysten_metrics::metered_channel::Sender<
+            BridgeActionExecutionWrapper,
+        >,
         execution_queue_sender: &mysten_metrics::metered_channel::Sender<
             CertifiedBridgeActionExecutionWrapper,
         >,
         bridge_object_arg: &ObjectArg,
         sui_token_type_tags: &ArcSwap<HashMap<u8, TypeTag>>,
+        tx_journal: Option<&TxJournal>,
+        reaggregated_actions: &mut HashSet<BridgeActionDigest>,
+        quarantine: &ActionQuarantine,
         metrics: &Arc<BridgeMetrics>,
     ) {
         metrics
             .action_executor_execution_queue_received_actions
             .inc();
+        if let Some(event_stream) = event_stream {
+            event_stream.beat("executor_execution");
+        }
         let CertifiedBridgeActionExecutionWrapper(certificate, attempt_times) = certificate_wrapper;
         let action = certificate.data();
         let action_key = action.key();
 
         info!("Received certified action for execution: {:?}", action);
+        let in_flight = metrics
+            .executor_state
+            .enter_stage(action, ExecutorStage::Submitting);
 
         // TODO check gas coin balance here. If gas balance too low, do not proceed.
         let (gas_coin, gas_object_ref) =
             Self::get_gas_data_assert_ownership(*sui_address, gas_object_id, sui_client).await;
         metrics.gas_coin_balance.set(gas_coin.value() as i64);
+        metrics
+            .executor_state
+            .set_gas_coin(gas_object_ref, gas_coin.value());
+        if let Some(alerts) = alerts {
+            alerts.check_gas_balance(gas_coin.value());
+        }
 
         let ceriticate_clone = certificate.clone();
 
         // Check once: if the action is already processed, skip it.
-        if Self::handle_already_processed_token_transfer_action_maybe(
-            sui_client, action, store, metrics,
-        )
-        .await
+        if shadow.is_none()
+            && Self::handle_already_processed_token_transfer_action_maybe(
+                sui_client, action, store, metrics,
+            )
+            .await
         {
             info!("Action already processed, skipping");
             return;
@@ -493,7 +969,12 @@ where
         ) {
             Ok(tx_data) => tx_data,
             Err(err) => {
-                metrics.err_build_sui_transaction.inc();
+                inc_err_counter!(
+                    metrics,
+                    err_build_sui_transaction,
+                    err.sanitized(),
+                    vec![format!("{:?}", action.digest())]
+                );
                 error!(
                     "Manual intervention is required. Failed to build transaction for action {:?}: {:?}",
                     action, err
@@ -510,6 +991,20 @@ where
         let signed_tx = Transaction::from_data(tx_data, vec![sig]);
         let tx_digest = *signed_tx.digest();
 
+        // In shadow mode the transaction is compared with the chain instead of submitted
+        if let Some(shadow) = shadow {
+            info!(?tx_digest, "Shadow: comparing transaction with the chain");
+            let shadow = shadow.clone();
+            let action = action.clone();
+            spawn_logged_monitored_task!(
+                async move {
+                    shadow.compare(action, tx_digest).await;
+                },
+                "shadow_compare"
+            );
+            return;
+        }
+
         // Check twice: If the action is already processed, skip it.
         if Self::handle_already_processed_token_transfer_action_maybe(
             sui_client, action, store, metrics,
@@ -520,31 +1015,57 @@ where
             return;
         }
 
+        if let Some(tx_journal) = tx_journal {
+            Self::write_tx_journal(tx_journal, action, &signed_tx, gas_object_ref, metrics);
+        }
+
         info!(?tx_digest, ?gas_object_ref, "Sending transaction to Sui");
-        match sui_client
-            .execute_transaction_block_with_effects(signed_tx)
-            .await
-        {
+        in_flight.advance(ExecutorStage::AwaitingEffects);
+        match tx_submitter.submit(signed_tx).await {
             Ok(resp) => {
-                Self::handle_execution_effects(tx_digest, resp, store, action, metrics).await
+                Self::handle_execution_effects(
+                    tx_digest,
+                    resp,
+                    store,
+                    &certificate,
+                    attempt_times,
+                    signing_queue_sender,
+                    execution_queue_sender,
+                    reaggregated_actions,
+                    quarantine,
+                    event_stream,
+                    metrics,
+                )
+                .await
             }
 
-            // If the transaction did not go through, retry up to a certain times.
+            // If the transaction did not go through, retry up to a certain times. Whatever
+            // the kind of the error, as the transaction is built again with the latest
+            // gas object.
             Err(err) => {
                 error!(
                     ?action_key,
                     ?tx_digest,
                     "Sui transaction failed at signing: {err:?}"
                 );
-                metrics.err_sui_transaction_submission.inc();
+                let digests = vec![format!("{:?}", action.digest()), tx_digest.to_string()];
+                inc_err_counter!(
+                    metrics,
+                    err_sui_transaction_submission,
+                    err.sanitized(),
+                    digests.clone()
+                );
                 let metrics_clone = metrics.clone();
                 // Do this in a separate task so we won't deadlock here
                 let sender_clone = execution_queue_sender.clone();
                 spawn_logged_monitored_task!(async move {
                     // If it fails for too many times, log and ask for manual intervention.
-                    metrics_clone
-                        .err_sui_transaction_submission_too_many_failures
-                        .inc();
+                    inc_err_counter!(
+                        metrics_clone,
+                        err_sui_transaction_submission_too_many_failures,
+                        format!("Attempt {}: {}", attempt_times, err.sanitized()),
+                        digests
+                    );
                     if attempt_times >= MAX_EXECUTION_ATTEMPTS {
                         error!("Manual intervention is required. Failed to collect execute transaction for bridge action after {MAX_EXECUTION_ATTEMPTS} attempts: {:?}", err);
                         return;
@@ -565,20 +1086,92 @@ where
         }
     }
 
+    // Records the transaction in the journal before it is submitted. Depending on the
+    // journal config, a failed write either stops the executor or is only reported.
+    fn write_tx_journal(
+        tx_journal: &TxJournal,
+        action: &BridgeAction,
+        signed_tx: &Transaction,
+        gas_object_ref: ObjectRef,
+        metrics: &Arc<BridgeMetrics>,
+    ) {
+        let entry = TxJournalEntry {
+            action_digest: action.digest(),
+            tx_digest: *signed_tx.digest(),
+            tx_bytes: bcs::to_bytes(signed_tx.data().transaction_data())
+                .expect("Serializing transaction data should not fail"),
+            signatures: signed_tx
+                .data()
+                .tx_signatures()
+                .iter()
+                .map(|sig| sig.as_ref().to_vec())
+                .collect(),
+            gas_object: gas_object_ref,
+            timestamp_ms: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .expect("Time went backwards")
+                .as_millis() as u64,
+        };
+        if let Err(err) = tx_journal.append(&entry) {
+            if tx_journal.fatal_on_failure() {
+                panic!(
+                    "Failed to write transaction {:?} to tx journal: {:?}",
+                    entry.tx_digest, err
+                );
+            }
+            warn!(tx_digest=?entry.tx_digest, "Failed to write transaction to tx journal: {:?}", err);
+            inc_err_counter!(
+                metrics,
+                err_tx_journal_write,
+                // The error may name the files of the journal
+                match err.downcast_ref::<BridgeError>() {
+                    Some(err) => err.sanitized(),
+                    None => "Couldn't append the transaction".to_string(),
+                },
+                vec![
+                    format!("{:?}", entry.action_digest),
+                    entry.tx_digest.to_string()
+                ]
+            );
+        }
+    }
+
     // TODO: do we need a mechanism to periodically read pending actions from DB?
     async fn handle_execution_effects(
         tx_digest: TransactionDigest,
         response: SuiTransactionBlockResponse,
         store: &Arc<BridgeOrchestratorTables>,
-        action: &BridgeAction,
+        certificate: &VerifiedCertifiedBridgeAction,
+        attempt_times: u64,
+        signing_queue_sender: &mysten_metrics::metered_channel::Sender<
+            BridgeActionExecutionWrapper,
+        >,
+        execution_queue_sender: &mysten_metrics::metered_channel::Sender<
+            CertifiedBridgeActionExecutionWrapper,
+        >,
+        reaggregated_actions: &mut HashSet<BridgeActionDigest>,
+        quarantine: &ActionQuarantine,
+        event_stream: Option<&EventStream>,
         metrics: &Arc<BridgeMetrics>,
     ) {
+        let action = certificate.data();
         let effects = response
             .effects
             .clone()
             .expect("We requested effects but got None.");
         let status = effects.status();
+        let gas_spent_mist = Self::record_gas_spent(action, &effects, metrics);
+        let move_error = BridgeMoveError::from_effects(status, response.events.as_ref());
         match status {
+            // The approval went through but the limiter rejected the claim, try to
+            // claim again later.
+            SuiExecutionStatus::Success if move_error == Some(BridgeMoveError::LimitExceeded) => {
+                warn!(
+                    ?tx_digest,
+                    "Token transfer limit exceeded, deferring action: {:?}", action
+                );
+                Self::defer_execution(certificate.clone(), attempt_times, execution_queue_sender);
+            }
             SuiExecutionStatus::Success => {
                 let events = response.events.expect("We requested events but got None.");
                 // If the transaction is successful, there must be either
@@ -594,25 +1187,193 @@ where
                     events,
                     );
                 info!(?tx_digest, "Sui transaction executed successfully");
+                reaggregated_actions.remove(&action.digest());
+                quarantine.clear(&action.digest());
+                metrics.action_latency.record_executed(action, metrics);
+                if let Some(event_stream) = event_stream {
+                    event_stream.action_status(action, StreamActionStatus::Executed, None);
+                }
                 store
-                    .remove_pending_actions(&[action.digest()])
+                    .mark_action_executed_with_gas(action, gas_spent_mist)
                     .unwrap_or_else(|e| {
                         panic!("Write to DB should not fail: {:?}", e);
                     })
             }
             SuiExecutionStatus::Failure { error } => {
-                // In practice the transaction could fail because of running out of gas, but really
-                // should not be due to other reasons.
-                // This means manual intervention is needed. So we do not push them back to
-                // the execution queue because retries are mostly likely going to fail anyway.
-                // After human examination, the node should be restarted and fetch them from WAL.
-
-                metrics.err_sui_transaction_execution.inc();
-                error!(?tx_digest, "Manual intervention is needed. Sui transaction executed and failed with error: {error:?}");
+                let category = move_error
+                    .as_ref()
+                    .map(|e| e.category())
+                    .unwrap_or("unknown");
+                inc_err_counter!(
+                    metrics,
+                    err_sui_transaction_execution[category],
+                    match &move_error {
+                        Some(move_error) => format!("{:?}", move_error),
+                        None => "Not an abort of the bridge package".to_string(),
+                    },
+                    vec![format!("{:?}", action.digest()), tx_digest.to_string()]
+                );
+                match move_error {
+                    Some(BridgeMoveError::AlreadyApproved) => {
+                        info!(
+                            ?tx_digest,
+                            "Action already approved or claimed, removing action from pending logs: {:?}",
+                            action
+                        );
+                        metrics.action_executor_already_processed_actions.inc();
+                        reaggregated_actions.remove(&action.digest());
+                        quarantine.clear(&action.digest());
+                        if let Some(event_stream) = event_stream {
+                            event_stream.action_status(action, StreamActionStatus::Executed, None);
+                        }
+                        store
+                            .mark_actions_executed(&[action.clone()])
+                            .unwrap_or_else(|e| {
+                                panic!("Write to DB should not fail: {:?}", e);
+                            })
+                    }
+                    Some(BridgeMoveError::Paused) => {
+                        warn!(
+                            ?tx_digest,
+                            "Bridge is paused, deferring action: {:?}", action
+                        );
+                        Self::defer_execution(
+                            certificate.clone(),
+                            attempt_times,
+                            execution_queue_sender,
+                        );
+                    }
+                    // The committee may have changed since the signatures were collected,
+                    // collect them again once.
+                    Some(BridgeMoveError::InvalidSignatures)
+                        if reaggregated_actions.insert(action.digest()) =>
+                    {
+                        warn!(
+                            ?tx_digest,
+                            "Invalid signatures, requesting signatures again for action: {:?}",
+                            action
+                        );
+                        let sender_clone = signing_queue_sender.clone();
+                        let action_clone = action.clone();
+                        spawn_logged_monitored_task!(async move {
+                            sender_clone
+                                .send(BridgeActionExecutionWrapper(
+                                    action_clone,
+                                    0,
+                                    Instant::now(),
+                                ))
+                                .await
+                                .unwrap_or_else(|e| {
+                                    panic!("Sending to signing queue should not fail: {:?}", e);
+                                });
+                        });
+                    }
+                    Some(BridgeMoveError::InvalidSignatures) => {
+                        error!(
+                            ?tx_digest,
+                            "Manual intervention is needed. Invalid signatures after signature re-aggregation, dropping action: {:?}",
+                            action
+                        );
+                        reaggregated_actions.remove(&action.digest());
+                        if let Some(event_stream) = event_stream {
+                            event_stream.action_status(
+                                action,
+                                StreamActionStatus::Failed,
+                                Some(format!("{:?}", error)),
+                            );
+                        }
+                        store
+                            .insert_dropped_actions(
+                                &[action.clone()],
+                                DroppedActionReason::InvalidSignatures,
+                            )
+                            .and_then(|_| store.remove_pending_actions(&[action.digest()]))
+                            .unwrap_or_else(|e| {
+                                panic!("Write to DB should not fail: {:?}", e);
+                            })
+                    }
+                    // Aborts that do not depend on the bridge state happen again on every
+                    // attempt, the action is quarantined after a few of them.
+                    Some(move_error) if move_error.is_permanent() => {
+                        if !quarantine.record_permanent_failure(action, format!("{:?}", error)) {
+                            Self::defer_execution(
+                                certificate.clone(),
+                                attempt_times,
+                                execution_queue_sender,
+                            );
+                        }
+                    }
+                    // In practice the transaction could fail because of running out of gas, but really
+                    // should not be due to other reasons.
+                    // This means manual intervention is needed. So we do not push them back to
+                    // the execution queue because retries are mostly likely going to fail anyway.
+                    // After human examination, the node should be restarted and fetch them from WAL.
+                    _ => {
+                        error!(?tx_digest, "Manual intervention is needed. Sui transaction executed and failed with error: {error:?}");
+                        if let Some(event_stream) = event_stream {
+                            event_stream.action_status(
+                                action,
+                                StreamActionStatus::Failed,
+                                Some(format!("{:?}", error)),
+                            );
+                        }
+                    }
+                }
             }
         }
     }
 
+    /// Counts the gas that the transaction of `action` was charged, net of storage
+    /// rebates, and returns it in mist. Failed transactions are charged too.
+    fn record_gas_spent(
+        action: &BridgeAction,
+        effects: &SuiTransactionBlockEffects,
+        metrics: &BridgeMetrics,
+    ) -> u64 {
+        let gas_spent_mist = effects.gas_cost_summary().net_gas_usage().max(0) as u64;
+        let action_type = format!("{:?}", action.action_type());
+        let status = match effects.status() {
+            SuiExecutionStatus::Success => "success",
+            SuiExecutionStatus::Failure { .. } => "failed",
+        };
+        metrics
+            .gas_spent_mist
+            .with_label_values(&[&action_type, status])
+            .inc_by(gas_spent_mist);
+        metrics
+            .gas_spent_mist_per_tx
+            .with_label_values(&[&action_type])
+            .observe(gas_spent_mist as f64);
+        gas_spent_mist
+    }
+
+    // Puts the certificate back to the execution queue after a delay.
+    fn defer_execution(
+        certificate: VerifiedCertifiedBridgeAction,
+        attempt_times: u64,
+        execution_queue_sender: &mysten_metrics::metered_channel::Sender<
+            CertifiedBridgeActionExecutionWrapper,
+        >,
+    ) {
+        if attempt_times >= MAX_EXECUTION_ATTEMPTS {
+            error!("Manual intervention is required. Failed to execute bridge action after {MAX_EXECUTION_ATTEMPTS} attempts: {:?}", certificate.data());
+            return;
+        }
+        let sender_clone = execution_queue_sender.clone();
+        spawn_logged_monitored_task!(async move {
+            delay(attempt_times).await;
+            sender_clone
+                .send(CertifiedBridgeActionExecutionWrapper(
+                    certificate,
+                    attempt_times + 1,
+                ))
+                .await
+                .unwrap_or_else(|e| {
+                    panic!("Sending to execution queue should not fail: {:?}", e);
+                });
+        });
+    }
+
     /// Panics if the gas object is not owned by the address.
     async fn get_gas_data_assert_ownership(
         sui_address: SuiAddress,
@@ -640,16 +1401,17 @@ pub async fn submit_to_executor(
     tx: &mysten_metrics::metered_channel::Sender<BridgeActionExecutionWrapper>,
     action: BridgeAction,
 ) -> Result<(), BridgeError> {
-    tx.send(BridgeActionExecutionWrapper(action, 0))
+    tx.send(BridgeActionExecutionWrapper(action, 0, Instant::now()))
         .await
         .map_err(|e| BridgeError::Generic(e.to_string()))
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::events::init_all_struct_tags;
+    use crate::events::{init_all_struct_tags, TokenTransferLimitExceed};
+    use crate::move_errors::tests::get_move_abort_status;
     use crate::test_utils::DUMMY_MUTALBE_BRIDGE_OBJECT_ARG;
-    use crate::types::BRIDGE_PAUSED;
+    use crate::types::{EmergencyAction, EmergencyActionType, BRIDGE_PAUSED};
     use fastcrypto::traits::KeyPair;
     use prometheus::Registry;
     use std::collections::{BTreeMap, HashMap};
@@ -657,7 +1419,9 @@ mod tests {
     use sui_json_rpc_types::SuiTransactionBlockEffects;
     use sui_json_rpc_types::SuiTransactionBlockEvents;
     use sui_json_rpc_types::{SuiEvent, SuiTransactionBlockResponse};
+    use sui_types::bridge::BridgeChainId;
     use sui_types::crypto::get_key_pair;
+    use sui_types::gas::GasCostSummary;
     use sui_types::gas_coin::GasCoin;
     use sui_types::TypeTag;
     use sui_types::{base_types::random_object_ref, transaction::TransactionData};
@@ -697,6 +1461,7 @@ mod tests {
             sui_address,
             sui_token_type_tags,
             _bridge_pause_tx,
+            _metrics,
         ) = setup().await;
         let (action_certificate, _, _) = get_bridge_authority_approved_action(
             vec![&mock0, &mock1, &mock2, &mock3],
@@ -895,6 +1660,7 @@ mod tests {
             sui_address,
             sui_token_type_tags,
             _bridge_pause_tx,
+            _metrics,
         ) = setup().await;
         let id_token_map = (*sui_token_type_tags.load().clone()).clone();
         let (action_certificate, sui_tx_digest, sui_tx_event_index) =
@@ -1018,6 +1784,7 @@ mod tests {
             _sui_address,
             _sui_token_type_tags,
             _bridge_pause_tx,
+            _metrics,
         ) = setup().await;
 
         let sui_tx_digest = TransactionDigest::random();
@@ -1067,6 +1834,77 @@ mod tests {
         tx_subscription.try_recv().unwrap_err();
     }
 
+    #[tokio::test]
+    async fn test_shadow_mode_compares_instead_of_submitting() {
+        let (
+            signing_tx,
+            _execution_tx,
+            sui_client_mock,
+            mut tx_subscription,
+            store,
+            secrets,
+            _dummy_sui_key,
+            mock0,
+            mock1,
+            mock2,
+            mock3,
+            _handles,
+            gas_object_ref,
+            sui_address,
+            _sui_token_type_tags,
+            _bridge_pause_tx,
+            metrics,
+        ) = setup_with_executor(|executor| {
+            let shadow = ShadowComparator::new(
+                executor.sui_client.clone(),
+                executor.store.clone(),
+                executor.metrics.clone(),
+            );
+            executor.with_shadow_comparator(shadow)
+        })
+        .await;
+        let (action_certificate, _, _) = get_bridge_authority_approved_action(
+            vec![&mock0, &mock1, &mock2, &mock3],
+            vec![&secrets[0], &secrets[1], &secrets[2], &secrets[3]],
+            None,
+            true,
+        );
+        let action = action_certificate.data().clone();
+        let gas_coin = GasCoin::new_for_testing(1_000_000_000_000); // dummy gas coin
+        sui_client_mock.add_gas_object_info(
+            gas_coin.clone(),
+            gas_object_ref,
+            Owner::AddressOwner(sui_address),
+        );
+        // The committee already approved the transfer, which is not skipped in shadow mode
+        sui_client_mock.set_action_onchain_status(&action, BridgeActionStatus::Approved);
+        store.insert_pending_actions(&[action.clone()]).unwrap();
+
+        submit_to_executor(&signing_tx, action.clone())
+            .await
+            .unwrap();
+
+        let now = std::time::Instant::now();
+        while store
+            .get_all_pending_actions()
+            .contains_key(&action.digest())
+        {
+            if now.elapsed().as_secs() > 10 {
+                panic!("Timeout waiting for the action to be compared");
+            }
+            tokio::time::sleep(tokio::time::Duration::from_millis(100)).await;
+        }
+        assert_eq!(
+            metrics
+                .shadow_comparisons
+                .with_label_values(&["agreement"])
+                .get(),
+            1
+        );
+        assert!(sui_client_mock.executed_transactions().is_empty());
+        tx_subscription.try_recv().unwrap_err();
+    }
+
     #[tokio::test]
     async fn test_skip_tx_submission_if_already_processed_on_chain() {
         let (
@@ -1086,6 +1924,7 @@ mod tests {
             sui_address,
             sui_token_type_tags,
             _bridge_pause_tx,
+            _metrics,
         ) = setup().await;
         let id_token_map = (*sui_token_type_tags.load().clone()).clone();
         let (action_certificate, _, _) = get_bridge_authority_approved_action(
@@ -1171,6 +2010,7 @@ mod tests {
             sui_address,
             sui_token_type_tags,
             bridge_pause_tx,
+            _metrics,
         ) = setup().await;
         let id_token_map: HashMap<u8, TypeTag> = (*sui_token_type_tags.load().clone()).clone();
         let (action_certificate, _, _) = get_bridge_authority_approved_action(
@@ -1252,17 +2092,17 @@ mod tests {
     }
 
     #[tokio::test]
-    async fn test_action_executor_handle_new_token() {
-        let new_token_id = 255u8; // token id that does not exist
-        let new_type_tag = TypeTag::from_str("0xbeef::beef::BEEF").unwrap();
+    async fn test_defer_execution_on_paused_route() {
+        let route_pauses = Arc::new(RoutePauses::new(Arc::new(BridgeMetrics::for_testing())));
+        let route_pauses_clone = route_pauses.clone();
         let (
             _signing_tx,
             execution_tx,
             sui_client_mock,
             mut tx_subscription,
-            _store,
+            store,
             secrets,
-            dummy_sui_key,
+            _dummy_sui_key,
             mock0,
             mock1,
             mock2,
@@ -1272,13 +2112,727 @@ mod tests {
             sui_address,
             sui_token_type_tags,
             _bridge_pause_tx,
-        ) = setup().await;
-        let mut id_token_map: HashMap<u8, TypeTag> = (*sui_token_type_tags.load().clone()).clone();
-        let (action_certificate, _, _) = get_bridge_authority_approved_action(
-            vec![&mock0, &mock1, &mock2, &mock3],
-            vec![&secrets[0], &secrets[1], &secrets[2], &secrets[3]],
-            Some(new_token_id),
-            false, // we need an eth -> sui action that entails the new token type tag in transaction building
+            metrics,
+        ) = setup_with_executor(|executor| executor.with_route_pauses(route_pauses_clone, false))
+            .await;
+        sui_client_mock.add_gas_object_info(
+            GasCoin::new_for_testing(1_000_000_000_000),
+            gas_object_ref,
+            Owner::AddressOwner(sui_address),
+        );
+        let mut event = SuiEvent::random_for_testing();
+        event.type_ = TokenTransferClaimed.get().unwrap().clone();
+        mock_transaction_response(
+            &sui_client_mock,
+            TransactionDigest::random(),
+            SuiExecutionStatus::Success,
+            Some(vec![event]),
+            true,
+        );
+        // Eth to Sui transfers are claimed with the type of their token
+        let token_id = 255;
+        sui_token_type_tags.store(Arc::new(maplit::hashmap! {
+            token_id => TypeTag::from_str("0xbeef::beef::BEEF").unwrap()
+        }));
+        let deferred = || {
+            metrics
+                .route_deferred_actions
+                .with_label_values(&["EthCustom_to_SuiCustom"])
+                .get()
+        };
+
+        // Only the Eth to Sui route is paused
+        route_pauses.update(HashSet::from([(
+            BridgeChainId::EthCustom,
+            BridgeChainId::SuiCustom,
+        )]));
+        let (eth_to_sui, _, _) = get_bridge_authority_approved_action(
+            vec![&mock0, &mock1, &mock2, &mock3],
+            vec![&secrets[0], &secrets[1], &secrets[2], &secrets[3]],
+            Some(token_id),
+            false,
+        );
+        let (sui_to_eth, _, _) = get_bridge_authority_approved_action(
+            vec![&mock0, &mock1, &mock2, &mock3],
+            vec![&secrets[0], &secrets[1], &secrets[2], &secrets[3]],
+            None,
+            true,
+        );
+        for certificate in [&eth_to_sui, &sui_to_eth] {
+            let action = certificate.data().clone();
+            sui_client_mock.set_action_onchain_status(&action, BridgeActionStatus::Pending);
+            store.insert_pending_actions(&[action]).unwrap();
+            execution_tx
+                .send(CertifiedBridgeActionExecutionWrapper(
+                    certificate.clone(),
+                    0,
+                ))
+                .await
+                .unwrap();
+        }
+
+        // The Sui to Eth transfer is executed, the other one is deferred
+        tx_subscription.recv().await.unwrap();
+        wait_until(|| {
+            !store
+                .get_all_pending_actions()
+                .contains_key(&sui_to_eth.data().digest())
+        })
+        .await;
+        tokio::time::sleep(tokio::time::Duration::from_millis(500)).await;
+        assert_eq!(
+            tx_subscription.try_recv().unwrap_err(),
+            tokio::sync::broadcast::error::TryRecvError::Empty
+        );
+        assert!(store
+            .get_all_pending_actions()
+            .contains_key(&eth_to_sui.data().digest()));
+        assert_eq!(deferred(), 1);
+
+        // It's executed once the route is no longer paused
+        route_pauses.update(HashSet::new());
+        tx_subscription.recv().await.unwrap();
+        wait_until(|| store.get_all_pending_actions().is_empty()).await;
+        assert_eq!(deferred(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_executor_state_shows_stuck_execution() {
+        let (
+            _signing_tx,
+            execution_tx,
+            sui_client_mock,
+            _tx_subscription,
+            store,
+            secrets,
+            _dummy_sui_key,
+            mock0,
+            mock1,
+            mock2,
+            mock3,
+            _handles,
+            gas_object_ref,
+            sui_address,
+            _sui_token_type_tags,
+            bridge_pause_tx,
+            metrics,
+        ) = setup().await;
+        let (action_certificate, _, _) = get_bridge_authority_approved_action(
+            vec![&mock0, &mock1, &mock2, &mock3],
+            vec![&secrets[0], &secrets[1], &secrets[2], &secrets[3]],
+            None,
+            true,
+        );
+        let action = action_certificate.data().clone();
+        let gas_coin = GasCoin::new_for_testing(1_000_000_000_000); // dummy gas coin
+        sui_client_mock.add_gas_object_info(
+            gas_coin,
+            gas_object_ref,
+            Owner::AddressOwner(sui_address),
+        );
+        sui_client_mock.set_action_onchain_status(&action, BridgeActionStatus::Pending);
+        // The transaction never returns its effects
+        sui_client_mock.set_execution_delay(tokio::time::Duration::from_secs(3600));
+        store.insert_pending_actions(&[action.clone()]).unwrap();
+
+        execution_tx
+            .send(CertifiedBridgeActionExecutionWrapper(action_certificate, 0))
+            .await
+            .unwrap();
+
+        let now = std::time::Instant::now();
+        let current = loop {
+            let snapshot = metrics.executor_state.snapshot();
+            if let Some(current) = snapshot.current() {
+                if current.stage == ExecutorStage::AwaitingEffects {
+                    break current.clone();
+                }
+            }
+            if now.elapsed().as_secs() > 10 {
+                panic!("Timeout waiting for the action to be submitted");
+            }
+            tokio::time::sleep(tokio::time::Duration::from_millis(100)).await;
+        };
+        assert_eq!(current.action, action);
+        let snapshot = metrics.executor_state.snapshot();
+        assert_eq!(snapshot.in_flight.len(), 1);
+        assert_eq!(snapshot.gas_coin, Some((gas_object_ref, 1_000_000_000_000)));
+        assert!(!snapshot.bridge_paused);
+
+        let state =
+            crate::api_types::ExecutorState::new(&snapshot, current.stage_entered_at_ms + 1500);
+        let current_action = state.current_action.unwrap();
+        assert_eq!(
+            current_action.action_digest,
+            crate::api_types::encode_action_digest(&action.digest())
+        );
+        assert_eq!(
+            current_action.stage,
+            crate::api_types::ExecutorStage::AwaitingEffects
+        );
+        assert_eq!(current_action.time_in_stage_ms, 1500);
+        assert_eq!(current_action.nonce, action.seq_number());
+
+        // The pause is visible while the execution is stuck
+        bridge_pause_tx.send(BRIDGE_PAUSED).unwrap();
+        let now = std::time::Instant::now();
+        while !metrics.executor_state.snapshot().bridge_paused {
+            if now.elapsed().as_secs() > 10 {
+                panic!("Timeout waiting for the pause to be published");
+            }
+            tokio::time::sleep(tokio::time::Duration::from_millis(100)).await;
+        }
+        assert_eq!(metrics.executor_state.snapshot().current(), Some(&current));
+    }
+
+    #[tokio::test]
+    async fn test_error_catalog_records_executor_errors() {
+        let (
+            signing_tx,
+            _execution_tx,
+            sui_client_mock,
+            mut tx_subscription,
+            _store,
+            secrets,
+            dummy_sui_key,
+            mock0,
+            mock1,
+            mock2,
+            mock3,
+            _handles,
+            gas_object_ref,
+            sui_address,
+            sui_token_type_tags,
+            _bridge_pause_tx,
+            metrics,
+        ) = setup().await;
+        let id_token_map = (*sui_token_type_tags.load().clone()).clone();
+        let gas_coin = GasCoin::new_for_testing(1_000_000_000_000); // dummy gas coin
+        sui_client_mock.add_gas_object_info(
+            gas_coin,
+            gas_object_ref,
+            Owner::AddressOwner(sui_address),
+        );
+
+        // 1. Transaction is executed and fails
+        let (action_certificate, _, _) = get_bridge_authority_approved_action(
+            vec![&mock0, &mock1, &mock2, &mock3],
+            vec![&secrets[0], &secrets[1], &secrets[2], &secrets[3]],
+            None,
+            true,
+        );
+        let failed_action = action_certificate.data().clone();
+        let tx_data = build_sui_transaction(
+            sui_address,
+            &gas_object_ref,
+            action_certificate,
+            DUMMY_MUTALBE_BRIDGE_OBJECT_ARG,
+            &id_token_map,
+            1000,
+        )
+        .unwrap();
+        let failed_tx_digest = get_tx_digest(tx_data, &dummy_sui_key);
+        mock_transaction_response(
+            &sui_client_mock,
+            failed_tx_digest,
+            SuiExecutionStatus::Failure {
+                error: "failure is mother of success".to_string(),
+            },
+            None,
+            true,
+        );
+        submit_to_executor(&signing_tx, failed_action.clone())
+            .await
+            .unwrap();
+        tx_subscription.recv().await.unwrap();
+
+        // 2. Transaction fails at signing stage
+        let (action_certificate, _, _) = get_bridge_authority_approved_action(
+            vec![&mock0, &mock1, &mock2, &mock3],
+            vec![&secrets[0], &secrets[1], &secrets[2], &secrets[3]],
+            None,
+            true,
+        );
+        let rejected_action = action_certificate.data().clone();
+        let tx_data = build_sui_transaction(
+            sui_address,
+            &gas_object_ref,
+            action_certificate,
+            DUMMY_MUTALBE_BRIDGE_OBJECT_ARG,
+            &id_token_map,
+            1000,
+        )
+        .unwrap();
+        let rejected_tx_digest = get_tx_digest(tx_data, &dummy_sui_key);
+        mock_transaction_error(
+            &sui_client_mock,
+            rejected_tx_digest,
+            BridgeError::Generic("some random error".to_string()),
+            true,
+        );
+        submit_to_executor(&signing_tx, rejected_action.clone())
+            .await
+            .unwrap();
+        tx_subscription.recv().await.unwrap();
+
+        // Wait for both errors to be recorded
+        let catalog = metrics.error_catalog.clone();
+        loop {
+            if !catalog.get("err_sui_transaction_execution").is_empty()
+                && !catalog.get("err_sui_transaction_submission").is_empty()
+            {
+                break;
+            }
+            tokio::time::sleep(tokio::time::Duration::from_millis(50)).await;
+        }
+
+        let execution_errors = catalog.get("err_sui_transaction_execution");
+        assert_eq!(execution_errors.len(), 1);
+        // Only what failed is recorded, not the message of the error
+        assert_eq!(
+            execution_errors[0].message,
+            "[unknown] Not an abort of the bridge package"
+        );
+        assert_eq!(
+            execution_errors[0].digests,
+            vec![
+                format!("{:?}", failed_action.digest()),
+                failed_tx_digest.to_string()
+            ]
+        );
+
+        let submission_errors = catalog.get("err_sui_transaction_submission");
+        assert_eq!(submission_errors[0].message, "Generic (node, transient)");
+        assert_eq!(
+            submission_errors[0].digests,
+            vec![
+                format!("{:?}", rejected_action.digest()),
+                rejected_tx_digest.to_string()
+            ]
+        );
+        // Errors are not mixed up across counters
+        assert!(submission_errors
+            .iter()
+            .all(|e| !e.message.contains("failure is mother of success")));
+        assert!(catalog
+            .snapshot()
+            .contains_key("err_sui_transaction_execution"));
+    }
+
+    #[tokio::test]
+    async fn test_execution_failure_branches_on_move_error() {
+        let (
+            signing_tx,
+            _execution_tx,
+            sui_client_mock,
+            mut tx_subscription,
+            store,
+            secrets,
+            _dummy_sui_key,
+            mock0,
+            mock1,
+            mock2,
+            mock3,
+            _handles,
+            gas_object_ref,
+            sui_address,
+            _sui_token_type_tags,
+            _bridge_pause_tx,
+            metrics,
+        ) = setup().await;
+        let gas_coin = GasCoin::new_for_testing(1_000_000_000_000); // dummy gas coin
+        sui_client_mock.add_gas_object_info(
+            gas_coin.clone(),
+            gas_object_ref,
+            Owner::AddressOwner(sui_address),
+        );
+        let mut claimed_event = SuiEvent::random_for_testing();
+        claimed_event.type_ = TokenTransferClaimed.get().unwrap().clone();
+        let mut limit_exceed_event = SuiEvent::random_for_testing();
+        limit_exceed_event.type_ = TokenTransferLimitExceed.get().unwrap().clone();
+        let execution_errors = |category: &str| {
+            metrics
+                .err_sui_transaction_execution
+                .with_label_values(&[category])
+                .get()
+        };
+
+        // 1. Already approved: the action is done
+        let (action_certificate, _, _) = get_bridge_authority_approved_action(
+            vec![&mock0, &mock1, &mock2, &mock3],
+            vec![&secrets[0], &secrets[1], &secrets[2], &secrets[3]],
+            None,
+            true,
+        );
+        let action = action_certificate.data().clone();
+        mock_transaction_response(
+            &sui_client_mock,
+            TransactionDigest::random(),
+            get_move_abort_status("bridge", 15),
+            None,
+            true,
+        );
+        store.insert_pending_actions(&[action.clone()]).unwrap();
+        submit_to_executor(&signing_tx, action.clone())
+            .await
+            .unwrap();
+        tx_subscription.recv().await.unwrap();
+        wait_until(|| store.get_all_pending_actions().is_empty()).await;
+        assert_eq!(execution_errors("already_approved"), 1);
+
+        // 2. Paused and 3. limit exceeded: the action is deferred and executed again
+        for (status, events, category) in [
+            (get_move_abort_status("bridge", 8), None, Some("paused")),
+            (
+                SuiExecutionStatus::Success,
+                Some(vec![limit_exceed_event.clone()]),
+                None,
+            ),
+        ] {
+            let (action_certificate, _, _) = get_bridge_authority_approved_action(
+                vec![&mock0, &mock1, &mock2, &mock3],
+                vec![&secrets[0], &secrets[1], &secrets[2], &secrets[3]],
+                None,
+                true,
+            );
+            let action = action_certificate.data().clone();
+            mock_transaction_response(
+                &sui_client_mock,
+                TransactionDigest::random(),
+                status,
+                events,
+                true,
+            );
+            store.insert_pending_actions(&[action.clone()]).unwrap();
+            submit_to_executor(&signing_tx, action.clone())
+                .await
+                .unwrap();
+            let tx_digest = tx_subscription.recv().await.unwrap();
+            // The same transaction is submitted again after a delay
+            assert_eq!(tx_subscription.recv().await.unwrap(), tx_digest);
+            assert!(store
+                .get_all_pending_actions()
+                .contains_key(&action.digest()));
+            if let Some(category) = category {
+                assert!(execution_errors(category) >= 1);
+            }
+
+            // Now let it succeed
+            mock_transaction_response(
+                &sui_client_mock,
+                tx_digest,
+                SuiExecutionStatus::Success,
+                Some(vec![claimed_event.clone()]),
+                true,
+            );
+            wait_until(|| store.get_all_pending_actions().is_empty()).await;
+        }
+
+        // 4. Invalid signatures: signatures are aggregated again once, then the action
+        // is dropped
+        let (action_certificate, _, _) = get_bridge_authority_approved_action(
+            vec![&mock0, &mock1, &mock2, &mock3],
+            vec![&secrets[0], &secrets[1], &secrets[2], &secrets[3]],
+            None,
+            true,
+        );
+        let action = action_certificate.data().clone();
+        mock_transaction_response(
+            &sui_client_mock,
+            TransactionDigest::random(),
+            get_move_abort_status("committee", 2),
+            None,
+            true,
+        );
+        store.insert_pending_actions(&[action.clone()]).unwrap();
+        submit_to_executor(&signing_tx, action.clone())
+            .await
+            .unwrap();
+        tx_subscription.recv().await.unwrap();
+        tx_subscription.recv().await.unwrap();
+        wait_until(|| store.get_all_pending_actions().is_empty()).await;
+        assert_eq!(store.get_all_dropped_actions()[&action.digest()], action);
+        assert_eq!(execution_errors("invalid_signatures"), 2);
+        // No more attempts
+        tokio::time::sleep(tokio::time::Duration::from_secs(1)).await;
+        assert!(tx_subscription.try_recv().is_err());
+    }
+
+    #[tokio::test]
+    async fn test_quarantine_permanently_failing_actions() {
+        let (
+            signing_tx,
+            _execution_tx,
+            sui_client_mock,
+            mut tx_subscription,
+            store,
+            secrets,
+            _dummy_sui_key,
+            mock0,
+            mock1,
+            mock2,
+            mock3,
+            _handles,
+            gas_object_ref,
+            sui_address,
+            _sui_token_type_tags,
+            _bridge_pause_tx,
+            metrics,
+        ) = setup().await;
+        let gas_coin = GasCoin::new_for_testing(1_000_000_000_000); // dummy gas coin
+        sui_client_mock.add_gas_object_info(
+            gas_coin,
+            gas_object_ref,
+            Owner::AddressOwner(sui_address),
+        );
+
+        // 1. The committee rejects the action as invalid
+        let (action_certificate, sui_tx_digest, sui_tx_event_index) =
+            get_bridge_authority_approved_action(
+                vec![&mock0, &mock1, &mock2, &mock3],
+                vec![&secrets[0], &secrets[1], &secrets[2], &secrets[3]],
+                None,
+                true,
+            );
+        let action = action_certificate.data().clone();
+        for mock in [&mock0, &mock1, &mock2] {
+            mock.add_sui_event_response(
+                sui_tx_digest,
+                sui_tx_event_index,
+                Err(BridgeError::NoBridgeEventsInTxPosition),
+            );
+        }
+        store.insert_pending_actions(&[action.clone()]).unwrap();
+        submit_to_executor(&signing_tx, action.clone())
+            .await
+            .unwrap();
+        wait_until(|| store.get_all_pending_actions().is_empty()).await;
+        let quarantined = store.get_quarantined_action(&action.digest()).unwrap();
+        assert_eq!(quarantined.unwrap().failures, DEFAULT_QUARANTINE_THRESHOLD);
+        assert_eq!(metrics.action_executor_quarantined_actions.get(), 1);
+        // Nothing is executed, and no more signatures are requested
+        let requested_times =
+            mock0.get_sui_token_events_requested(sui_tx_digest, sui_tx_event_index);
+        tokio::time::sleep(tokio::time::Duration::from_secs(1)).await;
+        assert!(tx_subscription.try_recv().is_err());
+        assert_eq!(
+            mock0.get_sui_token_events_requested(sui_tx_digest, sui_tx_event_index),
+            requested_times
+        );
+
+        // 2. The approval aborts in a way that does not depend on the bridge state
+        let (action_certificate, _, _) = get_bridge_authority_approved_action(
+            vec![&mock0, &mock1, &mock2, &mock3],
+            vec![&secrets[0], &secrets[1], &secrets[2], &secrets[3]],
+            None,
+            true,
+        );
+        let action = action_certificate.data().clone();
+        mock_transaction_response(
+            &sui_client_mock,
+            TransactionDigest::random(),
+            get_move_abort_status("limiter", 0),
+            None,
+            true,
+        );
+        store.insert_pending_actions(&[action.clone()]).unwrap();
+        submit_to_executor(&signing_tx, action.clone())
+            .await
+            .unwrap();
+        for _ in 0..DEFAULT_QUARANTINE_THRESHOLD {
+            tx_subscription.recv().await.unwrap();
+        }
+        wait_until(|| store.get_all_pending_actions().is_empty()).await;
+        let quarantined = store.get_quarantined_action(&action.digest()).unwrap();
+        assert!(quarantined.unwrap().reason.contains("MoveAbort"));
+        assert_eq!(metrics.action_executor_quarantined_actions.get(), 2);
+        tokio::time::sleep(tokio::time::Duration::from_secs(1)).await;
+        assert!(tx_subscription.try_recv().is_err());
+    }
+
+    #[tokio::test]
+    async fn test_drop_disallowed_action_types() {
+        let (
+            signing_tx,
+            _execution_tx,
+            _sui_client_mock,
+            mut tx_subscription,
+            store,
+            _secrets,
+            _dummy_sui_key,
+            _mock0,
+            _mock1,
+            _mock2,
+            _mock3,
+            _handles,
+            _gas_object_ref,
+            _sui_address,
+            _sui_token_type_tags,
+            _bridge_pause_tx,
+            metrics,
+        ) = setup_with_execute_action_types(HashSet::from([BridgeActionType::TokenTransfer])).await;
+
+        let action = BridgeAction::EmergencyAction(EmergencyAction {
+            chain_id: BridgeChainId::SuiCustom,
+            nonce: 0,
+            action_type: EmergencyActionType::Pause,
+        });
+        store.insert_pending_actions(&[action.clone()]).unwrap();
+        submit_to_executor(&signing_tx, action.clone())
+            .await
+            .unwrap();
+
+        wait_until(|| store.get_all_pending_actions().is_empty()).await;
+        assert_eq!(store.get_all_dropped_actions()[&action.digest()], action);
+        assert_eq!(
+            store.get_dropped_action_reason(&action.digest()).unwrap(),
+            Some(DroppedActionReason::ActionTypeNotAllowed)
+        );
+        assert_eq!(metrics.action_executor_disallowed_actions.get(), 1);
+        // Nothing is executed
+        tokio::time::sleep(tokio::time::Duration::from_secs(1)).await;
+        assert!(tx_subscription.try_recv().is_err());
+    }
+
+    #[tokio::test]
+    async fn test_gas_spent_by_action_type() {
+        let (
+            signing_tx,
+            _execution_tx,
+            sui_client_mock,
+            mut tx_subscription,
+            store,
+            secrets,
+            _dummy_sui_key,
+            mock0,
+            mock1,
+            mock2,
+            mock3,
+            _handles,
+            gas_object_ref,
+            sui_address,
+            _sui_token_type_tags,
+            _bridge_pause_tx,
+            metrics,
+        ) = setup().await;
+        sui_client_mock.add_gas_object_info(
+            GasCoin::new_for_testing(1_000_000_000_000),
+            gas_object_ref,
+            Owner::AddressOwner(sui_address),
+        );
+        let gas_spent = |status: &str| {
+            metrics
+                .gas_spent_mist
+                .with_label_values(&["TokenTransfer", status])
+                .get()
+        };
+
+        // Executed, charged 1M computation plus 2M storage minus a rebate of 0.5M
+        let (action_certificate, _, _) = get_bridge_authority_approved_action(
+            vec![&mock0, &mock1, &mock2, &mock3],
+            vec![&secrets[0], &secrets[1], &secrets[2], &secrets[3]],
+            None,
+            true,
+        );
+        let action = action_certificate.data().clone();
+        let mut event = SuiEvent::random_for_testing();
+        event.type_ = TokenTransferClaimed.get().unwrap().clone();
+        mock_transaction_response_with_gas(
+            &sui_client_mock,
+            TransactionDigest::random(),
+            SuiExecutionStatus::Success,
+            Some(vec![event]),
+            GasCostSummary::new(1_000_000, 2_000_000, 500_000, 0),
+        );
+        store.insert_pending_actions(&[action.clone()]).unwrap();
+        submit_to_executor(&signing_tx, action.clone())
+            .await
+            .unwrap();
+        tx_subscription.recv().await.unwrap();
+        wait_until(|| store.get_all_pending_actions().is_empty()).await;
+        assert_eq!(gas_spent("success"), 2_500_000);
+        let BridgeAction::SuiToEthBridgeAction(transfer) = &action else {
+            panic!("Expected a Sui to Eth token transfer");
+        };
+        let event = &transfer.sui_bridge_event;
+        assert_eq!(
+            store
+                .get_executed_action_gas(
+                    event.sui_chain_id as u8,
+                    event.eth_chain_id as u8,
+                    event.nonce
+                )
+                .unwrap(),
+            Some(2_500_000)
+        );
+
+        // Failed, but charged for the computation
+        let (action_certificate, _, _) = get_bridge_authority_approved_action(
+            vec![&mock0, &mock1, &mock2, &mock3],
+            vec![&secrets[0], &secrets[1], &secrets[2], &secrets[3]],
+            None,
+            true,
+        );
+        let action = action_certificate.data().clone();
+        mock_transaction_response_with_gas(
+            &sui_client_mock,
+            TransactionDigest::random(),
+            SuiExecutionStatus::Failure {
+                error: "failure is mother of success".to_string(),
+            },
+            None,
+            GasCostSummary::new(700_000, 0, 0, 0),
+        );
+        store.insert_pending_actions(&[action.clone()]).unwrap();
+        submit_to_executor(&signing_tx, action.clone())
+            .await
+            .unwrap();
+        tx_subscription.recv().await.unwrap();
+        wait_until(|| gas_spent("failed") > 0).await;
+        assert_eq!(gas_spent("failed"), 700_000);
+        assert_eq!(gas_spent("success"), 2_500_000);
+        assert_eq!(
+            metrics
+                .gas_spent_mist_per_tx
+                .with_label_values(&["TokenTransfer"])
+                .get_sample_count(),
+            2
+        );
+        // Nothing is recorded for actions that were not executed
+        assert!(store
+            .get_all_pending_actions()
+            .contains_key(&action.digest()));
+    }
+
+    #[tokio::test]
+    async fn test_action_executor_handle_new_token() {
+        let new_token_id = 255u8; // token id that does not exist
+        let new_type_tag = TypeTag::from_str("0xbeef::beef::BEEF").unwrap();
+        let (
+            _signing_tx,
+            execution_tx,
+            sui_client_mock,
+            mut tx_subscription,
+            _store,
+            secrets,
+            dummy_sui_key,
+            mock0,
+            mock1,
+            mock2,
+            mock3,
+            _handles,
+            gas_object_ref,
+            sui_address,
+            sui_token_type_tags,
+            _bridge_pause_tx,
+            _metrics,
+        ) = setup().await;
+        let mut id_token_map: HashMap<u8, TypeTag> = (*sui_token_type_tags.load().clone()).clone();
+        let (action_certificate, _, _) = get_bridge_authority_approved_action(
+            vec![&mock0, &mock1, &mock2, &mock3],
+            vec![&secrets[0], &secrets[1], &secrets[2], &secrets[3]],
+            Some(new_token_id),
+            false, // we need an eth -> sui action that entails the new token type tag in transaction building
         );
 
         let action = action_certificate.data().clone();
@@ -1379,6 +2933,16 @@ mod tests {
         }
     }
 
+    async fn wait_until(condition: impl Fn() -> bool) {
+        tokio::time::timeout(tokio::time::Duration::from_secs(10), async {
+            while !condition() {
+                tokio::time::sleep(tokio::time::Duration::from_millis(50)).await;
+            }
+        })
+        .await
+        .expect("Condition not met in time");
+    }
+
     /// Create a BridgeAction and mock authorities to return signatures
     fn get_bridge_authority_approved_action(
         mocks: Vec<&BridgeRequestMockHandler>,
@@ -1434,12 +2998,7 @@ mod tests {
         events: Option<Vec<SuiEvent>>,
         wildcard: bool,
     ) {
-        let mut response = SuiTransactionBlockResponse::new(tx_digest);
-        let effects = SuiTransactionBlockEffects::new_for_testing(tx_digest, status);
-        if let Some(events) = events {
-            response.events = Some(SuiTransactionBlockEvents { data: events });
-        }
-        response.effects = Some(effects);
+        let response = transaction_response(tx_digest, status, events, GasCostSummary::default());
         if wildcard {
             sui_client_mock.set_wildcard_transaction_response(Ok(response));
         } else {
@@ -1447,6 +3006,36 @@ mod tests {
         }
     }
 
+    /// Makes every transaction return effects charging `gas`.
+    fn mock_transaction_response_with_gas(
+        sui_client_mock: &SuiMockClient,
+        tx_digest: TransactionDigest,
+        status: SuiExecutionStatus,
+        events: Option<Vec<SuiEvent>>,
+        gas: GasCostSummary,
+    ) {
+        sui_client_mock.set_wildcard_transaction_response(Ok(transaction_response(
+            tx_digest, status, events, gas,
+        )));
+    }
+
+    fn transaction_response(
+        tx_digest: TransactionDigest,
+        status: SuiExecutionStatus,
+        events: Option<Vec<SuiEvent>>,
+        gas: GasCostSummary,
+    ) -> SuiTransactionBlockResponse {
+        let mut response = SuiTransactionBlockResponse::new(tx_digest);
+        let mut effects = SuiTransactionBlockEffects::new_for_testing(tx_digest, status);
+        let SuiTransactionBlockEffects::V1(effects_v1) = &mut effects;
+        effects_v1.gas_used = gas;
+        if let Some(events) = events {
+            response.events = Some(SuiTransactionBlockEvents { data: events });
+        }
+        response.effects = Some(effects);
+        response
+    }
+
     fn mock_transaction_error(
         sui_client_mock: &SuiMockClient,
         tx_digest: TransactionDigest,
@@ -1478,6 +3067,60 @@ mod tests {
         SuiAddress,
         Arc<ArcSwap<HashMap<u8, TypeTag>>>,
         tokio::sync::watch::Sender<IsBridgePaused>,
+        Arc<BridgeMetrics>,
+    ) {
+        setup_with_execute_action_types(HashSet::from(BridgeActionType::ALL)).await
+    }
+
+    #[allow(clippy::type_complexity)]
+    async fn setup_with_execute_action_types(
+        execute_action_types: HashSet<BridgeActionType>,
+    ) -> (
+        mysten_metrics::metered_channel::Sender<BridgeActionExecutionWrapper>,
+        mysten_metrics::metered_channel::Sender<CertifiedBridgeActionExecutionWrapper>,
+        SuiMockClient,
+        tokio::sync::broadcast::Receiver<TransactionDigest>,
+        Arc<BridgeOrchestratorTables>,
+        Vec<BridgeAuthorityKeyPair>,
+        SuiKeyPair,
+        BridgeRequestMockHandler,
+        BridgeRequestMockHandler,
+        BridgeRequestMockHandler,
+        BridgeRequestMockHandler,
+        Vec<tokio::task::JoinHandle<()>>,
+        ObjectRef,
+        SuiAddress,
+        Arc<ArcSwap<HashMap<u8, TypeTag>>>,
+        tokio::sync::watch::Sender<IsBridgePaused>,
+        Arc<BridgeMetrics>,
+    ) {
+        setup_with_executor(|executor| executor.with_execute_action_types(execute_action_types))
+            .await
+    }
+
+    #[allow(clippy::type_complexity)]
+    async fn setup_with_executor(
+        configure: impl FnOnce(
+            BridgeActionExecutor<SuiMockClient>,
+        ) -> BridgeActionExecutor<SuiMockClient>,
+    ) -> (
+        mysten_metrics::metered_channel::Sender<BridgeActionExecutionWrapper>,
+        mysten_metrics::metered_channel::Sender<CertifiedBridgeActionExecutionWrapper>,
+        SuiMockClient,
+        tokio::sync::broadcast::Receiver<TransactionDigest>,
+        Arc<BridgeOrchestratorTables>,
+        Vec<BridgeAuthorityKeyPair>,
+        SuiKeyPair,
+        BridgeRequestMockHandler,
+        BridgeRequestMockHandler,
+        BridgeRequestMockHandler,
+        BridgeRequestMockHandler,
+        Vec<tokio::task::JoinHandle<()>>,
+        ObjectRef,
+        SuiAddress,
+        Arc<ArcSwap<HashMap<u8, TypeTag>>>,
+        tokio::sync::watch::Sender<IsBridgePaused>,
+        Arc<BridgeMetrics>,
     ) {
         telemetry_subscribers::init_for_testing();
         let registry = Registry::new();
@@ -1510,10 +3153,11 @@ mod tests {
 
         let committee = BridgeCommittee::new(authorities).unwrap();
 
+        let metrics = Arc::new(BridgeMetrics::new(&registry));
         let agg = Arc::new(ArcSwap::new(Arc::new(BridgeAuthorityAggregator::new(
             Arc::new(committee),
+            metrics.clone(),
         ))));
-        let metrics = Arc::new(BridgeMetrics::new(&registry));
         let sui_token_type_tags = sui_client.get_token_id_map().await.unwrap();
         let sui_token_type_tags = Arc::new(ArcSwap::new(Arc::new(sui_token_type_tags)));
         let (bridge_pause_tx, bridge_pause_rx) = tokio::sync::watch::channel(false);
@@ -1526,9 +3170,11 @@ mod tests {
             gas_object_ref.0,
             sui_token_type_tags.clone(),
             bridge_pause_rx,
-            metrics,
+            None,
+            metrics.clone(),
         )
         .await;
+        let executor = configure(executor);
 
         let (executor_handle, signing_tx, execution_tx) = executor.run_inner();
         handles.extend(executor_handle);
@@ -1550,6 +3196,7 @@ mod tests {
             sui_address,
             sui_token_type_tags,
             bridge_pause_tx,
+            metrics,
         )
     }
 }
@@ -0,0 +1,174 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Splits the latency of token transfers into the part spent waiting for the source
+//! chain and the part spent in the node. The finality delay is the time from the block
+//! (Eth) or checkpoint (Sui) of the action until the orchestrator received it from the
+//! syncer, i.e. finality plus polling delay. The processing latency is the time from
+//! then until the action was executed on Sui.
+
+use crate::metrics::BridgeMetrics;
+use crate::timestamps::{elapsed_ms, now_ms};
+use crate::types::{BridgeAction, BridgeActionDigest};
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::Instant;
+
+// Actions that are never executed, e.g. quarantined ones, are evicted oldest first
+// beyond this many
+const MAX_TRACKED_ACTIONS: usize = 10_000;
+
+fn direction(action: &BridgeAction) -> Option<&'static str> {
+    match action {
+        BridgeAction::EthToSuiBridgeAction(_) => Some("eth_to_sui"),
+        BridgeAction::SuiToEthBridgeAction(_) => Some("sui_to_eth"),
+        _ => None,
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct ActionLatencyTracker {
+    // When the orchestrator received the token transfers that are not executed yet
+    observed: Mutex<HashMap<BridgeActionDigest, (&'static str, Instant)>>,
+}
+
+impl ActionLatencyTracker {
+    /// Records that the orchestrator received `action`, which happened on its source
+    /// chain at `chain_timestamp_ms`. The finality delay is not recorded when the
+    /// timestamp is unknown.
+    pub fn record_observed(
+        &self,
+        action: &BridgeAction,
+        chain_timestamp_ms: Option<u64>,
+        metrics: &BridgeMetrics,
+    ) {
+        self.record_observed_at(
+            action,
+            chain_timestamp_ms,
+            now_ms(),
+            Instant::now(),
+            metrics,
+        )
+    }
+
+    /// Records that `action` was executed on Sui.
+    pub fn record_executed(&self, action: &BridgeAction, metrics: &BridgeMetrics) {
+        self.record_executed_at(action, Instant::now(), metrics)
+    }
+
+    fn record_observed_at(
+        &self,
+        action: &BridgeAction,
+        chain_timestamp_ms: Option<u64>,
+        now_ms: u64,
+        now: Instant,
+        metrics: &BridgeMetrics,
+    ) {
+        let Some(direction) = direction(action) else {
+            return;
+        };
+        if let Some(chain_timestamp_ms) = chain_timestamp_ms {
+            // Clocks may disagree slightly, a timestamp in the future counts as no delay
+            let delay_ms = elapsed_ms(chain_timestamp_ms, now_ms);
+            metrics
+                .action_finality_delay
+                .with_label_values(&[direction])
+                .observe(delay_ms as f64 / 1000.0);
+        }
+        let mut observed = self.observed.lock().unwrap();
+        if observed.len() >= MAX_TRACKED_ACTIONS {
+            if let Some(oldest) = observed
+                .iter()
+                .min_by_key(|(_, (_, observed_at))| *observed_at)
+                .map(|(digest, _)| *digest)
+            {
+                observed.remove(&oldest);
+            }
+        }
+        observed.insert(action.digest(), (direction, now));
+    }
+
+    fn record_executed_at(&self, action: &BridgeAction, now: Instant, metrics: &BridgeMetrics) {
+        // Actions recovered after a restart were observed by the previous run
+        let Some((direction, observed_at)) = self.observed.lock().unwrap().remove(&action.digest())
+        else {
+            return;
+        };
+        metrics
+            .action_processing_latency
+            .with_label_values(&[direction])
+            .observe(now.saturating_duration_since(observed_at).as_secs_f64());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{get_test_eth_to_sui_bridge_action, get_test_sui_to_eth_bridge_action};
+    use std::time::Duration;
+
+    #[test]
+    fn test_finality_and_processing_are_recorded_separately() {
+        let metrics = BridgeMetrics::for_testing();
+        let tracker = ActionLatencyTracker::default();
+        let eth_to_sui = get_test_eth_to_sui_bridge_action(None, None, None, None);
+        let sui_to_eth =
+            get_test_sui_to_eth_bridge_action(None, None, None, None, None, None, None);
+        let start = Instant::now();
+        let now_ms = 1_700_000_000_000;
+
+        // The deposit block is 12 minutes old, the checkpoint 3 seconds
+        tracker.record_observed_at(&eth_to_sui, Some(now_ms - 720_000), now_ms, start, &metrics);
+        tracker.record_observed_at(&sui_to_eth, Some(now_ms - 3_000), now_ms, start, &metrics);
+        tracker.record_executed_at(&eth_to_sui, start + Duration::from_secs(5), &metrics);
+        tracker.record_executed_at(&sui_to_eth, start + Duration::from_secs(2), &metrics);
+
+        let finality = |direction: &str| {
+            let histogram = metrics
+                .action_finality_delay
+                .with_label_values(&[direction]);
+            (histogram.get_sample_count(), histogram.get_sample_sum())
+        };
+        let processing = |direction: &str| {
+            let histogram = metrics
+                .action_processing_latency
+                .with_label_values(&[direction]);
+            (histogram.get_sample_count(), histogram.get_sample_sum())
+        };
+        assert_eq!(finality("eth_to_sui"), (1, 720.0));
+        assert_eq!(finality("sui_to_eth"), (1, 3.0));
+        assert_eq!(processing("eth_to_sui"), (1, 5.0));
+        assert_eq!(processing("sui_to_eth"), (1, 2.0));
+
+        // Executing again, or executing an action observed by a previous run, records
+        // nothing
+        tracker.record_executed_at(&eth_to_sui, start + Duration::from_secs(9), &metrics);
+        assert_eq!(processing("eth_to_sui"), (1, 5.0));
+    }
+
+    #[test]
+    fn test_unknown_chain_timestamp() {
+        let metrics = BridgeMetrics::for_testing();
+        let tracker = ActionLatencyTracker::default();
+        let action = get_test_eth_to_sui_bridge_action(None, None, None, None);
+        let start = Instant::now();
+
+        // A block timestamp in the future counts as no delay
+        tracker.record_observed_at(&action, Some(2_000), 1_000, start, &metrics);
+        tracker.record_observed_at(&action, None, 1_000, start, &metrics);
+        tracker.record_executed_at(&action, start + Duration::from_secs(1), &metrics);
+
+        let finality = metrics
+            .action_finality_delay
+            .with_label_values(&["eth_to_sui"]);
+        assert_eq!(finality.get_sample_count(), 1);
+        assert_eq!(finality.get_sample_sum(), 0.0);
+        assert_eq!(
+            metrics
+                .action_processing_latency
+                .with_label_values(&["eth_to_sui"])
+                .get_sample_count(),
+            1
+        );
+    }
+}
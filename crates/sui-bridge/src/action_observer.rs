@@ -0,0 +1,281 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! BridgeActionObserver takes the place of `BridgeActionExecutor` in observer mode. It
+//! neither requests signatures nor submits transactions, but waits for every token
+//! transfer to be approved on chain by the committee, and then records it as executed.
+
+use crate::action_executor::{BridgeActionExecutionWrapper, BridgeActionExecutorTrait};
+use crate::api_types::StreamActionStatus;
+use crate::event_stream::EventStream;
+use crate::metrics::BridgeMetrics;
+use crate::storage::BridgeOrchestratorTables;
+use crate::sui_client::{SuiClient, SuiClientInner};
+use crate::types::{BridgeAction, BridgeActionStatus};
+use mysten_metrics::spawn_logged_monitored_task;
+use std::sync::Arc;
+use std::time::Duration;
+use tracing::{info, warn};
+
+pub const DEFAULT_OBSERVER_CHECK_INTERVAL: Duration = Duration::from_secs(10);
+
+pub struct BridgeActionObserver<C> {
+    sui_client: Arc<SuiClient<C>>,
+    store: Arc<BridgeOrchestratorTables>,
+    channel_size: usize,
+    check_interval: Duration,
+    event_stream: Option<Arc<EventStream>>,
+    metrics: Arc<BridgeMetrics>,
+}
+
+impl<C> BridgeActionObserver<C>
+where
+    C: SuiClientInner + 'static,
+{
+    pub fn new(
+        sui_client: Arc<SuiClient<C>>,
+        store: Arc<BridgeOrchestratorTables>,
+        channel_size: usize,
+        metrics: Arc<BridgeMetrics>,
+    ) -> Self {
+        Self {
+            sui_client,
+            store,
+            channel_size,
+            check_interval: DEFAULT_OBSERVER_CHECK_INTERVAL,
+            event_stream: None,
+            metrics,
+        }
+    }
+
+    /// Overrides how long to wait between two checks of a pending action.
+    pub fn with_check_interval(mut self, check_interval: Duration) -> Self {
+        self.check_interval = check_interval;
+        self
+    }
+
+    /// Streams the token transfers approved on chain to `event_stream`.
+    pub fn with_event_stream(mut self, event_stream: Arc<EventStream>) -> Self {
+        self.event_stream = Some(event_stream);
+        self
+    }
+
+    async fn run_observation_loop(
+        self,
+        mut receiver: mysten_metrics::metered_channel::Receiver<BridgeActionExecutionWrapper>,
+    ) {
+        info!("Starting run_observation_loop");
+        while let Some(BridgeActionExecutionWrapper(action, ..)) = receiver.recv().await {
+            match &action {
+                BridgeAction::SuiToEthBridgeAction(_) | BridgeAction::EthToSuiBridgeAction(_) => {}
+                _ => {
+                    warn!(
+                        "Observer only verifies token transfers, leaving action pending: {:?}",
+                        action
+                    );
+                    continue;
+                }
+            }
+            self.metrics.action_observer_pending_actions.inc();
+            spawn_logged_monitored_task!(
+                Self::observe_action(
+                    self.sui_client.clone(),
+                    self.store.clone(),
+                    action,
+                    self.check_interval,
+                    self.event_stream.clone(),
+                    self.metrics.clone(),
+                ),
+                "observe_action"
+            );
+        }
+    }
+
+    // Waits until the token transfer is approved or claimed on chain
+    async fn observe_action(
+        sui_client: Arc<SuiClient<C>>,
+        store: Arc<BridgeOrchestratorTables>,
+        action: BridgeAction,
+        check_interval: Duration,
+        event_stream: Option<Arc<EventStream>>,
+        metrics: Arc<BridgeMetrics>,
+    ) {
+        loop {
+            let status = sui_client
+                .get_token_transfer_action_onchain_status_until_success(
+                    action.chain_id() as u8,
+                    action.seq_number(),
+                )
+                .await;
+            match status {
+                BridgeActionStatus::Approved | BridgeActionStatus::Claimed => break,
+                BridgeActionStatus::Pending | BridgeActionStatus::NotFound => {
+                    tokio::time::sleep(check_interval).await;
+                }
+            }
+        }
+        info!("Action approved on chain: {:?}", action);
+        if let Some(event_stream) = &event_stream {
+            event_stream.action_status(&action, StreamActionStatus::Executed, None);
+        }
+        store.mark_actions_executed(&[action]).unwrap_or_else(|e| {
+            panic!("Write to DB should not fail: {:?}", e);
+        });
+        metrics.action_observer_verified_actions.inc();
+        metrics.action_observer_pending_actions.dec();
+    }
+}
+
+impl<C> BridgeActionExecutorTrait for BridgeActionObserver<C>
+where
+    C: SuiClientInner + 'static,
+{
+    fn run(
+        self,
+    ) -> (
+        Vec<tokio::task::JoinHandle<()>>,
+        mysten_metrics::metered_channel::Sender<BridgeActionExecutionWrapper>,
+    ) {
+        let (sender, receiver) = mysten_metrics::metered_channel::channel(
+            self.channel_size,
+            &mysten_metrics::get_metrics()
+                .unwrap()
+                .channel_inflight
+                .with_label_values(&["observer_queue"]),
+        );
+        self.metrics
+            .channel_capacity
+            .with_label_values(&["observer_queue"])
+            .set(self.channel_size as i64);
+        let task = spawn_logged_monitored_task!(self.run_observation_loop(receiver));
+        (vec![task], sender)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::action_executor::CHANNEL_SIZE;
+    use crate::events::init_all_struct_tags;
+    use crate::events::tests::get_test_sui_event_and_action;
+    use crate::notional::NotionalTracker;
+    use crate::orchestrator::BridgeOrchestrator;
+    use crate::sui_mock_client::SuiMockClient;
+    use crate::test_utils::get_test_log_and_action;
+    use crate::transfer_minimums::TransferMinimums;
+    use crate::types::EthLog;
+    use ethers::types::{Address as EthAddress, TxHash};
+    use move_core_types::identifier::Identifier;
+    use prometheus::Registry;
+    use std::str::FromStr;
+    use std::time::Instant;
+
+    async fn wait_until(condition: impl Fn() -> bool) {
+        let start = Instant::now();
+        while !condition() {
+            assert!(
+                start.elapsed() < Duration::from_secs(10),
+                "Timed out waiting for condition"
+            );
+            tokio::time::sleep(Duration::from_millis(50)).await;
+        }
+    }
+
+    fn test_channel<T>(
+        name: &str,
+    ) -> (
+        mysten_metrics::metered_channel::Sender<T>,
+        mysten_metrics::metered_channel::Receiver<T>,
+    ) {
+        mysten_metrics::metered_channel::channel(
+            100,
+            &mysten_metrics::get_metrics()
+                .unwrap()
+                .channel_inflight
+                .with_label_values(&[name]),
+        )
+    }
+
+    #[tokio::test]
+    async fn test_observer_tracks_transfers_without_signing_or_submitting() {
+        telemetry_subscribers::init_for_testing();
+        let registry = Registry::new();
+        mysten_metrics::init_metrics(&registry);
+        init_all_struct_tags();
+        let metrics = Arc::new(BridgeMetrics::for_testing());
+        let temp_dir = tempfile::tempdir().unwrap();
+        let store = BridgeOrchestratorTables::new(temp_dir.path());
+        let sui_client_mock = SuiMockClient::default();
+        let sui_client = Arc::new(SuiClient::new_for_testing(sui_client_mock.clone()));
+
+        let (sui_events_tx, sui_events_rx) = test_channel("unit_test_sui_events_queue");
+        let (eth_events_tx, eth_events_rx) = test_channel("unit_test_eth_events_queue");
+        let (monitor_tx, _monitor_rx) = test_channel("monitor_queue");
+
+        let observer = BridgeActionObserver::new(
+            sui_client.clone(),
+            store.clone(),
+            CHANNEL_SIZE,
+            metrics.clone(),
+        )
+        .with_check_interval(Duration::from_millis(100));
+        let _handles = BridgeOrchestrator::new(
+            sui_client,
+            sui_events_rx,
+            eth_events_rx,
+            store.clone(),
+            monitor_tx,
+            Arc::new(TransferMinimums::default()),
+            Arc::new(NotionalTracker::default()),
+            metrics.clone(),
+        )
+        .run(observer)
+        .await;
+
+        // A transfer from Sui, approved by the committee while it's observed
+        let identifier = Identifier::from_str("test_observer").unwrap();
+        let (sui_event, sui_action) = get_test_sui_event_and_action(identifier.clone());
+        sui_client_mock.set_action_onchain_status(&sui_action, BridgeActionStatus::Pending);
+        sui_events_tx
+            .send((identifier, vec![sui_event]))
+            .await
+            .unwrap();
+        wait_until(|| metrics.action_observer_pending_actions.get() == 1).await;
+        assert!(store
+            .get_all_pending_actions()
+            .contains_key(&sui_action.digest()));
+
+        // A transfer from Eth, already approved
+        let address = EthAddress::random();
+        let (log, eth_action) = get_test_log_and_action(address, TxHash::random(), 10);
+        sui_client_mock.set_action_onchain_status(&eth_action, BridgeActionStatus::Approved);
+        let block_number = log.block_number.unwrap().as_u64();
+        eth_events_tx
+            .send((
+                address,
+                block_number,
+                vec![EthLog {
+                    tx_hash: log.transaction_hash.unwrap(),
+                    block_number,
+                    log_index_in_tx: 10,
+                    block_timestamp_ms: None,
+                    log,
+                }],
+            ))
+            .await
+            .unwrap();
+        wait_until(|| metrics.action_observer_verified_actions.get() == 1).await;
+
+        sui_client_mock.set_action_onchain_status(&sui_action, BridgeActionStatus::Approved);
+        wait_until(|| metrics.action_observer_verified_actions.get() == 2).await;
+        assert_eq!(metrics.action_observer_pending_actions.get(), 0);
+        assert!(store.get_all_pending_actions().is_empty());
+
+        // Nothing was signed or submitted
+        assert!(sui_client_mock.executed_transactions().is_empty());
+        assert_eq!(
+            metrics.action_executor_signing_queue_received_actions.get(),
+            0
+        );
+    }
+}
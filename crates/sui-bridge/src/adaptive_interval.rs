@@ -0,0 +1,166 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Polling intervals that follow the activity of what is polled. The interval halves,
+//! down to a minimum, after each poll that observed events, and doubles, up to a maximum,
+//! after some consecutive polls that observed none. A burst of events sets it to the
+//! minimum right away. This keeps the syncers responsive when transfers come in, without
+//! spending the RPC quota on polling idle chains at the same pace.
+
+use std::time::Duration;
+use tokio::time;
+
+pub const DEFAULT_EMPTY_POLLS_BEFORE_GROWTH: u32 = 3;
+pub const DEFAULT_BURST_EVENTS: usize = 10;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AdaptiveIntervalBounds {
+    pub min: Duration,
+    pub max: Duration,
+    /// Consecutive polls without events after which the interval doubles
+    pub empty_polls_before_growth: u32,
+    /// Events observed by a poll that set the interval to `min` at once
+    pub burst_events: usize,
+}
+
+impl AdaptiveIntervalBounds {
+    /// Bounds of an interval that never changes.
+    pub fn fixed(interval: Duration) -> Self {
+        Self {
+            min: interval,
+            max: interval,
+            empty_polls_before_growth: DEFAULT_EMPTY_POLLS_BEFORE_GROWTH,
+            burst_events: DEFAULT_BURST_EVENTS,
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct AdaptiveInterval {
+    bounds: AdaptiveIntervalBounds,
+    current: Duration,
+    empty_polls: u32,
+    // Observed since the last call to `next`
+    events: usize,
+}
+
+impl AdaptiveInterval {
+    /// Starts at the minimum, so that a node catching up polls at full pace.
+    pub fn new(bounds: AdaptiveIntervalBounds) -> Self {
+        Self {
+            bounds,
+            current: bounds.min,
+            empty_polls: 0,
+            events: 0,
+        }
+    }
+
+    pub fn current(&self) -> Duration {
+        self.current
+    }
+
+    /// Records `events` observed by the current poll. Returns true if they are a burst,
+    /// in which case the interval is already the minimum.
+    pub fn observe(&mut self, events: usize) -> bool {
+        self.events += events;
+        if self.events >= self.bounds.burst_events {
+            self.current = self.bounds.min;
+            return true;
+        }
+        false
+    }
+
+    /// Ends the current poll, returns the interval until the next one.
+    pub fn next(&mut self) -> Duration {
+        if std::mem::take(&mut self.events) > 0 {
+            self.empty_polls = 0;
+            self.current = (self.current / 2).max(self.bounds.min);
+        } else {
+            self.empty_polls += 1;
+            if self.empty_polls >= self.bounds.empty_polls_before_growth {
+                self.empty_polls = 0;
+                self.current = (self.current * 2).min(self.bounds.max);
+            }
+        }
+        self.current
+    }
+}
+
+/// Makes `interval` tick every `period` from now on, unless it already does. Missed ticks
+/// are skipped.
+pub fn set_period(interval: &mut time::Interval, period: Duration) {
+    if interval.period() != period {
+        *interval = time::interval_at(time::Instant::now() + period, period);
+        interval.set_missed_tick_behavior(time::MissedTickBehavior::Skip);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn bounds() -> AdaptiveIntervalBounds {
+        AdaptiveIntervalBounds {
+            min: Duration::from_millis(500),
+            max: Duration::from_secs(8),
+            empty_polls_before_growth: 2,
+            burst_events: 10,
+        }
+    }
+
+    // Returns the intervals after polls that observed `events` each
+    fn poll(interval: &mut AdaptiveInterval, events: &[usize]) -> Vec<u64> {
+        events
+            .iter()
+            .map(|events| {
+                interval.observe(*events);
+                interval.next().as_millis() as u64
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_adaptive_interval_grows_when_idle() {
+        let mut interval = AdaptiveInterval::new(bounds());
+        assert_eq!(interval.current(), Duration::from_millis(500));
+        assert_eq!(
+            poll(&mut interval, &[0; 12]),
+            vec![500, 1000, 1000, 2000, 2000, 4000, 4000, 8000, 8000, 8000, 8000, 8000]
+        );
+    }
+
+    #[test]
+    fn test_adaptive_interval_shrinks_with_events() {
+        let mut interval = AdaptiveInterval::new(bounds());
+        poll(&mut interval, &[0; 8]);
+        assert_eq!(interval.current(), Duration::from_secs(8));
+        assert_eq!(
+            poll(&mut interval, &[1, 2, 1, 1, 1, 1]),
+            vec![4000, 2000, 1000, 500, 500, 500]
+        );
+        // An empty poll in between events resets the count of empty polls
+        assert_eq!(
+            poll(&mut interval, &[0, 1, 0, 0]),
+            vec![500, 500, 500, 1000]
+        );
+    }
+
+    #[test]
+    fn test_adaptive_interval_snaps_to_min_on_burst() {
+        let mut interval = AdaptiveInterval::new(bounds());
+        poll(&mut interval, &[0; 8]);
+        assert!(!interval.observe(4));
+        assert_eq!(interval.current(), Duration::from_secs(8));
+        // The events of a poll add up
+        assert!(interval.observe(6));
+        assert_eq!(interval.current(), Duration::from_millis(500));
+        assert_eq!(interval.next(), Duration::from_millis(500));
+    }
+
+    #[test]
+    fn test_fixed_interval() {
+        let mut interval =
+            AdaptiveInterval::new(AdaptiveIntervalBounds::fixed(Duration::from_secs(2)));
+        assert_eq!(poll(&mut interval, &[0, 0, 0, 0, 100, 1]), vec![2000; 6]);
+    }
+}
@@ -0,0 +1,179 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Progress of the signature aggregations in flight, for operators to find the committee
+//! members that hold up an action. The aggregator starts tracking an action when it
+//! requests the signatures of the committee, records the answer of every member as it
+//! arrives, and stops tracking it once the action is certified, the aggregation fails or
+//! its future is dropped. It's served on `/admin/aggregation_status`.
+
+use crate::crypto::BridgeAuthorityPublicKeyBytes;
+use crate::timestamps::now_ms;
+use crate::types::BridgeAction;
+use arc_swap::ArcSwap;
+use std::collections::BTreeMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use sui_types::committee::StakeUnit;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MemberSigningStatus {
+    /// Requested, without an answer yet
+    Pending,
+    Signed,
+    /// Failed with the error of this category, e.g. `timeout`, see `auth_agg_request_failures`
+    Errored(String),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AggregationProgress {
+    // Tells apart the same action aggregated twice at once
+    id: u64,
+    pub action: BridgeAction,
+    pub started_at_ms: u64,
+    pub stake_collected: StakeUnit,
+    pub stake_required: StakeUnit,
+    /// The requested committee members
+    pub members: BTreeMap<BridgeAuthorityPublicKeyBytes, MemberSigningStatus>,
+}
+
+#[derive(Debug, Default)]
+pub struct AggregationStatus {
+    // The aggregations in flight, the one started first first
+    snapshot: ArcSwap<Vec<AggregationProgress>>,
+    next_id: AtomicU64,
+}
+
+impl AggregationStatus {
+    pub fn snapshot(&self) -> Arc<Vec<AggregationProgress>> {
+        self.snapshot.load_full()
+    }
+
+    /// Starts tracking the aggregation of the signatures of `members` for `action`, with
+    /// every member pending. It's tracked until the returned guard is dropped.
+    pub fn start(
+        self: &Arc<Self>,
+        action: &BridgeAction,
+        stake_required: StakeUnit,
+        members: impl IntoIterator<Item = BridgeAuthorityPublicKeyBytes>,
+    ) -> AggregationTracker {
+        let progress = AggregationProgress {
+            id: self.next_id.fetch_add(1, Ordering::Relaxed),
+            action: action.clone(),
+            started_at_ms: now_ms(),
+            stake_collected: 0,
+            stake_required,
+            members: members
+                .into_iter()
+                .map(|member| (member, MemberSigningStatus::Pending))
+                .collect(),
+        };
+        let id = progress.id;
+        self.update(|snapshot| snapshot.push(progress.clone()));
+        AggregationTracker {
+            status: self.clone(),
+            id,
+        }
+    }
+
+    fn update(&self, f: impl Fn(&mut Vec<AggregationProgress>)) {
+        self.snapshot.rcu(|snapshot| {
+            let mut snapshot = Vec::clone(snapshot);
+            f(&mut snapshot);
+            snapshot
+        });
+    }
+}
+
+/// An aggregation in flight, removed from the snapshot when dropped.
+#[derive(Debug)]
+pub struct AggregationTracker {
+    status: Arc<AggregationStatus>,
+    id: u64,
+}
+
+impl AggregationTracker {
+    /// Records the signature of `member`, which brought the stake collected to
+    /// `stake_collected`.
+    pub fn signed(&self, member: &BridgeAuthorityPublicKeyBytes, stake_collected: StakeUnit) {
+        self.update(|progress| {
+            progress.stake_collected = stake_collected;
+            progress
+                .members
+                .insert(member.clone(), MemberSigningStatus::Signed);
+        });
+    }
+
+    pub fn errored(&self, member: &BridgeAuthorityPublicKeyBytes, category: &str) {
+        self.update(|progress| {
+            progress.members.insert(
+                member.clone(),
+                MemberSigningStatus::Errored(category.to_string()),
+            );
+        });
+    }
+
+    fn update(&self, f: impl Fn(&mut AggregationProgress)) {
+        self.status.update(|snapshot| {
+            if let Some(progress) = snapshot.iter_mut().find(|p| p.id == self.id) {
+                f(progress);
+            }
+        });
+    }
+}
+
+impl Drop for AggregationTracker {
+    fn drop(&mut self) {
+        let id = self.id;
+        self.status
+            .update(|snapshot| snapshot.retain(|progress| progress.id != id));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{get_test_authority_and_key, get_test_sui_to_eth_bridge_action};
+
+    #[test]
+    fn test_aggregation_progress() {
+        let status = Arc::new(AggregationStatus::default());
+        let members = (0..3)
+            .map(|_| get_test_authority_and_key(2500, 12345).0.pubkey_bytes())
+            .collect::<Vec<_>>();
+        let action = get_test_sui_to_eth_bridge_action(None, None, Some(1), None, None, None, None);
+
+        let first = status.start(&action, 5001, members.clone());
+        // The same action aggregated again is tracked separately
+        let second = status.start(&action, 5001, members.clone());
+        first.signed(&members[0], 2500);
+        first.errored(&members[1], "timeout");
+        let snapshot = status.snapshot();
+        assert_eq!(snapshot.len(), 2);
+        assert_eq!(snapshot[0].stake_collected, 2500);
+        assert_eq!(
+            snapshot[0].members.get(&members[0]),
+            Some(&MemberSigningStatus::Signed)
+        );
+        assert_eq!(
+            snapshot[0].members.get(&members[1]),
+            Some(&MemberSigningStatus::Errored("timeout".to_string()))
+        );
+        assert_eq!(
+            snapshot[0].members.get(&members[2]),
+            Some(&MemberSigningStatus::Pending)
+        );
+        assert_eq!(snapshot[1].stake_collected, 0);
+        assert!(snapshot[1]
+            .members
+            .values()
+            .all(|status| *status == MemberSigningStatus::Pending));
+
+        drop(first);
+        let snapshot = status.snapshot();
+        assert_eq!(snapshot.len(), 1);
+        assert_eq!(snapshot[0].stake_collected, 0);
+        drop(second);
+        assert!(status.snapshot().is_empty());
+    }
+}
@@ -0,0 +1,404 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! `AlertSink` notifies a webhook of a few critical conditions directly, for operators
+//! that want to be paged without building alerts on the metrics. Components raise alerts
+//! without waiting on the webhook: an alert is dropped if its condition was already
+//! notified within the re-notify interval, and otherwise queued for `AlertPoster`, which
+//! posts alerts raised close together as one Slack-compatible JSON payload, with retries.
+
+use crate::config::AlertsConfig;
+use crate::metrics::BridgeMetrics;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+use tokio::sync::mpsc;
+use tracing::{info, warn};
+
+pub const DEFAULT_ALERT_RENOTIFY_INTERVAL: Duration = Duration::from_secs(3600);
+// Same as the warning of `doctor`
+pub const DEFAULT_LOW_GAS_BALANCE_MIST: u64 = crate::doctor::LOW_GAS_BALANCE_MIST;
+// Alerts raised while this many are waiting to be posted are dropped
+const ALERT_QUEUE_SIZE: usize = 100;
+// Alerts raised within this time of the first one are posted together
+const ALERT_BATCH_WINDOW: Duration = Duration::from_secs(1);
+const ALERT_POST_ATTEMPTS: u32 = 3;
+const ALERT_POST_RETRY_DELAY: Duration = Duration::from_secs(2);
+const ALERT_POST_TIMEOUT: Duration = Duration::from_secs(10);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Deserialize, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum AlertCondition {
+    /// The bridge was paused by an emergency action, e.g. when the committee trips its
+    /// circuit breaker
+    BridgePaused,
+    /// An action was quarantined after failing to execute too many times
+    ExecutionFailures,
+    /// The balance of the gas coin of the client is below `low-gas-balance-mist`
+    LowGasBalance,
+    /// A token transfer was processed on chain, but this node has no record of its nonce
+    NonceGap,
+    /// The reachable committee stake is below the threshold to certify token transfers
+    CommitteeStakeBelowQuorum,
+    /// The remote signer returned a signature that doesn't verify with the authority key
+    RemoteSignerMismatch,
+}
+
+impl AlertCondition {
+    pub const ALL: [AlertCondition; 6] = [
+        AlertCondition::BridgePaused,
+        AlertCondition::ExecutionFailures,
+        AlertCondition::LowGasBalance,
+        AlertCondition::NonceGap,
+        AlertCondition::CommitteeStakeBelowQuorum,
+        AlertCondition::RemoteSignerMismatch,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            AlertCondition::BridgePaused => "bridge_paused",
+            AlertCondition::ExecutionFailures => "execution_failures",
+            AlertCondition::LowGasBalance => "low_gas_balance",
+            AlertCondition::NonceGap => "nonce_gap",
+            AlertCondition::CommitteeStakeBelowQuorum => "committee_stake_below_quorum",
+            AlertCondition::RemoteSignerMismatch => "remote_signer_mismatch",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct Alert {
+    pub condition: AlertCondition,
+    pub message: String,
+    pub timestamp_ms: u64,
+}
+
+/// The body posted to the webhook. Slack shows `text`, other receivers can read `alerts`.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct AlertPayload {
+    pub text: String,
+    pub alerts: Vec<Alert>,
+}
+
+impl AlertPayload {
+    fn new(alerts: Vec<Alert>) -> Self {
+        let text = alerts
+            .iter()
+            .map(|alert| {
+                format!(
+                    "[sui-bridge] {}: {}",
+                    alert.condition.as_str(),
+                    alert.message
+                )
+            })
+            .collect::<Vec<_>>()
+            .join("\n");
+        Self { text, alerts }
+    }
+}
+
+pub struct AlertSink {
+    conditions: HashSet<AlertCondition>,
+    renotify_interval: Duration,
+    low_gas_balance: u64,
+    last_notified: Mutex<HashMap<AlertCondition, Instant>>,
+    sender: mpsc::Sender<Alert>,
+    metrics: Arc<BridgeMetrics>,
+}
+
+impl AlertSink {
+    /// Returns the sink that components raise alerts with, and the poster that must be
+    /// run to post them to `config.webhook_url`.
+    pub fn new(config: &AlertsConfig, metrics: Arc<BridgeMetrics>) -> (Self, AlertPoster) {
+        let (sender, receiver) = mpsc::channel(ALERT_QUEUE_SIZE);
+        let sink = Self {
+            conditions: config
+                .conditions
+                .clone()
+                .map(HashSet::from_iter)
+                .unwrap_or_else(|| HashSet::from(AlertCondition::ALL)),
+            renotify_interval: config
+                .renotify_interval_seconds
+                .map(Duration::from_secs)
+                .unwrap_or(DEFAULT_ALERT_RENOTIFY_INTERVAL),
+            low_gas_balance: config
+                .low_gas_balance_mist
+                .unwrap_or(DEFAULT_LOW_GAS_BALANCE_MIST),
+            last_notified: Mutex::new(HashMap::new()),
+            sender,
+            metrics: metrics.clone(),
+        };
+        let poster = AlertPoster {
+            client: reqwest::Client::new(),
+            webhook_url: config.webhook_url.clone(),
+            receiver,
+            retry_delay: ALERT_POST_RETRY_DELAY,
+            metrics,
+        };
+        (sink, poster)
+    }
+
+    /// Queues an alert for `condition`, unless it is disabled or was already notified
+    /// within the re-notify interval. Never waits.
+    pub fn raise(&self, condition: AlertCondition, message: String) {
+        if !self.conditions.contains(&condition) {
+            return;
+        }
+        {
+            let now = Instant::now();
+            let mut last_notified = self.last_notified.lock().unwrap();
+            if let Some(notified_at) = last_notified.get(&condition) {
+                if now.saturating_duration_since(*notified_at) < self.renotify_interval {
+                    return;
+                }
+            }
+            last_notified.insert(condition, now);
+        }
+        let alert = Alert {
+            condition,
+            message,
+            timestamp_ms: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .expect("Time went backwards")
+                .as_millis() as u64,
+        };
+        if let Err(e) = self.sender.try_send(alert) {
+            warn!("Dropping alert, too many are waiting to be posted: {:?}", e);
+            record(&self.metrics, condition, "dropped");
+        }
+    }
+
+    /// Raises `LowGasBalance` if `balance` is below the configured threshold.
+    pub fn check_gas_balance(&self, balance: u64) {
+        if balance < self.low_gas_balance {
+            self.raise(
+                AlertCondition::LowGasBalance,
+                format!(
+                    "Gas coin balance {} is below {} mist",
+                    balance, self.low_gas_balance
+                ),
+            );
+        }
+    }
+}
+
+pub struct AlertPoster {
+    client: reqwest::Client,
+    webhook_url: String,
+    receiver: mpsc::Receiver<Alert>,
+    retry_delay: Duration,
+    metrics: Arc<BridgeMetrics>,
+}
+
+impl AlertPoster {
+    pub async fn run(mut self) {
+        info!("Starting AlertPoster");
+        while let Some(alert) = self.receiver.recv().await {
+            let mut alerts = vec![alert];
+            let batch_deadline = tokio::time::sleep(ALERT_BATCH_WINDOW);
+            tokio::pin!(batch_deadline);
+            loop {
+                tokio::select! {
+                    _ = &mut batch_deadline => break,
+                    alert = self.receiver.recv() => match alert {
+                        Some(alert) => alerts.push(alert),
+                        None => break,
+                    },
+                }
+            }
+            self.post(alerts).await;
+        }
+    }
+
+    async fn post(&self, alerts: Vec<Alert>) {
+        let payload = AlertPayload::new(alerts);
+        for attempt in 1..=ALERT_POST_ATTEMPTS {
+            let result = self
+                .client
+                .post(&self.webhook_url)
+                .timeout(ALERT_POST_TIMEOUT)
+                .json(&payload)
+                .send()
+                .await
+                .and_then(|response| response.error_for_status());
+            match result {
+                Ok(_) => {
+                    for alert in &payload.alerts {
+                        record(&self.metrics, alert.condition, "sent");
+                    }
+                    return;
+                }
+                Err(e) => {
+                    warn!(attempt, "Failed to post alerts to webhook: {:?}", e);
+                    if attempt < ALERT_POST_ATTEMPTS {
+                        tokio::time::sleep(self.retry_delay * attempt).await;
+                    }
+                }
+            }
+        }
+        for alert in &payload.alerts {
+            record(&self.metrics, alert.condition, "failed");
+        }
+    }
+}
+
+fn record(metrics: &BridgeMetrics, condition: AlertCondition, result: &str) {
+    metrics
+        .alerts
+        .with_label_values(&[condition.as_str(), result])
+        .inc();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{extract::State, http::StatusCode, routing::post, Json, Router};
+    use sui_config::local_ip_utils::get_available_port;
+
+    #[derive(Clone, Default)]
+    struct WebhookStub {
+        payloads: Arc<Mutex<Vec<AlertPayload>>>,
+        // Requests failing before the stub accepts them
+        failures: Arc<Mutex<u32>>,
+    }
+
+    async fn handle(
+        State(stub): State<WebhookStub>,
+        Json(payload): Json<AlertPayload>,
+    ) -> StatusCode {
+        let mut failures = stub.failures.lock().unwrap();
+        if *failures > 0 {
+            *failures -= 1;
+            return StatusCode::INTERNAL_SERVER_ERROR;
+        }
+        stub.payloads.lock().unwrap().push(payload);
+        StatusCode::OK
+    }
+
+    async fn start_webhook_stub(stub: WebhookStub) -> String {
+        let port = get_available_port("127.0.0.1");
+        let listener = tokio::net::TcpListener::bind(format!("127.0.0.1:{}", port))
+            .await
+            .unwrap();
+        let router = Router::new().route("/hook", post(handle)).with_state(stub);
+        tokio::spawn(async move { axum::serve(listener, router).await.unwrap() });
+        format!("http://127.0.0.1:{}/hook", port)
+    }
+
+    fn setup(
+        webhook_url: String,
+        conditions: Option<Vec<AlertCondition>>,
+    ) -> (AlertSink, AlertPoster, Arc<BridgeMetrics>) {
+        let metrics = Arc::new(BridgeMetrics::for_testing());
+        let config = AlertsConfig {
+            webhook_url,
+            conditions,
+            renotify_interval_seconds: Some(600),
+            low_gas_balance_mist: Some(1000),
+        };
+        let (sink, mut poster) = AlertSink::new(&config, metrics.clone());
+        poster.retry_delay = Duration::from_millis(10);
+        (sink, poster, metrics)
+    }
+
+    fn count(metrics: &BridgeMetrics, condition: AlertCondition, result: &str) -> u64 {
+        metrics
+            .alerts
+            .with_label_values(&[condition.as_str(), result])
+            .get()
+    }
+
+    #[tokio::test]
+    async fn test_alerts_are_batched_and_deduplicated() {
+        let stub = WebhookStub::default();
+        *stub.failures.lock().unwrap() = 1;
+        let webhook_url = start_webhook_stub(stub.clone()).await;
+        let (sink, poster, metrics) = setup(
+            webhook_url,
+            Some(vec![
+                AlertCondition::LowGasBalance,
+                AlertCondition::CommitteeStakeBelowQuorum,
+            ]),
+        );
+        tokio::spawn(poster.run());
+
+        sink.check_gas_balance(1000);
+        sink.check_gas_balance(999);
+        // Within the re-notify interval
+        sink.check_gas_balance(10);
+        sink.raise(
+            AlertCondition::CommitteeStakeBelowQuorum,
+            "Reachable stake 3000 is below 3334".to_string(),
+        );
+        // Disabled
+        sink.raise(AlertCondition::NonceGap, "nonce 4".to_string());
+
+        let now = Instant::now();
+        while stub.payloads.lock().unwrap().is_empty() {
+            assert!(
+                now.elapsed() < Duration::from_secs(10),
+                "Timeout waiting for alerts"
+            );
+            tokio::time::sleep(Duration::from_millis(50)).await;
+        }
+        // The first request failed and was retried
+        let payloads = stub.payloads.lock().unwrap().clone();
+        assert_eq!(payloads.len(), 1);
+        let payload = &payloads[0];
+        assert_eq!(
+            payload
+                .alerts
+                .iter()
+                .map(|alert| (alert.condition, alert.message.as_str()))
+                .collect::<Vec<_>>(),
+            vec![
+                (
+                    AlertCondition::LowGasBalance,
+                    "Gas coin balance 999 is below 1000 mist"
+                ),
+                (
+                    AlertCondition::CommitteeStakeBelowQuorum,
+                    "Reachable stake 3000 is below 3334"
+                ),
+            ]
+        );
+        assert_eq!(
+            payload.text,
+            "[sui-bridge] low_gas_balance: Gas coin balance 999 is below 1000 mist\n\
+             [sui-bridge] committee_stake_below_quorum: Reachable stake 3000 is below 3334"
+        );
+        assert_eq!(count(&metrics, AlertCondition::LowGasBalance, "sent"), 1);
+        assert_eq!(
+            count(&metrics, AlertCondition::CommitteeStakeBelowQuorum, "sent"),
+            1
+        );
+        assert_eq!(count(&metrics, AlertCondition::NonceGap, "sent"), 0);
+
+        // The raw payload uses kebab-case conditions
+        let json = serde_json::to_value(payload).unwrap();
+        assert_eq!(json["alerts"][0]["condition"], "low-gas-balance");
+        assert!(json["alerts"][0]["timestamp_ms"].is_u64());
+    }
+
+    #[tokio::test]
+    async fn test_alerts_fail_after_retries() {
+        let stub = WebhookStub::default();
+        *stub.failures.lock().unwrap() = ALERT_POST_ATTEMPTS;
+        let webhook_url = start_webhook_stub(stub.clone()).await;
+        let (sink, poster, metrics) = setup(webhook_url, None);
+        tokio::spawn(poster.run());
+
+        sink.raise(AlertCondition::BridgePaused, "Bridge is paused".to_string());
+        let now = Instant::now();
+        while count(&metrics, AlertCondition::BridgePaused, "failed") == 0 {
+            assert!(
+                now.elapsed() < Duration::from_secs(10),
+                "Timeout waiting for alerts"
+            );
+            tokio::time::sleep(Duration::from_millis(50)).await;
+        }
+        assert!(stub.payloads.lock().unwrap().is_empty());
+        assert_eq!(count(&metrics, AlertCondition::BridgePaused, "sent"), 0);
+    }
+}
@@ -0,0 +1,2176 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! The public JSON schema of the bridge node routes and of the `sui-bridge-cli` outputs.
+//! Internal types can change their serialization at any time (e.g. when a field is added
+//! to a `BridgeAction`), so they are converted to the types below before they leave the
+//! process. Changes to these types must keep the JSON of the previous release readable:
+//! new fields are optional, and removing or renaming a field requires bumping
+//! `API_SCHEMA_VERSION`. The routes return the version in the `API_SCHEMA_VERSION_HEADER`
+//! header. Signing routes are the committee protocol and are not part of this schema.
+
+use crate::aggregation_status::{AggregationProgress, MemberSigningStatus};
+use crate::chain_health::{ChainHealth, ChainHealthState, PipelineChain};
+use crate::client::authority_scores::AuthorityScore;
+use crate::clock_drift::ClockDrift;
+use crate::crypto::BridgeAuthorityPublicKeyBytes;
+use crate::error::BridgeResult;
+use crate::executor_state::{ExecutorSnapshot, InFlightAction};
+use crate::metrics::ErrorCatalogEntry;
+use crate::signed_set::{SignedSetBucket, SIGNED_SET_BUCKET_MS};
+use crate::storage::{AdminAuditRecord, CursorResetRecord, ResetCursor};
+use crate::timestamps::elapsed_ms;
+use crate::tx_journal::{JournalCorruption, JournalRecord};
+use crate::types::{
+    BlocklistType, BridgeAction, BridgeActionDigest, BridgeActionType, EmergencyActionType,
+    GovernanceDryRun,
+};
+use crate::url_check::{RegisteredUrlCheck, RegisteredUrlReport};
+use axum::{http::HeaderValue, response::Response};
+use ethers::types::Address as EthAddress;
+use fastcrypto::encoding::{Base64, Encoding, Hex};
+use fastcrypto::traits::ToFromBytes;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use sui_types::base_types::ConciseableName;
+
+pub const API_SCHEMA_VERSION: u32 = 1;
+pub const API_SCHEMA_VERSION_HEADER: &str = "x-bridge-api-schema-version";
+
+/// Adds the schema version to the responses of the routes that return the types below.
+pub async fn add_schema_version(mut response: Response) -> Response {
+    response.headers_mut().insert(
+        API_SCHEMA_VERSION_HEADER,
+        HeaderValue::from(API_SCHEMA_VERSION),
+    );
+    response
+}
+
+fn encode_eth_address(address: &EthAddress) -> String {
+    // The Display of `EthAddress` is abbreviated
+    format!("{:?}", address)
+}
+
+fn encode_pubkey(pubkey: &BridgeAuthorityPublicKeyBytes) -> String {
+    Hex::encode(pubkey.as_bytes())
+}
+
+/// Action digests are hex encoded, without prefix, as `sui-bridge-cli` expects them.
+pub fn encode_action_digest(digest: &BridgeActionDigest) -> String {
+    Hex::encode(digest.inner())
+}
+
+/// Parses a hex encoded action digest, with or without `0x` prefix.
+pub fn decode_action_digest(digest: &str) -> anyhow::Result<BridgeActionDigest> {
+    let bytes = Hex::decode(digest.trim_start_matches("0x"))
+        .map_err(|e| anyhow::anyhow!("Invalid digest {}: {:?}", digest, e))?;
+    let bytes: [u8; 32] = bytes
+        .try_into()
+        .map_err(|_| anyhow::anyhow!("Invalid digest {}: expected 32 bytes", digest))?;
+    Ok(BridgeActionDigest::new(bytes))
+}
+
+/// Response of `/ping`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub struct NodeMetadata {
+    pub version: Option<String>,
+    /// Base64 encoded Ed25519 key that the node signs its metrics with
+    pub metrics_pubkey: Option<String>,
+}
+
+/// Response of `/health`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub struct NodeHealth {
+    /// None until the registered url was checked once
+    pub registered_url: Option<RegisteredUrl>,
+    /// None in the responses of nodes that don't report it
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pipelines: Option<PipelinesHealth>,
+    /// None in the responses of nodes that don't report it
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub clock: Option<ClockHealth>,
+}
+
+/// Health of the Sui and Eth pipelines. The node is degraded, not down, while only one
+/// of them is.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub struct PipelinesHealth {
+    pub status: PipelineStatus,
+    pub sui: ChainPipelineHealth,
+    pub eth: ChainPipelineHealth,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub struct ChainPipelineHealth {
+    pub status: PipelineStatus,
+    /// Failed RPC queries since the last successful one
+    pub consecutive_failures: u64,
+    pub last_success_ms: Option<u64>,
+    pub last_error: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PipelineStatus {
+    Unknown,
+    Healthy,
+    Degraded,
+    Down,
+}
+
+impl From<crate::chain_health::PipelineStatus> for PipelineStatus {
+    fn from(status: crate::chain_health::PipelineStatus) -> Self {
+        match status {
+            crate::chain_health::PipelineStatus::Unknown => PipelineStatus::Unknown,
+            crate::chain_health::PipelineStatus::Healthy => PipelineStatus::Healthy,
+            crate::chain_health::PipelineStatus::Degraded => PipelineStatus::Degraded,
+            crate::chain_health::PipelineStatus::Down => PipelineStatus::Down,
+        }
+    }
+}
+
+impl From<&ChainHealthState> for ChainPipelineHealth {
+    fn from(state: &ChainHealthState) -> Self {
+        Self {
+            status: state.status().into(),
+            consecutive_failures: state.consecutive_failures,
+            last_success_ms: state.last_success_ms,
+            last_error: state.last_error.clone(),
+        }
+    }
+}
+
+impl From<&ChainHealth> for PipelinesHealth {
+    fn from(health: &ChainHealth) -> Self {
+        let sui = health.state(PipelineChain::Sui);
+        let eth = health.state(PipelineChain::Eth);
+        Self {
+            status: crate::chain_health::PipelineStatus::combine(&[sui.status(), eth.status()])
+                .into(),
+            sui: ChainPipelineHealth::from(&sui),
+            eth: ChainPipelineHealth::from(&eth),
+        }
+    }
+}
+
+/// Drift of the local clock from the chains, the local time minus the chain time. The
+/// node is degraded while it's above the maximum for either chain.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub struct ClockHealth {
+    pub status: PipelineStatus,
+    pub max_drift_ms: u64,
+    /// None until the chain was compared with once
+    pub sui_drift_ms: Option<i64>,
+    pub eth_drift_ms: Option<i64>,
+    /// Whether signing requests are refused because of the drift
+    pub refusing_signatures: bool,
+}
+
+impl From<&ClockDrift> for ClockHealth {
+    fn from(drift: &ClockDrift) -> Self {
+        let chains = [PipelineChain::Sui, PipelineChain::Eth];
+        let status = if chains.iter().any(|chain| drift.is_drifting(*chain)) {
+            PipelineStatus::Degraded
+        } else if chains.iter().any(|chain| drift.drift_ms(*chain).is_some()) {
+            PipelineStatus::Healthy
+        } else {
+            PipelineStatus::Unknown
+        };
+        Self {
+            status,
+            max_drift_ms: drift.settings().max_drift_ms,
+            sui_drift_ms: drift.drift_ms(PipelineChain::Sui),
+            eth_drift_ms: drift.drift_ms(PipelineChain::Eth),
+            refusing_signatures: drift.is_refusing_signatures(),
+        }
+    }
+}
+
+/// Response of `/ready`, served with 200 when the node is ready and 503 otherwise.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub struct ReadinessState {
+    pub ready: bool,
+    /// Empty when the node is ready
+    pub reasons: Vec<NotReadyReason>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "reason", rename_all = "snake_case")]
+pub enum NotReadyReason {
+    StorageNotOpen,
+    CommitteeNotLoaded,
+    /// The syncer of `chain` didn't complete an iteration yet
+    SyncerNotStarted {
+        chain: String,
+    },
+    /// The last iteration of the syncer of `chain` failed, or it was stopped
+    SyncerFailing {
+        chain: String,
+        error: String,
+    },
+    ServerNotBound,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub struct RegisteredUrl {
+    pub check: RegisteredUrlStatus,
+    /// Whether the node answered through the registered url. None if it was not probed.
+    pub reachable: Option<bool>,
+    pub checked_at_ms: u64,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "status", rename_all = "snake_case")]
+pub enum RegisteredUrlStatus {
+    Match {
+        url: String,
+    },
+    Mismatch {
+        registered_url: String,
+        external_url: String,
+    },
+    NotRegistered,
+    NotConfigured {
+        registered_url: String,
+    },
+}
+
+impl From<&RegisteredUrlReport> for RegisteredUrl {
+    fn from(report: &RegisteredUrlReport) -> Self {
+        let check = match &report.check {
+            RegisteredUrlCheck::Match { url } => RegisteredUrlStatus::Match { url: url.clone() },
+            RegisteredUrlCheck::Mismatch {
+                registered_url,
+                external_url,
+            } => RegisteredUrlStatus::Mismatch {
+                registered_url: registered_url.clone(),
+                external_url: external_url.clone(),
+            },
+            RegisteredUrlCheck::NotRegistered => RegisteredUrlStatus::NotRegistered,
+            RegisteredUrlCheck::NotConfigured { registered_url } => {
+                RegisteredUrlStatus::NotConfigured {
+                    registered_url: registered_url.clone(),
+                }
+            }
+        };
+        Self {
+            check,
+            reachable: report.reachable,
+            checked_at_ms: report.checked_at_ms,
+        }
+    }
+}
+
+/// An entry of `/admin/errors`, which returns the entries by counter name.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub struct ErrorEntry {
+    pub timestamp_ms: u64,
+    pub message: String,
+    /// Digests and addresses relevant to the error, e.g. action digest or tx digest.
+    pub digests: Vec<String>,
+}
+
+impl From<&ErrorCatalogEntry> for ErrorEntry {
+    fn from(entry: &ErrorCatalogEntry) -> Self {
+        Self {
+            timestamp_ms: entry.timestamp_ms,
+            message: entry.message.clone(),
+            digests: entry.digests.clone(),
+        }
+    }
+}
+
+/// An entry of `/admin/committee_scores`, which returns the scores by hex encoded pubkey.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub struct CommitteeMemberScore {
+    pub ewma_latency_ms: u64,
+    /// In basis points
+    pub failure_rate_bps: u64,
+    pub last_success_timestamp_ms: Option<u64>,
+}
+
+impl From<&AuthorityScore> for CommitteeMemberScore {
+    fn from(score: &AuthorityScore) -> Self {
+        Self {
+            ewma_latency_ms: score.ewma_latency_ms,
+            failure_rate_bps: score.failure_rate_bps,
+            last_success_timestamp_ms: score.last_success_timestamp_ms,
+        }
+    }
+}
+
+/// Response of `/admin/errors/requesters`, the requesters that sent the most signing
+/// requests in the last `window_secs`, most requests first.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub struct RequesterSummary {
+    pub window_secs: u64,
+    pub requesters: Vec<RequesterRate>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub struct RequesterRate {
+    /// `cert:<subject>`, `key:<base64 key>`, `ip:<salted hash>`, or `other` for the
+    /// requesters beyond the number of labels of the node
+    pub requester: String,
+    pub requests: u64,
+    /// Requests that were not answered with a success
+    pub errors: u64,
+}
+
+impl From<&crate::server::requester::RequesterRate> for RequesterRate {
+    fn from(rate: &crate::server::requester::RequesterRate) -> Self {
+        Self {
+            requester: rate.requester.clone(),
+            requests: rate.requests,
+            errors: rate.errors,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CursorComponent {
+    Eth,
+    Sui,
+}
+
+/// Request of `/admin/reset_cursor`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub struct ResetCursorRequest {
+    pub component: CursorComponent,
+    /// Address of the Eth contract, or name of the Sui bridge module
+    pub identifier: String,
+    /// The last processed Eth block, or the last processed Sui event as `<tx digest>:<event seq>`
+    pub new_value: String,
+    /// Resets even when events between the current and the new cursor were not processed
+    #[serde(default)]
+    pub force: bool,
+}
+
+/// Response of `/admin/reset_cursor`. The values have the format of `new_value` in the request.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub struct CursorReset {
+    pub component: CursorComponent,
+    pub identifier: String,
+    /// None if the cursor was never set
+    pub old_value: Option<String>,
+    pub new_value: String,
+    pub forced: bool,
+    pub reset_at_ms: u64,
+}
+
+impl From<&CursorResetRecord> for CursorReset {
+    fn from(record: &CursorResetRecord) -> Self {
+        let event_id =
+            |id: &sui_types::event::EventID| format!("{}:{}", id.tx_digest, id.event_seq);
+        let (component, identifier, old_value, new_value) = match &record.cursor {
+            ResetCursor::Eth {
+                contract,
+                old_block,
+                new_block,
+            } => (
+                CursorComponent::Eth,
+                encode_eth_address(contract),
+                old_block.map(|block| block.to_string()),
+                new_block.to_string(),
+            ),
+            ResetCursor::Sui {
+                module,
+                old_event_id,
+                new_event_id,
+            } => (
+                CursorComponent::Sui,
+                module.to_string(),
+                old_event_id.as_ref().map(event_id),
+                event_id(new_event_id),
+            ),
+        };
+        Self {
+            component,
+            identifier,
+            old_value,
+            new_value,
+            forced: record.forced,
+            reset_at_ms: record.reset_at_ms,
+        }
+    }
+}
+
+/// Response of `/admin/executor_state`, and `executor` of the output of
+/// `sui-bridge-cli node-status`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub struct ExecutorState {
+    /// The in flight action that is furthest along the stages
+    pub current_action: Option<ExecutorAction>,
+    pub in_flight_actions: Vec<ExecutorAction>,
+    /// The head of the queue of actions waiting for signature aggregation
+    pub queued_actions: Vec<QueuedAction>,
+    pub bridge_paused: bool,
+    /// None until the executor fetched its gas coin
+    pub gas_coin: Option<GasCoinState>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub struct ExecutorAction {
+    pub action_digest: String,
+    pub action_type: ActionType,
+    pub nonce: u64,
+    pub stage: ExecutorStage,
+    pub time_in_stage_ms: u64,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub struct QueuedAction {
+    pub action_digest: String,
+    pub action_type: ActionType,
+    pub nonce: u64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ExecutorStage {
+    Aggregating,
+    Submitting,
+    AwaitingEffects,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub struct GasCoinState {
+    pub object_id: String,
+    pub version: u64,
+    pub balance: u64,
+}
+
+impl ExecutorState {
+    pub fn new(snapshot: &ExecutorSnapshot, now_ms: u64) -> Self {
+        let executor_action = |in_flight: &InFlightAction| ExecutorAction {
+            action_digest: encode_action_digest(&in_flight.action.digest()),
+            action_type: ActionType::from(in_flight.action.action_type()),
+            nonce: in_flight.action.seq_number(),
+            stage: match in_flight.stage {
+                crate::executor_state::ExecutorStage::Aggregating => ExecutorStage::Aggregating,
+                crate::executor_state::ExecutorStage::Submitting => ExecutorStage::Submitting,
+                crate::executor_state::ExecutorStage::AwaitingEffects => {
+                    ExecutorStage::AwaitingEffects
+                }
+            },
+            time_in_stage_ms: elapsed_ms(in_flight.stage_entered_at_ms, now_ms),
+        };
+        Self {
+            current_action: snapshot.current().map(executor_action),
+            in_flight_actions: snapshot.in_flight.iter().map(executor_action).collect(),
+            queued_actions: snapshot
+                .queued
+                .iter()
+                .map(|action| QueuedAction {
+                    action_digest: encode_action_digest(&action.digest()),
+                    action_type: ActionType::from(action.action_type()),
+                    nonce: action.seq_number(),
+                })
+                .collect(),
+            bridge_paused: snapshot.bridge_paused,
+            gas_coin: snapshot
+                .gas_coin
+                .map(|((object_id, version, _), balance)| GasCoinState {
+                    object_id: object_id.to_string(),
+                    version: version.value(),
+                    balance,
+                }),
+        }
+    }
+}
+
+/// Entry of `/admin/aggregation_status`, which returns the signature aggregations in
+/// flight, the one started first first. Committee members are in their short form.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub struct AggregationStatus {
+    pub action_digest: String,
+    pub action_type: ActionType,
+    pub nonce: u64,
+    pub time_aggregating_ms: u64,
+    pub stake_collected: u64,
+    pub stake_required: u64,
+    pub signed: Vec<String>,
+    pub errored: Vec<MemberError>,
+    /// Requested members that haven't answered yet
+    pub pending: Vec<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub struct MemberError {
+    pub member: String,
+    /// As in `bridge_auth_agg_request_failures`, e.g. `timeout`
+    pub category: String,
+}
+
+impl AggregationStatus {
+    pub fn new(progress: &AggregationProgress, now_ms: u64) -> Self {
+        let mut status = Self {
+            action_digest: encode_action_digest(&progress.action.digest()),
+            action_type: ActionType::from(progress.action.action_type()),
+            nonce: progress.action.seq_number(),
+            time_aggregating_ms: elapsed_ms(progress.started_at_ms, now_ms),
+            stake_collected: progress.stake_collected,
+            stake_required: progress.stake_required,
+            signed: vec![],
+            errored: vec![],
+            pending: vec![],
+        };
+        for (member, member_status) in &progress.members {
+            let member = member.concise_owned();
+            match member_status {
+                MemberSigningStatus::Pending => status.pending.push(member),
+                MemberSigningStatus::Signed => status.signed.push(member),
+                MemberSigningStatus::Errored(category) => status.errored.push(MemberError {
+                    member,
+                    category: category.clone(),
+                }),
+            }
+        }
+        status
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub enum ActionType {
+    TokenTransfer,
+    UpdateCommitteeBlocklist,
+    EmergencyButton,
+    LimitUpdate,
+    AssetPriceUpdate,
+    EvmContractUpgrade,
+    AddTokensOnSui,
+    AddTokensOnEvm,
+}
+
+impl From<BridgeActionType> for ActionType {
+    fn from(action_type: BridgeActionType) -> Self {
+        match action_type {
+            BridgeActionType::TokenTransfer => Self::TokenTransfer,
+            BridgeActionType::UpdateCommitteeBlocklist => Self::UpdateCommitteeBlocklist,
+            BridgeActionType::EmergencyButton => Self::EmergencyButton,
+            BridgeActionType::LimitUpdate => Self::LimitUpdate,
+            BridgeActionType::AssetPriceUpdate => Self::AssetPriceUpdate,
+            BridgeActionType::EvmContractUpgrade => Self::EvmContractUpgrade,
+            BridgeActionType::AddTokensOnSui => Self::AddTokensOnSui,
+            BridgeActionType::AddTokensOnEvm => Self::AddTokensOnEvm,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BlocklistOperation {
+    Blocklist,
+    Unblocklist,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum EmergencyOperation {
+    Pause,
+    Unpause,
+}
+
+/// A `BridgeAction`. Chain ids are the numeric `BridgeChainId`s, Sui digests are base58
+/// encoded, Eth hashes and addresses and Sui addresses are 0x prefixed hex.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "action_type", rename_all = "PascalCase")]
+pub enum Action {
+    #[serde(rename_all = "snake_case")]
+    TokenTransfer {
+        nonce: u64,
+        source_chain_id: u8,
+        destination_chain_id: u8,
+        /// The Sui tx digest or the Eth tx hash that emitted the transfer
+        source_tx: String,
+        source_event_index: u16,
+        sender: String,
+        recipient: String,
+        token_id: u8,
+        /// With the decimals of the token on Sui
+        amount: u64,
+    },
+    #[serde(rename_all = "snake_case")]
+    UpdateCommitteeBlocklist {
+        nonce: u64,
+        chain_id: u8,
+        operation: BlocklistOperation,
+        /// Hex encoded bridge authority pubkeys
+        members: Vec<String>,
+    },
+    #[serde(rename_all = "snake_case")]
+    EmergencyButton {
+        nonce: u64,
+        chain_id: u8,
+        operation: EmergencyOperation,
+    },
+    #[serde(rename_all = "snake_case")]
+    LimitUpdate {
+        nonce: u64,
+        chain_id: u8,
+        sending_chain_id: u8,
+        /// 4 decimal places, namely 1 USD = 10000
+        new_usd_limit: u64,
+    },
+    #[serde(rename_all = "snake_case")]
+    AssetPriceUpdate {
+        nonce: u64,
+        chain_id: u8,
+        token_id: u8,
+        new_usd_price: u64,
+    },
+    #[serde(rename_all = "snake_case")]
+    EvmContractUpgrade {
+        nonce: u64,
+        chain_id: u8,
+        proxy_address: String,
+        new_impl_address: String,
+        call_data: String,
+    },
+    #[serde(rename_all = "snake_case")]
+    AddTokensOnSui {
+        nonce: u64,
+        chain_id: u8,
+        native: bool,
+        token_ids: Vec<u8>,
+        token_type_names: Vec<String>,
+        token_prices: Vec<u64>,
+    },
+    #[serde(rename_all = "snake_case")]
+    AddTokensOnEvm {
+        nonce: u64,
+        chain_id: u8,
+        native: bool,
+        token_ids: Vec<u8>,
+        token_addresses: Vec<String>,
+        token_sui_decimals: Vec<u8>,
+        token_prices: Vec<u64>,
+    },
+}
+
+impl From<&BridgeAction> for Action {
+    fn from(action: &BridgeAction) -> Self {
+        match action {
+            BridgeAction::SuiToEthBridgeAction(a) => {
+                let event = &a.sui_bridge_event;
+                Self::TokenTransfer {
+                    nonce: event.nonce,
+                    source_chain_id: event.sui_chain_id as u8,
+                    destination_chain_id: event.eth_chain_id as u8,
+                    source_tx: a.sui_tx_digest.to_string(),
+                    source_event_index: a.sui_tx_event_index,
+                    sender: event.sui_address.to_string(),
+                    recipient: encode_eth_address(&event.eth_address),
+                    token_id: event.token_id,
+                    amount: event.amount_sui_adjusted,
+                }
+            }
+            BridgeAction::EthToSuiBridgeAction(a) => {
+                let event = &a.eth_bridge_event;
+                Self::TokenTransfer {
+                    nonce: event.nonce,
+                    source_chain_id: event.eth_chain_id as u8,
+                    destination_chain_id: event.sui_chain_id as u8,
+                    source_tx: format!("{:?}", a.eth_tx_hash),
+                    source_event_index: a.eth_event_index,
+                    sender: encode_eth_address(&event.eth_address),
+                    recipient: event.sui_address.to_string(),
+                    token_id: event.token_id,
+                    amount: event.sui_adjusted_amount,
+                }
+            }
+            BridgeAction::BlocklistCommitteeAction(a) => Self::UpdateCommitteeBlocklist {
+                nonce: a.nonce,
+                chain_id: a.chain_id as u8,
+                operation: match a.blocklist_type {
+                    BlocklistType::Blocklist => BlocklistOperation::Blocklist,
+                    BlocklistType::Unblocklist => BlocklistOperation::Unblocklist,
+                },
+                members: a.members_to_update.iter().map(encode_pubkey).collect(),
+            },
+            BridgeAction::EmergencyAction(a) => Self::EmergencyButton {
+                nonce: a.nonce,
+                chain_id: a.chain_id as u8,
+                operation: match a.action_type {
+                    EmergencyActionType::Pause => EmergencyOperation::Pause,
+                    EmergencyActionType::Unpause => EmergencyOperation::Unpause,
+                },
+            },
+            BridgeAction::LimitUpdateAction(a) => Self::LimitUpdate {
+                nonce: a.nonce,
+                chain_id: a.chain_id as u8,
+                sending_chain_id: a.sending_chain_id as u8,
+                new_usd_limit: a.new_usd_limit,
+            },
+            BridgeAction::AssetPriceUpdateAction(a) => Self::AssetPriceUpdate {
+                nonce: a.nonce,
+                chain_id: a.chain_id as u8,
+                token_id: a.token_id,
+                new_usd_price: a.new_usd_price,
+            },
+            BridgeAction::EvmContractUpgradeAction(a) => Self::EvmContractUpgrade {
+                nonce: a.nonce,
+                chain_id: a.chain_id as u8,
+                proxy_address: encode_eth_address(&a.proxy_address),
+                new_impl_address: encode_eth_address(&a.new_impl_address),
+                call_data: format!("0x{}", Hex::encode(&a.call_data)),
+            },
+            BridgeAction::AddTokensOnSuiAction(a) => Self::AddTokensOnSui {
+                nonce: a.nonce,
+                chain_id: a.chain_id as u8,
+                native: a.native,
+                token_ids: a.token_ids.clone(),
+                token_type_names: a
+                    .token_type_names
+                    .iter()
+                    .map(|type_name| type_name.to_canonical_string(true))
+                    .collect(),
+                token_prices: a.token_prices.clone(),
+            },
+            BridgeAction::AddTokensOnEvmAction(a) => Self::AddTokensOnEvm {
+                nonce: a.nonce,
+                chain_id: a.chain_id as u8,
+                native: a.native,
+                token_ids: a.token_ids.clone(),
+                token_addresses: a.token_addresses.iter().map(encode_eth_address).collect(),
+                token_sui_decimals: a.token_sui_decimals.clone(),
+                token_prices: a.token_prices.clone(),
+            },
+        }
+    }
+}
+
+/// Output of `sui-bridge-cli action digest`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub struct ActionDigest {
+    pub action_type: ActionType,
+    /// Hex encoded
+    pub signing_bytes: String,
+    pub digest: String,
+}
+
+/// Output of `sui-bridge-cli quarantine list`, by action digest.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub struct QuarantinedAction {
+    pub action: Action,
+    /// The last permanent failure
+    pub reason: String,
+    pub failures: u64,
+    pub quarantined_at_ms: u64,
+}
+
+impl From<&crate::storage::QuarantinedAction> for QuarantinedAction {
+    fn from(quarantined: &crate::storage::QuarantinedAction) -> Self {
+        Self {
+            action: Action::from(&quarantined.action),
+            reason: quarantined.reason.clone(),
+            failures: quarantined.failures,
+            quarantined_at_ms: quarantined.quarantined_at_ms,
+        }
+    }
+}
+
+/// Encodes the key of an executed token transfer as
+/// `<source chain id>-<destination chain id>-<nonce>`, e.g. `1-11-42`.
+pub fn encode_transfer_cursor(key: (u8, u8, u64)) -> String {
+    format!("{}-{}-{}", key.0, key.1, key.2)
+}
+
+pub fn parse_transfer_cursor(cursor: &str) -> Result<(u8, u8, u64), String> {
+    let invalid = || {
+        format!(
+            "Invalid transfer cursor {:?}, expected <source chain id>-<destination chain id>-<nonce>",
+            cursor
+        )
+    };
+    let mut parts = cursor.split('-');
+    let (Some(source), Some(destination), Some(nonce), None) =
+        (parts.next(), parts.next(), parts.next(), parts.next())
+    else {
+        return Err(invalid());
+    };
+    Ok((
+        source.parse().map_err(|_| invalid())?,
+        destination.parse().map_err(|_| invalid())?,
+        nonce.parse().map_err(|_| invalid())?,
+    ))
+}
+
+/// An executed token transfer, as listed by `/transfers` and exported by
+/// `sui-bridge-cli transfers export`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub struct TransferRecord {
+    /// Position of the transfer, see `encode_transfer_cursor`. Transfers are listed in
+    /// the order of their cursors, a listing resumes after the last one it returned.
+    pub cursor: String,
+    pub nonce: u64,
+    pub source_chain_id: u8,
+    pub destination_chain_id: u8,
+    /// The Sui tx digest or the Eth tx hash that emitted the transfer
+    pub source_tx: String,
+    pub source_event_index: u16,
+    pub sender: String,
+    pub recipient: String,
+    pub token_id: u8,
+    /// With the decimals of the token on Sui
+    pub amount: u64,
+    /// When the node recorded the transfer as executed, None if it was executed before
+    /// the node recorded the times
+    pub executed_at_ms: Option<u64>,
+}
+
+impl TransferRecord {
+    /// Returns None if `action` is not a token transfer.
+    pub fn new(
+        key: (u8, u8, u64),
+        action: &BridgeAction,
+        executed_at_ms: Option<u64>,
+    ) -> Option<Self> {
+        let Action::TokenTransfer {
+            nonce,
+            source_chain_id,
+            destination_chain_id,
+            source_tx,
+            source_event_index,
+            sender,
+            recipient,
+            token_id,
+            amount,
+        } = Action::from(action)
+        else {
+            return None;
+        };
+        Some(Self {
+            cursor: encode_transfer_cursor(key),
+            nonce,
+            source_chain_id,
+            destination_chain_id,
+            source_tx,
+            source_event_index,
+            sender,
+            recipient,
+            token_id,
+            amount,
+            executed_at_ms,
+        })
+    }
+}
+
+/// Response of `/transfers`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub struct TransferPage {
+    pub transfers: Vec<TransferRecord>,
+    /// Cursor to request the next page with, None on the last page
+    pub next_cursor: Option<String>,
+}
+
+/// Request of `/admin/quarantine/requeue_matching`. Quarantined actions that match every
+/// field that is set are verified again.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub struct RequeueMatchingRequest {
+    #[serde(default)]
+    pub action_type: Option<ActionType>,
+    /// Chain id of the action, the source chain of token transfers
+    #[serde(default)]
+    pub chain_id: Option<u8>,
+    /// Inclusive bounds of the nonce
+    #[serde(default)]
+    pub min_nonce: Option<u64>,
+    #[serde(default)]
+    pub max_nonce: Option<u64>,
+    /// Verifies at most this many actions, the least recently verified first
+    #[serde(default)]
+    pub limit: Option<usize>,
+}
+
+impl RequeueMatchingRequest {
+    pub fn matches(&self, action: &BridgeAction) -> bool {
+        let nonce = action.seq_number();
+        self.action_type.map_or(true, |action_type| {
+            action_type == ActionType::from(action.action_type())
+        }) && self
+            .chain_id
+            .map_or(true, |chain_id| chain_id == action.chain_id() as u8)
+            && self.min_nonce.map_or(true, |min_nonce| nonce >= min_nonce)
+            && self.max_nonce.map_or(true, |max_nonce| nonce <= max_nonce)
+    }
+}
+
+/// Response of `/admin/quarantine/requeue_matching`, by action digest.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub struct RequeueMatching {
+    /// Actions that are valid now, moved back to the pending actions and executed
+    pub released: Vec<String>,
+    /// Actions that stay quarantined, with the reason
+    pub still_invalid: BTreeMap<String, String>,
+    /// Actions that couldn't be verified, e.g. because the RPC is down. They stay
+    /// quarantined and are verified again by the next pass.
+    pub errors: BTreeMap<String, String>,
+}
+
+/// Table compacted by `/admin/storage/compact`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub struct CompactedTable {
+    pub db: String,
+    pub table: String,
+    /// Size of the files of the table on disk before and after the compaction
+    pub bytes_before: u64,
+    pub bytes_after: u64,
+    pub duration_ms: u64,
+}
+
+/// Response of `/admin/storage/compact`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub struct StorageCompaction {
+    pub compacted: Vec<CompactedTable>,
+    /// Tables smaller than `compaction-min-table-bytes`, as `db.table`
+    pub skipped: Vec<String>,
+    pub duration_ms: u64,
+}
+
+/// Response of `/admin/storage/backup`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub struct StorageBackup {
+    pub path: String,
+    /// Size of the files of the backup, including the ones hard-linked to the db
+    pub bytes: u64,
+    /// Oldest backups deleted to keep `retain-backups`
+    pub deleted: Vec<String>,
+    pub duration_ms: u64,
+}
+
+/// Entry of `/admin/audit`, a mutation requested through the admin api.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub struct AdminAuditEntry {
+    /// Position in the audit log, pass it as `before` to get the older entries
+    pub index: u64,
+    /// Name of the caller that was granted a role, or the key that authenticated it
+    pub caller: String,
+    pub method: String,
+    pub path: String,
+    pub idempotency_key: String,
+    pub status: u16,
+    pub requested_at_ms: u64,
+}
+
+impl AdminAuditEntry {
+    pub fn new(index: u64, record: AdminAuditRecord) -> Self {
+        Self {
+            index,
+            caller: record.caller,
+            method: record.method,
+            path: record.path,
+            idempotency_key: record.idempotency_key,
+            status: record.status,
+            requested_at_ms: record.requested_at_ms,
+        }
+    }
+}
+
+/// Output of `sui-bridge-cli governance probe`: whether the committee members would sign
+/// a governance action, and the stake of each answer.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub struct GovernanceProbe {
+    pub action: Action,
+    pub action_digest: String,
+    pub yes_stake: u64,
+    pub no_stake: u64,
+    /// Stake of the members that didn't answer or don't support dry runs
+    pub unknown_stake: u64,
+    pub approval_threshold: u64,
+    /// Whether the stake that would sign reaches the approval threshold of the action
+    pub would_certify: bool,
+    pub votes: Vec<GovernanceVote>,
+}
+
+impl GovernanceProbe {
+    pub fn new(action: &BridgeAction, votes: Vec<GovernanceVote>) -> Self {
+        let stake_of = |vote: ProbeVote| {
+            votes
+                .iter()
+                .filter(|v| v.vote == vote)
+                .map(|v| v.stake)
+                .sum::<u64>()
+        };
+        let yes_stake = stake_of(ProbeVote::Yes);
+        Self {
+            action: Action::from(action),
+            action_digest: encode_action_digest(&action.digest()),
+            yes_stake,
+            no_stake: stake_of(ProbeVote::No),
+            unknown_stake: stake_of(ProbeVote::Unknown),
+            approval_threshold: action.approval_threshold(),
+            would_certify: yes_stake >= action.approval_threshold(),
+            votes,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub struct GovernanceVote {
+    pub pubkey: String,
+    pub stake: u64,
+    pub vote: ProbeVote,
+    pub reason: Option<String>,
+}
+
+impl GovernanceVote {
+    /// `dry_run` is None when the member doesn't support dry runs.
+    pub fn new(
+        pubkey: &BridgeAuthorityPublicKeyBytes,
+        stake: u64,
+        dry_run: BridgeResult<Option<GovernanceDryRun>>,
+    ) -> Self {
+        let (vote, reason) = match dry_run {
+            Ok(Some(GovernanceDryRun {
+                would_sign: true,
+                reason,
+            })) => (ProbeVote::Yes, reason),
+            Ok(Some(GovernanceDryRun {
+                would_sign: false,
+                reason,
+            })) => (ProbeVote::No, reason),
+            Ok(None) => (
+                ProbeVote::Unknown,
+                Some("Dry runs are not supported".to_string()),
+            ),
+            Err(e) => (ProbeVote::Unknown, Some(e.to_string())),
+        };
+        Self {
+            pubkey: encode_pubkey(pubkey),
+            stake,
+            vote,
+            reason,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ProbeVote {
+    Yes,
+    No,
+    Unknown,
+}
+
+/// Response of `/admin/approved_governance`: the governance actions the node signs when
+/// requested, as hex encoded digests.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub struct ApprovedGovernance {
+    /// Digests of the actions approved by their content
+    pub actions: Vec<String>,
+    /// Digests approved as such
+    pub digests: Vec<String>,
+}
+
+/// Response of `/signed_set_digest`: the digest of the set of actions the node
+/// signed, and of its buckets, see `signed_set`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub struct SignedSetDigest {
+    pub digest: String,
+    pub count: u64,
+    pub bucket_ms: u64,
+    /// Buckets with signed actions, oldest first
+    pub buckets: Vec<SignedSetBucketDigest>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub struct SignedSetBucketDigest {
+    pub start_ms: u64,
+    pub digest: String,
+    pub count: u64,
+}
+
+impl SignedSetDigest {
+    pub fn new(buckets: &BTreeMap<u64, SignedSetBucket>) -> Self {
+        let mut total = SignedSetBucket::default();
+        buckets.values().for_each(|bucket| total.merge(bucket));
+        Self {
+            digest: total.digest.to_hex(),
+            count: total.count,
+            bucket_ms: SIGNED_SET_BUCKET_MS,
+            buckets: buckets
+                .iter()
+                .map(|(start_ms, bucket)| SignedSetBucketDigest {
+                    start_ms: *start_ms,
+                    digest: bucket.digest.to_hex(),
+                    count: bucket.count,
+                })
+                .collect(),
+        }
+    }
+}
+
+/// Output of `sui-bridge-cli compare-signed-sets`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub struct SignedSetComparison {
+    /// Whether every node answered with the same digest
+    pub matching: bool,
+    pub nodes: Vec<NodeSignedSet>,
+    /// Buckets in which the nodes that answered signed different actions, oldest first.
+    /// Nodes with another bucket width than the first one that answered are left out.
+    pub diverging_buckets: Vec<DivergingBucket>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub struct NodeSignedSet {
+    pub url: String,
+    pub digest: Option<String>,
+    pub count: Option<u64>,
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub struct DivergingBucket {
+    pub start_ms: u64,
+    /// Actions signed in the bucket, by the url of the node
+    pub counts: BTreeMap<String, u64>,
+}
+
+impl SignedSetComparison {
+    /// `sets` are the signed sets of the nodes by url, or why they couldn't be fetched.
+    pub fn new(sets: Vec<(String, Result<SignedSetDigest, String>)>) -> Self {
+        let answered = sets
+            .iter()
+            .filter_map(|(url, set)| set.as_ref().ok().map(|set| (url, set)))
+            .collect::<Vec<_>>();
+        let matching = answered.len() == sets.len()
+            && answered
+                .windows(2)
+                .all(|pair| pair[0].1.digest == pair[1].1.digest);
+        let comparable = answered
+            .iter()
+            .filter(|(_, set)| set.bucket_ms == answered[0].1.bucket_ms)
+            .collect::<Vec<_>>();
+        // (digest, count) of the bucket of each node
+        let mut buckets = BTreeMap::<u64, BTreeMap<&String, (&str, u64)>>::new();
+        for (url, set) in &comparable {
+            for bucket in &set.buckets {
+                buckets
+                    .entry(bucket.start_ms)
+                    .or_default()
+                    .insert(*url, (bucket.digest.as_str(), bucket.count));
+            }
+        }
+        let diverging_buckets = buckets
+            .into_iter()
+            .filter(|(_, by_node)| {
+                by_node.len() < comparable.len()
+                    || by_node
+                        .values()
+                        .any(|bucket| Some(bucket) != by_node.values().next())
+            })
+            .map(|(start_ms, by_node)| DivergingBucket {
+                start_ms,
+                counts: comparable
+                    .iter()
+                    .map(|(url, _)| {
+                        let count = by_node.get(url).map_or(0, |(_, count)| *count);
+                        (url.to_string(), count)
+                    })
+                    .collect(),
+            })
+            .collect();
+        Self {
+            matching,
+            nodes: sets
+                .into_iter()
+                .map(|(url, set)| match set {
+                    Ok(set) => NodeSignedSet {
+                        url,
+                        digest: Some(set.digest),
+                        count: Some(set.count),
+                        error: None,
+                    },
+                    Err(error) => NodeSignedSet {
+                        url,
+                        digest: None,
+                        count: None,
+                        error: Some(error),
+                    },
+                })
+                .collect(),
+            diverging_buckets,
+        }
+    }
+}
+
+/// Response of `/limits/:route/window` and output of `sui-bridge-cli limits show`: the
+/// transfers the limiter on Sui counts against the limit of a route, see `limit_window`.
+/// Amounts are USD values with 8 decimal places, times are chain time.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub struct LimitWindow {
+    pub source_chain: u8,
+    pub destination_chain: u8,
+    /// None if the route has no limit, transfers on it are rejected
+    pub limit: Option<u64>,
+    /// Sum of the amounts of the buckets
+    pub total_amount: u64,
+    pub available_amount: u64,
+    /// Hours with transfers in the last 24 hours, oldest first
+    pub buckets: Vec<LimitWindowBucket>,
+    /// Time of the latest checkpoint, which the window is computed at
+    pub chain_time_ms: u64,
+    /// Local time of the node minus the chain time
+    pub clock_skew_ms: i64,
+    /// Set when an amount was requested
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub requested: Option<LimitCapacity>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub struct LimitWindowBucket {
+    pub hour_start_ms: u64,
+    pub amount: u64,
+    /// When the amount leaves the window
+    pub expires_at_ms: u64,
+}
+
+/// When enough of the limit is free for a transfer of `amount`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub struct LimitCapacity {
+    pub amount: u64,
+    /// None if the amount never fits, i.e. it's above the limit or the route has none
+    pub available_at_ms: Option<u64>,
+    /// Wait from the chain time, unaffected by the clock skew
+    pub available_in_ms: Option<u64>,
+}
+
+/// Response of `/admin/drain_estimate` and output of `sui-bridge-cli estimate-drain`: how
+/// long the node takes to execute its pending actions, see `drain`. Durations are in
+/// milliseconds from `now_ms`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub struct DrainEstimate {
+    pub now_ms: u64,
+    /// The throughput is measured over the transfers executed in this window
+    pub window_ms: u64,
+    pub pending_actions: u64,
+    /// Pending actions that the executor is working on
+    pub in_flight_actions: u64,
+    pub drain: DrainTime,
+    pub action_types: Vec<ActionTypeDrain>,
+    /// Routes with pending token transfers
+    pub routes: Vec<RouteDrain>,
+}
+
+/// None when it can't be estimated, e.g. nothing was executed in the window, or the
+/// upper bound when the throughput may be zero.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub struct DrainTime {
+    pub estimate_ms: Option<u64>,
+    /// Bounds of a 95% confidence interval
+    pub lower_ms: Option<u64>,
+    pub upper_ms: Option<u64>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub struct ActionTypeDrain {
+    pub action_type: String,
+    pub pending: u64,
+    /// Only token transfers record when they were executed
+    pub executed_in_window: u64,
+    pub drain: DrainTime,
+}
+
+/// Amounts are USD values with 8 decimal places.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub struct RouteDrain {
+    pub source_chain: u8,
+    pub destination_chain: u8,
+    pub pending: u64,
+    pub executed_in_window: u64,
+    /// Value of the pending transfers whose token has a price
+    pub pending_value: u64,
+    /// None if the route isn't limited on Sui, e.g. routes into Eth
+    pub limit: Option<u64>,
+    /// Transfers above the limit, they never execute and are left out of the drain
+    pub blocked_transfers: u64,
+    /// When the limit lets the last pending transfer through
+    pub limit_drain_ms: Option<u64>,
+    pub drain: DrainTime,
+}
+
+/// Description of a running node for incident reports, see `runtime_info`. Returned by
+/// `/admin/info`, it holds no secrets.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub struct RuntimeInfo {
+    /// None for nodes embedded without a version
+    pub version: Option<String>,
+    /// `committee`, `observer` or `shadow`
+    pub mode: String,
+    /// Hex encoded, None when the node doesn't sign
+    pub authority_public_key: Option<String>,
+    /// Read from the bridge on Sui, None if it couldn't be read at startup
+    pub sui_chain_id: Option<u8>,
+    /// Read from the bridge contracts on Eth, None if it couldn't be read at startup
+    pub eth_chain_id: Option<u8>,
+    pub sui_bridge_package_id: String,
+    pub sui_bridge_object_id: String,
+    pub eth_bridge_proxy_address: String,
+    /// The Eth contracts of the bridge behind the proxy, and its config contract. Only
+    /// known to nodes that run the client.
+    pub eth_contracts: Vec<String>,
+    /// Epoch of the last update of the committee on Sui
+    pub committee_epoch: Option<u64>,
+    /// Version of the bridge object on Sui
+    pub bridge_version: Option<u64>,
+    /// `STORAGE_SCHEMA_VERSION` of the client storage, None when the node has none
+    pub storage_schema_version: Option<u32>,
+    /// Cargo features the node was built with
+    pub features: Vec<String>,
+    /// Optional components enabled in the config, e.g. `client` or `remote-signer`
+    pub components: Vec<String>,
+    pub started_at_ms: u64,
+    /// Config the node runs with, see `runtime_info::effective_config`. None for nodes
+    /// not started from a `BridgeNodeConfig`.
+    pub config: Option<serde_json::Value>,
+}
+
+/// Output of `sui-bridge-cli node-status --json`, put together from the routes of a node,
+/// see `node_status`. A part is None when its route couldn't be fetched, e.g. `drain` from
+/// a node that doesn't run the client, with the error in `unavailable` by route.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub struct NodeStatusReport {
+    pub url: String,
+    pub now_ms: u64,
+    pub health: Option<NodeHealth>,
+    pub readiness: Option<ReadinessState>,
+    pub executor: Option<ExecutorState>,
+    pub drain: Option<DrainEstimate>,
+    /// By hex encoded pubkey
+    pub committee_scores: Option<BTreeMap<String, CommitteeMemberScore>>,
+    /// None in the reports of nodes that don't serve it
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub info: Option<RuntimeInfo>,
+    pub unavailable: BTreeMap<String, String>,
+    /// The parts beyond the thresholds of `node_status`, as highlighted without `--json`
+    pub warnings: Vec<String>,
+}
+
+/// A line of the event stream that the node writes to sidecars, see `event_stream`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "event", rename_all = "snake_case")]
+pub enum StreamEvent {
+    /// The orchestrator received a new action from a syncer
+    #[serde(rename_all = "snake_case")]
+    ActionObserved {
+        action_digest: String,
+        action: Action,
+        /// Timestamp of the Eth block or Sui checkpoint of the action, if known
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        chain_timestamp_ms: Option<u64>,
+        timestamp_ms: u64,
+    },
+    #[serde(rename_all = "snake_case")]
+    ActionStatus {
+        action_digest: String,
+        status: StreamActionStatus,
+        /// Why the action failed
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        reason: Option<String>,
+        timestamp_ms: u64,
+    },
+    /// Sent periodically for every component that was active since the node started
+    #[serde(rename_all = "snake_case")]
+    Heartbeat {
+        component: String,
+        last_active_ms: u64,
+        timestamp_ms: u64,
+    },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum StreamActionStatus {
+    /// The committee signatures were collected
+    Signed,
+    /// The action was executed on chain
+    Executed,
+    /// The action needs manual intervention, e.g. it was quarantined
+    Failed,
+}
+
+/// Output of `sui-bridge-cli journal list`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub struct JournalEntry {
+    pub file: String,
+    /// Byte offset of the frame in `file`
+    pub offset: u64,
+    pub action_digest: String,
+    pub tx_digest: String,
+    pub gas_object: GasObject,
+    pub timestamp_ms: u64,
+    /// Base64 encoded bcs bytes of the `TransactionData`
+    pub tx_bytes: String,
+    /// Base64 encoded
+    pub signatures: Vec<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub struct GasObject {
+    pub object_id: String,
+    pub version: u64,
+    pub digest: String,
+}
+
+impl From<&JournalRecord> for JournalEntry {
+    fn from(record: &JournalRecord) -> Self {
+        let (object_id, version, digest) = &record.entry.gas_object;
+        Self {
+            file: record.file.display().to_string(),
+            offset: record.offset,
+            action_digest: encode_action_digest(&record.entry.action_digest),
+            tx_digest: record.entry.tx_digest.to_string(),
+            gas_object: GasObject {
+                object_id: object_id.to_string(),
+                version: version.value(),
+                digest: digest.to_string(),
+            },
+            timestamp_ms: record.entry.timestamp_ms,
+            tx_bytes: Base64::encode(&record.entry.tx_bytes),
+            signatures: record.entry.signatures.iter().map(Base64::encode).collect(),
+        }
+    }
+}
+
+/// Output of `sui-bridge-cli journal verify` and `journal list` for corrupted frames.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub struct JournalFrameCorruption {
+    pub file: String,
+    /// Byte offset of the frame in `file`
+    pub offset: u64,
+    /// Position of the frame in `file`, starting from 0
+    pub frame_index: usize,
+    pub reason: String,
+}
+
+impl From<&JournalCorruption> for JournalFrameCorruption {
+    fn from(corruption: &JournalCorruption) -> Self {
+        Self {
+            file: corruption.file.display().to_string(),
+            offset: corruption.offset,
+            frame_index: corruption.frame_index,
+            reason: corruption.reason.clone(),
+        }
+    }
+}
+
+/// The outputs of the `view-*` commands of `sui-bridge-cli`, with the errors that
+/// did not prevent the output.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub struct CliOutput<P> {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub errors: Option<Vec<String>>,
+    pub inner: P,
+}
+
+impl<P> CliOutput<P> {
+    pub fn add_error(&mut self, error: String) {
+        self.errors.get_or_insert_with(Vec::new).push(error);
+    }
+}
+
+/// Output of `sui-bridge-cli view-eth-bridge`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub struct EthBridgeView {
+    pub chain_id: u64,
+    pub bridge_proxy: String,
+    pub committee_proxy: String,
+    pub limiter_proxy: String,
+    pub config_proxy: String,
+    pub vault: String,
+    pub nonces: EthBridgeNonces,
+}
+
+impl EthBridgeView {
+    pub fn new(
+        chain_id: u64,
+        bridge_proxy: EthAddress,
+        committee_proxy: EthAddress,
+        limiter_proxy: EthAddress,
+        config_proxy: EthAddress,
+        vault: EthAddress,
+        nonces: EthBridgeNonces,
+    ) -> Self {
+        Self {
+            chain_id,
+            bridge_proxy: encode_eth_address(&bridge_proxy),
+            committee_proxy: encode_eth_address(&committee_proxy),
+            limiter_proxy: encode_eth_address(&limiter_proxy),
+            config_proxy: encode_eth_address(&config_proxy),
+            vault: encode_eth_address(&vault),
+            nonces,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub struct EthBridgeNonces {
+    pub token_transfer: u64,
+    pub blocklist_update: u64,
+    pub emergency_button: u64,
+    pub limit_update: u64,
+    pub asset_price_update: u64,
+    pub add_evm_tokens: u64,
+    pub contract_upgrade_bridge: u64,
+    pub contract_upgrade_committee: u64,
+    pub contract_upgrade_limiter: u64,
+    pub contract_upgrade_config: u64,
+}
+
+/// Output of `sui-bridge-cli view-sui-bridge`.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub struct SuiBridgeView {
+    /// Percentage of the total voting power
+    pub total_stake: f32,
+    /// Percentage of the total voting power, only set when the members were pinged
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub total_online_stake: Option<f32>,
+    pub committee: Vec<CommitteeMember>,
+    pub nonces: BTreeMap<ActionType, u64>,
+}
+
+/// Output of `sui-bridge-cli view-bridge-registration`.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub struct SuiBridgeRegistrationView {
+    /// Percentage of the total voting power
+    pub total_registered_stake: f32,
+    pub committee: Vec<CommitteeMember>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub struct CommitteeMember {
+    pub name: String,
+    pub sui_address: String,
+    pub eth_address: String,
+    pub pubkey: String,
+    pub url: String,
+    pub stake: u64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub blocklisted: Option<bool>,
+    /// Only set when the member was pinged
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub status: Option<MemberStatus>,
+}
+
+impl CommitteeMember {
+    pub fn new(
+        name: String,
+        sui_address: sui_types::base_types::SuiAddress,
+        eth_address: EthAddress,
+        pubkey: String,
+        url: String,
+        stake: u64,
+    ) -> Self {
+        Self {
+            name,
+            sui_address: sui_address.to_string(),
+            eth_address: encode_eth_address(&eth_address),
+            pubkey,
+            url,
+            stake,
+            blocklisted: None,
+            status: None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MemberStatus {
+    Online,
+    Offline,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{get_test_eth_to_sui_bridge_action, get_test_sui_to_eth_bridge_action};
+    use crate::types::{EmergencyAction, EvmContractUpgradeAction};
+    use serde::de::DeserializeOwned;
+    use std::fmt::Debug;
+    use std::path::PathBuf;
+    use sui_types::bridge::BridgeChainId;
+
+    fn testdata(name: &str) -> PathBuf {
+        PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+            .join("testdata")
+            .join("api")
+            .join(name)
+    }
+
+    fn load<T: DeserializeOwned>(name: &str) -> T {
+        serde_json::from_str(&std::fs::read_to_string(testdata(name)).unwrap()).unwrap()
+    }
+
+    fn assert_round_trips<T: Serialize + DeserializeOwned + PartialEq + Debug>(value: &T) {
+        let json = serde_json::to_string(value).unwrap();
+        assert_eq!(&serde_json::from_str::<T>(&json).unwrap(), value);
+    }
+
+    fn member(status: Option<MemberStatus>) -> CommitteeMember {
+        CommitteeMember {
+            name: "validator-0".to_string(),
+            sui_address: "0x8f2a9c4b7e3d1f6a5c0b9e8d7a6f5e4d3c2b1a0f9e8d7c6b5a4f3e2d1c0b9a8f"
+                .to_string(),
+            eth_address: "0x68b43fd906c0b8f024a18c56e06744f7c6157c65".to_string(),
+            pubkey: "02321ede33d2c2d7a8a152f275a1484edef2098f034121a602cb7d767d38680aa4"
+                .to_string(),
+            url: "https://bridge.example.com".to_string(),
+            stake: 2500,
+            blocklisted: Some(false),
+            status,
+        }
+    }
+
+    fn json<T: Serialize>(value: &T) -> serde_json::Value {
+        serde_json::to_value(value).unwrap()
+    }
+
+    // One sample of every type of the schema, in `schema_v1.json`
+    fn schema_samples() -> BTreeMap<&'static str, serde_json::Value> {
+        let mut nonces = BTreeMap::new();
+        nonces.insert(ActionType::TokenTransfer, 12);
+        nonces.insert(ActionType::EmergencyButton, 1);
+        let transfer_record = TransferRecord {
+            cursor: "11-1-41".to_string(),
+            nonce: 41,
+            source_chain_id: 11,
+            destination_chain_id: 1,
+            source_tx: "0x3d3b7e2fc5db5d1b8d9e4f3c4b0c9b5a1f8e6d7c2b3a4f5e6d7c8b9a0f1e2d3c"
+                .to_string(),
+            source_event_index: 2,
+            sender: "0x0000000000000000000000000000000000000001".to_string(),
+            recipient: "0x0000000000000000000000000000000000000000000000000000000000000002"
+                .to_string(),
+            token_id: 4,
+            amount: 100000000,
+            executed_at_ms: Some(1700000000000),
+        };
+        BTreeMap::from([
+            (
+                "node_metadata",
+                json(&NodeMetadata {
+                    version: Some("1.0.0".to_string()),
+                    metrics_pubkey: Some(
+                        "7VovZ5XnMBbXLNCqxI2p2gH6M1u0b3vs+8lg6oUnvRQ=".to_string(),
+                    ),
+                }),
+            ),
+            (
+                "node_health",
+                json(&NodeHealth {
+                    registered_url: Some(RegisteredUrl {
+                        check: RegisteredUrlStatus::Mismatch {
+                            registered_url: "https://old.example.com".to_string(),
+                            external_url: "https://new.example.com".to_string(),
+                        },
+                        reachable: Some(false),
+                        checked_at_ms: 1700000000000,
+                    }),
+                    pipelines: Some(PipelinesHealth {
+                        status: PipelineStatus::Degraded,
+                        sui: ChainPipelineHealth {
+                            status: PipelineStatus::Healthy,
+                            consecutive_failures: 0,
+                            last_success_ms: Some(1700000000000),
+                            last_error: None,
+                        },
+                        eth: ChainPipelineHealth {
+                            status: PipelineStatus::Down,
+                            consecutive_failures: 5,
+                            last_success_ms: Some(1699999000000),
+                            last_error: Some("Connection refused".to_string()),
+                        },
+                    }),
+                    clock: Some(ClockHealth {
+                        status: PipelineStatus::Degraded,
+                        max_drift_ms: 60000,
+                        sui_drift_ms: Some(-125000),
+                        eth_drift_ms: None,
+                        refusing_signatures: false,
+                    }),
+                }),
+            ),
+            (
+                "error_entry",
+                json(&ErrorEntry {
+                    timestamp_ms: 1700000000000,
+                    message: "Transaction failed".to_string(),
+                    digests: vec!["8BWniKN37WsVe8yH9aVXUuaUXwsmU3zgNMDcQtKbTFUo".to_string()],
+                }),
+            ),
+            (
+                "committee_member_score",
+                json(&CommitteeMemberScore {
+                    ewma_latency_ms: 120,
+                    failure_rate_bps: 500,
+                    last_success_timestamp_ms: Some(1700000000000),
+                }),
+            ),
+            (
+                "reset_cursor_request",
+                json(&ResetCursorRequest {
+                    component: CursorComponent::Eth,
+                    identifier: "0x68b43fd906c0b8f024a18c56e06744f7c6157c65".to_string(),
+                    new_value: "100".to_string(),
+                    force: false,
+                }),
+            ),
+            (
+                "cursor_reset",
+                json(&CursorReset {
+                    component: CursorComponent::Sui,
+                    identifier: "bridge".to_string(),
+                    old_value: None,
+                    new_value: "8BWniKN37WsVe8yH9aVXUuaUXwsmU3zgNMDcQtKbTFUo:2".to_string(),
+                    forced: true,
+                    reset_at_ms: 1700000000000,
+                }),
+            ),
+            (
+                "executor_state",
+                json(&ExecutorState {
+                    current_action: Some(ExecutorAction {
+                        action_digest: "0a".to_string(),
+                        action_type: ActionType::TokenTransfer,
+                        nonce: 12,
+                        stage: ExecutorStage::AwaitingEffects,
+                        time_in_stage_ms: 1500,
+                    }),
+                    in_flight_actions: vec![ExecutorAction {
+                        action_digest: "0a".to_string(),
+                        action_type: ActionType::TokenTransfer,
+                        nonce: 12,
+                        stage: ExecutorStage::AwaitingEffects,
+                        time_in_stage_ms: 1500,
+                    }],
+                    queued_actions: vec![QueuedAction {
+                        action_digest: "0b".to_string(),
+                        action_type: ActionType::TokenTransfer,
+                        nonce: 13,
+                    }],
+                    bridge_paused: false,
+                    gas_coin: Some(GasCoinState {
+                        object_id: "0x5".to_string(),
+                        version: 7,
+                        balance: 1000000000000,
+                    }),
+                }),
+            ),
+            (
+                "aggregation_status",
+                json(&AggregationStatus {
+                    action_digest: "0c".to_string(),
+                    action_type: ActionType::EmergencyButton,
+                    nonce: 3,
+                    time_aggregating_ms: 4000,
+                    stake_collected: 4500,
+                    stake_required: 5001,
+                    signed: vec!["k#02a1b2c3..".to_string(), "k#03d4e5f6..".to_string()],
+                    errored: vec![MemberError {
+                        member: "k#0277aa88..".to_string(),
+                        category: "timeout".to_string(),
+                    }],
+                    pending: vec!["k#03ff0011..".to_string()],
+                }),
+            ),
+            (
+                "requester_summary",
+                json(&RequesterSummary {
+                    window_secs: 300,
+                    requesters: vec![
+                        RequesterRate {
+                            requester: "cert:CN=aggregator-1".to_string(),
+                            requests: 120,
+                            errors: 2,
+                        },
+                        RequesterRate {
+                            requester: "other".to_string(),
+                            requests: 7,
+                            errors: 7,
+                        },
+                    ],
+                }),
+            ),
+            (
+                "action",
+                json(&Action::EmergencyButton {
+                    nonce: 3,
+                    chain_id: 11,
+                    operation: EmergencyOperation::Pause,
+                }),
+            ),
+            (
+                "action_digest",
+                json(&ActionDigest {
+                    action_type: ActionType::EmergencyButton,
+                    signing_bytes: "0x00".to_string(),
+                    digest: "0x01".to_string(),
+                }),
+            ),
+            (
+                "quarantined_action",
+                json(&QuarantinedAction {
+                    action: Action::LimitUpdate {
+                        nonce: 4,
+                        chain_id: 1,
+                        sending_chain_id: 11,
+                        new_usd_limit: 1000000,
+                    },
+                    reason: "Nonce already used".to_string(),
+                    failures: 5,
+                    quarantined_at_ms: 1700000000000,
+                }),
+            ),
+            (
+                "governance_probe",
+                json(&GovernanceProbe {
+                    action: Action::LimitUpdate {
+                        nonce: 4,
+                        chain_id: 1,
+                        sending_chain_id: 11,
+                        new_usd_limit: 1000000,
+                    },
+                    action_digest: "0c".to_string(),
+                    yes_stake: 5000,
+                    no_stake: 2500,
+                    unknown_stake: 2500,
+                    approval_threshold: 5001,
+                    would_certify: false,
+                    votes: vec![
+                        GovernanceVote {
+                            pubkey:
+                                "02321ede33d2c2d7a8a152f275a1484edef2098f034121a602cb7d767d38680aa4"
+                                    .to_string(),
+                            stake: 5000,
+                            vote: ProbeVote::Yes,
+                            reason: None,
+                        },
+                        GovernanceVote {
+                            pubkey:
+                                "027f1178ff417fc9f5b8290bd8876f0a157a505a6c52db100a8492203ddd1d4279"
+                                    .to_string(),
+                            stake: 2500,
+                            vote: ProbeVote::No,
+                            reason: Some("Governance action is not approved".to_string()),
+                        },
+                        GovernanceVote {
+                            pubkey:
+                                "026f311bcd1c2664c14277c7a80e4857c690626597064f89edc33b8f67b99c6bc0"
+                                    .to_string(),
+                            stake: 2500,
+                            vote: ProbeVote::Unknown,
+                            reason: Some("Dry runs are not supported".to_string()),
+                        },
+                    ],
+                }),
+            ),
+            (
+                "stream_event",
+                json(&StreamEvent::ActionStatus {
+                    action_digest: "0a".to_string(),
+                    status: StreamActionStatus::Failed,
+                    reason: Some("Nonce already used".to_string()),
+                    timestamp_ms: 1700000000000,
+                }),
+            ),
+            (
+                "journal_entry",
+                json(&JournalEntry {
+                    file: "journal/00000000.log".to_string(),
+                    offset: 0,
+                    action_digest: "0a".to_string(),
+                    tx_digest: "8BWniKN37WsVe8yH9aVXUuaUXwsmU3zgNMDcQtKbTFUo".to_string(),
+                    gas_object: GasObject {
+                        object_id: "0x5".to_string(),
+                        version: 7,
+                        digest: "8BWniKN37WsVe8yH9aVXUuaUXwsmU3zgNMDcQtKbTFUo".to_string(),
+                    },
+                    timestamp_ms: 1700000000000,
+                    tx_bytes: "AAE=".to_string(),
+                    signatures: vec!["AAI=".to_string()],
+                }),
+            ),
+            (
+                "journal_frame_corruption",
+                json(&JournalFrameCorruption {
+                    file: "journal/00000000.log".to_string(),
+                    offset: 64,
+                    frame_index: 1,
+                    reason: "Checksum mismatch".to_string(),
+                }),
+            ),
+            (
+                "eth_bridge_view",
+                json(&EthBridgeView {
+                    chain_id: 10,
+                    bridge_proxy: "0x0000000000000000000000000000000000000001".to_string(),
+                    committee_proxy: "0x0000000000000000000000000000000000000002".to_string(),
+                    limiter_proxy: "0x0000000000000000000000000000000000000003".to_string(),
+                    config_proxy: "0x0000000000000000000000000000000000000004".to_string(),
+                    vault: "0x0000000000000000000000000000000000000005".to_string(),
+                    nonces: EthBridgeNonces {
+                        token_transfer: 12,
+                        ..Default::default()
+                    },
+                }),
+            ),
+            (
+                "sui_bridge_view",
+                json(&CliOutput {
+                    errors: Some(vec![
+                        "Invalid bridge http url for validator: 0x1".to_string()
+                    ]),
+                    inner: SuiBridgeView {
+                        total_stake: 100.0,
+                        total_online_stake: Some(75.0),
+                        committee: vec![member(Some(MemberStatus::Online))],
+                        nonces,
+                    },
+                }),
+            ),
+            (
+                "sui_bridge_registration_view",
+                json(&CliOutput {
+                    errors: None,
+                    inner: SuiBridgeRegistrationView {
+                        total_registered_stake: 25.0,
+                        committee: vec![member(None)],
+                    },
+                }),
+            ),
+            ("transfer_record", json(&transfer_record)),
+            (
+                "transfer_page",
+                json(&TransferPage {
+                    transfers: vec![transfer_record.clone()],
+                    next_cursor: Some("11-1-42".to_string()),
+                }),
+            ),
+        ])
+    }
+
+    #[test]
+    fn test_schema_snapshot() {
+        // Any change of the schema fails this test. Changes that break `previous_release`
+        // fixtures must bump `API_SCHEMA_VERSION` and add a new snapshot.
+        assert_eq!(API_SCHEMA_VERSION, 1);
+        let expected: BTreeMap<String, serde_json::Value> = load("schema_v1.json");
+        let actual = schema_samples()
+            .into_iter()
+            .map(|(name, value)| (name.to_string(), value))
+            .collect::<BTreeMap<_, _>>();
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn test_actions_round_trip() {
+        let sui_to_eth =
+            get_test_sui_to_eth_bridge_action(None, None, None, None, None, None, None);
+        let eth_to_sui = get_test_eth_to_sui_bridge_action(None, None, None, None);
+        let upgrade = BridgeAction::EvmContractUpgradeAction(EvmContractUpgradeAction {
+            nonce: 1,
+            chain_id: BridgeChainId::EthSepolia,
+            proxy_address: EthAddress::repeat_byte(1),
+            new_impl_address: EthAddress::repeat_byte(2),
+            call_data: vec![0xab, 0xcd],
+        });
+        let emergency = BridgeAction::EmergencyAction(EmergencyAction {
+            nonce: 2,
+            chain_id: BridgeChainId::SuiTestnet,
+            action_type: EmergencyActionType::Unpause,
+        });
+        for action in [&sui_to_eth, &eth_to_sui, &upgrade, &emergency] {
+            assert_round_trips(&Action::from(action));
+        }
+
+        let BridgeAction::SuiToEthBridgeAction(a) = &sui_to_eth else {
+            unreachable!()
+        };
+        let Action::TokenTransfer {
+            source_chain_id,
+            destination_chain_id,
+            source_tx,
+            recipient,
+            ..
+        } = Action::from(&sui_to_eth)
+        else {
+            panic!("Expected a token transfer");
+        };
+        assert_eq!(source_chain_id, a.sui_bridge_event.sui_chain_id as u8);
+        assert_eq!(destination_chain_id, a.sui_bridge_event.eth_chain_id as u8);
+        assert_eq!(source_tx, a.sui_tx_digest.to_string());
+        assert_eq!(recipient, format!("{:?}", a.sui_bridge_event.eth_address));
+
+        let upgrade_json = serde_json::to_value(Action::from(&upgrade)).unwrap();
+        assert_eq!(upgrade_json["action_type"], "EvmContractUpgrade");
+        assert_eq!(
+            upgrade_json["proxy_address"],
+            "0x0101010101010101010101010101010101010101"
+        );
+        assert_eq!(upgrade_json["call_data"], "0xabcd");
+    }
+
+    #[test]
+    fn test_schema_samples_round_trip() {
+        let samples = schema_samples();
+        fn check<T: Serialize + DeserializeOwned + PartialEq + Debug>(value: &serde_json::Value) {
+            let decoded: T = serde_json::from_value(value.clone()).unwrap();
+            assert_round_trips(&decoded);
+            assert_eq!(&serde_json::json(&decoded).unwrap(), value);
+        }
+        check::<NodeMetadata>(&samples["node_metadata"]);
+        check::<NodeHealth>(&samples["node_health"]);
+        check::<ErrorEntry>(&samples["error_entry"]);
+        check::<CommitteeMemberScore>(&samples["committee_member_score"]);
+        check::<ResetCursorRequest>(&samples["reset_cursor_request"]);
+        check::<CursorReset>(&samples["cursor_reset"]);
+        check::<ExecutorState>(&samples["executor_state"]);
+        check::<AggregationStatus>(&samples["aggregation_status"]);
+        check::<RequesterSummary>(&samples["requester_summary"]);
+        check::<Action>(&samples["action"]);
+        check::<ActionDigest>(&samples["action_digest"]);
+        check::<QuarantinedAction>(&samples["quarantined_action"]);
+        check::<GovernanceProbe>(&samples["governance_probe"]);
+        check::<StreamEvent>(&samples["stream_event"]);
+        check::<JournalEntry>(&samples["journal_entry"]);
+        check::<JournalFrameCorruption>(&samples["journal_frame_corruption"]);
+        check::<EthBridgeView>(&samples["eth_bridge_view"]);
+        check::<CliOutput<SuiBridgeView>>(&samples["sui_bridge_view"]);
+        check::<CliOutput<SuiBridgeRegistrationView>>(&samples["sui_bridge_registration_view"]);
+        check::<TransferRecord>(&samples["transfer_record"]);
+        check::<TransferPage>(&samples["transfer_page"]);
+    }
+
+    #[test]
+    fn test_previous_release_fixtures() {
+        // Outputs of the release before the schema was versioned
+        let metadata: NodeMetadata = load("previous_release/ping.json");
+        assert_eq!(metadata.version.as_deref(), Some("1.36.0"));
+        assert!(metadata.metrics_pubkey.is_some());
+
+        let health: NodeHealth = load("previous_release/health.json");
+        assert_eq!(
+            health.registered_url.unwrap().check,
+            RegisteredUrlStatus::NotConfigured {
+                registered_url: "https://bridge.example.com".to_string()
+            }
+        );
+        assert!(health.pipelines.is_none());
+
+        let errors: BTreeMap<String, Vec<ErrorEntry>> = load("previous_release/errors.json");
+        assert_eq!(errors["err_sui_transaction_execution"].len(), 2);
+
+        let scores: BTreeMap<String, CommitteeMemberScore> =
+            load("previous_release/committee_scores.json");
+        assert_eq!(scores.len(), 2);
+        assert!(scores
+            .values()
+            .any(|score| score.last_success_timestamp_ms.is_none()));
+
+        let eth_bridge: EthBridgeView = load("previous_release/view_eth_bridge.json");
+        assert_eq!(eth_bridge.chain_id, 11155111);
+        assert_eq!(eth_bridge.nonces.token_transfer, 42);
+
+        let sui_bridge: CliOutput<SuiBridgeView> = load("previous_release/view_sui_bridge.json");
+        assert_eq!(sui_bridge.errors.unwrap().len(), 1);
+        assert_eq!(sui_bridge.inner.committee.len(), 2);
+        assert_eq!(
+            sui_bridge.inner.committee[0].status,
+            Some(MemberStatus::Online)
+        );
+        assert_eq!(sui_bridge.inner.nonces[&ActionType::TokenTransfer], 42);
+
+        let registration: CliOutput<SuiBridgeRegistrationView> =
+            load("previous_release/view_bridge_registration.json");
+        assert!(registration.errors.is_none());
+        assert_eq!(registration.inner.committee[0].status, None);
+    }
+
+    #[test]
+    fn test_conversions_keep_the_previous_format() {
+        let report = RegisteredUrlReport {
+            check: RegisteredUrlCheck::Match {
+                url: "https://bridge.example.com".to_string(),
+            },
+            reachable: Some(true),
+            checked_at_ms: 1,
+        };
+        assert_eq!(
+            serde_json::to_value(RegisteredUrl::from(&report)).unwrap(),
+            serde_json::json(&report).unwrap()
+        );
+        let score = AuthorityScore {
+            ewma_latency_ms: 10,
+            failure_rate_bps: 20,
+            last_success_timestamp_ms: None,
+        };
+        assert_eq!(
+            serde_json::to_value(CommitteeMemberScore::from(&score)).unwrap(),
+            serde_json::to_value(score).unwrap()
+        );
+        let address = EthAddress::repeat_byte(0xab);
+        assert_eq!(
+            serde_json::to_value(encode_eth_address(&address)).unwrap(),
+            serde_json::to_value(address).unwrap()
+        );
+    }
+}
@@ -0,0 +1,357 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Archive of the executed token transfers that pruning deletes from the client storage,
+//! see `pruning`. Each batch of pruned transfers is written as a zstd compressed jsonl
+//! file, one `ArchivedAction` per line, followed by its manifest. The manifest is only
+//! written once the file was read back and matched its sha256, so a batch with a
+//! manifest is complete. Layout, under the prefix of the archive:
+//! - `executed/<source chain id>-<destination chain id>/<first nonce>-<last nonce>.jsonl.zst`
+//! - `manifests/<source chain id>-<destination chain id>/<first nonce>-<last nonce>.json`:
+//!   the `ArchiveManifest` of the file, with the digests of its actions
+//!
+//! The archive is a directory, e.g. on a mounted network volume, or with the `archive-s3`
+//! feature an S3 compatible bucket. GCS buckets are reached through their S3
+//! interoperability endpoint.
+
+use crate::timestamps::now_ms;
+use crate::types::{BridgeAction, BridgeActionDigest};
+use anyhow::{anyhow, bail};
+use async_trait::async_trait;
+use fastcrypto::encoding::{Encoding, Hex};
+use fastcrypto::hash::{HashFunction, Sha256};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+/// Bumped whenever the archived files change format.
+pub const ARCHIVE_FORMAT_VERSION: u32 = 1;
+
+const EXECUTED_DIR: &str = "executed";
+const MANIFESTS_DIR: &str = "manifests";
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub struct ArchivedAction {
+    pub source_chain: u8,
+    pub destination_chain: u8,
+    pub nonce: u64,
+    /// Hex encoded
+    pub digest: String,
+    pub action: BridgeAction,
+    /// Gas spent by the transaction of this node that executed the transfer, in mist
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub gas_spent_mist: Option<u64>,
+}
+
+impl ArchivedAction {
+    pub fn new(key: (u8, u8, u64), action: BridgeAction, gas_spent_mist: Option<u64>) -> Self {
+        Self {
+            source_chain: key.0,
+            destination_chain: key.1,
+            nonce: key.2,
+            digest: Hex::encode(action.digest().inner()),
+            action,
+            gas_spent_mist,
+        }
+    }
+
+    pub fn key(&self) -> (u8, u8, u64) {
+        (self.source_chain, self.destination_chain, self.nonce)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub struct ArchiveManifest {
+    pub format_version: u32,
+    /// Path of the archived file, relative to the prefix of the archive
+    pub file: String,
+    pub size: u64,
+    /// Hex encoded, of the compressed file
+    pub sha256: String,
+    pub created_at_ms: u64,
+    /// Hex encoded digests of the archived actions, in the order of the file
+    pub digests: Vec<String>,
+}
+
+/// Where archived files are stored.
+#[async_trait]
+pub trait ArchiveStore: Send + Sync {
+    async fn put(&self, path: &str, bytes: Vec<u8>) -> anyhow::Result<()>;
+
+    async fn get(&self, path: &str) -> anyhow::Result<Vec<u8>>;
+
+    /// Paths of the files under `prefix`
+    async fn list(&self, prefix: &str) -> anyhow::Result<Vec<String>>;
+}
+
+/// Archive in a local directory.
+pub struct FileArchiveStore {
+    root: PathBuf,
+}
+
+impl FileArchiveStore {
+    pub fn new(root: PathBuf) -> Self {
+        Self { root }
+    }
+}
+
+#[async_trait]
+impl ArchiveStore for FileArchiveStore {
+    async fn put(&self, path: &str, bytes: Vec<u8>) -> anyhow::Result<()> {
+        let path = self.root.join(path);
+        if let Some(parent) = path.parent() {
+            tokio::fs::create_dir_all(parent).await?;
+        }
+        // Renamed into place so that a partial write is never visible under the path
+        let tmp_path = path.with_extension("tmp");
+        tokio::fs::write(&tmp_path, bytes).await?;
+        tokio::fs::rename(&tmp_path, &path).await?;
+        Ok(())
+    }
+
+    async fn get(&self, path: &str) -> anyhow::Result<Vec<u8>> {
+        Ok(tokio::fs::read(self.root.join(path)).await?)
+    }
+
+    async fn list(&self, prefix: &str) -> anyhow::Result<Vec<String>> {
+        let mut paths = vec![];
+        let mut dirs = vec![self.root.join(prefix)];
+        while let Some(dir) = dirs.pop() {
+            let mut entries = match tokio::fs::read_dir(&dir).await {
+                Ok(entries) => entries,
+                Err(e) if e.kind() == std::io::ErrorKind::NotFound => continue,
+                Err(e) => return Err(e.into()),
+            };
+            while let Some(entry) = entries.next_entry().await? {
+                let path = entry.path();
+                if entry.file_type().await?.is_dir() {
+                    dirs.push(path);
+                } else if path.extension() != Some(std::ffi::OsStr::new("tmp")) {
+                    paths.push(relative_path(&self.root, &path)?);
+                }
+            }
+        }
+        Ok(paths)
+    }
+}
+
+fn relative_path(root: &Path, path: &Path) -> anyhow::Result<String> {
+    Ok(path
+        .strip_prefix(root)?
+        .components()
+        .map(|component| component.as_os_str().to_string_lossy())
+        .collect::<Vec<_>>()
+        .join("/"))
+}
+
+/// Archive in an S3 compatible bucket. Credentials are read from the environment, e.g.
+/// `AWS_ACCESS_KEY_ID` and `AWS_SECRET_ACCESS_KEY`.
+#[cfg(feature = "archive-s3")]
+pub struct S3ArchiveStore {
+    store: Box<dyn object_store::ObjectStore>,
+}
+
+#[cfg(feature = "archive-s3")]
+impl S3ArchiveStore {
+    pub fn new(bucket: &str, endpoint: Option<&str>, region: Option<&str>) -> anyhow::Result<Self> {
+        let mut builder = object_store::aws::AmazonS3Builder::from_env().with_bucket_name(bucket);
+        if let Some(endpoint) = endpoint {
+            builder = builder
+                .with_endpoint(endpoint)
+                .with_allow_http(endpoint.starts_with("http://"));
+        }
+        if let Some(region) = region {
+            builder = builder.with_region(region);
+        }
+        Ok(Self {
+            store: Box::new(builder.build()?),
+        })
+    }
+}
+
+#[cfg(feature = "archive-s3")]
+#[async_trait]
+impl ArchiveStore for S3ArchiveStore {
+    async fn put(&self, path: &str, bytes: Vec<u8>) -> anyhow::Result<()> {
+        self.store
+            .put(&object_store::path::Path::from(path), bytes.into())
+            .await?;
+        Ok(())
+    }
+
+    async fn get(&self, path: &str) -> anyhow::Result<Vec<u8>> {
+        Ok(self
+            .store
+            .get(&object_store::path::Path::from(path))
+            .await?
+            .bytes()
+            .await?
+            .to_vec())
+    }
+
+    async fn list(&self, prefix: &str) -> anyhow::Result<Vec<String>> {
+        use futures::TryStreamExt;
+        let prefix = object_store::path::Path::from(prefix);
+        Ok(self
+            .store
+            .list(Some(&prefix))
+            .map_ok(|meta| meta.location.to_string())
+            .try_collect()
+            .await?)
+    }
+}
+
+/// Writes batches of executed token transfers to an `ArchiveStore` and finds them again.
+#[derive(Clone)]
+pub struct ActionArchive {
+    store: Arc<dyn ArchiveStore>,
+    prefix: String,
+}
+
+impl ActionArchive {
+    pub fn new(store: Arc<dyn ArchiveStore>, prefix: Option<&str>) -> Self {
+        let prefix = match prefix.map(|prefix| prefix.trim_matches('/')) {
+            Some(prefix) if !prefix.is_empty() => format!("{}/", prefix),
+            _ => String::new(),
+        };
+        Self { store, prefix }
+    }
+
+    /// Archives `actions`, which must be of a single route and in ascending nonce order.
+    /// Returns once the file was read back and verified, and the manifest was written.
+    pub async fn archive(&self, actions: &[ArchivedAction]) -> anyhow::Result<ArchiveManifest> {
+        let (Some(first), Some(last)) = (actions.first(), actions.last()) else {
+            bail!("Nothing to archive");
+        };
+        let name = format!(
+            "{}-{}/{:020}-{:020}",
+            first.source_chain, first.destination_chain, first.nonce, last.nonce
+        );
+        let mut jsonl = vec![];
+        for action in actions {
+            serde_json::to_writer(&mut jsonl, action)?;
+            jsonl.push(b'\n');
+        }
+        let compressed = zstd::encode_all(jsonl.as_slice(), 0)?;
+        let manifest = ArchiveManifest {
+            format_version: ARCHIVE_FORMAT_VERSION,
+            file: format!("{}/{}.jsonl.zst", EXECUTED_DIR, name),
+            size: compressed.len() as u64,
+            sha256: sha256(&compressed),
+            created_at_ms: now_ms(),
+            digests: actions.iter().map(|action| action.digest.clone()).collect(),
+        };
+
+        let path = format!("{}{}", self.prefix, manifest.file);
+        self.store.put(&path, compressed).await?;
+        let uploaded = self.store.get(&path).await?;
+        if sha256(&uploaded) != manifest.sha256 {
+            bail!("Archived file {} doesn't match its checksum", path);
+        }
+        self.store
+            .put(
+                &format!("{}{}/{}.json", self.prefix, MANIFESTS_DIR, name),
+                serde_json::to_vec_pretty(&manifest)?,
+            )
+            .await?;
+        Ok(manifest)
+    }
+
+    /// Finds the archived action with `digest`, None if it's not in the archive.
+    pub async fn fetch(
+        &self,
+        digest: &BridgeActionDigest,
+    ) -> anyhow::Result<Option<ArchivedAction>> {
+        let digest = Hex::encode(digest.inner());
+        for manifest_path in self
+            .store
+            .list(&format!("{}{}", self.prefix, MANIFESTS_DIR))
+            .await?
+        {
+            let manifest: ArchiveManifest =
+                serde_json::from_slice(&self.store.get(&manifest_path).await?)
+                    .map_err(|e| anyhow!("Couldn't decode {}: {:?}", manifest_path, e))?;
+            if !manifest.digests.contains(&digest) {
+                continue;
+            }
+            let path = format!("{}{}", self.prefix, manifest.file);
+            let compressed = self.store.get(&path).await?;
+            if sha256(&compressed) != manifest.sha256 {
+                bail!("Archived file {} doesn't match its checksum", path);
+            }
+            for line in zstd::decode_all(compressed.as_slice())?.split(|byte| *byte == b'\n') {
+                if line.is_empty() {
+                    continue;
+                }
+                let action: ArchivedAction = serde_json::from_slice(line)
+                    .map_err(|e| anyhow!("Couldn't decode a line of {}: {:?}", path, e))?;
+                if action.digest == digest {
+                    return Ok(Some(action));
+                }
+            }
+            bail!("{} lists {} but doesn't hold it", manifest_path, digest);
+        }
+        Ok(None)
+    }
+}
+
+fn sha256(bytes: &[u8]) -> String {
+    Hex::encode(Sha256::digest(bytes).digest)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::get_test_sui_to_eth_bridge_action;
+
+    fn archived_action(nonce: u64) -> ArchivedAction {
+        let action =
+            get_test_sui_to_eth_bridge_action(None, None, Some(nonce), None, None, None, None);
+        ArchivedAction::new((2, 12, nonce), action, Some(nonce * 10))
+    }
+
+    #[tokio::test]
+    async fn test_archive_and_fetch() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let archive = ActionArchive::new(
+            Arc::new(FileArchiveStore::new(temp_dir.path().to_path_buf())),
+            Some("bridge/node-1/"),
+        );
+        let first_batch = (0..3).map(archived_action).collect::<Vec<_>>();
+        let second_batch = (3..5).map(archived_action).collect::<Vec<_>>();
+        let manifest = archive.archive(&first_batch).await.unwrap();
+        assert_eq!(
+            manifest.file,
+            "executed/2-12/00000000000000000000-00000000000000000002.jsonl.zst"
+        );
+        assert_eq!(manifest.digests.len(), 3);
+        archive.archive(&second_batch).await.unwrap();
+        assert!(temp_dir
+            .path()
+            .join("bridge/node-1/manifests/2-12/00000000000000000003-00000000000000000004.json")
+            .exists());
+
+        for action in first_batch.iter().chain(&second_batch) {
+            assert_eq!(
+                archive.fetch(&action.action.digest()).await.unwrap(),
+                Some(action.clone())
+            );
+        }
+        let unknown = archived_action(5).action.digest();
+        assert_eq!(archive.fetch(&unknown).await.unwrap(), None);
+
+        // A corrupted file is detected
+        std::fs::write(
+            temp_dir.path().join("bridge/node-1").join(&manifest.file),
+            b"",
+        )
+        .unwrap();
+        let err = archive
+            .fetch(&first_batch[0].action.digest())
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("checksum"), "{}", err);
+    }
+}
@@ -0,0 +1,147 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Broadcasts the actions the executor got certified by the committee, as soon as the
+//! quorum of signatures is reached, so that embedders and other components of the node,
+//! e.g. the event stream, react to them instead of polling the storage. Subscribers
+//! receive the actions in the order they were certified.
+//!
+//! The channel is bounded and never holds up the executor. A subscriber that falls more
+//! than the capacity of the channel, rounded up to a power of two, behind misses the
+//! oldest actions: its next `recv` returns `CertifiedActionsError::Lagged` with the number
+//! of missed actions, counted in `bridge_certified_actions_lagged`, and the following ones
+//! the next actions in order.
+
+use crate::metrics::BridgeMetrics;
+use crate::types::VerifiedCertifiedBridgeAction;
+use std::sync::Arc;
+use tokio::sync::broadcast;
+
+pub const DEFAULT_CERTIFIED_ACTIONS_CAPACITY: usize = 1000;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CertifiedActionsError {
+    /// The subscriber fell behind and missed this many actions
+    Lagged(u64),
+    /// The executor stopped
+    Closed,
+}
+
+pub struct CertifiedActions {
+    sender: broadcast::Sender<Arc<VerifiedCertifiedBridgeAction>>,
+    metrics: Arc<BridgeMetrics>,
+}
+
+impl CertifiedActions {
+    pub fn new(capacity: usize, metrics: Arc<BridgeMetrics>) -> Self {
+        let (sender, _) = broadcast::channel(capacity.max(1));
+        Self { sender, metrics }
+    }
+
+    /// Sends `certificate` to the current subscribers.
+    pub fn publish(&self, certificate: VerifiedCertifiedBridgeAction) {
+        self.metrics.certified_actions_published.inc();
+        // There may be no subscribers
+        let _ = self.sender.send(Arc::new(certificate));
+    }
+
+    /// Returns a receiver of the actions certified from now on.
+    pub fn subscribe(&self) -> CertifiedActionsReceiver {
+        CertifiedActionsReceiver {
+            receiver: self.sender.subscribe(),
+            metrics: self.metrics.clone(),
+        }
+    }
+}
+
+pub struct CertifiedActionsReceiver {
+    receiver: broadcast::Receiver<Arc<VerifiedCertifiedBridgeAction>>,
+    metrics: Arc<BridgeMetrics>,
+}
+
+impl CertifiedActionsReceiver {
+    pub async fn recv(
+        &mut self,
+    ) -> Result<Arc<VerifiedCertifiedBridgeAction>, CertifiedActionsError> {
+        match self.receiver.recv().await {
+            Ok(certificate) => Ok(certificate),
+            Err(broadcast::error::RecvError::Lagged(missed)) => {
+                self.metrics.certified_actions_lagged.inc_by(missed);
+                Err(CertifiedActionsError::Lagged(missed))
+            }
+            Err(broadcast::error::RecvError::Closed) => Err(CertifiedActionsError::Closed),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{
+        get_certified_action_with_validator_secrets, get_test_authority_and_key,
+        get_test_sui_to_eth_bridge_action,
+    };
+    use crate::types::BridgeAction;
+
+    fn certified_action(nonce: u64) -> VerifiedCertifiedBridgeAction {
+        let (_, _, secret) = get_test_authority_and_key(10000, 9999);
+        get_certified_action_with_validator_secrets(
+            get_test_sui_to_eth_bridge_action(None, None, Some(nonce), None, None, None, None),
+            &vec![secret],
+        )
+    }
+
+    async fn recv_nonce(receiver: &mut CertifiedActionsReceiver) -> u64 {
+        let certificate = receiver.recv().await.unwrap();
+        let action: &BridgeAction = certificate.data();
+        action.seq_number()
+    }
+
+    #[tokio::test]
+    async fn test_certified_actions_delivery_and_lag() {
+        let metrics = Arc::new(BridgeMetrics::for_testing());
+        let certified_actions = CertifiedActions::new(4, metrics.clone());
+        // Nothing to deliver to, nothing is held
+        certified_actions.publish(certified_action(0));
+
+        let mut first = certified_actions.subscribe();
+        let mut second = certified_actions.subscribe();
+        let mut stalled = certified_actions.subscribe();
+        for nonce in 1..=3 {
+            certified_actions.publish(certified_action(nonce));
+        }
+        for nonce in 1..=3 {
+            assert_eq!(recv_nonce(&mut first).await, nonce);
+        }
+        for nonce in 1..=3 {
+            assert_eq!(recv_nonce(&mut second).await, nonce);
+        }
+
+        // `first` keeps up and receives every action in order, the others miss the oldest
+        // ones and resume with the next ones
+        for nonce in 4..=8 {
+            certified_actions.publish(certified_action(nonce));
+            assert_eq!(recv_nonce(&mut first).await, nonce);
+        }
+        assert_eq!(
+            stalled.recv().await.unwrap_err(),
+            CertifiedActionsError::Lagged(4)
+        );
+        assert_eq!(
+            second.recv().await.unwrap_err(),
+            CertifiedActionsError::Lagged(1)
+        );
+        for nonce in 5..=8 {
+            assert_eq!(recv_nonce(&mut stalled).await, nonce);
+            assert_eq!(recv_nonce(&mut second).await, nonce);
+        }
+        assert_eq!(metrics.certified_actions_lagged.get(), 5);
+        assert_eq!(metrics.certified_actions_published.get(), 9);
+
+        drop(certified_actions);
+        assert_eq!(
+            first.recv().await.unwrap_err(),
+            CertifiedActionsError::Closed
+        );
+    }
+}
@@ -0,0 +1,250 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Health of the Sui and Eth pipelines of the node. Each chain is watched and verified by
+//! its own tasks, so an outage of one chain's RPC only stops the work that needs it: while
+//! Eth is down, Sui events are still observed and Sui transfers still signed. The health
+//! of each chain is tracked separately from the outcomes of its RPC queries, so that
+//! `/health` and `bridge_chain_pipeline_status` report a partial degradation instead of
+//! the node being up or down as a whole.
+
+use crate::error::{BridgeError, ErrorComponent, ErrorKind};
+use crate::metrics::BridgeMetrics;
+use crate::timestamps::now_ms;
+use std::sync::Mutex;
+
+/// Consecutive failed queries after which a chain is reported down rather than degraded
+pub const DOWN_AFTER_CONSECUTIVE_FAILURES: u64 = 5;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PipelineChain {
+    Sui,
+    Eth,
+}
+
+impl PipelineChain {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            PipelineChain::Sui => "sui",
+            PipelineChain::Eth => "eth",
+        }
+    }
+
+    /// Whether `err` means that the RPC of this chain could not be queried, rather than
+    /// answering something that makes the operation fail, e.g. a transaction that is not
+    /// finalized yet.
+    pub fn is_rpc_failure(&self, err: &BridgeError) -> bool {
+        let component = match self {
+            PipelineChain::Sui => ErrorComponent::SuiRpc,
+            PipelineChain::Eth => ErrorComponent::EthRpc,
+        };
+        err.component() == component
+            && matches!(err.kind(), ErrorKind::Transient | ErrorKind::RateLimited)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum PipelineStatus {
+    /// Nothing was queried yet
+    Unknown,
+    Healthy,
+    /// The last queries failed, but fewer than `DOWN_AFTER_CONSECUTIVE_FAILURES`
+    Degraded,
+    Down,
+}
+
+impl PipelineStatus {
+    /// The value of `bridge_chain_pipeline_status`
+    fn gauge_value(&self) -> i64 {
+        match self {
+            PipelineStatus::Unknown | PipelineStatus::Healthy => 0,
+            PipelineStatus::Degraded => 1,
+            PipelineStatus::Down => 2,
+        }
+    }
+
+    /// The status of the node as a whole: down if every chain is, degraded if any is.
+    pub fn combine(statuses: &[PipelineStatus]) -> PipelineStatus {
+        if !statuses.is_empty() && statuses.iter().all(|s| *s == PipelineStatus::Down) {
+            PipelineStatus::Down
+        } else if statuses
+            .iter()
+            .any(|s| matches!(s, PipelineStatus::Degraded | PipelineStatus::Down))
+        {
+            PipelineStatus::Degraded
+        } else if statuses.iter().any(|s| *s == PipelineStatus::Healthy) {
+            PipelineStatus::Healthy
+        } else {
+            PipelineStatus::Unknown
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ChainHealthState {
+    pub consecutive_failures: u64,
+    pub last_success_ms: Option<u64>,
+    pub last_failure_ms: Option<u64>,
+    pub last_error: Option<String>,
+    /// Tasks of the pipeline that were given up after crash looping, see `supervisor`
+    pub stopped_tasks: Vec<String>,
+}
+
+impl ChainHealthState {
+    pub fn status(&self) -> PipelineStatus {
+        if !self.stopped_tasks.is_empty() {
+            return PipelineStatus::Down;
+        }
+        match self.consecutive_failures {
+            0 if self.last_success_ms.is_none() => PipelineStatus::Unknown,
+            0 => PipelineStatus::Healthy,
+            n if n < DOWN_AFTER_CONSECUTIVE_FAILURES => PipelineStatus::Degraded,
+            _ => PipelineStatus::Down,
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct ChainHealth {
+    sui: Mutex<ChainHealthState>,
+    eth: Mutex<ChainHealthState>,
+}
+
+impl ChainHealth {
+    pub fn state(&self, chain: PipelineChain) -> ChainHealthState {
+        self.chain(chain).lock().unwrap().clone()
+    }
+
+    pub fn record_success(&self, chain: PipelineChain, metrics: &BridgeMetrics) {
+        let mut state = self.chain(chain).lock().unwrap();
+        state.consecutive_failures = 0;
+        state.last_success_ms = Some(now_ms());
+        Self::report(chain, &state, metrics);
+    }
+
+    pub fn record_failure(&self, chain: PipelineChain, error: String, metrics: &BridgeMetrics) {
+        let mut state = self.chain(chain).lock().unwrap();
+        state.consecutive_failures += 1;
+        state.last_failure_ms = Some(now_ms());
+        state.last_error = Some(error);
+        Self::report(chain, &state, metrics);
+    }
+
+    /// Reports the pipeline of `chain` down for good, since `task` is not running anymore.
+    pub fn record_stopped_task(&self, chain: PipelineChain, task: &str, metrics: &BridgeMetrics) {
+        let mut state = self.chain(chain).lock().unwrap();
+        state.stopped_tasks.push(task.to_string());
+        state.last_failure_ms = Some(now_ms());
+        state.last_error = Some(format!("Task {} was stopped after crash looping", task));
+        Self::report(chain, &state, metrics);
+    }
+
+    /// Records the outcome of an operation that queried the RPC of `chain`. Errors that
+    /// the RPC answered with count as a success of the pipeline.
+    pub fn record<T>(
+        &self,
+        chain: PipelineChain,
+        result: &Result<T, BridgeError>,
+        metrics: &BridgeMetrics,
+    ) {
+        match result {
+            Err(err) if chain.is_rpc_failure(err) => {
+                self.record_failure(chain, err.to_string(), metrics)
+            }
+            _ => self.record_success(chain, metrics),
+        }
+    }
+
+    fn chain(&self, chain: PipelineChain) -> &Mutex<ChainHealthState> {
+        match chain {
+            PipelineChain::Sui => &self.sui,
+            PipelineChain::Eth => &self.eth,
+        }
+    }
+
+    fn report(chain: PipelineChain, state: &ChainHealthState, metrics: &BridgeMetrics) {
+        metrics
+            .chain_pipeline_status
+            .with_label_values(&[chain.as_str()])
+            .set(state.status().gauge_value());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_chains_are_tracked_separately() {
+        let metrics = BridgeMetrics::for_testing();
+        let health = ChainHealth::default();
+        assert_eq!(
+            health.state(PipelineChain::Sui).status(),
+            PipelineStatus::Unknown
+        );
+
+        health.record::<()>(PipelineChain::Sui, &Ok(()), &metrics);
+        let eth_down = Err::<(), _>(BridgeError::ProviderError(
+            ErrorKind::Transient,
+            "connection refused".to_string(),
+        ));
+        health.record(PipelineChain::Eth, &eth_down, &metrics);
+        assert_eq!(
+            health.state(PipelineChain::Sui).status(),
+            PipelineStatus::Healthy
+        );
+        let eth = health.state(PipelineChain::Eth);
+        assert_eq!(eth.status(), PipelineStatus::Degraded);
+        assert!(eth.last_error.unwrap().contains("connection refused"));
+        let gauge = |chain: &str| {
+            metrics
+                .chain_pipeline_status
+                .with_label_values(&[chain])
+                .get()
+        };
+        assert_eq!((gauge("sui"), gauge("eth")), (0, 1));
+
+        for _ in 1..DOWN_AFTER_CONSECUTIVE_FAILURES {
+            health.record(PipelineChain::Eth, &eth_down, &metrics);
+        }
+        assert_eq!(
+            health.state(PipelineChain::Eth).status(),
+            PipelineStatus::Down
+        );
+        assert_eq!(gauge("eth"), 2);
+        assert_eq!(
+            PipelineStatus::combine(&[
+                health.state(PipelineChain::Sui).status(),
+                health.state(PipelineChain::Eth).status()
+            ]),
+            PipelineStatus::Degraded
+        );
+
+        // Answers of the RPC, even errors, mean it's up again
+        health.record::<()>(
+            PipelineChain::Eth,
+            &Err(BridgeError::TxNotFinalized),
+            &metrics,
+        );
+        assert_eq!(
+            health.state(PipelineChain::Eth).status(),
+            PipelineStatus::Healthy
+        );
+        // Errors of the other chain don't count
+        health.record(PipelineChain::Sui, &eth_down, &metrics);
+        assert_eq!(
+            health.state(PipelineChain::Sui).status(),
+            PipelineStatus::Healthy
+        );
+    }
+
+    #[test]
+    fn test_combined_status() {
+        use PipelineStatus::*;
+        assert_eq!(PipelineStatus::combine(&[Unknown, Unknown]), Unknown);
+        assert_eq!(PipelineStatus::combine(&[Healthy, Unknown]), Healthy);
+        assert_eq!(PipelineStatus::combine(&[Healthy, Down]), Degraded);
+        assert_eq!(PipelineStatus::combine(&[Degraded, Down]), Degraded);
+        assert_eq!(PipelineStatus::combine(&[Down, Down]), Down);
+    }
+}
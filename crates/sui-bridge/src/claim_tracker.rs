@@ -0,0 +1,541 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Claims on Eth of the token transfers from Sui. The committee approves a transfer from
+//! Sui on Sui, but the tokens only move once the user claims them on Eth, in a separate
+//! transaction. `ClaimTracker` polls the SuiBridge contract for the transfers that this
+//! node recorded as executed, i.e. approved, and reports the ones still unclaimed in
+//! `bridge_approved_unclaimed`, and by age in `bridge_approved_unclaimed_by_age`. Once a
+//! transfer is claimed, the claim transaction is looked up and recorded in the
+//! `transfer_claims` table. A transfer that stays unclaimed is checked half as often each
+//! time, up to a maximum interval, and all queries are spaced out to stay under a rate.
+//! The age of a transfer counts from when the tracker first found it, which is shortly
+//! after its approval unless the node was down.
+
+use crate::error::BridgeResult;
+use crate::eth_client::EthClient;
+use crate::metrics::BridgeMetrics;
+use crate::orchestrator::DEFAULT_ONCHAIN_STATUS_BATCH_SIZE;
+use crate::storage::{BridgeOrchestratorTables, TransferClaim};
+use crate::timestamps::now_ms;
+use ethers::providers::JsonRpcClient;
+use ethers::types::Address as EthAddress;
+use std::collections::BTreeMap;
+use std::sync::Arc;
+use sui_types::bridge::BridgeChainId;
+use tokio::time::{Duration, Interval, MissedTickBehavior};
+use tracing::{error, info, warn};
+
+pub const DEFAULT_CLAIM_TRACKING_INTERVAL: Duration = Duration::from_secs(60);
+pub const DEFAULT_CLAIM_TRACKING_MAX_BACKOFF: Duration = Duration::from_secs(6 * 60 * 60);
+pub const DEFAULT_CLAIM_TRACKING_MAX_RPC_QUERIES_PER_SECOND: u32 = 5;
+// Transfers approved before the tracker first ran are mostly claimed already
+pub const DEFAULT_CLAIM_TRACKING_LOOKBACK: u64 = 1000;
+// Executed transfers read from the storage at a time
+const DISCOVERY_BATCH_SIZE: usize = 1000;
+
+// Upper bounds of the age buckets of `bridge_approved_unclaimed_by_age`, older transfers
+// are counted as "older"
+const AGE_BUCKETS: &[(&str, u64)] = &[
+    ("1h", 60 * 60 * 1000),
+    ("6h", 6 * 60 * 60 * 1000),
+    ("1d", 24 * 60 * 60 * 1000),
+    ("7d", 7 * 24 * 60 * 60 * 1000),
+];
+const OLDER_AGE_BUCKET: &str = "older";
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct UnclaimedTransfer {
+    approved_at_ms: u64,
+    next_check_ms: u64,
+    // Times the transfer was found unclaimed, the interval between checks doubles with each
+    unclaimed_checks: u32,
+    // The latest block when the transfer was last found unclaimed, its claim is after it
+    unclaimed_at_block: Option<u64>,
+}
+
+pub struct ClaimTracker<P> {
+    eth_client: Arc<EthClient<P>>,
+    eth_bridge_address: EthAddress,
+    store: Arc<BridgeOrchestratorTables>,
+    sui_chain_id: u8,
+    eth_chain_id: u8,
+    interval: Duration,
+    max_backoff: Duration,
+    max_rpc_queries_per_second: u32,
+    status_batch_size: usize,
+    metrics: Arc<BridgeMetrics>,
+    // Unclaimed transfers by nonce
+    unclaimed: BTreeMap<u64, UnclaimedTransfer>,
+    // The next executed nonce to track, None until the tracked transfers are loaded
+    next_nonce: Option<u64>,
+}
+
+impl<P> ClaimTracker<P>
+where
+    P: JsonRpcClient + Clone + 'static,
+{
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        eth_client: Arc<EthClient<P>>,
+        eth_bridge_address: EthAddress,
+        store: Arc<BridgeOrchestratorTables>,
+        sui_chain_id: BridgeChainId,
+        eth_chain_id: BridgeChainId,
+        interval: Duration,
+        max_backoff: Duration,
+        max_rpc_queries_per_second: u32,
+        metrics: Arc<BridgeMetrics>,
+    ) -> Self {
+        Self {
+            eth_client,
+            eth_bridge_address,
+            store,
+            sui_chain_id: sui_chain_id as u8,
+            eth_chain_id: eth_chain_id as u8,
+            interval,
+            max_backoff,
+            max_rpc_queries_per_second,
+            status_batch_size: DEFAULT_ONCHAIN_STATUS_BATCH_SIZE,
+            metrics,
+            unclaimed: BTreeMap::new(),
+            next_nonce: None,
+        }
+    }
+
+    /// Number of nonces whose claim status is queried in one request.
+    pub fn with_status_batch_size(mut self, status_batch_size: usize) -> Self {
+        self.status_batch_size = status_batch_size;
+        self
+    }
+
+    pub async fn run(mut self) {
+        info!("Starting ClaimTracker");
+        let mut interval = tokio::time::interval(self.interval);
+        interval.set_missed_tick_behavior(MissedTickBehavior::Delay);
+        loop {
+            interval.tick().await;
+            if let Err(e) = self.poll(now_ms()).await {
+                error!("Failed to track the claims of token transfers: {:?}", e);
+            }
+        }
+    }
+
+    /// Tracks the transfers approved since the last poll, and checks the claim status of the
+    /// unclaimed ones that are due at `now_ms`.
+    pub async fn poll(&mut self, now_ms: u64) -> BridgeResult<()> {
+        if self.next_nonce.is_none() {
+            self.load(now_ms)?;
+        }
+        self.track_new_transfers(now_ms)?;
+
+        let due = self
+            .unclaimed
+            .iter()
+            .filter(|(_, transfer)| transfer.next_check_ms <= now_ms)
+            .map(|(nonce, _)| *nonce)
+            .collect::<Vec<_>>();
+        if !due.is_empty() {
+            self.check_claims(&due, now_ms).await?;
+        }
+        self.update_metrics(now_ms);
+        Ok(())
+    }
+
+    // Resumes tracking the transfers that were unclaimed before a restart
+    fn load(&mut self, now_ms: u64) -> BridgeResult<()> {
+        let claims = self
+            .store
+            .get_transfer_claims(self.sui_chain_id, self.eth_chain_id)?;
+        for (nonce, claim) in &claims {
+            if claim.claimed_at_ms.is_none() {
+                self.unclaimed.insert(
+                    *nonce,
+                    UnclaimedTransfer {
+                        approved_at_ms: claim.approved_at_ms,
+                        next_check_ms: now_ms,
+                        unclaimed_checks: 0,
+                        unclaimed_at_block: None,
+                    },
+                );
+            }
+        }
+        let next_nonce = match claims.keys().next_back() {
+            Some(nonce) => nonce + 1,
+            // First run, start from the most recent transfers
+            None => self
+                .store
+                .get_recent_executed_actions(self.sui_chain_id, self.eth_chain_id, 1)?
+                .first()
+                .map(|(nonce, _)| (nonce + 1).saturating_sub(DEFAULT_CLAIM_TRACKING_LOOKBACK))
+                .unwrap_or(0),
+        };
+        self.next_nonce = Some(next_nonce);
+        Ok(())
+    }
+
+    fn track_new_transfers(&mut self, now_ms: u64) -> BridgeResult<()> {
+        loop {
+            let next_nonce = self.next_nonce.unwrap_or(0);
+            let nonces = self.store.get_executed_nonces_from(
+                self.sui_chain_id,
+                self.eth_chain_id,
+                next_nonce,
+                DISCOVERY_BATCH_SIZE,
+            )?;
+            let Some(last_nonce) = nonces.last() else {
+                return Ok(());
+            };
+            let claim = TransferClaim {
+                approved_at_ms: now_ms,
+                claimed_at_ms: None,
+                claim_tx_hash: None,
+            };
+            self.store.insert_transfer_claims(
+                &nonces
+                    .iter()
+                    .map(|nonce| (self.key(*nonce), claim.clone()))
+                    .collect::<Vec<_>>(),
+            )?;
+            self.next_nonce = Some(last_nonce + 1);
+            for nonce in nonces {
+                self.unclaimed.insert(
+                    nonce,
+                    UnclaimedTransfer {
+                        approved_at_ms: now_ms,
+                        next_check_ms: now_ms,
+                        unclaimed_checks: 0,
+                        unclaimed_at_block: None,
+                    },
+                );
+            }
+        }
+    }
+
+    async fn check_claims(&mut self, nonces: &[u64], now_ms: u64) -> BridgeResult<()> {
+        // Spaces out RPC queries to stay under `max_rpc_queries_per_second`
+        let mut rpc_ticker =
+            tokio::time::interval(Duration::from_secs(1) / self.max_rpc_queries_per_second.max(1));
+        rpc_ticker.set_missed_tick_behavior(MissedTickBehavior::Delay);
+
+        // Transfers found unclaimed below are not claimed up to this block at least
+        rpc_ticker.tick().await;
+        let block = self.eth_client.get_latest_block_id().await?;
+        let batch_size = self.status_batch_size.max(1);
+        for batch in nonces.chunks(batch_size) {
+            rpc_ticker.tick().await;
+            let statuses = self
+                .eth_client
+                .are_transfers_processed(self.eth_bridge_address, batch, batch_size)
+                .await;
+            for (nonce, status) in batch.iter().zip(statuses) {
+                match status {
+                    Ok(true) => self.record_claim(*nonce, now_ms, &mut rpc_ticker).await?,
+                    Ok(false) => {
+                        let backoff = self.backoff(self.unclaimed[nonce].unclaimed_checks);
+                        let transfer = self.unclaimed.get_mut(nonce).unwrap();
+                        transfer.unclaimed_checks += 1;
+                        transfer.unclaimed_at_block = Some(block);
+                        transfer.next_check_ms = now_ms + backoff.as_millis() as u64;
+                    }
+                    Err(e) => {
+                        warn!(
+                            nonce,
+                            "Failed to get the claim status of token transfer: {:?}", e
+                        );
+                        let transfer = self.unclaimed.get_mut(nonce).unwrap();
+                        transfer.next_check_ms = now_ms + self.interval.as_millis() as u64;
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
+    async fn record_claim(
+        &mut self,
+        nonce: u64,
+        now_ms: u64,
+        rpc_ticker: &mut Interval,
+    ) -> BridgeResult<()> {
+        let Some(transfer) = self.unclaimed.remove(&nonce) else {
+            return Ok(());
+        };
+        // Pruned in the meantime
+        if self
+            .store
+            .get_executed_action(self.sui_chain_id, self.eth_chain_id, nonce)?
+            .is_none()
+        {
+            return Ok(());
+        }
+        // Without a block where it was unclaimed, the claim could be anywhere before
+        let claim_tx_hash = match transfer.unclaimed_at_block {
+            Some(block) => {
+                rpc_ticker.tick().await;
+                self.eth_client
+                    .get_claim_tx_hash(self.eth_bridge_address, self.sui_chain_id, nonce, block + 1)
+                    .await
+                    .unwrap_or_else(|e| {
+                        warn!(nonce, "Failed to get the claim transaction: {:?}", e);
+                        None
+                    })
+            }
+            None => None,
+        };
+        info!(
+            nonce,
+            "Token transfer claimed on Eth {}ms after its approval, in transaction {:?}",
+            now_ms.saturating_sub(transfer.approved_at_ms),
+            claim_tx_hash
+        );
+        self.store.insert_transfer_claims(&[(
+            self.key(nonce),
+            TransferClaim {
+                approved_at_ms: transfer.approved_at_ms,
+                claimed_at_ms: Some(now_ms),
+                claim_tx_hash,
+            },
+        )])
+    }
+
+    fn backoff(&self, unclaimed_checks: u32) -> Duration {
+        let factor = 1u32.checked_shl(unclaimed_checks).unwrap_or(u32::MAX);
+        self.interval.saturating_mul(factor).min(self.max_backoff)
+    }
+
+    fn update_metrics(&self, now_ms: u64) {
+        self.metrics
+            .approved_unclaimed
+            .set(self.unclaimed.len() as i64);
+        let mut by_age = AGE_BUCKETS
+            .iter()
+            .map(|(bucket, _)| (*bucket, 0))
+            .chain([(OLDER_AGE_BUCKET, 0)])
+            .collect::<BTreeMap<_, i64>>();
+        for transfer in self.unclaimed.values() {
+            let age_ms = now_ms.saturating_sub(transfer.approved_at_ms);
+            let bucket = AGE_BUCKETS
+                .iter()
+                .find(|(_, max_age_ms)| age_ms <= *max_age_ms)
+                .map(|(bucket, _)| *bucket)
+                .unwrap_or(OLDER_AGE_BUCKET);
+            *by_age.get_mut(bucket).unwrap() += 1;
+        }
+        for (bucket, count) in by_age {
+            self.metrics
+                .approved_unclaimed_by_age
+                .with_label_values(&[bucket])
+                .set(count);
+        }
+    }
+
+    fn key(&self, nonce: u64) -> (u8, u8, u64) {
+        (self.sui_chain_id, self.eth_chain_id, nonce)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::abi::{EthSuiBridge, TokensClaimedFilter};
+    use crate::eth_mock_provider::EthMockProvider;
+    use crate::test_utils::{get_test_sui_to_eth_bridge_action, mock_eth_call};
+    use ethers::abi::Token;
+    use ethers::contract::EthEvent;
+    use ethers::providers::Provider;
+    use ethers::types::{BlockNumber, Log, TxHash, H256, U256, U64};
+    use std::collections::HashSet;
+
+    const MINUTE_MS: u64 = 60_000;
+
+    fn mock_block_number(mock_provider: &EthMockProvider, block_number: u64) {
+        mock_provider
+            .add_response("eth_blockNumber", (), U64::from(block_number))
+            .unwrap();
+    }
+
+    fn mock_claimed(
+        mock_provider: &EthMockProvider,
+        bridge: &EthSuiBridge<Provider<EthMockProvider>>,
+        nonce: u64,
+        claimed: bool,
+    ) {
+        mock_eth_call(
+            mock_provider,
+            &bridge.is_transfer_processed(nonce).tx,
+            &[Token::Bool(claimed)],
+        );
+    }
+
+    fn mock_claim_log(
+        mock_provider: &EthMockProvider,
+        bridge: &EthSuiBridge<Provider<EthMockProvider>>,
+        nonce: u64,
+        start_block: u64,
+        tx_hash: TxHash,
+    ) {
+        let topic = |value: u64| H256::from_low_u64_be(value);
+        let log = Log {
+            address: bridge.address(),
+            topics: vec![
+                TokensClaimedFilter::signature(),
+                topic(BridgeChainId::SuiCustom as u64),
+                topic(nonce),
+                topic(BridgeChainId::EthCustom as u64),
+            ],
+            data: ethers::abi::encode(&[
+                Token::Uint(U256::from(1)),
+                Token::Uint(U256::from(100)),
+                Token::Bytes(vec![0; 32]),
+                Token::Address(EthAddress::random()),
+            ])
+            .into(),
+            block_hash: Some(H256::random()),
+            block_number: Some(U64::from(start_block + 1)),
+            transaction_hash: Some(tx_hash),
+            transaction_index: Some(U64::from(0)),
+            log_index: Some(U256::from(0)),
+            ..Default::default()
+        };
+        let filter = bridge
+            .tokens_claimed_filter()
+            .from_block(start_block)
+            .to_block(BlockNumber::Latest)
+            .topic1(U256::from(BridgeChainId::SuiCustom as u8))
+            .topic2(U256::from(nonce))
+            .filter;
+        mock_provider
+            .add_response("eth_getLogs", [filter], vec![log])
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_claim_tracker() {
+        telemetry_subscribers::init_for_testing();
+        let temp_dir = tempfile::tempdir().unwrap();
+        let store = BridgeOrchestratorTables::new(temp_dir.path());
+        let mock_provider = EthMockProvider::new();
+        let bridge_address = EthAddress::repeat_byte(7);
+        let bridge = EthSuiBridge::new(
+            bridge_address,
+            Arc::new(Provider::new(mock_provider.clone())),
+        );
+        let metrics = Arc::new(BridgeMetrics::for_testing());
+        let new_tracker = || {
+            ClaimTracker::new(
+                Arc::new(EthClient::new_mocked(mock_provider.clone(), HashSet::new())),
+                bridge_address,
+                store.clone(),
+                BridgeChainId::SuiCustom,
+                BridgeChainId::EthCustom,
+                Duration::from_secs(60),
+                Duration::from_secs(240),
+                1000,
+                metrics.clone(),
+            )
+        };
+        let mut tracker = new_tracker();
+        let by_age = |bucket: &str| {
+            metrics
+                .approved_unclaimed_by_age
+                .with_label_values(&[bucket])
+                .get()
+        };
+        let claims = || {
+            store
+                .get_transfer_claims(
+                    BridgeChainId::SuiCustom as u8,
+                    BridgeChainId::EthCustom as u8,
+                )
+                .unwrap()
+        };
+
+        let actions = (0..3)
+            .map(|nonce| {
+                get_test_sui_to_eth_bridge_action(None, None, Some(nonce), None, None, None, None)
+            })
+            .collect::<Vec<_>>();
+        store.mark_actions_executed(&actions).unwrap();
+        let start_ms = 1_700_000_000_000;
+
+        // Nonce 0 was claimed before it was tracked, its transaction is unknown
+        mock_block_number(&mock_provider, 100);
+        mock_claimed(&mock_provider, &bridge, 0, true);
+        mock_claimed(&mock_provider, &bridge, 1, false);
+        mock_claimed(&mock_provider, &bridge, 2, false);
+        tracker.poll(start_ms).await.unwrap();
+        assert_eq!(metrics.approved_unclaimed.get(), 2);
+        assert_eq!(by_age("1h"), 2);
+        assert_eq!(
+            claims()[&0],
+            TransferClaim {
+                approved_at_ms: start_ms,
+                claimed_at_ms: Some(start_ms),
+                claim_tx_hash: None,
+            }
+        );
+        assert_eq!(claims()[&1].claimed_at_ms, None);
+
+        // Nonce 1 is claimed after block 100
+        let claim_tx_hash = TxHash::random();
+        mock_block_number(&mock_provider, 110);
+        mock_claimed(&mock_provider, &bridge, 1, true);
+        mock_claim_log(&mock_provider, &bridge, 1, 101, claim_tx_hash);
+        tracker.poll(start_ms + MINUTE_MS).await.unwrap();
+        assert_eq!(metrics.approved_unclaimed.get(), 1);
+        assert_eq!(
+            claims()[&1],
+            TransferClaim {
+                approved_at_ms: start_ms,
+                claimed_at_ms: Some(start_ms + MINUTE_MS),
+                claim_tx_hash: Some(claim_tx_hash),
+            }
+        );
+
+        // Nonce 2 was found unclaimed twice, it's checked 2 minutes later
+        assert_eq!(
+            tracker.unclaimed[&2].next_check_ms,
+            start_ms + 3 * MINUTE_MS
+        );
+        mock_provider.clear_responses();
+        tracker.poll(start_ms + 2 * MINUTE_MS).await.unwrap();
+        assert_eq!(tracker.unclaimed[&2].unclaimed_checks, 2);
+
+        // Then at most every 4 minutes
+        mock_block_number(&mock_provider, 200);
+        mock_claimed(&mock_provider, &bridge, 2, false);
+        let two_hours_ms = 120 * MINUTE_MS;
+        tracker.poll(start_ms + two_hours_ms).await.unwrap();
+        assert_eq!(
+            tracker.unclaimed[&2].next_check_ms,
+            start_ms + two_hours_ms + 4 * MINUTE_MS
+        );
+        assert_eq!(metrics.approved_unclaimed.get(), 1);
+        assert_eq!(by_age("1h"), 0);
+        assert_eq!(by_age("6h"), 1);
+
+        // A new transfer is tracked, and the unclaimed ones again after a restart
+        store
+            .mark_actions_executed(&[get_test_sui_to_eth_bridge_action(
+                None,
+                None,
+                Some(3),
+                None,
+                None,
+                None,
+                None,
+            )])
+            .unwrap();
+        mock_claimed(&mock_provider, &bridge, 3, false);
+        let mut tracker = new_tracker();
+        tracker.poll(start_ms + two_hours_ms).await.unwrap();
+        assert_eq!(
+            tracker.unclaimed.keys().copied().collect::<Vec<_>>(),
+            vec![2, 3]
+        );
+        assert_eq!(tracker.unclaimed[&2].approved_at_ms, start_ms);
+        assert_eq!(metrics.approved_unclaimed.get(), 2);
+        assert_eq!(claims().len(), 4);
+    }
+}
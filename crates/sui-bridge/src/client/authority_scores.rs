@@ -0,0 +1,273 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Availability scores of the committee members, as seen by the `BridgeAuthorityAggregator`
+//! of this node. Authorities that are slow or failing are requested after the others.
+//! Scores are persisted periodically, so that right after a restart the node still
+//! knows which members were bad.
+
+use crate::client::bridge_authority_aggregator::BridgeAuthorityAggregator;
+use crate::crypto::BridgeAuthorityPublicKeyBytes;
+use crate::storage::BridgeOrchestratorTables;
+use crate::types::BridgeCommittee;
+use arc_swap::ArcSwap;
+use serde::{Deserialize, Serialize};
+use std::collections::btree_map::Entry;
+use std::collections::{BTreeMap, BTreeSet};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+use tokio::time;
+use tracing::{error, info};
+
+pub const DEFAULT_SCORE_PERSIST_INTERVAL: Duration = Duration::from_secs(60);
+
+// Weight of a new observation in the moving averages, in percent
+const EWMA_WEIGHT_PERCENT: u64 = 20;
+const MAX_FAILURE_RATE_BPS: u64 = 10_000;
+// Authorities at or above either of these are requested after the others
+const DEGRADED_FAILURE_RATE_BPS: u64 = 5_000;
+const DEGRADED_LATENCY_MS: u64 = 2_500;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AuthorityScore {
+    /// Moving average of the signing request latency
+    pub ewma_latency_ms: u64,
+    /// Moving average of failed signing requests, in basis points
+    pub failure_rate_bps: u64,
+    pub last_success_timestamp_ms: Option<u64>,
+}
+
+impl AuthorityScore {
+    fn new(latency_ms: u64, failed: bool) -> Self {
+        Self {
+            ewma_latency_ms: latency_ms,
+            failure_rate_bps: if failed { MAX_FAILURE_RATE_BPS } else { 0 },
+            last_success_timestamp_ms: (!failed).then(now_ms),
+        }
+    }
+
+    fn record_latency(&mut self, latency_ms: u64) {
+        self.ewma_latency_ms = ewma(self.ewma_latency_ms, latency_ms);
+    }
+
+    fn record_result(&mut self, failed: bool) {
+        if failed {
+            self.failure_rate_bps = ewma(self.failure_rate_bps, MAX_FAILURE_RATE_BPS);
+        } else {
+            self.failure_rate_bps = ewma(self.failure_rate_bps, 0);
+            self.last_success_timestamp_ms = Some(now_ms());
+        }
+    }
+
+    pub fn is_degraded(&self) -> bool {
+        self.failure_rate_bps >= DEGRADED_FAILURE_RATE_BPS
+            || self.ewma_latency_ms >= DEGRADED_LATENCY_MS
+    }
+}
+
+fn now_ms() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap()
+        .as_millis() as u64
+}
+
+fn ewma(average: u64, sample: u64) -> u64 {
+    (average * (100 - EWMA_WEIGHT_PERCENT) + sample * EWMA_WEIGHT_PERCENT) / 100
+}
+
+#[derive(Debug, Default)]
+pub struct AuthorityScoreboard {
+    scores: Mutex<BTreeMap<BridgeAuthorityPublicKeyBytes, AuthorityScore>>,
+}
+
+impl AuthorityScoreboard {
+    /// Records the result of a signing request to `name` that took `latency`.
+    pub fn record(&self, name: &BridgeAuthorityPublicKeyBytes, latency: Duration, failed: bool) {
+        let latency_ms = latency.as_millis() as u64;
+        match self.scores.lock().unwrap().entry(name.clone()) {
+            Entry::Vacant(e) => {
+                e.insert(AuthorityScore::new(latency_ms, failed));
+            }
+            Entry::Occupied(mut e) => {
+                e.get_mut().record_latency(latency_ms);
+                e.get_mut().record_result(failed);
+            }
+        }
+    }
+
+    /// Records that `name` had not responded after `elapsed`, when the aggregation
+    /// finished without it. Only the latency is updated, with a lower bound.
+    pub fn record_unfinished(&self, name: &BridgeAuthorityPublicKeyBytes, elapsed: Duration) {
+        let elapsed_ms = elapsed.as_millis() as u64;
+        match self.scores.lock().unwrap().entry(name.clone()) {
+            Entry::Vacant(e) => {
+                e.insert(AuthorityScore {
+                    ewma_latency_ms: elapsed_ms,
+                    ..Default::default()
+                });
+            }
+            Entry::Occupied(mut e) => e.get_mut().record_latency(elapsed_ms),
+        }
+    }
+
+    pub fn get(&self, name: &BridgeAuthorityPublicKeyBytes) -> Option<AuthorityScore> {
+        self.scores.lock().unwrap().get(name).copied()
+    }
+
+    pub fn snapshot(&self) -> BTreeMap<BridgeAuthorityPublicKeyBytes, AuthorityScore> {
+        self.scores.lock().unwrap().clone()
+    }
+
+    /// Replaces the scores with `scores` of the members of `committee`.
+    pub fn seed(
+        &self,
+        scores: BTreeMap<BridgeAuthorityPublicKeyBytes, AuthorityScore>,
+        committee: &BridgeCommittee,
+    ) {
+        let mut current = self.scores.lock().unwrap();
+        *current = scores;
+        current.retain(|name, _| committee.members().contains_key(name));
+    }
+
+    /// Drops the scores of authorities that are no longer in `committee`.
+    pub fn retain_members(&self, committee: &BridgeCommittee) {
+        self.scores
+            .lock()
+            .unwrap()
+            .retain(|name, _| committee.members().contains_key(name));
+    }
+
+    /// Returns the members of `committee` that should be requested first, i.e. the ones
+    /// that are not degraded. None if no member or every member is degraded.
+    pub fn preferred_authorities(
+        &self,
+        committee: &BridgeCommittee,
+    ) -> Option<BTreeSet<BridgeAuthorityPublicKeyBytes>> {
+        let scores = self.scores.lock().unwrap();
+        let (preferred, degraded): (BTreeSet<_>, BTreeSet<_>) = committee
+            .members()
+            .keys()
+            .cloned()
+            .partition(|name| !scores.get(name).is_some_and(|score| score.is_degraded()));
+        if preferred.is_empty() || degraded.is_empty() {
+            return None;
+        }
+        Some(preferred)
+    }
+
+    /// Starts timing a signing request to `name`. The request is recorded as unfinished
+    /// if the returned guard is dropped before `finish`.
+    pub(crate) fn start_request(
+        self: &Arc<Self>,
+        name: BridgeAuthorityPublicKeyBytes,
+    ) -> ScoredRequest {
+        ScoredRequest {
+            scoreboard: self.clone(),
+            name,
+            start: Instant::now(),
+            finished: false,
+        }
+    }
+}
+
+pub(crate) struct ScoredRequest {
+    scoreboard: Arc<AuthorityScoreboard>,
+    name: BridgeAuthorityPublicKeyBytes,
+    start: Instant,
+    finished: bool,
+}
+
+impl ScoredRequest {
+    pub(crate) fn finish(mut self, failed: bool) {
+        self.finished = true;
+        self.scoreboard
+            .record(&self.name, self.start.elapsed(), failed);
+    }
+}
+
+impl Drop for ScoredRequest {
+    fn drop(&mut self) {
+        if !self.finished {
+            self.scoreboard
+                .record_unfinished(&self.name, self.start.elapsed());
+        }
+    }
+}
+
+/// Periodically writes the scores of the current aggregator to storage.
+pub struct AuthorityScorePersister {
+    auth_agg: Arc<ArcSwap<BridgeAuthorityAggregator>>,
+    store: Arc<BridgeOrchestratorTables>,
+    interval: Duration,
+}
+
+impl AuthorityScorePersister {
+    pub fn new(
+        auth_agg: Arc<ArcSwap<BridgeAuthorityAggregator>>,
+        store: Arc<BridgeOrchestratorTables>,
+        interval: Duration,
+    ) -> Self {
+        Self {
+            auth_agg,
+            store,
+            interval,
+        }
+    }
+
+    pub async fn run(self) {
+        info!("Starting AuthorityScorePersister");
+        let mut interval = time::interval(self.interval);
+        interval.set_missed_tick_behavior(time::MissedTickBehavior::Delay);
+        loop {
+            interval.tick().await;
+            if let Err(e) = self.auth_agg.load().persist_scores(&self.store) {
+                error!("Failed to persist committee scores: {:?}", e);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::get_test_authority_and_key;
+
+    #[test]
+    fn test_authority_score_moving_averages() {
+        let (authority, _, _) = get_test_authority_and_key(10000, 9999);
+        let name = authority.pubkey_bytes();
+        let scoreboard = AuthorityScoreboard::default();
+
+        scoreboard.record(&name, Duration::from_millis(100), false);
+        let score = scoreboard.get(&name).unwrap();
+        assert_eq!(score.ewma_latency_ms, 100);
+        assert_eq!(score.failure_rate_bps, 0);
+        assert!(score.last_success_timestamp_ms.is_some());
+        assert!(!score.is_degraded());
+
+        scoreboard.record(&name, Duration::from_millis(600), true);
+        let score = scoreboard.get(&name).unwrap();
+        assert_eq!(score.ewma_latency_ms, 200);
+        assert_eq!(score.failure_rate_bps, 2_000);
+
+        // Unfinished requests only move the latency
+        scoreboard.record_unfinished(&name, Duration::from_millis(5_200));
+        let score = scoreboard.get(&name).unwrap();
+        assert_eq!(score.ewma_latency_ms, 1_200);
+        assert_eq!(score.failure_rate_bps, 2_000);
+
+        // A failing authority is degraded right away and recovers with successes
+        let (authority, _, _) = get_test_authority_and_key(10000, 9999);
+        let name = authority.pubkey_bytes();
+        scoreboard.record(&name, Duration::from_millis(100), true);
+        let score = scoreboard.get(&name).unwrap();
+        assert_eq!(score.failure_rate_bps, MAX_FAILURE_RATE_BPS);
+        assert_eq!(score.last_success_timestamp_ms, None);
+        assert!(score.is_degraded());
+        for _ in 0..4 {
+            scoreboard.record(&name, Duration::from_millis(100), false);
+        }
+        assert!(!scoreboard.get(&name).unwrap().is_degraded());
+    }
+}
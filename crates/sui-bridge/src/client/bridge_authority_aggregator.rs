@@ -3,10 +3,16 @@
 
 //! BridgeAuthorityAggregator aggregates signatures from BridgeCommittee.
 
-use crate::client::bridge_client::BridgeClient;
+use crate::aggregation_status::AggregationTracker;
+use crate::client::bridge_client::{BridgeClient, CommitteeConnectionOptions};
+use crate::client::hedging::RequestHedging;
+use crate::crypto::canonicalize_signed_action;
 use crate::crypto::BridgeAuthorityPublicKeyBytes;
 use crate::crypto::BridgeAuthoritySignInfo;
 use crate::error::{BridgeError, BridgeResult};
+use crate::metrics::BridgeMetrics;
+use crate::server::request_id::new_request_id;
+use crate::storage::BridgeOrchestratorTables;
 use crate::types::BridgeCommitteeValiditySignInfo;
 use crate::types::{
     BridgeAction, BridgeCommittee, CertifiedBridgeAction, VerifiedCertifiedBridgeAction,
@@ -22,15 +28,61 @@ use sui_authority_aggregation::ReduceOutput;
 use sui_types::base_types::ConciseableName;
 use sui_types::committee::StakeUnit;
 use sui_types::committee::TOTAL_VOTING_POWER;
-use tracing::{error, info, warn};
+use tokio::sync::OnceCell;
+use tracing::{error, info, info_span, warn, Instrument};
 
 pub struct BridgeAuthorityAggregator {
     pub committee: Arc<BridgeCommittee>,
     pub clients: Arc<BTreeMap<BridgeAuthorityPublicKeyBytes, Arc<BridgeClient>>>,
+    pub metrics: Arc<BridgeMetrics>,
+    // Whether the committee members must be reached over https
+    pub https_required: bool,
+    pub connection_options: Arc<CommitteeConnectionOptions>,
+    /// None when the signing requests are not hedged
+    pub hedging: Option<Arc<RequestHedging>>,
+    // Members registered with the same url as another member, to the member that is
+    // requested for all of them
+    url_representatives:
+        Arc<BTreeMap<BridgeAuthorityPublicKeyBytes, BridgeAuthorityPublicKeyBytes>>,
 }
 
 impl BridgeAuthorityAggregator {
-    pub fn new(committee: Arc<BridgeCommittee>) -> Self {
+    pub fn new(committee: Arc<BridgeCommittee>, metrics: Arc<BridgeMetrics>) -> Self {
+        Self::new_with_https_required(committee, metrics, false)
+    }
+
+    /// Like `new`, but members whose url is not https are unreachable when
+    /// `https_required` is true. Members with invalid urls are counted in
+    /// `bridge_committee_members_with_invalid_url`, members that registered the same url
+    /// as another member in `bridge_committee_anomalies`.
+    pub fn new_with_https_required(
+        committee: Arc<BridgeCommittee>,
+        metrics: Arc<BridgeMetrics>,
+        https_required: bool,
+    ) -> Self {
+        // The node behind a url signs with one key only, requesting it once per member
+        // would count its answer several times
+        let members_sharing_url = committee.members_sharing_url();
+        for members in &members_sharing_url {
+            warn!(
+                "Committee members {:?} registered the same url {:?}, they are requested once and only the member whose key signs is credited",
+                members
+                    .iter()
+                    .map(|member| member.concise_owned())
+                    .collect::<Vec<_>>(),
+                committee
+                    .member(&members[0])
+                    .map(|member| member.base_url.as_str())
+                    .unwrap_or_default(),
+            );
+        }
+        metrics
+            .committee_anomalies
+            .set(members_sharing_url.iter().map(Vec::len).sum::<usize>() as i64);
+        let url_groups = members_sharing_url
+            .iter()
+            .flat_map(|members| members.iter().map(move |member| (member, members)))
+            .collect::<BTreeMap<_, _>>();
         let clients: BTreeMap<BridgeAuthorityPublicKeyBytes, Arc<BridgeClient>> = committee
             .members()
             .iter()
@@ -40,11 +92,19 @@ impl BridgeAuthorityAggregator {
                     return None;
                 }
                 // TODO: we could also record bad stakes here and use in signature aggregation
-                match BridgeClient::new(
+                match BridgeClient::new_with_https_required(
                     name.clone(),
                     committee.clone(),
+                    https_required,
                 ) {
-                    Ok(client) => Some((name.clone(), Arc::new(client))),
+                    Ok(client) => {
+                        let client = client.with_metrics(metrics.clone());
+                        let client = match url_groups.get(name) {
+                            Some(members) => client.with_members_sharing_url(members.iter().cloned()),
+                            None => client,
+                        };
+                        Some((name.clone(), Arc::new(client)))
+                    }
                     Err(e) => {
                         error!(
                             "Failed to create BridgeClient for {:?}: {:?}",
@@ -56,23 +116,111 @@ impl BridgeAuthorityAggregator {
                 }
             })
             .collect::<BTreeMap<_, _>>();
+        let members_with_invalid_url = clients
+            .values()
+            .filter(|client| !client.has_valid_url())
+            .count();
+        metrics
+            .committee_members_with_invalid_url
+            .set(members_with_invalid_url as i64);
+        let mut url_representatives = BTreeMap::new();
+        for members in members_sharing_url {
+            let members = members
+                .into_iter()
+                .filter(|member| clients.contains_key(member))
+                .collect::<Vec<_>>();
+            if members.len() > 1 {
+                for member in &members {
+                    url_representatives.insert(member.clone(), members[0].clone());
+                }
+            }
+        }
         Self {
             committee,
             clients: Arc::new(clients),
+            metrics,
+            https_required,
+            connection_options: Arc::new(CommitteeConnectionOptions::default()),
+            hedging: None,
+            url_representatives: Arc::new(url_representatives),
         }
     }
 
+    /// Connects to the committee members with `options` rather than the defaults.
+    pub fn with_connection_options(mut self, options: Arc<CommitteeConnectionOptions>) -> Self {
+        let clients = self
+            .clients
+            .iter()
+            .map(|(name, client)| {
+                let client = match client
+                    .as_ref()
+                    .clone()
+                    .with_connection_options(options.clone())
+                {
+                    Ok(client) => Arc::new(client),
+                    Err(e) => {
+                        error!(
+                            "Failed to configure the connections to {:?}, using the defaults: {:?}",
+                            name.concise(),
+                            e
+                        );
+                        client.clone()
+                    }
+                };
+                (name.clone(), client)
+            })
+            .collect();
+        self.clients = Arc::new(clients);
+        self.connection_options = options;
+        self
+    }
+
+    /// Hedges the signing requests to the members that are slow to respond, see `hedging`.
+    pub fn with_request_hedging(mut self, hedging: Arc<RequestHedging>) -> Self {
+        self.hedging = Some(hedging);
+        self
+    }
+
+    /// Seeds the scores of the committee members from `store`, e.g. after a restart.
+    pub fn load_scores(&self, store: &BridgeOrchestratorTables) {
+        self.metrics
+            .committee_scores
+            .seed(store.get_authority_scores(), &self.committee);
+    }
+
+    /// Writes the scores to `store`, dropping the ones of authorities that are no longer
+    /// in the committee.
+    pub fn persist_scores(&self, store: &BridgeOrchestratorTables) -> BridgeResult<()> {
+        let scores = &self.metrics.committee_scores;
+        scores.retain_members(&self.committee);
+        store.replace_authority_scores(&scores.snapshot())
+    }
+
     pub async fn request_committee_signatures(
         &self,
         action: BridgeAction,
     ) -> BridgeResult<VerifiedCertifiedBridgeAction> {
-        let state = GetSigsState::new(action.approval_threshold(), self.committee.clone());
+        let mut state = GetSigsState::new(action.approval_threshold(), self.committee.clone());
+        // Dropped with the state, once the aggregation completes or is abandoned
+        state.tracker = Some(self.metrics.aggregation_status.start(
+            &action,
+            action.approval_threshold(),
+            self.clients.keys().cloned(),
+        ));
+        // Sent to every authority, so that this attempt can be found in their logs
+        let request_id = new_request_id();
+        let span = info_span!("aggregation", request_id = %request_id);
         request_sign_bridge_action_into_certification(
             action,
             self.committee.clone(),
             self.clients.clone(),
+            &self.url_representatives,
+            self.hedging.clone(),
             state,
+            self.metrics.clone(),
+            request_id,
         )
+        .instrument(span)
         .await
     }
 }
@@ -80,10 +228,14 @@ impl BridgeAuthorityAggregator {
 #[derive(Debug)]
 struct GetSigsState {
     total_bad_stake: StakeUnit,
+    // Stake of the authorities that rejected the action as invalid, part of the bad stake
+    total_rejected_stake: StakeUnit,
     total_ok_stake: StakeUnit,
     sigs: BTreeMap<BridgeAuthorityPublicKeyBytes, BridgeAuthoritySignInfo>,
     validity_threshold: StakeUnit,
     committee: Arc<BridgeCommittee>,
+    // Publishes the answers of the members in `/admin/aggregation_status`
+    tracker: Option<AggregationTracker>,
 }
 
 impl GetSigsState {
@@ -91,9 +243,11 @@ impl GetSigsState {
         Self {
             committee,
             total_bad_stake: 0,
+            total_rejected_stake: 0,
             total_ok_stake: 0,
             sigs: BTreeMap::new(),
             validity_threshold,
+            tracker: None,
         }
     }
 
@@ -116,6 +270,9 @@ impl GetSigsState {
             Entry::Vacant(e) => {
                 e.insert(signed_action.auth_sig().clone());
                 self.total_ok_stake += stake;
+                if let Some(tracker) = &self.tracker {
+                    tracker.signed(&name, self.total_ok_stake);
+                }
             }
             Entry::Occupied(_e) => {
                 return Err(BridgeError::AuthoritySignatureDuplication(format!(
@@ -152,29 +309,42 @@ impl GetSigsState {
         }
     }
 
+    fn record_error(&self, name: &BridgeAuthorityPublicKeyBytes, category: &str) {
+        if let Some(tracker) = &self.tracker {
+            tracker.errored(name, category);
+        }
+    }
+
     fn add_bad_stake(&mut self, bad_stake: StakeUnit) {
         self.total_bad_stake += bad_stake;
     }
 
+    fn add_rejected_stake(&mut self, rejected_stake: StakeUnit) {
+        self.total_rejected_stake += rejected_stake;
+        self.total_bad_stake += rejected_stake;
+    }
+
+    // Whether the rejections alone keep the action from being certified, no matter
+    // how often it is requested again
+    fn is_rejected(&self) -> bool {
+        TOTAL_VOTING_POWER - self.total_rejected_stake - self.committee.total_blocklisted_stake()
+            < self.validity_threshold
+    }
+
     fn is_too_many_error(&self) -> bool {
         TOTAL_VOTING_POWER - self.total_bad_stake - self.committee.total_blocklisted_stake()
             < self.validity_threshold
     }
 }
 
-async fn request_sign_bridge_action_into_certification(
-    action: BridgeAction,
-    committee: Arc<BridgeCommittee>,
-    clients: Arc<BTreeMap<BridgeAuthorityPublicKeyBytes, Arc<BridgeClient>>>,
-    state: GetSigsState,
-) -> BridgeResult<VerifiedCertifiedBridgeAction> {
+fn action_preference(action: &BridgeAction) -> Option<BTreeSet<BridgeAuthorityPublicKeyBytes>> {
     // `preferences` is used as a trick here to influence the order of validators to be requested.
     // * if `Some(_)`, then we will request validators in the order of the voting power.
     // * if `None`, we still refer to voting power, but they are shuffled by randomness.
     // Because ethereum gas price is not negligible, when the signatures are to be verified on ethereum,
     // we pass in `Some` to make sure the validators with higher voting power are requested first
     // to save gas cost.
-    let preference = match action {
+    match action {
         BridgeAction::SuiToEthBridgeAction(_) => Some(BTreeSet::new()),
         BridgeAction::EthToSuiBridgeAction(_) => None,
         _ => {
@@ -184,24 +354,107 @@ async fn request_sign_bridge_action_into_certification(
                 Some(BTreeSet::new())
             }
         }
-    };
+    }
+}
+
+// Returns the part of `result`, the result of the request shared by the members registered
+// with the url of `name`, that is credited to `name`: the signature only if it was signed
+// with the key of `name`, and the error only if `name` is the member that was requested.
+fn shared_request_result_of_member(
+    name: &BridgeAuthorityPublicKeyBytes,
+    representative: &BridgeAuthorityPublicKeyBytes,
+    result: &BridgeResult<VerifiedSignedBridgeAction>,
+) -> BridgeResult<VerifiedSignedBridgeAction> {
+    match result {
+        Ok(signed_action) if &signed_action.auth_sig().authority_pub_key_bytes() == name => {
+            Ok(signed_action.clone())
+        }
+        Ok(_) => Err(BridgeError::MismatchedAuthoritySigner),
+        Err(e) if name == representative => Err(e.clone()),
+        Err(_) => Err(BridgeError::Generic(format!(
+            "Request to the url shared with {} failed",
+            representative.concise()
+        ))),
+    }
+}
+
+async fn request_sign_bridge_action_into_certification(
+    action: BridgeAction,
+    committee: Arc<BridgeCommittee>,
+    clients: Arc<BTreeMap<BridgeAuthorityPublicKeyBytes, Arc<BridgeClient>>>,
+    url_representatives: &BTreeMap<BridgeAuthorityPublicKeyBytes, BridgeAuthorityPublicKeyBytes>,
+    hedging: Option<Arc<RequestHedging>>,
+    state: GetSigsState,
+    metrics: Arc<BridgeMetrics>,
+    request_id: String,
+) -> BridgeResult<VerifiedCertifiedBridgeAction> {
+    // Validators that were slow or failing recently are requested after the others
+    let preference = metrics
+        .committee_scores
+        .preferred_authorities(&committee)
+        .or_else(|| action_preference(&action));
+    let scores = metrics.committee_scores.clone();
+    // One request per url, sent by its representative whichever of its members is
+    // requested first
+    let shared_requests = url_representatives
+        .values()
+        .map(|representative| {
+            (
+                representative.clone(),
+                (clients[representative].clone(), Arc::new(OnceCell::new())),
+            )
+        })
+        .collect::<BTreeMap<_, _>>();
     let (result, _) = quorum_map_then_reduce_with_timeout_and_prefs(
         committee,
         clients,
         preference.as_ref(),
         state,
-        |_name, client| {
-            Box::pin(async move { client.request_sign_bridge_action(action.clone()).await })
+        |name, client| {
+            let shared_request = url_representatives.get(&name).map(|representative| {
+                let (client, result) = &shared_requests[representative];
+                (representative.clone(), client.clone(), result.clone())
+            });
+            Box::pin(async move {
+                let request = |name: BridgeAuthorityPublicKeyBytes, client: Arc<BridgeClient>| async move {
+                    // Recorded as unfinished if the signatures are aggregated before it returns
+                    let request = scores.start_request(name.clone());
+                    let sign = || {
+                        client.request_sign_bridge_action_with_request_id(action.clone(), &request_id)
+                    };
+                    let result = match &hedging {
+                        Some(hedging) => hedging.request(&name, sign).await,
+                        None => sign().await,
+                    };
+                    request.finish(result.is_err());
+                    result
+                };
+                match shared_request {
+                    Some((representative, representative_client, shared_result)) => {
+                        let result = shared_result
+                            .get_or_init(|| request(representative.clone(), representative_client))
+                            .await;
+                        shared_request_result_of_member(&name, &representative, result)
+                    }
+                    None => request(name, client).await,
+                }
+            })
         },
         |mut state, name, stake, result| {
+            let metrics = metrics.clone();
             Box::pin(async move {
                 match result {
                     Ok(verified_signed_action) => {
-                        match state.handle_verified_signed_action(
-                            name.clone(),
-                            stake,
-                            verified_signed_action,
-                        ) {
+                        // Certificates only hold signatures in the form the Move verifier
+                        // accepts, a non-canonical one would fail at execution
+                        match canonicalize_signed_action(verified_signed_action, &metrics)
+                            .and_then(|verified_signed_action| {
+                                state.handle_verified_signed_action(
+                                    name.clone(),
+                                    stake,
+                                    verified_signed_action,
+                                )
+                            }) {
                             Ok(Some(certified_action)) => {
                                 return ReduceOutput::Success(certified_action)
                             }
@@ -212,6 +465,11 @@ async fn request_sign_bridge_action_into_certification(
                                     name.concise(),
                                     e
                                 );
+                                metrics
+                                    .auth_agg_request_failures
+                                    .with_label_values(&[&name.concise_owned(), "other"])
+                                    .inc();
+                                state.record_error(&name, "other");
                                 state.add_bad_stake(stake);
                             }
                         }
@@ -222,7 +480,23 @@ async fn request_sign_bridge_action_into_certification(
                             name.concise(),
                             e
                         );
-                        state.add_bad_stake(stake);
+                        let category = match &e {
+                            BridgeError::BridgeClientError(category, _) => category.as_str(),
+                            _ => "other",
+                        };
+                        metrics
+                            .auth_agg_request_failures
+                            .with_label_values(&[&name.concise_owned(), category])
+                            .inc();
+                        state.record_error(&name, category);
+                        match &e {
+                            BridgeError::BridgeClientError(category, _)
+                                if category.is_rejection() =>
+                            {
+                                state.add_rejected_stake(stake)
+                            }
+                            _ => state.add_bad_stake(stake),
+                        }
                     }
                 };
 
@@ -239,6 +513,16 @@ async fn request_sign_bridge_action_into_certification(
     )
     .await
     .map_err(|state| {
+        if state.is_rejected() {
+            error!(
+                "Action rejected as invalid by the committee, rejected stake: {}, validity threshold: {}",
+                state.total_rejected_stake, state.validity_threshold,
+            );
+            return BridgeError::ActionRejectedByCommittee(format!(
+                "Action rejected as invalid by the committee, rejected stake: {}, validity threshold: {}, request id: {}",
+                state.total_rejected_stake, state.validity_threshold, request_id,
+            ));
+        }
         error!(
             "Failed to get enough signatures, bad stake: {}, blocklisted stake: {}, good stake: {}, validity threshold: {}",
             state.total_bad_stake,
@@ -247,11 +531,12 @@ async fn request_sign_bridge_action_into_certification(
             state.validity_threshold,
         );
         BridgeError::AuthoritySignatureAggregationTooManyError(format!(
-            "Failed to get enough signatures, bad stake: {}, blocklisted stake: {}, good stake: {}, validity threshold: {}",
+            "Failed to get enough signatures, bad stake: {}, blocklisted stake: {}, good stake: {}, validity threshold: {}, request id: {}",
             state.total_bad_stake,
             state.committee.total_blocklisted_stake(),
             state.total_ok_stake,
             state.validity_threshold,
+            request_id,
         ))
     })?;
     Ok(result)
@@ -262,11 +547,18 @@ mod tests {
     use std::collections::BTreeSet;
 
     use fastcrypto::traits::ToFromBytes;
-    use sui_types::committee::VALIDITY_THRESHOLD;
+    use sui_types::committee::{CommitteeTrait, VALIDITY_THRESHOLD};
     use sui_types::digests::TransactionDigest;
 
+    use crate::abi::eth_committee_upgradeable_contract;
+    use crate::client::authority_scores::AuthorityScore;
+    use crate::client::hedging::HedgingOptions;
     use crate::crypto::BridgeAuthorityPublicKey;
     use crate::server::mock_handler::BridgeRequestMockHandler;
+    use crate::storage::StorageExport;
+    use crate::types::EvmContractUpgradeAction;
+    use ethers::abi::ParamType;
+    use sui_types::bridge::BridgeChainId;
 
     use super::*;
     use crate::test_utils::{
@@ -286,7 +578,10 @@ mod tests {
         }
         let committee = BridgeCommittee::new(authorities.clone()).unwrap();
 
-        let agg = BridgeAuthorityAggregator::new(Arc::new(committee));
+        let agg = BridgeAuthorityAggregator::new(
+            Arc::new(committee),
+            Arc::new(BridgeMetrics::for_testing()),
+        );
         assert_eq!(
             agg.clients.keys().cloned().collect::<BTreeSet<_>>(),
             BTreeSet::from_iter(vec![
@@ -300,7 +595,10 @@ mod tests {
         // authority 2 is blocklisted
         authorities[2].is_blocklisted = true;
         let committee = BridgeCommittee::new(authorities.clone()).unwrap();
-        let agg = BridgeAuthorityAggregator::new(Arc::new(committee));
+        let agg = BridgeAuthorityAggregator::new(
+            Arc::new(committee),
+            Arc::new(BridgeMetrics::for_testing()),
+        );
         assert_eq!(
             agg.clients.keys().cloned().collect::<BTreeSet<_>>(),
             BTreeSet::from_iter(vec![
@@ -312,8 +610,9 @@ mod tests {
 
         // authority 3 has bad url
         authorities[3].base_url = "".into();
-        let committee = BridgeCommittee::new(authorities.clone()).unwrap();
-        let agg = BridgeAuthorityAggregator::new(Arc::new(committee));
+        let committee = Arc::new(BridgeCommittee::new(authorities.clone()).unwrap());
+        let metrics = Arc::new(BridgeMetrics::for_testing());
+        let agg = BridgeAuthorityAggregator::new(committee.clone(), metrics.clone());
         assert_eq!(
             agg.clients.keys().cloned().collect::<BTreeSet<_>>(),
             BTreeSet::from_iter(vec![
@@ -322,6 +621,13 @@ mod tests {
                 authorities[3].pubkey_bytes()
             ])
         );
+        assert_eq!(metrics.committee_members_with_invalid_url.get(), 1);
+
+        // The other urls are plain http
+        let agg =
+            BridgeAuthorityAggregator::new_with_https_required(committee, metrics.clone(), true);
+        assert_eq!(agg.clients.len(), 3);
+        assert_eq!(metrics.committee_members_with_invalid_url.get(), 3);
     }
 
     #[tokio::test]
@@ -339,9 +645,10 @@ mod tests {
             vec![mock0.clone(), mock1.clone(), mock2.clone(), mock3.clone()],
         );
 
-        let committee = BridgeCommittee::new(authorities).unwrap();
+        let committee = BridgeCommittee::new(authorities.clone()).unwrap();
 
-        let agg = BridgeAuthorityAggregator::new(Arc::new(committee));
+        let metrics = Arc::new(BridgeMetrics::for_testing());
+        let agg = BridgeAuthorityAggregator::new(Arc::new(committee), metrics.clone());
 
         let sui_tx_digest = TransactionDigest::random();
         let sui_tx_event_index = 0;
@@ -381,6 +688,8 @@ mod tests {
         agg.request_committee_signatures(action.clone())
             .await
             .unwrap();
+        // Every authority is scored, including the ones that had not responded yet
+        assert_eq!(metrics.committee_scores.snapshot().len(), 4);
 
         // 1 out of 4 authorities returns error
         mock3.add_sui_event_response(
@@ -416,6 +725,489 @@ mod tests {
             err,
             BridgeError::AuthoritySignatureAggregationTooManyError(_)
         ));
+        // Neither the certified nor the failed aggregations are in flight
+        assert!(metrics.aggregation_status.snapshot().is_empty());
+
+        // Failures are counted by authority and category. The mock server responds
+        // to errors with status 500.
+        for authority in &authorities[1..] {
+            assert!(
+                metrics
+                    .auth_agg_request_failures
+                    .with_label_values(&[&authority.pubkey_bytes().concise_owned(), "http_5xx"])
+                    .get()
+                    >= 1
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn test_bridge_auth_agg_hedged_requests() {
+        telemetry_subscribers::init_for_testing();
+
+        let mocks = (0..4)
+            .map(|_| BridgeRequestMockHandler::new())
+            .collect::<Vec<_>>();
+        let (_handles, authorities, secrets) =
+            get_test_authorities_and_run_mock_bridge_server(vec![2500; 4], mocks.clone());
+        let committee = Arc::new(BridgeCommittee::new(authorities.clone()).unwrap());
+        let metrics = Arc::new(BridgeMetrics::for_testing());
+        let hedging = Arc::new(RequestHedging::new(
+            HedgingOptions {
+                min_delay: Duration::from_millis(100),
+                max_delay: Duration::from_millis(300),
+                ..Default::default()
+            },
+            metrics.clone(),
+        ));
+        let agg = BridgeAuthorityAggregator::new(committee, metrics.clone())
+            .with_request_hedging(hedging);
+
+        let sui_tx_digest = TransactionDigest::random();
+        let action = get_test_sui_to_eth_bridge_action(
+            Some(sui_tx_digest),
+            Some(0),
+            Some(0),
+            None,
+            None,
+            None,
+            None,
+        );
+        // The signatures of authorities 0 and 1 are needed, and their first request hangs
+        for (i, mock) in mocks.iter().enumerate() {
+            let response = if i < 2 {
+                mock.hang_sui_event_requests(sui_tx_digest, 0, 1);
+                Ok(sign_action_with_key(&action, &secrets[i]))
+            } else {
+                Err(BridgeError::RestAPIError("".into()))
+            };
+            mock.add_sui_event_response(sui_tx_digest, 0, response);
+        }
+
+        // Without hedging the aggregation would time out after 5 seconds
+        let start = std::time::Instant::now();
+        agg.request_committee_signatures(action.clone())
+            .await
+            .unwrap();
+        assert!(start.elapsed() < Duration::from_secs(2));
+        for (i, authority) in authorities[..2].iter().enumerate() {
+            let authority_label = authority.pubkey_bytes().concise_owned();
+            assert_eq!(
+                metrics
+                    .committee_hedges_issued
+                    .with_label_values(&[&authority_label])
+                    .get(),
+                1
+            );
+            assert_eq!(
+                metrics
+                    .committee_hedges_won
+                    .with_label_values(&[&authority_label])
+                    .get(),
+                1
+            );
+            assert_eq!(mocks[i].get_sui_token_events_requested(sui_tx_digest, 0), 1);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_bridge_auth_agg_status_shows_pending_member() {
+        telemetry_subscribers::init_for_testing();
+
+        let mocks = (0..4)
+            .map(|_| BridgeRequestMockHandler::new())
+            .collect::<Vec<_>>();
+        let (_handles, authorities, secrets) = get_test_authorities_and_run_mock_bridge_server(
+            vec![7000, 1000, 1000, 1000],
+            mocks.clone(),
+        );
+        let committee = Arc::new(BridgeCommittee::new(authorities.clone()).unwrap());
+        let metrics = Arc::new(BridgeMetrics::for_testing());
+        let agg = Arc::new(BridgeAuthorityAggregator::new(committee, metrics.clone()));
+
+        let sui_tx_digest = TransactionDigest::random();
+        let action = get_test_sui_to_eth_bridge_action(
+            Some(sui_tx_digest),
+            Some(0),
+            Some(0),
+            None,
+            None,
+            None,
+            None,
+        );
+        // Authority 0 never responds, and the others don't have enough stake without it
+        mocks[0].hang_sui_event_requests(sui_tx_digest, 0, u64::MAX);
+        for (i, mock) in mocks.iter().enumerate() {
+            let response = if i < 3 {
+                Ok(sign_action_with_key(&action, &secrets[i]))
+            } else {
+                Err(BridgeError::RestAPIError("".into()))
+            };
+            mock.add_sui_event_response(sui_tx_digest, 0, response);
+        }
+
+        let aggregation = tokio::spawn({
+            let agg = agg.clone();
+            let action = action.clone();
+            async move { agg.request_committee_signatures(action).await }
+        });
+        let now = std::time::Instant::now();
+        let status = loop {
+            let snapshot = metrics.aggregation_status.snapshot();
+            if let Some(progress) = snapshot.first() {
+                let status =
+                    crate::api_types::AggregationStatus::new(progress, crate::timestamps::now_ms());
+                if status.pending.len() == 1 {
+                    break status;
+                }
+            }
+            if now.elapsed() > Duration::from_secs(4) {
+                panic!("Timeout waiting for the members to answer");
+            }
+            tokio::time::sleep(Duration::from_millis(20)).await;
+        };
+        let member = |i: usize| authorities[i].pubkey_bytes().concise_owned();
+        assert_eq!(
+            status.action_digest,
+            crate::api_types::encode_action_digest(&action.digest())
+        );
+        assert_eq!(status.stake_collected, 2000);
+        assert_eq!(status.stake_required, action.approval_threshold());
+        assert_eq!(status.pending, vec![member(0)]);
+        assert_eq!(
+            status.signed.iter().collect::<BTreeSet<_>>(),
+            BTreeSet::from([&member(1), &member(2)])
+        );
+        assert_eq!(
+            status.errored,
+            vec![crate::api_types::MemberError {
+                member: member(3),
+                category: "http_5xx".to_string(),
+            }]
+        );
+
+        // Abandoned, e.g. when the executor shuts down
+        aggregation.abort();
+        assert!(aggregation.await.unwrap_err().is_cancelled());
+        assert!(metrics.aggregation_status.snapshot().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_bridge_auth_agg_large_evm_contract_upgrade() {
+        telemetry_subscribers::init_for_testing();
+
+        let mocks = (0..4)
+            .map(|_| BridgeRequestMockHandler::new())
+            .collect::<Vec<_>>();
+        let (_handles, authorities, secrets) = get_test_authorities_and_run_mock_bridge_server(
+            vec![2500, 2500, 2500, 2500],
+            mocks.clone(),
+        );
+        for (mock, secret) in mocks.iter().zip(secrets) {
+            mock.set_signer(secret);
+        }
+        let committee = BridgeCommittee::new(authorities).unwrap();
+        let agg = BridgeAuthorityAggregator::new(
+            Arc::new(committee),
+            Arc::new(BridgeMetrics::for_testing()),
+        );
+
+        // Far too long for the path, the call data is sent in the body of the requests
+        let call_data = (0..64 * 1024).map(|i| (i % 251) as u8).collect::<Vec<_>>();
+        let upgrade = EvmContractUpgradeAction {
+            nonce: 7,
+            chain_id: BridgeChainId::EthCustom,
+            proxy_address: ethers::types::Address::repeat_byte(6),
+            new_impl_address: ethers::types::Address::repeat_byte(9),
+            call_data: call_data.clone(),
+        };
+        let action = BridgeAction::EvmContractUpgradeAction(upgrade.clone());
+        let certified = agg
+            .request_committee_signatures(action.clone())
+            .await
+            .unwrap();
+        // Every signature was verified over the whole call data
+        assert_eq!(certified.data(), &action);
+        assert_eq!(certified.auth_sig().signatures.len(), 4);
+
+        // The message submitted to Eth carries the whole call data
+        let message: eth_committee_upgradeable_contract::Message = upgrade.into();
+        let decoded = ethers::abi::decode(
+            &[ParamType::Address, ParamType::Address, ParamType::Bytes],
+            &message.payload,
+        )
+        .unwrap();
+        assert_eq!(decoded[2], ethers::abi::Token::Bytes(call_data));
+
+        // And so do the exported tables of the storage
+        let temp_dir = tempfile::tempdir().unwrap();
+        let store = BridgeOrchestratorTables::new(temp_dir.path());
+        store.insert_pending_actions(&[action.clone()]).unwrap();
+        let export: StorageExport =
+            bcs::from_bytes(&bcs::to_bytes(&store.export()).unwrap()).unwrap();
+        let other_dir = tempfile::tempdir().unwrap();
+        let other_store = BridgeOrchestratorTables::new(other_dir.path());
+        other_store.import(&export).unwrap();
+        assert_eq!(
+            other_store.get_pending_action(&action.digest()).unwrap(),
+            Some(action)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_bridge_auth_agg_rejected_action() {
+        telemetry_subscribers::init_for_testing();
+
+        let mocks = (0..4)
+            .map(|_| BridgeRequestMockHandler::new())
+            .collect::<Vec<_>>();
+        let (_handles, authorities, secrets) = get_test_authorities_and_run_mock_bridge_server(
+            vec![2500, 2500, 2500, 2500],
+            mocks.clone(),
+        );
+        let committee = BridgeCommittee::new(authorities).unwrap();
+        let agg = BridgeAuthorityAggregator::new(
+            Arc::new(committee),
+            Arc::new(BridgeMetrics::for_testing()),
+        );
+
+        let sui_tx_digest = TransactionDigest::random();
+        let sui_tx_event_index = 0;
+        let action = get_test_sui_to_eth_bridge_action(
+            Some(sui_tx_digest),
+            Some(sui_tx_event_index),
+            None,
+            None,
+            None,
+            None,
+            None,
+        );
+        mocks[0].add_sui_event_response(
+            sui_tx_digest,
+            sui_tx_event_index,
+            Ok(sign_action_with_key(&action, &secrets[0])),
+        );
+
+        // 2 out of 4 authorities reject the action, and 1 fails otherwise. The failure
+        // may be transient, so the action is not rejected.
+        for mock in &mocks[1..3] {
+            mock.add_sui_event_response(
+                sui_tx_digest,
+                sui_tx_event_index,
+                Err(BridgeError::NoBridgeEventsInTxPosition),
+            );
+        }
+        mocks[3].add_sui_event_response(
+            sui_tx_digest,
+            sui_tx_event_index,
+            Err(BridgeError::RestAPIError("".into())),
+        );
+        let err = agg
+            .request_committee_signatures(action.clone())
+            .await
+            .unwrap_err();
+        assert!(matches!(
+            err,
+            BridgeError::AuthoritySignatureAggregationTooManyError(_)
+        ));
+
+        // 3 out of 4 authorities reject the action
+        mocks[3].add_sui_event_response(
+            sui_tx_digest,
+            sui_tx_event_index,
+            Err(BridgeError::NoBridgeEventsInTxPosition),
+        );
+        let err = agg
+            .request_committee_signatures(action.clone())
+            .await
+            .unwrap_err();
+        assert!(matches!(err, BridgeError::ActionRejectedByCommittee(_)));
+    }
+
+    #[tokio::test]
+    async fn test_bridge_auth_agg_members_sharing_url() {
+        telemetry_subscribers::init_for_testing();
+
+        let mocks = (0..4)
+            .map(|_| BridgeRequestMockHandler::new())
+            .collect::<Vec<_>>();
+        let (_handles, mut authorities, secrets) = get_test_authorities_and_run_mock_bridge_server(
+            vec![2500, 2500, 2500, 2500],
+            mocks.clone(),
+        );
+        // The first two members registered the url of the first one
+        authorities[1].base_url = format!("{}/", authorities[0].base_url);
+        let committee = BridgeCommittee::new(authorities.clone()).unwrap();
+        let metrics = Arc::new(BridgeMetrics::for_testing());
+        let agg = BridgeAuthorityAggregator::new(Arc::new(committee), metrics.clone());
+        assert_eq!(metrics.committee_anomalies.get(), 2);
+        assert_eq!(agg.clients.len(), 4);
+
+        let sui_tx_digest = TransactionDigest::random();
+        let sui_tx_event_index = 0;
+        let action = get_test_sui_to_eth_bridge_action(
+            Some(sui_tx_digest),
+            Some(sui_tx_event_index),
+            None,
+            None,
+            None,
+            None,
+            None,
+        );
+        // The node behind the shared url signs with the key of the second member
+        mocks[0].add_sui_event_response(
+            sui_tx_digest,
+            sui_tx_event_index,
+            Ok(sign_action_with_key(&action, &secrets[1])),
+        );
+        mocks[2].add_sui_event_response(
+            sui_tx_digest,
+            sui_tx_event_index,
+            Ok(sign_action_with_key(&action, &secrets[2])),
+        );
+        mocks[3].add_sui_event_response(
+            sui_tx_digest,
+            sui_tx_event_index,
+            Err(BridgeError::RestAPIError("".into())),
+        );
+        let certified_action = agg
+            .request_committee_signatures(action.clone())
+            .await
+            .unwrap();
+        // Requested once, and credited to the member whose key signed
+        assert_eq!(
+            mocks[0].get_sui_token_events_requested(sui_tx_digest, sui_tx_event_index),
+            1
+        );
+        assert_eq!(
+            mocks[1].get_sui_token_events_requested(sui_tx_digest, sui_tx_event_index),
+            0
+        );
+        assert_eq!(
+            certified_action
+                .auth_sig()
+                .signatures
+                .keys()
+                .cloned()
+                .collect::<BTreeSet<_>>(),
+            BTreeSet::from_iter([authorities[1].pubkey_bytes(), authorities[2].pubkey_bytes()])
+        );
+
+        // The answer of the shared url is counted once: the signature of the first member
+        // and another one are short of the threshold
+        mocks[0].add_sui_event_response(
+            sui_tx_digest,
+            sui_tx_event_index,
+            Ok(sign_action_with_key(&action, &secrets[0])),
+        );
+        mocks[2].add_sui_event_response(
+            sui_tx_digest,
+            sui_tx_event_index,
+            Err(BridgeError::RestAPIError("".into())),
+        );
+        let err = agg
+            .request_committee_signatures(action.clone())
+            .await
+            .unwrap_err();
+        assert!(matches!(
+            err,
+            BridgeError::AuthoritySignatureAggregationTooManyError(_)
+        ));
+        assert_eq!(
+            mocks[0].get_sui_token_events_requested(sui_tx_digest, sui_tx_event_index),
+            2
+        );
+        assert_eq!(
+            metrics
+                .auth_agg_request_failures
+                .with_label_values(&[&authorities[1].pubkey_bytes().concise_owned(), "other"])
+                .get(),
+            1
+        );
+    }
+
+    #[tokio::test]
+    async fn test_bridge_auth_agg_scores_persisted_across_restarts() {
+        telemetry_subscribers::init_for_testing();
+        let temp_dir = tempfile::tempdir().unwrap();
+        let store = BridgeOrchestratorTables::new(temp_dir.path());
+
+        let mut authorities = vec![];
+        for _i in 0..4 {
+            let (authority, _, _) = get_test_authority_and_key(2500, 12345);
+            authorities.push(authority);
+        }
+        let names = authorities
+            .iter()
+            .map(|a| a.pubkey_bytes())
+            .collect::<Vec<_>>();
+        let committee = Arc::new(BridgeCommittee::new(authorities.clone()).unwrap());
+        // Stored by a node with an older committee
+        let (former_member, _, _) = get_test_authority_and_key(2500, 12345);
+        store
+            .replace_authority_scores(&BTreeMap::from([(
+                former_member.pubkey_bytes(),
+                AuthorityScore::default(),
+            )]))
+            .unwrap();
+
+        let agg = BridgeAuthorityAggregator::new(
+            committee.clone(),
+            Arc::new(BridgeMetrics::for_testing()),
+        );
+        agg.load_scores(&store);
+        assert!(agg.metrics.committee_scores.snapshot().is_empty());
+        // Authority 0 fails, authority 1 is slow
+        let scores = &agg.metrics.committee_scores;
+        for _ in 0..3 {
+            scores.record(&names[0], Duration::from_millis(100), true);
+            scores.record(&names[1], Duration::from_millis(4000), false);
+            scores.record(&names[2], Duration::from_millis(100), false);
+            scores.record(&names[3], Duration::from_millis(200), false);
+        }
+        agg.persist_scores(&store).unwrap();
+        let persisted = store.get_authority_scores();
+        assert_eq!(
+            persisted.keys().cloned().collect::<BTreeSet<_>>(),
+            names.iter().cloned().collect::<BTreeSet<_>>()
+        );
+
+        // Restart with fresh in-memory state
+        let agg = BridgeAuthorityAggregator::new(
+            committee.clone(),
+            Arc::new(BridgeMetrics::for_testing()),
+        );
+        assert_eq!(
+            agg.metrics
+                .committee_scores
+                .preferred_authorities(&committee),
+            None
+        );
+        agg.load_scores(&store);
+        assert_eq!(agg.metrics.committee_scores.snapshot(), persisted);
+        let preferred = agg
+            .metrics
+            .committee_scores
+            .preferred_authorities(&committee)
+            .unwrap();
+        assert_eq!(
+            preferred,
+            BTreeSet::from([names[2].clone(), names[3].clone()])
+        );
+        // The degraded authorities are requested last
+        let order = committee.shuffle_by_stake(Some(&preferred), None);
+        assert_eq!(
+            order[2..].iter().cloned().collect::<BTreeSet<_>>(),
+            BTreeSet::from([names[0].clone(), names[1].clone()])
+        );
+
+        // Scores of members removed from the committee are garbage collected
+        let committee = Arc::new(BridgeCommittee::new(authorities[1..].to_vec()).unwrap());
+        let agg = BridgeAuthorityAggregator::new(committee, agg.metrics.clone());
+        agg.persist_scores(&store).unwrap();
+        assert!(!store.get_authority_scores().contains_key(&names[0]));
+        assert!(agg.metrics.committee_scores.get(&names[0]).is_none());
+        assert_eq!(store.get_authority_scores().len(), 3);
     }
 
     #[tokio::test]
@@ -438,7 +1230,10 @@ mod tests {
 
         let committee = BridgeCommittee::new(authorities.clone()).unwrap();
 
-        let agg = BridgeAuthorityAggregator::new(Arc::new(committee));
+        let agg = BridgeAuthorityAggregator::new(
+            Arc::new(committee),
+            Arc::new(BridgeMetrics::for_testing()),
+        );
 
         let sui_tx_digest = TransactionDigest::random();
         let sui_tx_event_index = 0;
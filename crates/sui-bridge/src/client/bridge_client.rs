@@ -3,47 +3,471 @@
 
 //! `BridgeClient` talks to BridgeNode.
 
+use crate::client::dns_refresh::HostResolver;
 use crate::crypto::{verify_signed_bridge_action, BridgeAuthorityPublicKeyBytes};
-use crate::error::{BridgeError, BridgeResult};
-use crate::server::APPLICATION_JSON;
-use crate::types::{BridgeAction, BridgeCommittee, VerifiedSignedBridgeAction};
+use crate::error::{BridgeError, BridgeResult, ErrorKind};
+use crate::metrics::BridgeMetrics;
+use crate::server::request_id::{new_request_id, REQUEST_ID_HEADER};
+use crate::server::{APPLICATION_JSON, DRY_RUN_QUERY_PARAM, NONCE_QUERY_PARAM};
+use crate::types::{
+    BridgeAction, BridgeActionType, BridgeCommittee, GovernanceDryRun, SignedBridgeAction,
+    VerifiedSignedBridgeAction,
+};
+use arc_swap::ArcSwap;
 use fastcrypto::encoding::{Encoding, Hex};
 use fastcrypto::traits::ToFromBytes;
-use std::str::FromStr;
-use std::sync::Arc;
+use hyper_util::client::legacy::connect::HttpInfo;
+use reqwest::StatusCode;
+use std::collections::{BTreeSet, VecDeque};
+use std::net::{IpAddr, SocketAddr};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use sui_types::base_types::ConciseableName;
+use sui_types::bridge::BridgeChainId;
+use tracing::{info, warn};
 use url::Url;
 
+/// Authorities whose url has this scheme, e.g. `grpc://10.0.0.1:9192`, are reached
+/// through the gRPC signing API. Requires the `grpc` feature.
+pub const GRPC_URL_SCHEME: &str = "grpc";
+
+/// Call data of an EVM contract upgrade longer than this is sent in the body of a POST
+/// rather than hex encoded in the path, which proxies and servers limit in length.
+pub const MAX_CALL_DATA_IN_PATH_BYTES: usize = 1024;
+
+/// Returns whether committee members must be reached over https on the bridge of
+/// `sui_chain_id`. Test chains also allow plain http.
+pub fn requires_https(sui_chain_id: BridgeChainId) -> bool {
+    sui_chain_id == BridgeChainId::SuiMainnet
+}
+
+/// Parses the url that a committee member registered into the base that request paths
+/// are joined to. Whitespace around it is ignored, and a trailing slash is added so that
+/// a path prefix, e.g. `https://example.com/bridge`, is kept when joining. IPv6 hosts
+/// must be in brackets, e.g. `http://[::1]:9191`, unicode hosts are converted to punycode
+/// and paths are percent-encoded.
+pub fn parse_authority_base_url(base_url: &str, https_required: bool) -> Result<Url, String> {
+    let mut url = Url::parse(base_url.trim()).map_err(|e| e.to_string())?;
+    match url.scheme() {
+        "https" => (),
+        "http" if !https_required => (),
+        GRPC_URL_SCHEME if cfg!(feature = "grpc") => (),
+        scheme => return Err(format!("scheme {} is not allowed", scheme)),
+    }
+    if url.host().is_none() {
+        return Err("host is missing".to_string());
+    }
+    // Joining a path drops them
+    if url.query().is_some() || url.fragment().is_some() {
+        return Err("query and fragment are not allowed".to_string());
+    }
+    if !url.path().ends_with('/') {
+        let path = format!("{}/", url.path());
+        url.set_path(&path);
+    }
+    Ok(url)
+}
+
+pub const DEFAULT_TCP_KEEPALIVE: Duration = Duration::from_secs(30);
+pub const DEFAULT_HTTP2_KEEPALIVE_INTERVAL: Duration = Duration::from_secs(20);
+pub const DEFAULT_HTTP2_KEEPALIVE_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Options of the connections to the committee members. Idle connections are kept alive
+/// with TCP keepalive probes and HTTP/2 pings, so that middleboxes that drop idle flows,
+/// e.g. NATs, don't leave the next request to time out on a dead connection. HTTP/2 is
+/// negotiated with the members reached over https, and its flow control window adapts to
+/// the latency of the connection.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CommitteeConnectionOptions {
+    /// Idle time after which TCP keepalive probes are sent, None disables them
+    pub tcp_keepalive: Option<Duration>,
+    /// Interval of the pings of HTTP/2 connections, None disables them
+    pub http2_keep_alive_interval: Option<Duration>,
+    /// HTTP/2 connections whose ping is not answered within this are closed
+    pub http2_keep_alive_timeout: Duration,
+    /// Members reached over plain http are spoken HTTP/2 to without negotiating it, their
+    /// servers must support it. They are spoken HTTP/1.1 to otherwise.
+    pub http2_prior_knowledge: bool,
+    /// Members that are only spoken HTTP/1.1 to, e.g. whose servers misbehave on HTTP/2
+    pub http1_only_members: BTreeSet<BridgeAuthorityPublicKeyBytes>,
+}
+
+impl Default for CommitteeConnectionOptions {
+    fn default() -> Self {
+        Self {
+            tcp_keepalive: Some(DEFAULT_TCP_KEEPALIVE),
+            http2_keep_alive_interval: Some(DEFAULT_HTTP2_KEEPALIVE_INTERVAL),
+            http2_keep_alive_timeout: DEFAULT_HTTP2_KEEPALIVE_TIMEOUT,
+            http2_prior_knowledge: false,
+            http1_only_members: BTreeSet::new(),
+        }
+    }
+}
+
+fn client_builder(
+    options: &CommitteeConnectionOptions,
+    authority: &BridgeAuthorityPublicKeyBytes,
+    base_url: Option<&Url>,
+) -> reqwest::ClientBuilder {
+    let builder = reqwest::Client::builder()
+        .tcp_keepalive(options.tcp_keepalive)
+        .http2_adaptive_window(true)
+        .http2_keep_alive_interval(options.http2_keep_alive_interval)
+        .http2_keep_alive_timeout(options.http2_keep_alive_timeout)
+        .http2_keep_alive_while_idle(true);
+    if options.http1_only_members.contains(authority) {
+        builder.http1_only()
+    } else if options.http2_prior_knowledge && base_url.is_some_and(|url| url.scheme() == "http") {
+        builder.http2_prior_knowledge()
+    } else {
+        builder
+    }
+}
+
+// Local addresses of the latest connections that requests were sent over, to tell new
+// connections from reused ones
+const MAX_TRACKED_CONNECTIONS: usize = 64;
+
+// Signing requests that fail with a retryable error are attempted at most this many times.
+const MAX_SIGN_REQUEST_ATTEMPTS: u64 = 3;
+const SIGN_REQUEST_RETRY_DELAY: Duration = Duration::from_millis(50);
+
+/// Category of a failed request to a bridge authority. It labels the failure metrics and
+/// decides whether the request is worth retrying.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BridgeClientErrorCategory {
+    // The authority's host name could not be resolved
+    Dns,
+    // The TCP connection could not be established
+    Connect,
+    // The TLS handshake failed
+    Tls,
+    // The request timed out
+    Timeout,
+    // The authority responded with a 4xx status
+    Http4xx(u16),
+    // The authority responded with a 5xx (or other non-success) status
+    Http5xx(u16),
+    // The response body could not be read or decoded
+    Decode,
+}
+
+impl BridgeClientErrorCategory {
+    pub fn from_status(status: StatusCode) -> Self {
+        if status.is_client_error() {
+            Self::Http4xx(status.as_u16())
+        } else {
+            Self::Http5xx(status.as_u16())
+        }
+    }
+
+    pub fn from_reqwest_error(err: &reqwest::Error) -> Self {
+        if err.is_timeout() {
+            return Self::Timeout;
+        }
+        if let Some(status) = err.status() {
+            return Self::from_status(status);
+        }
+        if err.is_decode() || err.is_body() {
+            return Self::Decode;
+        }
+        // DNS and TLS failures are reported as connect errors and can only be told
+        // apart by the errors underneath.
+        let mut source = std::error::Error::source(err);
+        while let Some(e) = source {
+            if let Some(category) = Self::from_connect_error_message(&e.to_string()) {
+                return category;
+            }
+            source = e.source();
+        }
+        Self::Connect
+    }
+
+    fn from_connect_error_message(message: &str) -> Option<Self> {
+        let message = message.to_lowercase();
+        if message.contains("dns error") || message.contains("failed to lookup address") {
+            Some(Self::Dns)
+        } else if ["tls", "ssl", "certificate", "handshake", "corrupt message"]
+            .iter()
+            .any(|keyword| message.contains(keyword))
+        {
+            Some(Self::Tls)
+        } else {
+            None
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Dns => "dns",
+            Self::Connect => "connect",
+            Self::Tls => "tls",
+            Self::Timeout => "timeout",
+            Self::Http4xx(_) => "http_4xx",
+            Self::Http5xx(_) => "http_5xx",
+            Self::Decode => "decode",
+        }
+    }
+
+    /// How the same request behaves if sent again. A TLS failure, an undecodable
+    /// response or a 4xx status (other than 408 and 429) would just happen again.
+    pub fn kind(&self) -> ErrorKind {
+        match self {
+            Self::Dns | Self::Connect | Self::Timeout | Self::Http5xx(_) => ErrorKind::Transient,
+            Self::Http4xx(status) => match StatusCode::from_u16(*status) {
+                Ok(StatusCode::TOO_MANY_REQUESTS) => ErrorKind::RateLimited,
+                Ok(StatusCode::REQUEST_TIMEOUT) => ErrorKind::Transient,
+                _ => ErrorKind::Permanent,
+            },
+            Self::Tls | Self::Decode => ErrorKind::Permanent,
+        }
+    }
+
+    /// Whether the authority rejected the request as invalid, e.g. because the
+    /// referenced transaction has no bridge event at the given position.
+    pub fn is_rejection(&self) -> bool {
+        matches!(
+            self,
+            Self::Http4xx(status)
+                if *status == StatusCode::BAD_REQUEST.as_u16()
+                    || *status == StatusCode::UNPROCESSABLE_ENTITY.as_u16()
+        )
+    }
+}
+
+fn bridge_client_error(err: reqwest::Error) -> BridgeError {
+    BridgeError::BridgeClientError(
+        BridgeClientErrorCategory::from_reqwest_error(&err),
+        err.to_string(),
+    )
+}
+
+#[cfg(feature = "grpc")]
+fn grpc_client_error(status: tonic::Status) -> BridgeError {
+    // Same categories as the status codes the JSON API responds with
+    let category = match status.code() {
+        tonic::Code::Unavailable => BridgeClientErrorCategory::Connect,
+        tonic::Code::DeadlineExceeded => BridgeClientErrorCategory::Timeout,
+        tonic::Code::PermissionDenied => {
+            BridgeClientErrorCategory::Http4xx(StatusCode::FORBIDDEN.as_u16())
+        }
+        tonic::Code::InvalidArgument => {
+            BridgeClientErrorCategory::Http4xx(StatusCode::BAD_REQUEST.as_u16())
+        }
+        tonic::Code::ResourceExhausted => {
+            BridgeClientErrorCategory::Http4xx(StatusCode::TOO_MANY_REQUESTS.as_u16())
+        }
+        _ => BridgeClientErrorCategory::Http5xx(StatusCode::INTERNAL_SERVER_ERROR.as_u16()),
+    };
+    BridgeError::BridgeClientError(category, status.to_string())
+}
+
 // Note: `base_url` is `Option<Url>` because `quorum_map_then_reduce_with_timeout_and_prefs`
 // uses `[]` to get Client based on key. Therefore even when the URL is invalid we need to
 // create a Client instance.
 // TODO: In the future we can consider change `quorum_map_then_reduce_with_timeout_and_prefs`
 // and its callsites to use `get` instead of `[]`.
+// `inner` is shared by the clones, so that rebuilding it after the host of the authority
+// resolves to other addresses drops the pooled connections of every clone.
 #[derive(Clone, Debug)]
 pub struct BridgeClient {
-    inner: reqwest::Client,
+    inner: Arc<ArcSwap<reqwest::Client>>,
     authority: BridgeAuthorityPublicKeyBytes,
     committee: Arc<BridgeCommittee>,
     base_url: Option<Url>,
+    // Addresses that `inner` is pinned to, None until the host was resolved once
+    addresses: Arc<Mutex<Option<BTreeSet<SocketAddr>>>>,
+    // Other members registered with the same url, whose signatures are accepted too
+    members_sharing_url: BTreeSet<BridgeAuthorityPublicKeyBytes>,
+    connection_options: Arc<CommitteeConnectionOptions>,
+    connections: Arc<Mutex<VecDeque<SocketAddr>>>,
+    metrics: Option<Arc<BridgeMetrics>>,
 }
 
 impl BridgeClient {
     pub fn new(
         authority_name: BridgeAuthorityPublicKeyBytes,
         committee: Arc<BridgeCommittee>,
+    ) -> BridgeResult<Self> {
+        Self::new_with_https_required(authority_name, committee, false)
+    }
+
+    /// Like `new`, but the url of the authority is invalid unless it is https when
+    /// `https_required` is true.
+    pub fn new_with_https_required(
+        authority_name: BridgeAuthorityPublicKeyBytes,
+        committee: Arc<BridgeCommittee>,
+        https_required: bool,
     ) -> BridgeResult<Self> {
         if !committee.is_active_member(&authority_name) {
             return Err(BridgeError::InvalidBridgeAuthority(authority_name));
         }
         // Unwrap safe: we passed the `is_active_member` check above
         let member = committee.member(&authority_name).unwrap();
+        let base_url = parse_authority_base_url(&member.base_url, https_required)
+            .map_err(|e| {
+                warn!(
+                    "Invalid url {:?} of authority {:?}: {}",
+                    member.base_url, authority_name, e
+                );
+            })
+            .ok();
+        let connection_options = Arc::new(CommitteeConnectionOptions::default());
+        let client = client_builder(&connection_options, &authority_name, base_url.as_ref())
+            .build()
+            .map_err(|e| BridgeError::Generic(format!("Failed to build client: {:?}", e)))?;
         Ok(Self {
-            inner: reqwest::Client::new(),
+            inner: Arc::new(ArcSwap::from_pointee(client)),
             authority: authority_name.clone(),
-            base_url: Url::from_str(&member.base_url).ok(),
+            base_url,
             committee,
+            addresses: Arc::new(Mutex::new(None)),
+            members_sharing_url: BTreeSet::new(),
+            connection_options,
+            connections: Arc::new(Mutex::new(VecDeque::new())),
+            metrics: None,
         })
     }
 
+    /// Connects to the authority with `options` rather than the defaults. The returned
+    /// client doesn't share its connections with the clones of `self`.
+    pub fn with_connection_options(
+        mut self,
+        options: Arc<CommitteeConnectionOptions>,
+    ) -> BridgeResult<Self> {
+        let client = client_builder(&options, &self.authority, self.base_url.as_ref())
+            .build()
+            .map_err(|e| BridgeError::Generic(format!("Failed to build client: {:?}", e)))?;
+        self.inner = Arc::new(ArcSwap::from_pointee(client));
+        self.addresses = Arc::new(Mutex::new(None));
+        self.connections = Arc::new(Mutex::new(VecDeque::new()));
+        self.connection_options = options;
+        Ok(self)
+    }
+
+    /// Counts the requests sent over new and reused connections in
+    /// `bridge_committee_connections`.
+    pub fn with_metrics(mut self, metrics: Arc<BridgeMetrics>) -> Self {
+        self.metrics = Some(metrics);
+        self
+    }
+
+    // Counts the request of `resp` as sent over a new or a reused connection, told apart
+    // by the local address of the connection. It's not known for every connection, e.g.
+    // not through a proxy, and a port reused by a later connection is taken for the same
+    // connection.
+    fn record_connection(&self, resp: &reqwest::Response) {
+        let Some(metrics) = &self.metrics else {
+            return;
+        };
+        let Some(info) = resp.extensions().get::<HttpInfo>() else {
+            return;
+        };
+        let local_addr = info.local_addr();
+        let mut connections = self.connections.lock().unwrap();
+        let kind = if connections.contains(&local_addr) {
+            "reused"
+        } else {
+            if connections.len() == MAX_TRACKED_CONNECTIONS {
+                connections.pop_front();
+            }
+            connections.push_back(local_addr);
+            "new"
+        };
+        metrics
+            .committee_connections
+            .with_label_values(&[&self.authority.concise_owned(), kind])
+            .inc();
+    }
+
+    /// Accepts the signatures of `members` too, i.e. of the other members registered with
+    /// the url of the authority. The node behind the url signs with one of the keys only,
+    /// the callers must check whose signature they got.
+    pub fn with_members_sharing_url(
+        mut self,
+        members: impl IntoIterator<Item = BridgeAuthorityPublicKeyBytes>,
+    ) -> Self {
+        self.members_sharing_url = members
+            .into_iter()
+            .filter(|member| member != &self.authority)
+            .collect();
+        self
+    }
+
+    fn verify_signed_action(
+        &self,
+        action: &BridgeAction,
+        signed_action: SignedBridgeAction,
+    ) -> BridgeResult<VerifiedSignedBridgeAction> {
+        let signer = signed_action.auth_sig().authority_pub_key_bytes();
+        let expected_signer = if self.members_sharing_url.contains(&signer) {
+            &signer
+        } else {
+            &self.authority
+        };
+        verify_signed_bridge_action(action, signed_action, expected_signer, &self.committee)
+    }
+
+    /// Returns false if the url the authority registered is invalid, and it cannot be
+    /// reached.
+    pub fn has_valid_url(&self) -> bool {
+        self.base_url.is_some()
+    }
+
+    /// Resolves the host of the authority with `resolver`, and rebuilds the client with
+    /// the resolved addresses when they differ from the ones its connections were opened
+    /// to, dropping the pooled connections. Returns whether the client was rebuilt. The
+    /// first resolution only pins the client to the addresses. Urls with an IP address
+    /// and gRPC urls are never resolved.
+    pub async fn refresh_addresses(&self, resolver: &dyn HostResolver) -> BridgeResult<bool> {
+        let Some(url) = &self.base_url else {
+            return Ok(false);
+        };
+        if url.scheme() == GRPC_URL_SCHEME {
+            return Ok(false);
+        }
+        let (Some(host), Some(port)) = (url.host_str(), url.port_or_known_default()) else {
+            return Ok(false);
+        };
+        if host.trim_matches(['[', ']']).parse::<IpAddr>().is_ok() {
+            return Ok(false);
+        }
+        let resolved = resolver.resolve(host, port).await.map_err(|e| {
+            BridgeError::Generic(format!(
+                "Failed to resolve {} of authority {:?}: {:?}",
+                host, self.authority, e
+            ))
+        })?;
+        if resolved.is_empty() {
+            return Err(BridgeError::Generic(format!(
+                "{} of authority {:?} resolves to no address",
+                host, self.authority
+            )));
+        }
+        let mut addresses = self.addresses.lock().unwrap();
+        if addresses.as_ref() == Some(&resolved) {
+            return Ok(false);
+        }
+        let client = client_builder(
+            &self.connection_options,
+            &self.authority,
+            self.base_url.as_ref(),
+        )
+        .resolve_to_addrs(host, &resolved.iter().copied().collect::<Vec<_>>())
+        .build()
+        .map_err(|e| BridgeError::Generic(format!("Failed to build client: {:?}", e)))?;
+        self.inner.store(Arc::new(client));
+        let changed = addresses.is_some();
+        if changed {
+            info!(
+                "Authority {:?} at {} moved from {:?} to {:?}, dropped its connections",
+                self.authority, host, addresses, resolved
+            );
+        }
+        *addresses = Some(resolved);
+        Ok(changed)
+    }
+
     #[cfg(test)]
     pub fn update_committee(&mut self, committee: Arc<BridgeCommittee>) {
         self.committee = committee;
@@ -101,7 +525,7 @@ impl BridgeClient {
                 let path = format!(
                     "sign/upgrade_evm_contract/{chain_id}/{nonce}/{proxy_address}/{new_impl_address}"
                 );
-                if a.call_data.is_empty() {
+                if a.call_data.is_empty() || a.call_data.len() > MAX_CALL_DATA_IN_PATH_BYTES {
                     path
                 } else {
                     let call_data = Hex::encode(a.call_data.clone());
@@ -169,6 +593,26 @@ impl BridgeClient {
         }
     }
 
+    // The body of the request for `action`, if it doesn't fit in the path
+    fn bridge_action_to_body(action: &BridgeAction) -> Option<Vec<u8>> {
+        match action {
+            BridgeAction::EvmContractUpgradeAction(a)
+                if a.call_data.len() > MAX_CALL_DATA_IN_PATH_BYTES =>
+            {
+                Some(a.call_data.clone())
+            }
+            _ => None,
+        }
+    }
+
+    fn request_builder(&self, url: Url, action: &BridgeAction) -> reqwest::RequestBuilder {
+        let client = self.inner.load();
+        match Self::bridge_action_to_body(action) {
+            Some(body) => client.post(url).body(body),
+            None => client.get(url),
+        }
+    }
+
     // Returns Ok(true) if the server is up and running
     pub async fn ping(&self) -> BridgeResult<bool> {
         if self.base_url.is_none() {
@@ -176,50 +620,220 @@ impl BridgeClient {
         }
         // Unwrap safe: checked `self.base_url.is_none()` above
         let url = self.base_url.clone().unwrap();
-        Ok(self
+        #[cfg(feature = "grpc")]
+        if url.scheme() == GRPC_URL_SCHEME {
+            return Ok(self
+                .grpc_client(&url)
+                .await?
+                .health(crate::server::grpc::HealthRequest {})
+                .await
+                .is_ok());
+        }
+        let resp = self
             .inner
+            .load()
             .get(url)
             .header(reqwest::header::ACCEPT, APPLICATION_JSON)
             .send()
-            .await?
-            .error_for_status()
-            .is_ok())
+            .await
+            .map_err(bridge_client_error)?;
+        self.record_connection(&resp);
+        Ok(resp.error_for_status().is_ok())
     }
 
     pub async fn request_sign_bridge_action(
         &self,
         action: BridgeAction,
+    ) -> BridgeResult<VerifiedSignedBridgeAction> {
+        self.request_sign_bridge_action_with_request_id(action, &new_request_id())
+            .await
+    }
+
+    /// Requests the signature of `action`, sending `request_id` in the `x-request-id`
+    /// header so that the request can be found in the logs of the authority.
+    pub async fn request_sign_bridge_action_with_request_id(
+        &self,
+        action: BridgeAction,
+        request_id: &str,
+    ) -> BridgeResult<VerifiedSignedBridgeAction> {
+        let mut attempt = 1;
+        loop {
+            match self
+                .request_sign_bridge_action_once(&action, request_id)
+                .await
+            {
+                Err(err) if err.is_retryable() && attempt < MAX_SIGN_REQUEST_ATTEMPTS => {
+                    warn!(
+                        request_id,
+                        "Request to sign action failed ({}), attempt {}: {}",
+                        err.kind().as_str(),
+                        attempt,
+                        err
+                    );
+                    tokio::time::sleep(SIGN_REQUEST_RETRY_DELAY * attempt as u32).await;
+                    attempt += 1;
+                }
+                result => return result,
+            }
+        }
+    }
+
+    async fn request_sign_bridge_action_once(
+        &self,
+        action: &BridgeAction,
+        request_id: &str,
     ) -> BridgeResult<VerifiedSignedBridgeAction> {
         if self.base_url.is_none() {
             return Err(BridgeError::InvalidAuthorityUrl(self.authority.clone()));
         }
         // Unwrap safe: checked `self.base_url.is_none()` above
-        let url = self
-            .base_url
-            .clone()
-            .unwrap()
-            .join(&Self::bridge_action_to_path(&action))?;
+        let url = self.base_url.clone().unwrap();
+        #[cfg(feature = "grpc")]
+        if url.scheme() == GRPC_URL_SCHEME {
+            return self.request_sign_bridge_action_grpc(action, &url).await;
+        }
+        let mut url = url
+            .join(&Self::bridge_action_to_path(action))
+            .map_err(|_e| BridgeError::InvalidAuthorityUrl(self.authority.clone()))?;
+        // Lets the authority refuse a transfer far ahead of its chain without reading it
+        if action.action_type() == BridgeActionType::TokenTransfer {
+            url.query_pairs_mut()
+                .append_pair(NONCE_QUERY_PARAM, &action.seq_number().to_string());
+        }
         let resp = self
-            .inner
-            .get(url)
+            .request_builder(url, action)
             .header(reqwest::header::ACCEPT, APPLICATION_JSON)
+            .header(REQUEST_ID_HEADER, request_id)
             .send()
-            .await?;
-        if !resp.status().is_success() {
-            let error_status = format!("{:?}", resp.error_for_status_ref());
-            return Err(BridgeError::RestAPIError(format!(
-                "request_sign_bridge_action failed with status {:?}: {:?}",
-                error_status,
-                resp.text().await?
-            )));
+            .await
+            .map_err(bridge_client_error)?;
+        self.record_connection(&resp);
+        let status = resp.status();
+        if !status.is_success() {
+            return Err(BridgeError::BridgeClientError(
+                BridgeClientErrorCategory::from_status(status),
+                format!(
+                    "request_sign_bridge_action failed with status {:?} (request id {}): {:?}",
+                    status,
+                    request_id,
+                    resp.text().await.map_err(bridge_client_error)?
+                ),
+            ));
         }
-        let signed_bridge_action = resp.json().await?;
-        verify_signed_bridge_action(
-            &action,
-            signed_bridge_action,
-            &self.authority,
-            &self.committee,
-        )
+        let signed_bridge_action = resp.json().await.map_err(bridge_client_error)?;
+        self.verify_signed_action(action, signed_bridge_action)
+    }
+
+    /// Asks the authority whether it would sign the governance `action`, without signing
+    /// it. Returns None when the authority doesn't support dry runs. Such an authority
+    /// answers with its signature instead, which is discarded.
+    pub async fn request_dry_run_governance_action(
+        &self,
+        action: &BridgeAction,
+    ) -> BridgeResult<Option<GovernanceDryRun>> {
+        if self.base_url.is_none() {
+            return Err(BridgeError::InvalidAuthorityUrl(self.authority.clone()));
+        }
+        // Unwrap safe: checked `self.base_url.is_none()` above
+        let mut url = self.base_url.clone().unwrap();
+        // The gRPC signing API has no dry runs
+        if url.scheme() == GRPC_URL_SCHEME {
+            return Ok(None);
+        }
+        url = url
+            .join(&Self::bridge_action_to_path(action))
+            .map_err(|_e| BridgeError::InvalidAuthorityUrl(self.authority.clone()))?;
+        url.query_pairs_mut()
+            .append_pair(DRY_RUN_QUERY_PARAM, "true");
+        let request_id = new_request_id();
+        let resp = self
+            .request_builder(url, action)
+            .header(reqwest::header::ACCEPT, APPLICATION_JSON)
+            .header(REQUEST_ID_HEADER, &request_id)
+            .send()
+            .await
+            .map_err(bridge_client_error)?;
+        self.record_connection(&resp);
+        let status = resp.status();
+        let body = resp.text().await.map_err(bridge_client_error)?;
+        if !status.is_success() {
+            return Err(BridgeError::BridgeClientError(
+                BridgeClientErrorCategory::from_status(status),
+                format!(
+                    "request_dry_run_governance_action failed with status {:?} (request id {}): {:?}",
+                    status, request_id, body
+                ),
+            ));
+        }
+        Ok(serde_json::from_str(&body).ok())
+    }
+
+    #[cfg(feature = "grpc")]
+    async fn grpc_client(
+        &self,
+        url: &Url,
+    ) -> BridgeResult<crate::server::grpc::BridgeSigningServiceClient<tonic::transport::Channel>>
+    {
+        let (Some(host), Some(port)) = (url.host_str(), url.port()) else {
+            return Err(BridgeError::InvalidAuthorityUrl(self.authority.clone()));
+        };
+        crate::server::grpc::BridgeSigningServiceClient::connect(format!(
+            "http://{}:{}",
+            host, port
+        ))
+        .await
+        .map_err(|e| {
+            BridgeError::BridgeClientError(BridgeClientErrorCategory::Connect, e.to_string())
+        })
+    }
+
+    #[cfg(feature = "grpc")]
+    async fn request_sign_bridge_action_grpc(
+        &self,
+        action: &BridgeAction,
+        url: &Url,
+    ) -> BridgeResult<VerifiedSignedBridgeAction> {
+        use crate::server::grpc::{
+            SignGovernanceActionRequest, SignTokenTransferRequest, TransferSource,
+        };
+        let mut client = self.grpc_client(url).await?;
+        let response = match action {
+            BridgeAction::SuiToEthBridgeAction(e) => {
+                client
+                    .sign_token_transfer(SignTokenTransferRequest {
+                        source: TransferSource::Sui as i32,
+                        tx_id: e.sui_tx_digest.to_string(),
+                        event_index: e.sui_tx_event_index as u32,
+                        nonce: Some(e.sui_bridge_event.nonce),
+                    })
+                    .await
+            }
+            BridgeAction::EthToSuiBridgeAction(e) => {
+                client
+                    .sign_token_transfer(SignTokenTransferRequest {
+                        source: TransferSource::Eth as i32,
+                        tx_id: Hex::encode(e.eth_tx_hash.0),
+                        event_index: e.eth_event_index as u32,
+                        nonce: Some(e.eth_bridge_event.nonce),
+                    })
+                    .await
+            }
+            _ => {
+                client
+                    .sign_governance_action(SignGovernanceActionRequest {
+                        action: bcs::to_bytes(action).map_err(|e| {
+                            BridgeError::Generic(format!("Couldn't encode action: {:?}", e))
+                        })?,
+                    })
+                    .await
+            }
+        }
+        .map_err(grpc_client_error)?;
+        let signed_bridge_action =
+            bcs::from_bytes(&response.into_inner().signed_action).map_err(|e| {
+                BridgeError::BridgeClientError(BridgeClientErrorCategory::Decode, e.to_string())
+            })?;
+        self.verify_signed_action(action, signed_bridge_action)
     }
 }
 
@@ -240,6 +854,7 @@ mod tests {
     use fastcrypto::hash::{HashFunction, Keccak256};
     use fastcrypto::traits::KeyPair;
     use prometheus::Registry;
+    use std::str::FromStr;
     use sui_types::bridge::{BridgeChainId, TOKEN_ID_BTC, TOKEN_ID_USDT};
     use sui_types::TypeTag;
     use sui_types::{base_types::SuiAddress, crypto::get_key_pair, digests::TransactionDigest};
@@ -306,6 +921,201 @@ mod tests {
         ));
     }
 
+    #[test]
+    fn test_parse_authority_base_url() {
+        let path = "sign/bridge_tx/sui/eth/abc/0";
+        // (registered url, https required, url of the signing request)
+        let cases = [
+            (
+                "https://foo.suibridge.io",
+                true,
+                Some("https://foo.suibridge.io/sign/bridge_tx/sui/eth/abc/0"),
+            ),
+            (
+                "https://foo.suibridge.io/",
+                true,
+                Some("https://foo.suibridge.io/sign/bridge_tx/sui/eth/abc/0"),
+            ),
+            (
+                " https://foo.suibridge.io:9191\n",
+                true,
+                Some("https://foo.suibridge.io:9191/sign/bridge_tx/sui/eth/abc/0"),
+            ),
+            (
+                "https://foo.suibridge.io/bridge",
+                true,
+                Some("https://foo.suibridge.io/bridge/sign/bridge_tx/sui/eth/abc/0"),
+            ),
+            (
+                "https://foo.suibridge.io/bridge/v1/",
+                true,
+                Some("https://foo.suibridge.io/bridge/v1/sign/bridge_tx/sui/eth/abc/0"),
+            ),
+            (
+                "https://foo.suibridge.io/my%20bridge",
+                true,
+                Some("https://foo.suibridge.io/my%20bridge/sign/bridge_tx/sui/eth/abc/0"),
+            ),
+            (
+                "https://foo.suibridge.io/my bridge",
+                true,
+                Some("https://foo.suibridge.io/my%20bridge/sign/bridge_tx/sui/eth/abc/0"),
+            ),
+            (
+                "http://[::1]:9191",
+                false,
+                Some("http://[::1]:9191/sign/bridge_tx/sui/eth/abc/0"),
+            ),
+            (
+                "https://[2001:db8::1]:9191/bridge/",
+                true,
+                Some("https://[2001:db8::1]:9191/bridge/sign/bridge_tx/sui/eth/abc/0"),
+            ),
+            (
+                "https://münchen.de:9191",
+                true,
+                Some("https://xn--mnchen-3ya.de:9191/sign/bridge_tx/sui/eth/abc/0"),
+            ),
+            (
+                "http://127.0.0.1:9191",
+                false,
+                Some("http://127.0.0.1:9191/sign/bridge_tx/sui/eth/abc/0"),
+            ),
+            // Plain http is not allowed on production chains
+            ("http://127.0.0.1:9191", true, None),
+            ("http://[::1]:9191", true, None),
+            // IPv6 hosts without brackets
+            ("http://::1:9191", false, None),
+            // Missing scheme
+            ("127.0.0.1:12345", false, None),
+            ("foo.suibridge.io", false, None),
+            ("ftp://foo.suibridge.io", false, None),
+            ("http://127.256.0.1:12345", false, None),
+            ("https://foo.suibridge.io/?key=1", false, None),
+            ("https://foo.suibridge.io/#bridge", false, None),
+            ("", false, None),
+        ];
+        for (base_url, https_required, expected) in cases {
+            let url = parse_authority_base_url(base_url, https_required)
+                .map(|url| url.join(path).unwrap().to_string());
+            assert_eq!(
+                url.as_deref().ok(),
+                expected,
+                "{:?} (https required: {}): {:?}",
+                base_url,
+                https_required,
+                url
+            );
+        }
+    }
+
+    // Serves `/` slowly, so that concurrent requests overlap, and records the peer address
+    // and HTTP version of every request
+    async fn run_recording_server() -> (
+        SocketAddr,
+        Arc<Mutex<Vec<(SocketAddr, axum::http::Version)>>>,
+    ) {
+        let requests = Arc::new(Mutex::new(vec![]));
+        let recorded = requests.clone();
+        let app = axum::Router::new().route(
+            "/",
+            axum::routing::get(
+                move |axum::extract::ConnectInfo(peer): axum::extract::ConnectInfo<SocketAddr>,
+                      version: axum::http::Version| {
+                    let recorded = recorded.clone();
+                    async move {
+                        recorded.lock().unwrap().push((peer, version));
+                        tokio::time::sleep(Duration::from_millis(200)).await;
+                        "ok"
+                    }
+                },
+            ),
+        );
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            axum::serve(
+                listener,
+                app.into_make_service_with_connect_info::<SocketAddr>(),
+            )
+            .await
+            .unwrap()
+        });
+        (addr, requests)
+    }
+
+    #[tokio::test]
+    async fn test_bridge_client_connection_options() {
+        telemetry_subscribers::init_for_testing();
+        let (addr, requests) = run_recording_server().await;
+        let (authority, pubkey, _) = get_test_authority_and_key(5000, addr.port());
+        let (authority2, pubkey2, _) = get_test_authority_and_key(5000, addr.port());
+        let (pubkey, pubkey2) = (
+            BridgeAuthorityPublicKeyBytes::from(&pubkey),
+            BridgeAuthorityPublicKeyBytes::from(&pubkey2),
+        );
+        let committee = Arc::new(BridgeCommittee::new(vec![authority, authority2]).unwrap());
+        let options = Arc::new(CommitteeConnectionOptions {
+            http2_prior_knowledge: true,
+            http1_only_members: BTreeSet::from([pubkey2.clone()]),
+            ..Default::default()
+        });
+        let metrics = Arc::new(BridgeMetrics::for_testing());
+        let requests = &requests;
+        let ping_concurrently = |client: BridgeClient| async move {
+            let results = futures::future::join_all((0..10).map(|_| client.ping())).await;
+            assert!(results.into_iter().all(|result| result.unwrap()));
+            let requests = std::mem::take(&mut *requests.lock().unwrap());
+            let peers = requests
+                .iter()
+                .map(|(peer, _)| *peer)
+                .collect::<BTreeSet<_>>();
+            let versions = requests
+                .iter()
+                .map(|(_, version)| *version)
+                .collect::<BTreeSet<_>>();
+            (peers, versions)
+        };
+        let connections = |pubkey: &BridgeAuthorityPublicKeyBytes, kind: &str| {
+            metrics
+                .committee_connections
+                .with_label_values(&[&pubkey.concise_owned(), kind])
+                .get()
+        };
+
+        // Concurrent requests are multiplexed over one HTTP/2 connection
+        let client = BridgeClient::new(pubkey.clone(), committee.clone())
+            .unwrap()
+            .with_connection_options(options.clone())
+            .unwrap()
+            .with_metrics(metrics.clone());
+        let (peers, versions) = ping_concurrently(client).await;
+        assert_eq!(peers.len(), 1);
+        assert_eq!(versions, BTreeSet::from([axum::http::Version::HTTP_2]));
+        assert_eq!(connections(&pubkey, "new"), 1);
+        assert_eq!(connections(&pubkey, "reused"), 9);
+
+        // The member forced to HTTP/1.1 gets a connection per concurrent request
+        let client = BridgeClient::new(pubkey2.clone(), committee.clone())
+            .unwrap()
+            .with_connection_options(options.clone())
+            .unwrap()
+            .with_metrics(metrics.clone());
+        let (peers, versions) = ping_concurrently(client).await;
+        assert!(peers.len() > 1);
+        assert_eq!(versions, BTreeSet::from([axum::http::Version::HTTP_11]));
+        assert_eq!(connections(&pubkey2, "new"), peers.len() as u64);
+        assert_eq!(
+            connections(&pubkey2, "new") + connections(&pubkey2, "reused"),
+            10
+        );
+
+        // Plain http is spoken HTTP/1.1 to by default
+        let client = BridgeClient::new(pubkey.clone(), committee).unwrap();
+        let (_, versions) = ping_concurrently(client).await;
+        assert_eq!(versions, BTreeSet::from([axum::http::Version::HTTP_11]));
+    }
+
     #[tokio::test]
     async fn test_bridge_client_request_sign_action() {
         telemetry_subscribers::init_for_testing();
@@ -424,6 +1234,185 @@ mod tests {
         assert!(matches!(err, BridgeError::MismatchedAuthoritySigner));
     }
 
+    #[test]
+    fn test_error_category_from_status() {
+        for (status, category, retryable) in [
+            (400, BridgeClientErrorCategory::Http4xx(400), false),
+            (404, BridgeClientErrorCategory::Http4xx(404), false),
+            (422, BridgeClientErrorCategory::Http4xx(422), false),
+            (408, BridgeClientErrorCategory::Http4xx(408), true),
+            (429, BridgeClientErrorCategory::Http4xx(429), true),
+            (500, BridgeClientErrorCategory::Http5xx(500), true),
+            (503, BridgeClientErrorCategory::Http5xx(503), true),
+        ] {
+            let actual =
+                BridgeClientErrorCategory::from_status(StatusCode::from_u16(status).unwrap());
+            assert_eq!(actual, category);
+            assert_eq!(actual.kind().is_retryable(), retryable, "status {}", status);
+            assert_eq!(
+                actual.is_rejection(),
+                status == 400 || status == 422,
+                "status {}",
+                status
+            );
+        }
+        assert_eq!(BridgeClientErrorCategory::Http4xx(422).as_str(), "http_4xx");
+        assert_eq!(BridgeClientErrorCategory::Http5xx(500).as_str(), "http_5xx");
+    }
+
+    #[test]
+    fn test_error_category_from_connect_error_message() {
+        assert_eq!(
+            BridgeClientErrorCategory::from_connect_error_message(
+                "dns error: failed to lookup address information: Name or service not known"
+            ),
+            Some(BridgeClientErrorCategory::Dns)
+        );
+        assert_eq!(
+            BridgeClientErrorCategory::from_connect_error_message(
+                "invalid peer certificate: UnknownIssuer"
+            ),
+            Some(BridgeClientErrorCategory::Tls)
+        );
+        assert_eq!(
+            BridgeClientErrorCategory::from_connect_error_message(
+                "received corrupt message of type InvalidContentType"
+            ),
+            Some(BridgeClientErrorCategory::Tls)
+        );
+        assert_eq!(
+            BridgeClientErrorCategory::from_connect_error_message(
+                "tcp connect error: Connection refused (os error 111)"
+            ),
+            None
+        );
+        assert_eq!(BridgeClientErrorCategory::Tls.kind(), ErrorKind::Permanent);
+        assert_eq!(
+            BridgeClientErrorCategory::Decode.kind(),
+            ErrorKind::Permanent
+        );
+        assert_eq!(BridgeClientErrorCategory::Dns.kind(), ErrorKind::Transient);
+        assert_eq!(
+            BridgeClientErrorCategory::Connect.kind(),
+            ErrorKind::Transient
+        );
+        assert_eq!(
+            BridgeClientErrorCategory::Timeout.kind(),
+            ErrorKind::Transient
+        );
+        assert_eq!(
+            BridgeClientErrorCategory::Http4xx(429).kind(),
+            ErrorKind::RateLimited
+        );
+    }
+
+    // Starts a server that answers every connection with `response` and keeps the
+    // connection open, returning its address.
+    async fn run_raw_http_server(response: &'static str) -> std::net::SocketAddr {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            let mut conns = vec![];
+            loop {
+                let (mut stream, _) = listener.accept().await.unwrap();
+                tokio::io::AsyncWriteExt::write_all(&mut stream, response.as_bytes())
+                    .await
+                    .unwrap();
+                conns.push(stream);
+            }
+        });
+        addr
+    }
+
+    #[tokio::test]
+    async fn test_error_category_from_reqwest_error() {
+        let client = reqwest::Client::builder()
+            .timeout(Duration::from_millis(500))
+            .build()
+            .unwrap();
+
+        // Connection refused
+        let addr = tokio::net::TcpListener::bind("127.0.0.1:0")
+            .await
+            .unwrap()
+            .local_addr()
+            .unwrap();
+        let err = client
+            .get(format!("http://{}", addr))
+            .send()
+            .await
+            .unwrap_err();
+        assert_eq!(
+            BridgeClientErrorCategory::from_reqwest_error(&err),
+            BridgeClientErrorCategory::Connect
+        );
+
+        // Host name does not resolve
+        let err = client
+            .get("http://bridge-node.invalid")
+            .send()
+            .await
+            .unwrap_err();
+        assert_eq!(
+            BridgeClientErrorCategory::from_reqwest_error(&err),
+            BridgeClientErrorCategory::Dns
+        );
+
+        // Server never responds
+        let addr = run_raw_http_server("").await;
+        let err = client
+            .get(format!("http://{}", addr))
+            .send()
+            .await
+            .unwrap_err();
+        assert_eq!(
+            BridgeClientErrorCategory::from_reqwest_error(&err),
+            BridgeClientErrorCategory::Timeout
+        );
+
+        // Response is not json
+        let addr =
+            run_raw_http_server("HTTP/1.1 200 OK\r\nContent-Length: 8\r\n\r\nnot json").await;
+        let err = client
+            .get(format!("http://{}", addr))
+            .send()
+            .await
+            .unwrap()
+            .json::<serde_json::Value>()
+            .await
+            .unwrap_err();
+        assert_eq!(
+            BridgeClientErrorCategory::from_reqwest_error(&err),
+            BridgeClientErrorCategory::Decode
+        );
+
+        // TLS handshake with a plain http server
+        let err = client
+            .get(format!("https://{}", addr))
+            .send()
+            .await
+            .unwrap_err();
+        assert_eq!(
+            BridgeClientErrorCategory::from_reqwest_error(&err),
+            BridgeClientErrorCategory::Tls
+        );
+
+        // Error status
+        let addr =
+            run_raw_http_server("HTTP/1.1 422 Unprocessable Entity\r\nContent-Length: 0\r\n\r\n")
+                .await;
+        let err = client
+            .get(format!("http://{}", addr))
+            .send()
+            .await
+            .unwrap()
+            .error_for_status()
+            .unwrap_err();
+        let category = BridgeClientErrorCategory::from_reqwest_error(&err);
+        assert_eq!(category, BridgeClientErrorCategory::Http4xx(422));
+        assert!(!category.kind().is_retryable());
+    }
+
     #[test]
     fn test_bridge_action_path_regression_tests() {
         let sui_tx_digest = TransactionDigest::random();
@@ -582,6 +1571,26 @@ mod tests {
             BridgeClient::bridge_action_to_path(&action),
             "sign/upgrade_evm_contract/12/123/0606060606060606060606060606060606060606/0909090909090909090909090909090909090909/5cd8a76b000000000000000000000000000000000000000000000000000000000000002a",
         );
+        assert_eq!(BridgeClient::bridge_action_to_body(&action), None);
+
+        // Call data too long for the path is sent in the body
+        let call_data = vec![7u8; MAX_CALL_DATA_IN_PATH_BYTES + 1];
+        let action =
+            BridgeAction::EvmContractUpgradeAction(crate::types::EvmContractUpgradeAction {
+                nonce: 123,
+                chain_id: BridgeChainId::EthCustom,
+                proxy_address: EthAddress::repeat_byte(6),
+                new_impl_address: EthAddress::repeat_byte(9),
+                call_data: call_data.clone(),
+            });
+        assert_eq!(
+            BridgeClient::bridge_action_to_path(&action),
+            "sign/upgrade_evm_contract/12/123/0606060606060606060606060606060606060606/0909090909090909090909090909090909090909",
+        );
+        assert_eq!(
+            BridgeClient::bridge_action_to_body(&action),
+            Some(call_data)
+        );
 
         let action = BridgeAction::AddTokensOnSuiAction(crate::types::AddTokensOnSuiAction {
             nonce: 3,
@@ -0,0 +1,289 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Stake of the bridge committee and how much of it this node can currently reach.
+//! Token transfers can only be certified while the reachable stake is at least the
+//! validity threshold. `CommitteeProber` periodically pings the members that the
+//! aggregator requests signatures from. `probe_governance_action` asks them whether they
+//! would sign a governance action, before it is proposed.
+
+use crate::alerts::{AlertCondition, AlertSink};
+use crate::api_types::{GovernanceProbe, GovernanceVote};
+use crate::client::bridge_authority_aggregator::BridgeAuthorityAggregator;
+use crate::client::bridge_client::BridgeClientErrorCategory;
+use crate::error::BridgeError;
+use crate::metrics::BridgeMetrics;
+use crate::sui_client::{SuiClient, SuiClientInner};
+use crate::types::{BridgeAction, BridgeCommittee};
+use arc_swap::ArcSwap;
+use futures::future::join_all;
+use std::sync::Arc;
+use std::time::Duration;
+use sui_types::base_types::ConciseableName;
+use sui_types::bridge::APPROVAL_THRESHOLD_TOKEN_TRANSFER;
+use tokio::time;
+use tracing::{debug, info, warn};
+
+pub const DEFAULT_COMMITTEE_PROBE_INTERVAL: Duration = Duration::from_secs(30);
+// Members that don't answer the ping within this time are unreachable
+const COMMITTEE_PROBE_TIMEOUT: Duration = Duration::from_secs(5);
+pub const GOVERNANCE_PROBE_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Sets the gauges that only depend on the committee, whenever it is refreshed.
+pub fn update_committee_stake_metrics(metrics: &BridgeMetrics, committee: &BridgeCommittee) {
+    let total_stake: u64 = committee
+        .members()
+        .values()
+        .map(|member| member.voting_power)
+        .sum();
+    metrics.committee_total_stake.set(total_stake as i64);
+    metrics
+        .committee_blocklisted_stake
+        .set(committee.total_blocklisted_stake() as i64);
+    metrics
+        .committee_validity_threshold
+        .set(APPROVAL_THRESHOLD_TOKEN_TRANSFER as i64);
+}
+
+/// Returns the stake of the members that answered a ping within `timeout`. Blocklisted
+/// members are never reachable, as the aggregator has no client for them.
+pub async fn probe_reachable_stake(auth_agg: &BridgeAuthorityAggregator, timeout: Duration) -> u64 {
+    let probes = auth_agg.clients.iter().map(|(name, client)| async move {
+        match time::timeout(timeout, client.ping()).await {
+            Ok(Ok(true)) => auth_agg
+                .committee
+                .member(name)
+                .map(|member| member.voting_power)
+                .unwrap_or_default(),
+            result => {
+                debug!(
+                    "Committee member {} is unreachable: {:?}",
+                    name.concise(),
+                    result
+                );
+                0
+            }
+        }
+    });
+    join_all(probes).await.into_iter().sum()
+}
+
+/// Asks every member that the aggregator requests signatures from whether it would sign
+/// the governance `action`, with a dry run. Members that don't answer within `timeout`,
+/// or don't support dry runs, are unknown: they are not assumed to sign.
+pub async fn probe_governance_action(
+    auth_agg: &BridgeAuthorityAggregator,
+    action: &BridgeAction,
+    timeout: Duration,
+) -> GovernanceProbe {
+    let probes = auth_agg.clients.iter().map(|(name, client)| async move {
+        let dry_run = time::timeout(timeout, client.request_dry_run_governance_action(action))
+            .await
+            .unwrap_or_else(|_| {
+                Err(BridgeError::BridgeClientError(
+                    BridgeClientErrorCategory::Timeout,
+                    format!("No answer within {:?}", timeout),
+                ))
+            });
+        let stake = auth_agg
+            .committee
+            .member(name)
+            .map(|member| member.voting_power)
+            .unwrap_or_default();
+        GovernanceVote::new(name, stake, dry_run)
+    });
+    GovernanceProbe::new(action, join_all(probes).await)
+}
+
+pub struct CommitteeProber<C> {
+    auth_agg: Arc<ArcSwap<BridgeAuthorityAggregator>>,
+    sui_client: Arc<SuiClient<C>>,
+    metrics: Arc<BridgeMetrics>,
+    interval: Duration,
+    alerts: Option<Arc<AlertSink>>,
+}
+
+impl<C> CommitteeProber<C>
+where
+    C: SuiClientInner + 'static,
+{
+    pub fn new(
+        auth_agg: Arc<ArcSwap<BridgeAuthorityAggregator>>,
+        sui_client: Arc<SuiClient<C>>,
+        metrics: Arc<BridgeMetrics>,
+        interval: Duration,
+    ) -> Self {
+        Self {
+            auth_agg,
+            sui_client,
+            metrics,
+            interval,
+            alerts: None,
+        }
+    }
+
+    /// Raises an alert when the reachable stake is below the validity threshold.
+    pub fn with_alert_sink(mut self, alerts: Arc<AlertSink>) -> Self {
+        self.alerts = Some(alerts);
+        self
+    }
+
+    pub async fn run(self) {
+        info!("Starting CommitteeProber");
+        let mut interval = time::interval(self.interval);
+        interval.set_missed_tick_behavior(time::MissedTickBehavior::Delay);
+        loop {
+            interval.tick().await;
+            self.probe().await;
+        }
+    }
+
+    async fn probe(&self) {
+        let auth_agg = self.auth_agg.load_full();
+        update_committee_stake_metrics(&self.metrics, &auth_agg.committee);
+        match self.sui_client.get_bridge_summary().await {
+            Ok(summary) => self
+                .metrics
+                .committee_last_update_epoch
+                .set(summary.committee.last_committee_update_epoch as i64),
+            Err(e) => warn!("Failed to get bridge summary: {:?}", e),
+        }
+        let reachable_stake = probe_reachable_stake(&auth_agg, COMMITTEE_PROBE_TIMEOUT).await;
+        self.metrics
+            .committee_reachable_stake
+            .set(reachable_stake as i64);
+        if reachable_stake < APPROVAL_THRESHOLD_TOKEN_TRANSFER {
+            warn!(
+                "Reachable committee stake {} is below the validity threshold {}",
+                reachable_stake, APPROVAL_THRESHOLD_TOKEN_TRANSFER
+            );
+            if let Some(alerts) = &self.alerts {
+                alerts.raise(
+                    AlertCondition::CommitteeStakeBelowQuorum,
+                    format!(
+                        "Reachable committee stake {} is below the validity threshold {}",
+                        reachable_stake, APPROVAL_THRESHOLD_TOKEN_TRANSFER
+                    ),
+                );
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api_types::ProbeVote;
+    use crate::crypto::BridgeAuthoritySignInfo;
+    use crate::server::mock_handler::BridgeRequestMockHandler;
+    use crate::sui_mock_client::SuiMockClient;
+    use crate::test_utils::get_test_authorities_and_run_mock_bridge_server;
+    use crate::types::{GovernanceDryRun, LimitUpdateAction, SignedBridgeAction};
+    use fastcrypto::encoding::{Encoding, Hex};
+    use fastcrypto::traits::ToFromBytes;
+    use sui_config::local_ip_utils::get_available_port;
+    use sui_types::bridge::{BridgeChainId, BridgeCommitteeSummary};
+
+    #[tokio::test]
+    async fn test_committee_stake_metrics() {
+        telemetry_subscribers::init_for_testing();
+        let mocks = (0..4)
+            .map(|_| BridgeRequestMockHandler::new())
+            .collect::<Vec<_>>();
+        let (_handles, mut authorities, _secrets) =
+            get_test_authorities_and_run_mock_bridge_server(vec![2500, 2500, 2500, 2500], mocks);
+        // One member is blocklisted, another one unreachable
+        authorities[0].is_blocklisted = true;
+        authorities[1].base_url = format!("http://127.0.0.1:{}", get_available_port("127.0.0.1"));
+        let committee = Arc::new(BridgeCommittee::new(authorities).unwrap());
+        let metrics = Arc::new(BridgeMetrics::for_testing());
+        let auth_agg = Arc::new(ArcSwap::from_pointee(BridgeAuthorityAggregator::new(
+            committee,
+            metrics.clone(),
+        )));
+
+        let sui_client_mock = SuiMockClient::default();
+        sui_client_mock.set_bridge_committee(BridgeCommitteeSummary {
+            last_committee_update_epoch: 7,
+            ..Default::default()
+        });
+        let prober = CommitteeProber::new(
+            auth_agg,
+            Arc::new(SuiClient::new_for_testing(sui_client_mock)),
+            metrics.clone(),
+            DEFAULT_COMMITTEE_PROBE_INTERVAL,
+        );
+        prober.probe().await;
+
+        assert_eq!(metrics.committee_total_stake.get(), 10000);
+        assert_eq!(metrics.committee_blocklisted_stake.get(), 2500);
+        assert_eq!(metrics.committee_reachable_stake.get(), 5000);
+        assert_eq!(
+            metrics.committee_validity_threshold.get(),
+            APPROVAL_THRESHOLD_TOKEN_TRANSFER as i64
+        );
+        assert_eq!(metrics.committee_last_update_epoch.get(), 7);
+    }
+
+    #[tokio::test]
+    async fn test_probe_governance_action() {
+        telemetry_subscribers::init_for_testing();
+        let mocks = (0..3)
+            .map(|_| BridgeRequestMockHandler::new())
+            .collect::<Vec<_>>();
+        let (_handles, mut authorities, secrets) =
+            get_test_authorities_and_run_mock_bridge_server(vec![5000, 2500, 2500], mocks.clone());
+        let action = BridgeAction::LimitUpdateAction(LimitUpdateAction {
+            chain_id: BridgeChainId::SuiCustom,
+            sending_chain_id: BridgeChainId::EthCustom,
+            nonce: 1,
+            new_usd_limit: 10000,
+        });
+        // The second member doesn't have the action in its allowlist
+        mocks[1].add_governance_dry_run_response(
+            action.digest(),
+            GovernanceDryRun::from_verification(Err(BridgeError::GovernanceActionIsNotApproved)),
+        );
+        // The third member runs a release without dry runs, and signs the action instead
+        let sig = BridgeAuthoritySignInfo::new(&action, &secrets[2]);
+        let signed_action = SignedBridgeAction::new_from_data_and_sig(action.clone(), sig);
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        authorities[2].base_url = format!("http://{}", listener.local_addr().unwrap());
+        let legacy_server = axum::Router::new().fallback(move || {
+            let signed_action = signed_action.clone();
+            async move { axum::Json(signed_action) }
+        });
+        tokio::spawn(async move { axum::serve(listener, legacy_server).await.unwrap() });
+        let pubkeys = authorities
+            .iter()
+            .map(|authority| authority.pubkey_bytes())
+            .collect::<Vec<_>>();
+        let committee = Arc::new(BridgeCommittee::new(authorities).unwrap());
+        let auth_agg =
+            BridgeAuthorityAggregator::new(committee, Arc::new(BridgeMetrics::for_testing()));
+
+        let probe = probe_governance_action(&auth_agg, &action, Duration::from_secs(5)).await;
+
+        let vote_of = |i: usize| {
+            probe
+                .votes
+                .iter()
+                .find(|vote| vote.pubkey == Hex::encode(pubkeys[i].as_bytes()))
+                .unwrap()
+        };
+        assert_eq!(vote_of(0).vote, ProbeVote::Yes);
+        assert_eq!(vote_of(1).vote, ProbeVote::No);
+        assert_eq!(
+            vote_of(1).reason.as_deref(),
+            Some("Governance action is not approved")
+        );
+        assert_eq!(vote_of(2).vote, ProbeVote::Unknown);
+        assert_eq!(probe.yes_stake, 5000);
+        assert_eq!(probe.no_stake, 2500);
+        assert_eq!(probe.unknown_stake, 2500);
+        // The legacy member would have made it certifiable, but is not assumed to sign
+        assert_eq!(probe.approval_threshold, action.approval_threshold());
+        assert!(probe.yes_stake + probe.unknown_stake >= probe.approval_threshold);
+        assert!(!probe.would_certify);
+    }
+}
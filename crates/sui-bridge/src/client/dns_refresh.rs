@@ -0,0 +1,205 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Re-resolution of the hosts of the committee members. reqwest resolves a host only when
+//! it opens a connection, and pooled connections to a member are kept for as long as they
+//! are used, so a member that moves its node behind the same hostname is still requested
+//! at its old address. `CommitteeDnsRefresher` periodically resolves the host of every
+//! member the aggregator requests signatures from, and rebuilds the client of the members
+//! whose addresses changed, see `BridgeClient::refresh_addresses`.
+
+use crate::client::bridge_authority_aggregator::BridgeAuthorityAggregator;
+use crate::metrics::BridgeMetrics;
+use arc_swap::ArcSwap;
+use async_trait::async_trait;
+use futures::future::join_all;
+use std::collections::BTreeSet;
+use std::net::SocketAddr;
+use std::sync::Arc;
+use std::time::Duration;
+use sui_types::base_types::ConciseableName;
+use tokio::time;
+use tracing::{info, warn};
+
+pub const DEFAULT_COMMITTEE_DNS_REFRESH_INTERVAL: Duration = Duration::from_secs(60);
+
+#[async_trait]
+pub trait HostResolver: Send + Sync {
+    async fn resolve(&self, host: &str, port: u16) -> std::io::Result<BTreeSet<SocketAddr>>;
+}
+
+/// Resolves hosts with the resolver of the system.
+pub struct SystemResolver;
+
+#[async_trait]
+impl HostResolver for SystemResolver {
+    async fn resolve(&self, host: &str, port: u16) -> std::io::Result<BTreeSet<SocketAddr>> {
+        Ok(tokio::net::lookup_host((host, port)).await?.collect())
+    }
+}
+
+pub struct CommitteeDnsRefresher {
+    auth_agg: Arc<ArcSwap<BridgeAuthorityAggregator>>,
+    resolver: Arc<dyn HostResolver>,
+    metrics: Arc<BridgeMetrics>,
+    interval: Duration,
+}
+
+impl CommitteeDnsRefresher {
+    pub fn new(
+        auth_agg: Arc<ArcSwap<BridgeAuthorityAggregator>>,
+        resolver: Arc<dyn HostResolver>,
+        metrics: Arc<BridgeMetrics>,
+        interval: Duration,
+    ) -> Self {
+        Self {
+            auth_agg,
+            resolver,
+            metrics,
+            interval,
+        }
+    }
+
+    pub async fn run(self) {
+        info!("Starting CommitteeDnsRefresher");
+        let mut interval = time::interval(self.interval);
+        interval.set_missed_tick_behavior(time::MissedTickBehavior::Delay);
+        loop {
+            interval.tick().await;
+            self.refresh().await;
+        }
+    }
+
+    /// Resolves the host of every member once. Returns the number of members whose
+    /// client was rebuilt.
+    pub async fn refresh(&self) -> usize {
+        let auth_agg = self.auth_agg.load_full();
+        let refreshes = auth_agg.clients.iter().map(|(name, client)| async move {
+            match client.refresh_addresses(self.resolver.as_ref()).await {
+                Ok(changed) => changed,
+                Err(e) => {
+                    // The client keeps the addresses it has
+                    warn!(
+                        "Failed to re-resolve committee member {}: {:?}",
+                        name.concise(),
+                        e
+                    );
+                    false
+                }
+            }
+        });
+        let changed = join_all(refreshes)
+            .await
+            .into_iter()
+            .filter(|changed| *changed)
+            .count();
+        self.metrics.committee_dns_changes.inc_by(changed as u64);
+        changed
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::crypto::{BridgeAuthorityPublicKeyBytes, BridgeAuthoritySignInfo};
+    use crate::server::mock_handler::{run_mock_server, BridgeRequestMockHandler};
+    use crate::test_utils::{get_test_authority_and_key, get_test_sui_to_eth_bridge_action};
+    use crate::types::{BridgeAction, BridgeCommittee, SignedBridgeAction};
+    use std::sync::Mutex;
+    use sui_config::local_ip_utils::get_available_port;
+    use sui_types::digests::TransactionDigest;
+
+    // Answers with the addresses it's given, whatever the host
+    struct MockResolver {
+        addresses: Mutex<BTreeSet<SocketAddr>>,
+    }
+
+    impl MockResolver {
+        fn set(&self, address: SocketAddr) {
+            *self.addresses.lock().unwrap() = BTreeSet::from([address]);
+        }
+    }
+
+    #[async_trait]
+    impl HostResolver for MockResolver {
+        async fn resolve(&self, _host: &str, _port: u16) -> std::io::Result<BTreeSet<SocketAddr>> {
+            Ok(self.addresses.lock().unwrap().clone())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_committee_dns_refresh() {
+        telemetry_subscribers::init_for_testing();
+        let port = get_available_port("127.0.0.1");
+        let old_address: SocketAddr = format!("127.0.0.1:{port}").parse().unwrap();
+        let new_address: SocketAddr = format!("127.0.0.2:{port}").parse().unwrap();
+
+        let (mut authority, _, secret) = get_test_authority_and_key(10000, port);
+        authority.base_url = format!("http://bridge-authority.test:{port}");
+        let pubkey_bytes = BridgeAuthorityPublicKeyBytes::from(&authority.pubkey);
+        let committee = BridgeCommittee::new(vec![authority]).unwrap();
+        let metrics = Arc::new(BridgeMetrics::for_testing());
+        let auth_agg = Arc::new(ArcSwap::from_pointee(BridgeAuthorityAggregator::new(
+            Arc::new(committee),
+            metrics.clone(),
+        )));
+
+        // Both servers know the same signed action
+        let tx_digest = TransactionDigest::random();
+        let action = get_test_sui_to_eth_bridge_action(
+            Some(tx_digest),
+            Some(0),
+            None,
+            None,
+            None,
+            None,
+            None,
+        );
+        let sig = BridgeAuthoritySignInfo::new(&action, &secret);
+        let signed_action = SignedBridgeAction::new_from_data_and_sig(action.clone(), sig);
+        let handlers = [old_address, new_address].map(|address| {
+            let handler = BridgeRequestMockHandler::new();
+            handler.add_sui_event_response(tx_digest, 0, Ok(signed_action.clone()));
+            let _server = run_mock_server(address, handler.clone());
+            handler
+        });
+        let requests = |handler: &BridgeRequestMockHandler| {
+            handler.get_sui_token_events_requested(tx_digest, 0)
+        };
+        let request_signature = |action: BridgeAction| {
+            let client = auth_agg.load().clients[&pubkey_bytes].clone();
+            async move { client.request_sign_bridge_action(action).await }
+        };
+
+        let resolver = Arc::new(MockResolver {
+            addresses: Mutex::new(BTreeSet::from([old_address])),
+        });
+        let refresher = CommitteeDnsRefresher::new(
+            auth_agg.clone(),
+            resolver.clone(),
+            metrics.clone(),
+            DEFAULT_COMMITTEE_DNS_REFRESH_INTERVAL,
+        );
+        // The first resolution pins the client without counting as a change
+        assert_eq!(refresher.refresh().await, 0);
+        assert_eq!(metrics.committee_dns_changes.get(), 0);
+        request_signature(action.clone()).await.unwrap();
+        assert_eq!(requests(&handlers[0]), 1);
+        assert_eq!(requests(&handlers[1]), 0);
+
+        // Same answer, nothing is rebuilt
+        assert_eq!(refresher.refresh().await, 0);
+        request_signature(action.clone()).await.unwrap();
+        assert_eq!(requests(&handlers[0]), 2);
+
+        // The member moved, requests go to its new address
+        resolver.set(new_address);
+        assert_eq!(refresher.refresh().await, 1);
+        assert_eq!(metrics.committee_dns_changes.get(), 1);
+        request_signature(action.clone()).await.unwrap();
+        assert_eq!(requests(&handlers[0]), 2);
+        assert_eq!(requests(&handlers[1]), 1);
+        assert_eq!(refresher.refresh().await, 0);
+        assert_eq!(metrics.committee_dns_changes.get(), 1);
+    }
+}
@@ -0,0 +1,256 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Hedged signing requests to the committee members, for members whose first response is
+//! slow but whose retry is fast, e.g. behind a cold connection. A member that hasn't
+//! responded within its hedging delay is sent the same request again, the first of the two
+//! responses is taken and the other request is cancelled. The delay follows the latency of
+//! the member in the `AuthorityScoreboard`.
+//!
+//! A member is hedged at most once per aggregation. No hedge is sent while
+//! `max_in_flight` hedges are in flight, nor to a member that answered it was busy within
+//! the last `BUSY_BACKOFF`, as a hedge would only add to its load.
+
+use crate::client::bridge_client::BridgeClientErrorCategory;
+use crate::crypto::BridgeAuthorityPublicKeyBytes;
+use crate::error::{BridgeError, BridgeResult};
+use crate::metrics::BridgeMetrics;
+use std::collections::BTreeMap;
+use std::future::Future;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use sui_types::base_types::ConciseableName;
+use tokio::sync::{OwnedSemaphorePermit, Semaphore};
+
+pub const DEFAULT_HEDGING_MIN_DELAY: Duration = Duration::from_millis(200);
+pub const DEFAULT_HEDGING_MAX_DELAY: Duration = Duration::from_secs(2);
+pub const DEFAULT_HEDGING_LATENCY_FACTOR_PERCENT: u64 = 200;
+pub const DEFAULT_MAX_IN_FLIGHT_HEDGES: usize = 16;
+
+// Members that answered they are busy are not hedged for this long
+const BUSY_BACKOFF: Duration = Duration::from_secs(30);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct HedgingOptions {
+    pub min_delay: Duration,
+    /// Also the delay of the members without a score
+    pub max_delay: Duration,
+    /// The delay of a member is its moving average latency times this, in percent
+    pub latency_factor_percent: u64,
+    pub max_in_flight: usize,
+}
+
+impl Default for HedgingOptions {
+    fn default() -> Self {
+        Self {
+            min_delay: DEFAULT_HEDGING_MIN_DELAY,
+            max_delay: DEFAULT_HEDGING_MAX_DELAY,
+            latency_factor_percent: DEFAULT_HEDGING_LATENCY_FACTOR_PERCENT,
+            max_in_flight: DEFAULT_MAX_IN_FLIGHT_HEDGES,
+        }
+    }
+}
+
+pub struct RequestHedging {
+    options: HedgingOptions,
+    in_flight: Arc<Semaphore>,
+    busy_until: Mutex<BTreeMap<BridgeAuthorityPublicKeyBytes, Instant>>,
+    metrics: Arc<BridgeMetrics>,
+}
+
+impl RequestHedging {
+    pub fn new(options: HedgingOptions, metrics: Arc<BridgeMetrics>) -> Self {
+        Self {
+            options,
+            in_flight: Arc::new(Semaphore::new(options.max_in_flight)),
+            busy_until: Mutex::new(BTreeMap::new()),
+            metrics,
+        }
+    }
+
+    /// The delay after which a request to `name` is hedged.
+    pub fn delay(&self, name: &BridgeAuthorityPublicKeyBytes) -> Duration {
+        match self.metrics.committee_scores.get(name) {
+            Some(score) => Duration::from_millis(
+                score
+                    .ewma_latency_ms
+                    .saturating_mul(self.options.latency_factor_percent)
+                    / 100,
+            )
+            .clamp(self.options.min_delay, self.options.max_delay),
+            None => self.options.max_delay,
+        }
+    }
+
+    // Returns a permit for a hedge to `name`, None if it must not be hedged now
+    fn hedge_permit(&self, name: &BridgeAuthorityPublicKeyBytes) -> Option<OwnedSemaphorePermit> {
+        let mut busy_until = self.busy_until.lock().unwrap();
+        match busy_until.get(name) {
+            Some(until) if *until > Instant::now() => return None,
+            Some(_) => {
+                busy_until.remove(name);
+            }
+            None => (),
+        }
+        self.in_flight.clone().try_acquire_owned().ok()
+    }
+
+    fn record_result<T>(&self, name: &BridgeAuthorityPublicKeyBytes, result: &BridgeResult<T>) {
+        let busy = matches!(
+            result,
+            Err(BridgeError::BridgeClientError(
+                BridgeClientErrorCategory::Http4xx(429),
+                _
+            ))
+        );
+        if busy {
+            self.busy_until
+                .lock()
+                .unwrap()
+                .insert(name.clone(), Instant::now() + BUSY_BACKOFF);
+        }
+    }
+
+    /// Runs `request` to `name`, and runs it a second time if the first hasn't completed
+    /// within `delay(name)`. Returns the result of the request that completes first.
+    pub async fn request<T, F, Fut>(
+        &self,
+        name: &BridgeAuthorityPublicKeyBytes,
+        request: F,
+    ) -> BridgeResult<T>
+    where
+        F: Fn() -> Fut,
+        Fut: Future<Output = BridgeResult<T>>,
+    {
+        let first = request();
+        tokio::pin!(first);
+        let result = match tokio::time::timeout(self.delay(name), &mut first).await {
+            Ok(result) => result,
+            Err(_) => match self.hedge_permit(name) {
+                Some(_permit) => {
+                    let authority = name.concise_owned();
+                    self.metrics
+                        .committee_hedges_issued
+                        .with_label_values(&[&authority])
+                        .inc();
+                    let second = request();
+                    tokio::pin!(second);
+                    tokio::select! {
+                        biased;
+                        result = &mut first => result,
+                        result = &mut second => {
+                            self.metrics
+                                .committee_hedges_won
+                                .with_label_values(&[&authority])
+                                .inc();
+                            result
+                        }
+                    }
+                }
+                None => first.await,
+            },
+        };
+        self.record_result(name, &result);
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::get_test_authority_and_key;
+    use std::sync::atomic::{AtomicU64, Ordering};
+
+    fn hedging(max_in_flight: usize) -> (RequestHedging, BridgeAuthorityPublicKeyBytes) {
+        let options = HedgingOptions {
+            min_delay: Duration::from_millis(50),
+            max_delay: Duration::from_millis(100),
+            latency_factor_percent: 200,
+            max_in_flight,
+        };
+        let (authority, _, _) = get_test_authority_and_key(2500, 9999);
+        (
+            RequestHedging::new(options, Arc::new(BridgeMetrics::for_testing())),
+            authority.pubkey_bytes(),
+        )
+    }
+
+    // The first request hangs, the next ones respond right away
+    async fn request(requests: &AtomicU64) -> BridgeResult<u64> {
+        let attempt = requests.fetch_add(1, Ordering::SeqCst);
+        if attempt == 0 {
+            std::future::pending::<()>().await;
+        }
+        Ok(attempt)
+    }
+
+    #[tokio::test]
+    async fn test_hedged_request() {
+        let (hedging, name) = hedging(1);
+        let requests = AtomicU64::new(0);
+        let start = Instant::now();
+        assert_eq!(
+            hedging.request(&name, || request(&requests)).await.unwrap(),
+            1
+        );
+        assert!(start.elapsed() < Duration::from_secs(1));
+        let authority = name.concise_owned();
+        assert_eq!(
+            hedging
+                .metrics
+                .committee_hedges_issued
+                .with_label_values(&[&authority])
+                .get(),
+            1
+        );
+        assert_eq!(
+            hedging
+                .metrics
+                .committee_hedges_won
+                .with_label_values(&[&authority])
+                .get(),
+            1
+        );
+
+        // A request that responds in time is not hedged
+        assert_eq!(
+            hedging.request(&name, || request(&requests)).await.unwrap(),
+            2
+        );
+        assert_eq!(requests.load(Ordering::SeqCst), 3);
+    }
+
+    #[tokio::test]
+    async fn test_hedging_delay_and_limits() {
+        let (hedging, name) = hedging(1);
+        assert_eq!(hedging.delay(&name), Duration::from_millis(100));
+        hedging
+            .metrics
+            .committee_scores
+            .record(&name, Duration::from_millis(30), false);
+        assert_eq!(hedging.delay(&name), Duration::from_millis(60));
+
+        // No hedge while the hedges in flight are at their limit
+        let permit = hedging.hedge_permit(&name).unwrap();
+        assert!(hedging.hedge_permit(&name).is_none());
+        drop(permit);
+
+        // Nor to a member that answered it's busy
+        hedging.record_result::<()>(
+            &name,
+            &Err(BridgeError::BridgeClientError(
+                BridgeClientErrorCategory::Http4xx(429),
+                "Too Many Requests".to_string(),
+            )),
+        );
+        assert!(hedging.hedge_permit(&name).is_none());
+        let requests = AtomicU64::new(0);
+        let result = tokio::time::timeout(
+            Duration::from_millis(500),
+            hedging.request(&name, || request(&requests)),
+        )
+        .await;
+        assert!(result.is_err());
+        assert_eq!(requests.load(Ordering::SeqCst), 1);
+    }
+}
@@ -1,5 +1,9 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
+pub mod authority_scores;
 pub mod bridge_authority_aggregator;
 pub mod bridge_client;
+pub mod committee_probe;
+pub mod dns_refresh;
+pub mod hedging;
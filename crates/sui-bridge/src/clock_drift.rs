@@ -0,0 +1,300 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Drift of the local clock relative to the chains. The node periodically compares its
+//! clock with the timestamps of the latest Sui checkpoint and Eth block, and reports the
+//! difference in `bridge_clock_drift_ms`. A drift above the maximum for either chain
+//! degrades `/health`: either the clock is off or the RPC of that chain lags. Only when the
+//! clock drifts from every chain it was compared with, in the same direction, is the clock
+//! itself considered off. Signing can then be refused, since the time based checks of the
+//! verification can't be trusted.
+
+use crate::chain_health::PipelineChain;
+use crate::error::{BridgeError, BridgeResult};
+use crate::eth_client::EthClient;
+use crate::metrics::BridgeMetrics;
+use crate::sui_client::{SuiClient, SuiClientInner};
+use crate::timestamps::now_ms;
+use ethers::providers::JsonRpcClient;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::time::MissedTickBehavior;
+use tracing::{error, info, warn};
+
+pub const DEFAULT_CLOCK_DRIFT_CHECK_INTERVAL: Duration = Duration::from_secs(60);
+// Well above the time between two Eth blocks
+pub const DEFAULT_MAX_CLOCK_DRIFT_MS: u64 = 60_000;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ClockDriftSettings {
+    pub max_drift_ms: u64,
+    /// Refuse to sign while the clock is off
+    pub refuse_signing: bool,
+}
+
+impl Default for ClockDriftSettings {
+    fn default() -> Self {
+        Self {
+            max_drift_ms: DEFAULT_MAX_CLOCK_DRIFT_MS,
+            refuse_signing: false,
+        }
+    }
+}
+
+/// The latest drift of the local clock from each chain, in milliseconds: the local time
+/// minus the chain time, positive when the local clock is ahead.
+#[derive(Debug, Default)]
+pub struct ClockDrift {
+    settings: Mutex<ClockDriftSettings>,
+    sui: Mutex<Option<i64>>,
+    eth: Mutex<Option<i64>>,
+}
+
+impl ClockDrift {
+    pub fn configure(&self, settings: ClockDriftSettings) {
+        *self.settings.lock().unwrap() = settings;
+    }
+
+    pub fn settings(&self) -> ClockDriftSettings {
+        *self.settings.lock().unwrap()
+    }
+
+    /// Records the drift of the local clock at `local_time_ms` from `chain_time_ms`, the
+    /// timestamp of the latest checkpoint or block of `chain`.
+    pub fn record(
+        &self,
+        chain: PipelineChain,
+        chain_time_ms: u64,
+        local_time_ms: u64,
+        metrics: &BridgeMetrics,
+    ) {
+        let drift_ms = local_time_ms as i64 - chain_time_ms as i64;
+        *self.chain(chain).lock().unwrap() = Some(drift_ms);
+        metrics
+            .clock_drift_ms
+            .with_label_values(&[chain.as_str()])
+            .set(drift_ms);
+        let max_drift_ms = self.settings().max_drift_ms;
+        if drift_ms.unsigned_abs() > max_drift_ms {
+            error!(
+                "Local clock is {}ms off from the latest {} timestamp, above the maximum of {}ms",
+                drift_ms,
+                chain.as_str(),
+                max_drift_ms
+            );
+        }
+    }
+
+    pub fn drift_ms(&self, chain: PipelineChain) -> Option<i64> {
+        *self.chain(chain).lock().unwrap()
+    }
+
+    pub fn is_drifting(&self, chain: PipelineChain) -> bool {
+        let max_drift_ms = self.settings().max_drift_ms;
+        self.drift_ms(chain)
+            .is_some_and(|drift_ms| drift_ms.unsigned_abs() > max_drift_ms)
+    }
+
+    /// Whether the local clock is off: it drifts from every chain it was compared with,
+    /// in the same direction. A drift from only one chain is more likely its RPC lagging.
+    pub fn is_clock_off(&self) -> bool {
+        let max_drift_ms = self.settings().max_drift_ms as i64;
+        let drifts = [PipelineChain::Sui, PipelineChain::Eth]
+            .into_iter()
+            .filter_map(|chain| self.drift_ms(chain))
+            .collect::<Vec<_>>();
+        !drifts.is_empty()
+            && (drifts.iter().all(|drift_ms| *drift_ms > max_drift_ms)
+                || drifts.iter().all(|drift_ms| *drift_ms < -max_drift_ms))
+    }
+
+    pub fn is_refusing_signatures(&self) -> bool {
+        self.settings().refuse_signing && self.is_clock_off()
+    }
+
+    /// Fails while signing is refused because the clock is off.
+    pub fn check_signing(&self) -> BridgeResult<()> {
+        if !self.is_refusing_signatures() {
+            return Ok(());
+        }
+        Err(BridgeError::ClockDrift(format!(
+            "local clock is {:?}ms off from Sui and {:?}ms off from Eth",
+            self.drift_ms(PipelineChain::Sui),
+            self.drift_ms(PipelineChain::Eth)
+        )))
+    }
+
+    fn chain(&self, chain: PipelineChain) -> &Mutex<Option<i64>> {
+        match chain {
+            PipelineChain::Sui => &self.sui,
+            PipelineChain::Eth => &self.eth,
+        }
+    }
+}
+
+/// Periodically compares the local clock with the latest Sui checkpoint and Eth block.
+pub struct ClockDriftChecker<C, P> {
+    sui_client: Arc<SuiClient<C>>,
+    eth_client: Arc<EthClient<P>>,
+    interval: Duration,
+    metrics: Arc<BridgeMetrics>,
+}
+
+impl<C, P> ClockDriftChecker<C, P>
+where
+    C: SuiClientInner + 'static,
+    P: JsonRpcClient + 'static,
+{
+    pub fn new(
+        sui_client: Arc<SuiClient<C>>,
+        eth_client: Arc<EthClient<P>>,
+        settings: ClockDriftSettings,
+        interval: Duration,
+        metrics: Arc<BridgeMetrics>,
+    ) -> Self {
+        metrics.clock_drift.configure(settings);
+        Self {
+            sui_client,
+            eth_client,
+            interval,
+            metrics,
+        }
+    }
+
+    pub async fn run(self) {
+        info!("Starting ClockDriftChecker");
+        let mut interval = tokio::time::interval(self.interval);
+        interval.set_missed_tick_behavior(MissedTickBehavior::Delay);
+        loop {
+            interval.tick().await;
+            self.check().await;
+        }
+    }
+
+    pub async fn check(&self) {
+        match self.sui_client.get_latest_checkpoint_timestamp_ms().await {
+            Ok(chain_time_ms) => self.metrics.clock_drift.record(
+                PipelineChain::Sui,
+                chain_time_ms,
+                now_ms(),
+                &self.metrics,
+            ),
+            Err(e) => warn!("Failed to get the latest Sui checkpoint timestamp: {:?}", e),
+        }
+        match self.eth_client.get_latest_block_timestamp_ms().await {
+            Ok(chain_time_ms) => self.metrics.clock_drift.record(
+                PipelineChain::Eth,
+                chain_time_ms,
+                now_ms(),
+                &self.metrics,
+            ),
+            Err(e) => warn!("Failed to get the latest Eth block timestamp: {:?}", e),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api_types::{ClockHealth, PipelineStatus};
+    use crate::eth_mock_provider::EthMockProvider;
+    use crate::sui_mock_client::SuiMockClient;
+    use crate::test_utils::mock_latest_block_timestamp;
+    use std::collections::HashSet;
+
+    const MINUTE_MS: u64 = 60_000;
+
+    #[tokio::test]
+    async fn test_clock_drift_checker() {
+        let metrics = Arc::new(BridgeMetrics::for_testing());
+        let sui_client_mock = SuiMockClient::default();
+        let eth_provider = EthMockProvider::new();
+        let checker = ClockDriftChecker::new(
+            Arc::new(SuiClient::new_for_testing(sui_client_mock.clone())),
+            Arc::new(EthClient::new_mocked(eth_provider.clone(), HashSet::new())),
+            ClockDriftSettings {
+                max_drift_ms: MINUTE_MS,
+                refuse_signing: true,
+            },
+            DEFAULT_CLOCK_DRIFT_CHECK_INTERVAL,
+            metrics.clone(),
+        );
+        let drift = metrics.clock_drift.clone();
+        let gauge = |chain: &str| metrics.clock_drift_ms.with_label_values(&[chain]).get();
+
+        // Nothing measured yet
+        assert_eq!(
+            ClockHealth::from(drift.as_ref()).status,
+            PipelineStatus::Unknown
+        );
+        assert!(drift.check_signing().is_ok());
+
+        // Both chains are 5 minutes ahead of the local clock
+        let ahead_ms = now_ms() + 5 * MINUTE_MS;
+        sui_client_mock.set_latest_checkpoint_timestamp_ms(ahead_ms);
+        mock_latest_block_timestamp(&eth_provider, ahead_ms / 1000);
+        checker.check().await;
+        for chain in ["sui", "eth"] {
+            assert!(gauge(chain) <= -5 * MINUTE_MS as i64 + 1000, "{}", chain);
+            assert!(gauge(chain) > -6 * MINUTE_MS as i64, "{}", chain);
+        }
+        let health = ClockHealth::from(drift.as_ref());
+        assert_eq!(health.status, PipelineStatus::Degraded);
+        assert!(health.refusing_signatures);
+        assert!(matches!(
+            drift.check_signing(),
+            Err(BridgeError::ClockDrift(_))
+        ));
+
+        // The clock is fine again
+        sui_client_mock.set_latest_checkpoint_timestamp_ms(now_ms());
+        mock_latest_block_timestamp(&eth_provider, now_ms() / 1000);
+        checker.check().await;
+        assert!(gauge("sui").abs() < MINUTE_MS as i64);
+        assert_eq!(
+            ClockHealth::from(drift.as_ref()).status,
+            PipelineStatus::Healthy
+        );
+        assert!(drift.check_signing().is_ok());
+    }
+
+    #[test]
+    fn test_clock_off_only_when_all_chains_drift_the_same_way() {
+        let metrics = BridgeMetrics::for_testing();
+        let drift = ClockDrift::default();
+        drift.configure(ClockDriftSettings {
+            max_drift_ms: MINUTE_MS,
+            refuse_signing: true,
+        });
+        let now = 1_700_000_000_000;
+
+        // Only the Eth RPC lags, the clock is fine
+        drift.record(PipelineChain::Sui, now - 1000, now, &metrics);
+        drift.record(PipelineChain::Eth, now - 10 * MINUTE_MS, now, &metrics);
+        assert!(drift.is_drifting(PipelineChain::Eth));
+        assert!(!drift.is_drifting(PipelineChain::Sui));
+        assert!(!drift.is_clock_off());
+        let health = ClockHealth::from(&drift);
+        assert_eq!(health.status, PipelineStatus::Degraded);
+        assert!(!health.refusing_signatures);
+        assert!(drift.check_signing().is_ok());
+
+        // Drifts in opposite directions don't agree either
+        drift.record(PipelineChain::Sui, now + 10 * MINUTE_MS, now, &metrics);
+        assert!(!drift.is_clock_off());
+
+        // The local clock is behind both chains
+        drift.record(PipelineChain::Eth, now + 2 * MINUTE_MS, now, &metrics);
+        assert!(drift.is_clock_off());
+        assert!(drift.check_signing().is_err());
+
+        // Signing goes on unless refusing is configured
+        drift.configure(ClockDriftSettings {
+            max_drift_ms: MINUTE_MS,
+            refuse_signing: false,
+        });
+        assert!(drift.is_clock_off());
+        assert!(drift.check_signing().is_ok());
+        assert!(!ClockHealth::from(&drift).refusing_signatures);
+    }
+}
@@ -2,39 +2,83 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::abi::EthBridgeConfig;
-use crate::crypto::BridgeAuthorityKeyPair;
-use crate::error::BridgeError;
-use crate::eth_client::EthClient;
+use crate::adaptive_interval::{
+    AdaptiveIntervalBounds, DEFAULT_BURST_EVENTS, DEFAULT_EMPTY_POLLS_BEFORE_GROWTH,
+};
+use crate::alerts::AlertCondition;
+use crate::api_types::decode_action_digest;
+#[cfg(feature = "archive-s3")]
+use crate::archive::S3ArchiveStore;
+use crate::archive::{ActionArchive, ArchiveStore, FileArchiveStore};
+use crate::client::bridge_client::CommitteeConnectionOptions;
+use crate::client::dns_refresh::DEFAULT_COMMITTEE_DNS_REFRESH_INTERVAL;
+use crate::client::hedging::HedgingOptions;
+use crate::clock_drift::{
+    ClockDriftSettings, DEFAULT_CLOCK_DRIFT_CHECK_INTERVAL, DEFAULT_MAX_CLOCK_DRIFT_MS,
+};
+use crate::crypto::{
+    BridgeAuthorityKeyPair, BridgeAuthorityPublicKey, BridgeAuthorityPublicKeyBytes,
+};
+use crate::eth_client::{EthClient, EthFinality};
+use crate::eth_receipt_proof::EthReceiptVerifier;
 use crate::metered_eth_provider::new_metered_eth_provider;
 use crate::metered_eth_provider::MeteredEthHttpProvier;
 use crate::metrics::BridgeMetrics;
-use crate::sui_client::SuiClient;
-use crate::types::{is_route_valid, BridgeAction};
+use crate::orchestrator::DEFAULT_ONCHAIN_STATUS_BATCH_SIZE;
+use crate::provider_versions::endpoint_label;
+use crate::server::admin_auth::{AdminAuthorizer, AdminGrant, AdminRole};
+use crate::server::auth::{RequestAuthenticator, DEFAULT_MAX_CLOCK_SKEW};
+use crate::server::governance_verifier::GovernanceVerifier;
+use crate::server::handler::VerificationLimits;
+use crate::server::listener::ListenConfig;
+use crate::server::requester::DEFAULT_MAX_REQUESTER_LABELS;
+use crate::server::runtime::DEFAULT_SERVER_RUNTIME_WORKER_THREADS;
+use crate::server::timing::DEFAULT_SLOW_REQUEST_THRESHOLD;
+use crate::server::DEFAULT_GOVERNANCE_BODY_LIMIT;
+use crate::signature_inclusion::DEFAULT_MIN_SIGNATURE_INCLUSION_PERCENT;
+use crate::signer::RemoteSignerSettings;
+use crate::storage::DEFAULT_CURSOR_FLUSH_INTERVAL;
+use crate::storage_encryption::{FileStorageKeyProvider, StorageKeyProvider};
+use crate::storage_maintenance::MaintenanceSchedule;
+use crate::sui_client::{SuiClient, SuiClientInner};
+use crate::sui_rpc_retry::DEFAULT_SUI_RPC_MAX_ATTEMPTS;
+use crate::timestamps::{
+    TimestampSanitizer, DEFAULT_MAX_TIMESTAMP_AGE, DEFAULT_MAX_TIMESTAMP_SKEW,
+};
+use crate::tx_submission::TxSubmissionStrategy;
+use crate::types::{
+    is_reserved_evm_chain_id, is_route_valid, reserved_evm_chain_id, BridgeAction, BridgeActionType,
+};
 use crate::utils::get_eth_contract_addresses;
 use anyhow::anyhow;
-use ethers::providers::Middleware;
+use ethers::providers::{JsonRpcClient, Middleware};
 use ethers::types::Address as EthAddress;
+use fastcrypto::ed25519::Ed25519PublicKey;
+use fastcrypto::encoding::{Base64, Encoding};
+use fastcrypto::traits::ToFromBytes;
 use futures::{future, StreamExt};
 use serde::{Deserialize, Serialize};
 use serde_with::serde_as;
-use std::collections::HashSet;
+use std::collections::{BTreeMap, HashSet};
+use std::net::SocketAddr;
 use std::path::PathBuf;
 use std::str::FromStr;
 use std::sync::Arc;
+use std::time::Duration;
 use sui_config::Config;
 use sui_json_rpc_types::Coin;
 use sui_keys::keypair_file::read_key;
 use sui_sdk::apis::CoinReadApi;
 use sui_sdk::{SuiClient as SuiSdkClient, SuiClientBuilder};
 use sui_types::base_types::ObjectRef;
-use sui_types::base_types::{ObjectID, SuiAddress};
+use sui_types::base_types::{ConciseableName, ObjectID, SuiAddress};
 use sui_types::bridge::BridgeChainId;
 use sui_types::crypto::KeypairTraits;
 use sui_types::crypto::{get_key_pair_from_rng, NetworkKeyPair, SuiKeyPair};
 use sui_types::digests::{get_mainnet_chain_identifier, get_testnet_chain_identifier};
 use sui_types::event::EventID;
 use sui_types::object::Owner;
-use tracing::info;
+use tracing::{info, warn};
 
 #[serde_as]
 #[derive(Clone, Debug, Deserialize, Serialize)]
@@ -61,6 +105,131 @@ pub struct EthConfig {
     /// reprocess the events from this block number every time it starts.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub eth_contracts_start_block_override: Option<u64>,
+    /// Number of blocks an Eth deposit needs to be past the last finalized block
+    /// before the server signs it. Requests for deposits within the margin fail with
+    /// `TxNotFinalized` and can be retried. Defaults to 0, namely signing once the
+    /// deposit is finalized.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub extra_eth_confirmations: Option<u64>,
+    /// EVM chains other than Eth mainnet and Sepolia, e.g. private devnets, by their
+    /// chain id (as returned by `eth_chainId`). They are all bridged with the `EthCustom`
+    /// bridge chain id, the only one the bridge contracts accept for them. When this is
+    /// set, a node with `EthCustom` only connects to the listed chains.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub custom_evm_chains: Option<BTreeMap<u64, CustomEvmChainConfig>>,
+    /// Interval of the samples of the Eth fee market, see `eth_fee_history`. The fee
+    /// market is not sampled when this is not set.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fee_history_interval_seconds: Option<u64>,
+    /// Verify the deposits the server signs against the receipts root of their block,
+    /// see `eth_receipt_proof`. For nodes using an Eth provider they don't trust, e.g. a
+    /// free public RPC. Requires `eth_receipts_root_rpc_url`. Defaults to false.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub verify_eth_receipts: Option<bool>,
+    /// Rpc url of an Eth fullnode independent of `eth_rpc_url`, where the block headers
+    /// are fetched from when `verify_eth_receipts` is set.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub eth_receipts_root_rpc_url: Option<String>,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct CustomEvmChainConfig {
+    /// The proxy address of SuiBridge on this chain. `eth_bridge_proxy_address` must be
+    /// the same when connected to this chain.
+    pub bridge_proxy_address: String,
+    /// How Eth blocks are considered final on this chain. Defaults to the `finalized`
+    /// block tag.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub finality: Option<EthFinality>,
+}
+
+impl EthConfig {
+    /// Returns the url of the independent provider the receipts are verified with, None
+    /// if they are not verified.
+    pub fn receipts_root_rpc_url(&self) -> anyhow::Result<Option<&str>> {
+        if !self.verify_eth_receipts.unwrap_or(false) {
+            return Ok(None);
+        }
+        let Some(url) = &self.eth_receipts_root_rpc_url else {
+            anyhow::bail!("`verify_eth_receipts` requires `eth_receipts_root_rpc_url`");
+        };
+        if *url == self.eth_rpc_url {
+            anyhow::bail!(
+                "`eth_receipts_root_rpc_url` must be another provider than `eth_rpc_url`"
+            );
+        }
+        Ok(Some(url))
+    }
+
+    /// Checks that the custom EVM chains don't use the chain ids of the chains that have
+    /// their own bridge chain id, and only come with `EthCustom`.
+    pub fn validate_custom_evm_chains(&self) -> anyhow::Result<()> {
+        let Some(custom_evm_chains) = &self.custom_evm_chains else {
+            return Ok(());
+        };
+        if self.eth_bridge_chain_id != BridgeChainId::EthCustom as u8 {
+            anyhow::bail!(
+                "`custom_evm_chains` requires `eth_bridge_chain_id` {} (EthCustom), got {}",
+                BridgeChainId::EthCustom as u8,
+                self.eth_bridge_chain_id
+            );
+        }
+        for (chain_id, chain) in custom_evm_chains {
+            if is_reserved_evm_chain_id(*chain_id) {
+                anyhow::bail!(
+                    "Eth chain id {} is reserved for its own bridge chain id, it can't be a custom EVM chain",
+                    chain_id
+                );
+            }
+            EthAddress::from_str(&chain.bridge_proxy_address).map_err(|e| {
+                anyhow!(
+                    "Invalid bridge proxy address of custom EVM chain {}: {:?}",
+                    chain_id,
+                    e
+                )
+            })?;
+        }
+        Ok(())
+    }
+
+    /// Checks that the Eth fullnode serves the chain expected for `eth_bridge_chain_id`,
+    /// and returns how its blocks are considered final.
+    pub fn check_evm_chain_id(
+        &self,
+        chain_id: u64,
+        bridge_proxy_address: EthAddress,
+    ) -> anyhow::Result<EthFinality> {
+        let bridge_chain_id = BridgeChainId::try_from(self.eth_bridge_chain_id)?;
+        if let Some(expected_chain_id) = reserved_evm_chain_id(bridge_chain_id) {
+            if chain_id != expected_chain_id {
+                anyhow::bail!(
+                    "Expected Eth chain id {}, but connected to {}",
+                    expected_chain_id,
+                    chain_id
+                );
+            }
+            return Ok(EthFinality::default());
+        }
+        let Some(custom_evm_chains) = &self.custom_evm_chains else {
+            return Ok(EthFinality::default());
+        };
+        let chain = custom_evm_chains.get(&chain_id).ok_or_else(|| {
+            anyhow!(
+                "Connected to Eth chain id {}, which is not in `custom_evm_chains`",
+                chain_id
+            )
+        })?;
+        if EthAddress::from_str(&chain.bridge_proxy_address)? != bridge_proxy_address {
+            anyhow::bail!(
+                "`eth_bridge_proxy_address` {:?} is not the bridge proxy address {} of custom EVM chain {}",
+                bridge_proxy_address,
+                chain.bridge_proxy_address,
+                chain_id
+            );
+        }
+        Ok(chain.finality.unwrap_or_default())
+    }
 }
 
 #[serde_as]
@@ -90,6 +259,29 @@ pub struct SuiConfig {
     /// Otherwise, it will miss one event because of fullnode Event query semantics.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub sui_bridge_module_last_processed_event_id_override: Option<EventID>,
+    /// Bridge summary, committee and token config are cached for a short time, and
+    /// refreshed when events changing them are observed. Set this to always read them
+    /// from the fullnode, e.g. for debugging.
+    #[serde(default)]
+    pub disable_object_cache: bool,
+    /// Reads from the fullnode failing with transient errors, e.g. rate limiting, are
+    /// retried with backoff up to this many attempts in total. Defaults to 5.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sui_rpc_max_attempts: Option<u32>,
+    /// How the client submits its transactions. By default they are submitted through
+    /// `sui_rpc_url` only.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tx_submission: Option<TxSubmissionConfig>,
+}
+
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct TxSubmissionConfig {
+    pub strategy: TxSubmissionStrategy,
+    /// Fullnodes that transactions are submitted to in addition to `sui_rpc_url`.
+    /// Required by the `concurrent` strategy.
+    #[serde(default)]
+    pub fullnode_urls: Vec<String>,
 }
 
 #[serde_as]
@@ -98,18 +290,215 @@ pub struct SuiConfig {
 pub struct BridgeNodeConfig {
     /// The port that the server listens on.
     pub server_listen_port: u16,
+    /// Hostname, IPv4 or IPv6 address that the server listens on, IPv6 addresses with or
+    /// without brackets. A hostname must resolve to a single address. Defaults to 0.0.0.0.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub server_listen_address: Option<String>,
+    /// Accept IPv4 connections too when `server_listen_address` is `::`.
+    #[serde(default)]
+    pub server_dual_stack: bool,
+    /// Bind the server with `SO_REUSEPORT`, so that a new node process can listen before
+    /// the old one exits. Unix only.
+    #[serde(default)]
+    pub server_reuse_port: bool,
+    /// Run the signing server on its own tokio runtime, so that signing requests are
+    /// served promptly while the client catches up with a backlog. Committee mode only.
+    #[serde(default)]
+    pub server_dedicated_runtime: bool,
+    /// Number of worker threads of the runtime of the server when
+    /// `server_dedicated_runtime` is set. Defaults to 2.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub server_runtime_worker_threads: Option<usize>,
     /// The port that for metrics server.
     pub metrics_port: u16,
+    /// `observer` runs the node without a bridge authority key. It follows both chains
+    /// and tracks transfers until they are approved, but never signs or submits
+    /// anything. Defaults to `committee`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub mode: Option<BridgeNodeMode>,
     /// Path of the file where bridge authority key (Secp256k1) is stored.
-    pub bridge_authority_key_path: PathBuf,
+    /// Required unless `mode` is `observer` or `remote_signer` is set.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub bridge_authority_key_path: Option<PathBuf>,
+    /// The node signs through a remote signer service that holds the bridge authority
+    /// key, instead of with `bridge_authority_key_path`, when this is set.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub remote_signer: Option<RemoteSignerConfig>,
     /// Whether to run client. If true, `sui.bridge_client_key_path`
     /// and `db_path` needs to be provided.
     pub run_client: bool,
     /// Path of the client storage. Required when `run_client` is true.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub db_path: Option<PathBuf>,
+    /// Path of the client storage for history that grows over time, e.g. executed token
+    /// transfers, so it can be kept on a different disk. Kept in `db_path` when not set.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub aux_db_path: Option<PathBuf>,
+    /// Path of the file with the key the client storage is encrypted with, see
+    /// `storage_encryption`. New dbs are encrypted when set, existing plaintext ones must
+    /// be encrypted first with `sui-bridge-cli storage encrypt-in-place`. The node fails to
+    /// start if the key can't be read, or if the storage is encrypted and this is not set.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub storage_encryption_key_path: Option<PathBuf>,
+    /// Check the invariants between the client tables when the node starts, and report
+    /// the broken ones in `bridge_storage_integrity_violations`. Nothing is repaired, see
+    /// `sui-bridge-cli storage verify`. Also set by `--verify-storage`.
+    #[serde(default)]
+    pub verify_storage: bool,
+    /// Client buffers the cursor updates of the syncers and writes the latest cursor of
+    /// each at this interval, in milliseconds. A crash loses the updates of at most one
+    /// interval, which are read again. Defaults to 1000, 0 writes every update.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub storage_cursor_flush_ms: Option<u64>,
+    /// Client drops token transfers worth less than this USD value (with 8 decimal
+    /// places, same as on-chain token prices) instead of executing them. The minimum
+    /// amount of each token is derived from its prices on both Sui and Eth.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub min_transfer_usd_value: Option<u64>,
+    /// Client journals every Sui transaction it submits when this is set.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tx_journal: Option<TxJournalConfig>,
+    /// Client periodically reconciles its executed token transfers with the chain
+    /// when this is set.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reconciliation: Option<ReconciliationConfig>,
+    /// Client tracks whether the token transfers from Sui it approved are claimed on Eth
+    /// when this is set.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub claim_tracking: Option<ClaimTrackingConfig>,
+    /// Capacities of the channels between client components, and how long syncers wait
+    /// on a full channel. Defaults are used when this is not set.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub channels: Option<ChannelConfig>,
+    /// Syncers poll at intervals that follow the activity of each chain when this is set.
+    /// Otherwise Sui is polled every 2 seconds and Eth every 5 seconds.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub adaptive_polling: Option<AdaptivePollingConfig>,
+    /// Funding address that pays SUI to the client when the balance of its gas coin
+    /// drops below a threshold. Without it, the gas coin is only topped up by hand.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub gas_funding: Option<GasFundingConfig>,
+    /// Client posts alerts for critical conditions to a webhook when this is set.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub alerts: Option<AlertsConfig>,
+    /// Tolerances of the sanity checks of Eth block and Sui checkpoint timestamps.
+    /// Defaults are used when this is not set.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub timestamps: Option<TimestampConfig>,
+    /// How the local clock is compared with the chains, and whether signing is refused
+    /// while it's off. Defaults are used when this is not set.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub clock_drift: Option<ClockDriftConfig>,
+    /// Client streams observed actions, their status transitions and heartbeats to local
+    /// consumers when this is set.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub event_stream: Option<EventStreamConfig>,
+    /// Client periodically verifies quarantined actions against the chain again, and
+    /// releases the ones that are valid now, when this is set. Operators can always do it
+    /// through `/admin/quarantine/requeue_matching`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub quarantine_recheck: Option<QuarantineRecheckConfig>,
+    /// Number of token transfers whose on-chain status is queried in one request, when
+    /// the client recovers its pending actions and reconciles with the chain. Defaults
+    /// to 50.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub onchain_status_batch_size: Option<usize>,
+    /// Client periodically deletes its executed token transfers but the newest ones of
+    /// each route when this is set, archiving them first if an archive is configured.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub pruning: Option<PruningConfig>,
+    /// Client compacts its large tables and backs up its storage on a schedule when this
+    /// is set. Operators can do both through `/admin/storage/compact` and
+    /// `/admin/storage/backup` then.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub storage_maintenance: Option<StorageMaintenanceConfig>,
+    /// Client warns when the signature of this node is in less than this percentage of
+    /// the latest certificates that approved token transfers on Sui. Committee mode only,
+    /// defaults to 50.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub min_signature_inclusion_percent: Option<u8>,
+    /// Client skips the signature aggregation of token transfers on routes paused on
+    /// chain, i.e. whose transfer limit is zero. Their execution is deferred until the
+    /// route is no longer paused either way, but other members may still execute them.
+    #[serde(default)]
+    pub skip_signing_paused_routes: bool,
+    /// Client resolves the hosts of the committee members at this interval, and drops the
+    /// connections to a member whose host resolves to other addresses. Defaults to 60
+    /// seconds, 0 disables it.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub committee_dns_refresh_seconds: Option<u64>,
+    /// Keepalive and HTTP version of the connections of the client to the committee
+    /// members. Defaults are used when this is not set.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub committee_connections: Option<CommitteeConnectionsConfig>,
+    /// Client sends a signing request again to a committee member that is slow to respond,
+    /// and takes the first response, when this is set. See `client::hedging`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub committee_request_hedging: Option<CommitteeRequestHedgingConfig>,
+    /// Node refuses to start when committee members registered the same url. By default
+    /// they are only reported, and requested once, crediting the member whose key signs.
+    #[serde(default)]
+    pub refuse_committee_anomalies: bool,
     /// A list of approved governance actions. Action in this list will be signed when requested by client.
     pub approved_governance_actions: Vec<BridgeAction>,
+    /// Hex encoded digests of governance actions that will also be signed when requested by
+    /// client, as printed by `sui-bridge-cli`. An action can be approved either way, not both.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub approved_governance_digests: Vec<String>,
+    /// Action types the server signs. Signing requests for other types are rejected.
+    /// All types are signed when this is not set.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sign_action_types: Option<Vec<BridgeActionType>>,
+    /// Action types the client executes. Actions of other types are dropped.
+    /// All types are executed when this is not set.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub execute_action_types: Option<Vec<BridgeActionType>>,
+    /// Server only serves signing requests authenticated by an allowed key when this
+    /// is set. Otherwise anyone can request signatures.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub request_auth: Option<RequestAuthConfig>,
+    /// Callers of the routes under `/admin/` must be granted a role when this is set:
+    /// `reader` for the GET routes, `operator` for the mutations. Otherwise reads are
+    /// open, and the mutations are not served. Either way the admin routes are only
+    /// served to callers on the loopback interface, e.g. through a TLS terminating proxy
+    /// on the same host.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub admin_auth: Option<AdminAuthConfig>,
+    /// Signing requests slower than this are logged with the time spent in each stage.
+    /// Defaults to 2 seconds.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub slow_request_threshold_ms: Option<u64>,
+    /// Largest request body, in bytes, accepted by the governance signing routes, e.g. the
+    /// call data of an EVM contract upgrade. Defaults to 1 MiB.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub governance_body_limit_bytes: Option<usize>,
+    /// Number of requesters that get their own label in `bridge_requests_received_by_requester`
+    /// and `bridge_err_requests_by_requester`, others are counted as `other`. A requester
+    /// without requests in the last 5 minutes gives up its label. Defaults to 50.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_requester_labels: Option<usize>,
+    /// Address that the gRPC variant of the signing API listens on. It's not served
+    /// when this is not set. Requires the `grpc` feature.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub grpc_listen_address: Option<SocketAddr>,
+    /// Url that other committee members reach this node at. It should be the url
+    /// registered on chain for the bridge authority key, a difference is reported in
+    /// `bridge_url_mismatch`, `/health` and `doctor`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub external_url: Option<String>,
+    /// Before serving signing requests, the server verifies and signs the latest
+    /// token transfers so that their signatures are requested from a warm cache.
+    /// Not done when this is not set.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub warm_up: Option<WarmUpConfig>,
+    /// Bounds on the signing requests verified at once against each chain. Defaults to
+    /// 16 per chain, with requests waiting up to 5 seconds for a slot.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub verification_limits: Option<VerificationLimitsConfig>,
+    /// Format, level and output of the logs. When not set, logging is configured by the
+    /// `RUST_LOG`, `RUST_LOG_JSON` and `RUST_LOG_FILE` environment variables.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub logging: Option<LoggingConfig>,
     /// Sui configuration
     pub sui: SuiConfig,
     /// Eth configuration
@@ -119,6 +508,29 @@ pub struct BridgeNodeConfig {
     pub metrics_key_pair: NetworkKeyPair,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub metrics: Option<MetricsConfig>,
+    /// Opt-in summary of the health of the node, periodically sent to the bridge team.
+    /// Nothing is sent unless `telemetry.enabled` is set, see `telemetry`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub telemetry: Option<TelemetryConfig>,
+    /// Starts the node even if the Sui and Eth bridge contracts are not set up to bridge
+    /// between the configured chains. Set with `--i-know-what-i-am-doing`.
+    #[serde(skip)]
+    pub allow_mismatched_bridge_contracts: bool,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum BridgeNodeMode {
+    /// A committee member, signing actions and executing them when the client runs.
+    #[default]
+    Committee,
+    /// Follows both chains without a committee key. Nothing is signed or submitted.
+    Observer,
+    /// Runs the client of a committee member without effects, e.g. to try a new release
+    /// against the production chains. Actions are signed by the committee and built into
+    /// transactions, which are compared with the chain instead of being submitted.
+    /// Signing requests are rejected.
+    Shadow,
 }
 
 pub fn default_ed25519_key_pair() -> NetworkKeyPair {
@@ -133,13 +545,781 @@ pub struct MetricsConfig {
     pub push_url: String,
 }
 
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct TelemetryConfig {
+    /// Nothing is sent unless this is set.
+    #[serde(default)]
+    pub enabled: bool,
+    /// Where the summary is posted, with the TLS identity of `metrics-key-pair`.
+    pub endpoint: String,
+    /// Defaults to 6 hours.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub interval_seconds: Option<u64>,
+    /// Logs every summary before it is sent, to see exactly what leaves the node.
+    #[serde(default)]
+    pub log_payload: bool,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct TxJournalConfig {
+    /// Directory of the journal files.
+    pub path: PathBuf,
+    /// A new journal file is started when the current one would exceed this size.
+    /// Defaults to 64MB.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_file_size_bytes: Option<u64>,
+    /// If true, client stops executing transactions when one can't be journaled.
+    /// Otherwise the failure is only logged and counted.
+    #[serde(default)]
+    pub fatal_on_failure: bool,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct RequestAuthConfig {
+    /// Base64 encoded Ed25519 public keys of the callers allowed to request signatures,
+    /// e.g. known relayers and aggregators, and to reset the syncer cursors.
+    pub allowed_keys: Vec<String>,
+    /// Maximum difference between the timestamp of a request and the server clock.
+    /// Defaults to 30.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_clock_skew_seconds: Option<u64>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct AdminAuthConfig {
+    pub callers: Vec<AdminCallerConfig>,
+}
+
+/// A caller of the admin routes, identified by its token.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct AdminCallerConfig {
+    /// Name of the caller in the logs and in the audit log of the mutations
+    pub name: String,
+    pub role: AdminRole,
+    /// Sent by the caller as `Authorization: Bearer <token>`
+    pub token: String,
+}
+
+impl AdminCallerConfig {
+    fn grant(&self) -> anyhow::Result<AdminGrant> {
+        if self.token.trim().is_empty() {
+            return Err(anyhow!("Admin caller {} has an empty `token`", self.name));
+        }
+        Ok(AdminGrant {
+            name: self.name.clone(),
+            role: self.role,
+            token: self.token.trim().to_string(),
+        })
+    }
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct WarmUpConfig {
+    /// Number of the latest token transfers warmed up in each direction. Defaults to 100.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub transfers_per_direction: Option<u64>,
+    /// The server starts serving after this many seconds even if the warm-up has not
+    /// finished. Defaults to 30.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub timeout_seconds: Option<u64>,
+}
+
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct VerificationLimitsConfig {
+    /// Eth deposits verified at once, others wait for a slot
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_concurrent_eth_verifications: Option<usize>,
+    /// Sui deposits verified at once, others wait for a slot
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_concurrent_sui_verifications: Option<usize>,
+    /// Requests that waited this long for a slot are rejected with 429, so that the
+    /// requester retries later or asks other members
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub queue_timeout_ms: Option<u64>,
+    /// Token transfers this far ahead of the next nonce of their source chain are refused
+    /// before they are verified further. Must cover the transfers emitted between two
+    /// refreshes of the nonces, defaults to 10000.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_nonce_lead: Option<u64>,
+}
+
+impl VerificationLimitsConfig {
+    pub fn limits(&self) -> anyhow::Result<VerificationLimits> {
+        let defaults = VerificationLimits::default();
+        let limits = VerificationLimits {
+            max_concurrent_eth_verifications: self
+                .max_concurrent_eth_verifications
+                .unwrap_or(defaults.max_concurrent_eth_verifications),
+            max_concurrent_sui_verifications: self
+                .max_concurrent_sui_verifications
+                .unwrap_or(defaults.max_concurrent_sui_verifications),
+            queue_timeout: self
+                .queue_timeout_ms
+                .map(Duration::from_millis)
+                .unwrap_or(defaults.queue_timeout),
+            max_nonce_lead: self.max_nonce_lead.unwrap_or(defaults.max_nonce_lead),
+        };
+        if limits.max_concurrent_eth_verifications == 0
+            || limits.max_concurrent_sui_verifications == 0
+        {
+            return Err(anyhow!(
+                "`verification_limits` must allow at least one verification per chain"
+            ));
+        }
+        if limits.max_nonce_lead == 0 {
+            return Err(anyhow!(
+                "`max_nonce_lead` of `verification_limits` must be positive"
+            ));
+        }
+        Ok(limits)
+    }
+}
+
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct CommitteeConnectionsConfig {
+    /// Seconds a connection is idle before TCP keepalive probes are sent. Defaults to 30,
+    /// 0 disables them.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tcp_keepalive_seconds: Option<u64>,
+    /// Seconds between the pings of HTTP/2 connections. Defaults to 20, 0 disables them.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub http2_keepalive_interval_seconds: Option<u64>,
+    /// Connections whose ping is not answered within this many seconds are closed.
+    /// Defaults to 10.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub http2_keepalive_timeout_seconds: Option<u64>,
+    /// Members reached over plain http are spoken HTTP/2 to without negotiating it. Over
+    /// https it's always negotiated.
+    #[serde(default)]
+    pub http2_prior_knowledge: bool,
+    /// Hex encoded public keys of the members only spoken HTTP/1.1 to, e.g. whose servers
+    /// misbehave on HTTP/2.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub http1_only_members: Vec<String>,
+}
+
+impl CommitteeConnectionsConfig {
+    pub fn options(&self) -> anyhow::Result<CommitteeConnectionOptions> {
+        let defaults = CommitteeConnectionOptions::default();
+        let seconds = |value: Option<u64>, default: Option<Duration>| match value {
+            Some(0) => None,
+            Some(seconds) => Some(Duration::from_secs(seconds)),
+            None => default,
+        };
+        Ok(CommitteeConnectionOptions {
+            tcp_keepalive: seconds(self.tcp_keepalive_seconds, defaults.tcp_keepalive),
+            http2_keep_alive_interval: seconds(
+                self.http2_keepalive_interval_seconds,
+                defaults.http2_keep_alive_interval,
+            ),
+            http2_keep_alive_timeout: self
+                .http2_keepalive_timeout_seconds
+                .map(Duration::from_secs)
+                .unwrap_or(defaults.http2_keep_alive_timeout),
+            http2_prior_knowledge: self.http2_prior_knowledge,
+            http1_only_members: self
+                .http1_only_members
+                .iter()
+                .map(|member| {
+                    BridgeAuthorityPublicKeyBytes::from_str(member).map_err(|e| {
+                        anyhow!(
+                            "Invalid member {:?} in `committee_connections.http1-only-members`: {:?}",
+                            member,
+                            e
+                        )
+                    })
+                })
+                .collect::<anyhow::Result<_>>()?,
+        })
+    }
+}
+
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct CommitteeRequestHedgingConfig {
+    /// A signing request to a member is sent again when the member hasn't responded
+    /// within its moving average latency times this, in percent. Defaults to 200.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub latency_factor_percent: Option<u64>,
+    /// Lower bound of the hedging delay. Defaults to 200.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub min_delay_ms: Option<u64>,
+    /// Upper bound of the hedging delay, and the delay of the members without a latency
+    /// yet. Defaults to 2000.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_delay_ms: Option<u64>,
+    /// No more hedges are sent while this many are in flight. Defaults to 16.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_in_flight: Option<usize>,
+}
+
+impl CommitteeRequestHedgingConfig {
+    pub fn options(&self) -> anyhow::Result<HedgingOptions> {
+        let defaults = HedgingOptions::default();
+        let options = HedgingOptions {
+            min_delay: self
+                .min_delay_ms
+                .map(Duration::from_millis)
+                .unwrap_or(defaults.min_delay),
+            max_delay: self
+                .max_delay_ms
+                .map(Duration::from_millis)
+                .unwrap_or(defaults.max_delay),
+            latency_factor_percent: self
+                .latency_factor_percent
+                .unwrap_or(defaults.latency_factor_percent),
+            max_in_flight: self.max_in_flight.unwrap_or(defaults.max_in_flight),
+        };
+        if options.min_delay > options.max_delay {
+            anyhow::bail!(
+                "`committee_request_hedging.min-delay-ms` must not be above `max-delay-ms`"
+            );
+        }
+        if options.max_in_flight == 0 {
+            anyhow::bail!("`committee_request_hedging.max-in-flight` must be positive");
+        }
+        Ok(options)
+    }
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct RemoteSignerConfig {
+    /// Url that the digests of the verified actions are posted to, see `signer`.
+    pub url: String,
+    /// Public key of the bridge authority that the signer signs with, hex encoded. The
+    /// returned signatures are verified with it.
+    pub authority_public_key: String,
+    /// PEM file with the certificate and private key that the node authenticates with.
+    pub client_identity_path: PathBuf,
+    /// PEM file with the CA certificates that the certificate of the signer is verified
+    /// with. The system roots are not trusted.
+    pub ca_certificate_path: PathBuf,
+    /// Timeout of each request to the signer. Defaults to 5000.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub timeout_ms: Option<u64>,
+    /// Attempts of a request when the signer is unreachable or unavailable. Defaults to 3.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_attempts: Option<u32>,
+    /// After this many consecutive failed requests, signing requests are refused as
+    /// retryable without reaching the signer for `circuit-open-seconds`. Defaults to 5.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub failure_threshold: Option<u32>,
+    /// Defaults to 30.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub circuit_open_seconds: Option<u64>,
+}
+
+impl RemoteSignerConfig {
+    pub fn settings(&self) -> RemoteSignerSettings {
+        let defaults = RemoteSignerSettings::default();
+        RemoteSignerSettings {
+            timeout: self
+                .timeout_ms
+                .map(Duration::from_millis)
+                .unwrap_or(defaults.timeout),
+            max_attempts: self.max_attempts.unwrap_or(defaults.max_attempts).max(1),
+            retry_delay: defaults.retry_delay,
+            failure_threshold: self
+                .failure_threshold
+                .unwrap_or(defaults.failure_threshold)
+                .max(1),
+            circuit_open_duration: self
+                .circuit_open_seconds
+                .map(Duration::from_secs)
+                .unwrap_or(defaults.circuit_open_duration),
+        }
+    }
+
+    pub fn authority_public_key(&self) -> anyhow::Result<BridgeAuthorityPublicKey> {
+        let bytes = BridgeAuthorityPublicKeyBytes::from_str(&self.authority_public_key)
+            .map_err(|e| anyhow!("Invalid `remote_signer.authority-public-key`: {:?}", e))?;
+        BridgeAuthorityPublicKey::from_bytes(bytes.as_ref())
+            .map_err(|e| anyhow!("Invalid `remote_signer.authority-public-key`: {:?}", e))
+    }
+}
+
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct LoggingConfig {
+    #[serde(default)]
+    pub format: LogFormat,
+    /// Level of the logs, e.g. `debug`. Defaults to `info`. `RUST_LOG` takes precedence
+    /// over it and `modules` when set. Applied again on SIGHUP.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub level: Option<String>,
+    /// Level by module, e.g. `sui_bridge::eth_syncer: debug`. Applied again on SIGHUP.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub modules: BTreeMap<String, String>,
+    /// Logs are written to this file instead of stderr when set.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub file: Option<LogFileConfig>,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum LogFormat {
+    #[default]
+    Text,
+    /// One JSON object per line
+    Json,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct LogFileConfig {
+    pub path: PathBuf,
+    /// The file is rotated once it would exceed this size. Defaults to 100MB.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_file_size_bytes: Option<u64>,
+    /// Number of rotated files kept besides the current one, the oldest are deleted.
+    /// Defaults to 10.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub retained_files: Option<usize>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct ReconciliationConfig {
+    /// Seconds between two rounds of reconciliation. Defaults to 600.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub interval_seconds: Option<u64>,
+    /// Number of most recent nonces checked per route in each round. Defaults to 100.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sample_size: Option<u64>,
+    /// Upper bound of Sui and Eth RPC queries per second made by reconciliation.
+    /// Defaults to 5.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_rpc_queries_per_second: Option<u32>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct ClaimTrackingConfig {
+    /// Seconds between two polls of the claim status. Defaults to 60.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub interval_seconds: Option<u64>,
+    /// Maximum seconds between two checks of a transfer that stays unclaimed. The interval
+    /// doubles each time the transfer is found unclaimed. Defaults to 21600 (6 hours).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_backoff_seconds: Option<u64>,
+    /// Upper bound of Eth RPC queries per second made by claim tracking. Defaults to 5.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_rpc_queries_per_second: Option<u32>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct QuarantineRecheckConfig {
+    /// Seconds between two passes. Defaults to 21600 (6 hours).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub interval_seconds: Option<u64>,
+    /// Number of quarantined actions verified in each pass, the least recently verified
+    /// first. Defaults to 20.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_actions_per_pass: Option<usize>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct PruningConfig {
+    /// Seconds between two passes. Defaults to 3600.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub interval_seconds: Option<u64>,
+    /// Number of the newest executed token transfers of each route that are kept.
+    /// Defaults to 100000.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub retain_executed_actions: Option<u64>,
+    /// Number of executed token transfers archived and deleted together. Defaults to 1000.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub batch_size: Option<usize>,
+    /// Executed token transfers are archived before they are deleted when this is set,
+    /// and a batch that fails to be archived is kept. Otherwise they are deleted for good.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub archive: Option<ArchiveConfig>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct StorageMaintenanceConfig {
+    /// When the large tables are compacted, as a cron expression in UTC with the minute,
+    /// hour, day of month, month and day of week fields, e.g. `0 3 * * *`. Tables are not
+    /// compacted on a schedule when this is not set.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub compaction_schedule: Option<String>,
+    /// Tables whose files on disk are smaller than this are not compacted. Defaults to
+    /// 67108864 (64 MiB).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub compaction_min_table_bytes: Option<u64>,
+    /// When the storage is backed up, like `compaction-schedule`. The storage is not
+    /// backed up on a schedule when this is not set.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub backup_schedule: Option<String>,
+    /// Directory the backups are written to. Backups on the filesystem of the db are
+    /// hard links to its files. Required to back up the storage.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub backup_dir: Option<PathBuf>,
+    /// Number of the newest backups that are kept. Defaults to 3.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub retain_backups: Option<usize>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct ArchiveConfig {
+    /// Directory the archive is written to, e.g. on a mounted network volume.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub path: Option<PathBuf>,
+    /// Bucket the archive is written to. Requires the `archive-s3` feature. Exactly one of
+    /// `path` and `s3` must be set.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub s3: Option<S3ArchiveConfig>,
+    /// Prefix of the archived files, e.g. to share a bucket between nodes.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub prefix: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct S3ArchiveConfig {
+    pub bucket: String,
+    /// Endpoint of an S3 compatible service, e.g. `https://storage.googleapis.com` for
+    /// GCS. Defaults to AWS.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub endpoint: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub region: Option<String>,
+}
+
+impl ArchiveConfig {
+    /// Opens the archive. Credentials of a bucket are read from the environment, e.g.
+    /// `AWS_ACCESS_KEY_ID` and `AWS_SECRET_ACCESS_KEY`.
+    pub fn open(&self) -> anyhow::Result<ActionArchive> {
+        let store: Arc<dyn ArchiveStore> = match (&self.path, &self.s3) {
+            (Some(path), None) => Arc::new(FileArchiveStore::new(path.clone())),
+            #[cfg(feature = "archive-s3")]
+            (None, Some(s3)) => Arc::new(S3ArchiveStore::new(
+                &s3.bucket,
+                s3.endpoint.as_deref(),
+                s3.region.as_deref(),
+            )?),
+            #[cfg(not(feature = "archive-s3"))]
+            (None, Some(_)) => {
+                anyhow::bail!("Archiving to `s3` requires the `archive-s3` feature")
+            }
+            _ => anyhow::bail!("Exactly one of `path` and `s3` must be set for the archive"),
+        };
+        Ok(ActionArchive::new(store, self.prefix.as_deref()))
+    }
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct GasFundingConfig {
+    /// Path of the file where the funding key (any SuiKeyPair) is stored. Its address
+    /// must be different from the bridge client address.
+    pub funding_key_path: PathBuf,
+    /// The coin of the funding address that top-ups are paid from. If not set, the coin
+    /// with highest balance is used.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub funding_gas_object: Option<ObjectID>,
+    /// Gas coin is topped up when its balance is below this, in mist.
+    pub threshold_balance: u64,
+    /// Balance of the gas coin after a top-up, in mist.
+    pub target_balance: u64,
+    /// Seconds between two checks of the gas coin balance. Defaults to 60.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub check_interval_seconds: Option<u64>,
+    /// Minimum seconds between two top-ups. Defaults to 3600.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cooldown_seconds: Option<u64>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct AlertsConfig {
+    /// Url that alerts are posted to, as JSON with a Slack-compatible `text` field.
+    pub webhook_url: String,
+    /// Conditions to alert on. All conditions are alerted on when this is not set.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub conditions: Option<Vec<AlertCondition>>,
+    /// Minimum seconds between two alerts for the same condition. Defaults to 3600.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub renotify_interval_seconds: Option<u64>,
+    /// `low-gas-balance` is alerted on when the gas coin balance is below this, in mist.
+    /// Defaults to 1 SUI.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub low_gas_balance_mist: Option<u64>,
+}
+
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct EventStreamConfig {
+    /// Path of the unix domain socket that consumers connect to.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub socket_path: Option<PathBuf>,
+    /// Port on 127.0.0.1 that consumers connect to. Exactly one of `socket-path` and
+    /// `loopback-port` must be set.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub loopback_port: Option<u16>,
+    /// Number of events buffered per consumer. Events for a consumer with a full buffer
+    /// are dropped. Defaults to 1000.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub buffer_size: Option<usize>,
+    /// Seconds between two heartbeats of the components. Defaults to 10.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub heartbeat_interval_seconds: Option<u64>,
+}
+
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct TimestampConfig {
+    /// Seconds a chain timestamp may be ahead of the local clock before it is counted as
+    /// an anomaly. Defaults to 60.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_skew_seconds: Option<u64>,
+    /// Chain timestamps older than this many seconds are ignored. Defaults to 30 days.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_age_seconds: Option<u64>,
+}
+
+impl TimestampConfig {
+    pub fn sanitizer(&self) -> TimestampSanitizer {
+        TimestampSanitizer::new(
+            self.max_skew_seconds
+                .map(Duration::from_secs)
+                .unwrap_or(DEFAULT_MAX_TIMESTAMP_SKEW),
+            self.max_age_seconds
+                .map(Duration::from_secs)
+                .unwrap_or(DEFAULT_MAX_TIMESTAMP_AGE),
+        )
+    }
+}
+
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct ClockDriftConfig {
+    /// Seconds between two comparisons of the local clock with the latest Sui checkpoint
+    /// and Eth block. Defaults to 60.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub check_interval_seconds: Option<u64>,
+    /// Milliseconds the local clock may be off from a chain before it is reported.
+    /// Defaults to 60000.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_drift_ms: Option<u64>,
+    /// Refuse to sign while the local clock is off from every chain.
+    #[serde(default)]
+    pub refuse_signing: bool,
+}
+
+impl ClockDriftConfig {
+    pub fn settings(&self) -> ClockDriftSettings {
+        ClockDriftSettings {
+            max_drift_ms: self.max_drift_ms.unwrap_or(DEFAULT_MAX_CLOCK_DRIFT_MS),
+            refuse_signing: self.refuse_signing,
+        }
+    }
+
+    pub fn check_interval(&self) -> Duration {
+        self.check_interval_seconds
+            .map(Duration::from_secs)
+            .unwrap_or(DEFAULT_CLOCK_DRIFT_CHECK_INTERVAL)
+    }
+}
+
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct ChannelConfig {
+    /// Capacity of the channel from the Eth syncer to the orchestrator. Defaults to 1000.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub eth_events_channel_size: Option<usize>,
+    /// Capacity of the channel from the Sui syncer to the orchestrator. Defaults to 1000.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sui_events_channel_size: Option<usize>,
+    /// Capacity of the signing and execution queues of the executor. Defaults to 1000.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub executor_channel_size: Option<usize>,
+    /// Number of actions waiting for signature aggregation that the executor holds in
+    /// memory. Beyond that they are read from storage when they are due. Defaults to 1000.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub executor_signing_window: Option<usize>,
+    /// Seconds a syncer waits for room in a full events channel. After that the events
+    /// are dropped without moving the cursor, and are queried again later. Defaults to 30.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub events_send_timeout_seconds: Option<u64>,
+}
+
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct AdaptivePollingConfig {
+    /// Bounds of the interval between the event queries of each Sui module. It's fixed
+    /// when not set.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sui: Option<AdaptiveIntervalConfig>,
+    /// Bounds of the interval between the queries of the last finalized Eth block, after
+    /// which the events of the contracts are queried. It's fixed when not set.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub eth: Option<AdaptiveIntervalConfig>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct AdaptiveIntervalConfig {
+    pub min_interval_ms: u64,
+    pub max_interval_ms: u64,
+    /// Consecutive queries without events after which the interval doubles. Defaults to 3.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub empty_polls_before_growth: Option<u32>,
+    /// Events observed by a query that set the interval to the minimum at once. Defaults
+    /// to 10.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub burst_events: Option<usize>,
+}
+
+impl AdaptiveIntervalConfig {
+    pub fn bounds(&self) -> anyhow::Result<AdaptiveIntervalBounds> {
+        if self.min_interval_ms == 0 || self.min_interval_ms > self.max_interval_ms {
+            return Err(anyhow!(
+                "`min-interval-ms` of adaptive polling must be positive and at most `max-interval-ms`"
+            ));
+        }
+        if self.burst_events == Some(0) {
+            return Err(anyhow!(
+                "`burst-events` of adaptive polling must be positive"
+            ));
+        }
+        Ok(AdaptiveIntervalBounds {
+            min: Duration::from_millis(self.min_interval_ms),
+            max: Duration::from_millis(self.max_interval_ms),
+            empty_polls_before_growth: self
+                .empty_polls_before_growth
+                .unwrap_or(DEFAULT_EMPTY_POLLS_BEFORE_GROWTH),
+            burst_events: self.burst_events.unwrap_or(DEFAULT_BURST_EVENTS),
+        })
+    }
+}
+
 impl Config for BridgeNodeConfig {}
 
 impl BridgeNodeConfig {
+    pub fn mode(&self) -> BridgeNodeMode {
+        self.mode.unwrap_or_default()
+    }
+
+    /// Returns the key the client storage is encrypted with, None if it's not encrypted.
+    pub fn storage_key_provider(&self) -> anyhow::Result<Option<Arc<dyn StorageKeyProvider>>> {
+        self.storage_encryption_key_path
+            .as_deref()
+            .map(|path| {
+                FileStorageKeyProvider::read(path)
+                    .map(|key_provider| Arc::new(key_provider) as Arc<dyn StorageKeyProvider>)
+                    .map_err(|e| anyhow!("Storage encryption key is unavailable: {:?}", e))
+            })
+            .transpose()
+    }
+
+    // The node signs either with its key or through the remote signer
+    fn validate_signer(&self) -> anyhow::Result<()> {
+        match (&self.bridge_authority_key_path, &self.remote_signer) {
+            (None, None) => anyhow::bail!(
+                "`bridge_authority_key_path` or `remote_signer` is required unless `mode` is observer"
+            ),
+            (Some(_), Some(_)) => anyhow::bail!(
+                "Set only one of `bridge_authority_key_path` and `remote_signer`"
+            ),
+            _ => Ok(()),
+        }
+    }
+
+    /// Checks that the config fits its mode. An observer has no key to sign or submit
+    /// with, so the settings for signing and submitting are rejected rather than ignored.
+    pub fn validate_mode(&self) -> anyhow::Result<()> {
+        match self.mode() {
+            BridgeNodeMode::Committee => self.validate_signer()?,
+            BridgeNodeMode::Shadow => {
+                self.validate_signer()?;
+                // Gas top-ups are the only transactions not submitted by the executor
+                if self.gas_funding.is_some() {
+                    anyhow::bail!("Shadow mode does not submit transactions, remove gas_funding");
+                }
+                if !self.run_client {
+                    anyhow::bail!(
+                        "Shadow mode compares the client with the chains, `run_client` must be true"
+                    );
+                }
+            }
+            BridgeNodeMode::Observer => {
+                let signing_settings = [
+                    (
+                        "bridge_authority_key_path",
+                        self.bridge_authority_key_path.is_some(),
+                    ),
+                    ("remote_signer", self.remote_signer.is_some()),
+                    (
+                        "sui.bridge_client_key_path",
+                        self.sui.bridge_client_key_path.is_some(),
+                    ),
+                    (
+                        "sui.bridge_client_gas_object",
+                        self.sui.bridge_client_gas_object.is_some(),
+                    ),
+                    ("gas_funding", self.gas_funding.is_some()),
+                    ("tx_journal", self.tx_journal.is_some()),
+                    ("quarantine_recheck", self.quarantine_recheck.is_some()),
+                    (
+                        "clock_drift.refuse_signing",
+                        self.clock_drift
+                            .as_ref()
+                            .is_some_and(|config| config.refuse_signing),
+                    ),
+                    ("sui.tx_submission", self.sui.tx_submission.is_some()),
+                    (
+                        "approved_governance_actions",
+                        !self.approved_governance_actions.is_empty(),
+                    ),
+                    (
+                        "approved_governance_digests",
+                        !self.approved_governance_digests.is_empty(),
+                    ),
+                    ("sign_action_types", self.sign_action_types.is_some()),
+                    ("execute_action_types", self.execute_action_types.is_some()),
+                    ("request_auth", self.request_auth.is_some()),
+                    ("grpc_listen_address", self.grpc_listen_address.is_some()),
+                    ("external_url", self.external_url.is_some()),
+                    ("warm_up", self.warm_up.is_some()),
+                    ("verification_limits", self.verification_limits.is_some()),
+                ]
+                .into_iter()
+                .filter_map(|(name, is_set)| is_set.then_some(name))
+                .collect::<Vec<_>>();
+                if !signing_settings.is_empty() {
+                    anyhow::bail!(
+                        "Observer mode neither signs nor submits transactions, remove {}",
+                        signing_settings.join(", ")
+                    );
+                }
+                if !self.run_client {
+                    anyhow::bail!("Observer mode follows the chains with the client, `run_client` must be true");
+                }
+            }
+        }
+        Ok(())
+    }
+
     pub async fn validate(
         &self,
         metrics: Arc<BridgeMetrics>,
     ) -> anyhow::Result<(BridgeServerConfig, Option<BridgeClientConfig>)> {
+        self.validate_mode()?;
+        self.eth.validate_custom_evm_chains()?;
         if !is_route_valid(
             BridgeChainId::try_from(self.sui.sui_bridge_chain_id)?,
             BridgeChainId::try_from(self.eth.eth_bridge_chain_id)?,
@@ -151,25 +1331,65 @@ impl BridgeNodeConfig {
             ));
         };
 
-        let bridge_authority_key = match read_key(&self.bridge_authority_key_path, true)? {
-            SuiKeyPair::Secp256k1(key) => key,
-            _ => unreachable!("we required secp256k1 key in `read_key`"),
-        };
+        // Not set in observer mode
+        let bridge_authority_key = self
+            .bridge_authority_key_path
+            .as_ref()
+            .map(|path| match read_key(path, true)? {
+                SuiKeyPair::Secp256k1(key) => anyhow::Ok(key),
+                _ => unreachable!("we required secp256k1 key in `read_key`"),
+            })
+            .transpose()?;
 
         // we do this check here instead of `prepare_for_sui` below because
         // that is only called when `run_client` is true.
-        let sui_client = Arc::new(SuiClient::<SuiSdkClient>::new(&self.sui.sui_rpc_url).await?);
+        let mut sui_client = SuiClient::<SuiSdkClient>::new(&self.sui.sui_rpc_url)
+            .await?
+            .with_retry(
+                self.sui
+                    .sui_rpc_max_attempts
+                    .unwrap_or(DEFAULT_SUI_RPC_MAX_ATTEMPTS),
+                metrics.clone(),
+            );
+        if !self.sui.disable_object_cache {
+            sui_client = sui_client.with_object_cache(metrics.clone());
+        }
+        let sui_client = Arc::new(sui_client);
         let bridge_committee = sui_client
             .get_bridge_committee()
             .await
             .map_err(|e| anyhow!("Error getting bridge committee: {:?}", e))?;
-        if !bridge_committee.is_active_member(&bridge_authority_key.public().into()) {
+        if let Some(bridge_authority_key) = &bridge_authority_key {
+            if !bridge_committee.is_active_member(&bridge_authority_key.public().into()) {
+                return Err(anyhow!(
+                    "Bridge authority key is not part of bridge committee"
+                ));
+            }
+        }
+        if let Some(remote_signer) = &self.remote_signer {
+            if !bridge_committee.is_active_member(&(&remote_signer.authority_public_key()?).into())
+            {
+                return Err(anyhow!(
+                    "Public key of the remote signer is not part of bridge committee"
+                ));
+            }
+        }
+        let members_sharing_url = bridge_committee.members_sharing_url();
+        if self.refuse_committee_anomalies && !members_sharing_url.is_empty() {
             return Err(anyhow!(
-                "Bridge authority key is not part of bridge committee"
+                "Committee members registered the same url: {:?}",
+                members_sharing_url
+                    .iter()
+                    .map(|members| members
+                        .iter()
+                        .map(|member| member.concise_owned())
+                        .collect::<Vec<_>>())
+                    .collect::<Vec<_>>()
             ));
         }
 
-        let (eth_client, eth_contracts) = self.prepare_for_eth(metrics).await?;
+        let (eth_client, eth_contracts, eth_bridge_config_address) =
+            self.prepare_for_eth(metrics).await?;
         let bridge_summary = sui_client
             .get_bridge_summary()
             .await
@@ -181,48 +1401,314 @@ impl BridgeNodeConfig {
                 bridge_summary.chain_id
             );
         }
+        let contract_pair = BridgeContractPair::read(
+            &sui_client,
+            &eth_client,
+            eth_bridge_config_address,
+            self.sui.sui_bridge_chain_id,
+            self.eth.eth_bridge_chain_id,
+        )
+        .await?;
+        if let Err(e) = contract_pair.check() {
+            if !self.allow_mismatched_bridge_contracts {
+                return Err(e);
+            }
+            warn!(
+                "{}. Starting anyway as `--i-know-what-i-am-doing` is set",
+                e
+            );
+        }
+
+        // Validate approved actions that must be governace actions, approved once
+        let approved_governance_digests = self
+            .approved_governance_digests
+            .iter()
+            .map(|digest| decode_action_digest(digest))
+            .collect::<anyhow::Result<Vec<_>>>()?;
+        let governance_verifier = GovernanceVerifier::new(
+            self.approved_governance_actions.clone(),
+            approved_governance_digests,
+        )
+        .map_err(|e| anyhow!("{:?}", e))?;
+
+        if self.grpc_listen_address.is_some() && !cfg!(feature = "grpc") {
+            anyhow::bail!(
+                "`grpc_listen_address` requires the node to be built with the `grpc` feature"
+            );
+        }
 
-        // Validate approved actions that must be governace actions
-        for action in &self.approved_governance_actions {
-            if !action.is_governace_action() {
-                anyhow::bail!(format!(
-                    "{:?}",
-                    BridgeError::ActionIsNotGovernanceAction(action.clone())
+        if let Some(external_url) = &self.external_url {
+            url::Url::parse(external_url)
+                .map_err(|e| anyhow!("Invalid external_url {}: {:?}", external_url, e))?;
+        }
+        if let Some(alerts) = &self.alerts {
+            url::Url::parse(&alerts.webhook_url).map_err(|e| {
+                anyhow!("Invalid alerts webhook_url {}: {:?}", alerts.webhook_url, e)
+            })?;
+        }
+        if let Some(event_stream) = &self.event_stream {
+            if event_stream.socket_path.is_some() == event_stream.loopback_port.is_some() {
+                return Err(anyhow!(
+                    "Exactly one of `socket-path` and `loopback-port` must be set for the event stream"
+                ));
+            }
+        }
+        if let Some(pruning) = &self.pruning {
+            if pruning.batch_size == Some(0) {
+                return Err(anyhow!("`batch-size` of pruning must be positive"));
+            }
+            if let Some(archive) = &pruning.archive {
+                archive.open()?;
+            }
+        }
+        if let Some(storage_maintenance) = &self.storage_maintenance {
+            for schedule in [
+                &storage_maintenance.compaction_schedule,
+                &storage_maintenance.backup_schedule,
+            ]
+            .into_iter()
+            .flatten()
+            {
+                MaintenanceSchedule::from_str(schedule)
+                    .map_err(|e| anyhow!("Invalid storage maintenance schedule: {}", e))?;
+            }
+            if storage_maintenance.backup_schedule.is_some()
+                && storage_maintenance.backup_dir.is_none()
+            {
+                return Err(anyhow!(
+                    "`backup-dir` must be set to back up the storage on a schedule"
                 ));
             }
+            if storage_maintenance.retain_backups == Some(0) {
+                return Err(anyhow!("`retain-backups` must be positive"));
+            }
         }
-        let approved_governance_actions = self.approved_governance_actions.clone();
+        if self.server_runtime_worker_threads == Some(0) {
+            return Err(anyhow!("`server_runtime_worker_threads` must be positive"));
+        }
+        if self
+            .min_signature_inclusion_percent
+            .is_some_and(|percent| percent > 100)
+        {
+            return Err(anyhow!(
+                "`min_signature_inclusion_percent` must be at most 100"
+            ));
+        }
+
+        let request_authenticator = self
+            .request_auth
+            .as_ref()
+            .map(|auth_config| {
+                let allowed_keys = auth_config
+                    .allowed_keys
+                    .iter()
+                    .map(|key| {
+                        Base64::decode(key)
+                            .map_err(|e| anyhow!("{:?}", e))
+                            .and_then(|bytes| {
+                                Ed25519PublicKey::from_bytes(&bytes).map_err(|e| anyhow!("{:?}", e))
+                            })
+                            .map_err(|e| anyhow!("Invalid allowed key {}: {:?}", key, e))
+                    })
+                    .collect::<anyhow::Result<Vec<_>>>()?;
+                anyhow::Ok(Arc::new(RequestAuthenticator::new(
+                    allowed_keys,
+                    auth_config
+                        .max_clock_skew_seconds
+                        .map(Duration::from_secs)
+                        .unwrap_or(DEFAULT_MAX_CLOCK_SKEW),
+                )))
+            })
+            .transpose()?;
+        let admin_authorizer = self
+            .admin_auth
+            .as_ref()
+            .map(|admin_auth| {
+                let grants = admin_auth
+                    .callers
+                    .iter()
+                    .map(AdminCallerConfig::grant)
+                    .collect::<anyhow::Result<Vec<_>>>()?;
+                anyhow::Ok(Arc::new(AdminAuthorizer::new(grants)))
+            })
+            .transpose()?;
+
+        let listen = ListenConfig::resolve(
+            self.server_listen_address.as_deref(),
+            self.server_listen_port,
+            self.server_dual_stack,
+            self.server_reuse_port,
+        )
+        .await?;
+
+        // Only committee nodes have their signature in the certificates
+        let authority_public_key = match (self.mode(), &bridge_authority_key) {
+            (BridgeNodeMode::Committee, Some(key)) => Some(key.public().clone()),
+            (BridgeNodeMode::Committee, None) => self
+                .remote_signer
+                .as_ref()
+                .map(RemoteSignerConfig::authority_public_key)
+                .transpose()?,
+            _ => None,
+        };
 
         let bridge_server_config = BridgeServerConfig {
+            mode: self.mode(),
             key: bridge_authority_key,
+            remote_signer: self.remote_signer.clone(),
             metrics_port: self.metrics_port,
             server_listen_port: self.server_listen_port,
+            listen,
             sui_client: sui_client.clone(),
             eth_client: eth_client.clone(),
-            approved_governance_actions,
+            extra_eth_confirmations: self.eth.extra_eth_confirmations.unwrap_or(0),
+            governance_verifier,
+            sign_action_types: allowed_action_types(&self.sign_action_types),
+            request_authenticator,
+            admin_authorizer,
+            slow_request_threshold: self
+                .slow_request_threshold_ms
+                .map(Duration::from_millis)
+                .unwrap_or(DEFAULT_SLOW_REQUEST_THRESHOLD),
+            governance_body_limit: self
+                .governance_body_limit_bytes
+                .unwrap_or(DEFAULT_GOVERNANCE_BODY_LIMIT),
+            server_runtime_worker_threads: self.server_dedicated_runtime.then(|| {
+                self.server_runtime_worker_threads
+                    .unwrap_or(DEFAULT_SERVER_RUNTIME_WORKER_THREADS)
+            }),
+            max_requester_labels: self
+                .max_requester_labels
+                .unwrap_or(DEFAULT_MAX_REQUESTER_LABELS),
+            grpc_listen_address: self.grpc_listen_address,
+            external_url: self.external_url.clone(),
+            warm_up: self.warm_up.clone(),
+            verification_limits: self
+                .verification_limits
+                .as_ref()
+                .map(VerificationLimitsConfig::limits)
+                .transpose()?
+                .unwrap_or_default(),
+            eth_bridge_proxy_address: EthAddress::from_str(&self.eth.eth_bridge_proxy_address)?,
+            clock_drift: self.clock_drift.clone().unwrap_or_default(),
+            sui_rpc_endpoint: endpoint_label(&self.sui.sui_rpc_url),
+            eth_rpc_endpoint: endpoint_label(&self.eth.eth_rpc_url),
         };
         if !self.run_client {
             return Ok((bridge_server_config, None));
         }
 
         // If client is enabled, prepare client config
-        let (bridge_client_key, client_sui_address, gas_object_ref) =
-            self.prepare_for_sui(sui_client.clone()).await?;
+        self.check_sui_chain_identifier(&sui_client).await?;
+        let account = match self.mode() {
+            BridgeNodeMode::Committee | BridgeNodeMode::Shadow => {
+                Some(self.prepare_for_sui(sui_client.clone()).await?)
+            }
+            BridgeNodeMode::Observer => None,
+        };
 
         let db_path = self
             .db_path
             .clone()
             .ok_or(anyhow!("`db_path` is required when `run_client` is true"))?;
 
+        let tx_fullnodes = match &self.sui.tx_submission {
+            Some(config) if config.strategy == TxSubmissionStrategy::Concurrent => {
+                Some(self.prepare_tx_fullnodes(config, metrics.clone()).await?)
+            }
+            _ => None,
+        };
+
+        let gas_funding = match (&self.gas_funding, &account) {
+            (Some(gas_funding_config), Some(account)) => Some(
+                self.prepare_gas_funding(
+                    gas_funding_config,
+                    sui_client.clone(),
+                    account.sui_address,
+                )
+                .await?,
+            ),
+            _ => None,
+        };
+
+        let onchain_status_batch_size = match self.onchain_status_batch_size {
+            Some(0) => anyhow::bail!("`onchain_status_batch_size` must be positive"),
+            batch_size => batch_size.unwrap_or(DEFAULT_ONCHAIN_STATUS_BATCH_SIZE),
+        };
+        let adaptive_polling = self.adaptive_polling.clone().unwrap_or_default();
+        let sui_poll_interval = adaptive_polling
+            .sui
+            .as_ref()
+            .map(AdaptiveIntervalConfig::bounds)
+            .transpose()?;
+        let eth_poll_interval = adaptive_polling
+            .eth
+            .as_ref()
+            .map(AdaptiveIntervalConfig::bounds)
+            .transpose()?;
+
         let bridge_client_config = BridgeClientConfig {
-            sui_address: client_sui_address,
-            key: bridge_client_key,
-            gas_object_ref,
+            mode: self.mode(),
+            account,
             metrics_port: self.metrics_port,
             sui_client: sui_client.clone(),
+            tx_fullnodes,
             eth_client: eth_client.clone(),
             db_path,
+            aux_db_path: self.aux_db_path.clone(),
+            storage_key_provider: self.storage_key_provider()?,
+            verify_storage: self.verify_storage,
+            cursor_flush_interval: match self.storage_cursor_flush_ms {
+                Some(0) => None,
+                Some(ms) => Some(Duration::from_millis(ms)),
+                None => Some(DEFAULT_CURSOR_FLUSH_INTERVAL),
+            },
             eth_contracts,
+            eth_bridge_config_address,
+            min_transfer_usd_value: self.min_transfer_usd_value,
+            tx_journal: self.tx_journal.clone(),
+            reconciliation: self.reconciliation.clone(),
+            claim_tracking: self.claim_tracking.clone(),
+            channels: self.channels.clone().unwrap_or_default(),
+            sui_poll_interval,
+            eth_poll_interval,
+            gas_funding,
+            alerts: self.alerts.clone(),
+            timestamp_sanitizer: self
+                .timestamps
+                .as_ref()
+                .map(TimestampConfig::sanitizer)
+                .unwrap_or_default(),
+            event_stream: self.event_stream.clone(),
+            quarantine_recheck: self.quarantine_recheck.clone(),
+            onchain_status_batch_size,
+            pruning: self.pruning.clone(),
+            storage_maintenance: self.storage_maintenance.clone(),
+            authority_public_key,
+            min_signature_inclusion_percent: self
+                .min_signature_inclusion_percent
+                .unwrap_or(DEFAULT_MIN_SIGNATURE_INCLUSION_PERCENT),
+            skip_signing_paused_routes: self.skip_signing_paused_routes,
+            committee_dns_refresh_interval: match self.committee_dns_refresh_seconds {
+                Some(0) => None,
+                Some(seconds) => Some(Duration::from_secs(seconds)),
+                None => Some(DEFAULT_COMMITTEE_DNS_REFRESH_INTERVAL),
+            },
+            committee_connection_options: Arc::new(
+                self.committee_connections
+                    .clone()
+                    .unwrap_or_default()
+                    .options()?,
+            ),
+            committee_request_hedging: self
+                .committee_request_hedging
+                .as_ref()
+                .map(CommitteeRequestHedgingConfig::options)
+                .transpose()?,
+            execute_action_types: allowed_action_types(&self.execute_action_types),
+            sui_bridge_chain_id: BridgeChainId::try_from(self.sui.sui_bridge_chain_id)?,
+            eth_bridge_chain_id: BridgeChainId::try_from(self.eth.eth_bridge_chain_id)?,
+            eth_bridge_proxy_address: EthAddress::from_str(&self.eth.eth_bridge_proxy_address)?,
             // in `prepare_for_eth` we check if this is None when `run_client` is true. Safe to unwrap here.
             eth_contracts_start_block_fallback: self
                 .eth
@@ -237,11 +1723,42 @@ impl BridgeNodeConfig {
         Ok((bridge_server_config, Some(bridge_client_config)))
     }
 
+    async fn prepare_tx_fullnodes(
+        &self,
+        config: &TxSubmissionConfig,
+        metrics: Arc<BridgeMetrics>,
+    ) -> anyhow::Result<Vec<Arc<SuiClient<SuiSdkClient>>>> {
+        if config.fullnode_urls.is_empty() {
+            return Err(anyhow!(
+                "`sui.tx_submission.fullnode_urls` is required by the concurrent strategy"
+            ));
+        }
+        let mut fullnodes = vec![];
+        for url in &config.fullnode_urls {
+            let client = SuiClient::<SuiSdkClient>::new(url)
+                .await
+                .map_err(|e| anyhow!("Error connecting to fullnode {}: {:?}", url, e))?
+                .with_retry(
+                    self.sui
+                        .sui_rpc_max_attempts
+                        .unwrap_or(DEFAULT_SUI_RPC_MAX_ATTEMPTS),
+                    metrics.clone(),
+                );
+            fullnodes.push(Arc::new(client));
+        }
+        Ok(fullnodes)
+    }
+
     async fn prepare_for_eth(
         &self,
         metrics: Arc<BridgeMetrics>,
-    ) -> anyhow::Result<(Arc<EthClient<MeteredEthHttpProvier>>, Vec<EthAddress>)> {
+    ) -> anyhow::Result<(
+        Arc<EthClient<MeteredEthHttpProvier>>,
+        Vec<EthAddress>,
+        EthAddress,
+    )> {
         let bridge_proxy_address = EthAddress::from_str(&self.eth.eth_bridge_proxy_address)?;
+        let receipts_root_rpc_url = self.eth.receipts_root_rpc_url()?;
         let provider = Arc::new(
             new_metered_eth_provider(&self.eth.eth_rpc_url, metrics.clone())
                 .unwrap()
@@ -258,8 +1775,6 @@ impl BridgeNodeConfig {
             ));
         }
 
-        // If bridge chain id is Eth Mainent or Sepolia, we expect to see chain
-        // identifier to match accordingly.
         let bridge_chain_id: u8 = config.chain_id().call().await?;
         if self.eth.eth_bridge_chain_id != bridge_chain_id {
             return Err(anyhow!(
@@ -268,38 +1783,49 @@ impl BridgeNodeConfig {
                 bridge_chain_id
             ));
         }
-        if bridge_chain_id == BridgeChainId::EthMainnet as u8 && chain_id.as_u64() != 1 {
-            anyhow::bail!(
-                "Expected Eth chain id 1, but connected to {}",
-                chain_id.as_u64()
-            );
-        }
-        if bridge_chain_id == BridgeChainId::EthSepolia as u8 && chain_id.as_u64() != 11155111 {
-            anyhow::bail!(
-                "Expected Eth chain id 11155111, but connected to {}",
-                chain_id.as_u64()
-            );
-        }
+        // If bridge chain id is Eth Mainent or Sepolia, we expect to see chain
+        // identifier to match accordingly. With EthCustom, the chain must be one
+        // of `custom_evm_chains` if set.
+        let finality = self
+            .eth
+            .check_evm_chain_id(chain_id.as_u64(), bridge_proxy_address)?;
         info!(
             "Connected to Eth chain: {}, Bridge chain id: {}",
             chain_id.as_u64(),
             bridge_chain_id,
         );
 
-        let eth_client = Arc::new(
-            EthClient::<MeteredEthHttpProvier>::new(
-                &self.eth.eth_rpc_url,
-                HashSet::from_iter(vec![
-                    bridge_proxy_address,
-                    committee_address,
-                    config_address,
-                    limiter_address,
-                    vault_address,
-                ]),
-                metrics,
-            )
-            .await?,
-        );
+        let mut eth_client = EthClient::<MeteredEthHttpProvier>::new(
+            &self.eth.eth_rpc_url,
+            HashSet::from_iter(vec![
+                bridge_proxy_address,
+                committee_address,
+                config_address,
+                limiter_address,
+                vault_address,
+            ]),
+            metrics.clone(),
+        )
+        .await?
+        .with_finality(finality);
+        if let Some(url) = receipts_root_rpc_url {
+            let header_provider = new_metered_eth_provider(url, metrics.clone())?;
+            let header_chain_id = header_provider.get_chainid().await?;
+            if header_chain_id != chain_id {
+                return Err(anyhow!(
+                    "`eth_receipts_root_rpc_url` is on Eth chain {}, expected {}",
+                    header_chain_id,
+                    chain_id
+                ));
+            }
+            info!(
+                "Verifying the receipts of Eth deposits against the block headers of {}",
+                endpoint_label(url)
+            );
+            eth_client =
+                eth_client.with_receipt_verifier(EthReceiptVerifier::new(header_provider, metrics));
+        }
+        let eth_client = Arc::new(eth_client);
         let contract_addresses = vec![
             bridge_proxy_address,
             committee_address,
@@ -307,18 +1833,13 @@ impl BridgeNodeConfig {
             limiter_address,
             vault_address,
         ];
-        Ok((eth_client, contract_addresses))
+        Ok((eth_client, contract_addresses, config_address))
     }
 
-    async fn prepare_for_sui(
+    async fn check_sui_chain_identifier(
         &self,
-        sui_client: Arc<SuiClient<SuiSdkClient>>,
-    ) -> anyhow::Result<(SuiKeyPair, SuiAddress, ObjectRef)> {
-        let bridge_client_key = match &self.sui.bridge_client_key_path {
-            None => read_key(&self.bridge_authority_key_path, true),
-            Some(path) => read_key(path, false),
-        }?;
-
+        sui_client: &SuiClient<SuiSdkClient>,
+    ) -> anyhow::Result<()> {
         // If bridge chain id is Sui Mainent or Testnet, we expect to see chain
         // identifier to match accordingly.
         let sui_identifier = sui_client
@@ -347,6 +1868,23 @@ impl BridgeNodeConfig {
             "Connected to Sui chain: {}, Bridge chain id: {}",
             sui_identifier, self.sui.sui_bridge_chain_id,
         );
+        Ok(())
+    }
+
+    async fn prepare_for_sui(
+        &self,
+        sui_client: Arc<SuiClient<SuiSdkClient>>,
+    ) -> anyhow::Result<BridgeClientAccount> {
+        let bridge_client_key = match (
+            &self.sui.bridge_client_key_path,
+            &self.bridge_authority_key_path,
+        ) {
+            (Some(path), _) => read_key(path, false),
+            (None, Some(path)) => read_key(path, true),
+            (None, None) => Err(anyhow!(
+                "`bridge_authority_key_path` or `sui.bridge_client_key_path` is required to submit transactions"
+            )),
+        }?;
 
         let client_sui_address = SuiAddress::from(&bridge_client_key.public());
 
@@ -376,36 +1914,203 @@ impl BridgeNodeConfig {
             gas_coin.value()
         );
 
-        Ok((bridge_client_key, client_sui_address, gas_object_ref))
+        Ok(BridgeClientAccount {
+            sui_address: client_sui_address,
+            key: bridge_client_key,
+            gas_object_ref,
+        })
+    }
+
+    async fn prepare_gas_funding(
+        &self,
+        config: &GasFundingConfig,
+        sui_client: Arc<SuiClient<SuiSdkClient>>,
+        client_sui_address: SuiAddress,
+    ) -> anyhow::Result<GasFunding> {
+        let key = read_key(&config.funding_key_path, false)?;
+        let funding_address = SuiAddress::from(&key.public());
+        // The funding transactions must not use the coins of the bridge client
+        if funding_address == client_sui_address {
+            anyhow::bail!("Gas funding key must be different from the bridge client key");
+        }
+        if config.target_balance <= config.threshold_balance {
+            anyhow::bail!("Gas funding `target_balance` must be greater than `threshold_balance`");
+        }
+        let gas_object_id = match config.funding_gas_object {
+            Some(id) => id,
+            None => {
+                pick_highest_balance_coin(
+                    sui_client.sui_client().coin_read_api(),
+                    funding_address,
+                    config.target_balance,
+                )
+                .await?
+                .coin_object_id
+            }
+        };
+        info!(
+            "Topping up gas coin from address: {:?}, gas object {:?}",
+            funding_address, gas_object_id
+        );
+        Ok(GasFunding {
+            key,
+            gas_object_id,
+            config: config.clone(),
+        })
     }
 }
 
-pub struct BridgeServerConfig {
-    pub key: BridgeAuthorityKeyPair,
+// All action types are allowed when the config does not restrict them
+fn allowed_action_types(types: &Option<Vec<BridgeActionType>>) -> HashSet<BridgeActionType> {
+    match types {
+        Some(types) => types.iter().copied().collect(),
+        None => HashSet::from(BridgeActionType::ALL),
+    }
+}
+
+/// The settings of the server of a node. Generic over the clients so that embedders can
+/// run a node with their own, see `BridgeNodeBuilder`.
+pub struct BridgeServerConfig<C = SuiSdkClient, P = MeteredEthHttpProvier> {
+    pub mode: BridgeNodeMode,
+    /// None in observer mode, the server does not sign then.
+    pub key: Option<BridgeAuthorityKeyPair>,
+    /// Signs instead of `key` when set.
+    pub remote_signer: Option<RemoteSignerConfig>,
     pub server_listen_port: u16,
+    pub listen: ListenConfig,
     pub metrics_port: u16,
-    pub sui_client: Arc<SuiClient<SuiSdkClient>>,
-    pub eth_client: Arc<EthClient<MeteredEthHttpProvier>>,
-    /// A list of approved governance actions. Action in this list will be signed when requested by client.
-    pub approved_governance_actions: Vec<BridgeAction>,
+    pub sui_client: Arc<SuiClient<C>>,
+    pub eth_client: Arc<EthClient<P>>,
+    pub extra_eth_confirmations: u64,
+    /// The approved governance actions, which will be signed when requested by client.
+    pub governance_verifier: GovernanceVerifier,
+    pub sign_action_types: HashSet<BridgeActionType>,
+    pub request_authenticator: Option<Arc<RequestAuthenticator>>,
+    pub admin_authorizer: Option<Arc<AdminAuthorizer>>,
+    pub slow_request_threshold: Duration,
+    pub governance_body_limit: usize,
+    /// Number of worker threads of the dedicated runtime of the server, None when the
+    /// server runs on the runtime of the node, see `server::runtime`.
+    pub server_runtime_worker_threads: Option<usize>,
+    pub max_requester_labels: usize,
+    pub grpc_listen_address: Option<SocketAddr>,
+    pub external_url: Option<String>,
+    pub warm_up: Option<WarmUpConfig>,
+    pub verification_limits: VerificationLimits,
+    pub eth_bridge_proxy_address: EthAddress,
+    pub clock_drift: ClockDriftConfig,
+    /// Labels of the RPC endpoints of Sui and Eth, see `endpoint_label`.
+    pub sui_rpc_endpoint: String,
+    pub eth_rpc_endpoint: String,
+}
+
+impl<C, P> BridgeServerConfig<C, P> {
+    /// A server on `listen` with the default settings, in observer mode until a key is set.
+    pub fn new(
+        sui_client: Arc<SuiClient<C>>,
+        eth_client: Arc<EthClient<P>>,
+        listen: ListenConfig,
+        eth_bridge_proxy_address: EthAddress,
+    ) -> Self {
+        Self {
+            mode: BridgeNodeMode::Observer,
+            key: None,
+            remote_signer: None,
+            server_listen_port: listen.address.port(),
+            listen,
+            metrics_port: 0,
+            sui_client,
+            eth_client,
+            extra_eth_confirmations: 0,
+            governance_verifier: GovernanceVerifier::default(),
+            sign_action_types: allowed_action_types(&None),
+            request_authenticator: None,
+            admin_authorizer: None,
+            slow_request_threshold: DEFAULT_SLOW_REQUEST_THRESHOLD,
+            governance_body_limit: DEFAULT_GOVERNANCE_BODY_LIMIT,
+            server_runtime_worker_threads: None,
+            max_requester_labels: DEFAULT_MAX_REQUESTER_LABELS,
+            grpc_listen_address: None,
+            external_url: None,
+            warm_up: None,
+            verification_limits: VerificationLimits::default(),
+            eth_bridge_proxy_address,
+            clock_drift: ClockDriftConfig::default(),
+            sui_rpc_endpoint: "unknown".to_string(),
+            eth_rpc_endpoint: "unknown".to_string(),
+        }
+    }
 }
 
-// TODO: add gas balance alert threshold
-pub struct BridgeClientConfig {
+/// The account that the client submits transactions with.
+pub struct BridgeClientAccount {
     pub sui_address: SuiAddress,
     pub key: SuiKeyPair,
     pub gas_object_ref: ObjectRef,
+}
+
+pub struct BridgeClientConfig<C = SuiSdkClient, P = MeteredEthHttpProvier> {
+    pub mode: BridgeNodeMode,
+    /// None in observer mode, the client does not submit transactions then.
+    pub account: Option<BridgeClientAccount>,
     pub metrics_port: u16,
-    pub sui_client: Arc<SuiClient<SuiSdkClient>>,
-    pub eth_client: Arc<EthClient<MeteredEthHttpProvier>>,
+    pub sui_client: Arc<SuiClient<C>>,
+    /// Fullnodes that transactions are submitted to concurrently with `sui_client`.
+    /// None when they are submitted through `sui_client` only.
+    pub tx_fullnodes: Option<Vec<Arc<SuiClient<C>>>>,
+    pub eth_client: Arc<EthClient<P>>,
     pub db_path: PathBuf,
+    pub aux_db_path: Option<PathBuf>,
+    /// None when the client storage is not encrypted
+    pub storage_key_provider: Option<Arc<dyn StorageKeyProvider>>,
+    pub verify_storage: bool,
+    /// None when the cursor updates are written through, see
+    /// `BridgeOrchestratorTables::enable_cursor_coalescing`.
+    pub cursor_flush_interval: Option<Duration>,
     pub eth_contracts: Vec<EthAddress>,
+    pub eth_bridge_config_address: EthAddress,
+    pub min_transfer_usd_value: Option<u64>,
+    pub tx_journal: Option<TxJournalConfig>,
+    pub reconciliation: Option<ReconciliationConfig>,
+    pub claim_tracking: Option<ClaimTrackingConfig>,
+    pub channels: ChannelConfig,
+    /// Bounds of the adaptive polling of the syncers, None when they poll at a fixed
+    /// interval.
+    pub sui_poll_interval: Option<AdaptiveIntervalBounds>,
+    pub eth_poll_interval: Option<AdaptiveIntervalBounds>,
+    pub gas_funding: Option<GasFunding>,
+    pub alerts: Option<AlertsConfig>,
+    pub timestamp_sanitizer: TimestampSanitizer,
+    pub event_stream: Option<EventStreamConfig>,
+    pub quarantine_recheck: Option<QuarantineRecheckConfig>,
+    pub onchain_status_batch_size: usize,
+    pub pruning: Option<PruningConfig>,
+    pub storage_maintenance: Option<StorageMaintenanceConfig>,
+    /// Public key of this node, None unless in committee mode.
+    pub authority_public_key: Option<BridgeAuthorityPublicKey>,
+    pub min_signature_inclusion_percent: u8,
+    pub skip_signing_paused_routes: bool,
+    /// None when the hosts of the committee members are not re-resolved.
+    pub committee_dns_refresh_interval: Option<Duration>,
+    pub committee_connection_options: Arc<CommitteeConnectionOptions>,
+    /// None when the signing requests are not hedged
+    pub committee_request_hedging: Option<HedgingOptions>,
+    pub execute_action_types: HashSet<BridgeActionType>,
+    pub sui_bridge_chain_id: BridgeChainId,
+    pub eth_bridge_chain_id: BridgeChainId,
+    pub eth_bridge_proxy_address: EthAddress,
     // See `BridgeNodeConfig` for the explanation of following two fields.
     pub eth_contracts_start_block_fallback: u64,
     pub eth_contracts_start_block_override: Option<u64>,
     pub sui_bridge_module_last_processed_event_id_override: Option<EventID>,
 }
 
+pub struct GasFunding {
+    pub key: SuiKeyPair,
+    pub gas_object_id: ObjectID,
+    pub config: GasFundingConfig,
+}
+
 #[serde_as]
 #[derive(Clone, Debug, Deserialize, Serialize)]
 #[serde(rename_all = "kebab-case")]
@@ -453,3 +2158,292 @@ pub struct EthContractAddresses {
     pub bridge_limiter: EthAddress,
     pub bridge_vault: EthAddress,
 }
+
+/// Chain ids that a Sui bridge object and an Eth bridge contract are set up to bridge
+/// between, next to the configured ones. A Sui chain pointed at the contracts of the
+/// wrong Eth chain signs actions that can never be valid.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct BridgeContractPair {
+    config_sui_chain_id: u8,
+    config_eth_chain_id: u8,
+    /// Sui chains that the Eth bridge config contract accepts transfers to
+    eth_contract_sui_chain_ids: Vec<u8>,
+    /// Eth chains that the Sui bridge object has transfer routes from
+    sui_object_eth_chain_ids: Vec<u8>,
+}
+
+impl BridgeContractPair {
+    pub(crate) async fn read<C, P>(
+        sui_client: &SuiClient<C>,
+        eth_client: &EthClient<P>,
+        eth_bridge_config_address: EthAddress,
+        config_sui_chain_id: u8,
+        config_eth_chain_id: u8,
+    ) -> anyhow::Result<Self>
+    where
+        C: SuiClientInner,
+        P: JsonRpcClient + Clone + 'static,
+    {
+        let sui_chain_ids = [
+            BridgeChainId::SuiMainnet as u8,
+            BridgeChainId::SuiTestnet as u8,
+            BridgeChainId::SuiCustom as u8,
+        ];
+        let eth_contract_sui_chain_ids = eth_client
+            .get_supported_chain_ids(eth_bridge_config_address, &sui_chain_ids)
+            .await
+            .map_err(|e| anyhow!("Error getting chains supported by Eth bridge: {:?}", e))?;
+        let bridge_summary = sui_client
+            .get_bridge_summary()
+            .await
+            .map_err(|e| anyhow!("Error getting bridge summary: {:?}", e))?;
+        let mut sui_object_eth_chain_ids = bridge_summary
+            .limiter
+            .transfer_limit
+            .iter()
+            .filter(|(_, destination, _)| *destination as u8 == bridge_summary.chain_id)
+            .map(|(source, _, _)| *source as u8)
+            .collect::<Vec<_>>();
+        sui_object_eth_chain_ids.sort();
+        sui_object_eth_chain_ids.dedup();
+        Ok(Self {
+            config_sui_chain_id,
+            config_eth_chain_id,
+            eth_contract_sui_chain_ids,
+            sui_object_eth_chain_ids,
+        })
+    }
+
+    /// Fails unless the Eth contract bridges to the configured Sui chain and the Sui
+    /// object bridges from the configured Eth chain. The chain ids of the contracts
+    /// themselves are checked against the config separately, so this also makes the
+    /// two contracts bridge to each other.
+    pub(crate) fn check(&self) -> anyhow::Result<()> {
+        if self
+            .eth_contract_sui_chain_ids
+            .contains(&self.config_sui_chain_id)
+            && self
+                .sui_object_eth_chain_ids
+                .contains(&self.config_eth_chain_id)
+        {
+            return Ok(());
+        }
+        anyhow::bail!(
+            "Mismatched Sui and Eth bridge contracts: configured Sui chain id {}, configured Eth chain id {}, Eth bridge contract destination chain ids {:?}, Sui bridge object Eth chain ids {:?}",
+            self.config_sui_chain_id,
+            self.config_eth_chain_id,
+            self.eth_contract_sui_chain_ids,
+            self.sui_object_eth_chain_ids,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::eth_mock_provider::EthMockProvider;
+    use crate::sui_mock_client::SuiMockClient;
+    use crate::test_utils::mock_eth_call;
+    use crate::types::{ETH_MAINNET_CHAIN_ID, ETH_SEPOLIA_CHAIN_ID};
+    use ethers::abi::Token;
+    use ethers::providers::Provider;
+    use sui_types::bridge::BridgeLimiterSummary;
+
+    fn test_eth_config(
+        eth_bridge_chain_id: BridgeChainId,
+        proxy_address: EthAddress,
+        custom_evm_chains: Option<BTreeMap<u64, CustomEvmChainConfig>>,
+    ) -> EthConfig {
+        EthConfig {
+            eth_rpc_url: "http://127.0.0.1:8545".to_string(),
+            eth_bridge_proxy_address: format!("{:?}", proxy_address),
+            eth_bridge_chain_id: eth_bridge_chain_id as u8,
+            eth_contracts_start_block_fallback: Some(0),
+            eth_contracts_start_block_override: None,
+            extra_eth_confirmations: None,
+            custom_evm_chains,
+            fee_history_interval_seconds: None,
+            verify_eth_receipts: None,
+            eth_receipts_root_rpc_url: None,
+        }
+    }
+
+    #[test]
+    fn test_custom_evm_chains() {
+        let proxy_address = EthAddress::random();
+        let devnet = CustomEvmChainConfig {
+            bridge_proxy_address: format!("{:?}", proxy_address),
+            finality: Some(EthFinality::Confirmations(3)),
+        };
+        let config = test_eth_config(
+            BridgeChainId::EthCustom,
+            proxy_address,
+            Some(BTreeMap::from([(31337, devnet.clone())])),
+        );
+        config.validate_custom_evm_chains().unwrap();
+        assert_eq!(
+            config.check_evm_chain_id(31337, proxy_address).unwrap(),
+            EthFinality::Confirmations(3)
+        );
+        let err = config.check_evm_chain_id(31338, proxy_address).unwrap_err();
+        assert!(err.to_string().contains("not in `custom_evm_chains`"));
+        let err = config
+            .check_evm_chain_id(31337, EthAddress::random())
+            .unwrap_err();
+        assert!(err.to_string().contains("is not the bridge proxy address"));
+
+        // Chain ids of Eth mainnet and Sepolia are reserved
+        for reserved in [ETH_MAINNET_CHAIN_ID, ETH_SEPOLIA_CHAIN_ID] {
+            let config = test_eth_config(
+                BridgeChainId::EthCustom,
+                proxy_address,
+                Some(BTreeMap::from([(reserved, devnet.clone())])),
+            );
+            let err = config.validate_custom_evm_chains().unwrap_err();
+            assert!(err.to_string().contains("is reserved"));
+        }
+
+        // Custom chains only come with EthCustom
+        let config = test_eth_config(
+            BridgeChainId::EthSepolia,
+            proxy_address,
+            Some(BTreeMap::from([(31337, devnet)])),
+        );
+        assert!(config.validate_custom_evm_chains().is_err());
+
+        // Without custom chains, any chain can be EthCustom, and reserved ids must match
+        let config = test_eth_config(BridgeChainId::EthCustom, proxy_address, None);
+        config.validate_custom_evm_chains().unwrap();
+        assert_eq!(
+            config.check_evm_chain_id(31338, proxy_address).unwrap(),
+            EthFinality::Finalized
+        );
+        let config = test_eth_config(BridgeChainId::EthSepolia, proxy_address, None);
+        config
+            .check_evm_chain_id(ETH_SEPOLIA_CHAIN_ID, proxy_address)
+            .unwrap();
+        let err = config
+            .check_evm_chain_id(ETH_MAINNET_CHAIN_ID, proxy_address)
+            .unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("Expected Eth chain id 11155111, but connected to 1"));
+    }
+
+    #[test]
+    fn test_receipts_root_rpc_url() {
+        let mut config = test_eth_config(BridgeChainId::EthSepolia, EthAddress::random(), None);
+        assert_eq!(config.receipts_root_rpc_url().unwrap(), None);
+        // The url alone doesn't enable the verification
+        config.eth_receipts_root_rpc_url = Some("https://rpc.sepolia.org".to_string());
+        assert_eq!(config.receipts_root_rpc_url().unwrap(), None);
+        config.verify_eth_receipts = Some(true);
+        assert_eq!(
+            config.receipts_root_rpc_url().unwrap(),
+            Some("https://rpc.sepolia.org")
+        );
+
+        config.eth_receipts_root_rpc_url = Some(config.eth_rpc_url.clone());
+        let err = config.receipts_root_rpc_url().unwrap_err();
+        assert!(err.to_string().contains("must be another provider"));
+        config.eth_receipts_root_rpc_url = None;
+        let err = config.receipts_root_rpc_url().unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("requires `eth_receipts_root_rpc_url`"));
+    }
+
+    async fn read_contract_pair(
+        config_chain_ids: (BridgeChainId, BridgeChainId),
+        eth_contract_sui_chain_id: BridgeChainId,
+        sui_object_route: (BridgeChainId, BridgeChainId),
+    ) -> BridgeContractPair {
+        let (sui_object_eth_chain_id, sui_object_sui_chain_id) = sui_object_route;
+        let sui_client_mock = SuiMockClient::default();
+        sui_client_mock.set_bridge_chain_id(sui_object_sui_chain_id as u8);
+        sui_client_mock.set_bridge_limiter(BridgeLimiterSummary {
+            transfer_limit: vec![(sui_object_eth_chain_id, sui_object_sui_chain_id, 1000)],
+            transfer_records: vec![],
+        });
+        let sui_client = SuiClient::new_for_testing(sui_client_mock);
+
+        let config_address = EthAddress::random();
+        let eth_mock_provider = EthMockProvider::default();
+        let config = EthBridgeConfig::new(
+            config_address,
+            Arc::new(Provider::new(eth_mock_provider.clone())),
+        );
+        for chain_id in [
+            BridgeChainId::SuiMainnet,
+            BridgeChainId::SuiTestnet,
+            BridgeChainId::SuiCustom,
+        ] {
+            mock_eth_call(
+                &eth_mock_provider,
+                &config.is_chain_supported(chain_id as u8).tx,
+                &[Token::Bool(chain_id == eth_contract_sui_chain_id)],
+            );
+        }
+        let eth_client = EthClient::new_mocked(eth_mock_provider, HashSet::new());
+
+        BridgeContractPair::read(
+            &sui_client,
+            &eth_client,
+            config_address,
+            config_chain_ids.0 as u8,
+            config_chain_ids.1 as u8,
+        )
+        .await
+        .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_bridge_contract_pair() {
+        use BridgeChainId::*;
+        telemetry_subscribers::init_for_testing();
+        let registry = prometheus::Registry::new();
+        mysten_metrics::init_metrics(&registry);
+
+        // Both contracts bridge between the configured chains
+        let pair = read_contract_pair(
+            (SuiTestnet, EthSepolia),
+            SuiTestnet,
+            (EthSepolia, SuiTestnet),
+        )
+        .await;
+        assert_eq!(pair.eth_contract_sui_chain_ids, vec![SuiTestnet as u8]);
+        assert_eq!(pair.sui_object_eth_chain_ids, vec![EthSepolia as u8]);
+        pair.check().unwrap();
+
+        // Eth contract bridges to another Sui chain
+        let pair = read_contract_pair(
+            (SuiMainnet, EthMainnet),
+            SuiTestnet,
+            (EthMainnet, SuiMainnet),
+        )
+        .await;
+        assert!(pair.check().is_err());
+
+        // Sui object bridges from another Eth chain
+        let pair = read_contract_pair(
+            (SuiTestnet, EthSepolia),
+            SuiTestnet,
+            (EthCustom, SuiTestnet),
+        )
+        .await;
+        assert!(pair.check().is_err());
+
+        // Mainnet Sui pointed at the Sepolia contracts, neither side matches
+        let pair = read_contract_pair(
+            (SuiMainnet, EthSepolia),
+            SuiTestnet,
+            (EthMainnet, SuiMainnet),
+        )
+        .await;
+        let err = pair.check().unwrap_err().to_string();
+        assert!(err.contains("configured Sui chain id 0"));
+        assert!(err.contains("configured Eth chain id 11"));
+        assert!(err.contains("Eth bridge contract destination chain ids [1]"));
+        assert!(err.contains("Sui bridge object Eth chain ids [10]"));
+    }
+}
@@ -3,6 +3,7 @@
 
 use crate::{
     error::{BridgeError, BridgeResult},
+    metrics::BridgeMetrics,
     types::{BridgeAction, BridgeCommittee, SignedBridgeAction, VerifiedSignedBridgeAction},
 };
 use ethers::core::k256::ecdsa::VerifyingKey;
@@ -28,6 +29,18 @@ pub type BridgeAuthorityKeyPair = Secp256k1KeyPair;
 pub type BridgeAuthorityPublicKey = Secp256k1PublicKey;
 pub type BridgeAuthorityRecoverableSignature = Secp256k1RecoverableSignature;
 
+// The order n of secp256k1, and n / 2. A signature (r, s) with recovery id v verifies as
+// (r, n - s) with recovery id 1 - v too. Only the low-s form, s <= n / 2, is accepted on
+// chain.
+const SECP256K1_ORDER: [u8; 32] = [
+    0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xfe,
+    0xba, 0xae, 0xdc, 0xe6, 0xaf, 0x48, 0xa0, 0x3b, 0xbf, 0xd2, 0x5e, 0x8c, 0xd0, 0x36, 0x41, 0x41,
+];
+const SECP256K1_HALF_ORDER: [u8; 32] = [
+    0x7f, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff,
+    0x5d, 0x57, 0x6e, 0x73, 0x57, 0xa4, 0x50, 0x1d, 0xdf, 0xe9, 0x2f, 0x46, 0x68, 0x1b, 0x20, 0xa0,
+];
+
 #[derive(Ord, PartialOrd, PartialEq, Eq, Clone, Debug, Hash, Serialize, Deserialize)]
 pub struct BridgeAuthorityPublicKeyBytes(Secp256k1PublicKeyAsBytes);
 
@@ -118,7 +131,7 @@ pub struct BridgeAuthoritySignInfo {
 
 impl BridgeAuthoritySignInfo {
     pub fn new(msg: &BridgeAction, secret: &BridgeAuthorityKeyPair) -> Self {
-        let msg_bytes = msg.to_bytes();
+        let msg_bytes = msg.to_signing_bytes();
         Self {
             authority_pub_key: secret.public().clone(),
             signature: secret.sign_recoverable_with_hash::<Keccak256>(&msg_bytes),
@@ -134,7 +147,7 @@ impl BridgeAuthoritySignInfo {
         }
 
         // 2. verify signature
-        let msg_bytes = msg.to_bytes();
+        let msg_bytes = msg.to_signing_bytes();
 
         self.authority_pub_key
             .verify_recoverable_with_hash::<Keccak256>(&msg_bytes, &self.signature)
@@ -149,6 +162,112 @@ impl BridgeAuthoritySignInfo {
     pub fn authority_pub_key_bytes(&self) -> BridgeAuthorityPublicKeyBytes {
         BridgeAuthorityPublicKeyBytes::from(&self.authority_pub_key)
     }
+
+    /// Brings the signature into the canonical form the Move verifier expects: low-s,
+    /// with recovery id 0 or 1. A high-s signature is normalized when the low-s one still
+    /// verifies on `msg`, other signatures are rejected. Returns whether it was normalized.
+    pub fn canonicalize(self, msg: &BridgeAction) -> BridgeResult<(Self, bool)> {
+        let authority = self.authority_pub_key_bytes();
+        let invalid =
+            |e: String| BridgeError::InvalidBridgeAuthoritySignature((authority.clone(), e));
+        let Some(signature) =
+            normalize_signature(&self.signature).map_err(|e| invalid(e.to_string()))?
+        else {
+            return Ok((self, false));
+        };
+        self.authority_pub_key
+            .verify_recoverable_with_hash::<Keccak256>(&msg.to_signing_bytes(), &signature)
+            .map_err(|e| invalid(format!("Normalized signature doesn't verify: {}", e)))?;
+        Ok((Self { signature, ..self }, true))
+    }
+}
+
+/// The low-s form of `signature`, None if it is in that form already. Fails when the
+/// recovery id is not 0 or 1, or r or s are not in [1, n - 1].
+pub fn normalize_signature(
+    signature: &BridgeAuthorityRecoverableSignature,
+) -> Result<Option<BridgeAuthorityRecoverableSignature>, FastCryptoError> {
+    let bytes = signature.as_bytes();
+    let (r, s, recovery_id) = (&bytes[..32], &bytes[32..64], bytes[64]);
+    if recovery_id > 1 {
+        return Err(FastCryptoError::GeneralError(format!(
+            "Recovery id {} is not 0 or 1",
+            recovery_id
+        )));
+    }
+    // Big endian, so the byte order is the numeric order
+    let is_scalar = |x: &[u8]| x != [0u8; 32] && x < &SECP256K1_ORDER[..];
+    if !is_scalar(r) || !is_scalar(s) {
+        return Err(FastCryptoError::GeneralError(
+            "r or s is out of range".to_string(),
+        ));
+    }
+    if s <= &SECP256K1_HALF_ORDER[..] {
+        return Ok(None);
+    }
+    let mut normalized = [0u8; 65];
+    normalized[..32].copy_from_slice(r);
+    normalized[32..64].copy_from_slice(&negate_scalar(s));
+    normalized[64] = recovery_id ^ 1;
+    BridgeAuthorityRecoverableSignature::from_bytes(&normalized).map(Some)
+}
+
+// n - s for s in [1, n - 1], big endian
+fn negate_scalar(s: &[u8]) -> [u8; 32] {
+    let mut negated = [0u8; 32];
+    let mut borrow = 0u16;
+    for i in (0..32).rev() {
+        let difference = SECP256K1_ORDER[i] as u16 + 256 - s[i] as u16 - borrow;
+        negated[i] = difference as u8;
+        borrow = 1 - difference / 256;
+    }
+    negated
+}
+
+/// `BridgeAuthoritySignInfo::canonicalize` that counts the signatures that were not
+/// canonical by authority, whether they were normalized or rejected.
+pub fn canonicalize_sign_info(
+    sign_info: BridgeAuthoritySignInfo,
+    msg: &BridgeAction,
+    metrics: &BridgeMetrics,
+) -> BridgeResult<BridgeAuthoritySignInfo> {
+    let authority = sign_info.authority_pub_key_bytes().concise_owned();
+    let count = |outcome: &str| {
+        metrics
+            .noncanonical_signatures
+            .with_label_values(&[&authority, outcome])
+            .inc()
+    };
+    match sign_info.canonicalize(msg) {
+        Ok((sign_info, normalized)) => {
+            if normalized {
+                tracing::warn!("Normalized a high-s signature of {}", authority);
+                count("normalized");
+            }
+            Ok(sign_info)
+        }
+        Err(e) => {
+            tracing::error!(
+                "Rejected a non-canonical signature of {}: {:?}",
+                authority,
+                e
+            );
+            count("rejected");
+            Err(e)
+        }
+    }
+}
+
+/// Canonicalizes the signature of `signed_action`, see `canonicalize_sign_info`.
+pub fn canonicalize_signed_action(
+    signed_action: VerifiedSignedBridgeAction,
+    metrics: &BridgeMetrics,
+) -> BridgeResult<VerifiedSignedBridgeAction> {
+    let (action, sign_info) = signed_action.into_inner().into_data_and_sig();
+    let sign_info = canonicalize_sign_info(sign_info, &action, metrics)?;
+    Ok(VerifiedEnvelope::new_from_verified(
+        SignedBridgeAction::new_from_data_and_sig(action, sign_info),
+    ))
 }
 
 /// Verifies a SignedBridgeAction (response from bridge authority to bridge client)
@@ -186,6 +305,8 @@ mod tests {
     use ethers::types::Address as EthAddress;
     use fastcrypto::traits::{KeyPair, ToFromBytes};
     use prometheus::Registry;
+    use proptest::prelude::*;
+    use rand::{rngs::StdRng, SeedableRng};
     use std::str::FromStr;
     use std::sync::Arc;
     use sui_types::base_types::SuiAddress;
@@ -394,4 +515,143 @@ mod tests {
             .unwrap();
         assert_eq!(pub_key_bytes.to_eth_address(), addr);
     }
+
+    // The same signature with s in the other half, as a buggy signer could produce it
+    fn high_s_twin(signature: &BridgeAuthorityRecoverableSignature) -> Vec<u8> {
+        let mut bytes = signature.as_bytes().to_vec();
+        let negated = negate_scalar(&bytes[32..64]);
+        bytes[32..64].copy_from_slice(&negated);
+        bytes[64] ^= 1;
+        bytes
+    }
+
+    fn raw_signature(r: [u8; 32], s: [u8; 32], recovery_id: u8) -> Vec<u8> {
+        [&r[..], &s[..], &[recovery_id]].concat()
+    }
+
+    #[test]
+    fn test_normalize_signature_known_vectors() {
+        let one = {
+            let mut one = [0u8; 32];
+            one[31] = 1;
+            one
+        };
+        let mut order_minus_one = SECP256K1_ORDER;
+        order_minus_one[31] -= 1;
+        let mut half_order_plus_one = SECP256K1_HALF_ORDER;
+        half_order_plus_one[31] += 1;
+        let normalize = |bytes: Vec<u8>| {
+            normalize_signature(&BridgeAuthorityRecoverableSignature::from_bytes(&bytes).unwrap())
+                .map(|signature| signature.map(|signature| signature.as_bytes().to_vec()))
+        };
+
+        // n - 1 is the highest s, normalized to 1 with the other recovery id
+        assert_eq!(
+            normalize(raw_signature(one, order_minus_one, 0)).unwrap(),
+            Some(raw_signature(one, one, 1))
+        );
+        // n / 2 + 1 is the lowest high s, normalized to n / 2
+        assert_eq!(
+            normalize(raw_signature(one, half_order_plus_one, 1)).unwrap(),
+            Some(raw_signature(one, SECP256K1_HALF_ORDER, 0))
+        );
+        // Low s is left as is
+        assert_eq!(
+            normalize(raw_signature(one, SECP256K1_HALF_ORDER, 1)).unwrap(),
+            None
+        );
+        assert_eq!(normalize(raw_signature(one, one, 0)).unwrap(), None);
+
+        // Recovery ids other than 0 and 1 are rejected
+        for recovery_id in [2, 3] {
+            assert!(normalize(raw_signature(one, one, recovery_id)).is_err());
+        }
+    }
+
+    #[test]
+    fn test_canonicalize_sign_info() {
+        let metrics = BridgeMetrics::for_testing();
+        let (_, _, secret) = get_test_authority_and_key(5000, 9999);
+        let action =
+            get_test_sui_to_eth_bridge_action(None, Some(1), Some(1), Some(100), None, None, None);
+        let sig = BridgeAuthoritySignInfo::new(&action, &secret);
+        let authority = sig.authority_pub_key_bytes().concise_owned();
+        let count = |outcome: &str| {
+            metrics
+                .noncanonical_signatures
+                .with_label_values(&[&authority, outcome])
+                .get()
+        };
+
+        // Signatures are produced in the canonical form
+        assert_eq!(
+            canonicalize_sign_info(sig.clone(), &action, &metrics).unwrap(),
+            sig
+        );
+        assert_eq!(count("normalized"), 0);
+
+        // The high-s twin is normalized back
+        let high_s = BridgeAuthoritySignInfo {
+            signature: BridgeAuthorityRecoverableSignature::from_bytes(&high_s_twin(
+                &sig.signature,
+            ))
+            .unwrap(),
+            ..sig.clone()
+        };
+        assert_eq!(
+            canonicalize_sign_info(high_s, &action, &metrics).unwrap(),
+            sig
+        );
+        assert_eq!(count("normalized"), 1);
+
+        // High s without the matching recovery id recovers another key once normalized
+        let mut bytes = high_s_twin(&sig.signature);
+        bytes[64] ^= 1;
+        let mismatched = BridgeAuthoritySignInfo {
+            signature: BridgeAuthorityRecoverableSignature::from_bytes(&bytes).unwrap(),
+            ..sig.clone()
+        };
+        assert!(matches!(
+            canonicalize_sign_info(mismatched, &action, &metrics).unwrap_err(),
+            BridgeError::InvalidBridgeAuthoritySignature(_)
+        ));
+        assert_eq!(count("rejected"), 1);
+    }
+
+    proptest! {
+        #[test]
+        fn test_normalized_signatures_verify(
+            seed: [u8; 32],
+            nonce: u64,
+            amount: u64,
+            high_s: bool,
+        ) {
+            let secret = BridgeAuthorityKeyPair::generate(&mut StdRng::from_seed(seed));
+            let action = get_test_sui_to_eth_bridge_action(
+                None,
+                None,
+                Some(nonce),
+                Some(amount),
+                None,
+                None,
+                None,
+            );
+            let mut sig = BridgeAuthoritySignInfo::new(&action, &secret);
+            if high_s {
+                sig.signature =
+                    BridgeAuthorityRecoverableSignature::from_bytes(&high_s_twin(&sig.signature))
+                        .unwrap();
+            }
+            let (canonical, normalized) = sig.canonicalize(&action).unwrap();
+            prop_assert_eq!(normalized, high_s);
+            prop_assert!(normalize_signature(&canonical.signature).unwrap().is_none());
+            prop_assert!(canonical
+                .authority_pub_key
+                .verify_recoverable_with_hash::<Keccak256>(
+                    &action.to_signing_bytes(),
+                    &canonical.signature
+                )
+                .is_ok());
+        }
+    }
 }
@@ -0,0 +1,766 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Resets of the syncer cursors while the node runs, requested by an operator through
+//! `POST /admin/reset_cursor`. A new Eth cursor must not be past the last finalized block,
+//! a new Sui cursor must be an event of the module on chain. Resets that would skip events
+//! that were not processed yet are refused unless forced. The previous value is recorded in
+//! the `cursor_resets` table, with the caller and the idempotency key of the request in
+//! `cursor_reset_origins`, and the listening task of the syncer continues from the new
+//! value without a restart. Events that it queried before the reset are still processed.
+
+use crate::api_types::{CursorComponent, ResetCursorRequest};
+use crate::error::{BridgeError, BridgeResult};
+use crate::eth_client::EthClient;
+use crate::eth_syncer::EthTargetAddresses;
+use crate::storage::{
+    AdminRequestOrigin, BridgeOrchestratorTables, CursorResetRecord, ResetCursor,
+};
+use crate::sui_client::{SuiClient, SuiClientInner};
+use crate::sui_syncer::SuiTargetModules;
+use ethers::types::Address as EthAddress;
+use std::collections::HashMap;
+use std::str::FromStr;
+use std::sync::Arc;
+use std::time::{SystemTime, UNIX_EPOCH};
+use sui_json_rpc_types::SuiEvent;
+use sui_types::event::EventID;
+use sui_types::{Identifier, BRIDGE_PACKAGE_ID};
+use tokio::sync::watch;
+use tracing::info;
+
+/// Receives the cursor of one listening task of a syncer whenever it is reset.
+pub struct CursorReload<T> {
+    receiver: Option<watch::Receiver<T>>,
+}
+
+impl<T> Default for CursorReload<T> {
+    fn default() -> Self {
+        Self { receiver: None }
+    }
+}
+
+impl<T: Clone> CursorReload<T> {
+    /// Returns the new cursor if it was reset since the last call.
+    pub fn try_take(&mut self) -> Option<T> {
+        let receiver = self.receiver.as_mut()?;
+        if !receiver.has_changed().unwrap_or(false) {
+            return None;
+        }
+        Some(receiver.borrow_and_update().clone())
+    }
+
+    /// Waits until the cursor is reset and returns it, forever if it can't be reset.
+    pub async fn wait(&mut self) -> T {
+        match self.receiver.as_mut() {
+            Some(receiver) if receiver.changed().await.is_ok() => {
+                receiver.borrow_and_update().clone()
+            }
+            _ => std::future::pending().await,
+        }
+    }
+}
+
+pub struct CursorResetter<C, P> {
+    store: Arc<BridgeOrchestratorTables>,
+    sui_client: Arc<SuiClient<C>>,
+    eth_client: Arc<EthClient<P>>,
+    // The next block to query, by watched contract
+    eth_cursors: HashMap<EthAddress, watch::Sender<u64>>,
+    // The last processed event, by watched module
+    sui_cursors: HashMap<Identifier, watch::Sender<Option<EventID>>>,
+}
+
+impl<C, P> CursorResetter<C, P>
+where
+    C: SuiClientInner + 'static,
+    P: ethers::providers::JsonRpcClient + 'static,
+{
+    pub fn new(
+        store: Arc<BridgeOrchestratorTables>,
+        sui_client: Arc<SuiClient<C>>,
+        eth_client: Arc<EthClient<P>>,
+        eth_contracts: &EthTargetAddresses,
+        sui_modules: &SuiTargetModules,
+    ) -> Self {
+        Self {
+            store,
+            sui_client,
+            eth_client,
+            eth_cursors: eth_contracts
+                .iter()
+                .map(|(contract, start_block)| (*contract, watch::channel(*start_block).0))
+                .collect(),
+            sui_cursors: sui_modules
+                .iter()
+                .map(|(module, cursor)| (module.clone(), watch::channel(*cursor).0))
+                .collect(),
+        }
+    }
+
+    /// Returns the reloads to pass to `EthSyncer::with_cursor_reloads`.
+    pub fn eth_cursor_reloads(&self) -> HashMap<EthAddress, CursorReload<u64>> {
+        self.eth_cursors
+            .iter()
+            .map(|(contract, sender)| {
+                (
+                    *contract,
+                    CursorReload {
+                        receiver: Some(sender.subscribe()),
+                    },
+                )
+            })
+            .collect()
+    }
+
+    /// Returns the reloads to pass to `SuiSyncer::with_cursor_reloads`.
+    pub fn sui_cursor_reloads(&self) -> HashMap<Identifier, CursorReload<Option<EventID>>> {
+        self.sui_cursors
+            .iter()
+            .map(|(module, sender)| {
+                (
+                    module.clone(),
+                    CursorReload {
+                        receiver: Some(sender.subscribe()),
+                    },
+                )
+            })
+            .collect()
+    }
+
+    pub fn store(&self) -> &Arc<BridgeOrchestratorTables> {
+        &self.store
+    }
+
+    /// Resets the cursor of `request`. `origin` is recorded with it in the audit log.
+    pub async fn reset_cursor(
+        &self,
+        request: ResetCursorRequest,
+        origin: Option<&AdminRequestOrigin>,
+    ) -> BridgeResult<CursorResetRecord> {
+        let cursor = match request.component {
+            CursorComponent::Eth => self.check_eth_reset(&request).await?,
+            CursorComponent::Sui => self.check_sui_reset(&request).await?,
+        };
+        let record = CursorResetRecord {
+            cursor,
+            forced: request.force,
+            reset_at_ms: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .expect("Time went backwards")
+                .as_millis() as u64,
+        };
+        self.store.reset_syncer_cursor(&record, origin)?;
+        // Indexing safe: the identifiers were checked to be watched
+        match &record.cursor {
+            ResetCursor::Eth {
+                contract,
+                new_block,
+                ..
+            } => {
+                // The stored value is the last processed block
+                self.eth_cursors[contract].send_replace(new_block + 1);
+            }
+            ResetCursor::Sui {
+                module,
+                new_event_id,
+                ..
+            } => {
+                self.sui_cursors[module].send_replace(Some(*new_event_id));
+            }
+        }
+        info!(
+            "Reset syncer cursor: {:?}, requested by {:?}",
+            record, origin
+        );
+        Ok(record)
+    }
+
+    async fn check_eth_reset(&self, request: &ResetCursorRequest) -> BridgeResult<ResetCursor> {
+        let contract = EthAddress::from_str(&request.identifier)
+            .ok()
+            .filter(|contract| self.eth_cursors.contains_key(contract))
+            .ok_or_else(|| {
+                BridgeError::InvalidCursorReset(format!(
+                    "{} is not a watched Eth contract",
+                    request.identifier
+                ))
+            })?;
+        let new_block = request.new_value.parse::<u64>().map_err(|_| {
+            BridgeError::InvalidCursorReset(format!("{} is not a block number", request.new_value))
+        })?;
+        let last_finalized_block = self.eth_client.get_last_finalized_block_id().await?;
+        if new_block > last_finalized_block {
+            return Err(BridgeError::InvalidCursorReset(format!(
+                "Block {} is after the last finalized block {}",
+                new_block, last_finalized_block
+            )));
+        }
+        let old_block = self.store.get_eth_event_cursors(&[contract])?[0];
+        if let Some(old_block) = old_block {
+            if new_block > old_block && !request.force {
+                return Err(BridgeError::InvalidCursorReset(format!(
+                    "Blocks {} to {} of contract {:?} were not processed, set force to skip them",
+                    old_block + 1,
+                    new_block,
+                    contract
+                )));
+            }
+        }
+        Ok(ResetCursor::Eth {
+            contract,
+            old_block,
+            new_block,
+        })
+    }
+
+    async fn check_sui_reset(&self, request: &ResetCursorRequest) -> BridgeResult<ResetCursor> {
+        let module = Identifier::new(request.identifier.as_str())
+            .ok()
+            .filter(|module| self.sui_cursors.contains_key(module))
+            .ok_or_else(|| {
+                BridgeError::InvalidCursorReset(format!(
+                    "{} is not a watched Sui module",
+                    request.identifier
+                ))
+            })?;
+        let new_event_id = EventID::try_from(request.new_value.clone()).map_err(|_| {
+            BridgeError::InvalidCursorReset(format!("{} is not an event id", request.new_value))
+        })?;
+        let new_event = self
+            .sui_client
+            .get_event_by_id(new_event_id)
+            .await?
+            .filter(|event| {
+                event.type_.address.as_ref() == BRIDGE_PACKAGE_ID.as_ref()
+                    && event.type_.module == module
+            })
+            .ok_or_else(|| {
+                BridgeError::InvalidCursorReset(format!(
+                    "Event {} of module {} does not exist",
+                    request.new_value, module
+                ))
+            })?;
+        let old_event_id = self.store.get_sui_event_cursors(&[module.clone()])?[0];
+        if let Some(old_event_id) = old_event_id {
+            // A current cursor that doesn't exist on chain can't be compared, and is
+            // presumably what is being fixed
+            if let Some(old_event) = self.sui_client.get_event_by_id(old_event_id).await? {
+                if !is_not_after(&new_event, &old_event) && !request.force {
+                    return Err(BridgeError::InvalidCursorReset(format!(
+                        "Events of module {} after {} were not processed, set force to skip them",
+                        module,
+                        String::from(old_event_id)
+                    )));
+                }
+            }
+        }
+        Ok(ResetCursor::Sui {
+            module,
+            old_event_id,
+            new_event_id,
+        })
+    }
+}
+
+// Whether `event` is known to be emitted no later than `other`. Events of different
+// transactions in the same checkpoint can't be ordered.
+fn is_not_after(event: &SuiEvent, other: &SuiEvent) -> bool {
+    if event.id.tx_digest == other.id.tx_digest {
+        return event.id.event_seq <= other.id.event_seq;
+    }
+    matches!(
+        (event.timestamp_ms, other.timestamp_ms),
+        (Some(timestamp_ms), Some(other_timestamp_ms)) if timestamp_ms < other_timestamp_ms
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api_types::{AdminAuditEntry, CursorReset};
+    use crate::eth_mock_provider::EthMockProvider;
+    use crate::eth_syncer::EthSyncer;
+    use crate::metrics::BridgeMetrics;
+    use crate::server::admin_auth::{AdminAuthorizer, AdminCredential, AdminGrant, AdminRole};
+    use crate::server::auth::sign_request;
+    use crate::server::idempotency::IDEMPOTENCY_KEY_HEADER;
+    use crate::server::{
+        authorize_admin_routes, make_admin_router, ADMIN_AUDIT_PATH, ADMIN_RESET_CURSOR_PATH,
+    };
+    use crate::sui_mock_client::SuiMockClient;
+    use crate::test_utils::{mock_get_logs, mock_last_finalized_block};
+    use axum::http::StatusCode;
+    use ethers::types::{Log, TxHash, U256, U64};
+    use fastcrypto::ed25519::Ed25519KeyPair;
+    use prometheus::Registry;
+    use std::collections::HashSet;
+    use std::net::SocketAddr;
+    use std::time::Duration;
+    use sui_config::local_ip_utils::get_available_port;
+    use sui_types::bridge::{BRIDGE_COMMITTEE_MODULE_NAME, BRIDGE_MODULE_NAME};
+    use sui_types::crypto::get_key_pair;
+    use sui_types::digests::TransactionDigest;
+
+    fn eth_reset(contract: EthAddress, new_block: u64, force: bool) -> ResetCursorRequest {
+        ResetCursorRequest {
+            component: CursorComponent::Eth,
+            identifier: format!("{:?}", contract),
+            new_value: new_block.to_string(),
+            force,
+        }
+    }
+
+    fn sui_reset(module: &Identifier, new_event_id: EventID, force: bool) -> ResetCursorRequest {
+        ResetCursorRequest {
+            component: CursorComponent::Sui,
+            identifier: module.to_string(),
+            new_value: new_event_id.into(),
+            force,
+        }
+    }
+
+    // Adds an event of `module`, emitted at `timestamp_ms`, to the chain
+    fn add_bridge_event(
+        sui_client_mock: &SuiMockClient,
+        module: &Identifier,
+        timestamp_ms: u64,
+    ) -> EventID {
+        let mut event = SuiEvent::random_for_testing();
+        event.type_.address = BRIDGE_PACKAGE_ID.into();
+        event.type_.module = module.clone();
+        event.timestamp_ms = Some(timestamp_ms);
+        sui_client_mock.add_events_by_tx_digest(event.id.tx_digest, vec![event.clone()]);
+        event.id
+    }
+
+    #[tokio::test]
+    async fn test_reset_eth_cursor() {
+        telemetry_subscribers::init_for_testing();
+        let temp_dir = tempfile::tempdir().unwrap();
+        let store = BridgeOrchestratorTables::new(temp_dir.path());
+        let contract = EthAddress::random();
+        store.update_eth_event_cursor(contract, 100).unwrap();
+        let eth_mock_provider = EthMockProvider::new();
+        mock_last_finalized_block(&eth_mock_provider, 200);
+        let cursor_resetter = Arc::new(CursorResetter::new(
+            store.clone(),
+            Arc::new(SuiClient::new_for_testing(SuiMockClient::default())),
+            Arc::new(EthClient::new_mocked(
+                eth_mock_provider,
+                HashSet::from([contract]),
+            )),
+            &HashMap::from([(contract, 101)]),
+            &HashMap::new(),
+        ));
+        let mut cursor_reload = cursor_resetter
+            .eth_cursor_reloads()
+            .remove(&contract)
+            .unwrap();
+
+        let authorizer = Arc::new(AdminAuthorizer::new(vec![AdminGrant {
+            name: "release".to_string(),
+            role: AdminRole::Operator,
+            credential: AdminCredential::Token("operator-token".to_string()),
+        }]));
+        let port = get_available_port("127.0.0.1");
+        let listener = tokio::net::TcpListener::bind(format!("127.0.0.1:{}", port))
+            .await
+            .unwrap();
+        let router = make_admin_router(cursor_resetter, None, None, authorizer);
+        tokio::spawn(async move {
+            axum::serve(
+                listener,
+                router.into_make_service_with_connect_info::<SocketAddr>(),
+            )
+            .await
+            .unwrap()
+        });
+        let client = reqwest::Client::new();
+        let url = format!("http://127.0.0.1:{}{}", port, ADMIN_RESET_CURSOR_PATH);
+        let send = |request: ResetCursorRequest, idempotency_key: Option<&str>| {
+            let mut http_request = client
+                .post(&url)
+                .json(&request)
+                .bearer_auth("operator-token");
+            if let Some(idempotency_key) = idempotency_key {
+                http_request = http_request.header(IDEMPOTENCY_KEY_HEADER, idempotency_key);
+            }
+            http_request.send()
+        };
+
+        // Resets must be authorized
+        let resp = client
+            .post(&url)
+            .json(&eth_reset(contract, 50, false))
+            .header(IDEMPOTENCY_KEY_HEADER, "reset-0")
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), StatusCode::UNAUTHORIZED);
+        // Keys allowed to request signatures are not operators
+        let (_, key): (_, Ed25519KeyPair) = get_key_pair();
+        let now_ms = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_millis() as u64;
+        let mut request = client
+            .post(&url)
+            .json(&eth_reset(contract, 50, false))
+            .header(IDEMPOTENCY_KEY_HEADER, "reset-0");
+        for (name, value) in sign_request(&key, ADMIN_RESET_CURSOR_PATH, now_ms, "0") {
+            request = request.header(name, value);
+        }
+        let resp = request.send().await.unwrap();
+        assert_eq!(resp.status(), StatusCode::UNAUTHORIZED);
+        assert_eq!(
+            store.get_eth_event_cursors(&[contract]).unwrap()[0],
+            Some(100)
+        );
+
+        // Resets must have an idempotency key
+        let resp = send(eth_reset(contract, 50, false), None).await.unwrap();
+        assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+
+        // Backward
+        let resp = send(eth_reset(contract, 50, false), Some("reset-1"))
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), StatusCode::OK);
+        let reset: CursorReset = resp.json().await.unwrap();
+        assert_eq!(reset.component, CursorComponent::Eth);
+        assert_eq!(reset.identifier, format!("{:?}", contract));
+        assert_eq!(reset.old_value.as_deref(), Some("100"));
+        assert_eq!(reset.new_value, "50");
+        assert!(!reset.forced);
+        assert_eq!(
+            store.get_eth_event_cursors(&[contract]).unwrap()[0],
+            Some(50)
+        );
+        // The syncer continues after the new last processed block
+        assert_eq!(cursor_reload.try_take(), Some(51));
+
+        // A replay with the same key, e.g. a retried curl, gets the same response without
+        // resetting the cursor again
+        let resp = send(eth_reset(contract, 50, false), Some("reset-1"))
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), StatusCode::OK);
+        assert_eq!(resp.json::<CursorReset>().await.unwrap(), reset);
+        assert_eq!(cursor_reload.try_take(), None);
+        assert_eq!(store.get_cursor_resets().len(), 1);
+
+        // Past the last finalized block, even when forced
+        let resp = send(eth_reset(contract, 201, true), Some("reset-2"))
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+        assert!(resp.text().await.unwrap().contains("last finalized block"));
+
+        // Forward past unprocessed blocks
+        let resp = send(eth_reset(contract, 150, false), Some("reset-3"))
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+        assert!(resp.text().await.unwrap().contains("set force"));
+        assert_eq!(
+            store.get_eth_event_cursors(&[contract]).unwrap()[0],
+            Some(50)
+        );
+        assert_eq!(cursor_reload.try_take(), None);
+
+        // unless forced
+        let resp = send(eth_reset(contract, 150, true), Some("reset-4"))
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), StatusCode::OK);
+        assert_eq!(
+            store.get_eth_event_cursors(&[contract]).unwrap()[0],
+            Some(150)
+        );
+        assert_eq!(cursor_reload.try_take(), Some(151));
+
+        // Unknown contract
+        let resp = send(eth_reset(EthAddress::random(), 50, false), Some("reset-5"))
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+
+        let resets = store.get_cursor_resets();
+        assert_eq!(resets.len(), 2);
+        assert_eq!(
+            resets[0].cursor,
+            ResetCursor::Eth {
+                contract,
+                old_block: Some(100),
+                new_block: 50,
+            }
+        );
+        // The audit log records who reset the cursors
+        let origins = store.get_cursor_reset_origins();
+        assert_eq!(
+            origins[&0],
+            AdminRequestOrigin {
+                caller: "admin:release".to_string(),
+                idempotency_key: "reset-1".to_string(),
+            }
+        );
+        assert_eq!(origins[&1].idempotency_key, "reset-4");
+        assert_eq!(CursorReset::from(&resets[0]), reset);
+        assert!(resets[1].forced);
+    }
+
+    #[tokio::test]
+    async fn test_reset_cursor_with_admin_roles() {
+        telemetry_subscribers::init_for_testing();
+        let temp_dir = tempfile::tempdir().unwrap();
+        let store = BridgeOrchestratorTables::new(temp_dir.path());
+        let contract = EthAddress::random();
+        store.update_eth_event_cursor(contract, 100).unwrap();
+        let eth_mock_provider = EthMockProvider::new();
+        mock_last_finalized_block(&eth_mock_provider, 200);
+        let cursor_resetter = Arc::new(CursorResetter::new(
+            store.clone(),
+            Arc::new(SuiClient::new_for_testing(SuiMockClient::default())),
+            Arc::new(EthClient::new_mocked(
+                eth_mock_provider,
+                HashSet::from([contract]),
+            )),
+            &HashMap::from([(contract, 101)]),
+            &HashMap::new(),
+        ));
+        let grant = |name: &str, role, token: &str| AdminGrant {
+            name: name.to_string(),
+            role,
+            credential: AdminCredential::Token(token.to_string()),
+        };
+        let authorizer = Arc::new(AdminAuthorizer::new(vec![
+            grant("oncall", AdminRole::Reader, "reader-token"),
+            grant("release", AdminRole::Operator, "operator-token"),
+        ]));
+        let router = authorize_admin_routes(
+            make_admin_router(cursor_resetter, None, None, authorizer.clone()),
+            Some(authorizer),
+        );
+        let port = get_available_port("127.0.0.1");
+        let listener = tokio::net::TcpListener::bind(format!("127.0.0.1:{}", port))
+            .await
+            .unwrap();
+        tokio::spawn(async move {
+            axum::serve(
+                listener,
+                router.into_make_service_with_connect_info::<SocketAddr>(),
+            )
+            .await
+            .unwrap()
+        });
+        let client = reqwest::Client::new();
+        let url = |path: &str| format!("http://127.0.0.1:{}{}", port, path);
+        let reset = |token: Option<&str>, idempotency_key: &str| {
+            let mut request = client
+                .post(url(ADMIN_RESET_CURSOR_PATH))
+                .json(&eth_reset(contract, 50, false))
+                .header(IDEMPOTENCY_KEY_HEADER, idempotency_key);
+            if let Some(token) = token {
+                request = request.bearer_auth(token);
+            }
+            request.send()
+        };
+        let cursor = || store.get_eth_event_cursors(&[contract]).unwrap()[0];
+
+        let resp = reset(None, "reset-1").await.unwrap();
+        assert_eq!(resp.status(), StatusCode::UNAUTHORIZED);
+        let resp = reset(Some("unknown-token"), "reset-1").await.unwrap();
+        assert_eq!(resp.status(), StatusCode::UNAUTHORIZED);
+        // Readers can't mutate
+        let resp = reset(Some("reader-token"), "reset-1").await.unwrap();
+        assert_eq!(resp.status(), StatusCode::FORBIDDEN);
+        assert_eq!(cursor(), Some(100));
+        assert!(store.get_admin_audit(None, 10).unwrap().is_empty());
+
+        let resp = reset(Some("operator-token"), "reset-1").await.unwrap();
+        assert_eq!(resp.status(), StatusCode::OK);
+        assert_eq!(cursor(), Some(50));
+        assert_eq!(
+            store.get_cursor_reset_origins()[&0],
+            AdminRequestOrigin {
+                caller: "admin:release".to_string(),
+                idempotency_key: "reset-1".to_string(),
+            }
+        );
+
+        // The audit log requires a role, and readers have it
+        let resp = client.get(url(ADMIN_AUDIT_PATH)).send().await.unwrap();
+        assert_eq!(resp.status(), StatusCode::UNAUTHORIZED);
+        let resp = client
+            .get(url(ADMIN_AUDIT_PATH))
+            .bearer_auth("reader-token")
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), StatusCode::OK);
+        let audit: Vec<AdminAuditEntry> = resp.json().await.unwrap();
+        assert_eq!(audit.len(), 1);
+        let entry = &audit[0];
+        assert_eq!(entry.index, 0);
+        assert_eq!(entry.caller, "admin:release");
+        assert_eq!(entry.method, "POST");
+        assert_eq!(entry.path, ADMIN_RESET_CURSOR_PATH);
+        assert_eq!(entry.idempotency_key, "reset-1");
+        assert_eq!(entry.status, 200);
+        assert!(entry.requested_at_ms <= store.get_cursor_resets()[0].reset_at_ms);
+    }
+
+    #[tokio::test]
+    async fn test_reset_sui_cursor() {
+        telemetry_subscribers::init_for_testing();
+        let temp_dir = tempfile::tempdir().unwrap();
+        let store = BridgeOrchestratorTables::new(temp_dir.path());
+        let module = BRIDGE_MODULE_NAME.to_owned();
+        let sui_client_mock = SuiMockClient::default();
+        let current = add_bridge_event(&sui_client_mock, &module, 2000);
+        let earlier = add_bridge_event(&sui_client_mock, &module, 1000);
+        let later = add_bridge_event(&sui_client_mock, &module, 3000);
+        let other_module = add_bridge_event(
+            &sui_client_mock,
+            &BRIDGE_COMMITTEE_MODULE_NAME.to_owned(),
+            500,
+        );
+        store
+            .update_sui_event_cursor(module.clone(), current)
+            .unwrap();
+        let cursor_resetter = CursorResetter::new(
+            store.clone(),
+            Arc::new(SuiClient::new_for_testing(sui_client_mock.clone())),
+            Arc::new(EthClient::new_mocked(
+                EthMockProvider::new(),
+                HashSet::new(),
+            )),
+            &HashMap::new(),
+            &HashMap::from([(module.clone(), Some(current))]),
+        );
+        let mut cursor_reload = cursor_resetter
+            .sui_cursor_reloads()
+            .remove(&module)
+            .unwrap();
+
+        // Backward
+        let record = cursor_resetter
+            .reset_cursor(sui_reset(&module, earlier, false), None)
+            .await
+            .unwrap();
+        assert_eq!(
+            record.cursor,
+            ResetCursor::Sui {
+                module: module.clone(),
+                old_event_id: Some(current),
+                new_event_id: earlier,
+            }
+        );
+        assert_eq!(
+            store.get_sui_event_cursors(&[module.clone()]).unwrap()[0],
+            Some(earlier)
+        );
+        assert_eq!(cursor_reload.try_take(), Some(Some(earlier)));
+
+        // Forward past unprocessed events
+        let err = cursor_resetter
+            .reset_cursor(sui_reset(&module, later, false), None)
+            .await
+            .unwrap_err();
+        assert!(matches!(err, BridgeError::InvalidCursorReset(_)));
+        assert_eq!(
+            store.get_sui_event_cursors(&[module.clone()]).unwrap()[0],
+            Some(earlier)
+        );
+        assert_eq!(cursor_reload.try_take(), None);
+
+        // unless forced
+        cursor_resetter
+            .reset_cursor(sui_reset(&module, later, true), None)
+            .await
+            .unwrap();
+        assert_eq!(cursor_reload.try_take(), Some(Some(later)));
+
+        // The event must be emitted by the module
+        let err = cursor_resetter
+            .reset_cursor(sui_reset(&module, other_module, true), None)
+            .await
+            .unwrap_err();
+        assert!(matches!(err, BridgeError::InvalidCursorReset(_)));
+        // and exist
+        let err = cursor_resetter
+            .reset_cursor(
+                sui_reset(
+                    &module,
+                    EventID {
+                        tx_digest: later.tx_digest,
+                        event_seq: 1,
+                    },
+                    true,
+                ),
+                None,
+            )
+            .await
+            .unwrap_err();
+        assert!(matches!(err, BridgeError::InvalidCursorReset(_)));
+        assert_eq!(store.get_cursor_resets().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_eth_syncer_reloads_reset_cursor() {
+        telemetry_subscribers::init_for_testing();
+        let registry = Registry::new();
+        mysten_metrics::init_metrics(&registry);
+        let temp_dir = tempfile::tempdir().unwrap();
+        let store = BridgeOrchestratorTables::new(temp_dir.path());
+        let contract = EthAddress::random();
+        let eth_mock_provider = EthMockProvider::new();
+        mock_last_finalized_block(&eth_mock_provider, 200);
+        mock_get_logs(&eth_mock_provider, contract, 101, 200, vec![]);
+        let log = Log {
+            address: contract,
+            transaction_hash: Some(TxHash::random()),
+            block_number: Some(U64::from(160)),
+            log_index: Some(U256::from(0)),
+            ..Default::default()
+        };
+        mock_get_logs(&eth_mock_provider, contract, 150, 200, vec![log.clone()]);
+        let eth_client = Arc::new(EthClient::new_mocked(
+            eth_mock_provider,
+            HashSet::from([contract]),
+        ));
+        let eth_contracts = HashMap::from([(contract, 101)]);
+        let cursor_resetter = CursorResetter::new(
+            store.clone(),
+            Arc::new(SuiClient::new_for_testing(SuiMockClient::default())),
+            eth_client.clone(),
+            &eth_contracts,
+            &HashMap::new(),
+        );
+        let (_handles, mut eth_events_rx, _) = EthSyncer::new(eth_client, eth_contracts)
+            .with_cursor_reloads(cursor_resetter.eth_cursor_reloads())
+            .run(Arc::new(BridgeMetrics::for_testing()))
+            .await
+            .unwrap();
+
+        let (_, end_block, logs) = eth_events_rx.recv().await.unwrap();
+        assert_eq!(end_block, 200);
+        assert!(logs.is_empty());
+        store.update_eth_event_cursor(contract, 200).unwrap();
+
+        // The finalized block doesn't change, the syncer queries the blocks again right away
+        cursor_resetter
+            .reset_cursor(eth_reset(contract, 149, false), None)
+            .await
+            .unwrap();
+        let (_, end_block, logs) =
+            tokio::time::timeout(Duration::from_secs(10), eth_events_rx.recv())
+                .await
+                .unwrap()
+                .unwrap();
+        assert_eq!(end_block, 200);
+        assert_eq!(logs.len(), 1);
+        assert_eq!(logs[0].block_number, 160);
+    }
+}
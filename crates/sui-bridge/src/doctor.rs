@@ -0,0 +1,857 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Non-destructive self-test of a bridge node config, run by `sui-bridge-cli doctor`
+//! before starting a node. Each check is an independent async function returning a
+//! `CheckResult`. To add a check, write the function and call it from `run_checks`.
+
+use crate::config::{BridgeNodeConfig, BridgeNodeMode, CustomEvmChainConfig};
+use crate::crypto::BridgeAuthorityPublicKeyBytes;
+use crate::eth_client::{EthClient, EthFinality};
+use crate::metrics::BridgeMetrics;
+use crate::sui_client::{SuiClient, SuiClientInner};
+use crate::types::reserved_evm_chain_id;
+use crate::url_check::{
+    check_registered_url as check_registered_url_of_member, RegisteredUrlCheck,
+};
+use ethers::providers::JsonRpcClient;
+use std::collections::{BTreeMap, HashSet};
+use std::fmt::Write;
+use std::net::{IpAddr, Ipv4Addr, SocketAddr};
+use std::path::Path;
+use std::sync::Arc;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use sui_keys::keypair_file::read_key;
+use sui_sdk::SuiClient as SuiSdkClient;
+use sui_types::base_types::{ObjectID, SuiAddress};
+use sui_types::bridge::BridgeChainId;
+use sui_types::crypto::SuiKeyPair;
+use sui_types::digests::{get_mainnet_chain_identifier, get_testnet_chain_identifier};
+use sui_types::object::Owner;
+
+/// Gas coins with less balance than this (1 SUI) can run out soon.
+pub const LOW_GAS_BALANCE_MIST: u64 = 1_000_000_000;
+/// How far the system clock can be from the latest Sui checkpoint timestamp.
+pub const MAX_CLOCK_SKEW: Duration = Duration::from_secs(30);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CheckStatus {
+    Pass,
+    Warn,
+    Fail,
+}
+
+impl CheckStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Pass => "PASS",
+            Self::Warn => "WARN",
+            Self::Fail => "FAIL",
+        }
+    }
+
+    // ANSI escape sequence of the color of the status in a terminal
+    fn color(&self) -> &'static str {
+        match self {
+            Self::Pass => "\x1b[32m",
+            Self::Warn => "\x1b[33m",
+            Self::Fail => "\x1b[31m",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CheckResult {
+    pub name: &'static str,
+    pub status: CheckStatus,
+    pub detail: String,
+}
+
+impl CheckResult {
+    pub(crate) fn pass(name: &'static str, detail: impl Into<String>) -> Self {
+        Self {
+            name,
+            status: CheckStatus::Pass,
+            detail: detail.into(),
+        }
+    }
+
+    pub(crate) fn warn(name: &'static str, detail: impl Into<String>) -> Self {
+        Self {
+            name,
+            status: CheckStatus::Warn,
+            detail: detail.into(),
+        }
+    }
+
+    pub(crate) fn fail(name: &'static str, detail: impl Into<String>) -> Self {
+        Self {
+            name,
+            status: CheckStatus::Fail,
+            detail: detail.into(),
+        }
+    }
+}
+
+/// Runs every check against `config`. Checks that need a chain connection are skipped
+/// with a warning when the connection can't be established.
+pub async fn run_checks(config: &BridgeNodeConfig) -> Vec<CheckResult> {
+    let mut results = vec![check_mode(config)];
+    let sui_client = match SuiClient::<SuiSdkClient>::new(&config.sui.sui_rpc_url).await {
+        Ok(sui_client) => {
+            results.push(check_sui_rpc(&sui_client, config.sui.sui_bridge_chain_id).await);
+            Some(sui_client)
+        }
+        Err(e) => {
+            results.push(CheckResult::fail(
+                "sui_rpc",
+                format!("Sui RPC {} is unreachable: {:?}", config.sui.sui_rpc_url, e),
+            ));
+            None
+        }
+    };
+    match EthClient::new(
+        &config.eth.eth_rpc_url,
+        HashSet::new(),
+        Arc::new(BridgeMetrics::for_testing()),
+    )
+    .await
+    {
+        Ok(eth_client) => results.push(
+            check_eth_rpc(
+                &eth_client,
+                config.eth.eth_bridge_chain_id,
+                config.eth.custom_evm_chains.as_ref(),
+            )
+            .await,
+        ),
+        Err(e) => results.push(CheckResult::fail(
+            "eth_rpc",
+            format!("Eth RPC {} is unreachable: {:?}", config.eth.eth_rpc_url, e),
+        )),
+    }
+
+    // Observers have no key, so there is no registration or gas coin to check
+    let is_committee = config.mode() != BridgeNodeMode::Observer;
+    match &sui_client {
+        Some(sui_client) => {
+            if let Some(key_path) = config
+                .bridge_authority_key_path
+                .as_ref()
+                .filter(|_| is_committee)
+            {
+                results.push(check_authority_key(sui_client, key_path).await);
+                results.push(
+                    check_registered_url(sui_client, key_path, config.external_url.as_deref())
+                        .await,
+                );
+                if config.run_client {
+                    results.push(check_client_gas_coin(sui_client, config).await);
+                }
+            }
+            results.push(check_clock_skew(sui_client, MAX_CLOCK_SKEW).await);
+        }
+        None => {
+            let mut skipped = vec!["clock_skew"];
+            if is_committee {
+                skipped.extend(["authority_key", "registered_url"]);
+                if config.run_client {
+                    skipped.push("gas_coin");
+                }
+            }
+            for name in skipped {
+                results.push(CheckResult::warn(name, "Skipped, Sui RPC is unreachable"));
+            }
+        }
+    }
+
+    results.push(check_port_bindable("server_port", config.server_listen_port).await);
+    results.push(check_port_bindable("metrics_port", config.metrics_port).await);
+    results
+}
+
+/// Checks that the config fits its mode, see `BridgeNodeConfig::validate_mode`.
+pub fn check_mode(config: &BridgeNodeConfig) -> CheckResult {
+    const NAME: &str = "mode";
+    match config.validate_mode() {
+        Ok(()) => CheckResult::pass(NAME, format!("Running in {:?} mode", config.mode())),
+        Err(e) => CheckResult::fail(NAME, e.to_string()),
+    }
+}
+
+/// Formats `results` as a table, one check per row.
+pub fn format_check_results(results: &[CheckResult]) -> String {
+    format_check_table(results, false)
+}
+
+/// Like `format_check_results`, with the statuses colored for a terminal when `color` is
+/// set.
+pub fn format_check_table(results: &[CheckResult], color: bool) -> String {
+    let name_width = results
+        .iter()
+        .map(|result| result.name.len())
+        .max()
+        .unwrap_or_default()
+        .max("CHECK".len());
+    let mut table = format!("{:<name_width$}  STATUS  DETAIL\n", "CHECK");
+    for result in results {
+        let status = format!("{:<6}", result.status.as_str());
+        let status = if color {
+            format!("{}{}\x1b[0m", result.status.color(), status)
+        } else {
+            status
+        };
+        writeln!(
+            table,
+            "{:<name_width$}  {}  {}",
+            result.name, status, result.detail
+        )
+        .unwrap();
+    }
+    table
+}
+
+/// Checks that Sui RPC is reachable and the bridge on it has the expected chain id.
+pub async fn check_sui_rpc<C: SuiClientInner>(
+    sui_client: &SuiClient<C>,
+    expected_bridge_chain_id: u8,
+) -> CheckResult {
+    const NAME: &str = "sui_rpc";
+    let Ok(expected_chain) = BridgeChainId::try_from(expected_bridge_chain_id) else {
+        return CheckResult::fail(
+            NAME,
+            format!("Invalid Sui bridge chain id {}", expected_bridge_chain_id),
+        );
+    };
+    let identifier = match sui_client.get_chain_identifier().await {
+        Ok(identifier) => identifier,
+        Err(e) => return CheckResult::fail(NAME, format!("Sui RPC is unreachable: {:?}", e)),
+    };
+    let expected_identifier = match expected_chain {
+        BridgeChainId::SuiMainnet => Some(get_mainnet_chain_identifier().to_string()),
+        BridgeChainId::SuiTestnet => Some(get_testnet_chain_identifier().to_string()),
+        _ => None,
+    };
+    if let Some(expected_identifier) = expected_identifier {
+        if identifier != expected_identifier {
+            return CheckResult::fail(
+                NAME,
+                format!(
+                    "Expected Sui chain identifier {} for {:?}, but connected to {}",
+                    expected_identifier, expected_chain, identifier
+                ),
+            );
+        }
+    }
+    let summary = match sui_client.get_bridge_summary().await {
+        Ok(summary) => summary,
+        Err(e) => return CheckResult::fail(NAME, format!("Failed to get bridge summary: {:?}", e)),
+    };
+    if summary.chain_id != expected_bridge_chain_id {
+        return CheckResult::fail(
+            NAME,
+            format!(
+                "Expected bridge chain id {}, but connected to {}",
+                expected_bridge_chain_id, summary.chain_id
+            ),
+        );
+    }
+    CheckResult::pass(
+        NAME,
+        format!(
+            "Connected to Sui chain {}, bridge chain {:?}",
+            identifier, expected_chain
+        ),
+    )
+}
+
+/// Checks that Eth RPC is reachable, on the expected chain and serves finalized blocks.
+/// With `EthCustom`, the chain must be one of `custom_evm_chains` if set.
+pub async fn check_eth_rpc<P: JsonRpcClient>(
+    eth_client: &EthClient<P>,
+    expected_bridge_chain_id: u8,
+    custom_evm_chains: Option<&BTreeMap<u64, CustomEvmChainConfig>>,
+) -> CheckResult {
+    const NAME: &str = "eth_rpc";
+    let Ok(expected_chain) = BridgeChainId::try_from(expected_bridge_chain_id) else {
+        return CheckResult::fail(
+            NAME,
+            format!("Invalid Eth bridge chain id {}", expected_bridge_chain_id),
+        );
+    };
+    let chain_id = match eth_client.get_chain_id().await {
+        Ok(chain_id) => chain_id,
+        Err(e) => return CheckResult::fail(NAME, format!("Eth RPC is unreachable: {:?}", e)),
+    };
+    let mut finality = EthFinality::default();
+    if let Some(expected_chain_id) = reserved_evm_chain_id(expected_chain) {
+        if chain_id != expected_chain_id {
+            return CheckResult::fail(
+                NAME,
+                format!(
+                    "Expected Eth chain id {} for {:?}, but connected to {}",
+                    expected_chain_id, expected_chain, chain_id
+                ),
+            );
+        }
+    } else if let Some(custom_evm_chains) = custom_evm_chains {
+        let Some(chain) = custom_evm_chains.get(&chain_id) else {
+            return CheckResult::fail(
+                NAME,
+                format!(
+                    "Connected to Eth chain id {}, which is not in `custom_evm_chains`",
+                    chain_id
+                ),
+            );
+        };
+        finality = chain.finality.unwrap_or_default();
+    }
+    match eth_client.get_last_final_block_id(finality).await {
+        Ok(block) => CheckResult::pass(
+            NAME,
+            format!(
+                "Connected to Eth chain {}, last finalized block {}",
+                chain_id, block
+            ),
+        ),
+        Err(e) => CheckResult::fail(
+            NAME,
+            format!("Eth RPC does not serve finalized blocks: {:?}", e),
+        ),
+    }
+}
+
+/// Checks that the url registered on chain for the bridge authority key is `external_url`.
+pub async fn check_registered_url<C: SuiClientInner>(
+    sui_client: &SuiClient<C>,
+    key_path: &Path,
+    external_url: Option<&str>,
+) -> CheckResult {
+    const NAME: &str = "registered_url";
+    let key = match read_key(&key_path.to_path_buf(), true) {
+        Ok(SuiKeyPair::Secp256k1(key)) => key,
+        Ok(_) => unreachable!("we required secp256k1 key in `read_key`"),
+        Err(e) => {
+            return CheckResult::fail(NAME, format!("Can't read bridge authority key: {:?}", e))
+        }
+    };
+    let pubkey = BridgeAuthorityPublicKeyBytes::from(&key.public);
+    let committee = match sui_client.get_bridge_committee().await {
+        Ok(committee) => committee,
+        Err(e) => {
+            return CheckResult::fail(NAME, format!("Failed to get bridge committee: {:?}", e))
+        }
+    };
+    match check_registered_url_of_member(&committee, &pubkey, external_url) {
+        RegisteredUrlCheck::Match { url } => {
+            CheckResult::pass(NAME, format!("Registered url {} matches external_url", url))
+        }
+        RegisteredUrlCheck::Mismatch {
+            registered_url,
+            external_url,
+        } => CheckResult::fail(
+            NAME,
+            format!(
+                "Registered url {} does not match external_url {}",
+                registered_url, external_url
+            ),
+        ),
+        RegisteredUrlCheck::NotRegistered => CheckResult::warn(
+            NAME,
+            "Key is not registered in the bridge committee yet, no url to compare",
+        ),
+        RegisteredUrlCheck::NotConfigured { registered_url } => CheckResult::warn(
+            NAME,
+            format!(
+                "external_url is not configured, registered url is {}",
+                registered_url
+            ),
+        ),
+    }
+}
+
+/// Checks that the bridge authority key is an active member of the committee on chain.
+pub async fn check_authority_key<C: SuiClientInner>(
+    sui_client: &SuiClient<C>,
+    key_path: &Path,
+) -> CheckResult {
+    const NAME: &str = "authority_key";
+    let key = match read_key(&key_path.to_path_buf(), true) {
+        Ok(SuiKeyPair::Secp256k1(key)) => key,
+        Ok(_) => unreachable!("we required secp256k1 key in `read_key`"),
+        Err(e) => {
+            return CheckResult::fail(NAME, format!("Can't read bridge authority key: {:?}", e))
+        }
+    };
+    let pubkey = BridgeAuthorityPublicKeyBytes::from(&key.public);
+    let committee = match sui_client.get_bridge_committee().await {
+        Ok(committee) => committee,
+        Err(e) => {
+            return CheckResult::fail(NAME, format!("Failed to get bridge committee: {:?}", e))
+        }
+    };
+    match committee.member(&pubkey) {
+        None => CheckResult::fail(
+            NAME,
+            format!(
+                "Key with Eth address {:?} is not registered in the bridge committee",
+                pubkey.to_eth_address()
+            ),
+        ),
+        Some(member) if member.is_blocklisted => CheckResult::fail(
+            NAME,
+            format!(
+                "Key with Eth address {:?} is blocklisted",
+                pubkey.to_eth_address()
+            ),
+        ),
+        Some(member) => CheckResult::pass(
+            NAME,
+            format!(
+                "Key with Eth address {:?} is registered with voting power {} and url {}",
+                pubkey.to_eth_address(),
+                member.voting_power,
+                member.base_url
+            ),
+        ),
+    }
+}
+
+async fn check_client_gas_coin<C: SuiClientInner>(
+    sui_client: &SuiClient<C>,
+    config: &BridgeNodeConfig,
+) -> CheckResult {
+    let client_key = match (
+        &config.sui.bridge_client_key_path,
+        &config.bridge_authority_key_path,
+    ) {
+        (Some(path), _) => read_key(path, false),
+        (None, Some(path)) => read_key(path, true),
+        (None, None) => Err(anyhow::anyhow!("No bridge client key is configured")),
+    };
+    match client_key {
+        Ok(client_key) => {
+            check_gas_coin(
+                sui_client,
+                SuiAddress::from(&client_key.public()),
+                config.sui.bridge_client_gas_object,
+            )
+            .await
+        }
+        Err(e) => CheckResult::fail("gas_coin", format!("Can't read bridge client key: {:?}", e)),
+    }
+}
+
+/// Checks that the gas object exists, is owned by the client address and has balance.
+pub async fn check_gas_coin<C: SuiClientInner>(
+    sui_client: &SuiClient<C>,
+    client_address: SuiAddress,
+    gas_object_id: Option<ObjectID>,
+) -> CheckResult {
+    const NAME: &str = "gas_coin";
+    let Some(gas_object_id) = gas_object_id else {
+        return CheckResult::warn(
+            NAME,
+            format!(
+                "No gas object configured, the coin with the highest balance of {} will be used",
+                client_address
+            ),
+        );
+    };
+    let (gas_coin, _, owner) = match sui_client.get_gas_data_maybe(gas_object_id).await {
+        Ok(Some(gas_data)) => gas_data,
+        Ok(None) => {
+            return CheckResult::fail(NAME, format!("Gas object {} does not exist", gas_object_id))
+        }
+        Err(e) => {
+            return CheckResult::fail(
+                NAME,
+                format!("Failed to get gas object {}: {:?}", gas_object_id, e),
+            )
+        }
+    };
+    if owner != Owner::AddressOwner(client_address) {
+        return CheckResult::fail(
+            NAME,
+            format!(
+                "Gas object {} is not owned by client address {}, but {:?}",
+                gas_object_id, client_address, owner
+            ),
+        );
+    }
+    match gas_coin.value() {
+        0 => CheckResult::fail(NAME, format!("Gas object {} is empty", gas_object_id)),
+        balance if balance < LOW_GAS_BALANCE_MIST => CheckResult::warn(
+            NAME,
+            format!(
+                "Gas object {} has low balance {} MIST",
+                gas_object_id, balance
+            ),
+        ),
+        balance => CheckResult::pass(
+            NAME,
+            format!("Gas object {} has balance {} MIST", gas_object_id, balance),
+        ),
+    }
+}
+
+/// Checks that `port` can be bound on all interfaces, as the node does.
+pub async fn check_port_bindable(name: &'static str, port: u16) -> CheckResult {
+    let address = SocketAddr::new(IpAddr::V4(Ipv4Addr::UNSPECIFIED), port);
+    match tokio::net::TcpListener::bind(address).await {
+        Ok(_) => CheckResult::pass(name, format!("Port {} is bindable", port)),
+        Err(e) => CheckResult::fail(name, format!("Can't bind port {}: {:?}", port, e)),
+    }
+}
+
+/// Checks that the system clock is within `tolerance` of the latest Sui checkpoint.
+pub async fn check_clock_skew<C: SuiClientInner>(
+    sui_client: &SuiClient<C>,
+    tolerance: Duration,
+) -> CheckResult {
+    const NAME: &str = "clock_skew";
+    let checkpoint_timestamp_ms = match sui_client.get_latest_checkpoint_timestamp_ms().await {
+        Ok(timestamp_ms) => timestamp_ms,
+        Err(e) => {
+            return CheckResult::fail(
+                NAME,
+                format!("Failed to get latest checkpoint timestamp: {:?}", e),
+            )
+        }
+    };
+    let now_ms = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .expect("Time went backwards")
+        .as_millis() as i64;
+    let skew_ms = now_ms - checkpoint_timestamp_ms as i64;
+    let detail = format!(
+        "System clock is {}ms {} the latest Sui checkpoint",
+        skew_ms.unsigned_abs(),
+        if skew_ms >= 0 { "ahead of" } else { "behind" }
+    );
+    if skew_ms.unsigned_abs() > tolerance.as_millis() as u64 {
+        CheckResult::fail(NAME, detail)
+    } else {
+        CheckResult::pass(NAME, detail)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::eth_mock_provider::EthMockProvider;
+    use crate::sui_mock_client::SuiMockClient;
+    use crate::test_utils::{
+        bridge_committee_to_bridge_committee_summary, get_test_authority_and_key,
+        mock_last_finalized_block,
+    };
+    use crate::types::{BridgeCommittee, ETH_MAINNET_CHAIN_ID};
+    use crate::utils::generate_bridge_node_config_and_write_to_file;
+    use ethers::types::{Address as EthAddress, U64};
+    use fastcrypto::traits::EncodeDecodeBase64;
+    use sui_config::Config;
+    use sui_types::base_types::random_object_ref;
+    use sui_types::gas_coin::GasCoin;
+
+    #[tokio::test]
+    async fn test_check_sui_rpc() {
+        let sui_client = SuiClient::new_for_testing(SuiMockClient::default());
+        // Mock bridge is on SuiMainnet (chain id 0)
+        let result = check_sui_rpc(&sui_client, BridgeChainId::SuiCustom as u8).await;
+        assert_eq!(result.status, CheckStatus::Fail);
+        assert!(result.detail.contains("Expected bridge chain id 2"));
+        // Mock chain identifier is not mainnet
+        let result = check_sui_rpc(&sui_client, BridgeChainId::SuiMainnet as u8).await;
+        assert_eq!(result.status, CheckStatus::Fail);
+        assert!(result.detail.contains("Expected Sui chain identifier"));
+        let result = check_sui_rpc(&sui_client, 100).await;
+        assert_eq!(result.status, CheckStatus::Fail);
+    }
+
+    #[tokio::test]
+    async fn test_check_eth_rpc() {
+        let provider = EthMockProvider::new();
+        let eth_client = EthClient::new_mocked(provider.clone(), HashSet::new());
+        let eth_custom = BridgeChainId::EthCustom as u8;
+        // Unreachable
+        let result = check_eth_rpc(&eth_client, eth_custom, None).await;
+        assert_eq!(result.status, CheckStatus::Fail);
+        assert!(result.detail.contains("unreachable"));
+
+        provider
+            .add_response("eth_chainId", (), U64::from(ETH_MAINNET_CHAIN_ID))
+            .unwrap();
+        // Wrong chain
+        let result = check_eth_rpc(&eth_client, BridgeChainId::EthSepolia as u8, None).await;
+        assert_eq!(result.status, CheckStatus::Fail);
+        assert!(result.detail.contains("Expected Eth chain id 11155111"));
+        // No finalized blocks
+        let result = check_eth_rpc(&eth_client, eth_custom, None).await;
+        assert_eq!(result.status, CheckStatus::Fail);
+        assert!(result.detail.contains("finalized"));
+
+        mock_last_finalized_block(&provider, 100);
+        let result = check_eth_rpc(&eth_client, BridgeChainId::EthMainnet as u8, None).await;
+        assert_eq!(result.status, CheckStatus::Pass);
+
+        // A custom EVM chain without finalized blocks
+        let provider = EthMockProvider::new();
+        let eth_client = EthClient::new_mocked(provider.clone(), HashSet::new());
+        provider
+            .add_response("eth_chainId", (), U64::from(31337))
+            .unwrap();
+        provider
+            .add_response("eth_blockNumber", (), U64::from(100))
+            .unwrap();
+        let mut custom_evm_chains = BTreeMap::from([(
+            31338,
+            CustomEvmChainConfig {
+                bridge_proxy_address: format!("{:?}", EthAddress::random()),
+                finality: None,
+            },
+        )]);
+        let result = check_eth_rpc(&eth_client, eth_custom, Some(&custom_evm_chains)).await;
+        assert_eq!(result.status, CheckStatus::Fail);
+        assert!(result.detail.contains("not in `custom_evm_chains`"));
+        custom_evm_chains.insert(
+            31337,
+            CustomEvmChainConfig {
+                bridge_proxy_address: format!("{:?}", EthAddress::random()),
+                finality: Some(EthFinality::Confirmations(5)),
+            },
+        );
+        let result = check_eth_rpc(&eth_client, eth_custom, Some(&custom_evm_chains)).await;
+        assert_eq!(result.status, CheckStatus::Pass);
+        assert!(result.detail.contains("last finalized block 95"));
+    }
+
+    #[tokio::test]
+    async fn test_check_authority_key() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let sui_client_mock = SuiMockClient::default();
+        let sui_client = SuiClient::new_for_testing(sui_client_mock.clone());
+        let (authority, _, key) = get_test_authority_and_key(10000, 9999);
+        let (mut other_authority, _, other_key) = get_test_authority_and_key(0, 9999);
+        other_authority.is_blocklisted = true;
+        sui_client_mock.set_bridge_committee(bridge_committee_to_bridge_committee_summary(
+            BridgeCommittee::new(vec![authority, other_authority]).unwrap(),
+        ));
+
+        // Missing key file
+        let key_path = temp_dir.path().join("authority.key");
+        let result = check_authority_key(&sui_client, &key_path).await;
+        assert_eq!(result.status, CheckStatus::Fail);
+        assert!(result.detail.contains("Can't read"));
+
+        // Not registered
+        let (_, _, unregistered_key) = get_test_authority_and_key(10000, 9999);
+        std::fs::write(&key_path, unregistered_key.encode_base64()).unwrap();
+        let result = check_authority_key(&sui_client, &key_path).await;
+        assert_eq!(result.status, CheckStatus::Fail);
+        assert!(result.detail.contains("not registered"));
+
+        // Blocklisted
+        std::fs::write(&key_path, other_key.encode_base64()).unwrap();
+        let result = check_authority_key(&sui_client, &key_path).await;
+        assert_eq!(result.status, CheckStatus::Fail);
+        assert!(result.detail.contains("blocklisted"));
+
+        std::fs::write(&key_path, key.encode_base64()).unwrap();
+        let result = check_authority_key(&sui_client, &key_path).await;
+        assert_eq!(result.status, CheckStatus::Pass);
+    }
+
+    #[tokio::test]
+    async fn test_check_registered_url() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let sui_client_mock = SuiMockClient::default();
+        let sui_client = SuiClient::new_for_testing(sui_client_mock.clone());
+        let (authority, _, key) = get_test_authority_and_key(10000, 9999);
+        sui_client_mock.set_bridge_committee(bridge_committee_to_bridge_committee_summary(
+            BridgeCommittee::new(vec![authority]).unwrap(),
+        ));
+        let key_path = temp_dir.path().join("authority.key");
+
+        // Not registered yet
+        let (_, _, unregistered_key) = get_test_authority_and_key(10000, 9999);
+        std::fs::write(&key_path, unregistered_key.encode_base64()).unwrap();
+        let result =
+            check_registered_url(&sui_client, &key_path, Some("http://127.0.0.1:9999")).await;
+        assert_eq!(result.status, CheckStatus::Warn);
+        assert!(result.detail.contains("not registered"));
+
+        std::fs::write(&key_path, key.encode_base64()).unwrap();
+        // Mismatch
+        let result =
+            check_registered_url(&sui_client, &key_path, Some("http://127.0.0.1:9998")).await;
+        assert_eq!(result.status, CheckStatus::Fail);
+        assert!(result.detail.contains("does not match"));
+
+        let result = check_registered_url(&sui_client, &key_path, None).await;
+        assert_eq!(result.status, CheckStatus::Warn);
+
+        let result =
+            check_registered_url(&sui_client, &key_path, Some("http://127.0.0.1:9999/")).await;
+        assert_eq!(result.status, CheckStatus::Pass);
+    }
+
+    #[tokio::test]
+    async fn test_check_gas_coin() {
+        let sui_client_mock = SuiMockClient::default();
+        let sui_client = SuiClient::new_for_testing(sui_client_mock.clone());
+        let client_address = SuiAddress::random_for_testing_only();
+
+        let result = check_gas_coin(&sui_client, client_address, None).await;
+        assert_eq!(result.status, CheckStatus::Warn);
+
+        // Does not exist
+        let gas_object_ref = random_object_ref();
+        let result = check_gas_coin(&sui_client, client_address, Some(gas_object_ref.0)).await;
+        assert_eq!(result.status, CheckStatus::Fail);
+        assert!(result.detail.contains("does not exist"));
+
+        // Owned by another address
+        sui_client_mock.add_gas_object_info(
+            GasCoin::new_for_testing(LOW_GAS_BALANCE_MIST),
+            gas_object_ref,
+            Owner::AddressOwner(SuiAddress::random_for_testing_only()),
+        );
+        let result = check_gas_coin(&sui_client, client_address, Some(gas_object_ref.0)).await;
+        assert_eq!(result.status, CheckStatus::Fail);
+        assert!(result.detail.contains("is not owned by"));
+
+        // Empty
+        sui_client_mock.add_gas_object_info(
+            GasCoin::new_for_testing(0),
+            gas_object_ref,
+            Owner::AddressOwner(client_address),
+        );
+        let result = check_gas_coin(&sui_client, client_address, Some(gas_object_ref.0)).await;
+        assert_eq!(result.status, CheckStatus::Fail);
+        assert!(result.detail.contains("is empty"));
+
+        // Low balance
+        sui_client_mock.add_gas_object_info(
+            GasCoin::new_for_testing(LOW_GAS_BALANCE_MIST - 1),
+            gas_object_ref,
+            Owner::AddressOwner(client_address),
+        );
+        let result = check_gas_coin(&sui_client, client_address, Some(gas_object_ref.0)).await;
+        assert_eq!(result.status, CheckStatus::Warn);
+
+        sui_client_mock.add_gas_object_info(
+            GasCoin::new_for_testing(LOW_GAS_BALANCE_MIST),
+            gas_object_ref,
+            Owner::AddressOwner(client_address),
+        );
+        let result = check_gas_coin(&sui_client, client_address, Some(gas_object_ref.0)).await;
+        assert_eq!(result.status, CheckStatus::Pass);
+    }
+
+    #[tokio::test]
+    async fn test_check_port_bindable() {
+        let listener = std::net::TcpListener::bind("0.0.0.0:0").unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let result = check_port_bindable("server_port", port).await;
+        assert_eq!(result.status, CheckStatus::Fail);
+
+        drop(listener);
+        let result = check_port_bindable("server_port", port).await;
+        assert_eq!(result.status, CheckStatus::Pass);
+    }
+
+    #[tokio::test]
+    async fn test_check_clock_skew() {
+        let sui_client_mock = SuiMockClient::default();
+        let sui_client = SuiClient::new_for_testing(sui_client_mock.clone());
+        let now_ms = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_millis() as u64;
+
+        sui_client_mock.set_latest_checkpoint_timestamp_ms(now_ms - 60_000);
+        let result = check_clock_skew(&sui_client, MAX_CLOCK_SKEW).await;
+        assert_eq!(result.status, CheckStatus::Fail);
+        assert!(result.detail.contains("ahead of"));
+
+        sui_client_mock.set_latest_checkpoint_timestamp_ms(now_ms + 60_000);
+        let result = check_clock_skew(&sui_client, MAX_CLOCK_SKEW).await;
+        assert_eq!(result.status, CheckStatus::Fail);
+        assert!(result.detail.contains("behind"));
+
+        sui_client_mock.set_latest_checkpoint_timestamp_ms(now_ms);
+        let result = check_clock_skew(&sui_client, MAX_CLOCK_SKEW).await;
+        assert_eq!(result.status, CheckStatus::Pass);
+    }
+
+    #[test]
+    fn test_check_mode() {
+        let path = tempfile::tempdir()
+            .unwrap()
+            .into_path()
+            .join("bridge_node.yaml");
+        generate_bridge_node_config_and_write_to_file(&path, true).unwrap();
+        let mut config = BridgeNodeConfig::load(&path).unwrap();
+        assert_eq!(check_mode(&config).status, CheckStatus::Pass);
+
+        // Committee members need their key
+        let key_path = config.bridge_authority_key_path.take();
+        let result = check_mode(&config);
+        assert_eq!(result.status, CheckStatus::Fail);
+        assert!(result.detail.contains("bridge_authority_key_path"));
+
+        // Or a remote signer, but not both
+        config.remote_signer = Some(crate::config::RemoteSignerConfig {
+            url: "https://signer:9443/sign".to_string(),
+            authority_public_key: "02".repeat(33),
+            client_identity_path: "node.pem".into(),
+            ca_certificate_path: "ca.pem".into(),
+            timeout_ms: None,
+            max_attempts: None,
+            failure_threshold: None,
+            circuit_open_seconds: None,
+        });
+        assert_eq!(check_mode(&config).status, CheckStatus::Pass);
+        config.bridge_authority_key_path = key_path.clone();
+        let result = check_mode(&config);
+        assert_eq!(result.status, CheckStatus::Fail);
+        assert!(result.detail.contains("only one"));
+        config.bridge_authority_key_path = None;
+        config.remote_signer = None;
+
+        // Observers must not be configured to sign or submit
+        config.mode = Some(BridgeNodeMode::Observer);
+        let result = check_mode(&config);
+        assert_eq!(result.status, CheckStatus::Fail);
+        assert!(result.detail.contains("sui.bridge_client_key_path"));
+        config.sui.bridge_client_key_path = None;
+        assert_eq!(check_mode(&config).status, CheckStatus::Pass);
+        config.bridge_authority_key_path = key_path;
+        config.sign_action_types = Some(vec![]);
+        let result = check_mode(&config);
+        assert_eq!(result.status, CheckStatus::Fail);
+        assert!(result
+            .detail
+            .contains("remove bridge_authority_key_path, sign_action_types"));
+        config.bridge_authority_key_path = None;
+        config.sign_action_types = None;
+        config.run_client = false;
+        let result = check_mode(&config);
+        assert_eq!(result.status, CheckStatus::Fail);
+        assert!(result.detail.contains("run_client"));
+    }
+
+    #[test]
+    fn test_format_check_results() {
+        let table = format_check_results(&[
+            CheckResult::pass("sui_rpc", "ok"),
+            CheckResult::fail("authority_key", "not registered"),
+        ]);
+        assert_eq!(
+            table,
+            "CHECK          STATUS  DETAIL\n\
+             sui_rpc        PASS    ok\n\
+             authority_key  FAIL    not registered\n"
+        );
+    }
+}
@@ -0,0 +1,507 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Estimate of how long the node takes to execute its pending actions, to answer how long
+//! until the bridge catches up during incidents. The throughput is measured from the
+//! token transfers executed in a recent window, taken as a Poisson process: the bounds
+//! are those of a 95% confidence interval of its rate. Only token transfers record when
+//! they were executed, so other action types have no estimate of their own, and count
+//! at the throughput of the transfers in the estimate of the whole backlog.
+//!
+//! On routes limited on Sui, the transfers can't go faster than the limiter lets them:
+//! each pending transfer is let through in nonce order as soon as the amounts that leave
+//! the rolling window free enough of the limit, and then counts against the limit itself
+//! for the next 24 hours, see `limit_window`. A route drains at the later of its
+//! throughput and its limit. Transfers above the limit never execute and are left out.
+//! Limits on Eth are not modeled.
+
+use crate::api_types::{ActionTypeDrain, DrainEstimate, DrainTime, LimitWindow, RouteDrain};
+use crate::error::BridgeResult;
+use crate::limit_window::{limit_window, HOUR_MS, LIMIT_WINDOW_HOURS};
+use crate::metrics::BridgeMetrics;
+use crate::notional::{get_prices_and_limits, notional_value, TokenPrice};
+use crate::storage::BridgeOrchestratorTables;
+use crate::sui_client::{SuiClient, SuiClientInner};
+use crate::timestamps::now_ms;
+use crate::types::{BridgeAction, BridgeActionType};
+use std::cmp::Reverse;
+use std::collections::{BTreeMap, BinaryHeap, HashMap};
+use std::sync::Arc;
+use std::time::Duration;
+
+pub const DEFAULT_DRAIN_WINDOW: Duration = Duration::from_secs(30 * 60);
+// Two-sided 95% quantile of the normal distribution
+const Z_95: f64 = 1.96;
+
+pub struct DrainInputs {
+    pub pending: Vec<BridgeAction>,
+    pub in_flight: u64,
+    /// When the token transfers were executed, by (source chain id, destination chain id,
+    /// nonce)
+    pub executed_times: Vec<((u8, u8, u64), u64)>,
+    pub window_ms: u64,
+    pub now_ms: u64,
+    /// Windows of the routes limited on Sui
+    pub limit_windows: Vec<LimitWindow>,
+    pub prices: HashMap<u8, TokenPrice>,
+}
+
+/// Reads the backlog and the throughput of the node from its storage, and the limits of
+/// the routes from the bridge object on Sui.
+pub struct DrainEstimator<C> {
+    store: Arc<BridgeOrchestratorTables>,
+    sui_client: Arc<SuiClient<C>>,
+    metrics: Arc<BridgeMetrics>,
+}
+
+impl<C> DrainEstimator<C>
+where
+    C: SuiClientInner + 'static,
+{
+    pub fn new(
+        store: Arc<BridgeOrchestratorTables>,
+        sui_client: Arc<SuiClient<C>>,
+        metrics: Arc<BridgeMetrics>,
+    ) -> Self {
+        Self {
+            store,
+            sui_client,
+            metrics,
+        }
+    }
+
+    /// Estimates the drain with the throughput of the last `window`.
+    pub async fn estimate(&self, window: Duration) -> BridgeResult<DrainEstimate> {
+        let summary = self.sui_client.get_bridge_summary().await?;
+        let chain_time_ms = self.sui_client.get_latest_checkpoint_timestamp_ms().await?;
+        let now_ms = now_ms();
+        let window_ms = window.as_millis() as u64;
+        let (prices, _) = get_prices_and_limits(&summary);
+        let limit_windows = summary
+            .limiter
+            .transfer_limit
+            .iter()
+            .map(|(source, destination, _)| {
+                limit_window(
+                    (*source, *destination),
+                    &summary.limiter,
+                    chain_time_ms,
+                    now_ms,
+                    None,
+                )
+            })
+            .collect();
+        Ok(estimate_drain(&DrainInputs {
+            pending: self.store.get_all_pending_actions().into_values().collect(),
+            in_flight: self.metrics.executor_state.snapshot().in_flight.len() as u64,
+            executed_times: self
+                .store
+                .get_executed_action_times_since(now_ms.saturating_sub(window_ms)),
+            window_ms,
+            now_ms,
+            limit_windows,
+            prices,
+        }))
+    }
+}
+
+pub fn estimate_drain(inputs: &DrainInputs) -> DrainEstimate {
+    let since_ms = inputs.now_ms.saturating_sub(inputs.window_ms);
+    let mut executed_by_route = HashMap::<(u8, u8), u64>::new();
+    for ((source, destination, _), executed_at_ms) in &inputs.executed_times {
+        if (since_ms..=inputs.now_ms).contains(executed_at_ms) {
+            *executed_by_route
+                .entry((*source, *destination))
+                .or_default() += 1;
+        }
+    }
+    let executed = executed_by_route.values().sum::<u64>();
+
+    let mut pending_by_type = BTreeMap::<u8, u64>::new();
+    // (nonce, token id, amount) of the pending transfers of each route
+    let mut transfers_by_route = BTreeMap::<(u8, u8), Vec<(u64, u8, u64)>>::new();
+    for action in &inputs.pending {
+        *pending_by_type
+            .entry(action.action_type() as u8)
+            .or_default() += 1;
+        if let Some((route, transfer)) = pending_transfer(action) {
+            transfers_by_route.entry(route).or_default().push(transfer);
+        }
+    }
+
+    let routes = transfers_by_route
+        .into_iter()
+        .map(|(route, transfers)| {
+            route_drain(
+                route,
+                transfers,
+                executed_by_route.get(&route).copied().unwrap_or_default(),
+                inputs,
+            )
+        })
+        .collect::<Vec<_>>();
+    let blocked = routes.iter().map(|r| r.blocked_transfers).sum::<u64>();
+    let limit_drain_ms = routes.iter().filter_map(|r| r.limit_drain_ms).max();
+
+    let action_types = pending_by_type
+        .into_iter()
+        .map(|(action_type, pending)| {
+            let action_type =
+                BridgeActionType::try_from(action_type).expect("Action type comes from an action");
+            let (executed_in_window, drain) = if action_type == BridgeActionType::TokenTransfer {
+                let drain = throughput_drain(pending - blocked, executed, inputs.window_ms);
+                (executed, with_limit(drain, limit_drain_ms))
+            } else {
+                (0, throughput_drain(pending, 0, inputs.window_ms))
+            };
+            ActionTypeDrain {
+                action_type: format!("{:?}", action_type),
+                pending,
+                executed_in_window,
+                drain,
+            }
+        })
+        .collect();
+
+    let pending_actions = inputs.pending.len() as u64;
+    DrainEstimate {
+        now_ms: inputs.now_ms,
+        window_ms: inputs.window_ms,
+        pending_actions,
+        in_flight_actions: inputs.in_flight,
+        drain: with_limit(
+            throughput_drain(pending_actions - blocked, executed, inputs.window_ms),
+            limit_drain_ms,
+        ),
+        action_types,
+        routes,
+    }
+}
+
+fn route_drain(
+    (source, destination): (u8, u8),
+    mut transfers: Vec<(u64, u8, u64)>,
+    executed: u64,
+    inputs: &DrainInputs,
+) -> RouteDrain {
+    transfers.sort();
+    let values = transfers
+        .iter()
+        .filter_map(|(_, token_id, amount)| {
+            inputs
+                .prices
+                .get(token_id)
+                .and_then(|price| notional_value(*amount, *price))
+        })
+        .collect::<Vec<_>>();
+    let window = inputs
+        .limit_windows
+        .iter()
+        .find(|window| window.source_chain == source && window.destination_chain == destination);
+    let limit = window.and_then(|window| window.limit);
+    let (blocked, limit_drain_ms) = match (window, limit) {
+        (Some(window), Some(limit)) => {
+            let (fitting, blocked): (Vec<_>, Vec<_>) =
+                values.iter().partition(|value| **value <= limit);
+            (
+                blocked.len() as u64,
+                Some(limit_drain_ms(window, limit, &fitting)),
+            )
+        }
+        _ => (0, None),
+    };
+    let pending = transfers.len() as u64;
+    RouteDrain {
+        source_chain: source,
+        destination_chain: destination,
+        pending,
+        executed_in_window: executed,
+        pending_value: values
+            .iter()
+            .fold(0u64, |total, value| total.saturating_add(*value)),
+        limit,
+        blocked_transfers: blocked,
+        limit_drain_ms,
+        drain: with_limit(
+            throughput_drain(pending - blocked, executed, inputs.window_ms),
+            limit_drain_ms,
+        ),
+    }
+}
+
+// The route and the (nonce, token id, amount) of a token transfer
+fn pending_transfer(action: &BridgeAction) -> Option<((u8, u8), (u64, u8, u64))> {
+    match action {
+        BridgeAction::SuiToEthBridgeAction(a) => {
+            let event = &a.sui_bridge_event;
+            Some((
+                (event.sui_chain_id as u8, event.eth_chain_id as u8),
+                (event.nonce, event.token_id, event.amount_sui_adjusted),
+            ))
+        }
+        BridgeAction::EthToSuiBridgeAction(a) => {
+            let event = &a.eth_bridge_event;
+            Some((
+                (event.eth_chain_id as u8, event.sui_chain_id as u8),
+                (event.nonce, event.token_id, event.sui_adjusted_amount),
+            ))
+        }
+        _ => None,
+    }
+}
+
+// How long `backlog` actions take at the rate of `executed` actions in `window_ms`
+fn throughput_drain(backlog: u64, executed: u64, window_ms: u64) -> DrainTime {
+    if backlog == 0 {
+        return DrainTime {
+            estimate_ms: Some(0),
+            lower_ms: Some(0),
+            upper_ms: Some(0),
+        };
+    }
+    if executed == 0 || window_ms == 0 {
+        return DrainTime::default();
+    }
+    let at_rate = |count: f64| (backlog as f64 * window_ms as f64 / count).round() as u64;
+    let executed = executed as f64;
+    let margin = Z_95 * executed.sqrt();
+    DrainTime {
+        estimate_ms: Some(at_rate(executed)),
+        lower_ms: Some(at_rate(executed + margin)),
+        upper_ms: (executed > margin).then(|| at_rate(executed - margin)),
+    }
+}
+
+// No transfer drains before the limit lets it through
+fn with_limit(drain: DrainTime, limit_drain_ms: Option<u64>) -> DrainTime {
+    let Some(limit_drain_ms) = limit_drain_ms else {
+        return drain;
+    };
+    let at_least = |ms: Option<u64>| ms.map(|ms| ms.max(limit_drain_ms));
+    DrainTime {
+        estimate_ms: at_least(drain.estimate_ms),
+        lower_ms: at_least(drain.lower_ms),
+        upper_ms: at_least(drain.upper_ms),
+    }
+}
+
+// When the limit of `window` lets the last of `values` through, in milliseconds from the
+// chain time of the window. Every value must be at most `limit`.
+fn limit_drain_ms(window: &LimitWindow, limit: u64, values: &[u64]) -> u64 {
+    let mut used = window.total_amount;
+    // (expires at, amount) of the amounts in the window, earliest first
+    let mut expiring = window
+        .buckets
+        .iter()
+        .map(|bucket| Reverse((bucket.expires_at_ms, bucket.amount)))
+        .collect::<BinaryHeap<_>>();
+    let mut time_ms = window.chain_time_ms;
+    for value in values {
+        while used.saturating_add(*value) > limit {
+            let Some(Reverse((expires_at_ms, amount))) = expiring.pop() else {
+                break;
+            };
+            time_ms = time_ms.max(expires_at_ms);
+            used = used.saturating_sub(amount);
+        }
+        used += value;
+        let expires_at_ms = (time_ms / HOUR_MS + LIMIT_WINDOW_HOURS) * HOUR_MS;
+        expiring.push(Reverse((expires_at_ms, *value)));
+    }
+    time_ms - window.chain_time_ms
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api_types::LimitWindowBucket;
+    use crate::test_utils::{get_test_eth_to_sui_bridge_action, get_test_sui_to_eth_bridge_action};
+    use crate::types::{EmergencyAction, EmergencyActionType};
+    use sui_types::bridge::BridgeChainId;
+
+    const MINUTE_MS: u64 = 60_000;
+    const TOKEN_ID: u8 = 1;
+
+    fn capped_window(chain_time_ms: u64, limit: u64, buckets: Vec<(u64, u64)>) -> LimitWindow {
+        let total_amount = buckets.iter().map(|(_, amount)| amount).sum::<u64>();
+        LimitWindow {
+            source_chain: BridgeChainId::EthCustom as u8,
+            destination_chain: BridgeChainId::SuiCustom as u8,
+            limit: Some(limit),
+            total_amount,
+            available_amount: limit.saturating_sub(total_amount),
+            buckets: buckets
+                .into_iter()
+                .map(|(expires_at_ms, amount)| LimitWindowBucket {
+                    hour_start_ms: expires_at_ms - LIMIT_WINDOW_HOURS * HOUR_MS,
+                    amount,
+                    expires_at_ms,
+                })
+                .collect(),
+            chain_time_ms,
+            clock_skew_ms: 0,
+            requested: None,
+        }
+    }
+
+    #[test]
+    fn test_estimate_drain() {
+        let now_ms = 1000 * HOUR_MS;
+        let eth_to_sui = (
+            BridgeChainId::EthCustom as u8,
+            BridgeChainId::SuiCustom as u8,
+        );
+        let sui_to_eth = (
+            BridgeChainId::SuiCustom as u8,
+            BridgeChainId::EthCustom as u8,
+        );
+        // 10 transfers into Sui and 3 into Eth in the last 10 minutes, and older ones
+        let mut executed_times = (0..10)
+            .map(|nonce| {
+                (
+                    (eth_to_sui.0, eth_to_sui.1, nonce),
+                    now_ms - nonce * MINUTE_MS,
+                )
+            })
+            .collect::<Vec<_>>();
+        executed_times
+            .extend((0..3).map(|nonce| ((sui_to_eth.0, sui_to_eth.1, nonce), now_ms - MINUTE_MS)));
+        executed_times.push(((eth_to_sui.0, eth_to_sui.1, 100), now_ms - 11 * MINUTE_MS));
+
+        // Into Sui, 100 of the limit of 1000 is free until 900 expires in 2 hours, and
+        // the transfer of 2000 never fits
+        let mut pending = [(10, 50), (11, 100), (12, 2000), (13, 10), (14, 10)]
+            .into_iter()
+            .map(|(nonce, amount)| {
+                get_test_eth_to_sui_bridge_action(Some(nonce), Some(amount), None, Some(TOKEN_ID))
+            })
+            .collect::<Vec<_>>();
+        pending.push(get_test_sui_to_eth_bridge_action(
+            None,
+            None,
+            Some(10),
+            Some(1_000_000),
+            None,
+            None,
+            Some(TOKEN_ID),
+        ));
+        pending.push(BridgeAction::EmergencyAction(EmergencyAction {
+            nonce: 0,
+            chain_id: BridgeChainId::SuiCustom,
+            action_type: EmergencyActionType::Pause,
+        }));
+        let inputs = DrainInputs {
+            pending,
+            in_flight: 1,
+            executed_times,
+            window_ms: 10 * MINUTE_MS,
+            now_ms,
+            limit_windows: vec![capped_window(
+                now_ms,
+                1000,
+                vec![(now_ms + 2 * HOUR_MS, 900)],
+            )],
+            prices: HashMap::from([(
+                TOKEN_ID,
+                TokenPrice {
+                    price: 1,
+                    decimal_multiplier: 1,
+                },
+            )]),
+        };
+        let estimate = estimate_drain(&inputs);
+        assert_eq!(estimate.pending_actions, 7);
+        assert_eq!(estimate.in_flight_actions, 1);
+
+        // Into Sui: 4 transfers at 1 per minute, but the limit holds the second one back
+        // for 2 hours
+        let capped = &estimate.routes[1];
+        assert_eq!((capped.source_chain, capped.destination_chain), eth_to_sui);
+        assert_eq!(capped.pending, 5);
+        assert_eq!(capped.executed_in_window, 10);
+        assert_eq!(capped.pending_value, 2170);
+        assert_eq!(capped.blocked_transfers, 1);
+        assert_eq!(capped.limit_drain_ms, Some(2 * HOUR_MS));
+        let uncapped = throughput_drain(4, 10, 10 * MINUTE_MS);
+        assert_eq!(uncapped.estimate_ms, Some(4 * MINUTE_MS));
+        let margin = Z_95 * 10f64.sqrt();
+        assert_eq!(
+            uncapped.lower_ms,
+            Some((4.0 * 10.0 * MINUTE_MS as f64 / (10.0 + margin)).round() as u64)
+        );
+        assert_eq!(
+            uncapped.upper_ms,
+            Some((4.0 * 10.0 * MINUTE_MS as f64 / (10.0 - margin)).round() as u64)
+        );
+        assert!(uncapped.upper_ms.unwrap() < 2 * HOUR_MS);
+        assert_eq!(
+            capped.drain,
+            DrainTime {
+                estimate_ms: Some(2 * HOUR_MS),
+                lower_ms: Some(2 * HOUR_MS),
+                upper_ms: Some(2 * HOUR_MS),
+            }
+        );
+
+        // Into Eth: not limited, 1 transfer at 3 per 10 minutes, too few for an upper bound
+        let into_eth = &estimate.routes[0];
+        assert_eq!(
+            (into_eth.source_chain, into_eth.destination_chain),
+            sui_to_eth
+        );
+        assert_eq!(into_eth.limit, None);
+        assert_eq!(into_eth.limit_drain_ms, None);
+        assert_eq!(into_eth.drain.estimate_ms, Some(10 * MINUTE_MS / 3));
+        assert_eq!(into_eth.drain.upper_ms, None);
+
+        // 5 transfers at 13 per 10 minutes, then the limit
+        let transfers = &estimate.action_types[0];
+        assert_eq!(transfers.action_type, "TokenTransfer");
+        assert_eq!((transfers.pending, transfers.executed_in_window), (6, 13));
+        assert_eq!(transfers.drain.estimate_ms, Some(2 * HOUR_MS));
+        // Emergency actions record no throughput
+        let emergency = &estimate.action_types[1];
+        assert_eq!(emergency.action_type, "EmergencyButton");
+        assert_eq!(emergency.pending, 1);
+        assert_eq!(emergency.drain, DrainTime::default());
+        assert_eq!(estimate.drain.estimate_ms, Some(2 * HOUR_MS));
+
+        // Without the limit, the backlog drains at the throughput
+        let estimate = estimate_drain(&DrainInputs {
+            limit_windows: vec![],
+            ..inputs
+        });
+        assert_eq!(estimate.routes[1].blocked_transfers, 0);
+        assert_eq!(
+            estimate.drain.estimate_ms,
+            Some((7.0 * 10.0 * MINUTE_MS as f64 / 13.0).round() as u64)
+        );
+        assert!(estimate.drain.lower_ms < estimate.drain.estimate_ms);
+        assert!(estimate.drain.upper_ms > estimate.drain.estimate_ms);
+    }
+
+    #[test]
+    fn test_limit_drain_counts_the_drained_transfers() {
+        let chain_time_ms = 1000 * HOUR_MS + 10 * MINUTE_MS;
+        let window = capped_window(chain_time_ms, 1000, vec![]);
+        assert_eq!(limit_drain_ms(&window, 1000, &[]), 0);
+        assert_eq!(limit_drain_ms(&window, 1000, &[400, 600]), 0);
+        // The second transfer waits for the first one to leave the window, 24 hours after
+        // the start of its hour
+        assert_eq!(
+            limit_drain_ms(&window, 1000, &[600, 600]),
+            LIMIT_WINDOW_HOURS * HOUR_MS - 10 * MINUTE_MS
+        );
+        // Amounts already in the window expire first
+        let window = capped_window(
+            chain_time_ms,
+            1000,
+            vec![
+                (chain_time_ms + HOUR_MS, 500),
+                (chain_time_ms + 3 * HOUR_MS, 500),
+            ],
+        );
+        assert_eq!(limit_drain_ms(&window, 1000, &[400]), HOUR_MS);
+        assert_eq!(limit_drain_ms(&window, 1000, &[400, 600]), 3 * HOUR_MS);
+    }
+}
@@ -11,6 +11,7 @@ use crate::eth_transaction_builder::build_eth_transaction;
 use crate::events::{
     SuiBridgeEvent, SuiToEthTokenBridgeV1, TokenTransferApproved, TokenTransferClaimed,
 };
+use crate::metrics::BridgeMetrics;
 use crate::sui_client::SuiBridgeClient;
 use crate::sui_transaction_builder::build_add_tokens_on_sui_transaction;
 use crate::types::{AddTokensOnEvmAction, BridgeAction, BridgeActionStatus, SuiToEthBridgeAction};
@@ -232,7 +233,8 @@ async fn test_add_new_coins_on_sui_and_eth() {
             .await
             .expect("Failed to get bridge committee"),
     );
-    let agg = BridgeAuthorityAggregator::new(bridge_committee);
+    let agg =
+        BridgeAuthorityAggregator::new(bridge_committee, Arc::new(BridgeMetrics::for_testing()));
     let certified_sui_action = agg
         .request_committee_signatures(sui_action)
         .await
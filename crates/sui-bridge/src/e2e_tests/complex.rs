@@ -5,6 +5,7 @@ use crate::client::bridge_authority_aggregator::BridgeAuthorityAggregator;
 use crate::e2e_tests::basic::initiate_bridge_eth_to_sui;
 use crate::e2e_tests::basic::initiate_bridge_sui_to_eth;
 use crate::e2e_tests::test_utils::BridgeTestClusterBuilder;
+use crate::metrics::BridgeMetrics;
 use crate::sui_transaction_builder::build_sui_transaction;
 use crate::types::{BridgeAction, EmergencyAction};
 use crate::types::{BridgeActionStatus, EmergencyActionType};
@@ -70,7 +71,8 @@ async fn test_sui_bridge_paused() {
 
     // get pause bridge signatures from committee
     let bridge_committee = Arc::new(bridge_client.get_bridge_committee().await.unwrap());
-    let agg = BridgeAuthorityAggregator::new(bridge_committee);
+    let agg =
+        BridgeAuthorityAggregator::new(bridge_committee, Arc::new(BridgeMetrics::for_testing()));
     let certified_action = agg
         .request_committee_signatures(pause_action)
         .await
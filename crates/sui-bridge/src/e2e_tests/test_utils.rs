@@ -757,17 +757,67 @@ pub(crate) async fn start_bridge_cluster(
 
         let config = BridgeNodeConfig {
             server_listen_port: *server_listen_port,
+            server_listen_address: None,
+            server_dual_stack: false,
+            server_reuse_port: false,
+            server_dedicated_runtime: false,
+            server_runtime_worker_threads: None,
             metrics_port: get_available_port("127.0.0.1"),
-            bridge_authority_key_path: authority_key_path,
+            mode: None,
+            bridge_authority_key_path: Some(authority_key_path),
+            remote_signer: None,
             approved_governance_actions,
+            approved_governance_digests: vec![],
             run_client: i == 0,
             db_path: Some(db_path),
+            aux_db_path: None,
+            storage_encryption_key_path: None,
+            verify_storage: false,
+            storage_cursor_flush_ms: None,
+            min_transfer_usd_value: None,
+            tx_journal: None,
+            reconciliation: None,
+            claim_tracking: None,
+            channels: None,
+            adaptive_polling: None,
+            gas_funding: None,
+            alerts: None,
+            timestamps: None,
+            clock_drift: None,
+            event_stream: None,
+            quarantine_recheck: None,
+            onchain_status_batch_size: None,
+            pruning: None,
+            storage_maintenance: None,
+            min_signature_inclusion_percent: None,
+            skip_signing_paused_routes: false,
+            committee_dns_refresh_seconds: None,
+            committee_connections: None,
+            committee_request_hedging: None,
+            refuse_committee_anomalies: false,
+            sign_action_types: None,
+            execute_action_types: None,
+            request_auth: None,
+            admin_auth: None,
+            slow_request_threshold_ms: None,
+            governance_body_limit_bytes: None,
+            max_requester_labels: None,
+            grpc_listen_address: None,
+            external_url: None,
+            warm_up: None,
+            verification_limits: None,
+            logging: None,
             eth: EthConfig {
                 eth_rpc_url: eth_environment.rpc_url.clone(),
                 eth_bridge_proxy_address: eth_bridge_contract_address.clone(),
                 eth_bridge_chain_id: BridgeChainId::EthCustom as u8,
                 eth_contracts_start_block_fallback: Some(0),
                 eth_contracts_start_block_override: None,
+                extra_eth_confirmations: None,
+                custom_evm_chains: None,
+                fee_history_interval_seconds: None,
+                verify_eth_receipts: None,
+                eth_receipts_root_rpc_url: None,
             },
             sui: SuiConfig {
                 sui_rpc_url: test_cluster.fullnode_handle.rpc_url.clone(),
@@ -775,9 +825,14 @@ pub(crate) async fn start_bridge_cluster(
                 bridge_client_key_path: None,
                 bridge_client_gas_object: None,
                 sui_bridge_module_last_processed_event_id_override: None,
+                disable_object_cache: false,
+                sui_rpc_max_attempts: None,
+                tx_submission: None,
             },
             metrics_key_pair: default_ed25519_key_pair(),
             metrics: None,
+            telemetry: None,
+            allow_mismatched_bridge_contracts: false,
         };
         // Spawn bridge node in memory
         handles.push(
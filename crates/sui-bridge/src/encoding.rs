@@ -11,6 +11,7 @@ use crate::types::EmergencyAction;
 use crate::types::EthToSuiBridgeAction;
 use crate::types::EvmContractUpgradeAction;
 use crate::types::LimitUpdateAction;
+use crate::types::ParsedTokenTransferMessage;
 use crate::types::SuiToEthBridgeAction;
 use enum_dispatch::enum_dispatch;
 use ethers::types::Address as EthAddress;
@@ -378,8 +379,19 @@ impl BridgeMessageEncoding for AddTokensOnEvmAction {
 }
 
 impl BridgeAction {
-    /// Convert to message bytes to verify in Move and Solidity
-    pub fn to_bytes(&self) -> Vec<u8> {
+    /// Convert to message bytes to verify in Move and Solidity. These are the bytes
+    /// that authorities sign. The layout is frozen, any change breaks signature
+    /// verification on chain:
+    ///
+    /// | bytes | field |
+    /// |-------|-------|
+    /// | 18    | prefix `SUI_BRIDGE_MESSAGE` |
+    /// | 1     | message type, `BridgeActionType` |
+    /// | 1     | message version |
+    /// | 8     | nonce, big endian |
+    /// | 1     | chain id, `BridgeChainId` |
+    /// | n     | payload, specific to the message type |
+    pub fn to_signing_bytes(&self) -> Vec<u8> {
         let mut bytes = Vec::new();
         // Add prefix
         bytes.extend_from_slice(BRIDGE_MESSAGE_PREFIX);
@@ -387,6 +399,27 @@ impl BridgeAction {
         bytes.extend_from_slice(&self.as_bytes());
         bytes
     }
+
+    /// Same as `to_signing_bytes`, the name this had before its layout was documented.
+    #[deprecated(note = "use `to_signing_bytes`")]
+    pub fn to_bytes(&self) -> Vec<u8> {
+        self.to_signing_bytes()
+    }
+}
+
+impl ParsedTokenTransferMessage {
+    /// The bytes that authorities signed to approve the token transfer, like
+    /// `BridgeAction::to_signing_bytes`.
+    pub fn to_signing_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::new();
+        bytes.extend_from_slice(BRIDGE_MESSAGE_PREFIX);
+        bytes.push(BridgeActionType::TokenTransfer as u8);
+        bytes.push(self.message_version);
+        bytes.extend_from_slice(&self.seq_num.to_be_bytes());
+        bytes.push(self.source_chain as u8);
+        bytes.extend_from_slice(&self.payload);
+        bytes
+    }
 }
 
 #[cfg(test)]
@@ -446,7 +479,7 @@ mod tests {
             sui_tx_event_index,
             sui_bridge_event,
         })
-        .to_bytes();
+        .to_signing_bytes();
 
         // Construct the expected bytes
         let prefix_bytes = BRIDGE_MESSAGE_PREFIX.to_vec(); // len: 18
@@ -524,7 +557,7 @@ mod tests {
             sui_tx_event_index,
             sui_bridge_event,
         })
-        .to_bytes();
+        .to_signing_bytes();
         assert_eq!(
             encoded_bytes,
             Hex::decode("5355495f4252494447455f4d4553534147450001000000000000000a012000000000000000000000000000000000000000000000000000000000000000640b1400000000000000000000000000000000000000c8030000000000003039").unwrap(),
@@ -556,7 +589,7 @@ mod tests {
             blocklist_type: BlocklistType::Blocklist,
             members_to_update: vec![pub_key_bytes.clone()],
         });
-        let bytes = blocklist_action.to_bytes();
+        let bytes = blocklist_action.to_signing_bytes();
         /*
         5355495f4252494447455f4d455353414745: prefix
         01: msg type
@@ -583,7 +616,7 @@ mod tests {
             blocklist_type: BlocklistType::Unblocklist,
             members_to_update: vec![pub_key_bytes.clone(), pub_key_bytes_2.clone()],
         });
-        let bytes = blocklist_action.to_bytes();
+        let bytes = blocklist_action.to_signing_bytes();
         /*
         5355495f4252494447455f4d455353414745: prefix
         01: msg type
@@ -605,7 +638,7 @@ mod tests {
             blocklist_type: BlocklistType::Blocklist,
             members_to_update: vec![pub_key_bytes.clone()],
         });
-        let bytes = blocklist_action.to_bytes();
+        let bytes = blocklist_action.to_signing_bytes();
         /*
         5355495f4252494447455f4d455353414745: prefix
         01: msg type
@@ -626,7 +659,7 @@ mod tests {
             blocklist_type: BlocklistType::Unblocklist,
             members_to_update: vec![pub_key_bytes.clone(), pub_key_bytes_2.clone()],
         });
-        let bytes = blocklist_action.to_bytes();
+        let bytes = blocklist_action.to_signing_bytes();
         /*
         5355495f4252494447455f4d455353414745: prefix
         01: msg type
@@ -650,7 +683,7 @@ mod tests {
             chain_id: BridgeChainId::SuiCustom,
             action_type: EmergencyActionType::Pause,
         });
-        let bytes = action.to_bytes();
+        let bytes = action.to_signing_bytes();
         /*
         5355495f4252494447455f4d455353414745: prefix
         02: msg type
@@ -669,7 +702,7 @@ mod tests {
             chain_id: BridgeChainId::EthSepolia,
             action_type: EmergencyActionType::Unpause,
         });
-        let bytes = action.to_bytes();
+        let bytes = action.to_signing_bytes();
         /*
         5355495f4252494447455f4d455353414745: prefix
         02: msg type
@@ -692,7 +725,7 @@ mod tests {
             sending_chain_id: BridgeChainId::EthCustom,
             new_usd_limit: 1_000_000 * USD_MULTIPLIER, // $1M USD
         });
-        let bytes = action.to_bytes();
+        let bytes = action.to_signing_bytes();
         /*
         5355495f4252494447455f4d455353414745: prefix
         03: msg type
@@ -719,7 +752,7 @@ mod tests {
             token_id: TOKEN_ID_BTC,
             new_usd_price: 100_000 * USD_MULTIPLIER, // $100k USD
         });
-        let bytes = action.to_bytes();
+        let bytes = action.to_signing_bytes();
         /*
         5355495f4252494447455f4d455353414745: prefix
         04: msg type
@@ -766,7 +799,7 @@ mod tests {
         0000000000000000000000000000000000000000000000000000000000000004
         5cd8a76b00000000000000000000000000000000000000000000000000000000: call data
         */
-        assert_eq!(Hex::encode(action.to_bytes().clone()), "5355495f4252494447455f4d4553534147450501000000000000007b0c00000000000000000000000006060606060606060606060606060606060606060000000000000000000000000909090909090909090909090909090909090909000000000000000000000000000000000000000000000000000000000000006000000000000000000000000000000000000000000000000000000000000000045cd8a76b00000000000000000000000000000000000000000000000000000000");
+        assert_eq!(Hex::encode(action.to_signing_bytes().clone()), "5355495f4252494447455f4d4553534147450501000000000000007b0c00000000000000000000000006060606060606060606060606060606060606060000000000000000000000000909090909090909090909090909090909090909000000000000000000000000000000000000000000000000000000000000006000000000000000000000000000000000000000000000000000000000000000045cd8a76b00000000000000000000000000000000000000000000000000000000");
 
         // Calldata with one parameter: `function newMockFunction(bool)`
         let function_signature = "newMockFunction(bool)";
@@ -798,7 +831,7 @@ mod tests {
         417795ef00000000000000000000000000000000000000000000000000000000
         0000000100000000000000000000000000000000000000000000000000000000: call data
         */
-        assert_eq!(Hex::encode(action.to_bytes().clone()), "5355495f4252494447455f4d4553534147450501000000000000007b0c0000000000000000000000000606060606060606060606060606060606060606000000000000000000000000090909090909090909090909090909090909090900000000000000000000000000000000000000000000000000000000000000600000000000000000000000000000000000000000000000000000000000000024417795ef000000000000000000000000000000000000000000000000000000000000000100000000000000000000000000000000000000000000000000000000");
+        assert_eq!(Hex::encode(action.to_signing_bytes().clone()), "5355495f4252494447455f4d4553534147450501000000000000007b0c0000000000000000000000000606060606060606060606060606060606060606000000000000000000000000090909090909090909090909090909090909090900000000000000000000000000000000000000000000000000000000000000600000000000000000000000000000000000000000000000000000000000000024417795ef000000000000000000000000000000000000000000000000000000000000000100000000000000000000000000000000000000000000000000000000");
 
         // Calldata with two parameters: `function newerMockFunction(bool, uint8)`
         let function_signature = "newMockFunction(bool,uint8)";
@@ -834,7 +867,7 @@ mod tests {
         0000000100000000000000000000000000000000000000000000000000000000
         0000002a00000000000000000000000000000000000000000000000000000000: call data
         */
-        assert_eq!(Hex::encode(action.to_bytes().clone()), "5355495f4252494447455f4d4553534147450501000000000000007b0c0000000000000000000000000606060606060606060606060606060606060606000000000000000000000000090909090909090909090909090909090909090900000000000000000000000000000000000000000000000000000000000000600000000000000000000000000000000000000000000000000000000000000044be8fc25d0000000000000000000000000000000000000000000000000000000000000001000000000000000000000000000000000000000000000000000000000000002a00000000000000000000000000000000000000000000000000000000");
+        assert_eq!(Hex::encode(action.to_signing_bytes().clone()), "5355495f4252494447455f4d4553534147450501000000000000007b0c0000000000000000000000000606060606060606060606060606060606060606000000000000000000000000090909090909090909090909090909090909090900000000000000000000000000000000000000000000000000000000000000600000000000000000000000000000000000000000000000000000000000000044be8fc25d0000000000000000000000000000000000000000000000000000000000000001000000000000000000000000000000000000000000000000000000000000002a00000000000000000000000000000000000000000000000000000000");
 
         // Empty calldate
         let action = BridgeAction::EvmContractUpgradeAction(EvmContractUpgradeAction {
@@ -856,7 +889,7 @@ mod tests {
         0000000000000000000000000000000000000000000000000000000000000060
         0000000000000000000000000000000000000000000000000000000000000000: call data
         */
-        let data = action.to_bytes();
+        let data = action.to_signing_bytes();
         assert_eq!(Hex::encode(data.clone()), "5355495f4252494447455f4d4553534147450501000000000000007b0c0000000000000000000000000606060606060606060606060606060606060606000000000000000000000000090909090909090909090909090909090909090900000000000000000000000000000000000000000000000000000000000000600000000000000000000000000000000000000000000000000000000000000000");
         let types = vec![ParamType::Address, ParamType::Address, ParamType::Bytes];
         // Ensure that the call data (start from bytes 29) can be decoded
@@ -897,7 +930,7 @@ mod tests {
             eth_event_index,
             eth_bridge_event,
         })
-        .to_bytes();
+        .to_signing_bytes();
 
         assert_eq!(
             encoded_bytes,
@@ -935,7 +968,7 @@ mod tests {
                 1_000u64,
             ]
         });
-        let encoded_bytes = action.to_bytes();
+        let encoded_bytes = action.to_signing_bytes();
 
         assert_eq!(
             Hex::encode(encoded_bytes),
@@ -959,7 +992,7 @@ mod tests {
             token_sui_decimals: vec![5, 6, 7],
             token_prices: vec![1_000_000_000, 2_000_000_000, 3_000_000_000],
         });
-        let encoded_bytes = action.to_bytes();
+        let encoded_bytes = action.to_signing_bytes();
 
         assert_eq!(
             Hex::encode(encoded_bytes),
@@ -982,6 +1015,308 @@ mod tests {
         Ok(())
     }
 
+    // One action of every variant, with its signing bytes and digest. These are frozen:
+    // relayers and auditors compute digests with the same layout, so any change here
+    // is a breaking change to the message format.
+    fn golden_vectors() -> Vec<(BridgeAction, &'static str, &'static str)> {
+        let sui_address = SuiAddress::from_str(
+            "0x0000000000000000000000000000000000000000000000000000000000000064",
+        )
+        .unwrap();
+        let eth_address =
+            EthAddress::from_str("0x00000000000000000000000000000000000000c8").unwrap();
+        let pub_key_bytes = BridgeAuthorityPublicKeyBytes::from_bytes(
+            &Hex::decode("02321ede33d2c2d7a8a152f275a1484edef2098f034121a602cb7d767d38680aa4")
+                .unwrap(),
+        )
+        .unwrap();
+        let pub_key_bytes_2 = BridgeAuthorityPublicKeyBytes::from_bytes(
+            &Hex::decode("027f1178ff417fc9f5b8290bd8876f0a157a505a6c52db100a8492203ddd1d4279")
+                .unwrap(),
+        )
+        .unwrap();
+        vec![
+            (
+                BridgeAction::SuiToEthBridgeAction(SuiToEthBridgeAction {
+                    // The tx digest and event index are not part of the message
+                    sui_tx_digest: TransactionDigest::random(),
+                    sui_tx_event_index: 1,
+                    sui_bridge_event: EmittedSuiToEthTokenBridgeV1 {
+                        nonce: 10,
+                        sui_chain_id: BridgeChainId::SuiTestnet,
+                        eth_chain_id: BridgeChainId::EthSepolia,
+                        sui_address,
+                        eth_address,
+                        token_id: TOKEN_ID_USDC,
+                        amount_sui_adjusted: 12345,
+                    },
+                }),
+                "5355495f4252494447455f4d4553534147450001000000000000000a012000000000000000000000000000000000000000000000000000000000000000640b1400000000000000000000000000000000000000c8030000000000003039",
+                "6ab34c52b6264cbc12fe8c3874f9b08f8481d2e81530d136386646dbe2f8baf4",
+            ),
+            (
+                BridgeAction::EthToSuiBridgeAction(EthToSuiBridgeAction {
+                    eth_tx_hash: TxHash::random(),
+                    eth_event_index: 1,
+                    eth_bridge_event: EthToSuiTokenBridgeV1 {
+                        nonce: 10,
+                        sui_chain_id: BridgeChainId::SuiTestnet,
+                        eth_chain_id: BridgeChainId::EthSepolia,
+                        sui_address,
+                        eth_address,
+                        token_id: TOKEN_ID_USDC,
+                        sui_adjusted_amount: 12345,
+                    },
+                }),
+                "5355495f4252494447455f4d4553534147450001000000000000000a0b1400000000000000000000000000000000000000c801200000000000000000000000000000000000000000000000000000000000000064030000000000003039",
+                "b352508c301a37bb1b68a75dd0fc42b6f692b2650818631c8f8a4d4d3e5bef46",
+            ),
+            (
+                BridgeAction::BlocklistCommitteeAction(BlocklistCommitteeAction {
+                    nonce: 129,
+                    chain_id: BridgeChainId::SuiCustom,
+                    blocklist_type: BlocklistType::Blocklist,
+                    members_to_update: vec![pub_key_bytes.clone()],
+                }),
+                "5355495f4252494447455f4d4553534147450101000000000000008102000168b43fd906c0b8f024a18c56e06744f7c6157c65",
+                "04e3e041f11d292b56a397cc0d4bdf31a4963129687131e3036348aeb6ee642c",
+            ),
+            (
+                BridgeAction::BlocklistCommitteeAction(BlocklistCommitteeAction {
+                    nonce: 68,
+                    chain_id: BridgeChainId::SuiCustom,
+                    blocklist_type: BlocklistType::Unblocklist,
+                    members_to_update: vec![pub_key_bytes, pub_key_bytes_2],
+                }),
+                "5355495f4252494447455f4d4553534147450101000000000000004402010268b43fd906c0b8f024a18c56e06744f7c6157c65acaef39832cb995c4e049437a3e2ec6a7bad1ab5",
+                "f09330b47ee37a29d888bee219ad5a5b44b587524fc3ed3205ad2924208b846a",
+            ),
+            (
+                BridgeAction::EmergencyAction(EmergencyAction {
+                    nonce: 55,
+                    chain_id: BridgeChainId::SuiCustom,
+                    action_type: EmergencyActionType::Pause,
+                }),
+                "5355495f4252494447455f4d455353414745020100000000000000370200",
+                "b60a9b06bcb00169a55e9da47cb9848704e16b2a5edf6e9056b47e7feb8f90d9",
+            ),
+            (
+                BridgeAction::EmergencyAction(EmergencyAction {
+                    nonce: 56,
+                    chain_id: BridgeChainId::EthSepolia,
+                    action_type: EmergencyActionType::Unpause,
+                }),
+                "5355495f4252494447455f4d455353414745020100000000000000380b01",
+                "9897b6d64ae37e412fc95bfc556f1b3131797453b046256e6fe8adadbb8c3427",
+            ),
+            (
+                BridgeAction::LimitUpdateAction(LimitUpdateAction {
+                    nonce: 15,
+                    chain_id: BridgeChainId::SuiCustom,
+                    sending_chain_id: BridgeChainId::EthCustom,
+                    new_usd_limit: 1_000_000 * USD_MULTIPLIER,
+                }),
+                "5355495f4252494447455f4d4553534147450301000000000000000f020c00000002540be400",
+                "9fb6a4c7554d038fa48acec21c727e380b27eb2d0105aa245699b8058792af24",
+            ),
+            (
+                BridgeAction::AssetPriceUpdateAction(AssetPriceUpdateAction {
+                    nonce: 266,
+                    chain_id: BridgeChainId::SuiCustom,
+                    token_id: TOKEN_ID_BTC,
+                    new_usd_price: 100_000 * USD_MULTIPLIER,
+                }),
+                "5355495f4252494447455f4d4553534147450401000000000000010a0201000000003b9aca00",
+                "c9cfb1ad6976b7211b14020edc2a36554d4788ebf0f60eab91692109267623e9",
+            ),
+            (
+                BridgeAction::EvmContractUpgradeAction(EvmContractUpgradeAction {
+                    nonce: 123,
+                    chain_id: BridgeChainId::EthCustom,
+                    proxy_address: EthAddress::repeat_byte(6),
+                    new_impl_address: EthAddress::repeat_byte(9),
+                    call_data: vec![],
+                }),
+                "5355495f4252494447455f4d4553534147450501000000000000007b0c0000000000000000000000000606060606060606060606060606060606060606000000000000000000000000090909090909090909090909090909090909090900000000000000000000000000000000000000000000000000000000000000600000000000000000000000000000000000000000000000000000000000000000",
+                "8224ef8d25fe2bd0c478682bc00f3868d0ed46b448e4a154d21289f976f0ec04",
+            ),
+            (
+                BridgeAction::AddTokensOnSuiAction(AddTokensOnSuiAction {
+                    nonce: 0,
+                    chain_id: BridgeChainId::SuiCustom,
+                    native: false,
+                    token_ids: vec![1, 2, 3, 4],
+                    token_type_names: vec![
+                        TypeTag::from_str("0x9b5e13bcd0cb23ff25c07698e89d48056c745338d8c9dbd033a4172b87027073::btc::BTC").unwrap(),
+                        TypeTag::from_str("0x7970d71c03573f540a7157f0d3970e117effa6ae16cefd50b45c749670b24e6a::eth::ETH").unwrap(),
+                        TypeTag::from_str("0x500e429a24478405d5130222b20f8570a746b6bc22423f14b4d4e6a8ea580736::usdc::USDC").unwrap(),
+                        TypeTag::from_str("0x46bfe51da1bd9511919a92eb1154149b36c0f4212121808e13e3e5857d607a9c::usdt::USDT").unwrap(),
+                    ],
+                    token_prices: vec![500_000_000, 30_000_000, 1_000, 1_000],
+                }),
+                "5355495f4252494447455f4d4553534147450601000000000000000002000401020304044a396235653133626364306362323366663235633037363938653839643438303536633734353333386438633964626430333361343137326238373032373037333a3a6274633a3a4254434a373937306437316330333537336635343061373135376630643339373065313137656666613661653136636566643530623435633734393637306232346536613a3a6574683a3a4554484c353030653432396132343437383430356435313330323232623230663835373061373436623662633232343233663134623464346536613865613538303733363a3a757364633a3a555344434c343662666535316461316264393531313931396139326562313135343134396233366330663432313231323138303865313365336535383537643630376139633a3a757364743a3a55534454040065cd1d0000000080c3c90100000000e803000000000000e803000000000000",
+                "ec7eb81d620666224fb6b13a77db2ec4bc071200926641aab4abeb3eba2bb201",
+            ),
+            (
+                BridgeAction::AddTokensOnEvmAction(AddTokensOnEvmAction {
+                    nonce: 0,
+                    chain_id: BridgeChainId::EthCustom,
+                    native: true,
+                    token_ids: vec![99, 100, 101],
+                    token_addresses: vec![
+                        EthAddress::from_str("0x6B175474E89094C44Da98b954EedeAC495271d0F").unwrap(),
+                        EthAddress::from_str("0xae7ab96520DE3A18E5e111B5EaAb095312D7fE84").unwrap(),
+                        EthAddress::from_str("0xC18360217D8F7Ab5e7c516566761Ea12Ce7F9D72").unwrap(),
+                    ],
+                    token_sui_decimals: vec![5, 6, 7],
+                    token_prices: vec![1_000_000_000, 2_000_000_000, 3_000_000_000],
+                }),
+                "5355495f4252494447455f4d455353414745070100000000000000000c0103636465036b175474e89094c44da98b954eedeac495271d0fae7ab96520de3a18e5e111b5eaab095312d7fe84c18360217d8f7ab5e7c516566761ea12ce7f9d720305060703000000003b9aca00000000007735940000000000b2d05e00",
+                "d12431ff3a0ed65ba548c2e1e8ca2c41627b483a80e8681d4245a4e1497807c9",
+            ),
+        ]
+    }
+
+    #[test]
+    fn test_bridge_action_golden_vectors() {
+        let vectors = golden_vectors();
+        let mut action_types = vectors
+            .iter()
+            .map(|(action, _, _)| action.action_type())
+            .collect::<Vec<_>>();
+        action_types.dedup();
+        // Every action type has a vector
+        assert_eq!(action_types, BridgeActionType::ALL.to_vec());
+
+        for (action, signing_bytes, digest) in vectors {
+            assert_eq!(
+                Hex::encode(action.to_signing_bytes()),
+                signing_bytes,
+                "signing bytes of {:?} changed",
+                action
+            );
+            assert_eq!(
+                Hex::encode(action.digest().inner()),
+                digest,
+                "digest of {:?} changed",
+                action
+            );
+            assert_eq!(
+                Keccak256::digest(Hex::decode(signing_bytes).unwrap()).digest,
+                *action.digest().inner()
+            );
+            #[allow(deprecated)]
+            let bytes = action.to_bytes();
+            assert_eq!(bytes, action.to_signing_bytes());
+        }
+    }
+
+    // bcs bytes of actions are stored on disk and sent to authorities. Field order and
+    // variant order are part of the encoding, so reordering fields or enum variants
+    // must fail here.
+    #[test]
+    fn test_bridge_action_bcs_field_order() {
+        let cases = vec![
+            (
+                /*
+                03: BridgeAction variant
+                3700000000000000: nonce, little endian
+                02: chain id variant
+                00: action type variant
+                */
+                BridgeAction::EmergencyAction(EmergencyAction {
+                    nonce: 55,
+                    chain_id: BridgeChainId::SuiCustom,
+                    action_type: EmergencyActionType::Pause,
+                }),
+                "0337000000000000000200",
+            ),
+            (
+                // Variant index of the chain id, not its value
+                BridgeAction::EmergencyAction(EmergencyAction {
+                    nonce: 56,
+                    chain_id: BridgeChainId::EthSepolia,
+                    action_type: EmergencyActionType::Unpause,
+                }),
+                "0338000000000000000401",
+            ),
+            (
+                /*
+                04: BridgeAction variant
+                0f00000000000000: nonce
+                02: chain id
+                05: sending chain id
+                00e40b5402000000: new usd limit
+                */
+                BridgeAction::LimitUpdateAction(LimitUpdateAction {
+                    nonce: 15,
+                    chain_id: BridgeChainId::SuiCustom,
+                    sending_chain_id: BridgeChainId::EthCustom,
+                    new_usd_limit: 1_000_000 * USD_MULTIPLIER,
+                }),
+                "040f00000000000000020500e40b5402000000",
+            ),
+            (
+                /*
+                05: BridgeAction variant
+                0a01000000000000: nonce
+                02: chain id
+                01: token id
+                00ca9a3b00000000: new usd price
+                */
+                BridgeAction::AssetPriceUpdateAction(AssetPriceUpdateAction {
+                    nonce: 266,
+                    chain_id: BridgeChainId::SuiCustom,
+                    token_id: TOKEN_ID_BTC,
+                    new_usd_price: 100_000 * USD_MULTIPLIER,
+                }),
+                "050a01000000000000020100ca9a3b00000000",
+            ),
+        ];
+        for (action, expected) in cases {
+            let bytes = bcs::to_bytes(&action).unwrap();
+            assert_eq!(Hex::encode(&bytes), expected, "bcs of {:?} changed", action);
+            assert_eq!(bcs::from_bytes::<BridgeAction>(&bytes).unwrap(), action);
+        }
+
+        // Every variant round trips
+        for (action, _, _) in golden_vectors() {
+            let bytes = bcs::to_bytes(&action).unwrap();
+            assert_eq!(bcs::from_bytes::<BridgeAction>(&bytes).unwrap(), action);
+        }
+    }
+
+    #[test]
+    fn test_parsed_token_transfer_message_signing_bytes() {
+        for action in [
+            crate::test_utils::get_test_sui_to_eth_bridge_action(
+                None,
+                None,
+                Some(7),
+                Some(100),
+                None,
+                None,
+                None,
+            ),
+            crate::test_utils::get_test_eth_to_sui_bridge_action(Some(8), Some(200), None, None),
+        ] {
+            let message = ParsedTokenTransferMessage {
+                message_version: TOKEN_TRANSFER_MESSAGE_VERSION,
+                seq_num: action.seq_number(),
+                source_chain: action.chain_id(),
+                payload: action.as_payload_bytes(),
+                parsed_payload: sui_types::bridge::MoveTypeTokenTransferPayload {
+                    sender_address: vec![],
+                    target_chain: 0,
+                    target_address: vec![],
+                    token_type: 0,
+                    amount: 0,
+                },
+            };
+            assert_eq!(message.to_signing_bytes(), action.to_signing_bytes());
+        }
+    }
+
     fn get_bridge_encoding_regression_test_keys() -> Vec<BridgeAuthorityKeyPair> {
         vec![
             BridgeAuthorityKeyPair::from_bytes(
@@ -1,7 +1,78 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::{crypto::BridgeAuthorityPublicKeyBytes, types::BridgeAction};
+use crate::{
+    client::bridge_client::BridgeClientErrorCategory,
+    crypto::BridgeAuthorityPublicKeyBytes,
+    types::{BridgeAction, BridgeActionType},
+};
+use std::fmt;
+
+/// How the operation that failed is expected to behave when attempted again.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ErrorKind {
+    // Attempting the same operation again may succeed
+    Transient,
+    // Attempting the same operation again fails the same way
+    Permanent,
+    // The remote end throttles requests, attempting again later may succeed
+    RateLimited,
+    // What the operation depends on is not there yet, e.g. the transaction is not
+    // finalized, attempting again later succeeds eventually
+    NotReady,
+}
+
+impl ErrorKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ErrorKind::Transient => "transient",
+            ErrorKind::Permanent => "permanent",
+            ErrorKind::RateLimited => "rate_limited",
+            ErrorKind::NotReady => "not_ready",
+        }
+    }
+
+    pub fn is_retryable(&self) -> bool {
+        !matches!(self, ErrorKind::Permanent)
+    }
+}
+
+/// Where an error comes from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ErrorComponent {
+    // The Eth RPC provider
+    EthRpc,
+    // The Sui fullnode
+    SuiRpc,
+    // Other bridge authorities, alone or as a committee
+    Committee,
+    // The local database
+    Storage,
+    // The checks of this node on actions, requests and its own state
+    Node,
+}
+
+impl ErrorComponent {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ErrorComponent::EthRpc => "eth_rpc",
+            ErrorComponent::SuiRpc => "sui_rpc",
+            ErrorComponent::Committee => "committee",
+            ErrorComponent::Storage => "storage",
+            ErrorComponent::Node => "node",
+        }
+    }
+}
+
+/// Errors that tell how the operation that failed is expected to behave when
+/// attempted again. Retry decisions are made on the kind, never on the message.
+pub trait ClassifyError {
+    fn kind(&self) -> ErrorKind;
+
+    fn is_retryable(&self) -> bool {
+        self.kind().is_retryable()
+    }
+}
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum BridgeError {
@@ -29,10 +100,12 @@ pub enum BridgeError {
     AuthoritySignatureDuplication(String),
     // Too many errors when aggregating authority signatures
     AuthoritySignatureAggregationTooManyError(String),
-    // Transient Ethereum provider error
-    TransientProviderError(String),
+    // Enough of the committee rejected the action as invalid that it can't be certified
+    ActionRejectedByCommittee(String),
     // Ethereum provider error
-    ProviderError(String),
+    ProviderError(ErrorKind, String),
+    // The receipts of an Eth block don't match its receipts root
+    EthReceiptsRootMismatch(String),
     // TokenId is unknown
     UnknownTokenId(u8),
     // Invalid BridgeCommittee
@@ -55,20 +128,545 @@ pub enum BridgeError {
     ActionIsNotGovernanceAction(BridgeAction),
     // Client requested an non-approved governace action
     GovernanceActionIsNotApproved,
+    // This node is configured not to sign or execute actions of this type
+    ActionTypeNotAllowed(BridgeActionType),
+    // This node runs in observer mode, it has no key to sign with
+    ObserverMode,
+    // This node runs in shadow mode, it does not sign
+    ShadowMode,
+    // The local clock is off from the chains and the node is configured not to sign then
+    ClockDrift(String),
+    // Too many signing requests are waiting for a verification slot
+    ServerBusy(String),
+    // The remote signer failed to sign, or is unreachable
+    RemoteSignerError(ErrorKind, String),
     // Authority has invalid url
     AuthoirtyUrlInvalid,
     // Action is not token transfer
     ActionIsNotTokenTransferAction,
+    // Sui RPC request failed
+    SuiRpcError(ErrorKind, String),
     // Sui transaction failure due to generic error
     SuiTxFailureGeneric(String),
     // Zero value bridge transfer should not be allowed
     ZeroValueBridgeTransfer(String),
+    // Recipient of a token transfer is not a valid address on the destination chain
+    InvalidRecipientAddress(String),
     // Storage Error
     StorageError(String),
+    // Cursor reset requested by an operator is invalid or would skip unprocessed events
+    InvalidCursorReset(String),
+    // Nonce of a token transfer is too far ahead of the known nonces of its source chain
+    NonceTooFarAhead(String),
+    // Token amount can't be converted to other decimals without losing precision or overflowing
+    AmountPrecision(String),
     // Rest API Error
     RestAPIError(String),
+    // Request to a bridge authority failed
+    BridgeClientError(BridgeClientErrorCategory, String),
     // Uncategorized error
     Generic(String),
+    // Error of an operation that failed because of the wrapped error
+    Context(String, Box<BridgeError>),
+}
+
+impl BridgeError {
+    /// Wraps the error with what was being done when it happened, keeping its kind
+    /// and component.
+    pub fn context(self, context: impl Into<String>) -> Self {
+        BridgeError::Context(context.into(), Box::new(self))
+    }
+
+    pub fn kind(&self) -> ErrorKind {
+        match self {
+            BridgeError::ProviderError(kind, _)
+            | BridgeError::SuiRpcError(kind, _)
+            | BridgeError::RemoteSignerError(kind, _) => *kind,
+            BridgeError::BridgeClientError(category, _) => category.kind(),
+            BridgeError::Context(_, source) => source.kind(),
+            // The provider may not have indexed the transaction yet
+            BridgeError::TxNotFound | BridgeError::TxNotFinalized => ErrorKind::NotReady,
+            // Some of the authorities may respond next time
+            BridgeError::AuthoritySignatureAggregationTooManyError(_) => ErrorKind::Transient,
+            BridgeError::StorageError(_) | BridgeError::RestAPIError(_) => ErrorKind::Transient,
+            BridgeError::ServerBusy(_) => ErrorKind::RateLimited,
+            // Until the clock is fixed
+            BridgeError::ClockDrift(_) => ErrorKind::Transient,
+            // Nothing is known about these, they are retried like before they were
+            // categorized
+            BridgeError::InternalError(_) | BridgeError::Generic(_) => ErrorKind::Transient,
+            BridgeError::InvalidTxHash
+            | BridgeError::OriginTxFailed
+            | BridgeError::NoBridgeEventsInTxPosition
+            | BridgeError::BridgeEventInUnrecognizedEthContract
+            | BridgeError::BridgeEventInUnrecognizedSuiPackage
+            | BridgeError::BridgeEventNotActionable
+            | BridgeError::BridgeSerializationError(_)
+            | BridgeError::AuthoritySignatureDuplication(_)
+            | BridgeError::ActionRejectedByCommittee(_)
+            | BridgeError::UnknownTokenId(_)
+            | BridgeError::InvalidBridgeCommittee(_)
+            | BridgeError::InvalidBridgeAuthoritySignature(_)
+            | BridgeError::InvalidBridgeAuthority(_)
+            | BridgeError::InvalidAuthorityUrl(_)
+            | BridgeError::InvalidBridgeClientRequest(_)
+            | BridgeError::InvalidChainId
+            | BridgeError::MismatchedAuthoritySigner
+            | BridgeError::MismatchedAction
+            | BridgeError::ActionIsNotGovernanceAction(_)
+            | BridgeError::GovernanceActionIsNotApproved
+            | BridgeError::ActionTypeNotAllowed(_)
+            | BridgeError::ObserverMode
+            | BridgeError::ShadowMode
+            | BridgeError::AuthoirtyUrlInvalid
+            | BridgeError::ActionIsNotTokenTransferAction
+            | BridgeError::SuiTxFailureGeneric(_)
+            | BridgeError::ZeroValueBridgeTransfer(_)
+            | BridgeError::InvalidRecipientAddress(_)
+            | BridgeError::InvalidCursorReset(_)
+            | BridgeError::NonceTooFarAhead(_)
+            | BridgeError::AmountPrecision(_)
+            | BridgeError::EthReceiptsRootMismatch(_) => ErrorKind::Permanent,
+        }
+    }
+
+    pub fn component(&self) -> ErrorComponent {
+        match self {
+            BridgeError::TxNotFound
+            | BridgeError::TxNotFinalized
+            | BridgeError::ProviderError(..)
+            | BridgeError::EthReceiptsRootMismatch(_) => ErrorComponent::EthRpc,
+            BridgeError::SuiRpcError(..) | BridgeError::SuiTxFailureGeneric(_) => {
+                ErrorComponent::SuiRpc
+            }
+            BridgeError::AuthoritySignatureDuplication(_)
+            | BridgeError::AuthoritySignatureAggregationTooManyError(_)
+            | BridgeError::ActionRejectedByCommittee(_)
+            | BridgeError::InvalidBridgeAuthoritySignature(_)
+            | BridgeError::InvalidBridgeAuthority(_)
+            | BridgeError::InvalidAuthorityUrl(_)
+            | BridgeError::MismatchedAuthoritySigner
+            | BridgeError::MismatchedAction
+            | BridgeError::AuthoirtyUrlInvalid
+            | BridgeError::BridgeClientError(..) => ErrorComponent::Committee,
+            BridgeError::StorageError(_) => ErrorComponent::Storage,
+            BridgeError::Context(_, source) => source.component(),
+            BridgeError::InvalidTxHash
+            | BridgeError::OriginTxFailed
+            | BridgeError::NoBridgeEventsInTxPosition
+            | BridgeError::BridgeEventInUnrecognizedEthContract
+            | BridgeError::BridgeEventInUnrecognizedSuiPackage
+            | BridgeError::BridgeEventNotActionable
+            | BridgeError::BridgeSerializationError(_)
+            | BridgeError::InternalError(_)
+            | BridgeError::UnknownTokenId(_)
+            | BridgeError::InvalidBridgeCommittee(_)
+            | BridgeError::InvalidBridgeClientRequest(_)
+            | BridgeError::InvalidChainId
+            | BridgeError::ActionIsNotGovernanceAction(_)
+            | BridgeError::GovernanceActionIsNotApproved
+            | BridgeError::ActionTypeNotAllowed(_)
+            | BridgeError::ObserverMode
+            | BridgeError::ShadowMode
+            | BridgeError::ClockDrift(_)
+            | BridgeError::ServerBusy(_)
+            | BridgeError::RemoteSignerError(..)
+            | BridgeError::ActionIsNotTokenTransferAction
+            | BridgeError::ZeroValueBridgeTransfer(_)
+            | BridgeError::InvalidRecipientAddress(_)
+            | BridgeError::InvalidCursorReset(_)
+            | BridgeError::NonceTooFarAhead(_)
+            | BridgeError::AmountPrecision(_)
+            | BridgeError::RestAPIError(_)
+            | BridgeError::Generic(_) => ErrorComponent::Node,
+        }
+    }
+
+    /// Whether the failed operation may succeed when attempted again.
+    pub fn is_retryable(&self) -> bool {
+        self.kind().is_retryable()
+    }
+
+    /// Whether the error says that the requested action itself is invalid, so that
+    /// requesting it again fails the same way.
+    pub fn is_invalid_action(&self) -> bool {
+        matches!(
+            self,
+            BridgeError::OriginTxFailed
+                | BridgeError::NoBridgeEventsInTxPosition
+                | BridgeError::BridgeEventInUnrecognizedEthContract
+                | BridgeError::BridgeEventInUnrecognizedSuiPackage
+                | BridgeError::BridgeEventNotActionable
+                | BridgeError::ZeroValueBridgeTransfer(_)
+                | BridgeError::InvalidRecipientAddress(_)
+        )
+    }
+
+    /// Name of the variant of the error, that of the wrapped error for `Context`.
+    pub fn name(&self) -> &'static str {
+        match self {
+            BridgeError::InvalidTxHash => "InvalidTxHash",
+            BridgeError::OriginTxFailed => "OriginTxFailed",
+            BridgeError::TxNotFound => "TxNotFound",
+            BridgeError::TxNotFinalized => "TxNotFinalized",
+            BridgeError::NoBridgeEventsInTxPosition => "NoBridgeEventsInTxPosition",
+            BridgeError::BridgeEventInUnrecognizedEthContract => {
+                "BridgeEventInUnrecognizedEthContract"
+            }
+            BridgeError::BridgeEventInUnrecognizedSuiPackage => {
+                "BridgeEventInUnrecognizedSuiPackage"
+            }
+            BridgeError::BridgeEventNotActionable => "BridgeEventNotActionable",
+            BridgeError::BridgeSerializationError(..) => "BridgeSerializationError",
+            BridgeError::InternalError(..) => "InternalError",
+            BridgeError::AuthoritySignatureDuplication(..) => "AuthoritySignatureDuplication",
+            BridgeError::AuthoritySignatureAggregationTooManyError(..) => {
+                "AuthoritySignatureAggregationTooManyError"
+            }
+            BridgeError::ActionRejectedByCommittee(..) => "ActionRejectedByCommittee",
+            BridgeError::ProviderError(..) => "ProviderError",
+            BridgeError::EthReceiptsRootMismatch(..) => "EthReceiptsRootMismatch",
+            BridgeError::UnknownTokenId(..) => "UnknownTokenId",
+            BridgeError::InvalidBridgeCommittee(..) => "InvalidBridgeCommittee",
+            BridgeError::InvalidBridgeAuthoritySignature(..) => "InvalidBridgeAuthoritySignature",
+            BridgeError::InvalidBridgeAuthority(..) => "InvalidBridgeAuthority",
+            BridgeError::InvalidAuthorityUrl(..) => "InvalidAuthorityUrl",
+            BridgeError::InvalidBridgeClientRequest(..) => "InvalidBridgeClientRequest",
+            BridgeError::InvalidChainId => "InvalidChainId",
+            BridgeError::MismatchedAuthoritySigner => "MismatchedAuthoritySigner",
+            BridgeError::MismatchedAction => "MismatchedAction",
+            BridgeError::ActionIsNotGovernanceAction(..) => "ActionIsNotGovernanceAction",
+            BridgeError::GovernanceActionIsNotApproved => "GovernanceActionIsNotApproved",
+            BridgeError::ActionTypeNotAllowed(..) => "ActionTypeNotAllowed",
+            BridgeError::ObserverMode => "ObserverMode",
+            BridgeError::ShadowMode => "ShadowMode",
+            BridgeError::ClockDrift(..) => "ClockDrift",
+            BridgeError::ServerBusy(..) => "ServerBusy",
+            BridgeError::RemoteSignerError(..) => "RemoteSignerError",
+            BridgeError::AuthoirtyUrlInvalid => "AuthoirtyUrlInvalid",
+            BridgeError::ActionIsNotTokenTransferAction => "ActionIsNotTokenTransferAction",
+            BridgeError::SuiRpcError(..) => "SuiRpcError",
+            BridgeError::SuiTxFailureGeneric(..) => "SuiTxFailureGeneric",
+            BridgeError::ZeroValueBridgeTransfer(..) => "ZeroValueBridgeTransfer",
+            BridgeError::InvalidRecipientAddress(..) => "InvalidRecipientAddress",
+            BridgeError::StorageError(..) => "StorageError",
+            BridgeError::InvalidCursorReset(..) => "InvalidCursorReset",
+            BridgeError::NonceTooFarAhead(..) => "NonceTooFarAhead",
+            BridgeError::AmountPrecision(..) => "AmountPrecision",
+            BridgeError::RestAPIError(..) => "RestAPIError",
+            BridgeError::BridgeClientError(..) => "BridgeClientError",
+            BridgeError::Generic(..) => "Generic",
+            BridgeError::Context(_, source) => source.name(),
+        }
+    }
+
+    /// Describes the error without its message, e.g. `ProviderError (eth_rpc, transient)`.
+    /// Messages can carry what a remote end returned, or the URL of a provider with its
+    /// API key, so only this is served, e.g. by `/admin/errors`.
+    pub fn sanitized(&self) -> String {
+        format!(
+            "{} ({}, {})",
+            self.name(),
+            self.component().as_str(),
+            self.kind().as_str()
+        )
+    }
+}
+
+impl fmt::Display for BridgeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BridgeError::InvalidTxHash => write!(f, "Invalid transaction hash"),
+            BridgeError::OriginTxFailed => write!(f, "The referenced transaction failed"),
+            BridgeError::TxNotFound => write!(f, "The referenced transaction does not exist"),
+            BridgeError::TxNotFinalized => write!(f, "The transaction is not finalized yet"),
+            BridgeError::NoBridgeEventsInTxPosition => {
+                write!(f, "No bridge event in the transaction at the event index")
+            }
+            BridgeError::BridgeEventInUnrecognizedEthContract => {
+                write!(f, "Bridge event in an unrecognized Eth contract")
+            }
+            BridgeError::BridgeEventInUnrecognizedSuiPackage => {
+                write!(f, "Bridge event in an unrecognized Sui package")
+            }
+            BridgeError::BridgeEventNotActionable => write!(f, "Bridge event is not actionable"),
+            BridgeError::BridgeSerializationError(e) => write!(f, "Serialization failed: {e}"),
+            BridgeError::InternalError(e) => write!(f, "Internal error: {e}"),
+            BridgeError::AuthoritySignatureDuplication(e) => {
+                write!(f, "Duplicated authority signature: {e}")
+            }
+            BridgeError::AuthoritySignatureAggregationTooManyError(e) => write!(f, "{e}"),
+            BridgeError::ActionRejectedByCommittee(e) => {
+                write!(f, "Action rejected by the committee: {e}")
+            }
+            BridgeError::ProviderError(kind, e) => {
+                write!(f, "Eth provider error ({}): {e}", kind.as_str())
+            }
+            BridgeError::UnknownTokenId(id) => write!(f, "Unknown token id {id}"),
+            BridgeError::InvalidBridgeCommittee(e) => write!(f, "Invalid bridge committee: {e}"),
+            BridgeError::InvalidBridgeAuthoritySignature((authority, e)) => {
+                write!(f, "Invalid signature of authority {authority:?}: {e}")
+            }
+            BridgeError::InvalidBridgeAuthority(authority) => write!(
+                f,
+                "{authority:?} is not in the bridge committee or is blocklisted"
+            ),
+            BridgeError::InvalidAuthorityUrl(authority) => {
+                write!(f, "Authority {authority:?} has an invalid url")
+            }
+            BridgeError::InvalidBridgeClientRequest(e) => write!(f, "Invalid request: {e}"),
+            BridgeError::InvalidChainId => write!(f, "Invalid chain id"),
+            BridgeError::MismatchedAuthoritySigner => {
+                write!(f, "Signed by a different authority than expected")
+            }
+            BridgeError::MismatchedAction => write!(f, "Signature is over a different action"),
+            BridgeError::ActionIsNotGovernanceAction(action) => {
+                write!(f, "{action:?} is not a governance action")
+            }
+            BridgeError::GovernanceActionIsNotApproved => {
+                write!(f, "Governance action is not approved")
+            }
+            BridgeError::ActionTypeNotAllowed(action_type) => {
+                write!(f, "Action type {action_type:?} is not allowed on this node")
+            }
+            BridgeError::ObserverMode => write!(f, "The node runs in observer mode"),
+            BridgeError::ShadowMode => write!(f, "The node runs in shadow mode"),
+            BridgeError::ClockDrift(e) => write!(f, "Signing is refused, {e}"),
+            BridgeError::ServerBusy(e) => write!(f, "Server is busy: {e}"),
+            BridgeError::RemoteSignerError(kind, e) => {
+                write!(f, "Remote signer error ({}): {e}", kind.as_str())
+            }
+            BridgeError::AuthoirtyUrlInvalid => write!(f, "Authority has an invalid url"),
+            BridgeError::ActionIsNotTokenTransferAction => {
+                write!(f, "Action is not a token transfer")
+            }
+            BridgeError::SuiRpcError(kind, e) => {
+                write!(f, "Sui RPC error ({}): {e}", kind.as_str())
+            }
+            BridgeError::SuiTxFailureGeneric(e) => write!(f, "Sui transaction failed: {e}"),
+            BridgeError::ZeroValueBridgeTransfer(e) => write!(f, "Zero value transfer: {e}"),
+            BridgeError::InvalidRecipientAddress(e) => write!(f, "Invalid recipient address: {e}"),
+            BridgeError::StorageError(e) => write!(f, "Storage error: {e}"),
+            BridgeError::InvalidCursorReset(e) => write!(f, "Invalid cursor reset: {e}"),
+            BridgeError::NonceTooFarAhead(e) => write!(f, "Nonce is too far ahead: {e}"),
+            BridgeError::AmountPrecision(e) => write!(f, "Amount can't be converted exactly: {e}"),
+            BridgeError::EthReceiptsRootMismatch(e) => {
+                write!(f, "Eth receipts don't match the receipts root: {e}")
+            }
+            BridgeError::RestAPIError(e) => write!(f, "Rest API error: {e}"),
+            BridgeError::BridgeClientError(category, e) => {
+                write!(
+                    f,
+                    "Request to authority failed ({}): {e}",
+                    category.as_str()
+                )
+            }
+            BridgeError::Generic(e) => write!(f, "{e}"),
+            // The wrapped error is the source
+            BridgeError::Context(context, _) => write!(f, "{context}"),
+        }
+    }
+}
+
+impl std::error::Error for BridgeError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            BridgeError::Context(_, source) => Some(source.as_ref()),
+            _ => None,
+        }
+    }
+}
+
+impl ClassifyError for BridgeError {
+    fn kind(&self) -> ErrorKind {
+        BridgeError::kind(self)
+    }
+}
+
+impl ClassifyError for anyhow::Error {
+    fn kind(&self) -> ErrorKind {
+        // Errors of the bridge keep their kind, nothing is known about others
+        match self.downcast_ref::<BridgeError>() {
+            Some(err) => err.kind(),
+            None => ErrorKind::Transient,
+        }
+    }
 }
 
 pub type BridgeResult<T> = Result<T, BridgeError>;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::error::Error;
+    use std::path::Path;
+
+    #[test]
+    fn test_error_kind_mapping() {
+        for (err, kind, component) in [
+            (
+                BridgeError::TxNotFinalized,
+                ErrorKind::NotReady,
+                ErrorComponent::EthRpc,
+            ),
+            (
+                BridgeError::ProviderError(ErrorKind::RateLimited, "limited".to_string()),
+                ErrorKind::RateLimited,
+                ErrorComponent::EthRpc,
+            ),
+            (
+                BridgeError::SuiRpcError(ErrorKind::Transient, "busy".to_string()),
+                ErrorKind::Transient,
+                ErrorComponent::SuiRpc,
+            ),
+            (
+                BridgeError::BridgeClientError(
+                    BridgeClientErrorCategory::Http4xx(429),
+                    "too many requests".to_string(),
+                ),
+                ErrorKind::RateLimited,
+                ErrorComponent::Committee,
+            ),
+            (
+                BridgeError::BridgeClientError(BridgeClientErrorCategory::Tls, "tls".to_string()),
+                ErrorKind::Permanent,
+                ErrorComponent::Committee,
+            ),
+            (
+                BridgeError::ActionRejectedByCommittee("rejected".to_string()),
+                ErrorKind::Permanent,
+                ErrorComponent::Committee,
+            ),
+            (
+                BridgeError::AuthoritySignatureAggregationTooManyError("errors".to_string()),
+                ErrorKind::Transient,
+                ErrorComponent::Committee,
+            ),
+            (
+                BridgeError::StorageError("storage".to_string()),
+                ErrorKind::Transient,
+                ErrorComponent::Storage,
+            ),
+            (
+                BridgeError::NoBridgeEventsInTxPosition,
+                ErrorKind::Permanent,
+                ErrorComponent::Node,
+            ),
+            (
+                BridgeError::ActionTypeNotAllowed(BridgeActionType::TokenTransfer),
+                ErrorKind::Permanent,
+                ErrorComponent::Node,
+            ),
+            (
+                BridgeError::InvalidRecipientAddress("short".to_string()),
+                ErrorKind::Permanent,
+                ErrorComponent::Node,
+            ),
+            (
+                BridgeError::NonceTooFarAhead("ahead".to_string()),
+                ErrorKind::Permanent,
+                ErrorComponent::Node,
+            ),
+        ] {
+            assert_eq!(err.kind(), kind, "{err:?}");
+            assert_eq!(err.component(), component, "{err:?}");
+            assert_eq!(err.is_retryable(), kind != ErrorKind::Permanent, "{err:?}");
+        }
+    }
+
+    #[test]
+    fn test_error_context_chain() {
+        let err = BridgeError::SuiRpcError(ErrorKind::RateLimited, "429".to_string())
+            .context("Can't get bridge summary")
+            .context("Can't refresh the committee");
+        // The kind and component of the root cause are kept
+        assert_eq!(err.kind(), ErrorKind::RateLimited);
+        assert_eq!(err.component(), ErrorComponent::SuiRpc);
+        assert_eq!(err.to_string(), "Can't refresh the committee");
+
+        let source = err.source().unwrap();
+        assert_eq!(source.to_string(), "Can't get bridge summary");
+        let root = source.source().unwrap();
+        assert_eq!(root.to_string(), "Sui RPC error (rate_limited): 429");
+        assert!(root.source().is_none());
+
+        // The sanitized description is the root cause's, without any message
+        assert_eq!(err.sanitized(), "SuiRpcError (sui_rpc, rate_limited)");
+        let provider_err = BridgeError::ProviderError(
+            ErrorKind::Transient,
+            "https://eth.provider/v2/secret-api-key".to_string(),
+        );
+        assert_eq!(
+            provider_err.sanitized(),
+            "ProviderError (eth_rpc, transient)"
+        );
+
+        // Errors converted to anyhow keep their kind
+        let err = anyhow::Error::new(err).context("Failed to start");
+        assert_eq!(ClassifyError::kind(&err), ErrorKind::RateLimited);
+        assert_eq!(
+            ClassifyError::kind(&anyhow::anyhow!("unknown")),
+            ErrorKind::Transient
+        );
+    }
+
+    // Retry decisions must be made on the kind of errors. Matching on the messages of
+    // errors breaks silently whenever a message changes.
+    #[test]
+    fn test_no_retry_decisions_on_error_messages() {
+        // hyper only tells DNS and TLS failures apart from other connect errors in
+        // the messages of its private error types
+        let allowed = [("client/bridge_client.rs", "message.contains(")];
+        let src = Path::new(env!("CARGO_MANIFEST_DIR")).join("src");
+        let mut violations = vec![];
+        let mut dirs = vec![src.clone()];
+        while let Some(dir) = dirs.pop() {
+            for entry in std::fs::read_dir(dir).unwrap() {
+                let path = entry.unwrap().path();
+                if path.is_dir() {
+                    dirs.push(path);
+                    continue;
+                }
+                if path.extension().map_or(true, |extension| extension != "rs") {
+                    continue;
+                }
+                let relative_path = path
+                    .strip_prefix(&src)
+                    .unwrap()
+                    .to_str()
+                    .unwrap()
+                    .to_string();
+                let content = std::fs::read_to_string(&path).unwrap();
+                // Tests may look at messages
+                let content = match content.find("#[cfg(test)]\nmod tests") {
+                    Some(tests) => &content[..tests],
+                    None => &content[..],
+                };
+                for (line_number, line) in content.lines().enumerate() {
+                    let line = line.trim();
+                    if line.starts_with("//") {
+                        continue;
+                    }
+                    let matches_string = [".contains(", ".starts_with(", ".ends_with(", ".find("]
+                        .iter()
+                        .any(|method| line.contains(method));
+                    let lowercase = line.to_lowercase();
+                    let on_error = ["err", "message", "to_string()", "{:?}", "{e}"]
+                        .iter()
+                        .any(|hint| lowercase.contains(hint));
+                    let is_allowed = allowed
+                        .iter()
+                        .any(|(file, pattern)| relative_path == *file && line.contains(pattern));
+                    if matches_string && on_error && !is_allowed {
+                        violations.push(format!("{}:{}: {}", relative_path, line_number + 1, line));
+                    }
+                }
+            }
+        }
+        assert!(
+            violations.is_empty(),
+            "Decide on `BridgeError::kind` instead of the messages of errors:\n{}",
+            violations.join("\n")
+        );
+    }
+}
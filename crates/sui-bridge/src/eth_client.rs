@@ -1,25 +1,98 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
-use std::collections::HashSet;
+use std::collections::{BTreeSet, HashMap, HashSet};
 use std::sync::Arc;
 
-use crate::abi::EthBridgeEvent;
-use crate::error::{BridgeError, BridgeResult};
+use crate::abi::{
+    eth_bridge_event_topics, EthBridgeCommittee, EthBridgeConfig, EthBridgeEvent, EthERC20,
+    EthSuiBridge,
+};
+use crate::error::{BridgeError, BridgeResult, ClassifyError, ErrorKind};
+use crate::eth_receipt_proof::EthReceiptVerifier;
 use crate::metered_eth_provider::{new_metered_eth_provider, MeteredEthHttpProvier};
 use crate::metrics::BridgeMetrics;
-use crate::types::{BridgeAction, EthLog, RawEthLog};
-use ethers::providers::{JsonRpcClient, Middleware, Provider};
+use crate::types::{BridgeAction, BridgeActionType, EthLog, RawEthLog};
+use ethers::abi::Token;
+use ethers::contract::{ContractError, Multicall, MULTICALL_ADDRESS};
+use ethers::providers::{JsonRpcClient, Middleware, Provider, ProviderError, RpcError};
 use ethers::types::TxHash;
-use ethers::types::{Block, Filter};
+use ethers::types::{Block, BlockNumber, FeeHistory, Filter, TransactionReceipt, H256, U256};
 use tap::TapFallible;
 
 #[cfg(test)]
 use crate::eth_mock_provider::EthMockProvider;
 use ethers::types::Address as EthAddress;
+use serde::{Deserialize, Serialize};
+
+/// How the client decides that an Eth block is final.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum EthFinality {
+    /// Blocks up to the one tagged `finalized` by the fullnode are final.
+    #[default]
+    Finalized,
+    /// Blocks with at least this many blocks on top of them are final. For chains
+    /// whose fullnodes don't serve the `finalized` tag, e.g. some private devnets.
+    Confirmations(u64),
+}
+
 pub struct EthClient<P> {
     provider: Provider<P>,
     contract_addresses: HashSet<EthAddress>,
+    finality: EthFinality,
+    receipt_verifier: Option<Arc<EthReceiptVerifier<P>>>,
+}
+
+/// Classifies errors of the Eth provider. JSON-RPC errors carry the code the fullnode
+/// responded with, other errors are failures of the transport or of what is behind it.
+pub fn provider_error_kind(err: &ProviderError) -> ErrorKind {
+    match err {
+        ProviderError::JsonRpcClientError(err) => match err.as_error_response() {
+            Some(response) => json_rpc_error_kind(response.code),
+            // The fullnode was not reached, or a proxy in front of it responded
+            None => ErrorKind::Transient,
+        },
+        ProviderError::HTTPError(err) => match err.status().map(|status| status.as_u16()) {
+            Some(429) => ErrorKind::RateLimited,
+            Some(400..=499) => ErrorKind::Permanent,
+            _ => ErrorKind::Transient,
+        },
+        _ => ErrorKind::Permanent,
+    }
+}
+
+fn json_rpc_error_kind(code: i64) -> ErrorKind {
+    match code {
+        // Limit exceeded, which providers respond with when throttling
+        -32005 => ErrorKind::RateLimited,
+        // Internal error and resource unavailable
+        -32603 | -32002 => ErrorKind::Transient,
+        _ => ErrorKind::Permanent,
+    }
+}
+
+// A call of a contract function fails in the provider, or because the call reverted
+// or returned something else than expected, which happens again on the next call
+fn contract_call_error<P: JsonRpcClient>(err: ContractError<Provider<P>>) -> BridgeError {
+    let kind = match &err {
+        ContractError::MiddlewareError { e } => provider_error_kind(e),
+        ContractError::ProviderError { e } => provider_error_kind(e),
+        _ => ErrorKind::Permanent,
+    };
+    BridgeError::ProviderError(kind, format!("{:?}", err))
+}
+
+impl ClassifyError for ProviderError {
+    fn kind(&self) -> ErrorKind {
+        provider_error_kind(self)
+    }
+}
+
+impl From<ProviderError> for BridgeError {
+    fn from(err: ProviderError) -> Self {
+        BridgeError::ProviderError(provider_error_kind(&err), err.to_string())
+    }
 }
 
 impl EthClient<MeteredEthHttpProvier> {
@@ -32,6 +105,8 @@ impl EthClient<MeteredEthHttpProvier> {
         let self_ = Self {
             provider,
             contract_addresses,
+            finality: EthFinality::default(),
+            receipt_verifier: None,
         };
         self_.describe().await?;
         Ok(self_)
@@ -45,6 +120,8 @@ impl EthClient<EthMockProvider> {
         Self {
             provider,
             contract_addresses,
+            finality: EthFinality::default(),
+            receipt_verifier: None,
         }
     }
 }
@@ -53,6 +130,18 @@ impl<P> EthClient<P>
 where
     P: JsonRpcClient,
 {
+    pub fn with_finality(mut self, finality: EthFinality) -> Self {
+        self.finality = finality;
+        self
+    }
+
+    /// Makes `get_verified_bridge_action_maybe` verify the receipts of the deposits, see
+    /// `eth_receipt_proof`.
+    pub fn with_receipt_verifier(mut self, verifier: EthReceiptVerifier<P>) -> Self {
+        self.receipt_verifier = Some(Arc::new(verifier));
+        self
+    }
+
     // TODO assert chain identifier
     async fn describe(&self) -> anyhow::Result<()> {
         let chain_id = self.provider.get_chainid().await?;
@@ -65,26 +154,117 @@ where
 
     /// Returns BridgeAction from an Eth Transaction with transaction hash
     /// and the event index. If event is declared in an unrecognized
-    /// contract, return error.
+    /// contract, return error. The transaction is considered not finalized
+    /// until its block is `extra_confirmations` blocks past the last finalized block.
     pub async fn get_finalized_bridge_action_maybe(
         &self,
         tx_hash: TxHash,
         event_idx: u16,
+        extra_confirmations: u64,
     ) -> BridgeResult<BridgeAction> {
-        let receipt = self
-            .provider
+        let receipt = self.get_transaction_receipt(tx_hash).await?;
+        self.bridge_action_from_receipt(receipt, tx_hash, event_idx, extra_confirmations)
+            .await
+    }
+
+    /// Same as `get_finalized_bridge_action_maybe`, except that the receipt of the
+    /// transaction is verified against the receipts root of its block when the client
+    /// has a receipt verifier. Used for the signing requests, where a fabricated deposit
+    /// would get signed.
+    pub async fn get_verified_bridge_action_maybe(
+        &self,
+        tx_hash: TxHash,
+        event_idx: u16,
+        extra_confirmations: u64,
+    ) -> BridgeResult<BridgeAction> {
+        let receipt = self.get_verified_transaction_receipt(tx_hash).await?;
+        self.bridge_action_from_receipt(receipt, tx_hash, event_idx, extra_confirmations)
+            .await
+    }
+
+    /// Returns the receipt of the transaction, verified against the receipts root of its
+    /// block when the client has a receipt verifier.
+    pub async fn get_verified_transaction_receipt(
+        &self,
+        tx_hash: TxHash,
+    ) -> BridgeResult<TransactionReceipt> {
+        let mut receipt = self.get_transaction_receipt(tx_hash).await?;
+        if let Some(verifier) = &self.receipt_verifier {
+            let (block_hash, block_number) =
+                receipt
+                    .block_hash
+                    .zip(receipt.block_number)
+                    .ok_or(BridgeError::ProviderError(
+                        ErrorKind::NotReady,
+                        "Provider returns receipt without block".into(),
+                    ))?;
+            let receipts = self
+                .provider
+                .get_block_receipts(block_number)
+                .await
+                .map_err(BridgeError::from)?;
+            verifier
+                .verify_block_receipts(block_hash, &receipts)
+                .await?;
+            // The deposit is built from the receipt that was verified
+            receipt = receipts
+                .into_iter()
+                .find(|receipt| receipt.transaction_hash == tx_hash)
+                .ok_or_else(|| {
+                    BridgeError::EthReceiptsRootMismatch(format!(
+                        "Transaction {:?} is not in block {:?}",
+                        tx_hash, block_hash
+                    ))
+                })?;
+        }
+        Ok(receipt)
+    }
+
+    async fn get_transaction_receipt(&self, tx_hash: TxHash) -> BridgeResult<TransactionReceipt> {
+        self.provider
             .get_transaction_receipt(tx_hash)
             .await
             .map_err(BridgeError::from)?
-            .ok_or(BridgeError::TxNotFound)?;
+            .ok_or(BridgeError::TxNotFound)
+    }
+
+    /// Returns the bridge action of event `event_idx` of the transaction with `receipt`,
+    /// once its block is final with `extra_confirmations` blocks on top of it.
+    pub async fn bridge_action_from_receipt(
+        &self,
+        receipt: TransactionReceipt,
+        tx_hash: TxHash,
+        event_idx: u16,
+        extra_confirmations: u64,
+    ) -> BridgeResult<BridgeAction> {
         let receipt_block_num = receipt.block_number.ok_or(BridgeError::ProviderError(
+            ErrorKind::NotReady,
             "Provider returns log without block_number".into(),
         ))?;
         // TODO: save the latest finalized block id so we don't have to query it every time
         let last_finalized_block_id = self.get_last_finalized_block_id().await?;
-        if receipt_block_num.as_u64() > last_finalized_block_id {
+        if receipt_block_num
+            .as_u64()
+            .saturating_add(extra_confirmations)
+            > last_finalized_block_id
+        {
             return Err(BridgeError::TxNotFinalized);
         }
+        self.bridge_action_in_receipt(&receipt, tx_hash, event_idx)
+    }
+
+    /// Returns the action of the event at `event_idx` of `receipt`, without checking that
+    /// its block is final.
+    pub fn bridge_action_in_receipt(
+        &self,
+        receipt: &TransactionReceipt,
+        tx_hash: TxHash,
+        event_idx: u16,
+    ) -> BridgeResult<BridgeAction> {
+        let receipt_block_num = receipt.block_number.ok_or(BridgeError::ProviderError(
+            ErrorKind::NotReady,
+            "Provider returns log without block_number".into(),
+        ))?;
         let log = receipt
             .logs
             .get(event_idx as usize)
@@ -99,6 +279,7 @@ where
             block_number: receipt_block_num.as_u64(),
             tx_hash,
             log_index_in_tx: event_idx,
+            block_timestamp_ms: None,
             log: log.clone(),
         };
         let bridge_event = EthBridgeEvent::try_from_eth_log(&eth_log)
@@ -108,15 +289,44 @@ where
             .ok_or(BridgeError::BridgeEventNotActionable)
     }
 
+    pub async fn get_chain_id(&self) -> BridgeResult<u64> {
+        let chain_id = self
+            .provider
+            .get_chainid()
+            .await
+            .map_err(BridgeError::from)?;
+        Ok(chain_id.as_u64())
+    }
+
+    /// Returns the last block that is final according to the finality of the client.
     pub async fn get_last_finalized_block_id(&self) -> BridgeResult<u64> {
+        self.get_last_final_block_id(self.finality).await
+    }
+
+    /// Returns the last block that is final according to `finality`.
+    pub async fn get_last_final_block_id(&self, finality: EthFinality) -> BridgeResult<u64> {
+        let EthFinality::Confirmations(confirmations) = finality else {
+            return self.get_last_tagged_finalized_block_id().await;
+        };
+        let block_number = self
+            .provider
+            .get_block_number()
+            .await
+            .map_err(BridgeError::from)?;
+        Ok(block_number.as_u64().saturating_sub(confirmations))
+    }
+
+    async fn get_last_tagged_finalized_block_id(&self) -> BridgeResult<u64> {
         let block: Result<Option<Block<ethers::types::TxHash>>, ethers::prelude::ProviderError> =
             self.provider
                 .request("eth_getBlockByNumber", ("finalized", false))
                 .await;
-        let block = block?.ok_or(BridgeError::TransientProviderError(
+        let block = block?.ok_or(BridgeError::ProviderError(
+            ErrorKind::Transient,
             "Provider fails to return last finalized block".into(),
         ))?;
-        let number = block.number.ok_or(BridgeError::TransientProviderError(
+        let number = block.number.ok_or(BridgeError::ProviderError(
+            ErrorKind::Transient,
             "Provider returns block without number".into(),
         ))?;
         Ok(number.as_u64())
@@ -129,11 +339,30 @@ where
         address: ethers::types::Address,
         start_block: u64,
         end_block: u64,
+    ) -> BridgeResult<Vec<EthLog>> {
+        self.get_events_in_range_with_topics(
+            address,
+            start_block,
+            end_block,
+            &eth_bridge_event_topics(),
+        )
+        .await
+    }
+
+    /// Same as `get_events_in_range`, for the events with `topics` instead of the bridge
+    /// events, see `event_parsers`.
+    pub async fn get_events_in_range_with_topics(
+        &self,
+        address: ethers::types::Address,
+        start_block: u64,
+        end_block: u64,
+        topics: &[H256],
     ) -> BridgeResult<Vec<EthLog>> {
         let filter = Filter::new()
             .from_block(start_block)
             .to_block(end_block)
-            .address(address);
+            .address(address)
+            .topic0(topics.to_vec());
         let logs = self
             .provider
             // TODO use get_logs_paginated?
@@ -150,17 +379,20 @@ where
 
         // Safeguard check that all events are emitted from requested contract address
         if logs.iter().any(|log| log.address != address) {
-            return Err(BridgeError::ProviderError(format!(
-                "Provider returns logs from different contract address (expected: {:?}): {:?}",
-                address, logs
-            )));
+            return Err(BridgeError::ProviderError(
+                ErrorKind::Permanent,
+                format!(
+                    "Provider returns logs from different contract address (expected: {:?}): {:?}",
+                    address, logs
+                ),
+            ));
         }
         if logs.is_empty() {
             return Ok(vec![]);
         }
 
         let tasks = logs.into_iter().map(|log| self.get_log_tx_details(log));
-        futures::future::join_all(tasks)
+        let mut eth_logs = futures::future::join_all(tasks)
             .await
             .into_iter()
             .collect::<Result<Vec<_>, _>>()
@@ -170,7 +402,100 @@ where
                     filter,
                     e
                 )
+            })?;
+
+        // The block timestamps are only used for metrics, so failing to read them
+        // does not fail the query
+        let block_numbers = eth_logs
+            .iter()
+            .map(|log| log.block_number)
+            .collect::<BTreeSet<_>>();
+        let block_timestamps =
+            futures::future::join_all(block_numbers.into_iter().map(|block_number| async move {
+                (
+                    block_number,
+                    self.get_block_timestamp_ms(block_number).await,
+                )
+            }))
+            .await
+            .into_iter()
+            .filter_map(|(block_number, result)| match result {
+                Ok(timestamp_ms) => Some((block_number, timestamp_ms)),
+                Err(e) => {
+                    tracing::warn!("Failed to get timestamp of block {}: {:?}", block_number, e);
+                    None
+                }
             })
+            .collect::<HashMap<_, _>>();
+        for eth_log in &mut eth_logs {
+            eth_log.block_timestamp_ms = block_timestamps.get(&eth_log.block_number).copied();
+        }
+        Ok(eth_logs)
+    }
+
+    /// Returns the base fees and the priority fees at `reward_percentiles` of the last
+    /// `block_count` blocks.
+    pub async fn get_fee_history(
+        &self,
+        block_count: u64,
+        reward_percentiles: &[f64],
+    ) -> BridgeResult<FeeHistory> {
+        // The raw request, `Middleware::fee_history` retries with another encoding of the
+        // block count, which old fullnodes expect
+        let fee_history: Result<FeeHistory, ProviderError> = self
+            .provider
+            .request(
+                "eth_feeHistory",
+                (U256::from(block_count), "latest", reward_percentiles),
+            )
+            .await;
+        Ok(fee_history?)
+    }
+
+    pub async fn get_block_timestamp_ms(&self, block_number: u64) -> BridgeResult<u64> {
+        let block = self
+            .provider
+            .get_block(block_number)
+            .await
+            .map_err(BridgeError::from)?
+            .ok_or(BridgeError::ProviderError(
+                ErrorKind::NotReady,
+                format!("Provider cannot find block {}", block_number),
+            ))?;
+        Ok(block.timestamp.as_u64() * 1000)
+    }
+
+    /// Returns the hash of block `block_number` on the chain of the provider, None if the
+    /// provider doesn't have the block.
+    pub async fn get_block_hash(&self, block_number: u64) -> BridgeResult<Option<H256>> {
+        Ok(self
+            .provider
+            .get_block(block_number)
+            .await
+            .map_err(BridgeError::from)?
+            .and_then(|block| block.hash))
+    }
+
+    /// Returns the timestamp of the latest block, final or not.
+    /// Version of the provider, from `web3_clientVersion`, e.g. `Geth/v1.14.8-stable/linux-amd64/go1.22.5`.
+    pub async fn get_client_version(&self) -> BridgeResult<String> {
+        self.provider
+            .client_version()
+            .await
+            .map_err(BridgeError::from)
+    }
+
+    pub async fn get_latest_block_timestamp_ms(&self) -> BridgeResult<u64> {
+        let block: Option<Block<TxHash>> = self
+            .provider
+            .request("eth_getBlockByNumber", ("latest", false))
+            .await
+            .map_err(BridgeError::from)?;
+        let block = block.ok_or(BridgeError::ProviderError(
+            ErrorKind::Transient,
+            "Provider fails to return the latest block".into(),
+        ))?;
+        Ok(block.timestamp.as_u64() * 1000)
     }
 
     // Note: query may fail if range is too big. Callsite is responsible
@@ -184,7 +509,8 @@ where
         let filter = Filter::new()
             .from_block(start_block)
             .to_block(end_block)
-            .address(address);
+            .address(address)
+            .topic0(eth_bridge_event_topics());
         let logs = self
             .provider
             .get_logs(&filter)
@@ -201,11 +527,11 @@ where
         logs.into_iter().map(
             |log| {
                 if log.address != address {
-                    return Err(BridgeError::ProviderError(format!("Provider returns logs from different contract address (expected: {:?}): {:?}", address, log)));
+                    return Err(BridgeError::ProviderError(ErrorKind::Permanent, format!("Provider returns logs from different contract address (expected: {:?}): {:?}", address, log)));
                 }
                 Ok(RawEthLog {
-                block_number: log.block_number.ok_or(BridgeError::ProviderError("Provider returns log without block_number".into()))?.as_u64(),
-                tx_hash: log.transaction_hash.ok_or(BridgeError::ProviderError("Provider returns log without transaction_hash".into()))?,
+                block_number: log.block_number.ok_or(BridgeError::ProviderError(ErrorKind::Transient, "Provider returns log without block_number".into()))?.as_u64(),
+                tx_hash: log.transaction_hash.ok_or(BridgeError::ProviderError(ErrorKind::Transient, "Provider returns log without transaction_hash".into()))?,
                 log,
             })}
         ).collect::<Result<Vec<_>, _>>()
@@ -218,14 +544,17 @@ where
         let block_number = log
             .block_number
             .ok_or(BridgeError::ProviderError(
+                ErrorKind::Transient,
                 "Provider returns log without block_number".into(),
             ))?
             .as_u64();
         let tx_hash = log.transaction_hash.ok_or(BridgeError::ProviderError(
+            ErrorKind::Transient,
             "Provider returns log without transaction_hash".into(),
         ))?;
         // This is the log index in the block, rather than transaction.
         let log_index = log.log_index.ok_or(BridgeError::ProviderError(
+            ErrorKind::Transient,
             "Provider returns log without log_index".into(),
         ))?;
 
@@ -237,16 +566,17 @@ where
             .get_transaction_receipt(tx_hash)
             .await
             .map_err(BridgeError::from)?
-            .ok_or(BridgeError::ProviderError(format!(
-                "Provide cannot find eth transaction for log: {:?})",
-                log
-            )))?;
+            .ok_or(BridgeError::ProviderError(
+                ErrorKind::NotReady,
+                format!("Provide cannot find eth transaction for log: {:?})", log),
+            ))?;
 
         let receipt_block_num = receipt.block_number.ok_or(BridgeError::ProviderError(
+            ErrorKind::Transient,
             "Provider returns log without block_number".into(),
         ))?;
         if receipt_block_num.as_u64() != block_number {
-            return Err(BridgeError::ProviderError(format!("Provider returns receipt with different block number from log. Receipt: {:?}, Log: {:?}", receipt, log)));
+            return Err(BridgeError::ProviderError(ErrorKind::Permanent, format!("Provider returns receipt with different block number from log. Receipt: {:?}, Log: {:?}", receipt, log)));
         }
 
         // Find the log index in the transaction
@@ -256,32 +586,318 @@ where
             if receipt_log.log_index == Some(log_index) {
                 // make sure the topics and data match
                 if receipt_log.topics != log.topics || receipt_log.data != log.data {
-                    return Err(BridgeError::ProviderError(format!("Provider returns receipt with different log from log. Receipt: {:?}, Log: {:?}", receipt, log)));
+                    return Err(BridgeError::ProviderError(ErrorKind::Permanent, format!("Provider returns receipt with different log from log. Receipt: {:?}, Log: {:?}", receipt, log)));
                 }
                 log_index_in_tx = Some(idx);
             }
         }
-        let log_index_in_tx = log_index_in_tx.ok_or(BridgeError::ProviderError(format!(
-            "Couldn't find matching log: {:?} in transaction {}",
-            log, tx_hash
-        )))?;
+        let log_index_in_tx = log_index_in_tx.ok_or(BridgeError::ProviderError(
+            ErrorKind::Permanent,
+            format!(
+                "Couldn't find matching log: {:?} in transaction {}",
+                log, tx_hash
+            ),
+        ))?;
 
         Ok(EthLog {
             block_number,
             tx_hash,
             log_index_in_tx: log_index_in_tx as u16,
+            block_timestamp_ms: None,
             log,
         })
     }
 }
 
+impl<P> EthClient<P>
+where
+    P: JsonRpcClient + Clone + 'static,
+{
+    /// Returns the price and Sui decimals of the given tokens from the EthBridgeConfig
+    /// contract at `config_address`. Tokens not supported on Eth are omitted.
+    pub async fn get_token_prices_and_sui_decimals(
+        &self,
+        config_address: EthAddress,
+        token_ids: &[u8],
+    ) -> BridgeResult<HashMap<u8, (u64, u8)>> {
+        let config = EthBridgeConfig::new(config_address, Arc::new(self.provider.clone()));
+        let mut result = HashMap::new();
+        for token_id in token_ids {
+            let supported = config
+                .is_token_supported(*token_id)
+                .call()
+                .await
+                .map_err(contract_call_error)?;
+            if !supported {
+                continue;
+            }
+            let price = config
+                .token_price_of(*token_id)
+                .call()
+                .await
+                .map_err(contract_call_error)?;
+            let sui_decimal = config
+                .token_sui_decimal_of(*token_id)
+                .call()
+                .await
+                .map_err(contract_call_error)?;
+            result.insert(*token_id, (price, sui_decimal));
+        }
+        Ok(result)
+    }
+
+    /// Returns the decimals of the ERC20 contracts of the given tokens, as registered in the
+    /// EthBridgeConfig contract at `config_address`. Tokens not supported on Eth are omitted.
+    pub async fn get_token_decimals(
+        &self,
+        config_address: EthAddress,
+        token_ids: &[u8],
+    ) -> BridgeResult<HashMap<u8, u8>> {
+        let provider = Arc::new(self.provider.clone());
+        let config = EthBridgeConfig::new(config_address, provider.clone());
+        let mut result = HashMap::new();
+        for token_id in token_ids {
+            let supported = config
+                .is_token_supported(*token_id)
+                .call()
+                .await
+                .map_err(contract_call_error)?;
+            if !supported {
+                continue;
+            }
+            let token_address = config
+                .token_address_of(*token_id)
+                .call()
+                .await
+                .map_err(contract_call_error)?;
+            let decimals = EthERC20::new(token_address, provider.clone())
+                .decimals()
+                .call()
+                .await
+                .map_err(contract_call_error)?;
+            result.insert(*token_id, decimals);
+        }
+        Ok(result)
+    }
+
+    /// Returns the chains among `chain_ids` that the EthBridgeConfig contract at
+    /// `config_address` accepts transfers to.
+    pub async fn get_supported_chain_ids(
+        &self,
+        config_address: EthAddress,
+        chain_ids: &[u8],
+    ) -> BridgeResult<Vec<u8>> {
+        let config = EthBridgeConfig::new(config_address, Arc::new(self.provider.clone()));
+        let mut supported_chain_ids = vec![];
+        for chain_id in chain_ids {
+            let supported = config
+                .is_chain_supported(*chain_id)
+                .call()
+                .await
+                .map_err(contract_call_error)?;
+            if supported {
+                supported_chain_ids.push(*chain_id);
+            }
+        }
+        Ok(supported_chain_ids)
+    }
+
+    /// Returns the nonce of the next token transfer sent from Eth through the SuiBridge
+    /// contract at `bridge_address`.
+    pub async fn get_next_token_transfer_nonce(
+        &self,
+        bridge_address: EthAddress,
+    ) -> BridgeResult<u64> {
+        let bridge = EthSuiBridge::new(bridge_address, Arc::new(self.provider.clone()));
+        bridge
+            .nonces(BridgeActionType::TokenTransfer as u8)
+            .call()
+            .await
+            .map_err(contract_call_error)
+    }
+
+    /// Returns the bridge chain id of Eth, in the config of the SuiBridge contract at
+    /// `bridge_address`.
+    pub async fn get_bridge_chain_id(&self, bridge_address: EthAddress) -> BridgeResult<u8> {
+        let provider = Arc::new(self.provider.clone());
+        let bridge = EthSuiBridge::new(bridge_address, provider.clone());
+        let committee_address = bridge
+            .committee()
+            .call()
+            .await
+            .map_err(contract_call_error)?;
+        let committee = EthBridgeCommittee::new(committee_address, provider.clone());
+        let config_address = committee
+            .config()
+            .call()
+            .await
+            .map_err(contract_call_error)?;
+        EthBridgeConfig::new(config_address, provider)
+            .chain_id()
+            .call()
+            .await
+            .map_err(contract_call_error)
+    }
+
+    /// Returns whether the token transfer with `nonce` was claimed on the SuiBridge
+    /// contract at `bridge_address`.
+    pub async fn is_transfer_processed(
+        &self,
+        bridge_address: EthAddress,
+        nonce: u64,
+    ) -> BridgeResult<bool> {
+        let bridge = EthSuiBridge::new(bridge_address, Arc::new(self.provider.clone()));
+        bridge
+            .is_transfer_processed(nonce)
+            .call()
+            .await
+            .map_err(contract_call_error)
+    }
+
+    /// Returns whether the token transfers with `nonces` were claimed, in the same order.
+    /// Queries `batch_size` nonces at a time through the Multicall3 contract, and the
+    /// nonces of a batch that fails as a whole one by one, e.g. on chains without
+    /// Multicall3.
+    pub async fn are_transfers_processed(
+        &self,
+        bridge_address: EthAddress,
+        nonces: &[u64],
+        batch_size: usize,
+    ) -> Vec<BridgeResult<bool>> {
+        let bridge = EthSuiBridge::new(bridge_address, Arc::new(self.provider.clone()));
+        let mut processed = Vec::with_capacity(nonces.len());
+        for batch in nonces.chunks(batch_size.max(1)) {
+            match multicall_is_transfer_processed(&bridge, batch).await {
+                Ok(batch_processed) => processed.extend(batch_processed),
+                Err(e) => {
+                    tracing::warn!(
+                        "Failed to get a batch of {} transfer statuses, querying them one by one: {:?}",
+                        batch.len(),
+                        e
+                    );
+                    for nonce in batch {
+                        processed.push(self.is_transfer_processed(bridge_address, *nonce).await);
+                    }
+                }
+            }
+        }
+        processed
+    }
+
+    /// Returns the transaction that claimed the token transfer from `source_chain_id` with
+    /// `nonce` on the SuiBridge contract at `bridge_address`, None if it's not claimed
+    /// between `start_block` and the latest block.
+    pub async fn get_claim_tx_hash(
+        &self,
+        bridge_address: EthAddress,
+        source_chain_id: u8,
+        nonce: u64,
+        start_block: u64,
+    ) -> BridgeResult<Option<TxHash>> {
+        let bridge = EthSuiBridge::new(bridge_address, Arc::new(self.provider.clone()));
+        let claims = bridge
+            .tokens_claimed_filter()
+            .from_block(start_block)
+            .to_block(BlockNumber::Latest)
+            .topic1(U256::from(source_chain_id))
+            .topic2(U256::from(nonce))
+            .query_with_meta()
+            .await
+            .map_err(contract_call_error)?;
+        Ok(claims
+            .into_iter()
+            .find(|(claim, meta)| {
+                meta.address == bridge_address
+                    && claim.source_chain_id == source_chain_id
+                    && claim.nonce == nonce
+            })
+            .map(|(_, meta)| meta.transaction_hash))
+    }
+
+    /// Returns the number of the latest block, final or not.
+    pub async fn get_latest_block_id(&self) -> BridgeResult<u64> {
+        let block_number = self
+            .provider
+            .get_block_number()
+            .await
+            .map_err(BridgeError::from)?;
+        Ok(block_number.as_u64())
+    }
+}
+
+async fn multicall_is_transfer_processed<P: JsonRpcClient + 'static>(
+    bridge: &EthSuiBridge<Provider<P>>,
+    nonces: &[u64],
+) -> BridgeResult<Vec<BridgeResult<bool>>> {
+    let mut multicall = Multicall::new(bridge.client(), Some(MULTICALL_ADDRESS))
+        .await
+        .map_err(|e| BridgeError::Generic(format!("Failed to create multicall: {:?}", e)))?;
+    for nonce in nonces {
+        multicall.add_call(bridge.is_transfer_processed(*nonce), true);
+    }
+    let results = multicall
+        .call_raw()
+        .await
+        .map_err(|e| BridgeError::Generic(format!("Multicall failed: {:?}", e)))?;
+    if results.len() != nonces.len() {
+        return Err(BridgeError::Generic(format!(
+            "Multicall returned {} results for {} calls",
+            results.len(),
+            nonces.len()
+        )));
+    }
+    Ok(results
+        .into_iter()
+        .map(|result| match result {
+            Ok(Token::Bool(processed)) => Ok(processed),
+            Ok(token) => Err(BridgeError::Generic(format!(
+                "Unexpected isTransferProcessed result: {:?}",
+                token
+            ))),
+            Err(revert) => Err(BridgeError::Generic(format!(
+                "isTransferProcessed reverted: {}",
+                revert
+            ))),
+        })
+        .collect())
+}
+
 #[cfg(test)]
 mod tests {
     use ethers::types::{Address as EthAddress, Log, TransactionReceipt, U64};
     use prometheus::Registry;
 
     use super::*;
-    use crate::test_utils::{get_test_log_and_action, mock_last_finalized_block};
+    use crate::eth_receipt_proof::receipts_root;
+    use crate::test_utils::{
+        get_test_log_and_action, get_test_log_and_action_with_chain_ids, mock_block_timestamp,
+        mock_eth_call, mock_get_logs, mock_last_finalized_block,
+    };
+    use sui_types::bridge::BridgeChainId;
+
+    #[tokio::test]
+    async fn test_get_events_in_range_block_timestamps() {
+        telemetry_subscribers::init_for_testing();
+        let registry = Registry::new();
+        mysten_metrics::init_metrics(&registry);
+        let mock_provider = EthMockProvider::new();
+        let contract = EthAddress::repeat_byte(1);
+        let client =
+            EthClient::new_mocked(mock_provider.clone(), HashSet::from_iter(vec![contract]));
+        let (log1, _) = get_test_log_and_action(contract, TxHash::random(), 0);
+        let (mut log2, _) = get_test_log_and_action(contract, TxHash::random(), 0);
+        log2.block_number = Some(U64::from(2));
+        mock_get_logs(&mock_provider, contract, 1, 2, vec![log1, log2]);
+        // Only the timestamp of block 1 is served
+        mock_block_timestamp(&mock_provider, 1, 1_700_000_000);
+
+        let logs = client.get_events_in_range(contract, 1, 2).await.unwrap();
+        let timestamps = logs
+            .iter()
+            .map(|log| (log.block_number, log.block_timestamp_ms))
+            .collect::<Vec<_>>();
+        assert_eq!(timestamps, vec![(1, Some(1_700_000_000_000)), (2, None)]);
+    }
 
     #[tokio::test]
     async fn test_get_finalized_bridge_action_maybe() {
@@ -319,7 +935,7 @@ mod tests {
             .unwrap();
 
         let error = client
-            .get_finalized_bridge_action_maybe(eth_tx_hash, 0)
+            .get_finalized_bridge_action_maybe(eth_tx_hash, 0, 0)
             .await
             .unwrap_err();
         match error {
@@ -331,7 +947,7 @@ mod tests {
         mock_last_finalized_block(&mock_provider, 778);
 
         let error = client
-            .get_finalized_bridge_action_maybe(eth_tx_hash, 2)
+            .get_finalized_bridge_action_maybe(eth_tx_hash, 2, 0)
             .await
             .unwrap_err();
         // Receipt only has 2 logs
@@ -341,7 +957,7 @@ mod tests {
         };
 
         let error = client
-            .get_finalized_bridge_action_maybe(eth_tx_hash, 0)
+            .get_finalized_bridge_action_maybe(eth_tx_hash, 0, 0)
             .await
             .unwrap_err();
         // Same, `log` is not a BridgeEvent
@@ -351,10 +967,214 @@ mod tests {
         };
 
         let action = client
-            .get_finalized_bridge_action_maybe(eth_tx_hash, 1)
+            .get_finalized_bridge_action_maybe(eth_tx_hash, 1, 0)
+            .await
+            .unwrap();
+        assert_eq!(action, bridge_action);
+    }
+
+    #[tokio::test]
+    async fn test_get_verified_bridge_action_maybe() {
+        telemetry_subscribers::init_for_testing();
+        let registry = Registry::new();
+        mysten_metrics::init_metrics(&registry);
+        let metrics = Arc::new(BridgeMetrics::for_testing());
+        let mock_provider = EthMockProvider::new();
+        let header_provider = EthMockProvider::new();
+        mock_last_finalized_block(&mock_provider, 778);
+        let client = EthClient::new_mocked(
+            mock_provider.clone(),
+            HashSet::from_iter(vec![EthAddress::zero()]),
+        )
+        .with_receipt_verifier(EthReceiptVerifier::new(
+            Provider::new(header_provider.clone()),
+            metrics.clone(),
+        ));
+
+        // A block with a transfer and the deposit
+        let block_hash = H256::random();
+        let eth_tx_hash = TxHash::random();
+        let (deposit_log, bridge_action) =
+            get_test_log_and_action(EthAddress::zero(), eth_tx_hash, 0);
+        let receipt =
+            |tx_hash: TxHash, logs: Vec<Log>, cumulative_gas_used: u64| TransactionReceipt {
+                transaction_hash: tx_hash,
+                block_hash: Some(block_hash),
+                block_number: Some(U64::from(778)),
+                status: Some(U64::from(1)),
+                cumulative_gas_used: U256::from(cumulative_gas_used),
+                transaction_type: Some(U64::from(2)),
+                logs,
+                ..Default::default()
+            };
+        let transfer_log = Log {
+            address: EthAddress::random(),
+            topics: vec![H256::random(), H256::random()],
+            data: vec![7u8; 32].into(),
+            ..Default::default()
+        };
+        let receipts = vec![
+            receipt(TxHash::random(), vec![transfer_log], 21_000),
+            receipt(eth_tx_hash, vec![deposit_log], 90_000),
+        ];
+        let mock_receipts = |tx_receipt: &TransactionReceipt,
+                             receipts: &Vec<TransactionReceipt>| {
+            mock_provider
+                .add_response::<[TxHash; 1], TransactionReceipt, TransactionReceipt>(
+                    "eth_getTransactionReceipt",
+                    [eth_tx_hash],
+                    tx_receipt.clone(),
+                )
+                .unwrap();
+            mock_provider
+                .add_response::<[BlockNumber; 1], Vec<TransactionReceipt>, Vec<TransactionReceipt>>(
+                    "eth_getBlockReceipts",
+                    [BlockNumber::Number(U64::from(778))],
+                    receipts.clone(),
+                )
+                .unwrap();
+        };
+        // The header from the independent provider
+        header_provider
+            .add_response::<(H256, bool), Block<TxHash>, Block<TxHash>>(
+                "eth_getBlockByHash",
+                (block_hash, false),
+                Block {
+                    hash: Some(block_hash),
+                    number: Some(U64::from(778)),
+                    receipts_root: receipts_root(&receipts),
+                    ..Default::default()
+                },
+            )
+            .unwrap();
+        let verifications = |result: &str| {
+            metrics
+                .eth_receipt_verifications
+                .with_label_values(&[result])
+                .get()
+        };
+
+        mock_receipts(&receipts[1], &receipts);
+        let action = client
+            .get_verified_bridge_action_maybe(eth_tx_hash, 0, 0)
+            .await
+            .unwrap();
+        assert_eq!(action, bridge_action);
+        assert_eq!(verifications("verified"), 1);
+
+        // The provider fabricates the deposit, to another Sui address
+        let (fabricated_log, fabricated_action) =
+            get_test_log_and_action(EthAddress::zero(), eth_tx_hash, 0);
+        assert_ne!(fabricated_action, bridge_action);
+        let mut tampered_receipts = receipts.clone();
+        tampered_receipts[1].logs = vec![fabricated_log];
+        // Not verified, it's signed without the verifier
+        mock_receipts(&tampered_receipts[1], &tampered_receipts);
+        assert_eq!(
+            client
+                .get_finalized_bridge_action_maybe(eth_tx_hash, 0, 0)
+                .await
+                .unwrap(),
+            fabricated_action
+        );
+        let error = client
+            .get_verified_bridge_action_maybe(eth_tx_hash, 0, 0)
+            .await
+            .unwrap_err();
+        match error {
+            BridgeError::EthReceiptsRootMismatch(_) => {}
+            _ => panic!("expected EthReceiptsRootMismatch, got {:?}", error),
+        };
+        assert_eq!(verifications("mismatch"), 1);
+
+        // Only the receipt of the transaction is fabricated, the deposit is built from
+        // the verified receipts of the block, whose root is cached
+        mock_receipts(&tampered_receipts[1], &receipts);
+        let action = client
+            .get_verified_bridge_action_maybe(eth_tx_hash, 0, 0)
+            .await
+            .unwrap();
+        assert_eq!(action, bridge_action);
+        assert_eq!(verifications("cached"), 1);
+        assert_eq!(verifications("verified"), 1);
+
+        // Receipts of another block
+        let mut other_block_receipts = receipts.clone();
+        other_block_receipts[0].block_hash = Some(H256::random());
+        mock_receipts(&receipts[1], &other_block_receipts);
+        let error = client
+            .get_verified_bridge_action_maybe(eth_tx_hash, 0, 0)
+            .await
+            .unwrap_err();
+        assert!(matches!(error, BridgeError::EthReceiptsRootMismatch(_)));
+    }
+
+    #[tokio::test]
+    async fn test_get_finalized_bridge_action_maybe_custom_evm_chain() {
+        telemetry_subscribers::init_for_testing();
+        let registry = Registry::new();
+        mysten_metrics::init_metrics(&registry);
+        let mock_provider = EthMockProvider::new();
+        // The devnet does not serve finalized blocks, 3 confirmations are final instead
+        mock_provider
+            .add_response("eth_blockNumber", (), U64::from(780))
+            .unwrap();
+        let client = EthClient::new_mocked(
+            mock_provider.clone(),
+            HashSet::from_iter(vec![EthAddress::zero()]),
+        )
+        .with_finality(EthFinality::Confirmations(3));
+        assert_eq!(client.get_last_finalized_block_id().await.unwrap(), 777);
+
+        let eth_tx_hash = TxHash::random();
+        let (mut log, bridge_action) = get_test_log_and_action_with_chain_ids(
+            EthAddress::zero(),
+            eth_tx_hash,
+            0,
+            BridgeChainId::EthCustom,
+            BridgeChainId::SuiCustom,
+        );
+        log.block_number = Some(U64::from(778));
+        mock_provider
+            .add_response::<[TxHash; 1], TransactionReceipt, TransactionReceipt>(
+                "eth_getTransactionReceipt",
+                [eth_tx_hash],
+                TransactionReceipt {
+                    block_number: log.block_number,
+                    logs: vec![log.clone()],
+                    ..Default::default()
+                },
+            )
+            .unwrap();
+        let error = client
+            .get_finalized_bridge_action_maybe(eth_tx_hash, 0, 0)
+            .await
+            .unwrap_err();
+        match error {
+            BridgeError::TxNotFinalized => {}
+            _ => panic!("expected TxNotFinalized"),
+        };
+
+        mock_provider
+            .add_response("eth_blockNumber", (), U64::from(781))
+            .unwrap();
+        let action = client
+            .get_finalized_bridge_action_maybe(eth_tx_hash, 0, 0)
             .await
             .unwrap();
         assert_eq!(action, bridge_action);
+        assert_eq!(action.chain_id(), BridgeChainId::EthCustom);
+        // The digest only depends on the deposit, and differs from the same deposit on
+        // a chain with a reserved id
+        assert_eq!(action.digest(), bridge_action.digest());
+        let BridgeAction::EthToSuiBridgeAction(mut sepolia_action) = bridge_action else {
+            panic!("expected EthToSuiBridgeAction");
+        };
+        sepolia_action.eth_bridge_event.eth_chain_id = BridgeChainId::EthSepolia;
+        assert_ne!(
+            action.digest(),
+            BridgeAction::EthToSuiBridgeAction(sepolia_action).digest()
+        );
     }
 
     #[tokio::test]
@@ -393,7 +1213,7 @@ mod tests {
             .unwrap();
 
         let error = client
-            .get_finalized_bridge_action_maybe(eth_tx_hash, 0)
+            .get_finalized_bridge_action_maybe(eth_tx_hash, 0, 0)
             .await
             .unwrap_err();
         match error {
@@ -416,9 +1236,99 @@ mod tests {
             )
             .unwrap();
         let action = client
-            .get_finalized_bridge_action_maybe(eth_tx_hash, 0)
+            .get_finalized_bridge_action_maybe(eth_tx_hash, 0, 0)
             .await
             .unwrap();
         assert_eq!(action, bridge_action);
     }
+
+    #[tokio::test]
+    async fn test_are_transfers_processed() {
+        telemetry_subscribers::init_for_testing();
+        let mock_provider = EthMockProvider::new();
+        let bridge_address = EthAddress::repeat_byte(7);
+        let bridge = EthSuiBridge::new(
+            bridge_address,
+            Arc::new(Provider::new(mock_provider.clone())),
+        );
+        let client = EthClient::new_mocked(mock_provider.clone(), HashSet::new());
+        let call_result = |success: bool, processed: bool| {
+            Token::Tuple(vec![
+                Token::Bool(success),
+                Token::Bytes(ethers::abi::encode(&[Token::Bool(processed)])),
+            ])
+        };
+
+        // Nonces 0 to 2 in one multicall, nonce 1 reverts
+        let mut multicall = Multicall::new(bridge.client(), Some(MULTICALL_ADDRESS))
+            .await
+            .unwrap();
+        for nonce in 0..3 {
+            multicall.add_call(bridge.is_transfer_processed(nonce), true);
+        }
+        mock_eth_call(
+            &mock_provider,
+            &multicall.as_aggregate_3().tx,
+            &[Token::Array(vec![
+                call_result(true, true),
+                call_result(false, false),
+                call_result(true, false),
+            ])],
+        );
+        // Nonces 3 and 4 have no multicall response and are queried one by one
+        for (nonce, processed) in [(3, true), (4, false)] {
+            mock_eth_call(
+                &mock_provider,
+                &bridge.is_transfer_processed(nonce).tx,
+                &[Token::Bool(processed)],
+            );
+        }
+
+        let processed = client
+            .are_transfers_processed(bridge_address, &[0, 1, 2, 3, 4], 3)
+            .await;
+        assert_eq!(processed.len(), 5);
+        assert!(processed[0].as_ref().unwrap());
+        assert!(processed[1].is_err());
+        assert!(!processed[2].as_ref().unwrap());
+        assert!(processed[3].as_ref().unwrap());
+        assert!(!processed[4].as_ref().unwrap());
+    }
+
+    #[test]
+    fn test_provider_error_kind() {
+        use ethers::providers::{HttpClientError, JsonRpcError};
+
+        let json_rpc_error = |code| {
+            ProviderError::JsonRpcClientError(Box::new(HttpClientError::JsonRpcError(
+                JsonRpcError {
+                    code,
+                    message: "error".to_string(),
+                    data: None,
+                },
+            )))
+        };
+        for (err, kind) in [
+            (json_rpc_error(-32005), ErrorKind::RateLimited),
+            (json_rpc_error(-32603), ErrorKind::Transient),
+            (json_rpc_error(-32602), ErrorKind::Permanent),
+            // A proxy answered with something else than JSON-RPC
+            (
+                ProviderError::JsonRpcClientError(Box::new(HttpClientError::SerdeJson {
+                    err: serde_json::from_str::<u64>("<html>").unwrap_err(),
+                    text: "<html>".to_string(),
+                })),
+                ErrorKind::Transient,
+            ),
+            (
+                ProviderError::CustomError("unsupported".to_string()),
+                ErrorKind::Permanent,
+            ),
+        ] {
+            assert_eq!(err.kind(), kind, "{err:?}");
+            let err = BridgeError::from(err);
+            assert!(matches!(err, BridgeError::ProviderError(k, _) if k == kind));
+            assert_eq!(err.component(), crate::error::ErrorComponent::EthRpc);
+        }
+    }
 }
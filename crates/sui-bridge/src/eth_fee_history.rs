@@ -0,0 +1,339 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Samples the fee market of Eth with `eth_feeHistory`, so that operators see the fees
+//! the node would pay, and so that the transactions the node sends on Eth can be priced
+//! from the last sample rather than estimated one by one. Each sample covers the last
+//! `FEE_HISTORY_BLOCKS` blocks. Sampling is paused with a backoff while the provider
+//! rate limits the queries, to leave the quota to the syncer and the signer.
+
+use crate::error::{BridgeResult, ErrorKind};
+use crate::eth_client::EthClient;
+use crate::metrics::BridgeMetrics;
+use ethers::providers::JsonRpcClient;
+use ethers::types::{FeeHistory, U256};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::watch;
+use tokio::time;
+use tracing::{info, warn};
+
+pub const FEE_HISTORY_BLOCKS: u64 = 20;
+pub const PRIORITY_FEE_PERCENTILES: &[f64] = &[10.0, 50.0, 90.0];
+// Weight of each new block in the base fee trend
+const BASE_FEE_EWMA_ALPHA: f64 = 0.2;
+// Maximum number of intervals skipped while the provider rate limits the queries
+const MAX_RATE_LIMITED_SKIPS: u32 = 16;
+
+/// The fee market as of the newest block of a sample.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FeeMarket {
+    pub newest_block: u64,
+    /// Base fee of the block after the newest one
+    pub base_fee_wei: u128,
+    /// Exponentially weighted moving average of the base fees of the sampled blocks,
+    /// oldest first. Above `base_fee_wei` when fees are going down.
+    pub base_fee_ewma_wei: f64,
+    /// For each of the requested percentiles, the median over the sampled blocks of the
+    /// priority fee paid at that percentile of each block. Empty blocks are left out.
+    pub priority_fees_wei: Vec<(f64, u128)>,
+}
+
+impl FeeMarket {
+    /// Returns None if `history` has no blocks.
+    pub fn from_fee_history(history: &FeeHistory, percentiles: &[f64]) -> Option<Self> {
+        let base_fee = *history.base_fee_per_gas.last()?;
+        let sampled_blocks = history.base_fee_per_gas.len() as u64 - 1;
+        let newest_block = (history.oldest_block.as_u64() + sampled_blocks).saturating_sub(1);
+        let base_fee_ewma_wei = history
+            .base_fee_per_gas
+            .iter()
+            .map(|fee| fee.low_u128() as f64)
+            .reduce(|ewma, fee| BASE_FEE_EWMA_ALPHA * fee + (1.0 - BASE_FEE_EWMA_ALPHA) * ewma)
+            .unwrap_or_default();
+        let priority_fees_wei = percentiles
+            .iter()
+            .enumerate()
+            .map(|(i, percentile)| {
+                let fees = history
+                    .reward
+                    .iter()
+                    .zip(&history.gas_used_ratio)
+                    .filter(|(_, gas_used_ratio)| **gas_used_ratio > 0.0)
+                    .filter_map(|(rewards, _)| rewards.get(i).map(U256::low_u128))
+                    .collect::<Vec<_>>();
+                (*percentile, median(fees))
+            })
+            .collect();
+        Some(Self {
+            newest_block,
+            base_fee_wei: base_fee.low_u128(),
+            base_fee_ewma_wei,
+            priority_fees_wei,
+        })
+    }
+}
+
+// 0 for no values
+fn median(mut values: Vec<u128>) -> u128 {
+    if values.is_empty() {
+        return 0;
+    }
+    values.sort_unstable();
+    let mid = values.len() / 2;
+    if values.len() % 2 == 0 {
+        values[mid - 1] / 2 + values[mid] / 2 + (values[mid - 1] % 2 + values[mid] % 2) / 2
+    } else {
+        values[mid]
+    }
+}
+
+pub struct EthFeeSampler<P> {
+    eth_client: Arc<EthClient<P>>,
+    metrics: Arc<BridgeMetrics>,
+    interval: Duration,
+    sender: watch::Sender<Option<FeeMarket>>,
+    chain_id: Option<String>,
+    // Intervals to skip before the next sample, and how many to skip after the next
+    // rate limited one
+    skips: u32,
+    next_skips: u32,
+}
+
+impl<P> EthFeeSampler<P>
+where
+    P: JsonRpcClient + 'static,
+{
+    pub fn new(
+        eth_client: Arc<EthClient<P>>,
+        metrics: Arc<BridgeMetrics>,
+        interval: Duration,
+    ) -> Self {
+        Self {
+            eth_client,
+            metrics,
+            interval,
+            sender: watch::channel(None).0,
+            chain_id: None,
+            skips: 0,
+            next_skips: 1,
+        }
+    }
+
+    /// Receives the fee market of every sample, None until the first one.
+    pub fn subscribe(&self) -> watch::Receiver<Option<FeeMarket>> {
+        self.sender.subscribe()
+    }
+
+    pub async fn run(mut self) {
+        info!("Starting EthFeeSampler");
+        let mut interval = time::interval(self.interval);
+        interval.set_missed_tick_behavior(time::MissedTickBehavior::Skip);
+        loop {
+            interval.tick().await;
+            if self.skips > 0 {
+                self.skips -= 1;
+                continue;
+            }
+            let result = self.sample().await;
+            self.record(result);
+        }
+    }
+
+    async fn sample(&mut self) -> BridgeResult<Option<FeeMarket>> {
+        let chain_id = match &self.chain_id {
+            Some(chain_id) => chain_id.clone(),
+            None => {
+                let chain_id = self.eth_client.get_chain_id().await?.to_string();
+                self.chain_id = Some(chain_id.clone());
+                chain_id
+            }
+        };
+        let history = self
+            .eth_client
+            .get_fee_history(FEE_HISTORY_BLOCKS, PRIORITY_FEE_PERCENTILES)
+            .await?;
+        let Some(fee_market) = FeeMarket::from_fee_history(&history, PRIORITY_FEE_PERCENTILES)
+        else {
+            return Ok(None);
+        };
+        self.metrics
+            .eth_base_fee_wei
+            .with_label_values(&[&chain_id])
+            .set(fee_market.base_fee_wei as f64);
+        self.metrics
+            .eth_base_fee_ewma_wei
+            .with_label_values(&[&chain_id])
+            .set(fee_market.base_fee_ewma_wei);
+        for (percentile, fee) in &fee_market.priority_fees_wei {
+            self.metrics
+                .eth_priority_fee_wei
+                .with_label_values(&[&chain_id, &percentile.to_string()])
+                .set(*fee as f64);
+        }
+        Ok(Some(fee_market))
+    }
+
+    /// Publishes the fee market of a successful sample, and pauses sampling for a few
+    /// intervals after a rate limited one, twice as many each time in a row.
+    fn record(&mut self, result: BridgeResult<Option<FeeMarket>>) {
+        match result {
+            Ok(fee_market) => {
+                self.next_skips = 1;
+                self.metrics.eth_fee_sampling_paused.set(0);
+                if fee_market.is_some() {
+                    self.sender.send_replace(fee_market);
+                }
+            }
+            Err(e) if e.kind() == ErrorKind::RateLimited => {
+                warn!(
+                    "Eth fee history is rate limited, pausing sampling for {} intervals",
+                    self.next_skips
+                );
+                self.skips = self.next_skips;
+                self.next_skips = (self.next_skips * 2).min(MAX_RATE_LIMITED_SKIPS);
+                self.metrics.eth_fee_sampling_paused.set(1);
+            }
+            Err(e) => {
+                self.metrics.eth_fee_sampling_paused.set(0);
+                warn!("Couldn't sample Eth fee history: {:?}", e);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::BridgeError;
+    use crate::eth_mock_provider::EthMockProvider;
+    use std::collections::HashSet;
+
+    const GWEI: u64 = 1_000_000_000;
+
+    // Blocks 100 to 104, the second one empty
+    fn fee_history() -> FeeHistory {
+        FeeHistory {
+            base_fee_per_gas: [10, 12, 11, 13, 14, 15]
+                .iter()
+                .map(|fee| U256::from(fee * GWEI))
+                .collect(),
+            gas_used_ratio: vec![0.4, 0.0, 0.6, 0.9, 0.7],
+            oldest_block: U256::from(100),
+            reward: vec![
+                vec![1u64, 2, 5],
+                vec![0, 0, 0],
+                vec![1, 3, 8],
+                vec![2, 4, 9],
+                vec![1, 3, 6],
+            ]
+            .into_iter()
+            .map(|rewards| rewards.into_iter().map(|r| U256::from(r * GWEI)).collect())
+            .collect(),
+        }
+    }
+
+    #[test]
+    fn test_fee_market_from_fee_history() {
+        let fee_market =
+            FeeMarket::from_fee_history(&fee_history(), PRIORITY_FEE_PERCENTILES).unwrap();
+        assert_eq!(fee_market.newest_block, 104);
+        assert_eq!(fee_market.base_fee_wei, 15 * GWEI as u128);
+        // The empty block is left out: medians of [1, 1, 1, 2], [2, 3, 3, 4] and [5, 6, 8, 9]
+        assert_eq!(
+            fee_market.priority_fees_wei,
+            vec![
+                (10.0, GWEI as u128),
+                (50.0, 3 * GWEI as u128),
+                (90.0, 7 * GWEI as u128),
+            ]
+        );
+
+        // 10, then 0.8 * 10 + 0.2 * 12 = 10.4, 10.52, 11.016, 11.6128 and 12.29024
+        let expected_ewma = 12.29024 * GWEI as f64;
+        assert!((fee_market.base_fee_ewma_wei - expected_ewma).abs() < 1.0);
+        // Fees went up, the trend lags behind
+        assert!(fee_market.base_fee_ewma_wei < fee_market.base_fee_wei as f64);
+
+        assert_eq!(median(vec![]), 0);
+        assert_eq!(median(vec![3, 1, 2]), 2);
+        assert_eq!(median(vec![u128::MAX, u128::MAX]), u128::MAX);
+        assert_eq!(
+            FeeMarket::from_fee_history(
+                &FeeHistory {
+                    base_fee_per_gas: vec![],
+                    gas_used_ratio: vec![],
+                    oldest_block: U256::zero(),
+                    reward: vec![],
+                },
+                PRIORITY_FEE_PERCENTILES
+            ),
+            None
+        );
+    }
+
+    #[tokio::test]
+    async fn test_eth_fee_sampler() {
+        let metrics = Arc::new(BridgeMetrics::for_testing());
+        let eth_mock_provider = EthMockProvider::new();
+        eth_mock_provider
+            .add_response("eth_chainId", (), U256::from(11155111))
+            .unwrap();
+        eth_mock_provider
+            .add_response(
+                "eth_feeHistory",
+                (
+                    U256::from(FEE_HISTORY_BLOCKS),
+                    "latest",
+                    PRIORITY_FEE_PERCENTILES,
+                ),
+                fee_history(),
+            )
+            .unwrap();
+        let eth_client = Arc::new(EthClient::new_mocked(eth_mock_provider, HashSet::new()));
+        let mut sampler = EthFeeSampler::new(eth_client, metrics.clone(), Duration::from_secs(1));
+        let receiver = sampler.subscribe();
+        assert_eq!(*receiver.borrow(), None);
+
+        let result = sampler.sample().await;
+        sampler.record(result);
+        let fee_market = receiver.borrow().clone().unwrap();
+        assert_eq!(fee_market.newest_block, 104);
+        assert_eq!(
+            metrics
+                .eth_base_fee_wei
+                .with_label_values(&["11155111"])
+                .get(),
+            (15 * GWEI) as f64
+        );
+        assert_eq!(
+            metrics
+                .eth_priority_fee_wei
+                .with_label_values(&["11155111", "50"])
+                .get(),
+            (3 * GWEI) as f64
+        );
+
+        // Rate limited samples pause sampling for longer and longer
+        let rate_limited = || {
+            Err(BridgeError::ProviderError(
+                ErrorKind::RateLimited,
+                "limit exceeded".to_string(),
+            ))
+        };
+        for expected_skips in [1, 2, 4, 8, 16, 16] {
+            sampler.record(rate_limited());
+            assert_eq!(sampler.skips, expected_skips);
+            assert_eq!(metrics.eth_fee_sampling_paused.get(), 1);
+        }
+        // The last fee market is kept meanwhile
+        assert_eq!(receiver.borrow().as_ref(), Some(&fee_market));
+        // and sampling resumes once the provider answers
+        sampler.skips = 0;
+        let result = sampler.sample().await;
+        sampler.record(result);
+        assert_eq!(metrics.eth_fee_sampling_paused.get(), 0);
+        sampler.record(rate_limited());
+        assert_eq!(sampler.skips, 1);
+    }
+}
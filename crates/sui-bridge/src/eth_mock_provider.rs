@@ -28,6 +28,8 @@ enum MockParams {
 #[derive(Clone, Debug)]
 pub struct EthMockProvider {
     responses: Arc<Mutex<HashMap<(String, MockParams), Value>>>,
+    // Number of requests by method
+    requests: Arc<Mutex<HashMap<String, usize>>>,
 }
 
 impl Default for EthMockProvider {
@@ -49,6 +51,12 @@ impl JsonRpcClient for EthMockProvider {
         method: &str,
         params: P,
     ) -> Result<R, MockError> {
+        *self
+            .requests
+            .lock()
+            .unwrap()
+            .entry(method.to_owned())
+            .or_default() += 1;
         let params = if std::mem::size_of::<P>() == 0 {
             MockParams::Zst
         } else {
@@ -71,9 +79,20 @@ impl EthMockProvider {
     pub fn new() -> Self {
         Self {
             responses: Arc::new(Mutex::new(HashMap::new())),
+            requests: Arc::new(Mutex::new(HashMap::new())),
         }
     }
 
+    /// Number of requests of `method` so far, answered or not.
+    pub fn requests(&self, method: &str) -> usize {
+        self.requests
+            .lock()
+            .unwrap()
+            .get(method)
+            .copied()
+            .unwrap_or_default()
+    }
+
     pub fn add_response<P: Serialize + Send + Sync, T: Serialize + Send + Sync, K: Borrow<T>>(
         &self,
         method: &str,
@@ -92,6 +111,11 @@ impl EthMockProvider {
             .insert((method.to_owned(), params), value);
         Ok(())
     }
+
+    /// Removes all responses, so that every request fails like with an unreachable provider.
+    pub fn clear_responses(&self) {
+        self.responses.lock().unwrap().clear();
+    }
 }
 
 #[cfg(test)]
@@ -0,0 +1,305 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Verification of the Eth logs the server signs against the receipts root of their
+//! block, for nodes that use a provider they don't trust, e.g. a free public RPC. Such a
+//! provider could respond with a fabricated deposit to trick the node into signing it.
+//! With `verify-eth-receipts`, the server fetches all the receipts of the block of a
+//! deposit from its provider, recomputes the root of the receipts trie, and only accepts
+//! the deposit if the root matches the `receiptsRoot` of the block header fetched from
+//! a second, independent provider. The deposit is then built from the verified receipt.
+//! This costs a `eth_getBlockReceipts` per signing request, and a header per block, so
+//! the roots of the verified blocks are cached. The syncers are not verified.
+
+use crate::error::{BridgeError, BridgeResult, ErrorKind};
+use crate::metrics::BridgeMetrics;
+use ethers::providers::{JsonRpcClient, Middleware, Provider};
+use ethers::types::{TransactionReceipt, H256};
+use ethers::utils::keccak256;
+use ethers::utils::rlp::RlpStream;
+use lru::LruCache;
+use std::num::NonZeroUsize;
+use std::sync::{Arc, Mutex};
+use tracing::warn;
+
+pub const DEFAULT_VERIFIED_ROOTS_CACHE_SIZE: usize = 1024;
+
+/// Returns the EIP-2718 encoding of `receipt`, the value of its entry in the receipts
+/// trie: the RLP of its status (or state root before Byzantium), cumulative gas used,
+/// logs bloom and logs, prefixed with the transaction type unless it's a legacy one.
+pub fn encode_receipt(receipt: &TransactionReceipt) -> Vec<u8> {
+    let mut stream = RlpStream::new_list(4);
+    match (receipt.status, receipt.root) {
+        (Some(status), _) => stream.append(&status.as_u64()),
+        (None, Some(root)) => stream.append(&root),
+        (None, None) => stream.append_empty_data(),
+    };
+    stream.append(&receipt.cumulative_gas_used);
+    stream.append(&receipt.logs_bloom);
+    stream.begin_list(receipt.logs.len());
+    for log in &receipt.logs {
+        stream.begin_list(3);
+        stream.append(&log.address);
+        stream.append_list::<H256, H256>(&log.topics);
+        stream.append(&log.data.to_vec());
+    }
+    let encoded = stream.out().to_vec();
+    match receipt.transaction_type.map(|tx_type| tx_type.as_u64()) {
+        Some(tx_type) if tx_type != 0 => [vec![tx_type as u8], encoded].concat(),
+        _ => encoded,
+    }
+}
+
+/// Returns the root of the receipts trie of a block with `receipts`, in the order of
+/// their transactions.
+pub fn receipts_root(receipts: &[TransactionReceipt]) -> H256 {
+    trie_root(
+        receipts
+            .iter()
+            .enumerate()
+            .map(|(index, receipt)| {
+                (
+                    ethers::utils::rlp::encode(&(index as u64)).to_vec(),
+                    encode_receipt(receipt),
+                )
+            })
+            .collect(),
+    )
+}
+
+/// Returns the root of the Merkle Patricia trie with `entries`, as keys and values.
+pub fn trie_root(entries: Vec<(Vec<u8>, Vec<u8>)>) -> H256 {
+    let mut entries = entries
+        .into_iter()
+        .map(|(key, value)| (to_nibbles(&key), value))
+        .collect::<Vec<_>>();
+    if entries.is_empty() {
+        // The hash of the empty string
+        return H256::from(keccak256([0x80u8]));
+    }
+    entries.sort();
+    H256::from(keccak256(encode_node(&entries, 0)))
+}
+
+fn to_nibbles(key: &[u8]) -> Vec<u8> {
+    key.iter()
+        .flat_map(|byte| [byte >> 4, byte & 0x0f])
+        .collect()
+}
+
+// Compact encoding of the path of a leaf or an extension node
+fn hex_prefix(nibbles: &[u8], leaf: bool) -> Vec<u8> {
+    let flag = if leaf { 2 } else { 0 } + (nibbles.len() % 2) as u8;
+    let (mut encoded, rest) = if nibbles.len() % 2 == 1 {
+        (vec![flag << 4 | nibbles[0]], &nibbles[1..])
+    } else {
+        (vec![flag << 4], nibbles)
+    };
+    encoded.extend(rest.chunks(2).map(|pair| pair[0] << 4 | pair[1]));
+    encoded
+}
+
+// Returns the RLP of the node of the sorted `entries`, whose keys share their first
+// `depth` nibbles
+fn encode_node(entries: &[(Vec<u8>, Vec<u8>)], depth: usize) -> Vec<u8> {
+    if let [(key, value)] = entries {
+        let mut stream = RlpStream::new_list(2);
+        stream.append(&hex_prefix(&key[depth..], true));
+        stream.append(value);
+        return stream.out().to_vec();
+    }
+    // The entries are sorted, so the prefix of the first and the last is the one of all
+    let first = &entries[0].0[depth..];
+    let last = &entries[entries.len() - 1].0[depth..];
+    let prefix_len = first
+        .iter()
+        .zip(last)
+        .take_while(|(first, last)| first == last)
+        .count();
+    if prefix_len > 0 {
+        let mut stream = RlpStream::new_list(2);
+        stream.append(&hex_prefix(&first[..prefix_len], false));
+        append_child(&mut stream, &encode_node(entries, depth + prefix_len));
+        return stream.out().to_vec();
+    }
+    let mut stream = RlpStream::new_list(17);
+    let (value, mut rest) = match entries {
+        [(key, value), rest @ ..] if key.len() == depth => (Some(value), rest),
+        _ => (None, entries),
+    };
+    for nibble in 0..16 {
+        let count = rest
+            .iter()
+            .take_while(|(key, _)| key[depth] == nibble)
+            .count();
+        if count == 0 {
+            stream.append_empty_data();
+        } else {
+            append_child(&mut stream, &encode_node(&rest[..count], depth + 1));
+            rest = &rest[count..];
+        }
+    }
+    match value {
+        Some(value) => stream.append(value),
+        None => stream.append_empty_data(),
+    };
+    stream.out().to_vec()
+}
+
+// Nodes shorter than a hash are embedded in their parent
+fn append_child(stream: &mut RlpStream, node: &[u8]) {
+    if node.len() < 32 {
+        stream.append_raw(node, 1);
+    } else {
+        stream.append(&keccak256(node).to_vec());
+    }
+}
+
+/// Verifies the receipts of Eth blocks against the headers of an independent provider.
+pub struct EthReceiptVerifier<P> {
+    header_provider: Provider<P>,
+    // Receipts roots of the blocks verified before, by block hash
+    verified_roots: Mutex<LruCache<H256, H256>>,
+    metrics: Arc<BridgeMetrics>,
+}
+
+impl<P> EthReceiptVerifier<P>
+where
+    P: JsonRpcClient,
+{
+    pub fn new(header_provider: Provider<P>, metrics: Arc<BridgeMetrics>) -> Self {
+        Self {
+            header_provider,
+            verified_roots: Mutex::new(LruCache::new(
+                NonZeroUsize::new(DEFAULT_VERIFIED_ROOTS_CACHE_SIZE).unwrap(),
+            )),
+            metrics,
+        }
+    }
+
+    /// Checks that `receipts` are all the receipts of the block `block_hash`, namely
+    /// that they hash to the receipts root of its header.
+    pub async fn verify_block_receipts(
+        &self,
+        block_hash: H256,
+        receipts: &[TransactionReceipt],
+    ) -> BridgeResult<()> {
+        if let Some(receipt) = receipts
+            .iter()
+            .find(|receipt| receipt.block_hash != Some(block_hash))
+        {
+            return Err(BridgeError::EthReceiptsRootMismatch(format!(
+                "Receipt of {:?} is in block {:?}, not in {:?}",
+                receipt.transaction_hash, receipt.block_hash, block_hash
+            )));
+        }
+        let root = receipts_root(receipts);
+        let cached_root = self
+            .verified_roots
+            .lock()
+            .unwrap()
+            .get(&block_hash)
+            .copied();
+        let (expected_root, result) = match cached_root {
+            Some(cached_root) => (cached_root, "cached"),
+            None => (self.get_receipts_root(block_hash).await?, "verified"),
+        };
+        if root != expected_root {
+            warn!(
+                "Receipts of block {:?} hash to {:?}, but its receipts root is {:?}",
+                block_hash, root, expected_root
+            );
+            self.metrics
+                .eth_receipt_verifications
+                .with_label_values(&["mismatch"])
+                .inc();
+            return Err(BridgeError::EthReceiptsRootMismatch(format!(
+                "Receipts of block {:?} hash to {:?}, expected {:?}",
+                block_hash, root, expected_root
+            )));
+        }
+        self.verified_roots.lock().unwrap().put(block_hash, root);
+        self.metrics
+            .eth_receipt_verifications
+            .with_label_values(&[result])
+            .inc();
+        Ok(())
+    }
+
+    async fn get_receipts_root(&self, block_hash: H256) -> BridgeResult<H256> {
+        let block = self
+            .header_provider
+            .get_block(block_hash)
+            .await
+            .map_err(BridgeError::from)?
+            // The independent provider may not have the block yet
+            .ok_or_else(|| {
+                BridgeError::ProviderError(
+                    ErrorKind::NotReady,
+                    format!(
+                        "Block {:?} is not found by the independent provider",
+                        block_hash
+                    ),
+                )
+            })?;
+        Ok(block.receipts_root)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::str::FromStr;
+
+    #[test]
+    fn test_trie_root() {
+        assert_eq!(
+            trie_root(vec![]),
+            H256::from_str("0x56e81f171bcc55a6ff8345e692c0f86e5b48e01b996cadc001622fb5e363b421")
+                .unwrap()
+        );
+        // Same as the tests of the trie of go-ethereum
+        let entries = |entries: &[(&str, &str)]| {
+            entries
+                .iter()
+                .map(|(key, value)| (key.as_bytes().to_vec(), value.as_bytes().to_vec()))
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(
+            trie_root(entries(&[
+                ("doe", "reindeer"),
+                ("dog", "puppy"),
+                ("dogglesworth", "cat"),
+            ])),
+            H256::from_str("0x8aad789dff2f538bca5d8ea56e8abe10f4c7ba3a5dea95fea4cd6e7c3a1168d3")
+                .unwrap()
+        );
+        // The order of the entries doesn't matter
+        assert_eq!(
+            trie_root(entries(&[
+                ("dogglesworth", "cat"),
+                ("doe", "reindeer"),
+                ("dog", "puppy"),
+            ])),
+            H256::from_str("0x8aad789dff2f538bca5d8ea56e8abe10f4c7ba3a5dea95fea4cd6e7c3a1168d3")
+                .unwrap()
+        );
+    }
+
+    #[test]
+    fn test_hex_prefix() {
+        assert_eq!(hex_prefix(&[1, 2, 3, 4, 5], false), vec![0x11, 0x23, 0x45]);
+        assert_eq!(
+            hex_prefix(&[0, 1, 2, 3, 4, 5], false),
+            vec![0x00, 0x01, 0x23, 0x45]
+        );
+        assert_eq!(
+            hex_prefix(&[0, 0xf, 1, 0xc, 0xb, 8], true),
+            vec![0x20, 0x0f, 0x1c, 0xb8]
+        );
+        assert_eq!(
+            hex_prefix(&[0xf, 1, 0xc, 0xb, 8], true),
+            vec![0x3f, 0x1c, 0xb8]
+        );
+    }
+}
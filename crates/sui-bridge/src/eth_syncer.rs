@@ -6,27 +6,49 @@
 //! only query from that block number onwards. The syncer also keeps track of the last finalized
 //! block on Ethereum and will only query for events up to that block number.
 
+use crate::abi::{eth_bridge_event_topics, EthBridgeEvent};
+use crate::adaptive_interval::{set_period, AdaptiveInterval, AdaptiveIntervalBounds};
+use crate::chain_health::PipelineChain;
+use crate::cursor_reset::CursorReload;
 use crate::error::BridgeResult;
 use crate::eth_client::EthClient;
 use crate::metrics::BridgeMetrics;
 use crate::retry_with_max_elapsed_time;
+use crate::storage::BridgeOrchestratorTables;
+use crate::supervisor::{RestartPolicy, TaskStart, TaskSupervisor};
 use crate::types::EthLog;
-use ethers::types::Address as EthAddress;
+use ethers::types::{Address as EthAddress, H256};
 use mysten_metrics::spawn_logged_monitored_task;
 use std::collections::HashMap;
-use std::sync::Arc;
-use tokio::sync::watch;
+use std::sync::{Arc, Mutex};
+use tokio::sync::{watch, Notify};
 use tokio::task::JoinHandle;
 use tokio::time::{self, Duration, Instant};
-use tracing::error;
+use tracing::{error, warn};
 
-const ETH_LOG_QUERY_MAX_BLOCK_RANGE: u64 = 1000;
-const ETH_EVENTS_CHANNEL_SIZE: usize = 1000;
+pub(crate) const ETH_LOG_QUERY_MAX_BLOCK_RANGE: u64 = 1000;
+pub const ETH_EVENTS_CHANNEL_SIZE: usize = 1000;
+pub const ETH_EVENTS_SEND_TIMEOUT: Duration = Duration::from_secs(30);
 const FINALIZED_BLOCK_QUERY_INTERVAL: Duration = Duration::from_secs(5);
 
 pub struct EthSyncer<P> {
     eth_client: Arc<EthClient<P>>,
     contract_addresses: EthTargetAddresses,
+    events_channel_size: usize,
+    events_send_timeout: Duration,
+    cursor_reloads: HashMap<EthAddress, CursorReload<u64>>,
+    restart_policy: Option<RestartPolicy>,
+    store: Option<Arc<BridgeOrchestratorTables>>,
+    finalized_block_interval: AdaptiveIntervalBounds,
+    event_topics: Vec<H256>,
+}
+
+/// The interval of the queries of the last finalized block, which the listening tasks
+/// follow. It adapts to the events they observe.
+struct FinalizedBlockInterval {
+    interval: Mutex<AdaptiveInterval>,
+    // Notified on a burst of events, to query the next finalized block right away
+    burst: Notify,
 }
 
 /// Map from contract address to their start block.
@@ -41,11 +63,64 @@ where
         Self {
             eth_client,
             contract_addresses,
+            events_channel_size: ETH_EVENTS_CHANNEL_SIZE,
+            events_send_timeout: ETH_EVENTS_SEND_TIMEOUT,
+            cursor_reloads: HashMap::new(),
+            restart_policy: None,
+            store: None,
+            finalized_block_interval: AdaptiveIntervalBounds::fixed(FINALIZED_BLOCK_QUERY_INTERVAL),
+            event_topics: eth_bridge_event_topics(),
         }
     }
 
+    /// Overrides the capacity of the events channel and how long a listening task waits
+    /// for room in it before giving up on the current block range.
+    pub fn with_events_channel(mut self, size: usize, send_timeout: Duration) -> Self {
+        self.events_channel_size = size;
+        self.events_send_timeout = send_timeout;
+        self
+    }
+
+    /// Makes the listening tasks continue from the start block in `cursor_reloads`
+    /// whenever the cursor of their contract is reset.
+    pub fn with_cursor_reloads(
+        mut self,
+        cursor_reloads: HashMap<EthAddress, CursorReload<u64>>,
+    ) -> Self {
+        self.cursor_reloads = cursor_reloads;
+        self
+    }
+
+    /// Restarts the listening tasks that panic according to `restart_policy`. A restarted
+    /// task continues after the last block range it forwarded, and leaves out of its first
+    /// range the logs of the actions that are already in `store`.
+    pub fn with_supervision(
+        mut self,
+        restart_policy: RestartPolicy,
+        store: Arc<BridgeOrchestratorTables>,
+    ) -> Self {
+        self.restart_policy = Some(restart_policy);
+        self.store = Some(store);
+        self
+    }
+
+    /// Queries the last finalized block at an interval within `bounds` that follows the
+    /// events observed in the contracts, see `adaptive_interval`, instead of every
+    /// `FINALIZED_BLOCK_QUERY_INTERVAL`.
+    pub fn with_adaptive_interval(mut self, bounds: AdaptiveIntervalBounds) -> Self {
+        self.finalized_block_interval = bounds;
+        self
+    }
+
+    /// Queries the logs with `topics` instead of the ones of the bridge events, e.g. the
+    /// ones of all the event parsers, see `event_parsers`.
+    pub fn with_event_topics(mut self, topics: Vec<H256>) -> Self {
+        self.event_topics = topics;
+        self
+    }
+
     pub async fn run(
-        self,
+        mut self,
         metrics: Arc<BridgeMetrics>,
     ) -> BridgeResult<(
         Vec<JoinHandle<()>>,
@@ -53,40 +128,88 @@ where
         watch::Receiver<u64>,
     )> {
         let (eth_evnets_tx, eth_events_rx) = mysten_metrics::metered_channel::channel(
-            ETH_EVENTS_CHANNEL_SIZE,
+            self.events_channel_size,
             &mysten_metrics::get_metrics()
                 .unwrap()
                 .channel_inflight
                 .with_label_values(&["eth_events_queue"]),
         );
+        metrics
+            .channel_capacity
+            .with_label_values(&["eth_events_queue"])
+            .set(self.events_channel_size as i64);
         let last_finalized_block = self.eth_client.get_last_finalized_block_id().await?;
         let (last_finalized_block_tx, last_finalized_block_rx) =
             watch::channel(last_finalized_block);
         let mut task_handles = vec![];
         let eth_client_clone = self.eth_client.clone();
         let metrics_clone = metrics.clone();
+        let finalized_block_interval = Arc::new(FinalizedBlockInterval {
+            interval: Mutex::new(AdaptiveInterval::new(self.finalized_block_interval)),
+            burst: Notify::new(),
+        });
         task_handles.push(spawn_logged_monitored_task!(
             Self::run_finalized_block_refresh_task(
                 last_finalized_block_tx,
                 eth_client_clone,
+                finalized_block_interval.clone(),
                 metrics_clone
             )
         ));
         for (contract_address, start_block) in self.contract_addresses {
-            let eth_evnets_tx_clone = eth_evnets_tx.clone();
-            let last_finalized_block_rx_clone = last_finalized_block_rx.clone();
-            let eth_client_clone = self.eth_client.clone();
+            let eth_evnets_tx = eth_evnets_tx.clone();
+            let last_finalized_block_rx = last_finalized_block_rx.clone();
+            let eth_client = self.eth_client.clone();
             let metrics_clone = metrics.clone();
-            task_handles.push(spawn_logged_monitored_task!(
+            let store = self.store.clone();
+            let send_timeout = self.events_send_timeout;
+            let finalized_block_interval = finalized_block_interval.clone();
+            let event_topics = self.event_topics.clone();
+            let cursor_reload = Arc::new(tokio::sync::Mutex::new(
+                self.cursor_reloads
+                    .remove(&contract_address)
+                    .unwrap_or_default(),
+            ));
+            // The block after the last forwarded range, where a restarted task continues
+            let forwarded = Arc::new(Mutex::new(start_block));
+            let component = format!("eth_syncer_{:?}", contract_address);
+            let start_task = move |start: TaskStart| {
+                let start_block = *forwarded.lock().unwrap();
+                let boundary_store = match start {
+                    TaskStart::Clean => None,
+                    TaskStart::AfterCrash => store.clone(),
+                };
                 Self::run_event_listening_task(
                     contract_address,
                     start_block,
-                    last_finalized_block_rx_clone,
-                    eth_evnets_tx_clone,
-                    eth_client_clone,
-                    metrics_clone,
+                    cursor_reload.clone(),
+                    forwarded.clone(),
+                    boundary_store,
+                    last_finalized_block_rx.clone(),
+                    eth_evnets_tx.clone(),
+                    send_timeout,
+                    eth_client.clone(),
+                    event_topics.clone(),
+                    finalized_block_interval.clone(),
+                    metrics_clone.clone(),
                 )
-            ));
+            };
+            match &self.restart_policy {
+                Some(restart_policy) => {
+                    let supervisor = TaskSupervisor::new(
+                        component,
+                        PipelineChain::Eth,
+                        restart_policy.clone(),
+                        metrics.clone(),
+                    );
+                    task_handles.push(spawn_logged_monitored_task!(
+                        supervisor.supervise(start_task)
+                    ));
+                }
+                None => {
+                    task_handles.push(spawn_logged_monitored_task!(start_task(TaskStart::Clean)));
+                }
+            }
         }
         Ok((task_handles, eth_events_rx, last_finalized_block_rx))
     }
@@ -94,21 +217,44 @@ where
     async fn run_finalized_block_refresh_task(
         last_finalized_block_sender: watch::Sender<u64>,
         eth_client: Arc<EthClient<P>>,
+        finalized_block_interval: Arc<FinalizedBlockInterval>,
         metrics: Arc<BridgeMetrics>,
     ) {
         tracing::info!("Starting finalized block refresh task.");
         let mut last_block_number = 0;
-        let mut interval = time::interval(FINALIZED_BLOCK_QUERY_INTERVAL);
+        let interval_gauge = metrics
+            .syncer_poll_interval_ms
+            .with_label_values(&["eth_syncer"]);
+        let mut interval =
+            time::interval(finalized_block_interval.interval.lock().unwrap().current());
         interval.set_missed_tick_behavior(time::MissedTickBehavior::Skip);
         loop {
-            interval.tick().await;
+            tokio::select! {
+                _ = interval.tick() => {}
+                _ = finalized_block_interval.burst.notified() => {}
+            }
+            // The events observed since the previous query set the interval until the next one
+            let period = finalized_block_interval.interval.lock().unwrap().next();
+            interval_gauge.set(period.as_millis() as i64);
+            set_period(&mut interval, period);
             // TODO: allow to pass custom initial interval
-            let Ok(Ok(new_value)) = retry_with_max_elapsed_time!(
+            let result = retry_with_max_elapsed_time!(
                 eth_client.get_last_finalized_block_id(),
                 time::Duration::from_secs(600)
-            ) else {
-                error!("Failed to get last finalized block from eth client after retry");
-                continue;
+            )
+            .and_then(|result| result);
+            metrics
+                .chain_health
+                .record(PipelineChain::Eth, &result, &metrics);
+            let new_value = match result {
+                Ok(new_value) => new_value,
+                Err(e) => {
+                    error!("Failed to get last finalized block from eth client after retry");
+                    metrics
+                        .readiness
+                        .record_syncer_failure(PipelineChain::Eth, e.to_string());
+                    continue;
+                }
             };
             tracing::debug!("Last finalized block: {}", new_value);
             metrics.last_finalized_eth_block.set(new_value as i64);
@@ -130,20 +276,45 @@ where
     async fn run_event_listening_task(
         contract_address: EthAddress,
         mut start_block: u64,
+        cursor_reload: Arc<tokio::sync::Mutex<CursorReload<u64>>>,
+        forwarded: Arc<Mutex<u64>>,
+        // Set when restarted after a crash, to deduplicate the first range against
+        mut boundary_store: Option<Arc<BridgeOrchestratorTables>>,
         mut last_finalized_block_receiver: watch::Receiver<u64>,
         events_sender: mysten_metrics::metered_channel::Sender<(EthAddress, u64, Vec<EthLog>)>,
+        send_timeout: Duration,
         eth_client: Arc<EthClient<P>>,
+        event_topics: Vec<H256>,
+        finalized_block_interval: Arc<FinalizedBlockInterval>,
         metrics: Arc<BridgeMetrics>,
     ) {
-        tracing::info!(contract_address=?contract_address, "Starting eth events listening task from block {start_block}");
+        tracing::info!(
+            contract_address=?contract_address,
+            restarted = boundary_store.is_some(),
+            "Starting eth events listening task from block {start_block}"
+        );
+        let mut cursor_reload = cursor_reload.lock().await;
         let mut more_blocks = false;
         loop {
-            // If no more known blocks, wait for the next finalized block.
-            if !more_blocks {
-                last_finalized_block_receiver
-                    .changed()
-                    .await
-                    .expect("last_finalized_block channel sender is closed");
+            // If no more known blocks, wait for the next finalized block or a cursor reset.
+            let mut reset_start_block = cursor_reload.try_take();
+            if !more_blocks && reset_start_block.is_none() {
+                tokio::select! {
+                    result = last_finalized_block_receiver.changed() => {
+                        result.expect("last_finalized_block channel sender is closed");
+                    }
+                    new_start_block = cursor_reload.wait() => {
+                        reset_start_block = Some(new_start_block);
+                    }
+                }
+            }
+            if let Some(new_start_block) = reset_start_block {
+                tracing::info!(
+                    contract_address=?contract_address,
+                    "Cursor was reset, continuing from block {new_start_block} instead of {start_block}"
+                );
+                start_block = new_start_block;
+                *forwarded.lock().unwrap() = start_block;
             }
             let new_finalized_block = *last_finalized_block_receiver.borrow();
             if new_finalized_block < start_block {
@@ -163,12 +334,22 @@ where
             more_blocks = end_block < new_finalized_block;
             let timer = Instant::now();
             let Ok(Ok(events)) = retry_with_max_elapsed_time!(
-                eth_client.get_events_in_range(contract_address, start_block, end_block),
+                eth_client.get_events_in_range_with_topics(
+                    contract_address,
+                    start_block,
+                    end_block,
+                    &event_topics
+                ),
                 Duration::from_secs(600)
             ) else {
                 error!("Failed to get events from eth client after retry");
+                metrics.readiness.record_syncer_failure(
+                    PipelineChain::Eth,
+                    format!("Failed to get events of blocks {start_block} to {end_block}"),
+                );
                 continue;
             };
+            metrics.readiness.record_syncer_success(PipelineChain::Eth);
             tracing::debug!(
                 ?contract_address,
                 start_block,
@@ -176,8 +357,13 @@ where
                 "Querying eth events took {:?}",
                 timer.elapsed()
             );
-            let len = events.len();
             let last_block = events.last().map(|e| e.block_number);
+            // The first range after a crash may have been forwarded before it
+            let events = match &boundary_store {
+                Some(store) => drop_known_logs(events, store, &metrics),
+                None => events,
+            };
+            let len = events.len();
 
             // Note 1: we always events to the channel even when it is empty. This is because of
             // how `eth_getLogs` api is designed - we want cursor to move forward continuously.
@@ -185,10 +371,28 @@ where
             // Note 2: it's extremely critical to make sure the Logs we send via this channel
             // are complete per block height. Namely, we should never send a partial list
             // of events for a block. Otherwise, we may end up missing events.
-            events_sender
-                .send((contract_address, end_block, events))
-                .await
-                .expect("All Eth event channel receivers are closed");
+
+            // Note 3: when the channel stays full for `send_timeout`, the events are dropped
+            // and `start_block` is not moved, so the range is queried again later instead of
+            // being held in memory while the downstream is stalled.
+            let Ok(permit) = time::timeout(send_timeout, events_sender.reserve()).await else {
+                warn!(
+                    ?contract_address,
+                    start_block,
+                    end_block,
+                    "Eth events channel is full, will query the range again"
+                );
+                metrics
+                    .syncer_send_timeouts
+                    .with_label_values(&["eth"])
+                    .inc();
+                more_blocks = true;
+                continue;
+            };
+            permit
+                .expect("All Eth event channel receivers are closed")
+                .send((contract_address, end_block, events));
+            boundary_store = None;
             if len != 0 {
                 tracing::info!(
                     ?contract_address,
@@ -196,15 +400,49 @@ where
                     end_block,
                     "Observed {len} new Eth events",
                 );
+                if finalized_block_interval
+                    .interval
+                    .lock()
+                    .unwrap()
+                    .observe(len)
+                {
+                    finalized_block_interval.burst.notify_one();
+                }
             }
             if let Some(last_block) = last_block {
                 metrics.last_synced_eth_block.set(last_block as i64);
             }
             start_block = end_block + 1;
+            *forwarded.lock().unwrap() = start_block;
         }
     }
 }
 
+// Leaves out the logs of the actions that the orchestrator already received.
+fn drop_known_logs(
+    logs: Vec<EthLog>,
+    store: &BridgeOrchestratorTables,
+    metrics: &BridgeMetrics,
+) -> Vec<EthLog> {
+    let (known, logs): (Vec<_>, Vec<_>) =
+        logs.into_iter()
+            .partition(|log| match EthBridgeEvent::parse_eth_log(log) {
+                Some((_, Ok(Some(action)))) => store.is_known_action(&action).unwrap_or(false),
+                _ => false,
+            });
+    if !known.is_empty() {
+        tracing::info!(
+            "Skipping {} Eth logs of actions that were already received: {:?}",
+            known.len(),
+            known.iter().map(|log| log.tx_hash).collect::<Vec<_>>()
+        );
+        metrics
+            .orchestrator_duplicate_actions
+            .inc_by(known.len() as u64);
+    }
+    logs
+}
+
 #[cfg(test)]
 mod tests {
     use std::{collections::HashSet, str::FromStr};
@@ -215,7 +453,7 @@ mod tests {
 
     use crate::{
         eth_mock_provider::EthMockProvider,
-        test_utils::{mock_get_logs, mock_last_finalized_block},
+        test_utils::{mock_get_logs, mock_last_finalized_block, wait_for_gauge},
     };
 
     use super::*;
@@ -247,6 +485,7 @@ mod tests {
             block_number: 777,
             tx_hash: log.transaction_hash.unwrap(),
             log_index_in_tx: 0,
+            block_timestamp_ms: None,
             log: log.clone(),
         };
         mock_get_logs(
@@ -258,7 +497,7 @@ mod tests {
         );
         let (_handles, mut logs_rx, mut finalized_block_rx) =
             EthSyncer::new(Arc::new(client), addresses)
-                .run(Arc::new(BridgeMetrics::new_for_testing()))
+                .run(Arc::new(BridgeMetrics::for_testing()))
                 .await
                 .unwrap();
 
@@ -291,6 +530,63 @@ mod tests {
         Ok(())
     }
 
+    #[tokio::test]
+    async fn test_eth_syncer_adaptive_interval() -> anyhow::Result<()> {
+        telemetry_subscribers::init_for_testing();
+        let registry = Registry::new();
+        mysten_metrics::init_metrics(&registry);
+        let metrics = Arc::new(BridgeMetrics::for_testing());
+        let mock_provider = EthMockProvider::new();
+        mock_last_finalized_block(&mock_provider, 777);
+        let client = EthClient::new_mocked(
+            mock_provider.clone(),
+            HashSet::from_iter(vec![EthAddress::zero()]),
+        );
+        let log = |block_number: u64| Log {
+            address: EthAddress::zero(),
+            transaction_hash: Some(TxHash::random()),
+            block_number: Some(U64::from(block_number)),
+            log_index: Some(U256::from(0)),
+            ..Default::default()
+        };
+        mock_get_logs(&mock_provider, EthAddress::zero(), 100, 777, vec![log(700)]);
+        let bounds = AdaptiveIntervalBounds {
+            min: Duration::from_millis(50),
+            max: Duration::from_millis(400),
+            empty_polls_before_growth: 3,
+            burst_events: 2,
+        };
+        let (_handles, mut logs_rx, _) =
+            EthSyncer::new(Arc::new(client), HashMap::from([(EthAddress::zero(), 100)]))
+                .with_adaptive_interval(bounds)
+                .run(metrics.clone())
+                .await
+                .unwrap();
+        let interval_ms = metrics
+            .syncer_poll_interval_ms
+            .with_label_values(&["eth_syncer"]);
+        let (_, _, received_logs) = logs_rx.recv().await.unwrap();
+        assert_eq!(received_logs.len(), 1);
+
+        // No new finalized block, the interval grows to the maximum
+        wait_for_gauge(&interval_ms, 400).await;
+
+        // A burst of events sets it to the minimum at once
+        mock_get_logs(
+            &mock_provider,
+            EthAddress::zero(),
+            778,
+            888,
+            vec![log(800), log(801)],
+        );
+        mock_last_finalized_block(&mock_provider, 888);
+        let (_, end_block, received_logs) = logs_rx.recv().await.unwrap();
+        assert_eq!((end_block, received_logs.len()), (888, 2));
+        wait_for_gauge(&interval_ms, 50).await;
+        wait_for_gauge(&interval_ms, 400).await;
+        Ok(())
+    }
+
     #[tokio::test]
     async fn test_multiple_addresses() -> anyhow::Result<()> {
         telemetry_subscribers::init_for_testing();
@@ -320,6 +616,7 @@ mod tests {
             block_number: log1.block_number.unwrap().as_u64(),
             tx_hash: log1.transaction_hash.unwrap(),
             log_index_in_tx: 0,
+            block_timestamp_ms: None,
             log: log1.clone(),
         };
         mock_get_logs(
@@ -348,7 +645,7 @@ mod tests {
 
         let (_handles, mut logs_rx, mut finalized_block_rx) =
             EthSyncer::new(Arc::new(client), addresses)
-                .run(Arc::new(BridgeMetrics::new_for_testing()))
+                .run(Arc::new(BridgeMetrics::for_testing()))
                 .await
                 .unwrap();
 
@@ -372,6 +669,7 @@ mod tests {
             block_number: log1.block_number.unwrap().as_u64(),
             tx_hash: log1.transaction_hash.unwrap(),
             log_index_in_tx: 0,
+            block_timestamp_ms: None,
             log: log1.clone(),
         };
         mock_get_logs(
@@ -392,6 +690,7 @@ mod tests {
             block_number: log2.block_number.unwrap().as_u64(),
             tx_hash: log2.transaction_hash.unwrap(),
             log_index_in_tx: 0,
+            block_timestamp_ms: None,
             log: log2.clone(),
         };
         mock_get_logs(
@@ -458,12 +757,14 @@ mod tests {
             block_number: start_block,
             tx_hash: log.transaction_hash.unwrap(),
             log_index_in_tx: 0,
+            block_timestamp_ms: None,
             log: log.clone(),
         };
         let eth_log2 = EthLog {
             block_number: last_finalized_block,
             tx_hash: log2.transaction_hash.unwrap(),
             log_index_in_tx: 0,
+            block_timestamp_ms: None,
             log: log2.clone(),
         };
         // First query handles [start, start + ETH_LOG_QUERY_MAX_BLOCK_RANGE - 1]
@@ -485,7 +786,7 @@ mod tests {
 
         let (_handles, mut logs_rx, mut finalized_block_rx) =
             EthSyncer::new(Arc::new(client), addresses)
-                .run(Arc::new(BridgeMetrics::new_for_testing()))
+                .run(Arc::new(BridgeMetrics::for_testing()))
                 .await
                 .unwrap();
 
@@ -0,0 +1,206 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Conversion of the events received by the watchers into bridge actions. The events of
+//! the bridge package and contracts are parsed by `BridgeSuiEventParser` and
+//! `BridgeEthLogParser`. Forks that add their own event types register parsers for them
+//! with `BridgeNodeBuilder::with_sui_event_parser` and `with_eth_log_parser`, instead of
+//! patching `events.rs` and `abi.rs`.
+//!
+//! Parsers are consulted in order, the default ones first, then the additional ones in
+//! the order they were registered. The first parser that claims an event parses it and
+//! the following ones don't see it, so the conversion of the bridge events can't be
+//! overridden. An event is counted as unrecognized only when no parser claims it. The
+//! Eth syncer queries the logs with the topics of all the parsers, the Sui syncer the
+//! events of the bridge modules it watches.
+
+use crate::abi::{eth_bridge_event_topics, EthBridgeEvent};
+use crate::error::BridgeResult;
+use crate::events::SuiBridgeEvent;
+use crate::metrics::BridgeMetrics;
+use crate::types::{BridgeAction, EthLog};
+use ethers::types::{Address as EthAddress, H256};
+use std::sync::Arc;
+use sui_json_rpc_types::SuiEvent;
+use sui_types::Identifier;
+
+/// Where a Sui event was received from.
+#[derive(Debug, Clone, Copy)]
+pub struct SuiEventContext<'a> {
+    /// Module of the bridge package the event was queried for
+    pub module: &'a Identifier,
+}
+
+/// A Sui event claimed by a parser.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParsedSuiEvent {
+    /// Handed to the monitor. None for events that are not bridge events.
+    pub bridge_event: Option<SuiBridgeEvent>,
+    pub action: Option<BridgeAction>,
+}
+
+/// Converts Sui events into bridge actions. Parsers are pure: they don't query the
+/// chains nor the storage, and the same event is always parsed the same way.
+pub trait SuiEventParser: Send + Sync {
+    /// Name of the parser in the metrics and logs
+    fn name(&self) -> &'static str;
+
+    /// Returns `Ok(None)` if the parser doesn't know the type of `event`, which lets the
+    /// next parser parse it. An error means that the event is claimed but invalid.
+    fn parse(
+        &self,
+        event: &SuiEvent,
+        context: &SuiEventContext,
+    ) -> BridgeResult<Option<ParsedSuiEvent>>;
+}
+
+/// Where an Eth log was received from.
+#[derive(Debug, Clone, Copy)]
+pub struct EthLogContext {
+    /// Contract the log was queried for
+    pub contract: EthAddress,
+    /// Last block of the range the log was queried in
+    pub end_block: u64,
+}
+
+/// An Eth log claimed by a parser.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParsedEthLog {
+    /// None for logs that are not bridge events
+    pub bridge_event: Option<EthBridgeEvent>,
+    pub action: Option<BridgeAction>,
+}
+
+/// Converts Eth logs into bridge actions, see `SuiEventParser`.
+pub trait EthLogParser: Send + Sync {
+    fn name(&self) -> &'static str;
+
+    /// Signatures of the events the parser knows, the logs are queried by them.
+    fn topics(&self) -> Vec<H256>;
+
+    fn parse(&self, log: &EthLog, context: &EthLogContext) -> BridgeResult<Option<ParsedEthLog>>;
+}
+
+/// Parses the events of the bridge package.
+pub struct BridgeSuiEventParser;
+
+impl SuiEventParser for BridgeSuiEventParser {
+    fn name(&self) -> &'static str {
+        "bridge"
+    }
+
+    fn parse(
+        &self,
+        event: &SuiEvent,
+        _context: &SuiEventContext,
+    ) -> BridgeResult<Option<ParsedSuiEvent>> {
+        Ok(
+            SuiBridgeEvent::parse_sui_event(event)?.map(|(bridge_event, action)| ParsedSuiEvent {
+                bridge_event: Some(bridge_event),
+                action,
+            }),
+        )
+    }
+}
+
+/// Parses the events of the bridge contracts.
+pub struct BridgeEthLogParser;
+
+impl EthLogParser for BridgeEthLogParser {
+    fn name(&self) -> &'static str {
+        "bridge"
+    }
+
+    fn topics(&self) -> Vec<H256> {
+        eth_bridge_event_topics()
+    }
+
+    fn parse(&self, log: &EthLog, _context: &EthLogContext) -> BridgeResult<Option<ParsedEthLog>> {
+        let Some((bridge_event, action)) = EthBridgeEvent::parse_eth_log(log) else {
+            return Ok(None);
+        };
+        Ok(Some(ParsedEthLog {
+            bridge_event: Some(bridge_event),
+            action: action?,
+        }))
+    }
+}
+
+/// The parsers of the watchers, in the order they are consulted.
+#[derive(Clone)]
+pub struct EventParsers {
+    sui: Vec<Arc<dyn SuiEventParser>>,
+    eth: Vec<Arc<dyn EthLogParser>>,
+}
+
+impl Default for EventParsers {
+    fn default() -> Self {
+        Self {
+            sui: vec![Arc::new(BridgeSuiEventParser)],
+            eth: vec![Arc::new(BridgeEthLogParser)],
+        }
+    }
+}
+
+impl EventParsers {
+    /// Consults `parser` after the ones already added.
+    pub fn with_sui_parser(mut self, parser: Arc<dyn SuiEventParser>) -> Self {
+        self.sui.push(parser);
+        self
+    }
+
+    /// Consults `parser` after the ones already added.
+    pub fn with_eth_parser(mut self, parser: Arc<dyn EthLogParser>) -> Self {
+        self.eth.push(parser);
+        self
+    }
+
+    /// Topics of the logs of all the Eth parsers.
+    pub fn eth_topics(&self) -> Vec<H256> {
+        let mut topics = vec![];
+        for topic in self.eth.iter().flat_map(|parser| parser.topics()) {
+            if !topics.contains(&topic) {
+                topics.push(topic);
+            }
+        }
+        topics
+    }
+
+    /// Returns what the first parser that claims `event` parsed, None if none did.
+    pub fn parse_sui_event(
+        &self,
+        event: &SuiEvent,
+        context: &SuiEventContext,
+        metrics: &BridgeMetrics,
+    ) -> BridgeResult<Option<ParsedSuiEvent>> {
+        for parser in &self.sui {
+            if let Some(parsed) = parser.parse(event, context).transpose() {
+                metrics
+                    .watcher_parsed_events
+                    .with_label_values(&["sui", parser.name()])
+                    .inc();
+                return parsed.map(Some);
+            }
+        }
+        Ok(None)
+    }
+
+    /// Returns what the first parser that claims `log` parsed, None if none did.
+    pub fn parse_eth_log(
+        &self,
+        log: &EthLog,
+        context: &EthLogContext,
+        metrics: &BridgeMetrics,
+    ) -> BridgeResult<Option<ParsedEthLog>> {
+        for parser in &self.eth {
+            if let Some(parsed) = parser.parse(log, context).transpose() {
+                metrics
+                    .watcher_parsed_events
+                    .with_label_values(&["eth", parser.name()])
+                    .inc();
+                return parsed.map(Some);
+            }
+        }
+        Ok(None)
+    }
+}
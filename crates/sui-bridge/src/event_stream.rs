@@ -0,0 +1,364 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Streams what the node observes to local sidecars, e.g. monitoring agents, so that
+//! they do not have to poll the HTTP routes. Consumers connect to a unix domain socket
+//! or to a port on the loopback interface and read newline-delimited JSON
+//! `api_types::StreamEvent`s: every action the orchestrator observes, status transitions
+//! of actions in the executor, and periodic heartbeats of the components. Actions are
+//! reported signed as they are published to the `CertifiedActions` of the executor.
+//!
+//! Every consumer has a bounded buffer. Events for a consumer whose buffer is full, i.e.
+//! that reads too slowly or stalled, are dropped and counted in
+//! `bridge_event_stream_dropped_events`, so consumers never hold up the node.
+
+use crate::api_types::{encode_action_digest, Action, StreamActionStatus, StreamEvent};
+use crate::certified_actions::{CertifiedActionsError, CertifiedActionsReceiver};
+use crate::config::EventStreamConfig;
+use crate::metrics::BridgeMetrics;
+use crate::timestamps::now_ms;
+use crate::types::BridgeAction;
+use mysten_metrics::spawn_logged_monitored_task;
+use std::collections::BTreeMap;
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::io::{AsyncWrite, AsyncWriteExt};
+use tokio::net::{TcpListener, UnixListener};
+use tokio::sync::mpsc;
+use tracing::{info, warn};
+
+pub const DEFAULT_EVENT_STREAM_BUFFER_SIZE: usize = 1000;
+pub const DEFAULT_EVENT_STREAM_HEARTBEAT_INTERVAL: Duration = Duration::from_secs(10);
+
+pub enum EventStreamListener {
+    Unix(UnixListener),
+    Tcp(TcpListener),
+}
+
+impl EventStreamListener {
+    pub async fn bind(config: &EventStreamConfig) -> anyhow::Result<Self> {
+        match (&config.socket_path, config.loopback_port) {
+            (Some(socket_path), None) => {
+                remove_stale_socket(socket_path)?;
+                Ok(Self::Unix(UnixListener::bind(socket_path)?))
+            }
+            (None, Some(port)) => Ok(Self::Tcp(TcpListener::bind(("127.0.0.1", port)).await?)),
+            _ => Err(anyhow::anyhow!(
+                "Exactly one of `socket-path` and `loopback-port` must be set for the event stream"
+            )),
+        }
+    }
+
+    async fn accept(&self) -> std::io::Result<Box<dyn AsyncWrite + Send + Unpin>> {
+        match self {
+            Self::Unix(listener) => {
+                let (stream, _) = listener.accept().await?;
+                Ok(Box::new(stream))
+            }
+            Self::Tcp(listener) => {
+                let (stream, _) = listener.accept().await?;
+                Ok(Box::new(stream))
+            }
+        }
+    }
+}
+
+// The socket of the previous run is left behind when the node is not shut down cleanly
+fn remove_stale_socket(socket_path: &Path) -> anyhow::Result<()> {
+    use std::os::unix::fs::FileTypeExt;
+    match std::fs::symlink_metadata(socket_path) {
+        Ok(metadata) if metadata.file_type().is_socket() => {
+            std::fs::remove_file(socket_path)?;
+            Ok(())
+        }
+        Ok(_) => Err(anyhow::anyhow!(
+            "Event stream socket path {} exists and is not a socket",
+            socket_path.display()
+        )),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(()),
+        Err(e) => Err(e.into()),
+    }
+}
+
+pub struct EventStream {
+    buffer_size: usize,
+    heartbeat_interval: Duration,
+    consumers: Mutex<Vec<mpsc::Sender<Arc<str>>>>,
+    // Last time each component was active, in ms since the Unix epoch
+    last_active: Mutex<BTreeMap<&'static str, u64>>,
+    metrics: Arc<BridgeMetrics>,
+}
+
+impl EventStream {
+    pub fn new(config: &EventStreamConfig, metrics: Arc<BridgeMetrics>) -> Self {
+        Self {
+            buffer_size: config
+                .buffer_size
+                .unwrap_or(DEFAULT_EVENT_STREAM_BUFFER_SIZE)
+                .max(1),
+            heartbeat_interval: config
+                .heartbeat_interval_seconds
+                .map(Duration::from_secs)
+                .unwrap_or(DEFAULT_EVENT_STREAM_HEARTBEAT_INTERVAL),
+            consumers: Mutex::new(vec![]),
+            last_active: Mutex::new(BTreeMap::new()),
+            metrics,
+        }
+    }
+
+    /// Accepts consumers on `listener` and sends the heartbeats.
+    pub async fn serve(self: Arc<Self>, listener: EventStreamListener) {
+        info!("Starting event stream");
+        let mut heartbeat = tokio::time::interval(self.heartbeat_interval);
+        loop {
+            tokio::select! {
+                _ = heartbeat.tick() => self.publish_heartbeats(),
+                stream = listener.accept() => match stream {
+                    Ok(stream) => self.add_consumer(stream),
+                    Err(e) => warn!("Failed to accept event stream consumer: {:?}", e),
+                },
+            }
+        }
+    }
+
+    /// Reports the actions received from `certified_actions` as signed.
+    pub async fn run_certified_actions(
+        self: Arc<Self>,
+        mut certified_actions: CertifiedActionsReceiver,
+    ) {
+        loop {
+            match certified_actions.recv().await {
+                Ok(certificate) => {
+                    self.action_status(certificate.data(), StreamActionStatus::Signed, None)
+                }
+                Err(CertifiedActionsError::Lagged(missed)) => {
+                    warn!("Event stream missed {} certified actions", missed)
+                }
+                Err(CertifiedActionsError::Closed) => return,
+            }
+        }
+    }
+
+    pub fn action_observed(&self, action: &BridgeAction, chain_timestamp_ms: Option<u64>) {
+        self.publish(StreamEvent::ActionObserved {
+            action_digest: encode_action_digest(&action.digest()),
+            action: Action::from(action),
+            chain_timestamp_ms,
+            timestamp_ms: now_ms(),
+        });
+    }
+
+    pub fn action_status(
+        &self,
+        action: &BridgeAction,
+        status: StreamActionStatus,
+        reason: Option<String>,
+    ) {
+        self.publish(StreamEvent::ActionStatus {
+            action_digest: encode_action_digest(&action.digest()),
+            status,
+            reason,
+            timestamp_ms: now_ms(),
+        });
+    }
+
+    /// Records that `component` is active, it is reported in the next heartbeats.
+    pub fn beat(&self, component: &'static str) {
+        self.last_active.lock().unwrap().insert(component, now_ms());
+    }
+
+    fn publish_heartbeats(&self) {
+        let last_active = self.last_active.lock().unwrap().clone();
+        let timestamp_ms = now_ms();
+        for (component, last_active_ms) in last_active {
+            self.publish(StreamEvent::Heartbeat {
+                component: component.to_string(),
+                last_active_ms,
+                timestamp_ms,
+            });
+        }
+    }
+
+    fn add_consumer(&self, stream: Box<dyn AsyncWrite + Send + Unpin>) {
+        info!("Event stream consumer connected");
+        let (tx, rx) = mpsc::channel(self.buffer_size);
+        let mut consumers = self.consumers.lock().unwrap();
+        consumers.push(tx);
+        self.metrics
+            .event_stream_consumers
+            .set(consumers.len() as i64);
+        spawn_logged_monitored_task!(Self::write_to_consumer(stream, rx), "event_stream_consumer");
+    }
+
+    async fn write_to_consumer(
+        mut stream: Box<dyn AsyncWrite + Send + Unpin>,
+        mut rx: mpsc::Receiver<Arc<str>>,
+    ) {
+        while let Some(line) = rx.recv().await {
+            if let Err(e) = stream.write_all(line.as_bytes()).await {
+                // Dropping `rx` removes the consumer on the next event
+                info!("Event stream consumer disconnected: {:?}", e);
+                return;
+            }
+        }
+    }
+
+    fn publish(&self, event: StreamEvent) {
+        let mut consumers = self.consumers.lock().unwrap();
+        if consumers.is_empty() {
+            return;
+        }
+        let mut line = serde_json::to_string(&event).expect("Serializing event should not fail");
+        line.push('\n');
+        let line: Arc<str> = line.into();
+        consumers.retain(|consumer| match consumer.try_send(line.clone()) {
+            Ok(()) => true,
+            Err(mpsc::error::TrySendError::Full(_)) => {
+                self.metrics.event_stream_dropped_events.inc();
+                true
+            }
+            Err(mpsc::error::TrySendError::Closed(_)) => false,
+        });
+        self.metrics
+            .event_stream_consumers
+            .set(consumers.len() as i64);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::get_test_sui_to_eth_bridge_action;
+    use std::time::Instant;
+    use tokio::io::{AsyncBufReadExt, BufReader};
+    use tokio::net::UnixStream;
+
+    async fn start(
+        socket_path: &Path,
+        buffer_size: usize,
+        heartbeat_interval_seconds: u64,
+        metrics: Arc<BridgeMetrics>,
+    ) -> (Arc<EventStream>, UnixStream) {
+        let config = EventStreamConfig {
+            socket_path: Some(socket_path.to_path_buf()),
+            loopback_port: None,
+            buffer_size: Some(buffer_size),
+            heartbeat_interval_seconds: Some(heartbeat_interval_seconds),
+        };
+        let listener = EventStreamListener::bind(&config).await.unwrap();
+        let stream = Arc::new(EventStream::new(&config, metrics.clone()));
+        tokio::spawn(stream.clone().serve(listener));
+        let reader = UnixStream::connect(socket_path).await.unwrap();
+        let start = Instant::now();
+        while metrics.event_stream_consumers.get() == 0 {
+            assert!(start.elapsed() < Duration::from_secs(5));
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+        (stream, reader)
+    }
+
+    async fn read_event(reader: &mut BufReader<UnixStream>) -> StreamEvent {
+        let mut line = String::new();
+        tokio::time::timeout(Duration::from_secs(5), reader.read_line(&mut line))
+            .await
+            .unwrap()
+            .unwrap();
+        serde_json::from_str(&line).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_event_stream_writes_events() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let socket_path = temp_dir.path().join("events.sock");
+        let metrics = Arc::new(BridgeMetrics::for_testing());
+        let (stream, reader) = start(&socket_path, 100, 3600, metrics.clone()).await;
+        let mut reader = BufReader::new(reader);
+
+        let action = get_test_sui_to_eth_bridge_action(None, None, None, None, None, None, None);
+        let action_digest = encode_action_digest(&action.digest());
+        stream.action_observed(&action, Some(1700000000000));
+        stream.action_status(&action, StreamActionStatus::Signed, None);
+        stream.action_status(
+            &action,
+            StreamActionStatus::Failed,
+            Some("Quarantined".to_string()),
+        );
+        stream.beat("eth_watcher");
+        stream.publish_heartbeats();
+
+        let StreamEvent::ActionObserved {
+            action_digest: digest,
+            action: observed,
+            chain_timestamp_ms,
+            ..
+        } = read_event(&mut reader).await
+        else {
+            panic!("Expected an observed action");
+        };
+        assert_eq!(digest, action_digest);
+        assert_eq!(observed, Action::from(&action));
+        assert_eq!(chain_timestamp_ms, Some(1700000000000));
+        for (expected_status, expected_reason) in [
+            (StreamActionStatus::Signed, None),
+            (StreamActionStatus::Failed, Some("Quarantined".to_string())),
+        ] {
+            let StreamEvent::ActionStatus {
+                action_digest: digest,
+                status,
+                reason,
+                ..
+            } = read_event(&mut reader).await
+            else {
+                panic!("Expected an action status");
+            };
+            assert_eq!(digest, action_digest);
+            assert_eq!(status, expected_status);
+            assert_eq!(reason, expected_reason);
+        }
+        let StreamEvent::Heartbeat { component, .. } = read_event(&mut reader).await else {
+            panic!("Expected a heartbeat");
+        };
+        assert_eq!(component, "eth_watcher");
+        assert_eq!(metrics.event_stream_dropped_events.get(), 0);
+
+        // Disconnected consumers are removed
+        drop(reader);
+        let start = Instant::now();
+        while metrics.event_stream_consumers.get() != 0 {
+            assert!(start.elapsed() < Duration::from_secs(5));
+            stream.action_status(&action, StreamActionStatus::Executed, None);
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+    }
+
+    #[tokio::test]
+    async fn test_event_stream_drops_events_for_stalled_consumer() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let socket_path = temp_dir.path().join("events.sock");
+        let metrics = Arc::new(BridgeMetrics::for_testing());
+        let (stream, reader) = start(&socket_path, 4, 3600, metrics.clone()).await;
+
+        // The consumer does not read, publishing must neither block nor fail once its
+        // socket and buffer are full
+        let action = get_test_sui_to_eth_bridge_action(None, None, None, None, None, None, None);
+        let published = 20_000;
+        for _ in 0..published {
+            stream.action_observed(&action, None);
+            tokio::task::yield_now().await;
+        }
+        let dropped = metrics.event_stream_dropped_events.get();
+        assert!(dropped > 0);
+        assert!(dropped < published);
+        assert_eq!(metrics.event_stream_consumers.get(), 1);
+
+        // The consumer still receives well formed events once it catches up
+        let mut reader = BufReader::new(reader);
+        for _ in 0..10 {
+            assert!(matches!(
+                read_event(&mut reader).await,
+                StreamEvent::ActionObserved { .. }
+            ));
+        }
+    }
+}
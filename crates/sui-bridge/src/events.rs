@@ -11,6 +11,7 @@
 use crate::crypto::BridgeAuthorityPublicKey;
 use crate::error::BridgeError;
 use crate::error::BridgeResult;
+use crate::types::validate_recipient_address;
 use crate::types::BridgeAction;
 use crate::types::SuiToEthBridgeAction;
 use ethers::types::Address as EthAddress;
@@ -209,6 +210,34 @@ pub struct UpdateTokenPriceEvent {
     pub new_price: u64,
 }
 
+// Sanitized version of MoveUpdateRouteLimitEvent
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Clone)]
+pub struct UpdateRouteLimitEvent {
+    pub sending_chain: BridgeChainId,
+    pub receiving_chain: BridgeChainId,
+    pub new_limit: u64,
+}
+
+impl TryFrom<MoveUpdateRouteLimitEvent> for UpdateRouteLimitEvent {
+    type Error = BridgeError;
+
+    fn try_from(event: MoveUpdateRouteLimitEvent) -> BridgeResult<Self> {
+        let chain_id = |chain_id: u8| {
+            BridgeChainId::try_from(chain_id).map_err(|_e| {
+                BridgeError::Generic(format!(
+                    "Failed to convert MoveUpdateRouteLimitEvent to UpdateRouteLimitEvent. Failed to convert chain {} to BridgeChainId",
+                    chain_id,
+                ))
+            })
+        };
+        Ok(Self {
+            sending_chain: chain_id(event.sending_chain)?,
+            receiving_chain: chain_id(event.receiving_chain)?,
+            new_limit: event.new_limit,
+        })
+    }
+}
+
 // Sanitized version of MoveTokenDepositedEvent
 #[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Clone, Hash)]
 pub struct EmittedSuiToEthTokenBridgeV1 {
@@ -329,7 +358,9 @@ impl TryFrom<MoveTokenDepositedEvent> for EmittedSuiToEthTokenBridgeV1 {
 
         let sui_address = SuiAddress::from_bytes(event.sender_address)
             .map_err(|e| BridgeError::Generic(format!("Failed to convert MoveTokenDepositedEvent to EmittedSuiToEthTokenBridgeV1. Failed to convert sender_address to SuiAddress: {:?}", e)))?;
-        let eth_address = EthAddress::from_str(&Hex::encode(&event.target_address))?;
+        validate_recipient_address(eth_chain_id, &event.target_address)?;
+        let eth_address = EthAddress::from_str(&Hex::encode(&event.target_address))
+            .map_err(|e| BridgeError::Generic(format!("Failed to convert MoveTokenDepositedEvent to EmittedSuiToEthTokenBridgeV1. Failed to convert target_address to EthAddress: {:?}", e)))?;
 
         Ok(Self {
             nonce: event.seq_num,
@@ -360,6 +391,7 @@ crate::declare_events!(
     TokenRegistrationEvent(TokenRegistrationEvent) => ("treasury::TokenRegistrationEvent", MoveTokenRegistrationEvent),
     NewTokenEvent(NewTokenEvent) => ("treasury::NewTokenEvent", MoveNewTokenEvent),
     UpdateTokenPriceEvent(UpdateTokenPriceEvent) => ("treasury::UpdateTokenPriceEvent", UpdateTokenPriceEvent),
+    UpdateRouteLimitEvent(UpdateRouteLimitEvent) => ("limiter::UpdateRouteLimitEvent", MoveUpdateRouteLimitEvent),
 
     // Add new event types here. Format:
     // EnumVariantName(Struct) => ("{module}::{event_struct}", CorrespondingMoveStruct)
@@ -382,6 +414,12 @@ macro_rules! declare_events {
             });)*
         }
 
+        // In the order the events are declared
+        pub(crate) fn all_struct_tags() -> Vec<&'static StructTag> {
+            init_all_struct_tags();
+            vec![$($variant.get().unwrap(),)*]
+        }
+
         // Try to convert a SuiEvent into SuiBridgeEvent
         impl SuiBridgeEvent {
             pub fn try_from_sui_event(event: &SuiEvent) -> BridgeResult<Option<SuiBridgeEvent>> {
@@ -396,11 +434,39 @@ macro_rules! declare_events {
                 )*
                 Ok(None)
             }
+
+            pub fn name(&self) -> &'static str {
+                match self {
+                    $(SuiBridgeEvent::$variant(_) => stringify!($variant),)*
+                }
+            }
         }
     };
 }
 
 impl SuiBridgeEvent {
+    /// Parses a raw `SuiEvent` the same way the Sui watcher does. Returns `Ok(None)`
+    /// if the event is not a recognized bridge event, otherwise the sanitized event
+    /// together with the `BridgeAction` derived from it, if it is actionable.
+    pub fn parse_sui_event(
+        event: &SuiEvent,
+    ) -> BridgeResult<Option<(SuiBridgeEvent, Option<BridgeAction>)>> {
+        let Some(bridge_event) = Self::try_from_sui_event(event)? else {
+            return Ok(None);
+        };
+        // Transactions emit at most a few thousand events, a larger index is not a real one
+        let event_index = u16::try_from(event.id.event_seq).map_err(|_| {
+            BridgeError::Generic(format!(
+                "Event index {} of {} is out of range",
+                event.id.event_seq, event.id.tx_digest
+            ))
+        })?;
+        let action = bridge_event
+            .clone()
+            .try_into_bridge_action(event.id.tx_digest, event_index);
+        Ok(Some((bridge_event, action)))
+    }
+
     pub fn try_into_bridge_action(
         self,
         sui_tx_digest: TransactionDigest,
@@ -427,6 +493,7 @@ impl SuiBridgeEvent {
             SuiBridgeEvent::TokenRegistrationEvent(_event) => None,
             SuiBridgeEvent::NewTokenEvent(_event) => None,
             SuiBridgeEvent::UpdateTokenPriceEvent(_event) => None,
+            SuiBridgeEvent::UpdateRouteLimitEvent(_event) => None,
         }
     }
 }
@@ -581,4 +648,35 @@ pub mod tests {
             other => panic!("Expected Generic error, got: {:?}", other),
         }
     }
+
+    #[test]
+    fn test_recipient_address_conversion_for_sui_event() {
+        let deposit = |target_address: Vec<u8>| {
+            EmittedSuiToEthTokenBridgeV1::try_from(MoveTokenDepositedEvent {
+                seq_num: 1,
+                source_chain: BridgeChainId::SuiTestnet as u8,
+                sender_address: SuiAddress::random_for_testing_only().to_vec(),
+                target_chain: BridgeChainId::EthSepolia as u8,
+                target_address,
+                token_type: TOKEN_ID_SUI,
+                amount_sui_adjusted: 1,
+            })
+        };
+        let recipient = EthAddress::random();
+        assert_eq!(
+            deposit(recipient.as_bytes().to_vec()).unwrap().eth_address,
+            recipient
+        );
+        // The zero address is accepted by the Eth contract
+        assert_eq!(
+            deposit(vec![0; 20]).unwrap().eth_address,
+            EthAddress::zero()
+        );
+        for target_address in [vec![1; 19], vec![1; 21], vec![1; 32], vec![]] {
+            match deposit(target_address).unwrap_err() {
+                BridgeError::InvalidRecipientAddress(_) => (),
+                other => panic!("Unexpected error: {:?}", other),
+            }
+        }
+    }
 }
@@ -0,0 +1,186 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! What the action executor is doing right now, for operators to look at when the bridge
+//! looks stuck. The executor publishes a new `ExecutorSnapshot` whenever an action enters
+//! or leaves a stage, and whenever the head of its signing queue, the pause flag of the
+//! bridge or its gas coin change. Readers load the latest snapshot without ever blocking
+//! the executor. It's served on `/admin/executor_state`.
+
+use crate::timestamps::now_ms;
+use crate::types::BridgeAction;
+use arc_swap::ArcSwap;
+use std::cmp::Reverse;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use sui_types::base_types::ObjectRef;
+
+/// How many of the actions waiting for signature aggregation are in the snapshot
+pub const SNAPSHOT_QUEUED_ACTIONS: usize = 10;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum ExecutorStage {
+    /// Collecting the signatures of the committee
+    Aggregating,
+    /// Building and signing the Sui transaction
+    Submitting,
+    /// Waiting for the effects of the submitted transaction
+    AwaitingEffects,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InFlightAction {
+    // Tells apart the same action in flight twice, e.g. while it's aggregated again
+    id: u64,
+    pub action: BridgeAction,
+    pub stage: ExecutorStage,
+    pub stage_entered_at_ms: u64,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ExecutorSnapshot {
+    pub in_flight: Vec<InFlightAction>,
+    /// The head of the signing queue
+    pub queued: Vec<BridgeAction>,
+    pub bridge_paused: bool,
+    /// The gas coin with its balance, as of the last execution
+    pub gas_coin: Option<(ObjectRef, u64)>,
+}
+
+impl ExecutorSnapshot {
+    /// The action that is furthest along the stages. Signatures are aggregated for
+    /// several actions at once, then the one that has been aggregating the longest.
+    pub fn current(&self) -> Option<&InFlightAction> {
+        self.in_flight.iter().max_by_key(|in_flight| {
+            (
+                in_flight.stage,
+                Reverse((in_flight.stage_entered_at_ms, in_flight.id)),
+            )
+        })
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct ExecutorState {
+    snapshot: ArcSwap<ExecutorSnapshot>,
+    next_id: AtomicU64,
+}
+
+impl ExecutorState {
+    pub fn snapshot(&self) -> Arc<ExecutorSnapshot> {
+        self.snapshot.load_full()
+    }
+
+    /// Records that `action` entered `stage`. It stays in flight until the returned
+    /// guard is dropped.
+    pub fn enter_stage(self: &Arc<Self>, action: &BridgeAction, stage: ExecutorStage) -> InFlight {
+        let in_flight = InFlightAction {
+            id: self.next_id.fetch_add(1, Ordering::Relaxed),
+            action: action.clone(),
+            stage,
+            stage_entered_at_ms: now_ms(),
+        };
+        let id = in_flight.id;
+        self.update(|snapshot| snapshot.in_flight.push(in_flight.clone()));
+        InFlight {
+            state: self.clone(),
+            id,
+        }
+    }
+
+    pub fn set_queued(&self, queued: Vec<BridgeAction>) {
+        if self.snapshot.load().queued != queued {
+            self.update(|snapshot| snapshot.queued = queued.clone());
+        }
+    }
+
+    pub fn set_bridge_paused(&self, bridge_paused: bool) {
+        if self.snapshot.load().bridge_paused != bridge_paused {
+            self.update(|snapshot| snapshot.bridge_paused = bridge_paused);
+        }
+    }
+
+    pub fn set_gas_coin(&self, gas_object_ref: ObjectRef, balance: u64) {
+        self.update(|snapshot| snapshot.gas_coin = Some((gas_object_ref, balance)));
+    }
+
+    fn update(&self, f: impl Fn(&mut ExecutorSnapshot)) {
+        self.snapshot.rcu(|snapshot| {
+            let mut snapshot = ExecutorSnapshot::clone(snapshot);
+            f(&mut snapshot);
+            snapshot
+        });
+    }
+}
+
+/// An action in flight in the executor, removed from the snapshot when dropped.
+pub struct InFlight {
+    state: Arc<ExecutorState>,
+    id: u64,
+}
+
+impl InFlight {
+    pub fn advance(&self, stage: ExecutorStage) {
+        let entered_at_ms = now_ms();
+        self.state.update(|snapshot| {
+            if let Some(in_flight) = snapshot.in_flight.iter_mut().find(|a| a.id == self.id) {
+                in_flight.stage = stage;
+                in_flight.stage_entered_at_ms = entered_at_ms;
+            }
+        });
+    }
+}
+
+impl Drop for InFlight {
+    fn drop(&mut self) {
+        let id = self.id;
+        self.state
+            .update(|snapshot| snapshot.in_flight.retain(|in_flight| in_flight.id != id));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{get_test_eth_to_sui_bridge_action, get_test_sui_to_eth_bridge_action};
+
+    #[test]
+    fn test_in_flight_actions() {
+        let state = Arc::new(ExecutorState::default());
+        let first = get_test_sui_to_eth_bridge_action(None, None, Some(1), None, None, None, None);
+        let second = get_test_eth_to_sui_bridge_action(Some(2), None, None, None);
+        assert!(state.snapshot().current().is_none());
+
+        let first_in_flight = state.enter_stage(&first, ExecutorStage::Aggregating);
+        let second_in_flight = state.enter_stage(&second, ExecutorStage::Aggregating);
+        let snapshot = state.snapshot();
+        assert_eq!(snapshot.in_flight.len(), 2);
+        assert_eq!(snapshot.current().unwrap().action, first);
+
+        // An action being executed is further along than the aggregating ones
+        second_in_flight.advance(ExecutorStage::AwaitingEffects);
+        let current = state.snapshot().current().unwrap().clone();
+        assert_eq!(current.action, second);
+        assert_eq!(current.stage, ExecutorStage::AwaitingEffects);
+
+        drop(second_in_flight);
+        assert_eq!(state.snapshot().current().unwrap().action, first);
+        drop(first_in_flight);
+        assert!(state.snapshot().in_flight.is_empty());
+    }
+
+    #[test]
+    fn test_snapshots_are_not_changed_by_later_updates() {
+        let state = ExecutorState::default();
+        let action = get_test_eth_to_sui_bridge_action(Some(1), None, None, None);
+        let before = state.snapshot();
+        state.set_queued(vec![action.clone()]);
+        state.set_bridge_paused(true);
+        assert_eq!(*before, ExecutorSnapshot::default());
+
+        let snapshot = state.snapshot();
+        assert_eq!(snapshot.queued, vec![action]);
+        assert!(snapshot.bridge_paused);
+        assert!(snapshot.gas_coin.is_none());
+    }
+}
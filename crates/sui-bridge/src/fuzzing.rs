@@ -0,0 +1,412 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Harness for fuzzing the parsing of the events received by the watchers, which is
+//! untrusted chain data. The fuzz targets in `fuzz/` and the smoke tests below turn raw
+//! bytes into an Eth log or a Sui event with `eth_log_from_bytes` and
+//! `sui_event_from_bytes`, and run them through the default parsers with
+//! `check_eth_log` and `check_sui_event`. Parsing may fail, but must not panic, and a
+//! parsed token transfer must be valid.
+//!
+//! The committed corpus is in `testdata/fuzz`, one directory per target. Inputs that
+//! once crashed a parser are added there too, so that the smoke tests replay them.
+
+use crate::abi::eth_bridge_event_topics;
+use crate::event_parsers::{
+    BridgeEthLogParser, BridgeSuiEventParser, EthLogContext, EthLogParser, SuiEventContext,
+    SuiEventParser,
+};
+use crate::events::all_struct_tags;
+use crate::types::{BridgeAction, EthLog};
+use ethers::types::{Address as EthAddress, Log, H256};
+use std::str::FromStr;
+use sui_json_rpc_types::SuiEvent;
+use sui_types::base_types::SuiAddress;
+use sui_types::digests::TransactionDigest;
+use sui_types::event::EventID;
+use sui_types::BRIDGE_PACKAGE_ID;
+
+// Bit of the second byte of an Eth input that makes the first topic raw bytes
+const RAW_FIRST_TOPIC: u8 = 0x80;
+
+/// Builds an Eth log from `bytes`:
+/// - byte 0: number of topics, modulo 5
+/// - byte 1: the first topic is the signature of the bridge event with this index when
+///   `RAW_FIRST_TOPIC` is not set, so that most inputs reach a decoder. Otherwise it is
+///   read like the other topics.
+/// - 32 bytes for each topic read
+/// - the rest: data of the log
+///
+/// Missing bytes are zeros.
+pub fn eth_log_from_bytes(bytes: &[u8]) -> EthLog {
+    let mut reader = Reader(bytes);
+    let topic_count = reader.byte() % 5;
+    let selector = reader.byte();
+    let mut known_topics = eth_bridge_event_topics();
+    known_topics.sort();
+    let topics = (0..topic_count)
+        .map(|i| {
+            if i == 0 && selector & RAW_FIRST_TOPIC == 0 {
+                known_topics[selector as usize % known_topics.len()]
+            } else {
+                H256::from_slice(&reader.bytes(32))
+            }
+        })
+        .collect();
+    let tx_hash = H256::repeat_byte(1);
+    EthLog {
+        block_number: 1,
+        tx_hash,
+        log_index_in_tx: 0,
+        block_timestamp_ms: None,
+        log: Log {
+            address: EthAddress::repeat_byte(2),
+            topics,
+            data: reader.rest().to_vec().into(),
+            transaction_hash: Some(tx_hash),
+            ..Default::default()
+        },
+    }
+}
+
+/// Builds a Sui event from `bytes`:
+/// - byte 0: the type of the event is the one of the bridge event with this index,
+///   modulo the number of bridge events plus one. The last one is an unknown type.
+/// - bytes 1 to 8: index of the event in its transaction, little endian
+/// - the rest: bcs bytes of the event
+///
+/// Missing bytes are zeros.
+pub fn sui_event_from_bytes(bytes: &[u8]) -> SuiEvent {
+    let mut reader = Reader(bytes);
+    let struct_tags = all_struct_tags();
+    let selector = reader.byte() as usize % (struct_tags.len() + 1);
+    let type_ = match struct_tags.get(selector) {
+        Some(struct_tag) => (*struct_tag).clone(),
+        None => move_core_types::language_storage::StructTag::from_str(&format!(
+            "0x{}::bridge::UnknownEvent",
+            BRIDGE_PACKAGE_ID.to_hex()
+        ))
+        .unwrap(),
+    };
+    let event_seq = u64::from_le_bytes(reader.bytes(8).try_into().unwrap());
+    SuiEvent {
+        id: EventID {
+            tx_digest: TransactionDigest::new([1; 32]),
+            event_seq,
+        },
+        package_id: BRIDGE_PACKAGE_ID,
+        transaction_module: type_.module.clone(),
+        sender: SuiAddress::ZERO,
+        type_,
+        parsed_json: serde_json::Value::Null,
+        bcs: reader.rest().to_vec(),
+        timestamp_ms: None,
+    }
+}
+
+/// Parses `log` like the Eth syncer. Panics if a parsed token transfer is not valid.
+pub fn check_eth_log(log: &EthLog) {
+    let context = EthLogContext {
+        contract: log.log.address,
+        end_block: log.block_number,
+    };
+    let Ok(Some(parsed)) = BridgeEthLogParser.parse(log, &context) else {
+        return;
+    };
+    if let Some(action) = &parsed.action {
+        let BridgeAction::EthToSuiBridgeAction(transfer) = action else {
+            panic!("Eth log parsed into a {:?}", action);
+        };
+        assert_eq!(transfer.eth_tx_hash, log.tx_hash);
+        assert_eq!(transfer.eth_event_index, log.log_index_in_tx);
+        let event = &transfer.eth_bridge_event;
+        assert!(event.sui_adjusted_amount > 0, "{:?}", event);
+        assert!(event.sui_chain_id.is_sui_chain(), "{:?}", event);
+        check_action(action);
+    }
+}
+
+/// Parses `event` like the Sui syncer. Panics if a parsed token transfer is not valid.
+pub fn check_sui_event(event: &SuiEvent) {
+    let context = SuiEventContext {
+        module: &event.transaction_module,
+    };
+    let Ok(Some(parsed)) = BridgeSuiEventParser.parse(event, &context) else {
+        return;
+    };
+    if let Some(action) = &parsed.action {
+        let BridgeAction::SuiToEthBridgeAction(transfer) = action else {
+            panic!("Sui event parsed into a {:?}", action);
+        };
+        assert_eq!(transfer.sui_tx_digest, event.id.tx_digest);
+        assert_eq!(transfer.sui_tx_event_index as u64, event.id.event_seq);
+        let event = &transfer.sui_bridge_event;
+        assert!(event.amount_sui_adjusted > 0, "{:?}", event);
+        assert!(event.sui_chain_id.is_sui_chain(), "{:?}", event);
+        assert!(!event.eth_chain_id.is_sui_chain(), "{:?}", event);
+        check_action(action);
+    }
+}
+
+// The action can be signed, and submitted to the other chain
+fn check_action(action: &BridgeAction) {
+    action.digest();
+    action.to_signing_bytes();
+    if let BridgeAction::SuiToEthBridgeAction(transfer) = action {
+        crate::abi::eth_sui_bridge::Message::from(transfer.clone());
+    }
+}
+
+// Reads the input of a fuzz target, as zeros past its end
+struct Reader<'a>(&'a [u8]);
+
+impl<'a> Reader<'a> {
+    fn byte(&mut self) -> u8 {
+        self.bytes(1)[0]
+    }
+
+    fn bytes(&mut self, len: usize) -> Vec<u8> {
+        let (read, rest) = self.0.split_at(len.min(self.0.len()));
+        self.0 = rest;
+        let mut bytes = read.to_vec();
+        bytes.resize(len, 0);
+        bytes
+    }
+
+    fn rest(self) -> &'a [u8] {
+        self.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::abi::{EthBridgeEvent, EthSuiBridgeEvents, TokensDepositedFilter};
+    use crate::error::BridgeError;
+    use crate::events::{MoveTokenDepositedEvent, SuiBridgeEvent};
+    use ethers::abi::Token;
+    use ethers::contract::EthEvent;
+    use proptest::collection::vec;
+    use proptest::prelude::*;
+    use rand::rngs::StdRng;
+    use rand::{Rng, SeedableRng};
+    use std::path::Path;
+    use sui_types::bridge::BridgeChainId;
+
+    // Mutations of each input of the corpus checked by the smoke tests
+    const SMOKE_MUTATIONS_PER_INPUT: usize = 500;
+
+    fn corpus(target: &str) -> Vec<Vec<u8>> {
+        let dir = Path::new(env!("CARGO_MANIFEST_DIR"))
+            .join("testdata/fuzz")
+            .join(target);
+        let mut paths = std::fs::read_dir(&dir)
+            .unwrap()
+            .map(|entry| entry.unwrap().path())
+            .collect::<Vec<_>>();
+        paths.sort();
+        let inputs = paths
+            .iter()
+            .map(|path| std::fs::read(path).unwrap())
+            .collect::<Vec<_>>();
+        assert!(!inputs.is_empty(), "Empty corpus in {:?}", dir);
+        inputs
+    }
+
+    // Flips, truncates or extends `input`, like a fuzzer would
+    fn mutate(rng: &mut StdRng, input: &[u8]) -> Vec<u8> {
+        let mut mutated = input.to_vec();
+        for _ in 0..rng.gen_range(1..4) {
+            match rng.gen_range(0..4) {
+                0 if !mutated.is_empty() => {
+                    let i = rng.gen_range(0..mutated.len());
+                    mutated[i] ^= 1 << rng.gen_range(0..8);
+                }
+                1 if !mutated.is_empty() => {
+                    let i = rng.gen_range(0..mutated.len());
+                    mutated[i] = rng.gen();
+                }
+                2 => mutated.truncate(rng.gen_range(0..=mutated.len())),
+                _ => {
+                    let len = rng.gen_range(1..40);
+                    mutated.extend((0..len).map(|_| rng.gen::<u8>()));
+                }
+            }
+        }
+        mutated
+    }
+
+    #[test]
+    fn test_eth_log_parsing_smoke() {
+        let mut rng = StdRng::seed_from_u64(0);
+        for input in corpus("eth_log") {
+            check_eth_log(&eth_log_from_bytes(&input));
+            for _ in 0..SMOKE_MUTATIONS_PER_INPUT {
+                check_eth_log(&eth_log_from_bytes(&mutate(&mut rng, &input)));
+            }
+        }
+    }
+
+    #[test]
+    fn test_sui_event_parsing_smoke() {
+        let mut rng = StdRng::seed_from_u64(0);
+        for input in corpus("sui_event") {
+            check_sui_event(&sui_event_from_bytes(&input));
+            for _ in 0..SMOKE_MUTATIONS_PER_INPUT {
+                check_sui_event(&sui_event_from_bytes(&mutate(&mut rng, &input)));
+            }
+        }
+    }
+
+    #[test]
+    fn test_corpus_reaches_parsers() {
+        let deposits = corpus("eth_log")
+            .iter()
+            .filter_map(|input| EthBridgeEvent::parse_eth_log(&eth_log_from_bytes(input)))
+            .filter(|(_, action)| matches!(action, Ok(Some(_))))
+            .count();
+        assert!(deposits > 0);
+        let deposits = corpus("sui_event")
+            .iter()
+            .filter_map(|input| SuiBridgeEvent::parse_sui_event(&sui_event_from_bytes(input)).ok())
+            .filter(|parsed| matches!(parsed, Some((_, Some(_)))))
+            .count();
+        assert!(deposits > 0);
+    }
+
+    fn deposit_log(topics: Vec<H256>, data: Vec<u8>) -> EthLog {
+        let mut log = eth_log_from_bytes(&[]);
+        log.log.topics = topics;
+        log.log.data = data.into();
+        log
+    }
+
+    fn deposit_topics(source_chain: u8, nonce: u64, destination_chain: u8) -> Vec<H256> {
+        vec![
+            TokensDepositedFilter::signature(),
+            H256::from_low_u64_be(source_chain as u64),
+            H256::from_low_u64_be(nonce),
+            H256::from_low_u64_be(destination_chain as u64),
+        ]
+    }
+
+    fn deposit_data(token_id: u8, amount: u64, sender: EthAddress, recipient: Vec<u8>) -> Vec<u8> {
+        ethers::abi::encode(&[
+            Token::Uint(token_id.into()),
+            Token::Uint(amount.into()),
+            Token::Address(sender),
+            Token::Bytes(recipient),
+        ])
+    }
+
+    // Short data and missing topics are decoding errors, not panics
+    #[test]
+    fn test_truncated_deposit_logs() {
+        let topics = deposit_topics(
+            BridgeChainId::EthCustom as u8,
+            1,
+            BridgeChainId::SuiCustom as u8,
+        );
+        let data = deposit_data(2, 100, EthAddress::repeat_byte(3), vec![4; 32]);
+        let log = deposit_log(topics.clone(), data.clone());
+        assert!(matches!(
+            EthBridgeEvent::parse_eth_log(&log),
+            Some((
+                EthBridgeEvent::EthSuiBridgeEvents(EthSuiBridgeEvents::TokensDepositedFilter(_)),
+                Ok(Some(_))
+            ))
+        ));
+        for len in [0, 1, 31, 32, 100] {
+            let log = deposit_log(topics.clone(), data[..len].to_vec());
+            assert!(EthBridgeEvent::parse_eth_log(&log).is_none());
+        }
+        for len in 0..data.len() {
+            check_eth_log(&deposit_log(topics.clone(), data[..len].to_vec()));
+        }
+        for count in 1..topics.len() {
+            let log = deposit_log(topics[..count].to_vec(), data.clone());
+            assert!(EthBridgeEvent::parse_eth_log(&log).is_none());
+            check_eth_log(&log);
+        }
+    }
+
+    // Event indexes used to be truncated to 16 bits
+    #[test]
+    fn test_sui_event_index_out_of_range() {
+        let deposit = MoveTokenDepositedEvent {
+            seq_num: 1,
+            source_chain: BridgeChainId::SuiCustom as u8,
+            sender_address: SuiAddress::ZERO.to_vec(),
+            target_chain: BridgeChainId::EthCustom as u8,
+            target_address: EthAddress::repeat_byte(1).as_bytes().to_vec(),
+            token_type: 1,
+            amount_sui_adjusted: 100,
+        };
+        let mut event = sui_event_from_bytes(&[0]);
+        event.bcs = bcs::to_bytes(&deposit).unwrap();
+        event.id.event_seq = u16::MAX as u64;
+        assert!(matches!(
+            SuiBridgeEvent::parse_sui_event(&event),
+            Ok(Some((_, Some(_))))
+        ));
+        event.id.event_seq = u16::MAX as u64 + 1;
+        assert!(matches!(
+            SuiBridgeEvent::parse_sui_event(&event),
+            Err(BridgeError::Generic(_))
+        ));
+        check_sui_event(&event);
+    }
+
+    proptest! {
+        #[test]
+        fn test_sui_deposit_events_parse_or_fail(
+            seq_num: u64,
+            source_chain: u8,
+            sender_address in vec(any::<u8>(), 0..40),
+            target_chain: u8,
+            target_address in vec(any::<u8>(), 0..40),
+            token_type: u8,
+            amount_sui_adjusted: u64,
+            event_seq in prop_oneof![0..1024u64, any::<u64>()],
+            truncate_to: Option<usize>,
+        ) {
+            let mut event = sui_event_from_bytes(&[0]);
+            event.id.event_seq = event_seq;
+            event.bcs = bcs::to_bytes(&MoveTokenDepositedEvent {
+                seq_num,
+                source_chain,
+                sender_address,
+                target_chain,
+                target_address,
+                token_type,
+                amount_sui_adjusted,
+            })
+            .unwrap();
+            if let Some(len) = truncate_to {
+                event.bcs.truncate(len % (event.bcs.len() + 1));
+            }
+            check_sui_event(&event);
+        }
+
+        #[test]
+        fn test_eth_deposit_logs_parse_or_fail(
+            source_chain: u8,
+            nonce: u64,
+            destination_chain: u8,
+            token_id: u8,
+            amount: u64,
+            sender: [u8; 20],
+            recipient in vec(any::<u8>(), 0..80),
+            topic_count in 0..5usize,
+            truncate_to: Option<usize>,
+        ) {
+            let mut topics = deposit_topics(source_chain, nonce, destination_chain);
+            topics.truncate(topic_count);
+            let mut data = deposit_data(token_id, amount, EthAddress::from(sender), recipient);
+            if let Some(len) = truncate_to {
+                data.truncate(len % (data.len() + 1));
+            }
+            check_eth_log(&deposit_log(topics, data));
+        }
+    }
+}
@@ -0,0 +1,403 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! `GasTopUp` keeps the balance of the bridge client gas coin above a threshold. When it
+//! drops below, a funding address pays SUI to the bridge client address, and the received
+//! coin is merged into the gas coin, bringing its balance back to the target.
+
+use crate::error::{BridgeError, BridgeResult};
+use crate::metrics::BridgeMetrics;
+use crate::sui_client::{SuiClient, SuiClientInner};
+use shared_crypto::intent::{Intent, IntentMessage};
+use std::sync::Arc;
+use std::time::Instant;
+use sui_json_rpc_types::{
+    SuiExecutionStatus, SuiTransactionBlockEffectsAPI, SuiTransactionBlockResponse,
+};
+use sui_types::{
+    base_types::{ObjectID, ObjectRef, SuiAddress},
+    crypto::{Signature, SuiKeyPair},
+    object::Owner,
+    transaction::{Transaction, TransactionData},
+};
+use tokio::time::{Duration, MissedTickBehavior};
+use tracing::{error, info, warn};
+
+pub const DEFAULT_GAS_TOP_UP_CHECK_INTERVAL: Duration = Duration::from_secs(60);
+pub const DEFAULT_GAS_TOP_UP_COOLDOWN: Duration = Duration::from_secs(3600);
+// Gas budget of the funding and merging transactions, in mist
+const GAS_TOP_UP_GAS_BUDGET: u64 = 10_000_000;
+
+pub struct GasTopUp<C> {
+    sui_client: Arc<SuiClient<C>>,
+    bridge_key: SuiKeyPair,
+    bridge_address: SuiAddress,
+    gas_object_id: ObjectID,
+    // Shared with the executor, see `BridgeActionExecutor::with_gas_lock`
+    gas_lock: Arc<tokio::sync::Mutex<()>>,
+    funding_key: SuiKeyPair,
+    funding_address: SuiAddress,
+    funding_gas_object_id: ObjectID,
+    threshold_balance: u64,
+    target_balance: u64,
+    check_interval: Duration,
+    cooldown: Duration,
+    last_top_up: Option<Instant>,
+    // Coin paid to the bridge client address that is not merged into the gas coin yet
+    unmerged_coin: Option<ObjectRef>,
+    metrics: Arc<BridgeMetrics>,
+}
+
+impl<C> GasTopUp<C>
+where
+    C: SuiClientInner + 'static,
+{
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        sui_client: Arc<SuiClient<C>>,
+        bridge_key: SuiKeyPair,
+        gas_object_id: ObjectID,
+        gas_lock: Arc<tokio::sync::Mutex<()>>,
+        funding_key: SuiKeyPair,
+        funding_gas_object_id: ObjectID,
+        threshold_balance: u64,
+        target_balance: u64,
+        check_interval: Duration,
+        cooldown: Duration,
+        metrics: Arc<BridgeMetrics>,
+    ) -> anyhow::Result<Self> {
+        let bridge_address = SuiAddress::from(&bridge_key.public());
+        let funding_address = SuiAddress::from(&funding_key.public());
+        // Funding transactions are not serialized with the executor. If they could pick
+        // the coins of the bridge client, they could equivocate its gas coin.
+        if funding_address == bridge_address {
+            anyhow::bail!("Funding address must be different from the bridge client address");
+        }
+        if funding_gas_object_id == gas_object_id {
+            anyhow::bail!("Funding gas object must be different from the bridge client gas object");
+        }
+        if target_balance <= threshold_balance {
+            anyhow::bail!(
+                "Target balance {} must be greater than threshold balance {}",
+                target_balance,
+                threshold_balance
+            );
+        }
+        Ok(Self {
+            sui_client,
+            bridge_key,
+            bridge_address,
+            gas_object_id,
+            gas_lock,
+            funding_key,
+            funding_address,
+            funding_gas_object_id,
+            threshold_balance,
+            target_balance,
+            check_interval,
+            cooldown,
+            last_top_up: None,
+            unmerged_coin: None,
+            metrics,
+        })
+    }
+
+    pub async fn run(mut self) {
+        info!(
+            funding_address = ?self.funding_address,
+            "Starting GasTopUp"
+        );
+        let mut interval = tokio::time::interval(self.check_interval);
+        interval.set_missed_tick_behavior(MissedTickBehavior::Delay);
+        loop {
+            interval.tick().await;
+            if let Err(e) = self.check_and_top_up().await {
+                self.metrics.gas_top_up_failures.inc();
+                error!("Failed to top up gas coin: {:?}", e);
+            }
+        }
+    }
+
+    /// Tops up the gas coin if its balance is below the threshold, at most once per
+    /// cooldown. Returns the amount paid by the funding address, if any, which covers the
+    /// gas of merging it into the gas coin.
+    pub async fn check_and_top_up(&mut self) -> BridgeResult<Option<u64>> {
+        if let Some(coin_ref) = self.unmerged_coin {
+            self.merge_into_gas_coin(coin_ref).await?;
+        }
+        let (balance, _) = self
+            .get_gas_coin(self.gas_object_id, self.bridge_address)
+            .await?;
+        self.metrics.gas_coin_balance.set(balance as i64);
+        if balance >= self.threshold_balance {
+            return Ok(None);
+        }
+        if let Some(last_top_up) = self.last_top_up {
+            if last_top_up.elapsed() < self.cooldown {
+                warn!(
+                    balance,
+                    "Gas coin balance is below threshold again within top-up cooldown"
+                );
+                return Ok(None);
+            }
+        }
+        // A failed top-up also waits for the cooldown, so that it's not retried in a loop
+        self.last_top_up = Some(Instant::now());
+
+        // The merge is paid from the merged coins, so its budget is paid on top for the
+        // balance to reach the target
+        let amount = self.target_balance - balance + GAS_TOP_UP_GAS_BUDGET;
+        info!(balance, amount, "Topping up gas coin");
+        let coin_ref = self.fund(amount).await?;
+        self.unmerged_coin = Some(coin_ref);
+        self.merge_into_gas_coin(coin_ref).await?;
+        self.metrics.gas_top_ups.inc();
+        Ok(Some(amount))
+    }
+
+    // Pays `amount` from the funding address to the bridge client address, and returns
+    // the received coin.
+    async fn fund(&self, amount: u64) -> BridgeResult<ObjectRef> {
+        let (funding_balance, funding_coin_ref) = self
+            .get_gas_coin(self.funding_gas_object_id, self.funding_address)
+            .await?;
+        if funding_balance < amount + GAS_TOP_UP_GAS_BUDGET {
+            return Err(BridgeError::Generic(format!(
+                "Funding gas object balance {} is not enough to pay {}",
+                funding_balance, amount
+            )));
+        }
+        let rgp = self
+            .sui_client
+            .get_reference_gas_price_until_success()
+            .await;
+        let tx_data = TransactionData::new_pay_sui(
+            self.funding_address,
+            vec![],
+            vec![self.bridge_address],
+            vec![amount],
+            funding_coin_ref,
+            GAS_TOP_UP_GAS_BUDGET,
+            rgp,
+        )
+        .map_err(|e| BridgeError::Generic(format!("Failed to build funding tx: {:?}", e)))?;
+        let response = self.execute(tx_data, &self.funding_key).await?;
+        response
+            .effects
+            .as_ref()
+            .and_then(|effects| {
+                effects
+                    .created()
+                    .iter()
+                    .find(|created| created.owner == Owner::AddressOwner(self.bridge_address))
+                    .map(|created| created.reference.to_object_ref())
+            })
+            .ok_or(BridgeError::Generic(format!(
+                "No coin created for bridge client address in funding tx {:?}",
+                response.digest
+            )))
+    }
+
+    // Merges `coin_ref` into the gas coin with a transaction of the bridge client.
+    async fn merge_into_gas_coin(&mut self, coin_ref: ObjectRef) -> BridgeResult<()> {
+        let gas_lock = self.gas_lock.clone();
+        let _gas_guard = gas_lock.lock().await;
+        let (_, gas_object_ref) = self
+            .get_gas_coin(self.gas_object_id, self.bridge_address)
+            .await?;
+        let rgp = self
+            .sui_client
+            .get_reference_gas_price_until_success()
+            .await;
+        // Transferring the gas coin to its own owner merges the other gas payment coins
+        // into it
+        let tx_data = TransactionData::new_pay_all_sui(
+            self.bridge_address,
+            vec![coin_ref],
+            self.bridge_address,
+            gas_object_ref,
+            GAS_TOP_UP_GAS_BUDGET,
+            rgp,
+        );
+        self.execute(tx_data, &self.bridge_key).await?;
+        self.unmerged_coin = None;
+        Ok(())
+    }
+
+    async fn get_gas_coin(
+        &self,
+        object_id: ObjectID,
+        owner: SuiAddress,
+    ) -> BridgeResult<(u64, ObjectRef)> {
+        let (coin, object_ref, actual_owner) = self
+            .sui_client
+            .get_gas_data_maybe(object_id)
+            .await?
+            .ok_or(BridgeError::Generic(format!(
+                "Gas object {:?} not found",
+                object_id
+            )))?;
+        if actual_owner != Owner::AddressOwner(owner) {
+            return Err(BridgeError::Generic(format!(
+                "Gas object {:?} is not owned by {:?} but {:?}",
+                object_id, owner, actual_owner
+            )));
+        }
+        Ok((coin.value(), object_ref))
+    }
+
+    async fn execute(
+        &self,
+        tx_data: TransactionData,
+        key: &SuiKeyPair,
+    ) -> BridgeResult<SuiTransactionBlockResponse> {
+        let sig = Signature::new_secure(
+            &IntentMessage::new(Intent::sui_transaction(), &tx_data),
+            key,
+        );
+        let response = self
+            .sui_client
+            .execute_transaction_block_with_effects(Transaction::from_data(tx_data, vec![sig]))
+            .await?;
+        match response.effects.as_ref().map(|effects| effects.status()) {
+            Some(SuiExecutionStatus::Success) => Ok(response),
+            status => Err(BridgeError::SuiTxFailureGeneric(format!(
+                "Tx {:?} failed: {:?}",
+                response.digest, status
+            ))),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sui_mock_client::SuiMockClient;
+    use sui_json_rpc_types::{OwnedObjectRef, SuiTransactionBlockEffects};
+    use sui_types::base_types::random_object_ref;
+    use sui_types::crypto::{get_key_pair, AccountKeyPair};
+    use sui_types::digests::TransactionDigest;
+    use sui_types::gas_coin::GasCoin;
+    use sui_types::transaction::{CallArg, TransactionDataAPI, TransactionKind};
+
+    const THRESHOLD: u64 = 1_000_000_000;
+    const TARGET: u64 = 5_000_000_000;
+
+    fn new_key() -> (SuiAddress, SuiKeyPair) {
+        let (address, key): (_, AccountKeyPair) = get_key_pair();
+        (address, SuiKeyPair::Ed25519(key))
+    }
+
+    fn set_gas_coin(mock: &SuiMockClient, object_ref: ObjectRef, owner: SuiAddress, value: u64) {
+        mock.add_gas_object_info(
+            GasCoin::new_for_testing_with_id(object_ref.0, value),
+            object_ref,
+            Owner::AddressOwner(owner),
+        );
+    }
+
+    fn mock_funding_response(mock: &SuiMockClient, recipient: SuiAddress) -> ObjectRef {
+        let coin_ref = random_object_ref();
+        let mut effects = SuiTransactionBlockEffects::new_for_testing(
+            TransactionDigest::random(),
+            SuiExecutionStatus::Success,
+        );
+        let SuiTransactionBlockEffects::V1(effects_v1) = &mut effects;
+        effects_v1.created.push(OwnedObjectRef {
+            owner: Owner::AddressOwner(recipient),
+            reference: coin_ref.into(),
+        });
+        let mut response = SuiTransactionBlockResponse::new(TransactionDigest::random());
+        response.effects = Some(effects);
+        mock.set_wildcard_transaction_response(Ok(response));
+        coin_ref
+    }
+
+    #[tokio::test]
+    async fn test_gas_top_up_after_drain() {
+        telemetry_subscribers::init_for_testing();
+        let mock = SuiMockClient::default();
+        let sui_client = Arc::new(SuiClient::new_for_testing(mock.clone()));
+        let metrics = Arc::new(BridgeMetrics::for_testing());
+        let (bridge_address, bridge_key) = new_key();
+        let (funding_address, funding_key) = new_key();
+        let gas_object_ref = random_object_ref();
+        let funding_object_ref = random_object_ref();
+        set_gas_coin(&mock, gas_object_ref, bridge_address, TARGET);
+        set_gas_coin(&mock, funding_object_ref, funding_address, 100 * TARGET);
+        let coin_ref = mock_funding_response(&mock, bridge_address);
+
+        let mut gas_top_up = GasTopUp::new(
+            sui_client,
+            bridge_key,
+            gas_object_ref.0,
+            Arc::new(tokio::sync::Mutex::new(())),
+            funding_key,
+            funding_object_ref.0,
+            THRESHOLD,
+            TARGET,
+            DEFAULT_GAS_TOP_UP_CHECK_INTERVAL,
+            DEFAULT_GAS_TOP_UP_COOLDOWN,
+            metrics.clone(),
+        )
+        .unwrap();
+
+        // Balance is above threshold, nothing to do
+        assert_eq!(gas_top_up.check_and_top_up().await.unwrap(), None);
+        assert!(mock.executed_transactions().is_empty());
+
+        // The gas coin is drained
+        let drained_balance = THRESHOLD / 2;
+        set_gas_coin(&mock, gas_object_ref, bridge_address, drained_balance);
+        let amount = TARGET - drained_balance + GAS_TOP_UP_GAS_BUDGET;
+        assert_eq!(gas_top_up.check_and_top_up().await.unwrap(), Some(amount));
+        let txs = mock.executed_transactions();
+        assert_eq!(txs.len(), 2);
+
+        // The funding address pays the amount to the bridge client address
+        let funding_tx = txs[0].data().transaction_data();
+        assert_eq!(funding_tx.sender(), funding_address);
+        assert_eq!(funding_tx.gas(), &[funding_object_ref]);
+        let TransactionKind::ProgrammableTransaction(pt) = funding_tx.kind() else {
+            panic!("Expected programmable transaction");
+        };
+        assert!(pt
+            .inputs
+            .contains(&CallArg::Pure(bcs::to_bytes(&amount).unwrap())));
+        assert!(pt
+            .inputs
+            .contains(&CallArg::Pure(bcs::to_bytes(&bridge_address).unwrap())));
+
+        // The bridge client merges the received coin into its gas coin
+        let merge_tx = txs[1].data().transaction_data();
+        assert_eq!(merge_tx.sender(), bridge_address);
+        assert_eq!(merge_tx.gas(), &[gas_object_ref, coin_ref]);
+
+        // Still below threshold within the cooldown, e.g. the top-up is not indexed yet
+        assert_eq!(gas_top_up.check_and_top_up().await.unwrap(), None);
+        assert_eq!(mock.executed_transactions().len(), 2);
+        assert_eq!(metrics.gas_top_ups.get(), 1);
+    }
+
+    #[test]
+    fn test_gas_top_up_rejects_bridge_client_as_funder() {
+        let mock = SuiMockClient::default();
+        let (_, bridge_key) = new_key();
+        let err = GasTopUp::new(
+            Arc::new(SuiClient::new_for_testing(mock)),
+            bridge_key.copy(),
+            ObjectID::random(),
+            Arc::new(tokio::sync::Mutex::new(())),
+            bridge_key,
+            ObjectID::random(),
+            THRESHOLD,
+            TARGET,
+            DEFAULT_GAS_TOP_UP_CHECK_INTERVAL,
+            DEFAULT_GAS_TOP_UP_COOLDOWN,
+            Arc::new(BridgeMetrics::for_testing()),
+        )
+        .err()
+        .unwrap();
+        assert!(err.to_string().contains("Funding address"));
+    }
+}
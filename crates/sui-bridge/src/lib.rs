@@ -3,31 +3,87 @@
 
 pub mod abi;
 pub mod action_executor;
+pub mod action_latency;
+pub mod action_observer;
+pub mod adaptive_interval;
+pub mod aggregation_status;
+pub mod alerts;
+pub mod api_types;
+pub mod archive;
+pub mod certified_actions;
+pub mod chain_health;
+pub mod claim_tracker;
 pub mod client;
+pub mod clock_drift;
 pub mod config;
 pub mod crypto;
+pub mod cursor_reset;
+pub mod doctor;
+pub mod drain;
 pub mod encoding;
 pub mod error;
 pub mod eth_client;
+pub mod eth_fee_history;
+pub mod eth_receipt_proof;
 pub mod eth_syncer;
 pub mod eth_transaction_builder;
+pub mod event_parsers;
+pub mod event_stream;
 pub mod events;
+pub mod executor_state;
+pub mod gas_top_up;
+pub mod limit_window;
+pub mod logging;
 pub mod metered_eth_provider;
 pub mod metrics;
 pub mod monitor;
+pub mod move_errors;
+pub mod multi_node;
 pub mod node;
+pub mod node_status;
+pub mod notional;
 pub mod orchestrator;
+pub mod provider_versions;
+pub mod pruning;
+pub mod quarantine_recheck;
+pub mod readiness;
+pub mod reconciler;
+pub mod replay;
+pub mod route_pause;
+pub mod runtime_info;
 pub mod server;
+pub mod shadow;
+pub mod signature_inclusion;
+pub mod signed_set;
+pub mod signer;
+pub mod signing_queue;
+pub mod slo;
+pub mod snapshot;
 pub mod storage;
+pub mod storage_encryption;
+pub mod storage_maintenance;
 pub mod sui_client;
+pub mod sui_rpc_retry;
 pub mod sui_syncer;
 pub mod sui_transaction_builder;
+pub mod supervisor;
+pub mod telemetry;
+pub mod timestamps;
+pub mod token_math;
+pub mod transfer_export;
+pub mod transfer_minimums;
+pub mod tx_journal;
+pub mod tx_submission;
 pub mod types;
+pub mod url_check;
 pub mod utils;
 
 #[cfg(test)]
 pub(crate) mod eth_mock_provider;
 
+#[cfg(any(test, feature = "fuzzing"))]
+pub mod fuzzing;
+
 #[cfg(test)]
 pub(crate) mod sui_mock_client;
 
@@ -59,11 +115,14 @@ macro_rules! retry_with_max_elapsed_time {
                     Ok(_) => {
                         return Ok(result);
                     }
-                    Err(e) => {
-                        // For simplicity we treat every error as transient so we can retry until max_elapsed_time
+                    Err(e) if $crate::error::ClassifyError::is_retryable(&e) => {
                         tracing::debug!("Retrying due to error: {:?}", e);
                         return Err(backoff::Error::transient(e));
                     }
+                    Err(e) => {
+                        tracing::debug!("Not retrying due to permanent error: {:?}", e);
+                        return Err(backoff::Error::permanent(e));
+                    }
                 }
             };
             std::boxed::Box::pin(fut)
@@ -75,6 +134,8 @@ macro_rules! retry_with_max_elapsed_time {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::error::{BridgeError, BridgeResult};
+    use std::sync::atomic::{AtomicU32, Ordering};
     use std::time::Duration;
 
     async fn example_func_ok() -> anyhow::Result<()> {
@@ -86,6 +147,11 @@ mod tests {
         Err(anyhow::anyhow!(""))
     }
 
+    async fn example_func_permanent_err(attempts: &AtomicU32) -> BridgeResult<()> {
+        attempts.fetch_add(1, Ordering::SeqCst);
+        Err(BridgeError::InvalidChainId)
+    }
+
     #[tokio::test]
     async fn test_retry_with_max_elapsed_time() {
         telemetry_subscribers::init_for_testing();
@@ -101,5 +167,13 @@ mod tests {
         let instant = std::time::Instant::now();
         retry_with_max_elapsed_time!(example_func_err(), max_elapsed_time).unwrap_err();
         assert!(instant.elapsed() < max_elapsed_time);
+
+        // permanent errors are returned right away
+        let attempts = AtomicU32::new(0);
+        let err =
+            retry_with_max_elapsed_time!(example_func_permanent_err(&attempts), max_elapsed_time)
+                .unwrap_err();
+        assert_eq!(err, BridgeError::InvalidChainId);
+        assert_eq!(attempts.load(Ordering::SeqCst), 1);
     }
 }
@@ -0,0 +1,312 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! The rolling window of the transfer limiter on Sui, rebuilt from its transfer records.
+//! The limiter keeps the notional value of the transfers of each route by hour, and
+//! rejects a transfer when the last 24 hours, including the current one, plus the transfer
+//! are above the limit of the route. It only drops old hours when it records a transfer,
+//! so the window is rebuilt at the chain time rather than taken from the record as is.
+//! The chain time is the time of the latest checkpoint, the local clock of the node can be
+//! off and is only reported.
+
+use crate::api_types::{LimitCapacity, LimitWindow, LimitWindowBucket};
+use crate::error::{BridgeError, BridgeResult};
+use crate::notional::BridgeRoute;
+use sui_types::bridge::{BridgeChainId, BridgeLimiterSummary, MoveTypeBridgeTransferRecord};
+
+pub const LIMIT_WINDOW_HOURS: u64 = 24;
+pub(crate) const HOUR_MS: u64 = 60 * 60 * 1000;
+
+/// Parses a route given as `<source chain id>-<destination chain id>`, e.g. `12-2`.
+pub fn parse_route(route: &str) -> BridgeResult<BridgeRoute> {
+    let invalid = || {
+        BridgeError::InvalidBridgeClientRequest(format!(
+            "Invalid route {}, expected <source chain id>-<destination chain id>",
+            route
+        ))
+    };
+    let (source, destination) = route.split_once('-').ok_or_else(invalid)?;
+    let chain_id = |chain_id: &str| {
+        chain_id
+            .parse::<u8>()
+            .ok()
+            .and_then(|chain_id| BridgeChainId::try_from(chain_id).ok())
+            .ok_or_else(invalid)
+    };
+    Ok((chain_id(source)?, chain_id(destination)?))
+}
+
+/// Rebuilds the window of `route` at `chain_time_ms`. Routes without a transfer record
+/// have an empty window. When `amount` is set, also computes when a transfer of `amount`
+/// fits.
+pub fn limit_window(
+    route: BridgeRoute,
+    limiter: &BridgeLimiterSummary,
+    chain_time_ms: u64,
+    local_time_ms: u64,
+    amount: Option<u64>,
+) -> LimitWindow {
+    let limit = limiter
+        .transfer_limit
+        .iter()
+        .find(|(source, destination, _)| (*source, *destination) == route)
+        .map(|(_, _, limit)| *limit);
+    let buckets = limiter
+        .transfer_records
+        .iter()
+        .find(|(source, destination, _)| (*source, *destination) == route)
+        .map(|(_, _, record)| window_buckets(record, chain_time_ms))
+        .unwrap_or_default();
+    let total_amount = buckets
+        .iter()
+        .fold(0u64, |total, bucket| total.saturating_add(bucket.amount));
+    let requested = amount.map(|amount| {
+        let available_at_ms = limit.and_then(|limit| {
+            available_at(&buckets, total_amount, limit, amount).map(|at| at.max(chain_time_ms))
+        });
+        LimitCapacity {
+            amount,
+            available_at_ms,
+            available_in_ms: available_at_ms.map(|at| at - chain_time_ms),
+        }
+    });
+    LimitWindow {
+        source_chain: route.0 as u8,
+        destination_chain: route.1 as u8,
+        limit,
+        total_amount,
+        available_amount: limit.map_or(0, |limit| limit.saturating_sub(total_amount)),
+        buckets,
+        chain_time_ms,
+        clock_skew_ms: local_time_ms as i64 - chain_time_ms as i64,
+        requested,
+    }
+}
+
+// The hours of `record` still in the window at `chain_time_ms` with transfers, oldest first
+fn window_buckets(
+    record: &MoveTypeBridgeTransferRecord,
+    chain_time_ms: u64,
+) -> Vec<LimitWindowBucket> {
+    // The record can't be ahead of the chain, unless the checkpoint is from a lagging
+    // fullnode
+    let current_hour = (chain_time_ms / HOUR_MS).max(record.hour_head);
+    let oldest_hour = (current_hour + 1).saturating_sub(LIMIT_WINDOW_HOURS);
+    record
+        .per_hour_amounts
+        .iter()
+        .enumerate()
+        .map(|(i, amount)| (record.hour_tail + i as u64, *amount))
+        .filter(|(hour, amount)| *hour >= oldest_hour && *amount > 0)
+        .map(|(hour, amount)| LimitWindowBucket {
+            hour_start_ms: hour * HOUR_MS,
+            amount,
+            expires_at_ms: (hour + LIMIT_WINDOW_HOURS) * HOUR_MS,
+        })
+        .collect()
+}
+
+// When `amount` fits under `limit` as the buckets expire, 0 if it already fits. None if
+// it's above the limit.
+fn available_at(
+    buckets: &[LimitWindowBucket],
+    total_amount: u64,
+    limit: u64,
+    amount: u64,
+) -> Option<u64> {
+    if amount > limit {
+        return None;
+    }
+    let fits = |total: u64| total.saturating_add(amount) <= limit;
+    if fits(total_amount) {
+        return Some(0);
+    }
+    let mut total = total_amount;
+    for bucket in buckets {
+        total = total.saturating_sub(bucket.amount);
+        if fits(total) {
+            return Some(bucket.expires_at_ms);
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sui_client::SuiClient;
+    use crate::sui_mock_client::SuiMockClient;
+
+    const ROUTE: BridgeRoute = (BridgeChainId::EthSepolia, BridgeChainId::SuiTestnet);
+    // 10:30 on day 1000
+    const NOW_MS: u64 = (1000 * 24 + 10) * HOUR_MS + 30 * 60 * 1000;
+
+    fn hour(hours_ago: u64) -> u64 {
+        NOW_MS / HOUR_MS - hours_ago
+    }
+
+    // Transfers recorded 30, 23, 20 and 2 hours ago, and not since
+    fn limiter() -> BridgeLimiterSummary {
+        let mut per_hour_amounts = vec![0; 29];
+        per_hour_amounts[0] = 500;
+        per_hour_amounts[7] = 100;
+        per_hour_amounts[10] = 300;
+        per_hour_amounts[28] = 200;
+        BridgeLimiterSummary {
+            transfer_limit: vec![
+                (ROUTE.0, ROUTE.1, 1000),
+                (BridgeChainId::EthMainnet, BridgeChainId::SuiMainnet, 1000),
+            ],
+            transfer_records: vec![(
+                ROUTE.0,
+                ROUTE.1,
+                MoveTypeBridgeTransferRecord {
+                    hour_head: hour(2),
+                    hour_tail: hour(30),
+                    per_hour_amounts,
+                    total_amount: 1100,
+                },
+            )],
+        }
+    }
+
+    #[test]
+    fn test_limit_window() {
+        let window = limit_window(ROUTE, &limiter(), NOW_MS, NOW_MS + 1500, None);
+        // The limiter hasn't dropped the transfer of 30 hours ago yet, it's out of the window
+        assert_eq!(
+            window.buckets,
+            vec![
+                LimitWindowBucket {
+                    hour_start_ms: hour(23) * HOUR_MS,
+                    amount: 100,
+                    expires_at_ms: (hour(23) + 24) * HOUR_MS,
+                },
+                LimitWindowBucket {
+                    hour_start_ms: hour(20) * HOUR_MS,
+                    amount: 300,
+                    expires_at_ms: (hour(20) + 24) * HOUR_MS,
+                },
+                LimitWindowBucket {
+                    hour_start_ms: hour(2) * HOUR_MS,
+                    amount: 200,
+                    expires_at_ms: (hour(2) + 24) * HOUR_MS,
+                },
+            ]
+        );
+        assert_eq!(window.limit, Some(1000));
+        assert_eq!(window.total_amount, 600);
+        assert_eq!(window.available_amount, 400);
+        assert_eq!(window.clock_skew_ms, 1500);
+        assert_eq!(window.requested, None);
+        // The transfer of 23 hours ago expires in 30 minutes
+        assert_eq!(window.buckets[0].expires_at_ms - NOW_MS, 30 * 60 * 1000);
+
+        // Routes with a limit and no transfers, and routes without a limit
+        let window = limit_window(
+            (BridgeChainId::EthMainnet, BridgeChainId::SuiMainnet),
+            &limiter(),
+            NOW_MS,
+            NOW_MS,
+            Some(1000),
+        );
+        assert!(window.buckets.is_empty());
+        assert_eq!(window.available_amount, 1000);
+        assert_eq!(window.requested.unwrap().available_at_ms, Some(NOW_MS));
+        let window = limit_window(
+            (BridgeChainId::SuiMainnet, BridgeChainId::EthMainnet),
+            &limiter(),
+            NOW_MS,
+            NOW_MS,
+            Some(1),
+        );
+        assert_eq!(window.limit, None);
+        assert_eq!(window.available_amount, 0);
+        assert_eq!(window.requested.unwrap().available_at_ms, None);
+    }
+
+    #[test]
+    fn test_limit_capacity() {
+        let capacity = |amount| {
+            limit_window(ROUTE, &limiter(), NOW_MS, NOW_MS, Some(amount))
+                .requested
+                .unwrap()
+        };
+        // Fits now
+        assert_eq!(
+            capacity(400),
+            LimitCapacity {
+                amount: 400,
+                available_at_ms: Some(NOW_MS),
+                available_in_ms: Some(0),
+            }
+        );
+        // Once the transfer of 23 hours ago expires
+        assert_eq!(capacity(401).available_in_ms, Some(30 * 60 * 1000));
+        assert_eq!(capacity(500).available_in_ms, Some(30 * 60 * 1000));
+        // then the one of 20 hours ago
+        assert_eq!(
+            capacity(501).available_at_ms,
+            Some((hour(20) + 24) * HOUR_MS)
+        );
+        // and the whole limit once they all expired
+        assert_eq!(
+            capacity(1000).available_at_ms,
+            Some((hour(2) + 24) * HOUR_MS)
+        );
+        // Never above the limit
+        assert_eq!(capacity(1001).available_at_ms, None);
+        assert_eq!(capacity(1001).available_in_ms, None);
+    }
+
+    #[test]
+    fn test_limit_window_clock_skew() {
+        // The latest checkpoint is an hour behind the last recorded transfer: the window
+        // starts from the record
+        let record = &limiter().transfer_records[0].2;
+        let lagging_chain_time_ms = record.hour_head * HOUR_MS - HOUR_MS;
+        let buckets = window_buckets(record, lagging_chain_time_ms);
+        assert_eq!(buckets, window_buckets(record, record.hour_head * HOUR_MS));
+        assert_eq!(buckets.len(), 3);
+
+        // A local clock off by hours doesn't change the window
+        let skewed = limit_window(ROUTE, &limiter(), NOW_MS, NOW_MS - 5 * HOUR_MS, Some(501));
+        let window = limit_window(ROUTE, &limiter(), NOW_MS, NOW_MS, Some(501));
+        assert_eq!(skewed.clock_skew_ms, -5 * HOUR_MS as i64);
+        assert_eq!(skewed.buckets, window.buckets);
+        assert_eq!(skewed.requested, window.requested);
+    }
+
+    #[test]
+    fn test_parse_route() {
+        assert_eq!(parse_route("11-1").unwrap(), ROUTE);
+        for route in ["11", "11-", "11-3", "sepolia-1", "1-11-2"] {
+            assert!(parse_route(route).is_err(), "{}", route);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_get_limit_window() {
+        let mock_client = SuiMockClient::default();
+        mock_client.set_bridge_limiter(limiter());
+        mock_client.set_latest_checkpoint_timestamp_ms(NOW_MS);
+        let sui_client = SuiClient::new_for_testing(mock_client.clone());
+
+        let window = sui_client.get_limit_window(ROUTE, Some(401)).await.unwrap();
+        assert_eq!(window.chain_time_ms, NOW_MS);
+        assert_eq!(window.total_amount, 600);
+        assert_eq!(
+            window.requested.unwrap().available_in_ms,
+            Some(30 * 60 * 1000)
+        );
+
+        // A new transfer is seen right away
+        let mut limiter = limiter();
+        let record = &mut limiter.transfer_records[0].2;
+        record.per_hour_amounts.extend([0, 300]);
+        record.hour_head = hour(0);
+        mock_client.set_bridge_limiter(limiter);
+        let window = sui_client.get_limit_window(ROUTE, None).await.unwrap();
+        assert_eq!(window.total_amount, 900);
+    }
+}
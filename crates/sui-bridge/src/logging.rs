@@ -0,0 +1,403 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Logging of the node, configured by the `logging` section of `BridgeNodeConfig`. Logs
+//! are written as text or JSON lines to stderr, or to a file rotated by size: when the
+//! file would exceed the configured size, `node.log` is renamed `node.log.1`,
+//! `node.log.1` is renamed `node.log.2` and so on, and the oldest file beyond the
+//! retention is deleted. `RUST_LOG` takes precedence over the configured filters.
+//!
+//! The filters are applied again when the node receives SIGHUP, so that the level can be
+//! raised to debug an issue without restarting the node.
+
+use crate::config::{BridgeNodeConfig, LogFileConfig, LogFormat, LoggingConfig};
+use anyhow::anyhow;
+use prometheus::Registry;
+use std::fs::{File, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex, MutexGuard};
+use sui_config::Config;
+use telemetry_subscribers::span_latency_prom::PrometheusSpanLatencyLayer;
+use telemetry_subscribers::{TelemetryConfig, TelemetryGuards, TracingHandle};
+use tokio::task::JoinHandle;
+use tracing::{error, info, Level};
+use tracing_subscriber::{
+    filter::{self, EnvFilter},
+    fmt::{self, writer::BoxMakeWriter, MakeWriter},
+    layer::SubscriberExt,
+    reload, Layer, Registry as TracingRegistry,
+};
+
+pub const DEFAULT_LOG_LEVEL: &str = "info";
+pub const DEFAULT_MAX_LOG_FILE_SIZE: u64 = 100 * 1024 * 1024;
+pub const DEFAULT_RETAINED_LOG_FILES: usize = 10;
+// Same as `TelemetryConfig`
+const SPAN_LATENCY_BUCKETS: usize = 15;
+
+/// Returns the filter of the logs configured by `config`: its level, then the level of
+/// each module.
+pub fn filter_directives(config: Option<&LoggingConfig>) -> String {
+    let Some(config) = config else {
+        return DEFAULT_LOG_LEVEL.to_string();
+    };
+    let mut directives = config
+        .level
+        .clone()
+        .unwrap_or_else(|| DEFAULT_LOG_LEVEL.to_string());
+    for (module, level) in &config.modules {
+        directives.push_str(&format!(",{}={}", module, level));
+    }
+    directives
+}
+
+enum LogFilterHandle {
+    Telemetry(TracingHandle),
+    Config(reload::Handle<EnvFilter, TracingRegistry>),
+}
+
+/// Keeps the logs flowing while it's alive, and changes their filter on reloads.
+pub struct Logging {
+    _telemetry_guards: Option<TelemetryGuards>,
+    filter: LogFilterHandle,
+}
+
+impl Logging {
+    /// Sets the global tracing subscriber as configured by `config`, or from the
+    /// environment as `TelemetryConfig` does when `config` is None.
+    pub fn init(config: Option<&LoggingConfig>, registry: &Registry) -> anyhow::Result<Self> {
+        let Some(config) = config else {
+            let (guards, handle) = TelemetryConfig::new()
+                .with_env()
+                .with_prom_registry(registry)
+                .init();
+            return Ok(Self {
+                _telemetry_guards: Some(guards),
+                filter: LogFilterHandle::Telemetry(handle),
+            });
+        };
+
+        let env_filter = match std::env::var("RUST_LOG") {
+            Ok(directives) => EnvFilter::try_new(directives)?,
+            Err(_) => EnvFilter::try_new(filter_directives(Some(config)))?,
+        };
+        let (log_filter, reload_handle) = reload::Layer::new(env_filter);
+        let output = match &config.file {
+            Some(file) => BoxMakeWriter::new(RotatingFileWriter::from_config(file)?),
+            None => BoxMakeWriter::new(std::io::stderr),
+        };
+        let log_layer = match config.format {
+            LogFormat::Text => fmt::layer()
+                .with_ansi(false)
+                .with_writer(output)
+                .with_filter(log_filter)
+                .boxed(),
+            LogFormat::Json => fmt::layer()
+                .with_file(true)
+                .with_line_number(true)
+                .json()
+                .with_writer(output)
+                .with_filter(log_filter)
+                .boxed(),
+        };
+        let span_latency_layer =
+            PrometheusSpanLatencyLayer::try_new(registry, SPAN_LATENCY_BUCKETS)
+                .map_err(|e| anyhow!("Failed to create the span latency layer: {:?}", e))?
+                .with_filter(filter::filter_fn(|metadata| {
+                    metadata.is_span() && *metadata.level() <= Level::INFO
+                }))
+                .boxed();
+
+        let subscriber = tracing_subscriber::registry().with(vec![span_latency_layer, log_layer]);
+        tracing::subscriber::set_global_default(subscriber)?;
+        Ok(Self {
+            _telemetry_guards: None,
+            filter: LogFilterHandle::Config(reload_handle),
+        })
+    }
+
+    /// Applies the level and module filters of `config`. Nothing changes while `RUST_LOG`
+    /// is set.
+    pub fn reload(&self, config: Option<&LoggingConfig>) -> anyhow::Result<()> {
+        if std::env::var("RUST_LOG").is_ok() {
+            info!("RUST_LOG is set, the log filter is not reloaded");
+            return Ok(());
+        }
+        let directives = filter_directives(config);
+        match &self.filter {
+            LogFilterHandle::Telemetry(handle) => handle
+                .update_log(&directives)
+                .map_err(|e| anyhow!("Invalid log filter {:?}: {:?}", directives, e))?,
+            LogFilterHandle::Config(handle) => handle.reload(EnvFilter::try_new(&directives)?)?,
+        }
+        info!("Log filter set to {:?}", directives);
+        Ok(())
+    }
+
+    /// Reads the node config at `config_path` and applies its log filter each time the
+    /// process receives SIGHUP.
+    pub fn reload_on_sighup(self: Arc<Self>, config_path: PathBuf) -> JoinHandle<()> {
+        tokio::spawn(async move {
+            let mut hangups =
+                match tokio::signal::unix::signal(tokio::signal::unix::SignalKind::hangup()) {
+                    Ok(hangups) => hangups,
+                    Err(e) => {
+                        error!(
+                            "Failed to listen to SIGHUP, logging can't be reloaded: {:?}",
+                            e
+                        );
+                        return;
+                    }
+                };
+            while hangups.recv().await.is_some() {
+                let result = BridgeNodeConfig::load(&config_path)
+                    .and_then(|config| self.reload(config.logging.as_ref()));
+                if let Err(e) = result {
+                    error!("Failed to reload logging from {:?}: {:?}", config_path, e);
+                }
+            }
+        })
+    }
+}
+
+/// Log file rotated by size. Each event is written while holding the lock of the file,
+/// so concurrent events are never interleaved. The file is rotated before an event that
+/// would make it exceed the size, never in the middle of one.
+#[derive(Debug, Clone)]
+pub struct RotatingFileWriter {
+    file: Arc<Mutex<RotatingFile>>,
+}
+
+#[derive(Debug)]
+struct RotatingFile {
+    path: PathBuf,
+    max_file_size: u64,
+    retained_files: usize,
+    file: File,
+    size: u64,
+}
+
+impl RotatingFileWriter {
+    /// Opens the log file at `path`, appending to it if it exists.
+    pub fn new(path: &Path, max_file_size: u64, retained_files: usize) -> anyhow::Result<Self> {
+        if let Some(dir) = path.parent().filter(|dir| !dir.as_os_str().is_empty()) {
+            std::fs::create_dir_all(dir)?;
+        }
+        let file = open_log_file(path)?;
+        let size = file.metadata()?.len();
+        Ok(Self {
+            file: Arc::new(Mutex::new(RotatingFile {
+                path: path.to_path_buf(),
+                max_file_size,
+                retained_files,
+                file,
+                size,
+            })),
+        })
+    }
+
+    pub fn from_config(config: &LogFileConfig) -> anyhow::Result<Self> {
+        Self::new(
+            &config.path,
+            config
+                .max_file_size_bytes
+                .unwrap_or(DEFAULT_MAX_LOG_FILE_SIZE),
+            config.retained_files.unwrap_or(DEFAULT_RETAINED_LOG_FILES),
+        )
+    }
+}
+
+impl<'a> MakeWriter<'a> for RotatingFileWriter {
+    type Writer = RotatingFileGuard<'a>;
+
+    fn make_writer(&'a self) -> Self::Writer {
+        // A panic while logging must not stop the logs
+        RotatingFileGuard {
+            file: self.file.lock().unwrap_or_else(|e| e.into_inner()),
+            started: false,
+        }
+    }
+}
+
+/// Writes one event
+pub struct RotatingFileGuard<'a> {
+    file: MutexGuard<'a, RotatingFile>,
+    // Whether some of the event was written already
+    started: bool,
+}
+
+impl Write for RotatingFileGuard<'_> {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        let file = &mut *self.file;
+        if !self.started && file.size > 0 && file.size + buf.len() as u64 > file.max_file_size {
+            file.rotate()?;
+        }
+        self.started = true;
+        let written = file.file.write(buf)?;
+        file.size += written as u64;
+        Ok(written)
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        self.file.file.flush()
+    }
+}
+
+impl RotatingFile {
+    fn rotate(&mut self) -> std::io::Result<()> {
+        self.file.flush()?;
+        if self.retained_files == 0 {
+            std::fs::remove_file(&self.path)?;
+        } else {
+            // The last rotated file is overwritten by the one before it
+            for index in (1..=self.retained_files).rev() {
+                let from = rotated_log_path(&self.path, index - 1);
+                if from.exists() {
+                    std::fs::rename(from, rotated_log_path(&self.path, index))?;
+                }
+            }
+        }
+        self.file = open_log_file(&self.path)?;
+        self.size = 0;
+        Ok(())
+    }
+}
+
+/// Path of the log file rotated `index` times, `path` itself for 0.
+pub fn rotated_log_path(path: &Path, index: usize) -> PathBuf {
+    if index == 0 {
+        return path.to_path_buf();
+    }
+    let mut rotated = path.as_os_str().to_owned();
+    rotated.push(format!(".{}", index));
+    rotated.into()
+}
+
+fn open_log_file(path: &Path) -> std::io::Result<File> {
+    OpenOptions::new().create(true).append(true).open(path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::BTreeMap;
+
+    fn log_files(dir: &Path) -> Vec<String> {
+        let mut files = std::fs::read_dir(dir)
+            .unwrap()
+            .map(|entry| entry.unwrap().file_name().into_string().unwrap())
+            .collect::<Vec<_>>();
+        files.sort();
+        files
+    }
+
+    fn read_lines(path: &Path) -> Vec<String> {
+        std::fs::read_to_string(path)
+            .unwrap()
+            .lines()
+            .map(str::to_string)
+            .collect()
+    }
+
+    #[test]
+    fn test_filter_directives() {
+        assert_eq!(filter_directives(None), "info");
+        let config = LoggingConfig {
+            level: Some("warn".to_string()),
+            modules: BTreeMap::from([
+                ("sui_bridge::eth_syncer".to_string(), "debug".to_string()),
+                ("hyper".to_string(), "error".to_string()),
+            ]),
+            ..Default::default()
+        };
+        assert_eq!(
+            filter_directives(Some(&config)),
+            "warn,hyper=error,sui_bridge::eth_syncer=debug"
+        );
+        assert!(EnvFilter::try_new(filter_directives(Some(&config))).is_ok());
+    }
+
+    #[test]
+    fn test_concurrent_writes_are_rotated_whole() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("node.log");
+        // Large enough retention to keep everything
+        let writer = RotatingFileWriter::new(&path, 4096, 100).unwrap();
+        let threads = (0..8)
+            .map(|thread| {
+                let writer = writer.clone();
+                std::thread::spawn(move || {
+                    for line in 0..200 {
+                        let mut guard = writer.make_writer();
+                        // An event may be written in several pieces
+                        write!(guard, "thread {} ", thread).unwrap();
+                        writeln!(guard, "line {:04}", line).unwrap();
+                    }
+                })
+            })
+            .collect::<Vec<_>>();
+        for thread in threads {
+            thread.join().unwrap();
+        }
+
+        let files = log_files(dir.path());
+        assert!(files.len() > 10, "{:?}", files);
+        let mut lines = vec![];
+        for file in &files {
+            let path = dir.path().join(file);
+            // Only the first piece of an event is accounted for before rotating
+            assert!(std::fs::metadata(&path).unwrap().len() <= 4096 + "line 0000\n".len() as u64);
+            lines.extend(read_lines(&path));
+        }
+        assert_eq!(lines.len(), 8 * 200);
+        for line in &lines {
+            let (thread, line) = line.split_once(" line ").unwrap();
+            assert!(thread.starts_with("thread "), "{}", thread);
+            assert_eq!(line.len(), 4, "{}", line);
+        }
+    }
+
+    #[test]
+    fn test_rotated_files_beyond_retention_are_deleted() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("node.log");
+        let writer = RotatingFileWriter::new(&path, 1000, 3).unwrap();
+        let subscriber = tracing_subscriber::registry().with(
+            fmt::layer()
+                .with_ansi(false)
+                .with_writer(writer)
+                .with_filter(EnvFilter::new("info")),
+        );
+        tracing::subscriber::with_default(subscriber, || {
+            for i in 0..500 {
+                info!("event {:05}", i);
+            }
+            tracing::debug!("filtered out");
+        });
+
+        assert_eq!(
+            log_files(dir.path()),
+            vec!["node.log", "node.log.1", "node.log.2", "node.log.3"]
+        );
+        // The newest events are in the current file, the oldest were deleted
+        let current = read_lines(&path);
+        assert!(current.last().unwrap().ends_with("event 00499"));
+        let oldest = read_lines(&rotated_log_path(&path, 3));
+        assert!(!oldest[0].ends_with("event 00000"));
+        for index in 0..=3 {
+            assert!(
+                std::fs::metadata(rotated_log_path(&path, index))
+                    .unwrap()
+                    .len()
+                    <= 1000
+            );
+        }
+        assert!(!current.iter().any(|line| line.contains("filtered out")));
+
+        // Reopening appends to the current file
+        let size = std::fs::metadata(&path).unwrap().len();
+        let writer = RotatingFileWriter::new(&path, 1000, 3).unwrap();
+        writeln!(writer.make_writer(), "x").unwrap();
+        assert_eq!(std::fs::metadata(&path).unwrap().len(), size + 2);
+    }
+}
@@ -3,14 +3,15 @@
 
 use clap::Parser;
 use fastcrypto::traits::KeyPair;
-use mysten_metrics::start_prometheus_server;
+use prometheus::Registry;
 use std::{
     net::{IpAddr, Ipv4Addr, SocketAddr},
     path::PathBuf,
 };
 use sui_bridge::config::BridgeNodeConfig;
-use sui_bridge::metrics::start_metrics_push_task;
-use sui_bridge::node::run_bridge_node;
+use sui_bridge::metrics::{start_metrics_push_task, start_metrics_server};
+use sui_bridge::multi_node::{run_bridge_nodes, MultiBridgeNodeConfig};
+use sui_bridge::node::{init_logging, run_bridge_node};
 use sui_bridge::server::BridgeNodePublicMetadata;
 use sui_config::Config;
 use tracing::info;
@@ -23,28 +24,61 @@ bin_version::bin_version!();
 #[clap(name = env!("CARGO_BIN_NAME"))]
 #[clap(version = VERSION)]
 struct Args {
+    /// Config of the bridge node
+    #[clap(long, required_unless_present = "multi_node_config_path")]
+    pub config_path: Option<PathBuf>,
+    /// Config of several bridge nodes to run side by side in this process
+    #[clap(long, conflicts_with = "config_path")]
+    pub multi_node_config_path: Option<PathBuf>,
+    /// Start even if the Sui and Eth bridge contracts are not set up to bridge between
+    /// the configured chains
+    #[clap(long = "i-know-what-i-am-doing")]
+    pub allow_mismatched_bridge_contracts: bool,
+    /// Check the invariants between the tables of the client storage at startup, as if
+    /// `verify-storage` was set in the config
     #[clap(long)]
-    pub config_path: PathBuf,
+    pub verify_storage: bool,
 }
 
 #[tokio::main]
 async fn main() -> anyhow::Result<()> {
     let args = Args::parse();
-    let config = BridgeNodeConfig::load(&args.config_path).unwrap();
+    if let Some(multi_node_config_path) = args.multi_node_config_path {
+        let mut instances =
+            MultiBridgeNodeConfig::load(&multi_node_config_path)?.load_instances()?;
+        for (_, config) in &mut instances {
+            config.allow_mismatched_bridge_contracts = args.allow_mismatched_bridge_contracts;
+            config.verify_storage |= args.verify_storage;
+        }
+        // Process wide metrics, exported by the metrics server of every instance
+        let process_registry = Registry::new();
+        mysten_metrics::init_metrics(&process_registry);
+        let (_guard, _filter_handle) = telemetry_subscribers::TelemetryConfig::new()
+            .with_env()
+            .with_prom_registry(&process_registry)
+            .init();
+        info!("Starting {} bridge node instances", instances.len());
+        return tokio::task::spawn_blocking(move || {
+            run_bridge_nodes(instances, VERSION.into(), process_registry)
+        })
+        .await?;
+    }
+    let config_path = args.config_path.unwrap();
+    let mut config = BridgeNodeConfig::load(&config_path).unwrap();
+    config.allow_mismatched_bridge_contracts = args.allow_mismatched_bridge_contracts;
+    config.verify_storage |= args.verify_storage;
 
     // Init metrics server
     let metrics_address =
         SocketAddr::new(IpAddr::V4(Ipv4Addr::new(0, 0, 0, 0)), config.metrics_port);
-    let registry_service = start_prometheus_server(metrics_address);
+    let registry_service = start_metrics_server(metrics_address);
     let prometheus_registry = registry_service.default_registry();
     mysten_metrics::init_metrics(&prometheus_registry);
     info!("Metrics server started at port {}", config.metrics_port);
 
     // Init logging
-    let (_guard, _filter_handle) = telemetry_subscribers::TelemetryConfig::new()
-        .with_env()
-        .with_prom_registry(&prometheus_registry)
-        .init();
+    let logging = init_logging(&config, &prometheus_registry)?;
+    logging.clone().reload_on_sighup(config_path);
 
     let metadata =
         BridgeNodePublicMetadata::new(VERSION.into(), config.metrics_key_pair.public().clone());
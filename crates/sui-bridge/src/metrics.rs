@@ -1,17 +1,42 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
+use crate::action_latency::ActionLatencyTracker;
+use crate::aggregation_status::AggregationStatus;
+use crate::chain_health::ChainHealth;
+use crate::client::authority_scores::AuthorityScoreboard;
+use crate::clock_drift::ClockDrift;
 use crate::config::MetricsConfig;
-use mysten_metrics::RegistryService;
+use crate::executor_state::ExecutorState;
+use crate::readiness::BridgeReadiness;
+use crate::server::requester::RequesterTracker;
+use crate::slo::SloTracker;
+use crate::url_check::RegisteredUrlStatus;
+use axum::http::header::{ACCEPT, CONTENT_TYPE};
+use axum::http::{HeaderMap, StatusCode};
+use axum::response::{IntoResponse, Response};
+use axum::routing::get;
+use axum::{Extension, Router};
+use mysten_metrics::{RegistryService, METRICS_ROUTE};
+use once_cell::sync::Lazy;
+use prometheus::proto::{LabelPair, MetricFamily};
 use prometheus::{
-    register_histogram_vec_with_registry, register_int_counter_vec_with_registry,
-    register_int_counter_with_registry, register_int_gauge_vec_with_registry,
-    register_int_gauge_with_registry, Encoder, HistogramVec, IntCounter, IntCounterVec, IntGauge,
-    IntGaugeVec, Registry,
+    core::Collector, proto::MetricType, register_counter_vec_with_registry,
+    register_gauge_vec_with_registry, register_gauge_with_registry,
+    register_histogram_vec_with_registry, register_histogram_with_registry,
+    register_int_counter_vec_with_registry, register_int_counter_with_registry,
+    register_int_gauge_vec_with_registry, register_int_gauge_with_registry, CounterVec, Encoder,
+    Gauge, GaugeVec, Histogram, HistogramVec, IntCounter, IntCounterVec, IntGauge, IntGaugeVec,
+    Registry, TextEncoder,
 };
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, VecDeque};
+use std::io::Write;
+use std::net::SocketAddr;
+use std::sync::{Arc, Mutex};
 use std::time::{Duration, SystemTime, UNIX_EPOCH};
 use sui_types::crypto::NetworkKeyPair;
-use tracing::error;
+use tracing::{error, warn};
 
 const FINE_GRAINED_LATENCY_SEC_BUCKETS: &[f64] = &[
     0.001, 0.005, 0.01, 0.05, 0.1, 0.15, 0.2, 0.25, 0.3, 0.35, 0.4, 0.45, 0.5, 0.6, 0.7, 0.8, 0.9,
@@ -20,6 +45,23 @@ const FINE_GRAINED_LATENCY_SEC_BUCKETS: &[f64] = &[
     200., 250., 300., 350., 400.,
 ];
 
+// From 0.001 to 10 SUI
+const GAS_MIST_BUCKETS: &[f64] = &[
+    1e6, 2e6, 5e6, 1e7, 2e7, 5e7, 1e8, 2e8, 5e8, 1e9, 2e9, 5e9, 1e10,
+];
+
+// Token transfers wait for finality on Eth, which takes minutes
+const ACTION_LATENCY_SEC_BUCKETS: &[f64] = &[
+    1., 2., 5., 10., 15., 20., 30., 45., 60., 90., 120., 180., 240., 300., 420., 600., 720., 840.,
+    960., 1200., 1500., 1800., 2400., 3600.,
+];
+
+// Tasks are usually polled within microseconds on a runtime with idle workers
+const SCHEDULING_DELAY_SEC_BUCKETS: &[f64] = &[
+    0.00001, 0.00005, 0.0001, 0.0005, 0.001, 0.0025, 0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0,
+    2.5, 5.0,
+];
+
 pub struct MetricsPushClient {
     certificate: std::sync::Arc<sui_tls::SelfSignedCertificate>,
     client: reqwest::Client,
@@ -155,46 +197,630 @@ pub fn start_metrics_push_task(
     });
 }
 
+/// Content type of the OpenMetrics text format.
+pub const OPENMETRICS_CONTENT_TYPE: &str =
+    "application/openmetrics-text; version=1.0.0; charset=utf-8";
+
+// When the counters of the process started counting, exported as their `_created` samples
+static PROCESS_START_TIME: Lazy<SystemTime> = Lazy::new(SystemTime::now);
+
+/// Starts a server exposing the metrics on `METRICS_ROUTE`, like
+/// `mysten_metrics::start_prometheus_server`, except that scrapers accepting OpenMetrics,
+/// e.g. Prometheus, get the OpenMetrics text format, with the `_created` timestamps that
+/// tell counter resets on restarts apart from rollovers.
+pub fn start_metrics_server(addr: SocketAddr) -> RegistryService {
+    Lazy::force(&PROCESS_START_TIME);
+    let registry_service = RegistryService::new(Registry::new());
+
+    if cfg!(msim) {
+        warn!("not starting metrics server in simulator");
+        return registry_service;
+    }
+
+    let app = Router::new()
+        .route(METRICS_ROUTE, get(metrics))
+        .layer(Extension(registry_service.clone()));
+
+    tokio::spawn(async move {
+        let listener = tokio::net::TcpListener::bind(&addr).await.unwrap();
+        axum::serve(listener, app.into_make_service())
+            .await
+            .unwrap();
+    });
+
+    registry_service
+}
+
+async fn metrics(
+    Extension(registry_service): Extension<RegistryService>,
+    headers: HeaderMap,
+) -> Response {
+    let metric_families = registry_service.gather_all();
+    let openmetrics = headers
+        .get(ACCEPT)
+        .and_then(|accept| accept.to_str().ok())
+        .is_some_and(accepts_openmetrics);
+    if openmetrics {
+        encode_response(
+            &OpenMetricsEncoder::new(*PROCESS_START_TIME),
+            &metric_families,
+        )
+    } else {
+        encode_response(&TextEncoder::new(), &metric_families)
+    }
+}
+
+fn encode_response(encoder: &impl Encoder, metric_families: &[MetricFamily]) -> Response {
+    let mut buf = vec![];
+    match encoder.encode(metric_families, &mut buf) {
+        Ok(()) => ([(CONTENT_TYPE, encoder.format_type().to_string())], buf).into_response(),
+        Err(error) => (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            format!("unable to encode metrics: {error}"),
+        )
+            .into_response(),
+    }
+}
+
+// Whether the Accept header lists OpenMetrics, without `q=0`. Scrapers that accept it
+// prefer it to the Prometheus text format.
+fn accepts_openmetrics(accept: &str) -> bool {
+    accept.split(',').any(|media_range| {
+        let mut params = media_range.split(';').map(str::trim);
+        let media_type = params.next().unwrap_or_default();
+        media_type.eq_ignore_ascii_case("application/openmetrics-text")
+            && params
+                .filter_map(|param| param.strip_prefix("q="))
+                .all(|q| q.parse::<f64>().map_or(true, |q| q > 0.0))
+    })
+}
+
+/// Encodes metrics in the OpenMetrics text format. Counters are exposed with a `_total`
+/// sample, which OpenMetrics requires, so counters named without the suffix get it, and
+/// with a `_created` sample, as are histograms and summaries. Histogram buckets are sorted,
+/// with `le` as their last label and ending with `+Inf`.
+pub struct OpenMetricsEncoder {
+    created_secs: f64,
+}
+
+impl OpenMetricsEncoder {
+    /// `created` is when the counters, histograms and summaries started counting.
+    pub fn new(created: SystemTime) -> Self {
+        Self {
+            created_secs: created
+                .duration_since(UNIX_EPOCH)
+                .unwrap_or_default()
+                .as_secs_f64(),
+        }
+    }
+}
+
+impl Encoder for OpenMetricsEncoder {
+    fn encode<W: Write>(
+        &self,
+        metric_families: &[MetricFamily],
+        writer: &mut W,
+    ) -> prometheus::Result<()> {
+        for mf in metric_families {
+            let metric_type = mf.get_field_type();
+            let name = match metric_type {
+                // The name of a counter family is the name of its samples without `_total`
+                MetricType::COUNTER => mf
+                    .get_name()
+                    .strip_suffix("_total")
+                    .unwrap_or(mf.get_name()),
+                _ => mf.get_name(),
+            };
+            let type_name = match metric_type {
+                MetricType::COUNTER => "counter",
+                MetricType::GAUGE => "gauge",
+                MetricType::HISTOGRAM => "histogram",
+                MetricType::SUMMARY => "summary",
+                MetricType::UNTYPED => "unknown",
+            };
+            writeln!(writer, "# TYPE {} {}", name, type_name)?;
+            if !mf.get_help().is_empty() {
+                writeln!(
+                    writer,
+                    "# HELP {} {}",
+                    name,
+                    escape_openmetrics(mf.get_help())
+                )?;
+            }
+
+            for m in mf.get_metric() {
+                let sample = OpenMetricsSample {
+                    name,
+                    labels: m.get_label(),
+                    timestamp_ms: m.get_timestamp_ms(),
+                };
+                match metric_type {
+                    MetricType::COUNTER => {
+                        sample.write(writer, "_total", None, m.get_counter().get_value())?;
+                        sample.write(writer, "_created", None, self.created_secs)?;
+                    }
+                    MetricType::GAUGE => {
+                        sample.write(writer, "", None, m.get_gauge().get_value())?;
+                    }
+                    MetricType::UNTYPED => {
+                        sample.write(writer, "", None, m.get_untyped().get_value())?;
+                    }
+                    MetricType::HISTOGRAM => {
+                        let histogram = m.get_histogram();
+                        let mut buckets = histogram
+                            .get_bucket()
+                            .iter()
+                            .map(|b| (b.get_upper_bound(), b.get_cumulative_count()))
+                            .collect::<Vec<_>>();
+                        buckets.sort_by(|(a, _), (b, _)| a.total_cmp(b));
+                        if buckets.last().map_or(true, |(le, _)| *le != f64::INFINITY) {
+                            buckets.push((f64::INFINITY, histogram.get_sample_count()));
+                        }
+                        for (le, count) in buckets {
+                            sample.write(writer, "_bucket", Some(("le", le)), count)?;
+                        }
+                        sample.write(writer, "_count", None, histogram.get_sample_count())?;
+                        sample.write(writer, "_sum", None, histogram.get_sample_sum())?;
+                        sample.write(writer, "_created", None, self.created_secs)?;
+                    }
+                    MetricType::SUMMARY => {
+                        let summary = m.get_summary();
+                        for quantile in summary.get_quantile() {
+                            sample.write(
+                                writer,
+                                "",
+                                Some(("quantile", quantile.get_quantile())),
+                                quantile.get_value(),
+                            )?;
+                        }
+                        sample.write(writer, "_count", None, summary.get_sample_count())?;
+                        sample.write(writer, "_sum", None, summary.get_sample_sum())?;
+                        sample.write(writer, "_created", None, self.created_secs)?;
+                    }
+                }
+            }
+        }
+        writer.write_all(b"# EOF\n")?;
+        Ok(())
+    }
+
+    fn format_type(&self) -> &str {
+        OPENMETRICS_CONTENT_TYPE
+    }
+}
+
+struct OpenMetricsSample<'a> {
+    name: &'a str,
+    labels: &'a [LabelPair],
+    timestamp_ms: i64,
+}
+
+impl OpenMetricsSample<'_> {
+    // Writes the sample of the metric with `suffix`, and with `extra_label` after its labels
+    fn write<W: Write>(
+        &self,
+        writer: &mut W,
+        suffix: &str,
+        extra_label: Option<(&str, f64)>,
+        value: impl OpenMetricsValue,
+    ) -> prometheus::Result<()> {
+        write!(writer, "{}{}", self.name, suffix)?;
+        let extra_label = extra_label.map(|(name, value)| (name, format_float(value)));
+        let labels = self
+            .labels
+            .iter()
+            .map(|label| (label.get_name(), escape_openmetrics(label.get_value())))
+            .chain(extra_label)
+            .map(|(name, value)| format!("{}=\"{}\"", name, value))
+            .collect::<Vec<_>>();
+        if !labels.is_empty() {
+            write!(writer, "{{{}}}", labels.join(","))?;
+        }
+        write!(writer, " {}", value.format())?;
+        // Timestamps are in seconds in OpenMetrics
+        if self.timestamp_ms != 0 {
+            write!(
+                writer,
+                " {}",
+                format_float(self.timestamp_ms as f64 / 1000.0)
+            )?;
+        }
+        writeln!(writer)?;
+        Ok(())
+    }
+}
+
+trait OpenMetricsValue {
+    fn format(&self) -> String;
+}
+
+impl OpenMetricsValue for f64 {
+    fn format(&self) -> String {
+        format_float(*self)
+    }
+}
+
+// Counts are integers
+impl OpenMetricsValue for u64 {
+    fn format(&self) -> String {
+        self.to_string()
+    }
+}
+
+fn format_float(value: f64) -> String {
+    if value == f64::INFINITY {
+        "+Inf".to_string()
+    } else if value == f64::NEG_INFINITY {
+        "-Inf".to_string()
+    } else if value.is_nan() {
+        "NaN".to_string()
+    } else {
+        // Debug keeps the decimal point, e.g. `1.0`, the canonical form of `le` values
+        format!("{:?}", value)
+    }
+}
+
+fn escape_openmetrics(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
+
+/// Number of most recent errors retained per counter in the `ErrorCatalog`.
+pub const ERROR_CATALOG_CAPACITY_PER_COUNTER: usize = 20;
+// Long messages are truncated to this many chars.
+const ERROR_CATALOG_MAX_MESSAGE_LEN: usize = 1024;
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ErrorCatalogEntry {
+    pub timestamp_ms: u64,
+    pub message: String,
+    /// Digests and addresses relevant to the error, e.g. action digest or tx digest.
+    pub digests: Vec<String>,
+}
+
+/// A bounded, in-memory list of the most recent errors behind each `err_*` counter, keyed by
+/// counter name, so operators can see what failed without grepping the logs. Old entries roll
+/// off once `capacity` is reached.
+/// Only sanitized descriptions of the errors, e.g. `BridgeError::sanitized`, digests and
+/// addresses are recorded, never the messages of errors, which may carry provider URLs with
+/// their API keys.
+#[derive(Debug)]
+pub struct ErrorCatalog {
+    capacity: usize,
+    entries: Mutex<BTreeMap<&'static str, VecDeque<ErrorCatalogEntry>>>,
+}
+
+impl ErrorCatalog {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            entries: Mutex::new(BTreeMap::new()),
+        }
+    }
+
+    pub fn record(&self, counter: &'static str, mut message: String, digests: Vec<String>) {
+        if self.capacity == 0 {
+            return;
+        }
+        if let Some((idx, _)) = message.char_indices().nth(ERROR_CATALOG_MAX_MESSAGE_LEN) {
+            message.truncate(idx);
+        }
+        let entry = ErrorCatalogEntry {
+            timestamp_ms: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .unwrap()
+                .as_millis() as u64,
+            message,
+            digests,
+        };
+        let mut entries = self.entries.lock().unwrap();
+        let counter_entries = entries.entry(counter).or_default();
+        if counter_entries.len() >= self.capacity {
+            counter_entries.pop_front();
+        }
+        counter_entries.push_back(entry);
+    }
+
+    /// Returns the retained errors of `counter`, oldest first.
+    pub fn get(&self, counter: &str) -> Vec<ErrorCatalogEntry> {
+        self.entries
+            .lock()
+            .unwrap()
+            .get(counter)
+            .map(|entries| entries.iter().cloned().collect())
+            .unwrap_or_default()
+    }
+
+    /// Returns the retained errors of all counters, oldest first.
+    pub fn snapshot(&self) -> BTreeMap<String, Vec<ErrorCatalogEntry>> {
+        self.entries
+            .lock()
+            .unwrap()
+            .iter()
+            .map(|(counter, entries)| (counter.to_string(), entries.iter().cloned().collect()))
+            .collect()
+    }
+}
+
+/// Increments an error counter of `BridgeMetrics` and records the error in its
+/// `ErrorCatalog` under the counter's name. `$message` must be sanitized, e.g. with
+/// `BridgeError::sanitized`, and `$digests` must only contain digests and addresses.
+#[macro_export]
+macro_rules! inc_err_counter {
+    ($metrics:expr, $counter:ident[$label:expr], $message:expr, $digests:expr) => {{
+        $metrics.$counter.with_label_values(&[$label]).inc();
+        $metrics.error_catalog.record(
+            stringify!($counter),
+            format!("[{}] {}", $label, $message),
+            $digests,
+        );
+    }};
+    ($metrics:expr, $counter:ident, $message:expr, $digests:expr) => {{
+        $metrics.$counter.inc();
+        $metrics
+            .error_catalog
+            .record(stringify!($counter), $message, $digests);
+    }};
+}
+
+/// Name, type and labels of a metric of `BridgeMetrics`, output of
+/// `sui-bridge-cli metrics-schema`.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub struct MetricSchema {
+    pub name: String,
+    pub metric_type: String,
+    pub labels: Vec<String>,
+    pub help: String,
+}
+
+impl MetricSchema {
+    // One line of the snapshot of the schema, without the help that can be reworded
+    fn snapshot_line(&self) -> String {
+        format!(
+            "{} {} [{}]",
+            self.name,
+            self.metric_type,
+            self.labels.join(",")
+        )
+    }
+}
+
+/// Registers metrics in a registry, and records their schema. `BridgeMetrics::new` passes
+/// it to the `register_*_with_registry` macros in place of the registry, so that no
+/// metric is left out of `BridgeMetrics::schema`.
+struct SchemaRecorder<'a> {
+    registry: &'a Registry,
+    schema: Mutex<Vec<MetricSchema>>,
+}
+
+impl<'a> SchemaRecorder<'a> {
+    fn new(registry: &'a Registry) -> Self {
+        Self {
+            registry,
+            schema: Mutex::new(vec![]),
+        }
+    }
+
+    fn register(&self, collector: Box<dyn Collector>) -> prometheus::Result<()> {
+        let metric_type = match collector
+            .collect()
+            .first()
+            .map(|family| family.get_field_type())
+        {
+            Some(MetricType::COUNTER) => "counter",
+            Some(MetricType::GAUGE) => "gauge",
+            Some(MetricType::HISTOGRAM) => "histogram",
+            Some(MetricType::SUMMARY) => "summary",
+            Some(MetricType::UNTYPED) | None => "untyped",
+        };
+        let mut schema = self.schema.lock().unwrap();
+        for desc in collector.desc() {
+            schema.push(MetricSchema {
+                name: desc.fq_name.clone(),
+                metric_type: metric_type.to_string(),
+                labels: desc.variable_labels.clone(),
+                help: desc.help.clone(),
+            });
+        }
+        drop(schema);
+        self.registry.register(collector)
+    }
+}
+
+/// Handle to the metrics of the node. Cloning it is cheap, all the clones share the same
+/// metrics.
 #[derive(Clone, Debug)]
 pub struct BridgeMetrics {
+    inner: Arc<BridgeMetricsInner>,
+}
+
+impl std::ops::Deref for BridgeMetrics {
+    type Target = BridgeMetricsInner;
+
+    fn deref(&self) -> &Self::Target {
+        &self.inner
+    }
+}
+
+#[derive(Debug)]
+pub struct BridgeMetricsInner {
     pub(crate) err_build_sui_transaction: IntCounter,
     pub(crate) err_signature_aggregation: IntCounter,
     pub(crate) err_sui_transaction_submission: IntCounter,
     pub(crate) err_sui_transaction_submission_too_many_failures: IntCounter,
-    pub(crate) err_sui_transaction_execution: IntCounter,
+    pub(crate) err_sui_transaction_execution: IntCounterVec,
+    pub(crate) err_tx_journal_write: IntCounter,
     pub(crate) requests_received: IntCounterVec,
     pub(crate) requests_ok: IntCounterVec,
     pub(crate) err_requests: IntCounterVec,
     pub(crate) requests_inflight: IntGaugeVec,
+    pub(crate) request_stage_latency: HistogramVec,
+    pub(crate) slow_requests: IntCounterVec,
+    pub(crate) requests_received_by_requester: IntCounterVec,
+    pub(crate) err_requests_by_requester: IntCounterVec,
 
     pub last_synced_sui_checkpoint: IntGauge,
     pub(crate) last_finalized_eth_block: IntGauge,
     pub(crate) last_synced_eth_block: IntGauge,
 
+    pub(crate) channel_capacity: IntGaugeVec,
+    pub(crate) syncer_send_timeouts: IntCounterVec,
+    pub(crate) syncer_poll_interval_ms: IntGaugeVec,
+    pub(crate) watcher_restarts: IntCounterVec,
+    pub(crate) chain_pipeline_status: IntGaugeVec,
+    pub(crate) clock_drift_ms: IntGaugeVec,
+    pub(crate) provider_version: IntGaugeVec,
+    pub(crate) provider_version_changes: IntCounterVec,
+    pub(crate) eth_receipt_verifications: IntCounterVec,
+    pub(crate) watcher_parsed_events: IntCounterVec,
+
     pub(crate) sui_watcher_received_events: IntCounter,
     pub(crate) sui_watcher_received_actions: IntCounter,
     pub(crate) sui_watcher_unrecognized_events: IntCounter,
     pub(crate) eth_watcher_received_events: IntCounter,
     pub(crate) eth_watcher_received_actions: IntCounter,
     pub(crate) eth_watcher_unrecognized_events: IntCounter,
+    pub(crate) orchestrator_dropped_below_min_transfer_actions: IntCounter,
+    pub(crate) orchestrator_precision_rejected_actions: IntCounter,
+    pub(crate) orchestrator_duplicate_actions: IntCounter,
+    pub(crate) orchestrator_invalid_recipient_transfers: IntCounterVec,
+    pub(crate) recovery_pending_total: IntGauge,
+    pub(crate) recovery_processed_total: IntCounter,
     pub(crate) action_executor_already_processed_actions: IntCounter,
     pub(crate) action_executor_signing_queue_received_actions: IntCounter,
     pub(crate) action_executor_signing_queue_skipped_actions: IntCounter,
+    pub(crate) action_executor_signing_queue_spilled_actions: IntGauge,
+    pub(crate) action_executor_disallowed_actions: IntCounter,
     pub(crate) action_executor_execution_queue_received_actions: IntCounter,
     pub(crate) action_executor_execution_queue_skipped_actions_due_to_pausing: IntCounter,
+    pub(crate) action_executor_quarantined_actions: IntCounter,
+    pub(crate) quarantine_rechecks: IntCounterVec,
+    pub(crate) gas_spent_mist: IntCounterVec,
+    pub(crate) gas_spent_mist_per_tx: HistogramVec,
+    pub(crate) action_observer_verified_actions: IntCounter,
+    pub(crate) action_observer_pending_actions: IntGauge,
+    pub(crate) shadow_comparisons: IntCounterVec,
+    pub(crate) alerts: IntCounterVec,
+
+    pub(crate) auth_agg_request_failures: IntCounterVec,
+    pub(crate) noncanonical_signatures: IntCounterVec,
+    pub(crate) committee_total_stake: IntGauge,
+    pub(crate) committee_blocklisted_stake: IntGauge,
+    pub(crate) committee_reachable_stake: IntGauge,
+    pub(crate) committee_validity_threshold: IntGauge,
+    pub(crate) committee_last_update_epoch: IntGauge,
+    pub(crate) committee_members_with_invalid_url: IntGauge,
+    pub(crate) committee_anomalies: IntGauge,
+    pub(crate) committee_dns_changes: IntCounter,
+    pub(crate) committee_connections: IntCounterVec,
+    pub(crate) committee_hedges_issued: IntCounterVec,
+    pub(crate) committee_hedges_won: IntCounterVec,
+
+    pub(crate) reconciliation_mismatches: IntCounterVec,
+    pub(crate) timestamp_anomalies: IntCounterVec,
+    pub(crate) pruned_executed_actions: IntCounter,
+    pub(crate) archived_executed_actions: IntCounter,
+    pub(crate) archive_upload_failures: IntCounter,
+    pub(crate) storage_maintenance_duration_seconds: HistogramVec,
+    pub(crate) storage_maintenance_failures: IntCounterVec,
+    pub(crate) storage_table_size_bytes: IntGaugeVec,
+    pub(crate) storage_compaction_reclaimed_bytes: IntCounter,
+    pub(crate) storage_backup_size_bytes: IntGauge,
+    pub(crate) own_signature_included_total: IntCounter,
+    pub(crate) own_signature_missing_total: IntCounter,
+    pub(crate) own_signature_inclusion_rate: Gauge,
+    pub(crate) runtime_workers: IntGaugeVec,
+    pub(crate) runtime_scheduling_delay_seconds: HistogramVec,
 
     pub(crate) signer_with_cache_hit: IntCounterVec,
     pub(crate) signer_with_cache_miss: IntCounterVec,
+    pub(crate) unfinalized_receipt_cache_hit: IntCounter,
+    pub(crate) unfinalized_receipt_cache_reorgs: IntCounter,
+    pub(crate) signing_nonce_too_far_ahead: IntCounterVec,
+    pub(crate) verification_queue_depth: IntGaugeVec,
+    pub(crate) verifications_inflight: IntGaugeVec,
+    pub(crate) verification_queue_wait: HistogramVec,
+    pub(crate) verification_queue_timeouts: IntCounterVec,
+    pub(crate) remote_signer_latency: Histogram,
+    pub(crate) remote_signer_failures: IntCounterVec,
+    pub(crate) remote_signer_circuit_open: IntGauge,
+    pub(crate) sui_client_cache_hits: IntCounterVec,
+    pub(crate) sui_client_cache_misses: IntCounterVec,
+    pub(crate) sui_rpc_errors: IntCounterVec,
+    pub(crate) sui_rpc_retries: IntCounterVec,
+    pub(crate) sui_tx_submissions: IntCounterVec,
+
+    pub(crate) transfer_notional_usd_total: CounterVec,
+    pub(crate) limit_utilization_ratio: GaugeVec,
+    pub(crate) route_paused: IntGaugeVec,
+    pub(crate) route_deferred_actions: IntGaugeVec,
+    pub(crate) approved_unclaimed: IntGauge,
+    pub(crate) approved_unclaimed_by_age: IntGaugeVec,
+    pub(crate) transfer_notional_price_unavailable: IntCounter,
 
     pub(crate) eth_rpc_queries: IntCounterVec,
     pub(crate) eth_rpc_queries_latency: HistogramVec,
+    pub(crate) eth_base_fee_wei: GaugeVec,
+    pub(crate) eth_base_fee_ewma_wei: GaugeVec,
+    pub(crate) eth_priority_fee_wei: GaugeVec,
+    pub(crate) eth_fee_sampling_paused: IntGauge,
+    pub(crate) action_finality_delay: HistogramVec,
+    pub(crate) action_processing_latency: HistogramVec,
 
     pub(crate) gas_coin_balance: IntGauge,
+    pub(crate) gas_top_ups: IntCounter,
+    pub(crate) gas_top_up_failures: IntCounter,
+
+    pub(crate) url_mismatch: IntGauge,
+    pub(crate) storage_integrity_violations: IntGauge,
+    pub(crate) storage_cursor_updates: IntCounterVec,
+    pub(crate) storage_cursor_flushes: IntCounterVec,
+    pub(crate) storage_cursors_flushed: IntCounter,
+
+    pub(crate) event_stream_consumers: IntGauge,
+    pub(crate) event_stream_dropped_events: IntCounter,
+    pub(crate) certified_actions_published: IntCounter,
+    pub(crate) certified_actions_lagged: IntCounter,
+    pub(crate) telemetry_failures: IntCounter,
+
+    pub(crate) slo_signing_latency_p99: Gauge,
+    pub(crate) slo_signing_error_ratio: GaugeVec,
+    pub(crate) slo_executor_queue_wait_p95: Gauge,
+
+    pub(crate) error_catalog: Arc<ErrorCatalog>,
+    pub(crate) committee_scores: Arc<AuthorityScoreboard>,
+    pub(crate) registered_url: Arc<RegisteredUrlStatus>,
+    pub(crate) slo: Arc<SloTracker>,
+    pub(crate) action_latency: Arc<ActionLatencyTracker>,
+    pub(crate) executor_state: Arc<ExecutorState>,
+    pub(crate) aggregation_status: Arc<AggregationStatus>,
+    pub(crate) requesters: Arc<RequesterTracker>,
+    pub(crate) chain_health: Arc<ChainHealth>,
+    pub(crate) clock_drift: Arc<ClockDrift>,
+    pub(crate) readiness: Arc<BridgeReadiness>,
 }
 
 impl BridgeMetrics {
     pub fn new(registry: &Registry) -> Self {
-        Self {
+        Self::new_with_recorder(&SchemaRecorder::new(registry))
+    }
+
+    /// Every metric of the node, sorted by name. Dashboards break when a metric is renamed
+    /// or its labels change, see `test_metrics_schema_snapshot`.
+    pub fn schema() -> Vec<MetricSchema> {
+        let registry = Registry::new();
+        let recorder = SchemaRecorder::new(&registry);
+        Self::new_with_recorder(&recorder);
+        let mut schema = recorder.schema.into_inner().unwrap();
+        schema.sort();
+        schema
+    }
+
+    fn new_with_recorder(registry: &SchemaRecorder) -> Self {
+        let inner = BridgeMetricsInner {
             err_build_sui_transaction: register_int_counter_with_registry!(
                 "bridge_err_build_sui_transaction",
                 "Total number of errors of building sui transactions",
@@ -219,9 +845,16 @@ impl BridgeMetrics {
                 registry,
             )
             .unwrap(),
-            err_sui_transaction_execution: register_int_counter_with_registry!(
+            err_sui_transaction_execution: register_int_counter_vec_with_registry!(
                 "bridge_err_sui_transaction_execution",
-                "Total number of failures of sui transaction execution",
+                "Total number of failures of sui transaction execution, by decoded bridge error category",
+                &["category"],
+                registry,
+            )
+            .unwrap(),
+            err_tx_journal_write: register_int_counter_with_registry!(
+                "bridge_err_tx_journal_write",
+                "Total number of failures to write a sui transaction to the tx journal",
                 registry,
             )
             .unwrap(),
@@ -253,6 +886,105 @@ impl BridgeMetrics {
                 registry,
             )
             .unwrap(),
+            request_stage_latency: register_histogram_vec_with_registry!(
+                "bridge_request_stage_latency",
+                "Latency of each stage of signing requests, by stage",
+                &["stage"],
+                FINE_GRAINED_LATENCY_SEC_BUCKETS.to_vec(),
+                registry,
+            )
+            .unwrap(),
+            slow_requests: register_int_counter_vec_with_registry!(
+                "bridge_slow_requests",
+                "Total number of signing requests slower than the threshold, by route",
+                &["route"],
+                registry,
+            )
+            .unwrap(),
+            requests_received_by_requester: register_int_counter_vec_with_registry!(
+                "bridge_requests_received_by_requester",
+                "Total number of signing requests received, by requester. Requesters beyond the configured number of labels are counted as `other`",
+                &["requester"],
+                registry,
+            )
+            .unwrap(),
+            err_requests_by_requester: register_int_counter_vec_with_registry!(
+                "bridge_err_requests_by_requester",
+                "Total number of signing requests that were not answered with a success, by requester. Requesters beyond the configured number of labels are counted as `other`",
+                &["requester"],
+                registry,
+            )
+            .unwrap(),
+            channel_capacity: register_int_gauge_vec_with_registry!(
+                "bridge_channel_capacity",
+                "Capacity of the channels between bridge client components, by channel. Occupancy is reported by `channel_inflight` with the same label",
+                &["channel"],
+                registry,
+            )
+            .unwrap(),
+            syncer_send_timeouts: register_int_counter_vec_with_registry!(
+                "bridge_syncer_send_timeouts",
+                "Total number of times a syncer gave up on a full events channel and will query the events again, by syncer",
+                &["syncer"],
+                registry,
+            )
+            .unwrap(),
+            syncer_poll_interval_ms: register_int_gauge_vec_with_registry!(
+                "bridge_syncer_poll_interval_ms",
+                "Current interval between the queries of a syncer, in milliseconds, by syncer. It only changes when adaptive polling is enabled",
+                &["syncer"],
+                registry,
+            )
+            .unwrap(),
+            watcher_restarts: register_int_counter_vec_with_registry!(
+                "bridge_watcher_restarts",
+                "Total number of times a watcher task was restarted after it panicked, by component",
+                &["component"],
+                registry,
+            )
+            .unwrap(),
+            chain_pipeline_status: register_int_gauge_vec_with_registry!(
+                "bridge_chain_pipeline_status",
+                "Status of the pipeline of each chain from the outcomes of its RPC queries, by chain: 0 healthy, 1 degraded, 2 down",
+                &["chain"],
+                registry,
+            )
+            .unwrap(),
+            clock_drift_ms: register_int_gauge_vec_with_registry!(
+                "bridge_clock_drift_ms",
+                "Local time minus the timestamp of the latest Sui checkpoint or Eth block, by chain",
+                &["chain"],
+                registry,
+            )
+            .unwrap(),
+            provider_version: register_int_gauge_vec_with_registry!(
+                "bridge_provider_version",
+                "Always 1, labeled by the chain, the RPC endpoint and the version of the server behind it",
+                &["chain", "endpoint", "version"],
+                registry,
+            )
+            .unwrap(),
+            provider_version_changes: register_int_counter_vec_with_registry!(
+                "bridge_provider_version_changes",
+                "Total number of times the version of the server behind the RPC endpoint of a chain changed while the node ran, by chain",
+                &["chain"],
+                registry,
+            )
+            .unwrap(),
+            eth_receipt_verifications: register_int_counter_vec_with_registry!(
+                "bridge_eth_receipt_verifications",
+                "Total number of Eth blocks whose receipts were verified against the receipts root of the independent provider, by result",
+                &["result"],
+                registry,
+            )
+            .unwrap(),
+            watcher_parsed_events: register_int_counter_vec_with_registry!(
+                "bridge_watcher_parsed_events",
+                "Total number of events received by the watchers that were claimed by an event parser, by chain and parser",
+                &["chain", "parser"],
+                registry,
+            )
+            .unwrap(),
             sui_watcher_received_events: register_int_counter_with_registry!(
                 "bridge_sui_watcher_received_events",
                 "Total number of received events in sui watcher",
@@ -295,6 +1027,43 @@ impl BridgeMetrics {
                 registry,
             )
             .unwrap(),
+            orchestrator_dropped_below_min_transfer_actions: register_int_counter_with_registry!(
+                "bridge_orchestrator_dropped_below_min_transfer_actions",
+                "Total number of token transfers dropped by orchestrator because the amount is below the minimum transfer amount",
+                registry,
+            )
+            .unwrap(),
+            orchestrator_precision_rejected_actions: register_int_counter_with_registry!(
+                "bridge_orchestrator_precision_rejected_actions",
+                "Total number of token transfers quarantined by orchestrator because their amount can't be converted exactly between the decimals of Sui and Eth",
+                registry,
+            )
+            .unwrap(),
+            orchestrator_duplicate_actions: register_int_counter_with_registry!(
+                "bridge_orchestrator_duplicate_actions",
+                "Total number of actions skipped by orchestrator because they are already pending, executed or dropped",
+                registry,
+            )
+            .unwrap(),
+            orchestrator_invalid_recipient_transfers: register_int_counter_vec_with_registry!(
+                "bridge_orchestrator_invalid_recipient_transfers",
+                "Total number of token transfers quarantined by orchestrator because their recipient is not a valid address on the destination chain, by source chain",
+                &["source"],
+                registry,
+            )
+            .unwrap(),
+            recovery_pending_total: register_int_gauge_with_registry!(
+                "bridge_recovery_pending_total",
+                "Number of pending actions loaded on startup that are not yet recovered",
+                registry,
+            )
+            .unwrap(),
+            recovery_processed_total: register_int_counter_with_registry!(
+                "bridge_recovery_processed_total",
+                "Total number of pending actions processed by startup recovery",
+                registry,
+            )
+            .unwrap(),
             action_executor_signing_queue_received_actions: register_int_counter_with_registry!(
                 "bridge_action_executor_signing_queue_received_actions",
                 "Total number of received actions in action executor signing queue",
@@ -307,6 +1076,18 @@ impl BridgeMetrics {
                 registry,
             )
             .unwrap(),
+            action_executor_signing_queue_spilled_actions: register_int_gauge_with_registry!(
+                "bridge_action_executor_signing_queue_spilled_actions",
+                "Number of actions in action executor signing queue that are not held in memory",
+                registry,
+            )
+            .unwrap(),
+            action_executor_disallowed_actions: register_int_counter_with_registry!(
+                "bridge_action_executor_disallowed_actions",
+                "Total number of actions dropped by action executor because their type is not allowed",
+                registry,
+            )
+            .unwrap(),
             action_executor_execution_queue_received_actions: register_int_counter_with_registry!(
                 "bridge_action_executor_execution_queue_received_actions",
                 "Total number of received actions in action executor execution queue",
@@ -319,12 +1100,140 @@ impl BridgeMetrics {
                 registry,
             )
             .unwrap(),
+            action_executor_quarantined_actions: register_int_counter_with_registry!(
+                "bridge_action_executor_quarantined_actions",
+                "Total number of actions quarantined by action executor after failing permanently",
+                registry,
+            )
+            .unwrap(),
+            quarantine_rechecks: register_int_counter_vec_with_registry!(
+                "bridge_quarantine_rechecks",
+                "Total number of quarantined actions verified again against the chain, by whether they were released, are still invalid or couldn't be verified",
+                &["result"],
+                registry,
+            )
+            .unwrap(),
+            gas_spent_mist: register_int_counter_vec_with_registry!(
+                "bridge_gas_spent_mist_total",
+                "Total gas spent on Sui by the transactions of the executor in mist, net of storage rebates, by action type and by whether the transaction succeeded or failed",
+                &["action_type", "status"],
+                registry,
+            )
+            .unwrap(),
+            gas_spent_mist_per_tx: register_histogram_vec_with_registry!(
+                "bridge_gas_spent_mist_per_tx",
+                "Gas spent on Sui by each transaction of the executor in mist, net of storage rebates, by action type",
+                &["action_type"],
+                GAS_MIST_BUCKETS.to_vec(),
+                registry,
+            )
+            .unwrap(),
+            action_observer_verified_actions: register_int_counter_with_registry!(
+                "bridge_action_observer_verified_actions",
+                "Total number of actions that action observer saw approved or claimed on chain",
+                registry,
+            )
+            .unwrap(),
+            action_observer_pending_actions: register_int_gauge_with_registry!(
+                "bridge_action_observer_pending_actions",
+                "Number of actions that action observer waits to be approved on chain",
+                registry,
+            )
+            .unwrap(),
+            shadow_comparisons: register_int_counter_vec_with_registry!(
+                "bridge_shadow_comparisons",
+                "Total number of actions that a node in shadow mode would have executed, by whether the chain agrees",
+                &["outcome"],
+                registry,
+            )
+            .unwrap(),
+            alerts: register_int_counter_vec_with_registry!(
+                "bridge_alerts",
+                "Total number of alerts for the alerting webhook, by condition and whether they were sent, failed to send or dropped",
+                &["condition", "result"],
+                registry,
+            )
+            .unwrap(),
             gas_coin_balance: register_int_gauge_with_registry!(
                 "bridge_gas_coin_balance",
                 "Current balance of gas coin, in mist",
                 registry,
             )
             .unwrap(),
+            gas_top_ups: register_int_counter_with_registry!(
+                "bridge_gas_top_ups",
+                "Total number of gas coin top-ups from the funding address",
+                registry,
+            )
+            .unwrap(),
+            gas_top_up_failures: register_int_counter_with_registry!(
+                "bridge_gas_top_up_failures",
+                "Total number of failed gas coin top-ups",
+                registry,
+            )
+            .unwrap(),
+            url_mismatch: register_int_gauge_with_registry!(
+                "bridge_url_mismatch",
+                "1 if the url registered on chain for this node differs from its external url",
+                registry,
+            )
+            .unwrap(),
+            storage_integrity_violations: register_int_gauge_with_registry!(
+                "bridge_storage_integrity_violations",
+                "Number of broken invariants between the client tables found when the node started",
+                registry,
+            )
+            .unwrap(),
+            storage_cursor_updates: register_int_counter_vec_with_registry!(
+                "bridge_storage_cursor_updates",
+                "Number of updates of the syncer cursors, by whether they were written, buffered, or replaced a buffered cursor",
+                &["outcome"],
+                registry,
+            )
+            .unwrap(),
+            storage_cursor_flushes: register_int_counter_vec_with_registry!(
+                "bridge_storage_cursor_flushes",
+                "Number of writes of the buffered syncer cursors, by what triggered them",
+                &["reason"],
+                registry,
+            )
+            .unwrap(),
+            storage_cursors_flushed: register_int_counter_with_registry!(
+                "bridge_storage_cursors_flushed",
+                "Number of buffered syncer cursors written",
+                registry,
+            )
+            .unwrap(),
+            event_stream_consumers: register_int_gauge_with_registry!(
+                "bridge_event_stream_consumers",
+                "Number of consumers connected to the event stream",
+                registry,
+            )
+            .unwrap(),
+            event_stream_dropped_events: register_int_counter_with_registry!(
+                "bridge_event_stream_dropped_events",
+                "Total number of event stream events dropped because the buffer of a consumer was full",
+                registry,
+            )
+            .unwrap(),
+            certified_actions_published: register_int_counter_with_registry!(
+                "bridge_certified_actions_published",
+                "Total number of certified actions published to the subscribers of certified actions",
+                registry,
+            )
+            .unwrap(),
+            certified_actions_lagged: register_int_counter_with_registry!(
+                "bridge_certified_actions_lagged",
+                "Total number of certified actions missed by subscribers that fell behind",
+                registry,
+            )
+            .unwrap(),
+            telemetry_failures: register_int_counter_with_registry!(
+                "bridge_telemetry_failures",
+                "Total number of telemetry summaries that could not be sent",
+                registry,
+            )
+            .unwrap(),
             eth_rpc_queries: register_int_counter_vec_with_registry!(
                 "bridge_eth_rpc_queries",
                 "Total number of queries issued to eth provider, by request type",
@@ -340,6 +1249,49 @@ impl BridgeMetrics {
                 registry,
             )
             .unwrap(),
+            eth_base_fee_wei: register_gauge_vec_with_registry!(
+                "bridge_eth_base_fee_wei",
+                "Base fee of the next Eth block as of the last fee history sample, by chain id",
+                &["chain_id"],
+                registry,
+            )
+            .unwrap(),
+            eth_base_fee_ewma_wei: register_gauge_vec_with_registry!(
+                "bridge_eth_base_fee_ewma_wei",
+                "Exponentially weighted moving average of the base fees of the sampled Eth blocks, by chain id",
+                &["chain_id"],
+                registry,
+            )
+            .unwrap(),
+            eth_priority_fee_wei: register_gauge_vec_with_registry!(
+                "bridge_eth_priority_fee_wei",
+                "Median over the sampled Eth blocks of the priority fees paid at each percentile, by chain id",
+                &["chain_id", "percentile"],
+                registry,
+            )
+            .unwrap(),
+            eth_fee_sampling_paused: register_int_gauge_with_registry!(
+                "bridge_eth_fee_sampling_paused",
+                "1 while Eth fee history sampling is paused because the provider rate limits it",
+                registry,
+            )
+            .unwrap(),
+            action_finality_delay: register_histogram_vec_with_registry!(
+                "bridge_action_finality_delay_seconds",
+                "Time from the block or checkpoint of a token transfer until the orchestrator received it, i.e. finality plus polling delay, by direction",
+                &["direction"],
+                ACTION_LATENCY_SEC_BUCKETS.to_vec(),
+                registry,
+            )
+            .unwrap(),
+            action_processing_latency: register_histogram_vec_with_registry!(
+                "bridge_action_processing_latency_seconds",
+                "Time from the orchestrator receiving a token transfer until it was executed on Sui, by direction",
+                &["direction"],
+                ACTION_LATENCY_SEC_BUCKETS.to_vec(),
+                registry,
+            )
+            .unwrap(),
             last_synced_sui_checkpoint: register_int_gauge_with_registry!(
                 "last_synced_sui_checkpoint",
                 "The latest sui checkpoint that indexer synced",
@@ -358,6 +1310,188 @@ impl BridgeMetrics {
                 registry,
             )
             .unwrap(),
+            auth_agg_request_failures: register_int_counter_vec_with_registry!(
+                "bridge_auth_agg_request_failures",
+                "Total number of failed signature requests to bridge authorities, by authority and error category",
+                &["authority", "category"],
+                registry,
+            )
+            .unwrap(),
+            noncanonical_signatures: register_int_counter_vec_with_registry!(
+                "bridge_noncanonical_signatures",
+                "Total number of signatures that were not in the canonical low-s form, by authority and whether they were normalized or rejected",
+                &["authority", "outcome"],
+                registry,
+            )
+            .unwrap(),
+            committee_total_stake: register_int_gauge_with_registry!(
+                "bridge_committee_total_stake",
+                "Total stake of the bridge committee, including blocklisted members",
+                registry,
+            )
+            .unwrap(),
+            committee_blocklisted_stake: register_int_gauge_with_registry!(
+                "bridge_committee_blocklisted_stake",
+                "Stake of the blocklisted members of the bridge committee",
+                registry,
+            )
+            .unwrap(),
+            committee_reachable_stake: register_int_gauge_with_registry!(
+                "bridge_committee_reachable_stake",
+                "Stake of the non-blocklisted committee members that answered the last probe of this node",
+                registry,
+            )
+            .unwrap(),
+            committee_validity_threshold: register_int_gauge_with_registry!(
+                "bridge_committee_validity_threshold",
+                "Stake of the signatures required to certify a token transfer",
+                registry,
+            )
+            .unwrap(),
+            committee_last_update_epoch: register_int_gauge_with_registry!(
+                "bridge_committee_last_update_epoch",
+                "Sui epoch in which the bridge committee was last updated, i.e. the committee version",
+                registry,
+            )
+            .unwrap(),
+            committee_members_with_invalid_url: register_int_gauge_with_registry!(
+                "bridge_committee_members_with_invalid_url",
+                "Number of committee members that are unreachable because their registered url is invalid",
+                registry,
+            )
+            .unwrap(),
+            committee_anomalies: register_int_gauge_with_registry!(
+                "bridge_committee_anomalies",
+                "Number of committee members that registered the same url as another member",
+                registry,
+            )
+            .unwrap(),
+            committee_dns_changes: register_int_counter_with_registry!(
+                "bridge_committee_dns_changes",
+                "Number of times the host of a committee member resolved to other addresses, and its connections were dropped",
+                registry,
+            )
+            .unwrap(),
+            committee_connections: register_int_counter_vec_with_registry!(
+                "bridge_committee_connections",
+                "Total number of requests to committee members, by authority and whether they were sent over a new or a reused connection",
+                &["authority", "kind"],
+                registry,
+            )
+            .unwrap(),
+            committee_hedges_issued: register_int_counter_vec_with_registry!(
+                "bridge_committee_hedges_issued",
+                "Total number of signing requests sent again to a committee member that had not responded within its hedging delay, by authority",
+                &["authority"],
+                registry,
+            )
+            .unwrap(),
+            committee_hedges_won: register_int_counter_vec_with_registry!(
+                "bridge_committee_hedges_won",
+                "Total number of hedged signing requests that responded before the first request, by authority",
+                &["authority"],
+                registry,
+            )
+            .unwrap(),
+            reconciliation_mismatches: register_int_counter_vec_with_registry!(
+                "bridge_reconciliation_mismatches",
+                "Total number of token transfers whose local execution record disagrees with the chain, by kind",
+                &["kind"],
+                registry,
+            )
+            .unwrap(),
+            timestamp_anomalies: register_int_counter_vec_with_registry!(
+                "bridge_timestamp_anomalies",
+                "Total number of chain timestamps that are too far in the future or in the past, by source and kind",
+                &["source", "kind"],
+                registry,
+            )
+            .unwrap(),
+            pruned_executed_actions: register_int_counter_with_registry!(
+                "bridge_pruned_executed_actions",
+                "Total number of executed token transfers deleted from the storage by pruning",
+                registry,
+            )
+            .unwrap(),
+            archived_executed_actions: register_int_counter_with_registry!(
+                "bridge_archived_executed_actions",
+                "Total number of executed token transfers uploaded to the archive before pruning",
+                registry,
+            )
+            .unwrap(),
+            archive_upload_failures: register_int_counter_with_registry!(
+                "bridge_archive_upload_failures",
+                "Total number of batches of executed token transfers that failed to be archived, and were kept in the storage",
+                registry,
+            )
+            .unwrap(),
+            storage_maintenance_duration_seconds: register_histogram_vec_with_registry!(
+                "bridge_storage_maintenance_duration_seconds",
+                "Time taken by the compactions and the backups of the storage, by operation",
+                &["operation"],
+                FINE_GRAINED_LATENCY_SEC_BUCKETS.to_vec(),
+                registry,
+            )
+            .unwrap(),
+            storage_maintenance_failures: register_int_counter_vec_with_registry!(
+                "bridge_storage_maintenance_failures",
+                "Total number of compactions and backups of the storage that failed, by operation",
+                &["operation"],
+                registry,
+            )
+            .unwrap(),
+            storage_table_size_bytes: register_int_gauge_vec_with_registry!(
+                "bridge_storage_table_size_bytes",
+                "Size of the files of each table of the storage on disk, as of the last compaction",
+                &["db", "table"],
+                registry,
+            )
+            .unwrap(),
+            storage_compaction_reclaimed_bytes: register_int_counter_with_registry!(
+                "bridge_storage_compaction_reclaimed_bytes",
+                "Total number of bytes on disk reclaimed by the compactions of the storage",
+                registry,
+            )
+            .unwrap(),
+            storage_backup_size_bytes: register_int_gauge_with_registry!(
+                "bridge_storage_backup_size_bytes",
+                "Size of the files of the last backup of the storage, including the ones hard-linked to the db",
+                registry,
+            )
+            .unwrap(),
+            own_signature_included_total: register_int_counter_with_registry!(
+                "bridge_own_signature_included_total",
+                "Total number of token transfers approved on Sui with a certificate including the signature of this node",
+                registry,
+            )
+            .unwrap(),
+            own_signature_missing_total: register_int_counter_with_registry!(
+                "bridge_own_signature_missing_total",
+                "Total number of token transfers approved on Sui with a certificate missing the signature of this node",
+                registry,
+            )
+            .unwrap(),
+            own_signature_inclusion_rate: register_gauge_with_registry!(
+                "bridge_own_signature_inclusion_rate",
+                "Share of the latest token transfers approved on Sui with a certificate including the signature of this node",
+                registry,
+            )
+            .unwrap(),
+            runtime_workers: register_int_gauge_vec_with_registry!(
+                "bridge_runtime_workers",
+                "Number of worker threads of the tokio runtimes of the node, by runtime",
+                &["runtime"],
+                registry,
+            )
+            .unwrap(),
+            runtime_scheduling_delay_seconds: register_histogram_vec_with_registry!(
+                "bridge_runtime_scheduling_delay_seconds",
+                "Time a task spawned on the tokio runtimes of the node waits to be polled, by runtime",
+                &["runtime"],
+                SCHEDULING_DELAY_SEC_BUCKETS.to_vec(),
+                registry,
+            )
+            .unwrap(),
             signer_with_cache_hit: register_int_counter_vec_with_registry!(
                 "bridge_signer_with_cache_hit",
                 "Total number of hit in signer's cache, by verifier type",
@@ -372,11 +1506,532 @@ impl BridgeMetrics {
                 registry,
             )
             .unwrap(),
+            unfinalized_receipt_cache_hit: register_int_counter_with_registry!(
+                "bridge_unfinalized_receipt_cache_hit",
+                "Number of Eth signing requests verified with the receipt fetched by an earlier request, made before the transaction was final",
+                registry,
+            )
+            .unwrap(),
+            unfinalized_receipt_cache_reorgs: register_int_counter_with_registry!(
+                "bridge_unfinalized_receipt_cache_reorgs",
+                "Number of cached receipts of unfinalized Eth transactions dropped because their block was reorged out",
+                registry,
+            )
+            .unwrap(),
+            signing_nonce_too_far_ahead: register_int_counter_vec_with_registry!(
+                "bridge_signing_nonce_too_far_ahead",
+                "Number of token transfer signing requests refused because their nonce is too far ahead of the known nonces of the source chain, by source chain",
+                &["source"],
+                registry,
+            )
+            .unwrap(),
+            verification_queue_depth: register_int_gauge_vec_with_registry!(
+                "bridge_verification_queue_depth",
+                "Number of signing requests waiting for a verification slot, by chain",
+                &["chain"],
+                registry,
+            )
+            .unwrap(),
+            verifications_inflight: register_int_gauge_vec_with_registry!(
+                "bridge_verifications_inflight",
+                "Number of signing requests being verified, by chain",
+                &["chain"],
+                registry,
+            )
+            .unwrap(),
+            verification_queue_wait: register_histogram_vec_with_registry!(
+                "bridge_verification_queue_wait",
+                "Time signing requests waited for a verification slot, by chain",
+                &["chain"],
+                FINE_GRAINED_LATENCY_SEC_BUCKETS.to_vec(),
+                registry,
+            )
+            .unwrap(),
+            verification_queue_timeouts: register_int_counter_vec_with_registry!(
+                "bridge_verification_queue_timeouts",
+                "Total number of signing requests rejected as busy after waiting for a verification slot, by chain",
+                &["chain"],
+                registry,
+            )
+            .unwrap(),
+            remote_signer_latency: register_histogram_with_registry!(
+                "bridge_remote_signer_latency",
+                "Time to get a signature from the remote signer, retries included",
+                FINE_GRAINED_LATENCY_SEC_BUCKETS.to_vec(),
+                registry,
+            )
+            .unwrap(),
+            remote_signer_failures: register_int_counter_vec_with_registry!(
+                "bridge_remote_signer_failures",
+                "Total number of failed requests to the remote signer, by reason",
+                &["reason"],
+                registry,
+            )
+            .unwrap(),
+            remote_signer_circuit_open: register_int_gauge_with_registry!(
+                "bridge_remote_signer_circuit_open",
+                "Whether requests to the remote signer are refused after too many failures",
+                registry,
+            )
+            .unwrap(),
+            sui_client_cache_hits: register_int_counter_vec_with_registry!(
+                "bridge_sui_client_cache_hits",
+                "Total number of reads served by the sui client object cache, by object",
+                &["object"],
+                registry,
+            )
+            .unwrap(),
+            sui_client_cache_misses: register_int_counter_vec_with_registry!(
+                "bridge_sui_client_cache_misses",
+                "Total number of reads that missed the sui client object cache, by object",
+                &["object"],
+                registry,
+            )
+            .unwrap(),
+            sui_rpc_errors: register_int_counter_vec_with_registry!(
+                "bridge_sui_rpc_errors",
+                "Total number of failed sui rpc requests, by method and error classification",
+                &["method", "classification"],
+                registry,
+            )
+            .unwrap(),
+            sui_rpc_retries: register_int_counter_vec_with_registry!(
+                "bridge_sui_rpc_retries",
+                "Total number of retried sui rpc requests, by method",
+                &["method"],
+                registry,
+            )
+            .unwrap(),
+            sui_tx_submissions: register_int_counter_vec_with_registry!(
+                "bridge_sui_tx_submissions",
+                "Total number of sui transaction submissions, by fullnode and result",
+                &["fullnode", "result"],
+                registry,
+            )
+            .unwrap(),
+            transfer_notional_usd_total: register_counter_vec_with_registry!(
+                "bridge_transfer_notional_usd_total",
+                "Total USD notional value of observed token transfers, by route",
+                &["route"],
+                registry,
+            )
+            .unwrap(),
+            limit_utilization_ratio: register_gauge_vec_with_registry!(
+                "bridge_limit_utilization_ratio",
+                "USD notional value of token transfers in the last 24 hours over the transfer limit, by route",
+                &["route"],
+                registry,
+            )
+            .unwrap(),
+            route_paused: register_int_gauge_vec_with_registry!(
+                "bridge_route_paused",
+                "1 if the route is paused on chain by a zero transfer limit, 0 otherwise, by route",
+                &["route"],
+                registry,
+            )
+            .unwrap(),
+            route_deferred_actions: register_int_gauge_vec_with_registry!(
+                "bridge_route_deferred_actions",
+                "Number of certified actions whose execution is deferred until their route is no longer paused, by route",
+                &["route"],
+                registry,
+            )
+            .unwrap(),
+            approved_unclaimed: register_int_gauge_with_registry!(
+                "bridge_approved_unclaimed",
+                "Number of token transfers from Sui that are approved on Sui but not claimed on Eth yet",
+                registry,
+            )
+            .unwrap(),
+            approved_unclaimed_by_age: register_int_gauge_vec_with_registry!(
+                "bridge_approved_unclaimed_by_age",
+                "Number of token transfers from Sui that are approved but not claimed yet, by the age bucket of their approval, e.g. up to 1h",
+                &["age"],
+                registry,
+            )
+            .unwrap(),
+            transfer_notional_price_unavailable: register_int_counter_with_registry!(
+                "bridge_transfer_notional_price_unavailable",
+                "Total number of token transfers skipped in notional metrics because the token price was unavailable",
+                registry,
+            )
+            .unwrap(),
+            slo_signing_latency_p99: register_gauge_with_registry!(
+                "bridge_slo_signing_latency_p99_seconds",
+                "99th percentile latency of the signing requests served in the last 5 minutes. The window slides by 10 seconds and the gauge is updated every 15 seconds, 0 if no request was served in the window",
+                registry,
+            )
+            .unwrap(),
+            slo_signing_error_ratio: register_gauge_vec_with_registry!(
+                "bridge_slo_signing_error_ratio",
+                "Fraction of the signing requests served in the last 5m or 1h, by window, that failed with a server error. The 5m window slides by 10 seconds and the 1h window by 1 minute, the gauge is updated every 15 seconds, 0 if no request was served in the window",
+                &["window"],
+                registry,
+            )
+            .unwrap(),
+            slo_executor_queue_wait_p95: register_gauge_with_registry!(
+                "bridge_slo_executor_queue_wait_p95_seconds",
+                "95th percentile time the actions taken from the executor signing queue in the last 5 minutes waited in the queue. The window slides by 10 seconds and the gauge is updated every 15 seconds, 0 if no action was taken in the window",
+                registry,
+            )
+            .unwrap(),
+            error_catalog: Arc::new(ErrorCatalog::new(ERROR_CATALOG_CAPACITY_PER_COUNTER)),
+            committee_scores: Arc::new(AuthorityScoreboard::default()),
+            registered_url: Arc::new(RegisteredUrlStatus::default()),
+            slo: Arc::new(SloTracker::default()),
+            action_latency: Arc::new(ActionLatencyTracker::default()),
+            executor_state: Arc::new(ExecutorState::default()),
+            aggregation_status: Arc::new(AggregationStatus::default()),
+            requesters: Arc::new(RequesterTracker::default()),
+            chain_health: Arc::new(ChainHealth::default()),
+            clock_drift: Arc::new(ClockDrift::default()),
+            readiness: Arc::new(BridgeReadiness::default()),
+        };
+        Self {
+            inner: Arc::new(inner),
         }
     }
 
-    pub fn new_for_testing() -> Self {
+    /// Metrics registered in a new registry, so that tests never register a metric twice.
+    pub fn for_testing() -> Self {
+        Self::new(&Registry::new())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug)]
+    struct ParsedFamily {
+        metric_type: String,
+        help: Option<String>,
+        samples: Vec<ParsedSample>,
+    }
+
+    #[derive(Debug)]
+    struct ParsedSample {
+        suffix: String,
+        labels: Vec<(String, String)>,
+        value: f64,
+    }
+
+    impl ParsedFamily {
+        fn values(&self, suffix: &str) -> Vec<f64> {
+            self.samples
+                .iter()
+                .filter(|sample| sample.suffix == suffix)
+                .map(|sample| sample.value)
+                .collect()
+        }
+    }
+
+    // Parses the OpenMetrics text format, failing on what doesn't conform: samples must
+    // follow the metadata of their family, with the suffixes of its type, families can't
+    // be split, and the exposition ends with `# EOF`.
+    fn parse_openmetrics(text: &str) -> Result<BTreeMap<String, ParsedFamily>, String> {
+        let body = text.strip_suffix("# EOF\n").ok_or("missing # EOF")?;
+        let mut families = BTreeMap::new();
+        let mut current: Option<String> = None;
+        for line in body.lines() {
+            if let Some(metadata) = line.strip_prefix("# TYPE ") {
+                let (name, metric_type) = metadata.split_once(' ').ok_or(line)?;
+                if families.contains_key(name) {
+                    return Err(format!("family {} is split", name));
+                }
+                families.insert(
+                    name.to_string(),
+                    ParsedFamily {
+                        metric_type: metric_type.to_string(),
+                        help: None,
+                        samples: vec![],
+                    },
+                );
+                current = Some(name.to_string());
+            } else if let Some(metadata) = line.strip_prefix("# HELP ") {
+                let (name, help) = metadata.split_once(' ').ok_or(line)?;
+                if current.as_deref() != Some(name) {
+                    return Err(format!("HELP of {} outside of its family", name));
+                }
+                families.get_mut(name).unwrap().help = Some(help.to_string());
+            } else if line.starts_with('#') {
+                return Err(format!("unexpected comment {}", line));
+            } else {
+                let name = current
+                    .as_deref()
+                    .ok_or(format!("sample {} without family", line))?;
+                let family = families.get_mut(name).unwrap();
+                let (sample_name, labels, value) = parse_sample(line)?;
+                let suffix = sample_name
+                    .strip_prefix(name)
+                    .ok_or(format!("sample {} outside of its family", line))?;
+                let suffixes: &[&str] = match family.metric_type.as_str() {
+                    "counter" => &["_total", "_created"],
+                    "gauge" | "unknown" => &[""],
+                    "histogram" => &["_bucket", "_count", "_sum", "_created"],
+                    "summary" => &["", "_count", "_sum", "_created"],
+                    metric_type => return Err(format!("unknown type {}", metric_type)),
+                };
+                if !suffixes.contains(&suffix) {
+                    return Err(format!("unexpected sample {} in {}", line, name));
+                }
+                family.samples.push(ParsedSample {
+                    suffix: suffix.to_string(),
+                    labels,
+                    value,
+                });
+            }
+        }
+        Ok(families)
+    }
+
+    fn parse_sample(line: &str) -> Result<(&str, Vec<(String, String)>, f64), String> {
+        let (name, labels, rest) = match line.split_once('{') {
+            Some((name, rest)) => {
+                let (labels, rest) = rest.split_once('}').ok_or(line)?;
+                let labels = labels
+                    .split(',')
+                    .map(|label| {
+                        let (name, value) = label.split_once('=').ok_or(line)?;
+                        let value = value
+                            .strip_prefix('"')
+                            .and_then(|value| value.strip_suffix('"'))
+                            .ok_or(line)?;
+                        Ok((name.to_string(), value.to_string()))
+                    })
+                    .collect::<Result<Vec<_>, String>>()?;
+                (name, labels, rest)
+            }
+            None => {
+                let (name, rest) = line.split_once(' ').ok_or(line)?;
+                (name, vec![], rest)
+            }
+        };
+        let value = rest
+            .trim_start()
+            .split(' ')
+            .next()
+            .and_then(|value| value.parse::<f64>().ok())
+            .ok_or(line)?;
+        Ok((name, labels, value))
+    }
+
+    #[test]
+    fn test_openmetrics_encoder() {
         let registry = Registry::new();
-        Self::new(&registry)
+        let requests = register_int_counter_with_registry!(
+            "bridge_test_requests_total",
+            "Requests\nserved",
+            registry
+        )
+        .unwrap();
+        let errors = register_int_counter_vec_with_registry!(
+            "bridge_test_errors",
+            "Errors",
+            &["type"],
+            registry
+        )
+        .unwrap();
+        let height =
+            register_int_gauge_with_registry!("bridge_test_height", "Height", registry).unwrap();
+        let latency = register_histogram_vec_with_registry!(
+            "bridge_test_latency",
+            "Latency",
+            &["chain"],
+            vec![0.5, 1.0, 10.0],
+            registry
+        )
+        .unwrap();
+        requests.inc_by(3);
+        errors.with_label_values(&["rpc"]).inc();
+        height.set(42);
+        for value in [0.25, 0.75, 5.0, 20.0] {
+            latency.with_label_values(&["eth"]).observe(value);
+        }
+
+        let created = UNIX_EPOCH + Duration::from_secs(1_700_000_000);
+        let mut buf = vec![];
+        OpenMetricsEncoder::new(created)
+            .encode(&registry.gather(), &mut buf)
+            .unwrap();
+        let families = parse_openmetrics(&String::from_utf8(buf).unwrap()).unwrap();
+        assert_eq!(families.len(), 4);
+
+        // Counters are named without `_total`, which their samples get
+        let requests = &families["bridge_test_requests"];
+        assert_eq!(requests.metric_type, "counter");
+        assert_eq!(requests.help.as_deref(), Some("Requests\\nserved"));
+        assert_eq!(requests.values("_total"), vec![3.0]);
+        assert_eq!(requests.values("_created"), vec![1_700_000_000.0]);
+        let errors = &families["bridge_test_errors"];
+        assert_eq!(errors.metric_type, "counter");
+        assert_eq!(errors.values("_total"), vec![1.0]);
+        assert_eq!(errors.values("_created"), vec![1_700_000_000.0]);
+        assert!(errors
+            .samples
+            .iter()
+            .all(|sample| sample.labels == vec![("type".to_string(), "rpc".to_string())]));
+
+        let height = &families["bridge_test_height"];
+        assert_eq!(height.metric_type, "gauge");
+        assert_eq!(height.values(""), vec![42.0]);
+
+        let latency = &families["bridge_test_latency"];
+        assert_eq!(latency.metric_type, "histogram");
+        assert_eq!(latency.values("_count"), vec![4.0]);
+        assert_eq!(latency.values("_sum"), vec![26.0]);
+        assert_eq!(latency.values("_created"), vec![1_700_000_000.0]);
+        let buckets = latency
+            .samples
+            .iter()
+            .filter(|sample| sample.suffix == "_bucket")
+            .map(|sample| {
+                // `le` comes after the labels of the histogram
+                assert_eq!(sample.labels.len(), 2);
+                assert_eq!(sample.labels[0], ("chain".to_string(), "eth".to_string()));
+                assert_eq!(sample.labels[1].0, "le");
+                (sample.labels[1].1.as_str(), sample.value)
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            buckets,
+            vec![("0.5", 1.0), ("1.0", 2.0), ("10.0", 3.0), ("+Inf", 4.0)]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_metrics_content_negotiation() {
+        let registry_service = RegistryService::new(Registry::new());
+        register_int_gauge_with_registry!(
+            "bridge_test_height",
+            "Height",
+            registry_service.default_registry()
+        )
+        .unwrap();
+
+        // Sent by Prometheus
+        let openmetrics_accept = "application/openmetrics-text;version=1.0.0,\
+            application/openmetrics-text;version=0.0.1;q=0.75,\
+            text/plain;version=0.0.4;q=0.5,*/*;q=0.1";
+        for (accept, content_type) in [
+            (Some(openmetrics_accept), OPENMETRICS_CONTENT_TYPE),
+            (Some("text/plain;version=0.0.4"), prometheus::TEXT_FORMAT),
+            (
+                Some("application/openmetrics-text;q=0,text/plain"),
+                prometheus::TEXT_FORMAT,
+            ),
+            (None, prometheus::TEXT_FORMAT),
+        ] {
+            let mut headers = HeaderMap::new();
+            if let Some(accept) = accept {
+                headers.insert(ACCEPT, accept.parse().unwrap());
+            }
+            let response = metrics(Extension(registry_service.clone()), headers).await;
+            assert_eq!(response.status(), StatusCode::OK);
+            assert_eq!(response.headers()[CONTENT_TYPE], content_type);
+            let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+                .await
+                .unwrap();
+            let body = String::from_utf8(body.to_vec()).unwrap();
+            assert!(body.contains("bridge_test_height 0"));
+            assert_eq!(
+                parse_openmetrics(&body).is_ok(),
+                content_type == OPENMETRICS_CONTENT_TYPE
+            );
+        }
+    }
+
+    #[test]
+    fn test_metrics_for_testing_are_independent() {
+        // Tests running in parallel create their metrics at the same time
+        let handles = (0..8)
+            .map(|_| std::thread::spawn(BridgeMetrics::for_testing))
+            .collect::<Vec<_>>();
+        let metrics = handles
+            .into_iter()
+            .map(|handle| handle.join().unwrap())
+            .collect::<Vec<_>>();
+        metrics[0].err_build_sui_transaction.inc();
+        assert_eq!(metrics[0].err_build_sui_transaction.get(), 1);
+        assert_eq!(metrics[1].err_build_sui_transaction.get(), 0);
+
+        // Clones share the metrics
+        let clone = metrics[1].clone();
+        clone.err_build_sui_transaction.inc();
+        clone
+            .error_catalog
+            .record("err_foo", "error".to_string(), vec![]);
+        assert_eq!(metrics[1].err_build_sui_transaction.get(), 1);
+        assert_eq!(metrics[1].error_catalog.get("err_foo").len(), 1);
+    }
+
+    #[test]
+    fn test_metrics_schema_snapshot() {
+        // Regenerate after adding, renaming or relabeling a metric with
+        // `UPDATE_SNAPSHOTS=1 cargo test -p sui-bridge test_metrics_schema_snapshot`
+        let path = std::path::PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+            .join("testdata")
+            .join("metrics_schema.txt");
+        let schema = BridgeMetrics::schema();
+        let snapshot = schema
+            .iter()
+            .map(|metric| metric.snapshot_line() + "\n")
+            .collect::<String>();
+        if std::env::var("UPDATE_SNAPSHOTS").is_ok() {
+            std::fs::write(&path, &snapshot).unwrap();
+        }
+        assert_eq!(
+            snapshot,
+            std::fs::read_to_string(&path).unwrap(),
+            "The metrics changed, update {:?} if it's intended",
+            path
+        );
+
+        for metric in &schema {
+            assert!(
+                !metric.help.trim().is_empty(),
+                "{} has no help",
+                metric.name
+            );
+        }
+        let mut names = schema.iter().map(|metric| &metric.name).collect::<Vec<_>>();
+        names.dedup();
+        assert_eq!(names.len(), schema.len());
+    }
+
+    #[test]
+    fn test_error_catalog_rolls_off_old_entries() {
+        let catalog = ErrorCatalog::new(2);
+        for i in 0..3 {
+            catalog.record("err_foo", format!("error {i}"), vec![format!("digest {i}")]);
+        }
+        catalog.record("err_bar", "error".to_string(), vec![]);
+
+        let entries = catalog.get("err_foo");
+        assert_eq!(
+            entries
+                .iter()
+                .map(|e| e.message.as_str())
+                .collect::<Vec<_>>(),
+            vec!["error 1", "error 2"]
+        );
+        assert_eq!(entries[1].digests, vec!["digest 2".to_string()]);
+        assert_eq!(catalog.get("err_bar").len(), 1);
+        assert!(catalog.get("err_baz").is_empty());
+        assert_eq!(
+            catalog.snapshot().keys().collect::<Vec<_>>(),
+            vec!["err_bar", "err_foo"]
+        );
+
+        // Messages are bounded in length
+        catalog.record(
+            "err_bar",
+            "x".repeat(ERROR_CATALOG_MAX_MESSAGE_LEN * 2),
+            vec![],
+        );
+        assert_eq!(
+            catalog.get("err_bar")[1].message.len(),
+            ERROR_CATALOG_MAX_MESSAGE_LEN
+        );
     }
 }
@@ -3,15 +3,22 @@
 
 //! `BridgeMonitor` receives all `SuiBridgeEvent` and handles them accordingly.
 
+use crate::alerts::{AlertCondition, AlertSink};
 use crate::client::bridge_authority_aggregator::BridgeAuthorityAggregator;
+use crate::client::committee_probe::update_committee_stake_metrics;
 use crate::crypto::BridgeAuthorityPublicKeyBytes;
 use crate::events::{BlocklistValidatorEvent, CommitteeMemberUrlUpdateEvent};
-use crate::events::{EmergencyOpEvent, SuiBridgeEvent};
+use crate::events::{EmergencyOpEvent, SuiBridgeEvent, UpdateRouteLimitEvent};
+use crate::notional::BridgeRoute;
+use crate::notional::NotionalTracker;
 use crate::retry_with_max_elapsed_time;
+use crate::route_pause::{paused_routes, RoutePauses};
+use crate::signature_inclusion::SignatureInclusion;
 use crate::sui_client::{SuiClient, SuiClientInner};
+use crate::transfer_minimums::TransferMinimums;
 use crate::types::{BridgeCommittee, IsBridgePaused};
 use arc_swap::ArcSwap;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::sync::Arc;
 use sui_types::TypeTag;
 use tokio::time::Duration;
@@ -25,6 +32,11 @@ pub struct BridgeMonitor<C> {
     bridge_auth_agg: Arc<ArcSwap<BridgeAuthorityAggregator>>,
     bridge_paused_watch_tx: tokio::sync::watch::Sender<IsBridgePaused>,
     sui_token_type_tags: Arc<ArcSwap<HashMap<u8, TypeTag>>>,
+    transfer_minimums: Arc<TransferMinimums>,
+    notional_tracker: Arc<NotionalTracker>,
+    alerts: Option<Arc<AlertSink>>,
+    route_pauses: Option<Arc<RoutePauses>>,
+    signature_inclusion: Option<SignatureInclusion>,
 }
 
 impl<C> BridgeMonitor<C>
@@ -37,6 +49,8 @@ where
         bridge_auth_agg: Arc<ArcSwap<BridgeAuthorityAggregator>>,
         bridge_paused_watch_tx: tokio::sync::watch::Sender<IsBridgePaused>,
         sui_token_type_tags: Arc<ArcSwap<HashMap<u8, TypeTag>>>,
+        transfer_minimums: Arc<TransferMinimums>,
+        notional_tracker: Arc<NotionalTracker>,
     ) -> Self {
         Self {
             sui_client,
@@ -44,9 +58,32 @@ where
             bridge_auth_agg,
             bridge_paused_watch_tx,
             sui_token_type_tags,
+            transfer_minimums,
+            notional_tracker,
+            alerts: None,
+            route_pauses: None,
+            signature_inclusion: None,
         }
     }
 
+    /// Raises an alert when the bridge is paused.
+    pub fn with_alert_sink(mut self, alerts: Arc<AlertSink>) -> Self {
+        self.alerts = Some(alerts);
+        self
+    }
+
+    /// Reloads the paused routes when a transfer limit changes.
+    pub fn with_route_pauses(mut self, route_pauses: Arc<RoutePauses>) -> Self {
+        self.route_pauses = Some(route_pauses);
+        self
+    }
+
+    /// Checks whether the signature of this node is in the approved certificates.
+    pub fn with_signature_inclusion(mut self, signature_inclusion: SignatureInclusion) -> Self {
+        self.signature_inclusion = Some(signature_inclusion);
+        self
+    }
+
     pub async fn run(self) {
         tracing::info!("Starting BridgeMonitor");
         let Self {
@@ -55,13 +92,23 @@ where
             bridge_auth_agg,
             bridge_paused_watch_tx,
             sui_token_type_tags,
+            transfer_minimums,
+            notional_tracker,
+            alerts,
+            route_pauses,
+            signature_inclusion,
         } = self;
         let mut latest_token_config = (*sui_token_type_tags.load().clone()).clone();
 
         while let Some(events) = monitor_rx.recv().await {
+            sui_client.invalidate_cache_for_event(&events);
             match events {
                 SuiBridgeEvent::SuiToEthTokenBridgeV1(_) => (),
-                SuiBridgeEvent::TokenTransferApproved(_) => (),
+                SuiBridgeEvent::TokenTransferApproved(event) => {
+                    if let Some(signature_inclusion) = &signature_inclusion {
+                        signature_inclusion.observe(event);
+                    }
+                }
                 SuiBridgeEvent::TokenTransferClaimed(_) => (),
                 SuiBridgeEvent::TokenTransferAlreadyApproved(_) => (),
                 SuiBridgeEvent::TokenTransferAlreadyClaimed(_) => (),
@@ -77,6 +124,14 @@ where
                         Duration::from_secs(10),
                     )
                     .await;
+                    if is_paused {
+                        if let Some(alerts) = &alerts {
+                            alerts.raise(
+                                AlertCondition::BridgePaused,
+                                "Bridge was paused by an emergency action".to_string(),
+                            );
+                        }
+                    }
                     bridge_paused_watch_tx
                         .send(is_paused)
                         .expect("Bridge pause status watch channel should not be closed");
@@ -93,9 +148,27 @@ where
                         Duration::from_secs(10),
                     )
                     .await;
-                    bridge_auth_agg.store(Arc::new(BridgeAuthorityAggregator::new(Arc::new(
-                        new_committee,
-                    ))));
+                    let (metrics, https_required, connection_options, hedging) = {
+                        let auth_agg = bridge_auth_agg.load();
+                        (
+                            auth_agg.metrics.clone(),
+                            auth_agg.https_required,
+                            auth_agg.connection_options.clone(),
+                            auth_agg.hedging.clone(),
+                        )
+                    };
+                    update_committee_stake_metrics(&metrics, &new_committee);
+                    let mut new_auth_agg = BridgeAuthorityAggregator::new_with_https_required(
+                        Arc::new(new_committee),
+                        metrics.clone(),
+                        https_required,
+                    )
+                    .with_connection_options(connection_options);
+                    if let Some(hedging) = hedging {
+                        new_auth_agg = new_auth_agg.with_request_hedging(hedging);
+                    }
+                    bridge_auth_agg.store(Arc::new(new_auth_agg));
+                    metrics.registered_url.request_refresh();
                     info!("Committee updated with CommitteeMemberUrlUpdateEvent");
                 }
 
@@ -107,9 +180,27 @@ where
                         Duration::from_secs(10),
                     )
                     .await;
-                    bridge_auth_agg.store(Arc::new(BridgeAuthorityAggregator::new(Arc::new(
-                        new_committee,
-                    ))));
+                    let (metrics, https_required, connection_options, hedging) = {
+                        let auth_agg = bridge_auth_agg.load();
+                        (
+                            auth_agg.metrics.clone(),
+                            auth_agg.https_required,
+                            auth_agg.connection_options.clone(),
+                            auth_agg.hedging.clone(),
+                        )
+                    };
+                    update_committee_stake_metrics(&metrics, &new_committee);
+                    let mut new_auth_agg = BridgeAuthorityAggregator::new_with_https_required(
+                        Arc::new(new_committee),
+                        metrics.clone(),
+                        https_required,
+                    )
+                    .with_connection_options(connection_options);
+                    if let Some(hedging) = hedging {
+                        new_auth_agg = new_auth_agg.with_request_hedging(hedging);
+                    }
+                    bridge_auth_agg.store(Arc::new(new_auth_agg));
+                    metrics.registered_url.request_refresh();
                     info!("Committee updated with BlocklistValidatorEvent");
                 }
 
@@ -126,9 +217,28 @@ where
                         // invariant
                         assert_eq!(event.type_name, latest_token_config[&event.token_id]);
                     }
+                    transfer_minimums.request_refresh();
+                    notional_tracker.request_refresh();
+                }
+
+                SuiBridgeEvent::UpdateTokenPriceEvent(_) => {
+                    transfer_minimums.request_refresh();
+                    notional_tracker.request_refresh();
                 }
 
-                SuiBridgeEvent::UpdateTokenPriceEvent(_) => (),
+                SuiBridgeEvent::UpdateRouteLimitEvent(event) => {
+                    info!("Received UpdateRouteLimitEvent: {:?}", event);
+                    notional_tracker.request_refresh();
+                    if let Some(route_pauses) = &route_pauses {
+                        let paused = get_latest_paused_routes_with_limit_event(
+                            sui_client.clone(),
+                            event,
+                            Duration::from_secs(10),
+                        )
+                        .await;
+                        route_pauses.update(paused);
+                    }
+                }
             }
         }
 
@@ -143,6 +253,8 @@ async fn get_latest_bridge_committee_with_url_update_event<C: SuiClientInner>(
 ) -> BridgeCommittee {
     let mut remaining_retry_times = REFRESH_BRIDGE_RETRY_TIMES;
     loop {
+        // Retries must not be served a stale committee from the cache
+        sui_client.invalidate_committee();
         let Ok(Ok(committee)) = retry_with_max_elapsed_time!(
             sui_client.get_bridge_committee(),
             Duration::from_secs(600)
@@ -186,6 +298,8 @@ async fn get_latest_bridge_committee_with_blocklist_event<C: SuiClientInner>(
 ) -> BridgeCommittee {
     let mut remaining_retry_times = REFRESH_BRIDGE_RETRY_TIMES;
     loop {
+        // Retries must not be served a stale committee from the cache
+        sui_client.invalidate_committee();
         let Ok(Ok(committee)) = retry_with_max_elapsed_time!(
             sui_client.get_bridge_committee(),
             Duration::from_secs(600)
@@ -239,6 +353,8 @@ async fn get_latest_bridge_pause_status_with_emergency_event<C: SuiClientInner>(
 ) -> IsBridgePaused {
     let mut remaining_retry_times = REFRESH_BRIDGE_RETRY_TIMES;
     loop {
+        // Retries must not be served a stale summary from the cache
+        sui_client.invalidate_bridge_summary();
         let Ok(Ok(summary)) =
             retry_with_max_elapsed_time!(sui_client.get_bridge_summary(), Duration::from_secs(600))
         else {
@@ -264,12 +380,54 @@ async fn get_latest_bridge_pause_status_with_emergency_event<C: SuiClientInner>(
     }
 }
 
+async fn get_latest_paused_routes_with_limit_event<C: SuiClientInner>(
+    sui_client: Arc<SuiClient<C>>,
+    event: UpdateRouteLimitEvent,
+    staleness_retry_interval: Duration,
+) -> HashSet<BridgeRoute> {
+    let route = (event.sending_chain, event.receiving_chain);
+    let mut remaining_retry_times = REFRESH_BRIDGE_RETRY_TIMES;
+    loop {
+        // Retries must not be served a stale summary from the cache
+        sui_client.invalidate_bridge_summary();
+        let Ok(Ok(summary)) =
+            retry_with_max_elapsed_time!(sui_client.get_bridge_summary(), Duration::from_secs(600))
+        else {
+            error!("Failed to get bridge summary after retry");
+            continue;
+        };
+        let limit = summary
+            .limiter
+            .transfer_limit
+            .iter()
+            .find(|(source, destination, _)| (*source, *destination) == route)
+            .map(|(_, _, limit)| *limit);
+        if limit == Some(event.new_limit) {
+            return paused_routes(&summary);
+        }
+        // If the onchain limit does not match, it could be:
+        // 1. the query is sent to a stale fullnode that does not have the latest data yet
+        // 2. the node is processing an older message, and the latest limit has changed again
+        // In either case, we retry a few times. If it still fails to match, we assume it's the latter case.
+        tokio::time::sleep(staleness_retry_interval).await;
+        remaining_retry_times -= 1;
+        if remaining_retry_times == 0 {
+            warn!(
+                "Route limit {:?} does not match onchain record {:?} after retry",
+                event, limit
+            );
+            return paused_routes(&summary);
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use std::str::FromStr;
 
     use super::*;
     use crate::events::{init_all_struct_tags, NewTokenEvent};
+    use crate::metrics::BridgeMetrics;
     use crate::test_utils::{
         bridge_committee_to_bridge_committee_summary, get_test_authority_and_key,
     };
@@ -277,8 +435,8 @@ mod tests {
     use fastcrypto::traits::KeyPair;
     use prometheus::Registry;
     use sui_types::base_types::SuiAddress;
-    use sui_types::bridge::BridgeCommitteeSummary;
     use sui_types::bridge::MoveTypeCommitteeMember;
+    use sui_types::bridge::{BridgeChainId, BridgeCommitteeSummary, BridgeLimiterSummary};
     use sui_types::crypto::get_key_pair;
 
     use crate::{sui_mock_client::SuiMockClient, types::BridgeCommittee};
@@ -729,6 +887,7 @@ mod tests {
         let old_committee = BridgeCommittee::new(authorities.clone()).unwrap();
         let agg = Arc::new(ArcSwap::new(Arc::new(BridgeAuthorityAggregator::new(
             Arc::new(old_committee),
+            Arc::new(BridgeMetrics::for_testing()),
         ))));
         let sui_token_type_tags = Arc::new(ArcSwap::from(Arc::new(HashMap::new())));
         let _handle = tokio::task::spawn(
@@ -738,6 +897,8 @@ mod tests {
                 agg.clone(),
                 bridge_pause_tx,
                 sui_token_type_tags,
+                Arc::new(TransferMinimums::default()),
+                Arc::new(NotionalTracker::default()),
             )
             .run(),
         );
@@ -783,6 +944,7 @@ mod tests {
         let old_committee = BridgeCommittee::new(authorities.clone()).unwrap();
         let agg = Arc::new(ArcSwap::new(Arc::new(BridgeAuthorityAggregator::new(
             Arc::new(old_committee),
+            Arc::new(BridgeMetrics::for_testing()),
         ))));
         let sui_token_type_tags = Arc::new(ArcSwap::from(Arc::new(HashMap::new())));
         let _handle = tokio::task::spawn(
@@ -792,6 +954,8 @@ mod tests {
                 agg.clone(),
                 bridge_pause_tx,
                 sui_token_type_tags,
+                Arc::new(TransferMinimums::default()),
+                Arc::new(NotionalTracker::default()),
             )
             .run(),
         );
@@ -838,6 +1002,7 @@ mod tests {
         let committee = BridgeCommittee::new(authorities.clone()).unwrap();
         let agg = Arc::new(ArcSwap::new(Arc::new(BridgeAuthorityAggregator::new(
             Arc::new(committee),
+            Arc::new(BridgeMetrics::for_testing()),
         ))));
         let sui_token_type_tags = Arc::new(ArcSwap::from(Arc::new(HashMap::new())));
         let _handle = tokio::task::spawn(
@@ -847,6 +1012,8 @@ mod tests {
                 agg.clone(),
                 bridge_pause_tx,
                 sui_token_type_tags,
+                Arc::new(TransferMinimums::default()),
+                Arc::new(NotionalTracker::default()),
             )
             .run(),
         );
@@ -883,6 +1050,7 @@ mod tests {
         let committee = BridgeCommittee::new(authorities.clone()).unwrap();
         let agg = Arc::new(ArcSwap::new(Arc::new(BridgeAuthorityAggregator::new(
             Arc::new(committee),
+            Arc::new(BridgeMetrics::for_testing()),
         ))));
         let sui_token_type_tags = Arc::new(ArcSwap::from(Arc::new(HashMap::new())));
         let sui_token_type_tags_clone = sui_token_type_tags.clone();
@@ -893,6 +1061,8 @@ mod tests {
                 agg.clone(),
                 bridge_pause_tx,
                 sui_token_type_tags_clone,
+                Arc::new(TransferMinimums::default()),
+                Arc::new(NotionalTracker::default()),
             )
             .run(),
         );
@@ -911,6 +1081,82 @@ mod tests {
             .unwrap();
     }
 
+    #[tokio::test]
+    async fn test_update_route_pauses_with_limit_event() {
+        let (
+            monitor_tx,
+            monitor_rx,
+            sui_client_mock,
+            sui_client,
+            bridge_pause_tx,
+            _bridge_pause_rx,
+            authorities,
+        ) = setup();
+        let committee = BridgeCommittee::new(authorities.clone()).unwrap();
+        let metrics = Arc::new(BridgeMetrics::for_testing());
+        let agg = Arc::new(ArcSwap::new(Arc::new(BridgeAuthorityAggregator::new(
+            Arc::new(committee),
+            metrics.clone(),
+        ))));
+        let route_pauses = Arc::new(RoutePauses::new(metrics.clone()));
+        let _handle = tokio::task::spawn(
+            BridgeMonitor::new(
+                sui_client.clone(),
+                monitor_rx,
+                agg.clone(),
+                bridge_pause_tx,
+                Arc::new(ArcSwap::from(Arc::new(HashMap::new()))),
+                Arc::new(TransferMinimums::default()),
+                Arc::new(NotionalTracker::default()),
+            )
+            .with_route_pauses(route_pauses.clone())
+            .run(),
+        );
+        let paused_route = (BridgeChainId::EthCustom, BridgeChainId::SuiCustom);
+        let other_route = (BridgeChainId::EthSepolia, BridgeChainId::SuiCustom);
+        let set_limits = |paused_limit| {
+            sui_client_mock.set_bridge_limiter(BridgeLimiterSummary {
+                transfer_limit: vec![
+                    (paused_route.0, paused_route.1, paused_limit),
+                    (other_route.0, other_route.1, 1000),
+                ],
+                transfer_records: vec![],
+            });
+        };
+
+        // Governance sets the limit of one route to zero
+        set_limits(0);
+        monitor_tx
+            .send(SuiBridgeEvent::UpdateRouteLimitEvent(
+                UpdateRouteLimitEvent {
+                    sending_chain: paused_route.0,
+                    receiving_chain: paused_route.1,
+                    new_limit: 0,
+                },
+            ))
+            .await
+            .unwrap();
+        // Wait for the monitor to process the event
+        tokio::time::sleep(Duration::from_secs(1)).await;
+        assert!(route_pauses.is_paused(paused_route));
+        assert!(!route_pauses.is_paused(other_route));
+
+        // And back
+        set_limits(500);
+        monitor_tx
+            .send(SuiBridgeEvent::UpdateRouteLimitEvent(
+                UpdateRouteLimitEvent {
+                    sending_chain: paused_route.0,
+                    receiving_chain: paused_route.1,
+                    new_limit: 500,
+                },
+            ))
+            .await
+            .unwrap();
+        tokio::time::sleep(Duration::from_secs(1)).await;
+        assert!(!route_pauses.is_paused(paused_route));
+    }
+
     #[allow(clippy::type_complexity)]
     fn setup() -> (
         mysten_metrics::metered_channel::Sender<SuiBridgeEvent>,
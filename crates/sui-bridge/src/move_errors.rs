@@ -0,0 +1,259 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Decodes why a bridge transaction failed on Sui. JSON RPC only returns the
+//! `ExecutionFailureStatus` of a failed transaction as its debug string, so Move aborts
+//! raised by the bridge package are parsed out of it and mapped to `BridgeMoveError`.
+
+use sui_json_rpc_types::{SuiExecutionStatus, SuiTransactionBlockEvents};
+use sui_types::BRIDGE_ADDRESS;
+
+use crate::events::TokenTransferLimitExceed;
+
+const BRIDGE_MODULE: &str = "bridge";
+const COMMITTEE_MODULE: &str = "committee";
+
+// Abort codes of `bridge.move`
+const E_UNAUTHORISED_CLAIM: u64 = 1;
+const E_NOT_SYSTEM_ADDRESS: u64 = 5;
+const E_BRIDGE_UNAVAILABLE: u64 = 8;
+const E_INVARIANT_SUI_INITIALIZED_TOKEN_TRANSFER_SHOULD_NOT_BE_CLAIMED: u64 = 10;
+const E_TOKEN_ALREADY_CLAIMED: u64 = 15;
+
+// Abort codes of `committee.move`
+const E_SIGNATURE_BELOW_THRESHOLD: u64 = 0;
+const E_DUPLICATED_SIGNATURE: u64 = 1;
+const E_INVALID_SIGNATURE: u64 = 2;
+const E_COMMITTEE_NOT_SYSTEM_ADDRESS: u64 = 3;
+const E_SENDER_NOT_ACTIVE_VALIDATOR: u64 = 5;
+const E_SENDER_IS_NOT_IN_BRIDGE_COMMITTEE: u64 = 9;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BridgeMoveError {
+    // The token transfer was already approved or claimed
+    AlreadyApproved,
+    // The claim was rejected by the limiter. This is not an abort: the transaction
+    // succeeds and emits `TokenTransferLimitExceed` instead.
+    LimitExceeded,
+    // The bridge is paused
+    Paused,
+    // The committee signatures do not reach the threshold or are invalid
+    InvalidSignatures,
+    // The sender is not allowed to perform the operation
+    Unauthorized,
+    // Any other abort of the bridge package
+    Other { module: String, code: u64 },
+}
+
+impl BridgeMoveError {
+    pub fn from_abort(module: &str, code: u64) -> Self {
+        match (module, code) {
+            (BRIDGE_MODULE, E_TOKEN_ALREADY_CLAIMED)
+            | (BRIDGE_MODULE, E_INVARIANT_SUI_INITIALIZED_TOKEN_TRANSFER_SHOULD_NOT_BE_CLAIMED) => {
+                Self::AlreadyApproved
+            }
+            (BRIDGE_MODULE, E_BRIDGE_UNAVAILABLE) => Self::Paused,
+            (
+                COMMITTEE_MODULE,
+                E_SIGNATURE_BELOW_THRESHOLD | E_DUPLICATED_SIGNATURE | E_INVALID_SIGNATURE,
+            ) => Self::InvalidSignatures,
+            (BRIDGE_MODULE, E_UNAUTHORISED_CLAIM | E_NOT_SYSTEM_ADDRESS)
+            | (
+                COMMITTEE_MODULE,
+                E_COMMITTEE_NOT_SYSTEM_ADDRESS
+                | E_SENDER_NOT_ACTIVE_VALIDATOR
+                | E_SENDER_IS_NOT_IN_BRIDGE_COMMITTEE,
+            ) => Self::Unauthorized,
+            _ => Self::Other {
+                module: module.to_string(),
+                code,
+            },
+        }
+    }
+
+    /// Decodes the error of `SuiExecutionStatus::Failure`, which is the debug string of
+    /// `ExecutionFailureStatus`, e.g.
+    /// `MoveAbort(MoveLocation { module: ModuleId { address: 00..0b, name: Identifier("bridge") }, function: 11, instruction: 21, function_name: Some("approve_token_transfer") }, 8) in command 0`.
+    /// Returns None if the failure is not an abort of the bridge package.
+    pub fn from_failure_message(error: &str) -> Option<Self> {
+        let abort = error.split_once("MoveAbort(")?.1;
+        let address = abort.split_once("address: ")?.1.split_once(',')?.0;
+        if address != format!("{:x}", BRIDGE_ADDRESS) {
+            return None;
+        }
+        let module = abort
+            .split_once("name: Identifier(\"")?
+            .1
+            .split_once("\")")?
+            .0;
+        // The abort code is the last argument of `MoveAbort`
+        let code = abort[..abort.rfind(')')?]
+            .rsplit_once(", ")?
+            .1
+            .parse::<u64>()
+            .ok()?;
+        Some(Self::from_abort(module, code))
+    }
+
+    /// Returns the bridge error of an executed transaction, if any.
+    pub fn from_effects(
+        status: &SuiExecutionStatus,
+        events: Option<&SuiTransactionBlockEvents>,
+    ) -> Option<Self> {
+        match status {
+            SuiExecutionStatus::Success => events
+                .is_some_and(|events| {
+                    events
+                        .data
+                        .iter()
+                        .any(|e| e.type_ == *TokenTransferLimitExceed.get().unwrap())
+                })
+                .then_some(Self::LimitExceeded),
+            SuiExecutionStatus::Failure { error } => Self::from_failure_message(error),
+        }
+    }
+
+    /// Whether executing the same action again aborts the same way, whatever the state
+    /// of the bridge. Such actions are quarantined after a few attempts.
+    pub fn is_permanent(&self) -> bool {
+        match self {
+            Self::Unauthorized | Self::Other { .. } => true,
+            // Handled by deferring, re-aggregating or completing the action
+            Self::AlreadyApproved
+            | Self::LimitExceeded
+            | Self::Paused
+            | Self::InvalidSignatures => false,
+        }
+    }
+
+    pub fn category(&self) -> &'static str {
+        match self {
+            Self::AlreadyApproved => "already_approved",
+            Self::LimitExceeded => "limit_exceeded",
+            Self::Paused => "paused",
+            Self::InvalidSignatures => "invalid_signatures",
+            Self::Unauthorized => "unauthorized",
+            Self::Other { .. } => "other",
+        }
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use crate::events::init_all_struct_tags;
+    use move_core_types::identifier::Identifier;
+    use move_core_types::language_storage::ModuleId;
+    use sui_json_rpc_types::SuiEvent;
+    use sui_types::execution_status::{ExecutionFailureStatus, ExecutionStatus, MoveLocation};
+    use sui_types::SUI_FRAMEWORK_ADDRESS;
+
+    /// Returns the status of a transaction that aborted in `module` of the bridge package.
+    pub(crate) fn get_move_abort_status(module: &str, code: u64) -> SuiExecutionStatus {
+        let error = ExecutionFailureStatus::MoveAbort(
+            MoveLocation {
+                module: ModuleId::new(BRIDGE_ADDRESS, Identifier::new(module).unwrap()),
+                function: 11,
+                instruction: 21,
+                function_name: Some("approve_token_transfer".to_string()),
+            },
+            code,
+        );
+        ExecutionStatus::Failure {
+            error,
+            command: Some(0),
+        }
+        .into()
+    }
+
+    #[test]
+    fn test_decode_bridge_move_aborts() {
+        for (module, code, expected) in [
+            ("bridge", 15, BridgeMoveError::AlreadyApproved),
+            ("bridge", 10, BridgeMoveError::AlreadyApproved),
+            ("bridge", 8, BridgeMoveError::Paused),
+            ("committee", 0, BridgeMoveError::InvalidSignatures),
+            ("committee", 1, BridgeMoveError::InvalidSignatures),
+            ("committee", 2, BridgeMoveError::InvalidSignatures),
+            ("bridge", 1, BridgeMoveError::Unauthorized),
+            ("bridge", 5, BridgeMoveError::Unauthorized),
+            ("committee", 3, BridgeMoveError::Unauthorized),
+            ("committee", 5, BridgeMoveError::Unauthorized),
+            ("committee", 9, BridgeMoveError::Unauthorized),
+            (
+                "limiter",
+                0,
+                BridgeMoveError::Other {
+                    module: "limiter".to_string(),
+                    code: 0,
+                },
+            ),
+        ] {
+            assert_eq!(
+                BridgeMoveError::from_effects(&get_move_abort_status(module, code), None),
+                Some(expected),
+                "{}::{}",
+                module,
+                code
+            );
+        }
+    }
+
+    #[test]
+    fn test_permanent_move_errors() {
+        assert!(BridgeMoveError::Unauthorized.is_permanent());
+        assert!(BridgeMoveError::from_abort("limiter", 0).is_permanent());
+        assert!(!BridgeMoveError::Paused.is_permanent());
+        assert!(!BridgeMoveError::LimitExceeded.is_permanent());
+        assert!(!BridgeMoveError::InvalidSignatures.is_permanent());
+    }
+
+    #[test]
+    fn test_decode_non_bridge_failures() {
+        // Abort from another package
+        let status: SuiExecutionStatus = ExecutionStatus::Failure {
+            error: ExecutionFailureStatus::MoveAbort(
+                MoveLocation {
+                    module: ModuleId::new(
+                        SUI_FRAMEWORK_ADDRESS,
+                        Identifier::new("bridge").unwrap(),
+                    ),
+                    function: 0,
+                    instruction: 0,
+                    function_name: None,
+                },
+                8,
+            ),
+            command: None,
+        }
+        .into();
+        assert_eq!(BridgeMoveError::from_effects(&status, None), None);
+
+        // Not an abort
+        let status: SuiExecutionStatus = ExecutionStatus::Failure {
+            error: ExecutionFailureStatus::InsufficientGas,
+            command: None,
+        }
+        .into();
+        assert_eq!(BridgeMoveError::from_effects(&status, None), None);
+    }
+
+    #[test]
+    fn test_decode_limit_exceeded() {
+        init_all_struct_tags();
+        let mut event = SuiEvent::random_for_testing();
+        event.type_ = TokenTransferLimitExceed.get().unwrap().clone();
+        let events = SuiTransactionBlockEvents { data: vec![event] };
+        assert_eq!(
+            BridgeMoveError::from_effects(&SuiExecutionStatus::Success, Some(&events)),
+            Some(BridgeMoveError::LimitExceeded)
+        );
+        assert_eq!(
+            BridgeMoveError::from_effects(
+                &SuiExecutionStatus::Success,
+                Some(&SuiTransactionBlockEvents { data: vec![] })
+            ),
+            None
+        );
+    }
+}
@@ -0,0 +1,377 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Hosting several bridge nodes in one process, e.g. a testnet and a mainnet observer.
+//! Every instance has its own `BridgeNodeConfig`, and runs on its own tokio runtime with
+//! its own metrics registry, whose metrics are labeled with `network=<instance name>`.
+//! An instance that fails or is shut down takes down all of its tasks, and only them.
+
+use crate::config::BridgeNodeConfig;
+use crate::metrics::{start_metrics_push_task, start_metrics_server};
+use crate::node::run_bridge_node;
+use crate::server::BridgeNodePublicMetadata;
+use anyhow::anyhow;
+use fastcrypto::traits::KeyPair;
+use prometheus::Registry;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::future::Future;
+use std::net::{IpAddr, Ipv4Addr, SocketAddr};
+use std::path::PathBuf;
+use std::thread;
+use sui_config::Config;
+use tokio::sync::oneshot;
+use tracing::{error, info};
+
+pub const NETWORK_LABEL: &str = "network";
+
+#[derive(Debug, Deserialize, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct MultiBridgeNodeConfig {
+    pub instances: Vec<BridgeNodeInstanceConfig>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct BridgeNodeInstanceConfig {
+    /// Unique name of the instance, e.g. `testnet`. Used as the `network` label of its
+    /// metrics.
+    pub name: String,
+    /// Path of the `BridgeNodeConfig` of the instance.
+    pub config_path: PathBuf,
+}
+
+impl Config for MultiBridgeNodeConfig {}
+
+impl MultiBridgeNodeConfig {
+    /// Loads the config of every instance, and checks that the instances don't share
+    /// ports or storage.
+    pub fn load_instances(&self) -> anyhow::Result<Vec<(String, BridgeNodeConfig)>> {
+        let instances = self
+            .instances
+            .iter()
+            .map(|instance| {
+                let config = BridgeNodeConfig::load(&instance.config_path).map_err(|e| {
+                    anyhow!(
+                        "Failed to load config of instance {}: {:?}",
+                        instance.name,
+                        e
+                    )
+                })?;
+                Ok((instance.name.clone(), config))
+            })
+            .collect::<anyhow::Result<Vec<_>>>()?;
+        validate_instances(&instances)?;
+        Ok(instances)
+    }
+}
+
+pub fn validate_instances(instances: &[(String, BridgeNodeConfig)]) -> anyhow::Result<()> {
+    if instances.is_empty() {
+        return Err(anyhow!("At least one bridge node instance is required"));
+    }
+    let mut names = HashSet::new();
+    let mut ports = HashMap::new();
+    let mut paths = HashMap::new();
+    for (name, config) in instances {
+        if name.is_empty() || !names.insert(name) {
+            return Err(anyhow!(
+                "Instance names must be unique and non empty: {name:?}"
+            ));
+        }
+        for port in [config.server_listen_port, config.metrics_port] {
+            if let Some(other) = ports.insert(port, name) {
+                return Err(anyhow!("Instances {other} and {name} both use port {port}"));
+            }
+        }
+        for path in [&config.db_path, &config.aux_db_path].into_iter().flatten() {
+            if let Some(other) = paths.insert(path, name) {
+                return Err(anyhow!(
+                    "Instances {other} and {name} both use db path {path:?}"
+                ));
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Returns a registry whose metrics are all labeled with the name of the instance.
+pub fn instance_registry(name: &str) -> Registry {
+    Registry::new_custom(
+        None,
+        Some(HashMap::from([(
+            NETWORK_LABEL.to_string(),
+            name.to_string(),
+        )])),
+    )
+    .expect("Instance name should be a valid label value")
+}
+
+/// A bridge node instance running on its own runtime.
+pub struct BridgeNodeInstance {
+    name: String,
+    shutdown: Option<oneshot::Sender<()>>,
+    handle: thread::JoinHandle<anyhow::Result<()>>,
+}
+
+impl BridgeNodeInstance {
+    /// Runs `run` on a new runtime, until it returns or the instance is shut down.
+    pub fn spawn<F, Fut>(name: String, run: F) -> anyhow::Result<Self>
+    where
+        F: FnOnce() -> Fut + Send + 'static,
+        Fut: Future<Output = anyhow::Result<()>>,
+    {
+        let (shutdown_tx, shutdown_rx) = oneshot::channel();
+        let runtime = tokio::runtime::Builder::new_multi_thread()
+            .thread_name(format!("bridge-{}", name))
+            .enable_all()
+            .build()?;
+        let thread_name = name.clone();
+        let handle = thread::Builder::new()
+            .name(format!("bridge-{}", name))
+            .spawn(move || {
+                let result = runtime.block_on(async move {
+                    tokio::select! {
+                        result = run() => result,
+                        _ = shutdown_rx => {
+                            info!("Shutting down bridge node instance {}", thread_name);
+                            Ok(())
+                        }
+                    }
+                });
+                // Dropping the runtime cancels every task of the instance
+                drop(runtime);
+                result
+            })?;
+        Ok(Self {
+            name,
+            shutdown: Some(shutdown_tx),
+            handle,
+        })
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn shutdown(&mut self) {
+        if let Some(shutdown) = self.shutdown.take() {
+            let _ = shutdown.send(());
+        }
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.handle.is_finished()
+    }
+
+    /// Waits for the instance to stop.
+    pub fn join(self) -> anyhow::Result<()> {
+        self.handle
+            .join()
+            .map_err(|_| anyhow!("Bridge node instance {} panicked", self.name))?
+    }
+}
+
+/// Runs the bridge node of one instance, with its own metrics server on `metrics_port`.
+/// `process_registry` holds the process wide metrics, which every instance exports.
+pub async fn run_bridge_node_instance(
+    name: String,
+    config: BridgeNodeConfig,
+    version: String,
+    process_registry: Registry,
+) -> anyhow::Result<()> {
+    let metrics_address =
+        SocketAddr::new(IpAddr::V4(Ipv4Addr::new(0, 0, 0, 0)), config.metrics_port);
+    let registry_service = start_metrics_server(metrics_address);
+    registry_service.add(process_registry);
+    let registry = instance_registry(&name);
+    registry_service.add(registry.clone());
+    info!(
+        "Metrics server of instance {} started at port {}",
+        name, config.metrics_port
+    );
+
+    let metadata = BridgeNodePublicMetadata::new(version, config.metrics_key_pair.public().clone());
+    start_metrics_push_task(
+        &config.metrics,
+        config.metrics_key_pair.copy(),
+        registry_service,
+    );
+    run_bridge_node(config, metadata, registry).await?.await?;
+    Ok(())
+}
+
+/// Runs every instance until all of them stopped. An instance that stops, even with an
+/// error, does not stop the others.
+pub fn run_bridge_nodes(
+    instances: Vec<(String, BridgeNodeConfig)>,
+    version: String,
+    process_registry: Registry,
+) -> anyhow::Result<()> {
+    validate_instances(&instances)?;
+    let instances = instances
+        .into_iter()
+        .map(|(name, config)| {
+            let version = version.clone();
+            let process_registry = process_registry.clone();
+            BridgeNodeInstance::spawn(name.clone(), move || {
+                run_bridge_node_instance(name, config, version, process_registry)
+            })
+        })
+        .collect::<anyhow::Result<Vec<_>>>()?;
+    for instance in instances {
+        let name = instance.name().to_string();
+        match instance.join() {
+            Ok(()) => info!("Bridge node instance {} stopped", name),
+            Err(e) => error!("Bridge node instance {} failed: {:?}", name, e),
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::metrics::BridgeMetrics;
+    use crate::sui_client::SuiClient;
+    use crate::sui_mock_client::SuiMockClient;
+    use crate::test_utils::{
+        bridge_committee_to_bridge_committee_summary, get_test_authority_and_key,
+    };
+    use crate::types::BridgeCommittee;
+    use crate::url_check::{RegisteredUrlChecker, DEFAULT_URL_CHECK_INTERVAL};
+    use crate::utils::generate_bridge_node_config_and_write_to_file;
+    use std::sync::Arc;
+    use std::time::{Duration, Instant};
+    use sui_config::local_ip_utils::get_available_port;
+
+    fn gauge_value(registry: &Registry, name: &str) -> Option<(String, f64)> {
+        let family = registry
+            .gather()
+            .into_iter()
+            .find(|family| family.get_name() == name)?;
+        let metric = &family.get_metric()[0];
+        let network = metric
+            .get_label()
+            .iter()
+            .find(|label| label.get_name() == NETWORK_LABEL)?
+            .get_value()
+            .to_string();
+        Some((network, metric.get_gauge().get_value()))
+    }
+
+    // Runs a `RegisteredUrlChecker` of an instance against its own mock client. The url
+    // registered on chain matches `external_url` only if `matching` is true.
+    fn spawn_test_instance(name: &str, registry: Registry, matching: bool) -> BridgeNodeInstance {
+        BridgeNodeInstance::spawn(name.to_string(), move || async move {
+            let metrics = Arc::new(BridgeMetrics::new(&registry));
+            let sui_client_mock = SuiMockClient::default();
+            let port = get_available_port("127.0.0.1");
+            let (authority, _, _) = get_test_authority_and_key(10000, port);
+            let pubkey = authority.pubkey_bytes();
+            sui_client_mock.set_bridge_committee(bridge_committee_to_bridge_committee_summary(
+                BridgeCommittee::new(vec![authority]).unwrap(),
+            ));
+            let external_port = if matching { port } else { port + 1 };
+            RegisteredUrlChecker::new(
+                Arc::new(SuiClient::new_for_testing(sui_client_mock)),
+                pubkey,
+                Some(format!("http://127.0.0.1:{}", external_port)),
+                metrics,
+                DEFAULT_URL_CHECK_INTERVAL,
+            )
+            .run()
+            .await;
+            Ok(())
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn test_bridge_node_instances_are_isolated() {
+        let testnet_registry = instance_registry("testnet");
+        let mainnet_registry = instance_registry("mainnet");
+        let mut testnet = spawn_test_instance("testnet", testnet_registry.clone(), false);
+        let mut mainnet = spawn_test_instance("mainnet", mainnet_registry.clone(), true);
+
+        let deadline = Instant::now() + Duration::from_secs(10);
+        while Instant::now() < deadline
+            && (testnet_registry.gather().is_empty()
+                || mainnet_registry.gather().is_empty()
+                || gauge_value(&testnet_registry, "bridge_url_mismatch")
+                    != Some(("testnet".to_string(), 1.0)))
+        {
+            thread::sleep(Duration::from_millis(50));
+        }
+        // Each registry only has the metrics of its instance, labeled with its name
+        assert_eq!(
+            gauge_value(&testnet_registry, "bridge_url_mismatch"),
+            Some(("testnet".to_string(), 1.0))
+        );
+        assert_eq!(
+            gauge_value(&mainnet_registry, "bridge_url_mismatch"),
+            Some(("mainnet".to_string(), 0.0))
+        );
+
+        // Shutting down one instance leaves the other running
+        testnet.shutdown();
+        let testnet_name = testnet.name().to_string();
+        testnet.join().unwrap();
+        assert_eq!(testnet_name, "testnet");
+        assert!(!mainnet.is_finished());
+        mainnet.shutdown();
+        mainnet.join().unwrap();
+    }
+
+    #[test]
+    fn test_validate_instances() {
+        let path = tempfile::tempdir()
+            .unwrap()
+            .into_path()
+            .join("bridge_node.yaml");
+        generate_bridge_node_config_and_write_to_file(&path, false).unwrap();
+        let config = |server_listen_port, metrics_port, db_path: Option<&str>| {
+            let mut config = BridgeNodeConfig::load(&path).unwrap();
+            config.server_listen_port = server_listen_port;
+            config.metrics_port = metrics_port;
+            config.db_path = db_path.map(PathBuf::from);
+            config
+        };
+        validate_instances(&[
+            (
+                "testnet".to_string(),
+                config(9191, 9184, Some("/tmp/testnet")),
+            ),
+            (
+                "mainnet".to_string(),
+                config(9192, 9185, Some("/tmp/mainnet")),
+            ),
+        ])
+        .unwrap();
+        validate_instances(&[]).unwrap_err();
+        // Duplicated name
+        validate_instances(&[
+            ("testnet".to_string(), config(9191, 9184, None)),
+            ("testnet".to_string(), config(9192, 9185, None)),
+        ])
+        .unwrap_err();
+        // Shared port
+        validate_instances(&[
+            ("testnet".to_string(), config(9191, 9184, None)),
+            ("mainnet".to_string(), config(9192, 9191, None)),
+        ])
+        .unwrap_err();
+        // Shared storage
+        validate_instances(&[
+            (
+                "testnet".to_string(),
+                config(9191, 9184, Some("/tmp/bridge")),
+            ),
+            (
+                "mainnet".to_string(),
+                config(9192, 9185, Some("/tmp/bridge")),
+            ),
+        ])
+        .unwrap_err();
+    }
+}
@@ -2,27 +2,98 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::{
-    action_executor::BridgeActionExecutor,
-    client::bridge_authority_aggregator::BridgeAuthorityAggregator,
-    config::{BridgeClientConfig, BridgeNodeConfig},
-    eth_syncer::EthSyncer,
+    action_executor::{BridgeActionExecutor, CHANNEL_SIZE},
+    action_observer::BridgeActionObserver,
+    alerts::AlertSink,
+    api_types::NodeHealth,
+    certified_actions::{
+        CertifiedActions, CertifiedActionsReceiver, DEFAULT_CERTIFIED_ACTIONS_CAPACITY,
+    },
+    claim_tracker::{
+        ClaimTracker, DEFAULT_CLAIM_TRACKING_INTERVAL, DEFAULT_CLAIM_TRACKING_MAX_BACKOFF,
+        DEFAULT_CLAIM_TRACKING_MAX_RPC_QUERIES_PER_SECOND,
+    },
+    client::{
+        authority_scores::{AuthorityScorePersister, DEFAULT_SCORE_PERSIST_INTERVAL},
+        bridge_authority_aggregator::BridgeAuthorityAggregator,
+        bridge_client::requires_https,
+        committee_probe::{CommitteeProber, DEFAULT_COMMITTEE_PROBE_INTERVAL},
+        dns_refresh::{CommitteeDnsRefresher, SystemResolver},
+        hedging::RequestHedging,
+    },
+    clock_drift::ClockDriftChecker,
+    config::{
+        ArchiveConfig, BridgeClientConfig, BridgeNodeConfig, BridgeNodeMode, BridgeServerConfig,
+        RemoteSignerConfig,
+    },
+    crypto::{BridgeAuthorityKeyPair, BridgeAuthorityPublicKeyBytes},
+    cursor_reset::CursorResetter,
+    drain::DrainEstimator,
+    eth_fee_history::EthFeeSampler,
+    eth_syncer::{EthSyncer, ETH_EVENTS_CHANNEL_SIZE, ETH_EVENTS_SEND_TIMEOUT},
+    event_parsers::{EthLogParser, EventParsers, SuiEventParser},
+    event_stream::{EventStream, EventStreamListener},
     events::init_all_struct_tags,
+    gas_top_up::{GasTopUp, DEFAULT_GAS_TOP_UP_CHECK_INTERVAL, DEFAULT_GAS_TOP_UP_COOLDOWN},
+    logging::Logging,
+    metered_eth_provider::MeteredEthHttpProvier,
     metrics::BridgeMetrics,
     monitor::BridgeMonitor,
+    notional::{NotionalTracker, NotionalTrackerUpdater},
     orchestrator::BridgeOrchestrator,
-    server::{handler::BridgeRequestHandler, run_server, BridgeNodePublicMetadata},
-    storage::BridgeOrchestratorTables,
-    sui_syncer::SuiSyncer,
+    provider_versions::{ProviderVersionTracker, DEFAULT_PROVIDER_VERSION_REFRESH_INTERVAL},
+    pruning::{
+        ExecutedActionPruner, DEFAULT_PRUNING_BATCH_SIZE, DEFAULT_PRUNING_INTERVAL,
+        DEFAULT_RETAINED_EXECUTED_ACTIONS,
+    },
+    quarantine_recheck::{
+        QuarantineRechecker, DEFAULT_QUARANTINE_RECHECK_INTERVAL,
+        DEFAULT_QUARANTINE_RECHECK_MAX_ACTIONS,
+    },
+    readiness::BridgeReadinessHandle,
+    reconciler::{
+        BridgeReconciler, DEFAULT_RECONCILIATION_INTERVAL,
+        DEFAULT_RECONCILIATION_MAX_RPC_QUERIES_PER_SECOND, DEFAULT_RECONCILIATION_SAMPLE_SIZE,
+    },
+    route_pause::{paused_routes, RoutePauses},
+    runtime_info::{collect_runtime_info, effective_config},
+    server::{
+        handler::BridgeRequestHandler,
+        listener::ListenConfig,
+        make_admin_router, make_drain_router, make_limits_router, make_transfers_router,
+        node_health,
+        nonce_guard::{NonceGuard, DEFAULT_NONCE_REFRESH_INTERVAL},
+        run_observer_server, run_server, run_shadow_server,
+        runtime::{
+            RuntimeProbe, ServerRuntime, DEFAULT_RUNTIME_PROBE_INTERVAL, MAIN_RUNTIME,
+            SERVER_RUNTIME,
+        },
+        warm_up::SignerWarmUp,
+        BridgeNodePublicMetadata,
+    },
+    shadow::ShadowComparator,
+    signature_inclusion::SignatureInclusion,
+    signed_set::SignedSet,
+    signer::{BridgeAuthoritySigner, RemoteSigner},
+    signing_queue::DEFAULT_SIGNING_WINDOW,
+    slo::{SloMetricsUpdater, DEFAULT_SLO_UPDATE_INTERVAL},
+    storage::{run_cursor_flusher, BridgeOrchestratorTables},
+    storage_maintenance::StorageMaintenance,
+    sui_client::SuiClientInner,
+    sui_syncer::{SuiSyncer, SUI_EVENTS_CHANNEL_SIZE, SUI_EVENTS_SEND_TIMEOUT},
+    supervisor::RestartPolicy,
+    telemetry::TelemetryReporter,
+    transfer_minimums::{TransferMinimums, TransferMinimumsUpdater},
+    tx_journal::{TxJournal, DEFAULT_JOURNAL_MAX_FILE_SIZE},
+    tx_submission::SuiTxSubmitter,
+    url_check::{RegisteredUrlChecker, DEFAULT_URL_CHECK_INTERVAL},
 };
 use arc_swap::ArcSwap;
+use ethers::providers::JsonRpcClient;
 use ethers::types::Address as EthAddress;
 use mysten_metrics::spawn_logged_monitored_task;
-use std::{
-    collections::HashMap,
-    net::{IpAddr, Ipv4Addr, SocketAddr},
-    sync::Arc,
-    time::Duration,
-};
+use std::{collections::HashMap, net::SocketAddr, sync::Arc, time::Duration};
+use sui_sdk::SuiClient as SuiSdkClient;
 use sui_types::{
     bridge::{
         BRIDGE_COMMITTEE_MODULE_NAME, BRIDGE_LIMITER_MODULE_NAME, BRIDGE_MODULE_NAME,
@@ -32,7 +103,20 @@ use sui_types::{
     Identifier,
 };
 use tokio::task::JoinHandle;
-use tracing::info;
+use tracing::{info, warn};
+
+/// Sets the global tracing subscriber from the `logging` section of `config`, with
+/// `RUST_LOG` taking precedence. Logs are configured by the environment when the section
+/// is not set.
+pub fn init_logging(
+    config: &BridgeNodeConfig,
+    prometheus_registry: &prometheus::Registry,
+) -> anyhow::Result<Arc<Logging>> {
+    Ok(Arc::new(Logging::init(
+        config.logging.as_ref(),
+        prometheus_registry,
+    )?))
+}
 
 pub async fn run_bridge_node(
     config: BridgeNodeConfig,
@@ -42,40 +126,563 @@ pub async fn run_bridge_node(
     init_all_struct_tags();
     let metrics = Arc::new(BridgeMetrics::new(&prometheus_registry));
     let (server_config, client_config) = config.validate(metrics.clone()).await?;
+    if let Some(reporter) = TelemetryReporter::new(
+        config.telemetry.as_ref(),
+        config.metrics_key_pair.copy(),
+        metadata.version.clone(),
+        metrics.clone(),
+    ) {
+        spawn_logged_monitored_task!(reporter.run());
+    }
+    if let Some(interval) = config.eth.fee_history_interval_seconds {
+        spawn_logged_monitored_task!(EthFeeSampler::new(
+            server_config.eth_client.clone(),
+            metrics.clone(),
+            Duration::from_secs(interval),
+        )
+        .run());
+    }
 
-    // Start Client
-    let _handles = if let Some(client_config) = client_config {
-        start_client_components(client_config, metrics.clone()).await
-    } else {
-        Ok(vec![])
-    }?;
-
-    // Start Server
-    let socket_address = SocketAddr::new(
-        IpAddr::V4(Ipv4Addr::new(0, 0, 0, 0)),
-        server_config.server_listen_port,
-    );
-    Ok(run_server(
-        &socket_address,
-        BridgeRequestHandler::new(
-            server_config.key,
-            server_config.sui_client,
-            server_config.eth_client,
-            server_config.approved_governance_actions,
+    let effective_config = effective_config(&config, &server_config, client_config.as_ref());
+    let mut builder = BridgeNodeBuilder::new(server_config)
+        .with_metrics(metrics)
+        .with_metadata(metadata)
+        .with_effective_config(effective_config);
+    if let Some(client_config) = client_config {
+        builder = builder.with_client(client_config);
+    }
+    Ok(builder.build().await?.into_server_handle())
+}
+
+/// Builds a bridge node from typed parts instead of a `BridgeNodeConfig`, e.g. to embed
+/// a node in another process or to run one against mock clients. The server runs in the
+/// mode of `server_config`, and the client only when one is set with `with_client`.
+pub struct BridgeNodeBuilder<C = SuiSdkClient, P = MeteredEthHttpProvier> {
+    server_config: BridgeServerConfig<C, P>,
+    client_config: Option<BridgeClientConfig<C, P>>,
+    store: Option<Arc<BridgeOrchestratorTables>>,
+    metrics: Option<Arc<BridgeMetrics>>,
+    metadata: BridgeNodePublicMetadata,
+    event_parsers: EventParsers,
+    effective_config: Option<serde_json::Value>,
+}
+
+impl<C, P> BridgeNodeBuilder<C, P>
+where
+    C: SuiClientInner + 'static,
+    P: JsonRpcClient + Clone + 'static,
+{
+    pub fn new(server_config: BridgeServerConfig<C, P>) -> Self {
+        Self {
+            server_config,
+            client_config: None,
+            store: None,
+            metrics: None,
+            metadata: BridgeNodePublicMetadata {
+                version: None,
+                metrics_pubkey: None,
+                runtime_info: None,
+            },
+            event_parsers: EventParsers::default(),
+            effective_config: None,
+        }
+    }
+
+    /// Signs with `key` as a committee member.
+    pub fn with_authority_key(mut self, key: BridgeAuthorityKeyPair) -> Self {
+        self.server_config.mode = BridgeNodeMode::Committee;
+        self.server_config.key = Some(key);
+        self
+    }
+
+    /// Signs through the remote signer of `config` as a committee member, see `signer`.
+    pub fn with_remote_signer(mut self, config: RemoteSignerConfig) -> Self {
+        self.server_config.mode = BridgeNodeMode::Committee;
+        self.server_config.remote_signer = Some(config);
+        self
+    }
+
+    pub fn with_client(mut self, client_config: BridgeClientConfig<C, P>) -> Self {
+        self.client_config = Some(client_config);
+        self
+    }
+
+    /// Uses `store` instead of opening the storage at the `db_path` of the client. The
+    /// signed actions are kept in it too, also when the client doesn't run.
+    pub fn with_store(mut self, store: Arc<BridgeOrchestratorTables>) -> Self {
+        self.store = Some(store);
+        self
+    }
+
+    /// Registers the metrics of the node in `registry`.
+    pub fn with_registry(self, registry: &prometheus::Registry) -> Self {
+        self.with_metrics(Arc::new(BridgeMetrics::new(registry)))
+    }
+
+    pub fn with_metrics(mut self, metrics: Arc<BridgeMetrics>) -> Self {
+        self.metrics = Some(metrics);
+        self
+    }
+
+    pub fn with_metadata(mut self, metadata: BridgeNodePublicMetadata) -> Self {
+        self.metadata = metadata;
+        self
+    }
+
+    /// Reports `config` as the config of the node in its runtime info, see
+    /// `runtime_info::effective_config`.
+    pub fn with_effective_config(mut self, config: serde_json::Value) -> Self {
+        self.effective_config = Some(config);
+        self
+    }
+
+    /// Converts the Sui events that the default parsers don't claim with `parser` too,
+    /// see `event_parsers`.
+    pub fn with_sui_event_parser(mut self, parser: Arc<dyn SuiEventParser>) -> Self {
+        self.event_parsers = self.event_parsers.with_sui_parser(parser);
+        self
+    }
+
+    /// Converts the Eth logs that the default parsers don't claim with `parser` too, and
+    /// queries the logs with its topics.
+    pub fn with_eth_log_parser(mut self, parser: Arc<dyn EthLogParser>) -> Self {
+        self.event_parsers = self.event_parsers.with_eth_parser(parser);
+        self
+    }
+
+    /// Starts the components of the node and its server.
+    pub async fn build(self) -> anyhow::Result<RunningBridgeNode<C, P>> {
+        init_all_struct_tags();
+        let server_config = self.server_config;
+        let metrics = self
+            .metrics
+            .unwrap_or_else(|| Arc::new(BridgeMetrics::new(&prometheus::Registry::new())));
+        metrics
+            .requesters
+            .set_max_labels(server_config.max_requester_labels);
+        let mut handles = vec![];
+
+        handles.push(spawn_logged_monitored_task!(ClockDriftChecker::new(
+            server_config.sui_client.clone(),
+            server_config.eth_client.clone(),
+            server_config.clock_drift.settings(),
+            server_config.clock_drift.check_interval(),
+            metrics.clone(),
+        )
+        .run()));
+        handles.push(spawn_logged_monitored_task!(ProviderVersionTracker::new(
+            server_config.sui_client.clone(),
+            server_config.eth_client.clone(),
+            server_config.sui_rpc_endpoint.clone(),
+            server_config.eth_rpc_endpoint.clone(),
+            DEFAULT_PROVIDER_VERSION_REFRESH_INTERVAL,
+            metrics.clone(),
+        )
+        .run()));
+        handles.push(spawn_logged_monitored_task!(RuntimeProbe::new(
+            MAIN_RUNTIME,
+            tokio::runtime::Handle::current(),
+            DEFAULT_RUNTIME_PROBE_INTERVAL,
+            metrics.clone(),
+        )
+        .run()));
+
+        let store = match (self.store, &self.client_config) {
+            (Some(store), _) => Some(store),
+            (None, Some(client_config)) => Some(
+                BridgeOrchestratorTables::open_with_encryption(
+                    &client_config.db_path.join("client"),
+                    client_config.aux_db_path.as_deref(),
+                    client_config.storage_key_provider.as_deref(),
+                )
+                .map_err(|e| anyhow::anyhow!("Couldn't open the client storage: {:?}", e))?,
+            ),
+            (None, None) => None,
+        };
+        if self.client_config.is_none() {
+            metrics.readiness.without_client();
+        } else if store.is_some() {
+            metrics.readiness.set_storage_open();
+        }
+
+        let runtime_info = collect_runtime_info(
+            &server_config,
+            self.client_config.as_ref(),
+            store.is_some(),
+            self.metadata.version.clone(),
+            self.effective_config,
+        )
+        .await;
+        info!(
+            runtime_info = %serde_json::to_string(&runtime_info).expect("RuntimeInfo serializes"),
+            "Starting bridge node {}",
+            runtime_info.version.as_deref().unwrap_or("of unknown version"),
+        );
+        let mut metadata = self.metadata;
+        metadata.runtime_info = Some(Arc::new(runtime_info));
+
+        // Alerts are configured with the client, the server raises them too
+        let alerts = self
+            .client_config
+            .as_ref()
+            .and_then(|client_config| client_config.alerts.as_ref())
+            .map(|alerts_config| {
+                let (alert_sink, alert_poster) = AlertSink::new(alerts_config, metrics.clone());
+                handles.push(spawn_logged_monitored_task!(alert_poster.run()));
+                Arc::new(alert_sink)
+            });
+
+        // The watchers of the client and the signing requests update the known nonces
+        let nonce_guard = Arc::new(NonceGuard::new(
+            server_config.verification_limits.max_nonce_lead,
+            metrics.clone(),
+        ));
+
+        // The executor of the client publishes the actions it gets certified
+        let certified_actions = Arc::new(CertifiedActions::new(
+            DEFAULT_CERTIFIED_ACTIONS_CAPACITY,
+            metrics.clone(),
+        ));
+
+        // The client storage is compacted and backed up on a schedule and through the
+        // admin api
+        let storage_maintenance = match (&self.client_config, &store) {
+            (Some(client_config), Some(store)) => match &client_config.storage_maintenance {
+                Some(config) => {
+                    let storage_maintenance = Arc::new(StorageMaintenance::new(
+                        store.clone(),
+                        config,
+                        metrics.clone(),
+                    )?);
+                    handles.push(spawn_logged_monitored_task!(storage_maintenance
+                        .clone()
+                        .run()));
+                    Some(storage_maintenance)
+                }
+                None => None,
+            },
+            _ => None,
+        };
+
+        // Start Client
+        let (cursor_resetter, quarantine_rechecker) = match (self.client_config, &store) {
+            (Some(client_config), Some(store)) => {
+                let (client_handles, cursor_resetter, quarantine_rechecker) =
+                    start_client_components(
+                        client_config,
+                        store.clone(),
+                        self.event_parsers,
+                        alerts.clone(),
+                        nonce_guard.clone(),
+                        certified_actions.clone(),
+                        metrics.clone(),
+                    )
+                    .await?;
+                handles.extend(client_handles);
+                (Some(cursor_resetter), quarantine_rechecker)
+            }
+            _ => (None, None),
+        };
+        handles.push(spawn_logged_monitored_task!(SloMetricsUpdater::new(
             metrics.clone(),
-        ),
-        metrics,
-        Arc::new(metadata),
-    ))
+            DEFAULT_SLO_UPDATE_INTERVAL
+        )
+        .run()));
+
+        // Start Server
+        let listen = server_config.listen;
+        let metadata = Arc::new(metadata);
+        let running_store = store.clone();
+        let running =
+            |server_handle, handles, cursor_resetter, quarantine_rechecker| RunningBridgeNode {
+                server_handle,
+                handles,
+                metrics: metrics.clone(),
+                listen,
+                store: running_store.clone(),
+                certified_actions: certified_actions.clone(),
+                cursor_resetter,
+                quarantine_rechecker,
+                server_runtime: None,
+            };
+        if server_config.mode == BridgeNodeMode::Shadow {
+            info!("Running in shadow mode, signing is disabled");
+            let server_handle = run_shadow_server(
+                &listen,
+                metrics.clone(),
+                metadata,
+                server_config.admin_authorizer,
+            );
+            return Ok(running(
+                server_handle,
+                handles,
+                cursor_resetter,
+                quarantine_rechecker,
+            ));
+        }
+        let signer: Arc<dyn BridgeAuthoritySigner> =
+            match (server_config.key, &server_config.remote_signer) {
+                (Some(key), _) => Arc::new(key),
+                (None, Some(remote_signer)) => {
+                    let mut signer = RemoteSigner::from_config(remote_signer, metrics.clone())?;
+                    if let Some(alerts) = &alerts {
+                        signer = signer.with_alert_sink(alerts.clone());
+                    }
+                    info!("Signing with the remote signer at {}", remote_signer.url);
+                    Arc::new(signer)
+                }
+                (None, None) => {
+                    info!("Running in observer mode, signing is disabled");
+                    let server_handle = run_observer_server(
+                        &listen,
+                        metrics.clone(),
+                        metadata,
+                        server_config.admin_authorizer,
+                    );
+                    return Ok(running(
+                        server_handle,
+                        handles,
+                        cursor_resetter,
+                        quarantine_rechecker,
+                    ));
+                }
+            };
+        // Other committee members reach this node only through its url registered on chain
+        handles.push(spawn_logged_monitored_task!(RegisteredUrlChecker::new(
+            server_config.sui_client.clone(),
+            BridgeAuthorityPublicKeyBytes::from(signer.public_key()),
+            server_config.external_url,
+            metrics.clone(),
+            DEFAULT_URL_CHECK_INTERVAL,
+        )
+        .run()));
+        let mut limits_router = make_limits_router(server_config.sui_client.clone());
+        // The backlog and the executions are in the client storage
+        if let Some(store) = &store {
+            limits_router = limits_router.merge(make_drain_router(Arc::new(DrainEstimator::new(
+                store.clone(),
+                server_config.sui_client.clone(),
+                metrics.clone(),
+            ))));
+        }
+        handles.push(spawn_logged_monitored_task!(nonce_guard.clone().run(
+            server_config.sui_client.clone(),
+            server_config.eth_client.clone(),
+            server_config.eth_bridge_proxy_address,
+            DEFAULT_NONCE_REFRESH_INTERVAL,
+        )));
+        // Executed transfers are listed from the client storage
+        let transfers_router = store.clone().map(make_transfers_router);
+        // Signed actions are kept in the client storage
+        let signed_set = store.map(|store| Arc::new(SignedSet::new(store)));
+        let handler = Arc::new(BridgeRequestHandler::new(
+            signer,
+            server_config.sui_client.clone(),
+            server_config.eth_client.clone(),
+            server_config.extra_eth_confirmations,
+            server_config.governance_verifier,
+            server_config.sign_action_types,
+            server_config.verification_limits,
+            signed_set,
+            Some(nonce_guard),
+            metrics.clone(),
+        ));
+        if let Some(warm_up) = &server_config.warm_up {
+            SignerWarmUp::new(
+                handler.clone(),
+                server_config.sui_client,
+                server_config.eth_client,
+                server_config.eth_bridge_proxy_address,
+                warm_up,
+            )
+            .run()
+            .await;
+        }
+        // Cursors can only be reset by callers granted the operator role, on nodes that
+        // run the client
+        let admin_router = match (&cursor_resetter, &server_config.admin_authorizer) {
+            (Some(cursor_resetter), Some(authorizer)) => Some(make_admin_router(
+                cursor_resetter.clone(),
+                quarantine_rechecker.clone(),
+                storage_maintenance.clone(),
+                authorizer.clone(),
+            )),
+            _ => None,
+        };
+        // Signing requests are served on their own runtime, if any, so that they don't
+        // wait behind a backlog of the client
+        let server_runtime = server_config
+            .server_runtime_worker_threads
+            .map(ServerRuntime::new)
+            .transpose()?;
+        if let Some(server_runtime) = &server_runtime {
+            handles.push(spawn_logged_monitored_task!(RuntimeProbe::new(
+                SERVER_RUNTIME,
+                server_runtime.handle().clone(),
+                DEFAULT_RUNTIME_PROBE_INTERVAL,
+                metrics.clone(),
+            )
+            .run()));
+        }
+        let server_handle = {
+            let _runtime = server_runtime.as_ref().map(ServerRuntime::enter);
+            // The gRPC server shares the handler, and so its signing caches, with the JSON
+            // server
+            #[cfg(feature = "grpc")]
+            if let Some(grpc_listen_address) = server_config.grpc_listen_address {
+                handles.push(crate::server::grpc::run_grpc_server(
+                    &grpc_listen_address,
+                    handler.clone(),
+                    metrics.clone(),
+                    metadata.clone(),
+                ));
+            }
+            run_server(
+                &listen,
+                handler,
+                metrics.clone(),
+                metadata,
+                server_config.request_authenticator,
+                server_config.slow_request_threshold,
+                server_config.governance_body_limit,
+                admin_router,
+                Some(limits_router),
+                transfers_router,
+                server_config.admin_authorizer,
+            )
+        };
+        Ok(RunningBridgeNode {
+            server_runtime,
+            ..running(
+                server_handle,
+                handles,
+                cursor_resetter,
+                quarantine_rechecker,
+            )
+        })
+    }
+}
+
+/// A node started by `BridgeNodeBuilder`.
+pub struct RunningBridgeNode<C = SuiSdkClient, P = MeteredEthHttpProvier> {
+    server_handle: JoinHandle<()>,
+    handles: Vec<JoinHandle<()>>,
+    metrics: Arc<BridgeMetrics>,
+    listen: ListenConfig,
+    store: Option<Arc<BridgeOrchestratorTables>>,
+    certified_actions: Arc<CertifiedActions>,
+    cursor_resetter: Option<Arc<CursorResetter<C, P>>>,
+    quarantine_rechecker: Option<Arc<QuarantineRechecker<C, P>>>,
+    // Runs the server, and is shut down after it, when it has a dedicated runtime
+    server_runtime: Option<ServerRuntime>,
+}
+
+impl<C, P> RunningBridgeNode<C, P> {
+    pub fn listen_address(&self) -> SocketAddr {
+        self.listen.address
+    }
+
+    pub fn metrics(&self) -> &Arc<BridgeMetrics> {
+        &self.metrics
+    }
+
+    /// The health of the node as reported by `/health`.
+    pub fn health(&self) -> NodeHealth {
+        node_health(&self.metrics)
+    }
+
+    /// The readiness of the node as reported by `/ready`, e.g. for an embedding validator
+    /// to report it in its own health.
+    pub fn readiness(&self) -> BridgeReadinessHandle {
+        self.metrics.readiness.subscribe()
+    }
+
+    /// Returns a receiver of the actions certified from now on, as soon as the quorum of
+    /// signatures is reached. Nothing is received unless the client submits transactions.
+    pub fn subscribe_certified_actions(&self) -> CertifiedActionsReceiver {
+        self.certified_actions.subscribe()
+    }
+
+    /// Resets the cursors of the syncers. None when the client doesn't run.
+    pub fn cursor_resetter(&self) -> Option<&Arc<CursorResetter<C, P>>> {
+        self.cursor_resetter.as_ref()
+    }
+
+    /// Verifies the quarantined actions again. None unless the client submits transactions.
+    pub fn quarantine_rechecker(&self) -> Option<&Arc<QuarantineRechecker<C, P>>> {
+        self.quarantine_rechecker.as_ref()
+    }
+
+    /// Stops the server and every task of the node, and writes the buffered syncer
+    /// cursors.
+    pub async fn shutdown(self) {
+        for handle in self.handles.iter().chain([&self.server_handle]) {
+            handle.abort();
+        }
+        for handle in self.handles.into_iter().chain([self.server_handle]) {
+            let _ = handle.await;
+        }
+        if let Some(server_runtime) = self.server_runtime {
+            server_runtime.shutdown().await;
+        }
+        if let Some(store) = &self.store {
+            if let Err(e) = store.flush_cursors("shutdown") {
+                warn!("Couldn't write the buffered syncer cursors: {:?}", e);
+            }
+        }
+    }
+
+    /// The handle of the server. The other tasks of the node keep running in the
+    /// background.
+    pub fn into_server_handle(self) -> JoinHandle<()> {
+        let Some(server_runtime) = self.server_runtime else {
+            return self.server_handle;
+        };
+        // The dedicated runtime of the server lives as long as the server
+        let server_handle = self.server_handle;
+        tokio::spawn(async move {
+            let result = server_handle.await;
+            server_runtime.shutdown().await;
+            if let Err(e) = result {
+                if e.is_panic() {
+                    std::panic::resume_unwind(e.into_panic());
+                }
+            }
+        })
+    }
 }
 
 // TODO: is there a way to clean up the overrides after it's stored in DB?
-async fn start_client_components(
-    client_config: BridgeClientConfig,
+async fn start_client_components<C, P>(
+    client_config: BridgeClientConfig<C, P>,
+    store: Arc<BridgeOrchestratorTables>,
+    event_parsers: EventParsers,
+    alerts: Option<Arc<AlertSink>>,
+    nonce_guard: Arc<NonceGuard>,
+    certified_actions: Arc<CertifiedActions>,
     metrics: Arc<BridgeMetrics>,
-) -> anyhow::Result<Vec<JoinHandle<()>>> {
-    let store: std::sync::Arc<BridgeOrchestratorTables> =
-        BridgeOrchestratorTables::new(&client_config.db_path.join("client"));
+) -> anyhow::Result<(
+    Vec<JoinHandle<()>>,
+    Arc<CursorResetter<C, P>>,
+    Option<Arc<QuarantineRechecker<C, P>>>,
+)>
+where
+    C: SuiClientInner + 'static,
+    P: JsonRpcClient + Clone + 'static,
+{
+    if client_config.verify_storage {
+        let violations = store.verify_integrity();
+        for violation in &violations {
+            warn!("Storage integrity violation: {}", violation);
+        }
+        info!(
+            "Verified storage integrity, found {} violations",
+            violations.len()
+        );
+        metrics
+            .storage_integrity_violations
+            .set(violations.len() as i64);
+    }
     let sui_modules_to_watch = get_sui_modules_to_watch(
         &store,
         client_config.sui_bridge_module_last_processed_event_id_override,
@@ -87,21 +694,80 @@ async fn start_client_components(
         client_config.eth_contracts_start_block_override,
     );
 
+    let cursor_resetter = Arc::new(CursorResetter::new(
+        store.clone(),
+        client_config.sui_client.clone(),
+        client_config.eth_client.clone(),
+        &eth_contracts_to_watch,
+        &sui_modules_to_watch,
+    ));
+
     let sui_client = client_config.sui_client.clone();
+    let channels = client_config.channels;
 
     let mut all_handles = vec![];
-    let (task_handles, eth_events_rx, _) =
-        EthSyncer::new(client_config.eth_client.clone(), eth_contracts_to_watch)
-            .run(metrics.clone())
-            .await
-            .expect("Failed to start eth syncer");
+    if let Some(interval) = client_config.cursor_flush_interval {
+        store.enable_cursor_coalescing(metrics.clone());
+        all_handles.push(spawn_logged_monitored_task!(run_cursor_flusher(
+            store.clone(),
+            interval
+        )));
+    }
+    let event_stream = match &client_config.event_stream {
+        Some(event_stream_config) => {
+            let listener = EventStreamListener::bind(event_stream_config).await?;
+            let event_stream = Arc::new(EventStream::new(event_stream_config, metrics.clone()));
+            all_handles.push(spawn_logged_monitored_task!(event_stream
+                .clone()
+                .serve(listener)));
+            all_handles.push(spawn_logged_monitored_task!(event_stream
+                .clone()
+                .run_certified_actions(certified_actions.subscribe())));
+            Some(event_stream)
+        }
+        None => None,
+    };
+    let mut eth_syncer = EthSyncer::new(client_config.eth_client.clone(), eth_contracts_to_watch);
+    if let Some(bounds) = client_config.eth_poll_interval {
+        eth_syncer = eth_syncer.with_adaptive_interval(bounds);
+    }
+    let (task_handles, eth_events_rx, _) = eth_syncer
+        .with_event_topics(event_parsers.eth_topics())
+        .with_events_channel(
+            channels
+                .eth_events_channel_size
+                .unwrap_or(ETH_EVENTS_CHANNEL_SIZE),
+            channels
+                .events_send_timeout_seconds
+                .map(Duration::from_secs)
+                .unwrap_or(ETH_EVENTS_SEND_TIMEOUT),
+        )
+        .with_cursor_reloads(cursor_resetter.eth_cursor_reloads())
+        .with_supervision(RestartPolicy::default(), store.clone())
+        .run(metrics.clone())
+        .await
+        .expect("Failed to start eth syncer");
     all_handles.extend(task_handles);
 
-    let (task_handles, sui_events_rx) =
-        SuiSyncer::new(client_config.sui_client, sui_modules_to_watch)
-            .run(Duration::from_secs(2))
-            .await
-            .expect("Failed to start sui syncer");
+    let mut sui_syncer = SuiSyncer::new(client_config.sui_client, sui_modules_to_watch);
+    if let Some(bounds) = client_config.sui_poll_interval {
+        sui_syncer = sui_syncer.with_adaptive_interval(bounds);
+    }
+    let (task_handles, sui_events_rx) = sui_syncer
+        .with_events_channel(
+            channels
+                .sui_events_channel_size
+                .unwrap_or(SUI_EVENTS_CHANNEL_SIZE),
+            channels
+                .events_send_timeout_seconds
+                .map(Duration::from_secs)
+                .unwrap_or(SUI_EVENTS_SEND_TIMEOUT),
+        )
+        .with_cursor_reloads(cursor_resetter.sui_cursor_reloads())
+        .with_supervision(RestartPolicy::default(), store.clone())
+        .run(Duration::from_secs(2), metrics.clone())
+        .await
+        .expect("Failed to start sui syncer");
     all_handles.extend(task_handles);
 
     let committee = Arc::new(
@@ -110,12 +776,52 @@ async fn start_client_components(
             .await
             .expect("Failed to get committee"),
     );
-    let bridge_auth_agg = Arc::new(ArcSwap::from(Arc::new(BridgeAuthorityAggregator::new(
+    metrics.readiness.set_committee_loaded();
+    let mut bridge_auth_agg = BridgeAuthorityAggregator::new_with_https_required(
         committee,
-    ))));
+        metrics.clone(),
+        requires_https(client_config.sui_bridge_chain_id),
+    )
+    .with_connection_options(client_config.committee_connection_options.clone());
+    if let Some(options) = client_config.committee_request_hedging {
+        bridge_auth_agg = bridge_auth_agg
+            .with_request_hedging(Arc::new(RequestHedging::new(options, metrics.clone())));
+    }
+    // Known slow or failing authorities are requested last right after a restart
+    bridge_auth_agg.load_scores(&store);
+    let bridge_auth_agg = Arc::new(ArcSwap::from(Arc::new(bridge_auth_agg)));
+    all_handles.push(spawn_logged_monitored_task!(AuthorityScorePersister::new(
+        bridge_auth_agg.clone(),
+        store.clone(),
+        DEFAULT_SCORE_PERSIST_INTERVAL,
+    )
+    .run()));
+    let mut committee_prober = CommitteeProber::new(
+        bridge_auth_agg.clone(),
+        sui_client.clone(),
+        metrics.clone(),
+        DEFAULT_COMMITTEE_PROBE_INTERVAL,
+    );
+    if let Some(alerts) = &alerts {
+        committee_prober = committee_prober.with_alert_sink(alerts.clone());
+    }
+    all_handles.push(spawn_logged_monitored_task!(committee_prober.run()));
+    if let Some(interval) = client_config.committee_dns_refresh_interval {
+        all_handles.push(spawn_logged_monitored_task!(CommitteeDnsRefresher::new(
+            bridge_auth_agg.clone(),
+            Arc::new(SystemResolver),
+            metrics.clone(),
+            interval,
+        )
+        .run()));
+    }
     // TODO: should we use one query instead of two?
     let sui_token_type_tags = sui_client.get_token_id_map().await.unwrap();
     let is_bridge_paused = sui_client.is_bridge_paused().await.unwrap();
+    let route_pauses = Arc::new(RoutePauses::new(metrics.clone()));
+    route_pauses.update(paused_routes(
+        &sui_client.get_bridge_summary().await.unwrap(),
+    ));
 
     let (bridge_pause_tx, bridge_pause_rx) = tokio::sync::watch::channel(is_bridge_paused);
 
@@ -127,39 +833,279 @@ async fn start_client_components(
             .with_label_values(&["monitor_queue"]),
     );
     let sui_token_type_tags = Arc::new(ArcSwap::from(Arc::new(sui_token_type_tags)));
-    let bridge_action_executor = BridgeActionExecutor::new(
+    let transfer_minimums = Arc::new(TransferMinimums::default());
+    if let Some(min_transfer_usd_value) = client_config.min_transfer_usd_value {
+        let updater = TransferMinimumsUpdater::new(
+            sui_client.clone(),
+            client_config.eth_client.clone(),
+            client_config.eth_bridge_config_address,
+            min_transfer_usd_value,
+            transfer_minimums.clone(),
+        );
+        // Load the minimums before processing any events
+        let minimums = updater
+            .get_onchain_minimums()
+            .await
+            .expect("Failed to get minimum transfer amounts");
+        transfer_minimums.update(minimums);
+        let token_decimals = updater
+            .get_onchain_token_decimals()
+            .await
+            .expect("Failed to get token decimals");
+        transfer_minimums.update_token_decimals(token_decimals);
+        all_handles.push(spawn_logged_monitored_task!(updater.run()));
+    }
+    let notional_tracker = Arc::new(NotionalTracker::default());
+    all_handles.push(spawn_logged_monitored_task!(NotionalTrackerUpdater::new(
         sui_client.clone(),
-        bridge_auth_agg.clone(),
-        store.clone(),
-        client_config.key,
-        client_config.sui_address,
-        client_config.gas_object_ref.0,
-        sui_token_type_tags.clone(),
-        bridge_pause_rx,
+        notional_tracker.clone(),
         metrics.clone(),
     )
-    .await;
-
-    let monitor = BridgeMonitor::new(
+    .run()));
+    if let Some(reconciliation_config) = client_config.reconciliation {
+        let mut reconciler = BridgeReconciler::new(
+            sui_client.clone(),
+            client_config.eth_client.clone(),
+            client_config.eth_bridge_proxy_address,
+            store.clone(),
+            client_config.sui_bridge_chain_id,
+            client_config.eth_bridge_chain_id,
+            reconciliation_config
+                .interval_seconds
+                .map(Duration::from_secs)
+                .unwrap_or(DEFAULT_RECONCILIATION_INTERVAL),
+            reconciliation_config
+                .sample_size
+                .unwrap_or(DEFAULT_RECONCILIATION_SAMPLE_SIZE),
+            reconciliation_config
+                .max_rpc_queries_per_second
+                .unwrap_or(DEFAULT_RECONCILIATION_MAX_RPC_QUERIES_PER_SECOND),
+            metrics.clone(),
+        )
+        .with_status_batch_size(client_config.onchain_status_batch_size);
+        if let Some(alerts) = &alerts {
+            reconciler = reconciler.with_alert_sink(alerts.clone());
+        }
+        all_handles.push(spawn_logged_monitored_task!(reconciler.run()));
+    }
+    if let Some(claim_tracking_config) = &client_config.claim_tracking {
+        all_handles.push(spawn_logged_monitored_task!(ClaimTracker::new(
+            client_config.eth_client.clone(),
+            client_config.eth_bridge_proxy_address,
+            store.clone(),
+            client_config.sui_bridge_chain_id,
+            client_config.eth_bridge_chain_id,
+            claim_tracking_config
+                .interval_seconds
+                .map(Duration::from_secs)
+                .unwrap_or(DEFAULT_CLAIM_TRACKING_INTERVAL),
+            claim_tracking_config
+                .max_backoff_seconds
+                .map(Duration::from_secs)
+                .unwrap_or(DEFAULT_CLAIM_TRACKING_MAX_BACKOFF),
+            claim_tracking_config
+                .max_rpc_queries_per_second
+                .unwrap_or(DEFAULT_CLAIM_TRACKING_MAX_RPC_QUERIES_PER_SECOND),
+            metrics.clone(),
+        )
+        .with_status_batch_size(client_config.onchain_status_batch_size)
+        .run()));
+    }
+    if let Some(pruning_config) = &client_config.pruning {
+        let archive = pruning_config
+            .archive
+            .as_ref()
+            .map(ArchiveConfig::open)
+            .transpose()?;
+        all_handles.push(spawn_logged_monitored_task!(ExecutedActionPruner::new(
+            store.clone(),
+            archive,
+            pruning_config
+                .retain_executed_actions
+                .unwrap_or(DEFAULT_RETAINED_EXECUTED_ACTIONS),
+            pruning_config
+                .batch_size
+                .unwrap_or(DEFAULT_PRUNING_BATCH_SIZE),
+            metrics.clone(),
+        )
+        .run(
+            pruning_config
+                .interval_seconds
+                .map(Duration::from_secs)
+                .unwrap_or(DEFAULT_PRUNING_INTERVAL)
+        )));
+    }
+    let mut monitor = BridgeMonitor::new(
         sui_client.clone(),
         monitor_rx,
         bridge_auth_agg.clone(),
         bridge_pause_tx,
-        sui_token_type_tags,
+        sui_token_type_tags.clone(),
+        transfer_minimums.clone(),
+        notional_tracker.clone(),
     );
+    if let Some(alerts) = &alerts {
+        monitor = monitor.with_alert_sink(alerts.clone());
+    }
+    monitor = monitor.with_route_pauses(route_pauses.clone());
+    if let Some(authority_public_key) = &client_config.authority_public_key {
+        let (signature_inclusion, checker) = SignatureInclusion::new(
+            authority_public_key.clone(),
+            client_config.min_signature_inclusion_percent,
+            metrics.clone(),
+        );
+        all_handles.push(spawn_logged_monitored_task!(checker.run(sui_client.clone())));
+        monitor = monitor.with_signature_inclusion(signature_inclusion);
+    }
     all_handles.push(spawn_logged_monitored_task!(monitor.run()));
 
-    let orchestrator = BridgeOrchestrator::new(
-        sui_client,
+    let mut orchestrator = BridgeOrchestrator::new(
+        sui_client.clone(),
         sui_events_rx,
         eth_events_rx,
         store.clone(),
         monitor_tx,
-        metrics,
-    );
+        transfer_minimums,
+        notional_tracker,
+        metrics.clone(),
+    )
+    .with_timestamp_sanitizer(client_config.timestamp_sanitizer)
+    .with_status_batch_size(client_config.onchain_status_batch_size)
+    .with_event_parsers(event_parsers)
+    .with_nonce_guard(nonce_guard);
+    if let Some(event_stream) = &event_stream {
+        orchestrator = orchestrator.with_event_stream(event_stream.clone());
+    }
+
+    let quarantine_rechecker = match client_config.account {
+        Some(account) => {
+            // Quarantined actions that are valid again are handed back to the executor
+            let (released_tx, released_rx) = mysten_metrics::metered_channel::channel(
+                CHANNEL_SIZE,
+                &mysten_metrics::get_metrics()
+                    .unwrap()
+                    .channel_inflight
+                    .with_label_values(&["quarantine_released_queue"]),
+            );
+            orchestrator = orchestrator.with_released_actions(released_rx);
+            let quarantine_rechecker = Arc::new(QuarantineRechecker::new(
+                store.clone(),
+                sui_client.clone(),
+                client_config.eth_client.clone(),
+                released_tx,
+                metrics.clone(),
+            ));
+            if let Some(recheck_config) = &client_config.quarantine_recheck {
+                all_handles.push(spawn_logged_monitored_task!(quarantine_rechecker
+                    .clone()
+                    .run(
+                        recheck_config
+                            .interval_seconds
+                            .map(Duration::from_secs)
+                            .unwrap_or(DEFAULT_QUARANTINE_RECHECK_INTERVAL),
+                        recheck_config
+                            .max_actions_per_pass
+                            .unwrap_or(DEFAULT_QUARANTINE_RECHECK_MAX_ACTIONS),
+                    )));
+            }
+            let tx_journal = client_config
+                .tx_journal
+                .map(|journal_config| {
+                    TxJournal::new(
+                        &journal_config.path,
+                        journal_config
+                            .max_file_size_bytes
+                            .unwrap_or(DEFAULT_JOURNAL_MAX_FILE_SIZE),
+                        journal_config.fatal_on_failure,
+                    )
+                    .map(Arc::new)
+                })
+                .transpose()?;
+            let gas_lock = Arc::new(tokio::sync::Mutex::new(()));
+            if let Some(gas_funding) = client_config.gas_funding {
+                let gas_top_up = GasTopUp::new(
+                    sui_client.clone(),
+                    account.key.copy(),
+                    account.gas_object_ref.0,
+                    gas_lock.clone(),
+                    gas_funding.key,
+                    gas_funding.gas_object_id,
+                    gas_funding.config.threshold_balance,
+                    gas_funding.config.target_balance,
+                    gas_funding
+                        .config
+                        .check_interval_seconds
+                        .map(Duration::from_secs)
+                        .unwrap_or(DEFAULT_GAS_TOP_UP_CHECK_INTERVAL),
+                    gas_funding
+                        .config
+                        .cooldown_seconds
+                        .map(Duration::from_secs)
+                        .unwrap_or(DEFAULT_GAS_TOP_UP_COOLDOWN),
+                    metrics.clone(),
+                )?;
+                all_handles.push(spawn_logged_monitored_task!(gas_top_up.run()));
+            }
+            let tx_submitter = client_config.tx_fullnodes.map(|fullnodes| {
+                SuiTxSubmitter::concurrent(sui_client.clone(), fullnodes, metrics.clone())
+            });
+            let shadow = (client_config.mode == BridgeNodeMode::Shadow)
+                .then(|| ShadowComparator::new(sui_client.clone(), store.clone(), metrics.clone()));
+            let mut bridge_action_executor = BridgeActionExecutor::new(
+                sui_client,
+                bridge_auth_agg,
+                store,
+                account.key,
+                account.sui_address,
+                account.gas_object_ref.0,
+                sui_token_type_tags,
+                bridge_pause_rx,
+                tx_journal,
+                metrics,
+            )
+            .await
+            .with_channel_size(channels.executor_channel_size.unwrap_or(CHANNEL_SIZE))
+            .with_signing_window(
+                channels
+                    .executor_signing_window
+                    .unwrap_or(DEFAULT_SIGNING_WINDOW),
+            )
+            .with_execute_action_types(client_config.execute_action_types)
+            .with_gas_lock(gas_lock)
+            .with_route_pauses(route_pauses, client_config.skip_signing_paused_routes)
+            .with_certified_actions(certified_actions);
+            if let Some(tx_submitter) = tx_submitter {
+                bridge_action_executor = bridge_action_executor.with_tx_submitter(tx_submitter);
+            }
+            if let Some(shadow) = shadow {
+                bridge_action_executor = bridge_action_executor.with_shadow_comparator(shadow);
+            }
+            if let Some(alerts) = alerts {
+                bridge_action_executor = bridge_action_executor.with_alert_sink(alerts);
+            }
+            if let Some(event_stream) = event_stream {
+                bridge_action_executor = bridge_action_executor.with_event_stream(event_stream);
+            }
 
-    all_handles.extend(orchestrator.run(bridge_action_executor).await);
-    Ok(all_handles)
+            all_handles.extend(orchestrator.run(bridge_action_executor).await);
+            Some(quarantine_rechecker)
+        }
+        // Observer mode, the actions are verified on chain instead of being executed
+        None => {
+            let mut observer = BridgeActionObserver::new(
+                sui_client,
+                store,
+                channels.executor_channel_size.unwrap_or(CHANNEL_SIZE),
+                metrics,
+            );
+            if let Some(event_stream) = event_stream {
+                observer = observer.with_event_stream(event_stream);
+            }
+            all_handles.extend(orchestrator.run(observer).await);
+            None
+        }
+    };
+    Ok((all_handles, cursor_resetter, quarantine_rechecker))
 }
 
 fn get_sui_modules_to_watch(
@@ -244,13 +1190,22 @@ mod tests {
     use crate::config::BridgeNodeConfig;
     use crate::config::EthConfig;
     use crate::config::SuiConfig;
+    use crate::crypto::BridgeAuthorityKeyPair;
     use crate::e2e_tests::test_utils::BridgeTestCluster;
     use crate::e2e_tests::test_utils::BridgeTestClusterBuilder;
+    use crate::eth_client::EthClient;
+    use crate::eth_mock_provider::EthMockProvider;
+    use crate::events::{MoveTokenDepositedEvent, SuiToEthTokenBridgeV1};
+    use crate::sui_client::SuiClient;
+    use crate::sui_mock_client::SuiMockClient;
+    use crate::types::{BridgeAction, SignedBridgeAction};
     use crate::utils::wait_for_server_to_be_up;
     use fastcrypto::secp256k1::Secp256k1KeyPair;
+    use std::collections::HashSet;
     use sui_config::local_ip_utils::get_available_port;
+    use sui_json_rpc_types::SuiEvent;
     use sui_types::base_types::SuiAddress;
-    use sui_types::bridge::BridgeChainId;
+    use sui_types::bridge::{BridgeChainId, TOKEN_ID_USDC};
     use sui_types::crypto::get_key_pair;
     use sui_types::crypto::EncodeDecodeBase64;
     use sui_types::crypto::KeypairTraits;
@@ -399,6 +1354,173 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn test_bridge_node_builder() {
+        telemetry_subscribers::init_for_testing();
+        let registry = Registry::new();
+        mysten_metrics::init_metrics(&registry);
+        let sui_client_mock = SuiMockClient::default();
+        let temp_dir = tempdir().unwrap();
+        let store = BridgeOrchestratorTables::new(temp_dir.path());
+        let port = get_available_port("127.0.0.1");
+        let server_config = BridgeServerConfig::new(
+            Arc::new(SuiClient::new_for_testing(sui_client_mock.clone())),
+            Arc::new(EthClient::new_mocked(
+                EthMockProvider::default(),
+                HashSet::new(),
+            )),
+            ListenConfig::new(SocketAddr::new(
+                std::net::IpAddr::V4(std::net::Ipv4Addr::LOCALHOST),
+                port,
+            )),
+            EthAddress::random(),
+        );
+        let (_, key): (_, BridgeAuthorityKeyPair) = get_key_pair();
+        let node = BridgeNodeBuilder::new(server_config)
+            .with_authority_key(key)
+            .with_store(store.clone())
+            .with_registry(&registry)
+            .build()
+            .await
+            .unwrap();
+        // Signing only, the client doesn't run
+        assert!(node.cursor_resetter().is_none());
+        let server_url = format!("http://{}", node.listen_address());
+        wait_for_server_to_be_up(server_url.clone(), 5)
+            .await
+            .unwrap();
+
+        // The node describes itself, the Eth mock doesn't answer the chain id
+        let info: Option<crate::api_types::RuntimeInfo> =
+            reqwest::get(format!("{}{}", server_url, crate::server::ADMIN_INFO_PATH))
+                .await
+                .unwrap()
+                .json()
+                .await
+                .unwrap();
+        let info = info.unwrap();
+        assert_eq!(info.mode, "committee");
+        assert!(info.authority_public_key.is_some());
+        assert_eq!(info.eth_chain_id, None);
+        assert_eq!(
+            info.storage_schema_version,
+            Some(crate::storage::STORAGE_SCHEMA_VERSION)
+        );
+        assert!(info.config.is_none());
+
+        // A transfer from Sui to Eth is signed
+        let emitted_event = MoveTokenDepositedEvent {
+            seq_num: 1,
+            source_chain: BridgeChainId::SuiCustom as u8,
+            sender_address: SuiAddress::random_for_testing_only().to_vec(),
+            target_chain: BridgeChainId::EthCustom as u8,
+            target_address: EthAddress::random().as_bytes().to_vec(),
+            token_type: TOKEN_ID_USDC,
+            amount_sui_adjusted: 12345,
+        };
+        let mut sui_event = SuiEvent::random_for_testing();
+        sui_event.type_ = SuiToEthTokenBridgeV1.get().unwrap().clone();
+        sui_event.bcs = bcs::to_bytes(&emitted_event).unwrap();
+        let sui_tx_digest = sui_event.id.tx_digest;
+        sui_client_mock.add_events_by_tx_digest(sui_tx_digest, vec![sui_event]);
+        let signed: SignedBridgeAction = reqwest::get(format!(
+            "{}/sign/bridge_tx/sui/eth/{}/0",
+            server_url, sui_tx_digest
+        ))
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+        let BridgeAction::SuiToEthBridgeAction(action) = signed.data() else {
+            panic!("Unexpected action {:?}", signed.data());
+        };
+        assert_eq!(action.sui_bridge_event.amount_sui_adjusted, 12345);
+        // and kept in the injected store
+        assert_eq!(
+            store
+                .get_signed_set_buckets()
+                .values()
+                .map(|bucket| bucket.count)
+                .sum::<u64>(),
+            1
+        );
+        assert!(node.health().pipelines.is_some());
+
+        node.shutdown().await;
+        assert!(reqwest::get(&server_url).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_bridge_node_with_dedicated_server_runtime() {
+        telemetry_subscribers::init_for_testing();
+        let registry = Registry::new();
+        mysten_metrics::init_metrics(&registry);
+        let sui_client_mock = SuiMockClient::default();
+        let port = get_available_port("127.0.0.1");
+        let mut server_config = BridgeServerConfig::new(
+            Arc::new(SuiClient::new_for_testing(sui_client_mock.clone())),
+            Arc::new(EthClient::new_mocked(
+                EthMockProvider::default(),
+                HashSet::new(),
+            )),
+            ListenConfig::new(SocketAddr::new(
+                std::net::IpAddr::V4(std::net::Ipv4Addr::LOCALHOST),
+                port,
+            )),
+            EthAddress::random(),
+        );
+        server_config.server_runtime_worker_threads = Some(1);
+        let (_, key): (_, BridgeAuthorityKeyPair) = get_key_pair();
+        let node = BridgeNodeBuilder::new(server_config)
+            .with_authority_key(key)
+            .with_registry(&registry)
+            .build()
+            .await
+            .unwrap();
+        let server_url = format!("http://{}", node.listen_address());
+        wait_for_server_to_be_up(server_url.clone(), 5)
+            .await
+            .unwrap();
+
+        // Signing requests are served by the runtime of the server
+        let emitted_event = MoveTokenDepositedEvent {
+            seq_num: 1,
+            source_chain: BridgeChainId::SuiCustom as u8,
+            sender_address: SuiAddress::random_for_testing_only().to_vec(),
+            target_chain: BridgeChainId::EthCustom as u8,
+            target_address: EthAddress::random().as_bytes().to_vec(),
+            token_type: TOKEN_ID_USDC,
+            amount_sui_adjusted: 12345,
+        };
+        let mut sui_event = SuiEvent::random_for_testing();
+        sui_event.type_ = SuiToEthTokenBridgeV1.get().unwrap().clone();
+        sui_event.bcs = bcs::to_bytes(&emitted_event).unwrap();
+        let sui_tx_digest = sui_event.id.tx_digest;
+        sui_client_mock.add_events_by_tx_digest(sui_tx_digest, vec![sui_event]);
+        let signed: SignedBridgeAction = reqwest::get(format!(
+            "{}/sign/bridge_tx/sui/eth/{}/0",
+            server_url, sui_tx_digest
+        ))
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+        assert_eq!(signed.data().seq_number(), 1);
+        assert_eq!(
+            node.metrics()
+                .runtime_workers
+                .with_label_values(&[SERVER_RUNTIME])
+                .get(),
+            1
+        );
+
+        // Both runtimes stop
+        node.shutdown().await;
+        assert!(reqwest::get(&server_url).await.is_err());
+    }
+
     #[tokio::test(flavor = "multi_thread", worker_threads = 8)]
     async fn test_starting_bridge_node() {
         telemetry_subscribers::init_for_testing();
@@ -414,14 +1536,24 @@ mod tests {
 
         let config = BridgeNodeConfig {
             server_listen_port,
+            server_listen_address: None,
+            server_dual_stack: false,
+            server_reuse_port: false,
+            server_dedicated_runtime: false,
+            server_runtime_worker_threads: None,
             metrics_port: get_available_port("127.0.0.1"),
-            bridge_authority_key_path: tmp_dir.join(authority_key_path),
+            mode: None,
+            bridge_authority_key_path: Some(tmp_dir.join(authority_key_path)),
+            remote_signer: None,
             sui: SuiConfig {
                 sui_rpc_url: bridge_test_cluster.sui_rpc_url(),
                 sui_bridge_chain_id: BridgeChainId::SuiCustom as u8,
                 bridge_client_key_path: None,
                 bridge_client_gas_object: None,
                 sui_bridge_module_last_processed_event_id_override: None,
+                disable_object_cache: false,
+                sui_rpc_max_attempts: None,
+                tx_submission: None,
             },
             eth: EthConfig {
                 eth_rpc_url: bridge_test_cluster.eth_rpc_url(),
@@ -429,12 +1561,57 @@ mod tests {
                 eth_bridge_chain_id: BridgeChainId::EthCustom as u8,
                 eth_contracts_start_block_fallback: None,
                 eth_contracts_start_block_override: None,
+                extra_eth_confirmations: None,
+                custom_evm_chains: None,
+                fee_history_interval_seconds: None,
+                verify_eth_receipts: None,
+                eth_receipts_root_rpc_url: None,
             },
             approved_governance_actions: vec![],
+            approved_governance_digests: vec![],
             run_client: false,
             db_path: None,
+            aux_db_path: None,
+            storage_encryption_key_path: None,
+            verify_storage: false,
+            storage_cursor_flush_ms: None,
+            min_transfer_usd_value: None,
+            tx_journal: None,
+            reconciliation: None,
+            claim_tracking: None,
+            channels: None,
+            adaptive_polling: None,
+            gas_funding: None,
+            alerts: None,
+            timestamps: None,
+            clock_drift: None,
+            event_stream: None,
+            quarantine_recheck: None,
+            onchain_status_batch_size: None,
+            pruning: None,
+            storage_maintenance: None,
+            min_signature_inclusion_percent: None,
+            skip_signing_paused_routes: false,
+            committee_dns_refresh_seconds: None,
+            committee_connections: None,
+            committee_request_hedging: None,
+            refuse_committee_anomalies: false,
+            sign_action_types: None,
+            execute_action_types: None,
+            request_auth: None,
+            admin_auth: None,
+            slow_request_threshold_ms: None,
+            governance_body_limit_bytes: None,
+            max_requester_labels: None,
+            grpc_listen_address: None,
+            external_url: None,
+            warm_up: None,
+            verification_limits: None,
+            logging: None,
             metrics_key_pair: default_ed25519_key_pair(),
             metrics: None,
+            telemetry: None,
+            allow_mismatched_bridge_contracts: false,
         };
         // Spawn bridge node in memory
         let _handle = run_bridge_node(
@@ -476,8 +1653,15 @@ mod tests {
 
         let config = BridgeNodeConfig {
             server_listen_port,
+            server_listen_address: None,
+            server_dual_stack: false,
+            server_reuse_port: false,
+            server_dedicated_runtime: false,
+            server_runtime_worker_threads: None,
             metrics_port: get_available_port("127.0.0.1"),
-            bridge_authority_key_path: tmp_dir.join(authority_key_path),
+            mode: None,
+            bridge_authority_key_path: Some(tmp_dir.join(authority_key_path)),
+            remote_signer: None,
             sui: SuiConfig {
                 sui_rpc_url: bridge_test_cluster.sui_rpc_url(),
                 sui_bridge_chain_id: BridgeChainId::SuiCustom as u8,
@@ -487,6 +1671,9 @@ mod tests {
                     tx_digest: TransactionDigest::random(),
                     event_seq: 0,
                 }),
+                disable_object_cache: false,
+                sui_rpc_max_attempts: None,
+                tx_submission: None,
             },
             eth: EthConfig {
                 eth_rpc_url: bridge_test_cluster.eth_rpc_url(),
@@ -494,12 +1681,57 @@ mod tests {
                 eth_bridge_chain_id: BridgeChainId::EthCustom as u8,
                 eth_contracts_start_block_fallback: Some(0),
                 eth_contracts_start_block_override: None,
+                extra_eth_confirmations: None,
+                custom_evm_chains: None,
+                fee_history_interval_seconds: None,
+                verify_eth_receipts: None,
+                eth_receipts_root_rpc_url: None,
             },
             approved_governance_actions: vec![],
+            approved_governance_digests: vec![],
             run_client: true,
             db_path: Some(db_path),
+            aux_db_path: None,
+            storage_encryption_key_path: None,
+            verify_storage: false,
+            storage_cursor_flush_ms: None,
+            min_transfer_usd_value: None,
+            tx_journal: None,
+            reconciliation: None,
+            claim_tracking: None,
+            channels: None,
+            adaptive_polling: None,
+            gas_funding: None,
+            alerts: None,
+            timestamps: None,
+            clock_drift: None,
+            event_stream: None,
+            quarantine_recheck: None,
+            onchain_status_batch_size: None,
+            pruning: None,
+            storage_maintenance: None,
+            min_signature_inclusion_percent: None,
+            skip_signing_paused_routes: false,
+            committee_dns_refresh_seconds: None,
+            committee_connections: None,
+            committee_request_hedging: None,
+            refuse_committee_anomalies: false,
+            sign_action_types: None,
+            execute_action_types: None,
+            request_auth: None,
+            admin_auth: None,
+            slow_request_threshold_ms: None,
+            governance_body_limit_bytes: None,
+            max_requester_labels: None,
+            grpc_listen_address: None,
+            external_url: None,
+            warm_up: None,
+            verification_limits: None,
+            logging: None,
             metrics_key_pair: default_ed25519_key_pair(),
             metrics: None,
+            telemetry: None,
+            allow_mismatched_bridge_contracts: false,
         };
         // Spawn bridge node in memory
         let _handle = run_bridge_node(
@@ -552,8 +1784,15 @@ mod tests {
 
         let config = BridgeNodeConfig {
             server_listen_port,
+            server_listen_address: None,
+            server_dual_stack: false,
+            server_reuse_port: false,
+            server_dedicated_runtime: false,
+            server_runtime_worker_threads: None,
             metrics_port: get_available_port("127.0.0.1"),
-            bridge_authority_key_path: tmp_dir.join(authority_key_path),
+            mode: None,
+            bridge_authority_key_path: Some(tmp_dir.join(authority_key_path)),
+            remote_signer: None,
             sui: SuiConfig {
                 sui_rpc_url: bridge_test_cluster.sui_rpc_url(),
                 sui_bridge_chain_id: BridgeChainId::SuiCustom as u8,
@@ -563,6 +1802,9 @@ mod tests {
                     tx_digest: TransactionDigest::random(),
                     event_seq: 0,
                 }),
+                disable_object_cache: false,
+                sui_rpc_max_attempts: None,
+                tx_submission: None,
             },
             eth: EthConfig {
                 eth_rpc_url: bridge_test_cluster.eth_rpc_url(),
@@ -570,12 +1812,57 @@ mod tests {
                 eth_bridge_chain_id: BridgeChainId::EthCustom as u8,
                 eth_contracts_start_block_fallback: Some(0),
                 eth_contracts_start_block_override: Some(0),
+                extra_eth_confirmations: None,
+                custom_evm_chains: None,
+                fee_history_interval_seconds: None,
+                verify_eth_receipts: None,
+                eth_receipts_root_rpc_url: None,
             },
             approved_governance_actions: vec![],
+            approved_governance_digests: vec![],
             run_client: true,
             db_path: Some(db_path),
+            aux_db_path: None,
+            storage_encryption_key_path: None,
+            verify_storage: false,
+            storage_cursor_flush_ms: None,
+            min_transfer_usd_value: None,
+            tx_journal: None,
+            reconciliation: None,
+            claim_tracking: None,
+            channels: None,
+            adaptive_polling: None,
+            gas_funding: None,
+            alerts: None,
+            timestamps: None,
+            clock_drift: None,
+            event_stream: None,
+            quarantine_recheck: None,
+            onchain_status_batch_size: None,
+            pruning: None,
+            storage_maintenance: None,
+            min_signature_inclusion_percent: None,
+            skip_signing_paused_routes: false,
+            committee_dns_refresh_seconds: None,
+            committee_connections: None,
+            committee_request_hedging: None,
+            refuse_committee_anomalies: false,
+            sign_action_types: None,
+            execute_action_types: None,
+            request_auth: None,
+            admin_auth: None,
+            slow_request_threshold_ms: None,
+            governance_body_limit_bytes: None,
+            max_requester_labels: None,
+            grpc_listen_address: None,
+            external_url: None,
+            warm_up: None,
+            verification_limits: None,
+            logging: None,
             metrics_key_pair: default_ed25519_key_pair(),
             metrics: None,
+            telemetry: None,
+            allow_mismatched_bridge_contracts: false,
         };
         // Spawn bridge node in memory
         let _handle = run_bridge_node(
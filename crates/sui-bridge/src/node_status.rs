@@ -0,0 +1,586 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Summary of a running node for `sui-bridge-cli node-status`, put together from its
+//! routes: its runtime info, readiness, sync of both pipelines, the clock, the registered
+//! url, the executor and its gas coin, the pending actions and the committee as seen by
+//! the node. Each route
+//! is fetched on its own, and the summary shows whatever could be fetched, e.g. a node
+//! that doesn't run the client has no pending actions. Parts beyond the thresholds below
+//! are warnings.
+
+use crate::api_types::{
+    CommitteeMemberScore, DrainEstimate, ExecutorStage, ExecutorState, NodeHealth,
+    NodeStatusReport, NotReadyReason, PipelineStatus, ReadinessState, RegisteredUrlStatus,
+    RuntimeInfo,
+};
+use crate::doctor::{format_check_table, CheckResult, CheckStatus, LOW_GAS_BALANCE_MIST};
+use crate::server::{
+    ADMIN_COMMITTEE_SCORES_PATH, ADMIN_DRAIN_ESTIMATE_PATH, ADMIN_EXECUTOR_STATE_PATH,
+    ADMIN_INFO_PATH, HEALTH_PATH, READY_PATH,
+};
+use reqwest::StatusCode;
+use serde::de::DeserializeOwned;
+use std::collections::BTreeMap;
+use sui_types::bridge::BridgeChainId;
+
+/// A pipeline whose last successful query is older than this is lagging.
+pub const SYNC_LAG_WARN_MS: u64 = 2 * 60 * 1000;
+/// More pending actions than this are a backlog.
+pub const PENDING_ACTIONS_WARN: u64 = 100;
+/// An action in the same executor stage for longer than this is stuck.
+pub const STUCK_ACTION_WARN_MS: u64 = 5 * 60 * 1000;
+/// Committee members failing this share of the requests (in basis points) or more.
+pub const MEMBER_FAILURE_RATE_WARN_BPS: u64 = 1000;
+
+/// Fetches the routes of the node at `url`, e.g. `http://127.0.0.1:9191`, concurrently.
+pub async fn fetch_node_status(
+    client: &reqwest::Client,
+    url: &str,
+    now_ms: u64,
+) -> NodeStatusReport {
+    let base = url.trim_end_matches('/');
+    let (info, health, readiness, executor, drain, committee_scores) = tokio::join!(
+        fetch::<Option<RuntimeInfo>>(client, base, ADMIN_INFO_PATH),
+        fetch::<NodeHealth>(client, base, HEALTH_PATH),
+        fetch::<ReadinessState>(client, base, READY_PATH),
+        fetch::<ExecutorState>(client, base, ADMIN_EXECUTOR_STATE_PATH),
+        fetch::<DrainEstimate>(client, base, ADMIN_DRAIN_ESTIMATE_PATH),
+        fetch::<BTreeMap<String, CommitteeMemberScore>>(client, base, ADMIN_COMMITTEE_SCORES_PATH),
+    );
+    let mut unavailable = BTreeMap::new();
+    let mut report = NodeStatusReport {
+        url: url.to_string(),
+        now_ms,
+        health: available(HEALTH_PATH, health, &mut unavailable),
+        readiness: available(READY_PATH, readiness, &mut unavailable),
+        executor: available(ADMIN_EXECUTOR_STATE_PATH, executor, &mut unavailable),
+        drain: available(ADMIN_DRAIN_ESTIMATE_PATH, drain, &mut unavailable),
+        committee_scores: available(
+            ADMIN_COMMITTEE_SCORES_PATH,
+            committee_scores,
+            &mut unavailable,
+        ),
+        info: available(ADMIN_INFO_PATH, info, &mut unavailable).flatten(),
+        unavailable,
+        warnings: vec![],
+    };
+    report.warnings = node_status_checks(&report)
+        .into_iter()
+        .filter(|check| check.status != CheckStatus::Pass)
+        .map(|check| format!("{}: {}", check.name, check.detail))
+        .collect();
+    report
+}
+
+async fn fetch<T: DeserializeOwned>(
+    client: &reqwest::Client,
+    base: &str,
+    path: &str,
+) -> Result<T, String> {
+    let response = client
+        .get(format!("{}{}", base, path))
+        .send()
+        .await
+        .map_err(|e| e.to_string())?;
+    let status = response.status();
+    // `/ready` answers 503 with the reasons while the node is not ready
+    if !status.is_success() && !(path == READY_PATH && status == StatusCode::SERVICE_UNAVAILABLE) {
+        return Err(format!("HTTP {}", status));
+    }
+    response.json().await.map_err(|e| e.to_string())
+}
+
+fn available<T>(
+    path: &str,
+    result: Result<T, String>,
+    unavailable: &mut BTreeMap<String, String>,
+) -> Option<T> {
+    match result {
+        Ok(value) => Some(value),
+        Err(e) => {
+            unavailable.insert(path.to_string(), e);
+            None
+        }
+    }
+}
+
+/// Renders `report` as a table, with the statuses colored for a terminal when `color` is
+/// set.
+pub fn format_node_status(report: &NodeStatusReport, color: bool) -> String {
+    let info = report
+        .info
+        .as_ref()
+        .map(|info| format!("{}\n", format_runtime_info(info)))
+        .unwrap_or_default();
+    format!(
+        "Bridge node {}\n{}{}",
+        report.url,
+        info,
+        format_check_table(&node_status_checks(report), color)
+    )
+}
+
+/// One line with the version, mode, chains and committee epoch of the node.
+pub fn format_runtime_info(info: &RuntimeInfo) -> String {
+    let chain = |chain_id: Option<u8>| match chain_id {
+        Some(chain_id) => BridgeChainId::try_from(chain_id)
+            .map_or(format!("chain {}", chain_id), |chain_id| {
+                format!("{:?}", chain_id)
+            }),
+        None => "unknown chain".to_string(),
+    };
+    format!(
+        "version {}, {} mode, {} <-> {}, committee epoch {}",
+        info.version.as_deref().unwrap_or("unknown"),
+        info.mode,
+        chain(info.sui_chain_id),
+        chain(info.eth_chain_id),
+        info.committee_epoch
+            .map_or("unknown".to_string(), |epoch| epoch.to_string()),
+    )
+}
+
+/// Checks the parts of `report` against the thresholds. Parts whose route couldn't be
+/// fetched are warnings.
+pub fn node_status_checks(report: &NodeStatusReport) -> Vec<CheckResult> {
+    let unavailable = |name, path: &str| {
+        CheckResult::warn(
+            name,
+            format!(
+                "unavailable: {}",
+                report.unavailable.get(path).map_or("", String::as_str)
+            ),
+        )
+    };
+    let mut checks = vec![];
+
+    checks.push(match &report.readiness {
+        Some(readiness) if readiness.ready => CheckResult::pass("ready", "ready"),
+        Some(readiness) => CheckResult::warn(
+            "ready",
+            format!(
+                "not ready: {}",
+                readiness
+                    .reasons
+                    .iter()
+                    .map(format_not_ready_reason)
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ),
+        ),
+        None => unavailable("ready", READY_PATH),
+    });
+
+    match &report.health {
+        Some(health) => {
+            match &health.pipelines {
+                Some(pipelines) => {
+                    for (name, pipeline) in
+                        [("sui_sync", &pipelines.sui), ("eth_sync", &pipelines.eth)]
+                    {
+                        let lag_ms = pipeline
+                            .last_success_ms
+                            .map(|last_success_ms| report.now_ms.saturating_sub(last_success_ms));
+                        let mut detail = format!(
+                            "{}, {}",
+                            format_pipeline_status(pipeline.status),
+                            lag_ms.map_or("never synced".to_string(), |lag_ms| format!(
+                                "last synced {} ago",
+                                format_ms(lag_ms)
+                            ))
+                        );
+                        if pipeline.consecutive_failures > 0 {
+                            detail.push_str(&format!(
+                                ", {} failed queries: {}",
+                                pipeline.consecutive_failures,
+                                pipeline.last_error.as_deref().unwrap_or_default()
+                            ));
+                        }
+                        checks.push(match pipeline.status {
+                            PipelineStatus::Down => CheckResult::fail(name, detail),
+                            PipelineStatus::Healthy
+                                if lag_ms.is_some_and(|lag_ms| lag_ms <= SYNC_LAG_WARN_MS) =>
+                            {
+                                CheckResult::pass(name, detail)
+                            }
+                            _ => CheckResult::warn(name, detail),
+                        });
+                    }
+                }
+                None => checks.push(CheckResult::warn("sync", "not reported by the node")),
+            }
+            if let Some(clock) = &health.clock {
+                let drift = |drift_ms: Option<i64>| {
+                    drift_ms.map_or("unknown".to_string(), |drift_ms| format!("{}ms", drift_ms))
+                };
+                let detail = format!(
+                    "drift sui {}, eth {}, max {}ms",
+                    drift(clock.sui_drift_ms),
+                    drift(clock.eth_drift_ms),
+                    clock.max_drift_ms
+                );
+                checks.push(if clock.refusing_signatures {
+                    CheckResult::fail("clock", format!("{}, refusing signatures", detail))
+                } else if clock.status == PipelineStatus::Degraded {
+                    CheckResult::warn("clock", detail)
+                } else {
+                    CheckResult::pass("clock", detail)
+                });
+            }
+            if let Some(registered_url) = &health.registered_url {
+                checks.push(match &registered_url.check {
+                    RegisteredUrlStatus::Match { url }
+                        if registered_url.reachable == Some(false) =>
+                    {
+                        CheckResult::warn("registered_url", format!("{} is unreachable", url))
+                    }
+                    RegisteredUrlStatus::Match { url } => {
+                        CheckResult::pass("registered_url", url.clone())
+                    }
+                    RegisteredUrlStatus::Mismatch {
+                        registered_url,
+                        external_url,
+                    } => CheckResult::warn(
+                        "registered_url",
+                        format!(
+                            "{} differs from external url {}",
+                            registered_url, external_url
+                        ),
+                    ),
+                    RegisteredUrlStatus::NotRegistered => {
+                        CheckResult::warn("registered_url", "not registered")
+                    }
+                    RegisteredUrlStatus::NotConfigured { registered_url } => CheckResult::warn(
+                        "registered_url",
+                        format!("{}, external url is not configured", registered_url),
+                    ),
+                });
+            }
+        }
+        None => checks.push(unavailable("sync", HEALTH_PATH)),
+    }
+
+    match &report.executor {
+        Some(executor) => {
+            let mut detail = format!("{} in flight", executor.in_flight_actions.len());
+            let mut stuck = false;
+            if let Some(current) = &executor.current_action {
+                detail.push_str(&format!(
+                    ", {:?} {} {} for {}",
+                    current.action_type,
+                    current.nonce,
+                    format_stage(current.stage),
+                    format_ms(current.time_in_stage_ms)
+                ));
+                stuck = current.time_in_stage_ms > STUCK_ACTION_WARN_MS;
+            }
+            if executor.bridge_paused {
+                detail.push_str(", bridge is paused");
+            }
+            checks.push(if stuck || executor.bridge_paused {
+                CheckResult::warn("executor", detail)
+            } else {
+                CheckResult::pass("executor", detail)
+            });
+            checks.push(match &executor.gas_coin {
+                Some(gas_coin) => {
+                    let detail =
+                        format!("{} in {}", format_sui(gas_coin.balance), gas_coin.object_id);
+                    if gas_coin.balance < LOW_GAS_BALANCE_MIST {
+                        CheckResult::warn("gas", detail)
+                    } else {
+                        CheckResult::pass("gas", detail)
+                    }
+                }
+                None => CheckResult::warn("gas", "gas coin not fetched yet"),
+            });
+        }
+        None => checks.push(unavailable("executor", ADMIN_EXECUTOR_STATE_PATH)),
+    }
+
+    checks.push(match &report.drain {
+        Some(drain) => {
+            let detail = format!(
+                "{} pending, {} in flight, drains in {}",
+                drain.pending_actions,
+                drain.in_flight_actions,
+                drain
+                    .drain
+                    .estimate_ms
+                    .map_or("unknown time".to_string(), format_ms)
+            );
+            if drain.pending_actions > PENDING_ACTIONS_WARN {
+                CheckResult::warn("pending", detail)
+            } else {
+                CheckResult::pass("pending", detail)
+            }
+        }
+        None => unavailable("pending", ADMIN_DRAIN_ESTIMATE_PATH),
+    });
+
+    checks.push(match &report.committee_scores {
+        Some(scores) if scores.is_empty() => CheckResult::warn("committee", "no scores yet"),
+        Some(scores) => {
+            let slowest_ms = scores
+                .values()
+                .map(|score| score.ewma_latency_ms)
+                .max()
+                .unwrap_or_default();
+            let failing = scores
+                .iter()
+                .filter(|(_, score)| score.failure_rate_bps >= MEMBER_FAILURE_RATE_WARN_BPS)
+                .map(|(pubkey, score)| {
+                    format!(
+                        "{} failing {}.{:02}%",
+                        &pubkey[..pubkey.len().min(8)],
+                        score.failure_rate_bps / 100,
+                        score.failure_rate_bps % 100
+                    )
+                })
+                .collect::<Vec<_>>();
+            let detail = format!("{} members, slowest {}ms", scores.len(), slowest_ms);
+            if failing.is_empty() {
+                CheckResult::pass("committee", detail)
+            } else {
+                CheckResult::warn("committee", format!("{}, {}", detail, failing.join(", ")))
+            }
+        }
+        None => unavailable("committee", ADMIN_COMMITTEE_SCORES_PATH),
+    });
+    checks
+}
+
+fn format_not_ready_reason(reason: &NotReadyReason) -> String {
+    match reason {
+        NotReadyReason::StorageNotOpen => "storage not open".to_string(),
+        NotReadyReason::CommitteeNotLoaded => "committee not loaded".to_string(),
+        NotReadyReason::SyncerNotStarted { chain } => format!("{} syncer not started", chain),
+        NotReadyReason::SyncerFailing { chain, error } => {
+            format!("{} syncer failing ({})", chain, error)
+        }
+        NotReadyReason::ServerNotBound => "server not bound".to_string(),
+    }
+}
+
+fn format_pipeline_status(status: PipelineStatus) -> &'static str {
+    match status {
+        PipelineStatus::Unknown => "unknown",
+        PipelineStatus::Healthy => "healthy",
+        PipelineStatus::Degraded => "degraded",
+        PipelineStatus::Down => "down",
+    }
+}
+
+fn format_stage(stage: ExecutorStage) -> &'static str {
+    match stage {
+        ExecutorStage::Aggregating => "aggregating",
+        ExecutorStage::Submitting => "submitting",
+        ExecutorStage::AwaitingEffects => "awaiting effects",
+    }
+}
+
+fn format_ms(ms: u64) -> String {
+    match ms {
+        0..=999 => format!("{}ms", ms),
+        1_000..=59_999 => format!("{}.{}s", ms / 1000, ms % 1000 / 100),
+        60_000..=3_599_999 => format!("{}m {}s", ms / 60_000, ms % 60_000 / 1000),
+        _ => format!("{}h {}m", ms / 3_600_000, ms % 3_600_000 / 60_000),
+    }
+}
+
+fn format_sui(mist: u64) -> String {
+    format!(
+        "{}.{:02} SUI",
+        mist / 1_000_000_000,
+        mist % 1_000_000_000 / 10_000_000
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::http::StatusCode as HttpStatusCode;
+    use axum::routing::get;
+    use axum::{Json, Router};
+    use serde_json::{json, Value};
+    use std::path::PathBuf;
+
+    const NOW_MS: u64 = 1_700_000_000_000;
+
+    fn testdata(name: &str) -> PathBuf {
+        PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+            .join("testdata")
+            .join("node_status")
+            .join(name)
+    }
+
+    // Serves canned responses of every route but `/admin/drain_estimate`, like a node
+    // that doesn't run the client
+    async fn run_canned_node() -> String {
+        let health = json!({
+            "registered_url": {
+                "check": {"status": "match", "url": "https://bridge.example.com"},
+                "reachable": true,
+                "checked_at_ms": NOW_MS - 60_000
+            },
+            "pipelines": {
+                "status": "degraded",
+                "sui": {
+                    "status": "healthy",
+                    "consecutive_failures": 0,
+                    "last_success_ms": NOW_MS - 2_000,
+                    "last_error": null
+                },
+                "eth": {
+                    "status": "degraded",
+                    "consecutive_failures": 3,
+                    "last_success_ms": NOW_MS - 180_000,
+                    "last_error": "connection refused"
+                }
+            },
+            "clock": {
+                "status": "healthy",
+                "max_drift_ms": 60_000,
+                "sui_drift_ms": 120,
+                "eth_drift_ms": -40,
+                "refusing_signatures": false
+            }
+        });
+        let ready = json!({
+            "ready": false,
+            "reasons": [{"reason": "syncer_failing", "chain": "eth", "error": "connection refused"}]
+        });
+        let executor_state = json!({
+            "current_action": {
+                "action_digest": "0a",
+                "action_type": "TokenTransfer",
+                "nonce": 12,
+                "stage": "aggregating",
+                "time_in_stage_ms": 1_500
+            },
+            "in_flight_actions": [{
+                "action_digest": "0a",
+                "action_type": "TokenTransfer",
+                "nonce": 12,
+                "stage": "aggregating",
+                "time_in_stage_ms": 1_500
+            }],
+            "queued_actions": [],
+            "bridge_paused": false,
+            "gas_coin": {"object_id": "0x5", "version": 7, "balance": 500_000_000u64}
+        });
+        let committee_scores = json!({
+            "02321ede33d2c2d7a8a152f275a1484edef2098f034121a602cb7d767d38680aa4": {
+                "ewma_latency_ms": 120,
+                "failure_rate_bps": 0,
+                "last_success_timestamp_ms": NOW_MS
+            },
+            "027f1178ff417fc9f5b8290bd8876f0a157a505a6c52db100a8492203ddd1d4279": {
+                "ewma_latency_ms": 800,
+                "failure_rate_bps": 2500,
+                "last_success_timestamp_ms": NOW_MS - 60_000
+            }
+        });
+        let info = json!({
+            "version": "1.30.0-abcdef",
+            "mode": "committee",
+            "authority_public_key": "02321ede33d2c2d7a8a152f275a1484edef2098f034121a602cb7d767d38680aa4",
+            "sui_chain_id": 1,
+            "eth_chain_id": 11,
+            "sui_bridge_package_id": "0x000000000000000000000000000000000000000000000000000000000000000b",
+            "sui_bridge_object_id": "0x0000000000000000000000000000000000000000000000000000000000000009",
+            "eth_bridge_proxy_address": "0x0000000000000000000000000000000000000001",
+            "eth_contracts": [],
+            "committee_epoch": 42,
+            "bridge_version": 1,
+            "storage_schema_version": null,
+            "features": [],
+            "components": ["request-auth"],
+            "started_at_ms": NOW_MS - 3_600_000,
+            "config": {"server-listen-port": 9191, "mode": "committee"}
+        });
+        let router = Router::new()
+            .route(ADMIN_INFO_PATH, get(move || async move { Json(info) }))
+            .route(HEALTH_PATH, get(move || async move { Json(health) }))
+            .route(
+                READY_PATH,
+                get(move || async move { (HttpStatusCode::SERVICE_UNAVAILABLE, Json(ready)) }),
+            )
+            .route(
+                ADMIN_EXECUTOR_STATE_PATH,
+                get(move || async move { Json(executor_state) }),
+            )
+            .route(
+                ADMIN_COMMITTEE_SCORES_PATH,
+                get(move || async move { Json(committee_scores) }),
+            );
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}/", listener.local_addr().unwrap());
+        tokio::spawn(async move { axum::serve(listener, router).await.unwrap() });
+        url
+    }
+
+    #[tokio::test]
+    async fn test_node_status() {
+        let url = run_canned_node().await;
+        let mut report = fetch_node_status(&reqwest::Client::new(), &url, NOW_MS).await;
+        assert_eq!(
+            report.unavailable,
+            BTreeMap::from([(
+                ADMIN_DRAIN_ESTIMATE_PATH.to_string(),
+                "HTTP 404 Not Found".to_string()
+            )])
+        );
+        // The port of the canned node changes between runs
+        report.url = "http://127.0.0.1:9191/".to_string();
+
+        assert_eq!(
+            format_node_status(&report, false),
+            "Bridge node http://127.0.0.1:9191/\n\
+             version 1.30.0-abcdef, committee mode, SuiTestnet <-> EthSepolia, committee epoch 42\n\
+             CHECK           STATUS  DETAIL\n\
+             ready           WARN    not ready: eth syncer failing (connection refused)\n\
+             sui_sync        PASS    healthy, last synced 2.0s ago\n\
+             eth_sync        WARN    degraded, last synced 3m 0s ago, 3 failed queries: connection refused\n\
+             clock           PASS    drift sui 120ms, eth -40ms, max 60000ms\n\
+             registered_url  PASS    https://bridge.example.com\n\
+             executor        PASS    1 in flight, TokenTransfer 12 aggregating for 1.5s\n\
+             gas             WARN    0.50 SUI in 0x5\n\
+             pending         WARN    unavailable: HTTP 404 Not Found\n\
+             committee       WARN    2 members, slowest 800ms, 027f1178 failing 25.00%\n"
+        );
+        // Only the statuses are colored
+        let colored = format_node_status(&report, true);
+        assert!(colored.contains("ready           \x1b[33mWARN  \x1b[0m  not ready"));
+        assert!(colored.contains("sui_sync        \x1b[32mPASS  \x1b[0m  healthy"));
+
+        let expected: Value =
+            serde_json::from_str(&std::fs::read_to_string(testdata("report.json")).unwrap())
+                .unwrap();
+        assert_eq!(serde_json::to_value(&report).unwrap(), expected);
+        let round_trip: NodeStatusReport = serde_json::from_value(expected).unwrap();
+        assert_eq!(round_trip, report);
+    }
+
+    #[tokio::test]
+    async fn test_node_status_of_unreachable_node() {
+        // Nothing listens on the port anymore
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        drop(listener);
+
+        let report = fetch_node_status(&reqwest::Client::new(), &url, NOW_MS).await;
+        assert_eq!(report.unavailable.len(), 6);
+        let checks = node_status_checks(&report);
+        assert_eq!(
+            checks.iter().map(|check| check.name).collect::<Vec<_>>(),
+            vec!["ready", "sync", "executor", "pending", "committee"]
+        );
+        assert!(checks
+            .iter()
+            .all(|check| check.status == CheckStatus::Warn
+                && check.detail.starts_with("unavailable: ")));
+        assert_eq!(report.warnings.len(), 5);
+    }
+}
@@ -0,0 +1,344 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! USD notional value of token transfers by route. `NotionalTracker` converts transfer
+//! amounts with the token prices on Sui and reports, per route, the bridged notional
+//! value and how much of the route's transfer limit the last 24 hours of transfers use.
+//! `NotionalTrackerUpdater` reloads the prices and limits when token prices change on
+//! chain, and periodically otherwise.
+
+use crate::metrics::BridgeMetrics;
+use crate::retry_with_max_elapsed_time;
+use crate::sui_client::{SuiClient, SuiClientInner};
+use crate::token_math::{self, SuiAmount};
+use crate::types::BridgeAction;
+use arc_swap::ArcSwap;
+use std::collections::{HashMap, VecDeque};
+use std::sync::{Arc, Mutex};
+use std::time::Instant;
+use sui_types::bridge::{BridgeChainId, BridgeSummary};
+use tokio::sync::Notify;
+use tokio::time::{self, Duration};
+use tracing::{error, info, warn};
+
+/// Transfer limits on chain apply to the notional value of the last 24 hours.
+pub const LIMIT_WINDOW: Duration = Duration::from_secs(24 * 60 * 60);
+const REFRESH_INTERVAL: Duration = Duration::from_secs(60);
+// Token prices and transfer limits are USD values with 8 decimal places
+const USD_VALUE_MULTIPLIER: f64 = 100_000_000.0;
+
+/// (source chain, destination chain)
+pub type BridgeRoute = (BridgeChainId, BridgeChainId);
+
+/// Price of a token on Sui, in USD with 8 decimal places, and the multiplier of its
+/// decimals on Sui.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TokenPrice {
+    pub price: u64,
+    pub decimal_multiplier: u64,
+}
+
+/// Returns the USD value (with 8 decimal places) of `amount` of a token in Sui decimals,
+/// the same way the limiter on chain does. Returns None if the token has no price.
+pub fn notional_value(amount: u64, price: TokenPrice) -> Option<u64> {
+    token_math::notional_value(SuiAmount(amount), price.price, price.decimal_multiplier)
+}
+
+pub(crate) fn route_label((source, destination): BridgeRoute) -> String {
+    format!("{:?}_to_{:?}", source, destination)
+}
+
+#[derive(Debug, Default)]
+pub struct NotionalTracker {
+    prices: ArcSwap<HashMap<u8, TokenPrice>>,
+    // Transfer limits on Sui. Routes into Eth are limited on Eth and have no entry.
+    limits: ArcSwap<HashMap<BridgeRoute, u64>>,
+    // Notional values of the transfers in the last `LIMIT_WINDOW`, oldest first
+    recent_transfers: Mutex<HashMap<BridgeRoute, VecDeque<(Instant, u64)>>>,
+    refresh: Notify,
+}
+
+impl NotionalTracker {
+    pub fn update(&self, prices: HashMap<u8, TokenPrice>, limits: HashMap<BridgeRoute, u64>) {
+        self.prices.store(Arc::new(prices));
+        self.limits.store(Arc::new(limits));
+    }
+
+    /// Asks `NotionalTrackerUpdater` to reload the prices and limits from chain.
+    pub fn request_refresh(&self) {
+        self.refresh.notify_one();
+    }
+
+    /// Adds the notional value of `action` to its route if it's a token transfer. Transfers
+    /// of tokens without a price are skipped.
+    pub fn record(&self, action: &BridgeAction, metrics: &BridgeMetrics) {
+        let (route, token_id, amount) = match action {
+            BridgeAction::SuiToEthBridgeAction(a) => (
+                (
+                    a.sui_bridge_event.sui_chain_id,
+                    a.sui_bridge_event.eth_chain_id,
+                ),
+                a.sui_bridge_event.token_id,
+                a.sui_bridge_event.amount_sui_adjusted,
+            ),
+            BridgeAction::EthToSuiBridgeAction(a) => (
+                (
+                    a.eth_bridge_event.eth_chain_id,
+                    a.eth_bridge_event.sui_chain_id,
+                ),
+                a.eth_bridge_event.token_id,
+                a.eth_bridge_event.sui_adjusted_amount,
+            ),
+            _ => return,
+        };
+        let Some(value) = self
+            .prices
+            .load()
+            .get(&token_id)
+            .and_then(|price| notional_value(amount, *price))
+        else {
+            warn!(
+                token_id,
+                "Token price unavailable, skipping notional value of {:?}",
+                action.key()
+            );
+            metrics.transfer_notional_price_unavailable.inc();
+            return;
+        };
+        metrics
+            .transfer_notional_usd_total
+            .with_label_values(&[&route_label(route)])
+            .inc_by(value as f64 / USD_VALUE_MULTIPLIER);
+        self.recent_transfers
+            .lock()
+            .unwrap()
+            .entry(route)
+            .or_default()
+            .push_back((Instant::now(), value));
+        self.update_utilization(metrics);
+    }
+
+    /// Sets the limit utilization ratio of each route with a limit to the notional value
+    /// of its transfers in the last `LIMIT_WINDOW` over the limit.
+    pub fn update_utilization(&self, metrics: &BridgeMetrics) {
+        self.update_utilization_at(Instant::now(), metrics)
+    }
+
+    fn update_utilization_at(&self, now: Instant, metrics: &BridgeMetrics) {
+        let limits = self.limits.load();
+        let mut recent_transfers = self.recent_transfers.lock().unwrap();
+        for (route, transfers) in recent_transfers.iter_mut() {
+            while transfers
+                .front()
+                .is_some_and(|(time, _)| now.saturating_duration_since(*time) > LIMIT_WINDOW)
+            {
+                transfers.pop_front();
+            }
+            let Some(limit) = limits.get(route).filter(|limit| **limit > 0) else {
+                continue;
+            };
+            let total = transfers
+                .iter()
+                .map(|(_, value)| *value as u128)
+                .sum::<u128>();
+            metrics
+                .limit_utilization_ratio
+                .with_label_values(&[&route_label(*route)])
+                .set(total as f64 / *limit as f64);
+        }
+    }
+}
+
+/// Returns the token prices and the transfer limits by route in `summary`.
+pub fn get_prices_and_limits(
+    summary: &BridgeSummary,
+) -> (HashMap<u8, TokenPrice>, HashMap<BridgeRoute, u64>) {
+    let prices = summary
+        .treasury
+        .supported_tokens
+        .iter()
+        .map(|(_, metadata)| {
+            (
+                metadata.id,
+                TokenPrice {
+                    price: metadata.notional_value,
+                    decimal_multiplier: metadata.decimal_multiplier,
+                },
+            )
+        })
+        .collect();
+    let limits = summary
+        .limiter
+        .transfer_limit
+        .iter()
+        .map(|(source, destination, limit)| ((*source, *destination), *limit))
+        .collect();
+    (prices, limits)
+}
+
+pub struct NotionalTrackerUpdater<C> {
+    sui_client: Arc<SuiClient<C>>,
+    notional_tracker: Arc<NotionalTracker>,
+    metrics: Arc<BridgeMetrics>,
+}
+
+impl<C> NotionalTrackerUpdater<C>
+where
+    C: SuiClientInner + 'static,
+{
+    pub fn new(
+        sui_client: Arc<SuiClient<C>>,
+        notional_tracker: Arc<NotionalTracker>,
+        metrics: Arc<BridgeMetrics>,
+    ) -> Self {
+        Self {
+            sui_client,
+            notional_tracker,
+            metrics,
+        }
+    }
+
+    /// Reloads the prices and limits when a refresh is requested, and every
+    /// `REFRESH_INTERVAL` so that utilization ratios decay without new transfers.
+    pub async fn run(self) {
+        info!("Starting NotionalTrackerUpdater");
+        let mut interval = time::interval(REFRESH_INTERVAL);
+        interval.set_missed_tick_behavior(time::MissedTickBehavior::Delay);
+        loop {
+            tokio::select! {
+                _ = interval.tick() => (),
+                _ = self.notional_tracker.refresh.notified() => (),
+            }
+            let Ok(Ok(summary)) = retry_with_max_elapsed_time!(
+                self.sui_client.get_bridge_summary(),
+                Duration::from_secs(600)
+            ) else {
+                error!("Failed to get token prices and transfer limits after retry");
+                continue;
+            };
+            let (prices, limits) = get_prices_and_limits(&summary);
+            self.notional_tracker.update(prices, limits);
+            self.notional_tracker.update_utilization(&self.metrics);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{get_test_eth_to_sui_bridge_action, get_test_sui_to_eth_bridge_action};
+    use sui_types::bridge::{TOKEN_ID_BTC, TOKEN_ID_USDC};
+
+    const BTC_PRICE: TokenPrice = TokenPrice {
+        // $50,000
+        price: 5_000_000_000_000,
+        decimal_multiplier: 100_000_000,
+    };
+    const USDC_PRICE: TokenPrice = TokenPrice {
+        // $1
+        price: 100_000_000,
+        decimal_multiplier: 1_000_000,
+    };
+
+    #[test]
+    fn test_notional_value() {
+        // 0.5 BTC is $25,000
+        assert_eq!(
+            notional_value(50_000_000, BTC_PRICE),
+            Some(2_500_000_000_000)
+        );
+        // 2.5 USDC is $2.5
+        assert_eq!(notional_value(2_500_000, USDC_PRICE), Some(250_000_000));
+        // Rounds down below the 8th decimal of USD
+        assert_eq!(notional_value(1, BTC_PRICE), Some(50_000));
+        assert_eq!(
+            notional_value(
+                1,
+                TokenPrice {
+                    price: 1,
+                    decimal_multiplier: 1_000_000,
+                }
+            ),
+            Some(0)
+        );
+        // No price
+        assert_eq!(
+            notional_value(
+                1,
+                TokenPrice {
+                    price: 0,
+                    decimal_multiplier: 1,
+                }
+            ),
+            None
+        );
+    }
+
+    #[test]
+    fn test_record_notional_by_route() {
+        let metrics = BridgeMetrics::for_testing();
+        let tracker = NotionalTracker::default();
+        let eth_to_sui = (BridgeChainId::EthCustom, BridgeChainId::SuiCustom);
+        let sui_to_eth = (BridgeChainId::SuiCustom, BridgeChainId::EthCustom);
+        tracker.update(
+            HashMap::from([(TOKEN_ID_BTC, BTC_PRICE), (TOKEN_ID_USDC, USDC_PRICE)]),
+            // $100,000 limit into Sui
+            HashMap::from([(eth_to_sui, 10_000_000_000_000)]),
+        );
+
+        // 0.5 BTC and 1 USDC from Eth to Sui
+        tracker.record(
+            &get_test_eth_to_sui_bridge_action(None, Some(50_000_000), None, Some(TOKEN_ID_BTC)),
+            &metrics,
+        );
+        tracker.record(
+            &get_test_eth_to_sui_bridge_action(None, Some(1_000_000), None, Some(TOKEN_ID_USDC)),
+            &metrics,
+        );
+        // 2 USDC from Sui to Eth
+        tracker.record(
+            &get_test_sui_to_eth_bridge_action(
+                None,
+                None,
+                None,
+                Some(2_000_000),
+                None,
+                None,
+                Some(TOKEN_ID_USDC),
+            ),
+            &metrics,
+        );
+        // A token without price is skipped
+        tracker.record(
+            &get_test_eth_to_sui_bridge_action(None, Some(1_000_000), None, Some(100)),
+            &metrics,
+        );
+
+        let notional = |route| {
+            metrics
+                .transfer_notional_usd_total
+                .with_label_values(&[&route_label(route)])
+                .get()
+        };
+        let utilization = |route| {
+            metrics
+                .limit_utilization_ratio
+                .with_label_values(&[&route_label(route)])
+                .get()
+        };
+        assert_eq!(notional(eth_to_sui), 25_001.0);
+        assert_eq!(notional(sui_to_eth), 2.0);
+        assert_eq!(metrics.transfer_notional_price_unavailable.get(), 1);
+        assert_eq!(utilization(eth_to_sui), 0.25001);
+        // No limit on Sui for transfers into Eth
+        assert_eq!(utilization(sui_to_eth), 0.0);
+
+        // Transfers older than the limit window no longer count
+        tracker.update_utilization_at(
+            Instant::now() + LIMIT_WINDOW + Duration::from_secs(1),
+            &metrics,
+        );
+        assert_eq!(utilization(eth_to_sui), 0.0);
+        assert_eq!(notional(eth_to_sui), 25_001.0);
+    }
+}
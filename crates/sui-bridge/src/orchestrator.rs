@@ -6,30 +6,51 @@
 //! 2. updates WAL table and cursor tables
 //! 2. hands actions to `BridgeExecutor` for execution
 
-use crate::abi::EthBridgeEvent;
+use crate::abi::{EthBridgeConfigEvents, EthBridgeEvent, EthSuiBridgeEvents};
 use crate::action_executor::{
     submit_to_executor, BridgeActionExecutionWrapper, BridgeActionExecutorTrait,
 };
 use crate::error::BridgeError;
-use crate::events::SuiBridgeEvent;
+use crate::event_parsers::{EthLogContext, EventParsers, SuiEventContext};
+use crate::event_stream::EventStream;
+use crate::events::{MoveTokenDepositedEvent, SuiBridgeEvent};
 use crate::metrics::BridgeMetrics;
-use crate::storage::BridgeOrchestratorTables;
+use crate::notional::NotionalTracker;
+use crate::server::nonce_guard::NonceGuard;
+use crate::storage::{
+    BridgeOrchestratorTables, DroppedActionReason, QuarantinedAction, SyncerCursor,
+};
 use crate::sui_client::{SuiClient, SuiClientInner};
-use crate::types::EthLog;
+use crate::timestamps::{now_ms, TimestampSanitizer, TimestampSource};
+use crate::transfer_minimums::TransferMinimums;
+use crate::types::{BridgeAction, BridgeActionStatus, EthLog};
 use ethers::types::Address as EthAddress;
 use mysten_metrics::spawn_logged_monitored_task;
+use std::collections::HashMap;
 use std::sync::Arc;
 use sui_json_rpc_types::SuiEvent;
+use sui_types::base_types::SUI_ADDRESS_LENGTH;
 use sui_types::Identifier;
 use tokio::task::JoinHandle;
-use tracing::{error, info};
+use tracing::{error, info, warn};
+
+/// Number of token transfers whose onchain status is queried in one request.
+pub const DEFAULT_ONCHAIN_STATUS_BATCH_SIZE: usize = 50;
 
 pub struct BridgeOrchestrator<C> {
-    _sui_client: Arc<SuiClient<C>>,
+    sui_client: Arc<SuiClient<C>>,
     sui_events_rx: mysten_metrics::metered_channel::Receiver<(Identifier, Vec<SuiEvent>)>,
     eth_events_rx: mysten_metrics::metered_channel::Receiver<(EthAddress, u64, Vec<EthLog>)>,
     store: Arc<BridgeOrchestratorTables>,
     monitor_tx: mysten_metrics::metered_channel::Sender<SuiBridgeEvent>,
+    transfer_minimums: Arc<TransferMinimums>,
+    notional_tracker: Arc<NotionalTracker>,
+    timestamp_sanitizer: TimestampSanitizer,
+    event_stream: Option<Arc<EventStream>>,
+    nonce_guard: Option<Arc<NonceGuard>>,
+    released_actions_rx: Option<mysten_metrics::metered_channel::Receiver<BridgeAction>>,
+    status_batch_size: usize,
+    event_parsers: Arc<EventParsers>,
     metrics: Arc<BridgeMetrics>,
 }
 
@@ -43,18 +64,69 @@ where
         eth_events_rx: mysten_metrics::metered_channel::Receiver<(EthAddress, u64, Vec<EthLog>)>,
         store: Arc<BridgeOrchestratorTables>,
         monitor_tx: mysten_metrics::metered_channel::Sender<SuiBridgeEvent>,
+        transfer_minimums: Arc<TransferMinimums>,
+        notional_tracker: Arc<NotionalTracker>,
         metrics: Arc<BridgeMetrics>,
     ) -> Self {
         Self {
-            _sui_client: sui_client,
+            sui_client,
             sui_events_rx,
             eth_events_rx,
             store,
             monitor_tx,
+            transfer_minimums,
+            notional_tracker,
+            timestamp_sanitizer: TimestampSanitizer::default(),
+            event_stream: None,
+            nonce_guard: None,
+            released_actions_rx: None,
+            status_batch_size: DEFAULT_ONCHAIN_STATUS_BATCH_SIZE,
+            event_parsers: Arc::new(EventParsers::default()),
             metrics,
         }
     }
 
+    /// Converts the received events into actions with `event_parsers`, see
+    /// `event_parsers`.
+    pub fn with_event_parsers(mut self, event_parsers: EventParsers) -> Self {
+        self.event_parsers = Arc::new(event_parsers);
+        self
+    }
+
+    pub fn with_timestamp_sanitizer(mut self, timestamp_sanitizer: TimestampSanitizer) -> Self {
+        self.timestamp_sanitizer = timestamp_sanitizer;
+        self
+    }
+
+    /// Streams the observed actions and heartbeats of the watchers to `event_stream`.
+    pub fn with_event_stream(mut self, event_stream: Arc<EventStream>) -> Self {
+        self.event_stream = Some(event_stream);
+        self
+    }
+
+    /// Updates the nonces known to `nonce_guard` with the observed token transfers.
+    pub fn with_nonce_guard(mut self, nonce_guard: Arc<NonceGuard>) -> Self {
+        self.nonce_guard = Some(nonce_guard);
+        self
+    }
+
+    /// Hands the actions released from quarantine by `QuarantineRechecker` to the
+    /// executor.
+    pub fn with_released_actions(
+        mut self,
+        released_actions_rx: mysten_metrics::metered_channel::Receiver<BridgeAction>,
+    ) -> Self {
+        self.released_actions_rx = Some(released_actions_rx);
+        self
+    }
+
+    /// Number of pending token transfers whose onchain status is queried in one request
+    /// during recovery.
+    pub fn with_status_batch_size(mut self, status_batch_size: usize) -> Self {
+        self.status_batch_size = status_batch_size;
+        self
+    }
+
     pub async fn run(
         self,
         bridge_action_executor: impl BridgeActionExecutorTrait,
@@ -73,26 +145,43 @@ where
             executor_sender_clone,
             self.sui_events_rx,
             self.monitor_tx,
+            self.transfer_minimums.clone(),
+            self.notional_tracker.clone(),
+            self.timestamp_sanitizer,
+            self.event_stream.clone(),
+            self.nonce_guard.clone(),
+            self.event_parsers.clone(),
             metrics_clone,
         )));
-        let store_clone = self.store.clone();
 
-        // Re-submit pending actions to executor
-        let actions = store_clone
-            .get_all_pending_actions()
-            .into_values()
-            .collect::<Vec<_>>();
-        for action in actions {
-            submit_to_executor(&executor_sender, action)
-                .await
-                .expect("Submit to executor should not fail");
+        // Re-submit pending actions to executor in the background, so that a long
+        // recovery does not hold up the watchers or the server.
+        task_handles.push(spawn_logged_monitored_task!(Self::run_recovery(
+            self.sui_client.clone(),
+            self.store.clone(),
+            executor_sender.clone(),
+            self.status_batch_size,
+            self.metrics.clone(),
+        )));
+
+        if let Some(released_actions_rx) = self.released_actions_rx {
+            task_handles.push(spawn_logged_monitored_task!(
+                Self::run_released_actions_forwarder(released_actions_rx, executor_sender.clone())
+            ));
         }
 
+        let store_clone = self.store.clone();
         let metrics_clone = self.metrics.clone();
         task_handles.push(spawn_logged_monitored_task!(Self::run_eth_watcher(
             store_clone,
             executor_sender,
             self.eth_events_rx,
+            self.transfer_minimums,
+            self.notional_tracker,
+            self.timestamp_sanitizer,
+            self.event_stream.clone(),
+            self.nonce_guard,
+            self.event_parsers,
             metrics_clone,
         )));
 
@@ -104,10 +193,19 @@ where
         executor_tx: mysten_metrics::metered_channel::Sender<BridgeActionExecutionWrapper>,
         mut sui_events_rx: mysten_metrics::metered_channel::Receiver<(Identifier, Vec<SuiEvent>)>,
         monitor_tx: mysten_metrics::metered_channel::Sender<SuiBridgeEvent>,
+        transfer_minimums: Arc<TransferMinimums>,
+        notional_tracker: Arc<NotionalTracker>,
+        timestamp_sanitizer: TimestampSanitizer,
+        event_stream: Option<Arc<EventStream>>,
+        nonce_guard: Option<Arc<NonceGuard>>,
+        event_parsers: Arc<EventParsers>,
         metrics: Arc<BridgeMetrics>,
     ) {
         info!("Starting sui watcher task");
         while let Some((identifier, events)) = sui_events_rx.recv().await {
+            if let Some(event_stream) = &event_stream {
+                event_stream.beat("sui_watcher");
+            }
             if events.is_empty() {
                 continue;
             }
@@ -115,72 +213,115 @@ where
             metrics
                 .sui_watcher_received_events
                 .inc_by(events.len() as u64);
-            let bridge_events = events
-                .iter()
-                .filter_map(|sui_event| {
-                    match SuiBridgeEvent::try_from_sui_event(sui_event) {
-                        Ok(bridge_event) => Some(bridge_event),
-                        // On testnet some early bridge transactions could have zero value (before we disallow it in Move)
-                        Err(BridgeError::ZeroValueBridgeTransfer(_)) => {
-                            error!("Zero value bridge transfer: {:?}", sui_event);
-                            None
-                        }
-                        Err(e) => {
-                            panic!(
-                                "Sui Event could not be deserialzed to SuiBridgeEvent: {:?}",
-                                e
-                            );
-                        }
-                    }
-                })
-                .collect::<Vec<_>>();
-
             let mut actions = vec![];
-            for (sui_event, opt_bridge_event) in events.iter().zip(bridge_events) {
-                if opt_bridge_event.is_none() {
-                    // TODO: we probably should not miss any events, log for now.
-                    metrics.sui_watcher_unrecognized_events.inc();
-                    error!("Sui event not recognized: {:?}", sui_event);
-                    continue;
-                }
-                // Unwrap safe: checked above
-                let bridge_event: SuiBridgeEvent = opt_bridge_event.unwrap();
-                info!("Observed Sui bridge event: {:?}", bridge_event);
+            let mut chain_timestamps = HashMap::new();
+            let context = SuiEventContext {
+                module: &identifier,
+            };
+            for sui_event in events.iter() {
+                let parsed = match event_parsers.parse_sui_event(sui_event, &context, &metrics) {
+                    Ok(Some(parsed)) => parsed,
+                    Ok(None) => {
+                        // TODO: we probably should not miss any events, log for now.
+                        metrics.sui_watcher_unrecognized_events.inc();
+                        error!("Sui event not recognized: {:?}", sui_event);
+                        continue;
+                    }
+                    // On testnet some early bridge transactions could have zero value (before we disallow it in Move)
+                    Err(BridgeError::ZeroValueBridgeTransfer(_)) => {
+                        error!("Zero value bridge transfer: {:?}", sui_event);
+                        continue;
+                    }
+                    // The transfer can never be claimed on Eth, so it's not signed nor executed
+                    Err(e @ BridgeError::InvalidRecipientAddress(_)) => {
+                        error!(sui_tx_digest=?sui_event.id.tx_digest, "Quarantining bridge transfer that can't be claimed: {:?}", e);
+                        metrics
+                            .orchestrator_invalid_recipient_transfers
+                            .with_label_values(&["sui"])
+                            .inc();
+                        Self::quarantine_unclaimable_transfer(
+                            Self::unclaimable_sui_transfer(sui_event),
+                            &e,
+                            &store,
+                        );
+                        continue;
+                    }
+                    Err(e) => {
+                        panic!(
+                            "Sui Event could not be deserialzed to SuiBridgeEvent: {:?}",
+                            e
+                        );
+                    }
+                };
+                info!("Observed Sui event: {:?}", parsed);
 
                 // Send event to monitor
-                monitor_tx
-                    .send(bridge_event.clone())
-                    .await
-                    .expect("Sending event to monitor channel should not fail");
+                if let Some(bridge_event) = parsed.bridge_event {
+                    monitor_tx
+                        .send(bridge_event)
+                        .await
+                        .expect("Sending event to monitor channel should not fail");
+                }
 
-                if let Some(action) = bridge_event
-                    .try_into_bridge_action(sui_event.id.tx_digest, sui_event.id.event_seq as u16)
-                {
+                if let Some(action) = parsed.action {
+                    let timestamp_ms = sui_event.timestamp_ms.and_then(|timestamp_ms| {
+                        timestamp_sanitizer.sanitize(
+                            TimestampSource::SuiCheckpoint,
+                            timestamp_ms,
+                            &metrics,
+                        )
+                    });
+                    chain_timestamps.insert(action.digest(), timestamp_ms);
                     actions.push(action);
                 }
             }
 
+            // Unwrap safe: in the beginning of the loop we checked that events is not empty
+            let cursor = events.last().unwrap().id;
             if !actions.is_empty() {
                 info!("Received {} actions from Sui: {:?}", actions.len(), actions);
                 metrics
                     .sui_watcher_received_actions
                     .inc_by(actions.len() as u64);
-                // Write action to pending WAL
+                if let Some(nonce_guard) = &nonce_guard {
+                    actions
+                        .iter()
+                        .for_each(|action| nonce_guard.observe(action));
+                }
+                let actions = Self::drop_known_actions(actions, &store, &metrics);
+                let actions =
+                    Self::drop_actions_below_minimum(actions, &store, &transfer_minimums, &metrics);
+                let actions = Self::quarantine_imprecise_actions(
+                    actions,
+                    &store,
+                    &transfer_minimums,
+                    &metrics,
+                );
+                for action in &actions {
+                    notional_tracker.record(action, &metrics);
+                    let chain_timestamp_ms =
+                        chain_timestamps.get(&action.digest()).copied().flatten();
+                    metrics
+                        .action_latency
+                        .record_observed(action, chain_timestamp_ms, &metrics);
+                    if let Some(event_stream) = &event_stream {
+                        event_stream.action_observed(action, chain_timestamp_ms);
+                    }
+                }
+                // Write action to pending WAL, atomically with the cursor after them
                 store
-                    .insert_pending_actions(&actions)
+                    .commit_pending_actions(&actions, SyncerCursor::Sui(identifier, cursor))
                     .expect("Store operation should not fail");
                 for action in actions {
                     submit_to_executor(&executor_tx, action)
                         .await
                         .expect("Submit to executor should not fail");
                 }
+            } else {
+                store
+                    .update_sui_event_cursor(identifier, cursor)
+                    .expect("Store operation should not fail");
             }
-
-            // Unwrap safe: in the beginning of the loop we checked that events is not empty
-            let cursor = events.last().unwrap().id;
-            store
-                .update_sui_event_cursor(identifier, cursor)
-                .expect("Store operation should not fail");
         }
         panic!("Sui event channel was closed unexpectedly");
     }
@@ -193,10 +334,19 @@ where
             u64,
             Vec<EthLog>,
         )>,
+        transfer_minimums: Arc<TransferMinimums>,
+        notional_tracker: Arc<NotionalTracker>,
+        timestamp_sanitizer: TimestampSanitizer,
+        event_stream: Option<Arc<EventStream>>,
+        nonce_guard: Option<Arc<NonceGuard>>,
+        event_parsers: Arc<EventParsers>,
         metrics: Arc<BridgeMetrics>,
     ) {
         info!("Starting eth watcher task");
         while let Some((contract, end_block, logs)) = eth_events_rx.recv().await {
+            if let Some(event_stream) = &event_stream {
+                event_stream.beat("eth_watcher");
+            }
             if logs.is_empty() {
                 store
                     .update_eth_event_cursor(contract, end_block)
@@ -209,29 +359,61 @@ where
                 .eth_watcher_received_events
                 .inc_by(logs.len() as u64);
 
-            let bridge_events = logs
-                .iter()
-                .map(EthBridgeEvent::try_from_eth_log)
-                .collect::<Vec<_>>();
-
             let mut actions = vec![];
-            for (log, opt_bridge_event) in logs.iter().zip(bridge_events) {
-                if opt_bridge_event.is_none() {
-                    // TODO: we probably should not miss any events, log for now.
-                    metrics.eth_watcher_unrecognized_events.inc();
-                    error!("Eth event not recognized: {:?}", log);
-                    continue;
-                }
-                // Unwrap safe: checked above
-                let bridge_event = opt_bridge_event.unwrap();
-                info!("Observed Eth bridge event: {:?}", bridge_event);
-
-                match bridge_event.try_into_bridge_action(log.tx_hash, log.log_index_in_tx) {
-                    Ok(Some(action)) => actions.push(action),
-                    Ok(None) => {}
+            let mut chain_timestamps = HashMap::new();
+            let context = EthLogContext {
+                contract,
+                end_block,
+            };
+            for log in logs.iter() {
+                let parsed = match event_parsers.parse_eth_log(log, &context, &metrics) {
+                    Ok(Some(parsed)) => parsed,
+                    Ok(None) => {
+                        // TODO: we probably should not miss any events, log for now.
+                        metrics.eth_watcher_unrecognized_events.inc();
+                        error!("Eth event not recognized: {:?}", log);
+                        continue;
+                    }
+                    // The transfer can never be claimed on Sui, so it's not signed nor executed
+                    Err(e @ BridgeError::InvalidRecipientAddress(_)) => {
+                        error!(eth_tx_hash=?log.tx_hash, eth_event_index=?log.log_index_in_tx, "Quarantining bridge transfer that can't be claimed: {:?}", e);
+                        metrics
+                            .orchestrator_invalid_recipient_transfers
+                            .with_label_values(&["eth"])
+                            .inc();
+                        Self::quarantine_unclaimable_transfer(
+                            Self::unclaimable_eth_transfer(log),
+                            &e,
+                            &store,
+                        );
+                        continue;
+                    }
                     Err(e) => {
-                        error!(eth_tx_hash=?log.tx_hash, eth_event_index=?log.log_index_in_tx, "Error converting EthBridgeEvent to BridgeAction: {:?}", e);
+                        error!(eth_tx_hash=?log.tx_hash, eth_event_index=?log.log_index_in_tx, "Error converting Eth event to BridgeAction: {:?}", e);
+                        continue;
                     }
+                };
+                info!("Observed Eth event: {:?}", parsed);
+                if matches!(
+                    parsed.bridge_event,
+                    Some(EthBridgeEvent::EthBridgeConfigEvents(
+                        EthBridgeConfigEvents::TokenAddedFilter(_)
+                            | EthBridgeConfigEvents::TokenPriceUpdatedFilter(_)
+                    ))
+                ) {
+                    transfer_minimums.request_refresh();
+                }
+
+                if let Some(action) = parsed.action {
+                    let timestamp_ms = log.block_timestamp_ms.and_then(|timestamp_ms| {
+                        timestamp_sanitizer.sanitize(
+                            TimestampSource::EthBlock,
+                            timestamp_ms,
+                            &metrics,
+                        )
+                    });
+                    chain_timestamps.insert(action.digest(), timestamp_ms);
+                    actions.push(action);
                 }
                 // TODO: handle non Action events
             }
@@ -240,9 +422,34 @@ where
                 metrics
                     .eth_watcher_received_actions
                     .inc_by(actions.len() as u64);
-                // Write action to pending WAL
+                if let Some(nonce_guard) = &nonce_guard {
+                    actions
+                        .iter()
+                        .for_each(|action| nonce_guard.observe(action));
+                }
+                let actions = Self::drop_known_actions(actions, &store, &metrics);
+                let actions =
+                    Self::drop_actions_below_minimum(actions, &store, &transfer_minimums, &metrics);
+                let actions = Self::quarantine_imprecise_actions(
+                    actions,
+                    &store,
+                    &transfer_minimums,
+                    &metrics,
+                );
+                for action in &actions {
+                    notional_tracker.record(action, &metrics);
+                    let chain_timestamp_ms =
+                        chain_timestamps.get(&action.digest()).copied().flatten();
+                    metrics
+                        .action_latency
+                        .record_observed(action, chain_timestamp_ms, &metrics);
+                    if let Some(event_stream) = &event_stream {
+                        event_stream.action_observed(action, chain_timestamp_ms);
+                    }
+                }
+                // Write action to pending WAL, atomically with the cursor after them
                 store
-                    .insert_pending_actions(&actions)
+                    .commit_pending_actions(&actions, SyncerCursor::Eth(contract, end_block))
                     .expect("Store operation should not fail");
                 // Execution will remove the pending actions from DB when the action is completed.
                 for action in actions {
@@ -250,13 +457,246 @@ where
                         .await
                         .expect("Submit to executor should not fail");
                 }
+            } else {
+                store
+                    .update_eth_event_cursor(contract, end_block)
+                    .expect("Store operation should not fail");
+            }
+        }
+        panic!("Eth event channel was closed");
+    }
+
+    async fn run_released_actions_forwarder(
+        mut released_actions_rx: mysten_metrics::metered_channel::Receiver<BridgeAction>,
+        executor_tx: mysten_metrics::metered_channel::Sender<BridgeActionExecutionWrapper>,
+    ) {
+        while let Some(action) = released_actions_rx.recv().await {
+            info!("Submitting action released from quarantine: {:?}", action);
+            if let Err(e) = submit_to_executor(&executor_tx, action).await {
+                error!("Couldn't submit action released from quarantine: {:?}", e);
+            }
+        }
+    }
+
+    /// Hands the pending actions left over from the last run to the executor, ordered by
+    /// (chain, nonce). Token transfers are checked against Sui in batches first, and the
+    /// ones already approved or claimed are removed from the pending log without being
+    /// submitted.
+    async fn run_recovery(
+        sui_client: Arc<SuiClient<C>>,
+        store: Arc<BridgeOrchestratorTables>,
+        executor_tx: mysten_metrics::metered_channel::Sender<BridgeActionExecutionWrapper>,
+        status_batch_size: usize,
+        metrics: Arc<BridgeMetrics>,
+    ) {
+        let mut actions = store
+            .get_all_pending_actions()
+            .into_values()
+            .collect::<Vec<_>>();
+        actions.sort_by_key(|action| (action.chain_id() as u8, action.seq_number()));
+        let total = actions.len();
+        info!("Recovering {} pending actions", total);
+        metrics.recovery_pending_total.set(total as i64);
+
+        let start = std::time::Instant::now();
+        let mut skipped = 0;
+        for batch in actions.chunks(status_batch_size.max(1)) {
+            let token_transfers = batch
+                .iter()
+                .filter(|action| {
+                    matches!(
+                        action,
+                        BridgeAction::SuiToEthBridgeAction(_)
+                            | BridgeAction::EthToSuiBridgeAction(_)
+                    )
+                })
+                .map(|action| (action.chain_id() as u8, action.seq_number()))
+                .collect::<Vec<_>>();
+            let statuses = token_transfers
+                .iter()
+                .copied()
+                .zip(
+                    sui_client
+                        .get_token_transfer_action_onchain_statuses(
+                            &token_transfers,
+                            status_batch_size,
+                        )
+                        .await,
+                )
+                .collect::<HashMap<_, _>>();
+            for action in batch {
+                // The executor checks the status again before executing, so an action
+                // whose status couldn't be queried is submitted
+                match statuses.get(&(action.chain_id() as u8, action.seq_number())) {
+                    Some(Ok(BridgeActionStatus::Approved | BridgeActionStatus::Claimed)) => {
+                        info!(
+                            "Action already approved or claimed, removing action from pending logs: {:?}",
+                            action
+                        );
+                        metrics.action_executor_already_processed_actions.inc();
+                        store
+                            .mark_actions_executed(&[action.clone()])
+                            .expect("Store operation should not fail");
+                        skipped += 1;
+                    }
+                    status => {
+                        if let Some(Err(e)) = status {
+                            warn!(
+                                "Failed to get onchain status of pending action {:?}: {:?}",
+                                action.digest(),
+                                e
+                            );
+                        }
+                        submit_to_executor(&executor_tx, action.clone())
+                            .await
+                            .expect("Submit to executor should not fail");
+                    }
+                }
+                metrics.recovery_processed_total.inc();
+                metrics.recovery_pending_total.dec();
             }
+        }
+        info!(
+            "Recovered {} pending actions in {:?}: {} already processed, {} submitted to executor",
+            total,
+            start.elapsed(),
+            skipped,
+            total - skipped
+        );
+    }
+
+    // Events are read again after a restart, or when a syncer gave up handing them over
+    // to a full channel, so actions that are already pending, executed or dropped are
+    // skipped to submit each action only once.
+    fn drop_known_actions(
+        actions: Vec<BridgeAction>,
+        store: &BridgeOrchestratorTables,
+        metrics: &BridgeMetrics,
+    ) -> Vec<BridgeAction> {
+        let (known, actions): (Vec<_>, Vec<_>) = actions.into_iter().partition(|action| {
+            store
+                .is_known_action(action)
+                .expect("Store operation should not fail")
+        });
+        if !known.is_empty() {
+            info!(
+                "Skipping {} actions that were already received: {:?}",
+                known.len(),
+                known
+            );
+            metrics
+                .orchestrator_duplicate_actions
+                .inc_by(known.len() as u64);
+        }
+        actions
+    }
 
+    // Token transfers below the minimum transfer amount would fail on chain, so they are
+    // recorded in the dropped actions table instead of being executed.
+    fn drop_actions_below_minimum(
+        actions: Vec<BridgeAction>,
+        store: &BridgeOrchestratorTables,
+        transfer_minimums: &TransferMinimums,
+        metrics: &BridgeMetrics,
+    ) -> Vec<BridgeAction> {
+        let (dropped, actions): (Vec<_>, Vec<_>) = actions
+            .into_iter()
+            .partition(|action| transfer_minimums.is_below_minimum(action));
+        if !dropped.is_empty() {
+            warn!(
+                "Dropping {} actions below minimum transfer amount: {:?}",
+                dropped.len(),
+                dropped
+            );
+            metrics
+                .orchestrator_dropped_below_min_transfer_actions
+                .inc_by(dropped.len() as u64);
             store
-                .update_eth_event_cursor(contract, end_block)
+                .insert_dropped_actions(&dropped, DroppedActionReason::BelowMinimumTransfer)
                 .expect("Store operation should not fail");
         }
-        panic!("Eth event channel was closed");
+        actions
+    }
+
+    // Token transfers whose amount can't be converted exactly between the decimals of their
+    // token on Sui and Eth would be credited a different value on the destination chain, so
+    // they are quarantined for an operator instead of being executed.
+    fn quarantine_imprecise_actions(
+        actions: Vec<BridgeAction>,
+        store: &BridgeOrchestratorTables,
+        transfer_minimums: &TransferMinimums,
+        metrics: &BridgeMetrics,
+    ) -> Vec<BridgeAction> {
+        actions
+            .into_iter()
+            .filter(|action| {
+                let Err(e) = transfer_minimums.check_precision(action) else {
+                    return true;
+                };
+                error!(
+                    "Manual intervention is required. Quarantining action {:?}: {}",
+                    action.key(),
+                    e
+                );
+                metrics.orchestrator_precision_rejected_actions.inc();
+                store
+                    .quarantine_action(&QuarantinedAction {
+                        action: action.clone(),
+                        reason: format!("{:?}", e),
+                        failures: 1,
+                        quarantined_at_ms: now_ms(),
+                    })
+                    .expect("Store operation should not fail");
+                false
+            })
+            .collect()
+    }
+
+    // Quarantines a token transfer whose recipient can't be claimed, so that operators find
+    // it with the other quarantined actions. Actions only hold recipients of the right
+    // length, so the zero address stands for its recipient, which is in `e`. It's never
+    // signed: the authorities verify the event, and refuse it.
+    fn quarantine_unclaimable_transfer(
+        action: Option<BridgeAction>,
+        e: &BridgeError,
+        store: &BridgeOrchestratorTables,
+    ) {
+        let Some(action) = action else {
+            error!("Couldn't build the action of the bridge transfer that can't be claimed");
+            return;
+        };
+        store
+            .quarantine_action(&QuarantinedAction {
+                action,
+                reason: format!("{:?}", e),
+                failures: 1,
+                quarantined_at_ms: now_ms(),
+            })
+            .expect("Store operation should not fail");
+    }
+
+    // The transfer of `sui_event`, with the zero address as its recipient
+    fn unclaimable_sui_transfer(sui_event: &SuiEvent) -> Option<BridgeAction> {
+        let mut event: MoveTokenDepositedEvent = bcs::from_bytes(&sui_event.bcs).ok()?;
+        event.target_address = vec![0; EthAddress::len_bytes()];
+        SuiBridgeEvent::SuiToEthTokenBridgeV1(event.try_into().ok()?).try_into_bridge_action(
+            sui_event.id.tx_digest,
+            u16::try_from(sui_event.id.event_seq).ok()?,
+        )
+    }
+
+    // The transfer of `log`, with the zero address as its recipient
+    fn unclaimable_eth_transfer(log: &EthLog) -> Option<BridgeAction> {
+        let Some(EthBridgeEvent::EthSuiBridgeEvents(EthSuiBridgeEvents::TokensDepositedFilter(
+            mut event,
+        ))) = EthBridgeEvent::try_from_eth_log(log)
+        else {
+            return None;
+        };
+        event.recipient_address = vec![0; SUI_ADDRESS_LENGTH].into();
+        EthBridgeEvent::EthSuiBridgeEvents(EthSuiBridgeEvents::TokensDepositedFilter(event))
+            .try_into_bridge_action(log.tx_hash, log.log_index_in_tx)
+            .ok()?
     }
 }
 
@@ -268,12 +708,30 @@ mod tests {
     };
     use ethers::types::{Address as EthAddress, TxHash};
     use prometheus::Registry;
+    use std::collections::HashMap;
     use std::str::FromStr;
 
     use super::*;
-    use crate::events::init_all_struct_tags;
+    use crate::abi::EthToSuiTokenBridgeV1;
+    use crate::api_types::{encode_action_digest, Action, StreamEvent};
+    use crate::config::EventStreamConfig;
+    use crate::event_parsers::{EthLogParser, ParsedEthLog, ParsedSuiEvent, SuiEventParser};
+    use crate::event_stream::EventStreamListener;
+    use crate::events::{init_all_struct_tags, MoveTokenDepositedEvent};
+    use crate::sui_syncer::SuiSyncer;
     use crate::test_utils::get_test_sui_to_eth_bridge_action;
+    use crate::token_math::TokenDecimals;
+    use crate::types::EthToSuiBridgeAction;
     use crate::{events::tests::get_test_sui_event_and_action, sui_mock_client::SuiMockClient};
+    use std::collections::HashSet;
+    use sui_json_rpc_types::EventPage;
+    use sui_types::base_types::SuiAddress;
+    use sui_types::bridge::{BridgeChainId, TOKEN_ID_USDC};
+    use sui_types::digests::TransactionDigest;
+    use sui_types::event::EventID;
+    use sui_types::BRIDGE_PACKAGE_ID;
+    use tokio::io::AsyncBufReadExt;
+    use tokio::time::Duration;
 
     #[tokio::test]
     async fn test_sui_watcher_task() {
@@ -300,6 +758,8 @@ mod tests {
             eth_events_rx,
             store.clone(),
             monitor_tx,
+            Arc::new(TransferMinimums::default()),
+            Arc::new(NotionalTracker::default()),
             metrics,
         )
         .run(executor)
@@ -364,6 +824,8 @@ mod tests {
             eth_events_rx,
             store.clone(),
             monitor_tx,
+            Arc::new(TransferMinimums::default()),
+            Arc::new(NotionalTracker::default()),
             metrics,
         )
         .run(executor)
@@ -377,6 +839,7 @@ mod tests {
             tx_hash: log.transaction_hash.unwrap(),
             block_number: log_block_num,
             log_index_in_tx,
+            block_timestamp_ms: None,
         };
         let end_block_num = log_block_num + 15;
 
@@ -411,13 +874,85 @@ mod tests {
         }
     }
 
+    // A fork's Eth event that the bridge contracts don't emit
+    struct FastDepositParser;
+
+    fn fast_deposit_topic() -> ethers::types::H256 {
+        ethers::utils::keccak256("FastDeposit(uint64,address)").into()
+    }
+
+    impl EthLogParser for FastDepositParser {
+        fn name(&self) -> &'static str {
+            "fast_deposit"
+        }
+
+        fn topics(&self) -> Vec<ethers::types::H256> {
+            vec![fast_deposit_topic()]
+        }
+
+        fn parse(
+            &self,
+            log: &EthLog,
+            _context: &EthLogContext,
+        ) -> crate::error::BridgeResult<Option<ParsedEthLog>> {
+            if log.log.topics.first() != Some(&fast_deposit_topic()) {
+                return Ok(None);
+            }
+            // The log is claimed, a malformed one is an error
+            let Some(nonce) = log.log.topics.get(1) else {
+                return Err(crate::error::BridgeError::Generic(
+                    "FastDeposit log without a nonce".to_string(),
+                ));
+            };
+            let action = BridgeAction::EthToSuiBridgeAction(EthToSuiBridgeAction {
+                eth_tx_hash: log.tx_hash,
+                eth_event_index: log.log_index_in_tx,
+                eth_bridge_event: EthToSuiTokenBridgeV1 {
+                    eth_chain_id: BridgeChainId::EthCustom,
+                    nonce: nonce.to_low_u64_be(),
+                    sui_chain_id: BridgeChainId::SuiCustom,
+                    token_id: TOKEN_ID_USDC,
+                    sui_adjusted_amount: 100_000,
+                    sui_address: SuiAddress::ZERO,
+                    eth_address: log.log.address,
+                },
+            });
+            Ok(Some(ParsedEthLog {
+                bridge_event: None,
+                action: Some(action),
+            }))
+        }
+    }
+
+    // A fork's Sui event that is not actionable
+    struct HeartbeatParser;
+
+    impl SuiEventParser for HeartbeatParser {
+        fn name(&self) -> &'static str {
+            "heartbeat"
+        }
+
+        fn parse(
+            &self,
+            event: &SuiEvent,
+            _context: &SuiEventContext,
+        ) -> crate::error::BridgeResult<Option<ParsedSuiEvent>> {
+            if event.type_.name.as_str() != "Heartbeat" {
+                return Ok(None);
+            }
+            Ok(Some(ParsedSuiEvent {
+                bridge_event: None,
+                action: None,
+            }))
+        }
+    }
+
     #[tokio::test]
-    /// Test that when orchestrator starts, all pending actions are sent to executor
-    async fn test_resume_actions_in_pending_logs() {
+    async fn test_watchers_with_custom_event_parsers() {
         let (
-            _sui_events_tx,
+            sui_events_tx,
             sui_events_rx,
-            _eth_events_tx,
+            eth_events_tx,
             eth_events_rx,
             monitor_tx,
             _monitor_rx,
@@ -425,47 +960,813 @@ mod tests {
             store,
         ) = setup();
         let (executor, mut executor_requested_action_rx) = MockExecutor::new();
+        let metrics = Arc::new(BridgeMetrics::for_testing());
+        let _handles = BridgeOrchestrator::new(
+            Arc::new(sui_client),
+            sui_events_rx,
+            eth_events_rx,
+            store.clone(),
+            monitor_tx,
+            Arc::new(TransferMinimums::default()),
+            Arc::new(NotionalTracker::default()),
+            metrics.clone(),
+        )
+        .with_event_parsers(
+            EventParsers::default()
+                .with_eth_parser(Arc::new(FastDepositParser))
+                .with_sui_parser(Arc::new(HeartbeatParser)),
+        )
+        .run(executor)
+        .await;
+        let parsed_events = |chain: &str, parser: &str| {
+            metrics
+                .watcher_parsed_events
+                .with_label_values(&[chain, parser])
+                .get()
+        };
 
-        let action1 = get_test_sui_to_eth_bridge_action(
-            None,
-            Some(0),
-            Some(99),
-            Some(10000),
-            None,
-            None,
-            None,
+        // A fast deposit and a log that no parser claims
+        let address = EthAddress::random();
+        let eth_log = |topics: Vec<ethers::types::H256>, log_index_in_tx: u16| EthLog {
+            block_number: 100,
+            tx_hash: TxHash::random(),
+            log_index_in_tx,
+            block_timestamp_ms: None,
+            log: ethers::types::Log {
+                address,
+                topics,
+                ..Default::default()
+            },
+        };
+        let unknown_log = eth_log(vec![ethers::types::H256::random()], 0);
+        let fast_deposit_log = eth_log(
+            vec![
+                fast_deposit_topic(),
+                ethers::types::H256::from_low_u64_be(42),
+            ],
+            1,
+        );
+        let fast_deposit = FastDepositParser
+            .parse(
+                &fast_deposit_log,
+                &EthLogContext {
+                    contract: address,
+                    end_block: 100,
+                },
+            )
+            .unwrap()
+            .unwrap()
+            .action
+            .unwrap();
+        eth_events_tx
+            .send((address, 100, vec![unknown_log, fast_deposit_log]))
+            .await
+            .unwrap();
+        assert_eq!(
+            executor_requested_action_rx.recv().await.unwrap(),
+            fast_deposit.digest()
+        );
+        assert_eq!(
+            store.get_all_pending_actions().get(&fast_deposit.digest()),
+            Some(&fast_deposit)
         );
+        assert_eq!(parsed_events("eth", "fast_deposit"), 1);
+        assert_eq!(parsed_events("eth", "bridge"), 0);
+        assert_eq!(metrics.eth_watcher_unrecognized_events.get(), 1);
 
-        let action2 = get_test_eth_to_sui_bridge_action(None, None, None, None);
-        store
-            .insert_pending_actions(&vec![action1.clone(), action2.clone()])
+        // The default parser claims the bridge events first
+        let identifier = Identifier::from_str("test_custom_event_parsers").unwrap();
+        let (mut heartbeat, _) = get_test_sui_event_and_action(identifier.clone());
+        heartbeat.type_.name = Identifier::from_str("Heartbeat").unwrap();
+        let (sui_event, bridge_action) = get_test_sui_event_and_action(identifier.clone());
+        sui_events_tx
+            .send((identifier.clone(), vec![heartbeat, sui_event.clone()]))
+            .await
             .unwrap();
+        assert_eq!(
+            executor_requested_action_rx.recv().await.unwrap(),
+            bridge_action.digest()
+        );
+        let start = std::time::Instant::now();
+        while store.get_sui_event_cursors(&[identifier.clone()]).unwrap()[0] != Some(sui_event.id) {
+            assert!(
+                start.elapsed().as_secs() < 5,
+                "Timed out waiting for the Sui cursor"
+            );
+            tokio::time::sleep(Duration::from_millis(50)).await;
+        }
+        assert_eq!(parsed_events("sui", "heartbeat"), 1);
+        assert_eq!(parsed_events("sui", "bridge"), 1);
+        assert_eq!(metrics.sui_watcher_unrecognized_events.get(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_eth_watcher_streams_observed_actions() {
+        let (
+            _sui_events_tx,
+            sui_events_rx,
+            eth_events_tx,
+            eth_events_rx,
+            monitor_tx,
+            _monitor_rx,
+            sui_client,
+            store,
+        ) = setup();
+        let (executor, mut executor_requested_action_rx) = MockExecutor::new();
+        let metrics = Arc::new(BridgeMetrics::for_testing());
+        let temp_dir = tempfile::tempdir().unwrap();
+        let config = EventStreamConfig {
+            socket_path: Some(temp_dir.path().join("events.sock")),
+            ..Default::default()
+        };
+        let listener = EventStreamListener::bind(&config).await.unwrap();
+        let event_stream = Arc::new(EventStream::new(&config, metrics.clone()));
+        tokio::spawn(event_stream.clone().serve(listener));
+        let mut reader = tokio::io::BufReader::new(
+            tokio::net::UnixStream::connect(config.socket_path.as_ref().unwrap())
+                .await
+                .unwrap(),
+        );
+        let start = std::time::Instant::now();
+        while metrics.event_stream_consumers.get() == 0 {
+            assert!(start.elapsed() < Duration::from_secs(5));
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
 
-        // start orchestrator
-        let registry = Registry::new();
-        let metrics = Arc::new(BridgeMetrics::new(&registry));
         let _handles = BridgeOrchestrator::new(
             Arc::new(sui_client),
             sui_events_rx,
             eth_events_rx,
             store.clone(),
             monitor_tx,
+            Arc::new(TransferMinimums::default()),
+            Arc::new(NotionalTracker::default()),
             metrics,
         )
+        .with_event_stream(event_stream)
         .run(executor)
         .await;
+        let address = EthAddress::random();
+        let (log, bridge_action) = get_test_log_and_action(address, TxHash::random(), 10);
+        let eth_log = EthLog {
+            log: log.clone(),
+            tx_hash: log.transaction_hash.unwrap(),
+            block_number: log.block_number.unwrap().as_u64(),
+            log_index_in_tx: 10,
+            block_timestamp_ms: None,
+        };
+        eth_events_tx
+            .send((address, eth_log.block_number + 15, vec![eth_log]))
+            .await
+            .unwrap();
+        assert_eq!(
+            executor_requested_action_rx.recv().await.unwrap(),
+            bridge_action.digest()
+        );
 
-        // Executor should have received the action
-        let mut digests = std::collections::HashSet::new();
-        digests.insert(executor_requested_action_rx.recv().await.unwrap());
-        digests.insert(executor_requested_action_rx.recv().await.unwrap());
-        assert!(digests.contains(&action1.digest()));
-        assert!(digests.contains(&action2.digest()));
-        assert_eq!(digests.len(), 2);
+        let mut line = String::new();
+        tokio::time::timeout(Duration::from_secs(5), reader.read_line(&mut line))
+            .await
+            .unwrap()
+            .unwrap();
+        let StreamEvent::ActionObserved {
+            action_digest,
+            action,
+            chain_timestamp_ms,
+            ..
+        } = serde_json::from_str(&line).unwrap()
+        else {
+            panic!("Expected an observed action, got {line}");
+        };
+        assert_eq!(action_digest, encode_action_digest(&bridge_action.digest()));
+        assert_eq!(action, Action::from(&bridge_action));
+        assert_eq!(chain_timestamp_ms, None);
     }
 
-    #[allow(clippy::type_complexity)]
-    fn setup() -> (
+    #[tokio::test]
+    async fn test_drop_actions_below_min_transfer_amount() {
+        let (
+            sui_events_tx,
+            sui_events_rx,
+            _eth_events_tx,
+            eth_events_rx,
+            monitor_tx,
+            _monitor_rx,
+            sui_client,
+            store,
+        ) = setup();
+        let (executor, mut executor_requested_action_rx) = MockExecutor::new();
+        let identifier = Identifier::from_str("test_drop_actions_below_min").unwrap();
+        let (sui_event, bridge_action) = get_test_sui_event_and_action(identifier.clone());
+        let BridgeAction::SuiToEthBridgeAction(ref action) = bridge_action else {
+            panic!("Expected SuiToEthBridgeAction");
+        };
+        let transfer_minimums = Arc::new(TransferMinimums::new(HashMap::from([(
+            action.sui_bridge_event.token_id,
+            action.sui_bridge_event.amount_sui_adjusted + 1,
+        )])));
+        // start orchestrator
+        let registry = Registry::new();
+        let metrics = Arc::new(BridgeMetrics::new(&registry));
+        let _handles = BridgeOrchestrator::new(
+            Arc::new(sui_client),
+            sui_events_rx,
+            eth_events_rx,
+            store.clone(),
+            monitor_tx,
+            transfer_minimums,
+            Arc::new(NotionalTracker::default()),
+            metrics.clone(),
+        )
+        .run(executor)
+        .await;
+
+        sui_events_tx
+            .send((identifier.clone(), vec![sui_event.clone()]))
+            .await
+            .unwrap();
+
+        // The action is recorded as dropped
+        let start = std::time::Instant::now();
+        loop {
+            let actions = store.get_all_dropped_actions();
+            if actions.is_empty() {
+                if start.elapsed().as_secs() > 5 {
+                    panic!("Timed out waiting for action to be recorded as dropped");
+                }
+                tokio::time::sleep(tokio::time::Duration::from_millis(200)).await;
+                continue;
+            }
+            assert_eq!(actions.len(), 1);
+            assert_eq!(
+                actions.get(&bridge_action.digest()).unwrap(),
+                &bridge_action
+            );
+            break;
+        }
+        assert_eq!(
+            metrics
+                .orchestrator_dropped_below_min_transfer_actions
+                .get(),
+            1
+        );
+
+        // The action is never submitted, but the event is still processed
+        tokio::time::sleep(tokio::time::Duration::from_millis(500)).await;
+        assert_eq!(
+            store.get_sui_event_cursors(&[identifier]).unwrap()[0].unwrap(),
+            sui_event.id,
+        );
+        assert!(store.get_all_pending_actions().is_empty());
+        assert_eq!(
+            executor_requested_action_rx.try_recv().unwrap_err(),
+            tokio::sync::broadcast::error::TryRecvError::Empty
+        );
+    }
+
+    #[tokio::test]
+    async fn test_quarantine_actions_with_imprecise_amounts() {
+        let (
+            sui_events_tx,
+            sui_events_rx,
+            _eth_events_tx,
+            eth_events_rx,
+            monitor_tx,
+            _monitor_rx,
+            sui_client,
+            store,
+        ) = setup();
+        let (executor, mut executor_requested_action_rx) = MockExecutor::new();
+        let identifier = Identifier::from_str("test_quarantine_imprecise").unwrap();
+        let (sui_event, bridge_action) = get_test_sui_event_and_action(identifier.clone());
+        let (token_id, _) = bridge_action.token_amount().unwrap();
+        // No amount below 10^19 can be represented without decimals on Eth
+        let transfer_minimums = Arc::new(TransferMinimums::default());
+        transfer_minimums.update_token_decimals(HashMap::from([(
+            token_id,
+            TokenDecimals { sui: 19, eth: 0 },
+        )]));
+        let registry = Registry::new();
+        let metrics = Arc::new(BridgeMetrics::new(&registry));
+        let _handles = BridgeOrchestrator::new(
+            Arc::new(sui_client),
+            sui_events_rx,
+            eth_events_rx,
+            store.clone(),
+            monitor_tx,
+            transfer_minimums,
+            Arc::new(NotionalTracker::default()),
+            metrics.clone(),
+        )
+        .run(executor)
+        .await;
+
+        sui_events_tx
+            .send((identifier.clone(), vec![sui_event.clone()]))
+            .await
+            .unwrap();
+
+        let start = std::time::Instant::now();
+        let quarantined = loop {
+            if let Some(quarantined) = store
+                .get_quarantined_action(&bridge_action.digest())
+                .unwrap()
+            {
+                break quarantined;
+            }
+            if start.elapsed().as_secs() > 5 {
+                panic!("Timed out waiting for action to be quarantined");
+            }
+            tokio::time::sleep(tokio::time::Duration::from_millis(200)).await;
+        };
+        assert_eq!(quarantined.action, bridge_action);
+        assert!(quarantined.reason.contains("AmountPrecision"));
+        assert_eq!(metrics.orchestrator_precision_rejected_actions.get(), 1);
+        assert!(store.get_all_pending_actions().is_empty());
+        assert_eq!(
+            executor_requested_action_rx.try_recv().unwrap_err(),
+            tokio::sync::broadcast::error::TryRecvError::Empty
+        );
+    }
+
+    #[tokio::test]
+    async fn test_quarantine_transfers_with_invalid_recipients() {
+        let (
+            sui_events_tx,
+            sui_events_rx,
+            _eth_events_tx,
+            eth_events_rx,
+            monitor_tx,
+            _monitor_rx,
+            sui_client,
+            store,
+        ) = setup();
+        let (executor, mut executor_requested_action_rx) = MockExecutor::new();
+        let identifier = Identifier::from_str("test_invalid_recipient").unwrap();
+        let (mut sui_event, action) = get_test_sui_event_and_action(identifier.clone());
+        // One byte short of an Eth address
+        let mut deposit: MoveTokenDepositedEvent = bcs::from_bytes(&sui_event.bcs).unwrap();
+        deposit.target_address = vec![1; 19];
+        sui_event.bcs = bcs::to_bytes(&deposit).unwrap();
+        let registry = Registry::new();
+        let metrics = Arc::new(BridgeMetrics::new(&registry));
+        let _handles = BridgeOrchestrator::new(
+            Arc::new(sui_client),
+            sui_events_rx,
+            eth_events_rx,
+            store.clone(),
+            monitor_tx,
+            Arc::new(TransferMinimums::default()),
+            Arc::new(NotionalTracker::default()),
+            metrics.clone(),
+        )
+        .run(executor)
+        .await;
+
+        sui_events_tx
+            .send((identifier.clone(), vec![sui_event.clone()]))
+            .await
+            .unwrap();
+
+        let start = std::time::Instant::now();
+        let quarantined = loop {
+            let quarantined = store.get_all_quarantined_actions();
+            if !quarantined.is_empty() {
+                break quarantined;
+            }
+            if start.elapsed().as_secs() > 5 {
+                panic!("Timed out waiting for the transfer to be quarantined");
+            }
+            tokio::time::sleep(tokio::time::Duration::from_millis(200)).await;
+        };
+        assert_eq!(quarantined.len(), 1);
+        let quarantined = quarantined.into_values().next().unwrap();
+        assert!(quarantined.reason.contains("InvalidRecipientAddress"));
+        assert!(quarantined.reason.contains("got 19 bytes"));
+        // The same transfer, to the zero address
+        let BridgeAction::SuiToEthBridgeAction(mut expected) = action else {
+            panic!("Not a transfer from Sui: {:?}", action);
+        };
+        expected.sui_bridge_event.eth_address = EthAddress::zero();
+        assert_eq!(
+            quarantined.action,
+            BridgeAction::SuiToEthBridgeAction(expected)
+        );
+        assert_eq!(
+            metrics
+                .orchestrator_invalid_recipient_transfers
+                .with_label_values(&["sui"])
+                .get(),
+            1
+        );
+        // Not pending, it's never signed
+        assert!(store.get_all_pending_actions().is_empty());
+        assert_eq!(
+            executor_requested_action_rx.try_recv().unwrap_err(),
+            tokio::sync::broadcast::error::TryRecvError::Empty
+        );
+    }
+
+    #[tokio::test]
+    async fn test_skip_known_actions() {
+        let (
+            sui_events_tx,
+            sui_events_rx,
+            _eth_events_tx,
+            eth_events_rx,
+            monitor_tx,
+            _monitor_rx,
+            sui_client,
+            store,
+        ) = setup();
+        let (executor, mut executor_requested_action_rx) = MockExecutor::new();
+        let registry = Registry::new();
+        let metrics = Arc::new(BridgeMetrics::new(&registry));
+        let _handles = BridgeOrchestrator::new(
+            Arc::new(sui_client),
+            sui_events_rx,
+            eth_events_rx,
+            store.clone(),
+            monitor_tx,
+            Arc::new(TransferMinimums::default()),
+            Arc::new(NotionalTracker::default()),
+            metrics.clone(),
+        )
+        .run(executor)
+        .await;
+
+        let identifier = Identifier::from_str("test_skip_known_actions").unwrap();
+        let (sui_event, bridge_action) = get_test_sui_event_and_action(identifier.clone());
+        sui_events_tx
+            .send((identifier.clone(), vec![sui_event.clone()]))
+            .await
+            .unwrap();
+        assert_eq!(
+            executor_requested_action_rx.recv().await.unwrap(),
+            bridge_action.digest()
+        );
+
+        // The same event is read again, the action is still pending and not submitted again
+        sui_events_tx
+            .send((identifier.clone(), vec![sui_event.clone()]))
+            .await
+            .unwrap();
+        let start = std::time::Instant::now();
+        while metrics.orchestrator_duplicate_actions.get() == 0 {
+            if start.elapsed().as_secs() > 5 {
+                panic!("Timed out waiting for the duplicate action to be skipped");
+            }
+            tokio::time::sleep(tokio::time::Duration::from_millis(200)).await;
+        }
+        assert_eq!(metrics.orchestrator_duplicate_actions.get(), 1);
+        assert_eq!(
+            executor_requested_action_rx.try_recv().unwrap_err(),
+            tokio::sync::broadcast::error::TryRecvError::Empty
+        );
+        assert_eq!(store.get_all_pending_actions().len(), 1);
+    }
+
+    /// Test that a blocked executor stalls the sui syncer without buffering events in memory,
+    /// and that every action is submitted exactly once after the executor is unblocked.
+    #[tokio::test]
+    async fn test_backpressure_with_blocked_executor() {
+        let (
+            _sui_events_tx,
+            _sui_events_rx,
+            _eth_events_tx,
+            eth_events_rx,
+            monitor_tx,
+            _monitor_rx,
+            _sui_client,
+            store,
+        ) = setup();
+        let mock = SuiMockClient::default();
+        let sui_client = Arc::new(SuiClient::new_for_testing(mock.clone()));
+        let registry = Registry::new();
+        let metrics = Arc::new(BridgeMetrics::new(&registry));
+
+        // Each query returns one event and moves the cursor to it
+        let module = Identifier::from_str("bridge").unwrap();
+        let mut cursor = EventID {
+            tx_digest: TransactionDigest::random(),
+            event_seq: 0,
+        };
+        let start_cursor = cursor;
+        let mut expected = HashSet::new();
+        for _ in 0..30 {
+            let (sui_event, bridge_action) = get_test_sui_event_and_action(module.clone());
+            expected.insert(bridge_action.digest());
+            mock.add_event_response(
+                BRIDGE_PACKAGE_ID,
+                module.clone(),
+                cursor,
+                EventPage {
+                    data: vec![sui_event.clone()],
+                    next_cursor: Some(sui_event.id),
+                    has_next_page: false,
+                },
+            );
+            cursor = sui_event.id;
+        }
+        mock.add_event_response(
+            BRIDGE_PACKAGE_ID,
+            module.clone(),
+            cursor,
+            EventPage::empty(),
+        );
+
+        let (_handles, sui_events_rx) = SuiSyncer::new(
+            sui_client.clone(),
+            HashMap::from([(module.clone(), Some(start_cursor))]),
+        )
+        .with_events_channel(1, Duration::from_millis(100))
+        .run(Duration::from_millis(10), metrics.clone())
+        .await
+        .unwrap();
+        let (executor, mut executor_requested_action_rx, unblock_tx) = MockExecutor::new_blocked(1);
+        let _handles = BridgeOrchestrator::new(
+            sui_client,
+            sui_events_rx,
+            eth_events_rx,
+            store.clone(),
+            monitor_tx,
+            Arc::new(TransferMinimums::default()),
+            Arc::new(NotionalTracker::default()),
+            metrics.clone(),
+        )
+        .run(executor)
+        .await;
+
+        // The syncer keeps timing out while the executor is blocked
+        let start = std::time::Instant::now();
+        while metrics
+            .syncer_send_timeouts
+            .with_label_values(&["sui"])
+            .get()
+            < 5
+        {
+            if start.elapsed().as_secs() > 10 {
+                panic!("Timed out waiting for the sui syncer to time out");
+            }
+            tokio::time::sleep(tokio::time::Duration::from_millis(100)).await;
+        }
+        // Only the action in the executor channel and the one being submitted are in
+        // the pending log, the rest of the events are left on chain.
+        assert_eq!(store.get_all_pending_actions().len(), 2);
+        assert_eq!(
+            executor_requested_action_rx.try_recv().unwrap_err(),
+            tokio::sync::broadcast::error::TryRecvError::Empty
+        );
+
+        unblock_tx.send(()).unwrap();
+        let mut received = HashSet::new();
+        while received.len() < expected.len() {
+            let digest =
+                tokio::time::timeout(Duration::from_secs(10), executor_requested_action_rx.recv())
+                    .await
+                    .expect("Timed out waiting for actions after unblocking executor")
+                    .unwrap();
+            assert!(received.insert(digest), "Action submitted twice");
+        }
+        assert_eq!(received, expected);
+
+        let start = std::time::Instant::now();
+        while store.get_sui_event_cursors(&[module.clone()]).unwrap()[0] != Some(cursor) {
+            if start.elapsed().as_secs() > 5 {
+                panic!("Timed out waiting for the sui cursor to catch up");
+            }
+            tokio::time::sleep(tokio::time::Duration::from_millis(100)).await;
+        }
+        tokio::time::sleep(tokio::time::Duration::from_millis(500)).await;
+        assert_eq!(
+            executor_requested_action_rx.try_recv().unwrap_err(),
+            tokio::sync::broadcast::error::TryRecvError::Empty
+        );
+        assert_eq!(metrics.orchestrator_duplicate_actions.get(), 0);
+        assert_eq!(store.get_all_pending_actions().len(), expected.len());
+    }
+
+    #[tokio::test]
+    /// Test that when orchestrator starts, all pending actions are sent to executor
+    async fn test_resume_actions_in_pending_logs() {
+        let (
+            _sui_events_tx,
+            sui_events_rx,
+            _eth_events_tx,
+            eth_events_rx,
+            monitor_tx,
+            _monitor_rx,
+            sui_client,
+            store,
+        ) = setup();
+        let (executor, mut executor_requested_action_rx) = MockExecutor::new();
+
+        let action1 = get_test_sui_to_eth_bridge_action(
+            None,
+            Some(0),
+            Some(99),
+            Some(10000),
+            None,
+            None,
+            None,
+        );
+
+        let action2 = get_test_eth_to_sui_bridge_action(None, None, None, None);
+        store
+            .insert_pending_actions(&vec![action1.clone(), action2.clone()])
+            .unwrap();
+
+        // start orchestrator
+        let registry = Registry::new();
+        let metrics = Arc::new(BridgeMetrics::new(&registry));
+        let _handles = BridgeOrchestrator::new(
+            Arc::new(sui_client),
+            sui_events_rx,
+            eth_events_rx,
+            store.clone(),
+            monitor_tx,
+            Arc::new(TransferMinimums::default()),
+            Arc::new(NotionalTracker::default()),
+            metrics,
+        )
+        .run(executor)
+        .await;
+
+        // Executor should have received the action
+        let mut digests = std::collections::HashSet::new();
+        digests.insert(executor_requested_action_rx.recv().await.unwrap());
+        digests.insert(executor_requested_action_rx.recv().await.unwrap());
+        assert!(digests.contains(&action1.digest()));
+        assert!(digests.contains(&action2.digest()));
+        assert_eq!(digests.len(), 2);
+    }
+
+    #[tokio::test]
+    /// Test that recovery skips actions already processed on chain and submits the
+    /// rest in (chain, nonce) order
+    async fn test_recovery_skips_processed_actions_in_order() {
+        let (
+            _sui_events_tx,
+            sui_events_rx,
+            _eth_events_tx,
+            eth_events_rx,
+            monitor_tx,
+            _monitor_rx,
+            _sui_client,
+            store,
+        ) = setup();
+        let (executor, mut executor_requested_action_rx) = MockExecutor::new();
+        let mock_client = SuiMockClient::default();
+        let sui_client = SuiClient::new_for_testing(mock_client.clone());
+
+        let mut actions = vec![];
+        for nonce in 0..50 {
+            actions.push(get_test_sui_to_eth_bridge_action(
+                None,
+                None,
+                Some(nonce),
+                None,
+                None,
+                None,
+                None,
+            ));
+            actions.push(get_test_eth_to_sui_bridge_action(
+                Some(nonce),
+                None,
+                None,
+                None,
+            ));
+        }
+        // Half of the actions are already processed on chain
+        let mut expected = vec![];
+        for action in &actions {
+            let status = match action.seq_number() % 4 {
+                0 => BridgeActionStatus::Approved,
+                2 => BridgeActionStatus::Claimed,
+                _ => {
+                    expected.push(action.clone());
+                    continue;
+                }
+            };
+            mock_client.set_action_onchain_status(action, status);
+        }
+        expected.sort_by_key(|action| (action.chain_id() as u8, action.seq_number()));
+        actions.reverse();
+        store.insert_pending_actions(&actions).unwrap();
+
+        // start orchestrator
+        let registry = Registry::new();
+        let metrics = Arc::new(BridgeMetrics::new(&registry));
+        let _handles = BridgeOrchestrator::new(
+            Arc::new(sui_client),
+            sui_events_rx,
+            eth_events_rx,
+            store.clone(),
+            monitor_tx,
+            Arc::new(TransferMinimums::default()),
+            Arc::new(NotionalTracker::default()),
+            metrics.clone(),
+        )
+        .run(executor)
+        .await;
+
+        // Executor receives the unprocessed actions in order
+        for action in &expected {
+            assert_eq!(
+                executor_requested_action_rx.recv().await.unwrap(),
+                action.digest()
+            );
+        }
+        let start = std::time::Instant::now();
+        while metrics.recovery_processed_total.get() < 100 {
+            if start.elapsed().as_secs() > 5 {
+                panic!("Timed out waiting for recovery to finish");
+            }
+            tokio::time::sleep(tokio::time::Duration::from_millis(200)).await;
+        }
+        assert_eq!(metrics.recovery_pending_total.get(), 0);
+        assert_eq!(metrics.action_executor_already_processed_actions.get(), 50);
+        assert_eq!(
+            executor_requested_action_rx.try_recv().unwrap_err(),
+            tokio::sync::broadcast::error::TryRecvError::Empty
+        );
+        // One status query per batch of 50
+        assert_eq!(mock_client.onchain_status_query_count(), 2);
+
+        // Processed actions are removed from the pending log
+        let pending = store.get_all_pending_actions();
+        assert_eq!(pending.len(), 50);
+        for action in &expected {
+            assert!(pending.contains_key(&action.digest()));
+        }
+    }
+
+    #[tokio::test]
+    /// Test that recovery submits the actions whose status query fails, and still skips
+    /// the processed ones of the same batch
+    async fn test_recovery_with_failing_status_queries() {
+        let (
+            _sui_events_tx,
+            sui_events_rx,
+            _eth_events_tx,
+            eth_events_rx,
+            monitor_tx,
+            _monitor_rx,
+            _sui_client,
+            store,
+        ) = setup();
+        let (executor, mut executor_requested_action_rx) = MockExecutor::new();
+        let mock_client = SuiMockClient::default();
+        let sui_client = SuiClient::new_for_testing(mock_client.clone());
+
+        let actions = (0..10)
+            .map(|nonce| {
+                get_test_sui_to_eth_bridge_action(None, None, Some(nonce), None, None, None, None)
+            })
+            .collect::<Vec<_>>();
+        for action in &actions[..5] {
+            mock_client.set_action_onchain_status(action, BridgeActionStatus::Approved);
+        }
+        // Approved, but its status can't be queried
+        mock_client.set_action_onchain_status_error(&actions[2]);
+        store.insert_pending_actions(&actions).unwrap();
+
+        let metrics = Arc::new(BridgeMetrics::for_testing());
+        let _handles = BridgeOrchestrator::new(
+            Arc::new(sui_client),
+            sui_events_rx,
+            eth_events_rx,
+            store.clone(),
+            monitor_tx,
+            Arc::new(TransferMinimums::default()),
+            Arc::new(NotionalTracker::default()),
+            metrics.clone(),
+        )
+        .with_status_batch_size(4)
+        .run(executor)
+        .await;
+
+        for action in [&actions[2]].into_iter().chain(&actions[5..]) {
+            assert_eq!(
+                executor_requested_action_rx.recv().await.unwrap(),
+                action.digest()
+            );
+        }
+        let start = std::time::Instant::now();
+        while metrics.recovery_processed_total.get() < 10 {
+            if start.elapsed().as_secs() > 5 {
+                panic!("Timed out waiting for recovery to finish");
+            }
+            tokio::time::sleep(tokio::time::Duration::from_millis(200)).await;
+        }
+        assert_eq!(metrics.action_executor_already_processed_actions.get(), 4);
+        // The first batch fails and is queried one by one, the other two succeed
+        assert_eq!(mock_client.onchain_status_query_count(), 1 + 4 + 2);
+        assert_eq!(store.get_all_pending_actions().len(), 6);
+    }
+
+    #[allow(clippy::type_complexity)]
+    fn setup() -> (
         mysten_metrics::metered_channel::Sender<(Identifier, Vec<SuiEvent>)>,
         mysten_metrics::metered_channel::Receiver<(Identifier, Vec<SuiEvent>)>,
         mysten_metrics::metered_channel::Sender<(EthAddress, u64, Vec<EthLog>)>,
@@ -524,6 +1825,9 @@ mod tests {
     /// A `BridgeActionExecutorTrait` implementation that only tracks the submitted actions.
     struct MockExecutor {
         requested_transactions_tx: tokio::sync::broadcast::Sender<BridgeActionDigest>,
+        channel_size: usize,
+        // When set, no action is taken from the channel until it resolves
+        unblock_rx: Option<tokio::sync::oneshot::Receiver<()>>,
     }
 
     impl MockExecutor {
@@ -532,10 +1836,32 @@ mod tests {
             (
                 Self {
                     requested_transactions_tx: tx,
+                    channel_size: 100,
+                    unblock_rx: None,
                 },
                 rx,
             )
         }
+
+        fn new_blocked(
+            channel_size: usize,
+        ) -> (
+            Self,
+            tokio::sync::broadcast::Receiver<BridgeActionDigest>,
+            tokio::sync::oneshot::Sender<()>,
+        ) {
+            let (tx, rx) = tokio::sync::broadcast::channel(100);
+            let (unblock_tx, unblock_rx) = tokio::sync::oneshot::channel();
+            (
+                Self {
+                    requested_transactions_tx: tx,
+                    channel_size,
+                    unblock_rx: Some(unblock_rx),
+                },
+                rx,
+                unblock_tx,
+            )
+        }
     }
 
     impl BridgeActionExecutorTrait for MockExecutor {
@@ -547,7 +1873,7 @@ mod tests {
         ) {
             let (tx, mut rx) =
                 mysten_metrics::metered_channel::channel::<BridgeActionExecutionWrapper>(
-                    100,
+                    self.channel_size,
                     &mysten_metrics::get_metrics()
                         .unwrap()
                         .channel_inflight
@@ -555,6 +1881,9 @@ mod tests {
                 );
 
             let handles = tokio::spawn(async move {
+                if let Some(unblock_rx) = self.unblock_rx {
+                    unblock_rx.await.unwrap();
+                }
                 while let Some(action) = rx.recv().await {
                     self.requested_transactions_tx
                         .send(action.0.digest())
@@ -0,0 +1,313 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Versions of the servers behind the RPC endpoints of both chains. Providers upgrade
+//! their nodes without notice, and a load balancer may route the same endpoint to nodes
+//! of another version, so the node records the `web3_clientVersion` of the Eth provider
+//! and the version the Sui fullnode advertises in `rpc.discover` when it starts, and then
+//! periodically. Each is reported in `bridge_provider_version`, labeled by the endpoint
+//! and the version, and a change while the node runs is logged and counted so that it can
+//! be correlated with incidents.
+
+use crate::chain_health::PipelineChain;
+use crate::eth_client::EthClient;
+use crate::metrics::BridgeMetrics;
+use crate::sui_client::{SuiClient, SuiClientInner};
+use ethers::providers::JsonRpcClient;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::time::MissedTickBehavior;
+use tracing::{info, warn};
+use url::Url;
+
+pub const DEFAULT_PROVIDER_VERSION_REFRESH_INTERVAL: Duration = Duration::from_secs(300);
+
+/// Label of the endpoint at `url` in metrics and logs: its host and port. The path and
+/// the query are left out, as providers often embed API keys in them.
+pub fn endpoint_label(url: &str) -> String {
+    match Url::parse(url) {
+        Ok(url) => match (url.host_str(), url.port()) {
+            (Some(host), Some(port)) => format!("{}:{}", host, port),
+            (Some(host), None) => host.to_string(),
+            (None, _) => "unknown".to_string(),
+        },
+        Err(_) => "unknown".to_string(),
+    }
+}
+
+/// A version that differs from the one previously seen at the same endpoint.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VersionChange {
+    pub chain: PipelineChain,
+    pub endpoint: String,
+    pub previous: String,
+    pub current: String,
+}
+
+/// The latest version seen behind the RPC endpoint of each chain.
+#[derive(Debug, Default)]
+pub struct ProviderVersions {
+    sui: Mutex<Option<String>>,
+    eth: Mutex<Option<String>>,
+}
+
+impl ProviderVersions {
+    /// Records `version` as the one of the server at `endpoint`. Returns the change when
+    /// another version was seen before, which is logged.
+    pub fn record(
+        &self,
+        chain: PipelineChain,
+        endpoint: &str,
+        version: String,
+        metrics: &BridgeMetrics,
+    ) -> Option<VersionChange> {
+        let previous = self.chain(chain).lock().unwrap().replace(version.clone());
+        metrics
+            .provider_version
+            .with_label_values(&[chain.as_str(), endpoint, &version])
+            .set(1);
+        match previous {
+            None => {
+                info!(
+                    "{} RPC endpoint {} runs version {}",
+                    chain.as_str(),
+                    endpoint,
+                    version
+                );
+                None
+            }
+            Some(previous) if previous != version => {
+                warn!(
+                    "Version of the {} RPC endpoint {} changed from {} to {}",
+                    chain.as_str(),
+                    endpoint,
+                    previous,
+                    version
+                );
+                // Only the current version is reported
+                let _ = metrics.provider_version.remove_label_values(&[
+                    chain.as_str(),
+                    endpoint,
+                    &previous,
+                ]);
+                metrics
+                    .provider_version_changes
+                    .with_label_values(&[chain.as_str()])
+                    .inc();
+                Some(VersionChange {
+                    chain,
+                    endpoint: endpoint.to_string(),
+                    previous,
+                    current: version,
+                })
+            }
+            Some(_) => None,
+        }
+    }
+
+    pub fn version(&self, chain: PipelineChain) -> Option<String> {
+        self.chain(chain).lock().unwrap().clone()
+    }
+
+    fn chain(&self, chain: PipelineChain) -> &Mutex<Option<String>> {
+        match chain {
+            PipelineChain::Sui => &self.sui,
+            PipelineChain::Eth => &self.eth,
+        }
+    }
+}
+
+/// Periodically asks the Sui fullnode and the Eth provider for their version, starting
+/// right away.
+pub struct ProviderVersionTracker<C, P> {
+    sui_client: Arc<SuiClient<C>>,
+    eth_client: Arc<EthClient<P>>,
+    sui_endpoint: String,
+    eth_endpoint: String,
+    versions: ProviderVersions,
+    interval: Duration,
+    metrics: Arc<BridgeMetrics>,
+}
+
+impl<C, P> ProviderVersionTracker<C, P>
+where
+    C: SuiClientInner + 'static,
+    P: JsonRpcClient + 'static,
+{
+    /// `sui_endpoint` and `eth_endpoint` label the versions, see `endpoint_label`.
+    pub fn new(
+        sui_client: Arc<SuiClient<C>>,
+        eth_client: Arc<EthClient<P>>,
+        sui_endpoint: String,
+        eth_endpoint: String,
+        interval: Duration,
+        metrics: Arc<BridgeMetrics>,
+    ) -> Self {
+        Self {
+            sui_client,
+            eth_client,
+            sui_endpoint,
+            eth_endpoint,
+            versions: ProviderVersions::default(),
+            interval,
+            metrics,
+        }
+    }
+
+    pub async fn run(self) {
+        info!("Starting ProviderVersionTracker");
+        let mut interval = tokio::time::interval(self.interval);
+        interval.set_missed_tick_behavior(MissedTickBehavior::Delay);
+        loop {
+            interval.tick().await;
+            self.refresh().await;
+        }
+    }
+
+    /// Asks both servers for their version once. Returns the versions that changed.
+    pub async fn refresh(&self) -> Vec<VersionChange> {
+        let mut changes = vec![];
+        match self.sui_client.get_server_version().await {
+            Ok(version) => changes.extend(self.versions.record(
+                PipelineChain::Sui,
+                &self.sui_endpoint,
+                version,
+                &self.metrics,
+            )),
+            Err(e) => warn!("Failed to get the version of the Sui fullnode: {:?}", e),
+        }
+        match self.eth_client.get_client_version().await {
+            Ok(version) => changes.extend(self.versions.record(
+                PipelineChain::Eth,
+                &self.eth_endpoint,
+                version,
+                &self.metrics,
+            )),
+            Err(e) => warn!("Failed to get the version of the Eth provider: {:?}", e),
+        }
+        changes
+    }
+
+    pub fn versions(&self) -> &ProviderVersions {
+        &self.versions
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::eth_mock_provider::EthMockProvider;
+    use crate::sui_mock_client::SuiMockClient;
+    use std::collections::HashSet;
+
+    #[test]
+    fn test_endpoint_label() {
+        assert_eq!(
+            endpoint_label("https://mainnet.infura.io/v3/secret-api-key"),
+            "mainnet.infura.io"
+        );
+        assert_eq!(
+            endpoint_label("http://127.0.0.1:9000/?token=secret"),
+            "127.0.0.1:9000"
+        );
+        assert_eq!(endpoint_label("not a url"), "unknown");
+    }
+
+    #[tokio::test]
+    async fn test_provider_version_tracker() {
+        let registry = prometheus::Registry::new();
+        let metrics = Arc::new(BridgeMetrics::new(&registry));
+        let sui_client_mock = SuiMockClient::default();
+        let eth_provider = EthMockProvider::new();
+        let tracker = ProviderVersionTracker::new(
+            Arc::new(SuiClient::new_for_testing(sui_client_mock.clone())),
+            Arc::new(EthClient::new_mocked(eth_provider.clone(), HashSet::new())),
+            "fullnode.example.com".to_string(),
+            "eth.example.com".to_string(),
+            DEFAULT_PROVIDER_VERSION_REFRESH_INTERVAL,
+            metrics.clone(),
+        );
+        let gauge = |chain: &str, endpoint: &str, version: &str| {
+            metrics
+                .provider_version
+                .get_metric_with_label_values(&[chain, endpoint, version])
+                .unwrap()
+                .get()
+        };
+        let reported_versions = || {
+            let mut versions = vec![];
+            for family in registry.gather() {
+                if family.get_name() != "bridge_provider_version" {
+                    continue;
+                }
+                for metric in family.get_metric() {
+                    let labels = metric
+                        .get_label()
+                        .iter()
+                        .map(|label| label.get_value().to_string())
+                        .collect::<Vec<_>>();
+                    versions.push(labels.join(" "));
+                }
+            }
+            versions.sort();
+            versions
+        };
+
+        // Versions seen at startup are not changes
+        sui_client_mock.set_server_version("1.34.0");
+        eth_provider
+            .add_response("web3_clientVersion", (), "Geth/v1.14.7-stable")
+            .unwrap();
+        assert!(tracker.refresh().await.is_empty());
+        assert_eq!(
+            reported_versions(),
+            vec![
+                "eth eth.example.com Geth/v1.14.7-stable",
+                "sui fullnode.example.com 1.34.0",
+            ]
+        );
+        assert_eq!(gauge("sui", "fullnode.example.com", "1.34.0"), 1);
+
+        // Same versions
+        assert!(tracker.refresh().await.is_empty());
+
+        // The Eth provider upgraded silently
+        eth_provider
+            .add_response("web3_clientVersion", (), "Geth/v1.14.8-stable")
+            .unwrap();
+        assert_eq!(
+            tracker.refresh().await,
+            vec![VersionChange {
+                chain: PipelineChain::Eth,
+                endpoint: "eth.example.com".to_string(),
+                previous: "Geth/v1.14.7-stable".to_string(),
+                current: "Geth/v1.14.8-stable".to_string(),
+            }]
+        );
+        assert_eq!(
+            reported_versions(),
+            vec![
+                "eth eth.example.com Geth/v1.14.8-stable",
+                "sui fullnode.example.com 1.34.0",
+            ]
+        );
+        assert_eq!(
+            metrics
+                .provider_version_changes
+                .with_label_values(&["eth"])
+                .get(),
+            1
+        );
+        assert_eq!(
+            metrics
+                .provider_version_changes
+                .with_label_values(&["sui"])
+                .get(),
+            0
+        );
+        assert_eq!(
+            tracker.versions().version(PipelineChain::Eth).as_deref(),
+            Some("Geth/v1.14.8-stable")
+        );
+    }
+}
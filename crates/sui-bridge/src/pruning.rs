@@ -0,0 +1,253 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Pruning of the executed token transfers in the client storage. Each pass deletes the
+//! executed transfers of every route but the newest ones, oldest first and in batches.
+//! With an archive, a batch is only deleted once it was uploaded and verified, see
+//! `archive`: a batch that fails to be archived stays in the storage, and pruning of its
+//! route stops until the next pass. Pruned transfers are no longer known to the node: if a
+//! cursor is reset before them, their events are processed again and found executed on
+//! chain.
+
+use crate::archive::{ActionArchive, ArchivedAction};
+use crate::error::BridgeResult;
+use crate::metrics::BridgeMetrics;
+use crate::storage::BridgeOrchestratorTables;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::time::MissedTickBehavior;
+use tracing::{error, info, warn};
+
+pub const DEFAULT_PRUNING_INTERVAL: Duration = Duration::from_secs(60 * 60);
+pub const DEFAULT_RETAINED_EXECUTED_ACTIONS: u64 = 100_000;
+pub const DEFAULT_PRUNING_BATCH_SIZE: usize = 1000;
+
+pub struct ExecutedActionPruner {
+    store: Arc<BridgeOrchestratorTables>,
+    archive: Option<ActionArchive>,
+    retain: u64,
+    batch_size: usize,
+    metrics: Arc<BridgeMetrics>,
+}
+
+impl ExecutedActionPruner {
+    pub fn new(
+        store: Arc<BridgeOrchestratorTables>,
+        archive: Option<ActionArchive>,
+        retain: u64,
+        batch_size: usize,
+        metrics: Arc<BridgeMetrics>,
+    ) -> Self {
+        Self {
+            store,
+            archive,
+            retain,
+            batch_size,
+            metrics,
+        }
+    }
+
+    pub async fn run(self, interval: Duration) {
+        info!("Starting ExecutedActionPruner");
+        let mut interval = tokio::time::interval(interval);
+        interval.set_missed_tick_behavior(MissedTickBehavior::Delay);
+        loop {
+            interval.tick().await;
+            match self.prune().await {
+                Ok(0) => {}
+                Ok(pruned) => info!("Pruned {} executed actions", pruned),
+                Err(e) => warn!("Failed to prune executed actions: {:?}", e),
+            }
+        }
+    }
+
+    /// Runs one pass over every route. Returns the number of deleted transfers.
+    pub async fn prune(&self) -> BridgeResult<u64> {
+        let mut pruned = 0;
+        for (source, destination) in self.store.get_executed_action_routes()? {
+            let Some(oldest_retained) =
+                self.store
+                    .get_oldest_retained_executed_nonce(source, destination, self.retain)?
+            else {
+                continue;
+            };
+            loop {
+                let batch = self.store.get_executed_actions_before(
+                    source,
+                    destination,
+                    oldest_retained,
+                    self.batch_size,
+                )?;
+                if batch.is_empty() {
+                    break;
+                }
+                let keys = batch.iter().map(|(key, _, _)| *key).collect::<Vec<_>>();
+                if let Some(archive) = &self.archive {
+                    let archived = batch
+                        .into_iter()
+                        .map(|(key, action, gas_spent_mist)| {
+                            ArchivedAction::new(key, action, gas_spent_mist)
+                        })
+                        .collect::<Vec<_>>();
+                    if let Err(e) = archive.archive(&archived).await {
+                        error!(
+                            "Failed to archive executed actions {:?} to {:?}, keeping them: {:?}",
+                            keys.first(),
+                            keys.last(),
+                            e
+                        );
+                        self.metrics.archive_upload_failures.inc();
+                        break;
+                    }
+                    self.metrics
+                        .archived_executed_actions
+                        .inc_by(keys.len() as u64);
+                }
+                self.store.delete_executed_actions(&keys)?;
+                self.metrics
+                    .pruned_executed_actions
+                    .inc_by(keys.len() as u64);
+                pruned += keys.len() as u64;
+            }
+        }
+        Ok(pruned)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::archive::{ArchiveStore, FileArchiveStore};
+    use crate::test_utils::get_test_sui_to_eth_bridge_action;
+    use crate::types::BridgeAction;
+    use anyhow::bail;
+    use async_trait::async_trait;
+
+    fn executed_action(nonce: u64) -> BridgeAction {
+        get_test_sui_to_eth_bridge_action(None, None, Some(nonce), None, None, None, None)
+    }
+
+    fn store_with_executed_actions(
+        nonces: u64,
+    ) -> (tempfile::TempDir, Arc<BridgeOrchestratorTables>) {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let store = BridgeOrchestratorTables::new(temp_dir.path());
+        let actions = (0..nonces).map(executed_action).collect::<Vec<_>>();
+        store.insert_pending_actions(&actions).unwrap();
+        store.mark_actions_executed(&actions[1..]).unwrap();
+        store
+            .mark_action_executed_with_gas(&actions[0], 1000)
+            .unwrap();
+        (temp_dir, store)
+    }
+
+    fn executed_nonces(store: &BridgeOrchestratorTables) -> Vec<u64> {
+        let (source, destination) = store.get_executed_action_routes().unwrap()[0];
+        let mut nonces = store
+            .get_recent_executed_actions(source, destination, usize::MAX)
+            .unwrap()
+            .into_iter()
+            .map(|(nonce, _)| nonce)
+            .collect::<Vec<_>>();
+        nonces.reverse();
+        nonces
+    }
+
+    // Fails every upload, or returns corrupted files when `corrupt` is set
+    struct FailingArchiveStore {
+        corrupt: bool,
+    }
+
+    #[async_trait]
+    impl ArchiveStore for FailingArchiveStore {
+        async fn put(&self, path: &str, _bytes: Vec<u8>) -> anyhow::Result<()> {
+            if self.corrupt {
+                return Ok(());
+            }
+            bail!("Failed to upload {}", path)
+        }
+
+        async fn get(&self, _path: &str) -> anyhow::Result<Vec<u8>> {
+            Ok(vec![0; 10])
+        }
+
+        async fn list(&self, _prefix: &str) -> anyhow::Result<Vec<String>> {
+            Ok(vec![])
+        }
+    }
+
+    #[tokio::test]
+    async fn test_prune_and_archive() {
+        let (_db_dir, store) = store_with_executed_actions(10);
+        let archive_dir = tempfile::tempdir().unwrap();
+        let archive = ActionArchive::new(
+            Arc::new(FileArchiveStore::new(archive_dir.path().to_path_buf())),
+            None,
+        );
+        let metrics = Arc::new(BridgeMetrics::for_testing());
+        let pruner =
+            ExecutedActionPruner::new(store.clone(), Some(archive.clone()), 3, 4, metrics.clone());
+
+        assert_eq!(pruner.prune().await.unwrap(), 7);
+        assert_eq!(executed_nonces(&store), vec![7, 8, 9]);
+        assert_eq!(metrics.pruned_executed_actions.get(), 7);
+        assert_eq!(metrics.archived_executed_actions.get(), 7);
+        assert_eq!(metrics.archive_upload_failures.get(), 0);
+        // Two batches were archived, with the gas spent
+        let archived = archive
+            .fetch(&executed_action(0).digest())
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(archived.gas_spent_mist, Some(1000));
+        assert_eq!(store.get_executed_action_gas(2, 12, 0).unwrap(), None);
+        let archived = archive
+            .fetch(&executed_action(6).digest())
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(archived.action, executed_action(6));
+        assert!(archive
+            .fetch(&executed_action(7).digest())
+            .await
+            .unwrap()
+            .is_none());
+
+        // Nothing left to prune
+        assert_eq!(pruner.prune().await.unwrap(), 0);
+        assert_eq!(executed_nonces(&store), vec![7, 8, 9]);
+    }
+
+    #[tokio::test]
+    async fn test_prune_without_archive() {
+        let (_db_dir, store) = store_with_executed_actions(5);
+        let metrics = Arc::new(BridgeMetrics::for_testing());
+        let pruner = ExecutedActionPruner::new(store.clone(), None, 10, 2, metrics.clone());
+        // Fewer transfers than retained
+        assert_eq!(pruner.prune().await.unwrap(), 0);
+
+        let pruner = ExecutedActionPruner::new(store.clone(), None, 1, 2, metrics.clone());
+        assert_eq!(pruner.prune().await.unwrap(), 4);
+        assert_eq!(executed_nonces(&store), vec![4]);
+        assert_eq!(metrics.archived_executed_actions.get(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_archive_failure_blocks_pruning() {
+        for corrupt in [false, true] {
+            let (_db_dir, store) = store_with_executed_actions(5);
+            let metrics = Arc::new(BridgeMetrics::for_testing());
+            let archive = ActionArchive::new(Arc::new(FailingArchiveStore { corrupt }), None);
+            let pruner =
+                ExecutedActionPruner::new(store.clone(), Some(archive), 1, 2, metrics.clone());
+
+            assert_eq!(pruner.prune().await.unwrap(), 0, "corrupt: {}", corrupt);
+            // The first batch failed and nothing was deleted
+            assert_eq!(executed_nonces(&store), vec![0, 1, 2, 3, 4]);
+            assert_eq!(store.get_executed_action_gas(2, 12, 0).unwrap(), Some(1000));
+            assert_eq!(metrics.archive_upload_failures.get(), 1);
+            assert_eq!(metrics.archived_executed_actions.get(), 0);
+            assert_eq!(metrics.pruned_executed_actions.get(), 0);
+        }
+    }
+}
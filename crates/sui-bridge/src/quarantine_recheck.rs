@@ -0,0 +1,422 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Verifies quarantined actions against the chain again, e.g. after a contract upgrade
+//! fixed the events that couldn't be verified before. A token transfer is valid again when
+//! the event it was built from is found on its source chain and gives back the same
+//! action: it's moved back to the pending actions and handed to the executor. Actions that
+//! are still invalid stay quarantined, with the time and reason of the check recorded in
+//! `quarantine_checks`. Governance actions are not built from events and are never
+//! released. Operators verify the actions matching a filter through
+//! `POST /admin/quarantine/requeue_matching`, and `QuarantineRechecker::run` optionally
+//! verifies a few of them periodically, the least recently verified first.
+
+use crate::api_types::{encode_action_digest, RequeueMatching, RequeueMatchingRequest};
+use crate::error::{BridgeResult, ErrorKind};
+use crate::eth_client::EthClient;
+use crate::metrics::BridgeMetrics;
+use crate::storage::{BridgeOrchestratorTables, QuarantineCheck};
+use crate::sui_client::{SuiClient, SuiClientInner};
+use crate::timestamps::now_ms;
+use crate::types::BridgeAction;
+use ethers::providers::JsonRpcClient;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::Mutex;
+use tokio::time::{self, MissedTickBehavior};
+use tracing::{error, info, warn};
+
+pub const DEFAULT_QUARANTINE_RECHECK_INTERVAL: Duration = Duration::from_secs(6 * 60 * 60);
+pub const DEFAULT_QUARANTINE_RECHECK_MAX_ACTIONS: usize = 20;
+
+pub struct QuarantineRechecker<C, P> {
+    store: Arc<BridgeOrchestratorTables>,
+    sui_client: Arc<SuiClient<C>>,
+    eth_client: Arc<EthClient<P>>,
+    // Receives the released actions, to hand them to the executor
+    released_tx: mysten_metrics::metered_channel::Sender<BridgeAction>,
+    // Passes requested by operators and periodic ones don't run at once
+    lock: Mutex<()>,
+    metrics: Arc<BridgeMetrics>,
+}
+
+impl<C, P> QuarantineRechecker<C, P>
+where
+    C: SuiClientInner + 'static,
+    P: JsonRpcClient + 'static,
+{
+    pub fn new(
+        store: Arc<BridgeOrchestratorTables>,
+        sui_client: Arc<SuiClient<C>>,
+        eth_client: Arc<EthClient<P>>,
+        released_tx: mysten_metrics::metered_channel::Sender<BridgeAction>,
+        metrics: Arc<BridgeMetrics>,
+    ) -> Self {
+        Self {
+            store,
+            sui_client,
+            eth_client,
+            released_tx,
+            lock: Mutex::new(()),
+            metrics,
+        }
+    }
+
+    /// Verifies at most `max_actions_per_pass` quarantined actions every `interval`.
+    pub async fn run(self: Arc<Self>, interval: Duration, max_actions_per_pass: usize) {
+        info!(?interval, "Starting QuarantineRechecker");
+        let mut interval = time::interval(interval);
+        interval.set_missed_tick_behavior(MissedTickBehavior::Delay);
+        // The actions were just loaded, the first pass waits for a full interval
+        interval.tick().await;
+        loop {
+            interval.tick().await;
+            let request = RequeueMatchingRequest {
+                limit: Some(max_actions_per_pass),
+                ..Default::default()
+            };
+            match self.requeue_matching(&request).await {
+                Ok(result) => info!(
+                    "Verified quarantined actions again: {} released, {} still invalid, {} errors",
+                    result.released.len(),
+                    result.still_invalid.len(),
+                    result.errors.len()
+                ),
+                Err(e) => error!("Couldn't verify quarantined actions again: {:?}", e),
+            }
+        }
+    }
+
+    /// Verifies the quarantined actions that match `request` again, and releases the valid
+    /// ones.
+    pub async fn requeue_matching(
+        &self,
+        request: &RequeueMatchingRequest,
+    ) -> BridgeResult<RequeueMatching> {
+        let _guard = self.lock.lock().await;
+        let checks = self.store.get_quarantine_checks();
+        let mut candidates = self
+            .store
+            .get_all_quarantined_actions()
+            .into_iter()
+            .filter(|(_, quarantined)| request.matches(&quarantined.action))
+            .map(|(digest, quarantined)| {
+                let last_checked_at_ms = checks.get(&digest).map(|check| check.checked_at_ms);
+                (last_checked_at_ms, quarantined.quarantined_at_ms, digest)
+            })
+            .collect::<Vec<_>>();
+        // Never verified again first, then the least recently verified
+        candidates.sort();
+        candidates.truncate(request.limit.unwrap_or(usize::MAX));
+
+        let mut result = RequeueMatching::default();
+        for (_, _, digest) in candidates {
+            // Deleted or requeued by an operator meanwhile
+            let Some(quarantined) = self.store.get_quarantined_action(&digest)? else {
+                continue;
+            };
+            let encoded_digest = encode_action_digest(&digest);
+            match self.verify(&quarantined.action).await {
+                Ok(None) => {
+                    let Some(action) = self.store.requeue_quarantined_action(&digest)? else {
+                        continue;
+                    };
+                    info!(
+                        "Quarantined action {} is valid now, releasing it: {:?}",
+                        encoded_digest, action
+                    );
+                    self.metrics
+                        .quarantine_rechecks
+                        .with_label_values(&["released"])
+                        .inc();
+                    // It's pending already, the executor gets it on restart otherwise
+                    if let Err(e) = self.released_tx.send(action).await {
+                        warn!(
+                            "Couldn't hand released action {} to the executor: {:?}",
+                            encoded_digest, e
+                        );
+                    }
+                    result.released.push(encoded_digest);
+                }
+                Ok(Some(reason)) => {
+                    self.store.record_quarantine_check(
+                        &digest,
+                        &QuarantineCheck {
+                            checked_at_ms: now_ms(),
+                            reason: reason.clone(),
+                        },
+                    )?;
+                    self.metrics
+                        .quarantine_rechecks
+                        .with_label_values(&["still_invalid"])
+                        .inc();
+                    result.still_invalid.insert(encoded_digest, reason);
+                }
+                Err(e) => {
+                    warn!(
+                        "Couldn't verify quarantined action {} again: {:?}",
+                        encoded_digest, e
+                    );
+                    self.metrics
+                        .quarantine_rechecks
+                        .with_label_values(&["error"])
+                        .inc();
+                    result.errors.insert(encoded_digest, format!("{:?}", e));
+                }
+            }
+        }
+        Ok(result)
+    }
+
+    /// Returns why `action` is still invalid, None if it's valid. Errors are the ones that
+    /// may not happen again, e.g. RPC errors.
+    async fn verify(&self, action: &BridgeAction) -> BridgeResult<Option<String>> {
+        let onchain_action = match action {
+            BridgeAction::SuiToEthBridgeAction(transfer) => {
+                self.sui_client
+                    .get_bridge_action_by_tx_digest_and_event_idx_maybe(
+                        &transfer.sui_tx_digest,
+                        transfer.sui_tx_event_index,
+                    )
+                    .await
+            }
+            BridgeAction::EthToSuiBridgeAction(transfer) => {
+                self.eth_client
+                    .get_finalized_bridge_action_maybe(
+                        transfer.eth_tx_hash,
+                        transfer.eth_event_index,
+                        0,
+                    )
+                    .await
+            }
+            _ => {
+                return Ok(Some(
+                    "Governance actions are not verified against the chain".to_string(),
+                ))
+            }
+        };
+        match onchain_action {
+            Ok(onchain_action) if onchain_action.digest() == action.digest() => Ok(None),
+            Ok(onchain_action) => Ok(Some(format!(
+                "The event on chain is another action: {:?}",
+                onchain_action
+            ))),
+            Err(e) if e.kind() == ErrorKind::Permanent => Ok(Some(format!("{:?}", e))),
+            Err(e) => Err(e),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::eth_mock_provider::EthMockProvider;
+    use crate::events::{init_all_struct_tags, MoveTokenDepositedEvent, SuiToEthTokenBridgeV1};
+    use crate::storage::QuarantinedAction;
+    use crate::sui_mock_client::SuiMockClient;
+    use crate::test_utils::{get_test_eth_to_sui_bridge_action, get_test_sui_to_eth_bridge_action};
+    use ethers::types::Address as EthAddress;
+    use std::collections::HashSet;
+    use sui_json_rpc_types::SuiEvent;
+    use sui_types::base_types::SuiAddress;
+    use sui_types::bridge::{BridgeChainId, TOKEN_ID_USDC};
+
+    fn quarantine(store: &BridgeOrchestratorTables, action: &BridgeAction) {
+        store
+            .quarantine_action(&QuarantinedAction {
+                action: action.clone(),
+                reason: "Unverifiable event".to_string(),
+                failures: 3,
+                quarantined_at_ms: 1_700_000_000_000,
+            })
+            .unwrap();
+    }
+
+    // A deposit on Sui, and the action built from it
+    async fn sui_deposit(
+        sui_client: &SuiClient<SuiMockClient>,
+        sui_client_mock: &SuiMockClient,
+        nonce: u64,
+    ) -> (SuiEvent, BridgeAction) {
+        let mut event = SuiEvent::random_for_testing();
+        event.type_ = SuiToEthTokenBridgeV1.get().unwrap().clone();
+        event.bcs = bcs::to_bytes(&MoveTokenDepositedEvent {
+            seq_num: nonce,
+            source_chain: BridgeChainId::SuiCustom as u8,
+            sender_address: SuiAddress::random_for_testing_only().to_vec(),
+            target_chain: BridgeChainId::EthCustom as u8,
+            target_address: EthAddress::random().as_bytes().to_vec(),
+            token_type: TOKEN_ID_USDC,
+            amount_sui_adjusted: 12345,
+        })
+        .unwrap();
+        sui_client_mock.add_events_by_tx_digest(event.id.tx_digest, vec![event.clone()]);
+        let action = sui_client
+            .get_bridge_action_by_tx_digest_and_event_idx_maybe(&event.id.tx_digest, 0)
+            .await
+            .unwrap();
+        (event, action)
+    }
+
+    #[tokio::test]
+    async fn test_requeue_matching_releases_valid_actions() {
+        telemetry_subscribers::init_for_testing();
+        mysten_metrics::init_metrics(&prometheus::Registry::new());
+        init_all_struct_tags();
+        let temp_dir = tempfile::tempdir().unwrap();
+        let store = BridgeOrchestratorTables::new(temp_dir.path());
+        let metrics = Arc::new(BridgeMetrics::for_testing());
+        let sui_client_mock = SuiMockClient::default();
+        let sui_client = Arc::new(SuiClient::new_for_testing(sui_client_mock.clone()));
+        let eth_client = Arc::new(EthClient::new_mocked(
+            EthMockProvider::new(),
+            HashSet::new(),
+        ));
+        let (released_tx, mut released_rx) = mysten_metrics::metered_channel::channel(
+            10,
+            &mysten_metrics::get_metrics()
+                .unwrap()
+                .channel_inflight
+                .with_label_values(&["quarantine_released_queue"]),
+        );
+        let rechecker = QuarantineRechecker::new(
+            store.clone(),
+            sui_client.clone(),
+            eth_client,
+            released_tx,
+            metrics.clone(),
+        );
+
+        // Two Sui deposits whose events can't be found, and a governance action
+        let (fixed_event, fixed) = sui_deposit(&sui_client, &sui_client_mock, 1).await;
+        let (_, broken) = sui_deposit(&sui_client, &sui_client_mock, 2).await;
+        let governance = BridgeAction::EmergencyAction(crate::types::EmergencyAction {
+            nonce: 3,
+            chain_id: BridgeChainId::SuiCustom,
+            action_type: crate::types::EmergencyActionType::Pause,
+        });
+        for action in [&fixed, &broken, &governance] {
+            quarantine(&store, action);
+        }
+        let BridgeAction::SuiToEthBridgeAction(broken_transfer) = &broken else {
+            unreachable!()
+        };
+        sui_client_mock.add_events_by_tx_digest(fixed_event.id.tx_digest, vec![]);
+        sui_client_mock.add_events_by_tx_digest(broken_transfer.sui_tx_digest, vec![]);
+
+        let result = rechecker
+            .requeue_matching(&RequeueMatchingRequest::default())
+            .await
+            .unwrap();
+        assert!(result.released.is_empty());
+        assert_eq!(result.still_invalid.len(), 3);
+        let first_checks = store.get_quarantine_checks();
+        assert_eq!(first_checks.len(), 3);
+
+        // The upgrade makes the event of one of them verifiable
+        sui_client_mock.add_events_by_tx_digest(fixed_event.id.tx_digest, vec![fixed_event]);
+        tokio::time::sleep(Duration::from_millis(5)).await;
+        let result = rechecker
+            .requeue_matching(&RequeueMatchingRequest::default())
+            .await
+            .unwrap();
+        assert_eq!(result.released, vec![encode_action_digest(&fixed.digest())]);
+        assert_eq!(result.still_invalid.len(), 2);
+        assert!(result.errors.is_empty());
+        // Released to the pipeline
+        assert_eq!(released_rx.recv().await.unwrap(), fixed);
+        assert_eq!(
+            store.get_pending_action(&fixed.digest()).unwrap(),
+            Some(fixed.clone())
+        );
+        assert!(store
+            .get_quarantined_action(&fixed.digest())
+            .unwrap()
+            .is_none());
+        // The others stay quarantined, checked again
+        let checks = store.get_quarantine_checks();
+        assert_eq!(checks.len(), 2);
+        for action in [&broken, &governance] {
+            assert!(store
+                .get_quarantined_action(&action.digest())
+                .unwrap()
+                .is_some());
+            let check = &checks[&action.digest()];
+            assert!(check.checked_at_ms > first_checks[&action.digest()].checked_at_ms);
+        }
+        assert_eq!(
+            metrics
+                .quarantine_rechecks
+                .with_label_values(&["released"])
+                .get(),
+            1
+        );
+    }
+
+    #[tokio::test]
+    async fn test_requeue_matching_filter_and_limit() {
+        mysten_metrics::init_metrics(&prometheus::Registry::new());
+        let temp_dir = tempfile::tempdir().unwrap();
+        let store = BridgeOrchestratorTables::new(temp_dir.path());
+        let sui_client_mock = SuiMockClient::default();
+        let (released_tx, _released_rx) = mysten_metrics::metered_channel::channel(
+            10,
+            &mysten_metrics::get_metrics()
+                .unwrap()
+                .channel_inflight
+                .with_label_values(&["quarantine_released_queue"]),
+        );
+        let rechecker = QuarantineRechecker::new(
+            store.clone(),
+            Arc::new(SuiClient::new_for_testing(sui_client_mock.clone())),
+            Arc::new(EthClient::new_mocked(
+                EthMockProvider::new(),
+                HashSet::new(),
+            )),
+            released_tx,
+            Arc::new(BridgeMetrics::for_testing()),
+        );
+        let sui_actions = (1..=4)
+            .map(|nonce| {
+                get_test_sui_to_eth_bridge_action(None, None, Some(nonce), None, None, None, None)
+            })
+            .collect::<Vec<_>>();
+        for action in &sui_actions {
+            let BridgeAction::SuiToEthBridgeAction(transfer) = action else {
+                unreachable!()
+            };
+            sui_client_mock.add_events_by_tx_digest(transfer.sui_tx_digest, vec![]);
+            quarantine(&store, action);
+        }
+        quarantine(
+            &store,
+            &get_test_eth_to_sui_bridge_action(Some(2), None, None, None),
+        );
+
+        // Sui deposits with nonces 2 to 4, at most 2 of them
+        let request = RequeueMatchingRequest {
+            chain_id: Some(BridgeChainId::SuiCustom as u8),
+            min_nonce: Some(2),
+            max_nonce: Some(4),
+            limit: Some(2),
+            ..Default::default()
+        };
+        let result = rechecker.requeue_matching(&request).await.unwrap();
+        assert_eq!(result.still_invalid.len(), 2);
+        let checks = store.get_quarantine_checks();
+        assert_eq!(checks.len(), 2);
+        assert!(!checks.contains_key(&sui_actions[0].digest()));
+        let unchecked = sui_actions[1..]
+            .iter()
+            .find(|action| !checks.contains_key(&action.digest()))
+            .unwrap();
+
+        // The next pass verifies the one left first
+        let result = rechecker.requeue_matching(&request).await.unwrap();
+        assert_eq!(result.still_invalid.len(), 2);
+        assert!(result
+            .still_invalid
+            .contains_key(&encode_action_digest(&unchecked.digest())));
+        assert_eq!(store.get_quarantine_checks().len(), 3);
+    }
+}
@@ -0,0 +1,246 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Readiness of the node, for embedders that run it in the process of a validator and for
+//! `/ready`. Unlike `/health`, which reports how well the node works, readiness only
+//! answers whether it's up: the storage is open, the committee was loaded, both syncers
+//! completed an iteration, and the server is bound. A syncer whose last iteration failed,
+//! or that was given up after crash looping, makes the node not ready again until it
+//! succeeds. A node that doesn't run the client only waits for its server.
+
+use crate::api_types::{NotReadyReason, ReadinessState};
+use crate::chain_health::PipelineChain;
+use std::sync::Mutex;
+use tokio::sync::watch;
+use tracing::{info, warn};
+
+/// Notified whenever the readiness of the node changes.
+pub type BridgeReadinessHandle = watch::Receiver<ReadinessState>;
+
+#[derive(Debug, Clone, Default)]
+enum SyncerReadiness {
+    #[default]
+    NotStarted,
+    Succeeded,
+    Failing(String),
+}
+
+#[derive(Debug)]
+struct Components {
+    runs_client: bool,
+    storage_open: bool,
+    committee_loaded: bool,
+    sui_syncer: SyncerReadiness,
+    eth_syncer: SyncerReadiness,
+    server_bound: bool,
+}
+
+impl Default for Components {
+    fn default() -> Self {
+        Self {
+            runs_client: true,
+            storage_open: false,
+            committee_loaded: false,
+            sui_syncer: SyncerReadiness::NotStarted,
+            eth_syncer: SyncerReadiness::NotStarted,
+            server_bound: false,
+        }
+    }
+}
+
+impl Components {
+    fn state(&self) -> ReadinessState {
+        let mut reasons = vec![];
+        if self.runs_client {
+            if !self.storage_open {
+                reasons.push(NotReadyReason::StorageNotOpen);
+            }
+            if !self.committee_loaded {
+                reasons.push(NotReadyReason::CommitteeNotLoaded);
+            }
+            for (chain, syncer) in [
+                (PipelineChain::Sui, &self.sui_syncer),
+                (PipelineChain::Eth, &self.eth_syncer),
+            ] {
+                let chain = chain.as_str().to_string();
+                match syncer {
+                    SyncerReadiness::NotStarted => {
+                        reasons.push(NotReadyReason::SyncerNotStarted { chain })
+                    }
+                    SyncerReadiness::Failing(error) => {
+                        reasons.push(NotReadyReason::SyncerFailing {
+                            chain,
+                            error: error.clone(),
+                        })
+                    }
+                    SyncerReadiness::Succeeded => {}
+                }
+            }
+        }
+        if !self.server_bound {
+            reasons.push(NotReadyReason::ServerNotBound);
+        }
+        ReadinessState {
+            ready: reasons.is_empty(),
+            reasons,
+        }
+    }
+
+    fn syncer(&mut self, chain: PipelineChain) -> &mut SyncerReadiness {
+        match chain {
+            PipelineChain::Sui => &mut self.sui_syncer,
+            PipelineChain::Eth => &mut self.eth_syncer,
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct BridgeReadiness {
+    components: Mutex<Components>,
+    state: watch::Sender<ReadinessState>,
+}
+
+impl Default for BridgeReadiness {
+    fn default() -> Self {
+        let components = Components::default();
+        Self {
+            state: watch::channel(components.state()).0,
+            components: Mutex::new(components),
+        }
+    }
+}
+
+impl BridgeReadiness {
+    /// The node doesn't run the client, so it doesn't wait for the storage, the committee
+    /// and the syncers.
+    pub fn without_client(&self) {
+        self.update(|components| components.runs_client = false);
+    }
+
+    pub fn set_storage_open(&self) {
+        self.update(|components| components.storage_open = true);
+    }
+
+    pub fn set_committee_loaded(&self) {
+        self.update(|components| components.committee_loaded = true);
+    }
+
+    pub fn set_server_bound(&self) {
+        self.update(|components| components.server_bound = true);
+    }
+
+    /// Records an iteration of the syncer of `chain` that completed.
+    pub fn record_syncer_success(&self, chain: PipelineChain) {
+        self.update(|components| *components.syncer(chain) = SyncerReadiness::Succeeded);
+    }
+
+    /// Records an iteration of the syncer of `chain` that failed, or the syncer stopping.
+    pub fn record_syncer_failure(&self, chain: PipelineChain, error: String) {
+        self.update(|components| *components.syncer(chain) = SyncerReadiness::Failing(error));
+    }
+
+    pub fn state(&self) -> ReadinessState {
+        self.state.borrow().clone()
+    }
+
+    pub fn subscribe(&self) -> BridgeReadinessHandle {
+        self.state.subscribe()
+    }
+
+    // Subscribers are only notified when the state changes, not on every iteration
+    fn update(&self, f: impl FnOnce(&mut Components)) {
+        let mut components = self.components.lock().unwrap();
+        f(&mut components);
+        let state = components.state();
+        self.state.send_if_modified(|current| {
+            if *current == state {
+                return false;
+            }
+            if state.ready {
+                info!("Bridge node is ready");
+            } else if current.ready {
+                warn!("Bridge node is not ready anymore: {:?}", state.reasons);
+            }
+            *current = state;
+            true
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn syncer_not_started(chain: &str) -> NotReadyReason {
+        NotReadyReason::SyncerNotStarted {
+            chain: chain.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_readiness_transitions() {
+        let readiness = BridgeReadiness::default();
+        let mut handle = readiness.subscribe();
+        assert_eq!(
+            handle.borrow().reasons,
+            vec![
+                NotReadyReason::StorageNotOpen,
+                NotReadyReason::CommitteeNotLoaded,
+                syncer_not_started("sui"),
+                syncer_not_started("eth"),
+                NotReadyReason::ServerNotBound,
+            ]
+        );
+
+        readiness.set_storage_open();
+        readiness.set_committee_loaded();
+        readiness.set_server_bound();
+        assert!(handle.has_changed().unwrap());
+        let state = handle.borrow_and_update().clone();
+        assert!(!state.ready);
+        assert_eq!(
+            state.reasons,
+            vec![syncer_not_started("sui"), syncer_not_started("eth")]
+        );
+
+        readiness.record_syncer_success(PipelineChain::Sui);
+        assert_eq!(
+            handle.borrow_and_update().reasons,
+            vec![syncer_not_started("eth")]
+        );
+        readiness.record_syncer_success(PipelineChain::Eth);
+        assert!(handle.borrow_and_update().ready);
+        // Later iterations don't notify
+        readiness.record_syncer_success(PipelineChain::Eth);
+        assert!(!handle.has_changed().unwrap());
+
+        // A failed iteration makes the node not ready until the syncer succeeds again
+        readiness.record_syncer_failure(PipelineChain::Eth, "connection refused".to_string());
+        assert!(handle.has_changed().unwrap());
+        assert_eq!(
+            *handle.borrow_and_update(),
+            ReadinessState {
+                ready: false,
+                reasons: vec![NotReadyReason::SyncerFailing {
+                    chain: "eth".to_string(),
+                    error: "connection refused".to_string(),
+                }],
+            }
+        );
+        readiness.record_syncer_success(PipelineChain::Eth);
+        assert!(handle.borrow_and_update().ready);
+        assert_eq!(readiness.state(), *handle.borrow());
+    }
+
+    #[test]
+    fn test_readiness_without_client() {
+        let readiness = BridgeReadiness::default();
+        readiness.without_client();
+        assert_eq!(
+            readiness.state().reasons,
+            vec![NotReadyReason::ServerNotBound]
+        );
+        readiness.set_server_bound();
+        assert!(readiness.state().ready);
+    }
+}
@@ -0,0 +1,381 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! `BridgeReconciler` periodically compares the token transfers this node recorded as
+//! executed with their status on chain. For each route with local records, it samples
+//! the most recent nonces and reports transfers that are executed locally but not on
+//! chain, and transfers that are done on chain but unknown locally.
+
+use crate::alerts::{AlertCondition, AlertSink};
+use crate::error::BridgeResult;
+use crate::eth_client::EthClient;
+use crate::inc_err_counter;
+use crate::metrics::BridgeMetrics;
+use crate::orchestrator::DEFAULT_ONCHAIN_STATUS_BATCH_SIZE;
+use crate::storage::BridgeOrchestratorTables;
+use crate::sui_client::{SuiClient, SuiClientInner};
+use crate::types::{BridgeAction, BridgeActionStatus};
+use ethers::providers::JsonRpcClient;
+use ethers::types::Address as EthAddress;
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+use sui_types::bridge::BridgeChainId;
+use tokio::time::{Duration, MissedTickBehavior};
+use tracing::{error, info, warn};
+
+pub const DEFAULT_RECONCILIATION_INTERVAL: Duration = Duration::from_secs(600);
+pub const DEFAULT_RECONCILIATION_SAMPLE_SIZE: u64 = 100;
+pub const DEFAULT_RECONCILIATION_MAX_RPC_QUERIES_PER_SECOND: u32 = 5;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReconciliationMismatchKind {
+    // Executed locally but neither approved nor claimed on chain
+    NotOnChain,
+    // Approved or claimed on chain but not executed, pending or dropped locally
+    NotExecutedLocally,
+}
+
+impl ReconciliationMismatchKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::NotOnChain => "not_on_chain",
+            Self::NotExecutedLocally => "not_executed_locally",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReconciliationMismatch {
+    pub source_chain_id: u8,
+    pub destination_chain_id: u8,
+    pub nonce: u64,
+    pub kind: ReconciliationMismatchKind,
+}
+
+pub struct BridgeReconciler<C, P> {
+    sui_client: Arc<SuiClient<C>>,
+    eth_client: Arc<EthClient<P>>,
+    eth_bridge_address: EthAddress,
+    store: Arc<BridgeOrchestratorTables>,
+    routes: Vec<(BridgeChainId, BridgeChainId)>,
+    interval: Duration,
+    sample_size: u64,
+    max_rpc_queries_per_second: u32,
+    alerts: Option<Arc<AlertSink>>,
+    status_batch_size: usize,
+    metrics: Arc<BridgeMetrics>,
+}
+
+impl<C, P> BridgeReconciler<C, P>
+where
+    C: SuiClientInner + 'static,
+    P: JsonRpcClient + Clone + 'static,
+{
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        sui_client: Arc<SuiClient<C>>,
+        eth_client: Arc<EthClient<P>>,
+        eth_bridge_address: EthAddress,
+        store: Arc<BridgeOrchestratorTables>,
+        sui_chain_id: BridgeChainId,
+        eth_chain_id: BridgeChainId,
+        interval: Duration,
+        sample_size: u64,
+        max_rpc_queries_per_second: u32,
+        metrics: Arc<BridgeMetrics>,
+    ) -> Self {
+        Self {
+            sui_client,
+            eth_client,
+            eth_bridge_address,
+            store,
+            routes: vec![(sui_chain_id, eth_chain_id), (eth_chain_id, sui_chain_id)],
+            interval,
+            sample_size,
+            max_rpc_queries_per_second,
+            alerts: None,
+            status_batch_size: DEFAULT_ONCHAIN_STATUS_BATCH_SIZE,
+            metrics,
+        }
+    }
+
+    /// Raises an alert for transfers that are processed on chain but unknown locally.
+    pub fn with_alert_sink(mut self, alerts: Arc<AlertSink>) -> Self {
+        self.alerts = Some(alerts);
+        self
+    }
+
+    /// Number of nonces whose status is queried in one request, on each chain.
+    pub fn with_status_batch_size(mut self, status_batch_size: usize) -> Self {
+        self.status_batch_size = status_batch_size;
+        self
+    }
+
+    pub async fn run(self) {
+        info!("Starting BridgeReconciler");
+        let mut interval = tokio::time::interval(self.interval);
+        interval.set_missed_tick_behavior(MissedTickBehavior::Delay);
+        loop {
+            interval.tick().await;
+            match self.reconcile().await {
+                Ok(mismatches) => info!(
+                    "Reconciliation finished with {} mismatches",
+                    mismatches.len()
+                ),
+                Err(e) => error!("Reconciliation failed: {:?}", e),
+            }
+        }
+    }
+
+    /// Runs one round of reconciliation over all routes and returns the mismatches found.
+    pub async fn reconcile(&self) -> BridgeResult<Vec<ReconciliationMismatch>> {
+        // Spaces out RPC queries to stay under `max_rpc_queries_per_second`
+        let mut rpc_ticker =
+            tokio::time::interval(Duration::from_secs(1) / self.max_rpc_queries_per_second.max(1));
+        rpc_ticker.set_missed_tick_behavior(MissedTickBehavior::Delay);
+
+        // Token transfers that are known locally but not executed (yet)
+        let not_executed = self
+            .store
+            .get_all_pending_actions()
+            .into_values()
+            .chain(self.store.get_all_dropped_actions().into_values())
+            .filter(|action| {
+                matches!(
+                    action,
+                    BridgeAction::SuiToEthBridgeAction(_) | BridgeAction::EthToSuiBridgeAction(_)
+                )
+            })
+            .map(|action| (action.chain_id() as u8, action.seq_number()))
+            .collect::<HashSet<_>>();
+
+        // Skip routes with no local records, there is nothing to compare against
+        let mut routes = vec![];
+        for (source_chain, destination_chain) in &self.routes {
+            let (source_chain_id, destination_chain_id) =
+                (*source_chain as u8, *destination_chain as u8);
+            if let Some((latest_nonce, _)) = self
+                .store
+                .get_recent_executed_actions(source_chain_id, destination_chain_id, 1)?
+                .pop()
+            {
+                routes.push((*source_chain, destination_chain_id, latest_nonce));
+            }
+        }
+        if routes.is_empty() {
+            return Ok(vec![]);
+        }
+        let batch_size = self.status_batch_size.max(1);
+
+        let mut mismatches = vec![];
+        for (source_chain, destination_chain_id, latest_nonce) in routes {
+            let source_chain_id = source_chain as u8;
+            let first_nonce = (latest_nonce + 1).saturating_sub(self.sample_size);
+            let nonces = (first_nonce..=latest_nonce).collect::<Vec<_>>();
+            let mut statuses = Vec::with_capacity(nonces.len());
+            for batch in nonces.chunks(batch_size) {
+                rpc_ticker.tick().await;
+                let keys = batch
+                    .iter()
+                    .map(|nonce| (source_chain_id, *nonce))
+                    .collect::<Vec<_>>();
+                statuses.extend(
+                    self.sui_client
+                        .get_token_transfer_action_onchain_statuses(&keys, batch_size)
+                        .await,
+                );
+            }
+            // A transfer from Sui may have been claimed on Eth already
+            let mut processed_on_eth = HashMap::new();
+            if source_chain.is_sui_chain() {
+                let not_on_sui = nonces
+                    .iter()
+                    .zip(&statuses)
+                    .filter(|(_, status)| {
+                        !matches!(
+                            status,
+                            Ok(BridgeActionStatus::Approved | BridgeActionStatus::Claimed) | Err(_)
+                        )
+                    })
+                    .map(|(nonce, _)| *nonce)
+                    .collect::<Vec<_>>();
+                for batch in not_on_sui.chunks(batch_size) {
+                    rpc_ticker.tick().await;
+                    let processed = self
+                        .eth_client
+                        .are_transfers_processed(self.eth_bridge_address, batch, batch_size)
+                        .await;
+                    processed_on_eth.extend(batch.iter().copied().zip(processed));
+                }
+            }
+
+            for (nonce, status) in nonces.into_iter().zip(statuses) {
+                let executed_locally = self
+                    .store
+                    .get_executed_action(source_chain_id, destination_chain_id, nonce)?
+                    .is_some();
+                let status = match status {
+                    Ok(status) => status,
+                    Err(e) => {
+                        warn!(
+                            source_chain_id,
+                            nonce, "Failed to get token transfer status on Sui: {:?}", e
+                        );
+                        continue;
+                    }
+                };
+                let on_chain = match processed_on_eth.remove(&nonce) {
+                    None => matches!(
+                        status,
+                        BridgeActionStatus::Approved | BridgeActionStatus::Claimed
+                    ),
+                    Some(Ok(processed)) => processed,
+                    Some(Err(e)) => {
+                        warn!(
+                            source_chain_id,
+                            nonce, "Failed to get token transfer status on Eth: {:?}", e
+                        );
+                        continue;
+                    }
+                };
+                let kind = match (executed_locally, on_chain) {
+                    (true, false) => ReconciliationMismatchKind::NotOnChain,
+                    (false, true) if !not_executed.contains(&(source_chain_id, nonce)) => {
+                        ReconciliationMismatchKind::NotExecutedLocally
+                    }
+                    _ => continue,
+                };
+                warn!(
+                    source_chain_id,
+                    destination_chain_id,
+                    nonce,
+                    "Reconciliation mismatch: {}, on-chain status: {:?}",
+                    kind.as_str(),
+                    status
+                );
+                inc_err_counter!(
+                    self.metrics,
+                    reconciliation_mismatches[kind.as_str()],
+                    format!(
+                        "Token transfer from chain {} to chain {} with nonce {}, on-chain status: {:?}",
+                        source_chain_id, destination_chain_id, nonce, status
+                    ),
+                    vec![]
+                );
+                if kind == ReconciliationMismatchKind::NotExecutedLocally {
+                    if let Some(alerts) = &self.alerts {
+                        alerts.raise(
+                            AlertCondition::NonceGap,
+                            format!(
+                                "Token transfer from chain {} to chain {} with nonce {} is {:?} on chain but unknown locally",
+                                source_chain_id, destination_chain_id, nonce, status
+                            ),
+                        );
+                    }
+                }
+                mismatches.push(ReconciliationMismatch {
+                    source_chain_id,
+                    destination_chain_id,
+                    nonce,
+                    kind,
+                });
+            }
+        }
+        Ok(mismatches)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::eth_mock_provider::EthMockProvider;
+    use crate::sui_mock_client::SuiMockClient;
+    use crate::test_utils::get_test_eth_to_sui_bridge_action;
+    use prometheus::Registry;
+
+    #[tokio::test]
+    async fn test_reconciliation_detects_mismatches() {
+        telemetry_subscribers::init_for_testing();
+        let temp_dir = tempfile::tempdir().unwrap();
+        let store = BridgeOrchestratorTables::new(temp_dir.path());
+        let sui_client_mock = SuiMockClient::default();
+        let sui_client = Arc::new(SuiClient::new_for_testing(sui_client_mock.clone()));
+        let eth_client = Arc::new(EthClient::new_mocked(
+            EthMockProvider::new(),
+            HashSet::new(),
+        ));
+        let metrics = Arc::new(BridgeMetrics::new(&Registry::new()));
+        let reconciler = BridgeReconciler::new(
+            sui_client,
+            eth_client,
+            EthAddress::random(),
+            store.clone(),
+            BridgeChainId::SuiCustom,
+            BridgeChainId::EthCustom,
+            DEFAULT_RECONCILIATION_INTERVAL,
+            DEFAULT_RECONCILIATION_SAMPLE_SIZE,
+            100,
+            metrics.clone(),
+        );
+
+        // No local records, nothing to compare against
+        assert!(reconciler.reconcile().await.unwrap().is_empty());
+
+        let actions = (0..6)
+            .map(|nonce| get_test_eth_to_sui_bridge_action(Some(nonce), None, None, None))
+            .collect::<Vec<_>>();
+        for action in &actions {
+            sui_client_mock.set_action_onchain_status(action, BridgeActionStatus::Approved);
+        }
+        // Nonce 1 is still pending locally, nonce 2 is unknown locally
+        store.insert_pending_actions(&[actions[1].clone()]).unwrap();
+        store
+            .mark_actions_executed(&[
+                actions[0].clone(),
+                actions[3].clone(),
+                actions[4].clone(),
+                actions[5].clone(),
+            ])
+            .unwrap();
+        // Local says nonce 4 is executed, chain says not
+        sui_client_mock.set_action_onchain_status(&actions[4], BridgeActionStatus::Pending);
+
+        let mismatches = reconciler.reconcile().await.unwrap();
+        let eth_chain_id = BridgeChainId::EthCustom as u8;
+        let sui_chain_id = BridgeChainId::SuiCustom as u8;
+        assert_eq!(
+            mismatches,
+            vec![
+                ReconciliationMismatch {
+                    source_chain_id: eth_chain_id,
+                    destination_chain_id: sui_chain_id,
+                    nonce: 2,
+                    kind: ReconciliationMismatchKind::NotExecutedLocally,
+                },
+                ReconciliationMismatch {
+                    source_chain_id: eth_chain_id,
+                    destination_chain_id: sui_chain_id,
+                    nonce: 4,
+                    kind: ReconciliationMismatchKind::NotOnChain,
+                },
+            ]
+        );
+        for kind in [
+            ReconciliationMismatchKind::NotOnChain,
+            ReconciliationMismatchKind::NotExecutedLocally,
+        ] {
+            assert_eq!(
+                metrics
+                    .reconciliation_mismatches
+                    .with_label_values(&[kind.as_str()])
+                    .get(),
+                1
+            );
+        }
+        // The 6 nonces are queried in a single batch
+        assert_eq!(sui_client_mock.onchain_status_query_count(), 1);
+        let errors = metrics.error_catalog.get("reconciliation_mismatches");
+        assert_eq!(errors.len(), 2);
+        assert!(errors[1].message.contains("nonce 4"));
+    }
+}
@@ -0,0 +1,377 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Offline replay of recorded raw Sui events and Eth logs. The raw items are run
+//! through the same parsing code the orchestrator watchers use, so the resulting
+//! `BridgeAction`s (and their digests) can be compared against what a node derived
+//! when investigating a discrepancy.
+
+use crate::abi::EthBridgeEvent;
+use crate::api_types::Action;
+use crate::events::SuiBridgeEvent;
+use crate::types::{BridgeAction, EthLog};
+use anyhow::anyhow;
+use ethers::types::{Log, H256};
+use fastcrypto::encoding::{Base58, Encoding};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::path::Path;
+use sui_json_rpc_types::SuiEvent;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum ReplaySource {
+    /// A list of Sui events, e.g. the response of `suix_queryEvents`
+    Sui,
+    /// A list of Eth logs, e.g. the response of `eth_getLogs`
+    Eth,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ReplayStatus {
+    /// The item is a bridge event and maps to a `BridgeAction`
+    Action,
+    /// The item is a bridge event that does not map to any `BridgeAction`
+    NotActionable,
+    /// The item is not a recognized bridge event
+    Unrecognized,
+    /// The item could not be parsed or converted
+    Error,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ReplayRecord {
+    /// `{tx_digest}:{event_seq}` for Sui events, `{tx_hash}:{log_index_in_tx}` for Eth logs
+    pub source: String,
+    pub status: ReplayStatus,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub event_type: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub event: Option<serde_json::Value>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub action: Option<Action>,
+    /// Base58 encoded
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub action_digest: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+impl ReplayRecord {
+    fn new(source: String, status: ReplayStatus) -> Self {
+        Self {
+            source,
+            status,
+            event_type: None,
+            event: None,
+            action: None,
+            action_digest: None,
+            error: None,
+        }
+    }
+
+    fn with_error(mut self, error: String) -> Self {
+        self.error = Some(error);
+        self
+    }
+
+    fn with_action(mut self, action: Option<BridgeAction>) -> Self {
+        self.action_digest = action.as_ref().map(|a| Base58::encode(a.digest().inner()));
+        self.action = action.as_ref().map(Action::from);
+        self
+    }
+
+    /// Two records are considered the same if they come from the same source and
+    /// yield the same outcome. Error messages and event payloads are not compared
+    /// because they are for human consumption.
+    fn same_outcome(&self, other: &Self) -> bool {
+        self.source == other.source
+            && self.status == other.status
+            && self.event_type == other.event_type
+            && self.action_digest == other.action_digest
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ReplayDiff {
+    /// Present in the expected records but not produced by this replay
+    Missing(ReplayRecord),
+    /// Produced by this replay but absent from the expected records
+    Unexpected(ReplayRecord),
+    Changed {
+        expected: ReplayRecord,
+        actual: ReplayRecord,
+    },
+}
+
+/// Replays raw Sui events, in order, through `SuiBridgeEvent::parse_sui_event`.
+pub fn replay_sui_events(events: &[SuiEvent]) -> Vec<ReplayRecord> {
+    events
+        .iter()
+        .map(|event| {
+            let source = format!("{}:{}", event.id.tx_digest, event.id.event_seq);
+            match SuiBridgeEvent::parse_sui_event(event) {
+                Ok(Some((bridge_event, action))) => {
+                    let status = match action {
+                        Some(_) => ReplayStatus::Action,
+                        None => ReplayStatus::NotActionable,
+                    };
+                    let mut record = ReplayRecord::new(source, status).with_action(action);
+                    record.event_type = Some(bridge_event.name().to_string());
+                    record.event = serde_json::to_value(&bridge_event).ok();
+                    record
+                }
+                Ok(None) => ReplayRecord::new(source, ReplayStatus::Unrecognized)
+                    .with_error(format!("Unrecognized event type: {}", event.type_)),
+                Err(e) => {
+                    ReplayRecord::new(source, ReplayStatus::Error).with_error(format!("{:?}", e))
+                }
+            }
+        })
+        .collect()
+}
+
+/// Replays raw Eth logs, in order, through `EthBridgeEvent::parse_eth_log`.
+pub fn replay_eth_logs(logs: &[Log]) -> Vec<ReplayRecord> {
+    let indices_in_tx = infer_log_indices_in_tx(logs);
+    logs.iter()
+        .zip(indices_in_tx)
+        .enumerate()
+        .map(|(position, (log, log_index_in_tx))| {
+            let (Some(tx_hash), Some(block_number)) = (log.transaction_hash, log.block_number)
+            else {
+                return ReplayRecord::new(format!("unknown:{position}"), ReplayStatus::Error)
+                    .with_error("Log without transaction_hash or block_number".to_string());
+            };
+            let source = format!("{:?}:{}", tx_hash, log_index_in_tx);
+            let eth_log = EthLog {
+                block_number: block_number.as_u64(),
+                tx_hash,
+                log_index_in_tx,
+                block_timestamp_ms: None,
+                log: log.clone(),
+            };
+            let Some((bridge_event, action)) = EthBridgeEvent::parse_eth_log(&eth_log) else {
+                return ReplayRecord::new(source, ReplayStatus::Unrecognized).with_error(format!(
+                    "Unrecognized log from {:?} with topics {:?}",
+                    log.address, log.topics
+                ));
+            };
+            let mut record = match action {
+                Ok(Some(action)) => {
+                    ReplayRecord::new(source, ReplayStatus::Action).with_action(Some(action))
+                }
+                Ok(None) => ReplayRecord::new(source, ReplayStatus::NotActionable),
+                Err(e) => {
+                    ReplayRecord::new(source, ReplayStatus::Error).with_error(format!("{:?}", e))
+                }
+            };
+            record.event_type = Some(bridge_event.name().to_string());
+            record.event = serde_json::to_value(&bridge_event).ok();
+            record
+        })
+        .collect()
+}
+
+// `eth_getLogs` responses do not carry the index of a log within its transaction
+// (`transactionLogIndex` is hardly ever populated); the node reads it from the
+// receipt instead. Offline we fall back to the position of the log among the logs
+// of the same transaction in the input, ordered by their index in the block.
+fn infer_log_indices_in_tx(logs: &[Log]) -> Vec<u16> {
+    let mut logs_by_tx: HashMap<H256, Vec<(usize, u64)>> = HashMap::new();
+    for (position, log) in logs.iter().enumerate() {
+        if let Some(tx_hash) = log.transaction_hash {
+            let log_index = log.log_index.map(|i| i.as_u64()).unwrap_or(position as u64);
+            logs_by_tx
+                .entry(tx_hash)
+                .or_default()
+                .push((position, log_index));
+        }
+    }
+    let mut inferred = vec![0; logs.len()];
+    for mut tx_logs in logs_by_tx.into_values() {
+        tx_logs.sort_by_key(|(_, log_index)| *log_index);
+        for (rank, (position, _)) in tx_logs.into_iter().enumerate() {
+            inferred[position] = rank as u16;
+        }
+    }
+    logs.iter()
+        .zip(inferred)
+        .map(|(log, inferred)| {
+            log.transaction_log_index
+                .map(|i| i.as_u64() as u16)
+                .unwrap_or(inferred)
+        })
+        .collect()
+}
+
+/// Reads a file of raw items and replays them. The file may contain a bare JSON
+/// array, a page object with a `data` field, or a full JSON-RPC response wrapping
+/// either of them in `result`.
+pub fn replay_file(source: ReplaySource, path: &Path) -> anyhow::Result<Vec<ReplayRecord>> {
+    let content = std::fs::read_to_string(path)
+        .map_err(|e| anyhow!("Failed to read {}: {:?}", path.display(), e))?;
+    let items = unwrap_rpc_response(serde_json::from_str(&content)?)?;
+    Ok(match source {
+        ReplaySource::Sui => replay_sui_events(&serde_json::from_value::<Vec<SuiEvent>>(items)?),
+        ReplaySource::Eth => replay_eth_logs(&serde_json::from_value::<Vec<Log>>(items)?),
+    })
+}
+
+fn unwrap_rpc_response(mut value: serde_json::Value) -> anyhow::Result<serde_json::Value> {
+    loop {
+        value = match value {
+            serde_json::Value::Array(_) => return Ok(value),
+            serde_json::Value::Object(mut object) => {
+                if let Some(inner) = object.remove("result") {
+                    inner
+                } else if let Some(inner) = object.remove("data") {
+                    inner
+                } else {
+                    return Err(anyhow!(
+                        "Expected a list of items, a page or a JSON-RPC response"
+                    ));
+                }
+            }
+            _ => {
+                return Err(anyhow!(
+                    "Expected a list of items, a page or a JSON-RPC response"
+                ))
+            }
+        };
+    }
+}
+
+/// Compares replayed records against the ones from a previous run, keyed by `source`.
+pub fn diff_replay_records(actual: &[ReplayRecord], expected: &[ReplayRecord]) -> Vec<ReplayDiff> {
+    let actual_by_source = actual
+        .iter()
+        .map(|r| (r.source.as_str(), r))
+        .collect::<BTreeMap<_, _>>();
+    let expected_by_source = expected
+        .iter()
+        .map(|r| (r.source.as_str(), r))
+        .collect::<BTreeMap<_, _>>();
+    let mut diffs = vec![];
+    for (source, expected) in &expected_by_source {
+        match actual_by_source.get(source) {
+            None => diffs.push(ReplayDiff::Missing((*expected).clone())),
+            Some(actual) if !actual.same_outcome(expected) => diffs.push(ReplayDiff::Changed {
+                expected: (*expected).clone(),
+                actual: (*actual).clone(),
+            }),
+            Some(_) => (),
+        }
+    }
+    for (source, actual) in &actual_by_source {
+        if !expected_by_source.contains_key(source) {
+            diffs.push(ReplayDiff::Unexpected((*actual).clone()));
+        }
+    }
+    diffs
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ethers::types::{U256, U64};
+    use std::collections::BTreeSet;
+    use std::path::PathBuf;
+
+    fn testdata(name: &str) -> PathBuf {
+        PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+            .join("testdata")
+            .join("replay")
+            .join(name)
+    }
+
+    fn load_golden(name: &str) -> Vec<ReplayRecord> {
+        serde_json::from_str(&std::fs::read_to_string(testdata(name)).unwrap()).unwrap()
+    }
+
+    #[test]
+    fn test_replay_sui_events_golden() {
+        telemetry_subscribers::init_for_testing();
+        let records = replay_file(ReplaySource::Sui, &testdata("sui_events.json")).unwrap();
+        let expected = load_golden("sui_events.golden.json");
+        assert_eq!(diff_replay_records(&records, &expected), vec![]);
+
+        // Every declared Sui bridge event type is covered by the golden file
+        let covered = records
+            .iter()
+            .filter_map(|r| r.event_type.clone())
+            .collect::<BTreeSet<_>>();
+        assert_eq!(covered.len(), 14);
+    }
+
+    #[test]
+    fn test_replay_eth_logs_golden() {
+        telemetry_subscribers::init_for_testing();
+        let records = replay_file(ReplaySource::Eth, &testdata("eth_logs.json")).unwrap();
+        let expected = load_golden("eth_logs.golden.json");
+        assert_eq!(diff_replay_records(&records, &expected), vec![]);
+
+        // Events with identical signatures across contracts (`Initialized`, `Upgraded`)
+        // are always decoded as `EthSuiBridge` events, hence 12 distinct types.
+        let covered = records
+            .iter()
+            .filter_map(|r| r.event_type.clone())
+            .collect::<BTreeSet<_>>();
+        assert_eq!(covered.len(), 12);
+    }
+
+    #[test]
+    fn test_diff_replay_records() {
+        let record = |source: &str, status| ReplayRecord::new(source.to_string(), status);
+        let expected = vec![
+            record("a:0", ReplayStatus::Action),
+            record("b:0", ReplayStatus::NotActionable),
+            record("c:0", ReplayStatus::Unrecognized),
+        ];
+        let actual = vec![
+            record("a:0", ReplayStatus::Action),
+            // error messages are not compared
+            record("b:0", ReplayStatus::NotActionable).with_error("ignored".to_string()),
+            record("d:0", ReplayStatus::Error),
+        ];
+        assert_eq!(
+            diff_replay_records(&actual, &expected),
+            vec![
+                ReplayDiff::Missing(record("c:0", ReplayStatus::Unrecognized)),
+                ReplayDiff::Unexpected(record("d:0", ReplayStatus::Error)),
+            ]
+        );
+
+        let actual = vec![
+            record("a:0", ReplayStatus::Error),
+            record("b:0", ReplayStatus::NotActionable),
+            record("c:0", ReplayStatus::Unrecognized),
+        ];
+        assert_eq!(
+            diff_replay_records(&actual, &expected),
+            vec![ReplayDiff::Changed {
+                expected: record("a:0", ReplayStatus::Action),
+                actual: record("a:0", ReplayStatus::Error),
+            }]
+        );
+    }
+
+    #[test]
+    fn test_infer_log_indices_in_tx() {
+        let log = |tx_hash: H256, log_index: u64| Log {
+            transaction_hash: Some(tx_hash),
+            block_number: Some(U64::from(1)),
+            log_index: Some(U256::from(log_index)),
+            ..Default::default()
+        };
+        let tx1 = H256::random();
+        let tx2 = H256::random();
+        let mut logs = vec![log(tx1, 7), log(tx2, 3), log(tx1, 5), log(tx2, 4)];
+        assert_eq!(infer_log_indices_in_tx(&logs), vec![1, 0, 0, 1]);
+
+        // `transaction_log_index` takes precedence when present
+        logs[0].transaction_log_index = Some(U256::from(9));
+        assert_eq!(infer_log_indices_in_tx(&logs), vec![9, 0, 0, 1]);
+    }
+}
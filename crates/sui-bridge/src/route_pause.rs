@@ -0,0 +1,144 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Routes whose token transfers can't be claimed on Sui. Besides the global pause of
+//! emergency actions, the bridge package has no pause flag per route or token: governance
+//! disables a single route by setting its transfer limit to zero, and the limiter then
+//! rejects every claim on that route. The node reads the limits from the bridge object at
+//! startup and again on each `UpdateRouteLimitEvent`, and the executor defers the
+//! transfers of disabled routes until they are enabled again, rather than spending gas on
+//! transactions that don't claim anything. Signatures are still collected, other members
+//! may execute them, unless the node is configured to skip them.
+
+use crate::metrics::BridgeMetrics;
+use crate::notional::{route_label, BridgeRoute};
+use crate::types::BridgeAction;
+use std::collections::HashSet;
+use std::sync::Arc;
+use sui_types::bridge::BridgeSummary;
+use tokio::sync::watch;
+use tracing::{info, warn};
+
+pub struct RoutePauses {
+    paused: watch::Sender<HashSet<BridgeRoute>>,
+    metrics: Arc<BridgeMetrics>,
+}
+
+impl RoutePauses {
+    pub fn new(metrics: Arc<BridgeMetrics>) -> Self {
+        Self {
+            paused: watch::channel(HashSet::new()).0,
+            metrics,
+        }
+    }
+
+    /// Replaces the paused routes, e.g. after the transfer limits changed on chain.
+    pub fn update(&self, paused: HashSet<BridgeRoute>) {
+        let previous = self.paused.borrow().clone();
+        for route in paused.difference(&previous) {
+            warn!("Route {:?} is paused on chain", route);
+        }
+        for route in previous.difference(&paused) {
+            info!("Route {:?} is no longer paused on chain", route);
+        }
+        for route in previous.union(&paused) {
+            self.metrics
+                .route_paused
+                .with_label_values(&[&route_label(*route)])
+                .set(paused.contains(route) as i64);
+        }
+        self.paused.send_replace(paused);
+    }
+
+    pub fn is_paused(&self, route: BridgeRoute) -> bool {
+        self.paused.borrow().contains(&route)
+    }
+
+    /// Returns the route of `action` if it's a token transfer on a paused route.
+    pub fn paused_route(&self, action: &BridgeAction) -> Option<BridgeRoute> {
+        action_route(action).filter(|route| self.is_paused(*route))
+    }
+
+    /// Notified whenever the paused routes are updated.
+    pub fn subscribe(&self) -> watch::Receiver<HashSet<BridgeRoute>> {
+        self.paused.subscribe()
+    }
+}
+
+/// Returns the routes of the limiter in `summary` whose transfer limit is zero.
+pub fn paused_routes(summary: &BridgeSummary) -> HashSet<BridgeRoute> {
+    summary
+        .limiter
+        .transfer_limit
+        .iter()
+        .filter(|(_, _, limit)| *limit == 0)
+        .map(|(source, destination, _)| (*source, *destination))
+        .collect()
+}
+
+/// Returns the route of `action`, None if it's not a token transfer.
+pub fn action_route(action: &BridgeAction) -> Option<BridgeRoute> {
+    match action {
+        BridgeAction::SuiToEthBridgeAction(a) => Some((
+            a.sui_bridge_event.sui_chain_id,
+            a.sui_bridge_event.eth_chain_id,
+        )),
+        BridgeAction::EthToSuiBridgeAction(a) => Some((
+            a.eth_bridge_event.eth_chain_id,
+            a.eth_bridge_event.sui_chain_id,
+        )),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{get_test_eth_to_sui_bridge_action, get_test_sui_to_eth_bridge_action};
+    use sui_types::base_types::ObjectID;
+    use sui_types::bridge::{BridgeChainId, BridgeLimiterSummary};
+
+    #[test]
+    fn test_route_pauses() {
+        let metrics = Arc::new(BridgeMetrics::for_testing());
+        let route_pauses = RoutePauses::new(metrics.clone());
+        let eth_to_sui = get_test_eth_to_sui_bridge_action(None, None, None, None);
+        let sui_to_eth =
+            get_test_sui_to_eth_bridge_action(None, None, None, None, None, None, None);
+        let paused_route = (BridgeChainId::EthCustom, BridgeChainId::SuiCustom);
+        let gauge = || {
+            metrics
+                .route_paused
+                .with_label_values(&["EthCustom_to_SuiCustom"])
+                .get()
+        };
+        let changes = route_pauses.subscribe();
+
+        let summary = BridgeSummary {
+            bridge_version: 0,
+            message_version: 0,
+            chain_id: BridgeChainId::SuiCustom as u8,
+            sequence_nums: vec![],
+            committee: Default::default(),
+            treasury: Default::default(),
+            bridge_records_id: ObjectID::random(),
+            limiter: BridgeLimiterSummary {
+                transfer_limit: vec![
+                    (BridgeChainId::EthCustom, BridgeChainId::SuiCustom, 0),
+                    (BridgeChainId::EthSepolia, BridgeChainId::SuiCustom, 1000),
+                ],
+                transfer_records: vec![],
+            },
+            is_frozen: false,
+        };
+        route_pauses.update(paused_routes(&summary));
+        assert!(changes.has_changed().unwrap());
+        assert_eq!(route_pauses.paused_route(&eth_to_sui), Some(paused_route));
+        assert_eq!(route_pauses.paused_route(&sui_to_eth), None);
+        assert_eq!(gauge(), 1);
+
+        route_pauses.update(HashSet::new());
+        assert_eq!(route_pauses.paused_route(&eth_to_sui), None);
+        assert_eq!(gauge(), 0);
+    }
+}
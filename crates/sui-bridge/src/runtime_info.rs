@@ -0,0 +1,506 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Description of a running node for incident reports: its version, the chains and
+//! contracts it bridges, the committee epoch, the storage schema, the features it was
+//! built with, the components its config enables, and the config itself. The node logs it
+//! once at startup and serves it on `/admin/info`, and `sui-bridge-cli node-status`
+//! includes it.
+//!
+//! It holds no secrets. The config is reduced to the keys of `CONFIG_ALLOWLIST`, so that
+//! key paths, RPC urls that may embed API keys, credentials and keys added to the config
+//! later are left out until they are reviewed and allowed.
+
+use crate::api_types::RuntimeInfo;
+use crate::config::{BridgeClientConfig, BridgeNodeConfig, BridgeNodeMode, BridgeServerConfig};
+use crate::crypto::BridgeAuthorityPublicKeyBytes;
+use crate::storage::STORAGE_SCHEMA_VERSION;
+use crate::sui_client::SuiClientInner;
+use crate::timestamps::now_ms;
+use ethers::providers::JsonRpcClient;
+use fastcrypto::encoding::{Encoding, Hex};
+use fastcrypto::traits::{KeyPair, ToFromBytes};
+use serde_json::{json, Map, Value};
+use std::time::Duration;
+use sui_types::{BRIDGE_PACKAGE_ID, SUI_BRIDGE_OBJECT_ID};
+use tracing::warn;
+
+/// Keys of `BridgeNodeConfig` kept in the effective config, as paths of its kebab-case
+/// keys separated by dots. A kept key is kept with everything under it.
+pub const CONFIG_ALLOWLIST: &[&str] = &[
+    "server-listen-port",
+    "server-listen-address",
+    "server-dual-stack",
+    "server-reuse-port",
+    "server-dedicated-runtime",
+    "server-runtime-worker-threads",
+    "metrics-port",
+    "mode",
+    "run-client",
+    "verify-storage",
+    "storage-cursor-flush-ms",
+    "min-transfer-usd-value",
+    "channels",
+    "adaptive-polling",
+    "timestamps",
+    "clock-drift",
+    "onchain-status-batch-size",
+    "skip-signing-paused-routes",
+    "storage-maintenance.compaction-schedule",
+    "storage-maintenance.compaction-min-table-bytes",
+    "storage-maintenance.backup-schedule",
+    "storage-maintenance.retain-backups",
+    "min-signature-inclusion-percent",
+    "committee-dns-refresh-seconds",
+    "committee-connections",
+    "committee-request-hedging",
+    "refuse-committee-anomalies",
+    "approved-governance-digests",
+    "sign-action-types",
+    "execute-action-types",
+    "slow-request-threshold-ms",
+    "governance-body-limit-bytes",
+    "max-requester-labels",
+    "grpc-listen-address",
+    "external-url",
+    "warm-up",
+    "verification-limits",
+    "sui.sui-bridge-chain-id",
+    "sui.bridge-client-gas-object",
+    "sui.disable-object-cache",
+    "sui.sui-rpc-max-attempts",
+    "sui.tx-submission.strategy",
+    "eth.eth-bridge-proxy-address",
+    "eth.eth-bridge-chain-id",
+    "eth.eth-contracts-start-block-fallback",
+    "eth.eth-contracts-start-block-override",
+    "eth.extra-eth-confirmations",
+    "eth.custom-evm-chains",
+    "eth.fee-history-interval-seconds",
+    "eth.verify-eth-receipts",
+];
+
+// Reads of the chains at startup give up after this, the node starts either way
+const CHAIN_READ_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Returns `value` reduced to the keys of `CONFIG_ALLOWLIST`.
+pub fn retain_allowlisted(value: &Value) -> Value {
+    let mut retained = Value::Object(Map::new());
+    for path in CONFIG_ALLOWLIST {
+        let Some(kept) = value.pointer(&format!("/{}", path.replace('.', "/"))) else {
+            continue;
+        };
+        let mut parent = &mut retained;
+        let keys = path.split('.').collect::<Vec<_>>();
+        for key in &keys[..keys.len() - 1] {
+            parent = parent
+                .as_object_mut()
+                .expect("allowlisted paths don't overlap")
+                .entry(key.to_string())
+                .or_insert_with(|| Value::Object(Map::new()));
+        }
+        parent
+            .as_object_mut()
+            .expect("allowlisted paths don't overlap")
+            .insert(keys[keys.len() - 1].to_string(), kept.clone());
+    }
+    retained
+}
+
+/// Returns the config that the node runs with: `config` with the values that the node
+/// resolved from it, defaults included, reduced to `CONFIG_ALLOWLIST`. Options without a
+/// resolved value are left out when they are not set, their defaults are documented on
+/// `BridgeNodeConfig`.
+pub fn effective_config<C, P>(
+    config: &BridgeNodeConfig,
+    server_config: &BridgeServerConfig<C, P>,
+    client_config: Option<&BridgeClientConfig<C, P>>,
+) -> Value {
+    let mut value = serde_json::to_value(config).expect("BridgeNodeConfig serializes");
+    let mut sign_action_types = server_config
+        .sign_action_types
+        .iter()
+        .copied()
+        .collect::<Vec<_>>();
+    sign_action_types.sort_by_key(|action_type| *action_type as u8);
+    let limits = &server_config.verification_limits;
+    let mut resolved = json!({
+        "mode": server_config.mode,
+        "server-listen-address": server_config.listen.address.ip().to_string(),
+        "sign-action-types": sign_action_types,
+        "slow-request-threshold-ms": server_config.slow_request_threshold.as_millis() as u64,
+        "governance-body-limit-bytes": server_config.governance_body_limit,
+        "max-requester-labels": server_config.max_requester_labels,
+        "verification-limits": {
+            "max-concurrent-eth-verifications": limits.max_concurrent_eth_verifications,
+            "max-concurrent-sui-verifications": limits.max_concurrent_sui_verifications,
+            "queue-timeout-ms": limits.queue_timeout.as_millis() as u64,
+            "max-nonce-lead": limits.max_nonce_lead,
+        },
+    });
+    if let Some(worker_threads) = server_config.server_runtime_worker_threads {
+        resolved.as_object_mut().unwrap().insert(
+            "server-runtime-worker-threads".into(),
+            json!(worker_threads),
+        );
+    }
+    if let Some(client_config) = client_config {
+        let mut execute_action_types = client_config
+            .execute_action_types
+            .iter()
+            .copied()
+            .collect::<Vec<_>>();
+        execute_action_types.sort_by_key(|action_type| *action_type as u8);
+        let resolved = resolved.as_object_mut().unwrap();
+        resolved.insert("execute-action-types".into(), json!(execute_action_types));
+        resolved.insert(
+            "onchain-status-batch-size".into(),
+            json!(client_config.onchain_status_batch_size),
+        );
+        resolved.insert(
+            "committee-dns-refresh-seconds".into(),
+            json!(client_config
+                .committee_dns_refresh_interval
+                .map_or(0, |interval| interval.as_secs())),
+        );
+        resolved.insert(
+            "storage-cursor-flush-ms".into(),
+            json!(client_config
+                .cursor_flush_interval
+                .map_or(0, |interval| interval.as_millis() as u64)),
+        );
+        if client_config.authority_public_key.is_some() {
+            resolved.insert(
+                "min-signature-inclusion-percent".into(),
+                json!(client_config.min_signature_inclusion_percent),
+            );
+        }
+    }
+    let object = value
+        .as_object_mut()
+        .expect("BridgeNodeConfig is an object");
+    for (key, resolved) in resolved.as_object_mut().unwrap() {
+        object.insert(key.clone(), resolved.take());
+    }
+    if let Some(eth) = object.get_mut("eth").and_then(Value::as_object_mut) {
+        eth.insert(
+            "extra-eth-confirmations".into(),
+            json!(server_config.extra_eth_confirmations),
+        );
+    }
+    retain_allowlisted(&value)
+}
+
+/// Cargo features the node was built with.
+pub fn compiled_features() -> Vec<String> {
+    [
+        ("grpc", cfg!(feature = "grpc")),
+        ("archive-s3", cfg!(feature = "archive-s3")),
+    ]
+    .into_iter()
+    .filter(|(_, enabled)| *enabled)
+    .map(|(feature, _)| feature.to_string())
+    .collect()
+}
+
+fn mode_name(mode: BridgeNodeMode) -> &'static str {
+    match mode {
+        BridgeNodeMode::Committee => "committee",
+        BridgeNodeMode::Observer => "observer",
+        BridgeNodeMode::Shadow => "shadow",
+    }
+}
+
+fn enabled_components<C, P>(
+    server_config: &BridgeServerConfig<C, P>,
+    client_config: Option<&BridgeClientConfig<C, P>>,
+) -> Vec<String> {
+    let mut components = vec![
+        ("remote-signer", server_config.remote_signer.is_some()),
+        (
+            "request-auth",
+            server_config.request_authenticator.is_some(),
+        ),
+        ("admin-auth", server_config.admin_authorizer.is_some()),
+        ("grpc-server", server_config.grpc_listen_address.is_some()),
+        ("warm-up", server_config.warm_up.is_some()),
+        (
+            "server-runtime",
+            server_config.server_runtime_worker_threads.is_some(),
+        ),
+    ];
+    if let Some(client_config) = client_config {
+        components.extend([
+            ("client", true),
+            ("executor", client_config.account.is_some()),
+            (
+                "storage-encryption",
+                client_config.storage_key_provider.is_some(),
+            ),
+            ("tx-journal", client_config.tx_journal.is_some()),
+            ("reconciliation", client_config.reconciliation.is_some()),
+            ("claim-tracking", client_config.claim_tracking.is_some()),
+            (
+                "adaptive-polling",
+                client_config.sui_poll_interval.is_some()
+                    || client_config.eth_poll_interval.is_some(),
+            ),
+            ("gas-funding", client_config.gas_funding.is_some()),
+            ("alerts", client_config.alerts.is_some()),
+            ("event-stream", client_config.event_stream.is_some()),
+            (
+                "quarantine-recheck",
+                client_config.quarantine_recheck.is_some(),
+            ),
+            ("pruning", client_config.pruning.is_some()),
+            (
+                "storage-maintenance",
+                client_config.storage_maintenance.is_some(),
+            ),
+            (
+                "signature-inclusion",
+                client_config.authority_public_key.is_some(),
+            ),
+            (
+                "committee-dns-refresh",
+                client_config.committee_dns_refresh_interval.is_some(),
+            ),
+            (
+                "committee-request-hedging",
+                client_config.committee_request_hedging.is_some(),
+            ),
+        ]);
+    }
+    components
+        .into_iter()
+        .filter(|(_, enabled)| *enabled)
+        .map(|(component, _)| component.to_string())
+        .collect()
+}
+
+/// Puts together the description of a node. The chain ids and the committee epoch are
+/// read from the chains, and left unset if they can't be read.
+pub async fn collect_runtime_info<C, P>(
+    server_config: &BridgeServerConfig<C, P>,
+    client_config: Option<&BridgeClientConfig<C, P>>,
+    has_storage: bool,
+    version: Option<String>,
+    config: Option<Value>,
+) -> RuntimeInfo
+where
+    C: SuiClientInner + 'static,
+    P: JsonRpcClient + Clone + 'static,
+{
+    let summary = read_with_timeout(server_config.sui_client.get_bridge_summary()).await;
+    let eth_chain_id = read_with_timeout(
+        server_config
+            .eth_client
+            .get_bridge_chain_id(server_config.eth_bridge_proxy_address),
+    )
+    .await;
+    let authority_public_key = match (&server_config.key, &server_config.remote_signer) {
+        (Some(key), _) => Some(Hex::encode(
+            BridgeAuthorityPublicKeyBytes::from(key.public()).as_bytes(),
+        )),
+        (None, Some(remote_signer)) => Some(remote_signer.authority_public_key.clone()),
+        (None, None) => None,
+    };
+    let eth_contracts = client_config
+        .map(|client_config| {
+            client_config
+                .eth_contracts
+                .iter()
+                .chain([&client_config.eth_bridge_config_address])
+                .map(|address| format!("{:?}", address))
+                .collect()
+        })
+        .unwrap_or_default();
+    RuntimeInfo {
+        version,
+        mode: mode_name(server_config.mode).to_string(),
+        authority_public_key,
+        sui_chain_id: summary.as_ref().map(|summary| summary.chain_id),
+        eth_chain_id,
+        sui_bridge_package_id: BRIDGE_PACKAGE_ID.to_hex_uncompressed(),
+        sui_bridge_object_id: SUI_BRIDGE_OBJECT_ID.to_hex_uncompressed(),
+        eth_bridge_proxy_address: format!("{:?}", server_config.eth_bridge_proxy_address),
+        eth_contracts,
+        committee_epoch: summary
+            .as_ref()
+            .map(|summary| summary.committee.last_committee_update_epoch),
+        bridge_version: summary.as_ref().map(|summary| summary.bridge_version),
+        storage_schema_version: has_storage.then_some(STORAGE_SCHEMA_VERSION),
+        features: compiled_features(),
+        components: enabled_components(server_config, client_config),
+        started_at_ms: now_ms(),
+        config,
+    }
+}
+
+async fn read_with_timeout<T, E: std::fmt::Debug>(
+    read: impl std::future::Future<Output = Result<T, E>>,
+) -> Option<T> {
+    match tokio::time::timeout(CHAIN_READ_TIMEOUT, read).await {
+        Ok(Ok(value)) => Some(value),
+        Ok(Err(e)) => {
+            warn!("Couldn't read the chain for the runtime info: {:?}", e);
+            None
+        }
+        Err(_) => {
+            warn!("Timed out reading the chain for the runtime info");
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CONFIG: &str = r#"
+server-listen-port: 9191
+metrics-port: 9184
+bridge-authority-key-path: /secrets/bridge_authority.key
+run-client: true
+db-path: /var/lib/bridge/client_db
+storage-encryption-key-path: /secrets/storage.key
+approved-governance-actions: []
+request-auth:
+  allowed-keys: ["requester-key"]
+admin-auth:
+  callers:
+    - name: oncall
+      role: operator
+      token: admin-secret-token
+sui:
+  sui-rpc-url: https://sui.example.com/?apikey=sui-secret-api-key
+  sui-bridge-chain-id: 2
+  bridge-client-key-path: /secrets/bridge_client.key
+  tx-submission:
+    strategy: concurrent
+    fullnode-urls: ["https://fullnode.example.com/?apikey=fullnode-secret"]
+eth:
+  eth-rpc-url: https://eth.example.com/v3/eth-secret-api-key
+  eth-bridge-proxy-address: "0x0000000000000000000000000000000000000001"
+  eth-bridge-chain-id: 11
+  eth-contracts-start-block-fallback: 0
+  eth-receipts-root-rpc-url: https://receipts.example.com/receipts-secret
+"#;
+
+    // Paths of the leaves of `value`
+    fn leaf_paths(value: &Value, prefix: &str, paths: &mut Vec<String>) {
+        match value.as_object() {
+            Some(object) if !object.is_empty() => {
+                for (key, value) in object {
+                    let path = if prefix.is_empty() {
+                        key.clone()
+                    } else {
+                        format!("{}.{}", prefix, key)
+                    };
+                    leaf_paths(value, &path, paths);
+                }
+            }
+            _ => paths.push(prefix.to_string()),
+        }
+    }
+
+    #[test]
+    fn test_config_allowlist() {
+        let config: BridgeNodeConfig = serde_yaml::from_str(CONFIG).unwrap();
+        let full = serde_json::to_value(&config).unwrap();
+        let retained = retain_allowlisted(&full);
+
+        // Everything retained is under an allowlisted key
+        let mut paths = vec![];
+        leaf_paths(&retained, "", &mut paths);
+        for path in &paths {
+            assert!(
+                CONFIG_ALLOWLIST
+                    .iter()
+                    .any(|allowed| path == allowed || path.starts_with(&format!("{}.", allowed))),
+                "{} is not allowlisted",
+                path
+            );
+        }
+        assert_eq!(retained["server-listen-port"], 9191);
+        assert_eq!(retained["sui"]["sui-bridge-chain-id"], 2);
+        assert_eq!(retained["sui"]["tx-submission"]["strategy"], "concurrent");
+        assert_eq!(
+            retained["eth"]["eth-bridge-proxy-address"],
+            "0x0000000000000000000000000000000000000001"
+        );
+
+        // The secrets are in the full config, but not in the retained one
+        let full = full.to_string();
+        let retained = retained.to_string();
+        let metrics_key_pair = serde_json::to_value(&config.metrics_key_pair)
+            .unwrap()
+            .to_string();
+        for secret in [
+            "/secrets/",
+            "sui-secret-api-key",
+            "fullnode-secret",
+            "eth-secret-api-key",
+            "receipts-secret",
+            "admin-secret-token",
+            "requester-key",
+            "/var/lib/bridge",
+            metrics_key_pair.trim_matches('"'),
+        ] {
+            assert!(full.contains(secret), "{} is not in the config", secret);
+            assert!(!retained.contains(secret), "{} is retained", secret);
+        }
+    }
+
+    #[test]
+    fn test_runtime_info_keys() {
+        let info = RuntimeInfo {
+            version: Some("1.0.0".to_string()),
+            mode: mode_name(BridgeNodeMode::Committee).to_string(),
+            authority_public_key: None,
+            sui_chain_id: Some(2),
+            eth_chain_id: None,
+            sui_bridge_package_id: BRIDGE_PACKAGE_ID.to_hex_uncompressed(),
+            sui_bridge_object_id: SUI_BRIDGE_OBJECT_ID.to_hex_uncompressed(),
+            eth_bridge_proxy_address: "0x01".to_string(),
+            eth_contracts: vec![],
+            committee_epoch: Some(7),
+            bridge_version: Some(1),
+            storage_schema_version: Some(STORAGE_SCHEMA_VERSION),
+            features: compiled_features(),
+            components: vec!["client".to_string()],
+            started_at_ms: 1,
+            config: Some(json!({})),
+        };
+        let value = serde_json::to_value(&info).unwrap();
+        let mut keys = value
+            .as_object()
+            .unwrap()
+            .keys()
+            .cloned()
+            .collect::<Vec<_>>();
+        keys.sort();
+        assert_eq!(
+            keys,
+            [
+                "authority_public_key",
+                "bridge_version",
+                "committee_epoch",
+                "components",
+                "config",
+                "eth_bridge_proxy_address",
+                "eth_chain_id",
+                "eth_contracts",
+                "features",
+                "mode",
+                "started_at_ms",
+                "storage_schema_version",
+                "sui_bridge_object_id",
+                "sui_bridge_package_id",
+                "sui_chain_id",
+                "version",
+            ]
+        );
+        assert_eq!(serde_json::from_value::<RuntimeInfo>(value).unwrap(), info);
+    }
+}
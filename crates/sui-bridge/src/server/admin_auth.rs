@@ -0,0 +1,309 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Access to the admin routes. The server speaks plain HTTP, so the routes under
+//! `/admin/` are only served to callers on the loopback interface: operators reach them
+//! on the host, or through a TLS terminating proxy running there, and bearer tokens never
+//! cross the network in cleartext.
+//!
+//! Callers can optionally be given roles. When enabled, a caller presents a bearer token
+//! that is granted the `reader` or the `operator` role. The GET routes under `/admin/`
+//! require `reader`, the mutations require `operator`, which grants `reader` too. Callers
+//! are known by the name they were granted the role under, tokens are never logged. When
+//! not enabled, reads are open, and the mutations are not served.
+
+use axum::{
+    extract::{ConnectInfo, Request, State},
+    http::{header::AUTHORIZATION, HeaderMap, Method, StatusCode},
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use fastcrypto::hash::{Blake2b256, HashFunction};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::net::SocketAddr;
+use std::sync::Arc;
+use tracing::warn;
+
+pub const ADMIN_PATH_PREFIX: &str = "/admin/";
+const BEARER_PREFIX: &str = "Bearer ";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum AdminRole {
+    Reader,
+    Operator,
+}
+
+impl AdminRole {
+    /// Role required to call an admin route with `method`.
+    pub fn required_for(method: &Method) -> Self {
+        if matches!(*method, Method::GET | Method::HEAD) {
+            AdminRole::Reader
+        } else {
+            AdminRole::Operator
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AdminGrant {
+    pub name: String,
+    pub role: AdminRole,
+    /// Presented by the caller as `Authorization: Bearer <token>`
+    pub token: String,
+}
+
+/// The caller of an admin route. `authorize_admin_request` adds it to the extensions of
+/// the requests it lets through.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AdminCaller {
+    pub name: String,
+    pub role: AdminRole,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AdminAuthError {
+    /// The caller is not on the loopback interface
+    NotLocal,
+    MissingCredentials,
+    UnknownCredentials,
+    InsufficientRole {
+        caller: String,
+        role: AdminRole,
+        required: AdminRole,
+    },
+}
+
+// Tokens are looked up by digest, so they are not compared byte by byte
+fn token_digest(token: &str) -> [u8; 32] {
+    Blake2b256::digest(token.as_bytes()).digest
+}
+
+pub struct AdminAuthorizer {
+    tokens: HashMap<[u8; 32], AdminCaller>,
+}
+
+impl AdminAuthorizer {
+    pub fn new(grants: Vec<AdminGrant>) -> Self {
+        let tokens = grants
+            .into_iter()
+            .map(|grant| {
+                let caller = AdminCaller {
+                    name: grant.name,
+                    role: grant.role,
+                };
+                (token_digest(&grant.token), caller)
+            })
+            .collect();
+        Self { tokens }
+    }
+
+    /// Returns the caller of a request to an admin route with `method`, if it was granted
+    /// the role the route requires.
+    pub fn authorize(
+        &self,
+        method: &Method,
+        headers: &HeaderMap,
+    ) -> Result<AdminCaller, AdminAuthError> {
+        let token = headers
+            .get(AUTHORIZATION)
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| value.strip_prefix(BEARER_PREFIX))
+            .ok_or(AdminAuthError::MissingCredentials)?;
+        let caller = self
+            .tokens
+            .get(&token_digest(token.trim()))
+            .ok_or(AdminAuthError::UnknownCredentials)?;
+        let required = AdminRole::required_for(method);
+        if caller.role < required {
+            return Err(AdminAuthError::InsufficientRole {
+                caller: caller.name.clone(),
+                role: caller.role,
+                required,
+            });
+        }
+        Ok(caller.clone())
+    }
+}
+
+// Whether the request was sent from the loopback interface. The routers must be served
+// with the `SocketAddr` connect info, requests without it are not local.
+fn is_local(request: &Request) -> bool {
+    request
+        .extensions()
+        .get::<ConnectInfo<SocketAddr>>()
+        .is_some_and(|ConnectInfo(address)| address.ip().to_canonical().is_loopback())
+}
+
+/// Middleware that rejects the requests to the admin routes of callers that are not
+/// local, or without the role they require when `authorizer` is set, see the module doc.
+/// Other routes are let through.
+pub(crate) async fn authorize_admin_request(
+    State(authorizer): State<Option<Arc<AdminAuthorizer>>>,
+    mut request: Request,
+    next: Next,
+) -> Response {
+    if !request.uri().path().starts_with(ADMIN_PATH_PREFIX) {
+        return next.run(request).await;
+    }
+    let caller = if !is_local(&request) {
+        Err(AdminAuthError::NotLocal)
+    } else {
+        authorizer
+            .map(|authorizer| authorizer.authorize(request.method(), request.headers()))
+            .transpose()
+    };
+    match caller {
+        Ok(caller) => {
+            if let Some(caller) = caller {
+                request.extensions_mut().insert(caller);
+            }
+            next.run(request).await
+        }
+        Err(e) => {
+            warn!(
+                "Rejected unauthorized {} request to {}: {:?}",
+                request.method(),
+                request.uri().path(),
+                e
+            );
+            let status = match e {
+                AdminAuthError::NotLocal | AdminAuthError::InsufficientRole { .. } => {
+                    StatusCode::FORBIDDEN
+                }
+                _ => StatusCode::UNAUTHORIZED,
+            };
+            (status, format!("Request is not authorized: {:?}", e)).into_response()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::http::HeaderValue;
+
+    fn bearer(token: &str) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            AUTHORIZATION,
+            HeaderValue::from_str(&format!("Bearer {}", token)).unwrap(),
+        );
+        headers
+    }
+
+    #[test]
+    fn test_authorize_admin_roles() {
+        let grant = |name: &str, role, token: &str| AdminGrant {
+            name: name.to_string(),
+            role,
+            token: token.to_string(),
+        };
+        let authorizer = AdminAuthorizer::new(vec![
+            grant("oncall", AdminRole::Reader, "reader-token"),
+            grant("release", AdminRole::Operator, "operator-token"),
+        ]);
+        let reader = AdminCaller {
+            name: "oncall".to_string(),
+            role: AdminRole::Reader,
+        };
+
+        assert_eq!(
+            authorizer.authorize(&Method::GET, &bearer("reader-token")),
+            Ok(reader)
+        );
+        assert_eq!(
+            authorizer.authorize(&Method::POST, &bearer("reader-token")),
+            Err(AdminAuthError::InsufficientRole {
+                caller: "oncall".to_string(),
+                role: AdminRole::Reader,
+                required: AdminRole::Operator,
+            })
+        );
+        // Operators can read too
+        for method in [Method::GET, Method::POST] {
+            assert_eq!(
+                authorizer
+                    .authorize(&method, &bearer("operator-token"))
+                    .unwrap()
+                    .name,
+                "release"
+            );
+        }
+        assert_eq!(
+            authorizer.authorize(&Method::GET, &HeaderMap::new()),
+            Err(AdminAuthError::MissingCredentials)
+        );
+        assert_eq!(
+            authorizer.authorize(&Method::GET, &bearer("reader-token2")),
+            Err(AdminAuthError::UnknownCredentials)
+        );
+    }
+
+    #[test]
+    fn test_admin_callers_are_local() {
+        let request = |address: Option<&str>| {
+            let mut request = Request::get("/admin/info")
+                .body(axum::body::Body::empty())
+                .unwrap();
+            if let Some(address) = address {
+                request
+                    .extensions_mut()
+                    .insert(ConnectInfo(address.parse::<SocketAddr>().unwrap()));
+            }
+            request
+        };
+        assert!(is_local(&request(Some("127.0.0.1:5000"))));
+        assert!(is_local(&request(Some("[::1]:5000"))));
+        assert!(is_local(&request(Some("[::ffff:127.0.0.1]:5000"))));
+        assert!(!is_local(&request(Some("10.0.0.1:5000"))));
+        assert!(!is_local(&request(Some("[2001:db8::1]:5000"))));
+        // Served without the connect info
+        assert!(!is_local(&request(None)));
+    }
+
+    #[tokio::test]
+    async fn test_admin_routes_require_connect_info() {
+        let router = axum::Router::new()
+            .route("/admin/info", axum::routing::get(|| async { "info" }))
+            .route("/ping", axum::routing::get(|| async { "pong" }))
+            .layer(axum::middleware::from_fn_with_state(
+                None,
+                authorize_admin_request,
+            ));
+        let serve = |with_connect_info: bool| {
+            let router = router.clone();
+            async move {
+                let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+                let address = listener.local_addr().unwrap();
+                tokio::spawn(async move {
+                    if with_connect_info {
+                        axum::serve(
+                            listener,
+                            router.into_make_service_with_connect_info::<SocketAddr>(),
+                        )
+                        .await
+                        .unwrap()
+                    } else {
+                        axum::serve(listener, router).await.unwrap()
+                    }
+                });
+                format!("http://{}", address)
+            }
+        };
+        let status = |url: String| async move { reqwest::get(url).await.unwrap().status() };
+
+        let local = serve(true).await;
+        assert_eq!(
+            status(format!("{}/admin/info", local)).await,
+            StatusCode::OK
+        );
+        let unknown = serve(false).await;
+        assert_eq!(
+            status(format!("{}/admin/info", unknown)).await,
+            StatusCode::FORBIDDEN
+        );
+        assert_eq!(status(format!("{}/ping", unknown)).await, StatusCode::OK);
+    }
+}
@@ -0,0 +1,321 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Optional authentication of signing requests. When enabled, a request must carry a
+//! signature, by a key in the allowlist, over its path, a timestamp and a nonce. Requests
+//! with a timestamp outside of the allowed clock skew, or with a nonce that was already
+//! used by the same key, are rejected. This keeps unknown callers from making the server
+//! verify transactions on their behalf.
+
+use crate::server::requester::AuthenticatedKey;
+use axum::{
+    extract::{Request, State},
+    http::{HeaderMap, StatusCode},
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use fastcrypto::ed25519::{Ed25519KeyPair, Ed25519PublicKey, Ed25519Signature};
+use fastcrypto::encoding::{Base64, Encoding};
+use fastcrypto::traits::{KeyPair, Signer, ToFromBytes, VerifyingKey};
+use std::collections::{HashMap, HashSet};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use tracing::warn;
+
+pub const AUTH_KEY_HEADER: &str = "x-bridge-auth-key";
+pub const AUTH_TIMESTAMP_HEADER: &str = "x-bridge-auth-timestamp";
+pub const AUTH_NONCE_HEADER: &str = "x-bridge-auth-nonce";
+pub const AUTH_SIGNATURE_HEADER: &str = "x-bridge-auth-signature";
+
+pub const DEFAULT_MAX_CLOCK_SKEW: Duration = Duration::from_secs(30);
+
+// Expired nonces are pruned once the cache grows beyond this size
+const NONCE_CACHE_PRUNE_THRESHOLD: usize = 10_000;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RequestAuthError {
+    MissingHeader(&'static str),
+    InvalidHeader(&'static str),
+    Expired,
+    UnknownKey,
+    InvalidSignature,
+    Replayed,
+}
+
+/// Returns the message a caller signs to authenticate a request to `path`.
+pub fn request_auth_message(path: &str, timestamp_ms: u64, nonce: &str) -> Vec<u8> {
+    format!("{}\n{}\n{}", path, timestamp_ms, nonce).into_bytes()
+}
+
+/// Returns the headers that authenticate a request to `path` with `key`.
+pub fn sign_request(
+    key: &Ed25519KeyPair,
+    path: &str,
+    timestamp_ms: u64,
+    nonce: &str,
+) -> Vec<(&'static str, String)> {
+    let signature: Ed25519Signature = key.sign(&request_auth_message(path, timestamp_ms, nonce));
+    vec![
+        (AUTH_KEY_HEADER, Base64::encode(key.public().as_bytes())),
+        (AUTH_TIMESTAMP_HEADER, timestamp_ms.to_string()),
+        (AUTH_NONCE_HEADER, nonce.to_string()),
+        (AUTH_SIGNATURE_HEADER, Base64::encode(signature.as_bytes())),
+    ]
+}
+
+pub struct RequestAuthenticator {
+    allowed_keys: HashSet<Ed25519PublicKey>,
+    max_clock_skew: Duration,
+    // (key, nonce) to the time after which the nonce can no longer be replayed,
+    // because the request would be expired anyway.
+    seen_nonces: Mutex<HashMap<(Vec<u8>, String), u64>>,
+}
+
+impl RequestAuthenticator {
+    pub fn new(allowed_keys: Vec<Ed25519PublicKey>, max_clock_skew: Duration) -> Self {
+        Self {
+            allowed_keys: allowed_keys.into_iter().collect(),
+            max_clock_skew,
+            seen_nonces: Mutex::new(HashMap::new()),
+        }
+    }
+
+    pub fn verify(&self, path: &str, headers: &HeaderMap) -> Result<(), RequestAuthError> {
+        let now_ms = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .expect("Time went backwards")
+            .as_millis() as u64;
+        self.verify_at(path, headers, now_ms)
+    }
+
+    fn verify_at(
+        &self,
+        path: &str,
+        headers: &HeaderMap,
+        now_ms: u64,
+    ) -> Result<(), RequestAuthError> {
+        let timestamp_ms = get_header(headers, AUTH_TIMESTAMP_HEADER)?
+            .parse::<u64>()
+            .map_err(|_| RequestAuthError::InvalidHeader(AUTH_TIMESTAMP_HEADER))?;
+        let max_clock_skew_ms = self.max_clock_skew.as_millis() as u64;
+        if timestamp_ms.abs_diff(now_ms) > max_clock_skew_ms {
+            return Err(RequestAuthError::Expired);
+        }
+
+        let key_bytes = Base64::decode(get_header(headers, AUTH_KEY_HEADER)?)
+            .map_err(|_| RequestAuthError::InvalidHeader(AUTH_KEY_HEADER))?;
+        let key = Ed25519PublicKey::from_bytes(&key_bytes)
+            .map_err(|_| RequestAuthError::InvalidHeader(AUTH_KEY_HEADER))?;
+        if !self.allowed_keys.contains(&key) {
+            return Err(RequestAuthError::UnknownKey);
+        }
+
+        let nonce = get_header(headers, AUTH_NONCE_HEADER)?;
+        let signature = Base64::decode(get_header(headers, AUTH_SIGNATURE_HEADER)?)
+            .ok()
+            .and_then(|bytes| Ed25519Signature::from_bytes(&bytes).ok())
+            .ok_or(RequestAuthError::InvalidHeader(AUTH_SIGNATURE_HEADER))?;
+        key.verify(&request_auth_message(path, timestamp_ms, nonce), &signature)
+            .map_err(|_| RequestAuthError::InvalidSignature)?;
+
+        // Only remember nonces of authenticated requests, so others can't fill the cache
+        let mut seen_nonces = self.seen_nonces.lock().unwrap();
+        if seen_nonces.len() >= NONCE_CACHE_PRUNE_THRESHOLD {
+            seen_nonces.retain(|_, expiry_ms| *expiry_ms >= now_ms);
+        }
+        let expiry_ms = timestamp_ms.saturating_add(max_clock_skew_ms);
+        if seen_nonces
+            .insert((key_bytes, nonce.to_string()), expiry_ms)
+            .is_some()
+        {
+            return Err(RequestAuthError::Replayed);
+        }
+        Ok(())
+    }
+}
+
+fn get_header<'a>(headers: &'a HeaderMap, name: &'static str) -> Result<&'a str, RequestAuthError> {
+    headers
+        .get(name)
+        .ok_or(RequestAuthError::MissingHeader(name))?
+        .to_str()
+        .map_err(|_| RequestAuthError::InvalidHeader(name))
+}
+
+/// Middleware that rejects requests that are not authenticated by `authenticator`.
+pub(crate) async fn authenticate_request(
+    State(authenticator): State<Arc<RequestAuthenticator>>,
+    mut request: Request,
+    next: Next,
+) -> Response {
+    match authenticator.verify(request.uri().path(), request.headers()) {
+        Ok(()) => {
+            // The key was verified, so the header is there. Requests are counted by key.
+            if let Ok(key) = get_header(request.headers(), AUTH_KEY_HEADER) {
+                let key = AuthenticatedKey(key.to_string());
+                request.extensions_mut().insert(key);
+            }
+            next.run(request).await
+        }
+        Err(e) => {
+            warn!(
+                "Rejected unauthenticated request to {}: {:?}",
+                request.uri(),
+                e
+            );
+            (
+                StatusCode::UNAUTHORIZED,
+                format!("Request is not authenticated: {:?}", e),
+            )
+                .into_response()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::crypto::BridgeAuthorityKeyPair;
+    use crate::metrics::BridgeMetrics;
+    use crate::server::mock_handler::BridgeRequestMockHandler;
+    use crate::server::timing::DEFAULT_SLOW_REQUEST_THRESHOLD;
+    use crate::server::{
+        make_router, BridgeNodePublicMetadata, DEFAULT_GOVERNANCE_BODY_LIMIT, PING_PATH,
+    };
+    use sui_config::local_ip_utils::get_available_port;
+    use sui_types::crypto::get_key_pair;
+
+    #[test]
+    fn test_verify_request_auth() {
+        let (_, key): (_, Ed25519KeyPair) = get_key_pair();
+        let (_, unknown_key): (_, Ed25519KeyPair) = get_key_pair();
+        let authenticator =
+            RequestAuthenticator::new(vec![key.public().clone()], DEFAULT_MAX_CLOCK_SKEW);
+        let path = "/sign/bridge_tx/sui/eth/digest/0";
+        let now_ms = 1_700_000_000_000;
+        let to_headers = |headers: Vec<(&'static str, String)>| {
+            headers
+                .into_iter()
+                .map(|(name, value)| (name.parse().unwrap(), value.parse().unwrap()))
+                .collect::<HeaderMap>()
+        };
+
+        // Valid, within clock skew in both directions
+        for (timestamp_ms, nonce) in [
+            (now_ms, "a"),
+            (now_ms - 30_000, "b"),
+            (now_ms + 30_000, "c"),
+        ] {
+            let headers = to_headers(sign_request(&key, path, timestamp_ms, nonce));
+            assert_eq!(authenticator.verify_at(path, &headers, now_ms), Ok(()));
+        }
+
+        // Replayed
+        let headers = to_headers(sign_request(&key, path, now_ms, "a"));
+        assert_eq!(
+            authenticator.verify_at(path, &headers, now_ms + 1),
+            Err(RequestAuthError::Replayed)
+        );
+
+        // Expired
+        let headers = to_headers(sign_request(&key, path, now_ms - 30_001, "d"));
+        assert_eq!(
+            authenticator.verify_at(path, &headers, now_ms),
+            Err(RequestAuthError::Expired)
+        );
+
+        // Unknown key
+        let headers = to_headers(sign_request(&unknown_key, path, now_ms, "e"));
+        assert_eq!(
+            authenticator.verify_at(path, &headers, now_ms),
+            Err(RequestAuthError::UnknownKey)
+        );
+
+        // Signed for another path
+        let headers = to_headers(sign_request(&key, "/sign/other", now_ms, "f"));
+        assert_eq!(
+            authenticator.verify_at(path, &headers, now_ms),
+            Err(RequestAuthError::InvalidSignature)
+        );
+
+        // Missing headers
+        assert_eq!(
+            authenticator.verify_at(path, &HeaderMap::new(), now_ms),
+            Err(RequestAuthError::MissingHeader(AUTH_TIMESTAMP_HEADER))
+        );
+    }
+
+    #[tokio::test]
+    async fn test_server_rejects_unauthenticated_requests() {
+        telemetry_subscribers::init_for_testing();
+        let (_, key): (_, Ed25519KeyPair) = get_key_pair();
+        let (_, unknown_key): (_, Ed25519KeyPair) = get_key_pair();
+        let (_, signer): (_, BridgeAuthorityKeyPair) = get_key_pair();
+        let mock = BridgeRequestMockHandler::new();
+        mock.set_signer(signer);
+        let authenticator = Arc::new(RequestAuthenticator::new(
+            vec![key.public().clone()],
+            DEFAULT_MAX_CLOCK_SKEW,
+        ));
+
+        let port = get_available_port("127.0.0.1");
+        let listener = tokio::net::TcpListener::bind(format!("127.0.0.1:{}", port))
+            .await
+            .unwrap();
+        let router = make_router(
+            Arc::new(mock),
+            Arc::new(BridgeMetrics::for_testing()),
+            Arc::new(BridgeNodePublicMetadata::empty_for_testing()),
+            Some(authenticator),
+            DEFAULT_SLOW_REQUEST_THRESHOLD,
+            DEFAULT_GOVERNANCE_BODY_LIMIT,
+        );
+        tokio::spawn(async move { axum::serve(listener, router).await.unwrap() });
+
+        let client = reqwest::Client::new();
+        let path = "/sign/emergency_button/2/1/0";
+        let url = format!("http://127.0.0.1:{}{}", port, path);
+        let now_ms = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_millis() as u64;
+        let send = |headers: Vec<(&'static str, String)>| {
+            let mut request = client.get(&url);
+            for (name, value) in headers {
+                request = request.header(name, value);
+            }
+            request.send()
+        };
+
+        // Public endpoints don't need authentication
+        let resp = client
+            .get(format!("http://127.0.0.1:{}{}", port, PING_PATH))
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), StatusCode::OK);
+
+        // Valid
+        let resp = send(sign_request(&key, path, now_ms, "1")).await.unwrap();
+        assert_eq!(resp.status(), StatusCode::OK);
+        // Replayed
+        let resp = send(sign_request(&key, path, now_ms, "1")).await.unwrap();
+        assert_eq!(resp.status(), StatusCode::UNAUTHORIZED);
+        assert!(resp.text().await.unwrap().contains("Replayed"));
+        // Expired
+        let resp = send(sign_request(&key, path, now_ms - 60_000, "2"))
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), StatusCode::UNAUTHORIZED);
+        assert!(resp.text().await.unwrap().contains("Expired"));
+        // Unknown key
+        let resp = send(sign_request(&unknown_key, path, now_ms, "3"))
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), StatusCode::UNAUTHORIZED);
+        assert!(resp.text().await.unwrap().contains("UnknownKey"));
+        // No authentication
+        let resp = send(vec![]).await.unwrap();
+        assert_eq!(resp.status(), StatusCode::UNAUTHORIZED);
+    }
+}
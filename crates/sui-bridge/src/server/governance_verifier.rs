@@ -1,31 +1,133 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
-use std::collections::HashMap;
+use std::collections::{BTreeSet, HashMap, HashSet};
 
+use tracing::warn;
+
+use crate::api_types::encode_action_digest;
 use crate::error::{BridgeError, BridgeResult};
 use crate::server::handler::ActionVerifier;
 use crate::types::{BridgeAction, BridgeActionDigest};
 
-#[derive(Debug)]
+#[derive(Debug, Clone, Default)]
 pub struct GovernanceVerifier {
     approved_goverance_actions: HashMap<BridgeActionDigest, BridgeAction>,
+    // Approved by digest only, the content of the action is unknown until it's requested
+    approved_digests: HashSet<BridgeActionDigest>,
 }
 
 impl GovernanceVerifier {
-    pub fn new(approved_actions: Vec<BridgeAction>) -> BridgeResult<Self> {
+    /// Fails if an action is approved twice, or if two approved actions conflict: only
+    /// one governance action of each type and chain can be executed with a given nonce.
+    pub fn new(
+        approved_actions: Vec<BridgeAction>,
+        approved_digests: Vec<BridgeActionDigest>,
+    ) -> BridgeResult<Self> {
         // TOOD(audit-blocking): verify chain ids
         let mut approved_goverance_actions = HashMap::new();
+        let mut nonces = HashMap::new();
         for action in approved_actions {
             if !action.is_governace_action() {
                 return Err(BridgeError::ActionIsNotGovernanceAction(action));
             }
-            approved_goverance_actions.insert(action.digest(), action);
+            let digest = action.digest();
+            if approved_goverance_actions.contains_key(&digest) {
+                return Err(BridgeError::Generic(format!(
+                    "Governance action {:?} is approved twice",
+                    action
+                )));
+            }
+            let nonce = (action.action_type(), action.chain_id(), action.seq_number());
+            if let Some(other) = nonces.insert(nonce, action.clone()) {
+                return Err(BridgeError::Generic(format!(
+                    "Approved governance actions {:?} and {:?} conflict, they have the same nonce",
+                    other, action
+                )));
+            }
+            approved_goverance_actions.insert(digest, action);
+        }
+        let mut digests = HashSet::new();
+        for digest in approved_digests {
+            if approved_goverance_actions.contains_key(&digest) || !digests.insert(digest) {
+                return Err(BridgeError::Generic(format!(
+                    "Governance action digest {} is approved twice",
+                    encode_action_digest(&digest)
+                )));
+            }
         }
         Ok(Self {
             approved_goverance_actions,
+            approved_digests: digests,
         })
     }
+
+    /// Hex encoded digests of the actions approved by their content, sorted.
+    pub fn approved_action_digests(&self) -> Vec<String> {
+        encode_digests(self.approved_goverance_actions.keys())
+    }
+
+    /// Hex encoded digests approved as such, sorted.
+    pub fn approved_digests(&self) -> Vec<String> {
+        encode_digests(self.approved_digests.iter())
+    }
+
+    /// The approved action of the same type that `action` is closest to, with the names of
+    /// the fields they differ in. Actions approved by digest only can't be compared.
+    pub fn nearest_miss(&self, action: &BridgeAction) -> Option<(&BridgeAction, Vec<String>)> {
+        let fields = action_fields(action);
+        self.approved_goverance_actions
+            .values()
+            .filter(|approved| approved.action_type() == action.action_type())
+            .map(|approved| {
+                let approved_fields = action_fields(approved);
+                let differing = fields
+                    .iter()
+                    .filter(|(name, value)| approved_fields.get(*name) != Some(*value))
+                    .map(|(name, _)| name.clone())
+                    .collect::<Vec<_>>();
+                (approved, differing)
+            })
+            .min_by_key(|(approved, differing)| (differing.len(), approved.digest()))
+    }
+
+    fn log_rejection(&self, action: &BridgeAction) {
+        match self.nearest_miss(action) {
+            Some((approved, fields)) => warn!(
+                "Governance action {:?} is not approved, it differs in {:?} from the closest approved action {:?}",
+                action, fields, approved
+            ),
+            None => warn!(
+                "Governance action {:?} with digest {} is not approved, no {:?} action is approved by content",
+                action,
+                encode_action_digest(&action.digest()),
+                action.action_type()
+            ),
+        }
+    }
+}
+
+fn encode_digests<'a>(digests: impl Iterator<Item = &'a BridgeActionDigest>) -> Vec<String> {
+    digests
+        .map(encode_action_digest)
+        .collect::<BTreeSet<_>>()
+        .into_iter()
+        .collect()
+}
+
+// The fields of the variant of `action`, as in the JSON of the config
+fn action_fields(action: &BridgeAction) -> serde_json::Map<String, serde_json::Value> {
+    match serde_json::to_value(action) {
+        Ok(serde_json::Value::Object(variant)) => variant
+            .into_iter()
+            .next()
+            .and_then(|(_, fields)| match fields {
+                serde_json::Value::Object(fields) => Some(fields),
+                _ => None,
+            })
+            .unwrap_or_default(),
+        _ => serde_json::Map::new(),
+    }
 }
 
 #[async_trait::async_trait]
@@ -46,6 +148,10 @@ impl ActionVerifier<BridgeAction> for GovernanceVerifier {
             );
             return Ok(key);
         }
+        if self.approved_digests.contains(&key.digest()) {
+            return Ok(key);
+        }
+        self.log_rejection(&key);
         return Err(BridgeError::GovernanceActionIsNotApproved);
     }
 }
@@ -73,7 +179,8 @@ mod tests {
             new_usd_limit: 10000,
         });
 
-        let verifier = GovernanceVerifier::new(vec![action_1.clone(), action_2.clone()]).unwrap();
+        let verifier =
+            GovernanceVerifier::new(vec![action_1.clone(), action_2.clone()], vec![]).unwrap();
         assert_eq!(
             verifier.verify(action_1.clone()).await.unwrap(),
             action_1.clone()
@@ -97,7 +204,8 @@ mod tests {
         // Token transfer action is not allowed
         let action_4 = get_test_sui_to_eth_bridge_action(None, None, None, None, None, None, None);
         assert!(matches!(
-            GovernanceVerifier::new(vec![action_1, action_2, action_4.clone()]).unwrap_err(),
+            GovernanceVerifier::new(vec![action_1, action_2, action_4.clone()], vec![])
+                .unwrap_err(),
             BridgeError::ActionIsNotGovernanceAction(..)
         ));
 
@@ -107,4 +215,120 @@ mod tests {
             BridgeError::ActionIsNotGovernanceAction(..)
         ));
     }
+
+    #[tokio::test]
+    async fn test_governance_verifier_with_approved_digests() {
+        let approved_action = BridgeAction::EmergencyAction(EmergencyAction {
+            chain_id: BridgeChainId::EthCustom,
+            nonce: 1,
+            action_type: EmergencyActionType::Pause,
+        });
+        let pinned_action = BridgeAction::LimitUpdateAction(LimitUpdateAction {
+            chain_id: BridgeChainId::EthCustom,
+            sending_chain_id: BridgeChainId::SuiCustom,
+            nonce: 3,
+            new_usd_limit: 5000,
+        });
+        let verifier =
+            GovernanceVerifier::new(vec![approved_action.clone()], vec![pinned_action.digest()])
+                .unwrap();
+        assert_eq!(
+            verifier.verify(pinned_action.clone()).await.unwrap(),
+            pinned_action
+        );
+        assert_eq!(
+            verifier.approved_action_digests(),
+            vec![encode_action_digest(&approved_action.digest())]
+        );
+        assert_eq!(
+            verifier.approved_digests(),
+            vec![encode_action_digest(&pinned_action.digest())]
+        );
+
+        // Any change of the content changes the digest
+        let other_limit = BridgeAction::LimitUpdateAction(LimitUpdateAction {
+            chain_id: BridgeChainId::EthCustom,
+            sending_chain_id: BridgeChainId::SuiCustom,
+            nonce: 3,
+            new_usd_limit: 5001,
+        });
+        assert_eq!(
+            verifier.verify(other_limit).await.unwrap_err(),
+            BridgeError::GovernanceActionIsNotApproved
+        );
+    }
+
+    #[test]
+    fn test_nearest_miss() {
+        let limit_update = |nonce, sending_chain_id, new_usd_limit| {
+            BridgeAction::LimitUpdateAction(LimitUpdateAction {
+                chain_id: BridgeChainId::EthCustom,
+                sending_chain_id,
+                nonce,
+                new_usd_limit,
+            })
+        };
+        let closest = limit_update(1, BridgeChainId::SuiCustom, 10000);
+        let verifier = GovernanceVerifier::new(
+            vec![
+                closest.clone(),
+                limit_update(2, BridgeChainId::SuiTestnet, 20000),
+            ],
+            vec![],
+        )
+        .unwrap();
+
+        let requested = limit_update(1, BridgeChainId::SuiCustom, 10001);
+        let (approved, fields) = verifier.nearest_miss(&requested).unwrap();
+        assert_eq!(approved, &closest);
+        assert_eq!(fields, vec!["new_usd_limit".to_string()]);
+
+        let requested = limit_update(2, BridgeChainId::SuiCustom, 10000);
+        let (approved, fields) = verifier.nearest_miss(&requested).unwrap();
+        assert_eq!(approved, &closest);
+        assert_eq!(fields, vec!["nonce".to_string()]);
+
+        // No approved action of the same type to compare with
+        let emergency = BridgeAction::EmergencyAction(EmergencyAction {
+            chain_id: BridgeChainId::EthCustom,
+            nonce: 1,
+            action_type: EmergencyActionType::Pause,
+        });
+        assert!(verifier.nearest_miss(&emergency).is_none());
+    }
+
+    #[test]
+    fn test_governance_verifier_rejects_duplicates() {
+        let pause = BridgeAction::EmergencyAction(EmergencyAction {
+            chain_id: BridgeChainId::EthCustom,
+            nonce: 1,
+            action_type: EmergencyActionType::Pause,
+        });
+        let unpause = BridgeAction::EmergencyAction(EmergencyAction {
+            chain_id: BridgeChainId::EthCustom,
+            nonce: 1,
+            action_type: EmergencyActionType::Unpause,
+        });
+        let duplicates = [
+            (vec![pause.clone(), pause.clone()], vec![]),
+            (vec![pause.clone()], vec![pause.digest()]),
+            (vec![], vec![pause.digest(), pause.digest()]),
+            // Both can't be executed with the same nonce
+            (vec![pause.clone(), unpause.clone()], vec![]),
+        ];
+        for (actions, digests) in duplicates {
+            assert!(matches!(
+                GovernanceVerifier::new(actions, digests).unwrap_err(),
+                BridgeError::Generic(_)
+            ));
+        }
+
+        // The same nonce on another chain is fine
+        let other_chain = BridgeAction::EmergencyAction(EmergencyAction {
+            chain_id: BridgeChainId::SuiCustom,
+            nonce: 1,
+            action_type: EmergencyActionType::Unpause,
+        });
+        GovernanceVerifier::new(vec![pause, other_chain], vec![]).unwrap();
+    }
 }
@@ -0,0 +1,336 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! gRPC variant of the signing API. `BridgeGrpcService` serves the same operations as
+//! the JSON API with the same `BridgeRequestHandlerTrait` implementation, so signing
+//! behaves identically over both transports. Actions are bcs encoded in the messages.
+
+use crate::error::BridgeError;
+use crate::metrics::BridgeMetrics;
+use crate::server::handler::BridgeRequestHandlerTrait;
+use crate::server::BridgeNodePublicMetadata;
+use crate::types::{BridgeAction, SignedBridgeAction};
+use crate::with_metrics;
+use fastcrypto::traits::ToFromBytes;
+use std::net::SocketAddr;
+use std::sync::Arc;
+use tonic::transport::server::TcpIncoming;
+use tonic::{Request, Response, Status};
+use tracing::info;
+
+pub(crate) mod gen {
+    include!(concat!(env!("OUT_DIR"), "/bridge.BridgeSigningService.rs"));
+}
+
+pub use gen::bridge_signing_service_client::BridgeSigningServiceClient;
+pub use gen::bridge_signing_service_server::{BridgeSigningService, BridgeSigningServiceServer};
+
+/// Chain that the token transfer was initiated on.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, prost::Enumeration)]
+#[repr(i32)]
+pub enum TransferSource {
+    Eth = 0,
+    Sui = 1,
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct SignTokenTransferRequest {
+    #[prost(enumeration = "TransferSource", tag = "1")]
+    pub source: i32,
+    /// Transaction hash in hex on Eth, transaction digest in base58 on Sui
+    #[prost(string, tag = "2")]
+    pub tx_id: String,
+    #[prost(uint32, tag = "3")]
+    pub event_index: u32,
+    /// Nonce of the transfer, checked against the known nonces of its source chain before
+    /// the transaction is read
+    #[prost(uint64, optional, tag = "4")]
+    pub nonce: Option<u64>,
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct SignGovernanceActionRequest {
+    /// bcs encoded `BridgeAction`
+    #[prost(bytes = "vec", tag = "1")]
+    pub action: Vec<u8>,
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct SignedActionResponse {
+    /// bcs encoded `SignedBridgeAction`
+    #[prost(bytes = "vec", tag = "1")]
+    pub signed_action: Vec<u8>,
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct HealthRequest {}
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct HealthResponse {}
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct NodeInfoRequest {}
+
+/// Same as the metadata served on `/ping`.
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct NodeInfoResponse {
+    #[prost(string, optional, tag = "1")]
+    pub version: Option<String>,
+    #[prost(bytes = "vec", optional, tag = "2")]
+    pub metrics_pubkey: Option<Vec<u8>>,
+}
+
+impl From<BridgeError> for Status {
+    fn from(err: BridgeError) -> Self {
+        // Same distinction as the status codes of the JSON API
+        match err {
+            BridgeError::ActionTypeNotAllowed(_) => Status::permission_denied(format!("{:?}", err)),
+            BridgeError::ServerBusy(_) => Status::resource_exhausted(format!("{:?}", err)),
+            BridgeError::ClockDrift(_) => Status::unavailable(format!("{:?}", err)),
+            ref err @ BridgeError::RemoteSignerError(..) if err.is_retryable() => {
+                Status::unavailable(format!("{:?}", err))
+            }
+            ref err @ BridgeError::NonceTooFarAhead(_) => {
+                Status::invalid_argument(format!("{:?}", err))
+            }
+            ref err if err.is_invalid_action() => Status::invalid_argument(format!("{:?}", err)),
+            _ => Status::internal(format!("Something went wrong: {:?}", err)),
+        }
+    }
+}
+
+fn signed_action_response(
+    signed_action: &SignedBridgeAction,
+) -> Result<SignedActionResponse, BridgeError> {
+    Ok(SignedActionResponse {
+        signed_action: bcs::to_bytes(signed_action)
+            .map_err(|e| BridgeError::Generic(format!("Couldn't encode signed action: {:?}", e)))?,
+    })
+}
+
+pub struct BridgeGrpcService<H> {
+    handler: Arc<H>,
+    metrics: Arc<BridgeMetrics>,
+    metadata: Arc<BridgeNodePublicMetadata>,
+}
+
+impl<H> BridgeGrpcService<H> {
+    pub fn new(
+        handler: Arc<H>,
+        metrics: Arc<BridgeMetrics>,
+        metadata: Arc<BridgeNodePublicMetadata>,
+    ) -> Self {
+        Self {
+            handler,
+            metrics,
+            metadata,
+        }
+    }
+}
+
+#[tonic::async_trait]
+impl<H> BridgeSigningService for BridgeGrpcService<H>
+where
+    H: BridgeRequestHandlerTrait + Send + Sync + 'static,
+{
+    async fn sign_token_transfer(
+        &self,
+        request: Request<SignTokenTransferRequest>,
+    ) -> Result<Response<SignedActionResponse>, Status> {
+        let request = request.into_inner();
+        let event_idx = u16::try_from(request.event_index)
+            .map_err(|_| Status::invalid_argument("Invalid event index"))?;
+        let response = match TransferSource::try_from(request.source) {
+            Ok(TransferSource::Eth) => {
+                let future = async {
+                    let sig = self
+                        .handler
+                        .handle_eth_tx_hash(request.tx_id, event_idx, request.nonce)
+                        .await?;
+                    signed_action_response(&sig)
+                };
+                with_metrics!(self.metrics.clone(), "grpc_handle_eth_tx_hash", future).await?
+            }
+            Ok(TransferSource::Sui) => {
+                let future = async {
+                    let sig = self
+                        .handler
+                        .handle_sui_tx_digest(request.tx_id, event_idx, request.nonce)
+                        .await?;
+                    signed_action_response(&sig)
+                };
+                with_metrics!(self.metrics.clone(), "grpc_handle_sui_tx_digest", future).await?
+            }
+            Err(_) => return Err(Status::invalid_argument("Invalid transfer source")),
+        };
+        Ok(Response::new(response))
+    }
+
+    async fn sign_governance_action(
+        &self,
+        request: Request<SignGovernanceActionRequest>,
+    ) -> Result<Response<SignedActionResponse>, Status> {
+        let action: BridgeAction = bcs::from_bytes(&request.into_inner().action)
+            .map_err(|e| Status::invalid_argument(format!("Invalid action: {:?}", e)))?;
+        let future = async {
+            let sig = self.handler.handle_governance_action(action).await?;
+            signed_action_response(&sig)
+        };
+        let response = with_metrics!(
+            self.metrics.clone(),
+            "grpc_handle_governance_action",
+            future
+        )
+        .await?;
+        Ok(Response::new(response))
+    }
+
+    async fn health(
+        &self,
+        _request: Request<HealthRequest>,
+    ) -> Result<Response<HealthResponse>, Status> {
+        Ok(Response::new(HealthResponse {}))
+    }
+
+    async fn node_info(
+        &self,
+        _request: Request<NodeInfoRequest>,
+    ) -> Result<Response<NodeInfoResponse>, Status> {
+        Ok(Response::new(NodeInfoResponse {
+            version: self.metadata.version.clone(),
+            metrics_pubkey: self
+                .metadata
+                .metrics_pubkey
+                .as_ref()
+                .map(|key| key.as_bytes().to_vec()),
+        }))
+    }
+}
+
+pub fn run_grpc_server<H>(
+    socket_address: &SocketAddr,
+    handler: Arc<H>,
+    metrics: Arc<BridgeMetrics>,
+    metadata: Arc<BridgeNodePublicMetadata>,
+) -> tokio::task::JoinHandle<()>
+where
+    H: BridgeRequestHandlerTrait + Send + Sync + 'static,
+{
+    let socket_address = *socket_address;
+    tokio::spawn(async move {
+        let listener = tokio::net::TcpListener::bind(socket_address).await.unwrap();
+        let incoming = TcpIncoming::from_listener(listener, true, None).unwrap();
+        info!("Starting gRPC server at {}", socket_address);
+        tonic::transport::Server::builder()
+            .add_service(BridgeSigningServiceServer::new(BridgeGrpcService::new(
+                handler, metrics, metadata,
+            )))
+            .serve_with_incoming(incoming)
+            .await
+            .unwrap();
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::client::bridge_client::{BridgeClient, GRPC_URL_SCHEME};
+    use crate::crypto::BridgeAuthoritySignInfo;
+    use crate::server::mock_handler::BridgeRequestMockHandler;
+    use crate::server::timing::DEFAULT_SLOW_REQUEST_THRESHOLD;
+    use crate::server::{make_router, DEFAULT_GOVERNANCE_BODY_LIMIT};
+    use crate::test_utils::{get_test_authority_and_key, get_test_sui_to_eth_bridge_action};
+    use crate::types::{BridgeCommittee, EmergencyAction, EmergencyActionType};
+    use fastcrypto::traits::KeyPair;
+    use sui_config::local_ip_utils::get_available_port;
+    use sui_types::bridge::BridgeChainId;
+    use sui_types::digests::TransactionDigest;
+
+    #[tokio::test]
+    async fn test_grpc_and_json_servers_sign_identically() {
+        telemetry_subscribers::init_for_testing();
+        let json_port = get_available_port("127.0.0.1");
+        let grpc_port = get_available_port("127.0.0.1");
+        let (mut authority, _, secret) = get_test_authority_and_key(10000, json_port);
+        let handler = Arc::new(BridgeRequestMockHandler::new());
+        handler.set_signer(secret.copy());
+        let metrics = Arc::new(BridgeMetrics::for_testing());
+        let metadata = Arc::new(BridgeNodePublicMetadata::empty_for_testing());
+
+        // Both servers share one handler
+        let listener = tokio::net::TcpListener::bind(format!("127.0.0.1:{}", json_port))
+            .await
+            .unwrap();
+        let router = make_router(
+            handler.clone(),
+            metrics.clone(),
+            metadata.clone(),
+            None,
+            DEFAULT_SLOW_REQUEST_THRESHOLD,
+            DEFAULT_GOVERNANCE_BODY_LIMIT,
+        );
+        tokio::spawn(async move { axum::serve(listener, router).await.unwrap() });
+        let _grpc_handle = run_grpc_server(
+            &SocketAddr::from(([127, 0, 0, 1], grpc_port)),
+            handler.clone(),
+            metrics,
+            metadata,
+        );
+
+        let json_client = BridgeClient::new(
+            authority.pubkey_bytes(),
+            Arc::new(BridgeCommittee::new(vec![authority.clone()]).unwrap()),
+        )
+        .unwrap();
+        authority.base_url = format!("{}://127.0.0.1:{}", GRPC_URL_SCHEME, grpc_port);
+        let grpc_client = BridgeClient::new(
+            authority.pubkey_bytes(),
+            Arc::new(BridgeCommittee::new(vec![authority.clone()]).unwrap()),
+        )
+        .unwrap();
+        let now = std::time::Instant::now();
+        while !grpc_client.ping().await.unwrap_or(false) {
+            assert!(now.elapsed().as_secs() < 10, "gRPC server did not start");
+            tokio::time::sleep(std::time::Duration::from_millis(100)).await;
+        }
+
+        let tx_digest = TransactionDigest::random();
+        let transfer = get_test_sui_to_eth_bridge_action(
+            Some(tx_digest),
+            Some(1),
+            None,
+            None,
+            None,
+            None,
+            None,
+        );
+        let sig = BridgeAuthoritySignInfo::new(&transfer, &secret);
+        handler.add_sui_event_response(
+            tx_digest,
+            1,
+            Ok(SignedBridgeAction::new_from_data_and_sig(
+                transfer.clone(),
+                sig,
+            )),
+        );
+        let governance_action = BridgeAction::EmergencyAction(EmergencyAction {
+            chain_id: BridgeChainId::SuiCustom,
+            nonce: 1,
+            action_type: EmergencyActionType::Pause,
+        });
+
+        for action in [transfer, governance_action] {
+            let json_signed = json_client
+                .request_sign_bridge_action(action.clone())
+                .await
+                .unwrap();
+            let grpc_signed = grpc_client
+                .request_sign_bridge_action(action.clone())
+                .await
+                .unwrap();
+            assert_eq!(json_signed.data(), &action);
+            assert_eq!(grpc_signed.data(), &action);
+            assert_eq!(json_signed.auth_sig(), grpc_signed.auth_sig());
+        }
+    }
+}
@@ -3,44 +3,58 @@
 
 #![allow(clippy::type_complexity)]
 
-use crate::crypto::{BridgeAuthorityKeyPair, BridgeAuthoritySignInfo};
+use crate::api_types::{ApprovedGovernance, SignedSetDigest};
+use crate::chain_health::PipelineChain;
+use crate::crypto::canonicalize_sign_info;
 use crate::error::{BridgeError, BridgeResult};
 use crate::eth_client::EthClient;
 use crate::metrics::BridgeMetrics;
+use crate::signed_set::SignedSet;
+use crate::signer::BridgeAuthoritySigner;
 use crate::sui_client::{SuiClient, SuiClientInner};
-use crate::types::{BridgeAction, SignedBridgeAction};
+use crate::timestamps::now_ms;
+use crate::types::{BridgeAction, BridgeActionType, GovernanceDryRun, SignedBridgeAction};
 use async_trait::async_trait;
 use axum::Json;
 use ethers::providers::JsonRpcClient;
-use ethers::types::TxHash;
+use ethers::types::{TransactionReceipt, TxHash};
 use lru::LruCache;
+use prometheus::IntGauge;
+use std::collections::HashSet;
 use std::num::NonZeroUsize;
 use std::str::FromStr;
 use std::sync::Arc;
+use std::time::{Duration, Instant};
 use sui_types::digests::TransactionDigest;
 use tap::TapFallible;
-use tokio::sync::{oneshot, Mutex};
-use tracing::info;
+use tokio::sync::{oneshot, Mutex, OwnedSemaphorePermit, Semaphore};
+use tracing::{info, warn};
 
 use super::governance_verifier::GovernanceVerifier;
+use super::nonce_guard::{NonceGuard, DEFAULT_MAX_NONCE_LEAD};
+use super::timing::{RequestStage, RequestTiming};
 
 #[async_trait]
 pub trait BridgeRequestHandlerTrait {
     /// Handles a request to sign a BridgeAction that bridges assets
     /// from Ethereum to Sui. The inputs are a transaction hash on Ethereum
-    /// that emitted the bridge event and the Event index in that transaction
+    /// that emitted the bridge event, the Event index in that transaction, and
+    /// the nonce of the transfer if the requester claims it, see `nonce_guard`
     async fn handle_eth_tx_hash(
         &self,
         tx_hash_hex: String,
         event_idx: u16,
+        nonce: Option<u64>,
     ) -> Result<Json<SignedBridgeAction>, BridgeError>;
     /// Handles a request to sign a BridgeAction that bridges assets
     /// from Sui to Ethereum. The inputs are a transaction digest on Sui
-    /// that emitted the bridge event and the Event index in that transaction
+    /// that emitted the bridge event, the Event index in that transaction, and
+    /// the nonce of the transfer if the requester claims it, see `nonce_guard`
     async fn handle_sui_tx_digest(
         &self,
         tx_digest_base58: String,
         event_idx: u16,
+        nonce: Option<u64>,
     ) -> Result<Json<SignedBridgeAction>, BridgeError>;
 
     /// Handles a request to sign a governance action.
@@ -48,21 +62,255 @@ pub trait BridgeRequestHandlerTrait {
         &self,
         action: BridgeAction,
     ) -> Result<Json<SignedBridgeAction>, BridgeError>;
+
+    /// Verifies a governance action the same way as when asked to sign it, and answers
+    /// whether it would be signed. Nothing is signed or cached.
+    async fn dry_run_governance_action(
+        &self,
+        action: BridgeAction,
+    ) -> Result<Json<GovernanceDryRun>, BridgeError>;
+
+    /// Returns the digest of the set of actions this node signed, see `signed_set`.
+    async fn signed_set_digest(&self) -> Result<Json<SignedSetDigest>, BridgeError>;
+
+    /// Returns the digests of the governance actions this node signs when requested.
+    async fn approved_governance(&self) -> Result<Json<ApprovedGovernance>, BridgeError>;
+}
+
+/// Handler of a node in observer mode, which has no key to sign with. The observer
+/// server does not serve the signing routes, so requests never reach it.
+pub struct ObserverRequestHandler;
+
+#[async_trait]
+impl BridgeRequestHandlerTrait for ObserverRequestHandler {
+    async fn handle_eth_tx_hash(
+        &self,
+        _tx_hash_hex: String,
+        _event_idx: u16,
+        _nonce: Option<u64>,
+    ) -> Result<Json<SignedBridgeAction>, BridgeError> {
+        Err(BridgeError::ObserverMode)
+    }
+
+    async fn handle_sui_tx_digest(
+        &self,
+        _tx_digest_base58: String,
+        _event_idx: u16,
+        _nonce: Option<u64>,
+    ) -> Result<Json<SignedBridgeAction>, BridgeError> {
+        Err(BridgeError::ObserverMode)
+    }
+
+    async fn handle_governance_action(
+        &self,
+        _action: BridgeAction,
+    ) -> Result<Json<SignedBridgeAction>, BridgeError> {
+        Err(BridgeError::ObserverMode)
+    }
+
+    async fn dry_run_governance_action(
+        &self,
+        _action: BridgeAction,
+    ) -> Result<Json<GovernanceDryRun>, BridgeError> {
+        Err(BridgeError::ObserverMode)
+    }
+
+    async fn signed_set_digest(&self) -> Result<Json<SignedSetDigest>, BridgeError> {
+        Err(BridgeError::ObserverMode)
+    }
+
+    async fn approved_governance(&self) -> Result<Json<ApprovedGovernance>, BridgeError> {
+        Err(BridgeError::ObserverMode)
+    }
+}
+
+/// Handler of a node in shadow mode, which runs next to the committee member it shadows
+/// and must not sign in its place. Signing requests are rejected as unavailable, so that
+/// the aggregators ask the other members.
+pub struct ShadowRequestHandler;
+
+#[async_trait]
+impl BridgeRequestHandlerTrait for ShadowRequestHandler {
+    async fn handle_eth_tx_hash(
+        &self,
+        _tx_hash_hex: String,
+        _event_idx: u16,
+        _nonce: Option<u64>,
+    ) -> Result<Json<SignedBridgeAction>, BridgeError> {
+        Err(BridgeError::ShadowMode)
+    }
+
+    async fn handle_sui_tx_digest(
+        &self,
+        _tx_digest_base58: String,
+        _event_idx: u16,
+        _nonce: Option<u64>,
+    ) -> Result<Json<SignedBridgeAction>, BridgeError> {
+        Err(BridgeError::ShadowMode)
+    }
+
+    async fn handle_governance_action(
+        &self,
+        _action: BridgeAction,
+    ) -> Result<Json<SignedBridgeAction>, BridgeError> {
+        Err(BridgeError::ShadowMode)
+    }
+
+    async fn dry_run_governance_action(
+        &self,
+        _action: BridgeAction,
+    ) -> Result<Json<GovernanceDryRun>, BridgeError> {
+        Err(BridgeError::ShadowMode)
+    }
+
+    async fn signed_set_digest(&self) -> Result<Json<SignedSetDigest>, BridgeError> {
+        Err(BridgeError::ShadowMode)
+    }
+
+    async fn approved_governance(&self) -> Result<Json<ApprovedGovernance>, BridgeError> {
+        Err(BridgeError::ShadowMode)
+    }
 }
 
 #[async_trait::async_trait]
 pub trait ActionVerifier<K>: Send + Sync {
     // Name of the verifier, used for metrics
     fn name(&self) -> &'static str;
+    // The chain whose RPC the verifier queries, its health is updated on each verification
+    fn chain(&self) -> Option<PipelineChain> {
+        None
+    }
     async fn verify(&self, key: K) -> BridgeResult<BridgeAction>;
 }
 
 struct SuiActionVerifier<C> {
     sui_client: Arc<SuiClient<C>>,
+    nonce_guard: Option<Arc<NonceGuard>>,
 }
 
+/// How long the receipt of a transaction requested before it was final is kept, a bit
+/// longer than Eth takes to finalize a block.
+pub const UNFINALIZED_RECEIPT_TTL: Duration = Duration::from_secs(30 * 60);
+const UNFINALIZED_RECEIPT_CACHE_SIZE: usize = 1000;
+
 struct EthActionVerifier<P> {
     eth_client: Arc<EthClient<P>>,
+    // Number of blocks past the last finalized block a deposit needs to be before signing
+    extra_eth_confirmations: u64,
+    // Receipts of the transactions requested before they were final, so that the requests
+    // once they are final only check the finality of their block. This tier sits below
+    // the cache of the signatures in `SignerWithCache`, which is final. A receipt whose
+    // block is no longer on the chain of the provider when it is final is dropped.
+    unfinalized_receipts: std::sync::Mutex<LruCache<TxHash, (TransactionReceipt, Instant)>>,
+    nonce_guard: Option<Arc<NonceGuard>>,
+    metrics: Arc<BridgeMetrics>,
+}
+
+impl<P> EthActionVerifier<P>
+where
+    P: JsonRpcClient + Send + Sync + 'static,
+{
+    fn new(
+        eth_client: Arc<EthClient<P>>,
+        extra_eth_confirmations: u64,
+        nonce_guard: Option<Arc<NonceGuard>>,
+        metrics: Arc<BridgeMetrics>,
+    ) -> Self {
+        Self {
+            eth_client,
+            extra_eth_confirmations,
+            unfinalized_receipts: std::sync::Mutex::new(LruCache::new(
+                NonZeroUsize::new(UNFINALIZED_RECEIPT_CACHE_SIZE).unwrap(),
+            )),
+            nonce_guard,
+            metrics,
+        }
+    }
+
+    // Refuses the deposit of the receipt before its finality is queried, if its nonce is
+    // too far ahead. Receipts without the deposit fail the verification as before.
+    fn check_nonce(
+        &self,
+        receipt: &TransactionReceipt,
+        tx_hash: TxHash,
+        event_idx: u16,
+    ) -> BridgeResult<()> {
+        let Some(nonce_guard) = &self.nonce_guard else {
+            return Ok(());
+        };
+        match self
+            .eth_client
+            .bridge_action_in_receipt(receipt, tx_hash, event_idx)
+        {
+            Ok(action) => nonce_guard.check(&action),
+            Err(_) => Ok(()),
+        }
+    }
+
+    fn cached_unfinalized_receipt(&self, tx_hash: TxHash) -> Option<TransactionReceipt> {
+        let mut receipts = self.unfinalized_receipts.lock().unwrap();
+        match receipts.get(&tx_hash) {
+            Some((receipt, cached_at)) if cached_at.elapsed() < UNFINALIZED_RECEIPT_TTL => {
+                Some(receipt.clone())
+            }
+            Some(_) => {
+                receipts.pop(&tx_hash);
+                None
+            }
+            None => None,
+        }
+    }
+
+    // Whether the block of `receipt` is still on the chain of the provider
+    async fn is_canonical(&self, receipt: &TransactionReceipt) -> BridgeResult<bool> {
+        let (Some(block_number), Some(block_hash)) = (receipt.block_number, receipt.block_hash)
+        else {
+            return Ok(false);
+        };
+        Ok(self
+            .eth_client
+            .get_block_hash(block_number.as_u64())
+            .await?
+            == Some(block_hash))
+    }
+
+    // Verifies the action with the receipt fetched by an earlier request, returns None
+    // when the block of the receipt was reorged out
+    async fn verify_with_cached_receipt(
+        &self,
+        receipt: TransactionReceipt,
+        tx_hash: TxHash,
+        event_idx: u16,
+    ) -> BridgeResult<Option<BridgeAction>> {
+        self.check_nonce(&receipt, tx_hash, event_idx)?;
+        let result = self
+            .eth_client
+            .bridge_action_from_receipt(
+                receipt.clone(),
+                tx_hash,
+                event_idx,
+                self.extra_eth_confirmations,
+            )
+            .await;
+        // Not final yet, or the provider failed: the receipt is kept for the next request
+        if matches!(&result, Err(e) if e.is_retryable()) {
+            self.metrics.unfinalized_receipt_cache_hit.inc();
+            return result.map(Some);
+        }
+        // The block is final, a reorg can't remove the transaction anymore
+        let canonical = self.is_canonical(&receipt).await?;
+        self.unfinalized_receipts.lock().unwrap().pop(&tx_hash);
+        if !canonical {
+            info!(
+                "Block {:?} of Eth transaction {:?} was reorged out, dropped its cached receipt",
+                receipt.block_hash, tx_hash
+            );
+            self.metrics.unfinalized_receipt_cache_reorgs.inc();
+            return Ok(None);
+        }
+        self.metrics.unfinalized_receipt_cache_hit.inc();
+        result.map(Some)
+    }
 }
 
 #[async_trait::async_trait]
@@ -74,12 +322,22 @@ where
         "SuiActionVerifier"
     }
 
+    fn chain(&self) -> Option<PipelineChain> {
+        Some(PipelineChain::Sui)
+    }
+
     async fn verify(&self, key: (TransactionDigest, u16)) -> BridgeResult<BridgeAction> {
         let (tx_digest, event_idx) = key;
-        self.sui_client
+        let action = self
+            .sui_client
             .get_bridge_action_by_tx_digest_and_event_idx_maybe(&tx_digest, event_idx)
-            .await
-            .tap_ok(|action| info!("Sui action found: {:?}", action))
+            .await?;
+        if let Some(nonce_guard) = &self.nonce_guard {
+            nonce_guard.check(&action)?;
+            nonce_guard.observe(&action);
+        }
+        info!("Sui action found: {:?}", action);
+        Ok(action)
     }
 }
 
@@ -92,20 +350,156 @@ where
         "EthActionVerifier"
     }
 
+    fn chain(&self) -> Option<PipelineChain> {
+        Some(PipelineChain::Eth)
+    }
+
     async fn verify(&self, key: (TxHash, u16)) -> BridgeResult<BridgeAction> {
         let (tx_hash, event_idx) = key;
-        self.eth_client
-            .get_finalized_bridge_action_maybe(tx_hash, event_idx)
-            .await
-            .tap_ok(|action| info!("Eth action found: {:?}", action))
+        if let Some(receipt) = self.cached_unfinalized_receipt(tx_hash) {
+            if let Some(action) = self
+                .verify_with_cached_receipt(receipt, tx_hash, event_idx)
+                .await?
+            {
+                if let Some(nonce_guard) = &self.nonce_guard {
+                    nonce_guard.observe(&action);
+                }
+                info!("Eth action found: {:?}", action);
+                return Ok(action);
+            }
+        }
+        let receipt = self
+            .eth_client
+            .get_verified_transaction_receipt(tx_hash)
+            .await?;
+        self.check_nonce(&receipt, tx_hash, event_idx)?;
+        let result = self
+            .eth_client
+            .bridge_action_from_receipt(
+                receipt.clone(),
+                tx_hash,
+                event_idx,
+                self.extra_eth_confirmations,
+            )
+            .await;
+        if matches!(result, Err(BridgeError::TxNotFinalized)) && receipt.block_hash.is_some() {
+            self.unfinalized_receipts
+                .lock()
+                .unwrap()
+                .put(tx_hash, (receipt, Instant::now()));
+        }
+        if let (Ok(action), Some(nonce_guard)) = (&result, &self.nonce_guard) {
+            nonce_guard.observe(action);
+        }
+        result.tap_ok(|action| info!("Eth action found: {:?}", action))
+    }
+}
+
+pub const DEFAULT_MAX_CONCURRENT_ETH_VERIFICATIONS: usize = 16;
+pub const DEFAULT_MAX_CONCURRENT_SUI_VERIFICATIONS: usize = 16;
+pub const DEFAULT_VERIFICATION_QUEUE_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Bounds on the verifications of signing requests, which query the RPC of the chain of
+/// the request. Governance actions are verified locally and are not bounded.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct VerificationLimits {
+    pub max_concurrent_eth_verifications: usize,
+    pub max_concurrent_sui_verifications: usize,
+    /// How long a request waits for a verification slot before it's rejected as busy
+    pub queue_timeout: Duration,
+    /// Token transfers at least this far ahead of the next nonce of their source chain are
+    /// refused, see `NonceGuard`
+    pub max_nonce_lead: u64,
+}
+
+impl Default for VerificationLimits {
+    fn default() -> Self {
+        Self {
+            max_concurrent_eth_verifications: DEFAULT_MAX_CONCURRENT_ETH_VERIFICATIONS,
+            max_concurrent_sui_verifications: DEFAULT_MAX_CONCURRENT_SUI_VERIFICATIONS,
+            queue_timeout: DEFAULT_VERIFICATION_QUEUE_TIMEOUT,
+            max_nonce_lead: DEFAULT_MAX_NONCE_LEAD,
+        }
+    }
+}
+
+/// The verification slots of a chain. Requests wait for a free slot up to the queue
+/// timeout, and are then rejected with the retryable `BridgeError::ServerBusy`.
+struct VerificationQueue {
+    chain: PipelineChain,
+    slots: Arc<Semaphore>,
+    timeout: Duration,
+}
+
+impl VerificationQueue {
+    fn new(chain: PipelineChain, max_concurrent: usize, timeout: Duration) -> Self {
+        Self {
+            chain,
+            slots: Arc::new(Semaphore::new(max_concurrent)),
+            timeout,
+        }
+    }
+
+    async fn enter(
+        &self,
+        timing: &RequestTiming,
+        metrics: &BridgeMetrics,
+    ) -> BridgeResult<VerificationSlot> {
+        let chain = self.chain.as_str();
+        let depth = metrics.verification_queue_depth.with_label_values(&[chain]);
+        let start = Instant::now();
+        depth.inc();
+        let permit = tokio::time::timeout(self.timeout, self.slots.clone().acquire_owned()).await;
+        depth.dec();
+        let waited = start.elapsed();
+        timing.record(RequestStage::VerificationQueue, waited);
+        metrics
+            .verification_queue_wait
+            .with_label_values(&[chain])
+            .observe(waited.as_secs_f64());
+        match permit {
+            Ok(permit) => {
+                let inflight = metrics.verifications_inflight.with_label_values(&[chain]);
+                inflight.inc();
+                Ok(VerificationSlot {
+                    _permit: permit.expect("Verification slots are never closed"),
+                    inflight,
+                })
+            }
+            Err(_) => {
+                metrics
+                    .verification_queue_timeouts
+                    .with_label_values(&[chain])
+                    .inc();
+                Err(BridgeError::ServerBusy(format!(
+                    "no {} verification slot in {:?}",
+                    chain, self.timeout
+                )))
+            }
+        }
+    }
+}
+
+/// A verification slot, given back when dropped.
+struct VerificationSlot {
+    _permit: OwnedSemaphorePermit,
+    inflight: IntGauge,
+}
+
+impl Drop for VerificationSlot {
+    fn drop(&mut self) {
+        self.inflight.dec();
     }
 }
 
 struct SignerWithCache<K> {
-    signer: Arc<BridgeAuthorityKeyPair>,
+    signer: Arc<dyn BridgeAuthoritySigner>,
     verifier: Arc<dyn ActionVerifier<K>>,
-    mutex: Arc<Mutex<()>>,
-    cache: LruCache<K, Arc<Mutex<Option<BridgeResult<SignedBridgeAction>>>>>,
+    // Requests are signed concurrently, the same key gets the same entry
+    cache: Mutex<LruCache<K, Arc<Mutex<Option<BridgeResult<SignedBridgeAction>>>>>>,
+    // Cache hits don't wait for a slot
+    queue: Option<VerificationQueue>,
+    signed_set: Option<Arc<SignedSet>>,
     metrics: Arc<BridgeMetrics>,
 }
 
@@ -114,57 +508,74 @@ where
     K: std::hash::Hash + Eq + Clone + Send + Sync + 'static,
 {
     fn new(
-        signer: Arc<BridgeAuthorityKeyPair>,
+        signer: Arc<dyn BridgeAuthoritySigner>,
         verifier: impl ActionVerifier<K> + 'static,
         metrics: Arc<BridgeMetrics>,
     ) -> Self {
         Self {
             signer,
             verifier: Arc::new(verifier),
-            mutex: Arc::new(Mutex::new(())),
-            cache: LruCache::new(NonZeroUsize::new(1000).unwrap()),
+            cache: Mutex::new(LruCache::new(NonZeroUsize::new(1000).unwrap())),
+            queue: None,
+            signed_set: None,
             metrics,
         }
     }
 
+    fn with_verification_queue(mut self, queue: VerificationQueue) -> Self {
+        self.queue = Some(queue);
+        self
+    }
+
+    fn with_signed_set(mut self, signed_set: Option<Arc<SignedSet>>) -> Self {
+        self.signed_set = signed_set;
+        self
+    }
+
     fn spawn(
-        mut self,
+        self,
         mut rx: mysten_metrics::metered_channel::Receiver<(
             K,
+            RequestTiming,
             oneshot::Sender<BridgeResult<SignedBridgeAction>>,
         )>,
     ) -> tokio::task::JoinHandle<()> {
+        let signer = Arc::new(self);
         tokio::spawn(async move {
-            loop {
-                let (key, tx) = rx
-                    .recv()
-                    .await
-                    .unwrap_or_else(|| panic!("Server signer's channel is closed"));
-                let result = self.sign(key).await;
-                // The receiver may be dropped before the sender (client connection was dropped for example),
-                // we ignore the error in that case.
-                let _ = tx.send(result);
+            // The channel closes when the handler is dropped, e.g. the node shuts down
+            while let Some((key, timing, tx)) = rx.recv().await {
+                let signer = signer.clone();
+                tokio::spawn(async move {
+                    let result = signer.sign(key, &timing).await;
+                    // The receiver may be dropped before the sender (client connection was dropped for example),
+                    // we ignore the error in that case.
+                    let _ = tx.send(result);
+                });
             }
         })
     }
 
     async fn get_cache_entry(
-        &mut self,
+        &self,
         key: K,
     ) -> Arc<Mutex<Option<BridgeResult<SignedBridgeAction>>>> {
-        // This mutex exists to make sure everyone gets the same entry, namely no double insert
-        let _ = self.mutex.lock().await;
         self.cache
+            .lock()
+            .await
             .get_or_insert(key, || Arc::new(Mutex::new(None)))
             .clone()
     }
 
-    async fn sign(&mut self, key: K) -> BridgeResult<SignedBridgeAction> {
+    async fn sign(&self, key: K, timing: &RequestTiming) -> BridgeResult<SignedBridgeAction> {
+        // The time based checks of the verification can't be trusted while the clock is off
+        self.metrics.clock_drift.check_signing()?;
         let signer = self.signer.clone();
         let verifier = self.verifier.clone();
         let verifier_name = verifier.name();
+        let start = Instant::now();
         let entry = self.get_cache_entry(key.clone()).await;
         let mut guard = entry.lock().await;
+        timing.record(RequestStage::CacheLookup, start.elapsed());
         if let Some(result) = &*guard {
             self.metrics
                 .signer_with_cache_hit
@@ -176,26 +587,49 @@ where
             .signer_with_cache_miss
             .with_label_values(&[verifier_name])
             .inc();
-        match verifier.verify(key.clone()).await {
+        // Busy errors are not cached, the request can be retried
+        let slot = match &self.queue {
+            Some(queue) => Some(queue.enter(timing, &self.metrics).await?),
+            None => None,
+        };
+        let start = Instant::now();
+        let verified = verifier.verify(key.clone()).await;
+        timing.record(RequestStage::Verification, start.elapsed());
+        // Signing doesn't query the chain, the slot is freed for the next request
+        drop(slot);
+        if let Some(chain) = verifier.chain() {
+            self.metrics
+                .chain_health
+                .record(chain, &verified, &self.metrics);
+        }
+        match verified {
             Ok(bridge_action) => {
-                let sig = BridgeAuthoritySignInfo::new(&bridge_action, &signer);
+                let start = Instant::now();
+                // Signer errors are not cached, they are about the signer rather than
+                // the action, e.g. a remote signer that signs with another key, which is
+                // alerted on and may be fixed without restarting the node
+                let sig = signer.sign_action(&bridge_action).await?;
+                let sig = canonicalize_sign_info(sig, &bridge_action, &self.metrics)?;
+                timing.record(RequestStage::Signing, start.elapsed());
+                if let Some(signed_set) = &self.signed_set {
+                    // The signature is returned even if the set can't be updated
+                    if let Err(e) = signed_set.insert(&bridge_action, now_ms()) {
+                        warn!(
+                            "Couldn't add {:?} to the signed set: {:?}",
+                            bridge_action, e
+                        );
+                    }
+                }
                 let result = SignedBridgeAction::new_from_data_and_sig(bridge_action, sig);
                 // Cache result if Ok
                 *guard = Some(Ok(result.clone()));
                 Ok(result)
             }
             Err(e) => {
-                match e {
-                    // Only cache non-transient errors
-                    BridgeError::GovernanceActionIsNotApproved { .. }
-                    | BridgeError::ActionIsNotGovernanceAction(..)
-                    | BridgeError::BridgeEventInUnrecognizedSuiPackage
-                    | BridgeError::BridgeEventInUnrecognizedEthContract
-                    | BridgeError::BridgeEventNotActionable
-                    | BridgeError::NoBridgeEventsInTxPosition => {
-                        *guard = Some(Err(e.clone()));
-                    }
-                    _ => (),
+                // Only cache errors that verifying again would run into. The known nonces
+                // may catch up with a transfer refused as too far ahead.
+                if !e.is_retryable() && !matches!(e, BridgeError::NonceTooFarAhead(_)) {
+                    *guard = Some(Err(e.clone()));
                 }
                 Err(e)
             }
@@ -204,27 +638,35 @@ where
 
     #[cfg(test)]
     async fn get_testing_only(
-        &mut self,
+        &self,
         key: K,
-    ) -> Option<&Arc<Mutex<Option<BridgeResult<SignedBridgeAction>>>>> {
-        let _ = self.mutex.lock().await;
-        self.cache.get(&key)
+    ) -> Option<Arc<Mutex<Option<BridgeResult<SignedBridgeAction>>>>> {
+        self.cache.lock().await.get(&key).cloned()
     }
 }
 
 pub struct BridgeRequestHandler {
     sui_signer_tx: mysten_metrics::metered_channel::Sender<(
         (TransactionDigest, u16),
+        RequestTiming,
         oneshot::Sender<BridgeResult<SignedBridgeAction>>,
     )>,
     eth_signer_tx: mysten_metrics::metered_channel::Sender<(
         (TxHash, u16),
+        RequestTiming,
         oneshot::Sender<BridgeResult<SignedBridgeAction>>,
     )>,
     governance_signer_tx: mysten_metrics::metered_channel::Sender<(
         BridgeAction,
+        RequestTiming,
         oneshot::Sender<BridgeResult<SignedBridgeAction>>,
     )>,
+    // Verifies dry runs outside of the signer, so that they are never cached
+    governance_verifier: GovernanceVerifier,
+    sign_action_types: HashSet<BridgeActionType>,
+    // None when the node doesn't run the client, which has the storage
+    signed_set: Option<Arc<SignedSet>>,
+    nonce_guard: Option<Arc<NonceGuard>>,
 }
 
 impl BridgeRequestHandler {
@@ -232,10 +674,15 @@ impl BridgeRequestHandler {
         SC: SuiClientInner + Send + Sync + 'static,
         EP: JsonRpcClient + Send + Sync + 'static,
     >(
-        signer: BridgeAuthorityKeyPair,
+        signer: Arc<dyn BridgeAuthoritySigner>,
         sui_client: Arc<SuiClient<SC>>,
         eth_client: Arc<EthClient<EP>>,
-        approved_governance_actions: Vec<BridgeAction>,
+        extra_eth_confirmations: u64,
+        governance_verifier: GovernanceVerifier,
+        sign_action_types: HashSet<BridgeActionType>,
+        verification_limits: VerificationLimits,
+        signed_set: Option<Arc<SignedSet>>,
+        nonce_guard: Option<Arc<NonceGuard>>,
         metrics: Arc<BridgeMetrics>,
     ) -> Self {
         let (sui_signer_tx, sui_rx) = mysten_metrics::metered_channel::channel(
@@ -259,47 +706,97 @@ impl BridgeRequestHandler {
                 .channel_inflight
                 .with_label_values(&["server_governance_action_signing_queue"]),
         );
-        let signer = Arc::new(signer);
-
         SignerWithCache::new(
             signer.clone(),
-            SuiActionVerifier { sui_client },
+            SuiActionVerifier {
+                sui_client,
+                nonce_guard: nonce_guard.clone(),
+            },
             metrics.clone(),
         )
+        .with_verification_queue(VerificationQueue::new(
+            PipelineChain::Sui,
+            verification_limits.max_concurrent_sui_verifications,
+            verification_limits.queue_timeout,
+        ))
+        .with_signed_set(signed_set.clone())
         .spawn(sui_rx);
         SignerWithCache::new(
             signer.clone(),
-            EthActionVerifier { eth_client },
+            EthActionVerifier::new(
+                eth_client,
+                extra_eth_confirmations,
+                nonce_guard.clone(),
+                metrics.clone(),
+            ),
             metrics.clone(),
         )
+        .with_verification_queue(VerificationQueue::new(
+            PipelineChain::Eth,
+            verification_limits.max_concurrent_eth_verifications,
+            verification_limits.queue_timeout,
+        ))
+        .with_signed_set(signed_set.clone())
         .spawn(eth_rx);
-        SignerWithCache::new(
-            signer.clone(),
-            GovernanceVerifier::new(approved_governance_actions).unwrap(),
-            metrics.clone(),
-        )
-        .spawn(governance_rx);
+        SignerWithCache::new(signer.clone(), governance_verifier.clone(), metrics.clone())
+            .with_signed_set(signed_set.clone())
+            .spawn(governance_rx);
 
         Self {
             sui_signer_tx,
             eth_signer_tx,
             governance_signer_tx,
+            governance_verifier,
+            sign_action_types,
+            signed_set,
+            nonce_guard,
+        }
+    }
+
+    // Refuses a transfer whose claimed nonce is too far ahead before its transaction is
+    // read, the verifiers check the nonce of the event again
+    fn check_claimed_nonce(&self, from_sui: bool, nonce: Option<u64>) -> BridgeResult<()> {
+        match (&self.nonce_guard, nonce) {
+            (Some(nonce_guard), Some(nonce)) => nonce_guard.check_claimed(from_sui, nonce),
+            _ => Ok(()),
+        }
+    }
+
+    fn check_action_type_allowed(&self, action_type: BridgeActionType) -> BridgeResult<()> {
+        if !self.sign_action_types.contains(&action_type) {
+            return Err(BridgeError::ActionTypeNotAllowed(action_type));
+        }
+        Ok(())
+    }
+
+    fn check_governance_action(&self, action: &BridgeAction) -> BridgeResult<()> {
+        if !action.is_governace_action() {
+            return Err(BridgeError::ActionIsNotGovernanceAction(action.clone()));
         }
+        self.check_action_type_allowed(action.action_type())
     }
 }
 
+// Stage timings are only collected for requests served through the router
+fn request_timing() -> RequestTiming {
+    RequestTiming::current().unwrap_or_default()
+}
+
 #[async_trait]
 impl BridgeRequestHandlerTrait for BridgeRequestHandler {
     async fn handle_eth_tx_hash(
         &self,
         tx_hash_hex: String,
         event_idx: u16,
+        nonce: Option<u64>,
     ) -> Result<Json<SignedBridgeAction>, BridgeError> {
+        self.check_action_type_allowed(BridgeActionType::TokenTransfer)?;
         let tx_hash = TxHash::from_str(&tx_hash_hex).map_err(|_| BridgeError::InvalidTxHash)?;
+        self.check_claimed_nonce(false, nonce)?;
 
         let (tx, rx) = oneshot::channel();
         self.eth_signer_tx
-            .send(((tx_hash, event_idx), tx))
+            .send(((tx_hash, event_idx), request_timing(), tx))
             .await
             .unwrap_or_else(|_| panic!("Server eth signing channel is closed"));
         let signed_action = rx
@@ -312,12 +809,15 @@ impl BridgeRequestHandlerTrait for BridgeRequestHandler {
         &self,
         tx_digest_base58: String,
         event_idx: u16,
+        nonce: Option<u64>,
     ) -> Result<Json<SignedBridgeAction>, BridgeError> {
+        self.check_action_type_allowed(BridgeActionType::TokenTransfer)?;
         let tx_digest = TransactionDigest::from_str(&tx_digest_base58)
             .map_err(|_e| BridgeError::InvalidTxHash)?;
+        self.check_claimed_nonce(true, nonce)?;
         let (tx, rx) = oneshot::channel();
         self.sui_signer_tx
-            .send(((tx_digest, event_idx), tx))
+            .send(((tx_digest, event_idx), request_timing(), tx))
             .await
             .unwrap_or_else(|_| panic!("Server sui signing channel is closed"));
         let signed_action = rx
@@ -330,12 +830,10 @@ impl BridgeRequestHandlerTrait for BridgeRequestHandler {
         &self,
         action: BridgeAction,
     ) -> Result<Json<SignedBridgeAction>, BridgeError> {
-        if !action.is_governace_action() {
-            return Err(BridgeError::ActionIsNotGovernanceAction(action));
-        }
+        self.check_governance_action(&action)?;
         let (tx, rx) = oneshot::channel();
         self.governance_signer_tx
-            .send((action, tx))
+            .send((action, request_timing(), tx))
             .await
             .unwrap_or_else(|_| panic!("Server governance action signing channel is closed"));
         let signed_action = rx.await.unwrap_or_else(|_| {
@@ -343,23 +841,58 @@ impl BridgeRequestHandlerTrait for BridgeRequestHandler {
         })?;
         Ok(Json(signed_action))
     }
+
+    async fn dry_run_governance_action(
+        &self,
+        action: BridgeAction,
+    ) -> Result<Json<GovernanceDryRun>, BridgeError> {
+        let result = match self.check_governance_action(&action) {
+            Ok(()) => self.governance_verifier.verify(action).await.map(|_| ()),
+            Err(err) => Err(err),
+        };
+        Ok(Json(GovernanceDryRun::from_verification(result)))
+    }
+    async fn signed_set_digest(&self) -> Result<Json<SignedSetDigest>, BridgeError> {
+        let signed_set = self.signed_set.as_ref().ok_or_else(|| {
+            BridgeError::Generic("The signed set is only kept by nodes that run the client".into())
+        })?;
+        Ok(Json(signed_set.digest()))
+    }
+
+    async fn approved_governance(&self) -> Result<Json<ApprovedGovernance>, BridgeError> {
+        Ok(Json(ApprovedGovernance {
+            actions: self.governance_verifier.approved_action_digests(),
+            digests: self.governance_verifier.approved_digests(),
+        }))
+    }
 }
 
 #[cfg(test)]
 mod tests {
-    use std::collections::HashSet;
-
     use super::*;
     use crate::{
+        api_types::{encode_action_digest, NodeHealth, PipelineStatus, PipelinesHealth},
+        clock_drift::ClockDriftSettings,
+        crypto::{BridgeAuthorityKeyPair, BridgeAuthoritySignInfo},
         eth_mock_provider::EthMockProvider,
         events::{init_all_struct_tags, MoveTokenDepositedEvent, SuiToEthTokenBridgeV1},
+        server::{
+            make_router, BridgeNodePublicMetadata, ADMIN_APPROVED_GOVERNANCE_PATH,
+            DEFAULT_GOVERNANCE_BODY_LIMIT, HEALTH_PATH, SUI_TO_ETH_TX_PATH,
+        },
+        signer::{RemoteSigner, RemoteSignerSettings},
         sui_mock_client::SuiMockClient,
         test_utils::{
             get_test_log_and_action, get_test_sui_to_eth_bridge_action, mock_last_finalized_block,
+            RemoteSignerStub,
         },
         types::{EmergencyAction, EmergencyActionType, LimitUpdateAction},
     };
-    use ethers::types::{Address as EthAddress, TransactionReceipt};
+    use axum::http::StatusCode;
+    use ethers::types::{Address as EthAddress, Block, H256, U64};
+    use fastcrypto::traits::KeyPair;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use sui_config::local_ip_utils::get_available_port;
     use sui_json_rpc_types::SuiEvent;
     use sui_types::bridge::{BridgeChainId, TOKEN_ID_USDC};
     use sui_types::{base_types::SuiAddress, crypto::get_key_pair};
@@ -371,9 +904,10 @@ mod tests {
         let sui_client_mock = SuiMockClient::default();
         let sui_verifier = SuiActionVerifier {
             sui_client: Arc::new(SuiClient::new_for_testing(sui_client_mock.clone())),
+            nonce_guard: None,
         };
-        let metrics = Arc::new(BridgeMetrics::new_for_testing());
-        let mut sui_signer_with_cache = SignerWithCache::new(signer.clone(), sui_verifier, metrics);
+        let metrics = Arc::new(BridgeMetrics::for_testing());
+        let sui_signer_with_cache = SignerWithCache::new(signer.clone(), sui_verifier, metrics);
 
         // Test `get_cache_entry` creates a new entry if not exist
         let sui_tx_digest = TransactionDigest::random();
@@ -414,7 +948,7 @@ mod tests {
         // Mock an non-cacheable error such as rpc error
         sui_client_mock.add_events_by_tx_digest_error(sui_tx_digest);
         sui_signer_with_cache
-            .sign((sui_tx_digest, sui_event_idx))
+            .sign((sui_tx_digest, sui_event_idx), &RequestTiming::default())
             .await
             .unwrap_err();
         let entry_ = sui_signer_with_cache
@@ -426,7 +960,7 @@ mod tests {
         sui_client_mock.add_events_by_tx_digest(sui_tx_digest, vec![]);
         assert!(matches!(
             sui_signer_with_cache
-                .sign((sui_tx_digest, sui_event_idx))
+                .sign((sui_tx_digest, sui_event_idx), &RequestTiming::default())
                 .await,
             Err(BridgeError::NoBridgeEventsInTxPosition)
         ));
@@ -470,11 +1004,11 @@ mod tests {
             vec![sui_event_1.clone(), sui_event_2.clone()],
         );
         let signed_1 = sui_signer_with_cache
-            .sign((sui_tx_digest, sui_event_idx))
+            .sign((sui_tx_digest, sui_event_idx), &RequestTiming::default())
             .await
             .unwrap();
         let signed_2 = sui_signer_with_cache
-            .sign((sui_tx_digest, sui_event_idx_2))
+            .sign((sui_tx_digest, sui_event_idx_2), &RequestTiming::default())
             .await
             .unwrap();
 
@@ -483,14 +1017,14 @@ mod tests {
         sui_client_mock.add_events_by_tx_digest(sui_tx_digest, vec![]);
         assert_eq!(
             sui_signer_with_cache
-                .sign((sui_tx_digest, sui_event_idx))
+                .sign((sui_tx_digest, sui_event_idx), &RequestTiming::default())
                 .await
                 .unwrap(),
             signed_1
         );
         assert_eq!(
             sui_signer_with_cache
-                .sign((sui_tx_digest, sui_event_idx_2))
+                .sign((sui_tx_digest, sui_event_idx_2), &RequestTiming::default())
                 .await
                 .unwrap(),
             signed_2
@@ -507,11 +1041,9 @@ mod tests {
             eth_mock_provider.clone(),
             HashSet::from_iter(vec![contract_address]),
         );
-        let eth_verifier = EthActionVerifier {
-            eth_client: Arc::new(eth_client),
-        };
-        let metrics = Arc::new(BridgeMetrics::new_for_testing());
-        let mut eth_signer_with_cache =
+        let metrics = Arc::new(BridgeMetrics::for_testing());
+        let eth_verifier = EthActionVerifier::new(Arc::new(eth_client), 0, None, metrics.clone());
+        let eth_signer_with_cache =
             SignerWithCache::new(signer.clone(), eth_verifier, metrics.clone());
 
         // Test `get_cache_entry` creates a new entry if not exist
@@ -560,7 +1092,7 @@ mod tests {
         mock_last_finalized_block(&eth_mock_provider, log.block_number.unwrap().as_u64());
 
         eth_signer_with_cache
-            .sign((eth_tx_hash, eth_event_idx))
+            .sign((eth_tx_hash, eth_event_idx), &RequestTiming::default())
             .await
             .unwrap();
         let entry_ = eth_signer_with_cache
@@ -570,59 +1102,301 @@ mod tests {
     }
 
     #[tokio::test]
-    async fn test_signer_with_governace_verifier() {
-        let action_1 = BridgeAction::EmergencyAction(EmergencyAction {
-            chain_id: BridgeChainId::EthCustom,
-            nonce: 1,
-            action_type: EmergencyActionType::Pause,
-        });
-        let action_2 = BridgeAction::LimitUpdateAction(LimitUpdateAction {
-            chain_id: BridgeChainId::EthCustom,
-            sending_chain_id: BridgeChainId::SuiCustom,
-            nonce: 1,
-            new_usd_limit: 10000,
-        });
-
-        let verifier = GovernanceVerifier::new(vec![action_1.clone(), action_2.clone()]).unwrap();
-        assert_eq!(
-            verifier.verify(action_1.clone()).await.unwrap(),
-            action_1.clone()
+    async fn test_eth_receipt_fetched_once_across_finality() {
+        let (_, kp): (_, BridgeAuthorityKeyPair) = get_key_pair();
+        let eth_mock_provider = EthMockProvider::default();
+        let contract_address = EthAddress::random();
+        let eth_client = EthClient::new_mocked(
+            eth_mock_provider.clone(),
+            HashSet::from_iter(vec![contract_address]),
         );
+        let metrics = Arc::new(BridgeMetrics::for_testing());
+        let eth_signer_with_cache = SignerWithCache::new(
+            Arc::new(kp),
+            EthActionVerifier::new(Arc::new(eth_client), 0, None, metrics.clone()),
+            metrics.clone(),
+        );
+        let mock_receipt = |log: &ethers::types::Log, block_number: u64, block_hash: H256| {
+            eth_mock_provider
+                .add_response::<[TxHash; 1], TransactionReceipt, TransactionReceipt>(
+                    "eth_getTransactionReceipt",
+                    [log.transaction_hash.unwrap()],
+                    TransactionReceipt {
+                        block_number: Some(block_number.into()),
+                        block_hash: Some(block_hash),
+                        logs: vec![log.clone()],
+                        ..Default::default()
+                    },
+                )
+                .unwrap();
+        };
+        let mock_block_hash = |block_number: u64, block_hash: H256| {
+            eth_mock_provider
+                .add_response(
+                    "eth_getBlockByNumber",
+                    (U64::from(block_number), false),
+                    Block::<TxHash> {
+                        number: Some(block_number.into()),
+                        hash: Some(block_hash),
+                        ..Default::default()
+                    },
+                )
+                .unwrap();
+        };
+
+        // Requested before the deposit is final, and again once it is
+        let eth_tx_hash = TxHash::random();
+        let (log, action) = get_test_log_and_action(contract_address, eth_tx_hash, 0);
+        let block_number = log.block_number.unwrap().as_u64();
+        let block_hash = H256::random();
+        mock_receipt(&log, block_number, block_hash);
+        mock_block_hash(block_number, block_hash);
+        mock_last_finalized_block(&eth_mock_provider, block_number - 1);
+        for _ in 0..2 {
+            assert_eq!(
+                eth_signer_with_cache
+                    .sign((eth_tx_hash, 0), &RequestTiming::default())
+                    .await
+                    .unwrap_err(),
+                BridgeError::TxNotFinalized
+            );
+        }
+        mock_last_finalized_block(&eth_mock_provider, block_number);
+        let signed = eth_signer_with_cache
+            .sign((eth_tx_hash, 0), &RequestTiming::default())
+            .await
+            .unwrap();
+        assert_eq!(signed.data(), &action);
+        assert_eq!(eth_mock_provider.requests("eth_getTransactionReceipt"), 1);
+        assert_eq!(metrics.unfinalized_receipt_cache_hit.get(), 2);
+
+        // The block of the deposit is reorged out before it is final, and the deposit is
+        // included in the next block
+        let eth_tx_hash = TxHash::random();
+        let (log, action) = get_test_log_and_action(contract_address, eth_tx_hash, 0);
+        let block_number = log.block_number.unwrap().as_u64();
+        mock_receipt(&log, block_number, H256::random());
+        mock_last_finalized_block(&eth_mock_provider, block_number - 1);
         assert_eq!(
-            verifier.verify(action_2.clone()).await.unwrap(),
-            action_2.clone()
+            eth_signer_with_cache
+                .sign((eth_tx_hash, 0), &RequestTiming::default())
+                .await
+                .unwrap_err(),
+            BridgeError::TxNotFinalized
         );
+        let block_hash = H256::random();
+        mock_receipt(&log, block_number + 1, block_hash);
+        mock_block_hash(block_number, H256::random());
+        mock_block_hash(block_number + 1, block_hash);
+        mock_last_finalized_block(&eth_mock_provider, block_number + 1);
+        let signed = eth_signer_with_cache
+            .sign((eth_tx_hash, 0), &RequestTiming::default())
+            .await
+            .unwrap();
+        assert_eq!(signed.data(), &action);
+        // The receipt was fetched again
+        assert_eq!(eth_mock_provider.requests("eth_getTransactionReceipt"), 3);
+        assert_eq!(metrics.unfinalized_receipt_cache_reorgs.get(), 1);
+        assert_eq!(metrics.unfinalized_receipt_cache_hit.get(), 2);
+    }
 
+    #[tokio::test]
+    async fn test_eth_transfers_far_ahead_are_refused_before_finality() {
         let (_, kp): (_, BridgeAuthorityKeyPair) = get_key_pair();
-        let signer = Arc::new(kp);
-        let metrics = Arc::new(BridgeMetrics::new_for_testing());
-        let mut signer_with_cache = SignerWithCache::new(signer.clone(), verifier, metrics.clone());
+        let eth_mock_provider = EthMockProvider::default();
+        let contract_address = EthAddress::random();
+        let eth_client = EthClient::new_mocked(
+            eth_mock_provider.clone(),
+            HashSet::from_iter(vec![contract_address]),
+        );
+        let metrics = Arc::new(BridgeMetrics::for_testing());
+        let nonce_guard = Arc::new(NonceGuard::new(10, metrics.clone()));
+        let eth_signer_with_cache = SignerWithCache::new(
+            Arc::new(kp),
+            EthActionVerifier::new(
+                Arc::new(eth_client),
+                0,
+                Some(nonce_guard.clone()),
+                metrics.clone(),
+            ),
+            metrics.clone(),
+        );
+        let eth_tx_hash = TxHash::random();
+        let (log, action) = get_test_log_and_action(contract_address, eth_tx_hash, 0);
+        let block_number = log.block_number.unwrap().as_u64();
+        eth_mock_provider
+            .add_response::<[TxHash; 1], TransactionReceipt, TransactionReceipt>(
+                "eth_getTransactionReceipt",
+                [eth_tx_hash],
+                TransactionReceipt {
+                    block_number: Some(block_number.into()),
+                    logs: vec![log.clone()],
+                    ..Default::default()
+                },
+            )
+            .unwrap();
+        mock_last_finalized_block(&eth_mock_provider, block_number);
 
-        // action_1 is signable
-        signer_with_cache.sign(action_1.clone()).await.unwrap();
-        // signed action is cached
-        let entry_ = signer_with_cache.get_testing_only(action_1.clone()).await;
+        // The deposit is 10 ahead of the next nonce of its chain
+        let nonce = action.seq_number();
+        nonce_guard.observe_next_nonce(action.chain_id(), nonce - 10);
+        for _ in 0..2 {
+            assert!(matches!(
+                eth_signer_with_cache
+                    .sign((eth_tx_hash, 0), &RequestTiming::default())
+                    .await,
+                Err(BridgeError::NonceTooFarAhead(_))
+            ));
+        }
+        // Refused before its finality is queried, and not cached
+        assert_eq!(eth_mock_provider.requests("eth_getBlockByNumber"), 0);
+        assert_eq!(eth_mock_provider.requests("eth_getTransactionReceipt"), 2);
         assert_eq!(
-            entry_
-                .unwrap()
-                .lock()
-                .await
-                .clone()
-                .unwrap()
-                .unwrap()
-                .data(),
-            &action_1
+            metrics
+                .signing_nonce_too_far_ahead
+                .with_label_values(&[&format!("{:?}", action.chain_id())])
+                .get(),
+            2
         );
 
-        // alter action_1 to action_3
-        let action_3 = BridgeAction::EmergencyAction(EmergencyAction {
-            chain_id: BridgeChainId::EthCustom,
-            nonce: 1,
-            action_type: EmergencyActionType::Unpause,
+        // Within the margin once the known nonces catch up, the deposit is verified
+        nonce_guard.observe_next_nonce(action.chain_id(), nonce - 9);
+        let signed = eth_signer_with_cache
+            .sign((eth_tx_hash, 0), &RequestTiming::default())
+            .await
+            .unwrap();
+        assert_eq!(signed.data(), &action);
+        assert_eq!(eth_mock_provider.requests("eth_getBlockByNumber"), 1);
+        assert_eq!(nonce_guard.next_nonce(action.chain_id()), Some(nonce + 1));
+    }
+
+    #[tokio::test]
+    async fn test_claimed_nonces_far_ahead_are_refused_before_verification() {
+        telemetry_subscribers::init_for_testing();
+        let registry = prometheus::Registry::new();
+        mysten_metrics::init_metrics(&registry);
+        init_all_struct_tags();
+        let (_, kp): (_, BridgeAuthorityKeyPair) = get_key_pair();
+        let sui_client_mock = SuiMockClient::default();
+        let eth_client = EthClient::new_mocked(EthMockProvider::default(), HashSet::new());
+        let metrics = Arc::new(BridgeMetrics::for_testing());
+        let nonce_guard = Arc::new(NonceGuard::new(10, metrics.clone()));
+        nonce_guard.observe_next_nonce(BridgeChainId::SuiCustom, 1);
+        let handler = BridgeRequestHandler::new(
+            Arc::new(kp),
+            Arc::new(SuiClient::new_for_testing(sui_client_mock.clone())),
+            Arc::new(eth_client),
+            0,
+            GovernanceVerifier::default(),
+            HashSet::from(BridgeActionType::ALL),
+            VerificationLimits::default(),
+            None,
+            Some(nonce_guard),
+            metrics.clone(),
+        );
+
+        // The mock panics if the events of the transaction are read
+        let sui_tx_digest = TransactionDigest::random();
+        let err = handler
+            .handle_sui_tx_digest(sui_tx_digest.to_string(), 0, Some(11))
+            .await
+            .unwrap_err();
+        assert!(matches!(err, BridgeError::NonceTooFarAhead(_)));
+        assert!(!err.is_retryable());
+        assert_eq!(
+            axum::response::IntoResponse::into_response(err).status(),
+            StatusCode::UNPROCESSABLE_ENTITY
+        );
+        assert_eq!(
+            metrics
+                .signing_nonce_too_far_ahead
+                .with_label_values(&[&format!("{:?}", BridgeChainId::SuiCustom)])
+                .get(),
+            1
+        );
+
+        // Within the margin, the transfer is verified and signed
+        let emitted_event = MoveTokenDepositedEvent {
+            seq_num: 10,
+            source_chain: BridgeChainId::SuiCustom as u8,
+            sender_address: SuiAddress::random_for_testing_only().to_vec(),
+            target_chain: BridgeChainId::EthCustom as u8,
+            target_address: EthAddress::random().as_bytes().to_vec(),
+            token_type: TOKEN_ID_USDC,
+            amount_sui_adjusted: 12345,
+        };
+        let mut sui_event = SuiEvent::random_for_testing();
+        sui_event.type_ = SuiToEthTokenBridgeV1.get().unwrap().clone();
+        sui_event.bcs = bcs::to_bytes(&emitted_event).unwrap();
+        sui_client_mock.add_events_by_tx_digest(sui_tx_digest, vec![sui_event]);
+        let signed = handler
+            .handle_sui_tx_digest(sui_tx_digest.to_string(), 0, Some(10))
+            .await
+            .unwrap();
+        assert_eq!(signed.data().seq_number(), 10);
+    }
+
+    #[tokio::test]
+    async fn test_signer_with_governace_verifier() {
+        let action_1 = BridgeAction::EmergencyAction(EmergencyAction {
+            chain_id: BridgeChainId::EthCustom,
+            nonce: 1,
+            action_type: EmergencyActionType::Pause,
+        });
+        let action_2 = BridgeAction::LimitUpdateAction(LimitUpdateAction {
+            chain_id: BridgeChainId::EthCustom,
+            sending_chain_id: BridgeChainId::SuiCustom,
+            nonce: 1,
+            new_usd_limit: 10000,
+        });
+
+        let verifier =
+            GovernanceVerifier::new(vec![action_1.clone(), action_2.clone()], vec![]).unwrap();
+        assert_eq!(
+            verifier.verify(action_1.clone()).await.unwrap(),
+            action_1.clone()
+        );
+        assert_eq!(
+            verifier.verify(action_2.clone()).await.unwrap(),
+            action_2.clone()
+        );
+
+        let (_, kp): (_, BridgeAuthorityKeyPair) = get_key_pair();
+        let signer = Arc::new(kp);
+        let metrics = Arc::new(BridgeMetrics::for_testing());
+        let signer_with_cache = SignerWithCache::new(signer.clone(), verifier, metrics.clone());
+
+        // action_1 is signable
+        signer_with_cache
+            .sign(action_1.clone(), &RequestTiming::default())
+            .await
+            .unwrap();
+        // signed action is cached
+        let entry_ = signer_with_cache.get_testing_only(action_1.clone()).await;
+        assert_eq!(
+            entry_
+                .unwrap()
+                .lock()
+                .await
+                .clone()
+                .unwrap()
+                .unwrap()
+                .data(),
+            &action_1
+        );
+
+        // alter action_1 to action_3
+        let action_3 = BridgeAction::EmergencyAction(EmergencyAction {
+            chain_id: BridgeChainId::EthCustom,
+            nonce: 1,
+            action_type: EmergencyActionType::Unpause,
         });
         // action_3 is not signable
         assert!(matches!(
-            signer_with_cache.sign(action_3.clone()).await.unwrap_err(),
+            signer_with_cache
+                .sign(action_3.clone(), &RequestTiming::default())
+                .await
+                .unwrap_err(),
             BridgeError::GovernanceActionIsNotApproved { .. }
         ));
         // error is cached
@@ -635,7 +1409,10 @@ mod tests {
         // Non governace action is not signable
         let action_4 = get_test_sui_to_eth_bridge_action(None, None, None, None, None, None, None);
         assert!(matches!(
-            signer_with_cache.sign(action_4.clone()).await.unwrap_err(),
+            signer_with_cache
+                .sign(action_4.clone(), &RequestTiming::default())
+                .await
+                .unwrap_err(),
             BridgeError::ActionIsNotGovernanceAction(..)
         ));
         // error is cached
@@ -645,5 +1422,829 @@ mod tests {
             BridgeError::ActionIsNotGovernanceAction { .. }
         ));
     }
+
+    #[tokio::test]
+    async fn test_signer_errors_are_not_cached() {
+        let action = BridgeAction::EmergencyAction(EmergencyAction {
+            chain_id: BridgeChainId::EthCustom,
+            nonce: 1,
+            action_type: EmergencyActionType::Pause,
+        });
+        let verifier = GovernanceVerifier::new(vec![action.clone()], vec![]).unwrap();
+        let (_, kp): (_, BridgeAuthorityKeyPair) = get_key_pair();
+        let (_, other_kp): (_, BridgeAuthorityKeyPair) = get_key_pair();
+        // The remote signer signs with another key than the authority key
+        let stub = RemoteSignerStub::start(other_kp).await;
+        let metrics = Arc::new(BridgeMetrics::for_testing());
+        let signer = RemoteSigner::new(
+            stub.url(),
+            reqwest::Client::new(),
+            kp.public().clone(),
+            RemoteSignerSettings::default(),
+            metrics.clone(),
+        );
+        let signer_with_cache = SignerWithCache::new(Arc::new(signer), verifier, metrics);
+
+        for requests in 1..=2 {
+            assert!(matches!(
+                signer_with_cache
+                    .sign(action.clone(), &RequestTiming::default())
+                    .await
+                    .unwrap_err(),
+                BridgeError::InvalidBridgeAuthoritySignature(_)
+            ));
+            // The signer is reached again
+            assert_eq!(stub.requests(), requests);
+        }
+        let entry = signer_with_cache.get_testing_only(action).await;
+        assert!(entry.unwrap().lock().await.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_handler_with_extra_eth_confirmations() {
+        telemetry_subscribers::init_for_testing();
+        let registry = prometheus::Registry::new();
+        mysten_metrics::init_metrics(&registry);
+        init_all_struct_tags();
+        let (_, kp): (_, BridgeAuthorityKeyPair) = get_key_pair();
+        let sui_client_mock = SuiMockClient::default();
+        let eth_mock_provider = EthMockProvider::default();
+        let contract_address = EthAddress::random();
+        let eth_client = EthClient::new_mocked(
+            eth_mock_provider.clone(),
+            HashSet::from_iter(vec![contract_address]),
+        );
+        let extra_eth_confirmations = 5;
+        let handler = BridgeRequestHandler::new(
+            Arc::new(kp),
+            Arc::new(SuiClient::new_for_testing(sui_client_mock.clone())),
+            Arc::new(eth_client),
+            extra_eth_confirmations,
+            GovernanceVerifier::default(),
+            HashSet::from(BridgeActionType::ALL),
+            VerificationLimits::default(),
+            None,
+            None,
+            Arc::new(BridgeMetrics::for_testing()),
+        );
+
+        let eth_tx_hash = TxHash::random();
+        let eth_event_idx = 0;
+        let (log, action) = get_test_log_and_action(contract_address, eth_tx_hash, eth_event_idx);
+        eth_mock_provider
+            .add_response::<[TxHash; 1], TransactionReceipt, TransactionReceipt>(
+                "eth_getTransactionReceipt",
+                [log.transaction_hash.unwrap()],
+                TransactionReceipt {
+                    block_number: log.block_number,
+                    logs: vec![log.clone()],
+                    ..Default::default()
+                },
+            )
+            .unwrap();
+        let deposit_block = log.block_number.unwrap().as_u64();
+
+        // Finalized, but just inside the margin
+        mock_last_finalized_block(
+            &eth_mock_provider,
+            deposit_block + extra_eth_confirmations - 1,
+        );
+        assert_eq!(
+            handler
+                .handle_eth_tx_hash(format!("{:?}", eth_tx_hash), eth_event_idx, None)
+                .await
+                .unwrap_err(),
+            BridgeError::TxNotFinalized
+        );
+
+        // Just outside the margin, the error was not cached so it's signed now
+        mock_last_finalized_block(&eth_mock_provider, deposit_block + extra_eth_confirmations);
+        let signed = handler
+            .handle_eth_tx_hash(format!("{:?}", eth_tx_hash), eth_event_idx, None)
+            .await
+            .unwrap();
+        assert_eq!(signed.data(), &action);
+
+        // The margin does not apply to Sui side
+        let emitted_event = MoveTokenDepositedEvent {
+            seq_num: 1,
+            source_chain: BridgeChainId::SuiCustom as u8,
+            sender_address: SuiAddress::random_for_testing_only().to_vec(),
+            target_chain: BridgeChainId::EthCustom as u8,
+            target_address: EthAddress::random().as_bytes().to_vec(),
+            token_type: TOKEN_ID_USDC,
+            amount_sui_adjusted: 12345,
+        };
+        let mut sui_event = SuiEvent::random_for_testing();
+        sui_event.type_ = SuiToEthTokenBridgeV1.get().unwrap().clone();
+        sui_event.bcs = bcs::to_bytes(&emitted_event).unwrap();
+        let sui_tx_digest = sui_event.id.tx_digest;
+        sui_client_mock.add_events_by_tx_digest(sui_tx_digest, vec![sui_event]);
+        handler
+            .handle_sui_tx_digest(sui_tx_digest.to_string(), 0, None)
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_slow_requests_are_logged_with_stage_timings() {
+        telemetry_subscribers::init_for_testing();
+        let registry = prometheus::Registry::new();
+        mysten_metrics::init_metrics(&registry);
+        init_all_struct_tags();
+        let (_, kp): (_, BridgeAuthorityKeyPair) = get_key_pair();
+        let sui_client_mock = SuiMockClient::default();
+        let eth_client = EthClient::new_mocked(EthMockProvider::default(), HashSet::new());
+        let metrics = Arc::new(BridgeMetrics::for_testing());
+        let handler = BridgeRequestHandler::new(
+            Arc::new(kp),
+            Arc::new(SuiClient::new_for_testing(sui_client_mock.clone())),
+            Arc::new(eth_client),
+            0,
+            GovernanceVerifier::default(),
+            HashSet::from(BridgeActionType::ALL),
+            VerificationLimits::default(),
+            None,
+            None,
+            metrics.clone(),
+        );
+        let port = get_available_port("127.0.0.1");
+        let listener = tokio::net::TcpListener::bind(format!("127.0.0.1:{}", port))
+            .await
+            .unwrap();
+        let router = make_router(
+            Arc::new(handler),
+            metrics.clone(),
+            Arc::new(BridgeNodePublicMetadata::empty_for_testing()),
+            None,
+            Duration::from_millis(200),
+            DEFAULT_GOVERNANCE_BODY_LIMIT,
+        );
+        tokio::spawn(async move { axum::serve(listener, router).await.unwrap() });
+
+        let emitted_event = MoveTokenDepositedEvent {
+            seq_num: 1,
+            source_chain: BridgeChainId::SuiCustom as u8,
+            sender_address: SuiAddress::random_for_testing_only().to_vec(),
+            target_chain: BridgeChainId::EthCustom as u8,
+            target_address: EthAddress::random().as_bytes().to_vec(),
+            token_type: TOKEN_ID_USDC,
+            amount_sui_adjusted: 12345,
+        };
+        let mut sui_event = SuiEvent::random_for_testing();
+        sui_event.type_ = SuiToEthTokenBridgeV1.get().unwrap().clone();
+        sui_event.bcs = bcs::to_bytes(&emitted_event).unwrap();
+        let sui_tx_digest = sui_event.id.tx_digest;
+        sui_client_mock.add_events_by_tx_digest(sui_tx_digest, vec![sui_event]);
+        // Verification is slower than the threshold
+        sui_client_mock.set_events_by_tx_digest_delay(Duration::from_millis(500));
+
+        let url = format!(
+            "http://127.0.0.1:{}/sign/bridge_tx/sui/eth/{}/0",
+            port, sui_tx_digest
+        );
+        let stage = |stage: RequestStage| {
+            metrics
+                .request_stage_latency
+                .with_label_values(&[stage.as_str()])
+        };
+        let slow_requests = || {
+            metrics
+                .slow_requests
+                .with_label_values(&[SUI_TO_ETH_TX_PATH])
+                .get()
+        };
+
+        let resp = reqwest::get(&url).await.unwrap();
+        assert_eq!(resp.status(), StatusCode::OK);
+        assert_eq!(slow_requests(), 1);
+        assert_eq!(stage(RequestStage::CacheLookup).get_sample_count(), 1);
+        assert_eq!(stage(RequestStage::Verification).get_sample_count(), 1);
+        assert!(stage(RequestStage::Verification).get_sample_sum() >= 0.5);
+        assert_eq!(stage(RequestStage::Signing).get_sample_count(), 1);
+
+        // The signed action is cached, so the request is fast and not logged
+        let resp = reqwest::get(&url).await.unwrap();
+        assert_eq!(resp.status(), StatusCode::OK);
+        assert_eq!(slow_requests(), 1);
+        assert_eq!(stage(RequestStage::CacheLookup).get_sample_count(), 2);
+        assert_eq!(stage(RequestStage::Verification).get_sample_count(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_handler_rejects_disallowed_action_types() {
+        telemetry_subscribers::init_for_testing();
+        let registry = prometheus::Registry::new();
+        mysten_metrics::init_metrics(&registry);
+        let (_, kp): (_, BridgeAuthorityKeyPair) = get_key_pair();
+        let eth_client = EthClient::new_mocked(EthMockProvider::default(), HashSet::new());
+        let action = BridgeAction::EmergencyAction(EmergencyAction {
+            chain_id: BridgeChainId::SuiCustom,
+            nonce: 1,
+            action_type: EmergencyActionType::Pause,
+        });
+        // Transfers only, even though the emergency action is approved
+        let handler = BridgeRequestHandler::new(
+            Arc::new(kp),
+            Arc::new(SuiClient::new_for_testing(SuiMockClient::default())),
+            Arc::new(eth_client),
+            0,
+            GovernanceVerifier::new(vec![action.clone()], vec![]).unwrap(),
+            HashSet::from([BridgeActionType::TokenTransfer]),
+            VerificationLimits::default(),
+            None,
+            None,
+            Arc::new(BridgeMetrics::for_testing()),
+        );
+
+        let dry_run = handler
+            .dry_run_governance_action(action.clone())
+            .await
+            .unwrap();
+        assert!(!dry_run.would_sign);
+        let err = handler.handle_governance_action(action).await.unwrap_err();
+        assert_eq!(
+            err,
+            BridgeError::ActionTypeNotAllowed(BridgeActionType::EmergencyButton)
+        );
+        // Clients treat it as a permanent error and don't retry
+        assert_eq!(
+            axum::response::IntoResponse::into_response(err).status(),
+            StatusCode::FORBIDDEN
+        );
+    }
+
+    #[tokio::test]
+    async fn test_governance_dry_run() {
+        telemetry_subscribers::init_for_testing();
+        let registry = prometheus::Registry::new();
+        mysten_metrics::init_metrics(&registry);
+        let (_, kp): (_, BridgeAuthorityKeyPair) = get_key_pair();
+        let eth_client = EthClient::new_mocked(EthMockProvider::default(), HashSet::new());
+        let approved_action = BridgeAction::EmergencyAction(EmergencyAction {
+            chain_id: BridgeChainId::SuiCustom,
+            nonce: 1,
+            action_type: EmergencyActionType::Pause,
+        });
+        let metrics = Arc::new(BridgeMetrics::for_testing());
+        let handler = BridgeRequestHandler::new(
+            Arc::new(kp),
+            Arc::new(SuiClient::new_for_testing(SuiMockClient::default())),
+            Arc::new(eth_client),
+            0,
+            GovernanceVerifier::new(vec![approved_action.clone()], vec![]).unwrap(),
+            HashSet::from(BridgeActionType::ALL),
+            VerificationLimits::default(),
+            None,
+            None,
+            metrics.clone(),
+        );
+        let port = get_available_port("127.0.0.1");
+        let listener = tokio::net::TcpListener::bind(format!("127.0.0.1:{}", port))
+            .await
+            .unwrap();
+        let router = make_router(
+            Arc::new(handler),
+            metrics.clone(),
+            Arc::new(BridgeNodePublicMetadata::empty_for_testing()),
+            None,
+            Duration::from_secs(10),
+            DEFAULT_GOVERNANCE_BODY_LIMIT,
+        );
+        tokio::spawn(async move { axum::serve(listener, router).await.unwrap() });
+        let url = |nonce: u64| {
+            format!(
+                "http://127.0.0.1:{}/sign/emergency_button/{}/{}/0",
+                port,
+                BridgeChainId::SuiCustom as u8,
+                nonce
+            )
+        };
+        let stage = |stage: RequestStage| {
+            metrics
+                .request_stage_latency
+                .with_label_values(&[stage.as_str()])
+                .get_sample_count()
+        };
+
+        // Approved, would sign
+        let resp = reqwest::get(format!("{}?dry_run=true", url(1)))
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), StatusCode::OK);
+        let dry_run: GovernanceDryRun = resp.json().await.unwrap();
+        assert_eq!(
+            dry_run,
+            GovernanceDryRun {
+                would_sign: true,
+                reason: None
+            }
+        );
+
+        // Not in the allowlist, would not sign
+        let dry_run: GovernanceDryRun = reqwest::get(format!("{}?dry_run=true", url(2)))
+            .await
+            .unwrap()
+            .json()
+            .await
+            .unwrap();
+        assert!(!dry_run.would_sign);
+        assert_eq!(
+            dry_run.reason.as_deref(),
+            Some("Governance action is not approved")
+        );
+
+        // Dry runs are neither signed nor cached
+        assert_eq!(stage(RequestStage::CacheLookup), 0);
+        assert_eq!(stage(RequestStage::Signing), 0);
+
+        // Requests without the flag, e.g. from legacy clients, are signed as before
+        let signed: SignedBridgeAction = reqwest::get(url(1)).await.unwrap().json().await.unwrap();
+        assert_eq!(signed.data(), &approved_action);
+        assert_eq!(stage(RequestStage::Signing), 1);
+        let resp = reqwest::get(format!("{}?dry_run=false", url(2)))
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), StatusCode::UNPROCESSABLE_ENTITY);
+
+        let approved: ApprovedGovernance = reqwest::get(format!(
+            "http://127.0.0.1:{}{}",
+            port, ADMIN_APPROVED_GOVERNANCE_PATH
+        ))
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+        assert_eq!(
+            approved,
+            ApprovedGovernance {
+                actions: vec![encode_action_digest(&approved_action.digest())],
+                digests: vec![],
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn test_sui_signing_continues_while_eth_is_down() {
+        telemetry_subscribers::init_for_testing();
+        let registry = prometheus::Registry::new();
+        mysten_metrics::init_metrics(&registry);
+        init_all_struct_tags();
+        let (_, kp): (_, BridgeAuthorityKeyPair) = get_key_pair();
+        let sui_client_mock = SuiMockClient::default();
+        let eth_mock_provider = EthMockProvider::default();
+        let contract_address = EthAddress::random();
+        let eth_client = EthClient::new_mocked(
+            eth_mock_provider.clone(),
+            HashSet::from_iter(vec![contract_address]),
+        );
+        let metrics = Arc::new(BridgeMetrics::for_testing());
+        let handler = BridgeRequestHandler::new(
+            Arc::new(kp),
+            Arc::new(SuiClient::new_for_testing(sui_client_mock.clone())),
+            Arc::new(eth_client),
+            0,
+            GovernanceVerifier::default(),
+            HashSet::from(BridgeActionType::ALL),
+            VerificationLimits::default(),
+            None,
+            None,
+            metrics.clone(),
+        );
+        let port = get_available_port("127.0.0.1");
+        let listener = tokio::net::TcpListener::bind(format!("127.0.0.1:{}", port))
+            .await
+            .unwrap();
+        let router = make_router(
+            Arc::new(handler),
+            metrics.clone(),
+            Arc::new(BridgeNodePublicMetadata::empty_for_testing()),
+            None,
+            Duration::from_secs(10),
+            DEFAULT_GOVERNANCE_BODY_LIMIT,
+        );
+        tokio::spawn(async move { axum::serve(listener, router).await.unwrap() });
+        let server_url = format!("http://127.0.0.1:{}", port);
+        async fn health(server_url: &str) -> PipelinesHealth {
+            reqwest::get(format!("{}{}", server_url, HEALTH_PATH))
+                .await
+                .unwrap()
+                .json::<NodeHealth>()
+                .await
+                .unwrap()
+                .pipelines
+                .unwrap()
+        }
+        let sign_sui_transfer = |seq_num: u64| {
+            let emitted_event = MoveTokenDepositedEvent {
+                seq_num,
+                source_chain: BridgeChainId::SuiCustom as u8,
+                sender_address: SuiAddress::random_for_testing_only().to_vec(),
+                target_chain: BridgeChainId::EthCustom as u8,
+                target_address: EthAddress::random().as_bytes().to_vec(),
+                token_type: TOKEN_ID_USDC,
+                amount_sui_adjusted: 12345,
+            };
+            let mut sui_event = SuiEvent::random_for_testing();
+            sui_event.type_ = SuiToEthTokenBridgeV1.get().unwrap().clone();
+            sui_event.bcs = bcs::to_bytes(&emitted_event).unwrap();
+            let sui_tx_digest = sui_event.id.tx_digest;
+            sui_client_mock.add_events_by_tx_digest(sui_tx_digest, vec![sui_event]);
+            reqwest::get(format!(
+                "{}/sign/bridge_tx/sui/eth/{}/0",
+                server_url, sui_tx_digest
+            ))
+        };
+        let sign_eth_transfer = |eth_tx_hash: TxHash| {
+            reqwest::get(format!(
+                "{}/sign/bridge_tx/eth/sui/{:?}/0",
+                server_url, eth_tx_hash
+            ))
+        };
+
+        // Both chains are up
+        let eth_tx_hash = TxHash::random();
+        let (log, _) = get_test_log_and_action(contract_address, eth_tx_hash, 0);
+        eth_mock_provider
+            .add_response::<[TxHash; 1], TransactionReceipt, TransactionReceipt>(
+                "eth_getTransactionReceipt",
+                [log.transaction_hash.unwrap()],
+                TransactionReceipt {
+                    block_number: log.block_number,
+                    logs: vec![log.clone()],
+                    ..Default::default()
+                },
+            )
+            .unwrap();
+        mock_last_finalized_block(&eth_mock_provider, log.block_number.unwrap().as_u64());
+        assert!(sign_eth_transfer(eth_tx_hash)
+            .await
+            .unwrap()
+            .status()
+            .is_success());
+        assert!(sign_sui_transfer(1).await.unwrap().status().is_success());
+        let pipelines = health(&server_url).await;
+        assert_eq!(pipelines.status, PipelineStatus::Healthy);
+
+        // The Eth provider goes away mid-run
+        eth_mock_provider.clear_responses();
+        let resp = sign_eth_transfer(TxHash::random()).await.unwrap();
+        assert!(!resp.status().is_success());
+        // Transfers from Sui are still signed
+        assert!(sign_sui_transfer(2).await.unwrap().status().is_success());
+        assert!(sign_sui_transfer(3).await.unwrap().status().is_success());
+
+        // Reported as a partial degradation
+        let pipelines = health(&server_url).await;
+        assert_eq!(pipelines.status, PipelineStatus::Degraded);
+        assert_eq!(pipelines.sui.status, PipelineStatus::Healthy);
+        assert_eq!(pipelines.eth.status, PipelineStatus::Degraded);
+        assert_eq!(pipelines.eth.consecutive_failures, 1);
+        assert!(pipelines.eth.last_error.is_some());
+    }
+
+    #[tokio::test]
+    async fn test_signing_refused_while_clock_is_off() {
+        telemetry_subscribers::init_for_testing();
+        let registry = prometheus::Registry::new();
+        mysten_metrics::init_metrics(&registry);
+        init_all_struct_tags();
+        let (_, kp): (_, BridgeAuthorityKeyPair) = get_key_pair();
+        let sui_client_mock = SuiMockClient::default();
+        let metrics = Arc::new(BridgeMetrics::for_testing());
+        let handler = BridgeRequestHandler::new(
+            Arc::new(kp),
+            Arc::new(SuiClient::new_for_testing(sui_client_mock.clone())),
+            Arc::new(EthClient::new_mocked(
+                EthMockProvider::default(),
+                HashSet::new(),
+            )),
+            0,
+            GovernanceVerifier::default(),
+            HashSet::from(BridgeActionType::ALL),
+            VerificationLimits::default(),
+            None,
+            None,
+            metrics.clone(),
+        );
+        let port = get_available_port("127.0.0.1");
+        let listener = tokio::net::TcpListener::bind(format!("127.0.0.1:{}", port))
+            .await
+            .unwrap();
+        let router = make_router(
+            Arc::new(handler),
+            metrics.clone(),
+            Arc::new(BridgeNodePublicMetadata::empty_for_testing()),
+            None,
+            Duration::from_secs(10),
+            DEFAULT_GOVERNANCE_BODY_LIMIT,
+        );
+        tokio::spawn(async move { axum::serve(listener, router).await.unwrap() });
+        let server_url = format!("http://127.0.0.1:{}", port);
+        let clock_health = || async {
+            reqwest::get(format!("{}{}", server_url, HEALTH_PATH))
+                .await
+                .unwrap()
+                .json::<NodeHealth>()
+                .await
+                .unwrap()
+                .clock
+                .unwrap()
+        };
+        let sign_sui_transfer = |seq_num: u64| {
+            let emitted_event = MoveTokenDepositedEvent {
+                seq_num,
+                source_chain: BridgeChainId::SuiCustom as u8,
+                sender_address: SuiAddress::random_for_testing_only().to_vec(),
+                target_chain: BridgeChainId::EthCustom as u8,
+                target_address: EthAddress::random().as_bytes().to_vec(),
+                token_type: TOKEN_ID_USDC,
+                amount_sui_adjusted: 12345,
+            };
+            let mut sui_event = SuiEvent::random_for_testing();
+            sui_event.type_ = SuiToEthTokenBridgeV1.get().unwrap().clone();
+            sui_event.bcs = bcs::to_bytes(&emitted_event).unwrap();
+            let sui_tx_digest = sui_event.id.tx_digest;
+            sui_client_mock.add_events_by_tx_digest(sui_tx_digest, vec![sui_event]);
+            reqwest::get(format!(
+                "{}/sign/bridge_tx/sui/eth/{}/0",
+                server_url, sui_tx_digest
+            ))
+        };
+        metrics.clock_drift.configure(ClockDriftSettings {
+            max_drift_ms: 60_000,
+            refuse_signing: true,
+        });
+        let now = now_ms();
+
+        // The local clock is 10 minutes behind both chains
+        for chain in [PipelineChain::Sui, PipelineChain::Eth] {
+            metrics
+                .clock_drift
+                .record(chain, now + 600_000, now, &metrics);
+        }
+        let resp = sign_sui_transfer(1).await.unwrap();
+        assert_eq!(resp.status(), StatusCode::SERVICE_UNAVAILABLE);
+        let clock = clock_health().await;
+        assert_eq!(clock.status, PipelineStatus::Degraded);
+        assert_eq!(clock.sui_drift_ms, Some(-600_000));
+        assert!(clock.refusing_signatures);
+
+        // Signing resumes once the clock is back in sync
+        for chain in [PipelineChain::Sui, PipelineChain::Eth] {
+            metrics.clock_drift.record(chain, now, now, &metrics);
+        }
+        assert!(sign_sui_transfer(1).await.unwrap().status().is_success());
+        assert_eq!(clock_health().await.status, PipelineStatus::Healthy);
+    }
+
+    #[derive(Default)]
+    struct SlowVerifierState {
+        inflight: AtomicUsize,
+        max_inflight: AtomicUsize,
+        verified: AtomicUsize,
+    }
+
+    // Verifies any key after `delay`, recording how many verifications run at once
+    struct SlowVerifier {
+        delay: Duration,
+        state: Arc<SlowVerifierState>,
+    }
+
+    #[async_trait::async_trait]
+    impl ActionVerifier<u64> for SlowVerifier {
+        fn name(&self) -> &'static str {
+            "SlowVerifier"
+        }
+
+        fn chain(&self) -> Option<PipelineChain> {
+            Some(PipelineChain::Eth)
+        }
+
+        async fn verify(&self, key: u64) -> BridgeResult<BridgeAction> {
+            let inflight = self.state.inflight.fetch_add(1, Ordering::SeqCst) + 1;
+            self.state
+                .max_inflight
+                .fetch_max(inflight, Ordering::SeqCst);
+            tokio::time::sleep(self.delay).await;
+            self.state.inflight.fetch_sub(1, Ordering::SeqCst);
+            self.state.verified.fetch_add(1, Ordering::SeqCst);
+            Ok(get_test_sui_to_eth_bridge_action(
+                None,
+                None,
+                Some(key),
+                None,
+                None,
+                None,
+                None,
+            ))
+        }
+    }
+
+    async fn wait_for_queue_depth(metrics: &BridgeMetrics, chain: &str, depth: i64) {
+        let queue_depth = metrics.verification_queue_depth.with_label_values(&[chain]);
+        tokio::time::timeout(Duration::from_secs(5), async {
+            while queue_depth.get() != depth {
+                tokio::time::sleep(Duration::from_millis(5)).await;
+            }
+        })
+        .await
+        .unwrap_or_else(|_| panic!("{} requests never waited for a slot", depth));
+    }
+
+    #[tokio::test]
+    async fn test_verification_concurrency_is_bounded() {
+        telemetry_subscribers::init_for_testing();
+        let registry = prometheus::Registry::new();
+        mysten_metrics::init_metrics(&registry);
+        let (_, kp): (_, BridgeAuthorityKeyPair) = get_key_pair();
+        let metrics = Arc::new(BridgeMetrics::for_testing());
+        let state = Arc::new(SlowVerifierState::default());
+        let verifier = SlowVerifier {
+            delay: Duration::from_millis(200),
+            state: state.clone(),
+        };
+        let (tx, rx) = mysten_metrics::metered_channel::channel(
+            100,
+            &mysten_metrics::get_metrics()
+                .unwrap()
+                .channel_inflight
+                .with_label_values(&["unit_test_signing_queue"]),
+        );
+        SignerWithCache::new(Arc::new(kp), verifier, metrics.clone())
+            .with_verification_queue(VerificationQueue::new(
+                PipelineChain::Eth,
+                3,
+                Duration::from_secs(30),
+            ))
+            .spawn(rx);
+        let sign = |key: u64| {
+            let (result_tx, result_rx) = oneshot::channel();
+            let tx = &tx;
+            async move {
+                tx.send((key, RequestTiming::default(), result_tx))
+                    .await
+                    .unwrap();
+                result_rx
+            }
+        };
+
+        // Flooded with uncached requests, 3 are verified at once and the others wait
+        let mut results = vec![];
+        for key in 0..12 {
+            results.push(sign(key).await);
+        }
+        wait_for_queue_depth(&metrics, "eth", 9).await;
+        assert_eq!(
+            metrics
+                .verifications_inflight
+                .with_label_values(&["eth"])
+                .get(),
+            3
+        );
+        for result in results {
+            assert!(result.await.unwrap().is_ok());
+        }
+        assert_eq!(state.verified.load(Ordering::SeqCst), 12);
+        assert_eq!(state.max_inflight.load(Ordering::SeqCst), 3);
+        assert_eq!(
+            metrics
+                .verification_queue_wait
+                .with_label_values(&["eth"])
+                .get_sample_count(),
+            12
+        );
+        assert_eq!(
+            metrics
+                .verification_queue_timeouts
+                .with_label_values(&["eth"])
+                .get(),
+            0
+        );
+
+        // Cache hits don't wait for a slot, even when all are taken
+        let mut results = vec![];
+        for key in 100..106 {
+            results.push(sign(key).await);
+        }
+        wait_for_queue_depth(&metrics, "eth", 3).await;
+        let start = Instant::now();
+        let cached = sign(0).await.await.unwrap();
+        assert!(cached.is_ok());
+        assert!(start.elapsed() < Duration::from_millis(200));
+        assert_eq!(
+            metrics
+                .signer_with_cache_hit
+                .with_label_values(&["SlowVerifier"])
+                .get(),
+            1
+        );
+        for result in results {
+            assert!(result.await.unwrap().is_ok());
+        }
+        assert_eq!(state.max_inflight.load(Ordering::SeqCst), 3);
+    }
+
+    #[tokio::test]
+    async fn test_busy_verifications_are_rejected() {
+        telemetry_subscribers::init_for_testing();
+        let registry = prometheus::Registry::new();
+        mysten_metrics::init_metrics(&registry);
+        init_all_struct_tags();
+        let (_, kp): (_, BridgeAuthorityKeyPair) = get_key_pair();
+        let sui_client_mock = SuiMockClient::default();
+        sui_client_mock.set_events_by_tx_digest_delay(Duration::from_millis(500));
+        let eth_client = EthClient::new_mocked(EthMockProvider::default(), HashSet::new());
+        let metrics = Arc::new(BridgeMetrics::for_testing());
+        let handler = BridgeRequestHandler::new(
+            Arc::new(kp),
+            Arc::new(SuiClient::new_for_testing(sui_client_mock.clone())),
+            Arc::new(eth_client),
+            0,
+            GovernanceVerifier::default(),
+            HashSet::from(BridgeActionType::ALL),
+            VerificationLimits {
+                max_concurrent_eth_verifications: 1,
+                max_concurrent_sui_verifications: 1,
+                queue_timeout: Duration::from_millis(100),
+                max_nonce_lead: DEFAULT_MAX_NONCE_LEAD,
+            },
+            None,
+            None,
+            metrics.clone(),
+        );
+        let port = get_available_port("127.0.0.1");
+        let listener = tokio::net::TcpListener::bind(format!("127.0.0.1:{}", port))
+            .await
+            .unwrap();
+        let router = make_router(
+            Arc::new(handler),
+            metrics.clone(),
+            Arc::new(BridgeNodePublicMetadata::empty_for_testing()),
+            None,
+            Duration::from_secs(10),
+            DEFAULT_GOVERNANCE_BODY_LIMIT,
+        );
+        tokio::spawn(async move { axum::serve(listener, router).await.unwrap() });
+        let sui_tx_digests = (0..2)
+            .map(|seq_num| {
+                let emitted_event = MoveTokenDepositedEvent {
+                    seq_num,
+                    source_chain: BridgeChainId::SuiCustom as u8,
+                    sender_address: SuiAddress::random_for_testing_only().to_vec(),
+                    target_chain: BridgeChainId::EthCustom as u8,
+                    target_address: EthAddress::random().as_bytes().to_vec(),
+                    token_type: TOKEN_ID_USDC,
+                    amount_sui_adjusted: 12345,
+                };
+                let mut sui_event = SuiEvent::random_for_testing();
+                sui_event.type_ = SuiToEthTokenBridgeV1.get().unwrap().clone();
+                sui_event.bcs = bcs::to_bytes(&emitted_event).unwrap();
+                let sui_tx_digest = sui_event.id.tx_digest;
+                sui_client_mock.add_events_by_tx_digest(sui_tx_digest, vec![sui_event]);
+                sui_tx_digest
+            })
+            .collect::<Vec<_>>();
+        let sign_sui_transfer = |sui_tx_digest: TransactionDigest| {
+            reqwest::get(format!(
+                "http://127.0.0.1:{}/sign/bridge_tx/sui/eth/{}/0",
+                port, sui_tx_digest
+            ))
+        };
+
+        // The second request doesn't get the only slot in time
+        let (first, second) = tokio::join!(
+            sign_sui_transfer(sui_tx_digests[0]),
+            sign_sui_transfer(sui_tx_digests[1])
+        );
+        let mut statuses = vec![first.unwrap().status(), second.unwrap().status()];
+        statuses.sort();
+        assert_eq!(
+            statuses,
+            vec![StatusCode::OK, StatusCode::TOO_MANY_REQUESTS]
+        );
+        assert_eq!(
+            metrics
+                .verification_queue_timeouts
+                .with_label_values(&["sui"])
+                .get(),
+            1
+        );
+        assert_eq!(
+            metrics
+                .verification_queue_depth
+                .with_label_values(&["sui"])
+                .get(),
+            0
+        );
+        assert!(BridgeError::ServerBusy(String::new()).is_retryable());
+
+        // Busy responses are not cached, both transfers are signed once retried
+        for sui_tx_digest in &sui_tx_digests {
+            let resp = sign_sui_transfer(*sui_tx_digest).await.unwrap();
+            assert_eq!(resp.status(), StatusCode::OK);
+        }
+    }
     // TODO: add tests for BridgeRequestHandler (need to hook up local eth node)
 }
@@ -0,0 +1,396 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Replay protection of the mutations of the admin api. Requests other than GETs must
+//! carry an `Idempotency-Key` header. The response to the first request of a caller with
+//! a key is stored in the `admin_requests` table, and returned as is to later requests of
+//! the caller with the same key, without doing the mutation again, e.g. when a curl is
+//! retried or a proxy replays the request. Keys expire after `DEFAULT_IDEMPOTENCY_KEY_TTL`.
+//! Every mutation that is done, unlike its replays, is also appended to the audit log in
+//! the `admin_audit` table, which is kept forever.
+
+use crate::server::admin_auth::AdminCaller;
+use crate::server::requester::{AuthenticatedKey, UNKNOWN_REQUESTER};
+use crate::storage::{
+    AdminAuditRecord, AdminRequestOrigin, AdminRequestRecord, BridgeOrchestratorTables,
+};
+use crate::timestamps::now_ms;
+use axum::{
+    body::{to_bytes, Body},
+    extract::{Request, State},
+    http::{header::CONTENT_TYPE, HeaderValue, Method, StatusCode},
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use fastcrypto::hash::{Blake2b256, HashFunction};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::Mutex;
+use tracing::{info, warn};
+
+pub const IDEMPOTENCY_KEY_HEADER: &str = "idempotency-key";
+/// Set on the responses that were returned before, to a request with the same key
+pub const IDEMPOTENT_REPLAYED_HEADER: &str = "idempotent-replayed";
+pub const DEFAULT_IDEMPOTENCY_KEY_TTL: Duration = Duration::from_secs(24 * 60 * 60);
+const MAX_IDEMPOTENCY_KEY_LEN: usize = 128;
+// Admin requests are small, larger bodies are not buffered
+const MAX_ADMIN_BODY_BYTES: usize = 1024 * 1024;
+
+#[derive(Debug)]
+pub struct IdempotencyGuard {
+    store: Arc<BridgeOrchestratorTables>,
+    ttl: Duration,
+    // Mutations are rare, they are processed one at a time so that a replay waits for
+    // the response to the request it replays
+    lock: Mutex<()>,
+}
+
+impl IdempotencyGuard {
+    pub fn new(store: Arc<BridgeOrchestratorTables>, ttl: Duration) -> Self {
+        Self {
+            store,
+            ttl,
+            lock: Mutex::new(()),
+        }
+    }
+}
+
+fn request_digest(path: &str, body: &[u8]) -> [u8; 32] {
+    let mut hasher = Blake2b256::default();
+    hasher.update(path.as_bytes());
+    hasher.update([0u8]);
+    hasher.update(body);
+    hasher.finalize().digest
+}
+
+fn replayed_response(record: AdminRequestRecord) -> Response {
+    let status = StatusCode::from_u16(record.status).unwrap_or(StatusCode::OK);
+    let mut response = (status, record.body).into_response();
+    let headers = response.headers_mut();
+    if let Some(content_type) = record
+        .content_type
+        .and_then(|content_type| HeaderValue::from_str(&content_type).ok())
+    {
+        headers.insert(CONTENT_TYPE, content_type);
+    }
+    headers.insert(IDEMPOTENT_REPLAYED_HEADER, HeaderValue::from_static("true"));
+    response
+}
+
+/// Middleware that returns the stored response to replays of admin mutations, see the
+/// module doc. Runs after the request is authenticated, keys are scoped by caller.
+pub(crate) async fn enforce_idempotency(
+    State(guard): State<Arc<IdempotencyGuard>>,
+    request: Request,
+    next: Next,
+) -> Response {
+    if matches!(*request.method(), Method::GET | Method::HEAD) {
+        return next.run(request).await;
+    }
+    let Some(idempotency_key) = request
+        .headers()
+        .get(IDEMPOTENCY_KEY_HEADER)
+        .and_then(|value| value.to_str().ok())
+        .filter(|value| !value.is_empty() && value.len() <= MAX_IDEMPOTENCY_KEY_LEN)
+        .map(|value| value.to_owned())
+    else {
+        return (
+            StatusCode::BAD_REQUEST,
+            format!(
+                "Admin mutations require an {} header of at most {} chars",
+                IDEMPOTENCY_KEY_HEADER, MAX_IDEMPOTENCY_KEY_LEN
+            ),
+        )
+            .into_response();
+    };
+    // A caller granted a role is known by its name, else by the key that signed the request
+    let caller = match (
+        request.extensions().get::<AdminCaller>(),
+        request.extensions().get::<AuthenticatedKey>(),
+    ) {
+        (Some(admin_caller), _) => format!("admin:{}", admin_caller.name),
+        (None, Some(AuthenticatedKey(key))) => format!("key:{}", key),
+        (None, None) => UNKNOWN_REQUESTER.to_string(),
+    };
+    let (parts, body) = request.into_parts();
+    let body = match to_bytes(body, MAX_ADMIN_BODY_BYTES).await {
+        Ok(body) => body,
+        Err(e) => {
+            return (
+                StatusCode::BAD_REQUEST,
+                format!("Couldn't read request body: {:?}", e),
+            )
+                .into_response()
+        }
+    };
+    let path = parts.uri.path().to_string();
+    let method = parts.method.to_string();
+    let digest = request_digest(&path, &body);
+
+    let _guard = guard.lock.lock().await;
+    let now_ms = now_ms();
+    let expired_before_ms = now_ms.saturating_sub(guard.ttl.as_millis() as u64);
+    match guard.store.get_admin_request(&caller, &idempotency_key) {
+        Ok(Some(record)) if record.processed_at_ms >= expired_before_ms => {
+            if record.request_digest != digest {
+                return (
+                    StatusCode::UNPROCESSABLE_ENTITY,
+                    format!(
+                        "Idempotency key {} was used for another request to {}",
+                        idempotency_key, record.path
+                    ),
+                )
+                    .into_response();
+            }
+            info!(
+                %caller,
+                %idempotency_key,
+                "Returning the stored response to a replayed request to {}",
+                path
+            );
+            return replayed_response(record);
+        }
+        Ok(_) => {}
+        Err(e) => return e.into_response(),
+    }
+
+    let origin = AdminRequestOrigin {
+        caller: caller.clone(),
+        idempotency_key: idempotency_key.clone(),
+    };
+    let mut request = Request::from_parts(parts, Body::from(body));
+    request.extensions_mut().insert(origin);
+    let response = next.run(request).await;
+    let audit = AdminAuditRecord {
+        caller: caller.clone(),
+        method,
+        path: path.clone(),
+        idempotency_key: idempotency_key.clone(),
+        status: response.status().as_u16(),
+        requested_at_ms: now_ms,
+    };
+    if let Err(e) = guard.store.append_admin_audit(&audit) {
+        warn!(
+            %caller,
+            %idempotency_key,
+            "Couldn't append the request to {} to the audit log: {:?}",
+            path,
+            e
+        );
+    }
+    // Server errors may not happen again, the request can be retried with the same key
+    if response.status().is_server_error() {
+        return response;
+    }
+    let (parts, body) = response.into_parts();
+    let body = match to_bytes(body, usize::MAX).await {
+        Ok(body) => body,
+        Err(e) => {
+            warn!("Couldn't read the response to {}: {:?}", path, e);
+            return StatusCode::INTERNAL_SERVER_ERROR.into_response();
+        }
+    };
+    let record = AdminRequestRecord {
+        path,
+        request_digest: digest,
+        status: parts.status.as_u16(),
+        content_type: parts
+            .headers
+            .get(CONTENT_TYPE)
+            .and_then(|value| value.to_str().ok())
+            .map(|value| value.to_owned()),
+        body: body.to_vec(),
+        processed_at_ms: now_ms,
+    };
+    // The mutation is done, it is returned even if it can't be replayed
+    if let Err(e) =
+        guard
+            .store
+            .insert_admin_request(&caller, &idempotency_key, &record, expired_before_ms)
+    {
+        warn!(
+            %caller,
+            %idempotency_key,
+            "Couldn't store the response to {}: {:?}",
+            record.path,
+            e
+        );
+    }
+    Response::from_parts(parts, Body::from(body))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{routing::post, Extension, Router};
+    use std::sync::atomic::{AtomicU64, Ordering};
+    use sui_config::local_ip_utils::get_available_port;
+
+    const PAUSE_PATH: &str = "/admin/pause";
+
+    // Pauses on the first request, and fails on the next ones
+    async fn pause(
+        State(paused): State<Arc<AtomicU64>>,
+        Extension(origin): Extension<AdminRequestOrigin>,
+        body: String,
+    ) -> Response {
+        match paused.fetch_add(1, Ordering::SeqCst) {
+            0 => format!("paused by {} ({})", origin.caller, body).into_response(),
+            _ => (StatusCode::CONFLICT, "already paused").into_response(),
+        }
+    }
+
+    async fn serve(guard: Arc<IdempotencyGuard>, paused: Arc<AtomicU64>) -> String {
+        let router = Router::new()
+            .route(PAUSE_PATH, post(pause))
+            .route_layer(axum::middleware::from_fn_with_state(
+                guard,
+                enforce_idempotency,
+            ))
+            // Stands for the authentication of the requests
+            .route_layer(axum::middleware::from_fn(
+                |mut request: Request, next: Next| async move {
+                    let key = request
+                        .headers()
+                        .get("x-test-caller")
+                        .map(|value| value.to_str().unwrap().to_owned());
+                    if let Some(key) = key {
+                        request.extensions_mut().insert(AuthenticatedKey(key));
+                    }
+                    next.run(request).await
+                },
+            ))
+            .with_state(paused);
+        let port = get_available_port("127.0.0.1");
+        let listener = tokio::net::TcpListener::bind(format!("127.0.0.1:{}", port))
+            .await
+            .unwrap();
+        tokio::spawn(async move { axum::serve(listener, router).await.unwrap() });
+        format!("http://127.0.0.1:{}{}", port, PAUSE_PATH)
+    }
+
+    #[tokio::test]
+    async fn test_replayed_admin_mutations() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let store = BridgeOrchestratorTables::new(temp_dir.path());
+        let paused = Arc::new(AtomicU64::new(0));
+        let url = serve(
+            Arc::new(IdempotencyGuard::new(
+                store.clone(),
+                DEFAULT_IDEMPOTENCY_KEY_TTL,
+            )),
+            paused.clone(),
+        )
+        .await;
+        let client = reqwest::Client::new();
+        let send = |caller: &str, key: Option<&str>, body: &str| {
+            let mut request = client
+                .post(&url)
+                .header("x-test-caller", caller)
+                .body(body.to_string());
+            if let Some(key) = key {
+                request = request.header(IDEMPOTENCY_KEY_HEADER, key);
+            }
+            request.send()
+        };
+
+        // The key is required
+        let resp = send("a", None, "now").await.unwrap();
+        assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+        assert_eq!(paused.load(Ordering::SeqCst), 0);
+
+        let resp = send("a", Some("1"), "now").await.unwrap();
+        assert_eq!(resp.status(), StatusCode::OK);
+        assert!(resp.headers().get(IDEMPOTENT_REPLAYED_HEADER).is_none());
+        let body = resp.text().await.unwrap();
+        assert_eq!(body, "paused by key:a (now)");
+
+        // Replays don't pause again, and get the same response
+        for _ in 0..3 {
+            let resp = send("a", Some("1"), "now").await.unwrap();
+            assert_eq!(resp.status(), StatusCode::OK);
+            assert_eq!(
+                resp.headers().get(IDEMPOTENT_REPLAYED_HEADER).unwrap(),
+                "true"
+            );
+            assert_eq!(resp.text().await.unwrap(), body);
+        }
+        assert_eq!(paused.load(Ordering::SeqCst), 1);
+        assert_eq!(
+            store
+                .get_admin_request("key:a", "1")
+                .unwrap()
+                .unwrap()
+                .status,
+            200
+        );
+
+        // The key can't be reused for another request
+        let resp = send("a", Some("1"), "later").await.unwrap();
+        assert_eq!(resp.status(), StatusCode::UNPROCESSABLE_ENTITY);
+        assert_eq!(paused.load(Ordering::SeqCst), 1);
+
+        // Keys are scoped by caller
+        let resp = send("b", Some("1"), "now").await.unwrap();
+        assert_eq!(resp.status(), StatusCode::CONFLICT);
+        assert_eq!(paused.load(Ordering::SeqCst), 2);
+
+        // Only the requests that reached the mutation are audited, not the replays
+        let audit = store.get_admin_audit(None, 10).unwrap();
+        let audited = audit
+            .iter()
+            .map(|(index, record)| {
+                (
+                    *index,
+                    record.caller.as_str(),
+                    record.method.as_str(),
+                    record.path.as_str(),
+                    record.idempotency_key.as_str(),
+                    record.status,
+                )
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            audited,
+            vec![
+                (1, "key:b", "POST", PAUSE_PATH, "1", 409),
+                (0, "key:a", "POST", PAUSE_PATH, "1", 200),
+            ]
+        );
+        assert!(audit[0].1.requested_at_ms >= audit[1].1.requested_at_ms);
+        assert_eq!(store.get_admin_audit(Some(1), 10).unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_idempotency_keys_expire() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let store = BridgeOrchestratorTables::new(temp_dir.path());
+        let paused = Arc::new(AtomicU64::new(0));
+        let url = serve(
+            Arc::new(IdempotencyGuard::new(store.clone(), Duration::ZERO)),
+            paused.clone(),
+        )
+        .await;
+        let client = reqwest::Client::new();
+        let send = |key: &str| {
+            client
+                .post(&url)
+                .header(IDEMPOTENCY_KEY_HEADER, key)
+                .body("now")
+                .send()
+        };
+
+        assert_eq!(send("1").await.unwrap().status(), StatusCode::OK);
+        tokio::time::sleep(Duration::from_millis(10)).await;
+        // Expired, so the mutation is done again
+        assert_eq!(send("1").await.unwrap().status(), StatusCode::CONFLICT);
+        assert_eq!(paused.load(Ordering::SeqCst), 2);
+        // and the expired responses are deleted
+        tokio::time::sleep(Duration::from_millis(10)).await;
+        send("2").await.unwrap();
+        assert!(store
+            .get_admin_request(UNKNOWN_REQUESTER, "1")
+            .unwrap()
+            .is_none());
+    }
+}
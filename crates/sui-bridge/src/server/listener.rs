@@ -0,0 +1,213 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! The socket the server listens on. The address can be a hostname or an IPv4 or IPv6
+//! literal, and must resolve to exactly one address: the server binds a single socket.
+//! On the unspecified IPv6 address `::` the socket can accept IPv4 connections too. With
+//! `SO_REUSEPORT` a new process of the node can bind the address before the old one
+//! exits, so that deploys don't refuse connections.
+
+use anyhow::{anyhow, bail};
+use socket2::{Domain, Protocol, Socket, Type};
+use std::collections::BTreeSet;
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr};
+
+pub const DEFAULT_SERVER_LISTEN_ADDRESS: IpAddr = IpAddr::V4(Ipv4Addr::UNSPECIFIED);
+const LISTEN_BACKLOG: i32 = 1024;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ListenConfig {
+    pub address: SocketAddr,
+    /// Accept IPv4 connections on `::` too
+    pub dual_stack: bool,
+    /// Let other sockets bind the same address with `SO_REUSEPORT`
+    pub reuse_port: bool,
+}
+
+impl ListenConfig {
+    pub fn new(address: SocketAddr) -> Self {
+        Self {
+            address,
+            dual_stack: false,
+            reuse_port: false,
+        }
+    }
+
+    /// Resolves `host` to the address to listen on with `port`. Hosts that are not
+    /// exactly one address are refused rather than picking one of them.
+    pub async fn resolve(
+        host: Option<&str>,
+        port: u16,
+        dual_stack: bool,
+        reuse_port: bool,
+    ) -> anyhow::Result<Self> {
+        let ip = match host {
+            None => DEFAULT_SERVER_LISTEN_ADDRESS,
+            Some(host) => resolve_host(host, port).await?,
+        };
+        if dual_stack && ip != IpAddr::V6(Ipv6Addr::UNSPECIFIED) {
+            bail!(
+                "`server_dual_stack` requires `server_listen_address` to be `::`, got {}",
+                ip
+            );
+        }
+        if reuse_port && !cfg!(unix) {
+            bail!("`server_reuse_port` is only supported on unix");
+        }
+        Ok(Self {
+            address: SocketAddr::new(ip, port),
+            dual_stack,
+            reuse_port,
+        })
+    }
+
+    /// Binds the listener. Must be called within a tokio runtime.
+    pub fn bind(&self) -> std::io::Result<tokio::net::TcpListener> {
+        let socket = Socket::new(
+            Domain::for_address(self.address),
+            Type::STREAM,
+            Some(Protocol::TCP),
+        )?;
+        if self.address.is_ipv6() {
+            // Explicit, the default differs across platforms
+            socket.set_only_v6(!self.dual_stack)?;
+        }
+        // Same as `tokio::net::TcpListener::bind`
+        #[cfg(unix)]
+        socket.set_reuse_address(true)?;
+        #[cfg(unix)]
+        if self.reuse_port {
+            socket.set_reuse_port(true)?;
+        }
+        socket.set_nonblocking(true)?;
+        socket.bind(&self.address.into())?;
+        socket.listen(LISTEN_BACKLOG)?;
+        tokio::net::TcpListener::from_std(socket.into())
+    }
+}
+
+async fn resolve_host(host: &str, port: u16) -> anyhow::Result<IpAddr> {
+    let unbracketed = host
+        .strip_prefix('[')
+        .and_then(|host| host.strip_suffix(']'))
+        .unwrap_or(host);
+    if let Ok(ip) = unbracketed.parse::<IpAddr>() {
+        return Ok(ip);
+    }
+    // Not an IPv6 literal, so the colon starts a port
+    if host.contains(':') {
+        bail!(
+            "`server_listen_address` {} must not have a port, the port is `server_listen_port`",
+            host
+        );
+    }
+    let ips = tokio::net::lookup_host((host, port))
+        .await
+        .map_err(|e| anyhow!("Couldn't resolve `server_listen_address` {}: {:?}", host, e))?
+        .map(|address| address.ip())
+        .collect::<BTreeSet<_>>();
+    match ips.len() {
+        1 => Ok(*ips.first().unwrap()),
+        0 => bail!("`server_listen_address` {} resolves to no address", host),
+        _ => bail!(
+            "`server_listen_address` {} resolves to several addresses ({}), set the one to listen on",
+            host,
+            ips.iter()
+                .map(|ip| ip.to_string())
+                .collect::<Vec<_>>()
+                .join(", ")
+        ),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sui_config::local_ip_utils::get_available_port;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+    #[tokio::test]
+    async fn test_resolve_listen_address() {
+        let resolve = |host: Option<&'static str>, dual_stack: bool| async move {
+            ListenConfig::resolve(host, 9191, dual_stack, false)
+                .await
+                .map(|config| config.address)
+        };
+        assert_eq!(
+            resolve(None, false).await.unwrap(),
+            "0.0.0.0:9191".parse().unwrap()
+        );
+        assert_eq!(
+            resolve(Some("127.0.0.1"), false).await.unwrap(),
+            "127.0.0.1:9191".parse().unwrap()
+        );
+        for host in ["::1", "[::1]"] {
+            assert_eq!(
+                resolve(Some(host), false).await.unwrap(),
+                "[::1]:9191".parse().unwrap()
+            );
+        }
+        assert_eq!(
+            resolve(Some("[::]"), true).await.unwrap(),
+            "[::]:9191".parse().unwrap()
+        );
+
+        // Ports belong in `server_listen_port`
+        for host in ["[::1]:9191", "127.0.0.1:9191", "example.com:9191"] {
+            let err = resolve(Some(host), false).await.unwrap_err();
+            assert!(err.to_string().contains("must not have a port"), "{}", err);
+        }
+        // Both stacks are only bound on the unspecified address
+        for host in ["0.0.0.0", "::1"] {
+            let err = resolve(Some(host), true).await.unwrap_err();
+            assert!(err.to_string().contains("`server_dual_stack`"), "{}", err);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_bind_ipv6() {
+        let port = get_available_port("::1");
+        let config = ListenConfig::resolve(Some("::1"), port, false, false)
+            .await
+            .unwrap();
+        let listener = config.bind().unwrap();
+        assert_eq!(listener.local_addr().unwrap(), config.address);
+        let server = tokio::spawn(async move {
+            let (mut stream, peer) = listener.accept().await.unwrap();
+            stream.write_all(b"pong").await.unwrap();
+            peer
+        });
+
+        let mut stream = tokio::net::TcpStream::connect(config.address)
+            .await
+            .unwrap();
+        let mut reply = [0u8; 4];
+        stream.read_exact(&mut reply).await.unwrap();
+        assert_eq!(&reply, b"pong");
+        assert!(server.await.unwrap().is_ipv6());
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_reuse_port() {
+        let port = get_available_port("127.0.0.1");
+        let address = SocketAddr::new(IpAddr::V4(Ipv4Addr::LOCALHOST), port);
+        let reused = ListenConfig {
+            reuse_port: true,
+            ..ListenConfig::new(address)
+        };
+
+        // Two listeners with SO_REUSEPORT coexist, e.g. the old and new node in a deploy
+        let first = reused.bind().unwrap();
+        let second = reused.bind().unwrap();
+        assert_eq!(first.local_addr().unwrap(), second.local_addr().unwrap());
+        // A listener without it can't join them
+        assert!(ListenConfig::new(address).bind().is_err());
+        drop(first);
+        drop(second);
+        // nor share the address with one
+        let exclusive = ListenConfig::new(address).bind().unwrap();
+        assert!(reused.bind().is_err());
+        drop(exclusive);
+    }
+}
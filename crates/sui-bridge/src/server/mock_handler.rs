@@ -4,25 +4,27 @@
 //! A mock implementation for `BridgeRequestHandlerTrait`
 //! that handles requests according to preset behaviors.
 
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use std::net::SocketAddr;
 use std::str::FromStr;
 use std::sync::{Arc, Mutex};
 
+use crate::api_types::{ApprovedGovernance, SignedSetDigest};
 use crate::crypto::BridgeAuthorityKeyPair;
 use crate::crypto::BridgeAuthoritySignInfo;
 use crate::error::BridgeError;
 use crate::error::BridgeResult;
 use crate::metrics::BridgeMetrics;
 use crate::server::BridgeNodePublicMetadata;
-use crate::types::SignedBridgeAction;
+use crate::types::{BridgeActionDigest, GovernanceDryRun, SignedBridgeAction};
 use arc_swap::ArcSwap;
 use async_trait::async_trait;
 use axum::Json;
 use sui_types::digests::TransactionDigest;
 
 use super::handler::BridgeRequestHandlerTrait;
-use super::make_router;
+use super::timing::DEFAULT_SLOW_REQUEST_THRESHOLD;
+use super::{make_router, DEFAULT_GOVERNANCE_BODY_LIMIT};
 
 #[allow(clippy::type_complexity)]
 #[derive(Clone)]
@@ -31,6 +33,8 @@ pub struct BridgeRequestMockHandler {
     sui_token_events:
         Arc<Mutex<HashMap<(TransactionDigest, u16), BridgeResult<SignedBridgeAction>>>>,
     sui_token_events_requested: Arc<Mutex<HashMap<(TransactionDigest, u16), u64>>>,
+    sui_token_events_hanging: Arc<Mutex<HashMap<(TransactionDigest, u16), u64>>>,
+    governance_dry_runs: Arc<Mutex<HashMap<BridgeActionDigest, GovernanceDryRun>>>,
 }
 
 impl BridgeRequestMockHandler {
@@ -39,6 +43,8 @@ impl BridgeRequestMockHandler {
             signer: Arc::new(ArcSwap::new(Arc::new(None))),
             sui_token_events: Arc::new(Mutex::new(HashMap::new())),
             sui_token_events_requested: Arc::new(Mutex::new(HashMap::new())),
+            sui_token_events_hanging: Arc::new(Mutex::new(HashMap::new())),
+            governance_dry_runs: Arc::new(Mutex::new(HashMap::new())),
         }
     }
 
@@ -67,6 +73,27 @@ impl BridgeRequestMockHandler {
             .unwrap_or(&0)
     }
 
+    /// The next `requests` requests for the event never respond, and are not counted in
+    /// `get_sui_token_events_requested`.
+    pub fn hang_sui_event_requests(&self, tx_digest: TransactionDigest, idx: u16, requests: u64) {
+        self.sui_token_events_hanging
+            .lock()
+            .unwrap()
+            .insert((tx_digest, idx), requests);
+    }
+
+    /// Dry runs of governance actions without a preset response would sign.
+    pub fn add_governance_dry_run_response(
+        &self,
+        action_digest: BridgeActionDigest,
+        response: GovernanceDryRun,
+    ) {
+        self.governance_dry_runs
+            .lock()
+            .unwrap()
+            .insert(action_digest, response);
+    }
+
     pub fn set_signer(&self, signer: BridgeAuthorityKeyPair) {
         self.signer.store(Arc::new(Some(signer)));
     }
@@ -84,6 +111,7 @@ impl BridgeRequestHandlerTrait for BridgeRequestMockHandler {
         &self,
         _tx_hash_hex: String,
         _event_idx: u16,
+        _nonce: Option<u64>,
     ) -> Result<Json<SignedBridgeAction>, BridgeError> {
         unimplemented!()
     }
@@ -92,9 +120,25 @@ impl BridgeRequestHandlerTrait for BridgeRequestMockHandler {
         &self,
         tx_digest_base58: String,
         event_idx: u16,
+        _nonce: Option<u64>,
     ) -> Result<Json<SignedBridgeAction>, BridgeError> {
         let tx_digest = TransactionDigest::from_str(&tx_digest_base58)
             .map_err(|_e| BridgeError::InvalidTxHash)?;
+        let hang = match self
+            .sui_token_events_hanging
+            .lock()
+            .unwrap()
+            .get_mut(&(tx_digest, event_idx))
+        {
+            Some(requests) if *requests > 0 => {
+                *requests -= 1;
+                true
+            }
+            _ => false,
+        };
+        if hang {
+            std::future::pending::<()>().await;
+        }
         let preset = self.sui_token_events.lock().unwrap();
         if !preset.contains_key(&(tx_digest, event_idx)) {
             // Ok to panic in test
@@ -126,6 +170,31 @@ impl BridgeRequestHandlerTrait for BridgeRequestMockHandler {
         let signed_action = SignedBridgeAction::new_from_data_and_sig(action, sig);
         Ok(Json(signed_action))
     }
+
+    async fn dry_run_governance_action(
+        &self,
+        action: crate::types::BridgeAction,
+    ) -> Result<Json<GovernanceDryRun>, BridgeError> {
+        let dry_run = self
+            .governance_dry_runs
+            .lock()
+            .unwrap()
+            .get(&action.digest())
+            .cloned()
+            .unwrap_or(GovernanceDryRun {
+                would_sign: true,
+                reason: None,
+            });
+        Ok(Json(dry_run))
+    }
+
+    async fn signed_set_digest(&self) -> Result<Json<SignedSetDigest>, BridgeError> {
+        Ok(Json(SignedSetDigest::new(&BTreeMap::new())))
+    }
+
+    async fn approved_governance(&self) -> Result<Json<ApprovedGovernance>, BridgeError> {
+        Ok(Json(ApprovedGovernance::default()))
+    }
 }
 
 pub fn run_mock_server(
@@ -139,8 +208,11 @@ pub fn run_mock_server(
     tokio::spawn(async move {
         let router = make_router(
             Arc::new(mock_handler),
-            Arc::new(BridgeMetrics::new_for_testing()),
+            Arc::new(BridgeMetrics::for_testing()),
             Arc::new(BridgeNodePublicMetadata::empty_for_testing()),
+            None,
+            DEFAULT_SLOW_REQUEST_THRESHOLD,
+            DEFAULT_GOVERNANCE_BODY_LIMIT,
         );
         axum::serve(listener, router).await.unwrap()
     })
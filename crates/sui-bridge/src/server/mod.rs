@@ -4,10 +4,37 @@
 #![allow(clippy::inconsistent_digit_grouping)]
 use crate::with_metrics;
 use crate::{
+    api_types::{
+        add_schema_version, parse_transfer_cursor, AdminAuditEntry, AggregationStatus,
+        ApprovedGovernance, ClockHealth, CommitteeMemberScore, CursorReset, DrainEstimate,
+        ErrorEntry, ExecutorState, LimitWindow, NodeHealth, NodeMetadata, PipelinesHealth,
+        ReadinessState, RegisteredUrl, RequesterRate, RequesterSummary, RequeueMatching,
+        RequeueMatchingRequest, ResetCursorRequest, RuntimeInfo, SignedSetDigest, StorageBackup,
+        StorageCompaction, TransferPage,
+    },
     crypto::BridgeAuthorityPublicKeyBytes,
+    cursor_reset::CursorResetter,
+    drain::{DrainEstimator, DEFAULT_DRAIN_WINDOW},
     error::BridgeError,
+    limit_window::parse_route,
     metrics::BridgeMetrics,
-    server::handler::{BridgeRequestHandler, BridgeRequestHandlerTrait},
+    quarantine_recheck::QuarantineRechecker,
+    server::admin_auth::{authorize_admin_request, AdminAuthorizer},
+    server::auth::{authenticate_request, RequestAuthenticator},
+    server::handler::{
+        BridgeRequestHandler, BridgeRequestHandlerTrait, ObserverRequestHandler,
+        ShadowRequestHandler,
+    },
+    server::idempotency::{enforce_idempotency, IdempotencyGuard, DEFAULT_IDEMPOTENCY_KEY_TTL},
+    server::listener::ListenConfig,
+    server::request_id::{current_request_id, propagate_request_id},
+    server::requester::{count_requests_by_requester, REQUESTER_SUMMARY_SIZE, REQUESTER_WINDOW},
+    server::timing::{log_slow_requests, SlowRequestLogger, DEFAULT_SLOW_REQUEST_THRESHOLD},
+    storage::{AdminRequestOrigin, BridgeOrchestratorTables},
+    storage_maintenance::StorageMaintenance,
+    sui_client::{SuiClient, SuiClientInner},
+    timestamps::now_ms,
+    transfer_export::{get_transfer_page, TransferFilter},
     types::{
         AddTokensOnEvmAction, AddTokensOnSuiAction, AssetPriceUpdateAction,
         BlocklistCommitteeAction, BlocklistType, BridgeAction, EmergencyAction,
@@ -15,23 +42,44 @@ use crate::{
     },
 };
 use axum::{
-    extract::{Path, State},
-    Json,
+    body::Bytes,
+    extract::{DefaultBodyLimit, Path, Query, State},
+    response::{IntoResponse, Response},
+    Extension, Json,
+};
+use axum::{
+    http::StatusCode,
+    routing::{get, post},
+    Router,
 };
-use axum::{http::StatusCode, routing::get, Router};
 use ethers::types::Address as EthAddress;
 use fastcrypto::ed25519::Ed25519PublicKey;
 use fastcrypto::{
-    encoding::{Encoding, Hex},
+    encoding::{Base64, Encoding, Hex},
     traits::ToFromBytes,
 };
+use serde::Deserialize;
+use std::collections::BTreeMap;
 use std::sync::Arc;
+use std::time::Duration;
 use std::{net::SocketAddr, str::FromStr};
 use sui_types::{bridge::BridgeChainId, TypeTag};
 use tracing::{info, instrument};
 
+pub mod admin_auth;
+pub mod auth;
 pub mod governance_verifier;
+#[cfg(feature = "grpc")]
+pub mod grpc;
 pub mod handler;
+pub mod idempotency;
+pub mod listener;
+pub mod nonce_guard;
+pub mod request_id;
+pub mod requester;
+pub mod runtime;
+pub mod timing;
+pub mod warm_up;
 
 #[cfg(test)]
 pub(crate) mod mock_handler;
@@ -39,7 +87,25 @@ pub(crate) mod mock_handler;
 pub const APPLICATION_JSON: &str = "application/json";
 
 pub const PING_PATH: &str = "/ping";
+pub const HEALTH_PATH: &str = "/health";
+pub const READY_PATH: &str = "/ready";
 pub const METRICS_KEY_PATH: &str = "/metrics_pub_key";
+pub const SIGNED_SET_DIGEST_PATH: &str = "/signed_set_digest";
+pub const ADMIN_ERRORS_PATH: &str = "/admin/errors";
+pub const ADMIN_ERRORS_REQUESTERS_PATH: &str = "/admin/errors/requesters";
+pub const ADMIN_COMMITTEE_SCORES_PATH: &str = "/admin/committee_scores";
+pub const ADMIN_EXECUTOR_STATE_PATH: &str = "/admin/executor_state";
+pub const ADMIN_AGGREGATION_STATUS_PATH: &str = "/admin/aggregation_status";
+pub const ADMIN_RESET_CURSOR_PATH: &str = "/admin/reset_cursor";
+pub const ADMIN_APPROVED_GOVERNANCE_PATH: &str = "/admin/approved_governance";
+pub const ADMIN_DRAIN_ESTIMATE_PATH: &str = "/admin/drain_estimate";
+pub const ADMIN_QUARANTINE_REQUEUE_MATCHING_PATH: &str = "/admin/quarantine/requeue_matching";
+pub const ADMIN_AUDIT_PATH: &str = "/admin/audit";
+pub const ADMIN_STORAGE_COMPACT_PATH: &str = "/admin/storage/compact";
+pub const ADMIN_STORAGE_BACKUP_PATH: &str = "/admin/storage/backup";
+pub const ADMIN_INFO_PATH: &str = "/admin/info";
+pub const TRANSFERS_PATH: &str = "/transfers";
+pub const LIMIT_WINDOW_PATH: &str = "/limits/:route/window";
 
 // Important: for BridgeActions, the paths need to match the ones in bridge_client.rs
 pub const ETH_TO_SUI_TX_PATH: &str = "/sign/bridge_tx/eth/sui/:tx_hash/:event_index";
@@ -59,13 +125,24 @@ pub const ADD_TOKENS_ON_SUI_PATH: &str =
     "/sign/add_tokens_on_sui/:chain_id/:nonce/:native/:token_ids/:token_type_names/:token_prices";
 pub const ADD_TOKENS_ON_EVM_PATH: &str =
     "/sign/add_tokens_on_evm/:chain_id/:nonce/:native/:token_ids/:token_addresses/:token_sui_decimals/:token_prices";
+// With `?dry_run=true`, the governance routes answer whether they would sign the action
+pub const DRY_RUN_QUERY_PARAM: &str = "dry_run";
+// With `?nonce=`, the token transfer routes refuse a transfer too far ahead of its source
+// chain before reading it, see `nonce_guard`
+pub const NONCE_QUERY_PARAM: &str = "nonce";
+
+/// Largest request body accepted by the governance routes. Call data of an EVM contract
+/// upgrade too large to fit in a path is POSTed to `EVM_CONTRACT_UPGRADE_PATH` as the raw
+/// bytes of the body. Other routes keep the default limit of axum.
+pub const DEFAULT_GOVERNANCE_BODY_LIMIT: usize = 1024 * 1024;
 
 // BridgeNode's public metadata that is accessible via the `/ping` endpoint.
 // Be careful with what to put here, as it is public.
-#[derive(serde::Serialize)]
 pub struct BridgeNodePublicMetadata {
     pub version: Option<String>,
     pub metrics_pubkey: Option<Arc<Ed25519PublicKey>>,
+    // Served on `/admin/info` only, set when the node starts
+    pub runtime_info: Option<Arc<RuntimeInfo>>,
 }
 
 impl BridgeNodePublicMetadata {
@@ -73,6 +150,7 @@ impl BridgeNodePublicMetadata {
         Self {
             version: Some(version),
             metrics_pubkey: Some(metrics_pubkey.into()),
+            runtime_info: None,
         }
     }
 
@@ -80,37 +158,182 @@ impl BridgeNodePublicMetadata {
         Self {
             version: None,
             metrics_pubkey: None,
+            runtime_info: None,
         }
     }
+
+    fn encoded_metrics_pubkey(&self) -> Option<String> {
+        self.metrics_pubkey
+            .as_ref()
+            .map(|pubkey| Base64::encode(pubkey.as_bytes()))
+    }
 }
 
 pub fn run_server(
-    socket_address: &SocketAddr,
-    handler: BridgeRequestHandler,
+    listen: &ListenConfig,
+    handler: Arc<BridgeRequestHandler>,
     metrics: Arc<BridgeMetrics>,
     metadata: Arc<BridgeNodePublicMetadata>,
+    authenticator: Option<Arc<RequestAuthenticator>>,
+    slow_request_threshold: Duration,
+    governance_body_limit: usize,
+    admin_router: Option<Router>,
+    limits_router: Option<Router>,
+    transfers_router: Option<Router>,
+    admin_authorizer: Option<Arc<AdminAuthorizer>>,
 ) -> tokio::task::JoinHandle<()> {
-    let socket_address = *socket_address;
+    let listen = *listen;
     tokio::spawn(async move {
-        let listener = tokio::net::TcpListener::bind(socket_address).await.unwrap();
+        let listener = listen
+            .bind()
+            .unwrap_or_else(|e| panic!("Couldn't listen on {}: {:?}", listen.address, e));
+        metrics.readiness.set_server_bound();
+        let mut router = make_router(
+            handler,
+            metrics,
+            metadata,
+            authenticator,
+            slow_request_threshold,
+            governance_body_limit,
+        );
+        if let Some(admin_router) = admin_router {
+            router = router.merge(admin_router);
+        }
+        if let Some(limits_router) = limits_router {
+            router = router.merge(limits_router);
+        }
+        // Served whether or not `admin_auth` is set, see `make_transfers_router`
+        if let Some(transfers_router) = transfers_router {
+            router = router.merge(transfers_router);
+        }
+        let router = authorize_admin_routes(router, admin_authorizer);
+        // The source address identifies requesters that are neither mTLS nor authenticated
         axum::serve(
             listener,
-            make_router(Arc::new(handler), metrics, metadata).into_make_service(),
+            router.into_make_service_with_connect_info::<SocketAddr>(),
         )
         .await
         .unwrap();
     })
 }
 
-pub(crate) fn make_router(
-    handler: Arc<impl BridgeRequestHandlerTrait + Sync + Send + 'static>,
+/// Runs the server of a node in observer mode. It serves the same routes as
+/// `run_server` except for the signing ones.
+pub fn run_observer_server(
+    listen: &ListenConfig,
     metrics: Arc<BridgeMetrics>,
     metadata: Arc<BridgeNodePublicMetadata>,
+    admin_authorizer: Option<Arc<AdminAuthorizer>>,
+) -> tokio::task::JoinHandle<()> {
+    let listen = *listen;
+    tokio::spawn(async move {
+        let listener = listen
+            .bind()
+            .unwrap_or_else(|e| panic!("Couldn't listen on {}: {:?}", listen.address, e));
+        metrics.readiness.set_server_bound();
+        axum::serve(
+            listener,
+            authorize_admin_routes(make_observer_router(metrics, metadata), admin_authorizer)
+                .into_make_service_with_connect_info::<SocketAddr>(),
+        )
+        .await
+        .unwrap();
+    })
+}
+
+pub(crate) fn make_observer_router(
+    metrics: Arc<BridgeMetrics>,
+    metadata: Arc<BridgeNodePublicMetadata>,
+) -> Router {
+    public_routes().with_state((Arc::new(ObserverRequestHandler), metrics, metadata))
+}
+
+/// Runs the server of a node in shadow mode. It serves the same routes as `run_server`,
+/// but the signing ones return 503.
+pub fn run_shadow_server(
+    listen: &ListenConfig,
+    metrics: Arc<BridgeMetrics>,
+    metadata: Arc<BridgeNodePublicMetadata>,
+    admin_authorizer: Option<Arc<AdminAuthorizer>>,
+) -> tokio::task::JoinHandle<()> {
+    let listen = *listen;
+    tokio::spawn(async move {
+        let listener = listen
+            .bind()
+            .unwrap_or_else(|e| panic!("Couldn't listen on {}: {:?}", listen.address, e));
+        metrics.readiness.set_server_bound();
+        let router = make_router(
+            Arc::new(ShadowRequestHandler),
+            metrics,
+            metadata,
+            None,
+            DEFAULT_SLOW_REQUEST_THRESHOLD,
+            DEFAULT_GOVERNANCE_BODY_LIMIT,
+        );
+        axum::serve(
+            listener,
+            authorize_admin_routes(router, admin_authorizer)
+                .into_make_service_with_connect_info::<SocketAddr>(),
+        )
+        .await
+        .unwrap();
+    })
+}
+
+// The admin routes of `router` are only served to local callers, with a role when
+// `authorizer` is set, see `admin_auth`
+pub(crate) fn authorize_admin_routes(
+    router: Router,
+    authorizer: Option<Arc<AdminAuthorizer>>,
 ) -> Router {
+    router.layer(axum::middleware::from_fn_with_state(
+        authorizer,
+        authorize_admin_request,
+    ))
+}
+
+// Routes that don't sign, served in every mode. They return `api_types`.
+fn public_routes<H: BridgeRequestHandlerTrait + Sync + Send + 'static>(
+) -> Router<(Arc<H>, Arc<BridgeMetrics>, Arc<BridgeNodePublicMetadata>)> {
     Router::new()
         .route("/", get(health_check))
         .route(PING_PATH, get(ping))
+        .route(HEALTH_PATH, get(health_report))
+        .route(READY_PATH, get(readiness_report))
         .route(METRICS_KEY_PATH, get(metrics_key_fetch))
+        // Outside of `/admin/`, the other operators compare their digests with it
+        .route(SIGNED_SET_DIGEST_PATH, get(signed_set_digest_fetch))
+        .merge(admin_read_routes())
+        .layer(axum::middleware::map_response(add_schema_version))
+}
+
+// Routes that read the state of the node for operators, served in every mode. They are
+// only served through `authorize_admin_routes`, e.g. the error catalog tells what failed
+// on which action.
+fn admin_read_routes<H: BridgeRequestHandlerTrait + Sync + Send + 'static>(
+) -> Router<(Arc<H>, Arc<BridgeMetrics>, Arc<BridgeNodePublicMetadata>)> {
+    Router::new()
+        .route(ADMIN_INFO_PATH, get(runtime_info_fetch))
+        .route(ADMIN_ERRORS_PATH, get(error_catalog_fetch))
+        .route(ADMIN_ERRORS_REQUESTERS_PATH, get(requester_summary_fetch))
+        .route(ADMIN_COMMITTEE_SCORES_PATH, get(committee_scores_fetch))
+        .route(ADMIN_EXECUTOR_STATE_PATH, get(executor_state_fetch))
+        .route(ADMIN_AGGREGATION_STATUS_PATH, get(aggregation_status_fetch))
+        .route(
+            ADMIN_APPROVED_GOVERNANCE_PATH,
+            get(approved_governance_fetch),
+        )
+}
+
+pub(crate) fn make_router(
+    handler: Arc<impl BridgeRequestHandlerTrait + Sync + Send + 'static>,
+    metrics: Arc<BridgeMetrics>,
+    metadata: Arc<BridgeNodePublicMetadata>,
+    authenticator: Option<Arc<RequestAuthenticator>>,
+    slow_request_threshold: Duration,
+    governance_body_limit: usize,
+) -> Router {
+    let mut sign_routes = Router::new()
         .route(ETH_TO_SUI_TX_PATH, get(handle_eth_tx_hash))
         .route(SUI_TO_ETH_TX_PATH, get(handle_sui_tx_digest))
         .route(
@@ -123,33 +346,173 @@ pub(crate) fn make_router(
             ASSET_PRICE_UPDATE_PATH,
             get(handle_asset_price_update_action),
         )
-        .route(EVM_CONTRACT_UPGRADE_PATH, get(handle_evm_contract_upgrade))
+        .route(
+            EVM_CONTRACT_UPGRADE_PATH,
+            get(handle_evm_contract_upgrade)
+                .post(handle_evm_contract_upgrade_with_body)
+                .layer(DefaultBodyLimit::max(governance_body_limit)),
+        )
         .route(
             EVM_CONTRACT_UPGRADE_PATH_WITH_CALLDATA,
             get(handle_evm_contract_upgrade_with_calldata),
         )
         .route(ADD_TOKENS_ON_SUI_PATH, get(handle_add_tokens_on_sui))
         .route(ADD_TOKENS_ON_EVM_PATH, get(handle_add_tokens_on_evm))
+        .route_layer(axum::middleware::from_fn_with_state(
+            Arc::new(SlowRequestLogger::new(
+                slow_request_threshold,
+                metrics.clone(),
+            )),
+            log_slow_requests,
+        ))
+        .route_layer(axum::middleware::from_fn_with_state(
+            metrics.clone(),
+            count_requests_by_requester,
+        ));
+    // Signing requests are authenticated before reaching the handlers, and before being
+    // counted by requester so that the authenticated key is known
+    if let Some(authenticator) = authenticator {
+        sign_routes = sign_routes.route_layer(axum::middleware::from_fn_with_state(
+            authenticator,
+            authenticate_request,
+        ));
+    }
+    public_routes()
+        .merge(sign_routes)
         .with_state((handler, metrics, metadata))
+        .layer(axum::middleware::from_fn(propagate_request_id))
 }
 
-impl axum::response::IntoResponse for BridgeError {
-    // TODO: distinguish client error.
-    fn into_response(self) -> axum::response::Response {
-        (
-            StatusCode::INTERNAL_SERVER_ERROR,
-            format!("Something went wrong: {:?}", self),
-        )
-            .into_response()
-    }
+/// Routes that read the transfer limiter on Sui, to tell users why their transfers are
+/// waiting. They are not authenticated.
+pub fn make_limits_router<C>(sui_client: Arc<SuiClient<C>>) -> Router
+where
+    C: SuiClientInner + 'static,
+{
+    Router::new()
+        .route(LIMIT_WINDOW_PATH, get(limit_window_fetch::<C>))
+        .with_state(sui_client)
+        .layer(axum::middleware::map_response(add_schema_version))
+        .layer(axum::middleware::from_fn(propagate_request_id))
+}
+
+/// Route that lists the executed token transfers of the client storage, for the
+/// reconciliation systems of exchanges. It's outside of `/admin/` and not authenticated,
+/// like the limits: the transfers are public on both chains.
+pub fn make_transfers_router(store: Arc<BridgeOrchestratorTables>) -> Router {
+    Router::new()
+        .route(TRANSFERS_PATH, get(transfers_fetch))
+        .with_state(store)
+        .layer(axum::middleware::map_response(add_schema_version))
+        .layer(axum::middleware::from_fn(propagate_request_id))
+}
+
+/// Route that estimates how long the node takes to execute its pending actions. It's not
+/// authenticated.
+pub fn make_drain_router<C>(estimator: Arc<DrainEstimator<C>>) -> Router
+where
+    C: SuiClientInner + 'static,
+{
+    Router::new()
+        .route(ADMIN_DRAIN_ESTIMATE_PATH, get(drain_estimate_fetch::<C>))
+        .with_state(estimator)
+        .layer(axum::middleware::map_response(add_schema_version))
+        .layer(axum::middleware::from_fn(propagate_request_id))
 }
 
-impl<E> From<E> for BridgeError
+/// Routes that change the state of the node, and the audit log of their calls. The
+/// mutations require the `operator` role granted by `authorizer`, the keys allowed to
+/// request signatures can't call them.
+pub fn make_admin_router<C, P>(
+    cursor_resetter: Arc<CursorResetter<C, P>>,
+    quarantine_rechecker: Option<Arc<QuarantineRechecker<C, P>>>,
+    storage_maintenance: Option<Arc<StorageMaintenance>>,
+    authorizer: Arc<AdminAuthorizer>,
+) -> Router
 where
-    E: Into<anyhow::Error>,
+    C: SuiClientInner + 'static,
+    P: ethers::providers::JsonRpcClient + 'static,
 {
-    fn from(err: E) -> Self {
-        Self::Generic(err.into().to_string())
+    let store = cursor_resetter.store().clone();
+    let idempotency = Arc::new(IdempotencyGuard::new(
+        store.clone(),
+        DEFAULT_IDEMPOTENCY_KEY_TTL,
+    ));
+    let mut routes = Router::new()
+        .route(ADMIN_RESET_CURSOR_PATH, post(handle_reset_cursor::<C, P>))
+        .with_state(cursor_resetter);
+    // Only nodes that execute actions quarantine them
+    if let Some(quarantine_rechecker) = quarantine_rechecker {
+        routes = routes.merge(
+            Router::new()
+                .route(
+                    ADMIN_QUARANTINE_REQUEUE_MATCHING_PATH,
+                    post(handle_requeue_matching::<C, P>),
+                )
+                .with_state(quarantine_rechecker),
+        );
+    }
+    if let Some(storage_maintenance) = storage_maintenance {
+        routes = routes.merge(
+            Router::new()
+                .route(ADMIN_STORAGE_COMPACT_PATH, post(handle_storage_compact))
+                .route(ADMIN_STORAGE_BACKUP_PATH, post(handle_storage_backup))
+                .with_state(storage_maintenance),
+        );
+    }
+    // Authorized before the idempotency key is checked, which records the caller
+    let routes = routes
+        .route_layer(axum::middleware::from_fn_with_state(
+            idempotency,
+            enforce_idempotency,
+        ))
+        .route_layer(axum::middleware::from_fn_with_state(
+            Some(authorizer),
+            authorize_admin_request,
+        ));
+    // Merged after the layers, the audit log is read like the other admin routes
+    routes
+        .merge(
+            Router::new()
+                .route(ADMIN_AUDIT_PATH, get(admin_audit_fetch))
+                .with_state(store),
+        )
+        .layer(axum::middleware::map_response(add_schema_version))
+        .layer(axum::middleware::from_fn(propagate_request_id))
+}
+
+impl axum::response::IntoResponse for BridgeError {
+    // TODO: distinguish client error.
+    fn into_response(self) -> axum::response::Response {
+        let status = match self {
+            // Retrying won't help until the node config changes
+            BridgeError::ActionTypeNotAllowed(_) | BridgeError::ObserverMode => {
+                StatusCode::FORBIDDEN
+            }
+            BridgeError::InvalidCursorReset(_) => StatusCode::BAD_REQUEST,
+            BridgeError::NonceTooFarAhead(_) => StatusCode::UNPROCESSABLE_ENTITY,
+            // Unlike other invalid actions, the transfer was made but can never be claimed
+            BridgeError::InvalidRecipientAddress(_) => StatusCode::BAD_REQUEST,
+            BridgeError::ShadowMode | BridgeError::ClockDrift(_) => StatusCode::SERVICE_UNAVAILABLE,
+            BridgeError::ServerBusy(_) => StatusCode::TOO_MANY_REQUESTS,
+            // The remote signer may be reachable again, e.g. when its circuit closes
+            ref err @ BridgeError::RemoteSignerError(..) if err.is_retryable() => {
+                StatusCode::SERVICE_UNAVAILABLE
+            }
+            // Retrying won't help at all, the action is invalid
+            ref err if err.is_invalid_action() => StatusCode::UNPROCESSABLE_ENTITY,
+            _ => StatusCode::INTERNAL_SERVER_ERROR,
+        };
+        let body = match current_request_id() {
+            Some(request_id) => {
+                format!(
+                    "Something went wrong (request id {}): {:?}",
+                    request_id, self
+                )
+            }
+            None => format!("Something went wrong: {:?}", self),
+        };
+        (status, body).into_response()
     }
 }
 
@@ -157,14 +520,58 @@ async fn health_check() -> StatusCode {
     StatusCode::OK
 }
 
+// Returns the latest comparison of the registered and the external url of this node.
+async fn health_report(
+    State((_handler, metrics, _metadata)): State<(
+        Arc<impl BridgeRequestHandlerTrait + Sync + Send>,
+        Arc<BridgeMetrics>,
+        Arc<BridgeNodePublicMetadata>,
+    )>,
+) -> Result<Json<NodeHealth>, BridgeError> {
+    Ok(Json(node_health(&metrics)))
+}
+
+// Returns 200 once the node is ready, 503 with the reasons otherwise.
+async fn readiness_report(
+    State((_handler, metrics, _metadata)): State<(
+        Arc<impl BridgeRequestHandlerTrait + Sync + Send>,
+        Arc<BridgeMetrics>,
+        Arc<BridgeNodePublicMetadata>,
+    )>,
+) -> (StatusCode, Json<ReadinessState>) {
+    let state = metrics.readiness.state();
+    let status = if state.ready {
+        StatusCode::OK
+    } else {
+        StatusCode::SERVICE_UNAVAILABLE
+    };
+    (status, Json(state))
+}
+
+/// The health of the node as reported by `/health`.
+pub fn node_health(metrics: &BridgeMetrics) -> NodeHealth {
+    NodeHealth {
+        registered_url: metrics
+            .registered_url
+            .latest()
+            .as_ref()
+            .map(RegisteredUrl::from),
+        pipelines: Some(PipelinesHealth::from(metrics.chain_health.as_ref())),
+        clock: Some(ClockHealth::from(metrics.clock_drift.as_ref())),
+    }
+}
+
 async fn ping(
     State((_handler, _metrics, metadata)): State<(
         Arc<impl BridgeRequestHandlerTrait + Sync + Send>,
         Arc<BridgeMetrics>,
         Arc<BridgeNodePublicMetadata>,
     )>,
-) -> Result<Json<Arc<BridgeNodePublicMetadata>>, BridgeError> {
-    Ok(Json(metadata))
+) -> Result<Json<NodeMetadata>, BridgeError> {
+    Ok(Json(NodeMetadata {
+        version: metadata.version.clone(),
+        metrics_pubkey: metadata.encoded_metrics_pubkey(),
+    }))
 }
 
 async fn metrics_key_fetch(
@@ -173,13 +580,298 @@ async fn metrics_key_fetch(
         Arc<BridgeMetrics>,
         Arc<BridgeNodePublicMetadata>,
     )>,
-) -> Result<Json<Option<Arc<Ed25519PublicKey>>>, BridgeError> {
-    Ok(Json(metadata.metrics_pubkey.clone()))
+) -> Result<Json<Option<String>>, BridgeError> {
+    Ok(Json(metadata.encoded_metrics_pubkey()))
+}
+
+// Returns the description of the node, see `runtime_info`. Null until the node started.
+async fn runtime_info_fetch(
+    State((_handler, _metrics, metadata)): State<(
+        Arc<impl BridgeRequestHandlerTrait + Sync + Send>,
+        Arc<BridgeMetrics>,
+        Arc<BridgeNodePublicMetadata>,
+    )>,
+) -> Result<Json<Option<RuntimeInfo>>, BridgeError> {
+    Ok(Json(metadata.runtime_info.as_deref().cloned()))
+}
+
+// Returns the most recent errors recorded in the `ErrorCatalog`, by counter name.
+async fn error_catalog_fetch(
+    State((_handler, metrics, _metadata)): State<(
+        Arc<impl BridgeRequestHandlerTrait + Sync + Send>,
+        Arc<BridgeMetrics>,
+        Arc<BridgeNodePublicMetadata>,
+    )>,
+) -> Result<Json<BTreeMap<String, Vec<ErrorEntry>>>, BridgeError> {
+    Ok(Json(
+        metrics
+            .error_catalog
+            .snapshot()
+            .into_iter()
+            .map(|(counter, entries)| (counter, entries.iter().map(ErrorEntry::from).collect()))
+            .collect(),
+    ))
+}
+
+// Returns the requesters that sent the most signing requests recently.
+async fn requester_summary_fetch(
+    State((_handler, metrics, _metadata)): State<(
+        Arc<impl BridgeRequestHandlerTrait + Sync + Send>,
+        Arc<BridgeMetrics>,
+        Arc<BridgeNodePublicMetadata>,
+    )>,
+) -> Result<Json<RequesterSummary>, BridgeError> {
+    let top = metrics
+        .requesters
+        .top(REQUESTER_SUMMARY_SIZE, std::time::Instant::now());
+    Ok(Json(RequesterSummary {
+        window_secs: REQUESTER_WINDOW.as_secs(),
+        requesters: top.iter().map(RequesterRate::from).collect(),
+    }))
+}
+
+// Returns the availability scores of the committee members, by hex encoded pubkey.
+async fn committee_scores_fetch(
+    State((_handler, metrics, _metadata)): State<(
+        Arc<impl BridgeRequestHandlerTrait + Sync + Send>,
+        Arc<BridgeMetrics>,
+        Arc<BridgeNodePublicMetadata>,
+    )>,
+) -> Result<Json<BTreeMap<String, CommitteeMemberScore>>, BridgeError> {
+    Ok(Json(
+        metrics
+            .committee_scores
+            .snapshot()
+            .into_iter()
+            .map(|(name, score)| (Hex::encode(name.as_bytes()), (&score).into()))
+            .collect(),
+    ))
+}
+
+// Returns what the action executor is doing, see `executor_state`.
+async fn executor_state_fetch(
+    State((_handler, metrics, _metadata)): State<(
+        Arc<impl BridgeRequestHandlerTrait + Sync + Send>,
+        Arc<BridgeMetrics>,
+        Arc<BridgeNodePublicMetadata>,
+    )>,
+) -> Result<Json<ExecutorState>, BridgeError> {
+    Ok(Json(ExecutorState::new(
+        &metrics.executor_state.snapshot(),
+        now_ms(),
+    )))
+}
+
+// Returns the progress of the signature aggregations in flight, see `aggregation_status`.
+async fn aggregation_status_fetch(
+    State((_handler, metrics, _metadata)): State<(
+        Arc<impl BridgeRequestHandlerTrait + Sync + Send>,
+        Arc<BridgeMetrics>,
+        Arc<BridgeNodePublicMetadata>,
+    )>,
+) -> Result<Json<Vec<AggregationStatus>>, BridgeError> {
+    let now_ms = now_ms();
+    Ok(Json(
+        metrics
+            .aggregation_status
+            .snapshot()
+            .iter()
+            .map(|progress| AggregationStatus::new(progress, now_ms))
+            .collect(),
+    ))
+}
+
+// Returns the digest of the set of actions this node signed, see `signed_set`.
+async fn signed_set_digest_fetch(
+    State((handler, _metrics, _metadata)): State<(
+        Arc<impl BridgeRequestHandlerTrait + Sync + Send>,
+        Arc<BridgeMetrics>,
+        Arc<BridgeNodePublicMetadata>,
+    )>,
+) -> Result<Json<SignedSetDigest>, BridgeError> {
+    handler.signed_set_digest().await
+}
+
+// Returns the digests of the governance actions this node signs when requested.
+async fn approved_governance_fetch(
+    State((handler, _metrics, _metadata)): State<(
+        Arc<impl BridgeRequestHandlerTrait + Sync + Send>,
+        Arc<BridgeMetrics>,
+        Arc<BridgeNodePublicMetadata>,
+    )>,
+) -> Result<Json<ApprovedGovernance>, BridgeError> {
+    handler.approved_governance().await
+}
+
+#[derive(Debug, Deserialize)]
+struct LimitWindowQuery {
+    amount: Option<u64>,
+}
+
+// The route is `<source chain id>-<destination chain id>`, `amount` the USD value with 8
+// decimal places of a transfer to know when it fits under the limit.
+async fn limit_window_fetch<C>(
+    Path(route): Path<String>,
+    Query(query): Query<LimitWindowQuery>,
+    State(sui_client): State<Arc<SuiClient<C>>>,
+) -> Result<Json<LimitWindow>, BridgeError>
+where
+    C: SuiClientInner + 'static,
+{
+    let route = parse_route(&route)?;
+    Ok(Json(
+        sui_client.get_limit_window(route, query.amount).await?,
+    ))
+}
+
+#[derive(Debug, Deserialize)]
+struct DrainEstimateQuery {
+    window_minutes: Option<u64>,
+}
+
+// The throughput is measured over the last `window_minutes`, `DEFAULT_DRAIN_WINDOW` if
+// not set.
+async fn drain_estimate_fetch<C>(
+    Query(query): Query<DrainEstimateQuery>,
+    State(estimator): State<Arc<DrainEstimator<C>>>,
+) -> Result<Json<DrainEstimate>, BridgeError>
+where
+    C: SuiClientInner + 'static,
+{
+    let window = query
+        .window_minutes
+        .map_or(DEFAULT_DRAIN_WINDOW, |minutes| {
+            Duration::from_secs(minutes * 60)
+        });
+    Ok(Json(estimator.estimate(window).await?))
+}
+
+/// Entries of `/admin/audit` returned when `limit` is not set, and at most
+pub const DEFAULT_ADMIN_AUDIT_LIMIT: usize = 100;
+const MAX_ADMIN_AUDIT_LIMIT: usize = 1000;
+
+#[derive(Debug, Deserialize)]
+struct AdminAuditQuery {
+    before: Option<u64>,
+    limit: Option<usize>,
+}
+
+// The latest mutations, newest first, or the ones before the index `before`
+async fn admin_audit_fetch(
+    Query(query): Query<AdminAuditQuery>,
+    State(store): State<Arc<BridgeOrchestratorTables>>,
+) -> Result<Json<Vec<AdminAuditEntry>>, BridgeError> {
+    let limit = query
+        .limit
+        .unwrap_or(DEFAULT_ADMIN_AUDIT_LIMIT)
+        .min(MAX_ADMIN_AUDIT_LIMIT);
+    Ok(Json(
+        store
+            .get_admin_audit(query.before, limit)?
+            .into_iter()
+            .map(|(index, record)| AdminAuditEntry::new(index, record))
+            .collect(),
+    ))
+}
+
+/// Transfers of `/transfers` returned when `limit` is not set, and at most
+pub const DEFAULT_TRANSFERS_LIMIT: u64 = 1000;
+const MAX_TRANSFERS_LIMIT: u64 = 10_000;
+
+#[derive(Debug, Deserialize)]
+struct TransfersQuery {
+    cursor: Option<String>,
+    from: Option<u64>,
+    to: Option<u64>,
+    limit: Option<u64>,
+}
+
+// The executed transfers after `cursor`, in the order of their keys, see
+// `transfer_export`. `from` and `to` bound the execution time in ms.
+async fn transfers_fetch(
+    Query(query): Query<TransfersQuery>,
+    State(store): State<Arc<BridgeOrchestratorTables>>,
+) -> Result<Json<TransferPage>, BridgeError> {
+    let after = query
+        .cursor
+        .as_deref()
+        .map(parse_transfer_cursor)
+        .transpose()
+        .map_err(BridgeError::InvalidBridgeClientRequest)?;
+    let filter = TransferFilter {
+        after,
+        from_ms: query.from,
+        to_ms: query.to,
+        limit: Some(
+            query
+                .limit
+                .unwrap_or(DEFAULT_TRANSFERS_LIMIT)
+                .min(MAX_TRANSFERS_LIMIT),
+        ),
+    };
+    Ok(Json(get_transfer_page(&store, &filter)?))
+}
+
+// Resets a syncer cursor, returns the reset with the previous value.
+async fn handle_reset_cursor<C, P>(
+    State(cursor_resetter): State<Arc<CursorResetter<C, P>>>,
+    origin: Option<Extension<AdminRequestOrigin>>,
+    Json(request): Json<ResetCursorRequest>,
+) -> Result<Json<CursorReset>, BridgeError>
+where
+    C: SuiClientInner + 'static,
+    P: ethers::providers::JsonRpcClient + 'static,
+{
+    let origin = origin.map(|Extension(origin)| origin);
+    info!(
+        "Received cursor reset request: {:?}, from {:?}",
+        request, origin
+    );
+    let record = cursor_resetter
+        .reset_cursor(request, origin.as_ref())
+        .await?;
+    Ok(Json(CursorReset::from(&record)))
+}
+
+async fn handle_requeue_matching<C, P>(
+    State(quarantine_rechecker): State<Arc<QuarantineRechecker<C, P>>>,
+    origin: Option<Extension<AdminRequestOrigin>>,
+    Json(request): Json<RequeueMatchingRequest>,
+) -> Result<Json<RequeueMatching>, BridgeError>
+where
+    C: SuiClientInner + 'static,
+    P: ethers::providers::JsonRpcClient + 'static,
+{
+    let origin = origin.map(|Extension(origin)| origin);
+    info!(
+        "Received quarantine requeue request: {:?}, from {:?}",
+        request, origin
+    );
+    Ok(Json(quarantine_rechecker.requeue_matching(&request).await?))
+}
+
+async fn handle_storage_compact(
+    State(storage_maintenance): State<Arc<StorageMaintenance>>,
+    origin: Option<Extension<AdminRequestOrigin>>,
+) -> Result<Json<StorageCompaction>, BridgeError> {
+    let origin = origin.map(|Extension(origin)| origin);
+    info!("Received storage compaction request from {:?}", origin);
+    Ok(Json(storage_maintenance.compact().await?))
+}
+
+async fn handle_storage_backup(
+    State(storage_maintenance): State<Arc<StorageMaintenance>>,
+    origin: Option<Extension<AdminRequestOrigin>>,
+) -> Result<Json<StorageBackup>, BridgeError> {
+    let origin = origin.map(|Extension(origin)| origin);
+    info!("Received storage backup request from {:?}", origin);
+    Ok(Json(storage_maintenance.backup().await?))
 }
 
 #[instrument(level = "error", skip_all, fields(tx_hash_hex=tx_hash_hex, event_idx=event_idx))]
 async fn handle_eth_tx_hash(
     Path((tx_hash_hex, event_idx)): Path<(String, u16)>,
+    Query(query): Query<TransferQuery>,
     State((handler, metrics, _metadata)): State<(
         Arc<impl BridgeRequestHandlerTrait + Sync + Send>,
         Arc<BridgeMetrics>,
@@ -187,7 +879,9 @@ async fn handle_eth_tx_hash(
     )>,
 ) -> Result<Json<SignedBridgeAction>, BridgeError> {
     let future = async {
-        let sig = handler.handle_eth_tx_hash(tx_hash_hex, event_idx).await?;
+        let sig = handler
+            .handle_eth_tx_hash(tx_hash_hex, event_idx, query.nonce)
+            .await?;
         Ok(sig)
     };
     with_metrics!(metrics.clone(), "handle_eth_tx_hash", future).await
@@ -196,6 +890,7 @@ async fn handle_eth_tx_hash(
 #[instrument(level = "error", skip_all, fields(tx_digest_base58=tx_digest_base58, event_idx=event_idx))]
 async fn handle_sui_tx_digest(
     Path((tx_digest_base58, event_idx)): Path<(String, u16)>,
+    Query(query): Query<TransferQuery>,
     State((handler, metrics, _metadata)): State<(
         Arc<impl BridgeRequestHandlerTrait + Sync + Send>,
         Arc<BridgeMetrics>,
@@ -204,22 +899,49 @@ async fn handle_sui_tx_digest(
 ) -> Result<Json<SignedBridgeAction>, BridgeError> {
     let future = async {
         let sig: Json<SignedBridgeAction> = handler
-            .handle_sui_tx_digest(tx_digest_base58, event_idx)
+            .handle_sui_tx_digest(tx_digest_base58, event_idx, query.nonce)
             .await?;
         Ok(sig)
     };
     with_metrics!(metrics.clone(), "handle_sui_tx_digest", future).await
 }
 
+#[derive(Debug, Default, Deserialize)]
+pub struct TransferQuery {
+    #[serde(default)]
+    pub nonce: Option<u64>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+pub struct GovernanceQuery {
+    #[serde(default)]
+    pub dry_run: bool,
+}
+
+// Signs the governance action, or only verifies it for a dry run
+async fn sign_governance_action(
+    handler: &(impl BridgeRequestHandlerTrait + Sync + Send),
+    action: BridgeAction,
+    query: GovernanceQuery,
+) -> Result<Response, BridgeError> {
+    if query.dry_run {
+        let dry_run = handler.dry_run_governance_action(action).await?;
+        return Ok(dry_run.into_response());
+    }
+    let sig: Json<SignedBridgeAction> = handler.handle_governance_action(action).await?;
+    Ok(sig.into_response())
+}
+
 #[instrument(level = "error", skip_all, fields(chain_id=chain_id, nonce=nonce, blocklist_type=blocklist_type, keys=keys))]
 async fn handle_update_committee_blocklist_action(
     Path((chain_id, nonce, blocklist_type, keys)): Path<(u8, u64, u8, String)>,
+    Query(query): Query<GovernanceQuery>,
     State((handler, metrics, _metadata)): State<(
         Arc<impl BridgeRequestHandlerTrait + Sync + Send>,
         Arc<BridgeMetrics>,
         Arc<BridgeNodePublicMetadata>,
     )>,
-) -> Result<Json<SignedBridgeAction>, BridgeError> {
+) -> Result<Response, BridgeError> {
     let future = async {
         let chain_id = BridgeChainId::try_from(chain_id).map_err(|err| {
             BridgeError::InvalidBridgeClientRequest(format!("Invalid chain id: {:?}", err))
@@ -246,8 +968,7 @@ async fn handle_update_committee_blocklist_action(
             members_to_update,
         });
 
-        let sig: Json<SignedBridgeAction> = handler.handle_governance_action(action).await?;
-        Ok(sig)
+        sign_governance_action(handler.as_ref(), action, query).await
     };
     with_metrics!(
         metrics.clone(),
@@ -260,12 +981,13 @@ async fn handle_update_committee_blocklist_action(
 #[instrument(level = "error", skip_all, fields(chain_id=chain_id, nonce=nonce, action_type=action_type))]
 async fn handle_emergency_action(
     Path((chain_id, nonce, action_type)): Path<(u8, u64, u8)>,
+    Query(query): Query<GovernanceQuery>,
     State((handler, metrics, _metadata)): State<(
         Arc<impl BridgeRequestHandlerTrait + Sync + Send>,
         Arc<BridgeMetrics>,
         Arc<BridgeNodePublicMetadata>,
     )>,
-) -> Result<Json<SignedBridgeAction>, BridgeError> {
+) -> Result<Response, BridgeError> {
     let future = async {
         let chain_id = BridgeChainId::try_from(chain_id).map_err(|err| {
             BridgeError::InvalidBridgeClientRequest(format!("Invalid chain id: {:?}", err))
@@ -281,8 +1003,7 @@ async fn handle_emergency_action(
             nonce,
             action_type,
         });
-        let sig: Json<SignedBridgeAction> = handler.handle_governance_action(action).await?;
-        Ok(sig)
+        sign_governance_action(handler.as_ref(), action, query).await
     };
     with_metrics!(metrics.clone(), "handle_emergency_action", future).await
 }
@@ -290,12 +1011,13 @@ async fn handle_emergency_action(
 #[instrument(level = "error", skip_all, fields(chain_id=chain_id, nonce=nonce, sending_chain_id=sending_chain_id, new_usd_limit=new_usd_limit))]
 async fn handle_limit_update_action(
     Path((chain_id, nonce, sending_chain_id, new_usd_limit)): Path<(u8, u64, u8, u64)>,
+    Query(query): Query<GovernanceQuery>,
     State((handler, metrics, _metadata)): State<(
         Arc<impl BridgeRequestHandlerTrait + Sync + Send>,
         Arc<BridgeMetrics>,
         Arc<BridgeNodePublicMetadata>,
     )>,
-) -> Result<Json<SignedBridgeAction>, BridgeError> {
+) -> Result<Response, BridgeError> {
     let future = async {
         let chain_id = BridgeChainId::try_from(chain_id).map_err(|err| {
             BridgeError::InvalidBridgeClientRequest(format!("Invalid chain id: {:?}", err))
@@ -309,8 +1031,7 @@ async fn handle_limit_update_action(
             sending_chain_id,
             new_usd_limit,
         });
-        let sig: Json<SignedBridgeAction> = handler.handle_governance_action(action).await?;
-        Ok(sig)
+        sign_governance_action(handler.as_ref(), action, query).await
     };
     with_metrics!(metrics.clone(), "handle_limit_update_action", future).await
 }
@@ -318,12 +1039,13 @@ async fn handle_limit_update_action(
 #[instrument(level = "error", skip_all, fields(chain_id=chain_id, nonce=nonce, token_id=token_id, new_usd_price=new_usd_price))]
 async fn handle_asset_price_update_action(
     Path((chain_id, nonce, token_id, new_usd_price)): Path<(u8, u64, u8, u64)>,
+    Query(query): Query<GovernanceQuery>,
     State((handler, metrics, _metadata)): State<(
         Arc<impl BridgeRequestHandlerTrait + Sync + Send>,
         Arc<BridgeMetrics>,
         Arc<BridgeNodePublicMetadata>,
     )>,
-) -> Result<Json<SignedBridgeAction>, BridgeError> {
+) -> Result<Response, BridgeError> {
     let future = async {
         let chain_id = BridgeChainId::try_from(chain_id).map_err(|err| {
             BridgeError::InvalidBridgeClientRequest(format!("Invalid chain id: {:?}", err))
@@ -334,8 +1056,7 @@ async fn handle_asset_price_update_action(
             token_id,
             new_usd_price,
         });
-        let sig: Json<SignedBridgeAction> = handler.handle_governance_action(action).await?;
-        Ok(sig)
+        sign_governance_action(handler.as_ref(), action, query).await
     };
     with_metrics!(metrics.clone(), "handle_asset_price_update_action", future).await
 }
@@ -349,12 +1070,13 @@ async fn handle_evm_contract_upgrade_with_calldata(
         EthAddress,
         String,
     )>,
+    Query(query): Query<GovernanceQuery>,
     State((handler, metrics, _metadata)): State<(
         Arc<impl BridgeRequestHandlerTrait + Sync + Send>,
         Arc<BridgeMetrics>,
         Arc<BridgeNodePublicMetadata>,
     )>,
-) -> Result<Json<SignedBridgeAction>, BridgeError> {
+) -> Result<Response, BridgeError> {
     let future = async {
         let chain_id = BridgeChainId::try_from(chain_id).map_err(|err| {
             BridgeError::InvalidBridgeClientRequest(format!("Invalid chain id: {:?}", err))
@@ -369,8 +1091,7 @@ async fn handle_evm_contract_upgrade_with_calldata(
             new_impl_address,
             call_data,
         });
-        let sig: Json<SignedBridgeAction> = handler.handle_governance_action(action).await?;
-        Ok(sig)
+        sign_governance_action(handler.as_ref(), action, query).await
     };
     with_metrics!(
         metrics.clone(),
@@ -392,12 +1113,13 @@ async fn handle_evm_contract_upgrade(
         EthAddress,
         EthAddress,
     )>,
+    Query(query): Query<GovernanceQuery>,
     State((handler, metrics, _metadata)): State<(
         Arc<impl BridgeRequestHandlerTrait + Sync + Send>,
         Arc<BridgeMetrics>,
         Arc<BridgeNodePublicMetadata>,
     )>,
-) -> Result<Json<SignedBridgeAction>, BridgeError> {
+) -> Result<Response, BridgeError> {
     let future = async {
         let chain_id = BridgeChainId::try_from(chain_id).map_err(|err| {
             BridgeError::InvalidBridgeClientRequest(format!("Invalid chain id: {:?}", err))
@@ -409,13 +1131,53 @@ async fn handle_evm_contract_upgrade(
             new_impl_address,
             call_data: vec![],
         });
-        let sig: Json<SignedBridgeAction> = handler.handle_governance_action(action).await?;
-
-        Ok(sig)
+        sign_governance_action(handler.as_ref(), action, query).await
     };
     with_metrics!(metrics.clone(), "handle_evm_contract_upgrade", future).await
 }
 
+// The body is the raw call data, of any length up to the governance body limit
+#[instrument(
+    level = "error",
+    skip_all,
+    fields(chain_id, nonce, proxy_address, new_impl_address, call_data_len = body.len())
+)]
+async fn handle_evm_contract_upgrade_with_body(
+    Path((chain_id, nonce, proxy_address, new_impl_address)): Path<(
+        u8,
+        u64,
+        EthAddress,
+        EthAddress,
+    )>,
+    Query(query): Query<GovernanceQuery>,
+    State((handler, metrics, _metadata)): State<(
+        Arc<impl BridgeRequestHandlerTrait + Sync + Send>,
+        Arc<BridgeMetrics>,
+        Arc<BridgeNodePublicMetadata>,
+    )>,
+    body: Bytes,
+) -> Result<Response, BridgeError> {
+    let future = async {
+        let chain_id = BridgeChainId::try_from(chain_id).map_err(|err| {
+            BridgeError::InvalidBridgeClientRequest(format!("Invalid chain id: {:?}", err))
+        })?;
+        let action = BridgeAction::EvmContractUpgradeAction(EvmContractUpgradeAction {
+            chain_id,
+            nonce,
+            proxy_address,
+            new_impl_address,
+            call_data: body.to_vec(),
+        });
+        sign_governance_action(handler.as_ref(), action, query).await
+    };
+    with_metrics!(
+        metrics.clone(),
+        "handle_evm_contract_upgrade_with_body",
+        future
+    )
+    .await
+}
+
 #[instrument(level = "error", skip_all, fields(chain_id=chain_id, nonce=nonce, native=native, token_ids=token_ids, token_type_names=token_type_names, token_prices=token_prices))]
 async fn handle_add_tokens_on_sui(
     Path((chain_id, nonce, native, token_ids, token_type_names, token_prices)): Path<(
@@ -426,12 +1188,13 @@ async fn handle_add_tokens_on_sui(
         String,
         String,
     )>,
+    Query(query): Query<GovernanceQuery>,
     State((handler, metrics, _metadata)): State<(
         Arc<impl BridgeRequestHandlerTrait + Sync + Send>,
         Arc<BridgeMetrics>,
         Arc<BridgeNodePublicMetadata>,
     )>,
-) -> Result<Json<SignedBridgeAction>, BridgeError> {
+) -> Result<Response, BridgeError> {
     let future = async {
         let chain_id = BridgeChainId::try_from(chain_id).map_err(|err| {
             BridgeError::InvalidBridgeClientRequest(format!("Invalid chain id: {:?}", err))
@@ -491,8 +1254,7 @@ async fn handle_add_tokens_on_sui(
             token_type_names,
             token_prices,
         });
-        let sig: Json<SignedBridgeAction> = handler.handle_governance_action(action).await?;
-        Ok(sig)
+        sign_governance_action(handler.as_ref(), action, query).await
     };
     with_metrics!(metrics.clone(), "handle_add_tokens_on_sui", future).await
 }
@@ -508,12 +1270,13 @@ async fn handle_add_tokens_on_evm(
         String,
         String,
     )>,
+    Query(query): Query<GovernanceQuery>,
     State((handler, metrics, _metadata)): State<(
         Arc<impl BridgeRequestHandlerTrait + Sync + Send>,
         Arc<BridgeMetrics>,
         Arc<BridgeNodePublicMetadata>,
     )>,
-) -> Result<Json<SignedBridgeAction>, BridgeError> {
+) -> Result<Response, BridgeError> {
     let future = async {
         let chain_id = BridgeChainId::try_from(chain_id).map_err(|err| {
             BridgeError::InvalidBridgeClientRequest(format!("Invalid chain id: {:?}", err))
@@ -584,8 +1347,7 @@ async fn handle_add_tokens_on_evm(
             token_sui_decimals,
             token_prices,
         });
-        let sig: Json<SignedBridgeAction> = handler.handle_governance_action(action).await?;
-        Ok(sig)
+        sign_governance_action(handler.as_ref(), action, query).await
     };
     with_metrics!(metrics.clone(), "handle_add_tokens_on_evm", future).await
 }
@@ -613,7 +1375,7 @@ macro_rules! with_metrics {
                 }
                 Err(e) => {
                     info!("{} request failed: {:?}", $type_, e);
-                    $metrics.err_requests.with_label_values(&[$type_]).inc();
+                    $crate::inc_err_counter!($metrics, err_requests[$type_], e.sanitized(), vec![]);
                 }
             }
 
@@ -633,7 +1395,9 @@ mod tests {
     use super::*;
     use crate::client::bridge_client::BridgeClient;
     use crate::server::mock_handler::BridgeRequestMockHandler;
-    use crate::test_utils::get_test_authorities_and_run_mock_bridge_server;
+    use crate::test_utils::{
+        get_test_authorities_and_run_mock_bridge_server, get_test_sui_to_eth_bridge_action,
+    };
     use crate::types::BridgeCommittee;
 
     #[tokio::test]
@@ -713,6 +1477,81 @@ mod tests {
             call_data: vec![12, 34, 56],
         });
         client.request_sign_bridge_action(action).await.unwrap();
+
+        // 64KB of call data is POSTed, and signed without being truncated
+        let call_data = (0..64 * 1024).map(|i| i as u8).collect::<Vec<_>>();
+        let action = BridgeAction::EvmContractUpgradeAction(EvmContractUpgradeAction {
+            nonce: 123,
+            chain_id: BridgeChainId::EthCustom,
+            proxy_address: EthAddress::repeat_byte(6),
+            new_impl_address: EthAddress::repeat_byte(9),
+            call_data: call_data.clone(),
+        });
+        let signed = client
+            .request_sign_bridge_action(action.clone())
+            .await
+            .unwrap();
+        let BridgeAction::EvmContractUpgradeAction(signed_upgrade) = signed.data() else {
+            panic!("Unexpected signed action {:?}", signed.data());
+        };
+        assert_eq!(signed_upgrade.call_data, call_data);
+        assert_eq!(signed.data().digest(), action.digest());
+        assert_eq!(
+            client
+                .request_dry_run_governance_action(&action)
+                .await
+                .unwrap(),
+            Some(crate::types::GovernanceDryRun {
+                would_sign: true,
+                reason: None
+            })
+        );
+    }
+
+    #[tokio::test]
+    async fn test_governance_body_limit() {
+        let mock = BridgeRequestMockHandler::new();
+        let port = sui_config::local_ip_utils::get_available_port("127.0.0.1");
+        let (authority, _, secret) = crate::test_utils::get_test_authority_and_key(10000, port);
+        mock.set_signer(secret);
+        let router = make_router(
+            Arc::new(mock),
+            Arc::new(BridgeMetrics::for_testing()),
+            Arc::new(BridgeNodePublicMetadata::empty_for_testing()),
+            None,
+            DEFAULT_SLOW_REQUEST_THRESHOLD,
+            4096,
+        );
+        let listener = tokio::net::TcpListener::bind(("127.0.0.1", port))
+            .await
+            .unwrap();
+        tokio::spawn(async move { axum::serve(listener, router).await.unwrap() });
+        let committee = BridgeCommittee::new(vec![authority]).unwrap();
+        let pub_key = committee.members().keys().next().unwrap().clone();
+        let client = BridgeClient::new(pub_key, Arc::new(committee)).unwrap();
+
+        let upgrade = |call_data| {
+            BridgeAction::EvmContractUpgradeAction(EvmContractUpgradeAction {
+                nonce: 1,
+                chain_id: BridgeChainId::EthCustom,
+                proxy_address: EthAddress::repeat_byte(6),
+                new_impl_address: EthAddress::repeat_byte(9),
+                call_data,
+            })
+        };
+        client
+            .request_sign_bridge_action(upgrade(vec![1; 4096]))
+            .await
+            .unwrap();
+        let err = client
+            .request_sign_bridge_action(upgrade(vec![1; 4097]))
+            .await
+            .unwrap_err();
+        assert!(
+            format!("{:?}", err).contains("413"),
+            "Unexpected error {:?}",
+            err
+        );
     }
 
     #[tokio::test]
@@ -754,6 +1593,362 @@ mod tests {
         client.request_sign_bridge_action(action).await.unwrap();
     }
 
+    #[tokio::test]
+    async fn test_bridge_server_handle_governance_dry_run() {
+        let client = setup();
+
+        let action = BridgeAction::LimitUpdateAction(LimitUpdateAction {
+            nonce: 16,
+            chain_id: BridgeChainId::SuiCustom,
+            sending_chain_id: BridgeChainId::EthCustom,
+            new_usd_limit: 1_000_000_0000, // $1M USD
+        });
+        let dry_run = client
+            .request_dry_run_governance_action(&action)
+            .await
+            .unwrap();
+        assert_eq!(
+            dry_run,
+            Some(crate::types::GovernanceDryRun {
+                would_sign: true,
+                reason: None
+            })
+        );
+    }
+
+    #[tokio::test]
+    async fn test_observer_server_does_not_sign() {
+        let port = sui_config::local_ip_utils::get_available_port("127.0.0.1");
+        let _handle = run_observer_server(
+            &ListenConfig::new(SocketAddr::new(
+                std::net::IpAddr::V4(std::net::Ipv4Addr::LOCALHOST),
+                port,
+            )),
+            Arc::new(BridgeMetrics::for_testing()),
+            Arc::new(BridgeNodePublicMetadata::empty_for_testing()),
+            None,
+        );
+        let server_url = format!("http://127.0.0.1:{}", port);
+        crate::utils::wait_for_server_to_be_up(server_url.clone(), 5)
+            .await
+            .unwrap();
+
+        let res = reqwest::get(format!("{}{}", server_url, PING_PATH))
+            .await
+            .unwrap();
+        assert_eq!(res.status().as_u16(), 200);
+        assert_eq!(
+            res.headers()
+                .get(crate::api_types::API_SCHEMA_VERSION_HEADER)
+                .unwrap(),
+            &crate::api_types::API_SCHEMA_VERSION.to_string()
+        );
+        let metadata: NodeMetadata = res.json().await.unwrap();
+        assert_eq!(metadata, NodeMetadata::default());
+        // Signing routes are not served
+        let res = reqwest::get(format!(
+            "{}/sign/bridge_tx/sui/eth/{}/0",
+            server_url,
+            sui_types::digests::TransactionDigest::random()
+        ))
+        .await
+        .unwrap();
+        assert_eq!(res.status().as_u16(), 404);
+    }
+
+    #[tokio::test]
+    async fn test_ready_route() {
+        let port = sui_config::local_ip_utils::get_available_port("127.0.0.1");
+        let metrics = Arc::new(BridgeMetrics::for_testing());
+        let _handle = run_observer_server(
+            &ListenConfig::new(SocketAddr::new(
+                std::net::IpAddr::V4(std::net::Ipv4Addr::LOCALHOST),
+                port,
+            )),
+            metrics.clone(),
+            Arc::new(BridgeNodePublicMetadata::empty_for_testing()),
+            None,
+        );
+        let server_url = format!("http://127.0.0.1:{}", port);
+        crate::utils::wait_for_server_to_be_up(server_url.clone(), 5)
+            .await
+            .unwrap();
+        let ready_url = format!("{}{}", server_url, READY_PATH);
+
+        // The server is bound, but the client components are not up yet
+        let res = reqwest::get(&ready_url).await.unwrap();
+        assert_eq!(res.status().as_u16(), 503);
+        let state: ReadinessState = res.json().await.unwrap();
+        assert!(!state.ready);
+        assert_eq!(state.reasons.len(), 4);
+        assert!(!state
+            .reasons
+            .contains(&crate::api_types::NotReadyReason::ServerNotBound));
+
+        metrics.readiness.set_storage_open();
+        metrics.readiness.set_committee_loaded();
+        metrics
+            .readiness
+            .record_syncer_success(crate::chain_health::PipelineChain::Sui);
+        metrics
+            .readiness
+            .record_syncer_success(crate::chain_health::PipelineChain::Eth);
+        let res = reqwest::get(&ready_url).await.unwrap();
+        assert_eq!(res.status().as_u16(), 200);
+        let state: ReadinessState = res.json().await.unwrap();
+        assert!(state.ready);
+        assert!(state.reasons.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_error_catalog_route() {
+        use crate::error::ErrorKind;
+        use crate::server::admin_auth::{AdminGrant, AdminRole};
+
+        let port = sui_config::local_ip_utils::get_available_port("127.0.0.1");
+        let metrics = Arc::new(BridgeMetrics::for_testing());
+        let authorizer = AdminAuthorizer::new(vec![AdminGrant {
+            name: "oncall".to_string(),
+            role: AdminRole::Reader,
+            token: "reader-token".to_string(),
+        }]);
+        let _handle = run_observer_server(
+            &ListenConfig::new(SocketAddr::new(
+                std::net::IpAddr::V4(std::net::Ipv4Addr::LOCALHOST),
+                port,
+            )),
+            metrics.clone(),
+            Arc::new(BridgeNodePublicMetadata::empty_for_testing()),
+            Some(Arc::new(authorizer)),
+        );
+        let server_url = format!("http://127.0.0.1:{}", port);
+        crate::utils::wait_for_server_to_be_up(server_url.clone(), 5)
+            .await
+            .unwrap();
+        let err = BridgeError::ProviderError(
+            ErrorKind::Transient,
+            "https://eth.provider/v2/secret-api-key".to_string(),
+        );
+        crate::inc_err_counter!(
+            metrics,
+            err_requests["handle_eth_tx_hash"],
+            err.sanitized(),
+            vec![]
+        );
+        let errors_url = format!("{}{}", server_url, ADMIN_ERRORS_PATH);
+
+        // The catalog is an admin route
+        let res = reqwest::get(&errors_url).await.unwrap();
+        assert_eq!(res.status().as_u16(), 401);
+        let res = reqwest::Client::new()
+            .get(&errors_url)
+            .bearer_auth("reader-token")
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(res.status().as_u16(), 200);
+        let errors: BTreeMap<String, Vec<ErrorEntry>> = res.json().await.unwrap();
+        assert_eq!(
+            errors["err_requests"][0].message,
+            "[handle_eth_tx_hash] ProviderError (eth_rpc, transient)"
+        );
+    }
+
+    // Serves `router` without `admin_auth`, as if the requests came from another host
+    async fn serve_to_remote_callers(router: Router) -> String {
+        let router = authorize_admin_routes(router, None).layer(axum::middleware::map_request(
+            |mut request: axum::extract::Request| async move {
+                let address: SocketAddr = "203.0.113.7:41000".parse().unwrap();
+                request
+                    .extensions_mut()
+                    .insert(axum::extract::ConnectInfo(address));
+                request
+            },
+        ));
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let server_url = format!("http://{}", listener.local_addr().unwrap());
+        tokio::spawn(async move { axum::serve(listener, router).await.unwrap() });
+        server_url
+    }
+
+    fn mock_router() -> Router {
+        make_router(
+            Arc::new(BridgeRequestMockHandler::new()),
+            Arc::new(BridgeMetrics::for_testing()),
+            Arc::new(BridgeNodePublicMetadata::empty_for_testing()),
+            None,
+            DEFAULT_SLOW_REQUEST_THRESHOLD,
+            DEFAULT_GOVERNANCE_BODY_LIMIT,
+        )
+    }
+
+    #[tokio::test]
+    async fn test_signed_set_digest_is_served_to_remote_callers() {
+        let server_url = serve_to_remote_callers(mock_router()).await;
+
+        let res = reqwest::get(format!("{}{}", server_url, SIGNED_SET_DIGEST_PATH))
+            .await
+            .unwrap();
+        assert_eq!(res.status().as_u16(), 200);
+        let digest: SignedSetDigest = res.json().await.unwrap();
+        assert_eq!(digest.count, 0);
+        // Unlike the admin routes
+        let res = reqwest::get(format!("{}{}", server_url, ADMIN_INFO_PATH))
+            .await
+            .unwrap();
+        assert_eq!(res.status().as_u16(), 403);
+    }
+
+    #[tokio::test]
+    async fn test_transfers_are_served_without_admin_auth() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = BridgeOrchestratorTables::new(dir.path());
+        let actions = (0..3)
+            .map(|nonce| {
+                get_test_sui_to_eth_bridge_action(None, None, Some(nonce), None, None, None, None)
+            })
+            .collect::<Vec<_>>();
+        store.mark_actions_executed_at(&actions, 1_000_000).unwrap();
+        let server_url =
+            serve_to_remote_callers(mock_router().merge(make_transfers_router(store))).await;
+
+        let res = reqwest::get(format!("{}{}?limit=2", server_url, TRANSFERS_PATH))
+            .await
+            .unwrap();
+        assert_eq!(res.status().as_u16(), 200);
+        let page: TransferPage = res.json().await.unwrap();
+        assert_eq!(page.transfers.len(), 2);
+        let res = reqwest::get(format!(
+            "{}{}?cursor={}",
+            server_url,
+            TRANSFERS_PATH,
+            page.next_cursor.unwrap()
+        ))
+        .await
+        .unwrap();
+        assert_eq!(res.status().as_u16(), 200);
+        let page: TransferPage = res.json().await.unwrap();
+        assert_eq!(page.transfers.len(), 1);
+        assert_eq!(page.next_cursor, None);
+    }
+
+    #[tokio::test]
+    async fn test_shadow_server_rejects_signing() {
+        let port = sui_config::local_ip_utils::get_available_port("127.0.0.1");
+        let _handle = run_shadow_server(
+            &ListenConfig::new(SocketAddr::new(
+                std::net::IpAddr::V4(std::net::Ipv4Addr::LOCALHOST),
+                port,
+            )),
+            Arc::new(BridgeMetrics::for_testing()),
+            Arc::new(BridgeNodePublicMetadata::empty_for_testing()),
+            None,
+        );
+        let server_url = format!("http://127.0.0.1:{}", port);
+        crate::utils::wait_for_server_to_be_up(server_url.clone(), 5)
+            .await
+            .unwrap();
+
+        let res = reqwest::get(format!("{}{}", server_url, PING_PATH))
+            .await
+            .unwrap();
+        assert_eq!(res.status().as_u16(), 200);
+        let res = reqwest::get(format!(
+            "{}/sign/bridge_tx/sui/eth/{}/0",
+            server_url,
+            sui_types::digests::TransactionDigest::random()
+        ))
+        .await
+        .unwrap();
+        assert_eq!(res.status().as_u16(), 503);
+    }
+
+    #[tokio::test]
+    async fn test_signing_requests_are_summarized_by_requester() {
+        let port = sui_config::local_ip_utils::get_available_port("127.0.0.1");
+        let metrics = Arc::new(BridgeMetrics::for_testing());
+        let _handle = run_shadow_server(
+            &ListenConfig::new(SocketAddr::new(
+                std::net::IpAddr::V4(std::net::Ipv4Addr::LOCALHOST),
+                port,
+            )),
+            metrics.clone(),
+            Arc::new(BridgeNodePublicMetadata::empty_for_testing()),
+            None,
+        );
+        let server_url = format!("http://127.0.0.1:{}", port);
+        crate::utils::wait_for_server_to_be_up(server_url.clone(), 5)
+            .await
+            .unwrap();
+
+        for _ in 0..2 {
+            let res = reqwest::get(format!(
+                "{}/sign/bridge_tx/sui/eth/{}/0",
+                server_url,
+                sui_types::digests::TransactionDigest::random()
+            ))
+            .await
+            .unwrap();
+            assert_eq!(res.status().as_u16(), 503);
+        }
+        // Requests that don't sign are not counted
+        reqwest::get(format!("{}{}", server_url, PING_PATH))
+            .await
+            .unwrap();
+
+        let summary: RequesterSummary =
+            reqwest::get(format!("{}{}", server_url, ADMIN_ERRORS_REQUESTERS_PATH))
+                .await
+                .unwrap()
+                .json()
+                .await
+                .unwrap();
+        assert_eq!(summary.window_secs, 300);
+        assert_eq!(summary.requesters.len(), 1);
+        let rate = &summary.requesters[0];
+        // Identified by the hash of the source IP
+        assert!(rate.requester.starts_with("ip:"));
+        assert!(!rate.requester.contains("127.0.0.1"));
+        assert_eq!((rate.requests, rate.errors), (2, 2));
+        assert_eq!(
+            metrics
+                .err_requests_by_requester
+                .with_label_values(&[&rate.requester])
+                .get(),
+            2
+        );
+    }
+
+    #[tokio::test]
+    async fn test_limit_window_route() {
+        let mock_client = crate::sui_mock_client::SuiMockClient::default();
+        mock_client.set_bridge_limiter(sui_types::bridge::BridgeLimiterSummary {
+            transfer_limit: vec![(BridgeChainId::EthSepolia, BridgeChainId::SuiTestnet, 1000)],
+            transfer_records: vec![],
+        });
+        mock_client.set_latest_checkpoint_timestamp_ms(1_000_000);
+        let port = sui_config::local_ip_utils::get_available_port("127.0.0.1");
+        let listener = tokio::net::TcpListener::bind(format!("127.0.0.1:{}", port))
+            .await
+            .unwrap();
+        let router = make_limits_router(Arc::new(SuiClient::new_for_testing(mock_client)));
+        tokio::spawn(async move { axum::serve(listener, router).await.unwrap() });
+        let server_url = format!("http://127.0.0.1:{}", port);
+
+        let res = reqwest::get(format!("{}/limits/11-1/window?amount=1000", server_url))
+            .await
+            .unwrap();
+        assert_eq!(res.status().as_u16(), 200);
+        let window: LimitWindow = res.json().await.unwrap();
+        assert_eq!(window.limit, Some(1000));
+        assert_eq!(window.available_amount, 1000);
+        assert_eq!(window.requested.unwrap().available_at_ms, Some(1_000_000));
+        let res = reqwest::get(format!("{}/limits/11/window", server_url))
+            .await
+            .unwrap();
+        assert!(!res.status().is_success());
+    }
+
     fn setup() -> BridgeClient {
         let mock = BridgeRequestMockHandler::new();
         let (_handles, authorities, mut secrets) =
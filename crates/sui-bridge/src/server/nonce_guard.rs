@@ -0,0 +1,247 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Refuses to sign token transfers whose nonce is far ahead of the nonces known for their
+//! source chain. Nonces of token transfers are sequenced per source chain, so a transfer
+//! far ahead of the next nonce of its chain can't have been emitted yet. The next nonce of
+//! each chain is taken from the transfers observed by the watchers, from the verified
+//! signing requests, and from the sequence numbers read on chain by `NonceGuard::run`.
+//!
+//! Signing requests name the source transaction, and claim the nonce of its transfer with
+//! `?nonce=`, or the `nonce` field over gRPC. A claimed nonce is checked before the request
+//! is verified, so a refused request costs no chain query, on either chain. The nonce of
+//! the event is checked again once it's read, before the finality of an Eth deposit is
+//! queried and before anything is signed, for the requests that don't claim it or claim
+//! another one. The margin covers the transfers emitted since the known nonces were last
+//! updated, and nothing is refused for a chain whose nonces are not known yet.
+//!
+//! The nonces are tracked per source chain rather than per route: the routes out of a
+//! chain share its sequence, so the latest nonce seen on a quiet route lags the chain and
+//! would refuse its next legitimate transfer.
+//!
+//! A refused transfer is a permanent error (422), which the aggregator doesn't retry. It's
+//! not cached with the signatures, so that a transfer is signed once the known nonces
+//! catch up with it.
+
+use crate::error::{BridgeError, BridgeResult};
+use crate::eth_client::EthClient;
+use crate::metrics::BridgeMetrics;
+use crate::sui_client::{SuiClient, SuiClientInner};
+use crate::types::{BridgeAction, BridgeActionType};
+use ethers::providers::JsonRpcClient;
+use ethers::types::Address as EthAddress;
+use std::collections::HashMap;
+use std::sync::{Arc, RwLock};
+use std::time::Duration;
+use sui_types::bridge::BridgeChainId;
+use tracing::warn;
+
+/// Transfers at least this far ahead of the next nonce of their source chain are refused.
+pub const DEFAULT_MAX_NONCE_LEAD: u64 = 10_000;
+pub const DEFAULT_NONCE_REFRESH_INTERVAL: Duration = Duration::from_secs(60);
+
+#[derive(Debug)]
+pub struct NonceGuard {
+    max_nonce_lead: u64,
+    // Next nonce of the token transfers of each source chain, at least
+    next_nonces: RwLock<HashMap<BridgeChainId, u64>>,
+    metrics: Arc<BridgeMetrics>,
+}
+
+impl NonceGuard {
+    pub fn new(max_nonce_lead: u64, metrics: Arc<BridgeMetrics>) -> Self {
+        Self {
+            max_nonce_lead,
+            next_nonces: RwLock::new(HashMap::new()),
+            metrics,
+        }
+    }
+
+    /// Records that the token transfers of `chain_id` got nonces up to `next_nonce`,
+    /// excluded. Known nonces only move forward.
+    pub fn observe_next_nonce(&self, chain_id: BridgeChainId, next_nonce: u64) {
+        let mut next_nonces = self.next_nonces.write().unwrap();
+        let known = next_nonces.entry(chain_id).or_default();
+        *known = (*known).max(next_nonce);
+    }
+
+    /// Records the nonce of `action` if it's a token transfer.
+    pub fn observe(&self, action: &BridgeAction) {
+        if action.action_type() == BridgeActionType::TokenTransfer {
+            self.observe_next_nonce(action.chain_id(), action.seq_number().saturating_add(1));
+        }
+    }
+
+    pub fn next_nonce(&self, chain_id: BridgeChainId) -> Option<u64> {
+        self.next_nonces.read().unwrap().get(&chain_id).copied()
+    }
+
+    /// Refuses `action` if it's a token transfer at least `max_nonce_lead` ahead of the
+    /// next nonce of its source chain.
+    pub fn check(&self, action: &BridgeAction) -> BridgeResult<()> {
+        if action.action_type() != BridgeActionType::TokenTransfer {
+            return Ok(());
+        }
+        self.check_nonce(action.chain_id(), action.seq_number())
+    }
+
+    /// Refuses a token transfer from Sui if `from_sui`, else from Eth, whose nonce claimed
+    /// by the signing request is at least `max_nonce_lead` ahead of the next nonce of its
+    /// source chain. The source transaction is not read.
+    pub fn check_claimed(&self, from_sui: bool, nonce: u64) -> BridgeResult<()> {
+        // A node bridges one Sui chain and one Eth chain
+        let chain_id = self
+            .next_nonces
+            .read()
+            .unwrap()
+            .keys()
+            .find(|chain_id| chain_id.is_sui_chain() == from_sui)
+            .copied();
+        match chain_id {
+            Some(chain_id) => self.check_nonce(chain_id, nonce),
+            None => Ok(()),
+        }
+    }
+
+    fn check_nonce(&self, chain_id: BridgeChainId, nonce: u64) -> BridgeResult<()> {
+        let Some(next_nonce) = self.next_nonce(chain_id) else {
+            return Ok(());
+        };
+        if nonce < next_nonce.saturating_add(self.max_nonce_lead) {
+            return Ok(());
+        }
+        self.metrics
+            .signing_nonce_too_far_ahead
+            .with_label_values(&[&format!("{:?}", chain_id)])
+            .inc();
+        Err(BridgeError::NonceTooFarAhead(format!(
+            "nonce {} of {:?} is {} or more ahead of its next nonce {}",
+            nonce, chain_id, self.max_nonce_lead, next_nonce
+        )))
+    }
+
+    /// Reads the next nonces of the token transfers from Sui and from the SuiBridge
+    /// contract at `eth_bridge_address` every `interval`.
+    pub async fn run<C, P>(
+        self: Arc<Self>,
+        sui_client: Arc<SuiClient<C>>,
+        eth_client: Arc<EthClient<P>>,
+        eth_bridge_address: EthAddress,
+        interval: Duration,
+    ) where
+        C: SuiClientInner + 'static,
+        P: JsonRpcClient + 'static,
+    {
+        let mut eth_chain_id = None;
+        let mut interval = tokio::time::interval(interval);
+        interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+        loop {
+            interval.tick().await;
+            if let Err(e) = self.refresh_sui(&sui_client).await {
+                warn!(
+                    "Couldn't read the next token transfer nonce of Sui: {:?}",
+                    e
+                );
+            }
+            if eth_chain_id.is_none() {
+                eth_chain_id = match eth_client.get_bridge_chain_id(eth_bridge_address).await {
+                    Ok(chain_id) => BridgeChainId::try_from(chain_id).ok(),
+                    Err(e) => {
+                        warn!("Couldn't read the bridge chain id of Eth: {:?}", e);
+                        None
+                    }
+                };
+            }
+            if let Some(chain_id) = eth_chain_id {
+                match eth_client
+                    .get_next_token_transfer_nonce(eth_bridge_address)
+                    .await
+                {
+                    Ok(next_nonce) => self.observe_next_nonce(chain_id, next_nonce),
+                    Err(e) => warn!(
+                        "Couldn't read the next token transfer nonce of Eth: {:?}",
+                        e
+                    ),
+                }
+            }
+        }
+    }
+
+    async fn refresh_sui<C>(&self, sui_client: &SuiClient<C>) -> BridgeResult<()>
+    where
+        C: SuiClientInner,
+    {
+        let summary = sui_client.get_bridge_summary().await?;
+        let chain_id =
+            BridgeChainId::try_from(summary.chain_id).map_err(|_| BridgeError::InvalidChainId)?;
+        if let Some((_, next_nonce)) = summary
+            .sequence_nums
+            .iter()
+            .find(|(message_type, _)| *message_type == BridgeActionType::TokenTransfer as u8)
+        {
+            self.observe_next_nonce(chain_id, *next_nonce);
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{get_test_eth_to_sui_bridge_action, get_test_sui_to_eth_bridge_action};
+    use crate::types::{EmergencyAction, EmergencyActionType};
+
+    #[test]
+    fn test_nonce_guard() {
+        let metrics = Arc::new(BridgeMetrics::for_testing());
+        let guard = NonceGuard::new(100, metrics.clone());
+        let sui_transfer = |nonce| {
+            get_test_sui_to_eth_bridge_action(None, None, Some(nonce), None, None, None, None)
+        };
+        // Nothing is refused before the nonces of the chain are known
+        guard.check(&sui_transfer(1_000_000)).unwrap();
+
+        guard.observe(&sui_transfer(9));
+        assert_eq!(guard.next_nonce(sui_transfer(0).chain_id()), Some(10));
+        guard.check(&sui_transfer(109)).unwrap();
+        assert!(matches!(
+            guard.check(&sui_transfer(110)),
+            Err(BridgeError::NonceTooFarAhead(_))
+        ));
+        // Known nonces don't move back
+        guard.observe_next_nonce(sui_transfer(0).chain_id(), 5);
+        assert_eq!(guard.next_nonce(sui_transfer(0).chain_id()), Some(10));
+
+        // Other chains and governance actions are not affected
+        guard
+            .check(&get_test_eth_to_sui_bridge_action(
+                Some(1_000_000),
+                None,
+                None,
+                None,
+            ))
+            .unwrap();
+        guard
+            .check(&BridgeAction::EmergencyAction(EmergencyAction {
+                chain_id: sui_transfer(0).chain_id(),
+                nonce: 1_000_000,
+                action_type: EmergencyActionType::Pause,
+            }))
+            .unwrap();
+        assert_eq!(
+            metrics
+                .signing_nonce_too_far_ahead
+                .with_label_values(&[&format!("{:?}", sui_transfer(0).chain_id())])
+                .get(),
+            1
+        );
+
+        // Claimed nonces are checked against the chain on the side of the request
+        guard.check_claimed(true, 109).unwrap();
+        assert!(matches!(
+            guard.check_claimed(true, 110),
+            Err(BridgeError::NonceTooFarAhead(_))
+        ));
+        guard.check_claimed(false, 1_000_000).unwrap();
+    }
+}
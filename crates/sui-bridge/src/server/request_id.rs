@@ -0,0 +1,268 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Request ids correlate the logs of one signature aggregation attempt across the
+//! client and the servers of the committee. The client sends the id of the attempt in
+//! the `x-request-id` header of every signing request. `propagate_request_id` serves the
+//! request in a span with the id, includes it in error bodies and echoes it in the
+//! response. Requests without the header are served as before.
+
+use axum::{extract::Request, http::HeaderValue, middleware::Next, response::Response};
+use tracing::{info_span, Instrument};
+
+pub const REQUEST_ID_HEADER: &str = "x-request-id";
+// Longer ids are ignored, they don't come from a bridge client
+const MAX_REQUEST_ID_LEN: usize = 128;
+
+tokio::task_local! {
+    static REQUEST_ID: String;
+}
+
+/// Returns a new id for an aggregation attempt.
+pub fn new_request_id() -> String {
+    uuid::Uuid::new_v4().to_string()
+}
+
+/// Returns the id of the request served by the current task, if the client sent one.
+pub fn current_request_id() -> Option<String> {
+    REQUEST_ID.try_with(|request_id| request_id.clone()).ok()
+}
+
+pub async fn propagate_request_id(request: Request, next: Next) -> Response {
+    let Some(request_id) = request
+        .headers()
+        .get(REQUEST_ID_HEADER)
+        .and_then(|value| value.to_str().ok())
+        .filter(|value| !value.is_empty() && value.len() <= MAX_REQUEST_ID_LEN)
+        .map(|value| value.to_owned())
+    else {
+        return next.run(request).await;
+    };
+    let span = info_span!("bridge_request", request_id = %request_id);
+    let mut response = REQUEST_ID
+        .scope(request_id.clone(), next.run(request).instrument(span))
+        .await;
+    // Unwrap safe: the value was read from a header
+    response.headers_mut().insert(
+        REQUEST_ID_HEADER,
+        HeaderValue::from_str(&request_id).unwrap(),
+    );
+    response
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::client::bridge_authority_aggregator::BridgeAuthorityAggregator;
+    use crate::client::bridge_client::BridgeClient;
+    use crate::error::BridgeError;
+    use crate::metrics::BridgeMetrics;
+    use crate::server::mock_handler::BridgeRequestMockHandler;
+    use crate::test_utils::{
+        get_test_authorities_and_run_mock_bridge_server, get_test_sui_to_eth_bridge_action,
+        sign_action_with_key,
+    };
+    use crate::types::BridgeCommittee;
+    use std::fmt::Debug;
+    use std::sync::{Arc, Mutex};
+    use sui_types::digests::TransactionDigest;
+    use tracing::field::{Field, Visit};
+    use tracing::span::{Attributes, Id};
+    use tracing::Subscriber;
+    use tracing_subscriber::layer::{Context, SubscriberExt};
+    use tracing_subscriber::Layer;
+
+    /// Records the `request_id` field of every span, with the span name.
+    #[derive(Clone, Default)]
+    struct RequestIdRecorder(Arc<Mutex<Vec<(&'static str, String)>>>);
+
+    impl RequestIdRecorder {
+        fn request_ids(&self, span_name: &str) -> Vec<String> {
+            self.0
+                .lock()
+                .unwrap()
+                .iter()
+                .filter(|(name, _)| *name == span_name)
+                .map(|(_, request_id)| request_id.clone())
+                .collect()
+        }
+    }
+
+    struct RequestIdVisitor(Option<String>);
+
+    impl Visit for RequestIdVisitor {
+        fn record_debug(&mut self, field: &Field, value: &dyn Debug) {
+            if field.name() == "request_id" {
+                self.0 = Some(format!("{:?}", value));
+            }
+        }
+    }
+
+    impl<S: Subscriber> Layer<S> for RequestIdRecorder {
+        fn on_new_span(&self, attrs: &Attributes<'_>, _id: &Id, _ctx: Context<'_, S>) {
+            let mut visitor = RequestIdVisitor(None);
+            attrs.record(&mut visitor);
+            if let Some(request_id) = visitor.0 {
+                self.0
+                    .lock()
+                    .unwrap()
+                    .push((attrs.metadata().name(), request_id));
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn test_request_id_round_trips() {
+        let recorder = RequestIdRecorder::default();
+        let _guard =
+            tracing::subscriber::set_default(tracing_subscriber::registry().with(recorder.clone()));
+
+        let mock = BridgeRequestMockHandler::new();
+        let (_handles, authorities, secrets) =
+            get_test_authorities_and_run_mock_bridge_server(vec![10000], vec![mock.clone()]);
+        let committee = Arc::new(BridgeCommittee::new(authorities).unwrap());
+        let client = BridgeClient::new(
+            committee.members().keys().next().unwrap().clone(),
+            committee.clone(),
+        )
+        .unwrap();
+        let base_url = committee
+            .members()
+            .values()
+            .next()
+            .unwrap()
+            .base_url
+            .clone();
+
+        let sui_tx_digest = TransactionDigest::random();
+        let action = get_test_sui_to_eth_bridge_action(
+            Some(sui_tx_digest),
+            Some(0),
+            None,
+            None,
+            None,
+            None,
+            None,
+        );
+        mock.add_sui_event_response(
+            sui_tx_digest,
+            0,
+            Ok(sign_action_with_key(&action, &secrets[0])),
+        );
+        let failing_tx_digest = TransactionDigest::random();
+        mock.add_sui_event_response(
+            failing_tx_digest,
+            0,
+            Err(BridgeError::NoBridgeEventsInTxPosition),
+        );
+
+        // The header is echoed
+        let http = reqwest::Client::new();
+        let response = http
+            .get(format!(
+                "{}/sign/bridge_tx/sui/eth/{}/0",
+                base_url, sui_tx_digest
+            ))
+            .header(REQUEST_ID_HEADER, "test-request-1")
+            .send()
+            .await
+            .unwrap();
+        assert!(response.status().is_success());
+        assert_eq!(
+            response.headers().get(REQUEST_ID_HEADER).unwrap(),
+            "test-request-1"
+        );
+
+        // and included in error bodies
+        let response = http
+            .get(format!(
+                "{}/sign/bridge_tx/sui/eth/{}/0",
+                base_url, failing_tx_digest
+            ))
+            .header(REQUEST_ID_HEADER, "test-request-2")
+            .send()
+            .await
+            .unwrap();
+        assert!(!response.status().is_success());
+        assert_eq!(
+            response.headers().get(REQUEST_ID_HEADER).unwrap(),
+            "test-request-2"
+        );
+        assert!(response.text().await.unwrap().contains("test-request-2"));
+
+        // Requests without the header are served without one
+        let response = http
+            .get(format!(
+                "{}/sign/bridge_tx/sui/eth/{}/0",
+                base_url, sui_tx_digest
+            ))
+            .send()
+            .await
+            .unwrap();
+        assert!(response.status().is_success());
+        assert!(response.headers().get(REQUEST_ID_HEADER).is_none());
+        assert_eq!(
+            recorder.request_ids("bridge_request"),
+            vec!["test-request-1".to_string(), "test-request-2".to_string()]
+        );
+
+        // The client sends the header
+        client
+            .request_sign_bridge_action_with_request_id(action, "test-request-3")
+            .await
+            .unwrap();
+        assert_eq!(
+            recorder.request_ids("bridge_request").last().unwrap(),
+            "test-request-3"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_aggregation_request_id_in_client_and_server_logs() {
+        let recorder = RequestIdRecorder::default();
+        let _guard =
+            tracing::subscriber::set_default(tracing_subscriber::registry().with(recorder.clone()));
+
+        let mock0 = BridgeRequestMockHandler::new();
+        let mock1 = BridgeRequestMockHandler::new();
+        let (_handles, authorities, secrets) = get_test_authorities_and_run_mock_bridge_server(
+            vec![5000, 5000],
+            vec![mock0.clone(), mock1.clone()],
+        );
+        let committee = BridgeCommittee::new(authorities).unwrap();
+        let agg = BridgeAuthorityAggregator::new(
+            Arc::new(committee),
+            Arc::new(BridgeMetrics::for_testing()),
+        );
+
+        let sui_tx_digest = TransactionDigest::random();
+        let action = get_test_sui_to_eth_bridge_action(
+            Some(sui_tx_digest),
+            Some(0),
+            None,
+            None,
+            None,
+            None,
+            None,
+        );
+        mock0.add_sui_event_response(
+            sui_tx_digest,
+            0,
+            Ok(sign_action_with_key(&action, &secrets[0])),
+        );
+        mock1.add_sui_event_response(
+            sui_tx_digest,
+            0,
+            Ok(sign_action_with_key(&action, &secrets[1])),
+        );
+        agg.request_committee_signatures(action).await.unwrap();
+
+        let client_request_ids = recorder.request_ids("aggregation");
+        assert_eq!(client_request_ids.len(), 1);
+        let server_request_ids = recorder.request_ids("bridge_request");
+        assert!(!server_request_ids.is_empty());
+        assert!(server_request_ids
+            .iter()
+            .all(|request_id| *request_id == client_request_ids[0]));
+    }
+}
@@ -0,0 +1,347 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Who sends the signing requests. A requester is identified by the subject of its client
+//! certificate when the server is reached over mTLS, else by the key that authenticated
+//! the request, else by its source IP. IPs are hashed with a salt drawn at
+//! boot, so they never show up in metrics or responses. The number of requesters with
+//! their own label is capped, to bound the cardinality of the metrics: the requesters
+//! beyond it share the `other` label. A requester without requests in the window gives up
+//! its label, and its metrics, to the next new requester.
+
+use crate::metrics::BridgeMetrics;
+use crate::slo::WindowedRatio;
+use axum::{
+    extract::{ConnectInfo, Request, State},
+    middleware::Next,
+    response::Response,
+};
+use fastcrypto::encoding::{Encoding, Hex};
+use fastcrypto::hash::{Blake2b256, HashFunction};
+use std::collections::HashMap;
+use std::net::{IpAddr, SocketAddr};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use sui_tls::rustls::pki_types::CertificateDer;
+use sui_tls::TlsConnectionInfo;
+
+pub const DEFAULT_MAX_REQUESTER_LABELS: usize = 50;
+pub const OTHER_REQUESTER: &str = "other";
+pub const UNKNOWN_REQUESTER: &str = "unknown";
+// Number of requesters in `/admin/errors/requesters`
+pub const REQUESTER_SUMMARY_SIZE: usize = 10;
+pub const REQUESTER_WINDOW: Duration = Duration::from_secs(5 * 60);
+const REQUESTER_WINDOW_BUCKET: Duration = Duration::from_secs(10);
+
+/// The key that authenticated the request, base64 encoded. `authenticate_request` adds
+/// it to the extensions of the requests it lets through.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AuthenticatedKey(pub String);
+
+/// Returns the subject of the client certificate, the first of `certificates`. None if
+/// it can't be parsed or its subject is empty.
+pub fn requester_from_certificates(certificates: &[CertificateDer]) -> Option<String> {
+    use x509_parser::{certificate::X509Certificate, prelude::FromDer};
+
+    let (_, cert) = X509Certificate::from_der(certificates.first()?.as_ref()).ok()?;
+    let subject = cert.subject().to_string();
+    (!subject.is_empty()).then_some(subject)
+}
+
+/// Requests in the window and how many of them failed, of one requester.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RequesterRate {
+    pub requester: String,
+    pub requests: u64,
+    pub errors: u64,
+}
+
+#[derive(Debug)]
+pub struct RequesterTracker {
+    salt: [u8; 32],
+    max_labels: AtomicUsize,
+    origin: Instant,
+    // By label, including `OTHER_REQUESTER`
+    requesters: Mutex<HashMap<String, WindowedRatio>>,
+}
+
+impl Default for RequesterTracker {
+    fn default() -> Self {
+        Self::new(DEFAULT_MAX_REQUESTER_LABELS, Instant::now())
+    }
+}
+
+impl RequesterTracker {
+    pub fn new(max_labels: usize, origin: Instant) -> Self {
+        Self {
+            salt: rand::random(),
+            max_labels: AtomicUsize::new(max_labels),
+            origin,
+            requesters: Mutex::new(HashMap::new()),
+        }
+    }
+
+    pub fn set_max_labels(&self, max_labels: usize) {
+        self.max_labels.store(max_labels, Ordering::Relaxed);
+    }
+
+    /// Returns the identity of the sender of `request`, see the module doc.
+    pub fn identify(&self, request: &Request) -> String {
+        let extensions = request.extensions();
+        if let Some(subject) = extensions
+            .get::<TlsConnectionInfo>()
+            .and_then(|info| info.peer_certificates())
+            .and_then(requester_from_certificates)
+        {
+            return format!("cert:{}", subject);
+        }
+        if let Some(AuthenticatedKey(key)) = extensions.get::<AuthenticatedKey>() {
+            return format!("key:{}", key);
+        }
+        match extensions.get::<ConnectInfo<SocketAddr>>() {
+            Some(ConnectInfo(address)) => format!("ip:{}", self.hash_ip(address.ip())),
+            None => UNKNOWN_REQUESTER.to_string(),
+        }
+    }
+
+    fn hash_ip(&self, ip: IpAddr) -> String {
+        let mut hasher = Blake2b256::default();
+        hasher.update(self.salt);
+        hasher.update(ip.to_string().as_bytes());
+        // Enough to tell requesters apart, without making the label long
+        Hex::encode(&hasher.finalize().digest[..8])
+    }
+
+    /// Returns the label of `requester` in the metrics, and the labels evicted to make room
+    /// for it. It's the requester itself while there is room for a new label, once the
+    /// labels of the requesters without requests in the window are evicted, and
+    /// `OTHER_REQUESTER` otherwise.
+    pub fn label(&self, requester: String, now: Instant) -> (String, Vec<String>) {
+        let mut requesters = self.requesters.lock().unwrap();
+        if requesters.contains_key(&requester) {
+            return (requester, vec![]);
+        }
+        let mut evicted = vec![];
+        requesters.retain(|label, window| {
+            let idle = label != OTHER_REQUESTER && window.counts(now).0 == 0;
+            if idle {
+                evicted.push(label.clone());
+            }
+            !idle
+        });
+        // The `other` label doesn't count towards the limit
+        let labels = requesters.len() - requesters.contains_key(OTHER_REQUESTER) as usize;
+        if labels < self.max_labels.load(Ordering::Relaxed) {
+            (requester, evicted)
+        } else {
+            (OTHER_REQUESTER.to_string(), evicted)
+        }
+    }
+
+    pub fn record(&self, label: &str, failed: bool, now: Instant) {
+        self.requesters
+            .lock()
+            .unwrap()
+            .entry(label.to_string())
+            .or_insert_with(|| {
+                WindowedRatio::new(REQUESTER_WINDOW, REQUESTER_WINDOW_BUCKET, self.origin)
+            })
+            .record(failed, now);
+    }
+
+    /// Returns the `k` requesters that sent the most requests in the window.
+    pub fn top(&self, k: usize, now: Instant) -> Vec<RequesterRate> {
+        let mut rates = self
+            .requesters
+            .lock()
+            .unwrap()
+            .iter_mut()
+            .map(|(requester, window)| {
+                let (requests, errors) = window.counts(now);
+                RequesterRate {
+                    requester: requester.clone(),
+                    requests,
+                    errors,
+                }
+            })
+            .filter(|rate| rate.requests > 0)
+            .collect::<Vec<_>>();
+        rates.sort_by(|a, b| {
+            b.requests
+                .cmp(&a.requests)
+                .then_with(|| a.requester.cmp(&b.requester))
+        });
+        rates.truncate(k);
+        rates
+    }
+}
+
+/// Counts the signing requests and the failed ones by requester. Runs after the request
+/// is authenticated.
+pub(crate) async fn count_requests_by_requester(
+    State(metrics): State<Arc<BridgeMetrics>>,
+    request: Request,
+    next: Next,
+) -> Response {
+    let tracker = &metrics.requesters;
+    let (label, evicted) = tracker.label(tracker.identify(&request), Instant::now());
+    for label in evicted {
+        // Already gone if the requester never failed
+        let _ = metrics
+            .requests_received_by_requester
+            .remove_label_values(&[&label]);
+        let _ = metrics
+            .err_requests_by_requester
+            .remove_label_values(&[&label]);
+    }
+    metrics
+        .requests_received_by_requester
+        .with_label_values(&[&label])
+        .inc();
+    let response = next.run(request).await;
+    let failed = !response.status().is_success();
+    if failed {
+        metrics
+            .err_requests_by_requester
+            .with_label_values(&[&label])
+            .inc();
+    }
+    tracker.record(&label, failed, Instant::now());
+    response
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn request_from(address: &str) -> Request {
+        let mut request = Request::new(axum::body::Body::empty());
+        request
+            .extensions_mut()
+            .insert(ConnectInfo(address.parse::<SocketAddr>().unwrap()));
+        request
+    }
+
+    #[test]
+    fn test_requester_labels_are_capped() {
+        let origin = Instant::now();
+        let tracker = RequesterTracker::new(2, origin);
+        for requester in ["a", "b", "c", "a", "d"] {
+            let (label, evicted) = tracker.label(requester.to_string(), origin);
+            assert!(evicted.is_empty());
+            tracker.record(&label, requester == "d", origin);
+        }
+        let top = tracker.top(REQUESTER_SUMMARY_SIZE, origin);
+        let rates = top
+            .iter()
+            .map(|rate| (rate.requester.as_str(), rate.requests, rate.errors))
+            .collect::<Vec<_>>();
+        assert_eq!(rates, vec![("a", 2, 0), ("other", 2, 1), ("b", 1, 0)]);
+
+        // Only the top requesters are summarized
+        assert_eq!(tracker.top(1, origin)[0].requester, "a");
+        // Raising the limit makes room for new requesters
+        tracker.set_max_labels(3);
+        assert_eq!(tracker.label("e".to_string(), origin).0, "e");
+
+        // Requests roll off the window
+        assert!(tracker.top(10, origin + REQUESTER_WINDOW).is_empty());
+    }
+
+    #[test]
+    fn test_idle_requester_labels_are_evicted() {
+        let origin = Instant::now();
+        let tracker = RequesterTracker::new(2, origin);
+        for requester in ["a", "b"] {
+            let (label, _) = tracker.label(requester.to_string(), origin);
+            tracker.record(&label, false, origin);
+        }
+        let later = origin + REQUESTER_WINDOW / 2;
+        tracker.record("b", false, later);
+        assert_eq!(
+            tracker.label("c".to_string(), later),
+            (OTHER_REQUESTER.to_string(), vec![])
+        );
+        tracker.record(OTHER_REQUESTER, false, later);
+
+        // Once the requests of `a` rolled off the window, its label goes to a new requester
+        let now = origin + REQUESTER_WINDOW;
+        assert_eq!(
+            tracker.label("c".to_string(), now),
+            ("c".to_string(), vec!["a".to_string()])
+        );
+        tracker.record("c", false, now);
+        assert_eq!(
+            tracker.label("d".to_string(), now),
+            (OTHER_REQUESTER.to_string(), vec![])
+        );
+        let requesters = tracker
+            .top(REQUESTER_SUMMARY_SIZE, now)
+            .into_iter()
+            .map(|rate| rate.requester)
+            .collect::<Vec<_>>();
+        assert_eq!(requesters, vec!["b", "c", "other"]);
+    }
+
+    #[test]
+    fn test_source_ips_are_hashed_with_a_salt() {
+        let tracker = RequesterTracker::default();
+        let requester = tracker.identify(&request_from("10.1.2.3:40000"));
+        assert!(requester.starts_with("ip:"));
+        assert!(!requester.contains("10.1.2.3"));
+        // The port doesn't matter, the IP does
+        assert_eq!(tracker.identify(&request_from("10.1.2.3:40001")), requester);
+        assert_ne!(tracker.identify(&request_from("10.1.2.4:40000")), requester);
+        // Another boot, another salt
+        assert_ne!(
+            RequesterTracker::default().identify(&request_from("10.1.2.3:40000")),
+            requester
+        );
+        assert_eq!(
+            tracker.identify(&Request::new(axum::body::Body::empty())),
+            UNKNOWN_REQUESTER
+        );
+    }
+
+    #[test]
+    fn test_requester_identity_precedence() {
+        let tracker = RequesterTracker::default();
+        let mut request = request_from("10.1.2.3:40000");
+        request
+            .extensions_mut()
+            .insert(AuthenticatedKey("a2V5".to_string()));
+        // The authenticated key is preferred to the source IP
+        assert_eq!(tracker.identify(&request), "key:a2V5");
+    }
+
+    #[test]
+    fn test_requester_from_client_certificate() {
+        let key_pair = rcgen::KeyPair::generate().unwrap();
+        let mut params = rcgen::CertificateParams::new(vec!["bridge".to_string()]).unwrap();
+        params.distinguished_name = rcgen::DistinguishedName::new();
+        params
+            .distinguished_name
+            .push(rcgen::DnType::CommonName, "aggregator-1");
+        params
+            .distinguished_name
+            .push(rcgen::DnType::OrganizationName, "Validator A");
+        let cert = params.self_signed(&key_pair).unwrap();
+        let der = cert.der().clone();
+        assert_eq!(
+            requester_from_certificates(&[der]).as_deref(),
+            Some("CN=aggregator-1, O=Validator A")
+        );
+
+        // Without a subject, or not a certificate
+        let mut params = rcgen::CertificateParams::new(vec!["bridge".to_string()]).unwrap();
+        params.distinguished_name = rcgen::DistinguishedName::new();
+        let cert = params.self_signed(&key_pair).unwrap();
+        assert_eq!(requester_from_certificates(&[cert.der().clone()]), None);
+        assert_eq!(
+            requester_from_certificates(&[CertificateDer::from(vec![1, 2, 3])]),
+            None
+        );
+        assert_eq!(requester_from_certificates(&[]), None);
+    }
+}
@@ -0,0 +1,220 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Runs the signing server on its own tokio runtime. While the client catches up with a
+//! backlog, its syncers and executor keep every worker of the main runtime busy, and
+//! the signing requests that other committee members wait on are queued behind them.
+//! With `server_dedicated_runtime` the server, and the gRPC server sharing its handler,
+//! run on a `ServerRuntime` instead, whose workers serve nothing else.
+//!
+//! The depth of the task queues is not exposed by tokio without `tokio_unstable`, so
+//! `RuntimeProbe` measures how long a task spawned on each runtime waits to be polled
+//! instead, in `bridge_runtime_scheduling_delay_seconds`.
+
+use crate::metrics::BridgeMetrics;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::runtime::{EnterGuard, Handle, Runtime};
+use tokio::time::{self, MissedTickBehavior};
+
+pub const DEFAULT_SERVER_RUNTIME_WORKER_THREADS: usize = 2;
+pub const DEFAULT_RUNTIME_PROBE_INTERVAL: Duration = Duration::from_secs(1);
+/// Label of the runtime that everything but the server runs on.
+pub const MAIN_RUNTIME: &str = "main";
+/// Label of the `ServerRuntime`.
+pub const SERVER_RUNTIME: &str = "server";
+
+// Tasks of the server still running after this long are dropped on shutdown
+const SERVER_RUNTIME_SHUTDOWN_TIMEOUT: Duration = Duration::from_secs(5);
+
+pub struct ServerRuntime {
+    // Only None once shut down
+    runtime: Option<Runtime>,
+}
+
+impl ServerRuntime {
+    pub fn new(worker_threads: usize) -> std::io::Result<Self> {
+        let runtime = tokio::runtime::Builder::new_multi_thread()
+            .worker_threads(worker_threads)
+            .thread_name("bridge-server")
+            .enable_all()
+            .build()?;
+        Ok(Self {
+            runtime: Some(runtime),
+        })
+    }
+
+    pub fn handle(&self) -> &Handle {
+        self.runtime().handle()
+    }
+
+    /// Tasks spawned with `tokio::spawn` while the guard is held run on this runtime.
+    pub fn enter(&self) -> EnterGuard<'_> {
+        self.runtime().enter()
+    }
+
+    /// Stops the tasks of the runtime, waiting for them to yield for up to
+    /// `SERVER_RUNTIME_SHUTDOWN_TIMEOUT`.
+    pub async fn shutdown(mut self) {
+        let runtime = self
+            .runtime
+            .take()
+            .expect("Runtime is only taken on shutdown");
+        // Shutting down blocks, which is not allowed on the threads of a runtime
+        let _ = tokio::task::spawn_blocking(move || {
+            runtime.shutdown_timeout(SERVER_RUNTIME_SHUTDOWN_TIMEOUT)
+        })
+        .await;
+    }
+
+    fn runtime(&self) -> &Runtime {
+        self.runtime
+            .as_ref()
+            .expect("Runtime is only taken on shutdown")
+    }
+}
+
+impl Drop for ServerRuntime {
+    fn drop(&mut self) {
+        // Dropping a runtime blocks, which panics when dropped from another runtime
+        if let Some(runtime) = self.runtime.take() {
+            runtime.shutdown_background();
+        }
+    }
+}
+
+/// How long a task spawned on the runtime of `handle` waits before it is polled.
+pub async fn scheduling_delay(handle: &Handle) -> Duration {
+    let spawned_at = Instant::now();
+    handle
+        .spawn(async move { spawned_at.elapsed() })
+        .await
+        // Only fails when the runtime is shutting down
+        .unwrap_or_default()
+}
+
+/// Periodically reports the number of workers and the scheduling delay of a runtime.
+pub struct RuntimeProbe {
+    name: &'static str,
+    handle: Handle,
+    interval: Duration,
+    metrics: Arc<BridgeMetrics>,
+}
+
+impl RuntimeProbe {
+    pub fn new(
+        name: &'static str,
+        handle: Handle,
+        interval: Duration,
+        metrics: Arc<BridgeMetrics>,
+    ) -> Self {
+        Self {
+            name,
+            handle,
+            interval,
+            metrics,
+        }
+    }
+
+    pub async fn run(self) {
+        self.metrics
+            .runtime_workers
+            .with_label_values(&[self.name])
+            .set(self.handle.metrics().num_workers() as i64);
+        let mut interval = time::interval(self.interval);
+        interval.set_missed_tick_behavior(MissedTickBehavior::Delay);
+        loop {
+            interval.tick().await;
+            let delay = scheduling_delay(&self.handle).await;
+            self.metrics
+                .runtime_scheduling_delay_seconds
+                .with_label_values(&[self.name])
+                .observe(delay.as_secs_f64());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicBool, Ordering};
+
+    // Signing requests wait for a worker of the main runtime while a backlog keeps them
+    // busy, but not on the server runtime.
+    #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+    async fn test_server_runtime_under_backlog() {
+        let server_runtime = ServerRuntime::new(1).unwrap();
+        let main = Handle::current();
+        let backlog_done = Arc::new(AtomicBool::new(false));
+        // Tasks that hold the workers for a while between yields, like the executor
+        // and the syncers processing a backlog
+        let backlog = (0..8)
+            .map(|_| {
+                let backlog_done = backlog_done.clone();
+                tokio::spawn(async move {
+                    while !backlog_done.load(Ordering::Relaxed) {
+                        std::thread::sleep(Duration::from_millis(10));
+                        tokio::task::yield_now().await;
+                    }
+                })
+            })
+            .collect::<Vec<_>>();
+
+        let p99 = |mut delays: Vec<Duration>| {
+            delays.sort();
+            delays[delays.len() * 99 / 100]
+        };
+        let mut main_delays = vec![];
+        let mut server_delays = vec![];
+        for _ in 0..20 {
+            main_delays.push(scheduling_delay(&main).await);
+            server_delays.push(scheduling_delay(server_runtime.handle()).await);
+        }
+        backlog_done.store(true, Ordering::Relaxed);
+        for task in backlog {
+            task.await.unwrap();
+        }
+
+        let (main_p99, server_p99) = (p99(main_delays), p99(server_delays));
+        assert!(
+            server_p99 * 4 < main_p99,
+            "server runtime p99 {:?}, main runtime p99 {:?}",
+            server_p99,
+            main_p99
+        );
+        server_runtime.shutdown().await;
+    }
+
+    #[tokio::test]
+    async fn test_runtime_probe() {
+        let metrics = Arc::new(BridgeMetrics::for_testing());
+        let server_runtime = ServerRuntime::new(3).unwrap();
+        let probe = tokio::spawn(
+            RuntimeProbe::new(
+                SERVER_RUNTIME,
+                server_runtime.handle().clone(),
+                Duration::from_millis(10),
+                metrics.clone(),
+            )
+            .run(),
+        );
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        probe.abort();
+        assert_eq!(
+            metrics
+                .runtime_workers
+                .with_label_values(&[SERVER_RUNTIME])
+                .get(),
+            3
+        );
+        assert!(
+            metrics
+                .runtime_scheduling_delay_seconds
+                .with_label_values(&[SERVER_RUNTIME])
+                .get_sample_count()
+                > 0
+        );
+        // Dropped without shutdown, from a runtime
+        drop(server_runtime);
+    }
+}
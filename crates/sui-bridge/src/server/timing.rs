@@ -0,0 +1,166 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Per-stage timing of signing requests. `log_slow_requests` gives each signing request
+//! a `RequestTiming` that the handler and `SignerWithCache` record stage durations into.
+//! When the request completes, the stage durations are exported as histograms, and the
+//! request is logged with its breakdown and counted if it took longer than the threshold.
+
+use crate::metrics::BridgeMetrics;
+use axum::{
+    extract::{MatchedPath, Request, State},
+    middleware::Next,
+    response::Response,
+};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tracing::warn;
+
+pub const DEFAULT_SLOW_REQUEST_THRESHOLD: Duration = Duration::from_secs(2);
+
+tokio::task_local! {
+    static REQUEST_TIMING: RequestTiming;
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RequestStage {
+    // Finding the cached result, including waiting for a concurrent request of the same key
+    CacheLookup,
+    // Waiting for a free verification slot of the chain
+    VerificationQueue,
+    Verification,
+    Signing,
+}
+
+impl RequestStage {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            RequestStage::CacheLookup => "cache_lookup",
+            RequestStage::VerificationQueue => "verification_queue",
+            RequestStage::Verification => "verification",
+            RequestStage::Signing => "signing",
+        }
+    }
+}
+
+/// Durations of the stages of one request. Clones record into the same request.
+#[derive(Debug, Clone, Default)]
+pub struct RequestTiming(Arc<Mutex<Vec<(RequestStage, Duration)>>>);
+
+impl RequestTiming {
+    /// Returns the timing of the request served by the current task, if any.
+    pub fn current() -> Option<Self> {
+        REQUEST_TIMING.try_with(|timing| timing.clone()).ok()
+    }
+
+    pub fn record(&self, stage: RequestStage, duration: Duration) {
+        self.0.lock().unwrap().push((stage, duration));
+    }
+
+    pub fn stages(&self) -> Vec<(RequestStage, Duration)> {
+        self.0.lock().unwrap().clone()
+    }
+}
+
+/// Formats the stage durations of a request, e.g. `cache_lookup=1ms verification=2502ms`.
+pub fn format_breakdown(stages: &[(RequestStage, Duration)]) -> String {
+    stages
+        .iter()
+        .map(|(stage, duration)| format!("{}={}ms", stage.as_str(), duration.as_millis()))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+pub struct SlowRequestLogger {
+    threshold: Duration,
+    metrics: Arc<BridgeMetrics>,
+}
+
+impl SlowRequestLogger {
+    pub fn new(threshold: Duration, metrics: Arc<BridgeMetrics>) -> Self {
+        Self { threshold, metrics }
+    }
+}
+
+pub async fn log_slow_requests(
+    State(logger): State<Arc<SlowRequestLogger>>,
+    request: Request,
+    next: Next,
+) -> Response {
+    // The route rather than the full path, which contains the request arguments
+    let route = request
+        .extensions()
+        .get::<MatchedPath>()
+        .map(|path| path.as_str().to_owned())
+        .unwrap_or_default();
+    let timing = RequestTiming::default();
+    let start = Instant::now();
+    let response = REQUEST_TIMING
+        .scope(timing.clone(), next.run(request))
+        .await;
+    let elapsed = start.elapsed();
+    logger.metrics.slo.record_signing_request(
+        elapsed,
+        response.status().is_server_error(),
+        Instant::now(),
+    );
+
+    let stages = timing.stages();
+    for (stage, duration) in &stages {
+        logger
+            .metrics
+            .request_stage_latency
+            .with_label_values(&[stage.as_str()])
+            .observe(duration.as_secs_f64());
+    }
+    if elapsed > logger.threshold {
+        logger
+            .metrics
+            .slow_requests
+            .with_label_values(&[&route])
+            .inc();
+        warn!(
+            route,
+            status = response.status().as_u16(),
+            "Slow signing request took {}ms: {}",
+            elapsed.as_millis(),
+            format_breakdown(&stages)
+        );
+    }
+    response
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_breakdown() {
+        assert_eq!(format_breakdown(&[]), "");
+        assert_eq!(
+            format_breakdown(&[
+                (RequestStage::CacheLookup, Duration::from_micros(1500)),
+                (RequestStage::Verification, Duration::from_millis(2502)),
+                (RequestStage::Signing, Duration::from_millis(3)),
+            ]),
+            "cache_lookup=1ms verification=2502ms signing=3ms"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_request_timing_is_task_scoped() {
+        assert!(RequestTiming::current().is_none());
+        let timing = RequestTiming::default();
+        REQUEST_TIMING
+            .scope(timing.clone(), async {
+                RequestTiming::current()
+                    .unwrap()
+                    .record(RequestStage::Signing, Duration::from_millis(1));
+            })
+            .await;
+        assert_eq!(
+            timing.stages(),
+            vec![(RequestStage::Signing, Duration::from_millis(1))]
+        );
+    }
+}
@@ -0,0 +1,376 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Warms up the signing caches before the server starts. Right after a restart the
+//! caches are empty, and the aggregators re-request the signatures of the latest
+//! transfers all at once, each one verified against the fullnodes. `SignerWarmUp`
+//! fetches the latest token deposits on both chains and runs them through the handler,
+//! which verifies and signs them as if they were requested, so that those requests
+//! are served from the cache.
+
+use crate::abi::{EthBridgeEvent, EthSuiBridgeEvents};
+use crate::config::WarmUpConfig;
+use crate::error::BridgeResult;
+use crate::eth_client::EthClient;
+use crate::eth_syncer::ETH_LOG_QUERY_MAX_BLOCK_RANGE;
+use crate::events::{init_all_struct_tags, SuiToEthTokenBridgeV1};
+use crate::server::handler::{BridgeRequestHandler, BridgeRequestHandlerTrait};
+use crate::sui_client::{SuiClient, SuiClientInner};
+use ethers::providers::JsonRpcClient;
+use ethers::types::{Address as EthAddress, TxHash};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::time;
+use tracing::{debug, info, warn};
+
+pub const DEFAULT_WARM_UP_TRANSFERS_PER_DIRECTION: u64 = 100;
+pub const DEFAULT_WARM_UP_TIMEOUT: Duration = Duration::from_secs(30);
+// Eth deposits are looked for in this many blocks before the last finalized block
+const ETH_WARM_UP_MAX_LOOKBACK_BLOCKS: u64 = 10_000;
+
+pub struct SignerWarmUp<C, P> {
+    handler: Arc<BridgeRequestHandler>,
+    sui_client: Arc<SuiClient<C>>,
+    eth_client: Arc<EthClient<P>>,
+    eth_bridge_proxy_address: EthAddress,
+    transfers_per_direction: usize,
+    timeout: Duration,
+}
+
+impl<C, P> SignerWarmUp<C, P>
+where
+    C: SuiClientInner + 'static,
+    P: JsonRpcClient + 'static,
+{
+    pub fn new(
+        handler: Arc<BridgeRequestHandler>,
+        sui_client: Arc<SuiClient<C>>,
+        eth_client: Arc<EthClient<P>>,
+        eth_bridge_proxy_address: EthAddress,
+        config: &WarmUpConfig,
+    ) -> Self {
+        Self {
+            handler,
+            sui_client,
+            eth_client,
+            eth_bridge_proxy_address,
+            transfers_per_direction: config
+                .transfers_per_direction
+                .unwrap_or(DEFAULT_WARM_UP_TRANSFERS_PER_DIRECTION)
+                as usize,
+            timeout: config
+                .timeout_seconds
+                .map(Duration::from_secs)
+                .unwrap_or(DEFAULT_WARM_UP_TIMEOUT),
+        }
+    }
+
+    /// Warms up the signing caches, giving up after the timeout. Failures are only
+    /// logged, the server then starts with colder caches.
+    pub async fn run(self) {
+        if self.transfers_per_direction == 0 {
+            return;
+        }
+        info!(
+            "Warming up signing caches with the latest {} transfers per direction",
+            self.transfers_per_direction
+        );
+        let start = Instant::now();
+        match time::timeout(
+            self.timeout,
+            futures::future::join(self.warm_up_sui(), self.warm_up_eth()),
+        )
+        .await
+        {
+            Ok((sui_signed, eth_signed)) => info!(
+                "Warmed up signing caches with {} Sui and {} Eth transfers in {:?}",
+                sui_signed,
+                eth_signed,
+                start.elapsed()
+            ),
+            Err(_) => warn!(
+                "Warm-up did not finish within {:?}, starting with partially warm signing caches",
+                self.timeout
+            ),
+        }
+    }
+
+    // Returns the number of transfers signed
+    async fn warm_up_sui(&self) -> usize {
+        init_all_struct_tags();
+        let event_type = SuiToEthTokenBridgeV1.get().unwrap().clone();
+        let events = match self
+            .sui_client
+            .query_latest_events_by_type(event_type, self.transfers_per_direction)
+            .await
+        {
+            Ok(events) => events,
+            Err(e) => {
+                warn!(
+                    "Failed to query latest Sui token deposits to warm up: {:?}",
+                    e
+                );
+                return 0;
+            }
+        };
+        let mut signed = 0;
+        for event in events {
+            let (tx_digest, event_idx) = (event.id.tx_digest, event.id.event_seq as u16);
+            match self
+                .handler
+                .handle_sui_tx_digest(tx_digest.to_string(), event_idx, None)
+                .await
+            {
+                Ok(_) => signed += 1,
+                Err(e) => debug!(
+                    "Failed to warm up Sui transfer {:?} {}: {:?}",
+                    tx_digest, event_idx, e
+                ),
+            }
+        }
+        signed
+    }
+
+    // Returns the number of transfers signed
+    async fn warm_up_eth(&self) -> usize {
+        let deposits = match self.get_latest_eth_deposits().await {
+            Ok(deposits) => deposits,
+            Err(e) => {
+                warn!(
+                    "Failed to query latest Eth token deposits to warm up: {:?}",
+                    e
+                );
+                return 0;
+            }
+        };
+        let mut signed = 0;
+        for (tx_hash, event_idx) in deposits {
+            match self
+                .handler
+                .handle_eth_tx_hash(format!("{:?}", tx_hash), event_idx, None)
+                .await
+            {
+                Ok(_) => signed += 1,
+                Err(e) => debug!(
+                    "Failed to warm up Eth transfer {:?} {}: {:?}",
+                    tx_hash, event_idx, e
+                ),
+            }
+        }
+        signed
+    }
+
+    // Scans back from the last finalized block until enough deposits are found,
+    // returns them newest first
+    async fn get_latest_eth_deposits(&self) -> BridgeResult<Vec<(TxHash, u16)>> {
+        let mut end_block = self.eth_client.get_last_finalized_block_id().await?;
+        let lowest_block = end_block.saturating_sub(ETH_WARM_UP_MAX_LOOKBACK_BLOCKS);
+        let mut deposits = vec![];
+        loop {
+            let start_block = end_block
+                .saturating_sub(ETH_LOG_QUERY_MAX_BLOCK_RANGE - 1)
+                .max(lowest_block);
+            let logs = self
+                .eth_client
+                .get_events_in_range(self.eth_bridge_proxy_address, start_block, end_block)
+                .await?;
+            for log in logs.iter().rev() {
+                if let Some(EthBridgeEvent::EthSuiBridgeEvents(
+                    EthSuiBridgeEvents::TokensDepositedFilter(_),
+                )) = EthBridgeEvent::try_from_eth_log(log)
+                {
+                    deposits.push((log.tx_hash, log.log_index_in_tx));
+                    if deposits.len() >= self.transfers_per_direction {
+                        return Ok(deposits);
+                    }
+                }
+            }
+            if start_block <= lowest_block {
+                return Ok(deposits);
+            }
+            end_block = start_block - 1;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::crypto::BridgeAuthorityKeyPair;
+    use crate::eth_mock_provider::EthMockProvider;
+    use crate::events::MoveTokenDepositedEvent;
+    use crate::metrics::BridgeMetrics;
+    use crate::server::governance_verifier::GovernanceVerifier;
+    use crate::server::handler::VerificationLimits;
+    use crate::server::listener::ListenConfig;
+    use crate::server::{run_server, BridgeNodePublicMetadata, DEFAULT_GOVERNANCE_BODY_LIMIT};
+    use crate::sui_mock_client::SuiMockClient;
+    use crate::test_utils::{get_test_log_and_action, mock_get_logs, mock_last_finalized_block};
+    use crate::types::BridgeActionType;
+    use crate::utils::wait_for_server_to_be_up;
+    use std::collections::HashSet;
+    use std::net::SocketAddr;
+    use sui_config::local_ip_utils::get_available_port;
+    use sui_json_rpc_types::SuiEvent;
+    use sui_types::base_types::SuiAddress;
+    use sui_types::bridge::{BridgeChainId, TOKEN_ID_USDC};
+    use sui_types::crypto::get_key_pair;
+
+    fn test_sui_deposit_event() -> SuiEvent {
+        init_all_struct_tags();
+        let emitted_event = MoveTokenDepositedEvent {
+            seq_num: 1,
+            source_chain: BridgeChainId::SuiCustom as u8,
+            sender_address: SuiAddress::random_for_testing_only().to_vec(),
+            target_chain: BridgeChainId::EthCustom as u8,
+            target_address: EthAddress::random().as_bytes().to_vec(),
+            token_type: TOKEN_ID_USDC,
+            amount_sui_adjusted: 12345,
+        };
+        let mut sui_event = SuiEvent::random_for_testing();
+        sui_event.type_ = SuiToEthTokenBridgeV1.get().unwrap().clone();
+        sui_event.bcs = bcs::to_bytes(&emitted_event).unwrap();
+        sui_event.id.event_seq = 0;
+        sui_event
+    }
+
+    fn test_handler(
+        sui_client: Arc<SuiClient<SuiMockClient>>,
+        eth_client: Arc<EthClient<EthMockProvider>>,
+        metrics: Arc<BridgeMetrics>,
+    ) -> Arc<BridgeRequestHandler> {
+        let (_, kp): (_, BridgeAuthorityKeyPair) = get_key_pair();
+        Arc::new(BridgeRequestHandler::new(
+            Arc::new(kp),
+            sui_client,
+            eth_client,
+            0,
+            GovernanceVerifier::default(),
+            HashSet::from(BridgeActionType::ALL),
+            VerificationLimits::default(),
+            None,
+            None,
+            metrics,
+        ))
+    }
+
+    #[tokio::test]
+    async fn test_warm_up_populates_signer_caches() {
+        telemetry_subscribers::init_for_testing();
+        let registry = prometheus::Registry::new();
+        mysten_metrics::init_metrics(&registry);
+        let metrics = Arc::new(BridgeMetrics::for_testing());
+
+        let sui_client_mock = SuiMockClient::default();
+        let sui_event = test_sui_deposit_event();
+        let sui_tx_digest = sui_event.id.tx_digest;
+        sui_client_mock.add_events_by_tx_digest(sui_tx_digest, vec![sui_event.clone()]);
+        sui_client_mock.set_latest_events(
+            SuiToEthTokenBridgeV1.get().unwrap().clone(),
+            vec![sui_event],
+        );
+        let sui_client = Arc::new(SuiClient::new_for_testing(sui_client_mock));
+
+        let proxy_address = EthAddress::random();
+        let eth_mock_provider = EthMockProvider::default();
+        let eth_tx_hash = TxHash::random();
+        let (log, _) = get_test_log_and_action(proxy_address, eth_tx_hash, 0);
+        mock_last_finalized_block(&eth_mock_provider, 100);
+        mock_get_logs(&eth_mock_provider, proxy_address, 0, 100, vec![log]);
+        let eth_client = Arc::new(EthClient::new_mocked(
+            eth_mock_provider,
+            HashSet::from_iter(vec![proxy_address]),
+        ));
+
+        let handler = test_handler(sui_client.clone(), eth_client.clone(), metrics.clone());
+        SignerWarmUp::new(
+            handler.clone(),
+            sui_client,
+            eth_client,
+            proxy_address,
+            &WarmUpConfig {
+                transfers_per_direction: Some(10),
+                timeout_seconds: Some(10),
+            },
+        )
+        .run()
+        .await;
+        let cache_hits = |verifier: &str| {
+            metrics
+                .signer_with_cache_hit
+                .with_label_values(&[verifier])
+                .get()
+        };
+        assert_eq!(cache_hits("SuiActionVerifier"), 0);
+        assert_eq!(cache_hits("EthActionVerifier"), 0);
+
+        // Requests of the warmed up transfers hit the caches
+        handler
+            .handle_sui_tx_digest(sui_tx_digest.to_string(), 0, None)
+            .await
+            .unwrap();
+        handler
+            .handle_eth_tx_hash(format!("{:?}", eth_tx_hash), 0, None)
+            .await
+            .unwrap();
+        assert_eq!(cache_hits("SuiActionVerifier"), 1);
+        assert_eq!(cache_hits("EthActionVerifier"), 1);
+    }
+
+    #[tokio::test]
+    async fn test_server_serves_when_warm_up_stalls() {
+        telemetry_subscribers::init_for_testing();
+        let registry = prometheus::Registry::new();
+        mysten_metrics::init_metrics(&registry);
+        let metrics = Arc::new(BridgeMetrics::for_testing());
+
+        // Verifying the Sui transfer takes much longer than the warm-up timeout
+        let sui_client_mock = SuiMockClient::default();
+        let sui_event = test_sui_deposit_event();
+        sui_client_mock.add_events_by_tx_digest(sui_event.id.tx_digest, vec![sui_event.clone()]);
+        sui_client_mock.set_events_by_tx_digest_delay(Duration::from_secs(30));
+        sui_client_mock.set_latest_events(
+            SuiToEthTokenBridgeV1.get().unwrap().clone(),
+            vec![sui_event],
+        );
+        let sui_client = Arc::new(SuiClient::new_for_testing(sui_client_mock));
+        // Eth deposits can't be queried at all
+        let eth_client = Arc::new(EthClient::new_mocked(
+            EthMockProvider::default(),
+            HashSet::new(),
+        ));
+        let handler = test_handler(sui_client.clone(), eth_client.clone(), metrics.clone());
+
+        let start = Instant::now();
+        SignerWarmUp::new(
+            handler.clone(),
+            sui_client,
+            eth_client,
+            EthAddress::random(),
+            &WarmUpConfig {
+                transfers_per_direction: Some(10),
+                timeout_seconds: Some(1),
+            },
+        )
+        .run()
+        .await;
+        let port = get_available_port("127.0.0.1");
+        let _server = run_server(
+            &ListenConfig::new(SocketAddr::from(([127, 0, 0, 1], port))),
+            handler,
+            metrics,
+            Arc::new(BridgeNodePublicMetadata::empty_for_testing()),
+            None,
+            Duration::from_secs(2),
+            DEFAULT_GOVERNANCE_BODY_LIMIT,
+            None,
+            None,
+            None,
+            None,
+        );
+        wait_for_server_to_be_up(format!("http://127.0.0.1:{}/ping", port), 5)
+            .await
+            .unwrap();
+        assert!(start.elapsed() < Duration::from_secs(10));
+    }
+}
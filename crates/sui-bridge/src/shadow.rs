@@ -0,0 +1,212 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Shadow mode runs the client of a committee node against the production chains
+//! without effects, e.g. to try a new release before upgrading. The executor requests
+//! signatures and builds the transaction of every action as usual, but hands it to
+//! `ShadowComparator` instead of submitting it. The comparator waits for the committee to
+//! process the action on chain and counts whether the chain agrees with what this node
+//! would have done.
+
+use crate::metrics::BridgeMetrics;
+use crate::storage::BridgeOrchestratorTables;
+use crate::sui_client::{SuiClient, SuiClientInner};
+use crate::types::{BridgeAction, BridgeActionStatus};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use sui_types::digests::TransactionDigest;
+use tracing::{info, warn};
+
+pub const DEFAULT_SHADOW_CHECK_INTERVAL: Duration = Duration::from_secs(10);
+// The committee executes actions within minutes once they are final on the source chain
+pub const DEFAULT_SHADOW_COMPARISON_TIMEOUT: Duration = Duration::from_secs(30 * 60);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ShadowOutcome {
+    /// The action was approved or claimed on chain, as this node would have done
+    Agreement,
+    /// This node would have executed the action, but the chain did not in time
+    Divergence,
+    /// The chain has no record to compare to, e.g. for governance actions
+    Unverified,
+}
+
+impl ShadowOutcome {
+    fn as_str(&self) -> &'static str {
+        match self {
+            ShadowOutcome::Agreement => "agreement",
+            ShadowOutcome::Divergence => "divergence",
+            ShadowOutcome::Unverified => "unverified",
+        }
+    }
+}
+
+pub struct ShadowComparator<C> {
+    sui_client: Arc<SuiClient<C>>,
+    store: Arc<BridgeOrchestratorTables>,
+    check_interval: Duration,
+    timeout: Duration,
+    metrics: Arc<BridgeMetrics>,
+}
+
+impl<C> ShadowComparator<C>
+where
+    C: SuiClientInner + 'static,
+{
+    pub fn new(
+        sui_client: Arc<SuiClient<C>>,
+        store: Arc<BridgeOrchestratorTables>,
+        metrics: Arc<BridgeMetrics>,
+    ) -> Self {
+        Self {
+            sui_client,
+            store,
+            check_interval: DEFAULT_SHADOW_CHECK_INTERVAL,
+            timeout: DEFAULT_SHADOW_COMPARISON_TIMEOUT,
+            metrics,
+        }
+    }
+
+    /// Overrides how often the chain is checked, and for how long, before an action
+    /// that was not executed on chain is a divergence.
+    pub fn with_check_interval(mut self, check_interval: Duration, timeout: Duration) -> Self {
+        self.check_interval = check_interval;
+        self.timeout = timeout;
+        self
+    }
+
+    /// Compares `action`, which this node would have executed with the transaction
+    /// `tx_digest`, with the chain, and then removes it from the pending actions.
+    pub async fn compare(
+        &self,
+        action: BridgeAction,
+        tx_digest: TransactionDigest,
+    ) -> ShadowOutcome {
+        let outcome = self.wait_for_onchain_status(&action).await;
+        match outcome {
+            ShadowOutcome::Agreement => {
+                info!(
+                    ?tx_digest,
+                    "Shadow: action was executed on chain: {:?}", action
+                )
+            }
+            ShadowOutcome::Divergence => warn!(
+                ?tx_digest,
+                "Shadow: action was not executed on chain within {:?}: {:?}", self.timeout, action
+            ),
+            ShadowOutcome::Unverified => info!(
+                ?tx_digest,
+                "Shadow: no onchain record to compare action to: {:?}", action
+            ),
+        }
+        self.metrics
+            .shadow_comparisons
+            .with_label_values(&[outcome.as_str()])
+            .inc();
+        self.store
+            .mark_actions_executed(&[action])
+            .unwrap_or_else(|e| panic!("Write to DB should not fail: {:?}", e));
+        outcome
+    }
+
+    async fn wait_for_onchain_status(&self, action: &BridgeAction) -> ShadowOutcome {
+        if !matches!(
+            action,
+            BridgeAction::SuiToEthBridgeAction(_) | BridgeAction::EthToSuiBridgeAction(_)
+        ) {
+            return ShadowOutcome::Unverified;
+        }
+        let deadline = Instant::now() + self.timeout;
+        loop {
+            let status = self
+                .sui_client
+                .get_token_transfer_action_onchain_status_until_success(
+                    action.chain_id() as u8,
+                    action.seq_number(),
+                )
+                .await;
+            match status {
+                BridgeActionStatus::Approved | BridgeActionStatus::Claimed => {
+                    return ShadowOutcome::Agreement
+                }
+                BridgeActionStatus::Pending | BridgeActionStatus::NotFound => {
+                    if Instant::now() >= deadline {
+                        return ShadowOutcome::Divergence;
+                    }
+                    tokio::time::sleep(self.check_interval).await;
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sui_mock_client::SuiMockClient;
+    use crate::test_utils::{get_test_eth_to_sui_bridge_action, get_test_sui_to_eth_bridge_action};
+    use crate::types::{BlocklistCommitteeAction, BlocklistType};
+    use sui_types::bridge::BridgeChainId;
+
+    fn setup() -> (
+        tempfile::TempDir,
+        SuiMockClient,
+        Arc<BridgeOrchestratorTables>,
+        Arc<BridgeMetrics>,
+        ShadowComparator<SuiMockClient>,
+    ) {
+        let sui_client_mock = SuiMockClient::default();
+        let temp_dir = tempfile::tempdir().unwrap();
+        let store = BridgeOrchestratorTables::new(temp_dir.path());
+        let metrics = Arc::new(BridgeMetrics::for_testing());
+        let comparator = ShadowComparator::new(
+            Arc::new(SuiClient::new_for_testing(sui_client_mock.clone())),
+            store.clone(),
+            metrics.clone(),
+        )
+        .with_check_interval(Duration::from_millis(10), Duration::from_millis(100));
+        (temp_dir, sui_client_mock, store, metrics, comparator)
+    }
+
+    #[tokio::test]
+    async fn test_shadow_comparison_outcomes() {
+        let (_temp_dir, sui_client_mock, store, metrics, comparator) = setup();
+        let executed = get_test_eth_to_sui_bridge_action(None, None, None, None);
+        let missing = get_test_sui_to_eth_bridge_action(None, None, None, None, None, None, None);
+        let governance = BridgeAction::BlocklistCommitteeAction(BlocklistCommitteeAction {
+            nonce: 0,
+            chain_id: BridgeChainId::SuiCustom,
+            blocklist_type: BlocklistType::Blocklist,
+            members_to_update: vec![],
+        });
+        store
+            .insert_pending_actions(&[executed.clone(), missing.clone(), governance.clone()])
+            .unwrap();
+        sui_client_mock.set_action_onchain_status(&executed, BridgeActionStatus::Approved);
+        sui_client_mock.set_action_onchain_status(&missing, BridgeActionStatus::Pending);
+
+        let tx_digest = TransactionDigest::random();
+        assert_eq!(
+            comparator.compare(executed, tx_digest).await,
+            ShadowOutcome::Agreement
+        );
+        assert_eq!(
+            comparator.compare(missing, tx_digest).await,
+            ShadowOutcome::Divergence
+        );
+        assert_eq!(
+            comparator.compare(governance, tx_digest).await,
+            ShadowOutcome::Unverified
+        );
+        for outcome in ["agreement", "divergence", "unverified"] {
+            assert_eq!(
+                metrics
+                    .shadow_comparisons
+                    .with_label_values(&[outcome])
+                    .get(),
+                1
+            );
+        }
+        assert!(store.get_all_pending_actions().is_empty());
+    }
+}
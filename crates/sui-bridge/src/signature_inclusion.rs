@@ -0,0 +1,246 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Tracks whether the signature of this node is in the certificates that approved token
+//! transfers on Sui. Transfers routinely certified without it mean that the node is too
+//! slow to answer the signing requests, or that the other members can't reach it at its
+//! url.
+//!
+//! The monitor hands the `TokenTransferApproved` events over to the
+//! `SignatureInclusionChecker`, which reads the signatures recorded on chain for each
+//! approved transfer and looks for one by this node. The approvals are counted in
+//! `bridge_own_signature_included_total` and `bridge_own_signature_missing_total`, and the
+//! share of the last `SIGNATURE_INCLUSION_WINDOW` approvals that include the signature in
+//! `bridge_own_signature_inclusion_rate`. A warning is logged when the share drops below
+//! the configured floor.
+
+use crate::crypto::{BridgeAuthorityPublicKey, BridgeAuthorityRecoverableSignature};
+use crate::error::{BridgeError, BridgeResult};
+use crate::events::TokenTransferApproved;
+use crate::metrics::BridgeMetrics;
+use crate::sui_client::{SuiClient, SuiClientInner};
+use fastcrypto::hash::Keccak256;
+use fastcrypto::traits::{ToFromBytes, VerifyRecoverable};
+use std::collections::VecDeque;
+use std::sync::Arc;
+use tokio::sync::mpsc;
+use tracing::{info, warn};
+
+pub const DEFAULT_MIN_SIGNATURE_INCLUSION_PERCENT: u8 = 50;
+/// Number of the latest approvals the inclusion rate is computed over.
+pub const SIGNATURE_INCLUSION_WINDOW: usize = 100;
+
+// The rate is not compared to the floor before this many approvals were checked
+const MIN_INCLUSION_SAMPLES: usize = 20;
+// Approvals observed while this many are waiting to be checked are not checked
+const APPROVALS_CHANNEL_SIZE: usize = 1000;
+
+/// Hands the approvals observed by the monitor over to the `SignatureInclusionChecker`.
+#[derive(Clone)]
+pub struct SignatureInclusion {
+    sender: mpsc::Sender<TokenTransferApproved>,
+}
+
+impl SignatureInclusion {
+    pub fn new(
+        own_key: BridgeAuthorityPublicKey,
+        min_inclusion_percent: u8,
+        metrics: Arc<BridgeMetrics>,
+    ) -> (Self, SignatureInclusionChecker) {
+        let (sender, receiver) = mpsc::channel(APPROVALS_CHANNEL_SIZE);
+        (
+            Self { sender },
+            SignatureInclusionChecker {
+                own_key,
+                min_inclusion_percent,
+                receiver,
+                recent: VecDeque::with_capacity(SIGNATURE_INCLUSION_WINDOW),
+                below_floor: false,
+                metrics,
+            },
+        )
+    }
+
+    /// Queues `approved` to be checked. Never blocks the monitor, the approval is skipped
+    /// when the checker falls behind.
+    pub fn observe(&self, approved: TokenTransferApproved) {
+        let _ = self.sender.try_send(approved);
+    }
+}
+
+pub struct SignatureInclusionChecker {
+    own_key: BridgeAuthorityPublicKey,
+    min_inclusion_percent: u8,
+    receiver: mpsc::Receiver<TokenTransferApproved>,
+    // Whether each of the latest approvals included the signature, the oldest first
+    recent: VecDeque<bool>,
+    below_floor: bool,
+    metrics: Arc<BridgeMetrics>,
+}
+
+impl SignatureInclusionChecker {
+    /// Records the approval of the message whose signing bytes are `signing_bytes` by
+    /// the certificate with `signatures`. Returns whether one of them is by this node.
+    pub fn record(&mut self, signing_bytes: &[u8], signatures: &[Vec<u8>]) -> bool {
+        let included = signatures.iter().any(|signature| {
+            BridgeAuthorityRecoverableSignature::from_bytes(signature).is_ok_and(|signature| {
+                self.own_key
+                    .verify_recoverable_with_hash::<Keccak256>(signing_bytes, &signature)
+                    .is_ok()
+            })
+        });
+        if included {
+            self.metrics.own_signature_included_total.inc();
+        } else {
+            self.metrics.own_signature_missing_total.inc();
+        }
+        if self.recent.len() == SIGNATURE_INCLUSION_WINDOW {
+            self.recent.pop_front();
+        }
+        self.recent.push_back(included);
+
+        let rate = self.inclusion_rate();
+        self.metrics.own_signature_inclusion_rate.set(rate);
+        if self.recent.len() >= MIN_INCLUSION_SAMPLES {
+            let below_floor = rate * 100.0 < self.min_inclusion_percent as f64;
+            if below_floor && !self.below_floor {
+                warn!(
+                    "Signature of this node is in {:.0}% of the last {} approved certificates, below {}%. The node may be too slow to sign, or unreachable at its url",
+                    rate * 100.0,
+                    self.recent.len(),
+                    self.min_inclusion_percent
+                );
+            } else if !below_floor && self.below_floor {
+                info!(
+                    "Signature of this node is in {:.0}% of the last {} approved certificates again",
+                    rate * 100.0,
+                    self.recent.len()
+                );
+            }
+            self.below_floor = below_floor;
+        }
+        included
+    }
+
+    /// Share of the latest approvals that included the signature of this node, 1 before
+    /// any approval was checked.
+    pub fn inclusion_rate(&self) -> f64 {
+        if self.recent.is_empty() {
+            return 1.0;
+        }
+        self.recent.iter().filter(|included| **included).count() as f64 / self.recent.len() as f64
+    }
+
+    /// Whether the inclusion rate is below the floor, as last warned about.
+    pub fn is_below_floor(&self) -> bool {
+        self.below_floor
+    }
+
+    pub async fn run<C>(mut self, sui_client: Arc<SuiClient<C>>)
+    where
+        C: SuiClientInner + 'static,
+    {
+        info!("Starting SignatureInclusionChecker");
+        while let Some(approved) = self.receiver.recv().await {
+            if let Err(e) = self.check(&sui_client, &approved).await {
+                warn!(
+                    "Couldn't check the signatures that approved token transfer {} from {:?}: {:?}",
+                    approved.nonce, approved.source_chain, e
+                );
+            }
+        }
+    }
+
+    async fn check<C>(
+        &mut self,
+        sui_client: &SuiClient<C>,
+        approved: &TokenTransferApproved,
+    ) -> BridgeResult<()>
+    where
+        C: SuiClientInner,
+    {
+        let source_chain = approved.source_chain as u8;
+        let message = sui_client
+            .get_parsed_token_transfer_message(source_chain, approved.nonce)
+            .await?;
+        let signatures = sui_client
+            .get_token_transfer_action_onchain_signatures(source_chain, approved.nonce)
+            .await?;
+        let (Some(message), Some(signatures)) = (message, signatures) else {
+            return Err(BridgeError::Generic(
+                "No approved token transfer on chain".to_string(),
+            ));
+        };
+        self.record(&message.to_signing_bytes(), &signatures);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::crypto::{BridgeAuthorityKeyPair, BridgeAuthoritySignInfo};
+    use crate::test_utils::get_test_sui_to_eth_bridge_action;
+    use fastcrypto::traits::KeyPair;
+    use sui_types::crypto::get_key_pair;
+
+    #[test]
+    fn test_own_signature_inclusion() {
+        let (_, own_key): (_, BridgeAuthorityKeyPair) = get_key_pair();
+        let (_, other_key): (_, BridgeAuthorityKeyPair) = get_key_pair();
+        let metrics = Arc::new(BridgeMetrics::for_testing());
+        let (_, mut checker) =
+            SignatureInclusion::new(own_key.public().clone(), 50, metrics.clone());
+        let action = get_test_sui_to_eth_bridge_action(None, None, Some(1), None, None, None, None);
+        let signing_bytes = action.to_signing_bytes();
+        let signature = |key: &BridgeAuthorityKeyPair| {
+            BridgeAuthoritySignInfo::new(&action, key)
+                .signature
+                .as_bytes()
+                .to_vec()
+        };
+        let with_own = vec![signature(&other_key), signature(&own_key)];
+        let without_own = vec![signature(&other_key)];
+
+        assert!(checker.record(&signing_bytes, &with_own));
+        assert!(!checker.record(&signing_bytes, &without_own));
+        // Signatures of another message, or that don't parse, are not this node's
+        let other_action =
+            get_test_sui_to_eth_bridge_action(None, None, Some(2), None, None, None, None);
+        assert!(!checker.record(&other_action.to_signing_bytes(), &with_own));
+        assert!(!checker.record(&signing_bytes, &[vec![0; 10]]));
+        assert_eq!(metrics.own_signature_included_total.get(), 1);
+        assert_eq!(metrics.own_signature_missing_total.get(), 3);
+        assert_eq!(metrics.own_signature_inclusion_rate.get(), 0.25);
+        // Too few approvals to compare the rate to the floor yet
+        assert!(!checker.is_below_floor());
+
+        for _ in 0..MIN_INCLUSION_SAMPLES {
+            checker.record(&signing_bytes, &with_own);
+        }
+        assert!(!checker.is_below_floor());
+        // 21 of 45 approvals
+        for _ in 0..21 {
+            checker.record(&signing_bytes, &without_own);
+        }
+        assert!(checker.is_below_floor());
+        // 24 of 48
+        for _ in 0..3 {
+            checker.record(&signing_bytes, &with_own);
+        }
+        assert_eq!(checker.inclusion_rate(), 0.5);
+        assert!(!checker.is_below_floor());
+
+        // Only the latest approvals count
+        for _ in 0..SIGNATURE_INCLUSION_WINDOW {
+            checker.record(&signing_bytes, &without_own);
+        }
+        assert_eq!(checker.inclusion_rate(), 0.0);
+        assert!(checker.is_below_floor());
+        assert_eq!(metrics.own_signature_included_total.get(), 24);
+        assert_eq!(
+            metrics.own_signature_missing_total.get(),
+            24 + SIGNATURE_INCLUSION_WINDOW as u64
+        );
+    }
+}
@@ -0,0 +1,227 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Digest of the set of actions this node signed, for committee members to check that
+//! they signed the same actions without sharing their databases. The digest of each
+//! signed action is hashed, and the hashes are added modulo 2^256, so the digest of the
+//! set doesn't depend on the order the actions were signed in. An action is only added
+//! the first time it's signed, to the bucket of `SIGNED_SET_BUCKET_MS` it was signed in.
+//! The buckets add up to the digest of the whole set: nodes whose digests differ compare
+//! their buckets to find when they signed different actions. Members sign an action
+//! within seconds of each other, but one signed across the start of a bucket shows up in
+//! both neighbouring buckets.
+
+use crate::api_types::SignedSetDigest;
+use crate::error::BridgeResult;
+use crate::storage::BridgeOrchestratorTables;
+use crate::types::{BridgeAction, BridgeActionDigest};
+use fastcrypto::encoding::{Encoding, Hex};
+use fastcrypto::hash::{Blake2b256, HashFunction};
+use serde::{Deserialize, Serialize};
+use std::sync::{Arc, Mutex};
+
+pub const SIGNED_SET_BUCKET_MS: u64 = 24 * 60 * 60 * 1000;
+
+/// Sum of the hashes of the action digests of a set, modulo 2^256.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SetDigest([u8; 32]);
+
+impl SetDigest {
+    fn of_action(digest: &BridgeActionDigest) -> Self {
+        let mut hasher = Blake2b256::default();
+        hasher.update(digest.inner());
+        Self(hasher.finalize().digest)
+    }
+
+    /// Adds `other`, both read as little endian integers.
+    pub fn add(&mut self, other: &SetDigest) {
+        let mut carry = 0u16;
+        for (byte, other) in self.0.iter_mut().zip(other.0) {
+            let sum = *byte as u16 + other as u16 + carry;
+            *byte = sum as u8;
+            carry = sum >> 8;
+        }
+    }
+
+    pub fn to_hex(&self) -> String {
+        Hex::encode(self.0)
+    }
+}
+
+/// The actions first signed in a bucket.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SignedSetBucket {
+    pub digest: SetDigest,
+    pub count: u64,
+}
+
+impl SignedSetBucket {
+    pub fn insert(&mut self, action: &BridgeActionDigest) {
+        self.digest.add(&SetDigest::of_action(action));
+        self.count += 1;
+    }
+
+    pub fn merge(&mut self, other: &SignedSetBucket) {
+        self.digest.add(&other.digest);
+        self.count += other.count;
+    }
+}
+
+pub fn bucket_start_ms(timestamp_ms: u64) -> u64 {
+    timestamp_ms - timestamp_ms % SIGNED_SET_BUCKET_MS
+}
+
+/// The set of actions signed by the server, kept in the client storage.
+pub struct SignedSet {
+    store: Arc<BridgeOrchestratorTables>,
+    // Inserts read and write back their bucket, the signers insert one at a time
+    lock: Mutex<()>,
+}
+
+impl SignedSet {
+    pub fn new(store: Arc<BridgeOrchestratorTables>) -> Self {
+        Self {
+            store,
+            lock: Mutex::new(()),
+        }
+    }
+
+    /// Adds an action signed at `signed_at_ms`. Returns false if it was signed before.
+    pub fn insert(&self, action: &BridgeAction, signed_at_ms: u64) -> BridgeResult<bool> {
+        let _guard = self.lock.lock().unwrap();
+        self.store
+            .insert_signed_action(&action.digest(), bucket_start_ms(signed_at_ms))
+    }
+
+    pub fn digest(&self) -> SignedSetDigest {
+        SignedSetDigest::new(&self.store.get_signed_set_buckets())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api_types::SignedSetComparison;
+    use crate::test_utils::get_test_sui_to_eth_bridge_action;
+    use std::collections::BTreeMap;
+
+    fn action(nonce: u64) -> BridgeAction {
+        get_test_sui_to_eth_bridge_action(None, Some(0), Some(nonce), None, None, None, None)
+    }
+
+    #[test]
+    fn test_set_digest_is_order_independent() {
+        let digests = (0..5)
+            .map(|nonce| action(nonce).digest())
+            .collect::<Vec<_>>();
+        let mut forward = SignedSetBucket::default();
+        digests.iter().for_each(|digest| forward.insert(digest));
+        let mut backward = SignedSetBucket::default();
+        digests
+            .iter()
+            .rev()
+            .for_each(|digest| backward.insert(digest));
+        assert_eq!(forward, backward);
+        assert_eq!(forward.count, 5);
+
+        // Buckets add up to the digest of their union
+        let mut first = SignedSetBucket::default();
+        digests[..2].iter().for_each(|digest| first.insert(digest));
+        let mut second = SignedSetBucket::default();
+        digests[2..].iter().for_each(|digest| second.insert(digest));
+        first.merge(&second);
+        assert_eq!(first, forward);
+
+        // Carries propagate across bytes, and wrap around
+        let mut max = SetDigest([0xff; 32]);
+        let mut one = [0u8; 32];
+        one[0] = 1;
+        max.add(&SetDigest(one));
+        assert_eq!(max, SetDigest::default());
+    }
+
+    // async: existing runtime is required with typed-store
+    #[tokio::test]
+    async fn test_compare_signed_sets() {
+        let node = || {
+            let temp_dir = tempfile::tempdir().unwrap();
+            let set = SignedSet::new(BridgeOrchestratorTables::new(temp_dir.path()));
+            (temp_dir, set)
+        };
+        let (_dir_a, node_a) = node();
+        let (_dir_b, node_b) = node();
+        let day = |day: u64| day * SIGNED_SET_BUCKET_MS + 1000;
+        let compare = |a: &SignedSet, b: &SignedSet| {
+            SignedSetComparison::new(vec![
+                ("a".to_string(), Ok(a.digest())),
+                ("b".to_string(), Ok(b.digest())),
+            ])
+        };
+
+        // Both nodes sign the same actions over 3 days, in another order
+        for (nonce, signed_at) in [(1, day(0)), (2, day(1)), (3, day(1)), (4, day(2))] {
+            assert!(node_a.insert(&action(nonce), signed_at).unwrap());
+        }
+        for (nonce, signed_at) in [(4, day(2)), (3, day(1)), (1, day(0)), (2, day(1))] {
+            assert!(node_b.insert(&action(nonce), signed_at + 5000).unwrap());
+        }
+        // Signing again doesn't change the set
+        assert!(!node_a.insert(&action(1), day(2)).unwrap());
+        let digest = node_a.digest();
+        assert_eq!(digest, node_b.digest());
+        assert_eq!(digest.count, 4);
+        assert_eq!(
+            digest
+                .buckets
+                .iter()
+                .map(|bucket| (bucket.start_ms, bucket.count))
+                .collect::<Vec<_>>(),
+            vec![
+                (0, 1),
+                (SIGNED_SET_BUCKET_MS, 2),
+                (2 * SIGNED_SET_BUCKET_MS, 1)
+            ]
+        );
+        let comparison = compare(&node_a, &node_b);
+        assert!(comparison.matching);
+        assert!(comparison.diverging_buckets.is_empty());
+
+        // Then a signs an action that b doesn't on day 1, and they sign different ones on
+        // day 3
+        node_a.insert(&action(5), day(1)).unwrap();
+        node_a.insert(&action(6), day(3)).unwrap();
+        node_b.insert(&action(7), day(3)).unwrap();
+        let comparison = compare(&node_a, &node_b);
+        assert!(!comparison.matching);
+        assert_eq!(
+            comparison
+                .diverging_buckets
+                .iter()
+                .map(|bucket| (bucket.start_ms, bucket.counts.clone()))
+                .collect::<Vec<_>>(),
+            vec![
+                (
+                    SIGNED_SET_BUCKET_MS,
+                    BTreeMap::from([("a".to_string(), 3), ("b".to_string(), 2)])
+                ),
+                (
+                    3 * SIGNED_SET_BUCKET_MS,
+                    BTreeMap::from([("a".to_string(), 1), ("b".to_string(), 1)])
+                ),
+            ]
+        );
+
+        // Nodes that don't answer don't match, the others are still compared
+        let comparison = SignedSetComparison::new(vec![
+            ("a".to_string(), Ok(node_a.digest())),
+            ("b".to_string(), Ok(node_b.digest())),
+            ("c".to_string(), Err("connection refused".to_string())),
+        ]);
+        assert!(!comparison.matching);
+        assert_eq!(comparison.diverging_buckets.len(), 2);
+        assert_eq!(
+            comparison.nodes[2].error.as_deref(),
+            Some("connection refused")
+        );
+    }
+}
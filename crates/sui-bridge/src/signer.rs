@@ -0,0 +1,452 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! The signers of the bridge authority. The server verifies the requested actions and
+//! signs them with a `BridgeAuthoritySigner`: the authority key itself, or a
+//! `RemoteSigner` for operators that keep the key in a dedicated signer service.
+//!
+//! The remote signer is posted JSON `{"authority_public_key", "digest", "message"}` over
+//! mutually authenticated TLS, with `message` the signing bytes of the action and
+//! `digest` their Keccak-256, both hex encoded, and answers `{"signature"}` with the
+//! hex encoded 65 bytes recoverable signature of the digest. The message is sent along
+//! so that the signer can apply its own policy to what it signs.
+
+use crate::alerts::{AlertCondition, AlertSink};
+use crate::config::RemoteSignerConfig;
+use crate::crypto::{
+    BridgeAuthorityKeyPair, BridgeAuthorityPublicKey, BridgeAuthorityPublicKeyBytes,
+    BridgeAuthorityRecoverableSignature, BridgeAuthoritySignInfo,
+};
+use crate::error::{BridgeError, BridgeResult, ErrorKind};
+use crate::metrics::BridgeMetrics;
+use crate::types::BridgeAction;
+use anyhow::anyhow;
+use async_trait::async_trait;
+use fastcrypto::encoding::{Encoding, Hex};
+use fastcrypto::hash::{HashFunction, Keccak256};
+use fastcrypto::traits::{KeyPair, ToFromBytes, VerifyRecoverable};
+use serde::{Deserialize, Serialize};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use sui_types::base_types::ConciseableName;
+use tracing::{error, warn};
+
+pub const DEFAULT_REMOTE_SIGNER_TIMEOUT: Duration = Duration::from_secs(5);
+pub const DEFAULT_REMOTE_SIGNER_MAX_ATTEMPTS: u32 = 3;
+pub const DEFAULT_REMOTE_SIGNER_FAILURE_THRESHOLD: u32 = 5;
+pub const DEFAULT_REMOTE_SIGNER_CIRCUIT_OPEN_DURATION: Duration = Duration::from_secs(30);
+const REMOTE_SIGNER_RETRY_DELAY: Duration = Duration::from_millis(200);
+
+/// Signs the verified actions as the bridge authority.
+#[async_trait]
+pub trait BridgeAuthoritySigner: Send + Sync {
+    /// The key of the bridge authority, that the signatures verify with.
+    fn public_key(&self) -> &BridgeAuthorityPublicKey;
+
+    async fn sign_action(&self, action: &BridgeAction) -> BridgeResult<BridgeAuthoritySignInfo>;
+}
+
+#[async_trait]
+impl BridgeAuthoritySigner for BridgeAuthorityKeyPair {
+    fn public_key(&self) -> &BridgeAuthorityPublicKey {
+        self.public()
+    }
+
+    async fn sign_action(&self, action: &BridgeAction) -> BridgeResult<BridgeAuthoritySignInfo> {
+        Ok(BridgeAuthoritySignInfo::new(action, self))
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RemoteSignRequest {
+    pub authority_public_key: String,
+    pub digest: String,
+    pub message: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RemoteSignResponse {
+    pub signature: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RemoteSignerSettings {
+    /// Timeout of each request
+    pub timeout: Duration,
+    /// Attempts of a request that failed with a retryable error
+    pub max_attempts: u32,
+    pub retry_delay: Duration,
+    /// Consecutive retryable failures after which the circuit opens
+    pub failure_threshold: u32,
+    /// How long requests are refused once the circuit opened
+    pub circuit_open_duration: Duration,
+}
+
+impl Default for RemoteSignerSettings {
+    fn default() -> Self {
+        Self {
+            timeout: DEFAULT_REMOTE_SIGNER_TIMEOUT,
+            max_attempts: DEFAULT_REMOTE_SIGNER_MAX_ATTEMPTS,
+            retry_delay: REMOTE_SIGNER_RETRY_DELAY,
+            failure_threshold: DEFAULT_REMOTE_SIGNER_FAILURE_THRESHOLD,
+            circuit_open_duration: DEFAULT_REMOTE_SIGNER_CIRCUIT_OPEN_DURATION,
+        }
+    }
+}
+
+// Opens after `failure_threshold` consecutive failures to reach the signer. Once open
+// duration elapsed, requests reach the signer again, and the first one that fails opens
+// it again.
+#[derive(Debug, Default)]
+struct CircuitBreaker {
+    consecutive_failures: u32,
+    open_until: Option<Instant>,
+}
+
+/// Signs through a remote signer service, see the module docs.
+pub struct RemoteSigner {
+    url: reqwest::Url,
+    client: reqwest::Client,
+    public_key: BridgeAuthorityPublicKey,
+    settings: RemoteSignerSettings,
+    breaker: Mutex<CircuitBreaker>,
+    alerts: Option<Arc<AlertSink>>,
+    metrics: Arc<BridgeMetrics>,
+}
+
+impl RemoteSigner {
+    /// A signer reached at `config.url` with mutually authenticated TLS.
+    pub fn from_config(
+        config: &RemoteSignerConfig,
+        metrics: Arc<BridgeMetrics>,
+    ) -> anyhow::Result<Self> {
+        let read = |path: &std::path::Path| {
+            std::fs::read(path).map_err(|e| anyhow!("Couldn't read {}: {:?}", path.display(), e))
+        };
+        let identity = reqwest::Identity::from_pem(&read(&config.client_identity_path)?)
+            .map_err(|e| anyhow!("Invalid `remote_signer.client-identity-path`: {:?}", e))?;
+        let ca_certificate = reqwest::Certificate::from_pem(&read(&config.ca_certificate_path)?)
+            .map_err(|e| anyhow!("Invalid `remote_signer.ca-certificate-path`: {:?}", e))?;
+        let client = reqwest::Client::builder()
+            .use_rustls_tls()
+            .tls_built_in_root_certs(false)
+            .add_root_certificate(ca_certificate)
+            .identity(identity)
+            .build()?;
+        let url = reqwest::Url::parse(&config.url)
+            .map_err(|e| anyhow!("Invalid `remote_signer.url`: {:?}", e))?;
+        Ok(Self::new(
+            url,
+            client,
+            config.authority_public_key()?,
+            config.settings(),
+            metrics,
+        ))
+    }
+
+    pub fn new(
+        url: reqwest::Url,
+        client: reqwest::Client,
+        public_key: BridgeAuthorityPublicKey,
+        settings: RemoteSignerSettings,
+        metrics: Arc<BridgeMetrics>,
+    ) -> Self {
+        Self {
+            url,
+            client,
+            public_key,
+            settings,
+            breaker: Mutex::new(CircuitBreaker::default()),
+            alerts: None,
+            metrics,
+        }
+    }
+
+    /// Alerts on `RemoteSignerMismatch` with `alerts`.
+    pub fn with_alert_sink(mut self, alerts: Arc<AlertSink>) -> Self {
+        self.alerts = Some(alerts);
+        self
+    }
+
+    fn count_failure(&self, reason: &str) {
+        self.metrics
+            .remote_signer_failures
+            .with_label_values(&[reason])
+            .inc();
+    }
+
+    // Refuses the request while the circuit is open
+    fn check_circuit(&self) -> BridgeResult<()> {
+        let breaker = self.breaker.lock().unwrap();
+        match breaker.open_until {
+            Some(open_until) if Instant::now() < open_until => {
+                self.count_failure("circuit_open");
+                Err(BridgeError::RemoteSignerError(
+                    ErrorKind::Transient,
+                    format!(
+                        "Remote signer failed {} times in a row, not retried for {:?}",
+                        breaker.consecutive_failures,
+                        open_until.saturating_duration_since(Instant::now())
+                    ),
+                ))
+            }
+            _ => Ok(()),
+        }
+    }
+
+    fn record_outcome(&self, reached: bool) {
+        let mut breaker = self.breaker.lock().unwrap();
+        if reached {
+            breaker.consecutive_failures = 0;
+            breaker.open_until = None;
+        } else {
+            breaker.consecutive_failures += 1;
+            if breaker.consecutive_failures >= self.settings.failure_threshold {
+                if breaker.open_until.is_none() {
+                    warn!(
+                        "Remote signer failed {} times in a row, refusing signing requests for {:?}",
+                        breaker.consecutive_failures, self.settings.circuit_open_duration
+                    );
+                }
+                breaker.open_until = Some(Instant::now() + self.settings.circuit_open_duration);
+            }
+        }
+        self.metrics
+            .remote_signer_circuit_open
+            .set(breaker.open_until.is_some() as i64);
+    }
+
+    async fn request_signature(
+        &self,
+        request: &RemoteSignRequest,
+    ) -> BridgeResult<BridgeAuthorityRecoverableSignature> {
+        let mut attempt = 0;
+        loop {
+            self.check_circuit()?;
+            attempt += 1;
+            match self.request_signature_once(request).await {
+                Ok(signature) => {
+                    self.record_outcome(true);
+                    return Ok(signature);
+                }
+                Err((reason, e)) => {
+                    self.count_failure(reason);
+                    // The signer was reached when it refused the request
+                    self.record_outcome(!e.is_retryable());
+                    if !e.is_retryable() || attempt >= self.settings.max_attempts {
+                        return Err(e);
+                    }
+                    warn!(
+                        "Remote signer request failed, attempt {}/{}: {:?}",
+                        attempt, self.settings.max_attempts, e
+                    );
+                    tokio::time::sleep(self.settings.retry_delay).await;
+                }
+            }
+        }
+    }
+
+    // Fails with the reason the failure is counted under
+    async fn request_signature_once(
+        &self,
+        request: &RemoteSignRequest,
+    ) -> Result<BridgeAuthorityRecoverableSignature, (&'static str, BridgeError)> {
+        let error = |kind, e: String| BridgeError::RemoteSignerError(kind, e);
+        let response = async {
+            let response = self
+                .client
+                .post(self.url.clone())
+                .json(request)
+                .send()
+                .await
+                .map_err(|e| ("unreachable", error(ErrorKind::Transient, e.to_string())))?;
+            let status = response.status();
+            if !status.is_success() {
+                let body = response.text().await.unwrap_or_default();
+                let e = format!("[{}]: {}", status, body);
+                return Err(
+                    if status.is_server_error() || status == reqwest::StatusCode::TOO_MANY_REQUESTS
+                    {
+                        ("unavailable", error(ErrorKind::Transient, e))
+                    } else {
+                        ("refused", error(ErrorKind::Permanent, e))
+                    },
+                );
+            }
+            response
+                .json::<RemoteSignResponse>()
+                .await
+                .map_err(|e| ("unreachable", error(ErrorKind::Transient, e.to_string())))
+        };
+        let response = tokio::time::timeout(self.settings.timeout, response)
+            .await
+            .map_err(|_| {
+                (
+                    "timeout",
+                    error(
+                        ErrorKind::Transient,
+                        format!("No response in {:?}", self.settings.timeout),
+                    ),
+                )
+            })??;
+        Hex::decode(&response.signature)
+            .ok()
+            .and_then(|bytes| BridgeAuthorityRecoverableSignature::from_bytes(&bytes).ok())
+            .ok_or_else(|| {
+                (
+                    "invalid_response",
+                    error(
+                        ErrorKind::Permanent,
+                        format!("Invalid signature {}", response.signature),
+                    ),
+                )
+            })
+    }
+}
+
+#[async_trait]
+impl BridgeAuthoritySigner for RemoteSigner {
+    fn public_key(&self) -> &BridgeAuthorityPublicKey {
+        &self.public_key
+    }
+
+    async fn sign_action(&self, action: &BridgeAction) -> BridgeResult<BridgeAuthoritySignInfo> {
+        let message = action.to_signing_bytes();
+        let request = RemoteSignRequest {
+            authority_public_key: Hex::encode(self.public_key.as_bytes()),
+            digest: Hex::encode(Keccak256::digest(&message).digest),
+            message: Hex::encode(&message),
+        };
+        let start = Instant::now();
+        let signature = self.request_signature(&request).await;
+        self.metrics
+            .remote_signer_latency
+            .observe(start.elapsed().as_secs_f64());
+        let signature = signature?;
+
+        // A signature of another key or message would be rejected by the committee
+        // members and the chains, it is not returned
+        if let Err(e) = self
+            .public_key
+            .verify_recoverable_with_hash::<Keccak256>(&message, &signature)
+        {
+            self.count_failure("invalid_signature");
+            let authority = BridgeAuthorityPublicKeyBytes::from(&self.public_key);
+            let message = format!(
+                "Remote signer returned a signature of {:?} that doesn't verify with the authority key {}: {}",
+                action.digest(),
+                authority.concise(),
+                e
+            );
+            error!("{}", message);
+            if let Some(alerts) = &self.alerts {
+                alerts.raise(AlertCondition::RemoteSignerMismatch, message.clone());
+            }
+            return Err(BridgeError::InvalidBridgeAuthoritySignature((
+                authority, message,
+            )));
+        }
+        Ok(BridgeAuthoritySignInfo {
+            authority_pub_key: self.public_key.clone(),
+            signature,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{get_test_sui_to_eth_bridge_action, RemoteSignerStub};
+    use sui_types::crypto::get_key_pair;
+
+    fn remote_signer(
+        url: reqwest::Url,
+        public_key: BridgeAuthorityPublicKey,
+        metrics: Arc<BridgeMetrics>,
+    ) -> RemoteSigner {
+        RemoteSigner::new(
+            url,
+            reqwest::Client::new(),
+            public_key,
+            RemoteSignerSettings {
+                timeout: Duration::from_millis(200),
+                max_attempts: 2,
+                retry_delay: Duration::from_millis(10),
+                failure_threshold: 4,
+                circuit_open_duration: Duration::from_secs(60),
+            },
+            metrics,
+        )
+    }
+
+    fn failures(metrics: &BridgeMetrics, reason: &str) -> u64 {
+        metrics
+            .remote_signer_failures
+            .with_label_values(&[reason])
+            .get()
+    }
+
+    #[tokio::test]
+    async fn test_remote_signer() {
+        let (_, key): (_, BridgeAuthorityKeyPair) = get_key_pair();
+        let stub = RemoteSignerStub::start(key.copy()).await;
+        let metrics = Arc::new(BridgeMetrics::for_testing());
+        let signer = remote_signer(stub.url(), key.public().clone(), metrics.clone());
+
+        let action = get_test_sui_to_eth_bridge_action(None, None, None, None, None, None, None);
+        let sign_info = signer.sign_action(&action).await.unwrap();
+        assert_eq!(sign_info, key.sign_action(&action).await.unwrap());
+        assert_eq!(stub.requests(), 1);
+        assert_eq!(metrics.remote_signer_latency.get_sample_count(), 1);
+        assert_eq!(metrics.remote_signer_circuit_open.get(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_remote_signer_timeout_opens_circuit() {
+        let (_, key): (_, BridgeAuthorityKeyPair) = get_key_pair();
+        let stub = RemoteSignerStub::start(key.copy()).await;
+        stub.set_delay(Duration::from_secs(5));
+        let metrics = Arc::new(BridgeMetrics::for_testing());
+        let signer = remote_signer(stub.url(), key.public().clone(), metrics.clone());
+        let action = get_test_sui_to_eth_bridge_action(None, None, None, None, None, None, None);
+
+        // Each request is attempted twice
+        for _ in 0..2 {
+            let err = signer.sign_action(&action).await.unwrap_err();
+            assert!(matches!(err, BridgeError::RemoteSignerError(_, _)));
+            assert!(err.is_retryable());
+        }
+        assert_eq!(failures(&metrics, "timeout"), 4);
+        assert_eq!(metrics.remote_signer_circuit_open.get(), 1);
+
+        // The signer is not reached while the circuit is open, even once it answers
+        stub.set_delay(Duration::ZERO);
+        let requests = stub.requests();
+        let err = signer.sign_action(&action).await.unwrap_err();
+        assert!(err.is_retryable());
+        assert_eq!(stub.requests(), requests);
+        assert_eq!(failures(&metrics, "circuit_open"), 1);
+    }
+
+    #[tokio::test]
+    async fn test_remote_signer_signature_mismatch() {
+        let (_, key): (_, BridgeAuthorityKeyPair) = get_key_pair();
+        let (_, other_key): (_, BridgeAuthorityKeyPair) = get_key_pair();
+        // The signer signs with another key than the authority key
+        let stub = RemoteSignerStub::start(other_key).await;
+        let metrics = Arc::new(BridgeMetrics::for_testing());
+        let signer = remote_signer(stub.url(), key.public().clone(), metrics.clone());
+        let action = get_test_sui_to_eth_bridge_action(None, None, None, None, None, None, None);
+
+        let err = signer.sign_action(&action).await.unwrap_err();
+        assert!(matches!(
+            err,
+            BridgeError::InvalidBridgeAuthoritySignature(_)
+        ));
+        assert!(!err.is_retryable());
+        // Not retried, and the signer was reached
+        assert_eq!(stub.requests(), 1);
+        assert_eq!(failures(&metrics, "invalid_signature"), 1);
+        assert_eq!(metrics.remote_signer_circuit_open.get(), 0);
+    }
+}
@@ -0,0 +1,199 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! The signing queue of the executor holds the actions waiting for signature aggregation,
+//! which may be tens of thousands during a backlog. Only the first `window` of them are
+//! held in memory. Beyond that only their digests are queued, and the actions are read
+//! from the pending actions when they reach the head. Actions are persisted as pending
+//! before they are sent to the executor, so nothing is lost by not holding them.
+
+use crate::action_executor::BridgeActionExecutionWrapper;
+use crate::metrics::BridgeMetrics;
+use crate::storage::BridgeOrchestratorTables;
+use crate::types::{BridgeAction, BridgeActionDigest};
+use std::collections::VecDeque;
+use std::sync::Arc;
+use std::time::Instant;
+use tracing::warn;
+
+pub const DEFAULT_SIGNING_WINDOW: usize = 1000;
+
+enum QueuedAction {
+    Resident(BridgeActionExecutionWrapper),
+    Spilled {
+        digest: BridgeActionDigest,
+        attempt_times: u64,
+        enqueued_at: Instant,
+    },
+}
+
+pub struct SigningQueue {
+    queue: VecDeque<QueuedAction>,
+    window: usize,
+    // Number of `QueuedAction::Resident` in `queue`, at most `window`
+    resident: usize,
+    store: Arc<BridgeOrchestratorTables>,
+    metrics: Arc<BridgeMetrics>,
+}
+
+impl SigningQueue {
+    pub fn new(
+        window: usize,
+        store: Arc<BridgeOrchestratorTables>,
+        metrics: Arc<BridgeMetrics>,
+    ) -> Self {
+        Self {
+            queue: VecDeque::new(),
+            window,
+            resident: 0,
+            store,
+            metrics,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.queue.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.queue.is_empty()
+    }
+
+    pub fn push(&mut self, action: BridgeActionExecutionWrapper) {
+        if self.resident < self.window {
+            self.resident += 1;
+            self.queue.push_back(QueuedAction::Resident(action));
+            return;
+        }
+        let BridgeActionExecutionWrapper(action, attempt_times, enqueued_at) = action;
+        self.queue.push_back(QueuedAction::Spilled {
+            digest: action.digest(),
+            attempt_times,
+            enqueued_at,
+        });
+        self.metrics
+            .action_executor_signing_queue_spilled_actions
+            .inc();
+    }
+
+    /// The first `n` actions of the queue, without popping them. Spilled actions that are
+    /// no longer pending are left out.
+    pub fn peek(&self, n: usize) -> Vec<BridgeAction> {
+        self.queue
+            .iter()
+            .filter_map(|queued| match queued {
+                QueuedAction::Resident(BridgeActionExecutionWrapper(action, _, _)) => {
+                    Some(action.clone())
+                }
+                QueuedAction::Spilled { digest, .. } => self
+                    .store
+                    .get_pending_action(digest)
+                    .unwrap_or_else(|e| panic!("Read from DB should not fail: {:?}", e)),
+            })
+            .take(n)
+            .collect()
+    }
+
+    /// Pops the action at the head of the queue. Spilled actions that are no longer
+    /// pending, e.g. because they were executed meanwhile, are skipped.
+    pub fn pop(&mut self) -> Option<BridgeActionExecutionWrapper> {
+        while let Some(queued) = self.queue.pop_front() {
+            match queued {
+                QueuedAction::Resident(action) => {
+                    self.resident -= 1;
+                    return Some(action);
+                }
+                QueuedAction::Spilled {
+                    digest,
+                    attempt_times,
+                    enqueued_at,
+                } => {
+                    self.metrics
+                        .action_executor_signing_queue_spilled_actions
+                        .dec();
+                    let action = self
+                        .store
+                        .get_pending_action(&digest)
+                        .unwrap_or_else(|e| panic!("Read from DB should not fail: {:?}", e));
+                    match action {
+                        Some(action) => {
+                            return Some(BridgeActionExecutionWrapper(
+                                action,
+                                attempt_times,
+                                enqueued_at,
+                            ))
+                        }
+                        None => warn!("Queued action is no longer pending, skipping: {:?}", digest),
+                    }
+                }
+            }
+        }
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::get_test_eth_to_sui_bridge_action;
+
+    #[test]
+    fn test_signing_queue_spills_beyond_window() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let store = BridgeOrchestratorTables::new(temp_dir.path());
+        let metrics = Arc::new(BridgeMetrics::for_testing());
+        let mut queue = SigningQueue::new(2, store.clone(), metrics.clone());
+        let actions = (0..5)
+            .map(|nonce| get_test_eth_to_sui_bridge_action(Some(nonce), None, None, None))
+            .collect::<Vec<_>>();
+        store.insert_pending_actions(&actions).unwrap();
+
+        for (attempt_times, action) in actions.iter().enumerate() {
+            queue.push(BridgeActionExecutionWrapper(
+                action.clone(),
+                attempt_times as u64,
+                Instant::now(),
+            ));
+        }
+        assert_eq!(queue.len(), 5);
+        assert_eq!(queue.resident, 2);
+        let spilled = || metrics.action_executor_signing_queue_spilled_actions.get();
+        assert_eq!(spilled(), 3);
+
+        let first = queue.pop().unwrap();
+        assert_eq!((first.0.digest(), first.1), (actions[0].digest(), 0));
+        // A retried action is held in the freed room, behind the spilled ones
+        queue.push(BridgeActionExecutionWrapper(
+            actions[0].clone(),
+            7,
+            Instant::now(),
+        ));
+        assert_eq!(queue.resident, 2);
+        assert_eq!(spilled(), 3);
+
+        // A spilled action that was executed meanwhile is skipped
+        store
+            .remove_pending_actions(&[actions[3].digest()])
+            .unwrap();
+        assert_eq!(
+            queue.peek(3),
+            vec![actions[1].clone(), actions[2].clone(), actions[4].clone()]
+        );
+        assert_eq!(queue.len(), 5);
+        let popped = std::iter::from_fn(|| queue.pop())
+            .map(|action| (action.0.digest(), action.1))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            popped,
+            vec![
+                (actions[1].digest(), 1),
+                (actions[2].digest(), 2),
+                (actions[4].digest(), 4),
+                (actions[0].digest(), 7),
+            ]
+        );
+        assert!(queue.is_empty());
+        assert_eq!(queue.resident, 0);
+        assert_eq!(spilled(), 0);
+    }
+}
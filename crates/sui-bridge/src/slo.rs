@@ -0,0 +1,393 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Service level indicators computed in the node, so that every operator doesn't have
+//! to write the same recording rules. `SloTracker` keeps the raw events in sliding
+//! windows of fixed width buckets, and `SloMetricsUpdater` periodically exports the
+//! aggregates as `bridge_slo_` gauges.
+
+use crate::metrics::BridgeMetrics;
+use std::collections::VecDeque;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tokio::time;
+use tracing::info;
+
+pub const DEFAULT_SLO_UPDATE_INTERVAL: Duration = Duration::from_secs(15);
+
+pub const SHORT_WINDOW: Duration = Duration::from_secs(5 * 60);
+const SHORT_WINDOW_BUCKET: Duration = Duration::from_secs(10);
+pub const LONG_WINDOW: Duration = Duration::from_secs(60 * 60);
+const LONG_WINDOW_BUCKET: Duration = Duration::from_secs(60);
+// Samples beyond this in one bucket are dropped, to bound the memory used under load
+const MAX_SAMPLES_PER_BUCKET: usize = 10_000;
+
+/// Events of the last `window`, grouped by the bucket of `bucket_width` they happened in.
+/// The window covers the current bucket and the ones before it, so it moves forward by
+/// one bucket at a time and the oldest bucket is dropped as a whole.
+#[derive(Debug)]
+pub struct SlidingWindow<B> {
+    bucket_width: Duration,
+    num_buckets: u64,
+    origin: Instant,
+    // Index of the bucket since `origin` and its content, oldest first
+    buckets: VecDeque<(u64, B)>,
+}
+
+impl<B: Default> SlidingWindow<B> {
+    pub fn new(window: Duration, bucket_width: Duration, origin: Instant) -> Self {
+        assert!(!bucket_width.is_zero(), "bucket width must not be zero");
+        let num_buckets = (window.as_nanos().div_ceil(bucket_width.as_nanos()) as u64).max(1);
+        Self {
+            bucket_width,
+            num_buckets,
+            origin,
+            buckets: VecDeque::new(),
+        }
+    }
+
+    fn bucket_index(&self, now: Instant) -> u64 {
+        (now.saturating_duration_since(self.origin).as_nanos() / self.bucket_width.as_nanos())
+            as u64
+    }
+
+    fn evict(&mut self, current_index: u64) {
+        while let Some((index, _)) = self.buckets.front() {
+            if index + self.num_buckets > current_index {
+                break;
+            }
+            self.buckets.pop_front();
+        }
+    }
+
+    /// Returns the bucket that events happening at `now` are recorded into.
+    pub fn current_bucket(&mut self, now: Instant) -> &mut B {
+        let current_index = self.bucket_index(now);
+        self.evict(current_index);
+        if self.buckets.back().map(|(index, _)| *index) != Some(current_index) {
+            self.buckets.push_back((current_index, B::default()));
+        }
+        &mut self.buckets.back_mut().unwrap().1
+    }
+
+    /// Returns the buckets that are still in the window at `now`, oldest first.
+    pub fn buckets(&mut self, now: Instant) -> impl Iterator<Item = &B> {
+        self.evict(self.bucket_index(now));
+        self.buckets.iter().map(|(_, bucket)| bucket)
+    }
+}
+
+/// Values, e.g. latencies, recorded in a sliding window.
+#[derive(Debug)]
+pub struct WindowedSamples(SlidingWindow<Vec<f64>>);
+
+impl WindowedSamples {
+    pub fn new(window: Duration, bucket_width: Duration, origin: Instant) -> Self {
+        Self(SlidingWindow::new(window, bucket_width, origin))
+    }
+
+    pub fn record(&mut self, value: f64, now: Instant) {
+        let bucket = self.0.current_bucket(now);
+        if bucket.len() < MAX_SAMPLES_PER_BUCKET {
+            bucket.push(value);
+        }
+    }
+
+    /// Returns the `quantile` (between 0 and 1) of the values in the window, using the
+    /// nearest rank. None if the window is empty.
+    pub fn quantile(&mut self, quantile: f64, now: Instant) -> Option<f64> {
+        let mut values: Vec<f64> = self.0.buckets(now).flatten().copied().collect();
+        if values.is_empty() {
+            return None;
+        }
+        values.sort_by(|a, b| a.total_cmp(b));
+        let rank = (quantile * values.len() as f64).ceil() as usize;
+        Some(values[rank.clamp(1, values.len()) - 1])
+    }
+}
+
+#[derive(Debug, Default, Clone, Copy)]
+struct Outcomes {
+    total: u64,
+    failed: u64,
+}
+
+/// Outcomes of operations recorded in a sliding window.
+#[derive(Debug)]
+pub struct WindowedRatio(SlidingWindow<Outcomes>);
+
+impl WindowedRatio {
+    pub fn new(window: Duration, bucket_width: Duration, origin: Instant) -> Self {
+        Self(SlidingWindow::new(window, bucket_width, origin))
+    }
+
+    pub fn record(&mut self, failed: bool, now: Instant) {
+        let bucket = self.0.current_bucket(now);
+        bucket.total += 1;
+        if failed {
+            bucket.failed += 1;
+        }
+    }
+
+    /// Returns the number of operations in the window and how many of them failed.
+    pub fn counts(&mut self, now: Instant) -> (u64, u64) {
+        self.0.buckets(now).fold((0, 0), |(total, failed), bucket| {
+            (total + bucket.total, failed + bucket.failed)
+        })
+    }
+
+    /// Returns the fraction of failed operations in the window. None if the window is empty.
+    pub fn failure_ratio(&mut self, now: Instant) -> Option<f64> {
+        let (total, failed) = self.counts(now);
+        (total > 0).then(|| failed as f64 / total as f64)
+    }
+}
+
+/// Aggregates of the windows at one point in time, latencies in seconds. None for empty
+/// windows.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SloSnapshot {
+    pub signing_latency_p99_5m_secs: Option<f64>,
+    pub signing_error_ratio_5m: Option<f64>,
+    pub signing_error_ratio_1h: Option<f64>,
+    pub executor_queue_wait_p95_5m_secs: Option<f64>,
+}
+
+/// Raw events the service level indicators are computed from.
+#[derive(Debug)]
+pub struct SloTracker {
+    signing_latency: Mutex<WindowedSamples>,
+    signing_errors_5m: Mutex<WindowedRatio>,
+    signing_errors_1h: Mutex<WindowedRatio>,
+    executor_queue_wait: Mutex<WindowedSamples>,
+}
+
+impl Default for SloTracker {
+    fn default() -> Self {
+        Self::new(Instant::now())
+    }
+}
+
+impl SloTracker {
+    pub fn new(origin: Instant) -> Self {
+        Self {
+            signing_latency: Mutex::new(WindowedSamples::new(
+                SHORT_WINDOW,
+                SHORT_WINDOW_BUCKET,
+                origin,
+            )),
+            signing_errors_5m: Mutex::new(WindowedRatio::new(
+                SHORT_WINDOW,
+                SHORT_WINDOW_BUCKET,
+                origin,
+            )),
+            signing_errors_1h: Mutex::new(WindowedRatio::new(
+                LONG_WINDOW,
+                LONG_WINDOW_BUCKET,
+                origin,
+            )),
+            executor_queue_wait: Mutex::new(WindowedSamples::new(
+                SHORT_WINDOW,
+                SHORT_WINDOW_BUCKET,
+                origin,
+            )),
+        }
+    }
+
+    /// Records a signing request served by this node that took `latency`. `failed` is
+    /// whether the node failed to answer it, not whether it refused to sign.
+    pub fn record_signing_request(&self, latency: Duration, failed: bool, now: Instant) {
+        self.signing_latency
+            .lock()
+            .unwrap()
+            .record(latency.as_secs_f64(), now);
+        self.signing_errors_5m.lock().unwrap().record(failed, now);
+        self.signing_errors_1h.lock().unwrap().record(failed, now);
+    }
+
+    /// Records that an action waited `wait` in the executor signing queue.
+    pub fn record_executor_queue_wait(&self, wait: Duration, now: Instant) {
+        self.executor_queue_wait
+            .lock()
+            .unwrap()
+            .record(wait.as_secs_f64(), now);
+    }
+
+    pub fn snapshot(&self, now: Instant) -> SloSnapshot {
+        SloSnapshot {
+            signing_latency_p99_5m_secs: self.signing_latency.lock().unwrap().quantile(0.99, now),
+            signing_error_ratio_5m: self.signing_errors_5m.lock().unwrap().failure_ratio(now),
+            signing_error_ratio_1h: self.signing_errors_1h.lock().unwrap().failure_ratio(now),
+            executor_queue_wait_p95_5m_secs: self
+                .executor_queue_wait
+                .lock()
+                .unwrap()
+                .quantile(0.95, now),
+        }
+    }
+}
+
+/// Periodically sets the `bridge_slo_` gauges from `BridgeMetrics::slo`. Gauges of
+/// empty windows are set to 0.
+pub struct SloMetricsUpdater {
+    metrics: Arc<BridgeMetrics>,
+    interval: Duration,
+}
+
+impl SloMetricsUpdater {
+    pub fn new(metrics: Arc<BridgeMetrics>, interval: Duration) -> Self {
+        Self { metrics, interval }
+    }
+
+    pub async fn run(self) {
+        info!("Starting SloMetricsUpdater");
+        let mut interval = time::interval(self.interval);
+        interval.set_missed_tick_behavior(time::MissedTickBehavior::Delay);
+        loop {
+            interval.tick().await;
+            self.update(Instant::now());
+        }
+    }
+
+    fn update(&self, now: Instant) {
+        let snapshot = self.metrics.slo.snapshot(now);
+        self.metrics
+            .slo_signing_latency_p99
+            .set(snapshot.signing_latency_p99_5m_secs.unwrap_or(0.0));
+        self.metrics
+            .slo_signing_error_ratio
+            .with_label_values(&["5m"])
+            .set(snapshot.signing_error_ratio_5m.unwrap_or(0.0));
+        self.metrics
+            .slo_signing_error_ratio
+            .with_label_values(&["1h"])
+            .set(snapshot.signing_error_ratio_1h.unwrap_or(0.0));
+        self.metrics
+            .slo_executor_queue_wait_p95
+            .set(snapshot.executor_queue_wait_p95_5m_secs.unwrap_or(0.0));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sliding_window_rollover() {
+        let origin = Instant::now();
+        let mut samples =
+            WindowedSamples::new(Duration::from_secs(30), Duration::from_secs(10), origin);
+        samples.record(1.0, origin);
+        samples.record(2.0, origin + Duration::from_secs(5));
+        samples.record(3.0, origin + Duration::from_secs(15));
+        samples.record(4.0, origin + Duration::from_secs(25));
+        let now = origin + Duration::from_secs(29);
+        assert_eq!(samples.quantile(0.0, now), Some(1.0));
+        assert_eq!(samples.quantile(0.5, now), Some(2.0));
+        assert_eq!(samples.quantile(1.0, now), Some(4.0));
+
+        // The first bucket leaves the window as a whole
+        let now = origin + Duration::from_secs(30);
+        assert_eq!(samples.quantile(0.0, now), Some(3.0));
+        assert_eq!(samples.quantile(1.0, now), Some(4.0));
+        // Recording into a new bucket drops the expired ones too
+        samples.record(5.0, origin + Duration::from_secs(45));
+        let now = origin + Duration::from_secs(45);
+        assert_eq!(samples.quantile(0.0, now), Some(4.0));
+        assert_eq!(samples.quantile(1.0, now), Some(5.0));
+
+        let mut ratio =
+            WindowedRatio::new(Duration::from_secs(30), Duration::from_secs(10), origin);
+        ratio.record(true, origin);
+        ratio.record(false, origin + Duration::from_secs(10));
+        ratio.record(false, origin + Duration::from_secs(20));
+        ratio.record(true, origin + Duration::from_secs(20));
+        assert_eq!(
+            ratio.failure_ratio(origin + Duration::from_secs(29)),
+            Some(0.5)
+        );
+        assert_eq!(
+            ratio.failure_ratio(origin + Duration::from_secs(30)),
+            Some(1.0 / 3.0)
+        );
+        assert_eq!(
+            ratio.failure_ratio(origin + Duration::from_secs(40)),
+            Some(0.5)
+        );
+    }
+
+    #[test]
+    fn test_sliding_window_empty() {
+        let origin = Instant::now();
+        let mut samples =
+            WindowedSamples::new(Duration::from_secs(30), Duration::from_secs(10), origin);
+        let mut ratio =
+            WindowedRatio::new(Duration::from_secs(30), Duration::from_secs(10), origin);
+        assert_eq!(samples.quantile(0.99, origin), None);
+        assert_eq!(ratio.failure_ratio(origin), None);
+
+        samples.record(1.0, origin);
+        ratio.record(true, origin);
+        // Everything expired
+        let now = origin + Duration::from_secs(3600);
+        assert_eq!(samples.quantile(0.99, now), None);
+        assert_eq!(ratio.failure_ratio(now), None);
+
+        let tracker = SloTracker::new(origin);
+        assert_eq!(
+            tracker.snapshot(origin),
+            SloSnapshot {
+                signing_latency_p99_5m_secs: None,
+                signing_error_ratio_5m: None,
+                signing_error_ratio_1h: None,
+                executor_queue_wait_p95_5m_secs: None,
+            }
+        );
+    }
+
+    #[test]
+    fn test_slo_metrics_updater() {
+        let metrics = Arc::new(BridgeMetrics::for_testing());
+        let origin = Instant::now();
+        for i in 0..100 {
+            metrics.slo.record_signing_request(
+                Duration::from_millis(10 * (i + 1)),
+                i % 10 == 0,
+                origin,
+            );
+            metrics
+                .slo
+                .record_executor_queue_wait(Duration::from_millis(i + 1), origin);
+        }
+        let updater = SloMetricsUpdater::new(metrics.clone(), DEFAULT_SLO_UPDATE_INTERVAL);
+        updater.update(origin);
+        assert_eq!(metrics.slo_signing_latency_p99.get(), 0.99);
+        assert_eq!(
+            metrics
+                .slo_signing_error_ratio
+                .with_label_values(&["5m"])
+                .get(),
+            0.1
+        );
+        assert_eq!(metrics.slo_executor_queue_wait_p95.get(), 0.095);
+
+        // Only the 1h window still has the requests after 5 minutes
+        updater.update(origin + SHORT_WINDOW + SHORT_WINDOW_BUCKET);
+        assert_eq!(metrics.slo_signing_latency_p99.get(), 0.0);
+        assert_eq!(
+            metrics
+                .slo_signing_error_ratio
+                .with_label_values(&["5m"])
+                .get(),
+            0.0
+        );
+        assert_eq!(
+            metrics
+                .slo_signing_error_ratio
+                .with_label_values(&["1h"])
+                .get(),
+            0.1
+        );
+        assert_eq!(metrics.slo_executor_queue_wait_p95.get(), 0.0);
+    }
+}
@@ -0,0 +1,545 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Snapshots of a bridge node client for disaster recovery: everything needed to stand
+//! the node up on another machine, in a single `tar.zst` archive. The archive holds
+//! `manifest.json`, listing the other files with their sha256, and:
+//! - `storage.bcs`: every table of the client storage, see `StorageExport`
+//! - `config.yaml`: the node config, without secrets
+//! - `cursors.json` and `committee_scores.json`: readable copies of what is in the
+//!   storage, for operators to check the snapshot before restoring it
+//!
+//! Secrets are never included. Key files are only referenced by their path, and the
+//! secrets held in the config itself are removed or redacted. The manifest lists every
+//! item that was left out, for operators to provide it on the new machine.
+//! The node must be stopped while its db is snapshotted or restored.
+
+use crate::api_types::CommitteeMemberScore;
+use crate::config::BridgeNodeConfig;
+use crate::storage::{BridgeOrchestratorTables, StorageExport, STORAGE_EXPORT_VERSION};
+use crate::timestamps::now_ms;
+use anyhow::{anyhow, bail};
+use fastcrypto::encoding::{Encoding, Hex};
+use fastcrypto::hash::{HashFunction, Sha256};
+use fastcrypto::traits::ToFromBytes;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs::{File, OpenOptions};
+use std::io::Read;
+use std::path::Path;
+
+/// Bumped whenever files are added to or removed from the archive, or change format.
+pub const SNAPSHOT_FORMAT_VERSION: u32 = 1;
+
+const MANIFEST_FILE: &str = "manifest.json";
+const STORAGE_FILE: &str = "storage.bcs";
+const CONFIG_FILE: &str = "config.yaml";
+const CURSORS_FILE: &str = "cursors.json";
+const COMMITTEE_SCORES_FILE: &str = "committee_scores.json";
+
+// Replaces the config values that may embed credentials, e.g. api keys in rpc urls
+const REDACTED: &str = "<redacted>";
+const REDACTED_CONFIG_ENTRIES: &[&[&str]] = &[
+    &["sui", "sui-rpc-url"],
+    &["sui", "tx-submission", "fullnode-urls"],
+    &["eth", "eth-rpc-url"],
+    &["alerts", "webhook-url"],
+    &["metrics", "push-url"],
+];
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub struct SnapshotManifest {
+    pub format_version: u32,
+    pub storage_export_version: u32,
+    /// Version of the `sui-bridge` crate that created the snapshot
+    pub node_version: String,
+    pub created_at_ms: u64,
+    pub files: Vec<SnapshotFile>,
+    /// What was intentionally left out of the snapshot
+    pub omitted: Vec<OmittedItem>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub struct SnapshotFile {
+    pub name: String,
+    pub size: u64,
+    /// Hex encoded
+    pub sha256: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub struct OmittedItem {
+    pub item: String,
+    pub reason: String,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+struct SnapshotCursors {
+    /// The last processed event by Sui bridge module, as `<tx digest>:<event seq>`
+    sui: BTreeMap<String, String>,
+    /// The last processed block by Eth contract address
+    eth: BTreeMap<String, u64>,
+}
+
+/// Writes a snapshot of the client of the node configured by `config` to `out`, which
+/// must not exist yet.
+pub fn create_snapshot(config: &BridgeNodeConfig, out: &Path) -> anyhow::Result<SnapshotManifest> {
+    let db_path = config
+        .db_path
+        .as_ref()
+        .ok_or_else(|| anyhow!("db_path is not set, the node has no client storage"))?
+        .join("client");
+    if !db_path.exists() {
+        bail!("No client storage at {}", db_path.display());
+    }
+    // The export is not encrypted, even if the storage is
+    let store = BridgeOrchestratorTables::open_with_encryption(
+        &db_path,
+        config.aux_db_path.as_deref(),
+        config.storage_key_provider()?.as_deref(),
+    )
+    .map_err(|e| anyhow!("Couldn't open the client storage: {:?}", e))?;
+    let export = store.export();
+
+    let mut cursors = SnapshotCursors::default();
+    for (module, event_id) in &export.sui_syncer_cursors {
+        cursors.sui.insert(
+            module.to_string(),
+            format!("{}:{}", event_id.tx_digest, event_id.event_seq),
+        );
+    }
+    for (contract, block) in &export.eth_syncer_cursors {
+        cursors.eth.insert(format!("{:?}", contract), *block);
+    }
+    let committee_scores = export
+        .authority_scores
+        .iter()
+        .map(|(pubkey, score)| {
+            (
+                Hex::encode(pubkey.as_bytes()),
+                CommitteeMemberScore::from(score),
+            )
+        })
+        .collect::<BTreeMap<_, _>>();
+    let (config_yaml, omitted) = sanitize_config(config)?;
+
+    let contents = [
+        (STORAGE_FILE, bcs::to_bytes(&export)?),
+        (CONFIG_FILE, config_yaml),
+        (CURSORS_FILE, serde_json::to_vec_pretty(&cursors)?),
+        (
+            COMMITTEE_SCORES_FILE,
+            serde_json::to_vec_pretty(&committee_scores)?,
+        ),
+    ];
+    let manifest = SnapshotManifest {
+        format_version: SNAPSHOT_FORMAT_VERSION,
+        storage_export_version: STORAGE_EXPORT_VERSION,
+        node_version: env!("CARGO_PKG_VERSION").to_string(),
+        created_at_ms: now_ms(),
+        files: contents
+            .iter()
+            .map(|(name, bytes)| SnapshotFile {
+                name: name.to_string(),
+                size: bytes.len() as u64,
+                sha256: sha256(bytes),
+            })
+            .collect(),
+        omitted,
+    };
+
+    let file = OpenOptions::new().write(true).create_new(true).open(out)?;
+    let mut builder = tar::Builder::new(zstd::stream::write::Encoder::new(file, 0)?);
+    let manifest_json = serde_json::to_vec_pretty(&manifest)?;
+    let entries = std::iter::once((MANIFEST_FILE, &manifest_json))
+        .chain(contents.iter().map(|(name, bytes)| (*name, bytes)));
+    for (name, bytes) in entries {
+        let mut header = tar::Header::new_gnu();
+        header.set_size(bytes.len() as u64);
+        header.set_mode(0o600);
+        header.set_mtime(manifest.created_at_ms / 1000);
+        header.set_cksum();
+        builder.append_data(&mut header, name, bytes.as_slice())?;
+    }
+    builder.into_inner()?.finish()?.sync_all()?;
+    Ok(manifest)
+}
+
+/// Restores the snapshot in `archive` into `data_dir`, which must be empty or not
+/// exist. `data_dir` becomes the `db_path` of the restored config, written to
+/// `data_dir/config.yaml`. The executed actions are restored in the main db, as the
+/// path of the auxiliary db is specific to the machine the snapshot was created on.
+pub fn restore_snapshot(archive: &Path, data_dir: &Path) -> anyhow::Result<SnapshotManifest> {
+    if data_dir.exists() && std::fs::read_dir(data_dir)?.next().is_some() {
+        bail!(
+            "{} is not empty, refusing to restore onto it",
+            data_dir.display()
+        );
+    }
+    let files = read_archive(archive)?;
+    let manifest = verify_archive(&files)?;
+    let export: StorageExport = bcs::from_bytes(&files[STORAGE_FILE])
+        .map_err(|e| anyhow!("Couldn't decode {}: {:?}", STORAGE_FILE, e))?;
+    let mut config: serde_yaml::Value = serde_yaml::from_slice(&files[CONFIG_FILE])?;
+    let config_entries = config
+        .as_mapping_mut()
+        .ok_or_else(|| anyhow!("{} is not a mapping", CONFIG_FILE))?;
+    config_entries.insert("db-path".into(), data_dir.display().to_string().into());
+    config_entries.remove(&"aux-db-path".into());
+
+    std::fs::create_dir_all(data_dir)?;
+    let store = BridgeOrchestratorTables::new(&data_dir.join("client"));
+    store
+        .import(&export)
+        .map_err(|e| anyhow!("Couldn't import the storage: {:?}", e))?;
+    std::fs::write(data_dir.join(CONFIG_FILE), serde_yaml::to_string(&config)?)?;
+    Ok(manifest)
+}
+
+fn sha256(bytes: &[u8]) -> String {
+    Hex::encode(Sha256::digest(bytes).digest)
+}
+
+// Returns the YAML of `config` without its secrets, with the items that were left out
+fn sanitize_config(config: &BridgeNodeConfig) -> anyhow::Result<(Vec<u8>, Vec<OmittedItem>)> {
+    let mut yaml = serde_yaml::to_value(config)?;
+    let mut omitted = vec![];
+    let entries = yaml
+        .as_mapping_mut()
+        .ok_or_else(|| anyhow!("Config is not a mapping"))?;
+    if entries.remove(&"metrics-key-pair".into()).is_some() {
+        omitted.push(OmittedItem {
+            item: "metrics-key-pair".to_string(),
+            reason: "Private key, a new one is generated when the node starts".to_string(),
+        });
+    }
+    for path in REDACTED_CONFIG_ENTRIES {
+        let Some(value) = path
+            .iter()
+            .try_fold(&mut yaml, |value, key| value.get_mut(*key))
+        else {
+            continue;
+        };
+        *value = match value {
+            serde_yaml::Value::Sequence(values) => {
+                serde_yaml::Value::Sequence(values.iter().map(|_| REDACTED.into()).collect())
+            }
+            _ => REDACTED.into(),
+        };
+        omitted.push(OmittedItem {
+            item: path.join("."),
+            reason: "Url may embed credentials, it's redacted".to_string(),
+        });
+    }
+    // Keys are only referenced by the config, their files are never read
+    let key_paths = [
+        (
+            "bridge-authority-key-path",
+            config.bridge_authority_key_path.as_ref(),
+        ),
+        (
+            "sui.bridge-client-key-path",
+            config.sui.bridge_client_key_path.as_ref(),
+        ),
+        (
+            "gas-funding.funding-key-path",
+            config.gas_funding.as_ref().map(|f| &f.funding_key_path),
+        ),
+    ];
+    for (entry, path) in key_paths {
+        if let Some(path) = path {
+            omitted.push(OmittedItem {
+                item: format!("{} ({})", entry, path.display()),
+                reason: "Secret key, copy it to the new machine separately".to_string(),
+            });
+        }
+    }
+    // The snapshot holds the export of the storage, which is never encrypted
+    if let Some(path) = &config.storage_encryption_key_path {
+        omitted.push(OmittedItem {
+            item: format!("storage-encryption-key-path ({})", path.display()),
+            reason: "Secret key, copy it to the new machine separately, then encrypt the restored storage with `sui-bridge-cli storage encrypt-in-place`".to_string(),
+        });
+    }
+    if let Some(tx_journal) = &config.tx_journal {
+        omitted.push(OmittedItem {
+            item: format!("tx-journal.path ({})", tx_journal.path.display()),
+            reason: "Audit history of the submitted transactions, not needed to run the node"
+                .to_string(),
+        });
+    }
+    Ok((serde_yaml::to_string(&yaml)?.into_bytes(), omitted))
+}
+
+fn read_archive(archive: &Path) -> anyhow::Result<BTreeMap<String, Vec<u8>>> {
+    let decoder = zstd::stream::read::Decoder::new(File::open(archive)?)?;
+    let mut files = BTreeMap::new();
+    for entry in tar::Archive::new(decoder).entries()? {
+        let mut entry = entry?;
+        let name = entry.path()?.to_string_lossy().into_owned();
+        let mut bytes = vec![];
+        entry.read_to_end(&mut bytes)?;
+        if files.insert(name.clone(), bytes).is_some() {
+            bail!("{} is in the archive twice", name);
+        }
+    }
+    Ok(files)
+}
+
+// Checks that the archive has the files of its manifest and nothing else, unaltered,
+// and that this version can restore it
+fn verify_archive(files: &BTreeMap<String, Vec<u8>>) -> anyhow::Result<SnapshotManifest> {
+    let manifest: SnapshotManifest = serde_json::from_slice(
+        files
+            .get(MANIFEST_FILE)
+            .ok_or_else(|| anyhow!("The archive has no {}", MANIFEST_FILE))?,
+    )?;
+    if manifest.format_version != SNAPSHOT_FORMAT_VERSION {
+        bail!(
+            "Snapshot format version {} is not supported, expected {}",
+            manifest.format_version,
+            SNAPSHOT_FORMAT_VERSION
+        );
+    }
+    if manifest.storage_export_version != STORAGE_EXPORT_VERSION {
+        bail!(
+            "Storage export version {} is not supported, expected {}",
+            manifest.storage_export_version,
+            STORAGE_EXPORT_VERSION
+        );
+    }
+    for file in &manifest.files {
+        let bytes = files
+            .get(&file.name)
+            .ok_or_else(|| anyhow!("{} is missing from the archive", file.name))?;
+        if bytes.len() as u64 != file.size || sha256(bytes) != file.sha256 {
+            bail!("{} doesn't match its checksum in the manifest", file.name);
+        }
+    }
+    for name in files.keys() {
+        if name != MANIFEST_FILE && !manifest.files.iter().any(|file| &file.name == name) {
+            bail!("{} is not in the manifest", name);
+        }
+    }
+    for name in [STORAGE_FILE, CONFIG_FILE] {
+        if !files.contains_key(name) {
+            bail!("The archive has no {}", name);
+        }
+    }
+    Ok(manifest)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::client::authority_scores::AuthorityScore;
+    use crate::crypto::BridgeAuthorityPublicKeyBytes;
+    use crate::signed_set::SignedSet;
+    use crate::storage::QuarantinedAction;
+    use crate::test_utils::{
+        get_test_authority_and_key, get_test_eth_to_sui_bridge_action,
+        get_test_sui_to_eth_bridge_action,
+    };
+    use crate::utils::generate_bridge_node_config_and_write_to_file;
+    use std::str::FromStr;
+    use sui_config::Config;
+    use sui_types::digests::TransactionDigest;
+    use sui_types::event::EventID;
+    use sui_types::Identifier;
+
+    fn test_config(dir: &Path) -> BridgeNodeConfig {
+        let path = dir.join("bridge_node.yaml");
+        generate_bridge_node_config_and_write_to_file(&path, true).unwrap();
+        let mut config = BridgeNodeConfig::load(&path).unwrap();
+        config.db_path = Some(dir.join("db"));
+        config.eth.eth_rpc_url = "https://eth.example.com/v2/secret-api-key".to_string();
+        config
+    }
+
+    // Fills every table of the client storage of `config`
+    fn populate_storage(config: &BridgeNodeConfig) -> StorageExport {
+        let store = BridgeOrchestratorTables::new(&config.db_path.as_ref().unwrap().join("client"));
+        let pending = get_test_eth_to_sui_bridge_action(Some(1), None, None, None);
+        let executed =
+            get_test_sui_to_eth_bridge_action(None, None, Some(2), None, None, None, None);
+        let quarantined = get_test_eth_to_sui_bridge_action(Some(3), None, None, None);
+        store
+            .insert_pending_actions(&[pending.clone(), executed.clone()])
+            .unwrap();
+        store
+            .mark_action_executed_with_gas(&executed, 1_000_000)
+            .unwrap();
+        store
+            .quarantine_action(&QuarantinedAction {
+                action: quarantined,
+                reason: "Nonce already used".to_string(),
+                failures: 5,
+                quarantined_at_ms: 1_700_000_000_000,
+            })
+            .unwrap();
+        store
+            .update_sui_event_cursor(
+                Identifier::from_str("bridge").unwrap(),
+                EventID {
+                    tx_digest: TransactionDigest::random(),
+                    event_seq: 2,
+                },
+            )
+            .unwrap();
+        store
+            .update_eth_event_cursor(ethers::types::Address::random(), 100)
+            .unwrap();
+        SignedSet::new(store.clone())
+            .insert(&pending, 1_700_000_000_000)
+            .unwrap();
+        let (_, pubkey, _) = get_test_authority_and_key(2500, 0);
+        store
+            .replace_authority_scores(&BTreeMap::from([(
+                BridgeAuthorityPublicKeyBytes::from(&pubkey),
+                AuthorityScore {
+                    ewma_latency_ms: 120,
+                    failure_rate_bps: 0,
+                    last_success_timestamp_ms: Some(1_700_000_000_000),
+                },
+            )]))
+            .unwrap();
+        store.export()
+    }
+
+    // async: existing runtime is required with typed-store
+    #[tokio::test]
+    async fn test_snapshot_round_trip() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let config = test_config(temp_dir.path());
+        let export = populate_storage(&config);
+        let archive = temp_dir.path().join("snapshot.tar.zst");
+
+        let manifest = create_snapshot(&config, &archive).unwrap();
+        assert_eq!(manifest.format_version, SNAPSHOT_FORMAT_VERSION);
+        let names = manifest
+            .files
+            .iter()
+            .map(|file| file.name.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            names,
+            vec![
+                STORAGE_FILE,
+                CONFIG_FILE,
+                CURSORS_FILE,
+                COMMITTEE_SCORES_FILE
+            ]
+        );
+        let omitted = manifest
+            .omitted
+            .iter()
+            .map(|omitted| omitted.item.as_str())
+            .collect::<Vec<_>>();
+        assert!(omitted.contains(&"metrics-key-pair"));
+        assert!(omitted.contains(&"eth.eth-rpc-url"));
+        assert!(omitted
+            .iter()
+            .any(|item| item.starts_with("bridge-authority-key-path")));
+        // An existing archive is never overwritten
+        create_snapshot(&config, &archive).unwrap_err();
+
+        // Secrets are not in the archive
+        let files = read_archive(&archive).unwrap();
+        let config_yaml = String::from_utf8(files[CONFIG_FILE].clone()).unwrap();
+        assert!(!config_yaml.contains("secret-api-key"));
+        assert!(!config_yaml.contains("metrics-key-pair"));
+
+        let data_dir = temp_dir.path().join("restored");
+        assert_eq!(restore_snapshot(&archive, &data_dir).unwrap(), manifest);
+        let store = BridgeOrchestratorTables::new(&data_dir.join("client"));
+        assert_eq!(store.export(), export);
+        let restored = BridgeNodeConfig::load(data_dir.join(CONFIG_FILE)).unwrap();
+        assert_eq!(restored.db_path, Some(data_dir.clone()));
+        assert_eq!(restored.eth.eth_rpc_url, REDACTED);
+        assert_eq!(
+            restored.bridge_authority_key_path,
+            config.bridge_authority_key_path
+        );
+        drop(store);
+
+        // Restoring onto a non empty data dir is refused
+        let err = restore_snapshot(&archive, &data_dir).unwrap_err();
+        assert!(err.to_string().contains("not empty"));
+    }
+
+    #[tokio::test]
+    async fn test_restore_rejects_corrupted_archive() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let config = test_config(temp_dir.path());
+        populate_storage(&config);
+        let archive = temp_dir.path().join("snapshot.tar.zst");
+        create_snapshot(&config, &archive).unwrap();
+        let files = read_archive(&archive).unwrap();
+
+        // Rewrites the archive with `files`
+        let write_archive = |name: &str, files: &BTreeMap<String, Vec<u8>>| {
+            let path = temp_dir.path().join(format!("{}.tar.zst", name));
+            let encoder = zstd::stream::write::Encoder::new(File::create(&path).unwrap(), 0)
+                .unwrap()
+                .auto_finish();
+            let mut builder = tar::Builder::new(encoder);
+            for (name, bytes) in files {
+                let mut header = tar::Header::new_gnu();
+                header.set_size(bytes.len() as u64);
+                header.set_cksum();
+                builder
+                    .append_data(&mut header, name, bytes.as_slice())
+                    .unwrap();
+            }
+            builder.into_inner().unwrap();
+            path
+        };
+        let restore = |archive: &Path| {
+            let data_dir = tempfile::tempdir().unwrap();
+            let result = restore_snapshot(archive, data_dir.path());
+            // Nothing is restored from a rejected archive
+            if result.is_err() {
+                assert!(std::fs::read_dir(data_dir.path()).unwrap().next().is_none());
+            }
+            result
+        };
+        restore(&write_archive("rewritten", &files)).unwrap();
+
+        // A byte of the storage is flipped
+        let mut corrupted = files.clone();
+        corrupted.get_mut(STORAGE_FILE).unwrap()[0] ^= 1;
+        let err = restore(&write_archive("flipped", &corrupted)).unwrap_err();
+        assert!(err.to_string().contains("checksum"), "{}", err);
+
+        // A file is missing, or was added
+        let mut corrupted = files.clone();
+        corrupted.remove(CURSORS_FILE);
+        let err = restore(&write_archive("missing", &corrupted)).unwrap_err();
+        assert!(err.to_string().contains("missing"), "{}", err);
+        let mut corrupted = files.clone();
+        corrupted.insert("keys.json".to_string(), b"{}".to_vec());
+        let err = restore(&write_archive("added", &corrupted)).unwrap_err();
+        assert!(err.to_string().contains("not in the manifest"), "{}", err);
+
+        // Created by a newer version
+        let mut manifest: SnapshotManifest = serde_json::from_slice(&files[MANIFEST_FILE]).unwrap();
+        manifest.storage_export_version += 1;
+        let mut newer = files.clone();
+        newer.insert(
+            MANIFEST_FILE.to_string(),
+            serde_json::to_vec(&manifest).unwrap(),
+        );
+        let err = restore(&write_archive("newer", &newer)).unwrap_err();
+        assert!(err.to_string().contains("not supported"), "{}", err);
+
+        // Truncated archive
+        let path = temp_dir.path().join("truncated.tar.zst");
+        let bytes = std::fs::read(&archive).unwrap();
+        std::fs::write(&path, &bytes[..bytes.len() / 2]).unwrap();
+        restore(&path).unwrap_err();
+    }
+}
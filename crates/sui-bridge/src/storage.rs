@@ -1,46 +1,1019 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
-use std::collections::HashMap;
-use std::path::Path;
-use std::sync::Arc;
+use fastcrypto::encoding::{Encoding, Hex};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
 use sui_types::Identifier;
 
 use sui_types::event::EventID;
-use typed_store::rocks::{DBMap, MetricConf};
+use tracing::warn;
+use typed_store::rocks::{DBBatch, DBMap, MetricConf, RocksDB, ValueCodec};
+use typed_store::rocksdb::properties;
 use typed_store::traits::TableSummary;
 use typed_store::traits::TypedStoreDebug;
 use typed_store::DBMapUtils;
 use typed_store::Map;
+use typed_store::TypedStoreError;
 
+use crate::client::authority_scores::AuthorityScore;
+use crate::crypto::BridgeAuthorityPublicKeyBytes;
 use crate::error::{BridgeError, BridgeResult};
+use crate::metrics::BridgeMetrics;
+use crate::signed_set::SignedSetBucket;
+use crate::storage_encryption::{
+    PartlySealed, StorageCipher, StorageEncryptionRecord, StorageKeyProvider,
+};
+use crate::timestamps::now_ms;
 use crate::types::{BridgeAction, BridgeActionDigest};
 
+/// Version of the layout of the client tables, bumped with the changes to their keys or
+/// values that older nodes can't read.
+pub const STORAGE_SCHEMA_VERSION: u32 = 1;
+
+/// Interval at which the buffered syncer cursors are written, see
+/// `BridgeOrchestratorTables::enable_cursor_coalescing`.
+pub const DEFAULT_CURSOR_FLUSH_INTERVAL: Duration = Duration::from_secs(1);
+
 #[derive(DBMapUtils)]
-pub struct BridgeOrchestratorTables {
+pub struct BridgePrimaryTables {
     /// pending BridgeActions that orchestrator received but not yet executed
     pub(crate) pending_actions: DBMap<BridgeActionDigest, BridgeAction>,
     /// module identifier to the last processed EventID
     pub(crate) sui_syncer_cursors: DBMap<Identifier, EventID>,
     /// contract address to the last processed block
     pub(crate) eth_syncer_cursors: DBMap<ethers::types::Address, u64>,
+    /// BridgeActions that were dropped without being executed, e.g. token transfers
+    /// below the minimum transfer amount, or approvals whose signatures stay invalid
+    pub(crate) dropped_actions: DBMap<BridgeActionDigest, BridgeAction>,
+    /// why the actions in `dropped_actions` were dropped
+    pub(crate) dropped_action_reasons: DBMap<BridgeActionDigest, DroppedActionReason>,
+    /// token transfers that are done on Sui, by (source chain id, destination chain id, nonce).
+    /// Only used when there is no auxiliary db.
+    pub(crate) executed_actions: DBMap<(u8, u8, u64), BridgeAction>,
+    /// gas spent on Sui by the transactions that executed the token transfers in
+    /// `executed_actions`, in mist net of storage rebates, by the same key. Only used when
+    /// there is no auxiliary db.
+    pub(crate) executed_action_gas: DBMap<(u8, u8, u64), u64>,
+    /// claims on Eth of the token transfers from Sui in `executed_actions`, by the same
+    /// key, see `claim_tracker`. Only used when there is no auxiliary db.
+    pub(crate) transfer_claims: DBMap<(u8, u8, u64), TransferClaim>,
+    /// local time in milliseconds when the token transfers in `executed_actions` were
+    /// recorded as executed, by the same key. Transfers executed before the time was
+    /// recorded have no entry. Only used when there is no auxiliary db.
+    pub(crate) executed_action_times: DBMap<(u8, u8, u64), u64>,
+    /// path of the auxiliary db the last time one was opened
+    pub(crate) aux_db_path: DBMap<u8, PathBuf>,
+    /// availability scores of the committee members, as of the last time they were persisted
+    pub(crate) authority_scores: DBMap<BridgeAuthorityPublicKeyBytes, AuthorityScore>,
+    /// BridgeActions that failed permanently too many times. Unlike dropped actions,
+    /// they are expected to be deleted or requeued by an operator.
+    pub(crate) quarantined_actions: DBMap<BridgeActionDigest, QuarantinedAction>,
+    /// audit log of the syncer cursors that operators reset, in the order of the resets
+    pub(crate) cursor_resets: DBMap<u64, CursorResetRecord>,
+    /// who requested the resets in `cursor_resets`, by the same index. Resets from before
+    /// the callers were recorded have no entry.
+    pub(crate) cursor_reset_origins: DBMap<u64, AdminRequestOrigin>,
+    /// responses to the mutations requested through the admin api, by caller and
+    /// idempotency key, to return them again when a request is replayed
+    pub(crate) admin_requests: DBMap<(String, String), AdminRequestRecord>,
+    /// audit log of the mutations requested through the admin api, in the order they were
+    /// done. Unlike `admin_requests`, entries are never deleted.
+    pub(crate) admin_audit: DBMap<u64, AdminAuditRecord>,
+    /// actions this node signed, to the start of the bucket of the signed set they were
+    /// added to, see `signed_set`
+    pub(crate) signed_actions: DBMap<BridgeActionDigest, u64>,
+    /// digest and size of the buckets of the signed set, by the start of the bucket
+    pub(crate) signed_set_buckets: DBMap<u64, SignedSetBucket>,
+    /// the last time the actions in `quarantined_actions` were verified again, see
+    /// `quarantine_recheck`. Actions that were never verified again have no entry.
+    pub(crate) quarantine_checks: DBMap<BridgeActionDigest, QuarantineCheck>,
+    /// wrapped data key of the db if it's encrypted, or of its encryption in place while
+    /// it's not done, see `storage_encryption`. Never encrypted itself.
+    pub(crate) storage_encryption: DBMap<u8, StorageEncryptionRecord>,
+}
+
+/// Tables that grow with the bridge's history and can live on a different disk than
+/// the primary tables. Writes are only atomic within each db.
+#[derive(DBMapUtils)]
+pub struct BridgeAuxTables {
+    /// token transfers that are done on Sui, by (source chain id, destination chain id, nonce)
+    pub(crate) executed_actions: DBMap<(u8, u8, u64), BridgeAction>,
+    /// gas spent on Sui by the transactions that executed the token transfers in
+    /// `executed_actions`, in mist net of storage rebates, by the same key
+    pub(crate) executed_action_gas: DBMap<(u8, u8, u64), u64>,
+    /// claims on Eth of the token transfers from Sui in `executed_actions`, by the same
+    /// key, see `claim_tracker`
+    pub(crate) transfer_claims: DBMap<(u8, u8, u64), TransferClaim>,
+    /// local time in milliseconds when the token transfers in `executed_actions` were
+    /// recorded as executed, by the same key
+    pub(crate) executed_action_times: DBMap<(u8, u8, u64), u64>,
+    /// wrapped data key of the db if it's encrypted, with a key of its own
+    pub(crate) storage_encryption: DBMap<u8, StorageEncryptionRecord>,
+}
+
+/// Visits the tables of a db whose values are encrypted when the db is.
+trait TableVisitor {
+    fn visit<K, V>(&mut self, name: &'static str, table: &mut DBMap<K, V>) -> BridgeResult<()>
+    where
+        K: Serialize + DeserializeOwned,
+        V: Serialize + DeserializeOwned;
+}
+
+trait EncryptableTables {
+    fn storage_encryption(&self) -> &DBMap<u8, StorageEncryptionRecord>;
+
+    fn visit_tables(&mut self, visitor: &mut impl TableVisitor) -> BridgeResult<()>;
+}
+
+impl EncryptableTables for BridgePrimaryTables {
+    fn storage_encryption(&self) -> &DBMap<u8, StorageEncryptionRecord> {
+        &self.storage_encryption
+    }
+
+    fn visit_tables(&mut self, visitor: &mut impl TableVisitor) -> BridgeResult<()> {
+        // Destructured so that a new table can't be left out
+        let Self {
+            pending_actions,
+            sui_syncer_cursors,
+            eth_syncer_cursors,
+            dropped_actions,
+            dropped_action_reasons,
+            executed_actions,
+            executed_action_gas,
+            transfer_claims,
+            executed_action_times,
+            aux_db_path,
+            authority_scores,
+            quarantined_actions,
+            cursor_resets,
+            cursor_reset_origins,
+            admin_requests,
+            admin_audit,
+            signed_actions,
+            signed_set_buckets,
+            quarantine_checks,
+            storage_encryption: _,
+        } = self;
+        visitor.visit("pending_actions", pending_actions)?;
+        visitor.visit("sui_syncer_cursors", sui_syncer_cursors)?;
+        visitor.visit("eth_syncer_cursors", eth_syncer_cursors)?;
+        visitor.visit("dropped_actions", dropped_actions)?;
+        visitor.visit("dropped_action_reasons", dropped_action_reasons)?;
+        visitor.visit("executed_actions", executed_actions)?;
+        visitor.visit("executed_action_gas", executed_action_gas)?;
+        visitor.visit("transfer_claims", transfer_claims)?;
+        visitor.visit("executed_action_times", executed_action_times)?;
+        visitor.visit("aux_db_path", aux_db_path)?;
+        visitor.visit("authority_scores", authority_scores)?;
+        visitor.visit("quarantined_actions", quarantined_actions)?;
+        visitor.visit("cursor_resets", cursor_resets)?;
+        visitor.visit("cursor_reset_origins", cursor_reset_origins)?;
+        visitor.visit("admin_requests", admin_requests)?;
+        visitor.visit("admin_audit", admin_audit)?;
+        visitor.visit("signed_actions", signed_actions)?;
+        visitor.visit("signed_set_buckets", signed_set_buckets)?;
+        visitor.visit("quarantine_checks", quarantine_checks)
+    }
+}
+
+impl EncryptableTables for BridgeAuxTables {
+    fn storage_encryption(&self) -> &DBMap<u8, StorageEncryptionRecord> {
+        &self.storage_encryption
+    }
+
+    fn visit_tables(&mut self, visitor: &mut impl TableVisitor) -> BridgeResult<()> {
+        let Self {
+            executed_actions,
+            executed_action_gas,
+            transfer_claims,
+            executed_action_times,
+            storage_encryption: _,
+        } = self;
+        visitor.visit("executed_actions", executed_actions)?;
+        visitor.visit("executed_action_gas", executed_action_gas)?;
+        visitor.visit("transfer_claims", transfer_claims)?;
+        visitor.visit("executed_action_times", executed_action_times)
+    }
+}
+
+struct CountEntries(u64);
+
+impl TableVisitor for CountEntries {
+    fn visit<K, V>(&mut self, _name: &'static str, table: &mut DBMap<K, V>) -> BridgeResult<()> {
+        self.0 += table.iterator_cf().count() as u64;
+        Ok(())
+    }
+}
+
+struct SetValueCodec(Arc<dyn ValueCodec>);
+
+impl TableVisitor for SetValueCodec {
+    fn visit<K, V>(&mut self, name: &'static str, table: &mut DBMap<K, V>) -> BridgeResult<()> {
+        table.set_value_codec(self.0.clone()).map_err(|e| {
+            BridgeError::StorageError(format!("Couldn't set the encryption of {}: {:?}", name, e))
+        })
+    }
+}
+
+// Seals the plaintext entries of the tables, which are read through `codec`, a chunk at a
+// time
+struct EncryptEntries {
+    codec: Arc<PartlySealed>,
+    chunk_size: usize,
+    entries: u64,
+}
+
+impl TableVisitor for EncryptEntries {
+    fn visit<K, V>(&mut self, name: &'static str, table: &mut DBMap<K, V>) -> BridgeResult<()>
+    where
+        K: Serialize + DeserializeOwned,
+        V: Serialize + DeserializeOwned,
+    {
+        let read_error = |e| BridgeError::StorageError(format!("Couldn't read {}: {:?}", name, e));
+        // Checks that all the entries can be decoded before sealing any, and finds the first
+        // plaintext one: the chunks are sealed in order, so the entries sealed by an
+        // interrupted migration come first
+        let (mut decodable, mut first_plaintext) = (0, None);
+        let mut entries = table.safe_iter();
+        loop {
+            let plaintext_reads = self.codec.plaintext_reads();
+            let Some(entry) = entries.next() else {
+                break;
+            };
+            let (key, _) = entry.map_err(read_error)?;
+            decodable += 1;
+            if first_plaintext.is_none() && self.codec.plaintext_reads() > plaintext_reads {
+                first_plaintext = Some(key);
+            }
+        }
+        drop(entries);
+        let stored = table.iterator_cf().count();
+        if decodable != stored {
+            return Err(BridgeError::StorageError(format!(
+                "{}: only {} of {} entries can be decoded, run `sui-bridge-cli storage verify`",
+                name, decodable, stored
+            )));
+        }
+        let Some(first_plaintext) = first_plaintext else {
+            return Ok(());
+        };
+        let mut entries = table.safe_iter_with_bounds(Some(first_plaintext), None);
+        loop {
+            let chunk = entries
+                .by_ref()
+                .take(self.chunk_size)
+                .collect::<Result<Vec<_>, _>>()
+                .map_err(read_error)?;
+            if chunk.is_empty() {
+                return Ok(());
+            }
+            let mut batch = table.batch();
+            batch
+                .insert_batch(table, chunk.iter().map(|(key, value)| (key, value)))
+                .map_err(|e| insert_error(name, e))?;
+            batch.write().map_err(|e| insert_error(name, e))?;
+            self.entries += chunk.len() as u64;
+        }
+    }
+}
+
+// Compacts the tables, so that the files with the plaintext entries are deleted
+struct CompactTables;
+
+impl TableVisitor for CompactTables {
+    fn visit<K, V>(&mut self, _name: &'static str, table: &mut DBMap<K, V>) -> BridgeResult<()> {
+        table
+            .rocksdb
+            .compact_range_to_bottom(&table.cf(), None::<Vec<u8>>, None::<Vec<u8>>);
+        Ok(())
+    }
+}
+
+fn get_encryption_record(
+    tables: &impl EncryptableTables,
+    db: &str,
+    key: u8,
+) -> BridgeResult<Option<StorageEncryptionRecord>> {
+    tables.storage_encryption().get(&key).map_err(|e| {
+        BridgeError::StorageError(format!("Couldn't read the encryption of {}: {:?}", db, e))
+    })
+}
+
+/// Sets the data key of `tables` on them if the db is encrypted, or if it's empty and
+/// `key_provider` is set, in which case a new data key is generated. Fails closed: an
+/// encrypted db can't be opened without its key, and a db with plaintext entries can't be
+/// opened with a key, nor one whose encryption in place is not done, see
+/// `BridgeOrchestratorTables::encrypt_in_place`.
+fn set_up_encryption(
+    tables: &mut impl EncryptableTables,
+    db: &str,
+    key_provider: Option<&dyn StorageKeyProvider>,
+) -> BridgeResult<bool> {
+    if get_encryption_record(tables, db, PENDING_STORAGE_ENCRYPTION_KEY)?.is_some() {
+        return Err(BridgeError::StorageError(format!(
+            "The encryption in place of {} was interrupted, run `sui-bridge-cli storage encrypt-in-place` again to finish it",
+            db
+        )));
+    }
+    let cipher = match (
+        get_encryption_record(tables, db, STORAGE_ENCRYPTION_KEY)?,
+        key_provider,
+    ) {
+        (None, None) => return Ok(false),
+        (Some(record), None) => {
+            return Err(BridgeError::StorageError(format!(
+                "{} is encrypted with key {}, but no storage encryption key is configured",
+                db, record.key_id
+            )))
+        }
+        (Some(record), Some(key_provider)) => StorageCipher::unwrap(&record, key_provider)?,
+        (None, Some(key_provider)) => {
+            let mut count = CountEntries(0);
+            tables.visit_tables(&mut count)?;
+            if count.0 > 0 {
+                return Err(BridgeError::StorageError(format!(
+                    "{} has {} plaintext entries, encrypt them with `sui-bridge-cli storage encrypt-in-place` first",
+                    db, count.0
+                )));
+            }
+            let (cipher, record) = StorageCipher::generate(key_provider)?;
+            tables
+                .storage_encryption()
+                .insert(&STORAGE_ENCRYPTION_KEY, &record)
+                .map_err(|e| insert_error("storage_encryption", e))?;
+            cipher
+        }
+    };
+    tables.visit_tables(&mut SetValueCodec(Arc::new(cipher)))?;
+    Ok(true)
+}
+
+/// Encrypts the plaintext entries of `tables` with a new data key, `chunk_size` entries
+/// per batch. The key is recorded as pending before any entry is sealed, and becomes the
+/// key of the db once they all are, so an interrupted migration is resumed with the same
+/// key. Returns the number of entries sealed, or None if the db is encrypted already.
+fn encrypt_tables_in_place(
+    tables: &mut impl EncryptableTables,
+    db: &str,
+    key_provider: &dyn StorageKeyProvider,
+    chunk_size: usize,
+) -> BridgeResult<Option<u64>> {
+    if get_encryption_record(tables, db, STORAGE_ENCRYPTION_KEY)?.is_some() {
+        return Ok(None);
+    }
+    let (cipher, record) = match get_encryption_record(tables, db, PENDING_STORAGE_ENCRYPTION_KEY)?
+    {
+        Some(record) => (StorageCipher::unwrap(&record, key_provider)?, record),
+        None => {
+            let (cipher, record) = StorageCipher::generate(key_provider)?;
+            tables
+                .storage_encryption()
+                .insert(&PENDING_STORAGE_ENCRYPTION_KEY, &record)
+                .map_err(|e| insert_error("storage_encryption", e))?;
+            (cipher, record)
+        }
+    };
+    let codec = Arc::new(PartlySealed::new(cipher));
+    tables.visit_tables(&mut SetValueCodec(codec.clone()))?;
+    let mut encrypt = EncryptEntries {
+        codec,
+        chunk_size,
+        entries: 0,
+    };
+    tables.visit_tables(&mut encrypt)?;
+    let mut batch = tables.storage_encryption().batch();
+    batch
+        .delete_batch(
+            tables.storage_encryption(),
+            [PENDING_STORAGE_ENCRYPTION_KEY],
+        )
+        .map_err(|e| insert_error("storage_encryption", e))?;
+    batch
+        .insert_batch(
+            tables.storage_encryption(),
+            [(STORAGE_ENCRYPTION_KEY, record)],
+        )
+        .map_err(|e| insert_error("storage_encryption", e))?;
+    batch
+        .write()
+        .map_err(|e| insert_error("storage_encryption", e))?;
+    tables.visit_tables(&mut CompactTables)?;
+    Ok(Some(encrypt.entries))
+}
+
+pub struct BridgeOrchestratorTables {
+    primary: BridgePrimaryTables,
+    aux: Option<BridgeAuxTables>,
+    cursor_buffer: Mutex<CursorBuffer>,
+}
+
+/// The latest cursor of each syncer that was not written yet. Cursors are written through
+/// until coalescing is enabled.
+#[derive(Default)]
+struct CursorBuffer {
+    // Set once coalescing is enabled
+    metrics: Option<Arc<BridgeMetrics>>,
+    sui: BTreeMap<Identifier, EventID>,
+    eth: BTreeMap<ethers::types::Address, u64>,
+}
+
+impl CursorBuffer {
+    fn is_empty(&self) -> bool {
+        self.sui.is_empty() && self.eth.is_empty()
+    }
+
+    // Adds the buffered cursors to `batch`
+    fn insert_batch(&self, tables: &BridgePrimaryTables, batch: &mut DBBatch) -> BridgeResult<()> {
+        batch
+            .insert_batch(&tables.sui_syncer_cursors, self.sui.iter())
+            .map_err(|e| insert_error("sui_syncer_cursors", e))?
+            .insert_batch(&tables.eth_syncer_cursors, self.eth.iter())
+            .map_err(|e| insert_error("eth_syncer_cursors", e))?;
+        Ok(())
+    }
+
+    // Records that the buffered cursors were written because of `reason`, and forgets them
+    fn clear(&mut self, reason: &str) {
+        if let Some(metrics) = &self.metrics {
+            metrics
+                .storage_cursor_flushes
+                .with_label_values(&[reason])
+                .inc();
+            metrics
+                .storage_cursors_flushed
+                .inc_by((self.sui.len() + self.eth.len()) as u64);
+        }
+        self.sui.clear();
+        self.eth.clear();
+    }
+}
+
+/// The cursor of a syncer after a page of events, see
+/// `BridgeOrchestratorTables::commit_pending_actions`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum SyncerCursor {
+    Sui(Identifier, EventID),
+    Eth(ethers::types::Address, u64),
+}
+
+// Key of the single entry in `aux_db_path`
+const AUX_DB_PATH_KEY: u8 = 0;
+// Key of the data key of the db in `storage_encryption`
+const STORAGE_ENCRYPTION_KEY: u8 = 0;
+// Key of the data key of an encryption in place that is not done in `storage_encryption`
+const PENDING_STORAGE_ENCRYPTION_KEY: u8 = 1;
+// Entries sealed per batch by `BridgeOrchestratorTables::encrypt_in_place`
+const ENCRYPT_IN_PLACE_CHUNK_SIZE: usize = 1000;
+
+/// Name of the primary db in the checkpoints and the storage maintenance.
+pub const PRIMARY_DB: &str = "primary";
+/// Name of the auxiliary db in the checkpoints and the storage maintenance.
+pub const AUX_DB: &str = "aux";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum DroppedActionReason {
+    // Token transfer below the minimum transfer amount
+    BelowMinimumTransfer,
+    // Signatures were still invalid on chain after being aggregated again
+    InvalidSignatures,
+    // This node is configured not to execute actions of this type
+    ActionTypeNotAllowed,
+    // An operator deleted the action from quarantine
+    DeletedFromQuarantine,
+}
+
+/// An action that the executor stopped retrying because it failed permanently too many
+/// times, or a token transfer whose amount the orchestrator found can't be converted
+/// exactly between the decimals of Sui and Eth, or whose recipient can't be claimed. The
+/// latter is recorded with the zero address as its recipient. It stays quarantined until
+/// it is deleted or requeued with `sui-bridge-cli`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct QuarantinedAction {
+    pub action: BridgeAction,
+    /// The last permanent failure
+    pub reason: String,
+    pub failures: u64,
+    pub quarantined_at_ms: u64,
+}
+
+/// The claim on Eth of a token transfer from Sui that was approved on Sui.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TransferClaim {
+    /// When the claim tracker first found the transfer approved, shortly after its
+    /// approval unless the node was down
+    pub approved_at_ms: u64,
+    /// When the transfer was found claimed, None while it's not
+    pub claimed_at_ms: Option<u64>,
+    /// The transaction that claimed the transfer, None until it's found. It's only looked
+    /// for in the blocks since the transfer was last found unclaimed.
+    pub claim_tx_hash: Option<ethers::types::TxHash>,
+}
+
+/// The last verification of a quarantined action against the chain that failed.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct QuarantineCheck {
+    pub checked_at_ms: u64,
+    /// Why the action is still invalid
+    pub reason: String,
+}
+
+/// A syncer cursor that an operator reset through the admin api, with its previous value.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CursorResetRecord {
+    pub cursor: ResetCursor,
+    /// Whether the reset was forced past events that were not processed yet
+    pub forced: bool,
+    pub reset_at_ms: u64,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ResetCursor {
+    /// The last processed block of an Eth contract
+    Eth {
+        contract: ethers::types::Address,
+        old_block: Option<u64>,
+        new_block: u64,
+    },
+    /// The last processed event of a Sui bridge module
+    Sui {
+        module: Identifier,
+        old_event_id: Option<EventID>,
+        new_event_id: EventID,
+    },
+}
+
+/// The caller and the idempotency key of a mutation requested through the admin api.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AdminRequestOrigin {
+    pub caller: String,
+    pub idempotency_key: String,
+}
+
+/// The response to a mutation requested through the admin api, returned as is when the
+/// request is replayed with the same idempotency key.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AdminRequestRecord {
+    pub path: String,
+    /// Digest of the path and body of the request, a key can't be reused for another one
+    pub request_digest: [u8; 32],
+    pub status: u16,
+    pub content_type: Option<String>,
+    pub body: Vec<u8>,
+    pub processed_at_ms: u64,
+}
+
+/// A mutation requested through the admin api, as recorded in the audit log.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AdminAuditRecord {
+    pub caller: String,
+    pub method: String,
+    pub path: String,
+    pub idempotency_key: String,
+    /// Status of the response to the request
+    pub status: u16,
+    pub requested_at_ms: u64,
+}
+
+/// Version of the layout of `StorageExport`, bumped whenever a table is added or the
+/// type of its entries changes.
+pub const STORAGE_EXPORT_VERSION: u32 = 8;
+
+/// The entries of every client table, to move the storage to another machine. The path
+/// of the auxiliary db is left out, as it's specific to the machine.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StorageExport {
+    pub pending_actions: Vec<(BridgeActionDigest, BridgeAction)>,
+    pub sui_syncer_cursors: Vec<(Identifier, EventID)>,
+    pub eth_syncer_cursors: Vec<(ethers::types::Address, u64)>,
+    pub dropped_actions: Vec<(BridgeActionDigest, BridgeAction)>,
+    pub dropped_action_reasons: Vec<(BridgeActionDigest, DroppedActionReason)>,
+    pub executed_actions: Vec<((u8, u8, u64), BridgeAction)>,
+    pub executed_action_gas: Vec<((u8, u8, u64), u64)>,
+    pub authority_scores: Vec<(BridgeAuthorityPublicKeyBytes, AuthorityScore)>,
+    pub quarantined_actions: Vec<(BridgeActionDigest, QuarantinedAction)>,
+    pub cursor_resets: Vec<(u64, CursorResetRecord)>,
+    pub cursor_reset_origins: Vec<(u64, AdminRequestOrigin)>,
+    pub admin_requests: Vec<((String, String), AdminRequestRecord)>,
+    pub signed_actions: Vec<(BridgeActionDigest, u64)>,
+    pub signed_set_buckets: Vec<(u64, SignedSetBucket)>,
+    pub quarantine_checks: Vec<(BridgeActionDigest, QuarantineCheck)>,
+    pub transfer_claims: Vec<((u8, u8, u64), TransferClaim)>,
+    pub executed_action_times: Vec<((u8, u8, u64), u64)>,
+    pub admin_audit: Vec<(u64, AdminAuditRecord)>,
+}
+
+/// A broken invariant between the client tables, found by `verify_integrity`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum StorageViolation {
+    /// Entries of a table that can't be decoded. Reading the table stops at the first one,
+    /// so only `decoded` of its `entries` are checked.
+    UndecodableEntries {
+        table: &'static str,
+        entries: u64,
+        decoded: u64,
+    },
+    /// An action stored under another key than its digest
+    MisplacedAction {
+        table: &'static str,
+        key: BridgeActionDigest,
+        digest: BridgeActionDigest,
+    },
+    /// A token transfer stored in `executed_actions` under another key than its route and
+    /// nonce, or an action that is not a token transfer
+    MisplacedExecutedAction {
+        key: (u8, u8, u64),
+        expected_key: Option<(u8, u8, u64)>,
+    },
+    /// A pending action that was executed already, e.g. when the node stopped between
+    /// recording it as executed in the auxiliary db and removing it from pending actions
+    ExecutedActionStillPending {
+        digest: BridgeActionDigest,
+    },
+    DroppedActionStillPending {
+        digest: BridgeActionDigest,
+    },
+    QuarantinedActionStillPending {
+        digest: BridgeActionDigest,
+    },
+    /// The reason of a dropped action that is not in `dropped_actions`
+    OrphanedDropReason {
+        digest: BridgeActionDigest,
+    },
+    /// Syncers only move cursors forward after a reset, so the cursor of a contract is
+    /// never before the block it was last reset to, unless it's overridden in the config.
+    EthCursorBeforeReset {
+        contract: ethers::types::Address,
+        cursor: Option<u64>,
+        reset_block: u64,
+    },
+    /// A Sui module whose cursor was reset has no cursor
+    SuiCursorMissingAfterReset {
+        module: Identifier,
+    },
+    /// The indices of the cursor reset audit log are not consecutive
+    CursorResetLogGap {
+        expected_index: u64,
+        index: u64,
+    },
+}
+
+impl StorageViolation {
+    /// Whether `repair_integrity` fixes it. The others need an operator, e.g. to restore
+    /// a snapshot or reset a cursor.
+    pub fn is_repairable(&self) -> bool {
+        matches!(
+            self,
+            StorageViolation::MisplacedAction { .. }
+                | StorageViolation::MisplacedExecutedAction { .. }
+                | StorageViolation::ExecutedActionStillPending { .. }
+                | StorageViolation::DroppedActionStillPending { .. }
+                | StorageViolation::QuarantinedActionStillPending { .. }
+                | StorageViolation::OrphanedDropReason { .. }
+        )
+    }
+}
+
+impl std::fmt::Display for StorageViolation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let hex = |digest: &BridgeActionDigest| Hex::encode(digest.inner());
+        match self {
+            StorageViolation::UndecodableEntries {
+                table,
+                entries,
+                decoded,
+            } => write!(
+                f,
+                "{}: only {} of {} entries can be decoded",
+                table, decoded, entries
+            ),
+            StorageViolation::MisplacedAction { table, key, digest } => write!(
+                f,
+                "{}: action {} is stored under {}",
+                table,
+                hex(digest),
+                hex(key)
+            ),
+            StorageViolation::MisplacedExecutedAction { key, expected_key } => match expected_key {
+                Some(expected_key) => write!(
+                    f,
+                    "executed_actions: token transfer {:?} is stored under {:?}",
+                    expected_key, key
+                ),
+                None => write!(f, "executed_actions: {:?} is not a token transfer", key),
+            },
+            StorageViolation::ExecutedActionStillPending { digest } => write!(
+                f,
+                "pending_actions: action {} is executed already",
+                hex(digest)
+            ),
+            StorageViolation::DroppedActionStillPending { digest } => write!(
+                f,
+                "pending_actions: action {} is dropped already",
+                hex(digest)
+            ),
+            StorageViolation::QuarantinedActionStillPending { digest } => {
+                write!(f, "pending_actions: action {} is quarantined", hex(digest))
+            }
+            StorageViolation::OrphanedDropReason { digest } => write!(
+                f,
+                "dropped_action_reasons: action {} is not dropped",
+                hex(digest)
+            ),
+            StorageViolation::EthCursorBeforeReset {
+                contract,
+                cursor,
+                reset_block,
+            } => write!(
+                f,
+                "eth_syncer_cursors: cursor {:?} of {:?} is before block {} it was reset to",
+                cursor, contract, reset_block
+            ),
+            StorageViolation::SuiCursorMissingAfterReset { module } => write!(
+                f,
+                "sui_syncer_cursors: module {} was reset but has no cursor",
+                module
+            ),
+            StorageViolation::CursorResetLogGap {
+                expected_index,
+                index,
+            } => write!(
+                f,
+                "cursor_resets: found index {} instead of {}",
+                index, expected_index
+            ),
+        }
+    }
+}
+
+/// Adds a violation to `violations` if some entries of `table` can't be decoded.
+fn verify_decodable<K, V>(
+    table: &DBMap<K, V>,
+    name: &'static str,
+    violations: &mut Vec<StorageViolation>,
+) where
+    K: Serialize + DeserializeOwned,
+    V: Serialize + DeserializeOwned,
+{
+    let entries = match table.table_summary() {
+        Ok(summary) => summary.num_keys,
+        Err(e) => {
+            warn!("Couldn't count the entries of {}: {:?}", name, e);
+            return;
+        }
+    };
+    let decoded = table.unbounded_iter().count() as u64;
+    if decoded < entries {
+        violations.push(StorageViolation::UndecodableEntries {
+            table: name,
+            entries,
+            decoded,
+        });
+    }
+}
+
+fn contains_action<V>(table: &DBMap<BridgeActionDigest, V>, digest: &BridgeActionDigest) -> bool
+where
+    V: Serialize + DeserializeOwned,
+{
+    table.contains_key(digest).unwrap_or_else(|e| {
+        warn!("Couldn't read {:?}: {:?}", digest, e);
+        false
+    })
+}
+
+/// Moves the entry of `table` at `key` to `digest`, or deletes it if there is one at
+/// `digest` already. Returns what was done.
+fn relink_action<V>(
+    table: &DBMap<BridgeActionDigest, V>,
+    name: &str,
+    key: &BridgeActionDigest,
+    digest: &BridgeActionDigest,
+) -> BridgeResult<String>
+where
+    V: Serialize + DeserializeOwned,
+{
+    let storage_error = |e: TypedStoreError| {
+        BridgeError::StorageError(format!("Couldn't repair {}: {:?}", name, e))
+    };
+    let Some(value) = table.get(key).map_err(storage_error)? else {
+        return Ok(format!("{}: {} is gone", name, Hex::encode(key.inner())));
+    };
+    let mut batch = table.batch();
+    let done = if table.contains_key(digest).map_err(storage_error)? {
+        format!(
+            "{}: deleted {}, the action is stored under {} already",
+            name,
+            Hex::encode(key.inner()),
+            Hex::encode(digest.inner())
+        )
+    } else {
+        batch
+            .insert_batch(table, [(digest, value)])
+            .map_err(storage_error)?;
+        format!(
+            "{}: moved {} to {}",
+            name,
+            Hex::encode(key.inner()),
+            Hex::encode(digest.inner())
+        )
+    };
+    batch.delete_batch(table, [key]).map_err(storage_error)?;
+    batch.write().map_err(storage_error)?;
+    Ok(done)
+}
+
+fn insert_error(table: &str, e: impl std::fmt::Debug) -> BridgeError {
+    BridgeError::StorageError(format!("Couldn't insert into {}: {:?}", table, e))
+}
+
+/// Returns the key of `action` in `executed_actions`, None if it's not a token transfer.
+fn executed_action_key(action: &BridgeAction) -> Option<(u8, u8, u64)> {
+    match action {
+        BridgeAction::SuiToEthBridgeAction(a) => Some((
+            a.sui_bridge_event.sui_chain_id as u8,
+            a.sui_bridge_event.eth_chain_id as u8,
+            a.sui_bridge_event.nonce,
+        )),
+        BridgeAction::EthToSuiBridgeAction(a) => Some((
+            a.eth_bridge_event.eth_chain_id as u8,
+            a.eth_bridge_event.sui_chain_id as u8,
+            a.eth_bridge_event.nonce,
+        )),
+        _ => None,
+    }
 }
 
 impl BridgeOrchestratorTables {
     pub fn new(path: &Path) -> Arc<Self> {
-        Arc::new(Self::open_tables_read_write(
+        Self::open(path, None)
+    }
+
+    /// Opens the tables at `path`, with the auxiliary tables at `aux_path` if set.
+    /// Otherwise the auxiliary tables are kept at `path` too. Panics if the dbs are
+    /// encrypted.
+    pub fn open(path: &Path, aux_path: Option<&Path>) -> Arc<Self> {
+        Self::open_with_encryption(path, aux_path, None).expect("Couldn't open the bridge db")
+    }
+
+    /// Opens the tables like `open`, encrypted with data keys wrapped by `key_provider`
+    /// if set, see `storage_encryption`. New dbs are encrypted then. Fails if a db is
+    /// encrypted and `key_provider` is not set or can't unwrap its data key, or if
+    /// `key_provider` is set and a db has plaintext entries.
+    pub fn open_with_encryption(
+        path: &Path,
+        aux_path: Option<&Path>,
+        key_provider: Option<&dyn StorageKeyProvider>,
+    ) -> BridgeResult<Arc<Self>> {
+        let mut primary = BridgePrimaryTables::open_tables_read_write(
             path.to_path_buf(),
             MetricConf::new("bridge"),
             None,
             None,
-        ))
+        );
+        set_up_encryption(&mut primary, "primary db", key_provider)?;
+        let last_aux_path = primary
+            .aux_db_path
+            .get(&AUX_DB_PATH_KEY)
+            .expect("Couldn't read aux_db_path");
+        let aux = match aux_path {
+            Some(aux_path) => Some(Self::open_aux(
+                &primary,
+                aux_path,
+                last_aux_path.as_deref(),
+                key_provider,
+            )?),
+            None => None,
+        };
+        if aux.is_none() {
+            if let Some(last_aux_path) = &last_aux_path {
+                warn!(
+                    "Auxiliary db is disabled, the executed actions in {:?} are not visible",
+                    last_aux_path
+                );
+            }
+        }
+        Ok(Arc::new(Self {
+            primary,
+            aux,
+            cursor_buffer: Mutex::new(CursorBuffer::default()),
+        }))
+    }
+
+    fn open_aux(
+        primary: &BridgePrimaryTables,
+        aux_path: &Path,
+        last_aux_path: Option<&Path>,
+        key_provider: Option<&dyn StorageKeyProvider>,
+    ) -> BridgeResult<BridgeAuxTables> {
+        if let Some(last_aux_path) = last_aux_path.filter(|last| *last != aux_path) {
+            warn!(
+                "Opening auxiliary db at {:?}, the executed actions in {:?} are not visible",
+                aux_path, last_aux_path
+            );
+        }
+        primary
+            .aux_db_path
+            .insert(&AUX_DB_PATH_KEY, &aux_path.to_path_buf())
+            .expect("Couldn't write aux_db_path");
+        let mut aux = BridgeAuxTables::open_tables_read_write(
+            aux_path.to_path_buf(),
+            MetricConf::new("bridge_aux"),
+            None,
+            None,
+        );
+        set_up_encryption(&mut aux, "auxiliary db", key_provider)?;
+        Ok(aux)
+    }
+
+    /// Encrypts the plaintext entries of the db at `path`, and of the auxiliary db at
+    /// `aux_path` if set, with new data keys wrapped by `key_provider`. The entries are
+    /// rewritten a chunk at a time, a db can't be opened until all its entries are, and
+    /// an interrupted migration is resumed where it stopped when run again. The tables
+    /// are compacted afterwards so that the plaintext entries are deleted from the disk.
+    /// Dbs that are encrypted already are left as they are. Returns what was done.
+    pub fn encrypt_in_place(
+        path: &Path,
+        aux_path: Option<&Path>,
+        key_provider: &dyn StorageKeyProvider,
+    ) -> BridgeResult<Vec<String>> {
+        let mut done = vec![];
+        let mut primary = BridgePrimaryTables::open_tables_read_write(
+            path.to_path_buf(),
+            MetricConf::new("bridge"),
+            None,
+            None,
+        );
+        done.push(
+            match encrypt_tables_in_place(
+                &mut primary,
+                "primary db",
+                key_provider,
+                ENCRYPT_IN_PLACE_CHUNK_SIZE,
+            )? {
+                Some(entries) => format!("primary db: encrypted {} entries", entries),
+                None => "primary db: encrypted already".to_string(),
+            },
+        );
+        if let Some(aux_path) = aux_path {
+            let mut aux = BridgeAuxTables::open_tables_read_write(
+                aux_path.to_path_buf(),
+                MetricConf::new("bridge_aux"),
+                None,
+                None,
+            );
+            done.push(
+                match encrypt_tables_in_place(
+                    &mut aux,
+                    "auxiliary db",
+                    key_provider,
+                    ENCRYPT_IN_PLACE_CHUNK_SIZE,
+                )? {
+                    Some(entries) => format!("auxiliary db: encrypted {} entries", entries),
+                    None => "auxiliary db: encrypted already".to_string(),
+                },
+            );
+        }
+        Ok(done)
+    }
+
+    fn executed_actions(&self) -> &DBMap<(u8, u8, u64), BridgeAction> {
+        match &self.aux {
+            Some(aux) => &aux.executed_actions,
+            None => &self.primary.executed_actions,
+        }
+    }
+
+    fn executed_action_gas(&self) -> &DBMap<(u8, u8, u64), u64> {
+        match &self.aux {
+            Some(aux) => &aux.executed_action_gas,
+            None => &self.primary.executed_action_gas,
+        }
+    }
+
+    fn transfer_claims(&self) -> &DBMap<(u8, u8, u64), TransferClaim> {
+        match &self.aux {
+            Some(aux) => &aux.transfer_claims,
+            None => &self.primary.transfer_claims,
+        }
+    }
+
+    fn executed_action_times(&self) -> &DBMap<(u8, u8, u64), u64> {
+        match &self.aux {
+            Some(aux) => &aux.executed_action_times,
+            None => &self.primary.executed_action_times,
+        }
     }
 
     pub(crate) fn insert_pending_actions(&self, actions: &[BridgeAction]) -> BridgeResult<()> {
-        let mut batch = self.pending_actions.batch();
+        let mut batch = self.primary.pending_actions.batch();
         batch
             .insert_batch(
-                &self.pending_actions,
+                &self.primary.pending_actions,
                 actions.iter().map(|a| (a.digest(), a)),
             )
             .map_err(|e| {
@@ -55,9 +1028,144 @@ impl BridgeOrchestratorTables {
         &self,
         actions: &[BridgeActionDigest],
     ) -> BridgeResult<()> {
-        let mut batch = self.pending_actions.batch();
+        let mut batch = self.primary.pending_actions.batch();
+        batch
+            .delete_batch(&self.primary.pending_actions, actions)
+            .map_err(|e| {
+                BridgeError::StorageError(format!("Couldn't delete from pending_actions: {:?}", e))
+            })?;
+        batch
+            .write()
+            .map_err(|e| BridgeError::StorageError(format!("Couldn't write batch: {:?}", e)))
+    }
+
+    /// Removes `actions` from pending actions and records the token transfers among
+    /// them as executed. With an auxiliary db, the executed actions are written first so
+    /// that a failure in between leaves them pending rather than lost.
+    pub(crate) fn mark_actions_executed(&self, actions: &[BridgeAction]) -> BridgeResult<()> {
+        self.mark_executed(actions, None, now_ms())
+    }
+
+    /// Same as `mark_actions_executed` for the action that a transaction of this node
+    /// executed, with the gas that the transaction spent.
+    pub(crate) fn mark_action_executed_with_gas(
+        &self,
+        action: &BridgeAction,
+        gas_spent_mist: u64,
+    ) -> BridgeResult<()> {
+        self.mark_executed(std::slice::from_ref(action), Some(gas_spent_mist), now_ms())
+    }
+
+    /// Same as `mark_actions_executed`, recording the transfers as executed at
+    /// `executed_at_ms` instead of now.
+    pub(crate) fn mark_actions_executed_at(
+        &self,
+        actions: &[BridgeAction],
+        executed_at_ms: u64,
+    ) -> BridgeResult<()> {
+        self.mark_executed(actions, None, executed_at_ms)
+    }
+
+    fn mark_executed(
+        &self,
+        actions: &[BridgeAction],
+        gas_spent_mist: Option<u64>,
+        executed_at_ms: u64,
+    ) -> BridgeResult<()> {
+        let executed_actions = self.executed_actions();
+        let mut batch = executed_actions.batch();
+        batch
+            .insert_batch(
+                executed_actions,
+                actions
+                    .iter()
+                    .filter_map(|a| executed_action_key(a).map(|key| (key, a))),
+            )
+            .map_err(|e| {
+                BridgeError::StorageError(format!("Couldn't insert into executed_actions: {:?}", e))
+            })?
+            .insert_batch(
+                self.executed_action_times(),
+                actions
+                    .iter()
+                    .filter_map(executed_action_key)
+                    .map(|key| (key, executed_at_ms)),
+            )
+            .map_err(|e| insert_error("executed_action_times", e))?;
+        if let Some(gas_spent_mist) = gas_spent_mist {
+            batch
+                .insert_batch(
+                    self.executed_action_gas(),
+                    actions
+                        .iter()
+                        .filter_map(executed_action_key)
+                        .map(|key| (key, gas_spent_mist)),
+                )
+                .map_err(|e| insert_error("executed_action_gas", e))?;
+        }
+        if self.aux.is_some() {
+            batch
+                .write()
+                .map_err(|e| BridgeError::StorageError(format!("Couldn't write batch: {:?}", e)))?;
+            batch = self.primary.pending_actions.batch();
+        }
+        batch
+            .delete_batch(
+                &self.primary.pending_actions,
+                actions.iter().map(|a| a.digest()),
+            )
+            .map_err(|e| {
+                BridgeError::StorageError(format!("Couldn't delete from pending_actions: {:?}", e))
+            })?;
+        batch
+            .write()
+            .map_err(|e| BridgeError::StorageError(format!("Couldn't write batch: {:?}", e)))
+    }
+
+    pub(crate) fn insert_dropped_actions(
+        &self,
+        actions: &[BridgeAction],
+        reason: DroppedActionReason,
+    ) -> BridgeResult<()> {
+        let mut batch = self.primary.dropped_actions.batch();
+        batch
+            .insert_batch(
+                &self.primary.dropped_actions,
+                actions.iter().map(|a| (a.digest(), a)),
+            )
+            .map_err(|e| {
+                BridgeError::StorageError(format!("Couldn't insert into dropped_actions: {:?}", e))
+            })?;
+        batch
+            .insert_batch(
+                &self.primary.dropped_action_reasons,
+                actions.iter().map(|a| (a.digest(), reason)),
+            )
+            .map_err(|e| {
+                BridgeError::StorageError(format!(
+                    "Couldn't insert into dropped_action_reasons: {:?}",
+                    e
+                ))
+            })?;
+        batch
+            .write()
+            .map_err(|e| BridgeError::StorageError(format!("Couldn't write batch: {:?}", e)))
+    }
+
+    /// Moves the action of `quarantined` from pending actions to quarantined actions.
+    pub(crate) fn quarantine_action(&self, quarantined: &QuarantinedAction) -> BridgeResult<()> {
+        let digest = quarantined.action.digest();
+        let mut batch = self.primary.quarantined_actions.batch();
+        batch
+            .insert_batch(&self.primary.quarantined_actions, [(digest, quarantined)])
+            .map_err(|e| {
+                BridgeError::StorageError(format!(
+                    "Couldn't insert into quarantined_actions: {:?}",
+                    e
+                ))
+            })?;
         batch
-            .delete_batch(&self.pending_actions, actions)
+            .delete_batch(&self.primary.pending_actions, [digest])
             .map_err(|e| {
                 BridgeError::StorageError(format!("Couldn't delete from pending_actions: {:?}", e))
             })?;
@@ -66,15 +1174,274 @@ impl BridgeOrchestratorTables {
             .map_err(|e| BridgeError::StorageError(format!("Couldn't write batch: {:?}", e)))
     }
 
+    /// Removes an action from quarantine and records it as dropped, so that it's not
+    /// processed again. Returns None if it's not quarantined.
+    pub fn delete_quarantined_action(
+        &self,
+        digest: &BridgeActionDigest,
+    ) -> BridgeResult<Option<QuarantinedAction>> {
+        let Some(quarantined) = self.get_quarantined_action(digest)? else {
+            return Ok(None);
+        };
+        let mut batch = self.primary.dropped_actions.batch();
+        batch
+            .insert_batch(
+                &self.primary.dropped_actions,
+                [(*digest, &quarantined.action)],
+            )
+            .map_err(|e| {
+                BridgeError::StorageError(format!("Couldn't insert into dropped_actions: {:?}", e))
+            })?;
+        batch
+            .insert_batch(
+                &self.primary.dropped_action_reasons,
+                [(*digest, DroppedActionReason::DeletedFromQuarantine)],
+            )
+            .map_err(|e| {
+                BridgeError::StorageError(format!(
+                    "Couldn't insert into dropped_action_reasons: {:?}",
+                    e
+                ))
+            })?;
+        batch
+            .delete_batch(&self.primary.quarantined_actions, [*digest])
+            .map_err(|e| {
+                BridgeError::StorageError(format!(
+                    "Couldn't delete from quarantined_actions: {:?}",
+                    e
+                ))
+            })?;
+        batch
+            .delete_batch(&self.primary.quarantine_checks, [*digest])
+            .map_err(|e| {
+                BridgeError::StorageError(format!(
+                    "Couldn't delete from quarantine_checks: {:?}",
+                    e
+                ))
+            })?;
+        batch
+            .write()
+            .map_err(|e| BridgeError::StorageError(format!("Couldn't write batch: {:?}", e)))?;
+        Ok(Some(quarantined))
+    }
+
+    /// Moves a quarantined action back to pending actions, so that it's retried the next
+    /// time the node starts. Returns None if it's not quarantined.
+    pub fn requeue_quarantined_action(
+        &self,
+        digest: &BridgeActionDigest,
+    ) -> BridgeResult<Option<BridgeAction>> {
+        let Some(quarantined) = self.get_quarantined_action(digest)? else {
+            return Ok(None);
+        };
+        let mut batch = self.primary.pending_actions.batch();
+        batch
+            .insert_batch(
+                &self.primary.pending_actions,
+                [(*digest, &quarantined.action)],
+            )
+            .map_err(|e| {
+                BridgeError::StorageError(format!("Couldn't insert into pending_actions: {:?}", e))
+            })?;
+        batch
+            .delete_batch(&self.primary.quarantined_actions, [*digest])
+            .map_err(|e| {
+                BridgeError::StorageError(format!(
+                    "Couldn't delete from quarantined_actions: {:?}",
+                    e
+                ))
+            })?;
+        batch
+            .delete_batch(&self.primary.quarantine_checks, [*digest])
+            .map_err(|e| {
+                BridgeError::StorageError(format!(
+                    "Couldn't delete from quarantine_checks: {:?}",
+                    e
+                ))
+            })?;
+        batch
+            .write()
+            .map_err(|e| BridgeError::StorageError(format!("Couldn't write batch: {:?}", e)))?;
+        Ok(Some(quarantined.action))
+    }
+
+    /// Buffers the latest cursors of the syncers instead of writing each update, until
+    /// they are written by `flush_cursors`, by `commit_pending_actions`, or when the tables
+    /// are dropped. A crash loses the updates since the last write, the syncers then read
+    /// the events after the last written cursors again, but cursors are never written out
+    /// of order.
+    pub(crate) fn enable_cursor_coalescing(&self, metrics: Arc<BridgeMetrics>) {
+        self.cursor_buffer.lock().unwrap().metrics = Some(metrics);
+    }
+
+    // Returns whether the cursor was buffered rather than to be written through
+    fn buffer_cursor(&self, cursor: SyncerCursor) -> bool {
+        let mut buffer = self.cursor_buffer.lock().unwrap();
+        let Some(metrics) = buffer.metrics.clone() else {
+            return false;
+        };
+        let replaced = match cursor {
+            SyncerCursor::Sui(module, event_id) => buffer.sui.insert(module, event_id).is_some(),
+            SyncerCursor::Eth(contract, block) => buffer.eth.insert(contract, block).is_some(),
+        };
+        metrics
+            .storage_cursor_updates
+            .with_label_values(&[if replaced { "coalesced" } else { "buffered" }])
+            .inc();
+        true
+    }
+
+    /// Writes the buffered cursors, see `enable_cursor_coalescing`. Returns how many were
+    /// written.
+    pub(crate) fn flush_cursors(&self, reason: &str) -> BridgeResult<usize> {
+        let mut buffer = self.cursor_buffer.lock().unwrap();
+        if buffer.is_empty() {
+            return Ok(0);
+        }
+        let mut batch = self.primary.sui_syncer_cursors.batch();
+        buffer.insert_batch(&self.primary, &mut batch)?;
+        batch
+            .write()
+            .map_err(|e| BridgeError::StorageError(format!("Couldn't write batch: {:?}", e)))?;
+        let flushed = buffer.sui.len() + buffer.eth.len();
+        buffer.clear(reason);
+        Ok(flushed)
+    }
+
+    // The open dbs, with the names of their tables
+    fn dbs(&self) -> Vec<(&'static str, &Arc<RocksDB>, Vec<String>)> {
+        let mut dbs = vec![(
+            PRIMARY_DB,
+            &self.primary.storage_encryption.rocksdb,
+            BridgePrimaryTables::describe_tables().into_keys().collect(),
+        )];
+        if let Some(aux) = &self.aux {
+            dbs.push((
+                AUX_DB,
+                &aux.storage_encryption.rocksdb,
+                BridgeAuxTables::describe_tables().into_keys().collect(),
+            ));
+        }
+        dbs
+    }
+
+    fn db(&self, db: &str) -> BridgeResult<&Arc<RocksDB>> {
+        self.dbs()
+            .into_iter()
+            .find(|(name, _, _)| *name == db)
+            .map(|(_, rocksdb, _)| rocksdb)
+            .ok_or_else(|| BridgeError::StorageError(format!("No {} db is open", db)))
+    }
+
+    /// Returns the tables of the open dbs as (db, table), the primary db first.
+    pub(crate) fn table_names(&self) -> Vec<(&'static str, String)> {
+        self.dbs()
+            .into_iter()
+            .flat_map(|(db, _, tables)| tables.into_iter().map(move |table| (db, table)))
+            .collect()
+    }
+
+    /// Returns the size in bytes of the sst files of `table` in `db`.
+    pub(crate) fn table_size(&self, db: &str, table: &str) -> BridgeResult<u64> {
+        let rocksdb = self.db(db)?;
+        let cf = rocksdb
+            .cf_handle(table)
+            .ok_or_else(|| BridgeError::StorageError(format!("No table {} in {}", table, db)))?;
+        let property: &std::ffi::CStr = properties::TOTAL_SST_FILES_SIZE;
+        rocksdb
+            .property_int_value_cf(&cf, property)
+            .map(|size| size.unwrap_or(0))
+            .map_err(|e| {
+                BridgeError::StorageError(format!(
+                    "Couldn't read the size of {} in {}: {:?}",
+                    table, db, e
+                ))
+            })
+    }
+
+    /// Compacts `table` in `db` down to the bottommost level. Blocks until it's done.
+    pub(crate) fn compact_table(&self, db: &str, table: &str) -> BridgeResult<()> {
+        let rocksdb = self.db(db)?;
+        let cf = rocksdb
+            .cf_handle(table)
+            .ok_or_else(|| BridgeError::StorageError(format!("No table {} in {}", table, db)))?;
+        rocksdb.compact_range_to_bottom(&cf, None::<Vec<u8>>, None::<Vec<u8>>);
+        Ok(())
+    }
+
+    /// Writes a checkpoint of the primary db to `dir/primary`, and of the auxiliary db to
+    /// `dir/aux` if it's open. The buffered cursors are written first. Files are
+    /// hard-linked when `dir` is on the filesystem of the db, and copied otherwise. The
+    /// checkpoints of an encrypted db are encrypted with the same data key.
+    pub(crate) fn checkpoint(&self, dir: &Path) -> BridgeResult<()> {
+        self.flush_cursors("checkpoint")?;
+        std::fs::create_dir_all(dir).map_err(|e| {
+            BridgeError::StorageError(format!("Couldn't create {:?}: {:?}", dir, e))
+        })?;
+        for (db, rocksdb, _) in self.dbs() {
+            rocksdb.checkpoint(&dir.join(db)).map_err(|e| {
+                BridgeError::StorageError(format!("Couldn't checkpoint the {} db: {:?}", db, e))
+            })?;
+        }
+        Ok(())
+    }
+
+    /// Writes `actions` into pending actions in a single batch with `cursor`, the cursor
+    /// of the page of events they were read from, and with the buffered cursors, so that
+    /// the actions are never lost behind a cursor that was written without them.
+    pub(crate) fn commit_pending_actions(
+        &self,
+        actions: &[BridgeAction],
+        cursor: SyncerCursor,
+    ) -> BridgeResult<()> {
+        let mut buffer = self.cursor_buffer.lock().unwrap();
+        // The cursor of the page supersedes the buffered one of its syncer
+        match &cursor {
+            SyncerCursor::Sui(module, _) => {
+                buffer.sui.remove(module);
+            }
+            SyncerCursor::Eth(contract, _) => {
+                buffer.eth.remove(contract);
+            }
+        }
+        let mut batch = self.primary.pending_actions.batch();
+        batch
+            .insert_batch(
+                &self.primary.pending_actions,
+                actions.iter().map(|a| (a.digest(), a)),
+            )
+            .map_err(|e| insert_error("pending_actions", e))?;
+        buffer.insert_batch(&self.primary, &mut batch)?;
+        match cursor {
+            SyncerCursor::Sui(module, event_id) => {
+                batch.insert_batch(&self.primary.sui_syncer_cursors, [(module, event_id)])
+            }
+            SyncerCursor::Eth(contract, block) => {
+                batch.insert_batch(&self.primary.eth_syncer_cursors, [(contract, block)])
+            }
+        }
+        .map_err(|e| insert_error("syncer cursors", e))?;
+        batch
+            .write()
+            .map_err(|e| BridgeError::StorageError(format!("Couldn't write batch: {:?}", e)))?;
+        if !buffer.is_empty() {
+            buffer.clear("commit");
+        }
+        Ok(())
+    }
+
     pub(crate) fn update_sui_event_cursor(
         &self,
         module: Identifier,
         cursor: EventID,
     ) -> BridgeResult<()> {
-        let mut batch = self.sui_syncer_cursors.batch();
+        if self.buffer_cursor(SyncerCursor::Sui(module.clone(), cursor)) {
+            return Ok(());
+        }
+        let mut batch = self.primary.sui_syncer_cursors.batch();
 
         batch
-            .insert_batch(&self.sui_syncer_cursors, [(module, cursor)])
+            .insert_batch(&self.primary.sui_syncer_cursors, [(module, cursor)])
             .map_err(|e| {
                 BridgeError::StorageError(format!(
                     "Coudln't insert into sui_syncer_cursors: {:?}",
@@ -91,10 +1458,16 @@ impl BridgeOrchestratorTables {
         contract_address: ethers::types::Address,
         cursor: u64,
     ) -> BridgeResult<()> {
-        let mut batch = self.eth_syncer_cursors.batch();
+        if self.buffer_cursor(SyncerCursor::Eth(contract_address, cursor)) {
+            return Ok(());
+        }
+        let mut batch = self.primary.eth_syncer_cursors.batch();
 
         batch
-            .insert_batch(&self.eth_syncer_cursors, [(contract_address, cursor)])
+            .insert_batch(
+                &self.primary.eth_syncer_cursors,
+                [(contract_address, cursor)],
+            )
             .map_err(|e| {
                 BridgeError::StorageError(format!(
                     "Coudln't insert into eth_syncer_cursors: {:?}",
@@ -106,28 +1479,1028 @@ impl BridgeOrchestratorTables {
             .map_err(|e| BridgeError::StorageError(format!("Couldn't write batch: {:?}", e)))
     }
 
-    pub fn get_all_pending_actions(&self) -> HashMap<BridgeActionDigest, BridgeAction> {
-        self.pending_actions.unbounded_iter().collect()
+    /// Sets the cursor of `record` to its new value, and appends `record` to the audit log,
+    /// with `origin` when it was requested through the admin api.
+    pub(crate) fn reset_syncer_cursor(
+        &self,
+        record: &CursorResetRecord,
+        origin: Option<&AdminRequestOrigin>,
+    ) -> BridgeResult<()> {
+        let last_index = self
+            .primary
+            .cursor_resets
+            .unbounded_iter()
+            .skip_to_last()
+            .next()
+            .map(|(index, _)| index);
+        // The reset supersedes the buffered cursor, which must not be written after it
+        let mut buffer = self.cursor_buffer.lock().unwrap();
+        match &record.cursor {
+            ResetCursor::Eth { contract, .. } => {
+                buffer.eth.remove(contract);
+            }
+            ResetCursor::Sui { module, .. } => {
+                buffer.sui.remove(module);
+            }
+        }
+        let mut batch = self.primary.cursor_resets.batch();
+        match &record.cursor {
+            ResetCursor::Eth {
+                contract,
+                new_block,
+                ..
+            } => batch.insert_batch(&self.primary.eth_syncer_cursors, [(*contract, *new_block)]),
+            ResetCursor::Sui {
+                module,
+                new_event_id,
+                ..
+            } => batch.insert_batch(
+                &self.primary.sui_syncer_cursors,
+                [(module.clone(), *new_event_id)],
+            ),
+        }
+        .map_err(|e| BridgeError::StorageError(format!("Couldn't reset cursor: {:?}", e)))?;
+        let index = last_index.map_or(0, |index| index + 1);
+        batch
+            .insert_batch(&self.primary.cursor_resets, [(index, record)])
+            .map_err(|e| {
+                BridgeError::StorageError(format!("Couldn't insert into cursor_resets: {:?}", e))
+            })?;
+        if let Some(origin) = origin {
+            batch
+                .insert_batch(&self.primary.cursor_reset_origins, [(index, origin)])
+                .map_err(|e| insert_error("cursor_reset_origins", e))?;
+        }
+        batch
+            .write()
+            .map_err(|e| BridgeError::StorageError(format!("Couldn't write batch: {:?}", e)))
+    }
+
+    /// Stores the response to the admin request of `caller` with `idempotency_key`, and
+    /// deletes the responses processed before `expired_before_ms`.
+    pub(crate) fn insert_admin_request(
+        &self,
+        caller: &str,
+        idempotency_key: &str,
+        record: &AdminRequestRecord,
+        expired_before_ms: u64,
+    ) -> BridgeResult<()> {
+        let expired = self
+            .primary
+            .admin_requests
+            .unbounded_iter()
+            .filter(|(_, record)| record.processed_at_ms < expired_before_ms)
+            .map(|(key, _)| key)
+            .collect::<Vec<_>>();
+        let mut batch = self.primary.admin_requests.batch();
+        batch
+            .delete_batch(&self.primary.admin_requests, expired)
+            .map_err(|e| {
+                BridgeError::StorageError(format!("Couldn't delete from admin_requests: {:?}", e))
+            })?;
+        batch
+            .insert_batch(
+                &self.primary.admin_requests,
+                [((caller.to_string(), idempotency_key.to_string()), record)],
+            )
+            .map_err(|e| insert_error("admin_requests", e))?
+            .insert_batch(
+                &primary.signed_actions,
+                export.signed_actions.iter().map(|(k, v)| (k, v)),
+            )
+            .map_err(|e| insert_error("signed_actions", e))?
+            .insert_batch(
+                &primary.signed_set_buckets,
+                export.signed_set_buckets.iter().map(|(k, v)| (k, v)),
+            )
+            .map_err(|e| insert_error("signed_set_buckets", e))?
+            .insert_batch(
+                &primary.quarantine_checks,
+                export.quarantine_checks.iter().map(|(k, v)| (k, v)),
+            )
+            .map_err(|e| insert_error("quarantine_checks", e))?;
+        batch
+            .write()
+            .map_err(|e| BridgeError::StorageError(format!("Couldn't write batch: {:?}", e)))
+    }
+
+    pub fn get_admin_request(
+        &self,
+        caller: &str,
+        idempotency_key: &str,
+    ) -> BridgeResult<Option<AdminRequestRecord>> {
+        self.primary
+            .admin_requests
+            .get(&(caller.to_string(), idempotency_key.to_string()))
+            .map_err(|e| BridgeError::StorageError(format!("Couldn't get admin_requests: {:?}", e)))
+    }
+
+    /// Appends `record` to the audit log of the admin api. Returns its index. Callers
+    /// don't append concurrently.
+    pub(crate) fn append_admin_audit(&self, record: &AdminAuditRecord) -> BridgeResult<u64> {
+        let index = self
+            .primary
+            .admin_audit
+            .unbounded_iter()
+            .skip_to_last()
+            .next()
+            .map_or(0, |(index, _)| index + 1);
+        self.primary
+            .admin_audit
+            .insert(&index, record)
+            .map_err(|e| insert_error("admin_audit", e))?;
+        Ok(index)
+    }
+
+    /// Returns the entries of the audit log of the admin api before the index `before`,
+    /// or the latest ones when it's not set, newest first, at most `limit` of them.
+    pub fn get_admin_audit(
+        &self,
+        before: Option<u64>,
+        limit: usize,
+    ) -> BridgeResult<Vec<(u64, AdminAuditRecord)>> {
+        let iter = self.primary.admin_audit.unbounded_iter();
+        let iter = match before {
+            Some(0) => return Ok(vec![]),
+            Some(before) => iter.skip_prior_to(&(before - 1)).map_err(|e| {
+                BridgeError::StorageError(format!("Couldn't iterate admin_audit: {:?}", e))
+            })?,
+            None => iter.skip_to_last(),
+        };
+        Ok(iter.reverse().take(limit).collect())
+    }
+
+    /// Adds `digest` to the signed set, in the bucket that starts at `bucket_start_ms`.
+    /// Returns false if it was signed before, it stays in the bucket it was added to.
+    /// The bucket is read and written back, callers don't insert concurrently.
+    pub(crate) fn insert_signed_action(
+        &self,
+        digest: &BridgeActionDigest,
+        bucket_start_ms: u64,
+    ) -> BridgeResult<bool> {
+        let read_error = |e: TypedStoreError| {
+            BridgeError::StorageError(format!("Couldn't read the signed set: {:?}", e))
+        };
+        if self
+            .primary
+            .signed_actions
+            .contains_key(digest)
+            .map_err(read_error)?
+        {
+            return Ok(false);
+        }
+        let mut bucket = self
+            .primary
+            .signed_set_buckets
+            .get(&bucket_start_ms)
+            .map_err(read_error)?
+            .unwrap_or_default();
+        bucket.insert(digest);
+        let mut batch = self.primary.signed_actions.batch();
+        batch
+            .insert_batch(&self.primary.signed_actions, [(digest, bucket_start_ms)])
+            .map_err(|e| insert_error("signed_actions", e))?
+            .insert_batch(
+                &self.primary.signed_set_buckets,
+                [(bucket_start_ms, bucket)],
+            )
+            .map_err(|e| insert_error("signed_set_buckets", e))?;
+        batch
+            .write()
+            .map_err(|e| BridgeError::StorageError(format!("Couldn't write batch: {:?}", e)))?;
+        Ok(true)
+    }
+
+    /// Returns the buckets of the signed set by their start, oldest first.
+    pub fn get_signed_set_buckets(&self) -> BTreeMap<u64, SignedSetBucket> {
+        self.primary.signed_set_buckets.unbounded_iter().collect()
+    }
+
+    /// Replaces the stored authority scores with `scores`.
+    pub(crate) fn replace_authority_scores(
+        &self,
+        scores: &BTreeMap<BridgeAuthorityPublicKeyBytes, AuthorityScore>,
+    ) -> BridgeResult<()> {
+        let stale = self
+            .primary
+            .authority_scores
+            .unbounded_iter()
+            .map(|(name, _)| name)
+            .filter(|name| !scores.contains_key(name))
+            .collect::<Vec<_>>();
+        let mut batch = self.primary.authority_scores.batch();
+        batch
+            .delete_batch(&self.primary.authority_scores, stale)
+            .map_err(|e| {
+                BridgeError::StorageError(format!("Couldn't delete from authority_scores: {:?}", e))
+            })?;
+        batch
+            .insert_batch(&self.primary.authority_scores, scores.iter())
+            .map_err(|e| {
+                BridgeError::StorageError(format!("Couldn't insert into authority_scores: {:?}", e))
+            })?;
+        batch
+            .write()
+            .map_err(|e| BridgeError::StorageError(format!("Couldn't write batch: {:?}", e)))
+    }
+
+    pub fn get_authority_scores(&self) -> BTreeMap<BridgeAuthorityPublicKeyBytes, AuthorityScore> {
+        self.primary.authority_scores.unbounded_iter().collect()
+    }
+
+    pub fn get_all_pending_actions(&self) -> HashMap<BridgeActionDigest, BridgeAction> {
+        self.primary.pending_actions.unbounded_iter().collect()
+    }
+
+    pub fn get_all_dropped_actions(&self) -> HashMap<BridgeActionDigest, BridgeAction> {
+        self.primary.dropped_actions.unbounded_iter().collect()
+    }
+
+    pub fn get_all_quarantined_actions(&self) -> HashMap<BridgeActionDigest, QuarantinedAction> {
+        self.primary.quarantined_actions.unbounded_iter().collect()
+    }
+
+    /// Returns the audit log of cursor resets, oldest first.
+    pub fn get_cursor_resets(&self) -> Vec<CursorResetRecord> {
+        self.primary
+            .cursor_resets
+            .unbounded_iter()
+            .map(|(_, record)| record)
+            .collect()
+    }
+
+    /// Returns who requested the cursor resets, by their index in the audit log.
+    pub fn get_cursor_reset_origins(&self) -> BTreeMap<u64, AdminRequestOrigin> {
+        self.primary.cursor_reset_origins.unbounded_iter().collect()
+    }
+
+    pub fn get_pending_action(
+        &self,
+        digest: &BridgeActionDigest,
+    ) -> BridgeResult<Option<BridgeAction>> {
+        self.primary.pending_actions.get(digest).map_err(|e| {
+            BridgeError::StorageError(format!("Couldn't get pending_actions: {:?}", e))
+        })
+    }
+
+    /// Returns the last failed verification of the quarantined actions that were verified
+    /// again.
+    pub fn get_quarantine_checks(&self) -> HashMap<BridgeActionDigest, QuarantineCheck> {
+        self.primary.quarantine_checks.unbounded_iter().collect()
+    }
+
+    /// Records that the quarantined action `digest` is still invalid.
+    pub(crate) fn record_quarantine_check(
+        &self,
+        digest: &BridgeActionDigest,
+        check: &QuarantineCheck,
+    ) -> BridgeResult<()> {
+        self.primary
+            .quarantine_checks
+            .insert(digest, check)
+            .map_err(|e| insert_error("quarantine_checks", e))
+    }
+
+    pub fn get_quarantined_action(
+        &self,
+        digest: &BridgeActionDigest,
+    ) -> BridgeResult<Option<QuarantinedAction>> {
+        self.primary.quarantined_actions.get(digest).map_err(|e| {
+            BridgeError::StorageError(format!("Couldn't get quarantined_actions: {:?}", e))
+        })
+    }
+
+    /// Returns None for actions that are not dropped, or were dropped before reasons
+    /// were recorded.
+    pub fn get_dropped_action_reason(
+        &self,
+        digest: &BridgeActionDigest,
+    ) -> BridgeResult<Option<DroppedActionReason>> {
+        self.primary
+            .dropped_action_reasons
+            .get(digest)
+            .map_err(|e| {
+                BridgeError::StorageError(format!("Couldn't get dropped_action_reasons: {:?}", e))
+            })
+    }
+
+    /// Returns the executed token transfers from `source_chain_id` to `destination_chain_id`,
+    /// in descending nonce order, at most `limit` of them.
+    pub fn get_recent_executed_actions(
+        &self,
+        source_chain_id: u8,
+        destination_chain_id: u8,
+        limit: usize,
+    ) -> BridgeResult<Vec<(u64, BridgeAction)>> {
+        Ok(self
+            .executed_actions()
+            .unbounded_iter()
+            .skip_prior_to(&(source_chain_id, destination_chain_id, u64::MAX))
+            .map_err(|e| {
+                BridgeError::StorageError(format!("Couldn't iterate executed_actions: {:?}", e))
+            })?
+            .reverse()
+            .take_while(|((source, destination, _), _)| {
+                *source == source_chain_id && *destination == destination_chain_id
+            })
+            .take(limit)
+            .map(|((_, _, nonce), action)| (nonce, action))
+            .collect())
+    }
+
+    pub fn get_executed_action(
+        &self,
+        source_chain_id: u8,
+        destination_chain_id: u8,
+        nonce: u64,
+    ) -> BridgeResult<Option<BridgeAction>> {
+        self.executed_actions()
+            .get(&(source_chain_id, destination_chain_id, nonce))
+            .map_err(|e| {
+                BridgeError::StorageError(format!("Couldn't get executed_actions: {:?}", e))
+            })
+    }
+
+    /// Returns the gas spent by the transaction that executed a token transfer, None if it
+    /// was executed by another node or before gas was recorded.
+    pub fn get_executed_action_gas(
+        &self,
+        source_chain_id: u8,
+        destination_chain_id: u8,
+        nonce: u64,
+    ) -> BridgeResult<Option<u64>> {
+        self.executed_action_gas()
+            .get(&(source_chain_id, destination_chain_id, nonce))
+            .map_err(|e| {
+                BridgeError::StorageError(format!("Couldn't get executed_action_gas: {:?}", e))
+            })
+    }
+
+    /// Returns the routes, as (source chain id, destination chain id), with executed token
+    /// transfers.
+    pub fn get_executed_action_routes(&self) -> BridgeResult<Vec<(u8, u8)>> {
+        let mut routes = vec![];
+        let mut next = Some((0u8, 0u8));
+        while let Some((source, destination)) = next {
+            let Some(((source, destination, _), _)) = self
+                .executed_actions()
+                .unbounded_iter()
+                .skip_to(&(source, destination, 0))
+                .map_err(|e| {
+                    BridgeError::StorageError(format!("Couldn't iterate executed_actions: {:?}", e))
+                })?
+                .next()
+            else {
+                break;
+            };
+            routes.push((source, destination));
+            next = match destination.checked_add(1) {
+                Some(destination) => Some((source, destination)),
+                None => source.checked_add(1).map(|source| (source, 0)),
+            };
+        }
+        Ok(routes)
+    }
+
+    /// Returns the nonce of the oldest of the newest `retain` executed token transfers of a
+    /// route, None if the route has at most `retain` of them.
+    pub fn get_oldest_retained_executed_nonce(
+        &self,
+        source_chain_id: u8,
+        destination_chain_id: u8,
+        retain: u64,
+    ) -> BridgeResult<Option<u64>> {
+        // Nothing is retained
+        let Some(skip) = retain.checked_sub(1) else {
+            return Ok(Some(u64::MAX));
+        };
+        let mut retained = self
+            .executed_actions()
+            .unbounded_iter()
+            .skip_prior_to(&(source_chain_id, destination_chain_id, u64::MAX))
+            .map_err(|e| {
+                BridgeError::StorageError(format!("Couldn't iterate executed_actions: {:?}", e))
+            })?
+            .reverse()
+            .take_while(|((source, destination, _), _)| {
+                *source == source_chain_id && *destination == destination_chain_id
+            });
+        let Some(((_, _, nonce), _)) = retained.nth(skip as usize) else {
+            return Ok(None);
+        };
+        Ok(Some(nonce))
+    }
+
+    /// Returns the oldest executed token transfers of a route with a nonce below
+    /// `before_nonce`, at most `limit` of them, with the gas spent by their transaction.
+    pub fn get_executed_actions_before(
+        &self,
+        source_chain_id: u8,
+        destination_chain_id: u8,
+        before_nonce: u64,
+        limit: usize,
+    ) -> BridgeResult<Vec<((u8, u8, u64), BridgeAction, Option<u64>)>> {
+        self.executed_actions()
+            .unbounded_iter()
+            .skip_to(&(source_chain_id, destination_chain_id, 0))
+            .map_err(|e| {
+                BridgeError::StorageError(format!("Couldn't iterate executed_actions: {:?}", e))
+            })?
+            .take_while(|((source, destination, nonce), _)| {
+                *source == source_chain_id
+                    && *destination == destination_chain_id
+                    && *nonce < before_nonce
+            })
+            .take(limit)
+            .map(|(key, action)| {
+                let gas_spent_mist = self.executed_action_gas().get(&key).map_err(|e| {
+                    BridgeError::StorageError(format!("Couldn't get executed_action_gas: {:?}", e))
+                })?;
+                Ok((key, action, gas_spent_mist))
+            })
+            .collect()
+    }
+
+    /// Returns the executed token transfers after the key `after`, or from the first one,
+    /// in the order of their keys, with the time they were recorded as executed. They are
+    /// read from the db as the iterator advances.
+    pub fn iter_executed_transfers(
+        &self,
+        after: Option<(u8, u8, u64)>,
+    ) -> BridgeResult<
+        impl Iterator<Item = BridgeResult<((u8, u8, u64), BridgeAction, Option<u64>)>> + '_,
+    > {
+        Ok(self
+            .executed_actions()
+            .unbounded_iter()
+            .skip_to(&after.unwrap_or_default())
+            .map_err(|e| {
+                BridgeError::StorageError(format!("Couldn't iterate executed_actions: {:?}", e))
+            })?
+            .skip_while(move |(key, _)| Some(*key) == after)
+            .map(|(key, action)| {
+                let executed_at_ms = self.executed_action_times().get(&key).map_err(|e| {
+                    BridgeError::StorageError(format!(
+                        "Couldn't get executed_action_times: {:?}",
+                        e
+                    ))
+                })?;
+                Ok((key, action, executed_at_ms))
+            }))
+    }
+
+    /// Returns the nonces of the executed token transfers of a route from `from_nonce` on,
+    /// oldest first, at most `limit` of them.
+    pub fn get_executed_nonces_from(
+        &self,
+        source_chain_id: u8,
+        destination_chain_id: u8,
+        from_nonce: u64,
+        limit: usize,
+    ) -> BridgeResult<Vec<u64>> {
+        Ok(self
+            .executed_actions()
+            .unbounded_iter()
+            .skip_to(&(source_chain_id, destination_chain_id, from_nonce))
+            .map_err(|e| {
+                BridgeError::StorageError(format!("Couldn't iterate executed_actions: {:?}", e))
+            })?
+            .take_while(|((source, destination, _), _)| {
+                *source == source_chain_id && *destination == destination_chain_id
+            })
+            .take(limit)
+            .map(|((_, _, nonce), _)| nonce)
+            .collect())
+    }
+
+    /// Returns the claims of the token transfers of a route, by nonce.
+    pub fn get_transfer_claims(
+        &self,
+        source_chain_id: u8,
+        destination_chain_id: u8,
+    ) -> BridgeResult<BTreeMap<u64, TransferClaim>> {
+        Ok(self
+            .transfer_claims()
+            .unbounded_iter()
+            .skip_to(&(source_chain_id, destination_chain_id, 0))
+            .map_err(|e| {
+                BridgeError::StorageError(format!("Couldn't iterate transfer_claims: {:?}", e))
+            })?
+            .take_while(|((source, destination, _), _)| {
+                *source == source_chain_id && *destination == destination_chain_id
+            })
+            .map(|((_, _, nonce), claim)| (nonce, claim))
+            .collect())
+    }
+
+    /// Returns when the token transfers executed since `since_ms` were recorded as
+    /// executed, by (source chain id, destination chain id, nonce).
+    pub fn get_executed_action_times_since(&self, since_ms: u64) -> Vec<((u8, u8, u64), u64)> {
+        self.executed_action_times()
+            .unbounded_iter()
+            .filter(|(_, executed_at_ms)| *executed_at_ms >= since_ms)
+            .collect()
+    }
+
+    pub(crate) fn insert_transfer_claims(
+        &self,
+        claims: &[((u8, u8, u64), TransferClaim)],
+    ) -> BridgeResult<()> {
+        let transfer_claims = self.transfer_claims();
+        let mut batch = transfer_claims.batch();
+        batch
+            .insert_batch(transfer_claims, claims.iter().map(|(k, v)| (k, v)))
+            .map_err(|e| insert_error("transfer_claims", e))?;
+        batch
+            .write()
+            .map_err(|e| BridgeError::StorageError(format!("Couldn't write batch: {:?}", e)))
+    }
+
+    /// Deletes executed token transfers, the gas spent by their transaction and their
+    /// claims, once they are pruned. They are no longer known to the node afterwards.
+    pub(crate) fn delete_executed_actions(&self, keys: &[(u8, u8, u64)]) -> BridgeResult<()> {
+        let executed_actions = self.executed_actions();
+        let mut batch = executed_actions.batch();
+        batch.delete_batch(executed_actions, keys).map_err(|e| {
+            BridgeError::StorageError(format!("Couldn't delete from executed_actions: {:?}", e))
+        })?;
+        batch
+            .delete_batch(self.executed_action_gas(), keys)
+            .map_err(|e| {
+                BridgeError::StorageError(format!(
+                    "Couldn't delete from executed_action_gas: {:?}",
+                    e
+                ))
+            })?
+            .delete_batch(self.transfer_claims(), keys)
+            .map_err(|e| {
+                BridgeError::StorageError(format!("Couldn't delete from transfer_claims: {:?}", e))
+            })?
+            .delete_batch(self.executed_action_times(), keys)
+            .map_err(|e| {
+                BridgeError::StorageError(format!(
+                    "Couldn't delete from executed_action_times: {:?}",
+                    e
+                ))
+            })?;
+        batch
+            .write()
+            .map_err(|e| BridgeError::StorageError(format!("Couldn't write batch: {:?}", e)))
+    }
+
+    /// Returns true if `action` is already pending, executed, dropped or quarantined, e.g. when its
+    /// events are read again after a restart or a stalled send.
+    pub(crate) fn is_known_action(&self, action: &BridgeAction) -> BridgeResult<bool> {
+        let digest = action.digest();
+        let known = self
+            .primary
+            .pending_actions
+            .contains_key(&digest)
+            .and_then(|pending| {
+                if pending {
+                    return Ok(true);
+                }
+                if self.primary.dropped_actions.contains_key(&digest)?
+                    || self.primary.quarantined_actions.contains_key(&digest)?
+                {
+                    return Ok(true);
+                }
+                match executed_action_key(action) {
+                    Some(key) => self.executed_actions().contains_key(&key),
+                    None => Ok(false),
+                }
+            });
+        known.map_err(|e| BridgeError::StorageError(format!("Couldn't check action: {:?}", e)))
+    }
+
+    /// Reads every table, with the executed actions from the auxiliary db if it's open.
+    pub fn export(&self) -> StorageExport {
+        StorageExport {
+            pending_actions: self.primary.pending_actions.unbounded_iter().collect(),
+            sui_syncer_cursors: self.primary.sui_syncer_cursors.unbounded_iter().collect(),
+            eth_syncer_cursors: self.primary.eth_syncer_cursors.unbounded_iter().collect(),
+            dropped_actions: self.primary.dropped_actions.unbounded_iter().collect(),
+            dropped_action_reasons: self
+                .primary
+                .dropped_action_reasons
+                .unbounded_iter()
+                .collect(),
+            executed_actions: self.executed_actions().unbounded_iter().collect(),
+            executed_action_gas: self.executed_action_gas().unbounded_iter().collect(),
+            authority_scores: self.primary.authority_scores.unbounded_iter().collect(),
+            quarantined_actions: self.primary.quarantined_actions.unbounded_iter().collect(),
+            cursor_resets: self.primary.cursor_resets.unbounded_iter().collect(),
+            cursor_reset_origins: self.primary.cursor_reset_origins.unbounded_iter().collect(),
+            admin_requests: self.primary.admin_requests.unbounded_iter().collect(),
+            signed_actions: self.primary.signed_actions.unbounded_iter().collect(),
+            signed_set_buckets: self.primary.signed_set_buckets.unbounded_iter().collect(),
+            quarantine_checks: self.primary.quarantine_checks.unbounded_iter().collect(),
+            transfer_claims: self.transfer_claims().unbounded_iter().collect(),
+            executed_action_times: self.executed_action_times().unbounded_iter().collect(),
+            admin_audit: self.primary.admin_audit.unbounded_iter().collect(),
+        }
+    }
+
+    /// Writes the entries of `export` over the ones in the tables, the executed actions
+    /// into the auxiliary db if it's open.
+    pub fn import(&self, export: &StorageExport) -> BridgeResult<()> {
+        let executed_actions = self.executed_actions();
+        let mut batch = executed_actions.batch();
+        batch
+            .insert_batch(
+                executed_actions,
+                export.executed_actions.iter().map(|(k, v)| (k, v)),
+            )
+            .map_err(|e| insert_error("executed_actions", e))?
+            .insert_batch(
+                self.executed_action_gas(),
+                export.executed_action_gas.iter().map(|(k, v)| (k, v)),
+            )
+            .map_err(|e| insert_error("executed_action_gas", e))?
+            .insert_batch(
+                self.transfer_claims(),
+                export.transfer_claims.iter().map(|(k, v)| (k, v)),
+            )
+            .map_err(|e| insert_error("transfer_claims", e))?
+            .insert_batch(
+                self.executed_action_times(),
+                export.executed_action_times.iter().map(|(k, v)| (k, v)),
+            )
+            .map_err(|e| insert_error("executed_action_times", e))?;
+        if self.aux.is_some() {
+            batch
+                .write()
+                .map_err(|e| BridgeError::StorageError(format!("Couldn't write batch: {:?}", e)))?;
+            batch = self.primary.pending_actions.batch();
+        }
+        let primary = &self.primary;
+        batch
+            .insert_batch(
+                &primary.pending_actions,
+                export.pending_actions.iter().map(|(k, v)| (k, v)),
+            )
+            .map_err(|e| insert_error("pending_actions", e))?
+            .insert_batch(
+                &primary.sui_syncer_cursors,
+                export.sui_syncer_cursors.iter().map(|(k, v)| (k, v)),
+            )
+            .map_err(|e| insert_error("sui_syncer_cursors", e))?
+            .insert_batch(
+                &primary.eth_syncer_cursors,
+                export.eth_syncer_cursors.iter().map(|(k, v)| (k, v)),
+            )
+            .map_err(|e| insert_error("eth_syncer_cursors", e))?
+            .insert_batch(
+                &primary.dropped_actions,
+                export.dropped_actions.iter().map(|(k, v)| (k, v)),
+            )
+            .map_err(|e| insert_error("dropped_actions", e))?
+            .insert_batch(
+                &primary.dropped_action_reasons,
+                export.dropped_action_reasons.iter().map(|(k, v)| (k, v)),
+            )
+            .map_err(|e| insert_error("dropped_action_reasons", e))?
+            .insert_batch(
+                &primary.authority_scores,
+                export.authority_scores.iter().map(|(k, v)| (k, v)),
+            )
+            .map_err(|e| insert_error("authority_scores", e))?
+            .insert_batch(
+                &primary.quarantined_actions,
+                export.quarantined_actions.iter().map(|(k, v)| (k, v)),
+            )
+            .map_err(|e| insert_error("quarantined_actions", e))?
+            .insert_batch(
+                &primary.cursor_resets,
+                export.cursor_resets.iter().map(|(k, v)| (k, v)),
+            )
+            .map_err(|e| insert_error("cursor_resets", e))?
+            .insert_batch(
+                &primary.cursor_reset_origins,
+                export.cursor_reset_origins.iter().map(|(k, v)| (k, v)),
+            )
+            .map_err(|e| insert_error("cursor_reset_origins", e))?
+            .insert_batch(
+                &primary.admin_requests,
+                export.admin_requests.iter().map(|(k, v)| (k, v)),
+            )
+            .map_err(|e| insert_error("admin_requests", e))?
+            .insert_batch(
+                &primary.admin_audit,
+                export.admin_audit.iter().map(|(k, v)| (k, v)),
+            )
+            .map_err(|e| insert_error("admin_audit", e))?;
+        batch
+            .write()
+            .map_err(|e| BridgeError::StorageError(format!("Couldn't write batch: {:?}", e)))
+    }
+
+    /// Reads every table and returns the invariants between them that are broken. Nothing
+    /// is written.
+    pub fn verify_integrity(&self) -> Vec<StorageViolation> {
+        let primary = &self.primary;
+        let mut violations = vec![];
+        verify_decodable(&primary.pending_actions, "pending_actions", &mut violations);
+        verify_decodable(
+            &primary.sui_syncer_cursors,
+            "sui_syncer_cursors",
+            &mut violations,
+        );
+        verify_decodable(
+            &primary.eth_syncer_cursors,
+            "eth_syncer_cursors",
+            &mut violations,
+        );
+        verify_decodable(&primary.dropped_actions, "dropped_actions", &mut violations);
+        verify_decodable(
+            &primary.dropped_action_reasons,
+            "dropped_action_reasons",
+            &mut violations,
+        );
+        verify_decodable(self.executed_actions(), "executed_actions", &mut violations);
+        verify_decodable(
+            self.executed_action_gas(),
+            "executed_action_gas",
+            &mut violations,
+        );
+        verify_decodable(self.transfer_claims(), "transfer_claims", &mut violations);
+        verify_decodable(
+            self.executed_action_times(),
+            "executed_action_times",
+            &mut violations,
+        );
+        verify_decodable(
+            &primary.authority_scores,
+            "authority_scores",
+            &mut violations,
+        );
+        verify_decodable(
+            &primary.quarantined_actions,
+            "quarantined_actions",
+            &mut violations,
+        );
+        verify_decodable(&primary.cursor_resets, "cursor_resets", &mut violations);
+        verify_decodable(
+            &primary.cursor_reset_origins,
+            "cursor_reset_origins",
+            &mut violations,
+        );
+        verify_decodable(&primary.admin_requests, "admin_requests", &mut violations);
+        verify_decodable(&primary.admin_audit, "admin_audit", &mut violations);
+        verify_decodable(&primary.signed_actions, "signed_actions", &mut violations);
+        verify_decodable(
+            &primary.signed_set_buckets,
+            "signed_set_buckets",
+            &mut violations,
+        );
+        verify_decodable(
+            &primary.quarantine_checks,
+            "quarantine_checks",
+            &mut violations,
+        );
+
+        for (key, action) in primary.pending_actions.unbounded_iter() {
+            let digest = action.digest();
+            if key != digest {
+                violations.push(StorageViolation::MisplacedAction {
+                    table: "pending_actions",
+                    key,
+                    digest,
+                });
+                continue;
+            }
+            let executed = executed_action_key(&action)
+                .and_then(|executed_key| self.executed_actions().get(&executed_key).ok().flatten());
+            if executed.is_some_and(|executed| executed.digest() == digest) {
+                violations.push(StorageViolation::ExecutedActionStillPending { digest });
+            } else if contains_action(&primary.dropped_actions, &digest) {
+                violations.push(StorageViolation::DroppedActionStillPending { digest });
+            } else if contains_action(&primary.quarantined_actions, &digest) {
+                violations.push(StorageViolation::QuarantinedActionStillPending { digest });
+            }
+        }
+        for (key, action) in primary.dropped_actions.unbounded_iter() {
+            if key != action.digest() {
+                violations.push(StorageViolation::MisplacedAction {
+                    table: "dropped_actions",
+                    key,
+                    digest: action.digest(),
+                });
+            }
+        }
+        for (key, quarantined) in primary.quarantined_actions.unbounded_iter() {
+            if key != quarantined.action.digest() {
+                violations.push(StorageViolation::MisplacedAction {
+                    table: "quarantined_actions",
+                    key,
+                    digest: quarantined.action.digest(),
+                });
+            }
+        }
+        for (digest, _) in primary.dropped_action_reasons.unbounded_iter() {
+            if !contains_action(&primary.dropped_actions, &digest) {
+                violations.push(StorageViolation::OrphanedDropReason { digest });
+            }
+        }
+        for (key, action) in self.executed_actions().unbounded_iter() {
+            let expected_key = executed_action_key(&action);
+            if expected_key != Some(key) {
+                violations.push(StorageViolation::MisplacedExecutedAction { key, expected_key });
+            }
+        }
+
+        // The last reset of each cursor
+        let mut eth_resets = BTreeMap::new();
+        let mut sui_resets = BTreeMap::new();
+        for (expected_index, (index, record)) in (0..).zip(primary.cursor_resets.unbounded_iter()) {
+            if index != expected_index {
+                violations.push(StorageViolation::CursorResetLogGap {
+                    expected_index,
+                    index,
+                });
+            }
+            match record.cursor {
+                ResetCursor::Eth {
+                    contract,
+                    new_block,
+                    ..
+                } => {
+                    eth_resets.insert(contract, new_block);
+                }
+                ResetCursor::Sui { module, .. } => {
+                    sui_resets.insert(module, ());
+                }
+            }
+        }
+        for (contract, reset_block) in eth_resets {
+            let cursor = primary.eth_syncer_cursors.get(&contract).ok().flatten();
+            if cursor.map_or(true, |cursor| cursor < reset_block) {
+                violations.push(StorageViolation::EthCursorBeforeReset {
+                    contract,
+                    cursor,
+                    reset_block,
+                });
+            }
+        }
+        for module in sui_resets.into_keys() {
+            if !primary
+                .sui_syncer_cursors
+                .contains_key(&module)
+                .unwrap_or(false)
+            {
+                violations.push(StorageViolation::SuiCursorMissingAfterReset { module });
+            }
+        }
+        violations
+    }
+
+    /// Fixes the repairable `violations`: misplaced entries are moved under their key,
+    /// pending actions that were already executed, dropped or quarantined and orphaned
+    /// drop reasons are deleted. Returns what was done, for the audit of the repair.
+    pub fn repair_integrity(&self, violations: &[StorageViolation]) -> BridgeResult<Vec<String>> {
+        let primary = &self.primary;
+        let mut done = vec![];
+        for violation in violations {
+            let hex = |digest: &BridgeActionDigest| Hex::encode(digest.inner());
+            match violation {
+                StorageViolation::MisplacedAction { table, key, digest } => match *table {
+                    "pending_actions" => {
+                        done.push(relink_action(&primary.pending_actions, table, key, digest)?)
+                    }
+                    "dropped_actions" => {
+                        done.push(relink_action(&primary.dropped_actions, table, key, digest)?);
+                        if contains_action(&primary.dropped_action_reasons, key) {
+                            done.push(relink_action(
+                                &primary.dropped_action_reasons,
+                                "dropped_action_reasons",
+                                key,
+                                digest,
+                            )?);
+                        }
+                    }
+                    "quarantined_actions" => done.push(relink_action(
+                        &primary.quarantined_actions,
+                        table,
+                        key,
+                        digest,
+                    )?),
+                    _ => {}
+                },
+                StorageViolation::MisplacedExecutedAction { key, expected_key } => {
+                    let executed_actions = self.executed_actions();
+                    let storage_error = |e: TypedStoreError| {
+                        BridgeError::StorageError(format!(
+                            "Couldn't repair executed_actions: {:?}",
+                            e
+                        ))
+                    };
+                    let Some(action) = executed_actions.get(key).map_err(storage_error)? else {
+                        continue;
+                    };
+                    let mut batch = executed_actions.batch();
+                    match expected_key {
+                        Some(expected_key)
+                            if !executed_actions
+                                .contains_key(expected_key)
+                                .map_err(storage_error)? =>
+                        {
+                            batch
+                                .insert_batch(executed_actions, [(expected_key, &action)])
+                                .map_err(storage_error)?;
+                            done.push(format!(
+                                "executed_actions: moved {:?} to {:?}",
+                                key, expected_key
+                            ));
+                        }
+                        _ => done.push(format!("executed_actions: deleted {:?}", key)),
+                    }
+                    batch
+                        .delete_batch(executed_actions, [key])
+                        .map_err(storage_error)?;
+                    batch.write().map_err(storage_error)?;
+                }
+                StorageViolation::ExecutedActionStillPending { digest }
+                | StorageViolation::DroppedActionStillPending { digest }
+                | StorageViolation::QuarantinedActionStillPending { digest } => {
+                    self.remove_pending_actions(&[*digest])?;
+                    done.push(format!("pending_actions: deleted {}", hex(digest)));
+                }
+                StorageViolation::OrphanedDropReason { digest } => {
+                    primary.dropped_action_reasons.remove(digest).map_err(|e| {
+                        BridgeError::StorageError(format!(
+                            "Couldn't delete from dropped_action_reasons: {:?}",
+                            e
+                        ))
+                    })?;
+                    done.push(format!("dropped_action_reasons: deleted {}", hex(digest)));
+                }
+                _ => {}
+            }
+        }
+        Ok(done)
     }
 
+    /// Returns the cursors of `identifiers`, the buffered ones if they were not written
+    /// yet.
     pub fn get_sui_event_cursors(
         &self,
         identifiers: &[Identifier],
     ) -> BridgeResult<Vec<Option<EventID>>> {
-        self.sui_syncer_cursors.multi_get(identifiers).map_err(|e| {
-            BridgeError::StorageError(format!("Couldn't get sui_syncer_cursors: {:?}", e))
-        })
+        let buffer = self.cursor_buffer.lock().unwrap();
+        let cursors = self
+            .primary
+            .sui_syncer_cursors
+            .multi_get(identifiers)
+            .map_err(|e| {
+                BridgeError::StorageError(format!("Couldn't get sui_syncer_cursors: {:?}", e))
+            })?;
+        Ok(identifiers
+            .iter()
+            .zip(cursors)
+            .map(|(module, cursor)| buffer.sui.get(module).copied().or(cursor))
+            .collect())
     }
 
+    /// Returns the cursors of `contract_addresses`, the buffered ones if they were not
+    /// written yet.
     pub fn get_eth_event_cursors(
         &self,
         contract_addresses: &[ethers::types::Address],
     ) -> BridgeResult<Vec<Option<u64>>> {
-        self.eth_syncer_cursors
+        let buffer = self.cursor_buffer.lock().unwrap();
+        let cursors = self
+            .primary
+            .eth_syncer_cursors
             .multi_get(contract_addresses)
             .map_err(|e| {
                 BridgeError::StorageError(format!("Couldn't get sui_syncer_cursors: {:?}", e))
-            })
+            })?;
+        Ok(contract_addresses
+            .iter()
+            .zip(cursors)
+            .map(|(contract, cursor)| buffer.eth.get(contract).copied().or(cursor))
+            .collect())
+    }
+}
+
+impl Drop for BridgeOrchestratorTables {
+    fn drop(&mut self) {
+        if let Err(e) = self.flush_cursors("shutdown") {
+            warn!("Couldn't write the buffered syncer cursors: {:?}", e);
+        }
+    }
+}
+
+/// Writes the buffered cursors of `store` every `interval`, see
+/// `BridgeOrchestratorTables::enable_cursor_coalescing`.
+pub(crate) async fn run_cursor_flusher(store: Arc<BridgeOrchestratorTables>, interval: Duration) {
+    let mut interval = tokio::time::interval(interval);
+    interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+    loop {
+        interval.tick().await;
+        if let Err(e) = store.flush_cursors("interval") {
+            warn!("Couldn't write the buffered syncer cursors: {:?}", e);
+        }
     }
 }
 
@@ -137,7 +2510,11 @@ mod tests {
 
     use sui_types::digests::TransactionDigest;
 
-    use crate::test_utils::get_test_sui_to_eth_bridge_action;
+    use sui_types::bridge::BridgeChainId;
+
+    use crate::storage_encryption::FileStorageKeyProvider;
+    use crate::test_utils::{get_test_eth_to_sui_bridge_action, get_test_sui_to_eth_bridge_action};
+    use fastcrypto::encoding::Base64;
 
     use super::*;
 
@@ -211,6 +2588,119 @@ mod tests {
         let actions = store.get_all_pending_actions();
         assert!(actions.is_empty());
 
+        // executed actions are removed from pending actions and kept by route
+        let action3 = get_test_eth_to_sui_bridge_action(Some(7), None, None, None);
+        store
+            .insert_pending_actions(&[action1.clone(), action2.clone(), action3.clone()])
+            .unwrap();
+        store
+            .mark_actions_executed(&[action1.clone(), action2.clone(), action3.clone()])
+            .unwrap();
+        assert!(store.get_all_pending_actions().is_empty());
+        let sui_chain_id = BridgeChainId::SuiCustom as u8;
+        let eth_chain_id = BridgeChainId::EthCustom as u8;
+        assert_eq!(
+            store
+                .get_recent_executed_actions(sui_chain_id, eth_chain_id, 10)
+                .unwrap(),
+            vec![(100, action2.clone()), (99, action1.clone())]
+        );
+        assert_eq!(
+            store
+                .get_recent_executed_actions(sui_chain_id, eth_chain_id, 1)
+                .unwrap(),
+            vec![(100, action2.clone())]
+        );
+        assert_eq!(
+            store
+                .get_recent_executed_actions(eth_chain_id, sui_chain_id, 10)
+                .unwrap(),
+            vec![(7, action3.clone())]
+        );
+        assert_eq!(
+            store
+                .get_executed_action(sui_chain_id, eth_chain_id, 99)
+                .unwrap(),
+            Some(action1.clone())
+        );
+        assert!(store
+            .get_executed_action(sui_chain_id, eth_chain_id, 98)
+            .unwrap()
+            .is_none());
+
+        // gas is recorded for the actions that this node executed
+        let action7 = get_test_sui_to_eth_bridge_action(
+            None,
+            Some(0),
+            Some(101),
+            Some(10000),
+            None,
+            None,
+            None,
+        );
+        store.insert_pending_actions(&[action7.clone()]).unwrap();
+        store
+            .mark_action_executed_with_gas(&action7, 2_500_000)
+            .unwrap();
+        assert!(store.get_all_pending_actions().is_empty());
+        assert_eq!(
+            store
+                .get_executed_action_gas(sui_chain_id, eth_chain_id, 101)
+                .unwrap(),
+            Some(2_500_000)
+        );
+        assert_eq!(
+            store
+                .get_executed_action_gas(sui_chain_id, eth_chain_id, 100)
+                .unwrap(),
+            None
+        );
+
+        // and when they were executed
+        let action8 = get_test_sui_to_eth_bridge_action(
+            None,
+            Some(0),
+            Some(102),
+            Some(10000),
+            None,
+            None,
+            None,
+        );
+        store.mark_actions_executed_at(&[action8], 1000).unwrap();
+        let times = store.get_executed_action_times_since(0);
+        assert!(times.contains(&((sui_chain_id, eth_chain_id, 102), 1000)));
+        assert!(times.iter().any(|(key, executed_at_ms)| *key
+            == (sui_chain_id, eth_chain_id, 101)
+            && *executed_at_ms > 1000));
+        assert!(!store
+            .get_executed_action_times_since(2000)
+            .iter()
+            .any(|(key, _)| key.2 == 102));
+
+        // known actions are pending, executed or dropped
+        let action4 = get_test_eth_to_sui_bridge_action(Some(8), None, None, None);
+        let action5 = get_test_eth_to_sui_bridge_action(Some(9), None, None, None);
+        let action6 = get_test_eth_to_sui_bridge_action(Some(10), None, None, None);
+        store.insert_pending_actions(&[action4.clone()]).unwrap();
+        store
+            .insert_dropped_actions(
+                &[action5.clone()],
+                DroppedActionReason::BelowMinimumTransfer,
+            )
+            .unwrap();
+        for action in [&action1, &action3, &action4, &action5] {
+            assert!(store.is_known_action(action).unwrap());
+        }
+        assert!(!store.is_known_action(&action6).unwrap());
+        assert_eq!(
+            store.get_dropped_action_reason(&action5.digest()).unwrap(),
+            Some(DroppedActionReason::BelowMinimumTransfer)
+        );
+        assert_eq!(
+            store.get_dropped_action_reason(&action6.digest()).unwrap(),
+            None
+        );
+
         // update eth event cursor
         let eth_contract_address = ethers::types::Address::random();
         let eth_block_num = 199999u64;
@@ -244,4 +2734,598 @@ mod tests {
             sui_cursor
         );
     }
+
+    #[tokio::test]
+    async fn test_bridge_storage_with_aux_db() {
+        let primary_dir = tempfile::tempdir().unwrap();
+        let aux_dir = tempfile::tempdir().unwrap();
+        let new_aux_dir = tempfile::tempdir().unwrap();
+        let sui_chain_id = BridgeChainId::SuiCustom as u8;
+        let eth_chain_id = BridgeChainId::EthCustom as u8;
+        let action1 =
+            get_test_sui_to_eth_bridge_action(None, None, Some(1), None, None, None, None);
+        let action2 =
+            get_test_sui_to_eth_bridge_action(None, None, Some(2), None, None, None, None);
+        let executed = |store: &BridgeOrchestratorTables| {
+            store
+                .get_recent_executed_actions(sui_chain_id, eth_chain_id, 10)
+                .unwrap()
+        };
+
+        let store = BridgeOrchestratorTables::open(primary_dir.path(), Some(aux_dir.path()));
+        store
+            .insert_pending_actions(&[action1.clone(), action2.clone()])
+            .unwrap();
+        store.mark_actions_executed(&[action1.clone()]).unwrap();
+        assert_eq!(
+            store.get_all_pending_actions(),
+            HashMap::from([(action2.digest(), action2.clone())])
+        );
+        assert_eq!(executed(&store), vec![(1, action1.clone())]);
+        drop(store);
+
+        // Executed actions are only in the aux db
+        let store = BridgeOrchestratorTables::new(primary_dir.path());
+        assert_eq!(store.get_all_pending_actions().len(), 1);
+        assert!(executed(&store).is_empty());
+        drop(store);
+
+        // Aux db on a new path starts empty, and primary tables are unaffected
+        let store = BridgeOrchestratorTables::open(primary_dir.path(), Some(new_aux_dir.path()));
+        assert_eq!(store.get_all_pending_actions().len(), 1);
+        assert!(executed(&store).is_empty());
+        store.mark_actions_executed(&[action2.clone()]).unwrap();
+        assert!(store.get_all_pending_actions().is_empty());
+        assert_eq!(executed(&store), vec![(2, action2.clone())]);
+        drop(store);
+
+        // The original aux db still has its own executed actions only
+        let store = BridgeOrchestratorTables::open(primary_dir.path(), Some(aux_dir.path()));
+        assert_eq!(executed(&store), vec![(1, action1.clone())]);
+        assert!(!store.is_known_action(&action2).unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_bridge_storage_quarantined_actions() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let store = BridgeOrchestratorTables::new(temp_dir.path());
+        let action1 =
+            get_test_sui_to_eth_bridge_action(None, Some(0), Some(1), None, None, None, None);
+        let action2 =
+            get_test_sui_to_eth_bridge_action(None, Some(0), Some(2), None, None, None, None);
+        store
+            .insert_pending_actions(&[action1.clone(), action2.clone()])
+            .unwrap();
+
+        for action in [&action1, &action2] {
+            store
+                .quarantine_action(&QuarantinedAction {
+                    action: action.clone(),
+                    reason: "rejected".to_string(),
+                    failures: 3,
+                    quarantined_at_ms: 0,
+                })
+                .unwrap();
+        }
+        assert!(store.get_all_pending_actions().is_empty());
+        assert_eq!(store.get_all_quarantined_actions().len(), 2);
+        assert!(store.is_known_action(&action1).unwrap());
+
+        // Requeued actions are pending again
+        let requeued = store.requeue_quarantined_action(&action1.digest()).unwrap();
+        assert_eq!(requeued, Some(action1.clone()));
+        assert_eq!(
+            store.get_all_pending_actions(),
+            HashMap::from([(action1.digest(), action1.clone())])
+        );
+        assert_eq!(
+            store.requeue_quarantined_action(&action1.digest()).unwrap(),
+            None
+        );
+
+        // Deleted actions are dropped, so they are not processed again
+        let deleted = store.delete_quarantined_action(&action2.digest()).unwrap();
+        assert_eq!(deleted.unwrap().action, action2);
+        assert!(store.get_all_quarantined_actions().is_empty());
+        assert_eq!(
+            store.get_dropped_action_reason(&action2.digest()).unwrap(),
+            Some(DroppedActionReason::DeletedFromQuarantine)
+        );
+        assert!(store.is_known_action(&action2).unwrap());
+    }
+
+    fn storage_key(dir: &Path, byte: u8) -> FileStorageKeyProvider {
+        let path = dir.join(format!("storage-{}.key", byte));
+        std::fs::write(&path, Base64::encode([byte; 32])).unwrap();
+        FileStorageKeyProvider::read(&path).unwrap()
+    }
+
+    fn open_error(
+        path: &Path,
+        aux_path: Option<&Path>,
+        key_provider: Option<&dyn StorageKeyProvider>,
+    ) -> String {
+        match BridgeOrchestratorTables::open_with_encryption(path, aux_path, key_provider) {
+            Ok(_) => panic!("Opened {:?}", path),
+            Err(e) => format!("{:?}", e),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_bridge_storage_encryption() {
+        let primary_dir = tempfile::tempdir().unwrap();
+        let aux_dir = tempfile::tempdir().unwrap();
+        let key_dir = tempfile::tempdir().unwrap();
+        let key = storage_key(key_dir.path(), 1);
+        let (primary, aux) = (primary_dir.path(), Some(aux_dir.path()));
+        let sui_chain_id = BridgeChainId::SuiCustom as u8;
+        let eth_chain_id = BridgeChainId::EthCustom as u8;
+        let action1 =
+            get_test_sui_to_eth_bridge_action(None, None, Some(1), None, None, None, None);
+        let action2 =
+            get_test_sui_to_eth_bridge_action(None, None, Some(2), None, None, None, None);
+
+        let store =
+            BridgeOrchestratorTables::open_with_encryption(primary, aux, Some(&key)).unwrap();
+        store
+            .insert_pending_actions(&[action1.clone(), action2.clone()])
+            .unwrap();
+        store.mark_actions_executed(&[action1.clone()]).unwrap();
+        // Values are sealed on disk
+        let raw = store
+            .primary
+            .pending_actions
+            .get_raw_bytes(&action2.digest())
+            .unwrap()
+            .unwrap();
+        assert_ne!(raw, bcs::to_bytes(&action2).unwrap());
+        drop(store);
+
+        let store =
+            BridgeOrchestratorTables::open_with_encryption(primary, aux, Some(&key)).unwrap();
+        assert_eq!(
+            store.get_all_pending_actions(),
+            HashMap::from([(action2.digest(), action2.clone())])
+        );
+        assert_eq!(
+            store
+                .get_recent_executed_actions(sui_chain_id, eth_chain_id, 10)
+                .unwrap(),
+            vec![(1, action1.clone())]
+        );
+        assert!(store.verify_integrity().is_empty());
+        drop(store);
+
+        // The dbs can't be opened without their key, nor with another one
+        assert!(open_error(primary, aux, None).contains("no storage encryption key is configured"));
+        let other_key = storage_key(key_dir.path(), 2);
+        assert!(open_error(primary, aux, Some(&other_key)).contains("the configured key is"));
+        // The auxiliary db is checked too
+        let other_aux_dir = tempfile::tempdir().unwrap();
+        let store = BridgeOrchestratorTables::open_with_encryption(
+            other_aux_dir.path(),
+            aux,
+            Some(&other_key),
+        );
+        assert!(store.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_bridge_storage_encrypt_in_place() {
+        let primary_dir = tempfile::tempdir().unwrap();
+        let aux_dir = tempfile::tempdir().unwrap();
+        let key_dir = tempfile::tempdir().unwrap();
+        let key = storage_key(key_dir.path(), 1);
+        let (primary, aux) = (primary_dir.path(), Some(aux_dir.path()));
+        let sui_chain_id = BridgeChainId::SuiCustom as u8;
+        let eth_chain_id = BridgeChainId::EthCustom as u8;
+        let contract = ethers::types::Address::random();
+        let actions = (1..=10)
+            .map(|nonce| {
+                get_test_sui_to_eth_bridge_action(None, None, Some(nonce), None, None, None, None)
+            })
+            .collect::<Vec<_>>();
+
+        let store = BridgeOrchestratorTables::open(primary, aux);
+        store.insert_pending_actions(&actions).unwrap();
+        store.mark_actions_executed(&actions[..4]).unwrap();
+        store.update_eth_event_cursor(contract, 100).unwrap();
+        let export = store.export();
+        drop(store);
+
+        // A plaintext db can't be opened with a key
+        assert!(open_error(primary, aux, Some(&key)).contains("encrypt-in-place"));
+
+        // A migration of the primary db interrupted after sealing 3 pending actions
+        let open_primary = || {
+            BridgePrimaryTables::open_tables_read_write(
+                primary.to_path_buf(),
+                MetricConf::new("bridge"),
+                None,
+                None,
+            )
+        };
+        let mut primary_tables = open_primary();
+        let mut plaintext = CountEntries(0);
+        primary_tables.visit_tables(&mut plaintext).unwrap();
+        let (cipher, record) = StorageCipher::generate(&key).unwrap();
+        primary_tables
+            .storage_encryption
+            .insert(&PENDING_STORAGE_ENCRYPTION_KEY, &record)
+            .unwrap();
+        let sealed = primary_tables
+            .pending_actions
+            .safe_iter()
+            .take(3)
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+        primary_tables
+            .pending_actions
+            .set_value_codec(Arc::new(cipher))
+            .unwrap();
+        primary_tables.pending_actions.multi_insert(sealed).unwrap();
+        drop(primary_tables);
+        assert!(open_error(primary, aux, Some(&key)).contains("was interrupted"));
+        assert!(open_error(primary, aux, None).contains("was interrupted"));
+
+        // Resumed where it stopped, 2 entries per batch
+        let mut primary_tables = open_primary();
+        assert_eq!(
+            encrypt_tables_in_place(&mut primary_tables, "primary db", &key, 2).unwrap(),
+            Some(plaintext.0 - 3)
+        );
+        drop(primary_tables);
+
+        let done = BridgeOrchestratorTables::encrypt_in_place(primary, aux, &key).unwrap();
+        assert_eq!(done.len(), 2);
+        assert_eq!(done[0], "primary db: encrypted already");
+        assert!(done[1].starts_with("auxiliary db: encrypted"));
+        assert_eq!(
+            BridgeOrchestratorTables::encrypt_in_place(primary, aux, &key).unwrap(),
+            vec![
+                "primary db: encrypted already".to_string(),
+                "auxiliary db: encrypted already".to_string(),
+            ]
+        );
+        assert!(open_error(primary, aux, None).contains("no storage encryption key is configured"));
+
+        let store =
+            BridgeOrchestratorTables::open_with_encryption(primary, aux, Some(&key)).unwrap();
+        assert_eq!(store.export(), export);
+        assert_eq!(store.get_all_pending_actions().len(), 6);
+        assert_eq!(
+            store
+                .get_recent_executed_actions(sui_chain_id, eth_chain_id, 10)
+                .unwrap()
+                .len(),
+            4
+        );
+        assert_eq!(
+            store.get_eth_event_cursors(&[contract]).unwrap(),
+            vec![Some(100)]
+        );
+        assert!(store.verify_integrity().is_empty());
+        // Entries written after the migration are encrypted too
+        store.update_eth_event_cursor(contract, 200).unwrap();
+        assert_ne!(
+            store
+                .primary
+                .eth_syncer_cursors
+                .get_raw_bytes(&contract)
+                .unwrap()
+                .unwrap(),
+            bcs::to_bytes(&200u64).unwrap()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_bridge_storage_integrity() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let store = BridgeOrchestratorTables::new(temp_dir.path());
+        let primary = &store.primary;
+        let action = |nonce| {
+            get_test_sui_to_eth_bridge_action(None, Some(0), Some(nonce), None, None, None, None)
+        };
+        store
+            .insert_pending_actions(&[action(1), action(2)])
+            .unwrap();
+        store.mark_actions_executed(&[action(2)]).unwrap();
+        let contract = ethers::types::Address::random();
+        let module = Identifier::from_str("bridge").unwrap();
+        let eth_reset = CursorResetRecord {
+            cursor: ResetCursor::Eth {
+                contract,
+                old_block: None,
+                new_block: 100,
+            },
+            forced: false,
+            reset_at_ms: 0,
+        };
+        store.reset_syncer_cursor(&eth_reset, None).unwrap();
+        store
+            .reset_syncer_cursor(
+                &CursorResetRecord {
+                    cursor: ResetCursor::Sui {
+                        module: module.clone(),
+                        old_event_id: None,
+                        new_event_id: EventID {
+                            tx_digest: TransactionDigest::random(),
+                            event_seq: 0,
+                        },
+                    },
+                    forced: false,
+                    reset_at_ms: 0,
+                },
+                None,
+            )
+            .unwrap();
+        store.update_eth_event_cursor(contract, 120).unwrap();
+        assert_eq!(store.verify_integrity(), vec![]);
+
+        // Executed, dropped and quarantined actions that are pending again
+        primary
+            .pending_actions
+            .insert(&action(2).digest(), &action(2))
+            .unwrap();
+        store
+            .insert_dropped_actions(&[action(3)], DroppedActionReason::BelowMinimumTransfer)
+            .unwrap();
+        store.insert_pending_actions(&[action(4)]).unwrap();
+        store
+            .quarantine_action(&QuarantinedAction {
+                action: action(4),
+                reason: "rejected".to_string(),
+                failures: 3,
+                quarantined_at_ms: 0,
+            })
+            .unwrap();
+        store
+            .insert_pending_actions(&[action(3), action(4)])
+            .unwrap();
+        // Misplaced and orphaned entries
+        primary
+            .pending_actions
+            .insert(&action(6).digest(), &action(5))
+            .unwrap();
+        primary
+            .dropped_action_reasons
+            .insert(&action(7).digest(), &DroppedActionReason::InvalidSignatures)
+            .unwrap();
+        primary
+            .executed_actions
+            .insert(&(9, 9, 9), &action(8))
+            .unwrap();
+        // Cursors
+        store.update_eth_event_cursor(contract, 50).unwrap();
+        primary.sui_syncer_cursors.remove(&module).unwrap();
+        primary.cursor_resets.insert(&5, &eth_reset).unwrap();
+        let scores = &primary.authority_scores;
+        scores
+            .rocksdb
+            .put_cf(&scores.cf(), b"bad", [1u8], &scores.opts.writeopts())
+            .unwrap();
+
+        let violations = store.verify_integrity();
+        let expected = vec![
+            StorageViolation::UndecodableEntries {
+                table: "authority_scores",
+                entries: 1,
+                decoded: 0,
+            },
+            StorageViolation::ExecutedActionStillPending {
+                digest: action(2).digest(),
+            },
+            StorageViolation::DroppedActionStillPending {
+                digest: action(3).digest(),
+            },
+            StorageViolation::QuarantinedActionStillPending {
+                digest: action(4).digest(),
+            },
+            StorageViolation::MisplacedAction {
+                table: "pending_actions",
+                key: action(6).digest(),
+                digest: action(5).digest(),
+            },
+            StorageViolation::OrphanedDropReason {
+                digest: action(7).digest(),
+            },
+            StorageViolation::MisplacedExecutedAction {
+                key: (9, 9, 9),
+                expected_key: executed_action_key(&action(8)),
+            },
+            StorageViolation::CursorResetLogGap {
+                expected_index: 2,
+                index: 5,
+            },
+            StorageViolation::EthCursorBeforeReset {
+                contract,
+                cursor: Some(50),
+                reset_block: 100,
+            },
+            StorageViolation::SuiCursorMissingAfterReset {
+                module: module.clone(),
+            },
+        ];
+        assert_eq!(violations.len(), expected.len(), "{:?}", violations);
+        for violation in &expected {
+            assert!(violations.contains(violation), "{}", violation);
+        }
+
+        // Only the repairable violations are fixed
+        let done = store.repair_integrity(&violations).unwrap();
+        assert_eq!(done.len(), 6, "{:?}", done);
+        let remaining = store.verify_integrity();
+        assert_eq!(remaining.len(), 4, "{:?}", remaining);
+        assert!(remaining.iter().all(|v| !v.is_repairable()));
+        assert_eq!(
+            store.get_all_pending_actions(),
+            HashMap::from([
+                (action(1).digest(), action(1)),
+                (action(5).digest(), action(5))
+            ])
+        );
+        assert!(store.is_known_action(&action(3)).unwrap());
+        assert_eq!(store.get_all_quarantined_actions().len(), 1);
+        assert_eq!(
+            store
+                .get_dropped_action_reason(&action(7).digest())
+                .unwrap(),
+            None
+        );
+        let (source, destination, nonce) = executed_action_key(&action(8)).unwrap();
+        assert_eq!(
+            store
+                .get_executed_action(source, destination, nonce)
+                .unwrap(),
+            Some(action(8))
+        );
+        assert!(store.get_executed_action(9, 9, 9).unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_bridge_storage_cursor_coalescing() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let metrics = Arc::new(BridgeMetrics::for_testing());
+        let store = BridgeOrchestratorTables::new(temp_dir.path());
+        store.enable_cursor_coalescing(metrics.clone());
+        let contract = ethers::types::Address::random();
+        let module = Identifier::from_str("bridge").unwrap();
+        let event_id = |event_seq| EventID {
+            tx_digest: TransactionDigest::ZERO,
+            event_seq,
+        };
+        let updates = |outcome: &str| {
+            metrics
+                .storage_cursor_updates
+                .with_label_values(&[outcome])
+                .get()
+        };
+        let flushes = |reason: &str| {
+            metrics
+                .storage_cursor_flushes
+                .with_label_values(&[reason])
+                .get()
+        };
+
+        for block in 1..=100 {
+            store.update_eth_event_cursor(contract, block).unwrap();
+            store
+                .update_sui_event_cursor(module.clone(), event_id(block))
+                .unwrap();
+        }
+        // Nothing is written yet, but the latest cursors are read
+        assert!(store
+            .primary
+            .eth_syncer_cursors
+            .get(&contract)
+            .unwrap()
+            .is_none());
+        assert!(store
+            .primary
+            .sui_syncer_cursors
+            .get(&module)
+            .unwrap()
+            .is_none());
+        assert_eq!(
+            store.get_eth_event_cursors(&[contract]).unwrap(),
+            vec![Some(100)]
+        );
+        assert_eq!(
+            store.get_sui_event_cursors(&[module.clone()]).unwrap(),
+            vec![Some(event_id(100))]
+        );
+        assert_eq!(updates("buffered"), 2);
+        assert_eq!(updates("coalesced"), 198);
+
+        // 200 updates are written as 2 cursors in one batch
+        assert_eq!(store.flush_cursors("interval").unwrap(), 2);
+        assert_eq!(store.flush_cursors("interval").unwrap(), 0);
+        assert_eq!(flushes("interval"), 1);
+        assert_eq!(metrics.storage_cursors_flushed.get(), 2);
+        assert_eq!(
+            store.primary.eth_syncer_cursors.get(&contract).unwrap(),
+            Some(100)
+        );
+        assert_eq!(
+            store.primary.sui_syncer_cursors.get(&module).unwrap(),
+            Some(event_id(100))
+        );
+
+        // The buffered cursors are written when the tables are dropped
+        store.update_eth_event_cursor(contract, 101).unwrap();
+        drop(store);
+        let store = BridgeOrchestratorTables::new(temp_dir.path());
+        assert_eq!(
+            store.get_eth_event_cursors(&[contract]).unwrap(),
+            vec![Some(101)]
+        );
+        assert_eq!(flushes("shutdown"), 1);
+
+        // Cursors are written through until coalescing is enabled
+        store.update_eth_event_cursor(contract, 102).unwrap();
+        assert_eq!(
+            store.primary.eth_syncer_cursors.get(&contract).unwrap(),
+            Some(102)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_bridge_storage_commit_pending_actions() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let metrics = Arc::new(BridgeMetrics::for_testing());
+        let store = BridgeOrchestratorTables::new(temp_dir.path());
+        store.enable_cursor_coalescing(metrics.clone());
+        let contract = ethers::types::Address::random();
+        let other_contract = ethers::types::Address::random();
+        let action = get_test_eth_to_sui_bridge_action(None, None, None, None);
+
+        store.update_eth_event_cursor(contract, 10).unwrap();
+        store.update_eth_event_cursor(other_contract, 5).unwrap();
+        store
+            .commit_pending_actions(&[action.clone()], SyncerCursor::Eth(contract, 20))
+            .unwrap();
+        // The actions are written with the cursor of their page, which doesn't go through
+        // the buffer, and the buffered cursors are written with them
+        assert_eq!(
+            store.get_all_pending_actions(),
+            HashMap::from([(action.digest(), action.clone())])
+        );
+        assert_eq!(
+            store.primary.eth_syncer_cursors.get(&contract).unwrap(),
+            Some(20)
+        );
+        assert_eq!(
+            store
+                .primary
+                .eth_syncer_cursors
+                .get(&other_contract)
+                .unwrap(),
+            Some(5)
+        );
+        assert_eq!(store.flush_cursors("interval").unwrap(), 0);
+        assert_eq!(
+            metrics
+                .storage_cursor_flushes
+                .with_label_values(&["commit"])
+                .get(),
+            1
+        );
+        assert_eq!(metrics.storage_cursors_flushed.get(), 1);
+
+        // A commit with nothing buffered is not a flush
+        store
+            .commit_pending_actions(&[], SyncerCursor::Eth(contract, 30))
+            .unwrap();
+        assert_eq!(
+            store.primary.eth_syncer_cursors.get(&contract).unwrap(),
+            Some(30)
+        );
+        assert_eq!(
+            metrics
+                .storage_cursor_flushes
+                .with_label_values(&["commit"])
+                .get(),
+            1
+        );
+    }
 }
@@ -0,0 +1,344 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Optional encryption at rest of the client storage, for operators who must encrypt what
+//! the bridge persists and can't rely on full-disk encryption. Each db has a random data
+//! key, the values of all its tables are sealed with AES-256-GCM under it, bound to their
+//! table and key, while the keys are left in the clear so that the tables can still be
+//! scanned in order. The data key is stored in the db wrapped by a key encryption key,
+//! which never touches the disk of the db: `StorageKeyProvider` is what a KMS plugs into,
+//! `FileStorageKeyProvider` reads it from a file, e.g. mounted from a secret store.
+//!
+//! Opening an encrypted db without the key, or with another key, fails. Existing plaintext
+//! dbs are encrypted with `sui-bridge-cli storage encrypt-in-place`.
+
+use crate::error::{BridgeError, BridgeResult};
+use crate::timestamps::now_ms;
+use fastcrypto::aes::{Aes256Gcm, AesKey, AuthenticatedCipher};
+use fastcrypto::encoding::{Base64, Encoding, Hex};
+use fastcrypto::hash::{Blake2b256, HashFunction};
+use fastcrypto::traits::{Generate, ToFromBytes};
+use rand::RngCore;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use typed_store::rocks::ValueCodec;
+use typed_store::TypedStoreError;
+
+pub const KEY_LENGTH: usize = 32;
+const NONCE_LENGTH: usize = 12;
+// Prefix of the sealed values, in case the format changes
+const SEALED_VALUE_VERSION: u8 = 1;
+// Authenticated with the wrapped data keys
+const DATA_KEY_AAD: &[u8] = b"sui-bridge-storage-data-key";
+
+type Nonce = <Aes256Gcm as AuthenticatedCipher>::IVType;
+
+fn new_cipher(key: &[u8]) -> BridgeResult<Aes256Gcm> {
+    if key.len() != KEY_LENGTH {
+        return Err(BridgeError::StorageError(format!(
+            "Storage keys must be {} bytes, got {}",
+            KEY_LENGTH,
+            key.len()
+        )));
+    }
+    let key = AesKey::from_bytes(key)
+        .map_err(|e| BridgeError::StorageError(format!("Invalid storage key: {:?}", e)))?;
+    Ok(Aes256Gcm::new(key))
+}
+
+// Returns the nonce followed by the ciphertext
+fn seal(cipher: &Aes256Gcm, aad: &[u8], plaintext: &[u8]) -> Vec<u8> {
+    let nonce = Nonce::generate(&mut rand::thread_rng());
+    [
+        nonce.as_bytes(),
+        cipher
+            .encrypt_authenticated(&nonce, aad, plaintext)
+            .as_slice(),
+    ]
+    .concat()
+}
+
+fn open(cipher: &Aes256Gcm, aad: &[u8], sealed: &[u8]) -> Option<Vec<u8>> {
+    if sealed.len() < NONCE_LENGTH {
+        return None;
+    }
+    let (nonce, ciphertext) = sealed.split_at(NONCE_LENGTH);
+    let nonce = Nonce::from_bytes(nonce).ok()?;
+    cipher.decrypt_authenticated(&nonce, aad, ciphertext).ok()
+}
+
+/// Wraps and unwraps the data keys of the dbs with a key encryption key.
+pub trait StorageKeyProvider: Send + Sync {
+    /// Identifies the key encryption key. Recorded with the wrapped data keys, so that a
+    /// db opened with another key fails with a clear error.
+    fn key_id(&self) -> String;
+
+    fn wrap(&self, data_key: &[u8]) -> BridgeResult<Vec<u8>>;
+
+    fn unwrap(&self, wrapped: &[u8]) -> BridgeResult<Vec<u8>>;
+}
+
+/// Key encryption key read from a file with the base64 encoding of 32 bytes, e.g. written
+/// by `openssl rand -base64 32`.
+pub struct FileStorageKeyProvider {
+    path: PathBuf,
+    key_id: String,
+    cipher: Aes256Gcm,
+}
+
+impl FileStorageKeyProvider {
+    pub fn read(path: &Path) -> BridgeResult<Self> {
+        let contents = std::fs::read_to_string(path).map_err(|e| {
+            BridgeError::StorageError(format!(
+                "Couldn't read storage encryption key {}: {:?}",
+                path.display(),
+                e
+            ))
+        })?;
+        let key = Base64::decode(contents.trim()).map_err(|e| {
+            BridgeError::StorageError(format!(
+                "Storage encryption key {} is not base64: {:?}",
+                path.display(),
+                e
+            ))
+        })?;
+        Self::new(path, &key)
+    }
+
+    fn new(path: &Path, key: &[u8]) -> BridgeResult<Self> {
+        // The fingerprint of the key, not the path, so that the key can be moved
+        let fingerprint = Blake2b256::digest(key).digest;
+        Ok(Self {
+            path: path.to_path_buf(),
+            key_id: format!("file:{}", Hex::encode(&fingerprint[..8])),
+            cipher: new_cipher(key)?,
+        })
+    }
+}
+
+impl StorageKeyProvider for FileStorageKeyProvider {
+    fn key_id(&self) -> String {
+        self.key_id.clone()
+    }
+
+    fn wrap(&self, data_key: &[u8]) -> BridgeResult<Vec<u8>> {
+        Ok(seal(&self.cipher, DATA_KEY_AAD, data_key))
+    }
+
+    fn unwrap(&self, wrapped: &[u8]) -> BridgeResult<Vec<u8>> {
+        open(&self.cipher, DATA_KEY_AAD, wrapped).ok_or_else(|| {
+            BridgeError::StorageError(format!(
+                "Couldn't unwrap the storage data key with {}",
+                self.path.display()
+            ))
+        })
+    }
+}
+
+/// The data key of a db, wrapped by the key encryption key.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StorageEncryptionRecord {
+    /// `StorageKeyProvider::key_id` of the key encryption key
+    pub key_id: String,
+    pub wrapped_data_key: Vec<u8>,
+    pub created_at_ms: u64,
+}
+
+/// Seals the values of the tables of a db with its data key.
+pub struct StorageCipher {
+    cipher: Aes256Gcm,
+}
+
+impl std::fmt::Debug for StorageCipher {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("StorageCipher")
+    }
+}
+
+impl StorageCipher {
+    /// Returns a cipher with a new data key, and the record of the key wrapped by
+    /// `key_provider`.
+    pub fn generate(
+        key_provider: &dyn StorageKeyProvider,
+    ) -> BridgeResult<(Self, StorageEncryptionRecord)> {
+        let mut data_key = [0u8; KEY_LENGTH];
+        rand::thread_rng().fill_bytes(&mut data_key);
+        let record = StorageEncryptionRecord {
+            key_id: key_provider.key_id(),
+            wrapped_data_key: key_provider.wrap(&data_key)?,
+            created_at_ms: now_ms(),
+        };
+        Ok((
+            Self {
+                cipher: new_cipher(&data_key)?,
+            },
+            record,
+        ))
+    }
+
+    /// Returns the cipher of the data key in `record`.
+    pub fn unwrap(
+        record: &StorageEncryptionRecord,
+        key_provider: &dyn StorageKeyProvider,
+    ) -> BridgeResult<Self> {
+        if record.key_id != key_provider.key_id() {
+            return Err(BridgeError::StorageError(format!(
+                "Storage is encrypted with key {}, but the configured key is {}",
+                record.key_id,
+                key_provider.key_id()
+            )));
+        }
+        let data_key = key_provider.unwrap(&record.wrapped_data_key)?;
+        Ok(Self {
+            cipher: new_cipher(&data_key)?,
+        })
+    }
+}
+
+fn value_aad(cf: &str, key: &[u8]) -> Vec<u8> {
+    // The table name is prefixed with its length so that it can't run into the key
+    [&(cf.len() as u32).to_be_bytes()[..], cf.as_bytes(), key].concat()
+}
+
+impl ValueCodec for StorageCipher {
+    fn encode(&self, cf: &str, key: &[u8], value: Vec<u8>) -> Result<Vec<u8>, TypedStoreError> {
+        Ok([
+            vec![SEALED_VALUE_VERSION],
+            seal(&self.cipher, &value_aad(cf, key), &value),
+        ]
+        .concat())
+    }
+
+    fn decode(&self, cf: &str, key: &[u8], value: &[u8]) -> Result<Vec<u8>, TypedStoreError> {
+        match value.split_first() {
+            Some((&SEALED_VALUE_VERSION, sealed)) => {
+                open(&self.cipher, &value_aad(cf, key), sealed).ok_or_else(|| {
+                    TypedStoreError::SerializationError(format!(
+                        "Couldn't decrypt an entry of {}",
+                        cf
+                    ))
+                })
+            }
+            _ => Err(TypedStoreError::SerializationError(format!(
+                "Entry of {} is not encrypted",
+                cf
+            ))),
+        }
+    }
+}
+
+/// Codec of a db being encrypted in place: values are sealed when written, and the values
+/// read that are not sealed with the data key are taken as plaintext.
+#[derive(Debug)]
+pub struct PartlySealed {
+    cipher: StorageCipher,
+    plaintext_reads: AtomicU64,
+}
+
+impl PartlySealed {
+    pub fn new(cipher: StorageCipher) -> Self {
+        Self {
+            cipher,
+            plaintext_reads: AtomicU64::new(0),
+        }
+    }
+
+    /// Number of values read as plaintext so far.
+    pub fn plaintext_reads(&self) -> u64 {
+        self.plaintext_reads.load(Ordering::Relaxed)
+    }
+}
+
+impl ValueCodec for PartlySealed {
+    fn encode(&self, cf: &str, key: &[u8], value: Vec<u8>) -> Result<Vec<u8>, TypedStoreError> {
+        self.cipher.encode(cf, key, value)
+    }
+
+    fn decode(&self, cf: &str, key: &[u8], value: &[u8]) -> Result<Vec<u8>, TypedStoreError> {
+        self.cipher.decode(cf, key, value).or_else(|_| {
+            self.plaintext_reads.fetch_add(1, Ordering::Relaxed);
+            Ok(value.to_vec())
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn provider(key: [u8; KEY_LENGTH]) -> FileStorageKeyProvider {
+        FileStorageKeyProvider::new(Path::new("storage.key"), &key).unwrap()
+    }
+
+    #[test]
+    fn test_storage_cipher() {
+        let (cipher, record) = StorageCipher::generate(&provider([1; KEY_LENGTH])).unwrap();
+        let sealed = cipher
+            .encode("pending_actions", b"key", b"value".to_vec())
+            .unwrap();
+        assert!(!sealed.windows(5).any(|window| window == b"value"));
+        assert_eq!(
+            cipher.decode("pending_actions", b"key", &sealed).unwrap(),
+            b"value".to_vec()
+        );
+        // Values are bound to their table and key
+        assert!(cipher.decode("pending_actions", b"key2", &sealed).is_err());
+        assert!(cipher.decode("dropped_actions", b"key", &sealed).is_err());
+        // Plaintext values are rejected
+        assert!(cipher.decode("pending_actions", b"key", b"value").is_err());
+
+        // The unwrapped data key decrypts the values
+        let cipher = StorageCipher::unwrap(&record, &provider([1; KEY_LENGTH])).unwrap();
+        assert_eq!(
+            cipher.decode("pending_actions", b"key", &sealed).unwrap(),
+            b"value".to_vec()
+        );
+    }
+
+    #[test]
+    fn test_partly_sealed() {
+        let (cipher, _) = StorageCipher::generate(&provider([1; KEY_LENGTH])).unwrap();
+        let codec = PartlySealed::new(cipher);
+        let sealed = codec
+            .encode("pending_actions", b"key", b"value".to_vec())
+            .unwrap();
+        assert_ne!(sealed, b"value".to_vec());
+        assert_eq!(
+            codec.decode("pending_actions", b"key", &sealed).unwrap(),
+            b"value".to_vec()
+        );
+        assert_eq!(codec.plaintext_reads(), 0);
+        assert_eq!(
+            codec.decode("pending_actions", b"key", b"value").unwrap(),
+            b"value".to_vec()
+        );
+        assert_eq!(codec.plaintext_reads(), 1);
+    }
+
+    #[test]
+    fn test_storage_cipher_with_wrong_key() {
+        let (_, record) = StorageCipher::generate(&provider([1; KEY_LENGTH])).unwrap();
+        let err = StorageCipher::unwrap(&record, &provider([2; KEY_LENGTH])).unwrap_err();
+        assert!(format!("{:?}", err).contains("the configured key is"));
+
+        // A key with the same id that doesn't unwrap the data key
+        let mut record = record;
+        record.key_id = provider([2; KEY_LENGTH]).key_id();
+        let err = StorageCipher::unwrap(&record, &provider([2; KEY_LENGTH])).unwrap_err();
+        assert!(format!("{:?}", err).contains("Couldn't unwrap"));
+    }
+
+    #[test]
+    fn test_read_storage_key() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("storage.key");
+        std::fs::write(&path, format!("{}\n", Base64::encode([7u8; KEY_LENGTH]))).unwrap();
+        let key_provider = FileStorageKeyProvider::read(&path).unwrap();
+        assert_eq!(key_provider.key_id(), provider([7; KEY_LENGTH]).key_id());
+
+        std::fs::write(&path, Base64::encode([7u8; 16])).unwrap();
+        assert!(FileStorageKeyProvider::read(&path).is_err());
+        assert!(FileStorageKeyProvider::read(&dir.path().join("missing.key")).is_err());
+    }
+}
@@ -0,0 +1,592 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Maintenance of the client storage: manual compactions of its large tables, and
+//! backups as rocksdb checkpoints, on a schedule and through `/admin/storage/compact` and
+//! `/admin/storage/backup`.
+//!
+//! Maintenance never runs while the node is starting up, i.e. until it's ready and the
+//! pending actions left over from the last run are recovered, and only one compaction or
+//! backup runs at a time. Tables are compacted one after the other, each waiting as long
+//! as the previous one took, so that foreground writes get at least half of the disk.
+//! Backups are written to `backup-<timestamp>` directories, and the oldest ones beyond
+//! `retain-backups` are deleted after each backup.
+
+use crate::api_types::{CompactedTable, StorageBackup, StorageCompaction};
+use crate::config::StorageMaintenanceConfig;
+use crate::error::{BridgeError, BridgeResult};
+use crate::metrics::BridgeMetrics;
+use crate::storage::BridgeOrchestratorTables;
+use crate::timestamps::now_ms;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::sync::{Mutex, MutexGuard};
+use tracing::{info, warn};
+
+pub const DEFAULT_COMPACTION_MIN_TABLE_BYTES: u64 = 64 << 20;
+pub const DEFAULT_RETAINED_BACKUPS: usize = 3;
+
+// Schedules have a resolution of a minute
+const MINUTE_MS: u64 = 60_000;
+const SCHEDULE_CHECK_INTERVAL: Duration = Duration::from_secs(60);
+const BACKUP_PREFIX: &str = "backup-";
+// Suffix of a backup being written, renamed once it's complete
+const PARTIAL_BACKUP_SUFFIX: &str = ".partial";
+
+/// A cron expression in UTC with the minute, hour, day of month, month and day of week
+/// fields. Fields are `*`, values, ranges `a-b`, steps `*/n` or `a-b/n`, and lists of
+/// those separated by commas. Sunday is 0 or 7. Like cron, a time matches when both day
+/// fields match if one of them is `*`, and when either does otherwise.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MaintenanceSchedule {
+    // Bit n is set when the value n matches
+    minutes: u64,
+    hours: u64,
+    days: u64,
+    months: u64,
+    weekdays: u64,
+    any_day: bool,
+    any_weekday: bool,
+}
+
+impl FromStr for MaintenanceSchedule {
+    type Err = String;
+
+    fn from_str(schedule: &str) -> Result<Self, Self::Err> {
+        let fields = schedule.split_whitespace().collect::<Vec<_>>();
+        let [minutes, hours, days, months, weekdays] = fields[..] else {
+            return Err(format!(
+                "{:?} doesn't have the 5 fields minute, hour, day of month, month and day of week",
+                schedule
+            ));
+        };
+        let mut weekdays_mask = parse_field(weekdays, 0, 7)?;
+        if weekdays_mask & (1 << 7) != 0 {
+            weekdays_mask = (weekdays_mask | 1) & !(1 << 7);
+        }
+        Ok(Self {
+            minutes: parse_field(minutes, 0, 59)?,
+            hours: parse_field(hours, 0, 23)?,
+            days: parse_field(days, 1, 31)?,
+            months: parse_field(months, 1, 12)?,
+            weekdays: weekdays_mask,
+            any_day: days.starts_with('*'),
+            any_weekday: weekdays.starts_with('*'),
+        })
+    }
+}
+
+fn parse_field(field: &str, min: u64, max: u64) -> Result<u64, String> {
+    let parse = |value: &str| {
+        value
+            .parse::<u64>()
+            .ok()
+            .filter(|value| (min..=max).contains(value))
+            .ok_or_else(|| format!("{:?} is not a value from {} to {}", value, min, max))
+    };
+    let mut mask = 0;
+    for part in field.split(',') {
+        let (range, step) = match part.split_once('/') {
+            Some((range, step)) => (
+                range,
+                Some(
+                    step.parse::<usize>()
+                        .ok()
+                        .filter(|step| *step > 0)
+                        .ok_or_else(|| format!("{:?} is not a positive step", step))?,
+                ),
+            ),
+            None => (part, None),
+        };
+        let (start, end) = match range.split_once('-') {
+            _ if range == "*" => (min, max),
+            Some((start, end)) => (parse(start)?, parse(end)?),
+            // `a/n` runs from `a` to the end of the range
+            None if step.is_some() => (parse(range)?, max),
+            None => (parse(range)?, parse(range)?),
+        };
+        if start > end {
+            return Err(format!("{:?} is an empty range", range));
+        }
+        for value in (start..=end).step_by(step.unwrap_or(1)) {
+            mask |= 1 << value;
+        }
+    }
+    Ok(mask)
+}
+
+impl MaintenanceSchedule {
+    /// Whether the minute of `timestamp_ms` matches the schedule.
+    pub fn matches(&self, timestamp_ms: u64) -> bool {
+        let minutes = timestamp_ms / MINUTE_MS;
+        let days = minutes / (24 * 60);
+        let (month, day) = month_and_day(days);
+        // 1970-01-01 was a Thursday
+        let weekday = (days + 4) % 7;
+        let day_matches = self.days & (1 << day) != 0;
+        let weekday_matches = self.weekdays & (1 << weekday) != 0;
+        self.minutes & (1 << (minutes % 60)) != 0
+            && self.hours & (1 << (minutes / 60 % 24)) != 0
+            && self.months & (1 << month) != 0
+            && if self.any_day || self.any_weekday {
+                day_matches && weekday_matches
+            } else {
+                day_matches || weekday_matches
+            }
+    }
+}
+
+// Month and day of month of the day `days` after 1970-01-01, in the proleptic Gregorian
+// calendar
+fn month_and_day(days: u64) -> (u64, u64) {
+    // Days since 0000-03-01, so that leap days end the years
+    let days = days + 719_468;
+    let day_of_era = days % 146_097;
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let shifted_month = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * shifted_month + 2) / 5 + 1;
+    let month = if shifted_month < 10 {
+        shifted_month + 3
+    } else {
+        shifted_month - 9
+    };
+    (month, day)
+}
+
+pub struct StorageMaintenance {
+    store: Arc<BridgeOrchestratorTables>,
+    compaction_schedule: Option<MaintenanceSchedule>,
+    backup_schedule: Option<MaintenanceSchedule>,
+    compaction_min_table_bytes: u64,
+    backup_dir: Option<PathBuf>,
+    retain_backups: usize,
+    // Held by the compaction or backup that runs
+    running: Mutex<()>,
+    metrics: Arc<BridgeMetrics>,
+}
+
+impl StorageMaintenance {
+    pub fn new(
+        store: Arc<BridgeOrchestratorTables>,
+        config: &StorageMaintenanceConfig,
+        metrics: Arc<BridgeMetrics>,
+    ) -> anyhow::Result<Self> {
+        let parse = |schedule: &Option<String>| {
+            schedule
+                .as_deref()
+                .map(MaintenanceSchedule::from_str)
+                .transpose()
+                .map_err(|e| anyhow::anyhow!("Invalid storage maintenance schedule: {}", e))
+        };
+        Ok(Self {
+            store,
+            compaction_schedule: parse(&config.compaction_schedule)?,
+            backup_schedule: parse(&config.backup_schedule)?,
+            compaction_min_table_bytes: config
+                .compaction_min_table_bytes
+                .unwrap_or(DEFAULT_COMPACTION_MIN_TABLE_BYTES),
+            backup_dir: config.backup_dir.clone(),
+            retain_backups: config
+                .retain_backups
+                .unwrap_or(DEFAULT_RETAINED_BACKUPS)
+                .max(1),
+            running: Mutex::new(()),
+            metrics,
+        })
+    }
+
+    // Refuses to run while the node starts up, or while another compaction or backup runs
+    fn start(&self) -> BridgeResult<MutexGuard<'_, ()>> {
+        if !self.metrics.readiness.state().ready || self.metrics.recovery_pending_total.get() > 0 {
+            return Err(BridgeError::ServerBusy(
+                "storage maintenance doesn't run while the node starts up".to_string(),
+            ));
+        }
+        self.running.try_lock().map_err(|_| {
+            BridgeError::ServerBusy("storage maintenance is already running".to_string())
+        })
+    }
+
+    fn record<T>(&self, operation: &str, start: Instant, result: &BridgeResult<T>) {
+        self.metrics
+            .storage_maintenance_duration_seconds
+            .with_label_values(&[operation])
+            .observe(start.elapsed().as_secs_f64());
+        if result.is_err() {
+            self.metrics
+                .storage_maintenance_failures
+                .with_label_values(&[operation])
+                .inc();
+        }
+    }
+
+    /// Compacts the tables of at least `compaction-min-table-bytes`, one after the other.
+    pub async fn compact(&self) -> BridgeResult<StorageCompaction> {
+        let _running = self.start()?;
+        let start = Instant::now();
+        let result = self.compact_tables().await;
+        self.record("compaction", start, &result);
+        result.map(|compaction| StorageCompaction {
+            duration_ms: start.elapsed().as_millis() as u64,
+            ..compaction
+        })
+    }
+
+    async fn compact_tables(&self) -> BridgeResult<StorageCompaction> {
+        let mut compaction = StorageCompaction::default();
+        let mut pause = Duration::ZERO;
+        for (db, table) in self.store.table_names() {
+            let bytes_before = self.store.table_size(db, &table)?;
+            if bytes_before < self.compaction_min_table_bytes {
+                self.metrics
+                    .storage_table_size_bytes
+                    .with_label_values(&[db, table.as_str()])
+                    .set(bytes_before as i64);
+                compaction.skipped.push(format!("{}.{}", db, table));
+                continue;
+            }
+            // Foreground writes get the disk back between two compactions
+            tokio::time::sleep(pause).await;
+            let start = Instant::now();
+            let store = self.store.clone();
+            let compacted = table.clone();
+            tokio::task::spawn_blocking(move || store.compact_table(db, &compacted))
+                .await
+                .map_err(|e| {
+                    BridgeError::StorageError(format!(
+                        "Compaction of {} in {} failed: {:?}",
+                        table, db, e
+                    ))
+                })??;
+            pause = start.elapsed();
+            let bytes_after = self.store.table_size(db, &table)?;
+            self.metrics
+                .storage_table_size_bytes
+                .with_label_values(&[db, table.as_str()])
+                .set(bytes_after as i64);
+            self.metrics
+                .storage_compaction_reclaimed_bytes
+                .inc_by(bytes_before.saturating_sub(bytes_after));
+            info!(
+                "Compacted {} in {} db from {} to {} bytes in {:?}",
+                table, db, bytes_before, bytes_after, pause
+            );
+            compaction.compacted.push(CompactedTable {
+                db: db.to_string(),
+                table,
+                bytes_before,
+                bytes_after,
+                duration_ms: pause.as_millis() as u64,
+            });
+        }
+        Ok(compaction)
+    }
+
+    /// Writes a checkpoint of the storage to a new directory in `backup-dir`, and deletes
+    /// the oldest backups beyond `retain-backups`.
+    pub async fn backup(&self) -> BridgeResult<StorageBackup> {
+        let Some(backup_dir) = &self.backup_dir else {
+            return Err(BridgeError::StorageError(
+                "No `backup-dir` is configured".to_string(),
+            ));
+        };
+        let _running = self.start()?;
+        let start = Instant::now();
+        let result = self.write_backup(backup_dir).await;
+        self.record("backup", start, &result);
+        result.map(|backup| StorageBackup {
+            duration_ms: start.elapsed().as_millis() as u64,
+            ..backup
+        })
+    }
+
+    async fn write_backup(&self, backup_dir: &Path) -> BridgeResult<StorageBackup> {
+        let backups = list_backups(backup_dir)?;
+        // Ids increase even if the clock goes back
+        let id = backups.last().map_or(0, |(last, _)| last + 1).max(now_ms());
+        let path = backup_dir.join(format!("{}{}", BACKUP_PREFIX, id));
+        let partial = backup_dir.join(format!("{}{}{}", BACKUP_PREFIX, id, PARTIAL_BACKUP_SUFFIX));
+        let store = self.store.clone();
+        let checkpoint = partial.clone();
+        tokio::task::spawn_blocking(move || store.checkpoint(&checkpoint))
+            .await
+            .map_err(|e| BridgeError::StorageError(format!("Backup failed: {:?}", e)))??;
+        std::fs::rename(&partial, &path).map_err(|e| {
+            BridgeError::StorageError(format!("Couldn't rename {:?}: {:?}", partial, e))
+        })?;
+        let bytes = dir_size(&path)?;
+        self.metrics.storage_backup_size_bytes.set(bytes as i64);
+        info!("Backed up the storage to {:?}, {} bytes", path, bytes);
+
+        let backups = list_backups(backup_dir)?;
+        let mut deleted = vec![];
+        for (_, old) in &backups[..backups.len().saturating_sub(self.retain_backups)] {
+            std::fs::remove_dir_all(old).map_err(|e| {
+                BridgeError::StorageError(format!("Couldn't delete backup {:?}: {:?}", old, e))
+            })?;
+            info!("Deleted backup {:?}", old);
+            deleted.push(old.display().to_string());
+        }
+        Ok(StorageBackup {
+            path: path.display().to_string(),
+            bytes,
+            deleted,
+            duration_ms: 0,
+        })
+    }
+
+    /// Compacts the tables and backs up the storage in the minutes their schedules match.
+    /// A run that is refused, e.g. because the node is starting up, is skipped.
+    pub async fn run(self: Arc<Self>) {
+        let mut interval = tokio::time::interval(SCHEDULE_CHECK_INTERVAL);
+        interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+        let mut last_minute = now_ms() / MINUTE_MS;
+        loop {
+            interval.tick().await;
+            // Minutes missed while a run took longer than a minute still count
+            let minute = now_ms() / MINUTE_MS;
+            let due = |schedule: &Option<MaintenanceSchedule>| {
+                schedule.as_ref().is_some_and(|schedule| {
+                    (last_minute + 1..=minute).any(|minute| schedule.matches(minute * MINUTE_MS))
+                })
+            };
+            let (compaction_due, backup_due) =
+                (due(&self.compaction_schedule), due(&self.backup_schedule));
+            last_minute = last_minute.max(minute);
+            if compaction_due {
+                if let Err(e) = self.compact().await {
+                    warn!("Scheduled compaction of the storage failed: {:?}", e);
+                }
+            }
+            if backup_due {
+                if let Err(e) = self.backup().await {
+                    warn!("Scheduled backup of the storage failed: {:?}", e);
+                }
+            }
+        }
+    }
+}
+
+// Complete backups in `backup_dir` by id, the oldest first. Backups left partial by an
+// interrupted run are deleted.
+fn list_backups(backup_dir: &Path) -> BridgeResult<Vec<(u64, PathBuf)>> {
+    let io_error =
+        |e: std::io::Error| BridgeError::StorageError(format!("{:?}: {:?}", backup_dir, e));
+    std::fs::create_dir_all(backup_dir).map_err(io_error)?;
+    let mut backups = vec![];
+    for entry in std::fs::read_dir(backup_dir).map_err(io_error)? {
+        let path = entry.map_err(io_error)?.path();
+        let Some(name) = path.file_name().and_then(|name| name.to_str()) else {
+            continue;
+        };
+        let Some(id) = name.strip_prefix(BACKUP_PREFIX) else {
+            continue;
+        };
+        if id.ends_with(PARTIAL_BACKUP_SUFFIX) {
+            warn!("Deleting partial backup {:?}", path);
+            std::fs::remove_dir_all(&path).map_err(io_error)?;
+        } else if let Ok(id) = id.parse::<u64>() {
+            backups.push((id, path));
+        }
+    }
+    backups.sort();
+    Ok(backups)
+}
+
+fn dir_size(path: &Path) -> BridgeResult<u64> {
+    let io_error = |e: std::io::Error| BridgeError::StorageError(format!("{:?}: {:?}", path, e));
+    let mut size = 0;
+    for entry in std::fs::read_dir(path).map_err(io_error)? {
+        let entry = entry.map_err(io_error)?;
+        let metadata = entry.metadata().map_err(io_error)?;
+        size += if metadata.is_dir() {
+            dir_size(&entry.path())?
+        } else {
+            metadata.len()
+        };
+    }
+    Ok(size)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::{BridgePrimaryTables, PRIMARY_DB};
+    use crate::test_utils::get_test_sui_to_eth_bridge_action;
+    use typed_store::rocks::MetricConf;
+
+    // 2024-02-29T13:45:00Z, a Thursday
+    const LEAP_DAY_MS: u64 = 1_709_214_300_000;
+
+    fn schedule(schedule: &str) -> MaintenanceSchedule {
+        MaintenanceSchedule::from_str(schedule).unwrap()
+    }
+
+    #[test]
+    fn test_maintenance_schedule() {
+        assert_eq!(month_and_day(0), (1, 1));
+        assert_eq!(month_and_day(LEAP_DAY_MS / MINUTE_MS / (24 * 60)), (2, 29));
+
+        assert!(schedule("* * * * *").matches(LEAP_DAY_MS));
+        assert!(schedule("45 13 29 2 4").matches(LEAP_DAY_MS));
+        assert!(schedule("*/15 12-14 * * *").matches(LEAP_DAY_MS));
+        assert!(schedule("0,45 13 * * 1-5").matches(LEAP_DAY_MS));
+        assert!(!schedule("44 13 * * *").matches(LEAP_DAY_MS));
+        assert!(!schedule("45 13 * * 0,6").matches(LEAP_DAY_MS));
+        assert!(!schedule("45 13 * 3 *").matches(LEAP_DAY_MS));
+        // The seconds of the time don't matter
+        assert!(schedule("45 13 * * *").matches(LEAP_DAY_MS + 59_999));
+        // Sunday is 0 and 7, 2024-03-03 was one
+        let sunday = LEAP_DAY_MS + 3 * 24 * 60 * MINUTE_MS;
+        assert!(schedule("45 13 * * 7").matches(sunday));
+        assert!(schedule("45 13 * * 0").matches(sunday));
+        // Either restricted day field matches, like cron
+        assert!(schedule("45 13 1 * 0").matches(sunday));
+        assert!(!schedule("45 13 1 * *").matches(sunday));
+
+        for invalid in [
+            "* * * *",
+            "* * * * * *",
+            "60 * * * *",
+            "* 24 * * *",
+            "* * 0 * *",
+            "* * * 13 *",
+            "* * * * 8",
+            "*/0 * * * *",
+            "5-1 * * * *",
+            "a * * * *",
+        ] {
+            assert!(
+                MaintenanceSchedule::from_str(invalid).is_err(),
+                "{} is valid",
+                invalid
+            );
+        }
+    }
+
+    fn maintenance(
+        store: Arc<BridgeOrchestratorTables>,
+        backup_dir: PathBuf,
+        retain_backups: usize,
+    ) -> (StorageMaintenance, Arc<BridgeMetrics>) {
+        let metrics = Arc::new(BridgeMetrics::for_testing());
+        let config = StorageMaintenanceConfig {
+            compaction_schedule: None,
+            compaction_min_table_bytes: Some(0),
+            backup_schedule: None,
+            backup_dir: Some(backup_dir),
+            retain_backups: Some(retain_backups),
+        };
+        (
+            StorageMaintenance::new(store, &config, metrics.clone()).unwrap(),
+            metrics,
+        )
+    }
+
+    fn contents(path: PathBuf) -> Vec<std::collections::BTreeMap<String, String>> {
+        let tables =
+            BridgePrimaryTables::get_read_only_handle(path, None, None, MetricConf::default());
+        BridgePrimaryTables::describe_tables()
+            .into_keys()
+            .map(|table| tables.dump(&table, u16::MAX, 0).unwrap())
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn test_storage_backup_and_rotation() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let db_path = temp_dir.path().join("db");
+        let backup_dir = temp_dir.path().join("backups");
+        let store = BridgeOrchestratorTables::new(&db_path);
+        let actions = (0..10)
+            .map(|nonce| {
+                get_test_sui_to_eth_bridge_action(None, None, Some(nonce), None, None, None, None)
+            })
+            .collect::<Vec<_>>();
+        store.insert_pending_actions(&actions).unwrap();
+        store.mark_actions_executed(&actions[..5]).unwrap();
+        let (maintenance, metrics) = maintenance(store.clone(), backup_dir.clone(), 2);
+
+        // Nothing runs until the node is ready
+        assert!(matches!(
+            maintenance.backup().await,
+            Err(BridgeError::ServerBusy(_))
+        ));
+        metrics.readiness.without_client();
+        metrics.readiness.set_server_bound();
+
+        let first = maintenance.backup().await.unwrap();
+        assert!(first.bytes > 0);
+        assert!(first.deleted.is_empty());
+        assert_eq!(metrics.storage_backup_size_bytes.get(), first.bytes as i64);
+        // The backup opens read-only with the contents of the db
+        let first_path = PathBuf::from(&first.path);
+        let backed_up = contents(first_path.join(PRIMARY_DB));
+        assert_eq!(backed_up, contents(db_path.clone()));
+        // 5 pending and 5 executed transfers at least
+        assert!(backed_up.iter().map(|table| table.len()).sum::<usize>() >= 10);
+
+        // Later writes are not in the backup
+        store
+            .remove_pending_actions(&[actions[5].digest()])
+            .unwrap();
+        assert_eq!(contents(first_path.join(PRIMARY_DB)), backed_up);
+
+        // The oldest backups beyond the retained ones are deleted
+        let second = maintenance.backup().await.unwrap();
+        assert!(second.deleted.is_empty());
+        let third = maintenance.backup().await.unwrap();
+        assert_eq!(third.deleted, vec![first.path.clone()]);
+        assert!(!first_path.exists());
+        let remaining = list_backups(&backup_dir)
+            .unwrap()
+            .into_iter()
+            .map(|(_, path)| path.display().to_string())
+            .collect::<Vec<_>>();
+        assert_eq!(remaining, vec![second.path, third.path]);
+        assert_eq!(
+            metrics
+                .storage_maintenance_duration_seconds
+                .with_label_values(&["backup"])
+                .get_sample_count(),
+            3
+        );
+    }
+
+    #[tokio::test]
+    async fn test_storage_compaction() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let store = BridgeOrchestratorTables::new(&temp_dir.path().join("db"));
+        let actions = (0..100)
+            .map(|nonce| {
+                get_test_sui_to_eth_bridge_action(None, None, Some(nonce), None, None, None, None)
+            })
+            .collect::<Vec<_>>();
+        store.insert_pending_actions(&actions).unwrap();
+        let (maintenance, metrics) = maintenance(store.clone(), temp_dir.path().join("backups"), 1);
+        metrics.readiness.without_client();
+        metrics.readiness.set_server_bound();
+
+        let compaction = maintenance.compact().await.unwrap();
+        assert_eq!(
+            compaction.compacted.len() + compaction.skipped.len(),
+            store.table_names().len()
+        );
+        assert!(compaction
+            .compacted
+            .iter()
+            .any(|table| table.table == "pending_actions" && table.bytes_after > 0));
+        assert_eq!(store.get_all_pending_actions().len(), 100);
+
+        // One maintenance runs at a time
+        let _running = maintenance.running.try_lock().unwrap();
+        assert!(matches!(
+            maintenance.compact().await,
+            Err(BridgeError::ServerBusy(_))
+        ));
+    }
+}
@@ -5,12 +5,18 @@ use anyhow::anyhow;
 use async_trait::async_trait;
 use core::panic;
 use fastcrypto::traits::ToFromBytes;
+use jsonrpsee::core::client::ClientT;
+use jsonrpsee::rpc_params;
+use move_core_types::language_storage::StructTag;
 use serde::de::DeserializeOwned;
 use std::collections::HashMap;
+use std::fmt::Display;
+use std::future::Future;
 use std::str::from_utf8;
-use std::time::Duration;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
 use sui_json_rpc_api::BridgeReadApiClient;
-use sui_json_rpc_types::DevInspectResults;
+use sui_json_rpc_types::{CheckpointId, DevInspectResults};
 use sui_json_rpc_types::{EventFilter, Page, SuiEvent};
 use sui_json_rpc_types::{
     EventPage, SuiObjectDataOptions, SuiTransactionBlockResponse,
@@ -46,16 +52,114 @@ use sui_types::{
 use tokio::sync::OnceCell;
 use tracing::{error, warn};
 
+use crate::api_types::LimitWindow;
 use crate::crypto::BridgeAuthorityPublicKey;
-use crate::error::{BridgeError, BridgeResult};
+use crate::error::{BridgeError, BridgeResult, ClassifyError};
 use crate::events::SuiBridgeEvent;
+use crate::limit_window::limit_window;
+use crate::metrics::BridgeMetrics;
+use crate::notional::BridgeRoute;
 use crate::retry_with_max_elapsed_time;
+use crate::sui_rpc_retry::{sui_rpc_error, SuiRpcRetry};
+use crate::timestamps::now_ms;
 use crate::types::BridgeActionStatus;
 use crate::types::ParsedTokenTransferMessage;
 use crate::types::{BridgeAction, BridgeAuthority, BridgeCommittee};
 
+// How long the objects read through `SuiObjectCache` are reused
+pub const BRIDGE_SUMMARY_CACHE_TTL: Duration = Duration::from_secs(5);
+pub const BRIDGE_COMMITTEE_CACHE_TTL: Duration = Duration::from_secs(60);
+pub const TOKEN_ID_MAP_CACHE_TTL: Duration = Duration::from_secs(60);
+
 pub struct SuiClient<P> {
     inner: P,
+    cache: Option<SuiObjectCache>,
+    retry: Option<SuiRpcRetry>,
+    // Per client rather than static, the bridge object differs between networks
+    bridge_object_arg: OnceCell<ObjectArg>,
+}
+
+struct CacheEntry<T> {
+    value: Option<(T, Instant)>,
+    // Bumped by every invalidation, so that a read started before an invalidation
+    // does not put a stale value back.
+    generation: u64,
+}
+
+/// An object read from Sui, reused until `ttl` passes or it is invalidated.
+struct CachedObject<T> {
+    name: &'static str,
+    ttl: Duration,
+    entry: Mutex<CacheEntry<T>>,
+}
+
+impl<T: Clone> CachedObject<T> {
+    fn new(name: &'static str, ttl: Duration) -> Self {
+        Self {
+            name,
+            ttl,
+            entry: Mutex::new(CacheEntry {
+                value: None,
+                generation: 0,
+            }),
+        }
+    }
+
+    // Returns the cached value if it has not expired, and the generation to `set` a
+    // freshly read value with.
+    fn get(&self, metrics: &BridgeMetrics) -> (Option<T>, u64) {
+        let entry = self.entry.lock().unwrap();
+        let value = entry
+            .value
+            .as_ref()
+            .filter(|(_, read_at)| read_at.elapsed() < self.ttl)
+            .map(|(value, _)| value.clone());
+        if value.is_some() {
+            metrics
+                .sui_client_cache_hits
+                .with_label_values(&[self.name])
+                .inc();
+        } else {
+            metrics
+                .sui_client_cache_misses
+                .with_label_values(&[self.name])
+                .inc();
+        }
+        (value, entry.generation)
+    }
+
+    fn set(&self, value: T, generation: u64) {
+        let mut entry = self.entry.lock().unwrap();
+        if entry.generation == generation {
+            entry.value = Some((value, Instant::now()));
+        }
+    }
+
+    fn invalidate(&self) {
+        let mut entry = self.entry.lock().unwrap();
+        entry.value = None;
+        entry.generation += 1;
+    }
+}
+
+/// Read-through cache of the bridge objects that rarely change. Entries expire after
+/// their TTL, and are invalidated early when an event changing them is observed.
+pub struct SuiObjectCache {
+    bridge_summary: CachedObject<BridgeSummary>,
+    committee: CachedObject<BridgeCommittee>,
+    token_id_map: CachedObject<HashMap<u8, TypeTag>>,
+    metrics: Arc<BridgeMetrics>,
+}
+
+impl SuiObjectCache {
+    pub fn new(metrics: Arc<BridgeMetrics>) -> Self {
+        Self {
+            bridge_summary: CachedObject::new("bridge_summary", BRIDGE_SUMMARY_CACHE_TTL),
+            committee: CachedObject::new("committee", BRIDGE_COMMITTEE_CACHE_TTL),
+            token_id_map: CachedObject::new("token_id_map", TOKEN_ID_MAP_CACHE_TTL),
+            metrics,
+        }
+    }
 }
 
 pub type SuiBridgeClient = SuiClient<SuiSdkClient>;
@@ -68,7 +172,12 @@ impl SuiBridgeClient {
             .map_err(|e| {
                 anyhow!("Can't establish connection with Sui Rpc {rpc_url}. Error: {e}")
             })?;
-        let self_ = Self { inner };
+        let self_ = Self {
+            inner,
+            cache: None,
+            retry: None,
+            bridge_object_arg: OnceCell::new(),
+        };
         self_.describe().await?;
         Ok(self_)
     }
@@ -83,7 +192,103 @@ where
     P: SuiClientInner,
 {
     pub fn new_for_testing(inner: P) -> Self {
-        Self { inner }
+        Self {
+            inner,
+            cache: None,
+            retry: None,
+            bridge_object_arg: OnceCell::new(),
+        }
+    }
+
+    /// Reads the bridge summary, committee and token config through a cache.
+    pub fn with_object_cache(mut self, metrics: Arc<BridgeMetrics>) -> Self {
+        self.cache = Some(SuiObjectCache::new(metrics));
+        self
+    }
+
+    /// Retries reads that fail with transient errors, up to `max_attempts` in total.
+    pub fn with_retry(mut self, max_attempts: u32, metrics: Arc<BridgeMetrics>) -> Self {
+        self.retry = Some(SuiRpcRetry::new(max_attempts, metrics));
+        self
+    }
+
+    async fn request<T, E, F, Fut>(&self, method: &'static str, request: F) -> Result<T, E>
+    where
+        E: ClassifyError + Display,
+        F: Fn() -> Fut,
+        Fut: Future<Output = Result<T, E>>,
+    {
+        match &self.retry {
+            Some(retry) => retry.run(method, request).await,
+            None => request().await,
+        }
+    }
+
+    async fn read_through<T, F>(
+        &self,
+        cached_object: fn(&SuiObjectCache) -> &CachedObject<T>,
+        read: F,
+    ) -> BridgeResult<T>
+    where
+        T: Clone,
+        F: Future<Output = BridgeResult<T>>,
+    {
+        let Some(cache) = &self.cache else {
+            return read.await;
+        };
+        let cached_object = cached_object(cache);
+        let (value, generation) = cached_object.get(&cache.metrics);
+        if let Some(value) = value {
+            return Ok(value);
+        }
+        let value = read.await?;
+        cached_object.set(value.clone(), generation);
+        Ok(value)
+    }
+
+    /// Drops the cached bridge summary, e.g. when the bridge is paused or unpaused.
+    pub fn invalidate_bridge_summary(&self) {
+        if let Some(cache) = &self.cache {
+            cache.bridge_summary.invalidate();
+        }
+    }
+
+    /// Drops the cached committee and the bridge summary it is read from.
+    pub fn invalidate_committee(&self) {
+        if let Some(cache) = &self.cache {
+            cache.bridge_summary.invalidate();
+            cache.committee.invalidate();
+        }
+    }
+
+    /// Drops the cached token config and the bridge summary it is read from.
+    pub fn invalidate_token_config(&self) {
+        if let Some(cache) = &self.cache {
+            cache.bridge_summary.invalidate();
+            cache.token_id_map.invalidate();
+        }
+    }
+
+    /// Drops the cached objects that `event` changes, so they are read from chain again.
+    pub fn invalidate_cache_for_event(&self, event: &SuiBridgeEvent) {
+        match event {
+            SuiBridgeEvent::EmergencyOpEvent(_) | SuiBridgeEvent::UpdateRouteLimitEvent(_) => {
+                self.invalidate_bridge_summary()
+            }
+            SuiBridgeEvent::CommitteeMemberRegistration(_)
+            | SuiBridgeEvent::CommitteeUpdateEvent(_)
+            | SuiBridgeEvent::CommitteeMemberUrlUpdateEvent(_)
+            | SuiBridgeEvent::BlocklistValidatorEvent(_) => self.invalidate_committee(),
+            SuiBridgeEvent::TokenRegistrationEvent(_)
+            | SuiBridgeEvent::NewTokenEvent(_)
+            | SuiBridgeEvent::UpdateTokenPriceEvent(_) => self.invalidate_token_config(),
+            SuiBridgeEvent::SuiToEthTokenBridgeV1(_)
+            | SuiBridgeEvent::TokenTransferApproved(_)
+            | SuiBridgeEvent::TokenTransferClaimed(_)
+            | SuiBridgeEvent::TokenTransferAlreadyApproved(_)
+            | SuiBridgeEvent::TokenTransferAlreadyClaimed(_)
+            | SuiBridgeEvent::TokenTransferLimitExceed(_) => (),
+        }
     }
 
     // TODO assert chain identifier
@@ -101,17 +306,18 @@ where
     // In general it's safe to call in the beginning of the program.
     // After the first call, the result is cached since the value should never change.
     pub async fn get_mutable_bridge_object_arg_must_succeed(&self) -> ObjectArg {
-        static ARG: OnceCell<ObjectArg> = OnceCell::const_new();
-        *ARG.get_or_init(|| async move {
-            let Ok(Ok(bridge_object_arg)) = retry_with_max_elapsed_time!(
-                self.inner.get_mutable_bridge_object_arg(),
-                Duration::from_secs(30)
-            ) else {
-                panic!("Failed to get bridge object arg after retries");
-            };
-            bridge_object_arg
-        })
-        .await
+        *self
+            .bridge_object_arg
+            .get_or_init(|| async move {
+                let Ok(Ok(bridge_object_arg)) = retry_with_max_elapsed_time!(
+                    self.inner.get_mutable_bridge_object_arg(),
+                    Duration::from_secs(30)
+                ) else {
+                    panic!("Failed to get bridge object arg after retries");
+                };
+                bridge_object_arg
+            })
+            .await
     }
 
     /// Query emitted Events that are defined in the given Move Module.
@@ -126,7 +332,12 @@ where
             package,
             module: module.clone(),
         };
-        let events = self.inner.query_events(filter.clone(), cursor).await?;
+        let events = self
+            .request("query_events", || {
+                self.inner.query_events(filter.clone(), cursor)
+            })
+            .await
+            .map_err(sui_rpc_error)?;
 
         // Safeguard check that all events are emitted from requested package and module
         assert!(events
@@ -137,6 +348,22 @@ where
         Ok(events)
     }
 
+    /// Returns the latest `limit` events of type `event_type`, newest first.
+    pub async fn query_latest_events_by_type(
+        &self,
+        event_type: StructTag,
+        limit: usize,
+    ) -> BridgeResult<Vec<SuiEvent>> {
+        let filter = EventFilter::MoveEventType(event_type);
+        let events = self
+            .request("query_latest_events", || {
+                self.inner.query_latest_events(filter.clone(), limit)
+            })
+            .await
+            .map_err(sui_rpc_error)?;
+        Ok(events.data)
+    }
+
     /// Returns BridgeAction from a Sui Transaction with transaction hash
     /// and the event index. If event is declared in an unrecognized
     /// package, return error.
@@ -145,7 +372,12 @@ where
         tx_digest: &TransactionDigest,
         event_idx: u16,
     ) -> BridgeResult<BridgeAction> {
-        let events = self.inner.get_events_by_tx_digest(*tx_digest).await?;
+        let events = self
+            .request("get_events_by_tx_digest", || {
+                self.inner.get_events_by_tx_digest(*tx_digest)
+            })
+            .await
+            .map_err(sui_rpc_error)?;
         let event = events
             .get(event_idx as usize)
             .ok_or(BridgeError::NoBridgeEventsInTxPosition)?;
@@ -160,11 +392,46 @@ where
             .ok_or(BridgeError::BridgeEventNotActionable)
     }
 
+    /// Returns the event with `event_id`, None if its transaction has fewer events.
+    pub async fn get_event_by_id(&self, event_id: EventID) -> BridgeResult<Option<SuiEvent>> {
+        let events = self
+            .request("get_events_by_tx_digest", || {
+                self.inner.get_events_by_tx_digest(event_id.tx_digest)
+            })
+            .await
+            .map_err(sui_rpc_error)?;
+        Ok(events.into_iter().nth(event_id.event_seq as usize))
+    }
+
     pub async fn get_bridge_summary(&self) -> BridgeResult<BridgeSummary> {
-        self.inner
-            .get_bridge_summary()
+        self.read_through(|cache| &cache.bridge_summary, async {
+            self.request("get_bridge_summary", || self.inner.get_bridge_summary())
+                .await
+                .map_err(|e| sui_rpc_error(e).context("Can't get bridge summary"))
+        })
+        .await
+    }
+
+    /// Reads the transfer records of the limiter, bypassing the cache since every transfer
+    /// changes them, and rebuilds the window of `route` at the time of the latest
+    /// checkpoint. See `limit_window`.
+    pub async fn get_limit_window(
+        &self,
+        route: BridgeRoute,
+        amount: Option<u64>,
+    ) -> BridgeResult<LimitWindow> {
+        let summary = self
+            .request("get_bridge_summary", || self.inner.get_bridge_summary())
             .await
-            .map_err(|e| BridgeError::InternalError(format!("Can't get bridge committee: {e}")))
+            .map_err(|e| sui_rpc_error(e).context("Can't get bridge summary"))?;
+        let chain_time_ms = self.get_latest_checkpoint_timestamp_ms().await?;
+        Ok(limit_window(
+            route,
+            &summary.limiter,
+            chain_time_ms,
+            now_ms(),
+            amount,
+        ))
     }
 
     pub async fn is_bridge_paused(&self) -> BridgeResult<bool> {
@@ -178,21 +445,24 @@ where
     }
 
     pub async fn get_token_id_map(&self) -> BridgeResult<HashMap<u8, TypeTag>> {
-        self.get_bridge_summary()
-            .await?
-            .treasury
-            .id_token_type_map
-            .into_iter()
-            .map(|(id, name)| {
-                parse_sui_type_tag(&format!("0x{name}"))
-                    .map(|name| (id, name))
-                    .map_err(|e| {
-                        BridgeError::InternalError(format!(
-                            "Failed to retrieve token id mapping: {e}, type name: {name}"
-                        ))
-                    })
-            })
-            .collect()
+        self.read_through(|cache| &cache.token_id_map, async {
+            self.get_bridge_summary()
+                .await?
+                .treasury
+                .id_token_type_map
+                .into_iter()
+                .map(|(id, name)| {
+                    parse_sui_type_tag(&format!("0x{name}"))
+                        .map(|name| (id, name))
+                        .map_err(|e| {
+                            BridgeError::InternalError(format!(
+                                "Failed to retrieve token id mapping: {e}, type name: {name}"
+                            ))
+                        })
+                })
+                .collect()
+        })
+        .await
     }
 
     pub async fn get_notional_values(&self) -> BridgeResult<HashMap<u8, u64>> {
@@ -221,11 +491,15 @@ where
     }
 
     pub async fn get_bridge_committee(&self) -> BridgeResult<BridgeCommittee> {
-        let bridge_summary =
-            self.inner.get_bridge_summary().await.map_err(|e| {
-                BridgeError::InternalError(format!("Can't get bridge committee: {e}"))
-            })?;
-        let move_type_bridge_committee = bridge_summary.committee;
+        self.read_through(
+            |cache| &cache.committee,
+            self.get_bridge_committee_uncached(),
+        )
+        .await
+    }
+
+    async fn get_bridge_committee_uncached(&self) -> BridgeResult<BridgeCommittee> {
+        let move_type_bridge_committee = self.get_bridge_summary().await?.committee;
 
         let mut authorities = vec![];
         // TODO: move this to MoveTypeBridgeCommittee
@@ -237,7 +511,13 @@ where
                 http_rest_url,
                 blocklisted,
             } = member;
-            let pubkey = BridgeAuthorityPublicKey::from_bytes(&bridge_pubkey_bytes)?;
+            let pubkey =
+                BridgeAuthorityPublicKey::from_bytes(&bridge_pubkey_bytes).map_err(|e| {
+                    BridgeError::InvalidBridgeCommittee(format!(
+                        "Invalid public key of authority {}: {:?}",
+                        sui_address, e
+                    ))
+                })?;
             let base_url = from_utf8(&http_rest_url).unwrap_or_else(|_e| {
                 warn!(
                     "Bridge authority address: {}, pubkey: {:?} has invalid http url: {:?}",
@@ -256,7 +536,24 @@ where
     }
 
     pub async fn get_chain_identifier(&self) -> BridgeResult<String> {
-        Ok(self.inner.get_chain_identifier().await?)
+        self.request("get_chain_identifier", || self.inner.get_chain_identifier())
+            .await
+            .map_err(sui_rpc_error)
+    }
+
+    /// Version of the fullnode, as it advertises it in `rpc.discover`.
+    pub async fn get_server_version(&self) -> BridgeResult<String> {
+        self.request("get_server_version", || self.inner.get_server_version())
+            .await
+            .map_err(sui_rpc_error)
+    }
+
+    pub async fn get_latest_checkpoint_timestamp_ms(&self) -> BridgeResult<u64> {
+        self.request("get_latest_checkpoint_timestamp_ms", || {
+            self.inner.get_latest_checkpoint_timestamp_ms()
+        })
+        .await
+        .map_err(sui_rpc_error)
     }
 
     pub async fn get_reference_gas_price_until_success(&self) -> u64 {
@@ -280,6 +577,87 @@ where
         self.inner.execute_transaction_block_with_effects(tx).await
     }
 
+    /// Returns the effects and events of an executed transaction.
+    pub async fn get_transaction_block_with_effects(
+        &self,
+        tx_digest: TransactionDigest,
+    ) -> BridgeResult<SuiTransactionBlockResponse> {
+        self.inner
+            .get_transaction_block_with_effects(tx_digest)
+            .await
+    }
+
+    pub async fn get_token_transfer_action_onchain_status(
+        &self,
+        bridge_object_arg: ObjectArg,
+        source_chain_id: u8,
+        seq_number: u64,
+    ) -> BridgeResult<BridgeActionStatus> {
+        self.request("get_token_transfer_action_onchain_status", || {
+            self.inner.get_token_transfer_action_onchain_status(
+                bridge_object_arg,
+                source_chain_id,
+                seq_number,
+            )
+        })
+        .await
+    }
+
+    /// Statuses of the token transfers with the (source chain id, seq number) `keys`, in
+    /// the same order, queried `batch_size` at a time. The keys of a batch that fails as a
+    /// whole, e.g. because one of them aborts, are queried one by one, so that each key
+    /// gets its own result.
+    pub async fn get_token_transfer_action_onchain_statuses(
+        &self,
+        keys: &[(u8, u64)],
+        batch_size: usize,
+    ) -> Vec<BridgeResult<BridgeActionStatus>> {
+        let bridge_object_arg = self.get_mutable_bridge_object_arg_must_succeed().await;
+        let mut statuses = Vec::with_capacity(keys.len());
+        for batch in keys.chunks(batch_size.max(1)) {
+            if let [(source_chain_id, seq_number)] = batch {
+                statuses.push(
+                    self.get_token_transfer_action_onchain_status(
+                        bridge_object_arg,
+                        *source_chain_id,
+                        *seq_number,
+                    )
+                    .await,
+                );
+                continue;
+            }
+            match self
+                .request("get_token_transfer_action_onchain_statuses", || {
+                    self.inner
+                        .get_token_transfer_action_onchain_statuses(bridge_object_arg, batch)
+                })
+                .await
+            {
+                Ok(batch_statuses) if batch_statuses.len() == batch.len() => {
+                    statuses.extend(batch_statuses)
+                }
+                result => {
+                    warn!(
+                        "Failed to get a batch of {} token transfer statuses, querying them one by one: {:?}",
+                        batch.len(),
+                        result.map(|batch_statuses| batch_statuses.len())
+                    );
+                    for (source_chain_id, seq_number) in batch {
+                        statuses.push(
+                            self.get_token_transfer_action_onchain_status(
+                                bridge_object_arg,
+                                *source_chain_id,
+                                *seq_number,
+                            )
+                            .await,
+                        );
+                    }
+                }
+            }
+        }
+        statuses
+    }
+
     // TODO: this function is very slow (seconds) in tests, we need to optimize it
     pub async fn get_token_transfer_action_onchain_status_until_success(
         &self,
@@ -333,6 +711,25 @@ where
         }
     }
 
+    /// Signatures of the certificate that approved the token transfer on Sui, None if it's
+    /// not approved.
+    pub async fn get_token_transfer_action_onchain_signatures(
+        &self,
+        source_chain_id: u8,
+        seq_number: u64,
+    ) -> BridgeResult<Option<Vec<Vec<u8>>>> {
+        let bridge_object_arg = self.get_mutable_bridge_object_arg_must_succeed().await;
+        self.request("get_token_transfer_action_onchain_signatures", || {
+            self.inner.get_token_transfer_action_onchain_signatures(
+                bridge_object_arg,
+                source_chain_id,
+                seq_number,
+            )
+        })
+        .await
+        .map_err(sui_rpc_error)
+    }
+
     pub async fn get_parsed_token_transfer_message(
         &self,
         source_chain_id: u8,
@@ -340,9 +737,15 @@ where
     ) -> BridgeResult<Option<ParsedTokenTransferMessage>> {
         let bridge_object_arg = self.get_mutable_bridge_object_arg_must_succeed().await;
         let message = self
-            .inner
-            .get_parsed_token_transfer_message(bridge_object_arg, source_chain_id, seq_number)
-            .await?;
+            .request("get_parsed_token_transfer_message", || {
+                self.inner.get_parsed_token_transfer_message(
+                    bridge_object_arg,
+                    source_chain_id,
+                    seq_number,
+                )
+            })
+            .await
+            .map_err(sui_rpc_error)?;
         Ok(match message {
             Some(payload) => Some(ParsedTokenTransferMessage::try_from(payload)?),
             None => None,
@@ -357,18 +760,36 @@ where
             .get_gas_data_panic_if_not_gas(gas_object_id)
             .await
     }
+
+    /// Returns None if the gas object does not exist, and an error if it is not a gas coin.
+    pub async fn get_gas_data_maybe(
+        &self,
+        gas_object_id: ObjectID,
+    ) -> BridgeResult<Option<(GasCoin, ObjectRef, Owner)>> {
+        self.request("get_gas_data_maybe", || {
+            self.inner.get_gas_data_maybe(gas_object_id)
+        })
+        .await
+    }
 }
 
 /// Use a trait to abstract over the SuiSDKClient and SuiMockClient for testing.
 #[async_trait]
 pub trait SuiClientInner: Send + Sync {
-    type Error: Into<anyhow::Error> + Send + Sync + std::error::Error + 'static;
+    type Error: Into<anyhow::Error> + ClassifyError + Send + Sync + std::error::Error + 'static;
     async fn query_events(
         &self,
         query: EventFilter,
         cursor: Option<EventID>,
     ) -> Result<EventPage, Self::Error>;
 
+    /// Returns the latest `limit` events matching `query`, newest first.
+    async fn query_latest_events(
+        &self,
+        query: EventFilter,
+        limit: usize,
+    ) -> Result<EventPage, Self::Error>;
+
     async fn get_events_by_tx_digest(
         &self,
         tx_digest: TransactionDigest,
@@ -376,10 +797,14 @@ pub trait SuiClientInner: Send + Sync {
 
     async fn get_chain_identifier(&self) -> Result<String, Self::Error>;
 
+    async fn get_server_version(&self) -> Result<String, Self::Error>;
+
     async fn get_reference_gas_price(&self) -> Result<u64, Self::Error>;
 
     async fn get_latest_checkpoint_sequence_number(&self) -> Result<u64, Self::Error>;
 
+    async fn get_latest_checkpoint_timestamp_ms(&self) -> Result<u64, Self::Error>;
+
     async fn get_mutable_bridge_object_arg(&self) -> Result<ObjectArg, Self::Error>;
 
     async fn get_bridge_summary(&self) -> Result<BridgeSummary, Self::Error>;
@@ -389,6 +814,12 @@ pub trait SuiClientInner: Send + Sync {
         tx: Transaction,
     ) -> Result<SuiTransactionBlockResponse, BridgeError>;
 
+    /// Fails if the transaction is not known to the fullnode, e.g. was not executed.
+    async fn get_transaction_block_with_effects(
+        &self,
+        tx_digest: TransactionDigest,
+    ) -> Result<SuiTransactionBlockResponse, BridgeError>;
+
     async fn get_token_transfer_action_onchain_status(
         &self,
         bridge_object_arg: ObjectArg,
@@ -396,6 +827,14 @@ pub trait SuiClientInner: Send + Sync {
         seq_number: u64,
     ) -> Result<BridgeActionStatus, BridgeError>;
 
+    /// Statuses of the token transfers with the (source chain id, seq number) `keys`, in
+    /// the same order, in a single query.
+    async fn get_token_transfer_action_onchain_statuses(
+        &self,
+        bridge_object_arg: ObjectArg,
+        keys: &[(u8, u64)],
+    ) -> Result<Vec<Result<BridgeActionStatus, BridgeError>>, BridgeError>;
+
     async fn get_token_transfer_action_onchain_signatures(
         &self,
         bridge_object_arg: ObjectArg,
@@ -414,6 +853,11 @@ pub trait SuiClientInner: Send + Sync {
         &self,
         gas_object_id: ObjectID,
     ) -> (GasCoin, ObjectRef, Owner);
+
+    async fn get_gas_data_maybe(
+        &self,
+        gas_object_id: ObjectID,
+    ) -> Result<Option<(GasCoin, ObjectRef, Owner)>, BridgeError>;
 }
 
 #[async_trait]
@@ -430,6 +874,16 @@ impl SuiClientInner for SuiSdkClient {
             .await
     }
 
+    async fn query_latest_events(
+        &self,
+        query: EventFilter,
+        limit: usize,
+    ) -> Result<EventPage, Self::Error> {
+        self.event_api()
+            .query_events(query, None, Some(limit), true)
+            .await
+    }
+
     async fn get_events_by_tx_digest(
         &self,
         tx_digest: TransactionDigest,
@@ -441,6 +895,19 @@ impl SuiClientInner for SuiSdkClient {
         self.read_api().get_chain_identifier().await
     }
 
+    // `api_version` is the version when the client was built, ask the fullnode again
+    async fn get_server_version(&self) -> Result<String, Self::Error> {
+        let rpc_spec: serde_json::Value =
+            self.http().request("rpc.discover", rpc_params![]).await?;
+        rpc_spec
+            .pointer("/info/version")
+            .and_then(|version| version.as_str())
+            .map(|version| version.to_string())
+            .ok_or_else(|| {
+                sui_sdk::error::Error::DataError("No server version in rpc.discover".to_string())
+            })
+    }
+
     async fn get_reference_gas_price(&self) -> Result<u64, Self::Error> {
         self.governance_api().get_reference_gas_price().await
     }
@@ -451,6 +918,18 @@ impl SuiClientInner for SuiSdkClient {
             .await
     }
 
+    async fn get_latest_checkpoint_timestamp_ms(&self) -> Result<u64, Self::Error> {
+        let sequence_number = self
+            .read_api()
+            .get_latest_checkpoint_sequence_number()
+            .await?;
+        let checkpoint = self
+            .read_api()
+            .get_checkpoint(CheckpointId::SequenceNumber(sequence_number))
+            .await?;
+        Ok(checkpoint.timestamp_ms)
+    }
+
     async fn get_mutable_bridge_object_arg(&self) -> Result<ObjectArg, Self::Error> {
         let initial_shared_version = self
             .http()
@@ -484,6 +963,27 @@ impl SuiClientInner for SuiSdkClient {
         .and_then(|status_byte| BridgeActionStatus::try_from(status_byte).map_err(Into::into))
     }
 
+    async fn get_token_transfer_action_onchain_statuses(
+        &self,
+        bridge_object_arg: ObjectArg,
+        keys: &[(u8, u64)],
+    ) -> Result<Vec<Result<BridgeActionStatus, BridgeError>>, BridgeError> {
+        Ok(dev_inspect_bridge_batch::<u8>(
+            self,
+            bridge_object_arg,
+            keys,
+            "get_token_transfer_action_status",
+        )
+        .await?
+        .into_iter()
+        .map(|status_byte| {
+            status_byte.and_then(|status_byte| {
+                BridgeActionStatus::try_from(status_byte).map_err(Into::into)
+            })
+        })
+        .collect())
+    }
+
     async fn get_token_transfer_action_onchain_signatures(
         &self,
         bridge_object_arg: ObjectArg,
@@ -514,6 +1014,21 @@ impl SuiClientInner for SuiSdkClient {
         }
     }
 
+    async fn get_transaction_block_with_effects(
+        &self,
+        tx_digest: TransactionDigest,
+    ) -> Result<SuiTransactionBlockResponse, BridgeError> {
+        self.read_api()
+            .get_transaction_with_options(
+                tx_digest,
+                SuiTransactionBlockResponseOptions::new()
+                    .with_effects()
+                    .with_events(),
+            )
+            .await
+            .map_err(|e| BridgeError::SuiTxFailureGeneric(e.to_string()))
+    }
+
     async fn get_parsed_token_transfer_message(
         &self,
         bridge_object_arg: ObjectArg,
@@ -557,6 +1072,29 @@ impl SuiClientInner for SuiSdkClient {
             }
         }
     }
+
+    async fn get_gas_data_maybe(
+        &self,
+        gas_object_id: ObjectID,
+    ) -> Result<Option<(GasCoin, ObjectRef, Owner)>, BridgeError> {
+        let Some(gas_obj) = self
+            .read_api()
+            .get_object_with_options(
+                gas_object_id,
+                SuiObjectDataOptions::default().with_owner().with_content(),
+            )
+            .await
+            .map_err(sui_rpc_error)?
+            .data
+        else {
+            return Ok(None);
+        };
+        let owner = gas_obj.owner.expect("Owner is requested");
+        let gas_coin = GasCoin::try_from(&gas_obj).map_err(|err| {
+            BridgeError::Generic(format!("{} is not a gas coin: {err}", gas_object_id))
+        })?;
+        Ok(Some((gas_coin, gas_obj.object_ref(), owner)))
+    }
 }
 
 /// Helper function to dev-inspect `bridge::{function_name}` function
@@ -572,25 +1110,54 @@ async fn dev_inspect_bridge<T>(
 where
     T: DeserializeOwned,
 {
-    let pt = ProgrammableTransaction {
-        inputs: vec![
-            CallArg::Object(bridge_object_arg),
-            CallArg::Pure(bcs::to_bytes(&source_chain_id).unwrap()),
-            CallArg::Pure(bcs::to_bytes(&seq_number).unwrap()),
-        ],
-        commands: vec![Command::MoveCall(Box::new(ProgrammableMoveCall {
+    dev_inspect_bridge_batch::<T>(
+        sui_client,
+        bridge_object_arg,
+        &[(source_chain_id, seq_number)],
+        function_name,
+    )
+    .await?
+    .pop()
+    .expect("One value is returned per key")
+}
+
+/// Same as `dev_inspect_bridge` for several (source chain id, seq number) keys, with one
+/// call per key in a single dev-inspected transaction. Returns the values in the order of
+/// `keys`. Fails as a whole if any call aborts.
+async fn dev_inspect_bridge_batch<T>(
+    sui_client: &SuiSdkClient,
+    bridge_object_arg: ObjectArg,
+    keys: &[(u8, u64)],
+    function_name: &str,
+) -> Result<Vec<Result<T, BridgeError>>, BridgeError>
+where
+    T: DeserializeOwned,
+{
+    let mut inputs = vec![CallArg::Object(bridge_object_arg)];
+    let mut commands = vec![];
+    for (source_chain_id, seq_number) in keys {
+        let first_input = inputs.len() as u16;
+        inputs.push(CallArg::Pure(bcs::to_bytes(source_chain_id).unwrap()));
+        inputs.push(CallArg::Pure(bcs::to_bytes(seq_number).unwrap()));
+        commands.push(Command::MoveCall(Box::new(ProgrammableMoveCall {
             package: BRIDGE_PACKAGE_ID,
             module: Identifier::new("bridge").unwrap(),
             function: Identifier::new(function_name).unwrap(),
             type_arguments: vec![],
-            arguments: vec![Argument::Input(0), Argument::Input(1), Argument::Input(2)],
-        }))],
-    };
+            arguments: vec![
+                Argument::Input(0),
+                Argument::Input(first_input),
+                Argument::Input(first_input + 1),
+            ],
+        })));
+    }
+    let pt = ProgrammableTransaction { inputs, commands };
     let kind = TransactionKind::programmable(pt);
     let resp = sui_client
         .read_api()
         .dev_inspect_transaction_block(SuiAddress::ZERO, kind, None, None, None)
-        .await?;
+        .await
+        .map_err(sui_rpc_error)?;
     let DevInspectResults {
         results, effects, ..
     } = resp;
@@ -600,23 +1167,33 @@ where
             function_name, effects
         )));
     };
-    let return_values = &results
-        .first()
-        .ok_or(BridgeError::Generic(format!(
-            "No return values for '{}', results: {:?}",
-            function_name, results
-        )))?
-        .return_values;
-    let (value_bytes, _type_tag) = return_values.first().ok_or(BridgeError::Generic(format!(
-        "No first return value for '{}', results: {:?}",
-        function_name, results
-    )))?;
-    bcs::from_bytes::<T>(value_bytes).map_err(|e| {
-        BridgeError::Generic(format!(
-            "Failed to parse return value for '{}', error: {:?}, results: {:?}",
-            function_name, e, results
-        ))
-    })
+    if results.len() != keys.len() {
+        return Err(BridgeError::Generic(format!(
+            "Expected {} results for '{}', results: {:?}",
+            keys.len(),
+            function_name,
+            results
+        )));
+    }
+    Ok(results
+        .iter()
+        .map(|result| {
+            let (value_bytes, _type_tag) =
+                result
+                    .return_values
+                    .first()
+                    .ok_or(BridgeError::Generic(format!(
+                        "No first return value for '{}', result: {:?}",
+                        function_name, result
+                    )))?;
+            bcs::from_bytes::<T>(value_bytes).map_err(|e| {
+                BridgeError::Generic(format!(
+                    "Failed to parse return value for '{}', error: {:?}, result: {:?}",
+                    function_name, e, result
+                ))
+            })
+        })
+        .collect())
 }
 
 #[cfg(test)]
@@ -641,7 +1218,72 @@ mod tests {
     use test_cluster::TestClusterBuilder;
 
     use super::*;
-    use crate::events::{init_all_struct_tags, SuiToEthTokenBridgeV1};
+    use crate::events::tests::get_test_sui_event_and_action;
+    use crate::events::{init_all_struct_tags, EmergencyOpEvent, SuiToEthTokenBridgeV1};
+
+    #[tokio::test]
+    async fn test_object_cache() {
+        telemetry_subscribers::init_for_testing();
+        let mock_client = SuiMockClient::default();
+        let metrics = Arc::new(BridgeMetrics::for_testing());
+        let sui_client =
+            SuiClient::new_for_testing(mock_client.clone()).with_object_cache(metrics.clone());
+
+        // A second read within TTL is served by the cache
+        assert!(!sui_client.is_bridge_paused().await.unwrap());
+        mock_client.set_is_bridge_paused(true);
+        assert!(!sui_client.is_bridge_paused().await.unwrap());
+        assert_eq!(mock_client.bridge_summary_query_count(), 1);
+        let hits = |object: &str| {
+            metrics
+                .sui_client_cache_hits
+                .with_label_values(&[object])
+                .get()
+        };
+        let misses = |object: &str| {
+            metrics
+                .sui_client_cache_misses
+                .with_label_values(&[object])
+                .get()
+        };
+        assert_eq!((hits("bridge_summary"), misses("bridge_summary")), (1, 1));
+
+        // Token config is derived from the cached summary
+        sui_client.get_token_id_map().await.unwrap();
+        sui_client.get_token_id_map().await.unwrap();
+        assert_eq!(mock_client.bridge_summary_query_count(), 1);
+        assert_eq!((hits("token_id_map"), misses("token_id_map")), (1, 1));
+
+        // Observing a pause forces a refresh of the summary only
+        sui_client.invalidate_cache_for_event(&SuiBridgeEvent::EmergencyOpEvent(
+            EmergencyOpEvent { frozen: true },
+        ));
+        assert!(sui_client.is_bridge_paused().await.unwrap());
+        assert_eq!(mock_client.bridge_summary_query_count(), 2);
+        sui_client.get_token_id_map().await.unwrap();
+        assert_eq!(mock_client.bridge_summary_query_count(), 2);
+
+        // Token transfers do not change cached objects
+        let (sui_event, _) = get_test_sui_event_and_action(Identifier::new("bridge").unwrap());
+        let transfer = SuiBridgeEvent::try_from_sui_event(&sui_event)
+            .unwrap()
+            .unwrap();
+        sui_client.invalidate_cache_for_event(&transfer);
+        sui_client.is_bridge_paused().await.unwrap();
+        assert_eq!(mock_client.bridge_summary_query_count(), 2);
+
+        // Dropping the token config reads the summary again
+        sui_client.invalidate_token_config();
+        sui_client.get_token_id_map().await.unwrap();
+        assert_eq!(mock_client.bridge_summary_query_count(), 3);
+        assert_eq!(misses("token_id_map"), 2);
+
+        // Without the cache every read goes to the fullnode
+        let sui_client = SuiClient::new_for_testing(mock_client.clone());
+        sui_client.is_bridge_paused().await.unwrap();
+        sui_client.is_bridge_paused().await.unwrap();
+        assert_eq!(mock_client.bridge_summary_query_count(), 5);
+    }
 
     #[tokio::test]
     async fn get_bridge_action_by_tx_digest_and_event_idx_maybe() {
@@ -6,14 +6,17 @@
 use crate::error::{BridgeError, BridgeResult};
 use crate::test_utils::DUMMY_MUTALBE_BRIDGE_OBJECT_ARG;
 use async_trait::async_trait;
-use std::collections::{HashMap, VecDeque};
+use move_core_types::language_storage::StructTag;
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::{Arc, Mutex};
+use std::time::Duration;
 use sui_json_rpc_types::SuiTransactionBlockResponse;
 use sui_json_rpc_types::{EventFilter, EventPage, SuiEvent};
 use sui_types::base_types::ObjectID;
 use sui_types::base_types::ObjectRef;
 use sui_types::bridge::{
-    BridgeCommitteeSummary, BridgeSummary, MoveTypeParsedTokenTransferMessage,
+    BridgeCommitteeSummary, BridgeLimiterSummary, BridgeSummary, MoveTypeParsedTokenTransferMessage,
 };
 use sui_types::digests::TransactionDigest;
 use sui_types::event::EventID;
@@ -33,18 +36,32 @@ pub struct SuiMockClient {
     // the top two fields do not change during tests so we don't need them to be Arc<Mutex>>
     chain_identifier: String,
     latest_checkpoint_sequence_number: u64,
+    latest_checkpoint_timestamp_ms: Arc<AtomicU64>,
+    server_version: Arc<Mutex<String>>,
     events: Arc<Mutex<HashMap<(ObjectID, Identifier, Option<EventID>), EventPage>>>,
+    latest_events: Arc<Mutex<HashMap<StructTag, Vec<SuiEvent>>>>,
     past_event_query_params: Arc<Mutex<VecDeque<(ObjectID, Identifier, Option<EventID>)>>>,
+    event_query_panics: Arc<AtomicU64>,
     events_by_tx_digest:
         Arc<Mutex<HashMap<TransactionDigest, Result<Vec<SuiEvent>, sui_sdk::error::Error>>>>,
+    events_by_tx_digest_delay: Arc<Mutex<Duration>>,
     transaction_responses:
         Arc<Mutex<HashMap<TransactionDigest, BridgeResult<SuiTransactionBlockResponse>>>>,
     wildcard_transaction_response: Arc<Mutex<Option<BridgeResult<SuiTransactionBlockResponse>>>>,
+    execution_delay: Arc<Mutex<Duration>>,
+    // Transactions that are known on chain, returned by `get_transaction_block_with_effects`
+    onchain_transactions: Arc<Mutex<HashMap<TransactionDigest, SuiTransactionBlockResponse>>>,
     get_object_info: Arc<Mutex<HashMap<ObjectID, (GasCoin, ObjectRef, Owner)>>>,
     onchain_status: Arc<Mutex<HashMap<(u8, u64), BridgeActionStatus>>>,
+    onchain_status_errors: Arc<Mutex<HashSet<(u8, u64)>>>,
+    onchain_status_queries: Arc<AtomicU64>,
     bridge_committee_summary: Arc<Mutex<Option<BridgeCommitteeSummary>>>,
     is_paused: Arc<Mutex<Option<IsBridgePaused>>>,
+    bridge_summary_queries: Arc<AtomicU64>,
+    bridge_chain_id: Arc<Mutex<u8>>,
+    bridge_limiter: Arc<Mutex<BridgeLimiterSummary>>,
     requested_transactions_tx: tokio::sync::broadcast::Sender<TransactionDigest>,
+    executed_transactions: Arc<Mutex<Vec<Transaction>>>,
 }
 
 impl SuiMockClient {
@@ -52,16 +69,29 @@ impl SuiMockClient {
         Self {
             chain_identifier: "".to_string(),
             latest_checkpoint_sequence_number: 0,
+            latest_checkpoint_timestamp_ms: Default::default(),
+            server_version: Default::default(),
             events: Default::default(),
+            latest_events: Default::default(),
             past_event_query_params: Default::default(),
+            event_query_panics: Default::default(),
             events_by_tx_digest: Default::default(),
+            events_by_tx_digest_delay: Default::default(),
             transaction_responses: Default::default(),
             wildcard_transaction_response: Default::default(),
+            execution_delay: Default::default(),
+            onchain_transactions: Default::default(),
             get_object_info: Default::default(),
             onchain_status: Default::default(),
+            onchain_status_errors: Default::default(),
+            onchain_status_queries: Default::default(),
             bridge_committee_summary: Default::default(),
             is_paused: Default::default(),
+            bridge_summary_queries: Default::default(),
+            bridge_chain_id: Default::default(),
+            bridge_limiter: Default::default(),
             requested_transactions_tx: tokio::sync::broadcast::channel(10000).0,
+            executed_transactions: Default::default(),
         }
     }
 
@@ -78,6 +108,19 @@ impl SuiMockClient {
             .insert((package, module, Some(cursor)), events);
     }
 
+    /// Makes the next `count` queries of events by module panic, like a crashing watcher.
+    pub fn panic_on_next_event_queries(&self, count: u64) {
+        self.event_query_panics.store(count, Ordering::SeqCst);
+    }
+
+    /// Sets the events of `event_type` returned by `query_latest_events`, newest first.
+    pub fn set_latest_events(&self, event_type: StructTag, events: Vec<SuiEvent>) {
+        self.latest_events
+            .lock()
+            .unwrap()
+            .insert(event_type, events);
+    }
+
     pub fn add_events_by_tx_digest(&self, tx_digest: TransactionDigest, events: Vec<SuiEvent>) {
         self.events_by_tx_digest
             .lock()
@@ -86,10 +129,10 @@ impl SuiMockClient {
     }
 
     pub fn add_events_by_tx_digest_error(&self, tx_digest: TransactionDigest) {
-        self.events_by_tx_digest.lock().unwrap().insert(
-            tx_digest,
-            Err(sui_sdk::error::Error::DataError("".to_string())),
-        );
+        self.events_by_tx_digest
+            .lock()
+            .unwrap()
+            .insert(tx_digest, Err(transient_rpc_error()));
     }
 
     pub fn add_transaction_response(
@@ -110,6 +153,19 @@ impl SuiMockClient {
             .insert((action.chain_id() as u8, action.seq_number()), status);
     }
 
+    /// Makes the status queries of `action` fail, and the batches that include it.
+    pub fn set_action_onchain_status_error(&self, action: &BridgeAction) {
+        self.onchain_status_errors
+            .lock()
+            .unwrap()
+            .insert((action.chain_id() as u8, action.seq_number()));
+    }
+
+    /// Returns how many status queries were made, a batch counting as one.
+    pub fn onchain_status_query_count(&self) -> u64 {
+        self.onchain_status_queries.load(Ordering::Relaxed)
+    }
+
     pub fn set_bridge_committee(&self, committee: BridgeCommitteeSummary) {
         self.bridge_committee_summary
             .lock()
@@ -121,6 +177,66 @@ impl SuiMockClient {
         self.is_paused.lock().unwrap().replace(value);
     }
 
+    /// Returns how many times the bridge summary was queried.
+    /// Delays every response of `get_events_by_tx_digest` by `delay`.
+    pub fn set_events_by_tx_digest_delay(&self, delay: Duration) {
+        *self.events_by_tx_digest_delay.lock().unwrap() = delay;
+    }
+
+    /// Delays the responses of `execute_transaction_block_with_effects`. The transaction
+    /// is only recorded as executed after the delay.
+    pub fn set_execution_delay(&self, delay: Duration) {
+        *self.execution_delay.lock().unwrap() = delay;
+    }
+
+    pub fn add_onchain_transaction(
+        &self,
+        tx_digest: TransactionDigest,
+        response: SuiTransactionBlockResponse,
+    ) {
+        self.onchain_transactions
+            .lock()
+            .unwrap()
+            .insert(tx_digest, response);
+    }
+
+    pub fn set_server_version(&self, version: &str) {
+        *self.server_version.lock().unwrap() = version.to_string();
+    }
+
+    pub fn set_latest_checkpoint_timestamp_ms(&self, timestamp_ms: u64) {
+        self.latest_checkpoint_timestamp_ms
+            .store(timestamp_ms, Ordering::Relaxed);
+    }
+
+    pub fn set_bridge_chain_id(&self, chain_id: u8) {
+        *self.bridge_chain_id.lock().unwrap() = chain_id;
+    }
+
+    pub fn set_bridge_limiter(&self, limiter: BridgeLimiterSummary) {
+        *self.bridge_limiter.lock().unwrap() = limiter;
+    }
+
+    fn onchain_status(&self, key: (u8, u64)) -> Result<BridgeActionStatus, BridgeError> {
+        if self.onchain_status_errors.lock().unwrap().contains(&key) {
+            return Err(BridgeError::Generic(format!(
+                "Mock status query of {:?} failed",
+                key
+            )));
+        }
+        Ok(self
+            .onchain_status
+            .lock()
+            .unwrap()
+            .get(&key)
+            .cloned()
+            .unwrap_or(BridgeActionStatus::Pending))
+    }
+
+    pub fn bridge_summary_query_count(&self) -> u64 {
+        self.bridge_summary_queries.load(Ordering::Relaxed)
+    }
+
     pub fn set_wildcard_transaction_response(
         &self,
         response: BridgeResult<SuiTransactionBlockResponse>,
@@ -135,6 +251,11 @@ impl SuiMockClient {
             .insert(object_ref.0, (gas_coin, object_ref, owner));
     }
 
+    /// Returns the transactions requested to be executed, in order.
+    pub fn executed_transactions(&self) -> Vec<Transaction> {
+        self.executed_transactions.lock().unwrap().clone()
+    }
+
     pub fn subscribe_to_requested_transactions(
         &self,
     ) -> tokio::sync::broadcast::Receiver<TransactionDigest> {
@@ -142,6 +263,10 @@ impl SuiMockClient {
     }
 }
 
+fn transient_rpc_error() -> sui_sdk::error::Error {
+    sui_sdk::error::Error::RpcError(jsonrpsee::core::Error::RequestTimeout)
+}
+
 #[async_trait]
 impl SuiClientInner for SuiMockClient {
     type Error = sui_sdk::error::Error;
@@ -153,6 +278,15 @@ impl SuiClientInner for SuiMockClient {
         query: EventFilter,
         cursor: Option<EventID>,
     ) -> Result<EventPage, Self::Error> {
+        if self
+            .event_query_panics
+            .fetch_update(Ordering::SeqCst, Ordering::SeqCst, |count| {
+                count.checked_sub(1)
+            })
+            .is_ok()
+        {
+            panic!("Event query panicked for testing, cursor: {:?}", cursor);
+        }
         let events = self.events.lock().unwrap();
         match query {
             EventFilter::MoveEventModule { package, module } => {
@@ -175,10 +309,39 @@ impl SuiClientInner for SuiMockClient {
         }
     }
 
+    async fn query_latest_events(
+        &self,
+        query: EventFilter,
+        limit: usize,
+    ) -> Result<EventPage, Self::Error> {
+        match query {
+            EventFilter::MoveEventType(event_type) => {
+                let data: Vec<_> = self
+                    .latest_events
+                    .lock()
+                    .unwrap()
+                    .get(&event_type)
+                    .cloned()
+                    .unwrap_or_default()
+                    .into_iter()
+                    .take(limit)
+                    .collect();
+                Ok(EventPage {
+                    next_cursor: data.last().map(|event| event.id),
+                    data,
+                    has_next_page: false,
+                })
+            }
+            _ => unimplemented!(),
+        }
+    }
+
     async fn get_events_by_tx_digest(
         &self,
         tx_digest: TransactionDigest,
     ) -> Result<Vec<SuiEvent>, Self::Error> {
+        let delay = *self.events_by_tx_digest_delay.lock().unwrap();
+        tokio::time::sleep(delay).await;
         let events = self.events_by_tx_digest.lock().unwrap();
 
         match events
@@ -187,7 +350,7 @@ impl SuiClientInner for SuiMockClient {
         {
             Ok(events) => Ok(events.clone()),
             // sui_sdk::error::Error is not Clone
-            Err(_) => Err(sui_sdk::error::Error::DataError("".to_string())),
+            Err(_) => Err(transient_rpc_error()),
         }
     }
 
@@ -195,10 +358,18 @@ impl SuiClientInner for SuiMockClient {
         Ok(self.chain_identifier.clone())
     }
 
+    async fn get_server_version(&self) -> Result<String, Self::Error> {
+        Ok(self.server_version.lock().unwrap().clone())
+    }
+
     async fn get_latest_checkpoint_sequence_number(&self) -> Result<u64, Self::Error> {
         Ok(self.latest_checkpoint_sequence_number)
     }
 
+    async fn get_latest_checkpoint_timestamp_ms(&self) -> Result<u64, Self::Error> {
+        Ok(self.latest_checkpoint_timestamp_ms.load(Ordering::Relaxed))
+    }
+
     async fn get_mutable_bridge_object_arg(&self) -> Result<ObjectArg, Self::Error> {
         Ok(DUMMY_MUTALBE_BRIDGE_OBJECT_ARG)
     }
@@ -208,14 +379,15 @@ impl SuiClientInner for SuiMockClient {
     }
 
     async fn get_bridge_summary(&self) -> Result<BridgeSummary, Self::Error> {
+        self.bridge_summary_queries.fetch_add(1, Ordering::Relaxed);
         Ok(BridgeSummary {
             bridge_version: 0,
             message_version: 0,
-            chain_id: 0,
+            chain_id: *self.bridge_chain_id.lock().unwrap(),
             sequence_nums: vec![],
             bridge_records_id: ObjectID::random(),
             is_frozen: self.is_paused.lock().unwrap().unwrap_or_default(),
-            limiter: Default::default(),
+            limiter: self.bridge_limiter.lock().unwrap().clone(),
             committee: self
                 .bridge_committee_summary
                 .lock()
@@ -232,13 +404,22 @@ impl SuiClientInner for SuiMockClient {
         source_chain_id: u8,
         seq_number: u64,
     ) -> Result<BridgeActionStatus, BridgeError> {
-        Ok(self
-            .onchain_status
-            .lock()
-            .unwrap()
-            .get(&(source_chain_id, seq_number))
-            .cloned()
-            .unwrap_or(BridgeActionStatus::Pending))
+        self.onchain_status_queries.fetch_add(1, Ordering::Relaxed);
+        self.onchain_status((source_chain_id, seq_number))
+    }
+
+    async fn get_token_transfer_action_onchain_statuses(
+        &self,
+        _bridge_object_arg: ObjectArg,
+        keys: &[(u8, u64)],
+    ) -> Result<Vec<Result<BridgeActionStatus, BridgeError>>, BridgeError> {
+        self.onchain_status_queries.fetch_add(1, Ordering::Relaxed);
+        // Like a dev-inspected transaction, the batch aborts if one of its calls does
+        let statuses = keys
+            .iter()
+            .map(|key| self.onchain_status(*key))
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(statuses.into_iter().map(Ok).collect())
     }
 
     async fn get_token_transfer_action_onchain_signatures(
@@ -263,7 +444,10 @@ impl SuiClientInner for SuiMockClient {
         &self,
         tx: Transaction,
     ) -> Result<SuiTransactionBlockResponse, BridgeError> {
+        let delay = *self.execution_delay.lock().unwrap();
+        tokio::time::sleep(delay).await;
         self.requested_transactions_tx.send(*tx.digest()).unwrap();
+        self.executed_transactions.lock().unwrap().push(tx.clone());
         match self.transaction_responses.lock().unwrap().get(tx.digest()) {
             Some(response) => response.clone(),
             None => self
@@ -275,6 +459,18 @@ impl SuiClientInner for SuiMockClient {
         }
     }
 
+    async fn get_transaction_block_with_effects(
+        &self,
+        tx_digest: TransactionDigest,
+    ) -> Result<SuiTransactionBlockResponse, BridgeError> {
+        self.onchain_transactions
+            .lock()
+            .unwrap()
+            .get(&tx_digest)
+            .cloned()
+            .ok_or_else(|| BridgeError::Generic(format!("Transaction {} not found", tx_digest)))
+    }
+
     async fn get_gas_data_panic_if_not_gas(
         &self,
         gas_object_id: ObjectID,
@@ -291,4 +487,16 @@ impl SuiClientInner for SuiMockClient {
                 )
             })
     }
+
+    async fn get_gas_data_maybe(
+        &self,
+        gas_object_id: ObjectID,
+    ) -> Result<Option<(GasCoin, ObjectRef, Owner)>, BridgeError> {
+        Ok(self
+            .get_object_info
+            .lock()
+            .unwrap()
+            .get(&gas_object_id)
+            .cloned())
+    }
 }
@@ -0,0 +1,304 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Retries of Sui RPC requests. Errors are classified as `Transient`, e.g. a busy
+//! fullnode, or `RateLimited`, which are retried with backoff, or `Permanent`, e.g.
+//! invalid params or a missing object, which are returned right away.
+
+use crate::error::{BridgeError, ClassifyError, ErrorKind};
+use crate::metrics::BridgeMetrics;
+use jsonrpsee::core::Error as RpcError;
+use jsonrpsee::http_client::transport::Error as HttpTransportError;
+use jsonrpsee::types::error::{CallError, INTERNAL_ERROR_CODE, SERVER_IS_BUSY_CODE};
+use std::fmt::Display;
+use std::future::Future;
+use std::sync::Arc;
+use std::time::Duration;
+use sui_json_rpc_api::TRANSIENT_ERROR_CODE;
+use tracing::debug;
+
+pub const DEFAULT_SUI_RPC_MAX_ATTEMPTS: u32 = 5;
+const SUI_RPC_RETRY_INITIAL_DELAY: Duration = Duration::from_millis(400);
+const SUI_RPC_RETRY_MAX_DELAY: Duration = Duration::from_secs(10);
+
+impl ClassifyError for sui_sdk::error::Error {
+    fn kind(&self) -> ErrorKind {
+        use sui_sdk::error::Error;
+        match self {
+            Error::RpcError(err) => classify_rpc_error(err),
+            Error::JsonRpcError(err) => classify_error_code(err.code),
+            Error::FailToConfirmTransactionStatus(..) => ErrorKind::Transient,
+            Error::BcsSerialisationError(_)
+            | Error::UserInputError(_)
+            | Error::Subscription(_)
+            | Error::DataError(_)
+            | Error::ServerVersionMismatch { .. }
+            | Error::InsufficientFund { .. } => ErrorKind::Permanent,
+        }
+    }
+}
+
+/// Converts an error of a Sui RPC request to `BridgeError`, keeping its kind.
+pub fn sui_rpc_error<E: ClassifyError + Display>(err: E) -> BridgeError {
+    BridgeError::SuiRpcError(err.kind(), err.to_string())
+}
+
+impl From<sui_sdk::error::Error> for BridgeError {
+    fn from(err: sui_sdk::error::Error) -> Self {
+        sui_rpc_error(err)
+    }
+}
+
+fn classify_rpc_error(err: &RpcError) -> ErrorKind {
+    match err {
+        // Errors returned by the fullnode arrive as error objects
+        RpcError::Call(CallError::Custom(error_object)) => classify_error_code(error_object.code()),
+        RpcError::Call(CallError::InvalidParams(_) | CallError::Failed(_)) => ErrorKind::Permanent,
+        RpcError::Transport(err) => match err.downcast_ref::<HttpTransportError>() {
+            Some(HttpTransportError::Rejected { status_code }) => {
+                classify_http_status(*status_code)
+            }
+            Some(HttpTransportError::RequestTooLarge) => ErrorKind::Permanent,
+            // Connection failures
+            _ => ErrorKind::Transient,
+        },
+        RpcError::RequestTimeout | RpcError::RestartNeeded(_) | RpcError::MaxSlotsExceeded => {
+            ErrorKind::Transient
+        }
+        _ => ErrorKind::Permanent,
+    }
+}
+
+fn classify_error_code(code: i32) -> ErrorKind {
+    match code {
+        // The fullnode fails reads from its state with `INTERNAL_ERROR_CODE`
+        TRANSIENT_ERROR_CODE | SERVER_IS_BUSY_CODE | INTERNAL_ERROR_CODE => ErrorKind::Transient,
+        _ => ErrorKind::Permanent,
+    }
+}
+
+fn classify_http_status(status_code: u16) -> ErrorKind {
+    match status_code {
+        429 => ErrorKind::RateLimited,
+        // Errors of proxies in front of the fullnode
+        500..=599 => ErrorKind::Transient,
+        _ => ErrorKind::Permanent,
+    }
+}
+
+/// Retries Sui RPC requests that fail with transient errors, up to `max_attempts`
+/// attempts in total, with exponential backoff.
+pub struct SuiRpcRetry {
+    max_attempts: u32,
+    metrics: Arc<BridgeMetrics>,
+}
+
+impl SuiRpcRetry {
+    pub fn new(max_attempts: u32, metrics: Arc<BridgeMetrics>) -> Self {
+        Self {
+            max_attempts: max_attempts.max(1),
+            metrics,
+        }
+    }
+
+    pub async fn run<T, E, F, Fut>(&self, method: &'static str, request: F) -> Result<T, E>
+    where
+        E: ClassifyError + Display,
+        F: Fn() -> Fut,
+        Fut: Future<Output = Result<T, E>>,
+    {
+        let mut attempt = 1;
+        let mut delay = SUI_RPC_RETRY_INITIAL_DELAY;
+        loop {
+            let err = match request().await {
+                Ok(value) => return Ok(value),
+                Err(err) => err,
+            };
+            let kind = err.kind();
+            self.metrics
+                .sui_rpc_errors
+                .with_label_values(&[method, kind.as_str()])
+                .inc();
+            if !kind.is_retryable() || attempt >= self.max_attempts {
+                return Err(err);
+            }
+            debug!(method, attempt, "Retrying transient Sui RPC error: {err}");
+            self.metrics
+                .sui_rpc_retries
+                .with_label_values(&[method])
+                .inc();
+            tokio::time::sleep(delay).await;
+            delay = (delay * 2).min(SUI_RPC_RETRY_MAX_DELAY);
+            attempt += 1;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use jsonrpsee::types::error::{
+        ErrorObject, CALL_EXECUTION_FAILED_CODE, INVALID_PARAMS_CODE, METHOD_NOT_FOUND_CODE,
+    };
+    use std::sync::atomic::{AtomicU32, Ordering};
+    use sui_json_rpc_api::TRANSACTION_EXECUTION_CLIENT_ERROR_CODE;
+    use sui_sdk::error::Error;
+    use sui_types::base_types::TransactionDigest;
+
+    fn call_error(code: i32) -> Error {
+        Error::RpcError(RpcError::Call(CallError::Custom(ErrorObject::owned(
+            code, "error", None::<()>,
+        ))))
+    }
+
+    fn rejected(status_code: u16) -> Error {
+        Error::RpcError(RpcError::Transport(anyhow::Error::new(
+            HttpTransportError::Rejected { status_code },
+        )))
+    }
+
+    #[test]
+    fn test_classify_sui_sdk_errors() {
+        let cases = vec![
+            (rejected(429), ErrorKind::RateLimited),
+            (rejected(502), ErrorKind::Transient),
+            (rejected(503), ErrorKind::Transient),
+            (rejected(504), ErrorKind::Transient),
+            (rejected(400), ErrorKind::Permanent),
+            (rejected(404), ErrorKind::Permanent),
+            (
+                Error::RpcError(RpcError::Transport(anyhow::anyhow!("connection refused"))),
+                ErrorKind::Transient,
+            ),
+            (
+                Error::RpcError(RpcError::RequestTimeout),
+                ErrorKind::Transient,
+            ),
+            (
+                Error::RpcError(RpcError::RestartNeeded("closed".to_string())),
+                ErrorKind::Transient,
+            ),
+            (call_error(TRANSIENT_ERROR_CODE), ErrorKind::Transient),
+            (call_error(SERVER_IS_BUSY_CODE), ErrorKind::Transient),
+            (call_error(INTERNAL_ERROR_CODE), ErrorKind::Transient),
+            // Object not found and other bad requests
+            (call_error(INVALID_PARAMS_CODE), ErrorKind::Permanent),
+            (call_error(METHOD_NOT_FOUND_CODE), ErrorKind::Permanent),
+            (call_error(CALL_EXECUTION_FAILED_CODE), ErrorKind::Permanent),
+            (
+                call_error(TRANSACTION_EXECUTION_CLIENT_ERROR_CODE),
+                ErrorKind::Permanent,
+            ),
+            (
+                Error::RpcError(RpcError::Call(CallError::InvalidParams(anyhow::anyhow!(
+                    "invalid"
+                )))),
+                ErrorKind::Permanent,
+            ),
+            (
+                Error::RpcError(RpcError::Custom("custom".to_string())),
+                ErrorKind::Permanent,
+            ),
+            (
+                Error::JsonRpcError(sui_sdk::error::JsonRpcError {
+                    code: TRANSIENT_ERROR_CODE,
+                    message: "busy".to_string(),
+                    data: None,
+                }),
+                ErrorKind::Transient,
+            ),
+            (
+                Error::JsonRpcError(sui_sdk::error::JsonRpcError {
+                    code: INVALID_PARAMS_CODE,
+                    message: "invalid".to_string(),
+                    data: None,
+                }),
+                ErrorKind::Permanent,
+            ),
+            (
+                Error::FailToConfirmTransactionStatus(TransactionDigest::random(), 60),
+                ErrorKind::Transient,
+            ),
+            (
+                Error::DataError("not a bridge object".to_string()),
+                ErrorKind::Permanent,
+            ),
+            (
+                Error::ServerVersionMismatch {
+                    client_version: "1".to_string(),
+                    server_version: "2".to_string(),
+                },
+                ErrorKind::Permanent,
+            ),
+        ];
+        for (err, expected) in cases {
+            assert_eq!(err.kind(), expected, "{err:?}");
+            let err = BridgeError::from(err);
+            assert_eq!(err.kind(), expected);
+            assert_eq!(err.component(), crate::error::ErrorComponent::SuiRpc);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_retry_transient_errors_only() {
+        let metrics = Arc::new(BridgeMetrics::for_testing());
+        let retry = SuiRpcRetry::new(3, metrics.clone());
+
+        // Transient errors are retried until max attempts
+        let attempts = AtomicU32::new(0);
+        retry
+            .run("test_transient", || async {
+                attempts.fetch_add(1, Ordering::SeqCst);
+                Err::<(), _>(call_error(TRANSIENT_ERROR_CODE))
+            })
+            .await
+            .unwrap_err();
+        assert_eq!(attempts.load(Ordering::SeqCst), 3);
+        assert_eq!(
+            metrics
+                .sui_rpc_retries
+                .with_label_values(&["test_transient"])
+                .get(),
+            2
+        );
+
+        // Succeeds after a transient error
+        let attempts = AtomicU32::new(0);
+        let value = retry
+            .run("test_recover", || async {
+                match attempts.fetch_add(1, Ordering::SeqCst) {
+                    0 => Err(rejected(429)),
+                    _ => Ok(42),
+                }
+            })
+            .await
+            .unwrap();
+        assert_eq!(value, 42);
+        assert_eq!(attempts.load(Ordering::SeqCst), 2);
+
+        // Permanent errors fail fast
+        let attempts = AtomicU32::new(0);
+        retry
+            .run("test_permanent", || async {
+                attempts.fetch_add(1, Ordering::SeqCst);
+                Err::<(), _>(call_error(INVALID_PARAMS_CODE))
+            })
+            .await
+            .unwrap_err();
+        assert_eq!(attempts.load(Ordering::SeqCst), 1);
+        assert_eq!(
+            metrics
+                .sui_rpc_errors
+                .with_label_values(&["test_permanent", "permanent"])
+                .get(),
+            1
+        );
+        assert_eq!(
+            metrics
+                .sui_rpc_errors
+                .with_label_values(&["test_transient", "transient"])
+                .get(),
+            3
+        );
+    }
+}
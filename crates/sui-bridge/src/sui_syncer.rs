@@ -5,12 +5,21 @@
 //! on Sui blockchain from concerned modules of bridge package 0x9.
 
 use crate::{
+    adaptive_interval::{set_period, AdaptiveInterval, AdaptiveIntervalBounds},
+    chain_health::PipelineChain,
+    cursor_reset::CursorReload,
     error::BridgeResult,
-    retry_with_max_elapsed_time,
+    events::SuiBridgeEvent,
+    metrics::BridgeMetrics,
+    storage::BridgeOrchestratorTables,
     sui_client::{SuiClient, SuiClientInner},
+    supervisor::{RestartPolicy, TaskStart, TaskSupervisor},
 };
 use mysten_metrics::spawn_logged_monitored_task;
-use std::{collections::HashMap, sync::Arc};
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+};
 use sui_json_rpc_types::SuiEvent;
 use sui_types::BRIDGE_PACKAGE_ID;
 use sui_types::{event::EventID, Identifier};
@@ -19,7 +28,8 @@ use tokio::{
     time::{self, Duration},
 };
 
-const SUI_EVENTS_CHANNEL_SIZE: usize = 1000;
+pub const SUI_EVENTS_CHANNEL_SIZE: usize = 1000;
+pub const SUI_EVENTS_SEND_TIMEOUT: Duration = Duration::from_secs(30);
 
 /// Map from contract address to their start cursor (exclusive)
 pub type SuiTargetModules = HashMap<Identifier, Option<EventID>>;
@@ -29,6 +39,12 @@ pub struct SuiSyncer<C> {
     // The last transaction that the syncer has fully processed.
     // Syncer will resume post this transaction (i.e. exclusive), when it starts.
     cursors: SuiTargetModules,
+    events_channel_size: usize,
+    events_send_timeout: Duration,
+    cursor_reloads: HashMap<Identifier, CursorReload<Option<EventID>>>,
+    restart_policy: Option<RestartPolicy>,
+    store: Option<Arc<BridgeOrchestratorTables>>,
+    adaptive_interval: Option<AdaptiveIntervalBounds>,
 }
 
 impl<C> SuiSyncer<C>
@@ -39,40 +55,125 @@ where
         Self {
             sui_client,
             cursors,
+            events_channel_size: SUI_EVENTS_CHANNEL_SIZE,
+            events_send_timeout: SUI_EVENTS_SEND_TIMEOUT,
+            cursor_reloads: HashMap::new(),
+            restart_policy: None,
+            store: None,
+            adaptive_interval: None,
         }
     }
 
+    /// Overrides the capacity of the events channel and how long a listening task waits
+    /// for room in it before giving up on the current page of events.
+    pub fn with_events_channel(mut self, size: usize, send_timeout: Duration) -> Self {
+        self.events_channel_size = size;
+        self.events_send_timeout = send_timeout;
+        self
+    }
+
+    /// Makes the listening tasks continue from the cursor in `cursor_reloads` whenever
+    /// the cursor of their module is reset.
+    pub fn with_cursor_reloads(
+        mut self,
+        cursor_reloads: HashMap<Identifier, CursorReload<Option<EventID>>>,
+    ) -> Self {
+        self.cursor_reloads = cursor_reloads;
+        self
+    }
+
+    /// Restarts the listening tasks that panic according to `restart_policy`. A restarted
+    /// task continues after the last page it forwarded, and leaves out of its first page
+    /// the events of the actions that are already in `store`.
+    pub fn with_supervision(
+        mut self,
+        restart_policy: RestartPolicy,
+        store: Arc<BridgeOrchestratorTables>,
+    ) -> Self {
+        self.restart_policy = Some(restart_policy);
+        self.store = Some(store);
+        self
+    }
+
+    /// Makes the listening tasks query their module at an interval within `bounds` that
+    /// follows its activity, see `adaptive_interval`, instead of the fixed one `run` is
+    /// given.
+    pub fn with_adaptive_interval(mut self, bounds: AdaptiveIntervalBounds) -> Self {
+        self.adaptive_interval = Some(bounds);
+        self
+    }
+
     pub async fn run(
-        self,
+        mut self,
         query_interval: Duration,
+        metrics: Arc<BridgeMetrics>,
     ) -> BridgeResult<(
         Vec<JoinHandle<()>>,
         mysten_metrics::metered_channel::Receiver<(Identifier, Vec<SuiEvent>)>,
     )> {
         let (events_tx, events_rx) = mysten_metrics::metered_channel::channel(
-            SUI_EVENTS_CHANNEL_SIZE,
+            self.events_channel_size,
             &mysten_metrics::get_metrics()
                 .unwrap()
                 .channel_inflight
                 .with_label_values(&["sui_events_queue"]),
         );
+        metrics
+            .channel_capacity
+            .with_label_values(&["sui_events_queue"])
+            .set(self.events_channel_size as i64);
 
+        let interval_bounds = self
+            .adaptive_interval
+            .unwrap_or(AdaptiveIntervalBounds::fixed(query_interval));
         let mut task_handles = vec![];
         for (module, cursor) in self.cursors {
-            let events_rx_clone: mysten_metrics::metered_channel::Sender<(
-                Identifier,
-                Vec<SuiEvent>,
-            )> = events_tx.clone();
-            let sui_client_clone = self.sui_client.clone();
-            task_handles.push(spawn_logged_monitored_task!(
+            let events_tx = events_tx.clone();
+            let sui_client = self.sui_client.clone();
+            let metrics_clone = metrics.clone();
+            let store = self.store.clone();
+            let send_timeout = self.events_send_timeout;
+            let cursor_reload = Arc::new(tokio::sync::Mutex::new(
+                self.cursor_reloads.remove(&module).unwrap_or_default(),
+            ));
+            // The cursor after the last forwarded page, where a restarted task continues
+            let forwarded = Arc::new(Mutex::new(cursor));
+            let component = format!("sui_syncer_{}", module);
+            let start_task = move |start: TaskStart| {
+                let cursor = *forwarded.lock().unwrap();
+                let boundary_store = match start {
+                    TaskStart::Clean => None,
+                    TaskStart::AfterCrash => store.clone(),
+                };
                 Self::run_event_listening_task(
-                    module,
+                    module.clone(),
                     cursor,
-                    events_rx_clone,
-                    sui_client_clone,
-                    query_interval
+                    cursor_reload.clone(),
+                    forwarded.clone(),
+                    boundary_store,
+                    events_tx.clone(),
+                    send_timeout,
+                    sui_client.clone(),
+                    interval_bounds,
+                    metrics_clone.clone(),
                 )
-            ));
+            };
+            match &self.restart_policy {
+                Some(restart_policy) => {
+                    let supervisor = TaskSupervisor::new(
+                        component,
+                        PipelineChain::Sui,
+                        restart_policy.clone(),
+                        metrics.clone(),
+                    );
+                    task_handles.push(spawn_logged_monitored_task!(
+                        supervisor.supervise(start_task)
+                    ));
+                }
+                None => {
+                    task_handles.push(spawn_logged_monitored_task!(start_task(TaskStart::Clean)));
+                }
+            }
         }
         Ok((task_handles, events_rx))
     }
@@ -82,43 +183,145 @@ where
         // Moudle is always of bridge package 0x9.
         module: Identifier,
         mut cursor: Option<EventID>,
+        cursor_reload: Arc<tokio::sync::Mutex<CursorReload<Option<EventID>>>>,
+        forwarded: Arc<Mutex<Option<EventID>>>,
+        // Set when restarted after a crash, to deduplicate the first page against
+        mut boundary_store: Option<Arc<BridgeOrchestratorTables>>,
         events_sender: mysten_metrics::metered_channel::Sender<(Identifier, Vec<SuiEvent>)>,
+        send_timeout: Duration,
         sui_client: Arc<SuiClient<C>>,
-        query_interval: Duration,
+        interval_bounds: AdaptiveIntervalBounds,
+        metrics: Arc<BridgeMetrics>,
     ) {
-        tracing::info!(?module, ?cursor, "Starting sui events listening task");
-        let mut interval = time::interval(query_interval);
+        tracing::info!(
+            ?module,
+            ?cursor,
+            restarted = boundary_store.is_some(),
+            "Starting sui events listening task"
+        );
+        let mut cursor_reload = cursor_reload.lock().await;
+        let mut poll_interval = AdaptiveInterval::new(interval_bounds);
+        let interval_gauge = metrics
+            .syncer_poll_interval_ms
+            .with_label_values(&[&format!("sui_syncer_{}", module)]);
+        let mut interval = time::interval(poll_interval.current());
         interval.set_missed_tick_behavior(time::MissedTickBehavior::Skip);
         loop {
             interval.tick().await;
-            let Ok(Ok(events)) = retry_with_max_elapsed_time!(
-                sui_client.query_events_by_module(BRIDGE_PACKAGE_ID, module.clone(), cursor),
-                Duration::from_secs(120)
-            ) else {
-                tracing::error!("Failed to query events from sui client after retry");
-                continue;
+            // The events the previous query observed set the interval until the next one
+            let period = poll_interval.next();
+            interval_gauge.set(period.as_millis() as i64);
+            set_period(&mut interval, period);
+            if let Some(new_cursor) = cursor_reload.try_take() {
+                tracing::info!(?module, ?cursor, ?new_cursor, "Cursor was reset");
+                cursor = new_cursor;
+                *forwarded.lock().unwrap() = cursor;
+            }
+            // Transient errors are retried by `SuiClient`, otherwise query again next round
+            let events = sui_client
+                .query_events_by_module(BRIDGE_PACKAGE_ID, module.clone(), cursor)
+                .await;
+            metrics
+                .chain_health
+                .record(PipelineChain::Sui, &events, &metrics);
+            let mut events = match events {
+                Ok(events) => {
+                    metrics.readiness.record_syncer_success(PipelineChain::Sui);
+                    events
+                }
+                Err(e) => {
+                    tracing::error!(?module, "Failed to query events from sui client: {:?}", e);
+                    metrics
+                        .readiness
+                        .record_syncer_failure(PipelineChain::Sui, e.to_string());
+                    continue;
+                }
             };
 
+            // The first page after a crash may have been forwarded before it
+            if !events.data.is_empty() {
+                if let Some(store) = &boundary_store {
+                    events.data = drop_known_events(events.data, store, &metrics);
+                    if events.data.is_empty() {
+                        boundary_store = None;
+                        if let Some(next) = events.next_cursor {
+                            cursor = Some(next);
+                            *forwarded.lock().unwrap() = cursor;
+                        }
+                        continue;
+                    }
+                }
+            }
+
             let len = events.data.len();
             if len != 0 {
-                events_sender
-                    .send((module.clone(), events.data))
-                    .await
-                    .expect("All Sui event channel receivers are closed");
+                // When the channel stays full for `send_timeout`, the events are dropped and
+                // the cursor is not moved, so they are queried again in the next round
+                // instead of being held in memory while the downstream is stalled.
+                let Ok(permit) = time::timeout(send_timeout, events_sender.reserve()).await else {
+                    tracing::warn!(
+                        ?module,
+                        ?cursor,
+                        "Sui events channel is full, will query the events again"
+                    );
+                    metrics
+                        .syncer_send_timeouts
+                        .with_label_values(&["sui"])
+                        .inc();
+                    continue;
+                };
+                permit
+                    .expect("All Sui event channel receivers are closed")
+                    .send((module.clone(), events.data));
+                boundary_store = None;
                 if let Some(next) = events.next_cursor {
                     cursor = Some(next);
+                    *forwarded.lock().unwrap() = cursor;
                 }
                 tracing::info!(?module, ?cursor, "Observed {len} new Sui events");
+                if poll_interval.observe(len) {
+                    interval_gauge.set(poll_interval.current().as_millis() as i64);
+                    set_period(&mut interval, poll_interval.current());
+                }
             }
         }
     }
 }
 
+// Leaves out the events of the actions that the orchestrator already received.
+fn drop_known_events(
+    events: Vec<SuiEvent>,
+    store: &BridgeOrchestratorTables,
+    metrics: &BridgeMetrics,
+) -> Vec<SuiEvent> {
+    let (known, events): (Vec<_>, Vec<_>) =
+        events
+            .into_iter()
+            .partition(|event| match SuiBridgeEvent::parse_sui_event(event) {
+                Ok(Some((_, Some(action)))) => store.is_known_action(&action).unwrap_or(false),
+                _ => false,
+            });
+    if !known.is_empty() {
+        tracing::info!(
+            "Skipping {} Sui events of actions that were already received: {:?}",
+            known.len(),
+            known.iter().map(|event| event.id).collect::<Vec<_>>()
+        );
+        metrics
+            .orchestrator_duplicate_actions
+            .inc_by(known.len() as u64);
+    }
+    events
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
-    use crate::{sui_client::SuiClient, sui_mock_client::SuiMockClient};
+    use crate::{
+        events::tests::get_test_sui_event_and_action, sui_client::SuiClient,
+        sui_mock_client::SuiMockClient, test_utils::wait_for_gauge,
+    };
     use prometheus::Registry;
     use sui_json_rpc_types::EventPage;
     use sui_types::{digests::TransactionDigest, event::EventID, Identifier};
@@ -148,7 +351,7 @@ mod tests {
         ]);
         let interval = Duration::from_millis(200);
         let (_handles, mut events_rx) = SuiSyncer::new(client, target_modules)
-            .run(interval)
+            .run(interval, Arc::new(BridgeMetrics::for_testing()))
             .await
             .unwrap();
 
@@ -204,6 +407,163 @@ mod tests {
         Ok(())
     }
 
+    #[tokio::test]
+    async fn test_sui_syncer_restarts_after_crash() -> anyhow::Result<()> {
+        telemetry_subscribers::init_for_testing();
+        let registry = Registry::new();
+        mysten_metrics::init_metrics(&registry);
+        let metrics = Arc::new(BridgeMetrics::for_testing());
+        let temp_dir = tempfile::tempdir().unwrap();
+        let store = BridgeOrchestratorTables::new(temp_dir.path());
+
+        let mock = SuiMockClient::default();
+        let client = Arc::new(SuiClient::new_for_testing(mock.clone()));
+        let sender_module = Identifier::new("Foo").unwrap();
+        let (event_1, _) = get_test_sui_event_and_action(sender_module.clone());
+        let (event_2, action_2) = get_test_sui_event_and_action(sender_module.clone());
+        let (event_3, _) = get_test_sui_event_and_action(sender_module);
+        let module = event_1.type_.module.clone();
+        let cursor = EventID {
+            tx_digest: TransactionDigest::random(),
+            event_seq: 0,
+        };
+        let page = |events: Vec<SuiEvent>| EventPage {
+            next_cursor: events.last().map(|event| event.id),
+            data: events,
+            has_next_page: false,
+        };
+        add_event_response(&mock, module.clone(), cursor, page(vec![event_1.clone()]));
+        add_event_response(
+            &mock,
+            module.clone(),
+            event_1.id,
+            page(vec![event_2.clone(), event_3.clone()]),
+        );
+        add_event_response(&mock, module.clone(), event_3.id, EventPage::empty());
+        // The action of event 2 was received by the orchestrator before the crash
+        store.insert_pending_actions(&[action_2]).unwrap();
+
+        let interval = Duration::from_millis(200);
+        let restart_policy = RestartPolicy {
+            initial_backoff: Duration::from_millis(10),
+            max_backoff: Duration::from_millis(10),
+            max_restarts: 3,
+            window: Duration::from_secs(60),
+        };
+        let (_handles, mut events_rx) =
+            SuiSyncer::new(client, HashMap::from([(module.clone(), Some(cursor))]))
+                .with_supervision(restart_policy, store.clone())
+                .run(interval, metrics.clone())
+                .await
+                .unwrap();
+        let received_ids = |events: Vec<SuiEvent>| events.iter().map(|e| e.id).collect::<Vec<_>>();
+        let (_, received_events) = events_rx.recv().await.unwrap();
+        assert_eq!(received_ids(received_events), vec![event_1.id]);
+
+        // The watcher crashes twice when querying the next page, and continues after the
+        // first one rather than from the stored cursor, without the known event 2
+        mock.panic_on_next_event_queries(2);
+        let (_, received_events) = events_rx.recv().await.unwrap();
+        assert_eq!(received_ids(received_events), vec![event_3.id]);
+        assert_no_more_events(interval, &mut events_rx).await;
+        let restarts = || {
+            metrics
+                .watcher_restarts
+                .with_label_values(&[&format!("sui_syncer_{}", module)])
+                .get()
+        };
+        assert_eq!(restarts(), 2);
+        assert_eq!(metrics.orchestrator_duplicate_actions.get(), 1);
+        assert!(metrics
+            .chain_health
+            .state(PipelineChain::Sui)
+            .stopped_tasks
+            .is_empty());
+
+        // Crash looping, it's given up after the 3rd restart in the window
+        mock.panic_on_next_event_queries(u64::MAX);
+        timeout(Duration::from_secs(10), async {
+            while metrics
+                .chain_health
+                .state(PipelineChain::Sui)
+                .stopped_tasks
+                .is_empty()
+            {
+                time::sleep(Duration::from_millis(50)).await;
+            }
+        })
+        .await
+        .unwrap();
+        assert_eq!(restarts(), 3);
+        assert_eq!(
+            metrics.chain_health.state(PipelineChain::Sui).status(),
+            crate::chain_health::PipelineStatus::Down
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_sui_syncer_adaptive_interval() -> anyhow::Result<()> {
+        telemetry_subscribers::init_for_testing();
+        let registry = Registry::new();
+        mysten_metrics::init_metrics(&registry);
+        let metrics = Arc::new(BridgeMetrics::for_testing());
+
+        let mock = SuiMockClient::default();
+        let client = Arc::new(SuiClient::new_for_testing(mock.clone()));
+        let sender_module = Identifier::new("Foo").unwrap();
+        let event = || get_test_sui_event_and_action(sender_module.clone()).0;
+        let module = event().type_.module;
+        let cursor = EventID {
+            tx_digest: TransactionDigest::random(),
+            event_seq: 0,
+        };
+        add_event_response(&mock, module.clone(), cursor, EventPage::empty());
+        let bounds = AdaptiveIntervalBounds {
+            min: Duration::from_millis(50),
+            max: Duration::from_millis(400),
+            empty_polls_before_growth: 3,
+            burst_events: 3,
+        };
+        let (_handles, mut events_rx) =
+            SuiSyncer::new(client, HashMap::from([(module.clone(), Some(cursor))]))
+                .with_adaptive_interval(bounds)
+                .run(Duration::from_secs(2), metrics.clone())
+                .await
+                .unwrap();
+        let interval_ms = metrics
+            .syncer_poll_interval_ms
+            .with_label_values(&[&format!("sui_syncer_{}", module)]);
+        let page = |events: Vec<SuiEvent>| EventPage {
+            next_cursor: events.last().map(|event| event.id),
+            data: events,
+            has_next_page: false,
+        };
+
+        // The module is idle, the interval grows to the maximum
+        wait_for_gauge(&interval_ms, 400).await;
+
+        // An event halves the interval
+        let event_1 = event();
+        add_event_response(&mock, module.clone(), event_1.id, EventPage::empty());
+        add_event_response(&mock, module.clone(), cursor, page(vec![event_1.clone()]));
+        events_rx.recv().await.unwrap();
+        wait_for_gauge(&interval_ms, 200).await;
+        wait_for_gauge(&interval_ms, 400).await;
+
+        // A burst sets it to the minimum at once
+        let burst = vec![event(), event(), event()];
+        let last = burst.last().unwrap().id;
+        add_event_response(&mock, module.clone(), last, EventPage::empty());
+        add_event_response(&mock, module.clone(), event_1.id, page(burst));
+        let (_, received_events) = events_rx.recv().await.unwrap();
+        assert_eq!(received_events.len(), 3);
+        wait_for_gauge(&interval_ms, 50).await;
+        // and it grows again once the module is idle
+        wait_for_gauge(&interval_ms, 400).await;
+        Ok(())
+    }
+
     async fn assert_no_more_events(
         interval: Duration,
         events_rx: &mut mysten_metrics::metered_channel::Receiver<(Identifier, Vec<SuiEvent>)>,
@@ -0,0 +1,286 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Supervision of the tasks that watch the chains. A watcher task that panics is started
+//! again after a backoff that doubles with each restart in the last `RestartPolicy::window`.
+//! The task is told that it restarts after a crash, so that it continues from what it had
+//! already forwarded instead of from its stored cursor. After more than
+//! `RestartPolicy::max_restarts` restarts in the window the task is crash looping: it is
+//! given up, and the pipeline of its chain is reported down.
+
+use crate::chain_health::PipelineChain;
+use crate::metrics::BridgeMetrics;
+use mysten_metrics::spawn_logged_monitored_task;
+use std::collections::VecDeque;
+use std::future::Future;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tracing::{error, info, warn};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RestartPolicy {
+    /// Backoff before the first restart in the window
+    pub initial_backoff: Duration,
+    pub max_backoff: Duration,
+    /// Restarts allowed within `window` before the task is given up
+    pub max_restarts: usize,
+    pub window: Duration,
+}
+
+impl Default for RestartPolicy {
+    fn default() -> Self {
+        Self {
+            initial_backoff: Duration::from_secs(1),
+            max_backoff: Duration::from_secs(60),
+            max_restarts: 5,
+            window: Duration::from_secs(10 * 60),
+        }
+    }
+}
+
+impl RestartPolicy {
+    /// Backoff before the `restarts`-th restart in the window, 1-based.
+    pub fn backoff(&self, restarts: usize) -> Duration {
+        let exponent = restarts.saturating_sub(1).min(31) as u32;
+        self.initial_backoff
+            .saturating_mul(1 << exponent)
+            .min(self.max_backoff)
+    }
+}
+
+/// How a supervised task is started.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TaskStart {
+    /// First start of the task, from the stored cursor
+    Clean,
+    /// Start after the previous run of the task panicked
+    AfterCrash,
+}
+
+pub struct TaskSupervisor {
+    component: String,
+    chain: PipelineChain,
+    policy: RestartPolicy,
+    metrics: Arc<BridgeMetrics>,
+}
+
+impl TaskSupervisor {
+    pub fn new(
+        component: impl Into<String>,
+        chain: PipelineChain,
+        policy: RestartPolicy,
+        metrics: Arc<BridgeMetrics>,
+    ) -> Self {
+        Self {
+            component: component.into(),
+            chain,
+            policy,
+            metrics,
+        }
+    }
+
+    /// Runs the task returned by `start_task` until it returns, starting it again when it
+    /// panics. Returns when the task returns or is given up.
+    pub async fn supervise<F, Fut>(self, mut start_task: F)
+    where
+        F: FnMut(TaskStart) -> Fut,
+        Fut: Future<Output = ()> + Send + 'static,
+    {
+        let component = self.component.as_str();
+        let mut restarts = VecDeque::new();
+        let mut start = TaskStart::Clean;
+        loop {
+            let task = start_task(start);
+            let result = spawn_logged_monitored_task!(task).await;
+            let panic = match result {
+                Ok(()) => {
+                    info!(component, "Supervised task returned");
+                    return;
+                }
+                Err(e) if e.is_panic() => e,
+                Err(e) => {
+                    info!(component, "Supervised task was cancelled: {:?}", e);
+                    return;
+                }
+            };
+            let now = Instant::now();
+            while restarts
+                .front()
+                .is_some_and(|restart| now.duration_since(*restart) >= self.policy.window)
+            {
+                restarts.pop_front();
+            }
+            if restarts.len() >= self.policy.max_restarts {
+                error!(
+                    component,
+                    "Supervised task panicked {} times in {:?}, giving up: {:?}",
+                    restarts.len() + 1,
+                    self.policy.window,
+                    panic
+                );
+                self.metrics
+                    .chain_health
+                    .record_stopped_task(self.chain, component, &self.metrics);
+                self.metrics.readiness.record_syncer_failure(
+                    self.chain,
+                    format!("Task {} was stopped after crash looping", component),
+                );
+                return;
+            }
+            restarts.push_back(now);
+            let backoff = self.policy.backoff(restarts.len());
+            warn!(
+                component,
+                "Supervised task panicked, restarting in {:?}: {:?}", backoff, panic
+            );
+            self.metrics
+                .watcher_restarts
+                .with_label_values(&[component])
+                .inc();
+            tokio::time::sleep(backoff).await;
+            start = TaskStart::AfterCrash;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::chain_health::PipelineStatus;
+    use std::sync::Mutex;
+
+    #[test]
+    fn test_restart_backoff() {
+        let policy = RestartPolicy::default();
+        let backoffs = (1..=8)
+            .map(|restarts| policy.backoff(restarts).as_secs())
+            .collect::<Vec<_>>();
+        assert_eq!(backoffs, vec![1, 2, 4, 8, 16, 32, 60, 60]);
+        assert_eq!(policy.backoff(usize::MAX), policy.max_backoff);
+    }
+
+    // A watcher that records when it is started, and panics the first `panics` times
+    fn mock_watcher(
+        starts: Arc<Mutex<Vec<(Instant, TaskStart)>>>,
+        panics: usize,
+    ) -> impl FnMut(TaskStart) -> futures::future::BoxFuture<'static, ()> {
+        move |start| {
+            let starts = starts.clone();
+            Box::pin(async move {
+                let started = {
+                    let mut starts = starts.lock().unwrap();
+                    starts.push((Instant::now(), start));
+                    starts.len()
+                };
+                if started <= panics {
+                    panic!("mock watcher crashed");
+                }
+            })
+        }
+    }
+
+    #[tokio::test]
+    async fn test_crash_loop_breaker() {
+        telemetry_subscribers::init_for_testing();
+        let metrics = Arc::new(BridgeMetrics::for_testing());
+        let policy = RestartPolicy {
+            initial_backoff: Duration::from_millis(50),
+            max_backoff: Duration::from_millis(150),
+            max_restarts: 4,
+            window: Duration::from_secs(60),
+        };
+        let starts = Arc::new(Mutex::new(vec![]));
+        TaskSupervisor::new(
+            "sui_syncer_foo",
+            PipelineChain::Sui,
+            policy,
+            metrics.clone(),
+        )
+        .supervise(mock_watcher(starts.clone(), usize::MAX))
+        .await;
+
+        // Started once, restarted 4 times, then given up
+        let starts = starts.lock().unwrap().clone();
+        assert_eq!(
+            starts.iter().map(|(_, start)| *start).collect::<Vec<_>>(),
+            vec![
+                TaskStart::Clean,
+                TaskStart::AfterCrash,
+                TaskStart::AfterCrash,
+                TaskStart::AfterCrash,
+                TaskStart::AfterCrash,
+            ]
+        );
+        let gaps = starts
+            .windows(2)
+            .map(|pair| pair[1].0.duration_since(pair[0].0))
+            .collect::<Vec<_>>();
+        for (gap, backoff) in gaps.iter().zip([50, 100, 150, 150]) {
+            assert!(*gap >= Duration::from_millis(backoff), "{:?}", gaps);
+            assert!(*gap < Duration::from_millis(backoff + 1000), "{:?}", gaps);
+        }
+        assert_eq!(
+            metrics
+                .watcher_restarts
+                .with_label_values(&["sui_syncer_foo"])
+                .get(),
+            4
+        );
+        let sui = metrics.chain_health.state(PipelineChain::Sui);
+        assert_eq!(sui.status(), PipelineStatus::Down);
+        assert_eq!(sui.stopped_tasks, vec!["sui_syncer_foo".to_string()]);
+        // Successful queries of the other tasks don't hide it
+        metrics
+            .chain_health
+            .record_success(PipelineChain::Sui, &metrics);
+        assert_eq!(
+            metrics.chain_health.state(PipelineChain::Sui).status(),
+            PipelineStatus::Down
+        );
+        assert_eq!(
+            metrics.chain_health.state(PipelineChain::Eth).status(),
+            PipelineStatus::Unknown
+        );
+    }
+
+    #[tokio::test]
+    async fn test_restarts_out_of_window_are_forgotten() {
+        telemetry_subscribers::init_for_testing();
+        let metrics = Arc::new(BridgeMetrics::for_testing());
+        // Each restart happens after the previous one left the window
+        let policy = RestartPolicy {
+            initial_backoff: Duration::from_millis(60),
+            max_backoff: Duration::from_secs(10),
+            max_restarts: 1,
+            window: Duration::from_millis(50),
+        };
+        let starts = Arc::new(Mutex::new(vec![]));
+        TaskSupervisor::new(
+            "eth_syncer_foo",
+            PipelineChain::Eth,
+            policy,
+            metrics.clone(),
+        )
+        .supervise(mock_watcher(starts.clone(), 4))
+        .await;
+
+        // Restarted after each of the 4 panics, and returned on the 5th start
+        let starts = starts.lock().unwrap().clone();
+        assert_eq!(starts.len(), 5);
+        for pair in starts.windows(2) {
+            assert!(pair[1].0.duration_since(pair[0].0) >= Duration::from_millis(60));
+        }
+        assert_eq!(
+            metrics
+                .watcher_restarts
+                .with_label_values(&["eth_syncer_foo"])
+                .get(),
+            4
+        );
+        assert!(metrics
+            .chain_health
+            .state(PipelineChain::Eth)
+            .stopped_tasks
+            .is_empty());
+    }
+}
@@ -0,0 +1,303 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Opt-in telemetry. When enabled in the config, the node periodically posts a small
+//! summary of its health to the bridge team, so that fleet wide stats are at hand during
+//! incidents. The summary is derived from the metrics only, and `TelemetryPayload` is its
+//! whole schema: it has no room for keys, addresses or URLs. Failures to send it are only
+//! logged at debug level and counted.
+
+use crate::api_types::PipelineStatus;
+use crate::chain_health::PipelineChain;
+use crate::config::TelemetryConfig;
+use crate::metrics::{BridgeMetrics, MetricsPushClient};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use sui_types::crypto::NetworkKeyPair;
+use tokio::time;
+use tracing::{debug, info, warn};
+
+pub const TELEMETRY_SCHEMA_VERSION: u32 = 1;
+pub const DEFAULT_TELEMETRY_INTERVAL: Duration = Duration::from_secs(6 * 60 * 60);
+
+/// The fields of `TelemetryPayload`. A field can only be added to the payload together
+/// with its name here.
+pub const TELEMETRY_FIELDS: &[&str] = &[
+    "schema_version",
+    "version",
+    "uptime_seconds",
+    "sui_pipeline",
+    "eth_pipeline",
+    "last_synced_sui_checkpoint",
+    "eth_sync_lag_blocks",
+    "signing_error_ratio_5m",
+    "signing_error_ratio_1h",
+    "executor_queue_wait_p95_5m_secs",
+];
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case", deny_unknown_fields)]
+pub struct TelemetryPayload {
+    pub schema_version: u32,
+    pub version: Option<String>,
+    pub uptime_seconds: u64,
+    // Only the status, the last errors may contain the urls of the RPCs
+    pub sui_pipeline: PipelineStatus,
+    pub eth_pipeline: PipelineStatus,
+    pub last_synced_sui_checkpoint: u64,
+    /// Finalized Eth blocks that are not synced yet
+    pub eth_sync_lag_blocks: u64,
+    pub signing_error_ratio_5m: Option<f64>,
+    pub signing_error_ratio_1h: Option<f64>,
+    pub executor_queue_wait_p95_5m_secs: Option<f64>,
+}
+
+impl TelemetryPayload {
+    pub fn new(
+        version: Option<String>,
+        uptime: Duration,
+        metrics: &BridgeMetrics,
+        now: Instant,
+    ) -> Self {
+        let slo = metrics.slo.snapshot(now);
+        Self {
+            schema_version: TELEMETRY_SCHEMA_VERSION,
+            version,
+            uptime_seconds: uptime.as_secs(),
+            sui_pipeline: metrics
+                .chain_health
+                .state(PipelineChain::Sui)
+                .status()
+                .into(),
+            eth_pipeline: metrics
+                .chain_health
+                .state(PipelineChain::Eth)
+                .status()
+                .into(),
+            last_synced_sui_checkpoint: metrics.last_synced_sui_checkpoint.get().max(0) as u64,
+            eth_sync_lag_blocks: (metrics.last_finalized_eth_block.get()
+                - metrics.last_synced_eth_block.get())
+            .max(0) as u64,
+            signing_error_ratio_5m: slo.signing_error_ratio_5m,
+            signing_error_ratio_1h: slo.signing_error_ratio_1h,
+            executor_queue_wait_p95_5m_secs: slo.executor_queue_wait_p95_5m_secs,
+        }
+    }
+}
+
+/// Periodically posts a `TelemetryPayload` to the configured endpoint.
+pub struct TelemetryReporter {
+    client: MetricsPushClient,
+    endpoint: reqwest::Url,
+    interval: Duration,
+    log_payload: bool,
+    version: Option<String>,
+    started_at: Instant,
+    metrics: Arc<BridgeMetrics>,
+}
+
+impl TelemetryReporter {
+    /// Returns None unless telemetry is enabled with a valid endpoint.
+    pub fn new(
+        config: Option<&TelemetryConfig>,
+        metrics_key_pair: NetworkKeyPair,
+        version: Option<String>,
+        metrics: Arc<BridgeMetrics>,
+    ) -> Option<Self> {
+        let config = config.filter(|config| config.enabled)?;
+        let endpoint = match reqwest::Url::parse(&config.endpoint) {
+            Ok(endpoint) => endpoint,
+            Err(e) => {
+                warn!("Telemetry is disabled, invalid endpoint: {:?}", e);
+                return None;
+            }
+        };
+        Some(Self {
+            client: MetricsPushClient::new(metrics_key_pair),
+            endpoint,
+            interval: config
+                .interval_seconds
+                .map(Duration::from_secs)
+                .unwrap_or(DEFAULT_TELEMETRY_INTERVAL),
+            log_payload: config.log_payload,
+            version,
+            started_at: Instant::now(),
+            metrics,
+        })
+    }
+
+    pub async fn run(self) {
+        info!(endpoint =% self.endpoint, interval =? self.interval, "Starting TelemetryReporter");
+        let mut interval = time::interval(self.interval);
+        interval.set_missed_tick_behavior(time::MissedTickBehavior::Skip);
+        loop {
+            interval.tick().await;
+            self.report(Instant::now()).await;
+        }
+    }
+
+    async fn report(&self, now: Instant) {
+        let payload = TelemetryPayload::new(
+            self.version.clone(),
+            now.saturating_duration_since(self.started_at),
+            &self.metrics,
+            now,
+        );
+        if self.log_payload {
+            info!(
+                "Sending telemetry: {}",
+                serde_json::to_string(&payload).unwrap_or_default()
+            );
+        }
+        if let Err(e) = self.send(&payload).await {
+            debug!("Couldn't send telemetry: {:?}", e);
+            self.metrics.telemetry_failures.inc();
+        }
+    }
+
+    async fn send(&self, payload: &TelemetryPayload) -> anyhow::Result<()> {
+        self.client
+            .client()
+            .post(self.endpoint.clone())
+            .json(payload)
+            .timeout(Duration::from_secs(30))
+            .send()
+            .await?
+            .error_for_status()?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::default_ed25519_key_pair;
+    use crate::error::{BridgeError, ErrorKind};
+
+    fn telemetry_config(enabled: bool, endpoint: &str) -> TelemetryConfig {
+        TelemetryConfig {
+            enabled,
+            endpoint: endpoint.to_string(),
+            interval_seconds: None,
+            log_payload: false,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_telemetry_is_opt_in() {
+        let metrics = Arc::new(BridgeMetrics::for_testing());
+        let reporter = |config: Option<&TelemetryConfig>| {
+            TelemetryReporter::new(config, default_ed25519_key_pair(), None, metrics.clone())
+        };
+        assert!(reporter(None).is_none());
+        // Configuring an endpoint is not enough
+        let config: TelemetryConfig =
+            serde_yaml::from_str("endpoint: https://telemetry.example.com").unwrap();
+        assert!(!config.enabled);
+        assert!(reporter(Some(&config)).is_none());
+        assert!(reporter(Some(&telemetry_config(true, "not a url"))).is_none());
+
+        let reporter = reporter(Some(&telemetry_config(
+            true,
+            "https://telemetry.example.com",
+        )))
+        .unwrap();
+        assert_eq!(reporter.interval, DEFAULT_TELEMETRY_INTERVAL);
+    }
+
+    #[test]
+    fn test_telemetry_payload_schema() {
+        let metrics = BridgeMetrics::for_testing();
+        metrics.last_synced_sui_checkpoint.set(1234);
+        metrics.last_finalized_eth_block.set(100);
+        metrics.last_synced_eth_block.set(90);
+        metrics
+            .chain_health
+            .record::<()>(PipelineChain::Sui, &Ok(()), &metrics);
+        let payload = TelemetryPayload::new(
+            Some("1.2.3".to_string()),
+            Duration::from_secs(3600),
+            &metrics,
+            Instant::now(),
+        );
+
+        let json = serde_json::to_value(&payload).unwrap();
+        let mut fields = json
+            .as_object()
+            .unwrap()
+            .keys()
+            .map(|k| k.as_str())
+            .collect::<Vec<_>>();
+        let mut expected = TELEMETRY_FIELDS.to_vec();
+        fields.sort();
+        expected.sort();
+        assert_eq!(fields, expected);
+        assert_eq!(json["schema_version"], TELEMETRY_SCHEMA_VERSION);
+        assert_eq!(json["version"], "1.2.3");
+        assert_eq!(json["uptime_seconds"], 3600);
+        assert_eq!(json["sui_pipeline"], "healthy");
+        assert_eq!(json["eth_pipeline"], "unknown");
+        assert_eq!(json["last_synced_sui_checkpoint"], 1234);
+        assert_eq!(json["eth_sync_lag_blocks"], 10);
+        assert!(json["signing_error_ratio_5m"].is_null());
+
+        // Unknown fields are rejected
+        let mut extended = json.clone();
+        extended["eth_rpc_url"] = "https://eth.example.com".into();
+        assert!(serde_json::from_value::<TelemetryPayload>(extended).is_err());
+        assert_eq!(
+            serde_json::from_value::<TelemetryPayload>(json).unwrap(),
+            payload
+        );
+    }
+
+    #[test]
+    fn test_telemetry_payload_has_no_sensitive_data() {
+        for field in TELEMETRY_FIELDS {
+            assert!(
+                ["key", "url", "address", "error"]
+                    .iter()
+                    .all(|sensitive| !field.contains(sensitive)),
+                "{} may not be sent",
+                field
+            );
+        }
+
+        // The errors of the pipelines contain the urls of the RPCs
+        let metrics = BridgeMetrics::for_testing();
+        let rpc_url = "https://eth-mainnet.example.com/v2/secret-api-key";
+        metrics.chain_health.record::<()>(
+            PipelineChain::Eth,
+            &Err(BridgeError::ProviderError(
+                ErrorKind::Transient,
+                format!("error sending request for url ({})", rpc_url),
+            )),
+            &metrics,
+        );
+        let payload = TelemetryPayload::new(None, Duration::ZERO, &metrics, Instant::now());
+        assert_eq!(payload.eth_pipeline, PipelineStatus::Degraded);
+        let json = serde_json::to_string(&payload).unwrap();
+        assert!(!json.contains("secret-api-key"));
+        assert!(!json.contains("example.com"));
+    }
+
+    #[tokio::test]
+    async fn test_telemetry_failures_are_counted() {
+        // Nothing listens on the port once the listener is dropped
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let endpoint = format!("http://{}", listener.local_addr().unwrap());
+        drop(listener);
+
+        let metrics = Arc::new(BridgeMetrics::for_testing());
+        let reporter = TelemetryReporter::new(
+            Some(&telemetry_config(true, &endpoint)),
+            default_ed25519_key_pair(),
+            None,
+            metrics.clone(),
+        )
+        .unwrap();
+        reporter.report(Instant::now()).await;
+        assert_eq!(metrics.telemetry_failures.get(), 1);
+    }
+}
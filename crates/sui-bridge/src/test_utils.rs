@@ -1,10 +1,11 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::abi::EthToSuiTokenBridgeV1;
+use crate::abi::{eth_bridge_event_topics, EthToSuiTokenBridgeV1};
 use crate::eth_mock_provider::EthMockProvider;
 use crate::events::SuiBridgeEvent;
 use crate::server::mock_handler::run_mock_server;
+use crate::signer::{RemoteSignRequest, RemoteSignResponse};
 use crate::sui_transaction_builder::build_sui_transaction;
 use crate::types::{
     BridgeCommittee, BridgeCommitteeValiditySignInfo, CertifiedBridgeAction,
@@ -19,20 +20,24 @@ use crate::{
         SuiToEthBridgeAction,
     },
 };
-use ethers::abi::{long_signature, ParamType};
+use ethers::abi::{long_signature, ParamType, Token};
+use ethers::types::transaction::eip2718::TypedTransaction;
 use ethers::types::Address as EthAddress;
 use ethers::types::{
-    Block, BlockNumber, Filter, FilterBlockOption, Log, TransactionReceipt, TxHash, ValueOrArray,
-    U64,
+    Block, BlockId, BlockNumber, Bytes, Filter, Log, TransactionReceipt, TxHash, H256, U64,
 };
 use fastcrypto::encoding::{Encoding, Hex};
-use fastcrypto::traits::KeyPair;
+use fastcrypto::hash::{HashFunction, Keccak256};
+use fastcrypto::traits::{KeyPair, RecoverableSigner};
 use hex_literal::hex;
 use move_core_types::language_storage::TypeTag;
 use std::collections::{BTreeMap, HashMap};
 use std::net::IpAddr;
 use std::net::Ipv4Addr;
 use std::net::SocketAddr;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
 use sui_config::local_ip_utils;
 use sui_json_rpc_types::SuiTransactionBlockEffectsAPI;
 use sui_sdk::wallet_context::WalletContext;
@@ -178,6 +183,47 @@ pub fn mock_last_finalized_block(mock_provider: &EthMockProvider, block_number:
         .unwrap();
 }
 
+/// Waits for `gauge` to be set to `value`, panics after 5 seconds.
+pub async fn wait_for_gauge(gauge: &prometheus::IntGauge, value: i64) {
+    let waited = tokio::time::timeout(std::time::Duration::from_secs(5), async {
+        while gauge.get() != value {
+            tokio::time::sleep(std::time::Duration::from_millis(5)).await;
+        }
+    })
+    .await;
+    assert!(
+        waited.is_ok(),
+        "Gauge is {} instead of {}",
+        gauge.get(),
+        value
+    );
+}
+
+pub fn mock_latest_block_timestamp(mock_provider: &EthMockProvider, timestamp: u64) {
+    let block = Block::<ethers::types::TxHash> {
+        timestamp: timestamp.into(),
+        ..Default::default()
+    };
+    mock_provider
+        .add_response("eth_getBlockByNumber", ("latest", false), block)
+        .unwrap();
+}
+
+pub fn mock_block_timestamp(mock_provider: &EthMockProvider, block_number: u64, timestamp: u64) {
+    let block = Block::<ethers::types::TxHash> {
+        number: Some(U64::from(block_number)),
+        timestamp: timestamp.into(),
+        ..Default::default()
+    };
+    mock_provider
+        .add_response(
+            "eth_getBlockByNumber",
+            (U64::from(block_number), false),
+            block,
+        )
+        .unwrap();
+}
+
 // Mocks eth_getLogs and eth_getTransactionReceipt for the given address and block range.
 // The input log needs to have transaction_hash set.
 pub fn mock_get_logs(
@@ -190,14 +236,11 @@ pub fn mock_get_logs(
     mock_provider.add_response::<[ethers::types::Filter; 1], Vec<ethers::types::Log>, Vec<ethers::types::Log>>(
         "eth_getLogs",
         [
-            Filter {
-                block_option: FilterBlockOption::Range {
-                    from_block: Some(BlockNumber::Number(U64::from(from_block))),
-                    to_block: Some(BlockNumber::Number(U64::from(to_block))),
-                },
-                address: Some(ValueOrArray::Value(address)),
-                topics: [None, None, None, None],
-            }
+            Filter::new()
+                .from_block(from_block)
+                .to_block(to_block)
+                .address(address)
+                .topic0(eth_bridge_event_topics())
         ],
         logs.clone(),
     ).unwrap();
@@ -217,12 +260,44 @@ pub fn mock_get_logs(
     }
 }
 
+// Mocks eth_call of the contract call `tx` on the latest block, returning `output`.
+pub fn mock_eth_call(mock_provider: &EthMockProvider, tx: &TypedTransaction, output: &[Token]) {
+    mock_provider
+        .add_response::<[serde_json::Value; 2], Bytes, Bytes>(
+            "eth_call",
+            [
+                serde_json::to_value(tx).unwrap(),
+                serde_json::to_value(BlockId::from(BlockNumber::Latest)).unwrap(),
+            ],
+            Bytes::from(ethers::abi::encode(output)),
+        )
+        .unwrap();
+}
+
 /// Returns a test Log and corresponding BridgeAction
-// Refernece: https://github.com/rust-ethereum/ethabi/blob/master/ethabi/src/event.rs#L192
 pub fn get_test_log_and_action(
     contract_address: EthAddress,
     tx_hash: TxHash,
     event_index: u16,
+) -> (Log, BridgeAction) {
+    get_test_log_and_action_with_chain_ids(
+        contract_address,
+        tx_hash,
+        event_index,
+        BridgeChainId::SuiTestnet,
+        BridgeChainId::EthSepolia,
+    )
+}
+
+/// Returns a test Log and corresponding BridgeAction of a deposit from `source_chain_id`
+/// to `destination_chain_id`
+// Refernece: https://github.com/rust-ethereum/ethabi/blob/master/ethabi/src/event.rs#L192
+pub fn get_test_log_and_action_with_chain_ids(
+    contract_address: EthAddress,
+    tx_hash: TxHash,
+    event_index: u16,
+    source_chain_id: BridgeChainId,
+    destination_chain_id: BridgeChainId,
 ) -> (Log, BridgeAction) {
     let token_id = 3u8;
     let sui_adjusted_amount = 10000000u64;
@@ -252,9 +327,9 @@ pub fn get_test_log_and_action(
                     ParamType::Bytes,
                 ],
             ),
-            hex!("0000000000000000000000000000000000000000000000000000000000000001").into(), // chain id: sui testnet
+            H256::from_low_u64_be(source_chain_id as u64),
             hex!("0000000000000000000000000000000000000000000000000000000000000010").into(), // nonce: 16
-            hex!("000000000000000000000000000000000000000000000000000000000000000b").into(), // chain id: sepolia
+            H256::from_low_u64_be(destination_chain_id as u64),
         ],
         data: encoded.into(),
         block_hash: Some(TxHash::random()),
@@ -422,3 +497,67 @@ pub fn bridge_committee_to_bridge_committee_summary(
         last_committee_update_epoch: 0,
     }
 }
+
+/// A remote signer that signs the digests posted to it with its key, like the signer
+/// service of an operator, see `signer`. Serves plain HTTP.
+#[derive(Clone)]
+pub struct RemoteSignerStub {
+    address: SocketAddr,
+    state: Arc<RemoteSignerStubState>,
+}
+
+struct RemoteSignerStubState {
+    key: BridgeAuthorityKeyPair,
+    delay: std::sync::Mutex<Duration>,
+    requests: AtomicUsize,
+}
+
+impl RemoteSignerStub {
+    pub async fn start(key: BridgeAuthorityKeyPair) -> Self {
+        let state = Arc::new(RemoteSignerStubState {
+            key,
+            delay: std::sync::Mutex::new(Duration::ZERO),
+            requests: AtomicUsize::new(0),
+        });
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let address = listener.local_addr().unwrap();
+        let router = axum::Router::new()
+            .route("/sign", axum::routing::post(remote_sign))
+            .with_state(state.clone());
+        tokio::spawn(async move { axum::serve(listener, router).await.unwrap() });
+        Self { address, state }
+    }
+
+    pub fn url(&self) -> reqwest::Url {
+        reqwest::Url::parse(&format!("http://{}/sign", self.address)).unwrap()
+    }
+
+    /// Number of requests received, answered or not.
+    pub fn requests(&self) -> usize {
+        self.state.requests.load(Ordering::SeqCst)
+    }
+
+    /// Answers the requests after `delay`.
+    pub fn set_delay(&self, delay: Duration) {
+        *self.state.delay.lock().unwrap() = delay;
+    }
+}
+
+async fn remote_sign(
+    axum::extract::State(state): axum::extract::State<Arc<RemoteSignerStubState>>,
+    axum::Json(request): axum::Json<RemoteSignRequest>,
+) -> Result<axum::Json<RemoteSignResponse>, (axum::http::StatusCode, String)> {
+    state.requests.fetch_add(1, Ordering::SeqCst);
+    let delay = *state.delay.lock().unwrap();
+    tokio::time::sleep(delay).await;
+    let bad_request = |e: String| (axum::http::StatusCode::BAD_REQUEST, e);
+    let message = Hex::decode(&request.message).map_err(|e| bad_request(e.to_string()))?;
+    // Only what the digest is of is signed
+    if Hex::encode(Keccak256::digest(&message).digest) != request.digest {
+        return Err(bad_request("Digest of another message".to_string()));
+    }
+    let signature = state.key.sign_recoverable_with_hash::<Keccak256>(&message);
+    Ok(axum::Json(RemoteSignResponse {
+        signature: Hex::encode(signature.as_bytes()),
+    }))
+}
@@ -0,0 +1,174 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Sanity checks of the timestamps that the chains report for the events of actions, i.e.
+//! of Eth blocks and Sui checkpoints. Providers may report timestamps in the future, or
+//! garbage far in the past. Timestamps ahead of the local clock by more than the allowed
+//! skew, or older than the allowed age, are counted in `bridge_timestamp_anomalies`.
+//! Timestamps in the future are clamped to the local time and ancient ones are rejected,
+//! so that durations computed from them are never negative nor absurdly long.
+
+use crate::metrics::BridgeMetrics;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use tracing::warn;
+
+pub const DEFAULT_MAX_TIMESTAMP_SKEW: Duration = Duration::from_secs(60);
+// Longer than any plausible catch-up of the syncers after downtime
+pub const DEFAULT_MAX_TIMESTAMP_AGE: Duration = Duration::from_secs(30 * 24 * 60 * 60);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TimestampSource {
+    EthBlock,
+    SuiCheckpoint,
+}
+
+impl TimestampSource {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            TimestampSource::EthBlock => "eth_block",
+            TimestampSource::SuiCheckpoint => "sui_checkpoint",
+        }
+    }
+}
+
+/// Milliseconds since the Unix epoch on the local clock.
+pub fn now_ms() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .expect("Time went backwards")
+        .as_millis() as u64
+}
+
+/// Milliseconds from `earlier_ms` to `later_ms`, 0 if `earlier_ms` is actually later.
+pub fn elapsed_ms(earlier_ms: u64, later_ms: u64) -> u64 {
+    later_ms.saturating_sub(earlier_ms)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TimestampSanitizer {
+    max_skew: Duration,
+    max_age: Duration,
+}
+
+impl Default for TimestampSanitizer {
+    fn default() -> Self {
+        Self::new(DEFAULT_MAX_TIMESTAMP_SKEW, DEFAULT_MAX_TIMESTAMP_AGE)
+    }
+}
+
+impl TimestampSanitizer {
+    pub fn new(max_skew: Duration, max_age: Duration) -> Self {
+        Self { max_skew, max_age }
+    }
+
+    /// Returns `timestamp_ms` of `source`, clamped to the local time if it is in the
+    /// future, or None if it is older than the maximum age.
+    pub fn sanitize(
+        &self,
+        source: TimestampSource,
+        timestamp_ms: u64,
+        metrics: &BridgeMetrics,
+    ) -> Option<u64> {
+        self.sanitize_at(source, timestamp_ms, now_ms(), metrics)
+    }
+
+    fn sanitize_at(
+        &self,
+        source: TimestampSource,
+        timestamp_ms: u64,
+        now_ms: u64,
+        metrics: &BridgeMetrics,
+    ) -> Option<u64> {
+        if timestamp_ms > now_ms {
+            // Clocks may disagree slightly, only larger differences are anomalies
+            if elapsed_ms(now_ms, timestamp_ms) > self.max_skew.as_millis() as u64 {
+                warn!(
+                    "{} timestamp {} is ahead of the local time {}, clamping it",
+                    source.as_str(),
+                    timestamp_ms,
+                    now_ms
+                );
+                record_anomaly(metrics, source, "future");
+            }
+            return Some(now_ms);
+        }
+        if elapsed_ms(timestamp_ms, now_ms) > self.max_age.as_millis() as u64 {
+            warn!(
+                "{} timestamp {} is older than {:?}, ignoring it",
+                source.as_str(),
+                timestamp_ms,
+                self.max_age
+            );
+            record_anomaly(metrics, source, "ancient");
+            return None;
+        }
+        Some(timestamp_ms)
+    }
+}
+
+fn record_anomaly(metrics: &BridgeMetrics, source: TimestampSource, kind: &str) {
+    metrics
+        .timestamp_anomalies
+        .with_label_values(&[source.as_str(), kind])
+        .inc();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_timestamp_sanitizer() {
+        let metrics = BridgeMetrics::for_testing();
+        let sanitizer = TimestampSanitizer::new(Duration::from_secs(60), Duration::from_secs(3600));
+        let now_ms = 1_700_000_000_000;
+        let sanitize =
+            |source, timestamp_ms| sanitizer.sanitize_at(source, timestamp_ms, now_ms, &metrics);
+        let anomalies = |source: TimestampSource, kind: &str| {
+            metrics
+                .timestamp_anomalies
+                .with_label_values(&[source.as_str(), kind])
+                .get()
+        };
+
+        // Within the window
+        assert_eq!(
+            sanitize(TimestampSource::EthBlock, now_ms - 720_000),
+            Some(now_ms - 720_000)
+        );
+        assert_eq!(
+            sanitize(TimestampSource::SuiCheckpoint, now_ms),
+            Some(now_ms)
+        );
+        // Slightly ahead, clamped without an anomaly
+        assert_eq!(
+            sanitize(TimestampSource::SuiCheckpoint, now_ms + 60_000),
+            Some(now_ms)
+        );
+        assert_eq!(anomalies(TimestampSource::SuiCheckpoint, "future"), 0);
+
+        // Minutes in the future
+        assert_eq!(
+            sanitize(TimestampSource::EthBlock, now_ms + 5 * 60_000),
+            Some(now_ms)
+        );
+        assert_eq!(sanitize(TimestampSource::EthBlock, u64::MAX), Some(now_ms));
+        assert_eq!(anomalies(TimestampSource::EthBlock, "future"), 2);
+
+        // Ancient
+        assert_eq!(sanitize(TimestampSource::SuiCheckpoint, 0), None);
+        assert_eq!(
+            sanitize(TimestampSource::EthBlock, now_ms - 3_600_001),
+            None
+        );
+        assert_eq!(anomalies(TimestampSource::SuiCheckpoint, "ancient"), 1);
+        assert_eq!(anomalies(TimestampSource::EthBlock, "ancient"), 1);
+    }
+
+    #[test]
+    fn test_elapsed_is_never_negative() {
+        assert_eq!(elapsed_ms(1_000, 3_000), 2_000);
+        assert_eq!(elapsed_ms(3_000, 1_000), 0);
+        assert_eq!(elapsed_ms(u64::MAX, 0), 0);
+    }
+}
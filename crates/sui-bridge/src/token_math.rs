@@ -0,0 +1,246 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Decimal arithmetic of token amounts. Token transfers carry amounts in the decimals of
+//! their token on Sui, `SuiAmount`, which the SuiBridge contract converts to the decimals
+//! of the ERC20 token, `EthAmount`. Amounts are converted exactly or not at all: an amount
+//! with more precision than the destination decimals can represent, or too large for the
+//! destination type, is an error instead of being rounded or truncated.
+//!
+//! No other module multiplies or divides token amounts by powers of ten.
+
+use crate::error::{BridgeError, BridgeResult};
+use ethers::types::U256;
+use serde::{Deserialize, Serialize};
+
+/// An amount in the decimals of its token on Sui.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub struct SuiAmount(pub u64);
+
+/// An amount in the decimals of its ERC20 token on Eth.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub struct EthAmount(pub U256);
+
+/// Decimals of a token on Sui and of its ERC20 token on Eth.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TokenDecimals {
+    pub sui: u8,
+    pub eth: u8,
+}
+
+impl TokenDecimals {
+    pub fn to_eth(&self, amount: SuiAmount) -> BridgeResult<EthAmount> {
+        rescale(U256::from(amount.0), self.sui, self.eth).map(EthAmount)
+    }
+
+    pub fn to_sui(&self, amount: EthAmount) -> BridgeResult<SuiAmount> {
+        let converted = rescale(amount.0, self.eth, self.sui)?;
+        if converted > U256::from(u64::MAX) {
+            return Err(BridgeError::AmountPrecision(format!(
+                "{} with {} decimals overflows u64 with {} decimals",
+                amount.0, self.eth, self.sui
+            )));
+        }
+        Ok(SuiAmount(converted.as_u64()))
+    }
+}
+
+// 10^exp, None if it overflows U256
+fn pow10(exp: u8) -> Option<U256> {
+    U256::from(10).checked_pow(U256::from(exp))
+}
+
+// Converts `amount` from `from` to `to` decimals, exactly
+fn rescale(amount: U256, from: u8, to: u8) -> BridgeResult<U256> {
+    if to >= from {
+        // A multiplier beyond U256 only multiplies zero without overflowing
+        let converted = match pow10(to - from) {
+            Some(multiplier) => amount.checked_mul(multiplier),
+            None => amount.is_zero().then(U256::zero),
+        };
+        return converted.ok_or_else(|| {
+            BridgeError::AmountPrecision(format!(
+                "{} with {} decimals overflows with {} decimals",
+                amount, from, to
+            ))
+        });
+    }
+    // A divisor beyond U256 only divides zero exactly
+    let exact = match pow10(from - to) {
+        Some(divisor) => (amount % divisor).is_zero().then(|| amount / divisor),
+        None => amount.is_zero().then(U256::zero),
+    };
+    exact.ok_or_else(|| {
+        BridgeError::AmountPrecision(format!(
+            "{} with {} decimals can't be represented with {} decimals",
+            amount, from, to
+        ))
+    })
+}
+
+/// Returns the number of units of one token with `decimals`, i.e. 10^`decimals`.
+pub fn decimal_multiplier(decimals: u8) -> BridgeResult<u64> {
+    10u64
+        .checked_pow(decimals as u32)
+        .ok_or_else(|| BridgeError::AmountPrecision(format!("{} decimals overflow u64", decimals)))
+}
+
+/// Returns the decimals whose multiplier is `multiplier`, which must be a power of ten.
+pub fn decimals_of_multiplier(multiplier: u64) -> BridgeResult<u8> {
+    let mut decimals = 0;
+    let mut rest = multiplier;
+    while rest > 1 && rest % 10 == 0 {
+        rest /= 10;
+        decimals += 1;
+    }
+    if rest != 1 {
+        return Err(BridgeError::AmountPrecision(format!(
+            "Decimal multiplier {} is not a power of ten",
+            multiplier
+        )));
+    }
+    Ok(decimals)
+}
+
+/// Returns the USD value (with 8 decimal places) of `amount`, given the USD price (with 8
+/// decimal places) of one token and its decimal multiplier on Sui. The value is rounded
+/// down like the limiter on chain does. Returns None if the token has no price.
+pub fn notional_value(amount: SuiAmount, price: u64, decimal_multiplier: u64) -> Option<u64> {
+    if price == 0 || decimal_multiplier == 0 {
+        return None;
+    }
+    let value = amount.0 as u128 * price as u128 / decimal_multiplier as u128;
+    Some(u64::try_from(value).unwrap_or(u64::MAX))
+}
+
+/// Returns the smallest amount of a token that is worth at least `min_usd_value`, the
+/// inverse of `notional_value` rounded up. Returns None if the token has no price.
+pub fn min_amount_of_value(
+    min_usd_value: u64,
+    price: u64,
+    decimal_multiplier: u64,
+) -> Option<SuiAmount> {
+    if price == 0 {
+        return None;
+    }
+    let amount = (min_usd_value as u128 * decimal_multiplier as u128).div_ceil(price as u128);
+    Some(SuiAmount(u64::try_from(amount).unwrap_or(u64::MAX)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    const USDC: TokenDecimals = TokenDecimals { sui: 6, eth: 6 };
+    const ETH: TokenDecimals = TokenDecimals { sui: 8, eth: 18 };
+
+    #[test]
+    fn test_token_decimals_conversions() {
+        assert_eq!(
+            ETH.to_eth(SuiAmount(150_000_000)).unwrap(),
+            EthAmount(U256::from(1_500_000_000_000_000_000u64))
+        );
+        assert_eq!(
+            ETH.to_sui(EthAmount(U256::from(1_500_000_000_000_000_000u64)))
+                .unwrap(),
+            SuiAmount(150_000_000)
+        );
+        assert_eq!(
+            USDC.to_eth(SuiAmount(u64::MAX)).unwrap(),
+            EthAmount(U256::from(u64::MAX))
+        );
+        // Wei below the Sui decimals are not truncated
+        assert!(matches!(
+            ETH.to_sui(EthAmount(U256::from(1_500_000_000_000_000_001u64))),
+            Err(BridgeError::AmountPrecision(_))
+        ));
+        // Nor are amounts too large for u64 on Sui
+        assert!(matches!(
+            ETH.to_sui(EthAmount(
+                (U256::from(u64::MAX) + 1) * U256::from(10_000_000_000u64)
+            )),
+            Err(BridgeError::AmountPrecision(_))
+        ));
+        // Tokens with fewer decimals on Eth than on Sui
+        let fewer = TokenDecimals { sui: 9, eth: 6 };
+        assert_eq!(
+            fewer.to_eth(SuiAmount(1_000)).unwrap(),
+            EthAmount(U256::one())
+        );
+        assert!(matches!(
+            fewer.to_eth(SuiAmount(1_001)),
+            Err(BridgeError::AmountPrecision(_))
+        ));
+        // u64 amounts overflow U256 only beyond 57 decimals of difference
+        let max = TokenDecimals { sui: 0, eth: 57 };
+        max.to_eth(SuiAmount(u64::MAX)).unwrap();
+        let beyond = TokenDecimals { sui: 0, eth: 58 };
+        assert!(beyond.to_eth(SuiAmount(u64::MAX)).is_err());
+        let huge = TokenDecimals {
+            sui: 0,
+            eth: u8::MAX,
+        };
+        assert!(huge.to_eth(SuiAmount(1)).is_err());
+        assert_eq!(huge.to_eth(SuiAmount(0)).unwrap(), EthAmount(U256::zero()));
+        assert!(huge.to_sui(EthAmount(U256::MAX)).is_err());
+    }
+
+    #[test]
+    fn test_decimal_multipliers() {
+        assert_eq!(decimal_multiplier(0).unwrap(), 1);
+        assert_eq!(decimal_multiplier(9).unwrap(), 1_000_000_000);
+        assert_eq!(decimal_multiplier(19).unwrap(), 10_000_000_000_000_000_000);
+        assert!(decimal_multiplier(20).is_err());
+        for decimals in 0..=19 {
+            assert_eq!(
+                decimals_of_multiplier(decimal_multiplier(decimals).unwrap()).unwrap(),
+                decimals
+            );
+        }
+        assert!(decimals_of_multiplier(0).is_err());
+        assert!(decimals_of_multiplier(2_000).is_err());
+        assert!(decimals_of_multiplier(1_001).is_err());
+    }
+
+    proptest! {
+        #[test]
+        fn test_sui_amounts_round_trip(amount: u64, sui in 0u8..=19, eth in 0u8..=u8::MAX) {
+            let decimals = TokenDecimals { sui, eth };
+            // Converting never changes the value: either the amount comes back unchanged
+            // or the conversion is refused
+            if let Ok(converted) = decimals.to_eth(SuiAmount(amount)) {
+                prop_assert_eq!(decimals.to_sui(converted).unwrap(), SuiAmount(amount));
+            } else {
+                prop_assert!(eth > sui + 57 || eth < sui);
+            }
+            if eth >= sui && eth - sui <= 57 {
+                prop_assert!(decimals.to_eth(SuiAmount(amount)).is_ok());
+            }
+        }
+
+        #[test]
+        fn test_eth_amounts_round_trip(limbs: [u64; 4], sui in 0u8..=19, eth in 0u8..=77) {
+            let amount = EthAmount(U256(limbs));
+            let decimals = TokenDecimals { sui, eth };
+            if let Ok(converted) = decimals.to_sui(amount) {
+                prop_assert_eq!(decimals.to_eth(converted).unwrap(), amount);
+            }
+        }
+
+        #[test]
+        fn test_notional_value_never_overflows(
+            amount: u64,
+            price: u64,
+            decimals in 0u8..=19,
+        ) {
+            let multiplier = decimal_multiplier(decimals).unwrap();
+            let value = notional_value(SuiAmount(amount), price, multiplier);
+            prop_assert_eq!(value.is_none(), price == 0);
+            if let Some(minimum) = min_amount_of_value(value.unwrap_or(0), price, multiplier) {
+                // The minimum worth the value of `amount` is at most `amount`
+                prop_assert!(minimum <= SuiAmount(amount));
+            }
+        }
+    }
+}
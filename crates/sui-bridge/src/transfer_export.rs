@@ -0,0 +1,293 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Lists the executed token transfers of the client storage, for the reconciliation
+//! systems of exchanges and other integrations. Transfers are read in the order of their
+//! keys, i.e. by route and nonce, so that a listing that stopped, e.g. at a page limit,
+//! resumes after the cursor of the last transfer it returned without gaps or duplicates.
+//! Transfers are streamed from the db, an export of the whole history doesn't hold it in
+//! memory. Pruned transfers are not listed, see `sui-bridge-cli archive`.
+
+use crate::api_types::{TransferPage, TransferRecord};
+use crate::error::{BridgeError, BridgeResult};
+use crate::storage::BridgeOrchestratorTables;
+use std::io::Write;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum TransferExportFormat {
+    /// Comma separated values with a header row, in the order of the fields of
+    /// `TransferRecord`
+    Csv,
+    /// One JSON `TransferRecord` per line
+    Jsonl,
+}
+
+/// The transfers to list. Unset bounds don't filter.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TransferFilter {
+    /// Key of the last transfer that was listed, the listing resumes after it
+    pub after: Option<(u8, u8, u64)>,
+    /// Inclusive bound of the time the transfers were recorded as executed. Transfers
+    /// without a time are left out when a bound is set.
+    pub from_ms: Option<u64>,
+    /// Exclusive bound of the time the transfers were recorded as executed
+    pub to_ms: Option<u64>,
+    /// Lists at most this many transfers
+    pub limit: Option<u64>,
+}
+
+impl TransferFilter {
+    fn matches(&self, executed_at_ms: Option<u64>) -> bool {
+        if self.from_ms.is_none() && self.to_ms.is_none() {
+            return true;
+        }
+        let Some(executed_at_ms) = executed_at_ms else {
+            return false;
+        };
+        self.from_ms
+            .map_or(true, |from_ms| executed_at_ms >= from_ms)
+            && self.to_ms.map_or(true, |to_ms| executed_at_ms < to_ms)
+    }
+}
+
+/// Returns the transfers of `filter`, read from `store` as the iterator advances.
+pub fn iter_transfer_records<'a>(
+    store: &'a BridgeOrchestratorTables,
+    filter: &'a TransferFilter,
+) -> BridgeResult<impl Iterator<Item = BridgeResult<TransferRecord>> + 'a> {
+    Ok(store
+        .iter_executed_transfers(filter.after)?
+        .filter_map(|transfer| match transfer {
+            Ok((key, action, executed_at_ms)) => {
+                if !filter.matches(executed_at_ms) {
+                    return None;
+                }
+                TransferRecord::new(key, &action, executed_at_ms).map(Ok)
+            }
+            Err(e) => Some(Err(e)),
+        })
+        .take(filter.limit.map_or(usize::MAX, |limit| limit as usize)))
+}
+
+/// Returns a page of the transfers of `filter`, see `/transfers`.
+pub fn get_transfer_page(
+    store: &BridgeOrchestratorTables,
+    filter: &TransferFilter,
+) -> BridgeResult<TransferPage> {
+    let transfers = iter_transfer_records(store, filter)?.collect::<BridgeResult<Vec<_>>>()?;
+    // A full page may be followed by more transfers
+    let next_cursor = match filter.limit {
+        Some(limit) if transfers.len() as u64 == limit => {
+            transfers.last().map(|transfer| transfer.cursor.clone())
+        }
+        _ => None,
+    };
+    Ok(TransferPage {
+        transfers,
+        next_cursor,
+    })
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TransferExportSummary {
+    pub exported: u64,
+    /// Cursor of the last exported transfer, to resume the export after it
+    pub last_cursor: Option<String>,
+}
+
+const CSV_HEADER: [&str; 11] = [
+    "cursor",
+    "nonce",
+    "source_chain_id",
+    "destination_chain_id",
+    "source_tx",
+    "source_event_index",
+    "sender",
+    "recipient",
+    "token_id",
+    "amount",
+    "executed_at_ms",
+];
+
+fn csv_field(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+fn csv_row(record: &TransferRecord) -> String {
+    [
+        csv_field(&record.cursor),
+        record.nonce.to_string(),
+        record.source_chain_id.to_string(),
+        record.destination_chain_id.to_string(),
+        csv_field(&record.source_tx),
+        record.source_event_index.to_string(),
+        csv_field(&record.sender),
+        csv_field(&record.recipient),
+        record.token_id.to_string(),
+        record.amount.to_string(),
+        record
+            .executed_at_ms
+            .map(|executed_at_ms| executed_at_ms.to_string())
+            .unwrap_or_default(),
+    ]
+    .join(",")
+}
+
+/// Writes the transfers of `filter` to `out` in `format`, one at a time. Every CSV export
+/// starts with the header row.
+pub fn export_transfers(
+    store: &BridgeOrchestratorTables,
+    filter: &TransferFilter,
+    format: TransferExportFormat,
+    out: &mut impl Write,
+) -> BridgeResult<TransferExportSummary> {
+    let write_error =
+        |e: std::io::Error| BridgeError::Generic(format!("Couldn't write the export: {:?}", e));
+    if format == TransferExportFormat::Csv {
+        writeln!(out, "{}", CSV_HEADER.join(",")).map_err(write_error)?;
+    }
+    let mut summary = TransferExportSummary::default();
+    for record in iter_transfer_records(store, filter)? {
+        let record = record?;
+        match format {
+            TransferExportFormat::Csv => writeln!(out, "{}", csv_row(&record)),
+            TransferExportFormat::Jsonl => writeln!(
+                out,
+                "{}",
+                serde_json::to_string(&record).expect("TransferRecord serializes")
+            ),
+        }
+        .map_err(write_error)?;
+        summary.exported += 1;
+        summary.last_cursor = Some(record.cursor);
+    }
+    out.flush().map_err(write_error)?;
+    Ok(summary)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api_types::parse_transfer_cursor;
+    use crate::test_utils::{get_test_eth_to_sui_bridge_action, get_test_sui_to_eth_bridge_action};
+
+    const TRANSFERS_PER_ROUTE: u64 = 5000;
+
+    fn store_with_transfers() -> (tempfile::TempDir, std::sync::Arc<BridgeOrchestratorTables>) {
+        let dir = tempfile::tempdir().unwrap();
+        let store = BridgeOrchestratorTables::new(dir.path());
+        for nonce in 0..TRANSFERS_PER_ROUTE {
+            let actions = [
+                get_test_sui_to_eth_bridge_action(None, None, Some(nonce), None, None, None, None),
+                get_test_eth_to_sui_bridge_action(Some(nonce), None, None, None),
+            ];
+            store
+                .mark_actions_executed_at(&actions, 1_000_000 + nonce)
+                .unwrap();
+        }
+        (dir, store)
+    }
+
+    fn export_jsonl(
+        store: &BridgeOrchestratorTables,
+        filter: &TransferFilter,
+    ) -> (Vec<TransferRecord>, TransferExportSummary) {
+        let mut out = vec![];
+        let summary =
+            export_transfers(store, filter, TransferExportFormat::Jsonl, &mut out).unwrap();
+        let records = String::from_utf8(out)
+            .unwrap()
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect::<Vec<TransferRecord>>();
+        assert_eq!(records.len() as u64, summary.exported);
+        (records, summary)
+    }
+
+    #[test]
+    fn test_export_resumes_from_cursor() {
+        let (_dir, store) = store_with_transfers();
+        let (all, summary) = export_jsonl(&store, &TransferFilter::default());
+        assert_eq!(all.len() as u64, 2 * TRANSFERS_PER_ROUTE);
+        assert_eq!(summary.last_cursor, all.last().map(|r| r.cursor.clone()));
+        let keys = all
+            .iter()
+            .map(|record| parse_transfer_cursor(&record.cursor).unwrap())
+            .collect::<Vec<_>>();
+        assert!(keys.windows(2).all(|pair| pair[0] < pair[1]));
+
+        // Exported in chunks, each resuming after the last cursor of the previous one
+        let mut resumed = vec![];
+        let mut after = None;
+        loop {
+            let filter = TransferFilter {
+                after,
+                limit: Some(3333),
+                ..Default::default()
+            };
+            let (records, summary) = export_jsonl(&store, &filter);
+            resumed.extend(records);
+            match summary.last_cursor {
+                Some(cursor) => after = Some(parse_transfer_cursor(&cursor).unwrap()),
+                None => break,
+            }
+        }
+        assert_eq!(resumed, all);
+
+        // Pages of `/transfers` resume the same way
+        let mut paged = vec![];
+        let mut after = None;
+        loop {
+            let page = get_transfer_page(
+                &store,
+                &TransferFilter {
+                    after,
+                    limit: Some(4000),
+                    ..Default::default()
+                },
+            )
+            .unwrap();
+            paged.extend(page.transfers);
+            match page.next_cursor {
+                Some(cursor) => after = Some(parse_transfer_cursor(&cursor).unwrap()),
+                None => break,
+            }
+        }
+        assert_eq!(paged, all);
+    }
+
+    #[test]
+    fn test_export_filters_and_formats() {
+        let (_dir, store) = store_with_transfers();
+        let filter = TransferFilter {
+            from_ms: Some(1_000_010),
+            to_ms: Some(1_000_020),
+            ..Default::default()
+        };
+        let (records, _) = export_jsonl(&store, &filter);
+        // 10 nonces on both routes
+        assert_eq!(records.len(), 20);
+        assert!(records.iter().all(|record| {
+            let executed_at_ms = record.executed_at_ms.unwrap();
+            (1_000_010..1_000_020).contains(&executed_at_ms)
+        }));
+
+        let mut out = vec![];
+        let summary =
+            export_transfers(&store, &filter, TransferExportFormat::Csv, &mut out).unwrap();
+        let csv = String::from_utf8(out).unwrap();
+        let lines = csv.lines().collect::<Vec<_>>();
+        assert_eq!(lines[0], CSV_HEADER.join(","));
+        assert_eq!(lines.len(), 21);
+        assert_eq!(summary.exported, 20);
+        for (line, record) in lines[1..].iter().zip(&records) {
+            assert_eq!(*line, csv_row(record));
+            assert_eq!(line.split(',').count(), CSV_HEADER.len());
+        }
+        assert_eq!(csv_field("a,\"b\""), "\"a,\"\"b\"\"\"");
+    }
+}
@@ -0,0 +1,286 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Per-token minimum transfer amounts. `BridgeOrchestrator` drops token transfers below
+//! the minimum amount of their token instead of handing them to the executor, because
+//! they would fail on chain anyway. `TransferMinimumsUpdater` derives the minimums from
+//! the token prices on both Sui and Eth, and reloads them when governance changes them.
+//!
+//! The updater also loads the decimals of the tokens on both chains. Token transfers whose
+//! amount can't be converted exactly between them are quarantined by the orchestrator, see
+//! `token_math`.
+
+use crate::error::BridgeResult;
+use crate::eth_client::EthClient;
+use crate::retry_with_max_elapsed_time;
+use crate::sui_client::{SuiClient, SuiClientInner};
+use crate::token_math::{self, TokenDecimals};
+use crate::types::BridgeAction;
+use arc_swap::ArcSwap;
+use ethers::providers::JsonRpcClient;
+use ethers::types::Address as EthAddress;
+use std::collections::HashMap;
+use std::sync::Arc;
+use tokio::sync::Notify;
+use tokio::time::Duration;
+use tracing::{error, info};
+
+/// Minimum transfer amounts by token id, in Sui decimals. Tokens without
+/// an entry have no minimum.
+#[derive(Debug, Default)]
+pub struct TransferMinimums {
+    minimums: ArcSwap<HashMap<u8, u64>>,
+    // Decimals of the tokens on Sui and Eth. Tokens without an entry are not checked.
+    token_decimals: ArcSwap<HashMap<u8, TokenDecimals>>,
+    refresh: Notify,
+}
+
+impl TransferMinimums {
+    pub fn new(minimums: HashMap<u8, u64>) -> Self {
+        Self {
+            minimums: ArcSwap::from(Arc::new(minimums)),
+            token_decimals: ArcSwap::default(),
+            refresh: Notify::new(),
+        }
+    }
+
+    pub fn get(&self, token_id: u8) -> Option<u64> {
+        self.minimums.load().get(&token_id).copied()
+    }
+
+    pub fn update(&self, minimums: HashMap<u8, u64>) {
+        self.minimums.store(Arc::new(minimums));
+    }
+
+    pub fn update_token_decimals(&self, token_decimals: HashMap<u8, TokenDecimals>) {
+        self.token_decimals.store(Arc::new(token_decimals));
+    }
+
+    /// Returns an error if `action` is a token transfer whose amount can't be converted
+    /// exactly between the decimals of its token on Sui and on Eth.
+    pub fn check_precision(&self, action: &BridgeAction) -> BridgeResult<()> {
+        let Some((token_id, amount)) = action.token_amount() else {
+            return Ok(());
+        };
+        let Some(decimals) = self.token_decimals.load().get(&token_id).copied() else {
+            return Ok(());
+        };
+        decimals
+            .to_eth(amount)
+            .and_then(|eth_amount| decimals.to_sui(eth_amount))
+            .map(|_| ())
+            .map_err(|e| e.context(format!("Amount of token {}", token_id)))
+    }
+
+    /// Asks `TransferMinimumsUpdater` to reload the minimums from chain.
+    pub fn request_refresh(&self) {
+        self.refresh.notify_one();
+    }
+
+    /// Returns true if `action` is a token transfer whose amount is below the
+    /// minimum of its token.
+    pub fn is_below_minimum(&self, action: &BridgeAction) -> bool {
+        let Some((token_id, amount)) = action.token_amount() else {
+            return false;
+        };
+        self.get(token_id).is_some_and(|minimum| amount.0 < minimum)
+    }
+}
+
+/// Returns the smallest amount (in Sui decimals) of a token that is worth at least
+/// `min_usd_value`. Both `min_usd_value` and `token_price` are USD values with 8
+/// decimal places. Returns None if the token has no price.
+pub fn min_transfer_amount(
+    min_usd_value: u64,
+    token_price: u64,
+    decimal_multiplier: u64,
+) -> Option<u64> {
+    token_math::min_amount_of_value(min_usd_value, token_price, decimal_multiplier)
+        .map(|amount| amount.0)
+}
+
+pub struct TransferMinimumsUpdater<C, P> {
+    sui_client: Arc<SuiClient<C>>,
+    eth_client: Arc<EthClient<P>>,
+    eth_config_address: EthAddress,
+    min_usd_value: u64,
+    transfer_minimums: Arc<TransferMinimums>,
+}
+
+impl<C, P> TransferMinimumsUpdater<C, P>
+where
+    C: SuiClientInner + 'static,
+    P: JsonRpcClient + Clone + 'static,
+{
+    pub fn new(
+        sui_client: Arc<SuiClient<C>>,
+        eth_client: Arc<EthClient<P>>,
+        eth_config_address: EthAddress,
+        min_usd_value: u64,
+        transfer_minimums: Arc<TransferMinimums>,
+    ) -> Self {
+        Self {
+            sui_client,
+            eth_client,
+            eth_config_address,
+            min_usd_value,
+            transfer_minimums,
+        }
+    }
+
+    /// Reloads the minimums whenever a refresh is requested. Callers are expected
+    /// to load the initial minimums with `get_onchain_minimums`.
+    pub async fn run(self) {
+        info!("Starting TransferMinimumsUpdater");
+        loop {
+            self.transfer_minimums.refresh.notified().await;
+            let Ok(Ok(minimums)) =
+                retry_with_max_elapsed_time!(self.get_onchain_minimums(), Duration::from_secs(600))
+            else {
+                error!("Failed to get minimum transfer amounts after retry");
+                // Try again at the next refresh request
+                continue;
+            };
+            info!("Minimum transfer amounts updated: {:?}", minimums);
+            self.transfer_minimums.update(minimums);
+            match self.get_onchain_token_decimals().await {
+                Ok(token_decimals) => self.transfer_minimums.update_token_decimals(token_decimals),
+                Err(e) => error!("Failed to get token decimals: {:?}", e),
+            }
+        }
+    }
+
+    /// Returns the minimum amount of each token, which is the larger of the minimums
+    /// derived from the Sui and the Eth token price.
+    pub async fn get_onchain_minimums(&self) -> BridgeResult<HashMap<u8, u64>> {
+        let treasury = self.sui_client.get_treasury_summary().await?;
+        let token_ids = treasury
+            .supported_tokens
+            .iter()
+            .map(|(_, metadata)| metadata.id)
+            .collect::<Vec<_>>();
+        let eth_tokens = self
+            .eth_client
+            .get_token_prices_and_sui_decimals(self.eth_config_address, &token_ids)
+            .await?;
+
+        let mut minimums = HashMap::new();
+        for (_, metadata) in treasury.supported_tokens {
+            let sui_minimum = min_transfer_amount(
+                self.min_usd_value,
+                metadata.notional_value,
+                metadata.decimal_multiplier,
+            );
+            let eth_minimum = match eth_tokens.get(&metadata.id) {
+                Some((price, sui_decimal)) => {
+                    let decimal_multiplier =
+                        token_math::decimal_multiplier(*sui_decimal).map_err(|e| {
+                            e.context(format!("Sui decimal of token {} on Eth", metadata.id))
+                        })?;
+                    min_transfer_amount(self.min_usd_value, *price, decimal_multiplier)
+                }
+                None => None,
+            };
+            if let Some(minimum) = sui_minimum.max(eth_minimum) {
+                minimums.insert(metadata.id, minimum);
+            }
+        }
+        Ok(minimums)
+    }
+
+    /// Returns the decimals of the tokens supported on both Sui and Eth.
+    pub async fn get_onchain_token_decimals(&self) -> BridgeResult<HashMap<u8, TokenDecimals>> {
+        let treasury = self.sui_client.get_treasury_summary().await?;
+        let token_ids = treasury
+            .supported_tokens
+            .iter()
+            .map(|(_, metadata)| metadata.id)
+            .collect::<Vec<_>>();
+        let eth_decimals = self
+            .eth_client
+            .get_token_decimals(self.eth_config_address, &token_ids)
+            .await?;
+
+        let mut token_decimals = HashMap::new();
+        for (_, metadata) in treasury.supported_tokens {
+            let Some(eth) = eth_decimals.get(&metadata.id) else {
+                continue;
+            };
+            let sui = token_math::decimals_of_multiplier(metadata.decimal_multiplier)
+                .map_err(|e| e.context(format!("Decimals of token {} on Sui", metadata.id)))?;
+            token_decimals.insert(metadata.id, TokenDecimals { sui, eth: *eth });
+        }
+        Ok(token_decimals)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::BridgeError;
+    use crate::test_utils::{get_test_eth_to_sui_bridge_action, get_test_sui_to_eth_bridge_action};
+
+    #[test]
+    fn test_min_transfer_amount() {
+        // $1 minimum, token priced $2 with 8 decimals
+        assert_eq!(
+            min_transfer_amount(100_000_000, 200_000_000, 100_000_000),
+            Some(50_000_000)
+        );
+        // Rounds up
+        assert_eq!(min_transfer_amount(100_000_000, 300_000_000, 100), Some(34));
+        // No price, no minimum
+        assert_eq!(min_transfer_amount(100_000_000, 0, 100_000_000), None);
+    }
+
+    #[test]
+    fn test_is_below_minimum() {
+        let minimums = TransferMinimums::new(HashMap::from([(3, 100)]));
+        let below =
+            get_test_sui_to_eth_bridge_action(None, None, None, Some(99), None, None, Some(3));
+        let at =
+            get_test_sui_to_eth_bridge_action(None, None, None, Some(100), None, None, Some(3));
+        let other_token =
+            get_test_sui_to_eth_bridge_action(None, None, None, Some(1), None, None, Some(2));
+        assert!(minimums.is_below_minimum(&below));
+        assert!(!minimums.is_below_minimum(&at));
+        assert!(!minimums.is_below_minimum(&other_token));
+
+        // Minimums are updated
+        minimums.update(HashMap::from([(3, 100), (2, 10)]));
+        assert!(minimums.is_below_minimum(&other_token));
+        let eth_to_sui = get_test_eth_to_sui_bridge_action(None, Some(1), None, Some(3));
+        assert!(minimums.is_below_minimum(&eth_to_sui));
+    }
+
+    #[test]
+    fn test_check_precision() {
+        let minimums = TransferMinimums::default();
+        let transfer = |amount, token_id| {
+            get_test_sui_to_eth_bridge_action(
+                None,
+                None,
+                None,
+                Some(amount),
+                None,
+                None,
+                Some(token_id),
+            )
+        };
+        // Nothing is checked before the decimals are known
+        minimums.check_precision(&transfer(1_001, 3)).unwrap();
+
+        minimums.update_token_decimals(HashMap::from([
+            (3, TokenDecimals { sui: 9, eth: 6 }),
+            (2, TokenDecimals { sui: 8, eth: 18 }),
+        ]));
+        minimums.check_precision(&transfer(1_000, 3)).unwrap();
+        assert!(matches!(
+            minimums.check_precision(&transfer(1_001, 3)),
+            Err(BridgeError::Context(_, e)) if matches!(*e, BridgeError::AmountPrecision(_))
+        ));
+        minimums.check_precision(&transfer(u64::MAX, 2)).unwrap();
+        let eth_to_sui = get_test_eth_to_sui_bridge_action(None, Some(1_001), None, Some(3));
+        assert!(minimums.check_precision(&eth_to_sui).is_err());
+    }
+}
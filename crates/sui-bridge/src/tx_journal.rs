@@ -0,0 +1,323 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Append-only journal of the Sui transactions submitted by `BridgeActionExecutor`,
+//! kept for forensic audit. An entry is written right before each transaction is sent,
+//! so the journal also covers transactions that failed or never landed.
+//!
+//! The journal is a directory of files named `tx-journal-<index>.bin`. A new file is
+//! started when the current one would exceed the configured size. Each file is a
+//! sequence of frames:
+//! `[payload length: u32 LE][Keccak256 of payload: 32 bytes][payload: bcs TxJournalEntry]`
+
+use crate::types::BridgeActionDigest;
+use anyhow::anyhow;
+use fastcrypto::hash::{HashFunction, Keccak256};
+use serde::{Deserialize, Serialize};
+use std::fs::{File, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use sui_types::base_types::ObjectRef;
+use sui_types::digests::TransactionDigest;
+
+const JOURNAL_FILE_PREFIX: &str = "tx-journal-";
+const JOURNAL_FILE_SUFFIX: &str = ".bin";
+const FRAME_LEN_SIZE: usize = 4;
+const FRAME_CHECKSUM_SIZE: usize = 32;
+const FRAME_HEADER_SIZE: usize = FRAME_LEN_SIZE + FRAME_CHECKSUM_SIZE;
+
+pub const DEFAULT_JOURNAL_MAX_FILE_SIZE: u64 = 64 * 1024 * 1024;
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TxJournalEntry {
+    pub action_digest: BridgeActionDigest,
+    pub tx_digest: TransactionDigest,
+    /// bcs bytes of the `TransactionData`
+    pub tx_bytes: Vec<u8>,
+    /// Serialized signatures of the transaction
+    pub signatures: Vec<Vec<u8>>,
+    pub gas_object: ObjectRef,
+    pub timestamp_ms: u64,
+}
+
+struct JournalFile {
+    index: u64,
+    file: File,
+    size: u64,
+}
+
+pub struct TxJournal {
+    dir: PathBuf,
+    max_file_size: u64,
+    fatal_on_failure: bool,
+    current: Mutex<JournalFile>,
+}
+
+impl TxJournal {
+    /// Opens the journal in `dir`, appending to its latest file.
+    pub fn new(dir: &Path, max_file_size: u64, fatal_on_failure: bool) -> anyhow::Result<Self> {
+        std::fs::create_dir_all(dir)?;
+        let index = list_journal_files(dir)?
+            .last()
+            .map(|(index, _)| *index)
+            .unwrap_or_default();
+        let current = open_journal_file(dir, index)?;
+        Ok(Self {
+            dir: dir.to_path_buf(),
+            max_file_size,
+            fatal_on_failure,
+            current: Mutex::new(current),
+        })
+    }
+
+    /// Whether the executor should stop instead of submitting a transaction that
+    /// could not be journaled.
+    pub fn fatal_on_failure(&self) -> bool {
+        self.fatal_on_failure
+    }
+
+    /// Appends `entry` and syncs it to disk.
+    pub fn append(&self, entry: &TxJournalEntry) -> anyhow::Result<()> {
+        let frame = encode_frame(entry)?;
+        let mut current = self.current.lock().unwrap();
+        if current.size > 0 && current.size + frame.len() as u64 > self.max_file_size {
+            *current = open_journal_file(&self.dir, current.index + 1)?;
+        }
+        current.file.write_all(&frame)?;
+        current.file.sync_data()?;
+        current.size += frame.len() as u64;
+        Ok(())
+    }
+}
+
+fn encode_frame(entry: &TxJournalEntry) -> anyhow::Result<Vec<u8>> {
+    let payload = bcs::to_bytes(entry)?;
+    let len = u32::try_from(payload.len())
+        .map_err(|_| anyhow!("Journal entry too large: {} bytes", payload.len()))?;
+    let mut frame = Vec::with_capacity(FRAME_HEADER_SIZE + payload.len());
+    frame.extend_from_slice(&len.to_le_bytes());
+    frame.extend_from_slice(&Keccak256::digest(&payload).digest);
+    frame.extend_from_slice(&payload);
+    Ok(frame)
+}
+
+fn journal_file_path(dir: &Path, index: u64) -> PathBuf {
+    dir.join(format!(
+        "{}{:06}{}",
+        JOURNAL_FILE_PREFIX, index, JOURNAL_FILE_SUFFIX
+    ))
+}
+
+fn open_journal_file(dir: &Path, index: u64) -> anyhow::Result<JournalFile> {
+    let file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(journal_file_path(dir, index))?;
+    let size = file.metadata()?.len();
+    Ok(JournalFile { index, file, size })
+}
+
+/// Returns the journal files in `dir` ordered by index.
+fn list_journal_files(dir: &Path) -> anyhow::Result<Vec<(u64, PathBuf)>> {
+    let mut files = vec![];
+    for dir_entry in std::fs::read_dir(dir)? {
+        let path = dir_entry?.path();
+        let index = path
+            .file_name()
+            .and_then(|name| name.to_str())
+            .and_then(|name| name.strip_prefix(JOURNAL_FILE_PREFIX))
+            .and_then(|name| name.strip_suffix(JOURNAL_FILE_SUFFIX))
+            .and_then(|index| index.parse::<u64>().ok());
+        if let Some(index) = index {
+            files.push((index, path));
+        }
+    }
+    files.sort();
+    Ok(files)
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct JournalRecord {
+    pub file: PathBuf,
+    /// Byte offset of the frame in `file`
+    pub offset: u64,
+    pub entry: TxJournalEntry,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct JournalCorruption {
+    pub file: PathBuf,
+    /// Byte offset of the frame in `file`
+    pub offset: u64,
+    /// Position of the frame in `file`, starting from 0
+    pub frame_index: usize,
+    pub reason: String,
+}
+
+#[derive(Debug, Default)]
+pub struct JournalReport {
+    pub records: Vec<JournalRecord>,
+    pub corruptions: Vec<JournalCorruption>,
+}
+
+/// Reads and validates every frame of the journal in `dir`. A frame whose checksum
+/// or payload is invalid is reported and skipped. A truncated frame ends the reading
+/// of its file, because the frames after it cannot be located.
+pub fn read_journal(dir: &Path) -> anyhow::Result<JournalReport> {
+    let mut report = JournalReport::default();
+    for (_, path) in list_journal_files(dir)? {
+        let bytes = std::fs::read(&path)?;
+        let mut offset = 0;
+        let mut frame_index = 0;
+        while offset < bytes.len() {
+            let mut corruption = |reason: String| {
+                report.corruptions.push(JournalCorruption {
+                    file: path.clone(),
+                    offset: offset as u64,
+                    frame_index,
+                    reason,
+                })
+            };
+            let remaining = &bytes[offset..];
+            if remaining.len() < FRAME_HEADER_SIZE {
+                corruption(format!(
+                    "Truncated frame header: {} bytes left",
+                    remaining.len()
+                ));
+                break;
+            }
+            // Unwrap safe: the length was checked above
+            let len = u32::from_le_bytes(remaining[..FRAME_LEN_SIZE].try_into().unwrap()) as usize;
+            let checksum = &remaining[FRAME_LEN_SIZE..FRAME_HEADER_SIZE];
+            let Some(payload) = remaining[FRAME_HEADER_SIZE..].get(..len) else {
+                corruption(format!(
+                    "Truncated frame: expected {} payload bytes, found {}",
+                    len,
+                    remaining.len() - FRAME_HEADER_SIZE
+                ));
+                break;
+            };
+            if Keccak256::digest(payload).digest != checksum {
+                corruption("Checksum mismatch".to_string());
+            } else {
+                match bcs::from_bytes::<TxJournalEntry>(payload) {
+                    Ok(entry) => report.records.push(JournalRecord {
+                        file: path.clone(),
+                        offset: offset as u64,
+                        entry,
+                    }),
+                    Err(e) => corruption(format!("Invalid entry: {:?}", e)),
+                }
+            }
+            offset += FRAME_HEADER_SIZE + len;
+            frame_index += 1;
+        }
+    }
+    Ok(report)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::get_test_sui_to_eth_bridge_action;
+    use sui_types::base_types::{ObjectDigest, ObjectID, SequenceNumber};
+
+    fn get_test_entry(nonce: u64) -> TxJournalEntry {
+        let action =
+            get_test_sui_to_eth_bridge_action(None, None, Some(nonce), None, None, None, None);
+        TxJournalEntry {
+            action_digest: action.digest(),
+            tx_digest: TransactionDigest::random(),
+            tx_bytes: vec![nonce as u8; 100],
+            signatures: vec![vec![1; 97]],
+            gas_object: (
+                ObjectID::random(),
+                SequenceNumber::from_u64(nonce),
+                ObjectDigest::random(),
+            ),
+            timestamp_ms: 1_700_000_000_000 + nonce,
+        }
+    }
+
+    #[test]
+    fn test_journal_append_and_rotate() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let entries = (0..5).map(get_test_entry).collect::<Vec<_>>();
+        let frame_size = encode_frame(&entries[0]).unwrap().len() as u64;
+
+        // Each file fits two frames
+        let journal = TxJournal::new(temp_dir.path(), frame_size * 2, false).unwrap();
+        for entry in &entries[..3] {
+            journal.append(entry).unwrap();
+        }
+        // Reopening appends to the latest file
+        drop(journal);
+        let journal = TxJournal::new(temp_dir.path(), frame_size * 2, false).unwrap();
+        for entry in &entries[3..] {
+            journal.append(entry).unwrap();
+        }
+
+        let files = list_journal_files(temp_dir.path()).unwrap();
+        assert_eq!(files.len(), 3);
+        let report = read_journal(temp_dir.path()).unwrap();
+        assert!(report.corruptions.is_empty());
+        assert_eq!(
+            report
+                .records
+                .into_iter()
+                .map(|record| record.entry)
+                .collect::<Vec<_>>(),
+            entries
+        );
+    }
+
+    #[test]
+    fn test_journal_verify_detects_corruption() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let entries = (0..4).map(get_test_entry).collect::<Vec<_>>();
+        let journal =
+            TxJournal::new(temp_dir.path(), DEFAULT_JOURNAL_MAX_FILE_SIZE, false).unwrap();
+        for entry in &entries {
+            journal.append(entry).unwrap();
+        }
+        drop(journal);
+
+        // Flip a payload byte of the third frame
+        let path = journal_file_path(temp_dir.path(), 0);
+        let mut bytes = std::fs::read(&path).unwrap();
+        let frame_size = encode_frame(&entries[0]).unwrap().len();
+        let corrupted_offset = frame_size * 2;
+        bytes[corrupted_offset + FRAME_HEADER_SIZE + 10] ^= 0xff;
+        std::fs::write(&path, &bytes).unwrap();
+
+        let report = read_journal(temp_dir.path()).unwrap();
+        assert_eq!(
+            report.corruptions,
+            vec![JournalCorruption {
+                file: path.clone(),
+                offset: corrupted_offset as u64,
+                frame_index: 2,
+                reason: "Checksum mismatch".to_string(),
+            }]
+        );
+        // The frames around the corrupted one are still readable
+        assert_eq!(
+            report
+                .records
+                .iter()
+                .map(|record| record.entry.clone())
+                .collect::<Vec<_>>(),
+            vec![entries[0].clone(), entries[1].clone(), entries[3].clone()]
+        );
+
+        // A truncated tail is reported as well
+        bytes.truncate(bytes.len() - 5);
+        std::fs::write(&path, &bytes).unwrap();
+        let report = read_journal(temp_dir.path()).unwrap();
+        assert_eq!(report.corruptions.len(), 2);
+        assert_eq!(report.corruptions[1].frame_index, 3);
+        assert_eq!(report.corruptions[1].offset, (frame_size * 3) as u64);
+        assert_eq!(report.records.len(), 2);
+    }
+}
@@ -0,0 +1,267 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! How the executor submits signed Sui transactions. By default a transaction is submitted
+//! through the fullnode of the bridge client only, so its quorum driver decides whether the
+//! submission succeeds. With `concurrent`, the same signed transaction is submitted to
+//! several fullnodes at once and the first response with effects wins. Submitting the same
+//! signed transaction more than once is safe: it is executed at most once, and fullnodes
+//! return its effects again when it was already executed. When no fullnode returns effects,
+//! e.g. one reported the transaction as already executed and another timed out, the digest
+//! is polled before the submission is considered failed.
+
+use crate::error::{BridgeError, BridgeResult};
+use crate::metrics::BridgeMetrics;
+use crate::sui_client::{SuiClient, SuiClientInner};
+use futures::stream::{FuturesUnordered, StreamExt};
+use std::sync::Arc;
+use std::time::Duration;
+use sui_json_rpc_types::SuiTransactionBlockResponse;
+use sui_types::transaction::Transaction;
+use tracing::{info, warn};
+
+// How many times, and how often, the digest of a transaction that no fullnode returned
+// effects for is polled
+const TX_POLL_ATTEMPTS: usize = 3;
+const TX_POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, serde::Deserialize, serde::Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum TxSubmissionStrategy {
+    /// Submits through `sui-rpc-url` only
+    #[default]
+    SingleFullnode,
+    /// Submits to `sui-rpc-url` and every fullnode in `fullnode-urls` concurrently
+    Concurrent,
+}
+
+pub struct SuiTxSubmitter<C> {
+    strategy: TxSubmissionStrategy,
+    // Fullnodes with the label of their metrics, the first one is the client's
+    fullnodes: Vec<(String, Arc<SuiClient<C>>)>,
+    metrics: Arc<BridgeMetrics>,
+}
+
+impl<C> SuiTxSubmitter<C>
+where
+    C: SuiClientInner,
+{
+    pub fn single(sui_client: Arc<SuiClient<C>>, metrics: Arc<BridgeMetrics>) -> Self {
+        Self {
+            strategy: TxSubmissionStrategy::SingleFullnode,
+            fullnodes: vec![("primary".to_string(), sui_client)],
+            metrics,
+        }
+    }
+
+    /// `sui_client` is labeled `primary` in the metrics, `fullnodes` are labeled by their
+    /// position, starting from `fullnode-0`.
+    pub fn concurrent(
+        sui_client: Arc<SuiClient<C>>,
+        fullnodes: Vec<Arc<SuiClient<C>>>,
+        metrics: Arc<BridgeMetrics>,
+    ) -> Self {
+        let fullnodes = std::iter::once(("primary".to_string(), sui_client))
+            .chain(
+                fullnodes
+                    .into_iter()
+                    .enumerate()
+                    .map(|(i, client)| (format!("fullnode-{}", i), client)),
+            )
+            .collect();
+        Self {
+            strategy: TxSubmissionStrategy::Concurrent,
+            fullnodes,
+            metrics,
+        }
+    }
+
+    pub async fn submit(&self, tx: Transaction) -> BridgeResult<SuiTransactionBlockResponse> {
+        match self.strategy {
+            TxSubmissionStrategy::SingleFullnode => {
+                let (name, client) = &self.fullnodes[0];
+                let result = client.execute_transaction_block_with_effects(tx).await;
+                self.record(name, if result.is_ok() { "ok" } else { "error" });
+                result
+            }
+            TxSubmissionStrategy::Concurrent => self.submit_concurrently(tx).await,
+        }
+    }
+
+    async fn submit_concurrently(
+        &self,
+        tx: Transaction,
+    ) -> BridgeResult<SuiTransactionBlockResponse> {
+        let tx_digest = *tx.digest();
+        let mut submissions = self
+            .fullnodes
+            .iter()
+            .map(|(name, client)| {
+                let tx = tx.clone();
+                async move {
+                    (
+                        name,
+                        client.execute_transaction_block_with_effects(tx).await,
+                    )
+                }
+            })
+            .collect::<FuturesUnordered<_>>();
+        let mut first_error = None;
+        while let Some((name, result)) = submissions.next().await {
+            match result {
+                // The pending submissions are dropped, the transaction already reached
+                // a quorum
+                Ok(response) => {
+                    self.record(name, "ok");
+                    info!(?tx_digest, "Transaction submitted through {}", name);
+                    return Ok(response);
+                }
+                Err(err) => {
+                    self.record(name, "error");
+                    warn!(
+                        ?tx_digest,
+                        "Failed to submit transaction to {}: {:?}", name, err
+                    );
+                    first_error.get_or_insert(err);
+                }
+            }
+        }
+        // The errors may conflict, e.g. one fullnode reports that the transaction was
+        // already executed while another one timed out. The chain has the last word.
+        for attempt in 0..TX_POLL_ATTEMPTS {
+            if attempt > 0 {
+                tokio::time::sleep(TX_POLL_INTERVAL).await;
+            }
+            for (name, client) in &self.fullnodes {
+                if let Ok(response) = client.get_transaction_block_with_effects(tx_digest).await {
+                    if response.effects.is_some() {
+                        self.record(name, "found_executed");
+                        info!(
+                            ?tx_digest,
+                            "Transaction was executed, found through {}", name
+                        );
+                        return Ok(response);
+                    }
+                }
+            }
+        }
+        Err(first_error.unwrap_or_else(|| {
+            BridgeError::Generic("No fullnode to submit the transaction to".to_string())
+        }))
+    }
+
+    fn record(&self, fullnode: &str, result: &str) {
+        self.metrics
+            .sui_tx_submissions
+            .with_label_values(&[fullnode, result])
+            .inc();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sui_mock_client::SuiMockClient;
+    use sui_json_rpc_types::SuiTransactionBlockEffects;
+    use sui_types::base_types::random_object_ref;
+    use sui_types::crypto::{get_key_pair, AccountKeyPair};
+    use sui_types::digests::TransactionDigest;
+    use sui_types::transaction::{TransactionData, TEST_ONLY_GAS_UNIT_FOR_TRANSFER};
+
+    fn test_transaction() -> Transaction {
+        let (sender, key): (_, AccountKeyPair) = get_key_pair();
+        let tx_data = TransactionData::new_transfer_sui(
+            sender,
+            sender,
+            None,
+            random_object_ref(),
+            TEST_ONLY_GAS_UNIT_FOR_TRANSFER,
+            1000,
+        );
+        Transaction::from_data_and_signer(tx_data, vec![&key])
+    }
+
+    fn response_with_effects() -> SuiTransactionBlockResponse {
+        let mut response = SuiTransactionBlockResponse::new(TransactionDigest::random());
+        response.effects = Some(SuiTransactionBlockEffects::new_for_testing(
+            TransactionDigest::random(),
+            sui_json_rpc_types::SuiExecutionStatus::Success,
+        ));
+        response
+    }
+
+    #[tokio::test]
+    async fn test_concurrent_submission_takes_first_effects() {
+        let metrics = Arc::new(BridgeMetrics::for_testing());
+        let slow = SuiMockClient::default();
+        let fast = SuiMockClient::default();
+        let _slow_rx = slow.subscribe_to_requested_transactions();
+        let _fast_rx = fast.subscribe_to_requested_transactions();
+        let tx = test_transaction();
+        slow.add_transaction_response(*tx.digest(), Ok(response_with_effects()));
+        slow.set_execution_delay(Duration::from_secs(30));
+        fast.add_transaction_response(*tx.digest(), Ok(response_with_effects()));
+        let submitter = SuiTxSubmitter::concurrent(
+            Arc::new(SuiClient::new_for_testing(slow.clone())),
+            vec![Arc::new(SuiClient::new_for_testing(fast.clone()))],
+            metrics.clone(),
+        );
+
+        let response = tokio::time::timeout(Duration::from_secs(5), submitter.submit(tx))
+            .await
+            .expect("the fast fullnode should win");
+        assert!(response.unwrap().effects.is_some());
+        assert_eq!(fast.executed_transactions().len(), 1);
+        assert!(slow.executed_transactions().is_empty());
+        let count = |fullnode: &str, result: &str| {
+            metrics
+                .sui_tx_submissions
+                .with_label_values(&[fullnode, result])
+                .get()
+        };
+        assert_eq!(count("fullnode-0", "ok"), 1);
+        assert_eq!(count("primary", "ok"), 0);
+    }
+
+    #[tokio::test]
+    async fn test_concurrent_submission_polls_digest_after_errors() {
+        let metrics = Arc::new(BridgeMetrics::for_testing());
+        let first = SuiMockClient::default();
+        let second = SuiMockClient::default();
+        let _first_rx = first.subscribe_to_requested_transactions();
+        let _second_rx = second.subscribe_to_requested_transactions();
+        let tx = test_transaction();
+        first.add_transaction_response(
+            *tx.digest(),
+            Err(BridgeError::SuiTxFailureGeneric(
+                "Transaction was already executed".to_string(),
+            )),
+        );
+        second.add_transaction_response(
+            *tx.digest(),
+            Err(BridgeError::SuiTxFailureGeneric(
+                "Request timed out".to_string(),
+            )),
+        );
+        let submitter = SuiTxSubmitter::concurrent(
+            Arc::new(SuiClient::new_for_testing(first.clone())),
+            vec![Arc::new(SuiClient::new_for_testing(second.clone()))],
+            metrics.clone(),
+        );
+
+        // Not executed, the first error is returned
+        let err = submitter.submit(tx.clone()).await.unwrap_err();
+        assert!(matches!(err, BridgeError::SuiTxFailureGeneric(_)));
+
+        // Executed, its effects are returned
+        second.add_onchain_transaction(*tx.digest(), response_with_effects());
+        assert!(submitter.submit(tx).await.unwrap().effects.is_some());
+        assert_eq!(
+            metrics
+                .sui_tx_submissions
+                .with_label_values(&["fullnode-0", "found_executed"])
+                .get(),
+            1
+        );
+    }
+}
@@ -9,6 +9,7 @@ use crate::crypto::{
 use crate::encoding::BridgeMessageEncoding;
 use crate::error::{BridgeError, BridgeResult};
 use crate::events::EmittedSuiToEthTokenBridgeV1;
+use crate::token_math::SuiAmount;
 use enum_dispatch::enum_dispatch;
 use ethers::types::Address as EthAddress;
 use ethers::types::Log;
@@ -23,6 +24,7 @@ use serde::{Deserialize, Serialize};
 use shared_crypto::intent::IntentScope;
 use std::collections::{BTreeMap, BTreeSet};
 use std::fmt::Debug;
+use sui_types::base_types::SUI_ADDRESS_LENGTH;
 use sui_types::bridge::{
     BridgeChainId, MoveTypeTokenTransferPayload, APPROVAL_THRESHOLD_ADD_TOKENS_ON_EVM,
     APPROVAL_THRESHOLD_ADD_TOKENS_ON_SUI, BRIDGE_COMMITTEE_MAXIMAL_VOTING_POWER,
@@ -76,12 +78,13 @@ impl BridgeCommittee {
         let mut total_stake = 0;
         for member in members {
             let public_key = BridgeAuthorityPublicKeyBytes::from(&member.pubkey);
-            if members_map.contains_key(&public_key) {
-                return Err(BridgeError::InvalidBridgeCommittee(
-                    "Duplicate BridgeAuthority Public key".into(),
-                ));
+            if let Some(existing) = members_map.get(&public_key) {
+                return Err(BridgeError::InvalidBridgeCommittee(format!(
+                    "Duplicate BridgeAuthority Public key {:?}, registered with urls {:?} and {:?}",
+                    public_key, existing.base_url, member.base_url
+                )));
             }
-            // TODO: should we disallow identical network addresses?
+            // Members sharing a url are allowed, see `members_sharing_url`
             if member.is_blocklisted {
                 total_blocklisted_stake += member.voting_power;
             }
@@ -119,6 +122,32 @@ impl BridgeCommittee {
     pub fn total_blocklisted_stake(&self) -> StakeUnit {
         self.total_blocklisted_stake
     }
+
+    /// Returns the groups of members that registered the same url, e.g. after a messy key
+    /// rotation. The node behind such a url signs with one key only, so the members of a
+    /// group are requested once, see `BridgeAuthorityAggregator`. Urls are compared after
+    /// parsing, so that e.g. a trailing slash doesn't tell them apart.
+    pub fn members_sharing_url(&self) -> Vec<Vec<BridgeAuthorityPublicKeyBytes>> {
+        let mut members_by_url: BTreeMap<String, Vec<BridgeAuthorityPublicKeyBytes>> =
+            BTreeMap::new();
+        for (name, member) in &self.members {
+            let base_url = member.base_url.trim();
+            if base_url.is_empty() {
+                continue;
+            }
+            let base_url = url::Url::parse(base_url)
+                .map(|url| url.to_string())
+                .unwrap_or_else(|_| base_url.to_string());
+            members_by_url
+                .entry(base_url.trim_end_matches('/').to_string())
+                .or_default()
+                .push(name.clone());
+        }
+        members_by_url
+            .into_values()
+            .filter(|members| members.len() > 1)
+            .collect()
+    }
 }
 
 impl core::fmt::Display for BridgeCommittee {
@@ -143,7 +172,8 @@ impl CommitteeTrait<BridgeAuthorityPublicKeyBytes> for BridgeCommittee {
     fn shuffle_by_stake_with_rng(
         &self,
         // `preferences` is used as a *flag* here to influence the order of validators to be requested.
-        //  * if `Some(_)`, then we will request validators in the order of the voting power
+        //  * if `Some(_)`, then we will request validators in the order of the voting power,
+        //    with the validators in the set first.
         //  * if `None`, we still refer to voting power, but they are shuffled by randomness.
         //  to save gas cost.
         preferences: Option<&BTreeSet<BridgeAuthorityPublicKeyBytes>>,
@@ -170,8 +200,13 @@ impl CommitteeTrait<BridgeAuthorityPublicKeyBytes> for BridgeCommittee {
                 }
             })
             .collect::<Vec<_>>();
-        if preferences.is_some() {
-            candidates.sort_by(|(_, a), (_, b)| b.cmp(a));
+        if let Some(preferences) = preferences {
+            candidates.sort_by(|(name_a, a), (name_b, b)| {
+                preferences
+                    .contains(name_b)
+                    .cmp(&preferences.contains(name_a))
+                    .then(b.cmp(a))
+            });
             candidates.iter().map(|(name, _)| name.clone()).collect()
         } else {
             candidates
@@ -192,7 +227,7 @@ impl CommitteeTrait<BridgeAuthorityPublicKeyBytes> for BridgeCommittee {
     }
 }
 
-#[derive(Serialize, Copy, Clone, PartialEq, Eq, TryFromPrimitive, Hash)]
+#[derive(Serialize, Deserialize, Copy, Clone, Debug, PartialEq, Eq, TryFromPrimitive, Hash)]
 #[repr(u8)]
 pub enum BridgeActionType {
     TokenTransfer = 0,
@@ -205,6 +240,19 @@ pub enum BridgeActionType {
     AddTokensOnEvm = 7,
 }
 
+impl BridgeActionType {
+    pub const ALL: [BridgeActionType; 8] = [
+        BridgeActionType::TokenTransfer,
+        BridgeActionType::UpdateCommitteeBlocklist,
+        BridgeActionType::EmergencyButton,
+        BridgeActionType::LimitUpdate,
+        BridgeActionType::AssetPriceUpdate,
+        BridgeActionType::EvmContractUpgrade,
+        BridgeActionType::AddTokensOnSui,
+        BridgeActionType::AddTokensOnEvm,
+    ];
+}
+
 #[derive(Clone, PartialEq, Eq)]
 pub struct BridgeActionKey {
     pub action_type: BridgeActionType,
@@ -370,10 +418,11 @@ pub enum BridgeAction {
 }
 
 impl BridgeAction {
-    // Digest of BridgeAction (with Keccak256 hasher)
+    /// Keccak256 of `to_signing_bytes`. Authorities sign this digest, and Move and
+    /// Solidity verify signatures over it.
     pub fn digest(&self) -> BridgeActionDigest {
         let mut hasher = Keccak256::default();
-        hasher.update(self.to_bytes());
+        hasher.update(self.to_signing_bytes());
         BridgeActionDigest::new(hasher.finalize().into())
     }
 
@@ -442,6 +491,21 @@ impl BridgeAction {
         }
     }
 
+    /// Returns the token id and amount of a token transfer, None for other actions.
+    pub fn token_amount(&self) -> Option<(u8, SuiAmount)> {
+        match self {
+            BridgeAction::SuiToEthBridgeAction(a) => Some((
+                a.sui_bridge_event.token_id,
+                SuiAmount(a.sui_bridge_event.amount_sui_adjusted),
+            )),
+            BridgeAction::EthToSuiBridgeAction(a) => Some((
+                a.eth_bridge_event.token_id,
+                SuiAmount(a.eth_bridge_event.sui_adjusted_amount),
+            )),
+            _ => None,
+        }
+    }
+
     pub fn approval_threshold(&self) -> u64 {
         match self {
             BridgeAction::SuiToEthBridgeAction(_) => APPROVAL_THRESHOLD_TOKEN_TRANSFER,
@@ -467,6 +531,10 @@ impl BridgeActionDigest {
     pub const fn new(digest: [u8; 32]) -> Self {
         Self(Digest::new(digest))
     }
+
+    pub const fn inner(&self) -> &[u8; 32] {
+        self.0.inner()
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -480,6 +548,30 @@ pub type CertifiedBridgeAction = Envelope<BridgeAction, BridgeCommitteeValidityS
 pub type VerifiedCertifiedBridgeAction =
     VerifiedEnvelope<BridgeAction, BridgeCommitteeValiditySignInfo>;
 
+/// Response of the governance signing routes to a `dry_run` request: whether the
+/// authority would sign the action, without signing it.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GovernanceDryRun {
+    pub would_sign: bool,
+    /// Why the authority would not sign
+    pub reason: Option<String>,
+}
+
+impl GovernanceDryRun {
+    pub fn from_verification(result: BridgeResult<()>) -> Self {
+        match result {
+            Ok(()) => Self {
+                would_sign: true,
+                reason: None,
+            },
+            Err(err) => Self {
+                would_sign: false,
+                reason: Some(err.to_string()),
+            },
+        }
+    }
+}
+
 #[derive(Debug, Clone, Copy, Serialize, Deserialize)]
 pub struct BridgeEventDigest(Digest);
 
@@ -506,6 +598,8 @@ pub struct EthLog {
     pub block_number: u64,
     pub tx_hash: H256,
     pub log_index_in_tx: u16,
+    /// Timestamp of the block, None if it could not be read from the provider.
+    pub block_timestamp_ms: Option<u64>,
     pub log: Log,
 }
 
@@ -548,6 +642,26 @@ impl EthEvent for RawEthLog {
     }
 }
 
+/// Chain id of Eth mainnet, as returned by `eth_chainId`.
+pub const ETH_MAINNET_CHAIN_ID: u64 = 1;
+/// Chain id of Eth Sepolia, as returned by `eth_chainId`.
+pub const ETH_SEPOLIA_CHAIN_ID: u64 = 11155111;
+
+/// Returns the EVM chain id that an Eth bridge chain id is reserved for. None for
+/// `EthCustom`, which is used for any other EVM chain, and for Sui chains.
+pub fn reserved_evm_chain_id(bridge_chain_id: BridgeChainId) -> Option<u64> {
+    match bridge_chain_id {
+        BridgeChainId::EthMainnet => Some(ETH_MAINNET_CHAIN_ID),
+        BridgeChainId::EthSepolia => Some(ETH_SEPOLIA_CHAIN_ID),
+        _ => None,
+    }
+}
+
+/// Whether `chain_id` is the EVM chain id of a chain that has its own bridge chain id.
+pub fn is_reserved_evm_chain_id(chain_id: u64) -> bool {
+    chain_id == ETH_MAINNET_CHAIN_ID || chain_id == ETH_SEPOLIA_CHAIN_ID
+}
+
 /// Check if the bridge route is valid
 /// Only mainnet can bridge to mainnet, other than that we do not care.
 pub fn is_route_valid(one: BridgeChainId, other: BridgeChainId) -> bool {
@@ -572,6 +686,31 @@ pub fn is_route_valid(one: BridgeChainId, other: BridgeChainId) -> bool {
     true
 }
 
+/// Checks the encoded recipient of a token transfer to `destination` the same way the
+/// bridge contract of `destination` does when the transfer is claimed: it must be 32
+/// bytes on Sui and 20 bytes on EVM chains. Any address of the right length can be
+/// claimed, including the zero address, so it is accepted.
+pub fn validate_recipient_address(
+    destination: BridgeChainId,
+    recipient: &[u8],
+) -> BridgeResult<()> {
+    let expected_len = if destination.is_sui_chain() {
+        SUI_ADDRESS_LENGTH
+    } else {
+        EthAddress::len_bytes()
+    };
+    if recipient.len() != expected_len {
+        return Err(BridgeError::InvalidRecipientAddress(format!(
+            "{:?} expects a {} byte recipient, got {} bytes: 0x{}",
+            destination,
+            expected_len,
+            recipient.len(),
+            Hex::encode(recipient)
+        )));
+    }
+    Ok(())
+}
+
 // Sanitized version of MoveTypeParsedTokenTransferMessage
 #[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Clone)]
 pub struct ParsedTokenTransferMessage {
@@ -643,6 +782,45 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_bridge_committee_members_sharing_url() {
+        let (mut authority1, _, _) = get_test_authority_and_key(2500, 9999);
+        let (mut authority2, _, _) = get_test_authority_and_key(2500, 9999);
+        let (mut authority3, _, _) = get_test_authority_and_key(2500, 9998);
+        let (mut authority4, _, _) = get_test_authority_and_key(2500, 9997);
+        let committee = BridgeCommittee::new(vec![
+            authority1.clone(),
+            authority2.clone(),
+            authority3.clone(),
+            authority4.clone(),
+        ])
+        .unwrap();
+        let mut expected = vec![authority1.pubkey_bytes(), authority2.pubkey_bytes()];
+        expected.sort();
+        assert_eq!(committee.members_sharing_url(), vec![expected]);
+
+        // A trailing slash doesn't tell the urls apart, empty urls are not grouped
+        authority1.base_url = "http://127.0.0.1:9998/".into();
+        authority2.base_url = "".into();
+        authority4.base_url = "".into();
+        let committee = BridgeCommittee::new(vec![
+            authority1.clone(),
+            authority2.clone(),
+            authority3.clone(),
+            authority4.clone(),
+        ])
+        .unwrap();
+        let mut expected = vec![authority1.pubkey_bytes(), authority3.pubkey_bytes()];
+        expected.sort();
+        assert_eq!(committee.members_sharing_url(), vec![expected]);
+
+        authority1.base_url = "http://127.0.0.1:9996".into();
+        authority3.base_url = "http://127.0.0.1:9995".into();
+        let committee =
+            BridgeCommittee::new(vec![authority1, authority2, authority3, authority4]).unwrap();
+        assert!(committee.members_sharing_url().is_empty());
+    }
+
     #[test]
     fn test_bridge_committee_total_blocklisted_stake() -> anyhow::Result<()> {
         let (mut authority1, _, _) = get_test_authority_and_key(10000, 9999);
@@ -776,4 +954,32 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn test_validate_recipient_address() {
+        for (destination, recipient, valid) in [
+            // Eth to Sui
+            (BridgeChainId::SuiMainnet, vec![7u8; 32], true),
+            (BridgeChainId::SuiTestnet, vec![0u8; 32], true),
+            (BridgeChainId::SuiMainnet, vec![7u8; 31], false),
+            (BridgeChainId::SuiMainnet, vec![7u8; 33], false),
+            (BridgeChainId::SuiCustom, vec![7u8; 20], false),
+            (BridgeChainId::SuiMainnet, vec![], false),
+            // Sui to Eth
+            (BridgeChainId::EthMainnet, vec![7u8; 20], true),
+            (BridgeChainId::EthSepolia, vec![0u8; 20], true),
+            (BridgeChainId::EthMainnet, vec![7u8; 19], false),
+            (BridgeChainId::EthMainnet, vec![7u8; 21], false),
+            (BridgeChainId::EthCustom, vec![7u8; 32], false),
+            (BridgeChainId::EthMainnet, vec![], false),
+        ] {
+            match validate_recipient_address(destination, &recipient) {
+                Ok(()) => assert!(valid, "{:?} {:?}", destination, recipient),
+                Err(BridgeError::InvalidRecipientAddress(_)) => {
+                    assert!(!valid, "{:?} {:?}", destination, recipient)
+                }
+                Err(e) => panic!("Unexpected error: {:?}", e),
+            }
+        }
+    }
 }
@@ -0,0 +1,320 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Compares the url registered on chain for this node's bridge authority key with the
+//! `external_url` in its config. Other committee members only know the registered url,
+//! so a stale registration makes this node unreachable for them while it looks healthy
+//! locally. `RegisteredUrlChecker` runs the comparison at startup, periodically and
+//! whenever the committee is refreshed.
+
+use crate::client::bridge_client::BridgeClient;
+use crate::crypto::BridgeAuthorityPublicKeyBytes;
+use crate::metrics::BridgeMetrics;
+use crate::sui_client::{SuiClient, SuiClientInner};
+use crate::types::BridgeCommittee;
+use serde::{Deserialize, Serialize};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use tokio::sync::Notify;
+use tokio::time;
+use tracing::{error, info, warn};
+use url::Url;
+
+pub const DEFAULT_URL_CHECK_INTERVAL: Duration = Duration::from_secs(600);
+const URL_PROBE_TIMEOUT: Duration = Duration::from_secs(10);
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "status", rename_all = "snake_case")]
+pub enum RegisteredUrlCheck {
+    Match {
+        url: String,
+    },
+    Mismatch {
+        registered_url: String,
+        external_url: String,
+    },
+    /// The key is not (yet) a member of the committee
+    NotRegistered,
+    /// `external_url` is not set in the config, so there is nothing to compare with
+    NotConfigured {
+        registered_url: String,
+    },
+}
+
+impl RegisteredUrlCheck {
+    pub fn is_mismatch(&self) -> bool {
+        matches!(self, Self::Mismatch { .. })
+    }
+
+    pub fn registered_url(&self) -> Option<&str> {
+        match self {
+            Self::Match { url } => Some(url),
+            Self::Mismatch { registered_url, .. } | Self::NotConfigured { registered_url } => {
+                Some(registered_url)
+            }
+            Self::NotRegistered => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RegisteredUrlReport {
+    pub check: RegisteredUrlCheck,
+    /// Whether the node answered through the registered url. None if it was not probed.
+    pub reachable: Option<bool>,
+    pub checked_at_ms: u64,
+}
+
+/// Latest result of `RegisteredUrlChecker`, shared with the server and the monitor.
+#[derive(Debug, Default)]
+pub struct RegisteredUrlStatus {
+    latest: Mutex<Option<RegisteredUrlReport>>,
+    refresh: Notify,
+}
+
+impl RegisteredUrlStatus {
+    pub fn latest(&self) -> Option<RegisteredUrlReport> {
+        self.latest.lock().unwrap().clone()
+    }
+
+    /// Asks the checker to compare the urls again, e.g. after the committee changed.
+    pub fn request_refresh(&self) {
+        self.refresh.notify_one();
+    }
+
+    fn update(&self, report: RegisteredUrlReport) {
+        *self.latest.lock().unwrap() = Some(report);
+    }
+}
+
+/// Compares the url registered for `pubkey` in `committee` with `external_url`.
+pub fn check_registered_url(
+    committee: &BridgeCommittee,
+    pubkey: &BridgeAuthorityPublicKeyBytes,
+    external_url: Option<&str>,
+) -> RegisteredUrlCheck {
+    let Some(member) = committee.member(pubkey) else {
+        return RegisteredUrlCheck::NotRegistered;
+    };
+    let registered_url = member.base_url.clone();
+    match external_url {
+        None => RegisteredUrlCheck::NotConfigured { registered_url },
+        Some(external_url) if same_url(&registered_url, external_url) => {
+            RegisteredUrlCheck::Match {
+                url: registered_url,
+            }
+        }
+        Some(external_url) => RegisteredUrlCheck::Mismatch {
+            registered_url,
+            external_url: external_url.to_string(),
+        },
+    }
+}
+
+// Urls are compared after normalization, so that e.g. a trailing slash or an explicit
+// default port is not reported as a mismatch
+fn same_url(a: &str, b: &str) -> bool {
+    match (Url::parse(a), Url::parse(b)) {
+        (Ok(a), Ok(b)) => a == b,
+        _ => a.trim_end_matches('/') == b.trim_end_matches('/'),
+    }
+}
+
+/// Returns whether the node of `pubkey` answers through the url registered in `committee`.
+/// None if the url can't be requested, e.g. the member is blocklisted.
+pub async fn probe_registered_url(
+    committee: &BridgeCommittee,
+    pubkey: &BridgeAuthorityPublicKeyBytes,
+) -> Option<bool> {
+    let client = BridgeClient::new(pubkey.clone(), Arc::new(committee.clone())).ok()?;
+    match time::timeout(URL_PROBE_TIMEOUT, client.ping()).await {
+        Ok(Ok(reachable)) => Some(reachable),
+        Ok(Err(_)) | Err(_) => Some(false),
+    }
+}
+
+/// Periodically compares the registered url of this node with its `external_url`, and
+/// sets `bridge_url_mismatch` accordingly.
+pub struct RegisteredUrlChecker<C> {
+    sui_client: Arc<SuiClient<C>>,
+    pubkey: BridgeAuthorityPublicKeyBytes,
+    external_url: Option<String>,
+    metrics: Arc<BridgeMetrics>,
+    interval: Duration,
+}
+
+impl<C> RegisteredUrlChecker<C>
+where
+    C: SuiClientInner + 'static,
+{
+    pub fn new(
+        sui_client: Arc<SuiClient<C>>,
+        pubkey: BridgeAuthorityPublicKeyBytes,
+        external_url: Option<String>,
+        metrics: Arc<BridgeMetrics>,
+        interval: Duration,
+    ) -> Self {
+        Self {
+            sui_client,
+            pubkey,
+            external_url,
+            metrics,
+            interval,
+        }
+    }
+
+    pub async fn run(self) {
+        info!("Starting RegisteredUrlChecker");
+        let mut interval = time::interval(self.interval);
+        interval.set_missed_tick_behavior(time::MissedTickBehavior::Delay);
+        loop {
+            tokio::select! {
+                _ = interval.tick() => (),
+                _ = self.metrics.registered_url.refresh.notified() => (),
+            }
+            if let Err(e) = self.check().await {
+                warn!("Failed to check registered url: {:?}", e);
+            }
+        }
+    }
+
+    async fn check(&self) -> anyhow::Result<RegisteredUrlReport> {
+        let committee = self
+            .sui_client
+            .get_bridge_committee()
+            .await
+            .map_err(|e| anyhow::anyhow!("Failed to get bridge committee: {:?}", e))?;
+        let check = check_registered_url(&committee, &self.pubkey, self.external_url.as_deref());
+        match &check {
+            RegisteredUrlCheck::Match { url } => info!("Registered url {} matches external_url", url),
+            RegisteredUrlCheck::Mismatch {
+                registered_url,
+                external_url,
+            } => error!(
+                "Registered url {} does not match external_url {}. Other committee members can't reach this node until the url is updated on chain",
+                registered_url, external_url
+            ),
+            RegisteredUrlCheck::NotRegistered => {
+                warn!("Bridge authority key is not registered in the bridge committee")
+            }
+            RegisteredUrlCheck::NotConfigured { registered_url } => warn!(
+                "external_url is not configured, can't compare it with registered url {}",
+                registered_url
+            ),
+        }
+        self.metrics
+            .url_mismatch
+            .set(if check.is_mismatch() { 1 } else { 0 });
+        let reachable = match check.registered_url() {
+            Some(_) => probe_registered_url(&committee, &self.pubkey).await,
+            None => None,
+        };
+        if reachable == Some(false) {
+            warn!(
+                "Node is unreachable through its registered url {:?}",
+                check.registered_url()
+            );
+        }
+        let report = RegisteredUrlReport {
+            check,
+            reachable,
+            checked_at_ms: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .unwrap()
+                .as_millis() as u64,
+        };
+        self.metrics.registered_url.update(report.clone());
+        Ok(report)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sui_mock_client::SuiMockClient;
+    use crate::test_utils::{
+        bridge_committee_to_bridge_committee_summary, get_test_authority_and_key,
+    };
+    use sui_config::local_ip_utils::get_available_port;
+
+    #[test]
+    fn test_check_registered_url() {
+        let (authority, _, _) = get_test_authority_and_key(10000, 9999);
+        let (unregistered, _, _) = get_test_authority_and_key(10000, 9999);
+        let pubkey = authority.pubkey_bytes();
+        let committee = BridgeCommittee::new(vec![authority]).unwrap();
+
+        let expected_match = RegisteredUrlCheck::Match {
+            url: "http://127.0.0.1:9999".to_string(),
+        };
+        let check = check_registered_url(&committee, &pubkey, Some("http://127.0.0.1:9999"));
+        assert_eq!(check, expected_match);
+        // Equivalent urls match
+        let check = check_registered_url(&committee, &pubkey, Some("http://127.0.0.1:9999/"));
+        assert_eq!(check, expected_match);
+
+        let check = check_registered_url(&committee, &pubkey, Some("http://127.0.0.1:9998"));
+        assert_eq!(
+            check,
+            RegisteredUrlCheck::Mismatch {
+                registered_url: "http://127.0.0.1:9999".to_string(),
+                external_url: "http://127.0.0.1:9998".to_string(),
+            }
+        );
+        assert!(check.is_mismatch());
+
+        let check = check_registered_url(
+            &committee,
+            &unregistered.pubkey_bytes(),
+            Some("http://127.0.0.1:9999"),
+        );
+        assert_eq!(check, RegisteredUrlCheck::NotRegistered);
+        assert_eq!(check.registered_url(), None);
+
+        let check = check_registered_url(&committee, &pubkey, None);
+        assert_eq!(
+            check,
+            RegisteredUrlCheck::NotConfigured {
+                registered_url: "http://127.0.0.1:9999".to_string(),
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn test_registered_url_checker_sets_mismatch_gauge() {
+        let sui_client_mock = SuiMockClient::default();
+        let sui_client = Arc::new(SuiClient::new_for_testing(sui_client_mock.clone()));
+        let metrics = Arc::new(BridgeMetrics::for_testing());
+        let port = get_available_port("127.0.0.1");
+        let (authority, _, _) = get_test_authority_and_key(10000, port);
+        let pubkey = authority.pubkey_bytes();
+        sui_client_mock.set_bridge_committee(bridge_committee_to_bridge_committee_summary(
+            BridgeCommittee::new(vec![authority]).unwrap(),
+        ));
+
+        let checker = RegisteredUrlChecker::new(
+            sui_client.clone(),
+            pubkey.clone(),
+            Some(format!("http://127.0.0.1:{}", port + 1)),
+            metrics.clone(),
+            DEFAULT_URL_CHECK_INTERVAL,
+        );
+        let report = checker.check().await.unwrap();
+        assert!(report.check.is_mismatch());
+        // Nothing listens on the registered url
+        assert_eq!(report.reachable, Some(false));
+        assert_eq!(metrics.url_mismatch.get(), 1);
+        assert_eq!(metrics.registered_url.latest(), Some(report));
+
+        let checker = RegisteredUrlChecker::new(
+            sui_client,
+            pubkey,
+            Some(format!("http://127.0.0.1:{}", port)),
+            metrics.clone(),
+            DEFAULT_URL_CHECK_INTERVAL,
+        );
+        let report = checker.check().await.unwrap();
+        assert!(matches!(report.check, RegisteredUrlCheck::Match { .. }));
+        assert_eq!(metrics.url_mismatch.get(), 0);
+    }
+}
@@ -174,14 +174,24 @@ pub fn generate_bridge_node_config_and_write_to_file(
 ) -> Result<(), anyhow::Error> {
     let mut config = BridgeNodeConfig {
         server_listen_port: 9191,
+        server_listen_address: None,
+        server_dual_stack: false,
+        server_reuse_port: false,
+        server_dedicated_runtime: false,
+        server_runtime_worker_threads: None,
         metrics_port: 9184,
-        bridge_authority_key_path: PathBuf::from("/path/to/your/bridge_authority_key"),
+        mode: None,
+        bridge_authority_key_path: Some(PathBuf::from("/path/to/your/bridge_authority_key")),
+        remote_signer: None,
         sui: SuiConfig {
             sui_rpc_url: "your_sui_rpc_url".to_string(),
             sui_bridge_chain_id: BridgeChainId::SuiTestnet as u8,
             bridge_client_key_path: None,
             bridge_client_gas_object: None,
             sui_bridge_module_last_processed_event_id_override: None,
+            disable_object_cache: false,
+            sui_rpc_max_attempts: None,
+            tx_submission: None,
         },
         eth: EthConfig {
             eth_rpc_url: "your_eth_rpc_url".to_string(),
@@ -189,12 +199,57 @@ pub fn generate_bridge_node_config_and_write_to_file(
             eth_bridge_chain_id: BridgeChainId::EthSepolia as u8,
             eth_contracts_start_block_fallback: Some(0),
             eth_contracts_start_block_override: None,
+            extra_eth_confirmations: None,
+            custom_evm_chains: None,
+            fee_history_interval_seconds: None,
+            verify_eth_receipts: None,
+            eth_receipts_root_rpc_url: None,
         },
         approved_governance_actions: vec![],
+        approved_governance_digests: vec![],
         run_client,
         db_path: None,
+        aux_db_path: None,
+        storage_encryption_key_path: None,
+        verify_storage: false,
+        storage_cursor_flush_ms: None,
+        min_transfer_usd_value: None,
+        tx_journal: None,
+        reconciliation: None,
+        claim_tracking: None,
+        channels: None,
+        adaptive_polling: None,
+        gas_funding: None,
+        alerts: None,
+        timestamps: None,
+        clock_drift: None,
+        event_stream: None,
+        quarantine_recheck: None,
+        onchain_status_batch_size: None,
+        pruning: None,
+        storage_maintenance: None,
+        min_signature_inclusion_percent: None,
+        skip_signing_paused_routes: false,
+        committee_dns_refresh_seconds: None,
+        committee_connections: None,
+        committee_request_hedging: None,
+        refuse_committee_anomalies: false,
+        sign_action_types: None,
+        execute_action_types: None,
+        request_auth: None,
+        admin_auth: None,
+        slow_request_threshold_ms: None,
+        governance_body_limit_bytes: None,
+        max_requester_labels: None,
+        grpc_listen_address: None,
+        external_url: None,
+        warm_up: None,
+        verification_limits: None,
+        logging: None,
         metrics_key_pair: default_ed25519_key_pair(),
         metrics: None,
+        telemetry: None,
+        allow_mismatched_bridge_contracts: false,
     };
     if run_client {
         config.sui.bridge_client_key_path = Some(PathBuf::from("/path/to/your/bridge_client_key"));
@@ -7,7 +7,7 @@ use bincode::Options;
 use prometheus::{Histogram, HistogramTimer};
 use rocksdb::Direction;
 
-use super::{be_fix_int_ser, RocksDBRawIter, TypedStoreError};
+use super::{be_fix_int_ser, deserialize_value, RocksDBRawIter, TypedStoreError, ValueCodec};
 use crate::metrics::RocksDBPerfContext;
 use crate::DBMetrics;
 use serde::{de::DeserializeOwned, Serialize};
@@ -27,6 +27,7 @@ pub struct Iter<'a, K, V> {
     db_metrics: Option<Arc<DBMetrics>>,
     bytes_scanned_counter: usize,
     keys_returned_counter: usize,
+    value_codec: Option<Arc<dyn ValueCodec>>,
 }
 
 impl<'a, K: DeserializeOwned, V: DeserializeOwned> Iter<'a, K, V> {
@@ -52,8 +53,14 @@ impl<'a, K: DeserializeOwned, V: DeserializeOwned> Iter<'a, K, V> {
             db_metrics,
             bytes_scanned_counter: 0,
             keys_returned_counter: 0,
+            value_codec: None,
         }
     }
+
+    pub(super) fn with_value_codec(mut self, value_codec: Option<Arc<dyn ValueCodec>>) -> Self {
+        self.value_codec = value_codec;
+        self
+    }
 }
 
 impl<'a, K: DeserializeOwned, V: DeserializeOwned> Iterator for Iter<'a, K, V> {
@@ -81,7 +88,9 @@ impl<'a, K: DeserializeOwned, V: DeserializeOwned> Iterator for Iter<'a, K, V> {
             self.bytes_scanned_counter += raw_key.len() + raw_value.len();
             self.keys_returned_counter += 1;
             let key = config.deserialize(raw_key).ok();
-            let value = bcs::from_bytes(raw_value).ok();
+            let value =
+                deserialize_value(self.value_codec.as_ref(), &self.cf_name, raw_key, raw_value)
+                    .ok();
             match self.direction {
                 Direction::Forward => self.db_iter.next(),
                 Direction::Reverse => self.db_iter.prev(),
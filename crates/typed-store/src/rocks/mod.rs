@@ -20,6 +20,7 @@ use crate::{
 use bincode::Options;
 use collectable::TryExtend;
 use itertools::Itertools;
+use once_cell::sync::OnceCell;
 use prometheus::{Histogram, HistogramTimer};
 use rocksdb::properties::num_files_at_level;
 use rocksdb::{
@@ -35,12 +36,12 @@ use serde::{de::DeserializeOwned, Serialize};
 use std::ops::Bound;
 use std::{
     borrow::Borrow,
-    collections::BTreeMap,
+    collections::{BTreeMap, HashMap},
     env,
     marker::PhantomData,
     ops::RangeBounds,
     path::{Path, PathBuf},
-    sync::Arc,
+    sync::{Arc, Mutex},
     time::Duration,
 };
 use std::{collections::HashSet, ffi::CStr};
@@ -200,6 +201,7 @@ pub struct DBWithThreadModeWrapper {
     pub underlying: rocksdb::DBWithThreadMode<MultiThreaded>,
     pub metric_conf: MetricConf,
     pub db_path: PathBuf,
+    value_codecs: ValueCodecSlots,
 }
 
 impl DBWithThreadModeWrapper {
@@ -213,6 +215,7 @@ impl DBWithThreadModeWrapper {
             underlying,
             metric_conf,
             db_path,
+            value_codecs: ValueCodecSlots::default(),
         }
     }
 }
@@ -228,6 +231,7 @@ pub struct OptimisticTransactionDBWrapper {
     pub underlying: rocksdb::OptimisticTransactionDB<MultiThreaded>,
     pub metric_conf: MetricConf,
     pub db_path: PathBuf,
+    value_codecs: ValueCodecSlots,
 }
 
 impl OptimisticTransactionDBWrapper {
@@ -241,6 +245,7 @@ impl OptimisticTransactionDBWrapper {
             underlying,
             metric_conf,
             db_path,
+            value_codecs: ValueCodecSlots::default(),
         }
     }
 }
@@ -274,6 +279,21 @@ impl Drop for RocksDB {
 }
 
 impl RocksDB {
+    // The value codec of the column family `cf`, shared by all the maps opened on it
+    fn value_codec_slot(&self, cf: &str) -> ValueCodecSlot {
+        let slots = match self {
+            Self::DBWithThreadMode(d) => &d.value_codecs,
+            Self::OptimisticTransactionDB(d) => &d.value_codecs,
+        };
+        slots
+            .0
+            .lock()
+            .expect("Value codecs lock is poisoned")
+            .entry(cf.to_string())
+            .or_default()
+            .clone()
+    }
+
     pub fn get<K: AsRef<[u8]>>(&self, key: K) -> Result<Option<Vec<u8>>, rocksdb::Error> {
         delegate_call!(self.get(key))
     }
@@ -717,6 +737,46 @@ impl MetricConf {
 const CF_METRICS_REPORT_PERIOD_SECS: u64 = 30;
 const METRICS_ERROR: i64 = -1;
 
+/// Transforms the serialized values of the maps it is set on, e.g. to encrypt them at rest.
+/// The values are encoded after being serialized and decoded before being deserialized,
+/// the keys are left as they are so that the maps can still be scanned in order.
+pub trait ValueCodec: Send + Sync + std::fmt::Debug {
+    fn encode(&self, cf: &str, key: &[u8], value: Vec<u8>) -> Result<Vec<u8>, TypedStoreError>;
+
+    fn decode(&self, cf: &str, key: &[u8], value: &[u8]) -> Result<Vec<u8>, TypedStoreError>;
+}
+
+type ValueCodecSlot = Arc<OnceCell<Arc<dyn ValueCodec>>>;
+
+#[derive(Debug, Default)]
+struct ValueCodecSlots(Mutex<HashMap<String, ValueCodecSlot>>);
+
+pub(crate) fn serialize_value<V: Serialize + ?Sized>(
+    codec: Option<&Arc<dyn ValueCodec>>,
+    cf: &str,
+    key: &[u8],
+    value: &V,
+) -> Result<Vec<u8>, TypedStoreError> {
+    let value = bcs::to_bytes(value).map_err(typed_store_err_from_bcs_err)?;
+    match codec {
+        Some(codec) => codec.encode(cf, key, value),
+        None => Ok(value),
+    }
+}
+
+pub(crate) fn deserialize_value<V: DeserializeOwned>(
+    codec: Option<&Arc<dyn ValueCodec>>,
+    cf: &str,
+    key: &[u8],
+    value: &[u8],
+) -> Result<V, TypedStoreError> {
+    match codec {
+        Some(codec) => bcs::from_bytes(&codec.decode(cf, key, value)?),
+        None => bcs::from_bytes(value),
+    }
+    .map_err(typed_store_err_from_bcs_err)
+}
+
 /// An interface to a rocksDB database, keyed by a columnfamily
 #[derive(Clone, Debug)]
 pub struct DBMap<K, V> {
@@ -731,6 +791,8 @@ pub struct DBMap<K, V> {
     write_sample_interval: SamplingInterval,
     iter_sample_interval: SamplingInterval,
     _metrics_task_cancel_handle: Arc<oneshot::Sender<()>>,
+    // Shared with the other maps of the column family, see `set_value_codec`
+    value_codec: ValueCodecSlot,
 }
 
 unsafe impl<K: Send, V: Send> Send for DBMap<K, V> {}
@@ -780,9 +842,24 @@ impl<K, V> DBMap<K, V> {
             multiget_sample_interval: db.multiget_sampling_interval(),
             write_sample_interval: db.write_sampling_interval(),
             iter_sample_interval: db.iter_sampling_interval(),
+            value_codec: db.value_codec_slot(opt_cf),
         }
     }
 
+    /// Encodes the values written to the column family of the map with `codec` from now on,
+    /// and decodes the values read from it. The codec applies to every map of the column
+    /// family in this database, opened before or after, and their clones. The values
+    /// already in the map are not encoded. Fails if the column family has a codec already.
+    pub fn set_value_codec(&self, codec: Arc<dyn ValueCodec>) -> Result<(), TypedStoreError> {
+        self.value_codec.set(codec).map_err(|_| {
+            TypedStoreError::SerializationError(format!("{} has a value codec already", self.cf))
+        })
+    }
+
+    pub fn value_codec(&self) -> Option<&Arc<dyn ValueCodec>> {
+        self.value_codec.get()
+    }
+
     /// Opens a database from a path, with specific options and an optional column family.
     ///
     /// This database is used to perform operations on single column family, and parametrizes
@@ -1518,7 +1595,7 @@ impl DBBatch {
             .into_iter()
             .try_for_each::<_, Result<_, TypedStoreError>>(|(k, v)| {
                 let k_buf = be_fix_int_ser(k.borrow())?;
-                let v_buf = bcs::to_bytes(v.borrow()).map_err(typed_store_err_from_bcs_err)?;
+                let v_buf = serialize_value(db.value_codec(), &db.cf, &k_buf, v.borrow())?;
                 total += k_buf.len() + v_buf.len();
                 self.batch.put_cf(&db.cf(), k_buf, v_buf);
                 Ok(())
@@ -1540,6 +1617,13 @@ impl DBBatch {
         if !Arc::ptr_eq(&db.rocksdb, &self.rocksdb) {
             return Err(TypedStoreError::CrossDBBatch);
         }
+        // The merge operators combine the values as they are stored
+        if db.value_codec().is_some() {
+            return Err(TypedStoreError::SerializationError(format!(
+                "can't merge into {} which has a value codec",
+                db.cf
+            )));
+        }
 
         new_vals
             .into_iter()
@@ -1605,7 +1689,7 @@ impl<'a> DBTransaction<'a> {
             .into_iter()
             .try_for_each::<_, Result<_, TypedStoreError>>(|(k, v)| {
                 let k_buf = be_fix_int_ser(k.borrow())?;
-                let v_buf = bcs::to_bytes(v.borrow()).map_err(typed_store_err_from_bcs_err)?;
+                let v_buf = serialize_value(db.value_codec(), &db.cf, &k_buf, v.borrow())?;
                 self.transaction
                     .put_cf(&db.cf(), k_buf, v_buf)
                     .map_err(typed_store_err_from_rocks_err)?;
@@ -1653,12 +1737,15 @@ impl<'a> DBTransaction<'a> {
         let k_buf = be_fix_int_ser(key)?;
         match self
             .transaction
-            .get_for_update_cf_opt(&db.cf(), k_buf, true, &db.opts.readopts())
+            .get_for_update_cf_opt(&db.cf(), &k_buf, true, &db.opts.readopts())
             .map_err(typed_store_err_from_rocks_err)?
         {
-            Some(data) => Ok(Some(
-                bcs::from_bytes(&data).map_err(typed_store_err_from_bcs_err)?,
-            )),
+            Some(data) => Ok(Some(deserialize_value(
+                db.value_codec(),
+                &db.cf,
+                &k_buf,
+                &data,
+            )?)),
             None => Ok(None),
         }
     }
@@ -1670,9 +1757,13 @@ impl<'a> DBTransaction<'a> {
     ) -> Result<Option<V>, TypedStoreError> {
         let key_buf = be_fix_int_ser(key)?;
         self.transaction
-            .get_cf_opt(&db.cf(), key_buf, &db.opts.readopts())
+            .get_cf_opt(&db.cf(), &key_buf, &db.opts.readopts())
             .map_err(|e| TypedStoreError::RocksDBError(e.to_string()))
-            .map(|res| res.and_then(|bytes| bcs::from_bytes::<V>(&bytes).ok()))
+            .map(|res| {
+                res.and_then(|bytes| {
+                    deserialize_value::<V>(db.value_codec(), &db.cf, &key_buf, &bytes).ok()
+                })
+            })
     }
 
     pub fn multi_get<J: Borrow<K>, K: Serialize + DeserializeOwned, V: DeserializeOwned>(
@@ -1681,6 +1772,26 @@ impl<'a> DBTransaction<'a> {
         keys: impl IntoIterator<Item = J>,
     ) -> Result<Vec<Option<V>>, TypedStoreError> {
         let cf = db.cf();
+        if let Some(codec) = db.value_codec() {
+            // The codec needs the keys of the values it decodes
+            let keys_bytes: Vec<_> = keys
+                .into_iter()
+                .map(|k| be_fix_int_ser(k.borrow()))
+                .collect::<Result<_, TypedStoreError>>()?;
+            let results = self
+                .transaction
+                .multi_get_cf_opt(keys_bytes.iter().map(|k| (&cf, k)), &db.opts.readopts());
+            return results
+                .into_iter()
+                .zip(&keys_bytes)
+                .map(|(value_byte, key)| {
+                    match value_byte.map_err(typed_store_err_from_rocks_err)? {
+                        Some(data) => Ok(Some(deserialize_value(Some(codec), &db.cf, key, &data)?)),
+                        None => Ok(None),
+                    }
+                })
+                .collect();
+        }
         let keys_bytes: Result<Vec<_>, TypedStoreError> = keys
             .into_iter()
             .map(|k| Ok((&cf, be_fix_int_ser(k.borrow())?)))
@@ -1721,6 +1832,7 @@ impl<'a> DBTransaction<'a> {
             None,
             None,
         )
+        .with_value_codec(db.value_codec().cloned())
     }
 
     pub fn keys<K: DeserializeOwned, V: DeserializeOwned>(
@@ -1746,7 +1858,7 @@ impl<'a> DBTransaction<'a> {
         );
         db_iter.seek_to_first();
 
-        Values::new(db_iter)
+        Values::new(db_iter).with_value_codec(db.cf.clone(), db.value_codec().cloned())
     }
 
     pub fn commit(self) -> Result<(), TypedStoreError> {
@@ -1902,9 +2014,12 @@ where
                 .report_metrics(&self.cf);
         }
         match res {
-            Some(data) => Ok(Some(
-                bcs::from_bytes(&data).map_err(typed_store_err_from_bcs_err)?,
-            )),
+            Some(data) => Ok(Some(deserialize_value(
+                self.value_codec(),
+                &self.cf,
+                &key_buf,
+                &data,
+            )?)),
             None => Ok(None),
         }
     }
@@ -1957,7 +2072,7 @@ where
             None
         };
         let key_buf = be_fix_int_ser(key)?;
-        let value_buf = bcs::to_bytes(value).map_err(typed_store_err_from_bcs_err)?;
+        let value_buf = serialize_value(self.value_codec(), &self.cf, &key_buf, value)?;
         self.db_metrics
             .op_metrics
             .rocksdb_put_bytes
@@ -2091,6 +2206,7 @@ where
             keys_scanned,
             Some(self.db_metrics.clone()),
         )
+        .with_value_codec(self.value_codec().cloned())
     }
 
     /// Returns an iterator visiting each key-value pair in the map. By proving bounds of the
@@ -2113,6 +2229,7 @@ where
             keys_scanned,
             Some(self.db_metrics.clone()),
         )
+        .with_value_codec(self.value_codec().cloned())
     }
 
     /// Similar to `iter_with_bounds` but allows specifying inclusivity/exclusivity of ranges explicitly.
@@ -2130,6 +2247,7 @@ where
             keys_scanned,
             Some(self.db_metrics.clone()),
         )
+        .with_value_codec(self.value_codec().cloned())
     }
 
     fn safe_iter(&'a self) -> Self::SafeIterator {
@@ -2146,6 +2264,7 @@ where
             keys_scanned,
             Some(self.db_metrics.clone()),
         )
+        .with_value_codec(self.value_codec().cloned())
     }
 
     fn safe_iter_with_bounds(
@@ -2165,6 +2284,7 @@ where
             keys_scanned,
             Some(self.db_metrics.clone()),
         )
+        .with_value_codec(self.value_codec().cloned())
     }
 
     fn safe_range_iter(&'a self, range: impl RangeBounds<K>) -> Self::SafeIterator {
@@ -2180,6 +2300,7 @@ where
             keys_scanned,
             Some(self.db_metrics.clone()),
         )
+        .with_value_codec(self.value_codec().cloned())
     }
 
     fn keys(&'a self) -> Self::Keys {
@@ -2197,7 +2318,7 @@ where
             .raw_iterator_cf(&self.cf(), self.opts.readopts());
         db_iter.seek_to_first();
 
-        Values::new(db_iter)
+        Values::new(db_iter).with_value_codec(self.cf.clone(), self.value_codec().cloned())
     }
 
     /// Returns a vector of raw values corresponding to the keys provided.
@@ -2226,6 +2347,24 @@ where
     where
         J: Borrow<K>,
     {
+        if let Some(codec) = self.value_codec() {
+            // The codec needs the keys of the values it decodes
+            let keys = keys.into_iter().collect::<Vec<_>>();
+            let results = self.multi_get_pinned(keys.iter().map(Borrow::<K>::borrow))?;
+            return results
+                .into_iter()
+                .zip(&keys)
+                .map(|(value_byte, key)| match value_byte {
+                    Some(data) => Ok(Some(deserialize_value(
+                        Some(codec),
+                        &self.cf,
+                        &be_fix_int_ser(Borrow::<K>::borrow(key))?,
+                        &data,
+                    )?)),
+                    None => Ok(None),
+                })
+                .collect();
+        }
         let results = self.multi_get_pinned(keys)?;
         let values_parsed: Result<Vec<_>, TypedStoreError> = results
             .into_iter()
@@ -2258,6 +2397,25 @@ where
         let snapshot = self.snapshot()?;
         let mut results = vec![];
         for chunk in chunked_keys.into_iter() {
+            if let Some(codec) = self.value_codec() {
+                // The codec needs the keys of the values it decodes
+                let chunk = chunk.collect::<Vec<_>>();
+                let chunk_result = snapshot.multi_get_cf(chunk.iter().map(|(cf, k)| (*cf, k)));
+                let values_parsed: Result<Vec<_>, TypedStoreError> = chunk_result
+                    .into_iter()
+                    .zip(&chunk)
+                    .map(|(value_byte, (_, key))| {
+                        match value_byte.map_err(typed_store_err_from_rocks_err)? {
+                            Some(data) => {
+                                Ok(Some(deserialize_value(Some(codec), &self.cf, key, &data)?))
+                            }
+                            None => Ok(None),
+                        }
+                    })
+                    .collect();
+                results.extend(values_parsed?);
+                continue;
+            }
             let chunk_result = snapshot.multi_get_cf(chunk);
             let values_parsed: Result<Vec<_>, TypedStoreError> = chunk_result
                 .into_iter()
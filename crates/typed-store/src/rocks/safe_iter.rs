@@ -8,7 +8,7 @@ use rocksdb::Direction;
 
 use crate::metrics::{DBMetrics, RocksDBPerfContext};
 
-use super::{be_fix_int_ser, RocksDBRawIter, TypedStoreError};
+use super::{be_fix_int_ser, deserialize_value, RocksDBRawIter, TypedStoreError, ValueCodec};
 use serde::{de::DeserializeOwned, Serialize};
 
 /// An iterator over all key-value pairs in a data map.
@@ -25,6 +25,7 @@ pub struct SafeIter<'a, K, V> {
     db_metrics: Option<Arc<DBMetrics>>,
     bytes_scanned_counter: usize,
     keys_returned_counter: usize,
+    value_codec: Option<Arc<dyn ValueCodec>>,
 }
 
 impl<'a, K: DeserializeOwned, V: DeserializeOwned> SafeIter<'a, K, V> {
@@ -50,8 +51,14 @@ impl<'a, K: DeserializeOwned, V: DeserializeOwned> SafeIter<'a, K, V> {
             db_metrics,
             bytes_scanned_counter: 0,
             keys_returned_counter: 0,
+            value_codec: None,
         }
     }
+
+    pub(super) fn with_value_codec(mut self, value_codec: Option<Arc<dyn ValueCodec>>) -> Self {
+        self.value_codec = value_codec;
+        self
+    }
 }
 
 impl<'a, K: DeserializeOwned, V: DeserializeOwned> Iterator for SafeIter<'a, K, V> {
@@ -79,7 +86,9 @@ impl<'a, K: DeserializeOwned, V: DeserializeOwned> Iterator for SafeIter<'a, K,
             self.bytes_scanned_counter += raw_key.len() + raw_value.len();
             self.keys_returned_counter += 1;
             let key = config.deserialize(raw_key).ok();
-            let value = bcs::from_bytes(raw_value).ok();
+            let value =
+                deserialize_value(self.value_codec.as_ref(), &self.cf_name, raw_key, raw_value)
+                    .ok();
             match self.direction {
                 Direction::Forward => self.db_iter.next(),
                 Direction::Reverse => self.db_iter.prev(),
@@ -518,6 +518,94 @@ async fn test_values(#[values(true, false)] is_transactional: bool) {
     assert_eq!(None, values.next());
 }
 
+// Reverses the values, and prefixes them with the column family and the key
+#[derive(Debug)]
+struct TestValueCodec;
+
+impl ValueCodec for TestValueCodec {
+    fn encode(&self, cf: &str, key: &[u8], value: Vec<u8>) -> Result<Vec<u8>, TypedStoreError> {
+        let prefix = [cf.as_bytes(), key].concat();
+        Ok([prefix, value.into_iter().rev().collect()].concat())
+    }
+
+    fn decode(&self, cf: &str, key: &[u8], value: &[u8]) -> Result<Vec<u8>, TypedStoreError> {
+        let prefix = [cf.as_bytes(), key].concat();
+        match value.strip_prefix(prefix.as_slice()) {
+            Some(value) => Ok(value.iter().rev().copied().collect()),
+            None => Err(TypedStoreError::SerializationError(
+                "value of another key".to_string(),
+            )),
+        }
+    }
+}
+
+#[rstest]
+#[tokio::test]
+async fn test_value_codec(#[values(true, false)] is_transactional: bool) {
+    let db = open_map(temp_dir(), None, is_transactional);
+    db.insert(&1, &"plain".to_string())
+        .expect("Failed to insert");
+    let clone = db.clone();
+    db.set_value_codec(Arc::new(TestValueCodec))
+        .expect("Failed to set the codec");
+    assert!(db.set_value_codec(Arc::new(TestValueCodec)).is_err());
+
+    db.insert(&123, &"123".to_string())
+        .expect("Failed to insert");
+    let mut batch = db.batch();
+    batch
+        .insert_batch(&db, [(456, "456".to_string())])
+        .expect("Failed to batch insert");
+    batch.write().expect("Failed to write batch");
+
+    let raw = db.get_raw_bytes(&123).unwrap().unwrap();
+    assert_ne!(raw, bcs::to_bytes(&"123".to_string()).unwrap());
+    assert_eq!(db.get(&123).unwrap(), Some("123".to_string()));
+    assert_eq!(
+        db.multi_get([123, 456, 789]).unwrap(),
+        vec![Some("123".to_string()), Some("456".to_string()), None]
+    );
+    assert_eq!(
+        db.chunked_multi_get([456, 123], 1).unwrap(),
+        vec![Some("456".to_string()), Some("123".to_string())]
+    );
+    // The value written before the codec was set can't be decoded
+    assert!(db.get(&1).is_err());
+    db.remove(&1).expect("Failed to remove");
+    assert_eq!(
+        db.unbounded_iter().collect::<Vec<_>>(),
+        vec![(123, "123".to_string()), (456, "456".to_string())]
+    );
+    assert_eq!(
+        db.safe_iter().map(Result::unwrap).collect::<Vec<_>>(),
+        vec![(123, "123".to_string()), (456, "456".to_string())]
+    );
+    assert_eq!(
+        db.values().map(Result::unwrap).collect::<Vec<_>>(),
+        vec!["123".to_string(), "456".to_string()]
+    );
+    // The codec applies to the other maps of the column family, cloned before it was set
+    // or reopened after
+    let reopened =
+        DBMap::<i32, String>::reopen(&db.rocksdb, None, &ReadWriteOptions::default(), false)
+            .expect("Failed to reopen storage");
+    for other in [&clone, &reopened] {
+        assert!(other.value_codec().is_some());
+        assert_eq!(other.get(&123).unwrap(), Some("123".to_string()));
+    }
+
+    // Values are bound to their key
+    db.rocksdb
+        .put_cf(
+            &db.cf(),
+            be_fix_int_ser(&789).unwrap(),
+            db.get_raw_bytes(&456).unwrap().unwrap(),
+            &db.opts.writeopts(),
+        )
+        .unwrap();
+    assert!(db.get(&789).is_err());
+}
+
 #[rstest]
 #[tokio::test]
 async fn test_try_extend(#[values(true, false)] is_transactional: bool) {
@@ -1,16 +1,18 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
-use std::marker::PhantomData;
+use std::{marker::PhantomData, sync::Arc};
 
 use crate::TypedStoreError;
 use serde::de::DeserializeOwned;
 
-use super::RocksDBRawIter;
+use super::{deserialize_value, RocksDBRawIter, ValueCodec};
 
 /// An iterator over the values of a prefix.
 pub struct Values<'a, V> {
     db_iter: RocksDBRawIter<'a>,
     _phantom: PhantomData<V>,
+    cf_name: String,
+    value_codec: Option<Arc<dyn ValueCodec>>,
 }
 
 impl<'a, V: DeserializeOwned> Values<'a, V> {
@@ -18,8 +20,20 @@ impl<'a, V: DeserializeOwned> Values<'a, V> {
         Self {
             db_iter,
             _phantom: PhantomData,
+            cf_name: String::new(),
+            value_codec: None,
         }
     }
+
+    pub(crate) fn with_value_codec(
+        mut self,
+        cf_name: String,
+        value_codec: Option<Arc<dyn ValueCodec>>,
+    ) -> Self {
+        self.cf_name = cf_name;
+        self.value_codec = value_codec;
+        self
+    }
 }
 
 impl<'a, V: DeserializeOwned> Iterator for Values<'a, V> {
@@ -27,10 +41,11 @@ impl<'a, V: DeserializeOwned> Iterator for Values<'a, V> {
 
     fn next(&mut self) -> Option<Self::Item> {
         if self.db_iter.valid() {
-            let value = self
-                .db_iter
-                .key()
-                .and_then(|_| self.db_iter.value().and_then(|v| bcs::from_bytes(v).ok()));
+            let value = self.db_iter.key().and_then(|key| {
+                self.db_iter.value().and_then(|v| {
+                    deserialize_value(self.value_codec.as_ref(), &self.cf_name, key, v).ok()
+                })
+            });
 
             self.db_iter.next();
             value.map(Ok)